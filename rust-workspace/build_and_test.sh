@@ -111,6 +111,17 @@ else
     exit 1
 fi
 
+# 11. Check requirement traceability
+echo -e "\n${BLUE}🔎 Checking requirement traceability...${NC}"
+if cargo run -q -p space-comms-cli -- trace \
+    --requirements ../docs/SOFTWARE_REQUIREMENTS_SPECIFICATION.md \
+    --baseline ../docs/requirements_trace.json; then
+    print_status "Every traced requirement keeps its functions and tests"
+else
+    print_error "Requirement traceability check failed"
+    exit 1
+fi
+
 # Final summary
 echo -e "\n${GREEN}🎉 ALL TESTS COMPLETED SUCCESSFULLY!${NC}"
 echo "================================================================="
@@ -123,6 +134,7 @@ echo "✅ Unit tests"
 echo "✅ Integration tests"
 echo "✅ Priority system demonstration"
 echo "✅ Stress tests"
+echo "✅ Requirement traceability"
 
 echo -e "\n${BLUE}📡 Ready for space deployment! 🚀${NC}"
 
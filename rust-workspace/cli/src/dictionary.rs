@@ -0,0 +1,44 @@
+//! `export-dictionary`: the console's command dictionary
+//!
+//! Lists every command the `ground` subcommand and the console send by
+//! name, with the kind and range of each parameter: usage lines, or with
+//! `--json` the dictionary itself for tools that build command scripts.
+
+use std::path::PathBuf;
+
+use space_comms_ground::dictionary::COMMAND_DICTIONARY;
+
+use crate::{CliResult, Output, Verdict};
+
+/// Where to write the dictionary
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Write to a file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, output: Output) -> CliResult {
+    let value = serde_json::to_value(COMMAND_DICTIONARY)?;
+    match &args.output {
+        Some(path) => {
+            let text = if output.json {
+                format!("{:#}\n", value)
+            } else {
+                usage()
+            };
+            std::fs::write(path, text)?;
+        }
+        None => output.emit(&value, usage),
+    }
+    Ok(Verdict::Pass)
+}
+
+/// One usage line per command
+fn usage() -> String {
+    COMMAND_DICTIONARY
+        .iter()
+        .map(|spec| format!("{}\n", spec.usage()))
+        .collect()
+}
@@ -0,0 +1,182 @@
+//! `ground`: the ground station without its console
+//!
+//! Starts a `GroundStation`, uplinks the commands given with `--send` in
+//! order, each named and parameterized as in the console's `send` command
+//! (see `export-dictionary`), then keeps receiving telemetry for the rest of
+//! the run and reports what came down. The verdict fails if any command is
+//! rejected or cannot be uplinked.
+//!
+//! The station reports each frame it receives on stdout as it does on the
+//! console; the summary comes last. Flags other than `--send` override the
+//! `[ground]` section of the configuration.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use space_comms_ground::{dictionary, Command, GroundStation, GroundStationConfig};
+
+use crate::settings::override_with;
+use crate::{CliResult, Output, Verdict};
+
+/// Station configuration and the commands to send; unset flags keep the
+/// configured value
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Seconds to keep receiving telemetry after the commands are sent
+    #[arg(long)]
+    duration_s: Option<u64>,
+
+    /// Command to uplink, name then parameters, e.g.
+    /// "ActivateSafeMode Level2 none"; repeat for several
+    #[arg(long = "send", value_name = "COMMAND")]
+    commands: Vec<String>,
+
+    /// Validate, encode and show uplinks without sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Operator recorded against sent commands
+    #[arg(long)]
+    operator: Option<String>,
+
+    /// Append-only command audit file; kept in memory if not given
+    #[arg(long)]
+    audit_log: Option<PathBuf>,
+
+    /// UDP port telemetry is received on; 0 picks a free port
+    #[arg(long)]
+    telemetry_port: Option<u16>,
+
+    /// UDP port commands are sent from; 0 picks a free port
+    #[arg(long)]
+    command_port: Option<u16>,
+}
+
+/// `[ground]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// Seconds to keep receiving telemetry after the commands are sent
+    pub duration_s: u64,
+    /// Validate, encode and show uplinks without sending them
+    pub dry_run: bool,
+    /// Operator recorded against sent commands
+    pub operator: String,
+    /// Append-only command audit file; kept in memory if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<PathBuf>,
+    /// UDP port telemetry is received on; 0 picks a free port
+    pub telemetry_port: u16,
+    /// UDP port commands are sent from; 0 picks a free port
+    pub command_port: u16,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let station = GroundStationConfig::default();
+        Settings {
+            duration_s: 10,
+            dry_run: false,
+            operator: "batch".to_string(),
+            audit_log: None,
+            telemetry_port: station.telemetry_port,
+            command_port: station.command_port,
+        }
+    }
+}
+
+impl Args {
+    /// Override `settings` with the flags given
+    fn apply(&self, settings: &mut Settings) {
+        override_with(&mut settings.duration_s, self.duration_s);
+        settings.dry_run |= self.dry_run;
+        override_with(&mut settings.operator, self.operator.clone());
+        if self.audit_log.is_some() {
+            settings.audit_log = self.audit_log.clone();
+        }
+        override_with(&mut settings.telemetry_port, self.telemetry_port);
+        override_with(&mut settings.command_port, self.command_port);
+    }
+}
+
+/// Build a command from its dictionary name and parameters
+fn dictionary_command(text: &str) -> Result<Command, String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let spec = words
+        .first()
+        .and_then(|name| dictionary::lookup(name))
+        .ok_or_else(|| format!("{:?} is not in the command dictionary", text))?;
+    if words.len() != spec.parameters.len() + 1 {
+        return Err(format!("usage: {}", spec.usage()));
+    }
+    spec.parse(&words[1..])
+        .and_then(|command| Command::from_space_command(&command))
+        .map_err(|e| format!("{}: {}", spec.name, e))
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, mut settings: Settings, output: Output) -> CliResult {
+    args.apply(&mut settings);
+
+    // Every command is checked before anything is sent
+    let commands = args
+        .commands
+        .iter()
+        .map(|text| dictionary_command(text))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let station = GroundStation::new(GroundStationConfig {
+        operator: settings.operator,
+        audit_log_path: settings.audit_log,
+        dry_run: settings.dry_run,
+        telemetry_port: settings.telemetry_port,
+        command_port: settings.command_port,
+        emergency_port: 0,
+        ..GroundStationConfig::default()
+    })?;
+    station.start()?;
+
+    let mut failed = Vec::new();
+    for (text, command) in args.commands.iter().zip(commands) {
+        if let Err(e) = station.send_command(command) {
+            eprintln!("Failed to send {}: {}", text, e);
+            failed.push(text.as_str());
+        }
+    }
+
+    thread::sleep(Duration::from_secs(settings.duration_s));
+
+    let resources = station.resources();
+    let sent = args.commands.len() - failed.len();
+    output.emit(
+        &json!({
+            "commands_sent": sent,
+            "commands_failed": failed,
+            "connected": station.is_connected_to_satellite(),
+            "telemetry_packets": resources.telemetry_history,
+            "execution_reports": resources.verification_records,
+            "audit_records": resources.audit_records,
+        }),
+        || {
+            format!(
+                "Commands sent:     {} ({} failed)\n\
+                 Satellite:         {}\n\
+                 Telemetry packets: {}\n\
+                 Execution reports: {}\n",
+                sent,
+                failed.len(),
+                if station.is_connected_to_satellite() {
+                    "connected"
+                } else {
+                    "not heard"
+                },
+                resources.telemetry_history,
+                resources.verification_records
+            )
+        },
+    );
+    Ok(Verdict::from_pass(failed.is_empty()))
+}
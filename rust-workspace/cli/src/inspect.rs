@@ -0,0 +1,126 @@
+//! `inspect-packet`: annotated breakdown of a raw packet
+//!
+//! Runs `space_comms_shared::inspector::inspect` on a frame given as hex on
+//! the command line or stdin, or as raw bytes in a file. A frame captured on
+//! a Reed-Solomon coded band is decoded first with `--rs`. The verdict fails
+//! unless the CRC checks and the payload decodes in its registered format.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use serde_json::json;
+use space_comms_shared::fec;
+use space_comms_shared::inspector::{inspect, CrcCheck, PayloadDecode};
+
+use crate::{CliResult, Output, Verdict};
+
+/// Frame to inspect
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Frame as hex digits, spaces and `0x` allowed; read from stdin if
+    /// neither this nor `--file` is given
+    hex: Option<String>,
+
+    /// Read the frame as raw bytes from a file
+    #[arg(long, conflicts_with = "hex")]
+    file: Option<PathBuf>,
+
+    /// The frame is Reed-Solomon (255,223) coded; correct and strip the
+    /// check symbols first
+    #[arg(long)]
+    rs: bool,
+}
+
+/// Bytes of a hex string, ignoring whitespace and `0x` prefixes
+fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: String = text
+        .split_whitespace()
+        .map(|word| word.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err("hex frame has an odd number of digits".to_string());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| format!("not a hex byte: {:?}", &digits[i..i + 2]))
+        })
+        .collect()
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, output: Output) -> CliResult {
+    let mut frame = match (&args.hex, &args.file) {
+        (Some(hex), _) => parse_hex(hex)?,
+        (None, Some(path)) => std::fs::read(path)?,
+        (None, None) => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            parse_hex(&text)?
+        }
+    };
+    let mut corrected_symbols = None;
+    if args.rs {
+        let decoded = fec::decode_frame(&frame)?;
+        corrected_symbols = Some(decoded.corrected_symbols);
+        frame = decoded.bytes.to_vec();
+    }
+
+    let inspection = inspect(&frame)?;
+    let crc = match inspection.crc {
+        CrcCheck::Valid(crc) => json!({"status": "valid", "stored": crc}),
+        CrcCheck::Mismatch { stored, computed } => {
+            json!({"status": "mismatch", "stored": stored, "computed": computed})
+        }
+        CrcCheck::Missing => json!({"status": "missing"}),
+    };
+    let decode = match &inspection.decode {
+        PayloadDecode::Decoded(summary) => {
+            json!({"status": "decoded", "summary": format!("{:?}", summary)})
+        }
+        PayloadDecode::Failed(e) => json!({"status": "failed", "error": e.to_string()}),
+        PayloadDecode::Unregistered => json!({"status": "unregistered"}),
+    };
+    let value = json!({
+        "fields": inspection
+            .fields
+            .iter()
+            .map(|field| json!({"name": field.name, "value": field.value}))
+            .collect::<Vec<_>>(),
+        "apid": inspection.header.apid,
+        "apid_name": inspection.apid.map(|entry| entry.name),
+        "packet_type": format!("{:?}", inspection.header.packet_type),
+        "frame_len": inspection.frame_len,
+        "payload": {"start": inspection.payload.start, "end": inspection.payload.end},
+        "crc": crc,
+        "decode": decode,
+        "corrected_symbols": corrected_symbols,
+        "clean": inspection.is_clean(),
+    });
+    output.emit(&value, || {
+        let mut report = String::new();
+        if let Some(corrected) = corrected_symbols {
+            report.push_str(&format!("Reed-Solomon: {} bytes corrected\n", corrected));
+        }
+        report.push_str(&format!("{}\n", inspection));
+        report
+    });
+
+    Ok(Verdict::from_pass(inspection.is_clean()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_frames() {
+        assert_eq!(
+            parse_hex("0x1100 c0 00\n0X0a").unwrap(),
+            vec![0x11, 0x00, 0xC0, 0x00, 0x0A]
+        );
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("zz").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! Space Communications Command Line
+//!
+//! One scriptable entry point to the workspace's tools. Every subcommand
+//! takes its inputs as flags, runs to completion without prompting, writes
+//! its result to stdout and reports through its exit status, so the tools
+//! can be chained in batch workflows. The interactive consoles
+//! (`ground-station`, `freq_sim_interactive`) remain for operators.
+//!
+//! ```text
+//! space-comms simulate --band x --distance-km 1200 --elevation-deg 25
+//! space-comms plan-pass --band ka --max-elevation-deg 60 --json
+//! space-comms satellite-sim --duration-s 60 &
+//! space-comms ground --duration-s 30 --send "SendStatus Full false Json"
+//! space-comms inspect-packet 0100c0010003deadbeef17c9
+//! space-comms export-dictionary --json > dictionary.json
+//! space-comms --config station.toml config show --resolved
+//! space-comms trace --baseline ../docs/requirements_trace.json
+//! ```
+//!
+//! `--json` switches any subcommand from a readable report to JSON. Exit
+//! status is 0 on success, 1 when the run completed but its result is bad
+//! (a link that does not close, a corrupt packet, a rejected command) and 2
+//! for invalid arguments or a run that could not start.
+//!
+//! `ground`, `satellite-sim` and `simulate` read their settings from a
+//! configuration file and `SPACE_COMMS_*` environment variables below their
+//! flags; see `settings` for the layers and their precedence.
+//!
+//! # Requirements Traceability
+//! - FN-CLI-001: Workspace tools run non-interactively from one binary
+//! - FN-CLI-002: Consistent flags, output formats and exit status
+//! - FN-CLI-003: Settings layered from defaults, file, environment and flags
+//! - REQ-QL-001: Requirement to function to test matrix checked for coverage loss
+
+mod dictionary;
+mod ground;
+mod inspect;
+mod plan_pass;
+mod satellite_sim;
+mod settings;
+mod simulate;
+mod trace;
+
+use std::error::Error;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use frequency_band_simulation::BandType as SimBand;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a subcommand that ran: its verdict, or why it could not run
+type CliResult = Result<Verdict, Box<dyn Error>>;
+
+/// Verdict of a subcommand that ran to completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    /// The result is good
+    Pass,
+    /// The run completed but its result is bad
+    Fail,
+}
+
+impl Verdict {
+    /// `Pass` if `good`, otherwise `Fail`
+    fn from_pass(good: bool) -> Self {
+        if good {
+            Verdict::Pass
+        } else {
+            Verdict::Fail
+        }
+    }
+}
+
+/// Frequency band, as given on the command line and in the configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Band {
+    /// UHF band
+    Uhf,
+    /// S band
+    S,
+    /// X band
+    X,
+    /// K band
+    K,
+    /// Ka band
+    Ka,
+}
+
+impl Band {
+    /// Every band, in increasing frequency
+    const ALL: [Band; 5] = [Band::Uhf, Band::S, Band::X, Band::K, Band::Ka];
+
+    /// The band in the channel simulator
+    fn simulated(self) -> SimBand {
+        match self {
+            Band::Uhf => SimBand::UHFBand,
+            Band::S => SimBand::SBand,
+            Band::X => SimBand::XBand,
+            Band::K => SimBand::KBand,
+            Band::Ka => SimBand::KaBand,
+        }
+    }
+}
+
+/// Options every subcommand takes
+#[derive(Debug, Clone, Copy, clap::Args)]
+struct Output {
+    /// Write the result as JSON instead of a readable report
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+impl Output {
+    /// Print `value` as JSON, or `report` when JSON was not asked for
+    ///
+    /// A closed stdout, as when piped into `head`, is not an error.
+    fn emit(&self, value: &serde_json::Value, report: impl FnOnce() -> String) {
+        let mut stdout = std::io::stdout().lock();
+        let _ = if self.json {
+            writeln!(stdout, "{:#}", value)
+        } else {
+            write!(stdout, "{}", report())
+        };
+    }
+}
+
+/// Space communications tools
+#[derive(Debug, Parser)]
+#[command(name = "space-comms", version, about = "Space communications tools")]
+struct Cli {
+    #[command(flatten)]
+    output: Output,
+
+    /// Configuration file; `$SPACE_COMMS_CONFIG` or ./space-comms.toml if
+    /// not given
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Subcommands
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Simulate one transmission through the link model on each band
+    Simulate(simulate::Args),
+    /// Run the ground station for a fixed time, uplinking commands
+    Ground(ground::Args),
+    /// Run a stand-in satellite answering the ground station
+    SatelliteSim(satellite_sim::Args),
+    /// Break a raw packet down into annotated fields
+    InspectPacket(inspect::Args),
+    /// Plan a pass: geometry and the volume it can carry
+    PlanPass(plan_pass::Args),
+    /// Export the command dictionary
+    ExportDictionary(dictionary::Args),
+    /// Trace requirements to annotated functions and their tests
+    Trace(trace::Args),
+    /// Inspect the layered configuration
+    Config {
+        #[command(subcommand)]
+        action: settings::Action,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let settings = || settings::load(cli.config.as_deref());
+    let result = match &cli.command {
+        Command::Simulate(args) => {
+            settings().and_then(|settings| simulate::run(args, settings.simulate, cli.output))
+        }
+        Command::Ground(args) => {
+            settings().and_then(|settings| ground::run(args, settings.ground, cli.output))
+        }
+        Command::SatelliteSim(args) => settings()
+            .and_then(|settings| satellite_sim::run(args, settings.satellite_sim, cli.output)),
+        Command::InspectPacket(args) => inspect::run(args, cli.output),
+        Command::PlanPass(args) => plan_pass::run(args, cli.output),
+        Command::ExportDictionary(args) => dictionary::run(args, cli.output),
+        Command::Trace(args) => trace::run(args, cli.output),
+        Command::Config { action } => settings::run(action, cli.config.as_deref(), cli.output),
+    };
+
+    match result {
+        Ok(Verdict::Pass) => ExitCode::SUCCESS,
+        Ok(Verdict::Fail) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("space-comms: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_command_line_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_flags_parse_the_same_on_every_subcommand() {
+        let cli =
+            Cli::try_parse_from(["space-comms", "plan-pass", "--band", "ka", "--json"]).unwrap();
+        assert!(cli.output.json);
+        assert!(matches!(cli.command, Command::PlanPass(_)));
+
+        let cli = Cli::try_parse_from(["space-comms", "--json", "export-dictionary"]).unwrap();
+        assert!(cli.output.json);
+
+        let cli = Cli::try_parse_from([
+            "space-comms",
+            "config",
+            "show",
+            "--resolved",
+            "--config",
+            "station.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("station.toml")));
+        assert!(Cli::try_parse_from(["space-comms", "simulate", "--band", "q"]).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! `plan-pass`: geometry and capacity of one pass
+//!
+//! Generates the pass (`tracking::generate_pass`) and forecasts the volume
+//! it can carry with `forecast::ForecastStudy` in steady weather. Given a
+//! receiver noise temperature, the ground antenna's noise follows the
+//! elevation as in pass planning on the console. The verdict fails if the
+//! pass carries nothing.
+
+use frequency_band_simulation::antenna_noise::GroundAntenna;
+use frequency_band_simulation::forecast::{ContactOpportunity, ForecastStudy};
+use frequency_band_simulation::tracking::generate_pass;
+use frequency_band_simulation::weather::WeatherGenerator;
+use frequency_band_simulation::EnvironmentalConditions;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::json;
+
+use crate::simulate::{conditions, frequency_band};
+use crate::{Band, CliResult, Output, Verdict};
+
+/// Orbit, pass and link
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Band the pass is worked on
+    #[arg(long, value_enum, default_value_t = Band::X)]
+    band: Band,
+
+    /// Circular orbit altitude, km
+    #[arg(long, default_value_t = 550.0)]
+    altitude_km: f64,
+
+    /// Culmination elevation of the pass, degrees
+    #[arg(long, default_value_t = 45.0)]
+    max_elevation_deg: f64,
+
+    /// Minimum usable elevation, degrees
+    #[arg(long, default_value_t = 10.0)]
+    mask_deg: f64,
+
+    /// Sample spacing, s
+    #[arg(long, default_value_t = 10.0)]
+    step_s: f64,
+
+    /// Transmitter power, W
+    #[arg(long, default_value_t = 50.0)]
+    power_w: f64,
+
+    /// Ground antenna diameter, m
+    #[arg(long, default_value_t = 3.0)]
+    antenna_m: f64,
+
+    /// Lowest data rate worth keeping the link for, Mbps
+    #[arg(long, default_value_t = 1.0)]
+    rate_mbps: f64,
+
+    /// Rain rate throughout the pass, mm/h
+    #[arg(long, default_value_t = 0.0)]
+    rain_mm_h: f64,
+
+    /// Receiver noise temperature, K; adds the band's typical antenna
+    /// temperature curve
+    #[arg(long)]
+    receiver_noise_k: Option<f64>,
+
+    /// List every pass sample
+    #[arg(long)]
+    samples: bool,
+}
+
+/// Weather that stays at the base conditions
+struct Steady;
+
+impl WeatherGenerator for Steady {
+    fn conditions_at(
+        &mut self,
+        _time_s: f64,
+        base: &EnvironmentalConditions,
+        _rng: &mut StdRng,
+    ) -> EnvironmentalConditions {
+        base.clone()
+    }
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, output: Output) -> CliResult {
+    let band = frequency_band(args.band)?;
+    let mut study = ForecastStudy {
+        altitude_km: args.altitude_km,
+        elevation_mask_deg: args.mask_deg,
+        step_s: args.step_s,
+        transmit_power_watts: args.power_w,
+        antenna_diameter_meters: args.antenna_m,
+        required_data_rate_mbps: args.rate_mbps,
+        ..ForecastStudy::default()
+    };
+    if let Some(receiver_noise_k) = args.receiver_noise_k {
+        let antenna = GroundAntenna::typical("ground", args.band.simulated(), receiver_noise_k);
+        antenna.validate()?;
+        study = study.with_ground_antenna(antenna);
+    }
+
+    let pass = generate_pass(
+        args.altitude_km,
+        args.max_elevation_deg,
+        args.mask_deg,
+        args.step_s,
+    );
+    let contact = ContactOpportunity {
+        contact_id: 1,
+        start_s: 0,
+        max_elevation_deg: args.max_elevation_deg,
+    };
+    let forecast = study.forecast_contact(
+        &band,
+        &contact,
+        &mut Steady,
+        &conditions(args.rain_mm_h, 10.0),
+        &mut StdRng::seed_from_u64(0),
+    )?;
+    let min_range_km = pass
+        .iter()
+        .map(|sample| sample.range_km)
+        .fold(f64::INFINITY, f64::min);
+
+    let value = json!({
+        "band": args.band.simulated().to_string(),
+        "forecast": forecast,
+        "min_range_km": (!pass.is_empty()).then_some(min_range_km),
+        "samples": if args.samples { json!(pass) } else { json!(null) },
+    });
+    output.emit(&value, || {
+        if pass.is_empty() {
+            return format!("Pass never rises above the {:.0}° mask\n", args.mask_deg);
+        }
+        let mut report = format!(
+            "{} pass, {:.0} km orbit, {:.0}° max elevation\n\
+             Duration:  {} s above {:.0}°\n\
+             Min range: {:.0} km\n\
+             Capacity:  {} bytes ({}% of clear sky)\n",
+            args.band.simulated(),
+            args.altitude_km,
+            args.max_elevation_deg,
+            forecast.duration_s,
+            args.mask_deg,
+            min_range_km,
+            forecast.capacity_bytes,
+            forecast.capacity_percent()
+        );
+        if args.samples {
+            report.push_str("  Time s   Az °   El °   Range km\n");
+            for sample in &pass {
+                report.push_str(&format!(
+                    "  {:>6.0} {:>6.1} {:>6.1} {:>10.1}\n",
+                    sample.time_s,
+                    sample.pointing.azimuth_deg,
+                    sample.pointing.elevation_deg,
+                    sample.range_km
+                ));
+            }
+        }
+        report
+    });
+
+    Ok(Verdict::from_pass(forecast.capacity_bytes > 0))
+}
@@ -0,0 +1,113 @@
+//! `satellite-sim`: a stand-in satellite on the loopback interface
+//!
+//! Binds the satellite command address and runs the soak test's simulated
+//! satellite (`soak::simulate_satellite`): every command is answered with a
+//! completed execution report and housekeeping is downlinked at a steady
+//! rate to the ground station's telemetry port. Start it before `ground`.
+//!
+//! Flags override the `[satellite_sim]` section of the configuration.
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use space_comms_ground::{soak::simulate_satellite, GroundStationConfig, SATELLITE_COMMAND_ADDR};
+
+use crate::settings::override_with;
+use crate::{CliResult, Output, Verdict};
+
+/// Run length and traffic; unset flags keep the configured value
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Seconds to run for
+    #[arg(long)]
+    duration_s: Option<u64>,
+
+    /// Housekeeping frames downlinked per second
+    #[arg(long)]
+    telemetry_rate: Option<f64>,
+
+    /// Ground station telemetry address
+    #[arg(long)]
+    telemetry_addr: Option<SocketAddr>,
+}
+
+/// `[satellite_sim]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// Seconds to run for
+    pub duration_s: u64,
+    /// Housekeeping frames downlinked per second
+    pub telemetry_rate: f64,
+    /// Ground station telemetry address
+    pub telemetry_addr: SocketAddr,
+}
+
+impl Default for Settings {
+    /// A minute of 1 Hz telemetry to a ground station with the default
+    /// configuration on this host
+    fn default() -> Self {
+        Settings {
+            duration_s: 60,
+            telemetry_rate: 1.0,
+            telemetry_addr: SocketAddr::from((
+                [127, 0, 0, 1],
+                GroundStationConfig::default().telemetry_port,
+            )),
+        }
+    }
+}
+
+impl Args {
+    /// Override `settings` with the flags given
+    fn apply(&self, settings: &mut Settings) {
+        override_with(&mut settings.duration_s, self.duration_s);
+        override_with(&mut settings.telemetry_rate, self.telemetry_rate);
+        override_with(&mut settings.telemetry_addr, self.telemetry_addr);
+    }
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, mut settings: Settings, output: Output) -> CliResult {
+    args.apply(&mut settings);
+    if !(settings.telemetry_rate > 0.0 && settings.telemetry_rate.is_finite()) {
+        return Err("telemetry rate must be above zero".into());
+    }
+    let socket = UdpSocket::bind(SATELLITE_COMMAND_ADDR)
+        .map_err(|e| format!("cannot bind {}: {}", SATELLITE_COMMAND_ADDR, e))?;
+    socket.set_read_timeout(Some(Duration::from_millis(10)))?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let reports_sent = Arc::new(AtomicU64::new(0));
+    let satellite = {
+        let stop = Arc::clone(&stop);
+        let reports_sent = Arc::clone(&reports_sent);
+        let telemetry_addr = settings.telemetry_addr;
+        let period = Duration::from_secs_f64(1.0 / settings.telemetry_rate);
+        thread::spawn(move || {
+            simulate_satellite(&socket, telemetry_addr, period, &stop, &reports_sent)
+        })
+    };
+    if !output.json {
+        println!(
+            "Satellite listening on {}, telemetry to {} for {} s",
+            SATELLITE_COMMAND_ADDR, settings.telemetry_addr, settings.duration_s
+        );
+    }
+
+    thread::sleep(Duration::from_secs(settings.duration_s));
+    stop.store(true, Ordering::Relaxed);
+    let stopped_cleanly = satellite.join().is_ok();
+
+    let reports = reports_sent.load(Ordering::Relaxed);
+    output.emit(
+        &json!({"duration_s": settings.duration_s, "execution_reports": reports}),
+        || format!("Answered {} commands\n", reports),
+    );
+    Ok(Verdict::from_pass(stopped_cleanly))
+}
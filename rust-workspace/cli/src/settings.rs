@@ -0,0 +1,277 @@
+//! Layered configuration of the subcommands, and `config show`
+//!
+//! The `ground`, `satellite-sim` and `simulate` subcommands take their
+//! settings from four layers, each overriding the one before:
+//!
+//! 1. Built-in defaults (`config show` prints them)
+//! 2. The configuration file: `--config <PATH>`, else `$SPACE_COMMS_CONFIG`,
+//!    else `space-comms.toml` in the working directory if there is one.
+//!    TOML, JSON or YAML by extension
+//! 3. Environment variables `SPACE_COMMS_<SECTION>__<KEY>`, e.g.
+//!    `SPACE_COMMS_GROUND__TELEMETRY_PORT=9000`; lists are comma separated
+//! 4. The subcommand's flags
+//!
+//! Each subcommand has a section named after it (`[ground]`,
+//! `[satellite_sim]`, `[simulate]`) with one key per flag. Unknown sections
+//! and keys are errors, so a misspelt setting is never silently ignored.
+//! `config show --resolved` prints the settings after the file and the
+//! environment, with the layer each value came from.
+//!
+//! The file and environment layers are those of
+//! [`space_comms_shared::settings`], which the `ground-station` console and
+//! the interactive simulator read their sections through as well.
+//!
+//! # Requirements Traceability
+//! - FN-CLI-003: Settings layered from defaults, file, environment and flags
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use config::{Config, File, Source, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use space_comms_shared::settings::{config_file, env_var, environment};
+
+use crate::{ground, satellite_sim, simulate, CliResult, Output, Verdict};
+
+/// Keys whose environment variables hold comma-separated lists
+const LIST_KEYS: [&str; 1] = ["simulate.bands"];
+
+/// Origin `config` gives values read from the environment
+const ENV_ORIGIN: &str = "the environment";
+
+/// Settings of every configurable subcommand
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// `ground`
+    pub ground: ground::Settings,
+    /// `satellite-sim`
+    pub satellite_sim: satellite_sim::Settings,
+    /// `simulate`
+    pub simulate: simulate::Settings,
+}
+
+/// Settings with the layers they were resolved from
+#[derive(Debug)]
+pub struct Resolved {
+    /// Effective settings
+    pub settings: Settings,
+    /// Configuration file read, if any
+    pub file: Option<PathBuf>,
+    /// Layer of each setting set by the file or the environment, by dotted
+    /// key: the file's path or the environment variable
+    pub sources: BTreeMap<String, String>,
+}
+
+/// Replace `value` with the flag's, if the flag was given
+pub fn override_with<T>(value: &mut T, flag: Option<T>) {
+    if let Some(flag) = flag {
+        *value = flag;
+    }
+}
+
+/// Leaves of a configuration tree by dotted key
+fn leaves(prefix: &str, table: config::Map<String, Value>, out: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value.clone().into_table() {
+            Ok(table) => leaves(&key, table, out),
+            Err(_) => out.push((key, value)),
+        }
+    }
+}
+
+/// Resolve the settings below the flags
+///
+/// - **ID**: FN-CLI-003
+/// - **Requirement**: Every configurable subcommand resolves its settings
+///   the same way, defaults < file < environment < flags, and the result
+///   can be inspected.
+/// - **Inputs**: The file named on the command line, and the process
+///   environment; only `SPACE_COMMS_*` variables are read.
+/// - **Outputs**: The settings, the file read and the origin of every
+///   value not defaulted; flags are applied by each subcommand.
+/// - **Failure Modes**: A named file that is missing or unreadable, a
+///   value of the wrong type, or an unknown section or key → `Err`.
+pub fn resolve(named: Option<&Path>, env: HashMap<String, String>) -> Result<Resolved, String> {
+    let file = config_file(named, &env);
+    let defaults = Config::try_from(&Settings::default()).map_err(|e| e.to_string())?;
+    let mut builder = Config::builder().add_source(defaults);
+    if let Some(path) = &file {
+        builder = builder.add_source(File::from(path.as_path()).required(true));
+    }
+    let config = builder
+        .add_source(environment(env, &LIST_KEYS))
+        .build()
+        .map_err(|e| format!("configuration: {}", e))?;
+
+    // Defaults have no origin; the file's is its path relative to the
+    // working directory, reported as it was named
+    let mut values = Vec::new();
+    leaves(
+        "",
+        config.collect().map_err(|e| e.to_string())?,
+        &mut values,
+    );
+    let sources = values
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let source = match (value.origin()?, &file) {
+                (ENV_ORIGIN, _) => env_var(&key),
+                (_, Some(path)) => path.display().to_string(),
+                (origin, None) => origin.to_string(),
+            };
+            Some((key, source))
+        })
+        .collect();
+    let settings = config
+        .try_deserialize()
+        .map_err(|e| format!("configuration: {}", e))?;
+    Ok(Resolved {
+        settings,
+        file,
+        sources,
+    })
+}
+
+/// Settings of the process, from the file named on the command line
+pub fn load(named: Option<&Path>) -> Result<Settings, Box<dyn Error>> {
+    Ok(resolve(named, std::env::vars().collect())?.settings)
+}
+
+/// `config` actions
+#[derive(Debug, clap::Subcommand)]
+pub enum Action {
+    /// Print the configuration: the defaults, a starting point for a file
+    Show {
+        /// Print the effective configuration after the file and the
+        /// environment, with where each value came from
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+/// Run `config`
+pub fn run(action: &Action, named: Option<&Path>, output: Output) -> CliResult {
+    let Action::Show { resolved } = action;
+    let resolved = if *resolved {
+        resolve(named, std::env::vars().collect())?
+    } else {
+        Resolved {
+            settings: Settings::default(),
+            file: None,
+            sources: BTreeMap::new(),
+        }
+    };
+
+    let value = json!({
+        "file": resolved.file,
+        "settings": resolved.settings,
+        "sources": resolved.sources,
+    });
+    let toml = toml::Value::try_from(&resolved.settings)?;
+    output.emit(&value, || report(&toml, &resolved));
+    Ok(Verdict::Pass)
+}
+
+/// The settings as a TOML file, each value not defaulted annotated with
+/// its layer
+fn report(toml: &toml::Value, resolved: &Resolved) -> String {
+    let mut report = String::new();
+    if let Some(file) = &resolved.file {
+        report.push_str(&format!("# Configuration file: {}\n", file.display()));
+    }
+    let Some(sections) = toml.as_table() else {
+        return report;
+    };
+    for (section, keys) in sections {
+        if !report.is_empty() {
+            report.push('\n');
+        }
+        report.push_str(&format!("[{}]\n", section));
+        for (key, value) in keys.as_table().into_iter().flatten() {
+            report.push_str(&format!("{} = {}", key, value));
+            if let Some(source) = resolved.sources.get(&format!("{}.{}", section, key)) {
+                report.push_str(&format!("  # {}", source));
+            }
+            report.push('\n');
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Band;
+
+    fn env(variables: &[(&str, &str)]) -> HashMap<String, String> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("station.toml");
+        std::fs::write(
+            &path,
+            "[ground]\noperator = \"night shift\"\ntelemetry_port = 9000\n\
+             [simulate]\nbands = [\"x\"]\ndistance_km = 2000.0\n",
+        )
+        .unwrap();
+
+        let resolved = resolve(
+            Some(&path),
+            env(&[
+                ("SPACE_COMMS_GROUND__TELEMETRY_PORT", "9100"),
+                ("SPACE_COMMS_SIMULATE__BANDS", "s,ka"),
+                ("HOME", "/root"),
+            ]),
+        )
+        .unwrap();
+        let settings = &resolved.settings;
+        assert_eq!(settings.ground.operator, "night shift");
+        assert_eq!(settings.ground.telemetry_port, 9100);
+        assert_eq!(settings.simulate.bands, vec![Band::S, Band::Ka]);
+        assert_eq!(settings.simulate.distance_km, 2000.0);
+        assert_eq!(settings.satellite_sim, satellite_sim::Settings::default());
+
+        let file = path.display().to_string();
+        assert_eq!(resolved.sources["ground.operator"], file);
+        assert_eq!(
+            resolved.sources["ground.telemetry_port"],
+            "SPACE_COMMS_GROUND__TELEMETRY_PORT"
+        );
+        assert!(!resolved.sources.contains_key("ground.command_port"));
+    }
+
+    #[test]
+    fn test_bad_configuration_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("typo.toml");
+        std::fs::write(&path, "[ground]\ntelemetry_prot = 9000\n").unwrap();
+        assert!(resolve(Some(&path), HashMap::new()).is_err());
+
+        let missing = dir.path().join("missing.toml");
+        assert!(resolve(Some(&missing), HashMap::new()).is_err());
+
+        let env = env(&[("SPACE_COMMS_SATELLITE_SIM__DURATION_S", "soon")]);
+        assert!(resolve(None, env).is_err());
+    }
+
+    #[test]
+    fn test_defaults_round_trip_through_toml() {
+        let text = toml::to_string(&Settings::default()).unwrap();
+        let settings: Settings = toml::from_str(&text).unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+}
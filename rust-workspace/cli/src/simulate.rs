@@ -0,0 +1,201 @@
+//! `simulate`: one transmission through the link model
+//!
+//! Runs `FrequencyBand::simulate_transmission_with` on each requested band
+//! for the same geometry, transmitter and weather, judged with the mission's
+//! margins. The verdict fails if the link closes on none of them.
+//!
+//! Flags override the `[simulate]` section of the configuration.
+
+use frequency_band_simulation::margin::MarginPolicy;
+use frequency_band_simulation::{EnvironmentalConditions, FrequencyBand, TransmissionParameters};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::settings::override_with;
+use crate::{Band, CliResult, Output, Verdict};
+
+/// Link geometry, transmitter and weather; unset flags keep the configured
+/// value
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Band to simulate; repeat for several
+    #[arg(long = "band", value_enum)]
+    bands: Vec<Band>,
+
+    /// Slant range, km
+    #[arg(long)]
+    distance_km: Option<f64>,
+
+    /// Elevation of the ground antenna, degrees
+    #[arg(long)]
+    elevation_deg: Option<f64>,
+
+    /// Transmitter power, W
+    #[arg(long)]
+    power_w: Option<f64>,
+
+    /// Ground antenna diameter, m
+    #[arg(long)]
+    antenna_m: Option<f64>,
+
+    /// Volume to send, MB
+    #[arg(long)]
+    data_mb: Option<f64>,
+
+    /// Lowest data rate worth keeping the link for, Mbps
+    #[arg(long)]
+    rate_mbps: Option<f64>,
+
+    /// Rain rate, mm/h
+    #[arg(long)]
+    rain_mm_h: Option<f64>,
+
+    /// Cloud cover, percent
+    #[arg(long)]
+    cloud_percent: Option<f64>,
+
+    /// SNR required above closure, dB
+    #[arg(long)]
+    link_margin_db: Option<f64>,
+}
+
+/// `[simulate]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// Bands to simulate
+    pub bands: Vec<Band>,
+    /// Slant range, km
+    pub distance_km: f64,
+    /// Elevation of the ground antenna, degrees
+    pub elevation_deg: f64,
+    /// Transmitter power, W
+    pub power_w: f64,
+    /// Ground antenna diameter, m
+    pub antenna_m: f64,
+    /// Volume to send, MB
+    pub data_mb: f64,
+    /// Lowest data rate worth keeping the link for, Mbps
+    pub rate_mbps: f64,
+    /// Rain rate, mm/h
+    pub rain_mm_h: f64,
+    /// Cloud cover, percent
+    pub cloud_percent: f64,
+    /// SNR required above closure, dB
+    pub link_margin_db: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            bands: Band::ALL.to_vec(),
+            distance_km: 1000.0,
+            elevation_deg: 30.0,
+            power_w: 20.0,
+            antenna_m: 3.0,
+            data_mb: 100.0,
+            rate_mbps: 1.0,
+            rain_mm_h: 0.0,
+            cloud_percent: 10.0,
+            link_margin_db: MarginPolicy::default().link_margin_db,
+        }
+    }
+}
+
+impl Args {
+    /// Override `settings` with the flags given
+    fn apply(&self, settings: &mut Settings) {
+        if !self.bands.is_empty() {
+            settings.bands = self.bands.clone();
+        }
+        override_with(&mut settings.distance_km, self.distance_km);
+        override_with(&mut settings.elevation_deg, self.elevation_deg);
+        override_with(&mut settings.power_w, self.power_w);
+        override_with(&mut settings.antenna_m, self.antenna_m);
+        override_with(&mut settings.data_mb, self.data_mb);
+        override_with(&mut settings.rate_mbps, self.rate_mbps);
+        override_with(&mut settings.rain_mm_h, self.rain_mm_h);
+        override_with(&mut settings.cloud_percent, self.cloud_percent);
+        override_with(&mut settings.link_margin_db, self.link_margin_db);
+    }
+}
+
+/// Clear-sky conditions with the given rain and cloud
+pub fn conditions(rain_mm_h: f64, cloud_percent: f64) -> EnvironmentalConditions {
+    EnvironmentalConditions {
+        rain_rate_mm_hour: rain_mm_h,
+        cloud_cover_percent: cloud_percent,
+        atmospheric_pressure_mb: 1013.25,
+        temperature_celsius: 15.0,
+        humidity_percent: 50.0,
+        ionospheric_activity: 0.1,
+        solar_activity: 0.1,
+    }
+}
+
+/// The simulator's definition of `band`
+pub fn frequency_band(band: Band) -> Result<FrequencyBand, String> {
+    FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|definition| definition.name == band.simulated())
+        .ok_or_else(|| format!("no standard definition of {}", band.simulated()))
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, mut settings: Settings, output: Output) -> CliResult {
+    args.apply(&mut settings);
+    if settings.bands.is_empty() {
+        return Err("no bands to simulate".into());
+    }
+    let params = TransmissionParameters {
+        distance_km: settings.distance_km,
+        data_size_mb: settings.data_mb,
+        required_data_rate_mbps: settings.rate_mbps,
+        elevation_angle_degrees: settings.elevation_deg,
+        transmit_power_watts: settings.power_w,
+        antenna_diameter_meters: settings.antenna_m,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
+    };
+    let environment = conditions(settings.rain_mm_h, settings.cloud_percent);
+    let margins = MarginPolicy {
+        link_margin_db: settings.link_margin_db,
+        ..MarginPolicy::default()
+    };
+
+    let mut results = Vec::with_capacity(settings.bands.len());
+    for &band in &settings.bands {
+        let result =
+            frequency_band(band)?.simulate_transmission_with(&params, &environment, &margins)?;
+        results.push((band, result));
+    }
+
+    let value = json!(results
+        .iter()
+        .map(|(band, result)| json!({
+            "band": band.simulated().to_string(),
+            "result": result,
+        }))
+        .collect::<Vec<_>>());
+    output.emit(&value, || {
+        let mut report = format!(
+            "{:<9} {:>7} {:>10} {:>9} {:>11}\n",
+            "Band", "Closes", "Rate Mbps", "SNR dB", "Latency ms"
+        );
+        for (band, result) in &results {
+            report.push_str(&format!(
+                "{:<9} {:>7} {:>10.1} {:>9.1} {:>11.1}\n",
+                band.simulated().to_string(),
+                if result.success { "yes" } else { "no" },
+                result.actual_data_rate_mbps,
+                result.signal_to_noise_ratio_db,
+                result.total_latency_ms
+            ));
+        }
+        report
+    });
+
+    Ok(Verdict::from_pass(
+        results.iter().any(|(_, result)| result.success),
+    ))
+}
@@ -0,0 +1,744 @@
+//! `trace`: requirement traceability matrix from `#[req]` annotations
+//!
+//! Parses every Rust source under the workspace root and builds the matrix
+//! from each requirement to the functions annotated `#[req("REQ-XX-NNN")]`
+//! and the tests that exercise them. A test exercises the requirements it
+//! is annotated with and those of every annotated function of its own crate
+//! it calls, including calls inside macros such as `assert!`. `Type::name`
+//! calls match by type and name, free function calls by name, and method
+//! calls by name among the methods of types the test's file mentions.
+//!
+//! `--requirements` reads the IDs the specification defines from its
+//! `#### REQ-XX-NNN: Title` headings; an annotation citing any other ID
+//! fails the run, and specified requirements nothing traces to are listed.
+//! `--baseline` takes an earlier `trace --json` and fails the run when a
+//! requirement it traces to functions or tests has lost all of them.
+//!
+//! ```text
+//! space-comms trace --requirements ../docs/SOFTWARE_REQUIREMENTS_SPECIFICATION.md \
+//!     --baseline ../docs/requirements_trace.json
+//! space-comms trace --json > ../docs/requirements_trace.json
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Block, Expr, ImplItem, Item, LitStr, Token, Type};
+
+use crate::{CliResult, Output, Verdict};
+
+/// Where to look and what to check against
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Workspace root whose sources are scanned
+    #[arg(long, value_name = "PATH", default_value = ".")]
+    root: PathBuf,
+
+    /// Requirements specification defining the requirement IDs
+    #[arg(long, value_name = "PATH")]
+    requirements: Option<PathBuf>,
+
+    /// Earlier `trace --json` output; fail when a requirement it covers
+    /// loses its functions or tests
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+}
+
+/// Functions and tests traced to one requirement
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Coverage {
+    /// Annotated functions implementing the requirement
+    functions: BTreeSet<String>,
+    /// Tests exercising the requirement
+    tests: BTreeSet<String>,
+}
+
+/// Requirement → functions → tests
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Matrix {
+    /// Coverage of every requirement some annotation cites
+    requirements: BTreeMap<String, Coverage>,
+}
+
+/// A call made in a test body
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Call {
+    /// `Type::name(..)`
+    Path { owner: String, name: String },
+    /// `name(..)`
+    Free(String),
+    /// `value.name(..)`
+    Method(String),
+    /// `name(..)` inside a macro whose body is not expressions
+    Name(String),
+}
+
+/// A function found in the sources
+#[derive(Debug)]
+struct Function {
+    /// `file::module::Type::name`
+    id: String,
+    /// Top-level directory of the file under the root, the crate
+    krate: String,
+    /// Type of the `impl` block the function is in
+    owner: Option<String>,
+    /// Function name
+    name: String,
+    /// IDs from its `#[req]` attributes
+    requirements: Vec<String>,
+    /// Whether it is a `#[test]`
+    is_test: bool,
+    /// Calls in its body
+    calls: Vec<Call>,
+    /// Every identifier in its file
+    mentions: Rc<BTreeSet<String>>,
+}
+
+impl Function {
+    /// Whether `call`, made by `test`, may reach this function
+    fn answers(&self, call: &Call, test: &Function) -> bool {
+        let named = self
+            .owner
+            .as_ref()
+            .is_none_or(|o| test.mentions.contains(o));
+        match call {
+            Call::Path { owner, name } => {
+                *name == self.name && self.owner.as_deref() == Some(owner.as_str())
+            }
+            Call::Free(name) => *name == self.name && self.owner.is_none(),
+            Call::Method(name) => *name == self.name && self.owner.is_some() && named,
+            Call::Name(name) => *name == self.name && named,
+        }
+    }
+}
+
+/// Whether `id` is a requirement ID: `REQ-`, two capitals, `-`, three digits
+fn is_requirement_id(id: &str) -> bool {
+    let bytes = id.as_bytes();
+    bytes.len() == 10
+        && id.starts_with("REQ-")
+        && bytes[4..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6] == b'-'
+        && bytes[7..].iter().all(u8::is_ascii_digit)
+}
+
+/// Requirement IDs and titles from the specification's headings
+fn parse_specification(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .filter_map(|line| line.strip_prefix("#### "))
+        .filter_map(|heading| {
+            let (id, title) = heading.split_once(':').unwrap_or((heading, ""));
+            let id = id.trim();
+            is_requirement_id(id).then(|| (id.to_string(), title.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Collects the calls made in a function body
+#[derive(Debug, Default)]
+struct Calls(Vec<Call>);
+
+impl Calls {
+    /// Calls in `tokens` that do not parse as expressions: every identifier
+    /// followed by parentheses
+    fn scan_tokens(&mut self, tokens: TokenStream) {
+        let mut previous = None;
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    if let (Some(name), Delimiter::Parenthesis) =
+                        (previous.take(), group.delimiter())
+                    {
+                        self.0.push(Call::Name(name));
+                    }
+                    self.scan_tokens(group.stream());
+                }
+                TokenTree::Ident(ident) => previous = Some(ident.to_string()),
+                _ => previous = None,
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Calls {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match segments.as_slice() {
+                [.., owner, name] => self.0.push(Call::Path {
+                    owner: owner.clone(),
+                    name: name.clone(),
+                }),
+                [name] => self.0.push(Call::Free(name.clone())),
+                [] => {}
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        self.0.push(Call::Method(call.method.to_string()));
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        match mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            Ok(args) => args.iter().for_each(|arg| self.visit_expr(arg)),
+            Err(_) => self.scan_tokens(mac.tokens.clone()),
+        }
+    }
+}
+
+/// Requirement IDs cited by `#[req(..)]` attributes, malformed ones included
+fn requirements(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "req")
+        })
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flat_map(|ids| ids.into_iter().map(|id| id.value()))
+        .collect()
+}
+
+/// Whether `attrs` mark a test: `#[test]`, `#[tokio::test]` and the like
+fn is_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "test")
+    })
+}
+
+/// Last identifier of a type, the `impl` block's owner
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(reference) => type_name(&reference.elem),
+        _ => None,
+    }
+}
+
+/// Functions of one source file
+struct FileScan<'a> {
+    /// Path of the file relative to the root
+    file: &'a str,
+    /// Crate the file belongs to
+    krate: &'a str,
+    /// Every identifier in the file
+    mentions: Rc<BTreeSet<String>>,
+    /// Functions found so far
+    functions: Vec<Function>,
+}
+
+impl FileScan<'_> {
+    /// Record the function `name` in `modules`, inside `impl owner` if given
+    fn function(
+        &mut self,
+        modules: &[String],
+        owner: Option<String>,
+        attrs: &[Attribute],
+        name: &syn::Ident,
+        body: &Block,
+    ) {
+        let requirements = requirements(attrs);
+        let is_test = is_test(attrs);
+        if requirements.is_empty() && !is_test {
+            return;
+        }
+        let mut calls = Calls::default();
+        if is_test {
+            calls.visit_block(body);
+        }
+        let id = std::iter::once(self.file.to_string())
+            .chain(modules.iter().cloned())
+            .chain(owner.clone())
+            .chain(std::iter::once(name.to_string()))
+            .collect::<Vec<_>>()
+            .join("::");
+        self.functions.push(Function {
+            id,
+            krate: self.krate.to_string(),
+            owner,
+            name: name.to_string(),
+            requirements,
+            is_test,
+            calls: calls.0,
+            mentions: Rc::clone(&self.mentions),
+        });
+    }
+
+    /// Record the functions in `items`, nested in `modules`
+    fn items(&mut self, items: &[Item], modules: &mut Vec<String>) {
+        for item in items {
+            match item {
+                Item::Fn(f) => self.function(modules, None, &f.attrs, &f.sig.ident, &f.block),
+                Item::Impl(block) => {
+                    let owner = type_name(&block.self_ty);
+                    for item in &block.items {
+                        if let ImplItem::Fn(f) = item {
+                            self.function(modules, owner.clone(), &f.attrs, &f.sig.ident, &f.block);
+                        }
+                    }
+                }
+                Item::Mod(module) => {
+                    if let Some((_, items)) = &module.content {
+                        modules.push(module.ident.to_string());
+                        self.items(items, modules);
+                        modules.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Every `.rs` file under `dir`, skipping build output and hidden
+/// directories, in path order
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                source_files(&path, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Every identifier in `tokens`
+fn identifiers(tokens: TokenStream) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                found.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => found.extend(identifiers(group.stream())),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Annotated functions and tests of one file's source
+fn scan_source(file: &str, source: &str) -> syn::Result<Vec<Function>> {
+    let syntax = syn::parse_file(source)?;
+    let krate = file.split_once('/').map_or("", |(krate, _)| krate);
+    let mut scan = FileScan {
+        file,
+        krate,
+        mentions: Rc::new(identifiers(source.parse()?)),
+        functions: Vec::new(),
+    };
+    scan.items(&syntax.items, &mut Vec::new());
+    Ok(scan.functions)
+}
+
+/// The matrix of `functions`
+fn build_matrix(functions: &[Function]) -> Matrix {
+    let mut matrix = Matrix::default();
+    for function in functions.iter().filter(|f| !f.is_test) {
+        for id in &function.requirements {
+            let coverage = matrix.requirements.entry(id.clone()).or_default();
+            coverage.functions.insert(function.id.clone());
+        }
+    }
+    for test in functions.iter().filter(|f| f.is_test) {
+        let called = functions.iter().filter(|f| {
+            !f.is_test && f.krate == test.krate && test.calls.iter().any(|c| f.answers(c, test))
+        });
+        let ids: BTreeSet<&String> = test
+            .requirements
+            .iter()
+            .chain(called.flat_map(|f| &f.requirements))
+            .collect();
+        for id in ids {
+            let coverage = matrix.requirements.entry(id.clone()).or_default();
+            coverage.tests.insert(test.id.clone());
+        }
+    }
+    matrix
+}
+
+/// Requirements `baseline` covers with functions or tests that `current`
+/// no longer does
+fn lost_coverage(baseline: &Matrix, current: &Matrix) -> Vec<String> {
+    let none = Coverage::default();
+    let mut lost = Vec::new();
+    for (id, before) in &baseline.requirements {
+        let now = current.requirements.get(id).unwrap_or(&none);
+        if !before.functions.is_empty() && now.functions.is_empty() {
+            lost.push(format!("{}: no annotated function implements it", id));
+        }
+        if !before.tests.is_empty() && now.tests.is_empty() {
+            lost.push(format!("{}: no test exercises it", id));
+        }
+    }
+    lost
+}
+
+/// Outcome of tracing the workspace
+#[derive(Debug, Serialize)]
+struct Trace {
+    /// Coverage of every requirement some annotation cites
+    requirements: BTreeMap<String, Coverage>,
+    /// Titles of the specified requirements
+    #[serde(skip)]
+    titles: BTreeMap<String, String>,
+    /// Specified requirements nothing traces to
+    untraced: Vec<String>,
+    /// Annotations citing an ID the specification does not define
+    unknown: Vec<String>,
+    /// Requirements that lost coverage since the baseline
+    lost: Vec<String>,
+    /// Files skipped because they do not parse
+    unparsed: Vec<String>,
+    /// Annotated functions found
+    #[serde(skip)]
+    functions: usize,
+    /// Tests found
+    #[serde(skip)]
+    tests: usize,
+}
+
+impl Trace {
+    /// Whether every annotation is known and no coverage was lost
+    fn passes(&self) -> bool {
+        self.unknown.is_empty() && self.lost.is_empty()
+    }
+
+    /// The matrix and its findings as a readable report
+    fn report(&self) -> String {
+        let mut report = String::new();
+        for (id, coverage) in &self.requirements {
+            match self.titles.get(id) {
+                Some(title) => report.push_str(&format!("{} {}\n", id, title)),
+                None => report.push_str(&format!("{}\n", id)),
+            }
+            for function in &coverage.functions {
+                report.push_str(&format!("  fn   {}\n", function));
+            }
+            for test in &coverage.tests {
+                report.push_str(&format!("  test {}\n", test));
+            }
+        }
+        report.push_str(&format!(
+            "\n{} requirements traced, {} annotated functions, {} tests scanned\n",
+            self.requirements.len(),
+            self.functions,
+            self.tests
+        ));
+        let sections = [
+            ("Untraced requirements", &self.untraced),
+            ("Unknown requirement IDs", &self.unknown),
+            ("Lost coverage", &self.lost),
+            ("Files that did not parse", &self.unparsed),
+        ];
+        for (heading, lines) in sections {
+            if !lines.is_empty() {
+                report.push_str(&format!("\n{}:\n", heading));
+                for line in lines {
+                    report.push_str(&format!("  {}\n", line));
+                }
+            }
+        }
+        report
+    }
+}
+
+/// Scan the workspace and check it against the specification and baseline
+fn trace(args: &Args) -> Result<Trace, Box<dyn Error>> {
+    let mut files = Vec::new();
+    source_files(&args.root, &mut files)?;
+    let mut functions = Vec::new();
+    let mut unparsed = Vec::new();
+    for path in &files {
+        let relative = path.strip_prefix(&args.root).unwrap_or(path);
+        let file = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        match scan_source(&file, &std::fs::read_to_string(path)?) {
+            Ok(found) => functions.extend(found),
+            Err(e) => unparsed.push(format!("{}: {}", file, e)),
+        }
+    }
+    let matrix = build_matrix(&functions);
+
+    let titles = match &args.requirements {
+        Some(path) => {
+            let titles = parse_specification(&std::fs::read_to_string(path)?);
+            if titles.is_empty() {
+                return Err(format!("no requirements found in {}", path.display()).into());
+            }
+            titles
+        }
+        None => BTreeMap::new(),
+    };
+    let specified = |id: &str| args.requirements.is_none() || titles.contains_key(id);
+    let unknown = functions
+        .iter()
+        .flat_map(|f| f.requirements.iter().map(move |id| (id, f)))
+        .filter(|(id, _)| !is_requirement_id(id) || !specified(id))
+        .map(|(id, f)| format!("{} cites {}", f.id, id))
+        .collect();
+    let untraced = titles
+        .keys()
+        .filter(|id| !matrix.requirements.contains_key(*id))
+        .cloned()
+        .collect();
+    let lost = match &args.baseline {
+        Some(path) => {
+            let baseline: Matrix = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            lost_coverage(&baseline, &matrix)
+        }
+        None => Vec::new(),
+    };
+
+    let tests = functions.iter().filter(|f| f.is_test).count();
+    Ok(Trace {
+        requirements: matrix.requirements,
+        titles,
+        untraced,
+        unknown,
+        lost,
+        unparsed,
+        functions: functions.len() - tests,
+        tests,
+    })
+}
+
+/// Run the subcommand
+pub fn run(args: &Args, output: Output) -> CliResult {
+    let trace = trace(args)?;
+    output.emit(&serde_json::to_value(&trace)?, || trace.report());
+    Ok(Verdict::from_pass(trace.passes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+        use space_comms_req::req;
+
+        pub struct Queue;
+
+        impl Queue {
+            #[req("REQ-FN-009")]
+            pub fn push(&mut self) {}
+
+            #[req("REQ-FN-001", "REQ-FN-009")]
+            pub fn new() -> Self { Queue }
+        }
+
+        #[req("REQ-IF-002")]
+        pub fn encode() {}
+
+        pub fn new() {}
+
+        #[cfg(test)]
+        mod tests {
+            #[test]
+            fn test_push() {
+                let mut queue = Queue::new();
+                assert!(matches!(queue.push(), ()));
+            }
+
+            #[test]
+            #[req("REQ-SF-001")]
+            fn test_safe_mode() {
+                assert_eq!(new(), ());
+            }
+
+            #[tokio::test]
+            async fn test_encode() {
+                let _ = vec![encode(); 2];
+            }
+        }
+    "#;
+
+    fn matrix() -> Matrix {
+        build_matrix(&scan_source("shared/src/queue.rs", SOURCE).unwrap())
+    }
+
+    #[test]
+    fn test_annotations_trace_to_functions_and_tests() {
+        let matrix = matrix();
+        let ids: Vec<&str> = matrix.requirements.keys().map(String::as_str).collect();
+        assert_eq!(
+            ids,
+            ["REQ-FN-001", "REQ-FN-009", "REQ-IF-002", "REQ-SF-001"]
+        );
+
+        let queue = &matrix.requirements["REQ-FN-009"];
+        assert_eq!(
+            queue.functions.iter().collect::<Vec<_>>(),
+            [
+                "shared/src/queue.rs::Queue::new",
+                "shared/src/queue.rs::Queue::push"
+            ]
+        );
+        assert_eq!(
+            queue.tests.iter().collect::<Vec<_>>(),
+            ["shared/src/queue.rs::tests::test_push"]
+        );
+        // Calls inside vec! and an async test still count
+        assert_eq!(
+            matrix.requirements["REQ-IF-002"]
+                .tests
+                .iter()
+                .collect::<Vec<_>>(),
+            ["shared/src/queue.rs::tests::test_encode"]
+        );
+        // An annotated test is traced with no function behind it, and the
+        // bare new() it calls is not Queue::new
+        let safe_mode = &matrix.requirements["REQ-SF-001"];
+        assert!(safe_mode.functions.is_empty());
+        assert_eq!(safe_mode.tests.len(), 1);
+        assert_eq!(matrix.requirements["REQ-FN-001"].tests.len(), 1);
+    }
+
+    #[test]
+    fn test_tests_only_reach_their_own_crate_and_named_types() {
+        let mut functions = scan_source("shared/src/queue.rs", SOURCE).unwrap();
+        let other = r#"
+            #[test]
+            fn test_elsewhere() { encode(); }
+        "#;
+        functions.extend(scan_source("ground/src/lib.rs", other).unwrap());
+        // push() on a Vec in a file that never names Queue is not Queue::push
+        let unrelated = r#"
+            #[test]
+            fn test_vec() { let mut v = Vec::new(); v.push(1); }
+        "#;
+        functions.extend(scan_source("shared/src/other.rs", unrelated).unwrap());
+        let matrix = build_matrix(&functions);
+        assert_eq!(matrix.requirements["REQ-IF-002"].tests.len(), 1);
+        assert_eq!(matrix.requirements["REQ-FN-009"].tests.len(), 1);
+    }
+
+    #[test]
+    fn test_lost_coverage_against_baseline() {
+        let baseline = matrix();
+        assert!(lost_coverage(&baseline, &baseline).is_empty());
+
+        let untested = SOURCE.replace("vec![encode(); 2]", "()");
+        let current = build_matrix(&scan_source("shared/src/queue.rs", &untested).unwrap());
+        assert_eq!(
+            lost_coverage(&baseline, &current),
+            ["REQ-IF-002: no test exercises it"]
+        );
+
+        let removed = SOURCE.replace("#[req(\"REQ-IF-002\")]", "");
+        let current = build_matrix(&scan_source("shared/src/queue.rs", &removed).unwrap());
+        assert_eq!(
+            lost_coverage(&baseline, &current),
+            [
+                "REQ-IF-002: no annotated function implements it",
+                "REQ-IF-002: no test exercises it"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_specification_headings() {
+        let specification = parse_specification(
+            "## 3 Requirements\n#### REQ-FN-001: Priority Classification\n\
+             **Requirement ID:** REQ-FN-001\n#### REQ-NF-002: Memory Constraints\n\
+             #### Not a requirement\n",
+        );
+        assert_eq!(specification.len(), 2);
+        assert_eq!(specification["REQ-NF-002"], "Memory Constraints");
+    }
+
+    #[test]
+    fn test_trace_a_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        let src = root.path().join("shared/src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(root.path().join("target/debug")).unwrap();
+        std::fs::write(src.join("queue.rs"), SOURCE).unwrap();
+        std::fs::write(root.path().join("target/debug/junk.rs"), "not rust").unwrap();
+        let spec = root.path().join("srs.md");
+        std::fs::write(
+            &spec,
+            "#### REQ-FN-001: a\n#### REQ-FN-009: b\n#### REQ-IF-002: c\n#### REQ-SF-001: d\n",
+        )
+        .unwrap();
+        let baseline = root.path().join("baseline.json");
+        std::fs::write(
+            &baseline,
+            r#"{"requirements": {"REQ-FN-009": {"functions": ["x"], "tests": ["y"]}}}"#,
+        )
+        .unwrap();
+
+        let mut args = Args {
+            root: root.path().to_path_buf(),
+            requirements: Some(spec.clone()),
+            baseline: Some(baseline.clone()),
+        };
+        let traced = trace(&args).unwrap();
+        assert!(traced.passes());
+        assert_eq!(traced.untraced, Vec::<String>::new());
+        assert!(traced
+            .report()
+            .starts_with("REQ-FN-001 a\n  fn   shared/src/queue.rs::Queue::new\n"));
+
+        // An ID the specification does not define fails the run
+        std::fs::write(
+            &spec,
+            "#### REQ-FN-001: a\n#### REQ-FN-009: b\n#### REQ-QL-001: e\n",
+        )
+        .unwrap();
+        let traced = trace(&args).unwrap();
+        assert!(!traced.passes());
+        assert_eq!(traced.untraced, ["REQ-QL-001"]);
+        assert_eq!(
+            traced.unknown,
+            [
+                "shared/src/queue.rs::encode cites REQ-IF-002",
+                "shared/src/queue.rs::tests::test_safe_mode cites REQ-SF-001"
+            ]
+        );
+
+        // So does a requirement the baseline covers losing its tests
+        args.requirements = None;
+        std::fs::write(
+            &baseline,
+            r#"{"requirements": {"REQ-NF-004": {"functions": [], "tests": ["y"]}}}"#,
+        )
+        .unwrap();
+        let traced = trace(&args).unwrap();
+        assert_eq!(traced.lost, ["REQ-NF-004: no test exercises it"]);
+        assert!(!traced.passes());
+    }
+}
@@ -0,0 +1,524 @@
+//! End-to-End Pass Demonstration
+//!
+//! Smoke test of the whole stack: a channel simulator, a satellite simulator
+//! and a ground segment exchange CCSDS packets through one scripted LEO pass.
+//!
+//! - **Channel**   — `frequency_band_simulation` link budget per pass sample;
+//!   frames are delivered only while the link closes on the active band
+//! - **Satellite** — decodes uplinked command packets and answers with
+//!   telemetry on APID 0x100, as the flight software does
+//! - **Ground**    — encodes commands in the shared message wire format and
+//!   parses the downlinked telemetry
+//!
+//! The script acquires the satellite on S-band, switches to X-band for a
+//! telemetry dump near culmination, and switches back before LOS. Each step
+//! is checked and narrated; the process exits non-zero if any step fails.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (end-to-end packet exchange)
+//! - REQ-FN-007: Multi-Band Communication (in-pass band switching)
+//! - REQ-FN-008: Frequency Band Simulation (link conditions along the pass)
+
+use std::process::ExitCode;
+
+use frequency_band_simulation::margin::MarginPolicy;
+use frequency_band_simulation::tracking::{generate_pass, PassSample};
+use frequency_band_simulation::{
+    BandType as SimBand, EnvironmentalConditions, FrequencyBand, TransmissionParameters,
+};
+use space_comms_shared::{
+    ccsds::{PacketType, SpacePacket, SpacePacketHeader},
+    messaging::{decode_command_packet, Message, MessagePayload, MessagePriority},
+    types::{BandType, ComponentId, MessageId},
+    Result, SpaceCommError,
+};
+
+/// Telemetry APID used by the satellite downlink.
+const TELEMETRY_APID: u16 = 0x100;
+
+/// Ground station command identifiers.
+const CMD_SYSTEM_STATUS: u32 = 0x1001;
+const CMD_TELEMETRY_REQUEST: u32 = 0x1002;
+const CMD_SWITCH_BAND: u32 = 0x2001;
+
+/// Housekeeping frames returned per telemetry request.
+const TELEMETRY_DUMP_FRAMES: u8 = 5;
+
+/// Band identifier used in the switch-band command parameters.
+fn band_id(band: BandType) -> u8 {
+    match band {
+        BandType::UhfBand => 0,
+        BandType::SBand => 1,
+        BandType::XBand => 2,
+        BandType::KBand => 3,
+        BandType::KaBand => 4,
+    }
+}
+
+fn band_from_id(id: u8) -> Option<BandType> {
+    match id {
+        0 => Some(BandType::UhfBand),
+        1 => Some(BandType::SBand),
+        2 => Some(BandType::XBand),
+        3 => Some(BandType::KBand),
+        4 => Some(BandType::KaBand),
+        _ => None,
+    }
+}
+
+/// Corresponding band in the channel simulator.
+fn sim_band(band: BandType) -> SimBand {
+    match band {
+        BandType::UhfBand => SimBand::UHFBand,
+        BandType::SBand => SimBand::SBand,
+        BandType::XBand => SimBand::XBand,
+        BandType::KBand => SimBand::KBand,
+        BandType::KaBand => SimBand::KaBand,
+    }
+}
+
+/// Payload of a packet: the bytes between the primary header and the CRC.
+fn packet_payload(bytes: &[u8]) -> Result<(SpacePacketHeader, &[u8])> {
+    let header = SpacePacketHeader::from_bytes(bytes)?;
+    if bytes.len() < 8 {
+        return Err(SpaceCommError::invalid_packet("Packet too short", None));
+    }
+    Ok((header, &bytes[6..bytes.len() - 2]))
+}
+
+/// RF channel between the ground station and the satellite.
+struct ChannelSimulator {
+    bands: Vec<FrequencyBand>,
+    environment: EnvironmentalConditions,
+    transmit_power_watts: f64,
+    delivered: u32,
+    dropped: u32,
+}
+
+impl ChannelSimulator {
+    fn new(environment: EnvironmentalConditions) -> Self {
+        Self {
+            bands: FrequencyBand::get_standard_bands(),
+            environment,
+            transmit_power_watts: 20.0,
+            delivered: 0,
+            dropped: 0,
+        }
+    }
+
+    /// SNR at `sample` on `band`, or `None` when the link does not close.
+    fn link_snr_db(&self, band: BandType, sample: &PassSample, frame_len: usize) -> Option<f64> {
+        let band = self.bands.iter().find(|b| b.name == sim_band(band))?;
+        let params = TransmissionParameters {
+            distance_km: sample.range_km,
+            data_size_mb: frame_len as f64 / 1_000_000.0,
+            required_data_rate_mbps: 0.0096,
+            elevation_angle_degrees: sample.pointing.elevation_deg,
+            transmit_power_watts: self.transmit_power_watts,
+            antenna_diameter_meters: 3.0,
+            range_rate_m_s: 0.0,
+            range_acceleration_m_s2: 0.0,
+        };
+        // Frames get through whenever the link closes, margin or not; a
+        // geometry outside the model's range carries nothing
+        let result = band
+            .simulate_transmission_with(&params, &self.environment, &MarginPolicy::closure_only())
+            .ok()?;
+        result.success.then_some(result.signal_to_noise_ratio_db)
+    }
+
+    /// Carry a frame across the link; lost when the link does not close.
+    fn relay(&mut self, band: BandType, sample: &PassSample, frame: Vec<u8>) -> Option<Vec<u8>> {
+        if self.link_snr_db(band, sample, frame.len()).is_some() {
+            self.delivered += 1;
+            Some(frame)
+        } else {
+            self.dropped += 1;
+            None
+        }
+    }
+}
+
+/// Satellite side: executes commands and produces telemetry.
+struct SatelliteSimulator {
+    band: BandType,
+    telemetry_sequence: u16,
+    commands_accepted: u16,
+}
+
+impl SatelliteSimulator {
+    fn new() -> Self {
+        Self {
+            band: BandType::SBand,
+            telemetry_sequence: 0,
+            commands_accepted: 0,
+        }
+    }
+
+    /// Execute an uplinked command packet and return the downlink frames.
+    ///
+    /// Status and band-switch commands return one status frame; a telemetry
+    /// request returns a dump of housekeeping frames. The band switch takes
+    /// effect after the reply, which is sent on the old band.
+    fn handle_uplink(&mut self, frame: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let command = decode_command_packet(frame)?;
+        let parameters = command.parameters.as_slice();
+
+        let mut next_band = self.band;
+        let frames = match command.command_id {
+            CMD_SYSTEM_STATUS => 1,
+            CMD_TELEMETRY_REQUEST => TELEMETRY_DUMP_FRAMES,
+            CMD_SWITCH_BAND => {
+                next_band = parameters
+                    .first()
+                    .and_then(|id| band_from_id(*id))
+                    .ok_or_else(|| SpaceCommError::invalid_packet("Unknown band", None))?;
+                1
+            }
+            _ => return Err(SpaceCommError::invalid_packet("Unknown command", None)),
+        };
+        self.commands_accepted += 1;
+
+        // Report the band that will be in use once the command completes
+        let replies = (0..frames)
+            .map(|_| self.telemetry_frame(next_band))
+            .collect::<Result<Vec<_>>>()?;
+        self.band = next_band;
+        Ok(replies)
+    }
+
+    /// Status frame: band id (u8), accepted command count (u16 BE).
+    fn telemetry_frame(&mut self, band: BandType) -> Result<Vec<u8>> {
+        let mut payload = vec![band_id(band)];
+        payload.extend_from_slice(&self.commands_accepted.to_be_bytes());
+
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            TELEMETRY_APID,
+            self.telemetry_sequence,
+            &payload,
+            None,
+        )?;
+        self.telemetry_sequence = (self.telemetry_sequence + 1) & 0x3FFF;
+        Ok(packet.to_bytes()?.to_vec())
+    }
+}
+
+/// Status reported in a telemetry frame.
+#[derive(Debug, Clone, Copy)]
+struct StatusFrame {
+    sequence: u16,
+    band: BandType,
+    commands_accepted: u16,
+}
+
+/// Ground side: builds command packets and decodes telemetry.
+struct GroundSegment {
+    band: BandType,
+    command_sequence: u16,
+    received: Vec<StatusFrame>,
+}
+
+impl GroundSegment {
+    fn new() -> Self {
+        Self {
+            band: BandType::SBand,
+            command_sequence: 0,
+            received: Vec::new(),
+        }
+    }
+
+    /// Command packet in the shared message wire format.
+    fn command_frame(
+        &mut self,
+        command_id: u32,
+        priority: MessagePriority,
+        parameters: &[u8],
+    ) -> Result<Vec<u8>> {
+        if parameters.len() > 256 {
+            return Err(SpaceCommError::invalid_packet("Parameters too long", None));
+        }
+        self.command_sequence = (self.command_sequence + 1) & 0x3FFF;
+        let message = Message {
+            id: MessageId::from_value(u64::from(self.command_sequence)),
+            priority,
+            source: ComponentId::new(0x0100),
+            destination: ComponentId::new(0x0001),
+            timestamp: 0,
+            payload: MessagePayload::Command {
+                command_id,
+                parameters: parameters.iter().copied().collect(),
+            },
+            preferred_band: self.band,
+            ttl_seconds: 0,
+            retry_count: 0,
+            max_retries: 0,
+        };
+        Ok(message.to_command_packet()?.to_bytes()?.to_vec())
+    }
+
+    fn receive(&mut self, frame: &[u8]) -> Result<StatusFrame> {
+        let (header, payload) = packet_payload(frame)?;
+        if header.apid != TELEMETRY_APID || payload.len() < 3 {
+            return Err(SpaceCommError::invalid_packet("Not a status frame", None));
+        }
+        let status = StatusFrame {
+            sequence: header.sequence_count,
+            band: band_from_id(payload[0])
+                .ok_or_else(|| SpaceCommError::invalid_packet("Unknown band", None))?,
+            commands_accepted: u16::from_be_bytes([payload[1], payload[2]]),
+        };
+        self.received.push(status);
+        Ok(status)
+    }
+}
+
+/// The three simulators wired together for one pass.
+struct PassRunner {
+    channel: ChannelSimulator,
+    satellite: SatelliteSimulator,
+    ground: GroundSegment,
+}
+
+impl PassRunner {
+    /// Uplink a command at `sample` and collect the decoded replies.
+    fn exchange(
+        &mut self,
+        sample: &PassSample,
+        command_id: u32,
+        priority: MessagePriority,
+        parameters: &[u8],
+    ) -> std::result::Result<Vec<StatusFrame>, String> {
+        let band = self.ground.band;
+        let frame = self
+            .ground
+            .command_frame(command_id, priority, parameters)
+            .map_err(|e| format!("command encoding failed: {}", e))?;
+        let frame = self
+            .channel
+            .relay(band, sample, frame)
+            .ok_or_else(|| format!("uplink lost on {:?}", band))?;
+        let replies = self
+            .satellite
+            .handle_uplink(&frame)
+            .map_err(|e| format!("satellite rejected command: {}", e))?;
+
+        let mut statuses = Vec::new();
+        for reply in replies {
+            if let Some(reply) = self.channel.relay(band, sample, reply) {
+                statuses.push(
+                    self.ground
+                        .receive(&reply)
+                        .map_err(|e| format!("telemetry decoding failed: {}", e))?,
+                );
+            }
+        }
+        Ok(statuses)
+    }
+}
+
+/// Narrated outcome of one scripted step.
+struct Step {
+    time_s: f64,
+    name: &'static str,
+    outcome: std::result::Result<String, String>,
+}
+
+/// Run the scripted pass and return the narrated steps.
+///
+/// - **ID**: FN-DEMO-001
+/// - **Requirement**: Exercise channel, satellite and ground together over a
+///   scripted pass (REQ-IF-002, REQ-FN-007).
+/// - **Outputs**: One `Step` per scripted action, in time order. Steps after
+///   a failed acquisition are not attempted.
+/// - **Side Effects**: None.
+fn run_scripted_pass() -> Vec<Step> {
+    let mut steps = Vec::new();
+    let pass = generate_pass(550.0, 60.0, 10.0, 10.0);
+    let mut runner = PassRunner {
+        channel: ChannelSimulator::new(EnvironmentalConditions {
+            rain_rate_mm_hour: 0.0,
+            cloud_cover_percent: 20.0,
+            atmospheric_pressure_mb: 1013.0,
+            temperature_celsius: 15.0,
+            humidity_percent: 50.0,
+            ionospheric_activity: 0.2,
+            solar_activity: 0.2,
+        }),
+        satellite: SatelliteSimulator::new(),
+        ground: GroundSegment::new(),
+    };
+
+    let (Some(first), Some(last)) = (pass.first(), pass.last()) else {
+        steps.push(Step {
+            time_s: 0.0,
+            name: "Pass prediction",
+            outcome: Err("pass never rises above the mask".to_string()),
+        });
+        return steps;
+    };
+    steps.push(Step {
+        time_s: first.time_s,
+        name: "Pass prediction",
+        outcome: Ok(format!(
+            "{} samples, {:.0} s above 10 deg",
+            pass.len(),
+            last.time_s - first.time_s
+        )),
+    });
+
+    // Acquisition: first sample where the S-band link closes
+    let Some(aos) = pass
+        .iter()
+        .position(|s| runner.channel.link_snr_db(BandType::SBand, s, 16).is_some())
+    else {
+        steps.push(Step {
+            time_s: first.time_s,
+            name: "Acquisition",
+            outcome: Err("S-band link never closes".to_string()),
+        });
+        return steps;
+    };
+    let sample = &pass[aos];
+    let outcome = runner
+        .exchange(sample, CMD_SYSTEM_STATUS, MessagePriority::Medium, &[])
+        .and_then(|replies| match replies.first() {
+            Some(status) if status.band == BandType::SBand => Ok(format!(
+                "status received on {:?} at {:.1} deg elevation",
+                status.band, sample.pointing.elevation_deg
+            )),
+            Some(status) => Err(format!("satellite reports {:?}", status.band)),
+            None => Err("no status frame received".to_string()),
+        });
+    let acquired = outcome.is_ok();
+    steps.push(Step {
+        time_s: sample.time_s,
+        name: "Acquisition",
+        outcome,
+    });
+    if !acquired {
+        return steps;
+    }
+
+    // Band switch and dump around culmination, back to S-band before LOS
+    let culmination = pass.len() / 2;
+    let before_los = pass.len().saturating_sub(aos + 1).max(culmination);
+    let script: [(usize, &'static str, BandType); 2] = [
+        (
+            culmination.saturating_sub(1),
+            "Switch to X-band",
+            BandType::XBand,
+        ),
+        (before_los, "Switch to S-band", BandType::SBand),
+    ];
+
+    for (index, (at, name, band)) in script.into_iter().enumerate() {
+        let sample = &pass[at];
+        let outcome = runner
+            .exchange(
+                sample,
+                CMD_SWITCH_BAND,
+                MessagePriority::High,
+                &[band_id(band)],
+            )
+            .and_then(|replies| match replies.first() {
+                Some(status) if status.band == band => {
+                    runner.ground.band = band;
+                    Ok(format!("satellite confirms {:?}", band))
+                }
+                Some(status) => Err(format!("satellite reports {:?}", status.band)),
+                None => Err("no confirmation received".to_string()),
+            });
+        let switched = outcome.is_ok();
+        steps.push(Step {
+            time_s: sample.time_s,
+            name,
+            outcome,
+        });
+        if !switched {
+            return steps;
+        }
+
+        if index == 0 {
+            let sample = &pass[culmination];
+            let outcome = runner
+                .exchange(sample, CMD_TELEMETRY_REQUEST, MessagePriority::Low, &[])
+                .and_then(|replies| {
+                    let in_order = replies
+                        .windows(2)
+                        .all(|w| w[1].sequence == (w[0].sequence + 1) & 0x3FFF);
+                    if replies.len() == usize::from(TELEMETRY_DUMP_FRAMES) && in_order {
+                        Ok(format!(
+                            "{} frames on {:?} at {:.1} deg elevation",
+                            replies.len(),
+                            runner.ground.band,
+                            sample.pointing.elevation_deg
+                        ))
+                    } else {
+                        Err(format!(
+                            "{} of {} frames received",
+                            replies.len(),
+                            TELEMETRY_DUMP_FRAMES
+                        ))
+                    }
+                });
+            steps.push(Step {
+                time_s: sample.time_s,
+                name: "Telemetry dump",
+                outcome,
+            });
+        }
+    }
+
+    // Counters on the last frame must match what the ground sent
+    let outcome = match runner.ground.received.last() {
+        Some(status) if status.commands_accepted == runner.ground.command_sequence => Ok(format!(
+            "{} commands accepted, {} frames delivered, {} dropped",
+            status.commands_accepted, runner.channel.delivered, runner.channel.dropped
+        )),
+        Some(status) => Err(format!(
+            "satellite accepted {} of {} commands",
+            status.commands_accepted, runner.ground.command_sequence
+        )),
+        None => Err("no telemetry received".to_string()),
+    };
+    steps.push(Step {
+        time_s: last.time_s,
+        name: "LOS accounting",
+        outcome,
+    });
+
+    steps
+}
+
+fn main() -> ExitCode {
+    println!("===== END-TO-END PASS DEMONSTRATION =====");
+    println!("Channel simulator + satellite simulator + ground segment\n");
+
+    let steps = run_scripted_pass();
+    let mut failures = 0;
+    for step in &steps {
+        match &step.outcome {
+            Ok(detail) => println!(
+                "[T+{:>5.0}s] PASS  {:<18} {}",
+                step.time_s, step.name, detail
+            ),
+            Err(detail) => {
+                failures += 1;
+                println!(
+                    "[T+{:>5.0}s] FAIL  {:<18} {}",
+                    step.time_s, step.name, detail
+                );
+            }
+        }
+    }
+
+    println!();
+    if failures == 0 {
+        println!("All {} steps passed", steps.len());
+        ExitCode::SUCCESS
+    } else {
+        println!("{} of {} steps failed", failures, steps.len());
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,527 @@
+//! HTTP API for mission control integration
+//!
+//! External dashboards and automation drive the station over HTTP instead of
+//! the interactive console. The API is JSON throughout:
+//!
+//! | Method | Path              | Purpose                                        |
+//! |--------|-------------------|------------------------------------------------|
+//! | POST   | `/commands`       | Send a dictionary command by name              |
+//! | GET    | `/telemetry`      | Query stored telemetry                         |
+//! | GET    | `/telemetry/live` | Server-sent events, one per received packet    |
+//! | GET    | `/link`           | Link status, configuration and statistics      |
+//!
+//! A command is sent as `{"command": "SendStatus", "parameters":
+//! {"status_type": "PowerStatus", "include_diagnostics": true, "format":
+//! "Json"}}`; parameters are checked against the [`dictionary`] exactly as
+//! the console checks operator input, and may be given as JSON strings,
+//! numbers or booleans. The command goes through
+//! [`GroundStation::send_command`], so dry-run mode, command authority and
+//! the audit log apply as they do to the console.
+//!
+//! `/telemetry` takes the same selectors as the console `archive` command as
+//! query parameters: `from` and `until` (reception time, ms since the Unix
+//! epoch), `apid` and `id` (measurement ID).
+//!
+//! # Backpressure
+//! Live telemetry reaches the API through one
+//! [`GroundStation::subscribe_telemetry`] queue of [`LIVE_QUEUE_DEPTH`]
+//! packets and is fanned out to the event stream clients from there. A
+//! client that falls behind skips the packets it missed; the telemetry
+//! receiver never waits on the API.
+//!
+//! # Requirements Traceability
+//! - FN-API-001: Dictionary commands sent over HTTP
+//! - FN-API-002: Stored telemetry queried over HTTP
+//! - FN-API-003: Link status reported over HTTP
+//! - FN-API-004: Live telemetry streamed as server-sent events
+//! - REQ-NF-003: System Availability (operations path never waits on a client)
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::Arc;
+use std::thread;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use space_comms_shared::{
+    link_config::LinkConfiguration, link_forecast::DownlinkPolicyStats, link_stats::LinkStats,
+    telemetry::TelemetryPacket, Result, SpaceCommError,
+};
+
+use crate::dictionary;
+use crate::telemetry_archive::{StoredTelemetry, TelemetryQuery};
+use crate::{Command, GroundStation};
+
+/// Received packets queued for the API before further packets are dropped
+pub const LIVE_QUEUE_DEPTH: usize = 1024;
+
+/// Packets an event stream client may fall behind by before it skips ahead
+pub const LIVE_CLIENT_DEPTH: usize = 256;
+
+/// Worker threads of the API's runtime
+const API_WORKER_THREADS: usize = 2;
+
+/// Body of a `POST /commands` request
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandRequest {
+    /// Dictionary command name, matched without regard to case
+    pub command: String,
+    /// Parameter values by name
+    #[serde(default)]
+    pub parameters: HashMap<String, Value>,
+}
+
+/// A stored or live telemetry packet as served by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryRecord {
+    /// Reception time, milliseconds since the Unix epoch
+    pub received_ms: u64,
+    /// APID the packet was downlinked on
+    pub apid: u16,
+    /// Decoded packet
+    pub packet: TelemetryPacket,
+}
+
+impl From<StoredTelemetry> for TelemetryRecord {
+    fn from(record: StoredTelemetry) -> Self {
+        Self {
+            received_ms: record.received_ms,
+            apid: record.apid,
+            packet: record.packet,
+        }
+    }
+}
+
+/// Selectors of a `GET /telemetry` request
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct TelemetryParams {
+    /// Earliest reception time, ms since the Unix epoch, inclusive
+    pub from: Option<u64>,
+    /// Latest reception time, ms since the Unix epoch, exclusive
+    pub until: Option<u64>,
+    /// APID the packet was downlinked on
+    pub apid: Option<u16>,
+    /// Measurement the packet must carry
+    pub id: Option<u16>,
+}
+
+impl From<TelemetryParams> for TelemetryQuery {
+    fn from(params: TelemetryParams) -> Self {
+        Self {
+            from_ms: params.from,
+            until_ms: params.until,
+            apid: params.apid,
+            measurement_id: params.id,
+        }
+    }
+}
+
+/// Body of a `GET /link` response
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkStatus {
+    /// Whether telemetry has been received from the satellite
+    pub connected: bool,
+    /// Whether this station may uplink
+    pub command_authority: bool,
+    /// Uplink and downlink settings
+    pub links: LinkConfiguration,
+    /// Link statistics as seen by the station
+    pub station: LinkStats,
+    /// Link statistics as last downlinked by the satellite
+    pub satellite: Option<LinkStats>,
+    /// Recorder downlink policy as last downlinked by the satellite
+    pub downlink_policy: Option<DownlinkPolicyStats>,
+}
+
+/// Station and live feed shared by the request handlers
+#[derive(Clone)]
+struct ApiState {
+    station: Arc<GroundStation>,
+    live: broadcast::Sender<Arc<String>>,
+}
+
+/// JSON error response naming the problem
+fn error_response(status: StatusCode, message: impl ToString) -> Response {
+    (status, Json(json!({ "error": message.to_string() }))).into_response()
+}
+
+/// Build the API's routes over a station
+///
+/// - **ID**: FN-API-001..004
+/// - **Requirement**: External dashboards and automation send commands,
+///   query telemetry, read the link status and follow live telemetry.
+/// - **Inputs**: The station the API drives.
+/// - **Outputs**: Router ready to be served, or nested in another router.
+/// - **Side Effects**: Subscribes to the station's live telemetry and spawns
+///   the thread fanning it out to event stream clients.
+pub fn router(station: Arc<GroundStation>) -> Router {
+    let (live, _) = broadcast::channel(LIVE_CLIENT_DEPTH);
+    let feed = station.subscribe_telemetry(LIVE_QUEUE_DEPTH);
+    let fan_out = live.clone();
+    thread::spawn(move || {
+        for record in feed {
+            match serde_json::to_string(&TelemetryRecord::from(record)) {
+                // No client connected is not an error: the packet is dropped
+                Ok(json) => drop(fan_out.send(Arc::new(json))),
+                Err(e) => eprintln!("HTTP API: telemetry not encoded: {}", e),
+            }
+        }
+    });
+
+    Router::new()
+        .route("/commands", post(send_command))
+        .route("/telemetry", get(query_telemetry))
+        .route("/telemetry/live", get(live_telemetry))
+        .route("/link", get(link_status))
+        .with_state(ApiState { station, live })
+}
+
+/// Serve the API on its own thread
+///
+/// - **ID**: FN-API-001..004
+/// - **Requirement**: The API runs beside the console without holding up
+///   station operations.
+/// - **Inputs**: The station; the address to listen on, port 0 picking a
+///   free port.
+/// - **Outputs**: The address the API listens on.
+/// - **Side Effects**: Binds a TCP listener and spawns the server thread
+///   with its runtime.
+/// - **Failure Modes**: Listener cannot be bound or configured, or the
+///   runtime cannot be built → `Err`.
+pub fn start(station: Arc<GroundStation>, addr: SocketAddr) -> Result<SocketAddr> {
+    let failed = |e: std::io::Error| {
+        SpaceCommError::communication_timeout(1000, &format!("HTTP API listener: {}", e))
+    };
+    let listener = TcpListener::bind(addr).map_err(failed)?;
+    let local_addr = listener.local_addr().map_err(failed)?;
+    listener.set_nonblocking(true).map_err(failed)?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(API_WORKER_THREADS)
+        .thread_name("http-api")
+        .enable_all()
+        .build()
+        .map_err(failed)?;
+
+    let app = router(station);
+    thread::spawn(move || {
+        runtime.block_on(async move {
+            let served = match tokio::net::TcpListener::from_std(listener) {
+                Ok(listener) => axum::serve(listener, app).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = served {
+                eprintln!("HTTP API stopped: {}", e);
+            }
+        });
+    });
+    Ok(local_addr)
+}
+
+/// `POST /commands`: check, build and send a dictionary command
+///
+/// Answers 202 once uplinked, 404 for a command not in the dictionary, 400
+/// for parameters that are missing, unknown or out of range, and 503 when
+/// the station cannot uplink it.
+async fn send_command(
+    State(state): State<ApiState>,
+    Json(request): Json<CommandRequest>,
+) -> Response {
+    let Some(spec) = dictionary::lookup(&request.command) else {
+        let message = format!("{} is not a dictionary command", request.command);
+        return error_response(StatusCode::NOT_FOUND, message);
+    };
+    if let Some(unknown) = request
+        .parameters
+        .keys()
+        .find(|name| spec.parameters.iter().all(|p| p.name != name.as_str()))
+    {
+        let message = format!("{} has no parameter {}", spec.name, unknown);
+        return error_response(StatusCode::BAD_REQUEST, message);
+    }
+
+    let mut values = Vec::with_capacity(spec.parameters.len());
+    for parameter in spec.parameters {
+        let text = match request.parameters.get(parameter.name) {
+            Some(Value::String(text)) => text.clone(),
+            Some(value) => value.to_string(),
+            None => {
+                let message = format!(
+                    "missing parameter {} ({})",
+                    parameter.name,
+                    parameter.hint()
+                );
+                return error_response(StatusCode::BAD_REQUEST, message);
+            }
+        };
+        match parameter.parse(&text) {
+            Ok(value) => values.push(value),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        }
+    }
+
+    let command = match spec
+        .build(&values)
+        .and_then(|command| Command::from_space_command(&command))
+    {
+        Ok(command) => command,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let command_id = command.command_id;
+    match state.station.send_command(command) {
+        Ok(()) => (
+            StatusCode::ACCEPTED,
+            Json(json!({ "command": spec.name, "command_id": command_id })),
+        )
+            .into_response(),
+        Err(e) => error_response(StatusCode::SERVICE_UNAVAILABLE, e),
+    }
+}
+
+/// `GET /telemetry`: stored packets selected by the query parameters
+async fn query_telemetry(
+    State(state): State<ApiState>,
+    Query(params): Query<TelemetryParams>,
+) -> Json<Vec<TelemetryRecord>> {
+    let records = state.station.query_telemetry(&params.into());
+    Json(records.into_iter().map(TelemetryRecord::from).collect())
+}
+
+/// `GET /link`: link status, configuration and statistics
+async fn link_status(State(state): State<ApiState>) -> Json<LinkStatus> {
+    let station = &state.station;
+    Json(LinkStatus {
+        connected: station.is_connected_to_satellite(),
+        command_authority: station.has_command_authority(),
+        links: station.link_configuration(),
+        station: station.link_stats(),
+        satellite: station.satellite_link_stats(),
+        downlink_policy: station.downlink_policy_stats(),
+    })
+}
+
+/// `GET /telemetry/live`: one `telemetry` event per packet received from now
+async fn live_telemetry(
+    State(state): State<ApiState>,
+) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    let events = stream::unfold(state.live.subscribe(), |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(json) => {
+                    let event = Event::default().event("telemetry").data(json.as_str());
+                    return Some((Ok(event), receiver));
+                }
+                // FN-API-004: A slow client skips ahead rather than stalling
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seams::{MockClock, MockTelemetryStore, MockTransport};
+    use crate::{GroundStationConfig, StationSeams, SATELLITE_COMPONENT};
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket},
+        telemetry::{
+            measurement_ids, Measurement, MeasurementQuality, MeasurementValue, TelemetryData,
+        },
+        types::HealthStatus,
+    };
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    struct MockApi {
+        addr: SocketAddr,
+        station: Arc<GroundStation>,
+        telemetry: Arc<MockTransport>,
+        command: Arc<MockTransport>,
+    }
+
+    fn mock_api() -> MockApi {
+        let local = |port| SocketAddr::from(([127, 0, 0, 1], port));
+        let telemetry = Arc::new(MockTransport::new(local(8001)));
+        let command = Arc::new(MockTransport::new(local(8002)));
+        let station = GroundStation::with_seams(
+            GroundStationConfig::default(),
+            StationSeams {
+                telemetry: telemetry.clone(),
+                command: command.clone(),
+                emergency: Arc::new(MockTransport::new(local(8003))),
+                clock: Arc::new(MockClock::new(10_000)),
+                store: Box::new(MockTelemetryStore::new()),
+            },
+        )
+        .unwrap();
+        let station = Arc::new(station);
+        let addr = start(Arc::clone(&station), local(0)).unwrap();
+        MockApi {
+            addr,
+            station,
+            telemetry,
+            command,
+        }
+    }
+
+    fn housekeeping_bytes(sequence: u16) -> Vec<u8> {
+        let mut data = TelemetryData {
+            source: SATELLITE_COMPONENT,
+            timestamp: 0,
+            measurements: Default::default(),
+            health_status: HealthStatus::Good,
+        };
+        data.measurements
+            .push(Measurement {
+                measurement_id: measurement_ids::BATTERY_VOLTAGE,
+                value: MeasurementValue::Float(7.4),
+                unit: "V",
+                quality: MeasurementQuality::Good,
+            })
+            .unwrap();
+        SpacePacket::new(
+            PacketType::Telemetry,
+            0x100,
+            sequence,
+            &data.to_payload().unwrap(),
+            None,
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+        .to_vec()
+    }
+
+    /// Receive one delivered packet through the station's pipeline
+    fn receive(mock: &MockApi, sequence: u16) {
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        mock.telemetry
+            .deliver(&housekeeping_bytes(sequence), satellite);
+        assert!(mock.station.telemetry_receiver().unwrap().poll());
+    }
+
+    fn connect(addr: SocketAddr, method: &str, path: &str, body: &str) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        stream
+    }
+
+    /// Status code and JSON body of a complete request
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut response = String::new();
+        connect(addr, method, path, body)
+            .read_to_string(&mut response)
+            .unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+
+    /// Read from the stream until `text` has arrived
+    fn read_until(stream: &mut TcpStream, text: &str) -> String {
+        let mut received = String::new();
+        let mut buffer = [0u8; 1024];
+        while !received.contains(text) {
+            let size = stream.read(&mut buffer).unwrap();
+            assert!(size > 0, "stream closed before {:?}", text);
+            received.push_str(&String::from_utf8_lossy(&buffer[..size]));
+        }
+        received
+    }
+
+    #[test]
+    fn test_commands_checked_against_the_dictionary_and_sent() {
+        let mock = mock_api();
+
+        let body = r#"{"command": "sendstatus", "parameters":
+            {"status_type": "PowerStatus", "include_diagnostics": true, "format": "Json"}}"#;
+        let (status, reply) = request(mock.addr, "POST", "/commands", body);
+        assert_eq!(status, 202);
+        assert_eq!(reply["command"], "SendStatus");
+        assert_eq!(mock.command.sent().len(), 1);
+
+        // Nothing is sent for a request the dictionary refuses
+        let (status, _) = request(mock.addr, "POST", "/commands", r#"{"command": "Launch"}"#);
+        assert_eq!(status, 404);
+        let missing = r#"{"command": "SendStatus", "parameters": {"status_type": "PowerStatus"}}"#;
+        let (status, reply) = request(mock.addr, "POST", "/commands", missing);
+        assert_eq!(status, 400);
+        assert!(reply["error"]
+            .as_str()
+            .unwrap()
+            .contains("include_diagnostics"));
+        let unknown = body.replace("\"format\"", "\"fmt\"");
+        assert_eq!(request(mock.addr, "POST", "/commands", &unknown).0, 400);
+        assert_eq!(mock.command.sent().len(), 1);
+    }
+
+    #[test]
+    fn test_telemetry_and_link_status_served_as_json() {
+        let mock = mock_api();
+        let (_, link) = request(mock.addr, "GET", "/link", "");
+        assert_eq!(link["connected"], false);
+        assert_eq!(link["command_authority"], true);
+        assert!(link["satellite"].is_null());
+
+        receive(&mock, 1);
+        receive(&mock, 2);
+        let (status, records) = request(mock.addr, "GET", "/telemetry?apid=256", "");
+        assert_eq!(status, 200);
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["received_ms"], 10_000);
+        assert_eq!(records[1]["packet"]["sequence"], 2);
+        let (_, none) = request(mock.addr, "GET", "/telemetry?from=10001", "");
+        assert!(none.as_array().unwrap().is_empty());
+
+        let (_, link) = request(mock.addr, "GET", "/link", "");
+        assert_eq!(link["connected"], true);
+        assert_eq!(link["station"]["frames_received"], 2);
+    }
+
+    #[test]
+    fn test_live_telemetry_streamed_as_events() {
+        let mock = mock_api();
+
+        // Received before the client subscribed: not streamed
+        receive(&mock, 1);
+        let mut stream = connect(mock.addr, "GET", "/telemetry/live", "");
+        let head = read_until(&mut stream, "\r\n\r\n");
+        assert!(head.contains("text/event-stream"));
+
+        receive(&mock, 2);
+        let event = read_until(&mut stream, "\n\n");
+        assert!(event.contains("event: telemetry"));
+        let data = event
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .unwrap();
+        let record: Value = serde_json::from_str(data).unwrap();
+        assert_eq!(record["packet"]["sequence"], 2);
+    }
+}
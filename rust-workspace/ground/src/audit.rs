@@ -0,0 +1,374 @@
+//! Command audit log
+//!
+//! Every command the ground station sends is recorded with the operator on
+//! console, the send time, its parameters and the uplink outcome. When the
+//! command's execution report arrives, its verification outcome is recorded
+//! against the same command. Records are only ever appended: the log is a
+//! JSON Lines file with one self-contained record per line, written and
+//! synced as each record is made, and read back when the log is reopened so
+//! the history survives a ground station restart. The log exports as CSV or
+//! JSON for audit.
+//!
+//! Like the verification archive, the log does not read the clock: the caller
+//! passes record times in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-AUD-001: Append-only persistence of every sent command
+//! - FN-AUD-002: Audit export as CSV and JSON
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use space_comms_shared::{
+    execution_report::{ExecutionReport, ExecutionResult},
+    messaging::MessagePriority,
+    Result, SpaceCommError,
+};
+
+use crate::Command;
+
+/// CSV header written by [`AuditLog::to_csv`]
+pub const CSV_HEADER: &str =
+    "timestamp_unix_ms,operator,command_id,sequence_count,priority,event,parameters";
+
+/// What happened to a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditEvent {
+    /// Uplinked to the satellite
+    Sent,
+    /// Uplink failed after all retries
+    UplinkFailed,
+    /// Validated and encoded in dry-run mode, not uplinked
+    DryRun,
+    /// Execution report: command ran to completion
+    Completed,
+    /// Execution report: command ran but failed
+    Failed,
+    /// Execution report: command refused before execution
+    Rejected,
+}
+
+impl From<ExecutionResult> for AuditEvent {
+    fn from(result: ExecutionResult) -> Self {
+        match result {
+            ExecutionResult::Completed => AuditEvent::Completed,
+            ExecutionResult::Failed => AuditEvent::Failed,
+            ExecutionResult::Rejected => AuditEvent::Rejected,
+        }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Record time, milliseconds since the Unix epoch
+    pub timestamp_unix_ms: u64,
+    /// Operator who sent the command
+    pub operator: String,
+    /// Command identifier as uplinked
+    pub command_id: u32,
+    /// Sequence count of the uplinked command packet
+    pub sequence_count: u16,
+    /// Priority the command was sent at
+    pub priority: MessagePriority,
+    /// Command parameters as uplinked
+    pub parameters: Vec<u8>,
+    /// Uplink or verification outcome
+    pub event: AuditEvent,
+}
+
+/// Append-only log of sent commands and their verification outcomes
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    operator: String,
+    records: Vec<AuditRecord>,
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Create a log kept in memory only
+    pub fn new(operator: &str) -> Self {
+        Self {
+            operator: operator.to_string(),
+            records: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Open the log file at `path`, reading back the records already in it
+    ///
+    /// The file is created on the first record if it does not exist.
+    ///
+    /// # Arguments
+    /// * `path` - JSON Lines audit file
+    /// * `operator` - Operator recorded against commands sent from now on
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Log with its history, or configuration error if the
+    ///   file cannot be read or holds a malformed record
+    pub fn open(path: impl AsRef<Path>, operator: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let records = match std::fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|_| SpaceCommError::ConfigurationError {
+                        parameter: "audit_log_path",
+                        value: "<malformed record>",
+                        reason: "audit log contains a record that could not be parsed",
+                    })
+                })
+                .collect::<Result<Vec<AuditRecord>>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(_) => {
+                return Err(SpaceCommError::ConfigurationError {
+                    parameter: "audit_log_path",
+                    value: "<unreadable>",
+                    reason: "audit log file could not be read",
+                })
+            }
+        };
+
+        Ok(Self {
+            operator: operator.to_string(),
+            records,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Operator recorded against commands sent from now on
+    pub fn operator(&self) -> &str {
+        &self.operator
+    }
+
+    /// Hand the console over to another operator
+    pub fn set_operator(&mut self, operator: &str) {
+        self.operator = operator.to_string();
+    }
+
+    /// Record the uplink outcome of a command sent by the current operator
+    ///
+    /// # Requirements Traceability
+    /// - FN-AUD-001: The record is on disk before this returns
+    pub fn record_command(
+        &mut self,
+        command: &Command,
+        sequence_count: u16,
+        event: AuditEvent,
+        timestamp_unix_ms: u64,
+    ) -> Result<()> {
+        let record = AuditRecord {
+            timestamp_unix_ms,
+            operator: self.operator.clone(),
+            command_id: command.command_id,
+            sequence_count,
+            priority: command.priority,
+            parameters: command.parameters.clone(),
+            event,
+        };
+        self.append(record)
+    }
+
+    /// Record the verification outcome carried by an execution report
+    ///
+    /// The record names the operator and parameters of the matching sent
+    /// command, or the current operator if the command was not sent from
+    /// this log.
+    pub fn record_execution(
+        &mut self,
+        report: &ExecutionReport,
+        received_unix_ms: u64,
+    ) -> Result<()> {
+        let sent = self.records.iter().rev().find(|record| {
+            record.event == AuditEvent::Sent
+                && record.command_id == report.command_id
+                && record.sequence_count == report.sequence_count
+        });
+        let (operator, parameters) = match sent {
+            Some(sent) => (sent.operator.clone(), sent.parameters.clone()),
+            None => (self.operator.clone(), Vec::new()),
+        };
+
+        let record = AuditRecord {
+            timestamp_unix_ms: received_unix_ms,
+            operator,
+            command_id: report.command_id,
+            sequence_count: report.sequence_count,
+            priority: report.priority,
+            parameters,
+            event: report.result.into(),
+        };
+        self.append(record)
+    }
+
+    /// Records in the order they were made
+    pub fn records(&self) -> &[AuditRecord] {
+        &self.records
+    }
+
+    /// Export the log as CSV with [`CSV_HEADER`], parameters in hex
+    ///
+    /// # Requirements Traceability
+    /// - FN-AUD-002: One row per record
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(CSV_HEADER);
+        out.push('\n');
+        for record in &self.records {
+            let parameters: String = record
+                .parameters
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect();
+            out.push_str(&format!(
+                "{},{},0x{:04X},{},{:?},{:?},{}\n",
+                record.timestamp_unix_ms,
+                csv_field(&record.operator),
+                record.command_id,
+                record.sequence_count,
+                record.priority,
+                record.event,
+                parameters
+            ));
+        }
+        out
+    }
+
+    /// Export the log as a JSON array of records
+    ///
+    /// # Requirements Traceability
+    /// - FN-AUD-002: Same records as the log file, as one document
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.records).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Write a record to the file, then keep it in memory
+    fn append(&mut self, record: AuditRecord) -> Result<()> {
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(&record)
+                .map_err(std::io::Error::other)
+                .and_then(|line| {
+                    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                    writeln!(file, "{}", line)?;
+                    file.sync_data()
+                });
+            if written.is_err() {
+                return Err(SpaceCommError::ConfigurationError {
+                    parameter: "audit_log_path",
+                    value: "<unwritable>",
+                    reason: "audit record could not be written",
+                });
+            }
+        }
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+/// Quote a CSV field that contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(command_id: u32, sequence_count: u16, result: ExecutionResult) -> ExecutionReport {
+        ExecutionReport::new(
+            command_id,
+            sequence_count,
+            MessagePriority::Medium,
+            result,
+            250,
+        )
+    }
+
+    #[test]
+    fn test_log_persists_across_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        let mut log = AuditLog::open(&path, "alice").unwrap();
+        log.record_command(
+            &Command::system_status_request(),
+            1,
+            AuditEvent::Sent,
+            1_000,
+        )
+        .unwrap();
+        log.record_command(
+            &Command::telemetry_request(),
+            2,
+            AuditEvent::UplinkFailed,
+            2_000,
+        )
+        .unwrap();
+
+        let mut reopened = AuditLog::open(&path, "bob").unwrap();
+        assert_eq!(reopened.records(), log.records());
+        reopened
+            .record_execution(&report(0x1001, 1, ExecutionResult::Completed), 3_000)
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 3);
+        // The verification names the operator who sent the command
+        let verified = AuditLog::open(&path, "carol").unwrap().records()[2].clone();
+        assert_eq!(verified.operator, "alice");
+        assert_eq!(verified.event, AuditEvent::Completed);
+
+        std::fs::write(&path, "{not json}\n").unwrap();
+        assert!(AuditLog::open(&path, "alice").is_err());
+    }
+
+    #[test]
+    fn test_execution_matches_sent_command() {
+        let mut log = AuditLog::new("alice");
+        let command = Command::new(0x2001, MessagePriority::High, vec![0xAB, 0x01]);
+        log.record_command(&command, 7, AuditEvent::Sent, 10)
+            .unwrap();
+        log.set_operator("bob");
+
+        log.record_execution(&report(0x2001, 7, ExecutionResult::Rejected), 20)
+            .unwrap();
+        log.record_execution(&report(0x3001, 8, ExecutionResult::Failed), 30)
+            .unwrap();
+
+        let records = log.records();
+        assert_eq!(
+            (
+                records[1].operator.as_str(),
+                records[1].parameters.as_slice()
+            ),
+            ("alice", &[0xAB, 0x01][..])
+        );
+        assert_eq!(records[1].event, AuditEvent::Rejected);
+        // No matching send: current operator, no parameters
+        assert_eq!(records[2].operator, "bob");
+        assert!(records[2].parameters.is_empty());
+    }
+
+    #[test]
+    fn test_csv_and_json_export() {
+        let mut log = AuditLog::new("Smith, J.");
+        let command = Command::new(0x2001, MessagePriority::High, vec![0xAB, 0x01]);
+        log.record_command(&command, 7, AuditEvent::Sent, 1_000)
+            .unwrap();
+
+        let csv = log.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "1000,\"Smith, J.\",0x2001,7,High,Sent,AB01");
+        assert_eq!(lines.len(), 2);
+
+        let exported: Vec<AuditRecord> = serde_json::from_str(&log.to_json()).unwrap();
+        assert_eq!(exported, log.records());
+    }
+}
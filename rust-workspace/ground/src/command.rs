@@ -0,0 +1,345 @@
+//! Ground commands and the files they are uplinked from
+//!
+//! A [`Command`] is a command ID, a priority and its encoded parameters; the
+//! constructors build the shared [`SpaceCommand`] set so the satellite
+//! decodes exactly what the station encodes. Command loads, link capacity
+//! forecasts and onboard autonomy rules are read from JSON files here before
+//! the station uplinks them.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-001: Priority Classification (command priority handling)
+//! - REQ-IF-002: CCSDS Compliance (command packet creation)
+
+use std::collections::HashMap;
+
+use space_comms_req::req;
+use space_comms_shared::{
+    autonomy::AutonomyRule,
+    ccsds::SpacePacket,
+    command_load::CommandLoad,
+    commands::SpaceCommand,
+    link_forecast::LinkForecast,
+    messaging::{Message, MessagePayload, MessagePriority},
+    security::VcSecurityPolicy,
+    sequence::next_sequence_count,
+    types::{BandType, MessageId},
+    Result, SpaceCommError,
+};
+
+use crate::{GROUND_STATION_COMPONENT, SATELLITE_COMPONENT};
+
+/// Capacity of the shared command message parameter buffer
+const MAX_COMMAND_PARAMETERS: usize = 256;
+
+/// Command structure for satellite operations
+///
+/// Represents a command to be sent to the satellite with proper priority
+/// classification and parameter encoding.
+/// REQ-FN-001: Priority Classification - Command priority support
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// Unique command identifier for command type recognition
+    pub command_id: u32,
+
+    /// Command priority level for processing order
+    /// REQ-FN-001: Priority Classification - Five-tier priority system
+    pub priority: MessagePriority,
+
+    /// Command-specific parameters as binary data
+    /// Allows flexible parameter encoding for different command types
+    pub parameters: Vec<u8>,
+}
+
+impl Command {
+    /// Create new command with specified parameters
+    ///
+    /// # Arguments
+    /// * `command_id` - Unique identifier for the command type
+    /// * `priority` - Processing priority level (REQ-FN-001)
+    /// * `parameters` - Binary encoded command parameters
+    pub fn new(command_id: u32, priority: MessagePriority, parameters: Vec<u8>) -> Self {
+        Self {
+            command_id,
+            priority,
+            parameters,
+        }
+    }
+
+    /// Create system status request command
+    /// REQ-FN-005: Medium Priority Commands - Status and telemetry requests
+    #[req("REQ-FN-005")]
+    pub fn system_status_request() -> Self {
+        Self::new(0x1001, MessagePriority::Medium, vec![])
+    }
+
+    /// Create telemetry request command
+    /// REQ-FN-006: Low Priority Commands - Routine telemetry collection
+    #[req("REQ-FN-006")]
+    pub fn telemetry_request() -> Self {
+        Self::new(0x1002, MessagePriority::Low, vec![])
+    }
+
+    /// Create emergency stop command
+    /// REQ-FN-002: Emergency Command Set - Immediate termination of operations
+    #[req("REQ-FN-002")]
+    pub fn emergency_stop() -> Self {
+        Self::new(0x9999, MessagePriority::Emergency, vec![0x01])
+    }
+
+    /// Create frequency band switch command
+    /// REQ-FN-007: Multi-Band Communication - Dynamic band selection
+    /// REQ-FN-004: High Priority Commands - Communication configuration
+    #[req("REQ-FN-004", "REQ-FN-007")]
+    pub fn switch_band(band: BandType) -> Self {
+        // Map BandType to numeric identifier for transmission
+        let band_id = match band {
+            BandType::UhfBand => 0, // 0.3-3 GHz: Most reliable
+            BandType::SBand => 1,   // 2-4 GHz: All-weather
+            BandType::XBand => 2,   // 8-12 GHz: Balanced performance
+            BandType::KBand => 3,   // 20-30 GHz: High data rate
+            BandType::KaBand => 4,  // 26.5-40 GHz: Maximum data rate
+        };
+        Self::new(0x2001, MessagePriority::High, vec![band_id])
+    }
+
+    /// Wrap the command in a shared [`Message`] addressed to the satellite
+    ///
+    /// Commands are routed on S-band TT&C and do not expire; transmission
+    /// retries are handled by the uplink retry policy, so the message carries
+    /// no retry budget of its own.
+    ///
+    /// # Arguments
+    /// * `id` - Message ID; its low 14 bits become the packet sequence count
+    /// * `timestamp` - Creation time, nanoseconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Result<Message>` - Command message or error if the parameters exceed
+    ///   the 256-byte command parameter limit
+    pub fn to_message(&self, id: MessageId, timestamp: u64) -> Result<Message> {
+        if self.parameters.len() > MAX_COMMAND_PARAMETERS {
+            return Err(SpaceCommError::invalid_packet(
+                "Command parameters exceed 256 bytes",
+                None,
+            ));
+        }
+
+        Ok(Message {
+            id,
+            priority: self.priority,
+            source: GROUND_STATION_COMPONENT,
+            destination: SATELLITE_COMPONENT,
+            timestamp,
+            payload: MessagePayload::Command {
+                command_id: self.command_id,
+                parameters: self.parameters.iter().copied().collect(),
+            },
+            preferred_band: BandType::SBand,
+            ttl_seconds: 0,
+            retry_count: 0,
+            max_retries: 0,
+        })
+    }
+
+    /// Create virtual channel security policy command
+    /// REQ-FN-004: High Priority Commands - Communication configuration
+    /// REQ-SC-001: Per-virtual-channel link security policy
+    #[req("REQ-FN-004", "REQ-SC-001")]
+    pub fn set_vc_security_policy(virtual_channel: u8, policy: VcSecurityPolicy) -> Self {
+        Self::new(
+            0x0025,
+            MessagePriority::High,
+            vec![virtual_channel, policy.service as u8, policy.key_id],
+        )
+    }
+
+    /// Create a command from its shared definition
+    ///
+    /// Encoded as the shared command builder does: the command discriminant
+    /// as ID, the command's own priority, and the JSON-serialized command as
+    /// parameters.
+    ///
+    /// # Arguments
+    /// * `command` - Command built from the dictionary or a load file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Command, or error if it cannot be serialized
+    pub fn from_space_command(command: &SpaceCommand) -> Result<Self> {
+        let parameters = serde_json::to_vec(command).map_err(|_| {
+            SpaceCommError::invalid_packet(
+                "Command could not be serialized",
+                Some(command.discriminant()),
+            )
+        })?;
+        Ok(Self::new(
+            command.discriminant(),
+            command.priority(),
+            parameters,
+        ))
+    }
+}
+
+/// Load a command load file from disk
+///
+/// The file holds a JSON-encoded [`CommandLoad`]: a `load_id` and a list of
+/// entries, each with a `sequence`, an optional `release_time` in Unix seconds,
+/// and the `command` to execute.
+///
+/// # Arguments
+/// * `path` - Path to the command load file
+///
+/// # Returns
+/// * `Result<CommandLoad>` - Parsed load or configuration error
+pub fn load_command_file(path: &str) -> Result<CommandLoad> {
+    let bytes = std::fs::read(path).map_err(|_| SpaceCommError::ConfigurationError {
+        parameter: "command_load_file",
+        value: "<unreadable>",
+        reason: "command load file could not be read",
+    })?;
+
+    CommandLoad::from_bytes(&bytes)
+}
+
+/// Load a link capacity forecast from a JSON file
+///
+/// The file holds a JSON-encoded [`LinkForecast`], as written by the
+/// frequency band simulation's link forecast.
+///
+/// # Arguments
+/// * `path` - Path to the forecast file
+///
+/// # Returns
+/// * `Result<LinkForecast>` - Parsed forecast or configuration error
+pub fn load_link_forecast(path: &str) -> Result<LinkForecast> {
+    let bytes = std::fs::read(path).map_err(|_| SpaceCommError::ConfigurationError {
+        parameter: "link_forecast_file",
+        value: "<unreadable>",
+        reason: "link forecast file could not be read",
+    })?;
+
+    LinkForecast::from_bytes(&bytes)
+}
+
+/// Load an onboard autonomy rule from a JSON file
+///
+/// # Arguments
+/// * `path` - Path to a file holding a JSON-encoded [`AutonomyRule`]
+///
+/// # Returns
+/// * `Result<AutonomyRule>` - Parsed and validated rule or configuration error
+pub fn load_autonomy_rule(path: &str) -> Result<AutonomyRule> {
+    let bytes = std::fs::read(path).map_err(|_| SpaceCommError::ConfigurationError {
+        parameter: "autonomy_rule_file",
+        value: "<unreadable>",
+        reason: "autonomy rule file could not be read",
+    })?;
+
+    AutonomyRule::from_bytes(&bytes)
+}
+
+/// Advance the uplink sequence count of `apid`, wrapping at 14 bits
+///
+/// # Returns
+/// * `u16` - Count for the next packet on the APID; the first is 1
+pub(crate) fn next_uplink_sequence(sequences: &mut HashMap<u16, u16>, apid: u16) -> u16 {
+    let sequence = sequences.entry(apid).or_insert(0);
+    *sequence = next_sequence_count(*sequence);
+    *sequence
+}
+
+/// Create CCSDS command packet from message structure
+///
+/// Converts a shared [`Message`] to the standard CCSDS Space Packet format
+/// for transmission to satellite systems.
+///
+/// # Arguments
+/// * `message` - Internal message structure with command data
+///
+/// # Returns
+/// * `Result<SpacePacket>` - CCSDS-compliant packet or creation error
+///
+/// # Requirements Traceability
+/// - REQ-IF-002: CCSDS Compliance (Space Packet Protocol implementation)
+/// - REQ-FN-001: Priority Classification (priority-based APID assignment)
+#[req("REQ-FN-001", "REQ-IF-002")]
+pub fn create_command_packet(message: &Message) -> Result<SpacePacket> {
+    // Same encoding the satellite decodes with `decode_command_packet`
+    message.to_command_packet()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        ccsds::PacketType, messaging::decode_command_packet, types::BandType,
+    };
+
+    use crate::test_support::command_message;
+
+    #[test]
+    fn test_command_constructors() {
+        let stop = Command::emergency_stop();
+        assert_eq!(stop.priority, MessagePriority::Emergency);
+        assert_eq!(stop.parameters, vec![0x01]);
+
+        assert_eq!(Command::switch_band(BandType::UhfBand).parameters, vec![0]);
+        assert_eq!(Command::switch_band(BandType::KaBand).parameters, vec![4]);
+        assert_eq!(
+            Command::switch_band(BandType::XBand).priority,
+            MessagePriority::High
+        );
+        assert_eq!(Command::telemetry_request().priority, MessagePriority::Low);
+    }
+
+    #[test]
+    fn test_create_command_packet_layout() {
+        let command = Command::switch_band(BandType::XBand);
+        let packet = create_command_packet(&command_message(&command, 7)).unwrap();
+
+        assert_eq!(packet.header.packet_type, PacketType::Command);
+        assert_eq!(packet.header.apid, 0x003); // High priority APID
+        assert_eq!(packet.header.sequence_count, 7);
+        assert_eq!(packet.data.as_slice(), &[0x00, 0x00, 0x20, 0x01, 2]);
+        assert!(packet.verify_crc());
+
+        let stop = create_command_packet(&command_message(&Command::emergency_stop(), 8)).unwrap();
+        assert_eq!(stop.header.apid, 0x001);
+    }
+
+    #[test]
+    fn test_command_message_uses_shared_definition() {
+        let message = command_message(&Command::system_status_request(), 3);
+        assert_eq!(message.source, GROUND_STATION_COMPONENT);
+        assert_eq!(message.destination, SATELLITE_COMPONENT);
+        assert_eq!(message.priority, MessagePriority::Medium);
+        assert_eq!(message.ttl_seconds, 0);
+
+        let oversized = Command::new(0x1001, MessagePriority::Low, vec![0; 257]);
+        assert!(oversized.to_message(MessageId::from_value(1), 0).is_err());
+    }
+
+    /// Every ground command decodes on the satellite side to the same fields
+    #[test]
+    fn test_command_wire_compatibility() {
+        let commands = [
+            Command::system_status_request(),
+            Command::telemetry_request(),
+            Command::emergency_stop(),
+            Command::switch_band(BandType::KBand),
+            Command::set_vc_security_policy(1, VcSecurityPolicy::CLEAR),
+        ];
+
+        for (i, command) in commands.iter().enumerate() {
+            let id = 0x4000 + i as u64; // sequence count wraps at 14 bits
+            let bytes = create_command_packet(&command_message(command, id))
+                .unwrap()
+                .to_bytes()
+                .unwrap();
+            let fields = decode_command_packet(&bytes).unwrap();
+
+            assert_eq!(fields.priority, command.priority);
+            assert_eq!(fields.sequence_count, i as u16);
+            assert_eq!(fields.command_id, command.command_id);
+            assert_eq!(fields.parameters.as_slice(), command.parameters.as_slice());
+        }
+    }
+}
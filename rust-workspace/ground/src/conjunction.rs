@@ -0,0 +1,639 @@
+//! Conjunction screening against an object catalog
+//!
+//! The catalog is a simplified two-line element list: an optional name line
+//! followed by TLE lines 1 and 2, of which only the catalog number, epoch
+//! and mean elements are read. Blank lines and lines starting with `#` are
+//! skipped. Both the spacecraft and every catalog object are propagated as
+//! Keplerian orbits with J2 secular drift of the node and perigee, which is
+//! good to a few kilometres over the day-long windows screening uses; it is
+//! a screening filter, not a substitute for a covariance-based assessment.
+//!
+//! Screening runs in three passes per object:
+//! 1. An apogee/perigee filter drops objects whose radial shells cannot come
+//!    within the miss distance of the spacecraft's.
+//! 2. The range is sampled every [`ScreeningConfig::step_s`] across the
+//!    window; each local minimum that could hide a pass inside the miss
+//!    distance is kept.
+//! 3. Each kept minimum is refined by golden-section search to the time of
+//!    closest approach (TCA).
+//!
+//! Close approaches are reported as [`Conjunction`]s with the TCA, miss
+//! distance, its radial/in-track/cross-track components and the relative
+//! speed. [`Conjunction::draft_avoidance`] drafts the
+//! [`SpaceCommand::CollisionAvoidance`] that would open the miss distance;
+//! the draft is only a proposal and is uplinked once an operator approves it.
+//!
+//! # Requirements Traceability
+//! - FN-CA-001: Catalog objects screened for close approaches below a
+//!   configurable miss distance, with time and geometry of closest approach
+//! - FN-CA-002: Avoidance maneuver drafted for operator approval
+//! - REQ-FN-003: Critical system commands (CollisionAvoidance)
+
+use std::f64::consts::TAU;
+use std::fmt;
+
+use space_comms_shared::{
+    commands::{ManeuverType, SpaceCommand},
+    error::{Result, SpaceCommError},
+    orbit::{EARTH_MU_KM3_S2, EARTH_RADIUS_KM},
+};
+
+/// Earth's second zonal harmonic
+const EARTH_J2: f64 = 1.082_626_68e-3;
+
+/// Seconds in a day
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// TCA refinement tolerance, s
+const TCA_TOLERANCE_S: f64 = 1e-3;
+
+/// Minimum length of a TLE line up to the end of the mean motion field
+const TLE_LINE_LEN: usize = 63;
+
+/// Mean orbital elements of one catalog object, read from a TLE
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoLineElements {
+    /// NORAD catalog number
+    pub catalog_number: u32,
+    /// Object name; the catalog number when the entry has no name line
+    pub name: String,
+    /// Element epoch, seconds since the Unix epoch
+    pub epoch_unix_s: f64,
+    /// Inclination, degrees
+    pub inclination_deg: f64,
+    /// Right ascension of the ascending node, degrees
+    pub raan_deg: f64,
+    /// Eccentricity
+    pub eccentricity: f64,
+    /// Argument of perigee, degrees
+    pub arg_perigee_deg: f64,
+    /// Mean anomaly at epoch, degrees
+    pub mean_anomaly_deg: f64,
+    /// Mean motion, revolutions per day
+    pub mean_motion_rev_day: f64,
+}
+
+/// Position and velocity in the Earth-centred inertial frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVector {
+    /// Position, km
+    pub position_km: [f64; 3],
+    /// Velocity, km/s
+    pub velocity_km_s: [f64; 3],
+}
+
+impl TwoLineElements {
+    /// Parse a TLE from its two element lines
+    ///
+    /// # Arguments
+    /// * `name` - Object name, or `None` to name it by catalog number
+    /// * `line1` / `line2` - TLE lines 1 and 2
+    ///
+    /// # Returns
+    /// * `Result<TwoLineElements>` - Elements or configuration error for a
+    ///   malformed line
+    pub fn parse(name: Option<&str>, line1: &str, line2: &str) -> Result<Self> {
+        if !line1.starts_with('1') || line1.len() < TLE_LINE_LEN {
+            return Err(tle_error("TLE line 1 malformed"));
+        }
+        if !line2.starts_with('2') || line2.len() < TLE_LINE_LEN {
+            return Err(tle_error("TLE line 2 malformed"));
+        }
+
+        let catalog_number = field(line1, 2, 7)?;
+        if field::<u32>(line2, 2, 7)? != catalog_number {
+            return Err(tle_error("TLE lines belong to different objects"));
+        }
+
+        let epoch_year: i64 = field(line1, 18, 20)?;
+        let epoch_day: f64 = field(line1, 20, 32)?;
+        // Two-digit years 57-99 are 1957-1999, per the TLE convention
+        let year = if epoch_year < 57 {
+            2000 + epoch_year
+        } else {
+            1900 + epoch_year
+        };
+        let eccentricity: f64 = format!("0.{}", line2[26..33].trim())
+            .parse()
+            .map_err(|_| tle_error("TLE field malformed"))?;
+
+        Ok(Self {
+            catalog_number,
+            name: name.map_or_else(|| catalog_number.to_string(), |n| n.trim().to_string()),
+            epoch_unix_s: year_start_unix_s(year) + (epoch_day - 1.0) * SECONDS_PER_DAY,
+            inclination_deg: field(line2, 8, 16)?,
+            raan_deg: field(line2, 17, 25)?,
+            eccentricity,
+            arg_perigee_deg: field(line2, 34, 42)?,
+            mean_anomaly_deg: field(line2, 43, 51)?,
+            mean_motion_rev_day: field(line2, 52, 63)?,
+        })
+    }
+
+    /// Mean motion, rad/s
+    fn mean_motion_rad_s(&self) -> f64 {
+        self.mean_motion_rev_day * TAU / SECONDS_PER_DAY
+    }
+
+    /// Semi-major axis, km
+    pub fn semi_major_axis_km(&self) -> f64 {
+        (EARTH_MU_KM3_S2 / self.mean_motion_rad_s().powi(2)).cbrt()
+    }
+
+    /// Perigee and apogee radii, km
+    pub fn radius_bounds_km(&self) -> (f64, f64) {
+        let a = self.semi_major_axis_km();
+        (a * (1.0 - self.eccentricity), a * (1.0 + self.eccentricity))
+    }
+
+    /// Propagate to a time, with J2 secular drift of node and perigee
+    ///
+    /// # Arguments
+    /// * `unix_s` - Time, seconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `StateVector` - Inertial position and velocity at `unix_s`
+    pub fn state_at(&self, unix_s: f64) -> StateVector {
+        let n = self.mean_motion_rad_s();
+        let a = self.semi_major_axis_km();
+        let e = self.eccentricity;
+        let i = self.inclination_deg.to_radians();
+        let dt = unix_s - self.epoch_unix_s;
+
+        let p = a * (1.0 - e * e);
+        let j2_rate = 0.75 * n * EARTH_J2 * (EARTH_RADIUS_KM / p).powi(2);
+        let raan = self.raan_deg.to_radians() - 2.0 * j2_rate * i.cos() * dt;
+        let argp = self.arg_perigee_deg.to_radians() + j2_rate * (5.0 * i.cos().powi(2) - 1.0) * dt;
+        let mean_anomaly = (self.mean_anomaly_deg.to_radians() + n * dt).rem_euclid(TAU);
+
+        let ecc_anomaly = solve_kepler(mean_anomaly, e);
+        let (sin_e, cos_e) = ecc_anomaly.sin_cos();
+        let root = (1.0 - e * e).sqrt();
+        let radius = a * (1.0 - e * cos_e);
+        let perifocal_r = [a * (cos_e - e), a * root * sin_e];
+        let speed_scale = (EARTH_MU_KM3_S2 * a).sqrt() / radius;
+        let perifocal_v = [-speed_scale * sin_e, speed_scale * root * cos_e];
+
+        let (sin_o, cos_o) = raan.sin_cos();
+        let (sin_w, cos_w) = argp.sin_cos();
+        let (sin_i, cos_i) = i.sin_cos();
+        let p_axis = [
+            cos_o * cos_w - sin_o * sin_w * cos_i,
+            sin_o * cos_w + cos_o * sin_w * cos_i,
+            sin_w * sin_i,
+        ];
+        let q_axis = [
+            -cos_o * sin_w - sin_o * cos_w * cos_i,
+            -sin_o * sin_w + cos_o * cos_w * cos_i,
+            cos_w * sin_i,
+        ];
+
+        let rotate = |v: [f64; 2]| {
+            [
+                p_axis[0] * v[0] + q_axis[0] * v[1],
+                p_axis[1] * v[0] + q_axis[1] * v[1],
+                p_axis[2] * v[0] + q_axis[2] * v[1],
+            ]
+        };
+        StateVector {
+            position_km: rotate(perifocal_r),
+            velocity_km_s: rotate(perifocal_v),
+        }
+    }
+}
+
+/// Parse a catalog of simplified TLEs
+///
+/// Entries are an optional name line followed by lines 1 and 2; blank lines
+/// and `#` comments are skipped.
+///
+/// # Returns
+/// * `Result<Vec<TwoLineElements>>` - Catalog objects in file order, or
+///   configuration error for a malformed entry
+pub fn parse_catalog(text: &str) -> Result<Vec<TwoLineElements>> {
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+    let mut catalog = Vec::new();
+
+    while let Some(first) = lines.next() {
+        let (name, line1) = if first.starts_with("1 ") {
+            (None, first)
+        } else {
+            let line1 = lines
+                .next()
+                .ok_or_else(|| tle_error("TLE entry truncated"))?;
+            (Some(first), line1)
+        };
+        let line2 = lines
+            .next()
+            .ok_or_else(|| tle_error("TLE entry truncated"))?;
+        catalog.push(TwoLineElements::parse(name, line1, line2)?);
+    }
+    Ok(catalog)
+}
+
+/// Load a catalog of simplified TLEs from a file
+///
+/// # Arguments
+/// * `path` - Path to the catalog file
+///
+/// # Returns
+/// * `Result<Vec<TwoLineElements>>` - Catalog objects or configuration error
+pub fn load_catalog(path: &str) -> Result<Vec<TwoLineElements>> {
+    let text = std::fs::read_to_string(path).map_err(|_| SpaceCommError::ConfigurationError {
+        parameter: "catalog_file",
+        value: "<unreadable>",
+        reason: "object catalog file could not be read",
+    })?;
+
+    parse_catalog(&text)
+}
+
+/// Screening window and threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreeningConfig {
+    /// Close approaches below this are reported, km
+    pub miss_distance_km: f64,
+    /// Length of the screening window, s
+    pub window_s: f64,
+    /// Coarse sampling step, s
+    pub step_s: f64,
+}
+
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            miss_distance_km: 5.0,
+            window_s: SECONDS_PER_DAY,
+            step_s: 20.0,
+        }
+    }
+}
+
+/// Close approach between the spacecraft and a catalog object
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conjunction {
+    /// NORAD catalog number of the object
+    pub catalog_number: u32,
+    /// Object name
+    pub name: String,
+    /// Time of closest approach, seconds since the Unix epoch
+    pub tca_unix_s: f64,
+    /// Range at TCA, km
+    pub miss_distance_km: f64,
+    /// Object position relative to the spacecraft at TCA, along the
+    /// spacecraft's radial direction, km
+    pub radial_km: f64,
+    /// ... along its in-track direction, km
+    pub in_track_km: f64,
+    /// ... along its orbit normal, km
+    pub cross_track_km: f64,
+    /// Relative speed at TCA, km/s
+    pub relative_speed_km_s: f64,
+}
+
+impl Conjunction {
+    /// Draft an avoidance maneuver opening the miss distance to
+    /// `target_miss_km`
+    ///
+    /// The burn is along-track, `lead_time_s` before TCA, sized from the
+    /// along-track drift of a tangential burn, about three times the
+    /// velocity change per second elapsed. It is posigrade when the object
+    /// is ahead, so the spacecraft falls behind it, and retrograde
+    /// otherwise. The draft needs operator approval before it is uplinked,
+    /// and the command itself requires confirmation onboard.
+    ///
+    /// # Arguments
+    /// * `target_miss_km` - Miss distance the maneuver should achieve
+    /// * `lead_time_s` - Time between the burn and TCA, s
+    ///
+    /// # Returns
+    /// * `SpaceCommand` - Collision avoidance command, X along the velocity
+    pub fn draft_avoidance(&self, target_miss_km: f64, lead_time_s: f64) -> SpaceCommand {
+        let opening_m = (target_miss_km - self.miss_distance_km).max(0.0) * 1000.0;
+        let magnitude_m_s = opening_m / (3.0 * lead_time_s.max(1.0));
+        let delta_v_m_s = if self.in_track_km >= 0.0 {
+            magnitude_m_s
+        } else {
+            -magnitude_m_s
+        };
+
+        SpaceCommand::CollisionAvoidance {
+            debris_id: u64::from(self.catalog_number),
+            maneuver_type: ManeuverType::AvoidanceManeuver,
+            delta_v: [delta_v_m_s as f32, 0.0, 0.0],
+            execution_time: (self.tca_unix_s - lead_time_s).max(0.0) as u64,
+        }
+    }
+}
+
+impl fmt::Display for Conjunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) TCA {:.0} miss {:.3} km (R {:+.3} T {:+.3} N {:+.3}) at {:.2} km/s",
+            self.name,
+            self.catalog_number,
+            self.tca_unix_s,
+            self.miss_distance_km,
+            self.radial_km,
+            self.in_track_km,
+            self.cross_track_km,
+            self.relative_speed_km_s
+        )
+    }
+}
+
+/// Screen a catalog for close approaches to the spacecraft
+///
+/// The spacecraft itself, recognised by catalog number, is skipped.
+///
+/// # Arguments
+/// * `primary` - Elements of the spacecraft
+/// * `catalog` - Objects to screen against
+/// * `config` - Miss distance threshold and window
+/// * `start_unix_s` - Start of the window, seconds since the Unix epoch
+///
+/// # Returns
+/// * `Vec<Conjunction>` - Close approaches below the miss distance, by TCA
+pub fn screen(
+    primary: &TwoLineElements,
+    catalog: &[TwoLineElements],
+    config: &ScreeningConfig,
+    start_unix_s: f64,
+) -> Vec<Conjunction> {
+    let (primary_perigee, primary_apogee) = primary.radius_bounds_km();
+    let steps = (config.window_s / config.step_s).ceil().max(2.0) as usize;
+    let primary_states: Vec<StateVector> = (0..=steps)
+        .map(|k| primary.state_at(start_unix_s + k as f64 * config.step_s))
+        .collect();
+
+    let mut conjunctions = Vec::new();
+    for object in catalog {
+        if object.catalog_number == primary.catalog_number {
+            continue;
+        }
+        let (perigee, apogee) = object.radius_bounds_km();
+        if perigee.max(primary_perigee) - apogee.min(primary_apogee) > config.miss_distance_km {
+            continue;
+        }
+
+        let ranges: Vec<(f64, f64)> = primary_states
+            .iter()
+            .enumerate()
+            .map(|(k, own)| {
+                let other = object.state_at(start_unix_s + k as f64 * config.step_s);
+                (
+                    distance(own.position_km, other.position_km),
+                    distance(own.velocity_km_s, other.velocity_km_s),
+                )
+            })
+            .collect();
+
+        for k in 1..steps {
+            let (range, speed) = ranges[k];
+            let local_minimum = range <= ranges[k - 1].0 && range < ranges[k + 1].0;
+            // Between samples the range cannot fall faster than the relative speed
+            if !local_minimum || range - speed * config.step_s > config.miss_distance_km {
+                continue;
+            }
+
+            let sample_unix_s = start_unix_s + k as f64 * config.step_s;
+            let tca_unix_s = refine_tca(
+                primary,
+                object,
+                sample_unix_s - config.step_s,
+                sample_unix_s + config.step_s,
+            );
+            let conjunction = close_approach(primary, object, tca_unix_s);
+            if conjunction.miss_distance_km < config.miss_distance_km {
+                conjunctions.push(conjunction);
+            }
+        }
+    }
+
+    conjunctions.sort_by(|a, b| a.tca_unix_s.total_cmp(&b.tca_unix_s));
+    conjunctions
+}
+
+/// Geometry of the approach between two objects at a time
+fn close_approach(primary: &TwoLineElements, object: &TwoLineElements, unix_s: f64) -> Conjunction {
+    let own = primary.state_at(unix_s);
+    let other = object.state_at(unix_s);
+    let relative = sub(other.position_km, own.position_km);
+
+    let radial = unit(own.position_km);
+    let normal = unit(cross(own.position_km, own.velocity_km_s));
+    let in_track = cross(normal, radial);
+
+    Conjunction {
+        catalog_number: object.catalog_number,
+        name: object.name.clone(),
+        tca_unix_s: unix_s,
+        miss_distance_km: norm(relative),
+        radial_km: dot(relative, radial),
+        in_track_km: dot(relative, in_track),
+        cross_track_km: dot(relative, normal),
+        relative_speed_km_s: distance(own.velocity_km_s, other.velocity_km_s),
+    }
+}
+
+/// Time of minimum range between two objects within `[start, end]`, by
+/// golden-section search
+fn refine_tca(primary: &TwoLineElements, object: &TwoLineElements, start: f64, end: f64) -> f64 {
+    let range = |t: f64| {
+        distance(
+            primary.state_at(t).position_km,
+            object.state_at(t).position_km,
+        )
+    };
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (start, end);
+    let mut left = hi - ratio * (hi - lo);
+    let mut right = lo + ratio * (hi - lo);
+    let (mut left_range, mut right_range) = (range(left), range(right));
+
+    while hi - lo > TCA_TOLERANCE_S {
+        if left_range < right_range {
+            hi = right;
+            right = left;
+            right_range = left_range;
+            left = hi - ratio * (hi - lo);
+            left_range = range(left);
+        } else {
+            lo = left;
+            left = right;
+            left_range = right_range;
+            right = lo + ratio * (hi - lo);
+            right_range = range(right);
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+/// Eccentric anomaly for a mean anomaly, by Newton iteration
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let mut ecc_anomaly = if eccentricity < 0.8 {
+        mean_anomaly
+    } else {
+        core::f64::consts::PI
+    };
+    for _ in 0..20 {
+        let step = (ecc_anomaly - eccentricity * ecc_anomaly.sin() - mean_anomaly)
+            / (1.0 - eccentricity * ecc_anomaly.cos());
+        ecc_anomaly -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    ecc_anomaly
+}
+
+/// Seconds since the Unix epoch at the start of a year
+fn year_start_unix_s(year: i64) -> f64 {
+    let leap_days = |y: i64| y / 4 - y / 100 + y / 400;
+    let days = 365 * (year - 1970) + leap_days(year - 1) - leap_days(1969);
+    days as f64 * SECONDS_PER_DAY
+}
+
+/// Fixed-column TLE field, columns `start..end` counted from zero
+fn field<T: std::str::FromStr>(line: &str, start: usize, end: usize) -> Result<T> {
+    line.get(start..end)
+        .and_then(|text| text.trim().parse().ok())
+        .ok_or_else(|| tle_error("TLE field malformed"))
+}
+
+fn tle_error(reason: &'static str) -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter: "catalog",
+        value: "<tle>",
+        reason,
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn unit(a: [f64; 3]) -> [f64; 3] {
+    let length = norm(a);
+    [a[0] / length, a[1] / length, a[2] / length]
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    norm(sub(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9005
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.50377579 12345
+";
+
+    /// Catalog line pair for an object with the given node and mean anomaly
+    fn tle(catalog_number: u32, raan_deg: f64, mean_anomaly_deg: f64) -> String {
+        format!(
+            "1 {catalog_number:05}U 24001A   24001.50000000  .00000000  00000-0  00000-0 0  9990\n\
+             2 {catalog_number:05}  51.6416 {raan_deg:8.4} 0006703 130.5360 {mean_anomaly_deg:8.4} 15.50377579 12340\n"
+        )
+    }
+
+    #[test]
+    fn test_catalog_parses_and_propagates() {
+        let catalog =
+            parse_catalog(&format!("# test catalog\n{}\n{}", ISS, tle(1, 0.0, 0.0))).unwrap();
+        assert_eq!(catalog.len(), 2);
+        let iss = &catalog[0];
+        assert_eq!(iss.catalog_number, 25544);
+        assert_eq!(iss.name, "ISS (ZARYA)");
+        assert_eq!(catalog[1].name, "1");
+        assert!((iss.eccentricity - 0.0006703).abs() < 1e-9);
+        // 2024-01-01T12:00:00Z
+        assert!((iss.epoch_unix_s - 1_704_110_400.0).abs() < 1e-3);
+
+        let state = iss.state_at(iss.epoch_unix_s + 3600.0);
+        let altitude_km = norm(state.position_km) - EARTH_RADIUS_KM;
+        assert!((400.0..440.0).contains(&altitude_km), "{altitude_km}");
+        assert!((norm(state.velocity_km_s) - 7.66).abs() < 0.05);
+
+        let mut truncated = ISS.lines().take(2).collect::<Vec<_>>().join("\n");
+        assert!(parse_catalog(&truncated).is_err());
+        truncated = ISS.replace("2 25544", "2 25545");
+        assert!(parse_catalog(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_screening_reports_crossing_and_drafts_avoidance() {
+        let primary = parse_catalog(ISS).unwrap().remove(0);
+        // Same orbit shape in a plane 0.05 degrees away: the two cross at
+        // the nodes twice per revolution
+        let catalog = parse_catalog(&format!(
+            "{}{}{}",
+            ISS,
+            tle(40001, 247.5127, 325.0288),
+            tle(40002, 67.4627, 325.0288)
+        ))
+        .unwrap();
+
+        let config = ScreeningConfig {
+            window_s: 6000.0,
+            ..ScreeningConfig::default()
+        };
+        let conjunctions = screen(&primary, &catalog, &config, primary.epoch_unix_s);
+        assert!(!conjunctions.is_empty());
+        assert!(conjunctions.iter().all(|c| c.catalog_number == 40001));
+        assert!(conjunctions
+            .windows(2)
+            .all(|pair| pair[0].tca_unix_s <= pair[1].tca_unix_s));
+
+        let closest = &conjunctions[0];
+        assert!(closest.miss_distance_km < config.miss_distance_km);
+        let components = [
+            closest.radial_km,
+            closest.in_track_km,
+            closest.cross_track_km,
+        ];
+        assert!((norm(components) - closest.miss_distance_km).abs() < 1e-6);
+        // The planes cross at a shallow angle, so the objects barely move
+        // relative to each other
+        assert!(closest.relative_speed_km_s < 0.1);
+
+        let command = closest.draft_avoidance(10.0, 5400.0);
+        let SpaceCommand::CollisionAvoidance {
+            debris_id,
+            maneuver_type,
+            delta_v,
+            execution_time,
+        } = command
+        else {
+            panic!("expected a collision avoidance draft");
+        };
+        assert_eq!(debris_id, 40001);
+        assert_eq!(maneuver_type, ManeuverType::AvoidanceManeuver);
+        let expected_m_s = (10.0 - closest.miss_distance_km) * 1000.0 / (3.0 * 5400.0);
+        assert!((f64::from(delta_v[0].abs()) - expected_m_s).abs() < 1e-3);
+        assert_eq!(execution_time, (closest.tca_unix_s - 5400.0) as u64);
+        assert!(command.requires_confirmation());
+    }
+}
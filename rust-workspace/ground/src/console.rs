@@ -0,0 +1,505 @@
+//! Mission control console
+//!
+//! Interactive operator console over a [`GroundStation`]: starts the station
+//! and its background link services, drives the mission clock event
+//! scheduler and runs the operator command loop. The console commands
+//! themselves are run by the `commands` submodule.
+//!
+//! # Requirements Traceability
+//! - FN-EVT-001..003: Event countdowns, reminders and automatic procedures
+//! - FN-DIC-002: Completion of console, dictionary and macro names
+//! - FN-MAC-001..002: Operator macros kept in the console configuration
+//! - FN-LBK-001: Loopback test sent at every AOS
+//! - FN-SBN-002: cFS Software Bus Network bridge service
+//! - FN-YMC-001..002: YAMCS measurement feed and command link service
+//! - FN-RED-001..003: Hot-standby pair sync service
+//! - FN-VIS-002: Upcoming passes kept on the event schedule
+//! - FN-API-001..004: HTTP API served alongside the console (`api` feature)
+
+mod commands;
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use space_comms_shared::{Result, SpaceCommError};
+
+use crate::conjunction::Conjunction;
+use crate::dictionary;
+use crate::macros::MacroSet;
+use crate::pass_scheduler::{PassScheduler, DEFAULT_ELEVATION_MASK_DEG};
+use crate::scheduler::{format_countdown, EventScheduler, SchedulerNotice};
+use crate::{GroundStation, GroundStationConfig};
+
+/// Command-line flag starting the console in dry-run mode
+pub const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// Command-line flag adding a cFS SBN peer, followed by its UDP address
+pub const SBN_PEER_FLAG: &str = "--sbn-peer";
+
+/// Command-line flag adding a mirror endpoint, followed by
+/// `udp://<addr>` or `tcp://<addr>`, optionally `/frames` or `/telemetry`,
+/// and optionally `?encoding=protobuf`
+pub const MIRROR_FLAG: &str = "--mirror";
+
+/// Command-line flag pairing with a redundant instance, followed by the UDP
+/// address of its sync channel
+pub const REDUNDANCY_PEER_FLAG: &str = "--redundancy-peer";
+
+/// Command-line flag setting the local sync channel address
+pub const REDUNDANCY_BIND_FLAG: &str = "--redundancy-bind";
+
+/// Command-line flag starting this instance as the standby of its pair
+pub const STANDBY_FLAG: &str = "--standby";
+
+/// Command-line flag connecting to a YAMCS server on this host
+pub const YAMCS_FLAG: &str = "--yamcs";
+
+/// Command-line flag serving the HTTP API, followed by its TCP address
+pub const API_FLAG: &str = "--api";
+
+/// Command-line flag loading the link master keys, followed by the key file
+pub const LINK_KEYS_FLAG: &str = "--link-keys";
+
+/// Console configuration holding operator macros
+const CONSOLE_CONFIG_FILE: &str = "mission_control.json";
+
+/// Directory the YAMCS mission database is exported to by default
+const YAMCS_EXPORT_DIR: &str = "yamcs";
+
+/// Directory the protobuf schema is exported to by default
+const PROTO_EXPORT_DIR: &str = "proto";
+
+/// Miss distance an approved avoidance maneuver aims for, km
+const AVOIDANCE_TARGET_MISS_KM: f64 = 10.0;
+
+/// Time between an avoidance burn and the time of closest approach, s
+const AVOIDANCE_LEAD_TIME_S: f64 = 5_400.0;
+
+/// Time ahead visibility windows are predicted and scheduled, s
+const PASS_PLANNING_HORIZON_S: f64 = 86_400.0;
+
+/// Mission clock ticks between refreshes of the pass schedule
+const PASS_PLANNING_INTERVAL_TICKS: u64 = 60;
+
+/// Built-in console commands; macros may not shadow them
+const CONSOLE_COMMANDS: &[&str] = &[
+    "status",
+    "telem",
+    "values",
+    "archive",
+    "replay",
+    "eps",
+    "seq",
+    "cop1",
+    "verify",
+    "evlog",
+    "operator",
+    "audit",
+    "passes",
+    "sbn",
+    "mirror",
+    "redundancy",
+    "yamcs",
+    "proto",
+    "send",
+    "alias",
+    "unalias",
+    "macros",
+    "band",
+    "link",
+    "linkstats",
+    "budget",
+    "stop",
+    "load",
+    "retx",
+    "forecast",
+    "autonomy",
+    "vcsec",
+    "keys",
+    "rotkey",
+    "dryrun",
+    "inspect",
+    "diag",
+    "loopback",
+    "conj",
+    "vis",
+    "event",
+    "proc",
+    "events",
+    "cancel",
+    "quit",
+];
+
+/// Current mission time in seconds since the Unix epoch
+fn mission_time_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Current mission time in milliseconds since the Unix epoch
+fn mission_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Mission control interface
+pub struct MissionControl {
+    ground_station: Arc<GroundStation>,
+    scheduler: Arc<Mutex<EventScheduler>>,
+    /// Served satellites and their queued commands, scheduled into their
+    /// visibility windows
+    pass_scheduler: Arc<Mutex<PassScheduler>>,
+    macros: Mutex<MacroSet>,
+    /// Conjunctions from the last screening whose avoidance drafts await
+    /// operator approval
+    conjunctions: Mutex<Vec<Conjunction>>,
+}
+
+impl MissionControl {
+    /// Create new mission control interface
+    pub fn new(config: GroundStationConfig) -> Result<Self> {
+        let pass_scheduler = PassScheduler::new(config.location, DEFAULT_ELEVATION_MASK_DEG);
+        let ground_station = Arc::new(GroundStation::new(config)?);
+        Ok(Self {
+            ground_station,
+            scheduler: Arc::new(Mutex::new(EventScheduler::new())),
+            pass_scheduler: Arc::new(Mutex::new(pass_scheduler)),
+            macros: Mutex::new(MacroSet::load(CONSOLE_CONFIG_FILE)?),
+            conjunctions: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Start mission operations
+    pub fn start_operations(&self) -> Result<()> {
+        self.ground_station.start()?;
+        self.start_event_clock();
+        self.start_loopback_checks();
+        self.start_cop1_service();
+        if self.ground_station.sbn_bridge().is_some() {
+            self.start_sbn_bridge();
+        }
+        if self.ground_station.yamcs_config().is_some() {
+            self.start_yamcs_link();
+        }
+        if let Some(link) = self.ground_station.redundancy() {
+            println!(
+                "Redundancy: instance {} starting as {:?}",
+                link.config().instance_id,
+                link.role()
+            );
+            self.start_redundancy_link();
+        }
+
+        println!("Mission Control operational");
+        if self.ground_station.is_dry_run() {
+            println!("DRY RUN: uplinks are shown, not sent ('dryrun off' to go live)");
+        }
+
+        // Start command loop
+        self.command_loop();
+
+        Ok(())
+    }
+
+    /// Start the mission clock thread
+    ///
+    /// Polls the event scheduler once a second, prints reminders and uplinks
+    /// the procedure of every event that fires. Every minute, upcoming passes
+    /// of the served satellites are added to the schedule.
+    fn start_event_clock(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+        let scheduler = Arc::clone(&self.scheduler);
+        let pass_scheduler = Arc::clone(&self.pass_scheduler);
+
+        thread::spawn(move || {
+            for tick in 0u64.. {
+                // FN-VIS-002: Passes entering the planning horizon are scheduled
+                if tick % PASS_PLANNING_INTERVAL_TICKS == 0 {
+                    let planned = pass_scheduler.lock().unwrap().schedule(
+                        &mut scheduler.lock().unwrap(),
+                        mission_time_secs(),
+                        PASS_PLANNING_HORIZON_S,
+                    );
+                    for pass in planned {
+                        println!("[PASS] #{} {}", pass.event_id, pass.window);
+                    }
+                }
+
+                let notices = scheduler.lock().unwrap().poll(mission_time_secs());
+                for notice in notices {
+                    match notice {
+                        SchedulerNotice::Reminder {
+                            id,
+                            name,
+                            kind,
+                            remaining_secs,
+                        } => println!(
+                            "[REMINDER] #{} {} ({:?}) at {}",
+                            id,
+                            name,
+                            kind,
+                            format_countdown(remaining_secs)
+                        ),
+                        SchedulerNotice::Fired(event) => {
+                            println!(
+                                "[EVENT] #{} {} ({:?}) now, {} procedure steps",
+                                event.id,
+                                event.name,
+                                event.kind,
+                                event.procedure.len()
+                            );
+                            for command in event.procedure {
+                                if let Err(e) = ground_station.send_command(command) {
+                                    eprintln!("Event #{} procedure step failed: {}", event.id, e);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                thread::sleep(Duration::from_secs(1));
+            }
+        });
+    }
+
+    /// Start the AOS loopback check thread
+    ///
+    /// Checks once a second whether a contact has started and, if so, sends
+    /// its loopback test.
+    fn start_loopback_checks(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+
+        thread::spawn(move || loop {
+            match ground_station.service_loopback() {
+                Ok(Some(test_id)) => println!("[AOS] Loopback test {} sent", test_id),
+                Ok(None) => {}
+                Err(e) => eprintln!("AOS loopback test failed: {}", e),
+            }
+
+            thread::sleep(Duration::from_secs(1));
+        });
+    }
+
+    /// Start the COP-1 retransmission thread
+    ///
+    /// Sends the command frames FOP-1 has due for retransmission every 100ms,
+    /// and reports the alert when a frame reaches the transmission limit.
+    fn start_cop1_service(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+
+        thread::spawn(move || loop {
+            if let Err(e) = ground_station.service_cop1() {
+                eprintln!("COP-1: {}", e);
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        });
+    }
+
+    /// Start the SBN bridge thread
+    ///
+    /// Services the cFS Software Bus Network bridge continuously; each call
+    /// waits up to 100ms for a peer datagram.
+    fn start_sbn_bridge(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+
+        thread::spawn(move || loop {
+            if let Err(e) = ground_station.service_sbn() {
+                eprintln!("SBN bridge: {}", e);
+            }
+        });
+    }
+
+    /// Start the YAMCS command link thread
+    ///
+    /// Services the YAMCS command link continuously; each call waits up to
+    /// 100ms for a command packet.
+    fn start_yamcs_link(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+
+        thread::spawn(move || loop {
+            if let Err(e) = ground_station.service_yamcs() {
+                eprintln!("YAMCS link: {}", e);
+            }
+        });
+    }
+
+    /// Start the hot-standby sync thread
+    ///
+    /// Services the sync channel to the redundant peer continuously; each
+    /// call waits up to 100ms for a peer datagram.
+    fn start_redundancy_link(&self) {
+        let ground_station = Arc::clone(&self.ground_station);
+
+        thread::spawn(move || loop {
+            if let Err(e) = ground_station.service_redundancy() {
+                eprintln!("Redundancy link: {}", e);
+            }
+        });
+    }
+
+    /// Serve the HTTP API for dashboards and automation
+    #[cfg(feature = "api")]
+    pub fn start_api(&self, addr: SocketAddr) -> Result<()> {
+        let addr = crate::api::start(Arc::clone(&self.ground_station), addr)?;
+        println!("HTTP API listening on http://{}", addr);
+        Ok(())
+    }
+
+    /// Serve the HTTP API for dashboards and automation
+    #[cfg(not(feature = "api"))]
+    pub fn start_api(&self, _addr: SocketAddr) -> Result<()> {
+        Err(SpaceCommError::ConfigurationError {
+            parameter: "api",
+            value: "unavailable",
+            reason: "Built without the api feature",
+        })
+    }
+
+    /// Interactive command loop
+    fn command_loop(&self) {
+        use std::io::{self, Write};
+
+        println!("Mission Control Command Interface");
+        println!("Available commands:");
+        println!("  status   - Request system status");
+        println!("  telem    - Request telemetry");
+        println!("  values   - Show latest telemetry values and quality");
+        println!("  archive [filters] - Find archived telemetry (from/until <ms>, apid/id <n>)");
+        println!("  replay [filters] - Replay archived telemetry through the displays");
+        println!("  eps      - Show latest power system summary and per-subsystem draw");
+        println!("  seq      - Show sequence counts and windows per APID");
+        println!("  cop1 [init <n>|unlock] - Show COP-1 uplink state, restart it with Set V(R) or Unlock");
+        println!("  sbn      - Show cFS Software Bus Network peers");
+        println!("  mirror   - Show mirror consumers and their sent and dropped counts");
+        println!("  redundancy - Show hot-standby role, authority epoch and peer");
+        println!("  yamcs [dir] - Export the YAMCS mission database and instance configuration");
+        println!("  proto [dir] - Export the protobuf schema of telemetry and dictionary commands");
+        println!("  verify [file] - Summarise execution reports and latency, export as CSV");
+        println!("  evlog    - Show event log compression statistics");
+        println!("  operator <name> - Record subsequent commands against operator");
+        println!("  audit [csv|json <file>] - Show command audit log size, export it");
+        println!("  passes [n] - List pass reports, show report n in full");
+        println!("  send [command [params...]] - Send a dictionary command, prompting for params");
+        println!("  alias <name> <line>[; <line>...] - Define a macro ($1-$9 for arguments)");
+        println!("  unalias <name> - Remove a macro");
+        println!("  macros   - List macros");
+        println!("  band <n> - Switch to band (0=UHF, 1=S, 2=X, 3=K, 4=Ka)");
+        println!("  link [up|down <n> <power%> <bps>] - Show or set uplink/downlink band");
+        println!("  linkstats - Show link layer statistics and the satellite downlink policy");
+        println!("  budget [<s> <max elev°> [modcod]] - Plan the pass volume or show it");
+        println!("  stop     - Emergency stop");
+        println!("  load <f> - Validate and uplink command load file");
+        println!("  retx <file> [offset len] - Request file retransmission");
+        println!("  forecast <f> - Uplink link capacity forecast file");
+        println!("  autonomy <f> - Uplink autonomy rule file (enable with SetAutonomyRule)");
+        println!("  vcsec <vc> <clear|auth|enc> [key] - Set virtual channel security");
+        println!("  keys [file] - Show link key generations, load master keys");
+        println!("  rotkey <slot> <generation> - Rotate a link key on both ends");
+        println!("  dryrun [on|off] - Show uplinks as hex and decoded view instead of sending");
+        println!("  inspect <hex> - Annotated breakdown of a raw packet");
+        println!("  diag [dump|dwell <id> [file]] - Show memory dumps and dwells, export one");
+        println!("  loopback [band <n> [text]|offset <ms>] - Show loopback delays, send a test");
+        println!("  conj [<own tle> <catalog> [miss_km]|approve <n>] - Screen conjunctions, approve avoidance");
+        println!("  vis [load <tle file>|queue <catalog no> <step>] - Show passes, load or queue for them");
+        println!("  event <maneuver|aos|deadline|other> <secs> <name> - Schedule event");
+        println!("  proc <id> <status|telem|stop|band <n>> - Add event procedure step");
+        println!("  events   - Show event countdowns");
+        println!("  cancel <id> - Cancel scheduled event");
+        println!("  quit     - Exit mission control");
+        println!("Type part of a command and Tab, then Enter, to list completions.");
+
+        loop {
+            if self.ground_station.is_dry_run() {
+                print!("MC (dry run)> ");
+            } else {
+                print!("MC> ");
+            }
+            io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input).is_err() {
+                continue;
+            }
+
+            // A tab in the line asks for completion instead of running it
+            if let Some((line, _)) = input.split_once('\t') {
+                self.show_completions(line);
+                continue;
+            }
+
+            let parts: Vec<&str> = input.split_whitespace().collect();
+            let Some((&verb, args)) = parts.split_first() else {
+                continue;
+            };
+
+            if !self.macros.lock().unwrap().contains(verb) {
+                if !self.execute(&parts) {
+                    break;
+                }
+                continue;
+            }
+
+            let steps = match self.macros.lock().unwrap().expand(verb, args) {
+                Ok(steps) => steps,
+                Err(e) => {
+                    eprintln!("Macro {} not run: {}", verb, e);
+                    continue;
+                }
+            };
+            for step in steps {
+                println!("{}> {}", verb, step);
+                let words: Vec<&str> = step.split_whitespace().collect();
+                if !self.execute(&words) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Print completions for the last word of `line`
+    ///
+    /// Completes console commands and macros, dictionary command names and
+    /// enumerated parameter values after `send`, and macro names after
+    /// `unalias`.
+    fn show_completions(&self, line: &str) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        // After a trailing space the word being completed is still empty
+        let (done, partial) = match words.split_last() {
+            Some((last, done)) if !line.ends_with(char::is_whitespace) => (done, *last),
+            _ => (&words[..], ""),
+        };
+
+        let macros = self.macros.lock().unwrap();
+        let candidates: Vec<&str> = match done.split_first() {
+            None => CONSOLE_COMMANDS
+                .iter()
+                .copied()
+                .chain(macros.names())
+                .collect(),
+            Some((&"send", args)) => dictionary::argument_candidates(args),
+            Some((&"unalias", [])) => macros.names().collect(),
+            _ => Vec::new(),
+        };
+
+        let matches = dictionary::complete(partial, candidates);
+        let stem = &line[..line.len() - partial.len()];
+        match matches.as_slice() {
+            [] => println!("No completions"),
+            [only] => println!("{}{}", stem, only),
+            _ => {
+                println!("{}", matches.join("  "));
+                let prefix = dictionary::common_prefix(&matches);
+                if prefix.len() > partial.len() {
+                    println!("{}{}", stem, prefix);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1068 @@
+//! Console commands
+//!
+//! Runs the operator's console commands against the ground station: uplinks,
+//! displays, exports, pass planning, conjunction screening and event
+//! scheduling.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-001: Priority Classification (operator command priorities)
+//! - REQ-FN-007: Multi-Band Communication (operator band selection)
+//! - FN-EVT-001..002: Event scheduling, procedures and countdowns
+//! - FN-AUD-001..002: Persistent command audit log and its export
+//! - FN-DIC-001: Dictionary commands with parameter prompting
+//! - FN-DRY-001: `dryrun` console command showing uplinks instead of sending
+//!   them
+//! - FN-INS-001: `inspect` console command for raw packets pasted as hex
+//! - FN-DMP-002 / FN-DWL-002: `diag` console command showing reassembled
+//!   memory dumps and dwell traces
+//! - FN-LBK-002: `loopback` console command sending tests and showing their
+//!   measured delays
+//! - FN-SBN-002: `sbn` console command showing the bridge peers
+//! - FN-YMC-003: `yamcs` console command exporting the mission database
+//! - FN-MIR-002: `mirror` console command showing delivery counts
+//! - FN-PB-001: `proto` console command exporting the protobuf schema of the
+//!   telemetry and dictionary commands
+//! - FN-VOL-001..002: `budget` console command planning the pass volume
+//!   on the current downlink and showing delivery against it
+//! - FN-RED-003: `redundancy` console command showing role and peer
+//! - FN-VIS-001..002: `vis` console command predicting visibility windows
+//!   of loaded element sets and queuing commands for their next pass
+//! - FN-CA-001..002: `conj` console command screening a catalog for close
+//!   approaches and uplinking avoidance drafts the operator approves
+//! - REQ-SC-003: `keys` and `rotkey` console commands loading and rotating
+//!   the link keys
+
+use serde_json::Value;
+use space_comms_shared::{
+    command_load::LoadConstraints,
+    eps::EpsSummary,
+    file_downlink::{ByteRange, RetransmitRequest},
+    link_config::{DirectionalLink, LinkDirection},
+    power_attribution::PowerAttribution,
+    security::{KeyRotation, SecurityService, VcSecurityPolicy, MAX_KEY_SLOTS},
+    types::BandType,
+};
+
+use crate::conjunction::{self, ScreeningConfig};
+use crate::dictionary::{self, ParameterSpec, COMMAND_DICTIONARY};
+use crate::loopback::format_loopback_results;
+use crate::mirror::{format_mirror_statistics, MirrorEncoding, MirrorEndpoint};
+use crate::pass_scheduler::format_visibility_windows;
+use crate::power_trend::format_power_attribution;
+use crate::redundancy::format_redundancy;
+use crate::sbn::format_sbn_peers;
+use crate::scheduler::{format_countdown, EventKind};
+use crate::telemetry_archive::TelemetryQuery;
+use crate::volume_budget::{format_pass_budget, Modcod, PassGeometry, MODCODS};
+use crate::{
+    display_load_manifest, format_cop1, format_eps_summary, format_sequence_windows,
+    load_autonomy_rule, load_command_file, load_link_forecast, Command,
+};
+use crate::{dry_run, link_security, protobuf, verification, yamcs};
+
+use super::{
+    mission_time_ms, mission_time_secs, MissionControl, AVOIDANCE_LEAD_TIME_S,
+    AVOIDANCE_TARGET_MISS_KM, CONSOLE_COMMANDS, CONSOLE_CONFIG_FILE, MIRROR_FLAG,
+    PASS_PLANNING_HORIZON_S, PROTO_EXPORT_DIR, REDUNDANCY_PEER_FLAG, SBN_PEER_FLAG,
+    YAMCS_EXPORT_DIR, YAMCS_FLAG,
+};
+
+/// Parse an operator band number (0=UHF, 1=S, 2=X, 3=K, 4=Ka)
+fn parse_band(number: &str) -> Option<BandType> {
+    match number {
+        "0" => Some(BandType::UhfBand),
+        "1" => Some(BandType::SBand),
+        "2" => Some(BandType::XBand),
+        "3" => Some(BandType::KBand),
+        "4" => Some(BandType::KaBand),
+        _ => None,
+    }
+}
+
+/// Parse a procedure step: `status`, `telem`, `stop` or `band <n>`
+fn parse_procedure_step(parts: &[&str]) -> Option<Command> {
+    match parts {
+        ["status"] => Some(Command::system_status_request()),
+        ["telem"] => Some(Command::telemetry_request()),
+        ["stop"] => Some(Command::emergency_stop()),
+        ["band", number] => parse_band(number).map(Command::switch_band),
+        _ => None,
+    }
+}
+
+impl MissionControl {
+    /// Send a dictionary command, prompting for parameters not given
+    ///
+    /// A parameter given on the line that fails validation is prompted for
+    /// again; an empty answer cancels the command.
+    fn send_dictionary_command(&self, args: &[&str]) {
+        let Some(spec) = args.first().and_then(|name| dictionary::lookup(name)) else {
+            println!("Usage: send <command> [parameters...]");
+            for spec in COMMAND_DICTIONARY {
+                println!("  {}", spec.usage());
+            }
+            return;
+        };
+        if args.len() > spec.parameters.len() + 1 {
+            println!("Usage: send {}", spec.usage());
+            return;
+        }
+
+        let mut values = Vec::with_capacity(spec.parameters.len());
+        for (index, parameter) in spec.parameters.iter().enumerate() {
+            let given = match args.get(index + 1).map(|word| parameter.parse(word)) {
+                Some(Ok(value)) => Some(value),
+                Some(Err(e)) => {
+                    println!("  {}", e);
+                    None
+                }
+                None => None,
+            };
+            match given.or_else(|| prompt_parameter(parameter)) {
+                Some(value) => values.push(value),
+                None => {
+                    println!("{} cancelled", spec.name);
+                    return;
+                }
+            }
+        }
+
+        let sent = spec
+            .build(&values)
+            .and_then(|command| Command::from_space_command(&command))
+            .and_then(|command| self.ground_station.send_command(command));
+        if let Err(e) = sent {
+            eprintln!("Failed to send {}: {}", spec.name, e);
+        }
+    }
+
+    /// Show upcoming visibility windows, load element sets to serve, or
+    /// queue a command for a satellite's next pass
+    ///
+    /// Loading schedules the new satellites' passes at once rather than at
+    /// the next refresh of the event clock.
+    fn plan_passes(&self, args: &[&str]) {
+        let mut pass_scheduler = self.pass_scheduler.lock().unwrap();
+        match args {
+            [] => {
+                let windows = pass_scheduler
+                    .visibility_windows(mission_time_secs() as f64, PASS_PLANNING_HORIZON_S);
+                print!("{}", format_visibility_windows(&windows));
+            }
+            ["load", path] => {
+                let catalog = match conjunction::load_catalog(path) {
+                    Ok(catalog) => catalog,
+                    Err(e) => {
+                        eprintln!("Failed to load elements: {}", e);
+                        return;
+                    }
+                };
+                println!("Serving {} satellite(s) from {}", catalog.len(), path);
+                for elements in catalog {
+                    pass_scheduler.add_satellite(elements);
+                }
+                let planned = pass_scheduler.schedule(
+                    &mut self.scheduler.lock().unwrap(),
+                    mission_time_secs(),
+                    PASS_PLANNING_HORIZON_S,
+                );
+                for pass in planned {
+                    println!("  Event #{} {}", pass.event_id, pass.window);
+                }
+            }
+            ["queue", catalog_number, step @ ..] => {
+                let (Ok(catalog_number), Some(command)) =
+                    (catalog_number.parse::<u32>(), parse_procedure_step(step))
+                else {
+                    println!("Usage: vis queue <catalog no> <status|telem|stop|band <n>>");
+                    return;
+                };
+                if pass_scheduler.queue_command(catalog_number, command) {
+                    println!(
+                        "Queued for the next pass of {}: {} command(s)",
+                        catalog_number,
+                        pass_scheduler.queued(catalog_number).len()
+                    );
+                } else {
+                    println!(
+                        "Satellite {} not served (vis load <tle file>)",
+                        catalog_number
+                    );
+                }
+            }
+            _ => println!("Usage: vis [load <tle file>|queue <catalog no> <step>]"),
+        }
+    }
+
+    /// Screen a catalog for conjunctions, list pending avoidance drafts or
+    /// uplink the one the operator approves
+    ///
+    /// With no arguments, lists the drafts of the last screening. Screening
+    /// replaces them; approving sends one and removes it.
+    fn screen_conjunctions(&self, args: &[&str]) {
+        let mut conjunctions = self.conjunctions.lock().unwrap();
+        match args {
+            [] => {}
+            ["approve", index] => {
+                let Some(index) = index
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&i| i >= 1 && i <= conjunctions.len())
+                else {
+                    println!("No conjunction {}", index);
+                    return;
+                };
+                let conjunction = conjunctions.remove(index - 1);
+                let draft =
+                    conjunction.draft_avoidance(AVOIDANCE_TARGET_MISS_KM, AVOIDANCE_LEAD_TIME_S);
+                let sent = Command::from_space_command(&draft)
+                    .and_then(|command| self.ground_station.send_command(command));
+                match sent {
+                    Ok(()) => println!("Avoidance of {} uplinked", conjunction.name),
+                    Err(e) => eprintln!("Failed to uplink avoidance: {}", e),
+                }
+                return;
+            }
+            [own, catalog, rest @ ..] if rest.len() <= 1 => {
+                let mut config = ScreeningConfig::default();
+                if let Some(miss) = rest.first() {
+                    match miss.parse::<f64>() {
+                        Ok(miss_km) if miss_km > 0.0 => config.miss_distance_km = miss_km,
+                        _ => {
+                            println!("Invalid miss distance: {}", miss);
+                            return;
+                        }
+                    }
+                }
+                let loaded = conjunction::load_catalog(own).and_then(|own| {
+                    conjunction::load_catalog(catalog).map(|catalog| (own, catalog))
+                });
+                let (primary, catalog) = match loaded {
+                    Ok((own, catalog)) if !own.is_empty() => (own[0].clone(), catalog),
+                    Ok(_) => {
+                        println!("No elements in {}", own);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to load catalog: {}", e);
+                        return;
+                    }
+                };
+                *conjunctions =
+                    conjunction::screen(&primary, &catalog, &config, mission_time_secs() as f64);
+                println!(
+                    "{} object(s) screened, {} conjunction(s) below {} km",
+                    catalog.len(),
+                    conjunctions.len(),
+                    config.miss_distance_km
+                );
+            }
+            _ => {
+                println!("Usage: conj [<own tle> <catalog> [miss_km]|approve <n>]");
+                return;
+            }
+        }
+
+        for (index, conjunction) in conjunctions.iter().enumerate() {
+            let draft =
+                conjunction.draft_avoidance(AVOIDANCE_TARGET_MISS_KM, AVOIDANCE_LEAD_TIME_S);
+            println!("  {}. {}", index + 1, conjunction);
+            println!("     draft: {:?}", draft);
+        }
+    }
+
+    /// Run one console command, returning `false` when the console should exit
+    pub(super) fn execute(&self, parts: &[&str]) -> bool {
+        let Some(&verb) = parts.first() else {
+            return true;
+        };
+
+        match verb {
+            "status" => {
+                if let Err(e) = self
+                    .ground_station
+                    .send_command(Command::system_status_request())
+                {
+                    eprintln!("Failed to send status command: {}", e);
+                }
+            }
+            "telem" => {
+                if let Err(e) = self
+                    .ground_station
+                    .send_command(Command::telemetry_request())
+                {
+                    eprintln!("Failed to send telemetry command: {}", e);
+                }
+            }
+            "values" => {
+                for m in self.ground_station.latest_telemetry() {
+                    println!(
+                        "  0x{:04X} {:?} {} [{:?}]",
+                        m.measurement_id, m.value, m.unit, m.quality
+                    );
+                }
+            }
+            "archive" => match TelemetryQuery::parse(&parts[1..]) {
+                Some(query) => {
+                    let records = self.ground_station.query_telemetry(&query);
+                    println!("Archived packets: {}", records.len());
+                    for record in &records {
+                        let measurements = &record.packet.data.measurements;
+                        print!(
+                            "  {} ms APID 0x{:03X} seq {} ({} measurements)",
+                            record.received_ms,
+                            record.apid,
+                            record.packet.sequence,
+                            measurements.len()
+                        );
+                        match query
+                            .measurement_id
+                            .and_then(|id| measurements.iter().find(|m| m.measurement_id == id))
+                        {
+                            Some(m) => println!(": {:?} {} [{:?}]", m.value, m.unit, m.quality),
+                            None => println!(),
+                        }
+                    }
+                }
+                None => println!("Usage: archive [from <ms>] [until <ms>] [apid <n>] [id <n>]"),
+            },
+            "replay" => match TelemetryQuery::parse(&parts[1..]) {
+                Some(query) => {
+                    let replayed = self.ground_station.replay_telemetry(&query);
+                    println!("Replayed {} packets", replayed);
+                }
+                None => println!("Usage: replay [from <ms>] [until <ms>] [apid <n>] [id <n>]"),
+            },
+            "eps" => {
+                let measurements = self.ground_station.latest_telemetry();
+                match EpsSummary::from_measurements(&measurements) {
+                    Some(summary) => {
+                        print!("{}", format_eps_summary(&summary, &measurements));
+                        if let Some(attribution) =
+                            PowerAttribution::from_measurements(&measurements)
+                        {
+                            let suspect = self.ground_station.power_trend().depletion_suspect();
+                            print!(
+                                "{}",
+                                format_power_attribution(&summary, &attribution, suspect.as_ref())
+                            );
+                        }
+                    }
+                    None => println!("No EPS summary received"),
+                }
+            }
+            "seq" => {
+                print!(
+                    "{}",
+                    format_sequence_windows(&self.ground_station.downlink_sequences())
+                );
+                for (apid, sequence) in self.ground_station.uplink_sequences() {
+                    println!("Uplink APID {:#05X}: last count {}", apid, sequence);
+                }
+            }
+            "cop1" => match parts {
+                [_] => print!("{}", format_cop1(&self.ground_station.cop1())),
+                ["cop1", "init", sequence] => match sequence.parse::<u8>() {
+                    Ok(sequence) => match self.ground_station.set_cop1_sequence(sequence) {
+                        Ok(()) => println!(
+                            "Set V(R) {} sent; commanding resumes once the satellite confirms it",
+                            sequence
+                        ),
+                        Err(e) => eprintln!("Failed to send Set V(R): {}", e),
+                    },
+                    Err(_) => println!("Invalid frame sequence number: {}", sequence),
+                },
+                ["cop1", "unlock"] => match self.ground_station.unlock_cop1() {
+                    Ok(()) => {
+                        println!("Unlock sent; commanding resumes once the satellite confirms it")
+                    }
+                    Err(e) => eprintln!("Failed to send Unlock: {}", e),
+                },
+                _ => println!("Usage: cop1 [init <n>|unlock]"),
+            },
+            "sbn" => match self.ground_station.sbn_bridge() {
+                Some(bridge) => print!("{}", format_sbn_peers(&bridge, mission_time_ms())),
+                None => println!(
+                    "SBN bridge not configured (start with {} <addr>)",
+                    SBN_PEER_FLAG
+                ),
+            },
+            "mirror" => {
+                if self.ground_station.mirror_endpoints().is_empty() {
+                    println!(
+                        "No mirrors configured (start with {} <endpoint>)",
+                        MIRROR_FLAG
+                    );
+                } else {
+                    for endpoint in self.ground_station.mirror_endpoints() {
+                        println!("Mirroring to {}", endpoint);
+                    }
+                    print!(
+                        "{}",
+                        format_mirror_statistics(&self.ground_station.mirror_statistics())
+                    );
+                }
+            }
+            "redundancy" => match self.ground_station.redundancy() {
+                Some(link) => print!("{}", format_redundancy(&link, mission_time_ms())),
+                None => println!(
+                    "No standby pair configured (start with {} <addr>)",
+                    REDUNDANCY_PEER_FLAG
+                ),
+            },
+            "yamcs" => {
+                let dir = parts.get(1).copied().unwrap_or(YAMCS_EXPORT_DIR);
+                let config = self
+                    .ground_station
+                    .yamcs_config()
+                    .cloned()
+                    .unwrap_or_default();
+                match yamcs::export(std::path::Path::new(dir), &config) {
+                    Ok(paths) => {
+                        for path in paths {
+                            println!("Exported {}", path.display());
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to export YAMCS mission database: {}", e),
+                }
+                match self.ground_station.yamcs_config() {
+                    Some(config) => println!(
+                        "Measurements to {}, commands on {}",
+                        config.tm_addr, config.tc_addr
+                    ),
+                    None => println!("YAMCS link not configured (start with {})", YAMCS_FLAG),
+                }
+            }
+            "proto" => {
+                let dir = parts.get(1).copied().unwrap_or(PROTO_EXPORT_DIR);
+                match protobuf::export(std::path::Path::new(dir)) {
+                    Ok(path) => println!("Exported {}", path.display()),
+                    Err(e) => eprintln!("Failed to export protobuf schema: {}", e),
+                }
+                let mirrors: Vec<&MirrorEndpoint> = self
+                    .ground_station
+                    .mirror_endpoints()
+                    .iter()
+                    .filter(|endpoint| endpoint.encoding == MirrorEncoding::Protobuf)
+                    .collect();
+                if mirrors.is_empty() {
+                    println!(
+                        "No protobuf telemetry mirrors (start with {} <endpoint>?encoding=protobuf)",
+                        MIRROR_FLAG
+                    );
+                }
+                for endpoint in mirrors {
+                    println!("Protobuf telemetry to {}", endpoint);
+                }
+            }
+            "verify" => {
+                let archive = self.ground_station.verification_archive();
+                let summary = archive.summary();
+                println!(
+                    "Executions: {} ({} completed, {} failed, {} rejected), {} over budget",
+                    summary.total,
+                    summary.completed,
+                    summary.failed,
+                    summary.rejected,
+                    summary.overruns
+                );
+                print!(
+                    "{}",
+                    verification::format_latency(&archive.latency_percentiles())
+                );
+                if let Some(path) = parts.get(1) {
+                    match std::fs::write(path, archive.to_csv()) {
+                        Ok(()) => println!("Execution reports exported to {}", path),
+                        Err(e) => eprintln!("Failed to export execution reports: {}", e),
+                    }
+                }
+            }
+            "evlog" => {
+                let stats = self.ground_station.event_log_statistics();
+                println!(
+                    "Event log: {} events, {} bytes downlinked for {} raw ({:.1}%)",
+                    stats.records,
+                    stats.compressed_bytes,
+                    stats.raw_bytes,
+                    stats.ratio() * 100.0
+                );
+            }
+            "linkstats" => {
+                println!("Station:   {}", self.ground_station.link_stats());
+                match self.ground_station.satellite_link_stats() {
+                    Some(stats) => println!("Satellite: {}", stats),
+                    None => println!("Satellite: no link statistics received"),
+                }
+                match self.ground_station.downlink_policy_stats() {
+                    Some(stats) => println!("Downlink:  {}", stats),
+                    None => println!("Downlink:  no downlink policy statistics received"),
+                }
+            }
+            "operator" => match parts.get(1) {
+                Some(name) => {
+                    self.ground_station.set_operator(name);
+                    println!("Commands now recorded against {}", name);
+                }
+                None => println!("Operator: {}", self.ground_station.audit_log().operator()),
+            },
+            "dryrun" => {
+                match parts.get(1) {
+                    Some(&"on") => self.ground_station.set_dry_run(true),
+                    Some(&"off") => self.ground_station.set_dry_run(false),
+                    None => {}
+                    Some(_) => println!("Usage: dryrun [on|off]"),
+                }
+                if self.ground_station.is_dry_run() {
+                    println!("Dry run: uplinks are shown, not sent");
+                } else {
+                    println!("Live: uplinks are sent to the satellite");
+                }
+            }
+            "inspect" => match dry_run::parse_hex(&parts[1..].concat()) {
+                Some(bytes) => print!("{}{}", dry_run::hex_dump(&bytes), dry_run::decode(&bytes)),
+                None => println!("Usage: inspect <hex bytes>"),
+            },
+            "diag" => {
+                let archive = self.ground_station.diagnostics();
+                let id = parts.get(2).and_then(|id| id.parse::<u16>().ok());
+                match (parts.get(1), id) {
+                    (None, _) => {
+                        for dump in archive.dumps() {
+                            println!(
+                                "  Dump {}: 0x{:08X} {}/{} bytes",
+                                dump.dump_id,
+                                dump.address,
+                                dump.received_bytes(),
+                                dump.total_length
+                            );
+                        }
+                        for trace in archive.dwells() {
+                            println!(
+                                "  Dwell {}: {:?} 0x{:X}, {} samples, {} gaps{}",
+                                trace.dwell_id,
+                                trace.source,
+                                trace.target,
+                                trace.samples().count(),
+                                trace.gaps().len(),
+                                if trace.ended { ", ended" } else { "" }
+                            );
+                        }
+                    }
+                    (Some(&"dump"), Some(id)) => match archive.dump(id) {
+                        Some(dump) => match (dump.image(), parts.get(3)) {
+                            (Some(image), Some(path)) => match std::fs::write(path, image) {
+                                Ok(()) => println!("Memory dump {} exported to {}", id, path),
+                                Err(e) => eprintln!("Failed to export memory dump: {}", e),
+                            },
+                            (Some(image), None) => {
+                                println!("Memory dump {} from 0x{:08X}:", id, dump.address);
+                                print!("{}", dry_run::hex_dump(&image));
+                            }
+                            (None, _) => {
+                                for range in dump.missing() {
+                                    println!(
+                                        "  Missing 0x{:08X}..0x{:08X}",
+                                        dump.address + range.start,
+                                        dump.address + range.end
+                                    );
+                                }
+                            }
+                        },
+                        None => println!("No memory dump {}", id),
+                    },
+                    (Some(&"dwell"), Some(id)) => match archive.dwell(id) {
+                        Some(trace) => match parts.get(3) {
+                            Some(path) => match std::fs::write(path, trace.to_csv()) {
+                                Ok(()) => println!("Dwell {} exported to {}", id, path),
+                                Err(e) => eprintln!("Failed to export dwell: {}", e),
+                            },
+                            None => {
+                                for (index, offset_ms, value) in trace.samples() {
+                                    println!("  {:>5} {:>8} ms  {}", index, offset_ms, value);
+                                }
+                                for gap in trace.gaps() {
+                                    println!("  Gap: samples {}..{}", gap.start, gap.end);
+                                }
+                            }
+                        },
+                        None => println!("No dwell {}", id),
+                    },
+                    _ => println!("Usage: diag [dump|dwell <id> [file]]"),
+                }
+            }
+            "loopback" => match parts {
+                [_] => print!(
+                    "{}",
+                    format_loopback_results(&self.ground_station.loopback())
+                ),
+                ["loopback", "band", number, text @ ..] => match parse_band(number) {
+                    Some(band) => {
+                        let payload = if text.is_empty() {
+                            "LOOPBACK".to_string()
+                        } else {
+                            text.join(" ")
+                        };
+                        match self
+                            .ground_station
+                            .send_loopback_test(band, payload.as_bytes())
+                        {
+                            Ok(test_id) => println!("Loopback test {} sent", test_id),
+                            Err(e) => eprintln!("Failed to send loopback test: {}", e),
+                        }
+                    }
+                    None => println!("Invalid band number. Use 0-4."),
+                },
+                ["loopback", "offset", offset] => match offset.parse::<i64>() {
+                    Ok(offset_ms) => {
+                        self.ground_station.set_loopback_clock_offset(offset_ms);
+                        println!("Onboard clock offset set to {} ms", offset_ms);
+                    }
+                    Err(_) => println!("Invalid offset: {}", offset),
+                },
+                _ => println!("Usage: loopback [band <n> [text]|offset <ms>]"),
+            },
+            "audit" => {
+                let log = self.ground_station.audit_log();
+                println!("Audit log: {} records", log.records().len());
+                let export = match (parts.get(1), parts.get(2)) {
+                    (Some(&"csv"), Some(path)) => Some((path, log.to_csv())),
+                    (Some(&"json"), Some(path)) => Some((path, log.to_json())),
+                    (None, _) => None,
+                    _ => {
+                        println!("Usage: audit [csv|json <file>]");
+                        None
+                    }
+                };
+                if let Some((path, contents)) = export {
+                    match std::fs::write(path, contents) {
+                        Ok(()) => println!("Audit log exported to {}", path),
+                        Err(e) => eprintln!("Failed to export audit log: {}", e),
+                    }
+                }
+            }
+            "passes" => {
+                let reports = self.ground_station.pass_reports();
+                match parts.get(1).map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) => match reports.get(n) {
+                        Some(report) => println!("{}", report.to_markdown()),
+                        None => println!("No pass report {}", n),
+                    },
+                    Some(Err(_)) => println!("Usage: passes [n]"),
+                    None => {
+                        println!("Pass reports: {}", reports.len());
+                        for (n, report) in reports.iter().enumerate() {
+                            println!(
+                                "  {}: AOS {} {:.1} s, {} bytes down, {} up, {} commands, {} alarms",
+                                n,
+                                report.aos_unix_ms,
+                                report.duration_s(),
+                                report.bytes_downlinked,
+                                report.bytes_uplinked,
+                                report.commands_sent,
+                                report.alarms.len()
+                            );
+                        }
+                    }
+                }
+            }
+            "send" => self.send_dictionary_command(&parts[1..]),
+            "alias" => {
+                if parts.len() < 3 {
+                    println!("Usage: alias <name> <line>[; <line>...]");
+                    return true;
+                }
+                let steps = parts[2..].join(" ").split(';').map(String::from).collect();
+                let mut macros = self.macros.lock().unwrap();
+                match macros
+                    .define(parts[1], steps, CONSOLE_COMMANDS)
+                    .and_then(|_| macros.save(CONSOLE_CONFIG_FILE))
+                {
+                    Ok(()) => println!("Macro {} saved", parts[1]),
+                    Err(e) => eprintln!("Failed to define macro: {}", e),
+                }
+            }
+            "unalias" => {
+                let Some(name) = parts.get(1) else {
+                    println!("Usage: unalias <name>");
+                    return true;
+                };
+                let mut macros = self.macros.lock().unwrap();
+                if !macros.remove(name) {
+                    println!("No macro {}", name);
+                } else if let Err(e) = macros.save(CONSOLE_CONFIG_FILE) {
+                    eprintln!("Failed to save macros: {}", e);
+                }
+            }
+            "macros" => {
+                let macros = self.macros.lock().unwrap();
+                for name in macros.names() {
+                    println!(
+                        "  {} = {}",
+                        name,
+                        macros.get(name).unwrap_or_default().join("; ")
+                    );
+                }
+            }
+            "band" => {
+                if parts.len() < 2 {
+                    println!("Usage: band <0-4>");
+                    return true;
+                }
+
+                let band = match parse_band(parts[1]) {
+                    Some(band) => band,
+                    None => {
+                        println!("Invalid band number. Use 0-4.");
+                        return true;
+                    }
+                };
+
+                if let Err(e) = self.ground_station.send_command(Command::switch_band(band)) {
+                    eprintln!("Failed to send band switch command: {}", e);
+                }
+            }
+            "link" => match parts {
+                [_] => {
+                    let links = self.ground_station.link_configuration();
+                    for direction in [LinkDirection::Uplink, LinkDirection::Downlink] {
+                        let link = links.direction(direction);
+                        println!(
+                            "  {:<8} {:?} at {}% power, {} bps",
+                            direction.to_string(),
+                            link.band,
+                            link.power_level,
+                            link.data_rate_bps
+                        );
+                    }
+                }
+                [_, direction, band, power, rate] => {
+                    let direction = match *direction {
+                        "up" => LinkDirection::Uplink,
+                        "down" => LinkDirection::Downlink,
+                        _ => {
+                            println!("Direction must be up or down");
+                            return true;
+                        }
+                    };
+                    let (Some(band), Ok(power), Ok(rate)) =
+                        (parse_band(band), power.parse::<u8>(), rate.parse::<u64>())
+                    else {
+                        println!("Usage: link <up|down> <0-4> <power%> <bps>");
+                        return true;
+                    };
+                    match self
+                        .ground_station
+                        .set_link(direction, DirectionalLink::new(band, power, rate))
+                    {
+                        Ok(()) => {
+                            println!("Ground {} now on {:?}", direction, band);
+                            let margins = self.ground_station.margin_policy();
+                            if let Some(shortfall) = margins.check_power(power) {
+                                println!("Margin warning: {} {}", direction, shortfall);
+                            }
+                        }
+                        Err(e) => eprintln!("Failed to set {}: {}", direction, e),
+                    }
+                }
+                _ => println!("Usage: link [up|down <0-4> <power%> <bps>]"),
+            },
+            "budget" => match parts {
+                [_] => match self.ground_station.pass_volume_budget() {
+                    Some(budget) => {
+                        print!("{}", format_pass_budget(&budget));
+                        match self.ground_station.pass_volume_status() {
+                            Some(status) => println!("  {}", status),
+                            None => println!("  Pass not started"),
+                        }
+                    }
+                    None => println!("No pass budget (plan one with budget <s> <max elev°>)"),
+                },
+                [_, duration, elevation, rest @ ..] if rest.len() <= 1 => {
+                    let modcod = match rest.first() {
+                        Some(name) => match Modcod::lookup(name) {
+                            Some(modcod) => modcod,
+                            None => {
+                                let names: Vec<_> = MODCODS.iter().map(|m| m.name).collect();
+                                println!("Unknown MODCOD; one of {}", names.join(", "));
+                                return true;
+                            }
+                        },
+                        None => Modcod::default(),
+                    };
+                    let geometry = match (duration.parse::<u32>(), elevation.parse::<f64>()) {
+                        (Ok(duration), Ok(elevation)) => PassGeometry::arc(duration, elevation),
+                        _ => {
+                            println!("Usage: budget [<s> <max elev°> [modcod]]");
+                            return true;
+                        }
+                    };
+                    match geometry {
+                        Ok(geometry) => print!(
+                            "{}",
+                            format_pass_budget(
+                                &self.ground_station.plan_pass_volume(geometry, modcod)
+                            )
+                        ),
+                        Err(e) => eprintln!("Invalid pass geometry: {}", e),
+                    }
+                }
+                _ => println!("Usage: budget [<s> <max elev°> [modcod]]"),
+            },
+            "load" => {
+                if parts.len() < 2 {
+                    println!("Usage: load <file>");
+                    return true;
+                }
+
+                let load = match load_command_file(parts[1]) {
+                    Ok(load) => load,
+                    Err(e) => {
+                        eprintln!("Failed to read command load: {}", e);
+                        return true;
+                    }
+                };
+
+                match self
+                    .ground_station
+                    .uplink_command_load(&load, &LoadConstraints::default())
+                {
+                    Ok(manifest) if !manifest.is_fully_accepted() => {
+                        println!("Command load rejected, not uplinked:");
+                        display_load_manifest(&manifest);
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Failed to uplink command load: {}", e),
+                }
+            }
+            "retx" => {
+                let file_id = match parts.get(1).and_then(|p| p.parse::<u32>().ok()) {
+                    Some(id) => id,
+                    None => {
+                        println!("Usage: retx <file_id> [offset length]");
+                        return true;
+                    }
+                };
+
+                let pass_id = match self.ground_station.last_file_manifest() {
+                    Some(manifest) => manifest.pass_id,
+                    None => {
+                        println!("No file manifest received yet");
+                        return true;
+                    }
+                };
+
+                let mut request = RetransmitRequest::new(pass_id);
+                let added = match (
+                    parts.get(2).and_then(|p| p.parse::<u32>().ok()),
+                    parts.get(3).and_then(|p| p.parse::<u32>().ok()),
+                ) {
+                    (Some(offset), Some(length)) => {
+                        request.request_range(file_id, ByteRange::new(offset, length))
+                    }
+                    _ => request.request_file(file_id),
+                };
+
+                if let Err(e) =
+                    added.and_then(|_| self.ground_station.request_retransmission(&request))
+                {
+                    eprintln!("Failed to request retransmission: {}", e);
+                }
+            }
+            "forecast" => {
+                let Some(path) = parts.get(1) else {
+                    println!("Usage: forecast <file>");
+                    return true;
+                };
+
+                let sent = load_link_forecast(path)
+                    .and_then(|forecast| self.ground_station.uplink_link_forecast(&forecast));
+                if let Err(e) = sent {
+                    eprintln!("Failed to uplink link forecast: {}", e);
+                }
+            }
+            "autonomy" => {
+                let Some(path) = parts.get(1) else {
+                    println!("Usage: autonomy <file>");
+                    return true;
+                };
+
+                let sent = load_autonomy_rule(path)
+                    .and_then(|rule| self.ground_station.uplink_autonomy_rule(&rule));
+                if let Err(e) = sent {
+                    eprintln!("Failed to uplink autonomy rule: {}", e);
+                }
+            }
+            "vcsec" => {
+                let virtual_channel = parts.get(1).and_then(|p| p.parse::<u8>().ok());
+                let service = match parts.get(2).copied() {
+                    Some("clear") => Some(SecurityService::Clear),
+                    Some("auth") => Some(SecurityService::Authenticated),
+                    Some("enc") => Some(SecurityService::AuthenticatedEncryption),
+                    _ => None,
+                };
+                let key_id = parts.get(3).and_then(|p| p.parse::<u8>().ok()).unwrap_or(0);
+
+                let (virtual_channel, service) = match (virtual_channel, service) {
+                    (Some(vc), Some(service)) => (vc, service),
+                    _ => {
+                        println!("Usage: vcsec <vc> <clear|auth|enc> [key_id]");
+                        return true;
+                    }
+                };
+
+                let policy = VcSecurityPolicy::new(service, key_id);
+                if let Err(e) = self
+                    .ground_station
+                    .send_vc_security_policy(virtual_channel, policy)
+                {
+                    eprintln!("Failed to send security policy command: {}", e);
+                }
+            }
+            "keys" => {
+                if let Some(path) = parts.get(1) {
+                    match link_security::load_key_file(path) {
+                        Ok(keys) => self.ground_station.enable_link_security(keys),
+                        Err(e) => {
+                            eprintln!("Failed to load link keys: {}", e);
+                            return true;
+                        }
+                    }
+                }
+
+                let Some(security) = self.ground_station.link_security() else {
+                    println!("Link unprotected (load keys with keys <file>)");
+                    return true;
+                };
+                for key_id in 0..MAX_KEY_SLOTS as u8 {
+                    if let Some(generation) = security.generation(key_id) {
+                        println!("  Key slot {}: generation {}", key_id, generation);
+                    }
+                }
+            }
+            "rotkey" => {
+                let key_id = parts.get(1).and_then(|p| p.parse::<u8>().ok());
+                let generation = parts.get(2).and_then(|p| p.parse::<u32>().ok());
+                let rotation = match (key_id, generation) {
+                    (Some(key_id), Some(generation)) => KeyRotation { key_id, generation },
+                    _ => {
+                        println!("Usage: rotkey <slot> <generation>");
+                        return true;
+                    }
+                };
+
+                match self.ground_station.rotate_keys(rotation) {
+                    Ok(()) => println!(
+                        "Key slot {} rotated to generation {}",
+                        rotation.key_id, rotation.generation
+                    ),
+                    Err(e) => eprintln!("Failed to rotate link key: {}", e),
+                }
+            }
+            "event" => {
+                let kind = parts.get(1).and_then(|k| EventKind::from_name(k));
+                let delay = parts.get(2).and_then(|d| d.parse::<u64>().ok());
+                let (kind, delay) = match (kind, delay) {
+                    (Some(kind), Some(delay)) if parts.len() > 3 => (kind, delay),
+                    _ => {
+                        println!("Usage: event <maneuver|aos|deadline|other> <secs> <name>");
+                        return true;
+                    }
+                };
+
+                let name = parts[3..].join(" ");
+                let fire_at = mission_time_secs() + delay;
+                let id = self
+                    .scheduler
+                    .lock()
+                    .unwrap()
+                    .schedule(&name, kind, fire_at);
+                println!("Event #{} scheduled at {}", id, format_countdown(delay));
+            }
+            "proc" => {
+                let id = parts.get(1).and_then(|p| p.parse::<u32>().ok());
+                let step = parts.get(2..).and_then(parse_procedure_step);
+                let (id, step) = match (id, step) {
+                    (Some(id), Some(step)) => (id, step),
+                    _ => {
+                        println!("Usage: proc <id> <status|telem|stop|band <n>>");
+                        return true;
+                    }
+                };
+
+                if !self.scheduler.lock().unwrap().add_procedure_step(id, step) {
+                    println!("No pending event #{}", id);
+                }
+            }
+            "events" => {
+                let countdowns = self
+                    .scheduler
+                    .lock()
+                    .unwrap()
+                    .countdowns(mission_time_secs());
+                if countdowns.is_empty() {
+                    println!("No events scheduled");
+                }
+                for c in countdowns {
+                    println!(
+                        "  #{} {} {} ({:?}, {} procedure steps)",
+                        c.id,
+                        format_countdown(c.remaining_secs),
+                        c.name,
+                        c.kind,
+                        c.procedure_len
+                    );
+                }
+            }
+            "cancel" => {
+                let id = match parts.get(1).and_then(|p| p.parse::<u32>().ok()) {
+                    Some(id) => id,
+                    None => {
+                        println!("Usage: cancel <id>");
+                        return true;
+                    }
+                };
+
+                match self.scheduler.lock().unwrap().cancel(id) {
+                    Some(event) => println!("Event #{} {} cancelled", event.id, event.name),
+                    None => println!("No pending event #{}", id),
+                }
+            }
+            "conj" => self.screen_conjunctions(&parts[1..]),
+            "vis" => self.plan_passes(&parts[1..]),
+            "stop" => {
+                if let Err(e) = self.ground_station.send_command(Command::emergency_stop()) {
+                    eprintln!("Failed to send emergency stop: {}", e);
+                }
+            }
+            "quit" => {
+                println!("Mission Control shutting down");
+                return false;
+            }
+            _ => {
+                println!("Unknown command: {}", verb);
+            }
+        }
+        true
+    }
+}
+
+/// Prompt until the operator enters a valid value; `None` if they enter nothing
+fn prompt_parameter(parameter: &ParameterSpec) -> Option<Value> {
+    use std::io::{self, Write};
+
+    loop {
+        print!("  {} ({}): ", parameter.name, parameter.hint());
+        io::stdout().flush().ok()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).ok()?;
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        match parameter.parse(input) {
+            Ok(value) => return Some(value),
+            Err(e) => println!("  {}", e),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Memory dump and dwell reassembly
+//!
+//! The satellite downlinks a memory dump as segments and a dwell as batches
+//! of samples, each carrying its place in the whole. The archive collects
+//! them by dump or dwell ID in whatever order they arrive, so operators can
+//! see how much of a dump is in, which byte ranges are still missing, and
+//! the dwell trace so far. A segment or batch whose dump or dwell ID comes
+//! back with a different range or target starts a new entry: the onboard
+//! IDs restart at every boot.
+//!
+//! Like the verification archive, the archive does not read the clock: the
+//! caller passes the receipt time in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-DMP-002: Memory dump reassembly with missing ranges
+//! - FN-DWL-002: Dwell trace reassembly with gaps
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use space_comms_shared::diagnostics::{DwellBatch, DwellSource, MemoryDumpSegment};
+
+/// CSV header written by [`DwellTrace::to_csv`]
+pub const DWELL_CSV_HEADER: &str = "sample,offset_ms,value";
+
+/// Memory dump as reassembled on the ground
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDump {
+    /// Dump identifier
+    pub dump_id: u16,
+    /// First address of the dump
+    pub address: u32,
+    /// Length of the whole dump, bytes
+    pub total_length: u32,
+    /// Receipt time of the latest segment, milliseconds since the Unix epoch
+    pub updated_unix_ms: u64,
+    segments: BTreeMap<u32, Vec<u8>>,
+}
+
+impl MemoryDump {
+    fn new(segment: &MemoryDumpSegment) -> Self {
+        Self {
+            dump_id: segment.dump_id,
+            address: segment.address,
+            total_length: segment.total_length,
+            updated_unix_ms: 0,
+            segments: BTreeMap::new(),
+        }
+    }
+
+    /// Bytes received so far
+    pub fn received_bytes(&self) -> u32 {
+        self.total_length - self.missing().iter().map(|r| r.end - r.start).sum::<u32>()
+    }
+
+    /// Offsets within the dump not yet received, in address order
+    ///
+    /// # Requirements Traceability
+    /// - FN-DMP-002: Missing ranges named for retransmission or a new dump
+    pub fn missing(&self) -> Vec<Range<u32>> {
+        let mut missing = Vec::new();
+        let mut covered = 0;
+        for (&offset, data) in &self.segments {
+            if offset > covered {
+                missing.push(covered..offset);
+            }
+            covered = covered.max(offset + data.len() as u32);
+        }
+        if covered < self.total_length {
+            missing.push(covered..self.total_length);
+        }
+        missing
+    }
+
+    /// Whether every byte of the dump has been received
+    pub fn is_complete(&self) -> bool {
+        self.missing().is_empty()
+    }
+
+    /// Dump contents, once complete
+    pub fn image(&self) -> Option<Vec<u8>> {
+        if !self.is_complete() {
+            return None;
+        }
+        let mut image = vec![0; self.total_length as usize];
+        for (&offset, data) in &self.segments {
+            let start = offset as usize;
+            image[start..start + data.len()].copy_from_slice(data);
+        }
+        Some(image)
+    }
+}
+
+/// Dwell trace as reassembled on the ground
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwellTrace {
+    /// Dwell identifier
+    pub dwell_id: u16,
+    /// What was sampled
+    pub source: DwellSource,
+    /// Memory address or measurement ID
+    pub target: u32,
+    /// Sample interval, milliseconds
+    pub interval_ms: u16,
+    /// Whether the final batch has arrived
+    pub ended: bool,
+    /// Receipt time of the latest batch, milliseconds since the Unix epoch
+    pub updated_unix_ms: u64,
+    samples: BTreeMap<u32, f64>,
+}
+
+impl DwellTrace {
+    fn new(batch: &DwellBatch) -> Self {
+        Self {
+            dwell_id: batch.dwell_id,
+            source: batch.source,
+            target: batch.target,
+            interval_ms: batch.interval_ms,
+            ended: false,
+            updated_unix_ms: 0,
+            samples: BTreeMap::new(),
+        }
+    }
+
+    /// Samples received, in time order: sample index, milliseconds from the
+    /// dwell start, and value
+    pub fn samples(&self) -> impl Iterator<Item = (u32, u64, f64)> + '_ {
+        self.samples.iter().map(move |(&index, &value)| {
+            (index, u64::from(index) * u64::from(self.interval_ms), value)
+        })
+    }
+
+    /// Sample indices with no sample before the latest one received: not
+    /// taken onboard, or lost on the downlink
+    ///
+    /// # Requirements Traceability
+    /// - FN-DWL-002: Gaps in the trace shown, not interpolated
+    pub fn gaps(&self) -> Vec<Range<u32>> {
+        let mut gaps = Vec::new();
+        let mut expected = 0;
+        for &index in self.samples.keys() {
+            if index > expected {
+                gaps.push(expected..index);
+            }
+            expected = index + 1;
+        }
+        gaps
+    }
+
+    /// Export the trace as CSV with [`DWELL_CSV_HEADER`]
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(DWELL_CSV_HEADER);
+        out.push('\n');
+        for (index, offset_ms, value) in self.samples() {
+            out.push_str(&format!("{},{},{}\n", index, offset_ms, value));
+        }
+        out
+    }
+}
+
+/// Memory dumps and dwell traces received from the satellite
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsArchive {
+    dumps: BTreeMap<u16, MemoryDump>,
+    dwells: BTreeMap<u16, DwellTrace>,
+}
+
+impl DiagnosticsArchive {
+    /// Create an empty archive
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a dump segment received at `received_unix_ms`
+    ///
+    /// # Returns
+    /// * `&MemoryDump` - The dump the segment belongs to
+    ///
+    /// # Requirements Traceability
+    /// - FN-DMP-002: Segments placed by offset in any arrival order
+    pub fn record_segment(
+        &mut self,
+        segment: &MemoryDumpSegment,
+        received_unix_ms: u64,
+    ) -> &MemoryDump {
+        let dump = self
+            .dumps
+            .entry(segment.dump_id)
+            .or_insert_with(|| MemoryDump::new(segment));
+        if dump.address != segment.address || dump.total_length != segment.total_length {
+            *dump = MemoryDump::new(segment);
+        }
+        dump.segments.insert(segment.offset, segment.data.to_vec());
+        dump.updated_unix_ms = received_unix_ms;
+        dump
+    }
+
+    /// Add a dwell batch received at `received_unix_ms`
+    ///
+    /// # Returns
+    /// * `&DwellTrace` - The trace the batch belongs to
+    ///
+    /// # Requirements Traceability
+    /// - FN-DWL-002: Batches placed by sample index in any arrival order
+    pub fn record_batch(&mut self, batch: &DwellBatch, received_unix_ms: u64) -> &DwellTrace {
+        let trace = self
+            .dwells
+            .entry(batch.dwell_id)
+            .or_insert_with(|| DwellTrace::new(batch));
+        if (trace.source, trace.target, trace.interval_ms)
+            != (batch.source, batch.target, batch.interval_ms)
+        {
+            *trace = DwellTrace::new(batch);
+        }
+        for index in 0..batch.samples.len() {
+            if let Some(value) = batch.value(index) {
+                trace
+                    .samples
+                    .insert(batch.first_index + index as u32, value);
+            }
+        }
+        trace.ended |= batch.last;
+        trace.updated_unix_ms = received_unix_ms;
+        trace
+    }
+
+    /// Dumps by dump ID
+    pub fn dumps(&self) -> impl Iterator<Item = &MemoryDump> {
+        self.dumps.values()
+    }
+
+    /// Dwell traces by dwell ID
+    pub fn dwells(&self) -> impl Iterator<Item = &DwellTrace> {
+        self.dwells.values()
+    }
+
+    /// Dump with `dump_id`
+    pub fn dump(&self, dump_id: u16) -> Option<&MemoryDump> {
+        self.dumps.get(&dump_id)
+    }
+
+    /// Dwell trace with `dwell_id`
+    pub fn dwell(&self, dwell_id: u16) -> Option<&DwellTrace> {
+        self.dwells.get(&dwell_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::diagnostics::DwellRequest;
+
+    fn segment(dump_id: u16, offset: u32, data: &[u8]) -> MemoryDumpSegment {
+        MemoryDumpSegment {
+            dump_id,
+            address: 0x0800_0000,
+            total_length: 10,
+            offset,
+            data: data.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn test_dump_reassembly_out_of_order() {
+        let mut archive = DiagnosticsArchive::new();
+        let dump = archive.record_segment(&segment(1, 6, &[6, 7, 8, 9]), 1_000);
+        assert_eq!(dump.missing(), vec![0..6]);
+        assert_eq!(dump.received_bytes(), 4);
+        assert!(dump.image().is_none());
+
+        archive.record_segment(&segment(1, 0, &[0, 1]), 2_000);
+        assert_eq!(archive.dump(1).unwrap().missing(), vec![2..6]);
+
+        let dump = archive.record_segment(&segment(1, 2, &[2, 3, 4, 5]), 3_000);
+        assert!(dump.is_complete());
+        assert_eq!(dump.image().unwrap(), (0..10).collect::<Vec<u8>>());
+        assert_eq!(dump.updated_unix_ms, 3_000);
+
+        // The same ID over a different range is a new dump after a reboot
+        let mut restarted = segment(1, 0, &[0xFF; 4]);
+        restarted.address = 0x2000_0000;
+        let dump = archive.record_segment(&restarted, 4_000);
+        assert_eq!(dump.missing(), vec![4..10]);
+        assert_eq!(archive.dumps().count(), 1);
+    }
+
+    #[test]
+    fn test_dwell_trace_with_gaps() {
+        let request = DwellRequest {
+            source: DwellSource::Measurement,
+            target: 0x0010,
+            interval_ms: 20,
+            duration_s: 1,
+        };
+        let mut later = DwellBatch::new(4, &request, 5);
+        later.samples.push(28.0f32.to_bits()).unwrap();
+        later.last = true;
+        let mut first = DwellBatch::new(4, &request, 0);
+        first.samples.push(27.5f32.to_bits()).unwrap();
+        first.samples.push(27.75f32.to_bits()).unwrap();
+
+        let mut archive = DiagnosticsArchive::new();
+        archive.record_batch(&later, 1_000);
+        let trace = archive.record_batch(&first, 1_100);
+        assert!(trace.ended);
+        assert_eq!(trace.gaps(), vec![2..5]);
+        assert_eq!(
+            trace.samples().collect::<Vec<_>>(),
+            vec![(0, 0, 27.5), (1, 20, 27.75), (5, 100, 28.0)]
+        );
+        assert_eq!(
+            trace.to_csv(),
+            "sample,offset_ms,value\n0,0,27.5\n1,20,27.75\n5,100,28\n"
+        );
+    }
+}
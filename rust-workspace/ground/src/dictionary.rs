@@ -0,0 +1,642 @@
+//! Command dictionary
+//!
+//! Commands operators can send by name from the console, with the kind of
+//! each parameter. Each entry names a [`SpaceCommand`] variant: parameter
+//! text is first checked against its kind, then the values are decoded as
+//! that variant, so the console only builds commands the shared definition
+//! accepts. The dictionary also drives console completion of command names
+//! and enumerated parameter values, and serializes to JSON for tools
+//! outside the console.
+//!
+//! # Requirements Traceability
+//! - FN-DIC-001: Parameter validation against the command dictionary
+//! - FN-DIC-002: Completion of command names and parameter values
+//! - FN-DIC-003: Dictionary exported for scripted commanding
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use space_comms_shared::{
+    commands::SpaceCommand,
+    diagnostics::{MAX_DUMP_LENGTH, MAX_DWELL_DURATION_S},
+    Result, SpaceCommError,
+};
+
+/// Kind of value a command parameter takes
+///
+/// Serialized as `{"type": "choice", "value": [...]}`, with `value` the
+/// choices or maximum where the kind has one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum ParameterKind {
+    /// One of a fixed set of names, matched without regard to case
+    Choice(&'static [&'static str]),
+    /// Unsigned integer no greater than the maximum
+    Unsigned(u64),
+    /// Unsigned integer no greater than the maximum, or `none`
+    OptionalUnsigned(u64),
+    /// Finite decimal number
+    Float,
+    /// `true`/`false`, also accepted as `yes`/`no` or `on`/`off`
+    Flag,
+}
+
+/// One parameter of a dictionary command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ParameterSpec {
+    /// Field name in the shared command definition
+    pub name: &'static str,
+    /// Values the parameter accepts
+    pub kind: ParameterKind,
+}
+
+/// A command operators can send by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CommandSpec {
+    /// [`SpaceCommand`] variant name
+    pub name: &'static str,
+    /// Parameters in the order they are given on the console
+    pub parameters: &'static [ParameterSpec],
+}
+
+const FLAG_VALUES: &[&str] = &["true", "false"];
+const NONE_VALUE: &[&str] = &["none"];
+
+const EMERGENCY_REASONS: &[&str] = &[
+    "SystemFailure",
+    "PowerCritical",
+    "ThermalEmergency",
+    "AttitudeLoss",
+    "CommunicationLoss",
+    "CollisionImminent",
+    "GroundCommand",
+    "OnboardFailsafe",
+];
+const SAFE_MODE_LEVELS: &[&str] = &["Level1", "Level2", "Level3", "Level4"];
+const SUBSYSTEMS: &[&str] = &[
+    "Power",
+    "Communications",
+    "AttitudeControl",
+    "Propulsion",
+    "ThermalControl",
+    "PayloadControl",
+    "OnboardComputer",
+    "Navigation",
+    "SolarPanels",
+    "BatteryManagement",
+    "Antenna",
+    "Sensors",
+    "DataStorage",
+    "CommandProcessor",
+    "Telemetry",
+    "ErrorCorrection",
+];
+const RESET_TYPES: &[&str] = &[
+    "SoftReset",
+    "HardReset",
+    "WatchdogReset",
+    "PowerCycle",
+    "FactoryReset",
+];
+const LINK_DIRECTIONS: &[&str] = &["Uplink", "Downlink"];
+const BANDS: &[&str] = &["UhfBand", "SBand", "XBand", "KBand", "KaBand"];
+const MODULATIONS: &[&str] = &["BPSK", "QPSK", "PSK8", "QAM16", "QAM64", "OFDM"];
+const DEPLOYABLES: &[&str] = &[
+    "SolarPanel",
+    "Antenna",
+    "Magnetometer",
+    "Sensor",
+    "CameraLens",
+    "Radiator",
+];
+const SECURITY_SERVICES: &[&str] = &["Clear", "Authenticated", "AuthenticatedEncryption"];
+const MISSION_PHASES: &[&str] = &[
+    "Leop",
+    "Commissioning",
+    "NominalOps",
+    "Extended",
+    "Decommissioning",
+];
+const TELEMETRY_TYPES: &[&str] = &[
+    "Health",
+    "Position",
+    "Attitude",
+    "Power",
+    "Thermal",
+    "Communications",
+    "Payload",
+    "Navigation",
+    "Diagnostics",
+    "Science",
+];
+const DATA_TYPES: &[&str] = &[
+    "Telemetry",
+    "Science",
+    "Images",
+    "Logs",
+    "Configuration",
+    "Diagnostic",
+];
+const STORAGE_LOCATIONS: &[&str] = &[
+    "VolatileMemory",
+    "NonVolatileMemory",
+    "BackupStorage",
+    "ExternalStorage",
+];
+const STATUS_TYPES: &[&str] = &[
+    "SystemHealth",
+    "MissionStatus",
+    "ComponentStatus",
+    "PowerStatus",
+    "CommunicationStatus",
+    "Full",
+];
+const DWELL_SOURCES: &[&str] = &["Memory", "Measurement"];
+const DOWNLINK_POLICIES: &[&str] = &[
+    "Priority",
+    "NewestFirst",
+    "HighestValueFirst",
+    "OldestFirst",
+];
+const REPORT_FORMATS: &[&str] = &["Binary", "Json", "Csv", "Compressed"];
+const TIME_SOURCES: &[&str] = &[
+    "GroundStation",
+    "Gps",
+    "OnboardClock",
+    "NetworkTime",
+    "AtomicClock",
+];
+const MAINTENANCE_TYPES: &[&str] = &[
+    "SystemCheck",
+    "Calibration",
+    "SoftwareUpdate",
+    "HardwareTest",
+    "Performance",
+    "Preventive",
+];
+
+const fn param(name: &'static str, kind: ParameterKind) -> ParameterSpec {
+    ParameterSpec { name, kind }
+}
+
+/// Commands with scalar parameters that can be sent from the console
+///
+/// Commands taking lists, strings or nested commands are sent in command
+/// load files instead. New commands are appended, as the protobuf `Command`
+/// numbers each command by its position here.
+pub const COMMAND_DICTIONARY: &[CommandSpec] = &[
+    CommandSpec {
+        name: "EmergencyAbort",
+        parameters: &[
+            param("reason", ParameterKind::Choice(EMERGENCY_REASONS)),
+            param(
+                "confirmation_code",
+                ParameterKind::Unsigned(u32::MAX as u64),
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "ActivateSafeMode",
+        parameters: &[
+            param("safe_mode_level", ParameterKind::Choice(SAFE_MODE_LEVELS)),
+            param(
+                "duration_seconds",
+                ParameterKind::OptionalUnsigned(u32::MAX as u64),
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "HaltSubsystem",
+        parameters: &[
+            param("subsystem", ParameterKind::Choice(SUBSYSTEMS)),
+            param("graceful_shutdown", ParameterKind::Flag),
+            param("timeout_seconds", ParameterKind::Unsigned(u32::MAX as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "ResetSystem",
+        parameters: &[
+            param("component", ParameterKind::Unsigned(u16::MAX as u64)),
+            param("reset_type", ParameterKind::Choice(RESET_TYPES)),
+            param("preserve_config", ParameterKind::Flag),
+        ],
+    },
+    CommandSpec {
+        name: "ReconfigureComm",
+        parameters: &[
+            param("direction", ParameterKind::Choice(LINK_DIRECTIONS)),
+            param("band", ParameterKind::Choice(BANDS)),
+            param("frequency_hz", ParameterKind::Unsigned(u64::MAX)),
+            param("power_level", ParameterKind::Unsigned(100)),
+            param("data_rate_bps", ParameterKind::Unsigned(u64::MAX)),
+            param("modulation", ParameterKind::Choice(MODULATIONS)),
+            param("error_correction", ParameterKind::Flag),
+        ],
+    },
+    CommandSpec {
+        name: "Deploy",
+        parameters: &[
+            param("deployable", ParameterKind::Choice(DEPLOYABLES)),
+            param("deployment_angle", ParameterKind::Float),
+            param("deployment_rate", ParameterKind::Float),
+            param("force_limit", ParameterKind::Float),
+        ],
+    },
+    CommandSpec {
+        name: "SetVcSecurityPolicy",
+        parameters: &[
+            param("virtual_channel", ParameterKind::Unsigned(u8::MAX as u64)),
+            param("service", ParameterKind::Choice(SECURITY_SERVICES)),
+            param("key_id", ParameterKind::Unsigned(u8::MAX as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "SetMissionPhase",
+        parameters: &[param("phase", ParameterKind::Choice(MISSION_PHASES))],
+    },
+    CommandSpec {
+        name: "SetAutonomyRule",
+        parameters: &[
+            param("rule_id", ParameterKind::Unsigned(u16::MAX as u64)),
+            param("enabled", ParameterKind::Flag),
+        ],
+    },
+    CommandSpec {
+        name: "RequestTelemetry",
+        parameters: &[
+            param("telemetry_type", ParameterKind::Choice(TELEMETRY_TYPES)),
+            param("sampling_rate_hz", ParameterKind::Float),
+            param("duration_seconds", ParameterKind::Unsigned(u32::MAX as u64)),
+            param("compression", ParameterKind::Flag),
+        ],
+    },
+    CommandSpec {
+        name: "StoreData",
+        parameters: &[
+            param("data_type", ParameterKind::Choice(DATA_TYPES)),
+            param("storage_location", ParameterKind::Choice(STORAGE_LOCATIONS)),
+            param("compression_level", ParameterKind::Unsigned(u8::MAX as u64)),
+            param("encryption", ParameterKind::Flag),
+            param("science_value", ParameterKind::Unsigned(u8::MAX as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "DumpMemory",
+        parameters: &[
+            param("address", ParameterKind::Unsigned(u32::MAX as u64)),
+            param("length", ParameterKind::Unsigned(MAX_DUMP_LENGTH as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "DwellSample",
+        parameters: &[
+            param("source", ParameterKind::Choice(DWELL_SOURCES)),
+            param("target", ParameterKind::Unsigned(u32::MAX as u64)),
+            param("interval_ms", ParameterKind::Unsigned(u16::MAX as u64)),
+            param(
+                "duration_s",
+                ParameterKind::Unsigned(MAX_DWELL_DURATION_S as u64),
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "SendStatus",
+        parameters: &[
+            param("status_type", ParameterKind::Choice(STATUS_TYPES)),
+            param("include_diagnostics", ParameterKind::Flag),
+            param("format", ParameterKind::Choice(REPORT_FORMATS)),
+        ],
+    },
+    CommandSpec {
+        name: "UpdateTime",
+        parameters: &[
+            param("utc_time", ParameterKind::Unsigned(u64::MAX)),
+            param("time_source", ParameterKind::Choice(TIME_SOURCES)),
+            param(
+                "precision_microseconds",
+                ParameterKind::Unsigned(u32::MAX as u64),
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "PerformMaintenance",
+        parameters: &[
+            param("maintenance_type", ParameterKind::Choice(MAINTENANCE_TYPES)),
+            param("automated", ParameterKind::Flag),
+            param(
+                "estimated_duration",
+                ParameterKind::Unsigned(u32::MAX as u64),
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "ListFiles",
+        parameters: &[param("data_type", ParameterKind::Choice(DATA_TYPES))],
+    },
+    CommandSpec {
+        name: "DeleteFile",
+        parameters: &[
+            param("data_type", ParameterKind::Choice(DATA_TYPES)),
+            param("file_id", ParameterKind::Unsigned(u32::MAX as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "SetRetention",
+        parameters: &[
+            param("data_type", ParameterKind::Choice(DATA_TYPES)),
+            param("retention_s", ParameterKind::Unsigned(u32::MAX as u64)),
+        ],
+    },
+    CommandSpec {
+        name: "SetDownlinkPolicy",
+        parameters: &[param("policy", ParameterKind::Choice(DOWNLINK_POLICIES))],
+    },
+];
+
+/// Find a dictionary command by name, ignoring case
+pub fn lookup(name: &str) -> Option<&'static CommandSpec> {
+    COMMAND_DICTIONARY
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+impl ParameterSpec {
+    /// Check operator text against the parameter kind
+    ///
+    /// # Returns
+    /// * `Result<Value>` - Value for the command definition, or configuration
+    ///   error naming the parameter
+    ///
+    /// # Requirements Traceability
+    /// - FN-DIC-001: Values are checked before a command is built
+    pub fn parse(&self, text: &str) -> Result<Value> {
+        let invalid = |reason| SpaceCommError::ConfigurationError {
+            parameter: self.name,
+            value: "<operator input>",
+            reason,
+        };
+        let unsigned = |max: u64| {
+            text.parse::<u64>()
+                .ok()
+                .filter(|value| *value <= max)
+                .map(Value::from)
+                .ok_or_else(|| invalid("not an unsigned integer in range"))
+        };
+
+        match self.kind {
+            ParameterKind::Choice(options) => options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(text))
+                .map(|option| Value::from(*option))
+                .ok_or_else(|| invalid("not one of the listed values")),
+            ParameterKind::Unsigned(max) => unsigned(max),
+            ParameterKind::OptionalUnsigned(_) if text.eq_ignore_ascii_case("none") => {
+                Ok(Value::Null)
+            }
+            ParameterKind::OptionalUnsigned(max) => unsigned(max),
+            ParameterKind::Float => text
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(Value::from)
+                .ok_or_else(|| invalid("not a finite number")),
+            ParameterKind::Flag => match text.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" => Ok(Value::Bool(true)),
+                "false" | "no" | "off" => Ok(Value::Bool(false)),
+                _ => Err(invalid("not true or false")),
+            },
+        }
+    }
+
+    /// Short description of the accepted values for prompts
+    pub fn hint(&self) -> String {
+        match self.kind {
+            ParameterKind::Choice(options) => options.join("|"),
+            ParameterKind::Unsigned(max) => format!("0-{}", max),
+            ParameterKind::OptionalUnsigned(max) => format!("0-{} or none", max),
+            ParameterKind::Float => "number".to_string(),
+            ParameterKind::Flag => "true|false".to_string(),
+        }
+    }
+
+    /// Values offered by completion
+    pub fn completions(&self) -> &'static [&'static str] {
+        match self.kind {
+            ParameterKind::Choice(options) => options,
+            ParameterKind::Flag => FLAG_VALUES,
+            ParameterKind::OptionalUnsigned(_) => NONE_VALUE,
+            ParameterKind::Unsigned(_) | ParameterKind::Float => &[],
+        }
+    }
+}
+
+impl CommandSpec {
+    /// Build the command from one value per parameter
+    ///
+    /// # Arguments
+    /// * `values` - Values from [`ParameterSpec::parse`], in parameter order
+    ///
+    /// # Returns
+    /// * `Result<SpaceCommand>` - Command, or configuration error if a value
+    ///   is missing or the values do not form the command
+    pub fn build(&self, values: &[Value]) -> Result<SpaceCommand> {
+        if values.len() != self.parameters.len() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: self.name,
+                value: "<operator input>",
+                reason: "wrong number of parameters",
+            });
+        }
+
+        let fields: Map<String, Value> = self
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.to_string())
+            .zip(values.iter().cloned())
+            .collect();
+        let mut command = Map::new();
+        command.insert(self.name.to_string(), Value::Object(fields));
+
+        serde_json::from_value(Value::Object(command)).map_err(|_| {
+            SpaceCommError::ConfigurationError {
+                parameter: self.name,
+                value: "<operator input>",
+                reason: "parameters do not form a valid command",
+            }
+        })
+    }
+
+    /// Parse and build the command from operator text, one word per parameter
+    pub fn parse(&self, words: &[&str]) -> Result<SpaceCommand> {
+        let values = self
+            .parameters
+            .iter()
+            .zip(words)
+            .map(|(parameter, word)| parameter.parse(word))
+            .collect::<Result<Vec<Value>>>()?;
+        self.build(&values)
+    }
+
+    /// Usage line listing the parameters and their values
+    pub fn usage(&self) -> String {
+        self.parameters
+            .iter()
+            .fold(self.name.to_string(), |mut usage, parameter| {
+                usage.push_str(&format!(" <{}: {}>", parameter.name, parameter.hint()));
+                usage
+            })
+    }
+}
+
+/// Candidates for the next `send` argument after `args`
+///
+/// The first argument is a command name; later arguments are that command's
+/// parameters in order.
+///
+/// # Requirements Traceability
+/// - FN-DIC-002: Completion follows the dictionary
+pub fn argument_candidates(args: &[&str]) -> Vec<&'static str> {
+    match args.split_first() {
+        None => COMMAND_DICTIONARY.iter().map(|spec| spec.name).collect(),
+        Some((name, given)) => lookup(name)
+            .and_then(|spec| spec.parameters.get(given.len()))
+            .map(|parameter| parameter.completions().to_vec())
+            .unwrap_or_default(),
+    }
+}
+
+/// Candidates starting with `partial`, ignoring case, in sorted order
+pub fn complete<'a>(partial: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let partial = partial.to_ascii_lowercase();
+    let mut matches: Vec<&str> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.to_ascii_lowercase().starts_with(&partial))
+        .collect();
+    matches.sort_unstable();
+    matches.dedup();
+    matches
+}
+
+/// Longest prefix shared by all matches, ignoring case
+pub fn common_prefix<'a>(matches: &[&'a str]) -> &'a str {
+    let Some((first, rest)) = matches.split_first() else {
+        return "";
+    };
+    let len = rest.iter().fold(first.len(), |len, other| {
+        first
+            .bytes()
+            .zip(other.bytes())
+            .take(len)
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count()
+    });
+    &first[..len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Command;
+    use space_comms_shared::{commands::SafeModeLevel, messaging::MessagePriority};
+
+    /// A valid word for each parameter, with `choice` picking enumerated values
+    fn sample_words(spec: &CommandSpec, choice: usize) -> Vec<&'static str> {
+        spec.parameters
+            .iter()
+            .map(|parameter| match parameter.kind {
+                ParameterKind::Choice(options) => options[choice % options.len()],
+                ParameterKind::Unsigned(_) => "7",
+                ParameterKind::OptionalUnsigned(_) => "none",
+                ParameterKind::Float => "1.5",
+                ParameterKind::Flag => "true",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_dictionary_value_builds_its_command() {
+        for spec in COMMAND_DICTIONARY {
+            let widest = spec
+                .parameters
+                .iter()
+                .map(|parameter| parameter.completions().len())
+                .max()
+                .unwrap_or(1);
+            for choice in 0..widest {
+                let command = spec.parse(&sample_words(spec, choice));
+                assert!(command.is_ok(), "{} choice {}", spec.name, choice);
+                assert!(format!("{:?}", command.unwrap()).starts_with(spec.name));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parameter_validation() {
+        let spec = lookup("reconfigurecomm").unwrap();
+        let power = &spec.parameters[3];
+        assert_eq!(power.parse("100").unwrap(), Value::from(100u64));
+        assert!(power.parse("101").is_err());
+        assert!(power.parse("-1").is_err());
+        assert!(spec.parameters[0].parse("Sideways").is_err());
+        assert!(spec.parameters[1].parse("LBand").is_err());
+        assert_eq!(spec.parameters[6].parse("on").unwrap(), Value::Bool(true));
+        assert!(spec
+            .parse(&["Downlink", "XBand", "8400000000", "80"])
+            .is_err());
+
+        let safe_mode = lookup("ActivateSafeMode").unwrap();
+        assert_eq!(
+            safe_mode.usage(),
+            "ActivateSafeMode <safe_mode_level: Level1|Level2|Level3|Level4> \
+             <duration_seconds: 0-4294967295 or none>"
+        );
+        assert_eq!(
+            safe_mode.parse(&["level2", "600"]).unwrap(),
+            SpaceCommand::ActivateSafeMode {
+                safe_mode_level: SafeModeLevel::Level2,
+                duration_seconds: Some(600),
+            }
+        );
+    }
+
+    #[test]
+    fn test_dictionary_command_encoding() {
+        let command = lookup("ActivateSafeMode")
+            .unwrap()
+            .parse(&["Level2", "none"])
+            .unwrap();
+        let encoded = Command::from_space_command(&command).unwrap();
+        assert_eq!(encoded.command_id, 0x0003);
+        assert_eq!(encoded.priority, MessagePriority::Emergency);
+        let decoded: SpaceCommand = serde_json::from_slice(&encoded.parameters).unwrap();
+        assert_eq!(decoded, command);
+    }
+
+    #[test]
+    fn test_completion() {
+        let names = complete("act", argument_candidates(&[]));
+        assert_eq!(names, vec!["ActivateSafeMode"]);
+        assert_eq!(
+            complete("l", argument_candidates(&["ActivateSafeMode"])),
+            SAFE_MODE_LEVELS.to_vec()
+        );
+        assert_eq!(
+            argument_candidates(&["ActivateSafeMode", "Level2"]),
+            vec!["none"]
+        );
+        assert!(argument_candidates(&["Unknown"]).is_empty());
+
+        assert_eq!(common_prefix(&["SendStatus", "SetVcSecurityPolicy"]), "Se");
+        assert_eq!(common_prefix(&["Level1", "level2"]), "Level");
+        assert_eq!(common_prefix(&[]), "");
+    }
+
+    #[test]
+    fn test_dictionary_exports_as_json() {
+        let exported = serde_json::to_value(lookup("ActivateSafeMode").unwrap()).unwrap();
+        assert_eq!(exported["name"], "ActivateSafeMode");
+        assert_eq!(exported["parameters"][0]["kind"]["type"], "choice");
+        assert_eq!(exported["parameters"][0]["kind"]["value"][0], "Level1");
+        let flag = serde_json::to_value(ParameterKind::Flag).unwrap();
+        assert_eq!(flag, serde_json::json!({"type": "flag"}));
+    }
+}
@@ -0,0 +1,585 @@
+//! Console rendering of downlinked products
+//!
+//! The `format_*` functions return the text the mission control console
+//! shows; the `display_*` functions print a product as the telemetry
+//! receiver takes it off the downlink.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (band shown with each packet)
+//! - REQ-NF-004: Power Management (power budget at a glance)
+
+use space_comms_shared::{
+    command_load::LoadManifest,
+    cop1::Fop,
+    eps::{decode_eps, EpsField, EpsSummary, EPS_APID},
+    event_log::CompressionStats,
+    execution_report::{ExecutionReport, ExecutionResult},
+    file_downlink::FileManifest,
+    link_stats::decode_link_stats,
+    loopback::LoopbackResult,
+    mass_memory::{directory_name, FileListing},
+    power_attribution::decode_power_attribution,
+    rf_housekeeping::{
+        decode_lock_recovery, decode_rf_housekeeping, LockRecoveryReport, LockRecoveryState,
+        RfBandStatus, RfField, RF_HOUSEKEEPING_APID,
+    },
+    telemetry::{Measurement, MeasurementQuality, TelemetryPacket},
+};
+
+use crate::telemetry_archive::StoredTelemetry;
+use crate::{diagnostics, downlink::DownlinkedEvent, power_trend, DownlinkSequences};
+
+/// Format the downlink sequence windows as a table, one APID per line
+///
+/// # Arguments
+/// * `sequences` - Windows of the downlink APIDs
+///
+/// # Returns
+/// * `String` - Window, last count and counters of each APID seen
+pub fn format_sequence_windows(sequences: &DownlinkSequences) -> String {
+    let mut out = format!(
+        "{:<6} {:>6} {:>6} {:>9} {:>7} {:>9} {:>7}\n",
+        "APID", "Window", "Last", "Accepted", "Missed", "Rejected", "Resync"
+    );
+    for (apid, window) in sequences.iter() {
+        let stats = window.stats();
+        let last = window
+            .last()
+            .map_or_else(|| "-".to_string(), |last| last.to_string());
+        out.push_str(&format!(
+            "{:<#6X} {:>6} {:>6} {:>9} {:>7} {:>9} {:>7}\n",
+            apid,
+            window.window(),
+            last,
+            stats.accepted,
+            stats.missed,
+            stats.rejected,
+            stats.resyncs
+        ));
+    }
+    out
+}
+
+/// Format the COP-1 state of the command uplink
+///
+/// # Arguments
+/// * `fop` - FOP-1 state, from [`GroundStation::cop1`]
+///
+/// # Returns
+/// * `String` - State, frame sequence numbers and sliding window use, and the
+///   alert that stopped the AD service, if any
+pub fn format_cop1(fop: &Fop) -> String {
+    let config = fop.config();
+    let mut out = format!(
+        "COP-1 {:?} on VC {}: V(S) {}, NN(R) {}, {}/{} frames unacknowledged\n",
+        fop.state(),
+        config.virtual_channel,
+        fop.transmitter_sequence(),
+        fop.expected_ack(),
+        fop.outstanding(),
+        config.window
+    );
+    out.push_str(&format!(
+        "T1 {} ms, transmission limit {}\n",
+        config.timer_ms, config.transmission_limit
+    ));
+    if let Some(alert) = fop.alert() {
+        out.push_str(&format!("Stopped by alert: {}\n", alert));
+    }
+    out
+}
+
+/// Display formatted telemetry information
+///
+/// Provides human-readable output of telemetry packet contents for
+/// mission operations monitoring and debugging.
+///
+/// # Arguments
+/// * `packet` - Telemetry packet to display
+pub(crate) fn display_telemetry(packet: &TelemetryPacket) {
+    println!("=== Telemetry Packet ===");
+    println!("Sequence: {}", packet.sequence);
+    println!("Band: {:?}", packet.band); // REQ-FN-007: Multi-Band Communication
+    println!("Source: {:?}", packet.data.source);
+    println!("Timestamp: {}", packet.data.timestamp);
+    println!("Health: {:?}", packet.data.health_status);
+    println!("Measurements: {}", packet.data.measurements.len());
+
+    // Display individual measurements with detailed formatting
+    for (i, measurement) in packet.data.measurements.iter().enumerate() {
+        println!(
+            "  [{}] ID: 0x{:04X}, Value: {:?}, Unit: {}, Quality: {:?}",
+            i, measurement.measurement_id, measurement.value, measurement.unit, measurement.quality
+        );
+    }
+    println!("========================");
+}
+
+/// Display a stored packet as its APID is shown on reception
+///
+/// # Arguments
+/// * `record` - Received or archived telemetry packet
+pub(crate) fn display_stored_telemetry(record: &StoredTelemetry) {
+    match record.apid {
+        RF_HOUSEKEEPING_APID => display_rf_housekeeping(&record.packet),
+        EPS_APID => display_eps_summary(&record.packet),
+        _ => display_telemetry(&record.packet),
+    }
+}
+
+/// Display the delays measured by a loopback test
+///
+/// # Arguments
+/// * `result` - Measurement of the test its echo closed
+pub(crate) fn display_loopback_result(result: &LoopbackResult) {
+    println!(
+        "Loopback {} on {:?}: RTT {} ms, up {} ms, down {} ms, asymmetry {} ms{}",
+        result.test_id,
+        result.band,
+        result.round_trip_ms,
+        result.uplink_ms,
+        result.downlink_ms,
+        result.asymmetry_ms(),
+        if result.payload_intact {
+            ""
+        } else {
+            ", PAYLOAD CORRUPTED"
+        }
+    );
+}
+
+/// Display the progress of a memory dump as its segments arrive
+///
+/// # Arguments
+/// * `dump` - Dump the latest segment belongs to
+pub(crate) fn display_memory_dump(dump: &diagnostics::MemoryDump) {
+    println!(
+        "Memory dump {}: 0x{:08X} {}/{} bytes{}",
+        dump.dump_id,
+        dump.address,
+        dump.received_bytes(),
+        dump.total_length,
+        if dump.is_complete() { ", complete" } else { "" }
+    );
+}
+
+/// Display the progress of a dwell as its batches arrive
+///
+/// # Arguments
+/// * `trace` - Trace the latest batch belongs to
+pub(crate) fn display_dwell_trace(trace: &diagnostics::DwellTrace) {
+    let latest = trace.samples().last();
+    println!(
+        "Dwell {}: {:?} 0x{:X} every {} ms, {} samples{}{}",
+        trace.dwell_id,
+        trace.source,
+        trace.target,
+        trace.interval_ms,
+        trace.samples().count(),
+        latest.map_or(String::new(), |(_, _, value)| format!(", latest {}", value)),
+        if trace.ended { ", ended" } else { "" }
+    );
+}
+
+/// Display a command execution report, flagging budget overruns
+///
+/// # Arguments
+/// * `report` - Execution report to display
+pub(crate) fn display_execution_report(report: &ExecutionReport) {
+    let result = match report.result {
+        ExecutionResult::Completed => "completed",
+        ExecutionResult::Failed => "FAILED",
+        ExecutionResult::Rejected => "REJECTED",
+    };
+    println!(
+        "Command 0x{:04X} (seq {}, {:?}) {} in {}us, budget {}ms{}",
+        report.command_id,
+        report.sequence_count,
+        report.priority,
+        result,
+        report.execution_time_us,
+        report.budget_ms,
+        if report.within_budget() {
+            ""
+        } else {
+            " OVERRUN"
+        }
+    );
+}
+
+/// Display a decompressed event log block with its compression ratio
+///
+/// # Arguments
+/// * `events` - Decoded events
+/// * `stats` - Raw versus compressed size of the block
+pub(crate) fn display_event_log(events: &[DownlinkedEvent], stats: &CompressionStats) {
+    println!(
+        "Event log: {} events, {} bytes compressed from {} ({:.1}%)",
+        stats.records,
+        stats.compressed_bytes,
+        stats.raw_bytes,
+        stats.ratio() * 100.0
+    );
+    for event in events {
+        let code = event
+            .error_code
+            .map(|code| format!(" (code {})", code))
+            .unwrap_or_default();
+        println!(
+            "  T+{}ms {:?} [{}] {}{}",
+            event.timestamp_ms, event.level, event.component, event.message, code
+        );
+    }
+}
+
+/// Display per-band transceiver status with out-of-limit fields marked,
+/// per-band lock recovery state for a lock recovery frame, or the satellite's
+/// link layer statistics for a link statistics frame
+///
+/// # Arguments
+/// * `packet` - RF housekeeping frame
+pub(crate) fn display_rf_housekeeping(packet: &TelemetryPacket) {
+    let measurements = &packet.data.measurements;
+    let flag = |status: &RfBandStatus, field: RfField| {
+        let id = field.measurement_id(status.band);
+        match measurements.iter().find(|m| m.measurement_id == id) {
+            Some(m) if m.quality != MeasurementQuality::Good => "!",
+            _ => "",
+        }
+    };
+
+    // Transceiver status and lock recovery arrive in separate frames
+    let recovery = decode_lock_recovery(&packet.data);
+    if !recovery.is_empty() {
+        println!("=== RF Lock Recovery ===");
+        for report in recovery.iter() {
+            println!("  {}", format_lock_recovery(report));
+        }
+        println!("========================");
+        return;
+    }
+
+    if let Some(stats) = decode_link_stats(&packet.data) {
+        println!("=== Link Statistics ===");
+        println!("  {}", stats);
+        println!("=======================");
+        return;
+    }
+
+    println!("=== RF Housekeeping ===");
+    for status in decode_rf_housekeeping(&packet.data).iter() {
+        println!(
+            "  {:?}: {} {} tx {}%{} rssi {} dBm{} temp {} C{} freq {} kHz{}",
+            status.band,
+            if status.is_powered { "ON" } else { "OFF" },
+            if status.lock_lost() {
+                "LOCK LOST"
+            } else if status.is_locked {
+                "locked"
+            } else {
+                "unlocked"
+            },
+            status.tx_power,
+            flag(status, RfField::TxPower),
+            status.signal_strength,
+            flag(status, RfField::SignalStrength),
+            status.temperature,
+            flag(status, RfField::Temperature),
+            status.frequency / 1_000,
+            flag(status, RfField::Frequency),
+        );
+    }
+    println!("=======================");
+}
+
+/// One-line summary of a transceiver's lock recovery state and counters
+///
+/// # Arguments
+/// * `report` - Decoded lock recovery report for one band
+///
+/// # Returns
+/// * `String` - State (upper case once failed) followed by the counters
+pub(crate) fn format_lock_recovery(report: &LockRecoveryReport) -> String {
+    let state = match report.state {
+        LockRecoveryState::Nominal => "nominal",
+        LockRecoveryState::LockLost => "lock lost",
+        LockRecoveryState::Retuning => "re-tuning",
+        LockRecoveryState::PowerCycling => "power cycling",
+        LockRecoveryState::Failed => "FAILED",
+    };
+    let counters = &report.counters;
+    format!(
+        "{:?}: {} (losses {}, re-tunes {}, power cycles {}, recoveries {})",
+        report.band,
+        state,
+        counters.lock_losses,
+        counters.retunes,
+        counters.power_cycles,
+        counters.recoveries,
+    )
+}
+
+/// Width of the state of charge gauge and load current bars, characters
+const EPS_BAR_WIDTH: usize = 20;
+
+/// Current drawn by a load filling its whole bar, A
+const EPS_BAR_FULL_SCALE_A: f32 = 2.0;
+
+/// Render an EPS summary for the console
+///
+/// Shows bus and battery voltage, a state of charge gauge, solar array
+/// output, the power balance, and a bar per load. Fields whose measurement
+/// is not `Good` are marked `!`, unreadable ones `--`.
+///
+/// # Arguments
+/// * `summary` - Decoded summary
+/// * `measurements` - Measurements it was decoded from, for quality flags
+///
+/// # Returns
+/// * `String` - Multi-line rendering
+///
+/// # Requirements Traceability
+/// - REQ-NF-004: Power Management (power budget at a glance)
+pub fn format_eps_summary(summary: &EpsSummary, measurements: &[Measurement]) -> String {
+    let flag = |field: EpsField| {
+        let id = field.measurement_id();
+        match measurements.iter().find(|m| m.measurement_id == id) {
+            Some(m) if m.quality != MeasurementQuality::Good => "!",
+            _ => "",
+        }
+    };
+    let value = |field: EpsField, decimals: usize| {
+        let v = summary.value(field);
+        if v.is_nan() {
+            format!("-- {}", field.unit())
+        } else {
+            format!("{:.*} {}{}", decimals, v, field.unit(), flag(field))
+        }
+    };
+    let bar = |fraction: f32| {
+        let filled = if fraction.is_nan() {
+            0
+        } else {
+            (fraction.clamp(0.0, 1.0) * EPS_BAR_WIDTH as f32).round() as usize
+        };
+        format!(
+            "[{}{}]",
+            "#".repeat(filled),
+            "-".repeat(EPS_BAR_WIDTH - filled)
+        )
+    };
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "  Bus {}  Battery {}\n",
+        value(EpsField::BusVoltage, 2),
+        value(EpsField::BatteryVoltage, 2)
+    ));
+    out.push_str(&format!(
+        "  SoC {} {}\n",
+        bar(summary.battery_soc / 100.0),
+        value(EpsField::BatterySoc, 0)
+    ));
+    out.push_str(&format!(
+        "  Solar {} x {} = {:.1} W\n",
+        value(EpsField::SolarVoltage, 1),
+        value(EpsField::SolarCurrent, 2),
+        summary.solar_power()
+    ));
+    let net = summary.net_power();
+    out.push_str(&format!(
+        "  Load {} = {:.1} W, net {:+.1} W ({})\n",
+        value(EpsField::LoadCurrent, 2),
+        summary.load_power(),
+        net,
+        if net.is_nan() {
+            "unknown"
+        } else if net >= 0.0 {
+            "charging"
+        } else {
+            "discharging"
+        }
+    ));
+    for field in EpsField::LOADS {
+        out.push_str(&format!(
+            "  {:<12}{} {}\n",
+            field.name(),
+            bar(summary.value(field) / EPS_BAR_FULL_SCALE_A),
+            value(field, 2)
+        ));
+    }
+    out
+}
+
+/// Display an EPS summary frame
+///
+/// # Arguments
+/// * `packet` - EPS summary frame
+pub(crate) fn display_eps_summary(packet: &TelemetryPacket) {
+    println!("=== EPS Summary ===");
+    match decode_eps(&packet.data) {
+        Some(summary) => {
+            print!(
+                "{}",
+                format_eps_summary(&summary, &packet.data.measurements)
+            );
+            if let Some(attribution) = decode_power_attribution(&packet.data) {
+                print!(
+                    "{}",
+                    power_trend::format_power_attribution(&summary, &attribution, None)
+                );
+            }
+        }
+        None => println!("  Incomplete EPS frame"),
+    }
+    println!("===================");
+}
+
+/// Display a recorder file manifest
+///
+/// # Arguments
+/// * `manifest` - Manifest announcing the files in a downlink pass
+pub(crate) fn display_file_manifest(manifest: &FileManifest) {
+    println!("=== File Manifest (pass {}) ===", manifest.pass_id);
+    for file in manifest.files.iter() {
+        println!(
+            "  File 0x{:08X}: {} bytes, CRC 0x{:04X}",
+            file.file_id, file.size_bytes, file.crc
+        );
+    }
+    println!("===============================");
+}
+
+/// Display a mass memory directory listing
+///
+/// # Arguments
+/// * `listing` - Listing downlinked in answer to a `ListFiles` command
+pub(crate) fn display_file_listing(listing: &FileListing) {
+    let directory = &listing.directory;
+    println!("=== /{} ===", directory_name(directory.data_type));
+    println!(
+        "Used {} of {} bytes in {} files, retention {}",
+        directory.used_bytes,
+        directory.quota_bytes,
+        directory.file_count,
+        directory
+            .retention_s
+            .map_or("until deleted".to_string(), |s| format!("{} s", s))
+    );
+    for file in listing.files.iter() {
+        println!(
+            "  File 0x{:08X} {:<24} {:>10} bytes, value {:>3}, written {} s",
+            file.file_id, file.name, file.size_bytes, file.science_value, file.created_s
+        );
+    }
+    if listing.truncated {
+        println!(
+            "  ... {} more",
+            directory.file_count as usize - listing.files.len()
+        );
+    }
+    println!("===============================");
+}
+
+/// Display a command-load manifest acknowledgment
+///
+/// # Arguments
+/// * `manifest` - Manifest returned by the onboard scheduler
+pub fn display_load_manifest(manifest: &LoadManifest) {
+    println!("=== Command Load {} Manifest ===", manifest.load_id);
+    println!("Accepted: {:?}", manifest.accepted.as_slice());
+    for rejected in manifest.rejected.iter() {
+        println!("  Rejected #{}: {:?}", rejected.sequence, rejected.reason);
+    }
+    println!("================================");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket},
+        rf_housekeeping::{LockMonitor, LockRecoveryPolicy},
+        types::BandType,
+    };
+
+    use crate::downlink::{parse_eps_summary, parse_rf_housekeeping};
+    use crate::test_support::telemetry_frame;
+
+    #[test]
+    fn test_lock_recovery_frame() {
+        let mut monitor = LockMonitor::new();
+        let policy = LockRecoveryPolicy::default();
+        for t in (0..=10_000).step_by(500) {
+            monitor.update(&policy, true, false, t);
+        }
+
+        let mut data = telemetry_frame(0, &[]);
+        LockRecoveryReport::new(BandType::XBand, &monitor)
+            .append_measurements(&mut data)
+            .unwrap();
+        let payload = data.to_payload().unwrap();
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            RF_HOUSEKEEPING_APID,
+            6,
+            &payload,
+            None,
+        )
+        .unwrap();
+
+        let parsed = parse_rf_housekeeping(&packet.to_bytes().unwrap()).unwrap();
+        assert!(decode_rf_housekeeping(&parsed.data).is_empty());
+        let reports = decode_lock_recovery(&parsed.data);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(
+            format_lock_recovery(&reports[0]),
+            "XBand: FAILED (losses 1, re-tunes 2, power cycles 1, recoveries 0)"
+        );
+    }
+
+    #[test]
+    fn test_eps_summary_parse_and_format() {
+        let summary = EpsSummary {
+            bus_voltage: 12.0,
+            battery_voltage: 26.6,
+            battery_soc: 25.0,
+            solar_voltage: 0.0,
+            solar_current: 0.0,
+            load_current: 2.0,
+            load_currents: [1.0, 0.5, f32::NAN],
+        };
+        let mut data = telemetry_frame(0, &[]);
+        summary.append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+        let packet = SpacePacket::new(PacketType::Telemetry, EPS_APID, 1, &payload, None).unwrap();
+        let parsed = parse_eps_summary(&packet.to_bytes().unwrap()).unwrap();
+        let decoded = decode_eps(&parsed.data).unwrap();
+        assert_eq!(decoded.battery_soc, 25.0);
+
+        let text = format_eps_summary(&decoded, &parsed.data.measurements);
+        assert!(text.contains("SoC [#####---------------] 25 %"), "{}", text);
+        assert!(text.contains("net -24.0 W (discharging)"), "{}", text);
+        assert!(
+            text.contains("Digital     [##########----------] 1.00 A"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("Transmitter [--------------------] -- A"),
+            "{}",
+            text
+        );
+
+        // RF housekeeping is not the EPS summary
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            RF_HOUSEKEEPING_APID,
+            1,
+            &payload,
+            None,
+        )
+        .unwrap();
+        assert!(parse_eps_summary(&packet.to_bytes().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,676 @@
+//! Parsers of downlinked packets
+//!
+//! Each parser takes the raw bytes of one downlinked Space Packet and returns
+//! the product it carries when the packet is on that product's APID:
+//! telemetry frames, command-load and file manifests, execution reports,
+//! diagnostics, CLCWs, loopback echoes, event logs, RF housekeeping and EPS
+//! summaries. The downlink sequence check and the power trend update sit
+//! here with them, ahead of any display.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (CCSDS packet parsing)
+//! - REQ-NF-004: Fault Tolerance (robust packet validation)
+
+use space_comms_req::req;
+use space_comms_shared::{
+    ccsds::{PacketType, SpacePacketHeader},
+    command_load::{LoadManifest, COMMAND_LOAD_APID},
+    cop1::{Clcw, CLCW_APID},
+    diagnostics::{DwellBatch, MemoryDumpSegment, DWELL_APID, MEMORY_DUMP_APID},
+    eps::{decode_eps, EPS_APID},
+    event_log::{CompressionStats, EventLevel, EventLogDecoder, EVENT_LOG_APID},
+    execution_report::{ExecutionReport, EXECUTION_REPORT_APID},
+    file_downlink::{FileManifest, FILE_MANIFEST_APID},
+    loopback::{LoopbackEcho, LOOPBACK_APID},
+    mass_memory::{FileListing, FILE_LISTING_APID},
+    power_attribution::decode_power_attribution,
+    rf_housekeeping::RF_HOUSEKEEPING_APID,
+    sequence::SequenceVerdict,
+    telemetry::{TelemetryData, TelemetryPacket},
+    types::{BandType, HealthStatus},
+    wire, Result, SpaceCommError,
+};
+
+use crate::power_trend::{DepletionSuspect, PowerTrend};
+use crate::{DownlinkSequences, SATELLITE_COMPONENT};
+
+/// Check the sequence count of a downlinked packet against its APID window
+///
+/// Gaps and resynchronisations are reported but the packet is kept; a
+/// duplicate, late or out-of-window packet is reported and dropped. Bytes
+/// without a valid header are left to the packet parsers to reject.
+///
+/// # Arguments
+/// * `sequences` - Windows of the downlink APIDs
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `bool` - Whether the packet should be processed
+///
+/// # Requirements Traceability
+/// - FN-SEQ-002: Rollover-safe acceptance window per downlink APID
+/// - REQ-NF-004: Fault Tolerance (recovery after an onboard reboot)
+pub fn accept_downlink_sequence(sequences: &mut DownlinkSequences, bytes: &[u8]) -> bool {
+    let Ok(header) = SpacePacketHeader::from_bytes(bytes) else {
+        return true;
+    };
+    let verdict = match sequences.check(header.apid, header.sequence_count) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!(
+                "Sequence count of APID {:#05X} not checked: {}",
+                header.apid, e
+            );
+            return true;
+        }
+    };
+    match verdict {
+        SequenceVerdict::First | SequenceVerdict::InSequence => {}
+        SequenceVerdict::Gap { missed } => println!(
+            "APID {:#05X}: {} packet(s) missed before count {}",
+            header.apid, missed, header.sequence_count
+        ),
+        SequenceVerdict::Resynchronized { offset } => println!(
+            "APID {:#05X}: sequence resynchronized at count {} ({:+} from last)",
+            header.apid, header.sequence_count, offset
+        ),
+        SequenceVerdict::Behind { by: 0 } => println!(
+            "APID {:#05X}: duplicate count {} dropped",
+            header.apid, header.sequence_count
+        ),
+        SequenceVerdict::Behind { by } => println!(
+            "APID {:#05X}: count {} is {} behind, dropped",
+            header.apid, header.sequence_count, by
+        ),
+        SequenceVerdict::OutOfWindow { offset } => println!(
+            "APID {:#05X}: count {} outside window ({:+} from last), dropped",
+            header.apid, header.sequence_count, offset
+        ),
+    }
+    verdict.is_accepted()
+}
+
+/// Parse telemetry packet from received bytes
+///
+/// Processes incoming telemetry data according to CCSDS packet format
+/// and creates structured telemetry packet for analysis.
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Result<TelemetryPacket>` - Parsed telemetry packet or parsing error
+///
+/// # Requirements Traceability
+/// - REQ-IF-002: CCSDS Compliance (CCSDS packet header parsing)
+/// - REQ-NF-004: Fault Tolerance (robust packet validation)
+/// - FN-TLM-002: Measurement quality flags decoded from the downlink
+#[req("REQ-IF-002", "REQ-NF-004")]
+pub fn parse_telemetry_packet(bytes: &[u8]) -> Result<TelemetryPacket> {
+    // REQ-IF-002: CCSDS Compliance - Header, declared length and CRC checked
+    // against the wire format before the data field is trusted
+    let (header, data) = wire::split_frame(bytes)?;
+    if header.packet_type != PacketType::Telemetry {
+        return Err(SpaceCommError::invalid_packet(
+            "Not a telemetry packet",
+            None,
+        ));
+    }
+
+    let telemetry_data =
+        TelemetryData::from_payload(SATELLITE_COMPONENT, HealthStatus::Good, data)?;
+
+    // Create structured telemetry packet with parsed data
+    Ok(TelemetryPacket::new(
+        header.sequence_count as u32,
+        telemetry_data,
+        BandType::SBand, // Default to S-Band for simulation
+    ))
+}
+
+/// Extract a command-load manifest from a downlinked packet, if it carries one
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<LoadManifest>` - Manifest when the packet is on the command-load APID
+pub fn parse_load_manifest(bytes: &[u8]) -> Option<LoadManifest> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != COMMAND_LOAD_APID {
+        return None;
+    }
+
+    LoadManifest::from_bytes(data).ok()
+}
+
+/// Extract a recorder file manifest from a downlinked packet, if it carries one
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<FileManifest>` - Manifest when the packet is on the file manifest APID
+pub fn parse_file_manifest(bytes: &[u8]) -> Option<FileManifest> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != FILE_MANIFEST_APID {
+        return None;
+    }
+
+    FileManifest::from_bytes(data).ok()
+}
+
+/// Extract a mass memory directory listing from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<FileListing>` - Listing when the packet is on the file listing
+///   APID and decodes
+///
+/// # Requirements Traceability
+/// - REQ-FN-005: Medium Priority Commands (mass memory file management)
+#[req("REQ-FN-005")]
+pub fn parse_file_listing(bytes: &[u8]) -> Option<FileListing> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != FILE_LISTING_APID {
+        return None;
+    }
+
+    FileListing::from_bytes(data).ok()
+}
+
+/// Extract a command execution report from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<ExecutionReport>` - Report when the packet is on the execution
+///   report APID and decodes
+///
+/// # Requirements Traceability
+/// - REQ-PF-001: Command Response Time (measured execution time vs. budget)
+#[req("REQ-PF-001")]
+pub fn parse_execution_report(bytes: &[u8]) -> Option<ExecutionReport> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != EXECUTION_REPORT_APID {
+        return None;
+    }
+
+    ExecutionReport::from_bytes(data).ok()
+}
+
+/// Extract a memory dump segment from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<MemoryDumpSegment>` - Segment when the packet is on the memory
+///   dump APID and decodes
+///
+/// # Requirements Traceability
+/// - FN-DMP-002: Memory dump reassembly with missing ranges
+pub fn parse_memory_dump_segment(bytes: &[u8]) -> Option<MemoryDumpSegment> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != MEMORY_DUMP_APID {
+        return None;
+    }
+
+    MemoryDumpSegment::from_bytes(data).ok()
+}
+
+/// Extract a dwell sample batch from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<DwellBatch>` - Batch when the packet is on the dwell APID and
+///   decodes
+///
+/// # Requirements Traceability
+/// - FN-DWL-002: Dwell trace reassembly with gaps
+pub fn parse_dwell_batch(bytes: &[u8]) -> Option<DwellBatch> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != DWELL_APID {
+        return None;
+    }
+
+    DwellBatch::from_bytes(data).ok()
+}
+
+/// Extract a COP-1 CLCW from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<Clcw>` - Report when the packet is on the CLCW APID and decodes
+///
+/// # Requirements Traceability
+/// - REQ-IF-002: CCSDS Compliance (COP-1 CLCW)
+#[req("REQ-IF-002")]
+pub fn parse_clcw(bytes: &[u8]) -> Option<Clcw> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != CLCW_APID {
+        return None;
+    }
+
+    Clcw::from_bytes(data).ok()
+}
+
+/// Extract a loopback echo from a downlinked packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<LoopbackEcho>` - Echo when the packet is on the loopback APID
+///   and decodes
+///
+/// # Requirements Traceability
+/// - FN-LBK-001: Round trip, uplink and downlink delays of the command path
+pub fn parse_loopback_echo(bytes: &[u8]) -> Option<LoopbackEcho> {
+    let (header, data) = wire::split_frame(bytes).ok()?;
+    if header.apid != LOOPBACK_APID {
+        return None;
+    }
+
+    LoopbackEcho::from_bytes(data).ok()
+}
+
+/// Onboard event decoded from a compressed event log block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownlinkedEvent {
+    /// Satellite time of the event, milliseconds since boot
+    pub timestamp_ms: u64,
+    /// Severity
+    pub level: EventLevel,
+    /// Component that logged the event
+    pub component: String,
+    /// Event message
+    pub message: String,
+    /// Error code, if any
+    pub error_code: Option<u32>,
+}
+
+/// Decompress an event log block
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<(Vec<DownlinkedEvent>, CompressionStats)>` - Events in log order
+///   and the block's raw versus compressed size, when the packet is on the
+///   event log APID and decodes completely
+///
+/// # Requirements Traceability
+/// - REQ-NF-002: Memory Constraints (compressed onboard log downlink)
+#[req("REQ-NF-002")]
+pub fn parse_event_log(bytes: &[u8]) -> Option<(Vec<DownlinkedEvent>, CompressionStats)> {
+    let (header, block) = wire::split_frame(bytes).ok()?;
+    if header.apid != EVENT_LOG_APID {
+        return None;
+    }
+
+    let mut stats = CompressionStats {
+        compressed_bytes: block.len() as u32,
+        ..CompressionStats::default()
+    };
+    let mut events = Vec::new();
+    for record in EventLogDecoder::new(block).ok()? {
+        let record = record.ok()?;
+        stats.records += 1;
+        stats.raw_bytes += record.raw_len() as u32;
+        events.push(DownlinkedEvent {
+            timestamp_ms: record.timestamp_ms,
+            level: record.level,
+            component: record.component.to_string(),
+            message: record.message.to_string(),
+            error_code: record.error_code,
+        });
+    }
+    Some((events, stats))
+}
+
+/// Extract per-band transceiver status from an RF housekeeping packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<TelemetryPacket>` - Decoded frame when the packet is on the RF
+///   housekeeping APID; its measurements carry the onboard limit flags
+///
+/// # Requirements Traceability
+/// - REQ-PF-002: Link quality monitoring (transceiver RF metrics)
+#[req("REQ-PF-002")]
+pub fn parse_rf_housekeeping(bytes: &[u8]) -> Option<TelemetryPacket> {
+    let header = SpacePacketHeader::from_bytes(bytes).ok()?;
+    if header.apid != RF_HOUSEKEEPING_APID {
+        return None;
+    }
+
+    parse_telemetry_packet(bytes).ok()
+}
+
+/// Extract the power system summary frame from an EPS packet
+///
+/// # Arguments
+/// * `bytes` - Raw packet bytes received from satellite
+///
+/// # Returns
+/// * `Option<TelemetryPacket>` - Decoded frame when the packet is on the EPS
+///   APID; its measurements carry the onboard limit flags
+///
+/// # Requirements Traceability
+/// - REQ-NF-004: Power Management (EPS summary telemetry)
+pub fn parse_eps_summary(bytes: &[u8]) -> Option<TelemetryPacket> {
+    let header = SpacePacketHeader::from_bytes(bytes).ok()?;
+    if header.apid != EPS_APID {
+        return None;
+    }
+
+    parse_telemetry_packet(bytes).ok()
+}
+
+/// Add an EPS summary frame to the power trend
+///
+/// Frames without a complete summary and power attribution are ignored.
+///
+/// # Arguments
+/// * `trend` - Power trend to update
+/// * `data` - EPS summary frame
+/// * `now_unix_ms` - Receive time, milliseconds since the Unix epoch
+///
+/// # Returns
+/// * `Option<DepletionSuspect>` - Suspect of a depletion first detected by
+///   this frame
+///
+/// # Requirements Traceability
+/// - FN-PWR-003: Unexpected battery depletion traced to a subsystem
+pub fn record_power_trend(
+    trend: &mut PowerTrend,
+    data: &TelemetryData,
+    now_unix_ms: u64,
+) -> Option<DepletionSuspect> {
+    let summary = decode_eps(data)?;
+    let attribution = decode_power_attribution(data)?;
+    trend.record(&summary, &attribution, now_unix_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use space_comms_shared::{
+        ccsds::SpacePacket,
+        command_load::{CommandLoad, LoadConstraints},
+        execution_report::ExecutionResult,
+        file_downlink::FileEntry,
+        link_forecast::LINK_FORECAST_APID,
+        messaging::MessagePriority,
+        rf_housekeeping::{decode_rf_housekeeping, RfBandStatus, RfField},
+        telemetry::{measurement_ids, MeasurementQuality},
+    };
+
+    use crate::test_support::telemetry_frame;
+    use crate::{
+        command::next_uplink_sequence, display::format_sequence_windows,
+        telemetry_tracker::TelemetryTracker,
+    };
+
+    #[test]
+    fn test_parse_telemetry_packet() {
+        assert!(parse_telemetry_packet(&[0x01, 0x00, 0xC0]).is_err());
+
+        let data = telemetry_frame(
+            1_000,
+            &[(
+                measurement_ids::BATTERY_VOLTAGE,
+                MeasurementQuality::Suspect,
+            )],
+        );
+        let payload = data.to_payload().unwrap();
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 42, &payload, None).unwrap();
+        let parsed = parse_telemetry_packet(&packet.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.sequence, 42);
+        assert_eq!(parsed.band, BandType::SBand);
+        assert_eq!(parsed.data.timestamp, 1_000);
+        assert_eq!(parsed.data.measurements.len(), 1);
+        assert_eq!(
+            parsed.data.measurements[0].quality,
+            MeasurementQuality::Suspect
+        );
+
+        // Data field too short to hold a telemetry frame
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 43, &[1, 2, 3], None).unwrap();
+        assert!(parse_telemetry_packet(&packet.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_parse_execution_report() {
+        let report = ExecutionReport::new(
+            0x9999,
+            3,
+            MessagePriority::Emergency,
+            ExecutionResult::Completed,
+            250,
+        );
+        let bytes = report.to_packet(3).unwrap().to_bytes().unwrap();
+        assert_eq!(parse_execution_report(&bytes), Some(report));
+
+        // Telemetry on another APID is not an execution report
+        let payload = telemetry_frame(0, &[]).to_payload().unwrap();
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 1, &payload, None).unwrap();
+        assert!(parse_execution_report(&packet.to_bytes().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_parse_diagnostics_downlinks() {
+        let segment = MemoryDumpSegment {
+            dump_id: 2,
+            address: 0x2000_0000,
+            total_length: 512,
+            offset: 256,
+            data: [0xDE, 0xAD].into_iter().collect(),
+        };
+        let bytes = segment.to_packet(1).unwrap().to_bytes().unwrap();
+        assert_eq!(parse_memory_dump_segment(&bytes), Some(segment));
+        assert!(parse_dwell_batch(&bytes).is_none());
+
+        let request = space_comms_shared::diagnostics::DwellRequest {
+            source: space_comms_shared::diagnostics::DwellSource::Memory,
+            target: 0x2000_0010,
+            interval_ms: 50,
+            duration_s: 2,
+        };
+        let mut batch = DwellBatch::new(3, &request, 0);
+        batch.samples.push(0x1234_5678).unwrap();
+        let bytes = batch.to_packet(1).unwrap().to_bytes().unwrap();
+        assert_eq!(parse_dwell_batch(&bytes), Some(batch));
+        assert!(parse_memory_dump_segment(&bytes).is_none());
+    }
+
+    #[test]
+    fn test_parse_file_listing() {
+        use space_comms_shared::commands::{DataType, StorageLocation};
+        use space_comms_shared::mass_memory::MassMemory;
+
+        let mut memory = MassMemory::<4>::new();
+        let location = StorageLocation::NonVolatileMemory;
+        memory
+            .create(DataType::Images, "pass_0001.raw", 4096, location, 0, 60)
+            .unwrap();
+        let listing = memory.listing(DataType::Images);
+        let bytes = listing.to_packet(1).unwrap().to_bytes().unwrap();
+        assert_eq!(parse_file_listing(&bytes), Some(listing));
+        assert!(parse_file_manifest(&bytes).is_none());
+    }
+
+    #[test]
+    fn test_parse_loopback_echo() {
+        let request = space_comms_shared::loopback::LoopbackRequest {
+            test_id: 4,
+            band: BandType::SBand,
+            payload: [0xA5, 0x5A].into_iter().collect(),
+        };
+        let echo = request.echo(1_000, 1_003);
+        let bytes = echo.to_packet(1).unwrap().to_bytes().unwrap();
+        assert_eq!(parse_loopback_echo(&bytes), Some(echo));
+        assert!(parse_dwell_batch(&bytes).is_none());
+    }
+
+    #[test]
+    fn test_sequence_counts_per_apid() {
+        let mut uplink = HashMap::new();
+        assert_eq!(next_uplink_sequence(&mut uplink, COMMAND_LOAD_APID), 1);
+        assert_eq!(next_uplink_sequence(&mut uplink, LINK_FORECAST_APID), 1);
+        uplink.insert(COMMAND_LOAD_APID, 0x3FFF);
+        assert_eq!(next_uplink_sequence(&mut uplink, COMMAND_LOAD_APID), 0);
+
+        let frame = |apid, count| {
+            SpacePacket::new(PacketType::Telemetry, apid, count, &[0; 4], None)
+                .unwrap()
+                .to_bytes()
+                .unwrap()
+        };
+        let mut downlink = DownlinkSequences::default();
+        downlink.set_window(EPS_APID, 2).unwrap();
+        assert!(accept_downlink_sequence(
+            &mut downlink,
+            &frame(EPS_APID, 0x3FFF)
+        ));
+        assert!(accept_downlink_sequence(&mut downlink, &frame(EPS_APID, 1)));
+        assert!(!accept_downlink_sequence(
+            &mut downlink,
+            &frame(EPS_APID, 1)
+        ));
+        assert!(!accept_downlink_sequence(
+            &mut downlink,
+            &frame(EPS_APID, 9)
+        ));
+        assert!(accept_downlink_sequence(
+            &mut downlink,
+            &frame(EPS_APID, 10)
+        ));
+        assert!(accept_downlink_sequence(
+            &mut downlink,
+            &frame(RF_HOUSEKEEPING_APID, 7)
+        ));
+
+        let table = format_sequence_windows(&downlink);
+        assert!(table.contains("0x102       2     10         3       1         2       1"));
+        assert_eq!(table.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_parse_event_log() {
+        use space_comms_shared::event_log::{EventLogCompressor, EventRecord};
+
+        let mut block = EventLogCompressor::<256>::new();
+        for timestamp_ms in [1_000, 1_250] {
+            block
+                .push(&EventRecord {
+                    timestamp_ms,
+                    level: EventLevel::Warning,
+                    component: "TELEMETRY",
+                    message: "Telemetry drop rate exceeded",
+                    error_code: Some(420),
+                })
+                .unwrap();
+        }
+        let bytes = block.to_packet(0).unwrap().to_bytes().unwrap();
+
+        let (events, stats) = parse_event_log(&bytes).unwrap();
+        assert_eq!(stats, *block.stats());
+        assert!(stats.ratio() < 1.0);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].timestamp_ms, 1_250);
+        assert_eq!(events[1].component, "TELEMETRY");
+        assert_eq!(events[1].error_code, Some(420));
+
+        // Other downlink packets are not event log blocks
+        let report =
+            ExecutionReport::new(1, 1, MessagePriority::High, ExecutionResult::Completed, 1);
+        assert!(parse_event_log(&report.to_packet(0).unwrap().to_bytes().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_parse_rf_housekeeping() {
+        let mut data = telemetry_frame(0, &[]);
+        let mut s_band = RfBandStatus {
+            band: BandType::SBand,
+            is_powered: true,
+            is_locked: false,
+            tx_power: 40,
+            signal_strength: -95,
+            temperature: 30,
+            frequency: 2_200_000_000,
+        };
+        s_band.append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            RF_HOUSEKEEPING_APID,
+            5,
+            &payload,
+            None,
+        )
+        .unwrap();
+        let parsed = parse_rf_housekeeping(&packet.to_bytes().unwrap()).unwrap();
+        let bands = decode_rf_housekeeping(&parsed.data);
+        assert_eq!(bands.as_slice(), &[s_band]);
+        assert!(bands[0].lock_lost());
+
+        // Regular telemetry is not RF housekeeping
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 5, &payload, None).unwrap();
+        assert!(parse_rf_housekeeping(&packet.to_bytes().unwrap()).is_none());
+
+        // Out-of-limit temperature arrives flagged
+        s_band.temperature = 80;
+        let mut data = telemetry_frame(0, &[]);
+        s_band.append_measurements(&mut data).unwrap();
+        let mut tracker = TelemetryTracker::new();
+        tracker.update(&data, 0);
+        let id = RfField::Temperature.measurement_id(BandType::SBand);
+        let temperature = tracker.current(id, 0).unwrap();
+        assert_eq!(temperature.quality, MeasurementQuality::Suspect);
+        assert_eq!(temperature.unit, "C");
+    }
+
+    #[test]
+    fn test_manifest_parsers_ignore_other_apids() {
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 1, &[0; 16], None).unwrap();
+        let bytes = packet.to_bytes().unwrap();
+
+        assert!(parse_load_manifest(&bytes).is_none());
+        assert!(parse_file_manifest(&bytes).is_none());
+        assert!(parse_load_manifest(&bytes[..4]).is_none());
+    }
+
+    #[test]
+    fn test_load_manifest_parsed_from_satellite_packet() {
+        let load = CommandLoad::new(12);
+        let manifest = load.validate(&LoadConstraints::default(), 0);
+        let bytes = manifest.to_packet(4).unwrap().to_bytes().unwrap();
+
+        assert_eq!(parse_load_manifest(&bytes), Some(manifest));
+    }
+
+    #[test]
+    fn test_file_manifest_parsed_from_satellite_packet() {
+        let mut manifest = FileManifest::new(3);
+        manifest
+            .push(FileEntry::from_contents(21, &[0u8; 128]))
+            .unwrap();
+        let bytes = manifest.to_packet(9).unwrap().to_bytes().unwrap();
+
+        assert_eq!(parse_file_manifest(&bytes), Some(manifest));
+    }
+}
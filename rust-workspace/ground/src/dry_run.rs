@@ -0,0 +1,243 @@
+//! Dry-run rendering of uplink frames
+//!
+//! In dry-run mode the ground station validates and encodes every uplink as
+//! it would for the satellite, records commands in the audit log, and then
+//! prints the frame instead of sending it: a hex dump of the bytes that would
+//! go on the wire and a decoded view of the CCSDS header and payload. Operators
+//! and scripts can be checked against the live system without commanding the
+//! satellite. Command uplinks are shown as the TC Transfer Frame that would
+//! go on the wire, followed by the packet the frame carries.
+//!
+//! Rendering works from the encoded bytes alone, through the shared packet
+//! inspector, so the decoded view shows what the satellite would receive, not
+//! what the ground meant to send. The console's `inspect` command uses the
+//! same view for any frame pasted as hex.
+//!
+//! # Requirements Traceability
+//! - FN-DRY-001: Uplink frames shown exactly as they would be transmitted
+//! - REQ-SF-001: Command Validation (commands validated and encoded in dry run)
+
+use std::fmt::Write;
+use std::net::SocketAddr;
+
+use space_comms_shared::ccsds::TcTransferFrame;
+use space_comms_shared::inspector::{inspect, PayloadFormat};
+
+/// Bytes per hex dump line
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// Hex dump of `bytes`: offset, hex bytes and printable ASCII, 16 per line
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (line, chunk) in bytes.chunks(HEX_DUMP_WIDTH).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02X}", byte)).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "{:04X}  {:<width$}  |{}|",
+            line * HEX_DUMP_WIDTH,
+            hex.join(" "),
+            ascii,
+            width = HEX_DUMP_WIDTH * 3 - 1
+        );
+    }
+    out
+}
+
+/// Decoded view of a packet: the shared inspector's annotated breakdown,
+/// followed by command parameters or the JSON document the payload carries
+///
+/// # Requirements Traceability
+/// - FN-DRY-001: Decoded from the encoded bytes, not the request
+pub fn decode(bytes: &[u8]) -> String {
+    let inspection = match inspect(bytes) {
+        Ok(inspection) => inspection,
+        Err(e) => return format!("Undecodable packet: {}\n", e),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", inspection);
+
+    let payload = &bytes[inspection.payload.clone()];
+    match inspection.apid.map(|entry| entry.format) {
+        Some(PayloadFormat::Command) if payload.len() >= 4 => {
+            let parameters: Vec<String> = payload[4..]
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect();
+            let _ = writeln!(out, "Parameters [{}]", parameters.join(" "));
+        }
+        Some(
+            PayloadFormat::CommandLoad
+            | PayloadFormat::LoadManifest
+            | PayloadFormat::FileManifest
+            | PayloadFormat::RetransmitRequest
+            | PayloadFormat::LinkForecast,
+        ) => {
+            if let Ok(document) = serde_json::from_slice::<serde_json::Value>(payload) {
+                let pretty = serde_json::to_string_pretty(&document).unwrap_or_default();
+                let _ = writeln!(out, "{}", pretty);
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Parse operator-entered hex, ignoring whitespace and an optional `0x`
+/// prefix; `None` unless every digit pairs into a byte
+pub fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text.split_whitespace().collect();
+    let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Full dry-run rendering of one uplink: destination, hex dump and decoded
+/// view
+///
+/// # Arguments
+/// * `operation` - Uplink label, as used in logs
+/// * `destination` - Address the frame would be sent to
+/// * `bytes` - Encoded frame
+pub fn render(operation: &str, destination: SocketAddr, bytes: &[u8]) -> String {
+    format!(
+        "[DRY RUN] {} to {} not transmitted, {} bytes:\n{}{}",
+        operation,
+        destination,
+        bytes.len(),
+        hex_dump(bytes),
+        decode(bytes)
+    )
+}
+
+/// Dry-run rendering of one TC Transfer Frame uplink: destination, hex dump
+/// of the bytes on the wire, the frame header and the decoded view of the
+/// packet the frame carries
+///
+/// # Arguments
+/// * `operation` - Uplink label, as used in logs
+/// * `destination` - Address the frame would be sent to
+/// * `bytes` - Frame as transmitted, Reed-Solomon coded on coded bands
+/// * `frame` - The frame before coding
+pub fn render_frame(
+    operation: &str,
+    destination: SocketAddr,
+    bytes: &[u8],
+    frame: &TcTransferFrame,
+) -> String {
+    format!(
+        "[DRY RUN] {} to {} not transmitted, {} bytes:\n{}TC Transfer Frame {:?}, SCID {:#05X}, VC {}, N(S) {}\n{}",
+        operation,
+        destination,
+        bytes.len(),
+        hex_dump(bytes),
+        frame.frame_type,
+        frame.spacecraft_id,
+        frame.virtual_channel,
+        frame.sequence,
+        decode(&frame.data)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_command_packet, Command, SATELLITE_COMMAND_ADDR};
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket, TcFrameType},
+        link_forecast::LINK_FORECAST_APID,
+        types::{BandType, MessageId},
+    };
+
+    #[test]
+    fn test_hex_dump_layout() {
+        let bytes: Vec<u8> = (0x3C..0x50).collect();
+        let dump = hex_dump(&bytes);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("0000  3C 3D 3E 3F 40 41"));
+        assert!(lines[0].ends_with("|<=>?@ABCDEFGHIJK|"));
+        assert!(lines[1].starts_with("0010  4C 4D 4E 4F "));
+        assert!(lines[1].ends_with("|LMNO|"));
+        assert!(hex_dump(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_decode_command_and_json_payloads() {
+        let command = Command::switch_band(BandType::XBand);
+        let message = command.to_message(MessageId::from_value(7), 0).unwrap();
+        let bytes = create_command_packet(&message).unwrap().to_bytes().unwrap();
+        let view = decode(&bytes);
+        assert!(
+            view.contains("APID 0x003: High priority command (Command)"),
+            "{}",
+            view
+        );
+        assert!(view.contains("Packet Sequence Count  00000000000111 = 7"));
+        assert!(view.contains("command_id: 8193, parameter_bytes: 1"));
+        assert!(view.contains("Parameters [02]\n"));
+        assert!(view.contains("valid"));
+
+        let rendered = render("command uplink", SATELLITE_COMMAND_ADDR, &bytes);
+        assert!(rendered.starts_with("[DRY RUN] command uplink to 127.0.0.1:8080 not transmitted"));
+        assert!(rendered.contains(&hex_dump(&bytes)));
+
+        // A corrupted frame is shown as the satellite would see it
+        let mut corrupted = bytes.to_vec();
+        corrupted[8] ^= 0xFF;
+        assert!(decode(&corrupted).contains("MISMATCH"));
+
+        let payload = br#"{"forecast_id":3,"issued_s":0,"contacts":[]}"#;
+        let forecast = SpacePacket::new(PacketType::Command, LINK_FORECAST_APID, 9, payload, None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let view = decode(&forecast);
+        assert!(
+            view.contains("APID 0x015: Link forecast (Command)"),
+            "{}",
+            view
+        );
+        assert!(view.contains("\"forecast_id\": 3"));
+    }
+
+    #[test]
+    fn test_render_frame_shows_header_and_packet() {
+        let command = Command::switch_band(BandType::XBand);
+        let message = command.to_message(MessageId::from_value(7), 0).unwrap();
+        let packet = create_command_packet(&message).unwrap().to_bytes().unwrap();
+        let frame = TcTransferFrame::new(TcFrameType::Ad, 0x2A5, 7, 12, &packet).unwrap();
+        let bytes = frame.to_bytes().unwrap();
+
+        let rendered = render_frame("command uplink", SATELLITE_COMMAND_ADDR, &bytes, &frame);
+        assert!(rendered.contains(&hex_dump(&bytes)));
+        assert!(rendered.contains("TC Transfer Frame Ad, SCID 0x2A5, VC 7, N(S) 12\n"));
+        assert!(rendered.contains("APID 0x003: High priority command (Command)"));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("0x1A 2b\t3C"), Some(vec![0x1A, 0x2B, 0x3C]));
+        assert_eq!(parse_hex("1A2"), None);
+        assert_eq!(parse_hex("zz"), None);
+        assert_eq!(parse_hex(""), None);
+
+        let bytes = parse_hex("1003 C000 0000 FF 0000").unwrap();
+        assert!(decode(&bytes).contains("Decode failed"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Ground Station Communication System
+//!
+//! Implements the ground station side of the satellite-ground communication link.
+//! Handles command uplink, telemetry downlink, and mission operations.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (CCSDS packet creation and parsing)
+//! - REQ-FN-007: Multi-Band Communication (frequency band selection support)
+//! - REQ-PF-001: Command Response Time (command acknowledgment processing)
+//! - REQ-FN-001: Priority Classification (command priority handling)
+//!
+//! # NASA/DoD Standards Compliance
+//! - **CCSDS 133.0-B-2**: Space Packet Protocol (ground station implementation)
+//! - **NASA-STD-8739.8**: Software Assurance (mission control software)
+//! - **CCSDS 401.0-B-30**: Radio Frequency and Modulation (multi-band support)
+//! - **DoD-STD-2167A**: Defense System Software (ground system architecture)
+//! - **NASA-HDBK-2203**: Software Engineering (ground station requirements)
+//! - **CCSDS 102.0-B-5**: Packet Telemetry (telemetry processing)
+//! - **MIL-STD-188-165B**: Interoperability Standard for Satellite Communications
+//!
+//! # Architecture
+//! The ground station operates as a UDP-based communication gateway that:
+//! - Receives telemetry data from satellites via UDP sockets
+//! - Sends commands to satellites with proper priority classification
+//! - Maintains connection status monitoring and telemetry history
+//! - Provides interactive mission control interface
+//!
+//! # Communication Protocol
+//! - Uses CCSDS Space Packet Protocol for all communications
+//! - Supports all five frequency bands (UHF, S, X, K, Ka)
+//! - Implements priority-based command transmission
+//! - Reed-Solomon (255,223) codes frames on the bands its FEC policy names
+//! - Command uplinks travel in TC Transfer Frames retransmitted under COP-1
+//!   until the satellite's CLCW acknowledges them
+//! - Maintains telemetry packet history for analysis
+//!
+//! # Public API
+//! - [`GroundStationConfig`] / [`GroundStation`]: station configuration, UDP
+//!   uplink/downlink threads, command and command-load uplink, and the
+//!   dedicated emergency command lane
+//! - [`dry_run`]: uplinks validated, encoded and shown as hex dump and decoded
+//!   view instead of being sent
+//! - [`Command`]: ground command construction with priority classification
+//! - [`dictionary`]: commands sendable by name, with parameter validation and
+//!   console completion
+//! - [`macros`]: operator-defined console aliases and macros
+//! - [`create_command_packet`] / [`parse_telemetry_packet`]: CCSDS encoding of
+//!   commands and decoding of downlinked telemetry
+//! - [`parse_load_manifest`] / [`parse_file_manifest`]: downlinked manifest
+//!   extraction
+//! - [`parse_file_listing`]: mass memory directory listings with usage, quota
+//!   and retention
+//! - [`parse_rf_housekeeping`]: per-band transceiver RF metrics
+//! - [`GroundStation::link_stats`] / [`GroundStation::satellite_link_stats`]:
+//!   frames, errors, retransmissions, throughput and SNR of the link as seen
+//!   by the station and as downlinked by the satellite
+//! - [`GroundStation::downlink_policy_stats`]: recorder downlink policy in
+//!   use and the contacts, files and volume planned under each policy
+//! - [`parse_eps_summary`] / [`format_eps_summary`]: power system summary
+//!   with state of charge gauge and per-load current bars
+//! - `api` (`api` feature): HTTP API sending dictionary commands, querying
+//!   stored telemetry, reporting the link status and streaming live
+//!   telemetry as server-sent events
+//! - `ldpc_decoder` (`ldpc` feature): CCSDS C2 LDPC decoding of high-rate
+//!   downlinks with decoder effort statistics
+//! - [`power_trend`]: per-subsystem power attribution trended to name the
+//!   subsystem behind an unexpected battery depletion
+//! - [`parse_execution_report`] / [`verification`]: per-command execution
+//!   reports archived as timing compliance evidence, with rolling
+//!   send-to-acknowledgment latency percentiles per priority
+//! - [`parse_memory_dump_segment`] / [`parse_dwell_batch`] / [`diagnostics`]:
+//!   memory dump and dwell downlinks reassembled by dump or dwell ID
+//! - [`GroundStation::service_cop1`] / [`parse_clcw`]: FOP-1 sliding window
+//!   of command uplink frames, acknowledged by downlinked CLCWs and
+//!   retransmitted on request or timeout
+//! - [`TelemetryReceiver`]: TM Transfer Frames demultiplexed back into
+//!   packets, with the CLCW from each frame's OCF passed to FOP-1
+//! - [`parse_loopback_echo`] / [`loopback`]: command path loopback tests,
+//!   sent at every AOS, with measured uplink and downlink delays
+//! - [`parse_event_log`]: compressed onboard event log blocks with their
+//!   compression statistics
+//! - [`audit`]: append-only log of every sent command, its operator and its
+//!   verification outcome, exported as CSV or JSON
+//! - [`pass_report`]: per-contact reports generated at LOS and archived as
+//!   Markdown and JSON
+//! - [`load_command_file`] / [`load_link_forecast`] / [`load_autonomy_rule`]:
+//!   command load, link capacity forecast and onboard autonomy rule files
+//!   from disk
+//! - [`TelemetryTracker`]: latest measurement values with stale-data detection
+//! - [`soak`]: long-duration soak runs against a simulated satellite with
+//!   resource leak detection
+//! - [`scheduler`]: mission clock events with countdowns, reminders and
+//!   automatic procedures
+//! - [`pass_scheduler`]: visibility windows of the served satellites from
+//!   the station location, with queued commands and telemetry downloads
+//!   scheduled into them
+//! - [`volume_budget`]: per-pass downlink volume budgets from the pass
+//!   geometry and band, with alerts when the backlog will not fit
+//! - [`mirror`]: live downlink frames and telemetry mirrored to secondary
+//!   consumers over UDP multicast or TCP fan-out, each with its own queue
+//! - [`sbn`]: cFS Software Bus Network bridge forwarding downlinked packets to
+//!   cFS peers and uplinking the commands they publish
+//! - [`yamcs`]: YAMCS mission database export, measurement feed and command
+//!   link
+//! - [`protobuf`]: proto3 schema generated from the telemetry types and the
+//!   command dictionary, with telemetry and commands encoded to it for
+//!   consumers outside Rust
+//! - [`redundancy`]: hot-standby station pairs with replicated sequence counts
+//!   and verification state, and failover of command authority
+//! - [`link_security`]: AES-256-GCM protection of uplinks and downlinks
+//!   under the virtual channel and APID policy table, with master keys from
+//!   a key file and commanded key rotation
+//! - [`conjunction`]: catalog screening for close approaches with time and
+//!   geometry of closest approach, and collision avoidance drafts for operator
+//!   approval
+//! - [`seams`]: transport, clock and telemetry store traits the station runs
+//!   on, with UDP, system clock and in-memory implementations and test mocks
+//! - [`telemetry_archive`]: append-only telemetry archive queried by time,
+//!   APID and measurement ID, with replay through the console displays
+//!
+//! The interactive mission control console is [`console`]; the
+//! `ground-station` binary (`main.rs`) only wires its flags and settings.
+
+#[cfg(feature = "api")]
+pub mod api;
+pub mod audit;
+pub mod command;
+pub mod conjunction;
+pub mod console;
+pub mod diagnostics;
+pub mod dictionary;
+pub mod display;
+pub mod downlink;
+pub mod dry_run;
+#[cfg(feature = "ldpc")]
+pub mod ldpc_decoder;
+pub mod link_security;
+pub mod loopback;
+pub mod macros;
+pub mod mirror;
+pub mod pass_report;
+pub mod pass_scheduler;
+pub mod power_trend;
+pub mod protobuf;
+pub mod redundancy;
+pub mod sbn;
+pub mod scheduler;
+pub mod seams;
+pub mod soak;
+pub mod station;
+pub mod telemetry_archive;
+pub mod telemetry_tracker;
+#[cfg(test)]
+mod test_support;
+pub mod verification;
+pub mod volume_budget;
+pub mod yamcs;
+
+pub use command::{
+    create_command_packet, load_autonomy_rule, load_command_file, load_link_forecast, Command,
+};
+pub use display::{
+    display_load_manifest, format_cop1, format_eps_summary, format_sequence_windows,
+};
+pub use downlink::{
+    accept_downlink_sequence, parse_clcw, parse_dwell_batch, parse_eps_summary, parse_event_log,
+    parse_execution_report, parse_file_listing, parse_file_manifest, parse_load_manifest,
+    parse_loopback_echo, parse_memory_dump_segment, parse_rf_housekeeping, parse_telemetry_packet,
+    record_power_trend, DownlinkedEvent,
+};
+pub use station::{GroundStation, GroundStationConfig, StationSeams, TelemetryReceiver};
+pub use telemetry_tracker::TelemetryTracker;
+
+use std::net::SocketAddr;
+
+use space_comms_shared::{sequence::SequenceWindows, types::ComponentId};
+
+/// Component ID of the ground station in message routing
+pub const GROUND_STATION_COMPONENT: ComponentId = ComponentId::new(0x0100);
+
+/// Component ID of the satellite command handler in message routing
+pub const SATELLITE_COMPONENT: ComponentId = ComponentId::new(0x0001);
+
+/// Satellite address of the command uplink
+pub const SATELLITE_COMMAND_ADDR: SocketAddr =
+    SocketAddr::new(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST), 8080);
+
+/// Satellite address of the emergency uplink lane (emergency virtual channel)
+pub const SATELLITE_EMERGENCY_ADDR: SocketAddr =
+    SocketAddr::new(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST), 8079);
+
+/// Telemetry packets kept in the rolling history
+pub const TELEMETRY_HISTORY_LIMIT: usize = 1000;
+
+/// Downlink APIDs whose sequence counts are tracked
+const DOWNLINK_SEQUENCE_APIDS: usize = 32;
+
+/// Sequence count windows of the downlink APIDs
+pub type DownlinkSequences = SequenceWindows<DOWNLINK_SEQUENCE_APIDS>;
@@ -0,0 +1,289 @@
+//! Link security of the station's uplinks and downlinks
+//!
+//! Holds the station's copy of the virtual channel and APID security policy
+//! table and the master keys provisioned for each key slot. Once enabled,
+//! every uplinked command packet is protected as the table requires for its
+//! virtual channel and APID, and every downlinked frame is verified, and
+//! decrypted where the table calls for it, before any other check sees it.
+//!
+//! The table is kept in step with the satellite's: a policy or key rotation
+//! command is applied here as it is uplinked.
+//!
+//! # Key Files
+//! One key per line: the key slot, then the 256-bit master key as 64 hex
+//! digits. Blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # slot master key
+//! 0 8f1d...e2a4
+//! ```
+//!
+//! # Requirements Traceability
+//! - REQ-SC-001: Commandable per-virtual-channel security policy
+//! - REQ-SC-003: Authenticated encryption of packets with rotatable keys
+
+use std::path::Path;
+
+use space_comms_shared::{
+    ccsds::SpacePacket,
+    security::{
+        virtual_channels, KeyRotation, KeyStore, SecurityPolicyTable, VcSecurityPolicy, KEY_LEN,
+    },
+    wire, Result, SpaceCommError,
+};
+
+/// Station link security: policy table and key store
+#[derive(Debug, Clone, Default)]
+pub struct LinkSecurity {
+    policies: SecurityPolicyTable,
+    keys: KeyStore,
+}
+
+impl LinkSecurity {
+    /// Link security with the default policy table and `keys`
+    pub fn new(keys: KeyStore) -> Self {
+        Self {
+            policies: SecurityPolicyTable::default(),
+            keys,
+        }
+    }
+
+    /// Current policy table
+    pub fn policies(&self) -> &SecurityPolicyTable {
+        &self.policies
+    }
+
+    /// Current key generation of slot `key_id`, if a key is installed
+    pub fn generation(&self, key_id: u8) -> Option<u32> {
+        self.keys.generation(key_id)
+    }
+
+    /// Apply a virtual channel policy change commanded to the satellite
+    pub fn set_policy(&mut self, virtual_channel: u8, policy: VcSecurityPolicy) -> Result<()> {
+        self.policies
+            .set_policy(virtual_channel, policy)
+            .map(|_| ())
+    }
+
+    /// Apply a key rotation commanded to the satellite
+    pub fn rotate(&mut self, rotation: KeyRotation) -> Result<()> {
+        rotation.apply(&mut self.keys)
+    }
+
+    /// Protect an uplinked packet
+    ///
+    /// # Arguments
+    /// * `packet` - Command packet; its APID selects the virtual channel
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - Frame bytes, or cryptographic error when the
+    ///   policy's key slot holds no key
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-003: Emergency commands signed and encrypted
+    pub fn seal_uplink(&mut self, packet: &SpacePacket) -> Result<Vec<u8>> {
+        let vc = virtual_channels::uplink(packet.header.apid);
+        let sealed = self.keys.protect(&self.policies, vc, packet)?;
+        Ok(sealed.to_bytes()?.to_vec())
+    }
+
+    /// Verify and, where required, decrypt a downlinked frame
+    ///
+    /// # Arguments
+    /// * `frame` - Frame bytes after any Reed-Solomon decoding
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - Frame with its plaintext data field and a fresh
+    ///   CRC, or error for a malformed, forged, replayed or undecryptable frame
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-003: Downlinks accepted only as their policy protects them
+    pub fn open_downlink(&mut self, frame: &[u8]) -> Result<Vec<u8>> {
+        let (header, data) = wire::split_frame(frame)?;
+        let packet = SpacePacket::new(
+            header.packet_type,
+            header.apid,
+            header.sequence_count,
+            data,
+            None,
+        )?;
+        let vc = virtual_channels::downlink(header.apid);
+        let opened = self.keys.unprotect(&self.policies, vc, &packet)?;
+        Ok(opened.to_bytes()?.to_vec())
+    }
+}
+
+/// Load master keys from a key file
+///
+/// # Arguments
+/// * `path` - Key file, laid out as described in the module documentation
+///
+/// # Returns
+/// * `Result<KeyStore>` - Keys at generation 0, or configuration error for
+///   an unreadable file, a malformed line or an empty file
+///
+/// # Requirements Traceability
+/// - REQ-SC-003: Master keys provisioned out-of-band, never uplinked
+pub fn load_key_file(path: impl AsRef<Path>) -> Result<KeyStore> {
+    let text = std::fs::read_to_string(path).map_err(|_| SpaceCommError::ConfigurationError {
+        parameter: "key_file",
+        value: "<unreadable>",
+        reason: "key file could not be read",
+    })?;
+
+    parse_keys(&text)
+}
+
+/// Parse key file text into a key store
+pub fn parse_keys(text: &str) -> Result<KeyStore> {
+    let malformed = || SpaceCommError::ConfigurationError {
+        parameter: "key_file",
+        value: "<key line>",
+        reason: "expected a key slot and 64 hex digits",
+    };
+
+    let mut keys = KeyStore::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(slot), Some(key), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(malformed());
+        };
+        let key_id = slot.parse::<u8>().map_err(|_| malformed())?;
+        keys.install(key_id, parse_key(key).ok_or_else(malformed)?)?;
+    }
+
+    if keys.is_empty() {
+        return Err(SpaceCommError::ConfigurationError {
+            parameter: "key_file",
+            value: "<empty>",
+            reason: "key file holds no keys",
+        });
+    }
+    Ok(keys)
+}
+
+fn parse_key(hex: &str) -> Option<[u8; KEY_LEN]> {
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        ccsds::PacketType,
+        messaging::MessagePriority,
+        security::{SecurityService, SECURITY_OVERHEAD},
+    };
+
+    const KEY_0: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const KEY_1: &str = "FFEEDDCCBBAA99887766554433221100ffeeddccbbaa99887766554433221100";
+
+    fn keys() -> KeyStore {
+        parse_keys(&format!("# test keys\n0 {}\n\n  1 {}\n", KEY_0, KEY_1)).unwrap()
+    }
+
+    /// Frame as the satellite downlinks it on the channel of its APID
+    fn downlink(satellite: &mut LinkSecurity, apid: u16, sequence: u16) -> Vec<u8> {
+        let packet =
+            SpacePacket::new(PacketType::Telemetry, apid, sequence, b"measurements", None).unwrap();
+        let vc = virtual_channels::downlink(apid);
+        let sealed = satellite
+            .keys
+            .protect(&satellite.policies, vc, &packet)
+            .unwrap();
+        sealed.to_bytes().unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_key_file_parsing() {
+        let keys = keys();
+        assert_eq!(keys.generation(0), Some(0));
+        assert_eq!(keys.generation(1), Some(0));
+        assert_eq!(keys.generation(2), None);
+
+        assert!(parse_keys("# nothing\n").is_err());
+        assert!(parse_keys("0 0011").is_err());
+        assert!(parse_keys(&format!("x {}", KEY_0)).is_err());
+        assert!(parse_keys(&format!("9 {}", KEY_0)).is_err());
+        assert!(parse_keys(&format!("0 {} extra", KEY_0)).is_err());
+        assert!(load_key_file("/nonexistent/keys").is_err());
+    }
+
+    #[test]
+    fn test_emergency_uplink_sealed_and_opened() {
+        let mut ground = LinkSecurity::new(keys());
+        let mut satellite = ground.clone();
+        let command = b"EMERGENCY_ABORT";
+        let apid = MessagePriority::Emergency.command_apid();
+        let packet = SpacePacket::new(PacketType::Command, apid, 7, command, None).unwrap();
+
+        let frame = ground.seal_uplink(&packet).unwrap();
+        assert_eq!(
+            frame.len(),
+            packet.to_bytes().unwrap().len() + SECURITY_OVERHEAD
+        );
+        let (header, data) = wire::split_frame(&frame).unwrap();
+        assert!(!data.windows(command.len()).any(|window| window == command));
+
+        // The satellite opens it with the emergency channel's policy
+        let received = SpacePacket::new(
+            header.packet_type,
+            header.apid,
+            header.sequence_count,
+            data,
+            None,
+        )
+        .unwrap();
+        let opened = satellite
+            .keys
+            .unprotect(&satellite.policies, virtual_channels::EMERGENCY, &received)
+            .unwrap();
+        assert_eq!(&opened.data[..], command);
+    }
+
+    #[test]
+    fn test_downlink_policy_and_rotation() {
+        let mut satellite = LinkSecurity::new(keys());
+        let mut ground = satellite.clone();
+
+        // Housekeeping in the clear passes through unchanged
+        let housekeeping = downlink(&mut satellite, 0x100, 1);
+        assert_eq!(ground.open_downlink(&housekeeping).unwrap(), housekeeping);
+
+        // Science is encrypted; a replayed frame is refused
+        let science = downlink(&mut satellite, 0x013, 2);
+        let opened = ground.open_downlink(&science).unwrap();
+        assert_eq!(&opened[wire::PRIMARY_HEADER_LEN..][..12], b"measurements");
+        assert!(ground.open_downlink(&science).is_err());
+
+        // Both ends rotate; a replayed rotation cannot roll the key back
+        let rotation = KeyRotation {
+            key_id: 1,
+            generation: 1,
+        };
+        satellite.rotate(rotation).unwrap();
+        ground.rotate(rotation).unwrap();
+        assert!(ground.rotate(rotation).is_err());
+        assert_eq!(ground.generation(1), Some(1));
+        let rotated = downlink(&mut satellite, 0x013, 3);
+        assert!(ground.open_downlink(&rotated).is_ok());
+
+        // The command channel is never cleared, here as on the satellite
+        let clear = VcSecurityPolicy::new(SecurityService::Clear, 0);
+        assert!(ground.set_policy(virtual_channels::COMMAND, clear).is_err());
+        ground.set_policy(virtual_channels::SCIENCE, clear).unwrap();
+        let plain = SpacePacket::new(PacketType::Telemetry, 0x013, 4, b"plain", None).unwrap();
+        let frame = plain.to_bytes().unwrap().to_vec();
+        assert_eq!(ground.open_downlink(&frame).unwrap(), frame);
+    }
+}
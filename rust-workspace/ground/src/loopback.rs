@@ -0,0 +1,268 @@
+//! Command path loopback tests
+//!
+//! Every loopback test sent is remembered with its payload and send time
+//! until its echo comes back, then measured into a [`LoopbackResult`]: round
+//! trip, onboard turnaround, uplink and downlink delays and their asymmetry.
+//! A test whose echo has not arrived after [`LOOPBACK_TIMEOUT_MS`] is
+//! counted lost and forgotten, so a late echo is never taken for a newer
+//! test with the same ID.
+//!
+//! The tracker also notes the start of each contact, so the station can
+//! check the command path once at every AOS.
+//!
+//! Like the verification archive, the tracker does not read the clock: the
+//! caller passes the current time in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-LBK-001: Round trip, uplink and downlink delays of the command path
+//! - FN-LBK-002: Loopback test at the start of each contact
+
+use std::collections::BTreeMap;
+
+use space_comms_shared::{
+    loopback::{LoopbackEcho, LoopbackRequest, LoopbackResult, MAX_LOOPBACK_PAYLOAD},
+    types::BandType,
+    Result,
+};
+
+/// Time after which an unanswered loopback test is counted lost, milliseconds
+pub const LOOPBACK_TIMEOUT_MS: u64 = 30_000;
+
+/// Measured tests kept, oldest dropped first
+pub const MAX_LOOPBACK_RESULTS: usize = 100;
+
+/// Payload of the loopback test sent at AOS
+pub const AOS_LOOPBACK_PAYLOAD: &[u8] = b"AOS LOOPBACK";
+
+/// Test sent and not yet answered
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingTest {
+    payload: Vec<u8>,
+    sent_unix_ms: u64,
+}
+
+/// Loopback tests in flight and measured
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoopbackTracker {
+    last_test_id: u16,
+    pending: BTreeMap<u16, PendingTest>,
+    results: Vec<LoopbackResult>,
+    lost: u32,
+    aos_due: bool,
+    clock_offset_ms: i64,
+}
+
+impl LoopbackTracker {
+    /// Create a tracker with no tests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a test, returning the request to uplink
+    ///
+    /// # Arguments
+    /// * `band` - Band the satellite should echo on
+    /// * `payload` - Bytes to echo, at most `MAX_LOOPBACK_PAYLOAD`
+    /// * `now_unix_ms` - Send time, milliseconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Result<LoopbackRequest>` - Request with a fresh test ID, or error
+    ///   for an oversized payload
+    pub fn start(
+        &mut self,
+        band: BandType,
+        payload: &[u8],
+        now_unix_ms: u64,
+    ) -> Result<LoopbackRequest> {
+        self.expire(now_unix_ms);
+
+        // IDs wrap; skip any still waiting for an echo
+        let mut test_id = self.last_test_id.wrapping_add(1);
+        while test_id == 0 || self.pending.contains_key(&test_id) {
+            test_id = test_id.wrapping_add(1);
+        }
+        let request = LoopbackRequest::new(test_id, band, payload)?;
+        self.last_test_id = test_id;
+        self.pending.insert(
+            test_id,
+            PendingTest {
+                payload: payload.to_vec(),
+                sent_unix_ms: now_unix_ms,
+            },
+        );
+
+        Ok(request)
+    }
+
+    /// Set the onboard clock minus station clock used to split round trips
+    /// into uplink and downlink delays, milliseconds
+    pub fn set_clock_offset(&mut self, clock_offset_ms: i64) {
+        self.clock_offset_ms = clock_offset_ms;
+    }
+
+    /// Onboard clock minus station clock, milliseconds
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.clock_offset_ms
+    }
+
+    /// Forget a test whose command was never sent
+    pub fn abandon(&mut self, test_id: u16) {
+        self.pending.remove(&test_id);
+    }
+
+    /// Measure the test an echo answers
+    ///
+    /// # Arguments
+    /// * `echo` - Echo received
+    /// * `now_unix_ms` - Receipt time, milliseconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Option<LoopbackResult>` - Measurement, or `None` for an echo of no
+    ///   pending test
+    ///
+    /// # Requirements Traceability
+    /// - FN-LBK-001: Round trip, uplink and downlink delays of the command path
+    pub fn echo_received(
+        &mut self,
+        echo: &LoopbackEcho,
+        now_unix_ms: u64,
+    ) -> Option<LoopbackResult> {
+        self.expire(now_unix_ms);
+        let test = self.pending.remove(&echo.test_id)?;
+        let result = LoopbackResult::measure(
+            echo,
+            &test.payload,
+            test.sent_unix_ms,
+            now_unix_ms,
+            self.clock_offset_ms,
+        );
+        if self.results.len() == MAX_LOOPBACK_RESULTS {
+            self.results.remove(0);
+        }
+        self.results.push(result);
+        Some(result)
+    }
+
+    /// Note that a contact has started
+    ///
+    /// # Requirements Traceability
+    /// - FN-LBK-002: Loopback test at the start of each contact
+    pub fn contact_started(&mut self) {
+        self.aos_due = true;
+    }
+
+    /// Whether a contact has started since the last call
+    pub fn take_aos_due(&mut self) -> bool {
+        std::mem::take(&mut self.aos_due)
+    }
+
+    /// Measured tests, oldest first
+    pub fn results(&self) -> &[LoopbackResult] {
+        &self.results
+    }
+
+    /// Tests waiting for their echo
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Tests whose echo never arrived
+    pub fn lost(&self) -> u32 {
+        self.lost
+    }
+
+    /// Count and forget tests unanswered for `LOOPBACK_TIMEOUT_MS`
+    fn expire(&mut self, now_unix_ms: u64) {
+        let before = self.pending.len();
+        self.pending
+            .retain(|_, test| now_unix_ms.saturating_sub(test.sent_unix_ms) < LOOPBACK_TIMEOUT_MS);
+        self.lost += (before - self.pending.len()) as u32;
+    }
+}
+
+/// Format measured loopback tests as a table, newest last
+///
+/// # Arguments
+/// * `tracker` - Tracker holding the tests
+///
+/// # Returns
+/// * `String` - One line per test, then the pending and lost counts
+pub fn format_loopback_results(tracker: &LoopbackTracker) -> String {
+    let mut out = String::from("Test   Band     RTT ms  Up ms  Down ms  Asym ms  Payload\n");
+    for result in tracker.results() {
+        out.push_str(&format!(
+            "{:<6} {:<8} {:>6} {:>6} {:>8} {:>8}  {}\n",
+            result.test_id,
+            format!("{:?}", result.band),
+            result.round_trip_ms,
+            result.uplink_ms,
+            result.downlink_ms,
+            result.asymmetry_ms(),
+            if result.payload_intact {
+                "intact"
+            } else {
+                "CORRUPTED"
+            }
+        ));
+    }
+    out.push_str(&format!(
+        "{} pending, {} lost (max payload {} bytes)\n",
+        tracker.pending(),
+        tracker.lost(),
+        MAX_LOOPBACK_PAYLOAD
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_echo_closes_its_test() {
+        let mut tracker = LoopbackTracker::new();
+        let request = tracker.start(BandType::SBand, b"ping", 10_000).unwrap();
+        assert_eq!(request.test_id, 1);
+        assert_eq!(tracker.pending(), 1);
+
+        // 150 ms up, 10 ms onboard, 90 ms down; onboard clock 2 s behind
+        tracker.set_clock_offset(-2_000);
+        let echo = request.echo(8_150, 8_160);
+        let result = tracker.echo_received(&echo, 10_250).unwrap();
+        assert_eq!(result.round_trip_ms, 240);
+        assert_eq!((result.uplink_ms, result.downlink_ms), (150, 90));
+        assert_eq!(result.asymmetry_ms(), 60);
+        assert!(result.payload_intact);
+        assert_eq!(tracker.pending(), 0);
+
+        // A repeated echo matches nothing
+        assert!(tracker.echo_received(&echo, 10_300).is_none());
+        assert!(format_loopback_results(&tracker).contains("intact"));
+    }
+
+    #[test]
+    fn test_unanswered_tests_are_lost() {
+        let mut tracker = LoopbackTracker::new();
+        let first = tracker.start(BandType::XBand, b"one", 0).unwrap();
+        let second = tracker.start(BandType::XBand, b"two", 0).unwrap();
+        assert_ne!(first.test_id, second.test_id);
+        tracker.abandon(second.test_id);
+
+        let late = first.echo(0, 0);
+        assert!(tracker.echo_received(&late, LOOPBACK_TIMEOUT_MS).is_none());
+        assert_eq!((tracker.pending(), tracker.lost()), (0, 1));
+
+        assert!(tracker
+            .start(BandType::XBand, &[0; MAX_LOOPBACK_PAYLOAD + 1], 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_aos_flag_is_taken_once() {
+        let mut tracker = LoopbackTracker::new();
+        assert!(!tracker.take_aos_due());
+        tracker.contact_started();
+        assert!(tracker.take_aos_due());
+        assert!(!tracker.take_aos_due());
+    }
+}
@@ -0,0 +1,262 @@
+//! Console macros
+//!
+//! Operator-defined names for one or more console lines, such as `safemode2`
+//! for `send ActivateSafeMode Level2 none`. A step may use `$1` to `$9` for
+//! the arguments the macro is invoked with. Macros are kept in the console
+//! configuration file as JSON so they carry over between sessions.
+//!
+//! A macro step cannot invoke another macro, so expansion always ends.
+//!
+//! # Requirements Traceability
+//! - FN-MAC-001: Operator-defined command aliases and macros
+//! - FN-MAC-002: Macros persisted in the console configuration
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use space_comms_shared::{Result, SpaceCommError};
+
+/// Named console macros
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroSet {
+    macros: BTreeMap<String, Vec<String>>,
+}
+
+/// Configuration error about a macro
+const fn macro_error(reason: &'static str) -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter: "macro",
+        value: "<operator input>",
+        reason,
+    }
+}
+
+impl MacroSet {
+    /// Create an empty macro set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load macros from the console configuration file
+    ///
+    /// A missing file is an empty set.
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Macros, or configuration error if the file cannot
+    ///   be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                serde_json::from_slice(&bytes).map_err(|_| SpaceCommError::ConfigurationError {
+                    parameter: "console_config",
+                    value: "<malformed>",
+                    reason: "console configuration could not be parsed",
+                })
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(_) => Err(SpaceCommError::ConfigurationError {
+                parameter: "console_config",
+                value: "<unreadable>",
+                reason: "console configuration file could not be read",
+            }),
+        }
+    }
+
+    /// Write macros to the console configuration file
+    ///
+    /// # Requirements Traceability
+    /// - FN-MAC-002: Macros survive a console restart
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|_| macro_error("macros could not be serialized"))?;
+        std::fs::write(path, json).map_err(|_| SpaceCommError::ConfigurationError {
+            parameter: "console_config",
+            value: "<unwritable>",
+            reason: "console configuration file could not be written",
+        })
+    }
+
+    /// Define or replace a macro
+    ///
+    /// # Arguments
+    /// * `name` - Single word of letters, digits, `-` or `_`
+    /// * `steps` - Console lines run in order
+    /// * `reserved` - Built-in console commands a macro may not shadow
+    pub fn define(&mut self, name: &str, steps: Vec<String>, reserved: &[&str]) -> Result<()> {
+        let valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            return Err(macro_error("macro names are letters, digits, - or _"));
+        }
+        if reserved.contains(&name) {
+            return Err(macro_error("macro name is a built-in command"));
+        }
+        if steps.iter().all(|step| step.trim().is_empty()) {
+            return Err(macro_error("macro has no steps"));
+        }
+
+        let steps = steps
+            .into_iter()
+            .map(|step| step.trim().to_string())
+            .filter(|step| !step.is_empty())
+            .collect();
+        self.macros.insert(name.to_string(), steps);
+        Ok(())
+    }
+
+    /// Remove a macro, returning whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.macros.remove(name).is_some()
+    }
+
+    /// Whether `name` is a macro
+    pub fn contains(&self, name: &str) -> bool {
+        self.macros.contains_key(name)
+    }
+
+    /// Steps of a macro
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.macros.get(name).map(Vec::as_slice)
+    }
+
+    /// Macro names in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.macros.keys().map(String::as_str)
+    }
+
+    /// Console lines a macro invocation runs
+    ///
+    /// # Arguments
+    /// * `name` - Macro name
+    /// * `args` - Words following the macro name, substituted for `$1`-`$9`
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>>` - Expanded steps, or configuration error if
+    ///   the macro is unknown, an argument is missing, or a step invokes a
+    ///   macro
+    ///
+    /// # Requirements Traceability
+    /// - FN-MAC-001: Expansion never recurses
+    pub fn expand(&self, name: &str, args: &[&str]) -> Result<Vec<String>> {
+        let steps = self
+            .get(name)
+            .ok_or(macro_error("no macro with that name"))?;
+
+        steps
+            .iter()
+            .map(|step| {
+                let verb = step.split_whitespace().next().unwrap_or_default();
+                if self.contains(verb) {
+                    return Err(macro_error("macro steps cannot invoke macros"));
+                }
+                substitute(step, args)
+            })
+            .collect()
+    }
+}
+
+/// Replace `$1`-`$9` in a step with the macro arguments
+fn substitute(step: &str, args: &[&str]) -> Result<String> {
+    let mut out = String::with_capacity(step.len());
+    let mut chars = step.chars().peekable();
+    while let Some(c) = chars.next() {
+        let index = match (c, chars.peek().and_then(|next| next.to_digit(10))) {
+            ('$', Some(digit @ 1..=9)) => digit as usize - 1,
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        let arg = args
+            .get(index)
+            .ok_or(macro_error("macro invoked with too few arguments"))?;
+        out.push_str(arg);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESERVED: &[&str] = &["status", "send", "quit"];
+
+    fn steps(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_define_and_expand() {
+        let mut macros = MacroSet::new();
+        macros
+            .define(
+                "safemode2",
+                steps(&["send ActivateSafeMode Level2 none"]),
+                RESERVED,
+            )
+            .unwrap();
+        macros
+            .define(
+                "halt",
+                steps(&["send HaltSubsystem $1 true $2", " ", "status"]),
+                RESERVED,
+            )
+            .unwrap();
+
+        assert_eq!(
+            macros.expand("safemode2", &[]).unwrap(),
+            vec!["send ActivateSafeMode Level2 none"]
+        );
+        assert_eq!(
+            macros.expand("halt", &["Propulsion", "30"]).unwrap(),
+            vec!["send HaltSubsystem Propulsion true 30", "status"]
+        );
+        assert!(macros.expand("halt", &["Propulsion"]).is_err());
+        assert!(macros.expand("missing", &[]).is_err());
+        assert_eq!(
+            macros.names().collect::<Vec<_>>(),
+            vec!["halt", "safemode2"]
+        );
+
+        assert!(macros.remove("halt"));
+        assert!(!macros.contains("halt"));
+    }
+
+    #[test]
+    fn test_invalid_macros_rejected() {
+        let mut macros = MacroSet::new();
+        assert!(macros
+            .define("status", steps(&["telem"]), RESERVED)
+            .is_err());
+        assert!(macros
+            .define("two words", steps(&["telem"]), RESERVED)
+            .is_err());
+        assert!(macros.define("empty", steps(&["  "]), RESERVED).is_err());
+
+        // A step naming a macro is refused at expansion, whenever it was defined
+        macros.define("outer", steps(&["inner"]), RESERVED).unwrap();
+        macros.define("inner", steps(&["outer"]), RESERVED).unwrap();
+        assert!(macros.expand("outer", &[]).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.json");
+        assert_eq!(MacroSet::load(&path).unwrap(), MacroSet::new());
+
+        let mut macros = MacroSet::new();
+        macros
+            .define("pass", steps(&["status", "telem"]), RESERVED)
+            .unwrap();
+        macros.save(&path).unwrap();
+        assert_eq!(MacroSet::load(&path).unwrap(), macros);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(MacroSet::load(&path).is_err());
+    }
+}
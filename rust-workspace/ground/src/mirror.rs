@@ -0,0 +1,710 @@
+//! Telemetry mirroring to secondary consumers
+//!
+//! Science teams and other secondary consumers take live downlink data from
+//! the station without touching the operations path. Each configured
+//! [`MirrorEndpoint`] receives copies of the accepted downlink frames, the
+//! parsed telemetry packets, or both:
+//!
+//! - `udp://<addr>`: one datagram per record to `addr`, typically a
+//!   multicast group any number of hosts can join
+//! - `tcp://<addr>`: a listener on `addr`; every client that connects gets
+//!   the stream from then on
+//!
+//! Append `/frames` or `/telemetry` to send only that content, e.g.
+//! `udp://239.192.0.1:5000/telemetry`, and `?encoding=protobuf` to send
+//! telemetry as protobuf instead of JSON, e.g.
+//! `tcp://0.0.0.0:6000/telemetry?encoding=protobuf`.
+//!
+//! # Wire format
+//! Both transports carry the same records; a UDP datagram holds exactly one:
+//!
+//! | Offset | Size | Field                                                |
+//! |--------|------|------------------------------------------------------|
+//! | 0      | 1    | Kind: 1 = downlink frame, 2 = JSON, 3 = protobuf     |
+//! | 1      | 4    | Payload length, big-endian                           |
+//! | 5      | n    | Frame bytes, or the telemetry packet in its encoding |
+//!
+//! Protobuf telemetry is a `TelemetryPacket` message of the schema in
+//! [`crate::protobuf`].
+//!
+//! Frames are mirrored as accepted: after Reed-Solomon correction and the
+//! downlink sequence check, so duplicates and stale frames never reach a
+//! mirror.
+//!
+//! # Backpressure
+//! Every UDP endpoint and every TCP client has its own bounded queue and
+//! sender thread. The telemetry receiver only ever offers a record to a
+//! queue: when a consumer falls behind, its queue fills and further records
+//! to it are dropped and counted, while the other consumers and the
+//! operations path carry on. A TCP client that does not take a write within
+//! [`MIRROR_WRITE_TIMEOUT`] is disconnected.
+//!
+//! # Requirements Traceability
+//! - FN-MIR-001: Downlink frames and telemetry mirrored to secondary endpoints
+//! - FN-MIR-002: Independent backpressure per consumer
+//! - REQ-NF-003: System Availability (operations path never waits on a mirror)
+
+use std::fmt;
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::str::FromStr;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use space_comms_shared::{telemetry::TelemetryPacket, Result, SpaceCommError};
+
+use crate::protobuf;
+
+/// Records queued per consumer before further records are dropped
+pub const MIRROR_QUEUE_DEPTH: usize = 1024;
+
+/// Time a TCP client has to take a record before it is disconnected
+pub const MIRROR_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Hop limit of multicast mirror datagrams: stays on the site network
+pub const DEFAULT_MULTICAST_TTL: u32 = 4;
+
+/// Length of the record header
+pub const RECORD_HEADER_LEN: usize = 5;
+
+/// Content of one mirror record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    /// Downlink frame as accepted by the station
+    Frame = 1,
+    /// Parsed telemetry packet as JSON
+    Telemetry = 2,
+    /// Parsed telemetry packet as a protobuf `TelemetryPacket` message
+    TelemetryProtobuf = 3,
+}
+
+impl RecordKind {
+    /// Kind of a record header byte
+    pub fn from_byte(byte: u8) -> Option<RecordKind> {
+        match byte {
+            1 => Some(RecordKind::Frame),
+            2 => Some(RecordKind::Telemetry),
+            3 => Some(RecordKind::TelemetryProtobuf),
+            _ => None,
+        }
+    }
+}
+
+/// Records an endpoint is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorContent {
+    /// Downlink frames only
+    Frames,
+    /// Parsed telemetry only
+    Telemetry,
+    /// Both
+    #[default]
+    All,
+}
+
+impl MirrorContent {
+    /// Whether records of `kind` go to the endpoint
+    pub fn carries(self, kind: RecordKind) -> bool {
+        matches!(
+            (self, kind),
+            (MirrorContent::All, _)
+                | (MirrorContent::Frames, RecordKind::Frame)
+                | (
+                    MirrorContent::Telemetry,
+                    RecordKind::Telemetry | RecordKind::TelemetryProtobuf
+                )
+        )
+    }
+}
+
+/// Encoding of the telemetry records an endpoint is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorEncoding {
+    /// JSON of the shared telemetry packet
+    #[default]
+    Json,
+    /// Protobuf message of the generated schema
+    Protobuf,
+}
+
+/// How records reach an endpoint's consumers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorTransport {
+    /// Datagrams to one address; multicast groups get `multicast_ttl`
+    Udp {
+        addr: SocketAddr,
+        multicast_ttl: u32,
+    },
+    /// Stream to every client of a listener on `listen_addr`
+    TcpFanOut { listen_addr: SocketAddr },
+}
+
+/// One secondary endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MirrorEndpoint {
+    /// Transport and address
+    pub transport: MirrorTransport,
+    /// Records sent to it
+    pub content: MirrorContent,
+    /// Encoding of the telemetry records sent to it
+    pub encoding: MirrorEncoding,
+    /// Records queued per consumer before dropping
+    pub queue_depth: usize,
+}
+
+impl MirrorEndpoint {
+    /// Endpoint over `transport` with every record and the default queue
+    pub fn new(transport: MirrorTransport) -> Self {
+        Self {
+            transport,
+            content: MirrorContent::All,
+            encoding: MirrorEncoding::Json,
+            queue_depth: MIRROR_QUEUE_DEPTH,
+        }
+    }
+
+    /// Whether records of `kind` go to the endpoint, by content and
+    /// telemetry encoding
+    pub fn carries(&self, kind: RecordKind) -> bool {
+        let encoded = match kind {
+            RecordKind::Frame => true,
+            RecordKind::Telemetry => self.encoding == MirrorEncoding::Json,
+            RecordKind::TelemetryProtobuf => self.encoding == MirrorEncoding::Protobuf,
+        };
+        encoded && self.content.carries(kind)
+    }
+}
+
+impl fmt::Display for MirrorEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.transport {
+            MirrorTransport::Udp { addr, .. } => write!(f, "udp://{}", addr)?,
+            MirrorTransport::TcpFanOut { listen_addr } => write!(f, "tcp://{}", listen_addr)?,
+        }
+        match self.content {
+            MirrorContent::Frames => f.write_str("/frames")?,
+            MirrorContent::Telemetry => f.write_str("/telemetry")?,
+            MirrorContent::All => {}
+        }
+        match self.encoding {
+            MirrorEncoding::Json => Ok(()),
+            MirrorEncoding::Protobuf => f.write_str("?encoding=protobuf"),
+        }
+    }
+}
+
+impl FromStr for MirrorEndpoint {
+    type Err = SpaceCommError;
+
+    /// Parse `udp://<addr>` or `tcp://<addr>`, optionally followed by
+    /// `/frames` or `/telemetry` and `?encoding=json` or `?encoding=protobuf`
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = |reason| SpaceCommError::ConfigurationError {
+            parameter: "mirror",
+            value: "invalid",
+            reason,
+        };
+        let (text, encoding) = match text.split_once('?') {
+            None => (text, MirrorEncoding::Json),
+            Some((text, "encoding=json")) => (text, MirrorEncoding::Json),
+            Some((text, "encoding=protobuf")) => (text, MirrorEncoding::Protobuf),
+            Some(_) => return Err(invalid("mirror encoding must be json or protobuf")),
+        };
+        let (scheme, rest) = text
+            .split_once("://")
+            .ok_or_else(|| invalid("mirror must be udp://<addr> or tcp://<addr>"))?;
+        let (addr, content) = match rest.split_once('/') {
+            None => (rest, MirrorContent::All),
+            Some((addr, "frames")) => (addr, MirrorContent::Frames),
+            Some((addr, "telemetry")) => (addr, MirrorContent::Telemetry),
+            Some(_) => return Err(invalid("mirror content must be frames or telemetry")),
+        };
+        let addr: SocketAddr = addr
+            .parse()
+            .map_err(|_| invalid("mirror address must be an IP address and port"))?;
+        let transport = match scheme {
+            "udp" => MirrorTransport::Udp {
+                addr,
+                multicast_ttl: DEFAULT_MULTICAST_TTL,
+            },
+            "tcp" => MirrorTransport::TcpFanOut { listen_addr: addr },
+            _ => return Err(invalid("mirror transport must be udp or tcp")),
+        };
+        Ok(Self {
+            content,
+            encoding,
+            ..MirrorEndpoint::new(transport)
+        })
+    }
+}
+
+/// Encode one mirror record
+pub fn encode_record(kind: RecordKind, payload: &[u8]) -> Vec<u8> {
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+    record.push(kind as u8);
+    record.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    record.extend_from_slice(payload);
+    record
+}
+
+/// Decode the record at the start of `bytes`
+///
+/// # Returns
+/// * Kind, payload and the length of the whole record; `None` if `bytes`
+///   holds no complete record or an unknown kind
+pub fn decode_record(bytes: &[u8]) -> Option<(RecordKind, &[u8], usize)> {
+    let header = bytes.get(..RECORD_HEADER_LEN)?;
+    let kind = RecordKind::from_byte(header[0])?;
+    let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    let payload = bytes.get(RECORD_HEADER_LEN..RECORD_HEADER_LEN + len)?;
+    Some((kind, payload, RECORD_HEADER_LEN + len))
+}
+
+/// Delivery counts of one consumer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerStats {
+    /// Endpoint the consumer is on
+    pub endpoint: String,
+    /// Consumer: the UDP address, or the TCP client's address
+    pub consumer: String,
+    /// Still receiving records
+    pub connected: bool,
+    /// Records written to the consumer
+    pub sent: u64,
+    /// Records dropped because its queue was full
+    pub dropped: u64,
+}
+
+/// Queue and counts of one consumer, shared with its sender thread
+struct Consumer {
+    queue: SyncSender<Arc<Vec<u8>>>,
+    stats: Arc<Mutex<ConsumerStats>>,
+}
+
+impl Consumer {
+    /// Consumer with an empty queue of `depth` records, and the receiving
+    /// end its sender thread drains
+    fn new(endpoint: String, consumer: String, depth: usize) -> (Self, Receiver<Arc<Vec<u8>>>) {
+        let (queue, records) = sync_channel(depth);
+        let stats = ConsumerStats {
+            endpoint,
+            consumer,
+            connected: true,
+            sent: 0,
+            dropped: 0,
+        };
+        let consumer = Self {
+            queue,
+            stats: Arc::new(Mutex::new(stats)),
+        };
+        (consumer, records)
+    }
+
+    /// Queue `record` without waiting
+    ///
+    /// - **ID**: FN-MIR-002
+    /// - **Requirement**: A slow or stalled consumer costs only its own
+    ///   records; the caller never waits on it.
+    /// - **Inputs**: Encoded record, shared between consumers.
+    /// - **Outputs**: `false` once the consumer's sender thread has stopped.
+    /// - **Side Effects**: Counts the record dropped if the queue is full.
+    /// - **Failure Modes**: None.
+    fn offer(&self, record: &Arc<Vec<u8>>) -> bool {
+        match self.queue.try_send(Arc::clone(record)) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.stats.lock().unwrap().dropped += 1;
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                self.stats.lock().unwrap().connected = false;
+                false
+            }
+        }
+    }
+}
+
+/// Drain `records` into `send` until it fails or the mirror is dropped
+fn drain(
+    records: Receiver<Arc<Vec<u8>>>,
+    stats: Arc<Mutex<ConsumerStats>>,
+    mut send: impl FnMut(&[u8]) -> std::io::Result<()>,
+) {
+    for record in records {
+        if let Err(e) = send(&record) {
+            let mut stats = stats.lock().unwrap();
+            eprintln!(
+                "Mirror {} to {} stopped: {}",
+                stats.endpoint, stats.consumer, e
+            );
+            stats.connected = false;
+            return;
+        }
+        stats.lock().unwrap().sent += 1;
+    }
+}
+
+/// One configured endpoint and its consumers
+struct Endpoint {
+    endpoint: MirrorEndpoint,
+    local_addr: SocketAddr,
+    consumers: Arc<Mutex<Vec<Consumer>>>,
+}
+
+/// Secondary endpoints fed by the telemetry receiver
+///
+/// Owns a sender thread per consumer and, for TCP endpoints, an accept
+/// thread; dropping the mirror stops the sender threads after their queues
+/// drain.
+pub struct TelemetryMirror {
+    endpoints: Vec<Endpoint>,
+}
+
+impl TelemetryMirror {
+    /// Open every endpoint and start its threads
+    ///
+    /// - **ID**: FN-MIR-001
+    /// - **Requirement**: Secondary consumers receive live downlink data
+    ///   over UDP (multicast) or TCP fan-out.
+    /// - **Inputs**: Endpoints; a TCP port of 0 picks a free port.
+    /// - **Outputs**: The running mirror.
+    /// - **Side Effects**: Binds a UDP socket or TCP listener per endpoint
+    ///   and spawns its threads.
+    /// - **Failure Modes**: Zero queue depth, or a socket that cannot be
+    ///   bound or configured → `Err`.
+    pub fn start(endpoints: &[MirrorEndpoint]) -> Result<Self> {
+        let endpoints = endpoints
+            .iter()
+            .map(|&endpoint| {
+                if endpoint.queue_depth == 0 {
+                    return Err(SpaceCommError::ConfigurationError {
+                        parameter: "mirror",
+                        value: "queue_depth",
+                        reason: "mirror queue depth must be at least one record",
+                    });
+                }
+                match endpoint.transport {
+                    MirrorTransport::Udp {
+                        addr,
+                        multicast_ttl,
+                    } => start_udp(endpoint, addr, multicast_ttl),
+                    MirrorTransport::TcpFanOut { listen_addr } => start_tcp(endpoint, listen_addr),
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { endpoints })
+    }
+
+    /// Whether no endpoint is configured
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Address each endpoint is bound to, in configuration order: the
+    /// sending UDP socket, or the TCP listener
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.endpoints.iter().map(|e| e.local_addr).collect()
+    }
+
+    /// Mirror a downlink frame as accepted by the station
+    pub fn mirror_frame(&self, frame: &[u8]) {
+        self.publish(RecordKind::Frame, frame);
+    }
+
+    /// Mirror a parsed telemetry packet, encoded once for each encoding
+    /// an endpoint takes
+    pub fn mirror_telemetry(&self, packet: &TelemetryPacket) {
+        if self.carries(RecordKind::Telemetry) {
+            match serde_json::to_vec(packet) {
+                Ok(json) => self.publish(RecordKind::Telemetry, &json),
+                Err(e) => eprintln!("Failed to encode mirrored telemetry: {}", e),
+            }
+        }
+        if self.carries(RecordKind::TelemetryProtobuf) {
+            let message = protobuf::encode_telemetry(packet);
+            self.publish(RecordKind::TelemetryProtobuf, &message);
+        }
+    }
+
+    /// Whether any endpoint carries records of `kind`
+    fn carries(&self, kind: RecordKind) -> bool {
+        self.endpoints.iter().any(|e| e.endpoint.carries(kind))
+    }
+
+    /// Offer one record to every consumer of the endpoints carrying it;
+    /// consumers whose sender thread has stopped are removed
+    fn publish(&self, kind: RecordKind, payload: &[u8]) {
+        let mut record = None;
+        for endpoint in &self.endpoints {
+            if !endpoint.endpoint.carries(kind) {
+                continue;
+            }
+            let record = record.get_or_insert_with(|| Arc::new(encode_record(kind, payload)));
+            endpoint
+                .consumers
+                .lock()
+                .unwrap()
+                .retain(|consumer| consumer.offer(record));
+        }
+    }
+
+    /// Delivery counts of every current consumer, by endpoint
+    pub fn statistics(&self) -> Vec<ConsumerStats> {
+        self.endpoints
+            .iter()
+            .flat_map(|endpoint| {
+                endpoint
+                    .consumers
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|consumer| consumer.stats.lock().unwrap().clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Configured endpoints
+    pub fn endpoints(&self) -> Vec<MirrorEndpoint> {
+        self.endpoints.iter().map(|e| e.endpoint).collect()
+    }
+}
+
+/// UDP endpoint: one consumer, the destination address
+fn start_udp(endpoint: MirrorEndpoint, addr: SocketAddr, multicast_ttl: u32) -> Result<Endpoint> {
+    let bind: SocketAddr = if addr.is_ipv4() {
+        "0.0.0.0:0".parse().unwrap()
+    } else {
+        "[::]:0".parse().unwrap()
+    };
+    let socket = UdpSocket::bind(bind).map_err(|e| {
+        SpaceCommError::communication_timeout(1000, &format!("Failed to bind mirror socket: {}", e))
+    })?;
+    if addr.ip().is_multicast() && addr.is_ipv4() {
+        socket.set_multicast_ttl_v4(multicast_ttl).map_err(|e| {
+            SpaceCommError::communication_timeout(
+                1000,
+                &format!("Failed to set mirror multicast TTL: {}", e),
+            )
+        })?;
+    }
+    let local_addr = socket.local_addr().map_err(|e| {
+        SpaceCommError::communication_timeout(1000, &format!("Mirror socket: {}", e))
+    })?;
+
+    let (consumer, records) =
+        Consumer::new(endpoint.to_string(), addr.to_string(), endpoint.queue_depth);
+    let stats = Arc::clone(&consumer.stats);
+    thread::spawn(move || {
+        drain(records, stats, |record| {
+            socket.send_to(record, addr).map(|_| ())
+        })
+    });
+
+    Ok(Endpoint {
+        endpoint,
+        local_addr,
+        consumers: Arc::new(Mutex::new(vec![consumer])),
+    })
+}
+
+/// TCP endpoint: a consumer per accepted client
+fn start_tcp(endpoint: MirrorEndpoint, listen_addr: SocketAddr) -> Result<Endpoint> {
+    let listener = TcpListener::bind(listen_addr).map_err(|e| {
+        SpaceCommError::communication_timeout(
+            1000,
+            &format!("Failed to bind mirror listener: {}", e),
+        )
+    })?;
+    let local_addr = listener.local_addr().map_err(|e| {
+        SpaceCommError::communication_timeout(1000, &format!("Mirror listener: {}", e))
+    })?;
+
+    let consumers = Arc::new(Mutex::new(Vec::new()));
+    let accepted = Arc::clone(&consumers);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = accept(endpoint, stream, &accepted) {
+                        eprintln!("Mirror {} client refused: {}", endpoint, e);
+                    }
+                }
+                Err(e) => eprintln!("Mirror {} accept failed: {}", endpoint, e),
+            }
+        }
+    });
+
+    Ok(Endpoint {
+        endpoint,
+        local_addr,
+        consumers,
+    })
+}
+
+/// Start streaming to a newly connected TCP client
+fn accept(
+    endpoint: MirrorEndpoint,
+    mut stream: TcpStream,
+    consumers: &Mutex<Vec<Consumer>>,
+) -> std::io::Result<()> {
+    stream.set_write_timeout(Some(MIRROR_WRITE_TIMEOUT))?;
+    stream.set_nodelay(true)?;
+    let peer = stream.peer_addr()?;
+    println!("Mirror {}: client {} connected", endpoint, peer);
+
+    let (consumer, records) =
+        Consumer::new(endpoint.to_string(), peer.to_string(), endpoint.queue_depth);
+    let stats = Arc::clone(&consumer.stats);
+    thread::spawn(move || drain(records, stats, |record| stream.write_all(record)));
+    consumers.lock().unwrap().push(consumer);
+    Ok(())
+}
+
+/// Format mirror delivery counts as a table, one consumer per line
+pub fn format_mirror_statistics(statistics: &[ConsumerStats]) -> String {
+    if statistics.is_empty() {
+        return "No mirror consumers\n".to_string();
+    }
+    let mut out = String::from(
+        "  Endpoint                         Consumer               State      Sent      Dropped\n",
+    );
+    for stats in statistics {
+        out.push_str(&format!(
+            "  {:<32} {:<22} {:<10} {:<9} {}\n",
+            stats.endpoint,
+            stats.consumer,
+            if stats.connected { "LIVE" } else { "STOPPED" },
+            stats.sent,
+            stats.dropped
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn endpoint(text: &str) -> MirrorEndpoint {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_mirror_endpoint_parsing_and_records() {
+        let multicast = endpoint("udp://239.192.0.1:5000/telemetry");
+        assert_eq!(
+            multicast.transport,
+            MirrorTransport::Udp {
+                addr: "239.192.0.1:5000".parse().unwrap(),
+                multicast_ttl: DEFAULT_MULTICAST_TTL,
+            }
+        );
+        assert_eq!(multicast.content, MirrorContent::Telemetry);
+        assert_eq!(multicast.to_string(), "udp://239.192.0.1:5000/telemetry");
+        assert_eq!(endpoint("tcp://0.0.0.0:6000").content, MirrorContent::All);
+        assert_eq!(multicast.encoding, MirrorEncoding::Json);
+        let protobuf = endpoint("tcp://0.0.0.0:6000/telemetry?encoding=protobuf");
+        assert_eq!(protobuf.encoding, MirrorEncoding::Protobuf);
+        assert_eq!(
+            protobuf.to_string(),
+            "tcp://0.0.0.0:6000/telemetry?encoding=protobuf"
+        );
+        assert!(protobuf.carries(RecordKind::TelemetryProtobuf));
+        assert!(!protobuf.carries(RecordKind::Telemetry));
+        assert!(!multicast.carries(RecordKind::TelemetryProtobuf));
+        assert_eq!(
+            endpoint("udp://1.2.3.4:5?encoding=json").encoding,
+            MirrorEncoding::Json
+        );
+        for bad in [
+            "239.192.0.1:5000",
+            "sctp://1.2.3.4:5",
+            "udp://host:5",
+            "tcp://1.2.3.4:5/raw",
+            "tcp://1.2.3.4:5/telemetry?encoding=xml",
+        ] {
+            assert!(bad.parse::<MirrorEndpoint>().is_err(), "{}", bad);
+        }
+
+        assert!(MirrorContent::Frames.carries(RecordKind::Frame));
+        assert!(!MirrorContent::Frames.carries(RecordKind::Telemetry));
+        assert!(MirrorContent::All.carries(RecordKind::Telemetry));
+
+        let record = encode_record(RecordKind::Frame, &[0xAB, 0xCD]);
+        assert_eq!(record, vec![1, 0, 0, 0, 2, 0xAB, 0xCD]);
+        assert_eq!(
+            decode_record(&record),
+            Some((RecordKind::Frame, &[0xAB, 0xCD][..], 7))
+        );
+        assert_eq!(decode_record(&record[..6]), None);
+    }
+
+    #[test]
+    fn test_full_queue_drops_only_for_its_consumer() {
+        let (slow, _undrained) = Consumer::new("tcp://test".into(), "slow".into(), 2);
+        let (fast, records) = Consumer::new("tcp://test".into(), "fast".into(), 8);
+        let record = Arc::new(encode_record(RecordKind::Frame, &[1]));
+        for _ in 0..5 {
+            assert!(slow.offer(&record));
+            assert!(fast.offer(&record));
+        }
+        assert_eq!(slow.stats.lock().unwrap().dropped, 3);
+        assert_eq!(fast.stats.lock().unwrap().dropped, 0);
+        assert_eq!(records.try_iter().count(), 5);
+
+        // A consumer whose sender thread stopped is reported and removed
+        drop(records);
+        assert!(!fast.offer(&record));
+        assert!(!fast.stats.lock().unwrap().connected);
+    }
+
+    #[test]
+    fn test_mirror_delivers_over_udp_and_tcp() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let udp = format!("udp://{}/frames", receiver.local_addr().unwrap());
+        let mirror =
+            TelemetryMirror::start(&[endpoint(&udp), endpoint("tcp://127.0.0.1:0")]).unwrap();
+
+        let mut client = TcpStream::connect(mirror.local_addrs()[1]).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        // The client is streamed to once its connection is accepted
+        for _ in 0..200 {
+            if mirror.statistics().len() == 2 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        mirror.mirror_frame(&[0x08, 0x01]);
+        let mut datagram = [0u8; 64];
+        let size = receiver.recv(&mut datagram).unwrap();
+        assert_eq!(
+            decode_record(&datagram[..size]),
+            Some((RecordKind::Frame, &[0x08, 0x01][..], size))
+        );
+        let mut streamed = [0u8; RECORD_HEADER_LEN + 2];
+        client.read_exact(&mut streamed).unwrap();
+        assert_eq!(
+            streamed,
+            encode_record(RecordKind::Frame, &[0x08, 0x01])[..]
+        );
+
+        let table = format_mirror_statistics(&mirror.statistics());
+        assert!(table.contains(&udp));
+        assert!(table.contains("LIVE"));
+        assert!(TelemetryMirror::start(&[MirrorEndpoint {
+            queue_depth: 0,
+            ..endpoint("tcp://127.0.0.1:0")
+        }])
+        .is_err());
+    }
+}
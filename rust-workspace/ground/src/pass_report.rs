@@ -0,0 +1,500 @@
+//! Ground pass reports
+//!
+//! A pass runs from acquisition of signal (AOS), the first downlink frame
+//! received with no pass in progress, to loss of signal (LOS), declared when
+//! the link monitor finds the downlink silent. While a pass is open the
+//! [`PassTracker`] accumulates what happened during it: the bands used, data
+//! volume each way, commands sent and their verification outcomes, downlink
+//! SNR and alarms raised. At LOS the tracker closes the pass into a
+//! [`PassReport`], and the [`PassArchive`] writes it as Markdown for the
+//! operators and JSON for tools, one pair of files per pass.
+//!
+//! LOS is dated by the last frame received, not by when the silence was
+//! noticed, so the reported duration does not include the monitor's
+//! detection delay. Like the verification archive, nothing here reads the
+//! clock: callers pass times in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-PAS-001: Pass report generated at every LOS
+//! - FN-PAS-002: Pass reports archived as Markdown and JSON
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use space_comms_shared::{
+    execution_report::ExecutionReport,
+    margin::{MarginKind, MarginShortfall},
+    types::BandType,
+    Result, SpaceCommError,
+};
+
+use crate::verification::VerificationSummary;
+
+/// Thermal noise power spectral density at 290 K, dBm/Hz
+const THERMAL_NOISE_DBM_PER_HZ: f64 = -174.0;
+
+/// Estimate downlink SNR from received signal strength
+///
+/// Compares the signal with the thermal noise floor over a bandwidth equal to
+/// the downlink data rate.
+///
+/// # Arguments
+/// * `signal_strength_dbm` - Received signal strength, dBm
+/// * `data_rate_bps` - Downlink data rate, bits per second
+pub fn estimate_snr_db(signal_strength_dbm: f64, data_rate_bps: u64) -> f64 {
+    let noise_floor_dbm = THERMAL_NOISE_DBM_PER_HZ + 10.0 * (data_rate_bps.max(1) as f64).log10();
+    signal_strength_dbm - noise_floor_dbm
+}
+
+/// Alarm raised during a pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassAlarm {
+    /// Time the alarm was raised, milliseconds since the Unix epoch
+    pub time_unix_ms: u64,
+    /// What was alarmed
+    pub description: String,
+}
+
+/// Summary of one contact, AOS to LOS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassReport {
+    /// Station that held the contact
+    pub station_id: String,
+    /// First downlink frame, milliseconds since the Unix epoch
+    pub aos_unix_ms: u64,
+    /// Last downlink frame, milliseconds since the Unix epoch
+    pub los_unix_ms: u64,
+    /// Bands commands were uplinked on
+    pub uplink_bands: Vec<BandType>,
+    /// Bands frames were received on
+    pub downlink_bands: Vec<BandType>,
+    /// Bytes uplinked
+    pub bytes_uplinked: u64,
+    /// Bytes received
+    pub bytes_downlinked: u64,
+    /// Commands sent, whether or not the uplink succeeded
+    pub commands_sent: usize,
+    /// Commands whose uplink failed after all retries
+    pub uplink_failures: usize,
+    /// Execution reports received during the pass
+    pub verification: VerificationSummary,
+    /// Mean downlink SNR, dB, if any was measured
+    pub mean_snr_db: Option<f64>,
+    /// Lowest downlink SNR, dB, if any was measured
+    pub min_snr_db: Option<f64>,
+    /// Alarms in the order raised
+    pub alarms: Vec<PassAlarm>,
+}
+
+impl PassReport {
+    /// Contact duration in seconds
+    pub fn duration_s(&self) -> f64 {
+        self.los_unix_ms.saturating_sub(self.aos_unix_ms) as f64 / 1000.0
+    }
+
+    /// File name of the report without extension
+    pub fn file_stem(&self) -> String {
+        format!("pass_{}_{}", self.station_id, self.aos_unix_ms)
+    }
+
+    /// Render the report as Markdown
+    ///
+    /// # Requirements Traceability
+    /// - FN-PAS-002: Operator-readable pass report
+    pub fn to_markdown(&self) -> String {
+        let bands = |bands: &[BandType]| {
+            if bands.is_empty() {
+                "none".to_string()
+            } else {
+                bands
+                    .iter()
+                    .map(|band| format!("{:?}", band))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+        let snr = |snr: Option<f64>| match snr {
+            Some(db) => format!("{:.1} dB", db),
+            None => "not measured".to_string(),
+        };
+        let verification = &self.verification;
+
+        let mut out = format!(
+            "# Pass report {} AOS {}\n\n| Item | Value |\n|---|---|\n",
+            self.station_id, self.aos_unix_ms
+        );
+        let rows = [
+            ("AOS (Unix ms)", self.aos_unix_ms.to_string()),
+            ("LOS (Unix ms)", self.los_unix_ms.to_string()),
+            ("Duration", format!("{:.1} s", self.duration_s())),
+            ("Uplink bands", bands(&self.uplink_bands)),
+            ("Downlink bands", bands(&self.downlink_bands)),
+            ("Data uplinked", format!("{} bytes", self.bytes_uplinked)),
+            (
+                "Data downlinked",
+                format!("{} bytes", self.bytes_downlinked),
+            ),
+            (
+                "Commands sent",
+                format!(
+                    "{} ({} uplink failures)",
+                    self.commands_sent, self.uplink_failures
+                ),
+            ),
+            (
+                "Verification",
+                format!(
+                    "{} reports: {} completed, {} failed, {} rejected, {} over budget",
+                    verification.total,
+                    verification.completed,
+                    verification.failed,
+                    verification.rejected,
+                    verification.overruns
+                ),
+            ),
+            ("Mean SNR", snr(self.mean_snr_db)),
+            ("Minimum SNR", snr(self.min_snr_db)),
+        ];
+        for (item, value) in rows {
+            let _ = writeln!(out, "| {} | {} |", item, value);
+        }
+
+        out.push_str("\n## Alarms\n\n");
+        if self.alarms.is_empty() {
+            out.push_str("None\n");
+        }
+        for alarm in &self.alarms {
+            let _ = writeln!(out, "- {}: {}", alarm.time_unix_ms, alarm.description);
+        }
+        out
+    }
+
+    /// Render the report as JSON
+    ///
+    /// # Requirements Traceability
+    /// - FN-PAS-002: Machine-readable pass report
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Contact in progress
+#[derive(Debug, Clone, Default)]
+struct OpenPass {
+    aos_unix_ms: u64,
+    last_contact_unix_ms: u64,
+    uplink_bands: Vec<BandType>,
+    downlink_bands: Vec<BandType>,
+    bytes_uplinked: u64,
+    bytes_downlinked: u64,
+    commands_sent: usize,
+    uplink_failures: usize,
+    verification: VerificationSummary,
+    snr_sum_db: f64,
+    snr_samples: usize,
+    min_snr_db: Option<f64>,
+    alarms: Vec<PassAlarm>,
+    margins_short: Vec<MarginKind>,
+}
+
+/// Accumulates the activity of the contact in progress
+///
+/// Uplink activity, execution reports, SNR samples and alarms outside a
+/// contact belong to no pass and are not counted.
+#[derive(Debug, Clone, Default)]
+pub struct PassTracker {
+    pass: Option<OpenPass>,
+}
+
+impl PassTracker {
+    /// Create a tracker with no contact in progress
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a contact is in progress
+    pub fn in_pass(&self) -> bool {
+        self.pass.is_some()
+    }
+
+    /// Record a received frame, starting a pass at AOS
+    pub fn downlinked(&mut self, bytes: usize, band: BandType, now_unix_ms: u64) {
+        let pass = self.pass.get_or_insert_with(|| OpenPass {
+            aos_unix_ms: now_unix_ms,
+            ..OpenPass::default()
+        });
+        pass.last_contact_unix_ms = now_unix_ms;
+        pass.bytes_downlinked += bytes as u64;
+        add_band(&mut pass.downlink_bands, band);
+    }
+
+    /// Record bytes uplinked on `band`
+    pub fn uplinked(&mut self, bytes: usize, band: BandType) {
+        if let Some(pass) = &mut self.pass {
+            pass.bytes_uplinked += bytes as u64;
+            add_band(&mut pass.uplink_bands, band);
+        }
+    }
+
+    /// Record a command sent, and whether its uplink succeeded
+    pub fn command_sent(&mut self, uplinked: bool) {
+        if let Some(pass) = &mut self.pass {
+            pass.commands_sent += 1;
+            pass.uplink_failures += usize::from(!uplinked);
+        }
+    }
+
+    /// Record a received execution report
+    pub fn execution_reported(&mut self, report: &ExecutionReport) {
+        if let Some(pass) = &mut self.pass {
+            pass.verification.record(report);
+        }
+    }
+
+    /// Record a downlink SNR measurement
+    pub fn snr_measured(&mut self, snr_db: f64) {
+        if let Some(pass) = &mut self.pass {
+            pass.snr_sum_db += snr_db;
+            pass.snr_samples += 1;
+            pass.min_snr_db = Some(pass.min_snr_db.map_or(snr_db, |min| min.min(snr_db)));
+        }
+    }
+
+    /// Lowest downlink SNR measured in the pass in progress, dB
+    pub fn min_snr_db(&self) -> Option<f64> {
+        self.pass.as_ref().and_then(|pass| pass.min_snr_db)
+    }
+
+    /// Record an alarm raised during the pass
+    pub fn alarm(&mut self, description: String, now_unix_ms: u64) {
+        if let Some(pass) = &mut self.pass {
+            pass.alarms.push(PassAlarm {
+                time_unix_ms: now_unix_ms,
+                description,
+            });
+        }
+    }
+
+    /// Record a margin found short, once per kind of margin in a pass
+    ///
+    /// # Arguments
+    /// * `shortfall` - Margin below the policy requirement
+    /// * `now_unix_ms` - Time the shortfall was measured
+    ///
+    /// # Returns
+    /// * `bool` - Whether this is the first shortfall of its kind in the
+    ///   pass; later ones are not recorded
+    ///
+    /// # Requirements Traceability
+    /// - FN-MAR-002: Margin shortfalls reported with the pass
+    pub fn margin_short(&mut self, shortfall: MarginShortfall, now_unix_ms: u64) -> bool {
+        let Some(pass) = &mut self.pass else {
+            return false;
+        };
+        if pass.margins_short.contains(&shortfall.kind) {
+            return false;
+        }
+        pass.margins_short.push(shortfall.kind);
+        pass.alarms.push(PassAlarm {
+            time_unix_ms: now_unix_ms,
+            description: shortfall.to_string(),
+        });
+        true
+    }
+
+    /// Close the contact at LOS
+    ///
+    /// # Arguments
+    /// * `station_id` - Station that held the contact
+    ///
+    /// # Returns
+    /// * `Option<PassReport>` - Report of the pass, or `None` if no contact
+    ///   was in progress
+    ///
+    /// # Requirements Traceability
+    /// - FN-PAS-001: One report per contact, dated AOS to last frame
+    pub fn close(&mut self, station_id: &str) -> Option<PassReport> {
+        let pass = self.pass.take()?;
+        Some(PassReport {
+            station_id: station_id.to_string(),
+            aos_unix_ms: pass.aos_unix_ms,
+            los_unix_ms: pass.last_contact_unix_ms,
+            uplink_bands: pass.uplink_bands,
+            downlink_bands: pass.downlink_bands,
+            bytes_uplinked: pass.bytes_uplinked,
+            bytes_downlinked: pass.bytes_downlinked,
+            commands_sent: pass.commands_sent,
+            uplink_failures: pass.uplink_failures,
+            verification: pass.verification,
+            mean_snr_db: (pass.snr_samples > 0).then(|| pass.snr_sum_db / pass.snr_samples as f64),
+            min_snr_db: pass.min_snr_db,
+            alarms: pass.alarms,
+        })
+    }
+}
+
+/// Add `band` to `bands` unless already listed
+fn add_band(bands: &mut Vec<BandType>, band: BandType) {
+    if !bands.contains(&band) {
+        bands.push(band);
+    }
+}
+
+/// Completed pass reports, kept on disk as Markdown and JSON
+#[derive(Debug, Clone, Default)]
+pub struct PassArchive {
+    reports: Vec<PassReport>,
+    dir: Option<PathBuf>,
+}
+
+impl PassArchive {
+    /// Create an archive kept in memory only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the archive directory, reading back the JSON reports already in it
+    ///
+    /// The directory is created if it does not exist.
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Archive with its reports in AOS order, or
+    ///   configuration error if the directory cannot be read or holds a
+    ///   malformed report
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let unreadable = SpaceCommError::ConfigurationError {
+            parameter: "pass_report_dir",
+            value: "<unreadable>",
+            reason: "pass report directory could not be read",
+        };
+        std::fs::create_dir_all(dir).map_err(|_| unreadable.clone())?;
+
+        let mut reports = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(|_| unreadable.clone())? {
+            let path = entry.map_err(|_| unreadable.clone())?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let report = std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<PassReport>(&bytes).ok())
+                .ok_or(SpaceCommError::ConfigurationError {
+                    parameter: "pass_report_dir",
+                    value: "<malformed report>",
+                    reason: "pass report could not be parsed",
+                })?;
+            reports.push(report);
+        }
+        reports.sort_by_key(|report| report.aos_unix_ms);
+
+        Ok(Self {
+            reports,
+            dir: Some(dir.to_path_buf()),
+        })
+    }
+
+    /// Archive a completed pass, writing its Markdown and JSON files
+    ///
+    /// # Requirements Traceability
+    /// - FN-PAS-002: Report on disk in both formats before this returns
+    pub fn store(&mut self, report: PassReport) -> Result<()> {
+        if let Some(dir) = &self.dir {
+            let stem = dir.join(report.file_stem());
+            let written = std::fs::write(stem.with_extension("md"), report.to_markdown())
+                .and_then(|_| std::fs::write(stem.with_extension("json"), report.to_json()));
+            if written.is_err() {
+                return Err(SpaceCommError::ConfigurationError {
+                    parameter: "pass_report_dir",
+                    value: "<unwritable>",
+                    reason: "pass report could not be written",
+                });
+            }
+        }
+        self.reports.push(report);
+        Ok(())
+    }
+
+    /// Reports in AOS order
+    pub fn reports(&self) -> &[PassReport] {
+        &self.reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{execution_report::ExecutionResult, messaging::MessagePriority};
+
+    fn report(result: ExecutionResult) -> ExecutionReport {
+        ExecutionReport::new(0x1001, 1, MessagePriority::Medium, result, 250)
+    }
+
+    fn sample_pass() -> PassReport {
+        let mut tracker = PassTracker::new();
+        // Nothing is counted before AOS
+        tracker.command_sent(true);
+        tracker.snr_measured(1.0);
+        assert!(!tracker.in_pass());
+
+        tracker.downlinked(100, BandType::XBand, 10_000);
+        tracker.uplinked(40, BandType::SBand);
+        tracker.command_sent(true);
+        tracker.command_sent(false);
+        tracker.execution_reported(&report(ExecutionResult::Completed));
+        tracker.execution_reported(&report(ExecutionResult::Failed));
+        tracker.snr_measured(12.0);
+        tracker.snr_measured(8.0);
+        tracker.alarm("Invalid measurements [0101]".to_string(), 30_000);
+        tracker.downlinked(200, BandType::XBand, 70_500);
+
+        let pass = tracker.close("GST-001").unwrap();
+        assert!(tracker.close("GST-001").is_none());
+        pass
+    }
+
+    #[test]
+    fn test_tracker_summarises_pass() {
+        let pass = sample_pass();
+        assert_eq!((pass.aos_unix_ms, pass.los_unix_ms), (10_000, 70_500));
+        assert_eq!(pass.duration_s(), 60.5);
+        assert_eq!(pass.uplink_bands, vec![BandType::SBand]);
+        assert_eq!(pass.downlink_bands, vec![BandType::XBand]);
+        assert_eq!((pass.bytes_uplinked, pass.bytes_downlinked), (40, 300));
+        assert_eq!((pass.commands_sent, pass.uplink_failures), (2, 1));
+        assert_eq!(
+            (pass.verification.completed, pass.verification.failed),
+            (1, 1)
+        );
+        assert_eq!((pass.mean_snr_db, pass.min_snr_db), (Some(10.0), Some(8.0)));
+        assert_eq!(pass.alarms.len(), 1);
+
+        let markdown = pass.to_markdown();
+        assert!(markdown.starts_with("# Pass report GST-001 AOS 10000"));
+        assert!(markdown.contains("| Duration | 60.5 s |"));
+        assert!(markdown.contains("| Minimum SNR | 8.0 dB |"));
+        assert!(markdown.contains("- 30000: Invalid measurements [0101]"));
+    }
+
+    #[test]
+    fn test_archive_writes_and_reloads_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = PassArchive::open(dir.path()).unwrap();
+        let pass = sample_pass();
+        archive.store(pass.clone()).unwrap();
+
+        assert!(dir.path().join("pass_GST-001_10000.md").exists());
+        let reopened = PassArchive::open(dir.path()).unwrap();
+        assert_eq!(reopened.reports(), &[pass]);
+
+        std::fs::write(dir.path().join("pass_bad.json"), "not json").unwrap();
+        assert!(PassArchive::open(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_snr_estimate() {
+        // -100 dBm over 1 MHz sits 14 dB above the -114 dBm noise floor
+        assert!((estimate_snr_db(-100.0, 1_000_000) - 14.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,538 @@
+//! Pass scheduling from predicted visibility windows
+//!
+//! The [`PassScheduler`] knows the ground station location
+//! (`GroundStationConfig::location`) and the element sets of the satellites
+//! it serves, read as the conjunction screening reads them
+//! ([`TwoLineElements`]). It predicts each satellite's visibility windows
+//! above the elevation mask: acquisition of signal (AOS), loss of signal
+//! (LOS) and the culmination elevation. Orbits are propagated with the same
+//! Keplerian model and J2 drift as the screening, and the inertial position
+//! is rotated to the Earth-fixed frame by Greenwich mean sidereal time, so
+//! AOS and LOS are good to some seconds over the day after the element
+//! epoch, and drift as the elements age.
+//!
+//! Operators queue commands per satellite instead of timing them by hand.
+//! [`PassScheduler::schedule`] puts every upcoming window on the
+//! [`EventScheduler`] as an expected-AOS event whose procedure requests a
+//! telemetry download and uplinks the commands queued for that satellite,
+//! so they go out as the pass starts. Each window is scheduled once,
+//! however often the plan is refreshed. Like the event scheduler, nothing
+//! here reads the clock: times are seconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-VIS-001: Visibility windows (AOS, LOS, culmination) predicted per
+//!   satellite from the station location
+//! - FN-VIS-002: Queued commands and telemetry downloads scheduled into the
+//!   next window of their satellite
+
+use std::fmt;
+
+use crate::conjunction::TwoLineElements;
+use crate::scheduler::{EventKind, EventScheduler};
+use crate::Command;
+
+/// Elevation a pass must rise above to count as visible, degrees
+pub const DEFAULT_ELEVATION_MASK_DEG: f64 = 10.0;
+
+/// Time between visibility samples, s; shorter than any LEO pass
+pub const VISIBILITY_STEP_S: f64 = 30.0;
+
+/// AOS and LOS refinement tolerance, s
+const CROSSING_TOLERANCE_S: f64 = 0.5;
+
+/// WGS-84 equatorial radius, km
+const WGS84_RADIUS_KM: f64 = 6378.137;
+
+/// WGS-84 flattening
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// Julian date of the Unix epoch
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+/// Julian date of J2000.0
+const J2000_JD: f64 = 2_451_545.0;
+
+/// Direction and distance from the station to a satellite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookAngles {
+    /// Azimuth clockwise from north, degrees
+    pub azimuth_deg: f64,
+    /// Elevation above the horizon, degrees
+    pub elevation_deg: f64,
+    /// Slant range, km
+    pub range_km: f64,
+}
+
+/// One pass of a satellite above the elevation mask
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibilityWindow {
+    /// NORAD catalog number of the satellite
+    pub catalog_number: u32,
+    /// Satellite name
+    pub name: String,
+    /// Acquisition of signal, seconds since the Unix epoch; the start of
+    /// the prediction for a pass already in progress
+    pub aos_unix_s: f64,
+    /// Loss of signal, seconds since the Unix epoch; the end of the
+    /// prediction for a pass still in progress there
+    pub los_unix_s: f64,
+    /// Culmination elevation, degrees
+    pub max_elevation_deg: f64,
+    /// Time of culmination, seconds since the Unix epoch
+    pub max_elevation_unix_s: f64,
+}
+
+impl VisibilityWindow {
+    /// Pass duration, s
+    pub fn duration_s(&self) -> f64 {
+        self.los_unix_s - self.aos_unix_s
+    }
+
+    /// Whether the two windows are of the same satellite and overlap
+    fn overlaps(&self, other: &VisibilityWindow) -> bool {
+        self.catalog_number == other.catalog_number
+            && self.aos_unix_s <= other.los_unix_s
+            && other.aos_unix_s <= self.los_unix_s
+    }
+}
+
+impl fmt::Display for VisibilityWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): AOS {:.0}, LOS {:.0} ({:.1} min), max elevation {:.1}°",
+            self.name,
+            self.catalog_number,
+            self.aos_unix_s,
+            self.los_unix_s,
+            self.duration_s() / 60.0,
+            self.max_elevation_deg
+        )
+    }
+}
+
+/// Window put on the event scheduler by [`PassScheduler::schedule`]
+#[derive(Debug, Clone)]
+pub struct ScheduledPass {
+    /// The window
+    pub window: VisibilityWindow,
+    /// Identifier of its expected-AOS event
+    pub event_id: u32,
+    /// Queued commands its procedure uplinks after the telemetry request
+    pub commands: usize,
+}
+
+/// Visibility prediction and pass scheduling for one ground station
+///
+/// - **ID**: FN-VIS-001
+/// - **Requirement**: Predict when each served satellite is visible from
+///   the station and time commands and downloads to those windows.
+/// - **Inputs**: Station location, element sets, current time on every
+///   query.
+/// - **Outputs**: Visibility windows; expected-AOS events with procedures.
+/// - **Side Effects**: [`PassScheduler::schedule`] adds events to the event
+///   scheduler and hands over the queued commands.
+/// - **Failure Modes**: Stale element sets give drifting AOS and LOS; the
+///   prediction is only as good as the elements.
+#[derive(Debug, Clone)]
+pub struct PassScheduler {
+    /// Station location as (latitude, longitude, altitude in meters)
+    location: (f64, f64, f64),
+    /// Elevation a pass must rise above, degrees
+    elevation_mask_deg: f64,
+    /// Served satellites with the commands queued for their next pass
+    satellites: Vec<(TwoLineElements, Vec<Command>)>,
+    /// Windows already on the event scheduler
+    scheduled: Vec<VisibilityWindow>,
+}
+
+impl PassScheduler {
+    /// Create a scheduler for a station
+    ///
+    /// # Arguments
+    /// * `location` - (latitude, longitude, altitude in meters), as in
+    ///   `GroundStationConfig::location`
+    /// * `elevation_mask_deg` - Elevation a pass must rise above
+    pub fn new(location: (f64, f64, f64), elevation_mask_deg: f64) -> Self {
+        Self {
+            location,
+            elevation_mask_deg,
+            satellites: Vec::new(),
+            scheduled: Vec::new(),
+        }
+    }
+
+    /// Serve a satellite, replacing the elements of one already served
+    ///
+    /// Commands queued for the satellite are kept.
+    pub fn add_satellite(&mut self, elements: TwoLineElements) {
+        match self
+            .satellites
+            .iter_mut()
+            .find(|(served, _)| served.catalog_number == elements.catalog_number)
+        {
+            Some((served, _)) => *served = elements,
+            None => self.satellites.push((elements, Vec::new())),
+        }
+    }
+
+    /// Element sets of the served satellites
+    pub fn satellites(&self) -> impl Iterator<Item = &TwoLineElements> {
+        self.satellites.iter().map(|(elements, _)| elements)
+    }
+
+    /// Queue a command for the next pass of a satellite
+    ///
+    /// # Returns
+    /// * `bool` - `false` if the satellite is not served
+    pub fn queue_command(&mut self, catalog_number: u32, command: Command) -> bool {
+        match self
+            .satellites
+            .iter_mut()
+            .find(|(elements, _)| elements.catalog_number == catalog_number)
+        {
+            Some((_, queue)) => {
+                queue.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Commands queued for the next pass of a satellite
+    pub fn queued(&self, catalog_number: u32) -> &[Command] {
+        self.satellites
+            .iter()
+            .find(|(elements, _)| elements.catalog_number == catalog_number)
+            .map_or(&[], |(_, queue)| queue.as_slice())
+    }
+
+    /// Look angles from the station to a satellite
+    ///
+    /// # Arguments
+    /// * `elements` - Satellite elements
+    /// * `unix_s` - Time, seconds since the Unix epoch
+    pub fn look_angles(&self, elements: &TwoLineElements, unix_s: f64) -> LookAngles {
+        let position = elements.state_at(unix_s).position_km;
+
+        // Inertial to Earth-fixed, by sidereal time only
+        let (sin_g, cos_g) = gmst_rad(unix_s).sin_cos();
+        let satellite = [
+            cos_g * position[0] + sin_g * position[1],
+            -sin_g * position[0] + cos_g * position[1],
+            position[2],
+        ];
+        let station = station_ecef_km(self.location);
+        let d = [0, 1, 2].map(|i| satellite[i] - station[i]);
+
+        // South, east and zenith components at the station
+        let (sin_lat, cos_lat) = self.location.0.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.location.1.to_radians().sin_cos();
+        let south = sin_lat * cos_lon * d[0] + sin_lat * sin_lon * d[1] - cos_lat * d[2];
+        let east = -sin_lon * d[0] + cos_lon * d[1];
+        let zenith = cos_lat * cos_lon * d[0] + cos_lat * sin_lon * d[1] + sin_lat * d[2];
+        let range_km = (south * south + east * east + zenith * zenith).sqrt();
+        LookAngles {
+            azimuth_deg: east.atan2(-south).to_degrees().rem_euclid(360.0),
+            elevation_deg: (zenith / range_km).asin().to_degrees(),
+            range_km,
+        }
+    }
+
+    /// Predict the visibility windows of every served satellite
+    ///
+    /// # Arguments
+    /// * `from_unix_s` - Start of the prediction, seconds since the Unix epoch
+    /// * `horizon_s` - Length of the prediction, s
+    ///
+    /// # Returns
+    /// * `Vec<VisibilityWindow>` - Windows of all satellites ordered by AOS
+    ///
+    /// # Requirements Traceability
+    /// - FN-VIS-001: AOS, LOS and culmination refined to half a second
+    pub fn visibility_windows(&self, from_unix_s: f64, horizon_s: f64) -> Vec<VisibilityWindow> {
+        let mut windows: Vec<VisibilityWindow> = self
+            .satellites
+            .iter()
+            .flat_map(|(elements, _)| self.windows_of(elements, from_unix_s, horizon_s))
+            .collect();
+        windows.sort_by(|a, b| a.aos_unix_s.total_cmp(&b.aos_unix_s));
+        windows
+    }
+
+    /// Visibility windows of one satellite, in time order
+    fn windows_of(
+        &self,
+        elements: &TwoLineElements,
+        from_unix_s: f64,
+        horizon_s: f64,
+    ) -> Vec<VisibilityWindow> {
+        let end_unix_s = from_unix_s + horizon_s.max(0.0);
+        let elevation = |t: f64| self.look_angles(elements, t).elevation_deg;
+        let visible = |t: f64| elevation(t) >= self.elevation_mask_deg;
+
+        let mut windows = Vec::new();
+        let mut open: Option<VisibilityWindow> = None;
+        let mut previous = from_unix_s;
+        let mut t = from_unix_s;
+        loop {
+            let sample = elevation(t);
+            match (&mut open, sample >= self.elevation_mask_deg) {
+                (None, true) => {
+                    let aos_unix_s = if t == from_unix_s {
+                        t
+                    } else {
+                        bisect_crossing(previous, t, &visible)
+                    };
+                    open = Some(VisibilityWindow {
+                        catalog_number: elements.catalog_number,
+                        name: elements.name.clone(),
+                        aos_unix_s,
+                        los_unix_s: t,
+                        max_elevation_deg: sample,
+                        max_elevation_unix_s: t,
+                    });
+                }
+                (Some(window), true) => {
+                    window.los_unix_s = t;
+                    if sample > window.max_elevation_deg {
+                        window.max_elevation_deg = sample;
+                        window.max_elevation_unix_s = t;
+                    }
+                }
+                (Some(window), false) => {
+                    window.los_unix_s = bisect_crossing(previous, t, &visible);
+                    windows.extend(open.take());
+                }
+                (None, false) => {}
+            }
+            if t >= end_unix_s {
+                break;
+            }
+            previous = t;
+            t = (t + VISIBILITY_STEP_S).min(end_unix_s);
+        }
+        windows.extend(open);
+
+        // Culmination lies within a sample of the highest one
+        for window in &mut windows {
+            let (t, elevation) = culmination(
+                (window.max_elevation_unix_s - VISIBILITY_STEP_S).max(window.aos_unix_s),
+                (window.max_elevation_unix_s + VISIBILITY_STEP_S).min(window.los_unix_s),
+                &elevation,
+            );
+            if elevation > window.max_elevation_deg {
+                window.max_elevation_deg = elevation;
+                window.max_elevation_unix_s = t;
+            }
+        }
+        windows
+    }
+
+    /// Put upcoming windows on the event scheduler
+    ///
+    /// Every window starting after `now_unix_s` within `horizon_s` and not
+    /// already scheduled becomes an [`EventKind::ExpectedAos`] event at its
+    /// AOS. Its procedure requests a telemetry download, then uplinks the
+    /// commands queued for the satellite, which leave the queue: queued
+    /// commands go to the satellite's next window only. Windows that have
+    /// ended are forgotten.
+    ///
+    /// # Returns
+    /// * `Vec<ScheduledPass>` - Windows newly scheduled, ordered by AOS
+    ///
+    /// # Requirements Traceability
+    /// - FN-VIS-002: Commands and downloads timed to the next window
+    pub fn schedule(
+        &mut self,
+        scheduler: &mut EventScheduler,
+        now_unix_s: u64,
+        horizon_s: f64,
+    ) -> Vec<ScheduledPass> {
+        let now = now_unix_s as f64;
+        self.scheduled.retain(|window| window.los_unix_s >= now);
+
+        let mut passes = Vec::new();
+        for window in self.visibility_windows(now, horizon_s) {
+            if window.aos_unix_s <= now || self.scheduled.iter().any(|s| s.overlaps(&window)) {
+                continue;
+            }
+            let event_id = scheduler.schedule(
+                &format!("AOS {}", window.name),
+                EventKind::ExpectedAos,
+                window.aos_unix_s.floor() as u64,
+            );
+            scheduler.add_procedure_step(event_id, Command::telemetry_request());
+            let queue = self
+                .satellites
+                .iter_mut()
+                .find(|(elements, _)| elements.catalog_number == window.catalog_number)
+                .map(|(_, queue)| std::mem::take(queue))
+                .unwrap_or_default();
+            let commands = queue.len();
+            for command in queue {
+                scheduler.add_procedure_step(event_id, command);
+            }
+            self.scheduled.push(window.clone());
+            passes.push(ScheduledPass {
+                window,
+                event_id,
+                commands,
+            });
+        }
+        passes
+    }
+}
+
+/// Time within `[before, after]` a visibility test changes, to
+/// [`CROSSING_TOLERANCE_S`]
+fn bisect_crossing(mut before: f64, mut after: f64, visible: &dyn Fn(f64) -> bool) -> f64 {
+    let rising = visible(after);
+    while after - before > CROSSING_TOLERANCE_S {
+        let mid = 0.5 * (before + after);
+        if visible(mid) == rising {
+            after = mid;
+        } else {
+            before = mid;
+        }
+    }
+    0.5 * (before + after)
+}
+
+/// Highest elevation within `[start, end]` by golden-section search
+fn culmination(mut start: f64, mut end: f64, elevation: &dyn Fn(f64) -> f64) -> (f64, f64) {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    while end - start > CROSSING_TOLERANCE_S {
+        let lower = end - ratio * (end - start);
+        let upper = start + ratio * (end - start);
+        if elevation(lower) > elevation(upper) {
+            end = upper;
+        } else {
+            start = lower;
+        }
+    }
+    let t = 0.5 * (start + end);
+    (t, elevation(t))
+}
+
+/// Greenwich mean sidereal time, radians (IAU 1982)
+fn gmst_rad(unix_s: f64) -> f64 {
+    let centuries = (unix_s / 86_400.0 + UNIX_EPOCH_JD - J2000_JD) / 36_525.0;
+    let seconds = 67_310.548_41
+        + (876_600.0 * 3600.0 + 8_640_184.812_866) * centuries
+        + 0.093_104 * centuries.powi(2)
+        - 6.2e-6 * centuries.powi(3);
+    // 240 seconds of sidereal time per degree
+    (seconds / 240.0)
+        .to_radians()
+        .rem_euclid(std::f64::consts::TAU)
+}
+
+/// Earth-fixed position of a station on the WGS-84 ellipsoid, km
+fn station_ecef_km(location: (f64, f64, f64)) -> [f64; 3] {
+    let (sin_lat, cos_lat) = location.0.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = location.1.to_radians().sin_cos();
+    let altitude_km = location.2 / 1000.0;
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let n = WGS84_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + altitude_km) * cos_lat * cos_lon,
+        (n + altitude_km) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + altitude_km) * sin_lat,
+    ]
+}
+
+/// Format visibility windows for the operator
+pub fn format_visibility_windows(windows: &[VisibilityWindow]) -> String {
+    if windows.is_empty() {
+        return "No passes in the prediction window\n".to_string();
+    }
+    windows
+        .iter()
+        .map(|window| format!("  {}\n", window))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conjunction::parse_catalog;
+    use space_comms_shared::types::BandType;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9005
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.50377579 12345
+";
+
+    /// Los Angeles, as in the default station configuration
+    const STATION: (f64, f64, f64) = (34.0522, -118.2437, 75.0);
+
+    fn scheduler() -> (PassScheduler, f64) {
+        let iss = parse_catalog(ISS).unwrap().remove(0);
+        let epoch = iss.epoch_unix_s;
+        let mut passes = PassScheduler::new(STATION, DEFAULT_ELEVATION_MASK_DEG);
+        passes.add_satellite(iss);
+        (passes, epoch)
+    }
+
+    #[test]
+    fn test_visibility_windows_over_a_day() {
+        let (passes, epoch) = scheduler();
+        let iss = passes.satellites().next().unwrap().clone();
+        let windows = passes.visibility_windows(epoch, 86_400.0);
+
+        // A 51.6° orbit passes a 34° N station a few times a day
+        assert!((2..=8).contains(&windows.len()), "{}", windows.len());
+        for window in &windows {
+            assert!(window.duration_s() > 0.0 && window.duration_s() < 900.0);
+            assert!(window.max_elevation_deg >= DEFAULT_ELEVATION_MASK_DEG);
+            assert!((window.aos_unix_s..=window.los_unix_s).contains(&window.max_elevation_unix_s));
+            // AOS and LOS sit on the mask, culmination above it
+            for edge in [window.aos_unix_s, window.los_unix_s] {
+                let elevation = passes.look_angles(&iss, edge).elevation_deg;
+                assert!((elevation - DEFAULT_ELEVATION_MASK_DEG).abs() < 0.1);
+            }
+            let peak = passes.look_angles(&iss, window.max_elevation_unix_s);
+            assert!((peak.elevation_deg - window.max_elevation_deg).abs() < 1e-9);
+            // Slant range between the altitude and the range at the mask
+            assert!((400.0..1_500.0).contains(&peak.range_km), "{:?}", peak);
+        }
+        assert!(windows
+            .windows(2)
+            .all(|pair| pair[0].los_unix_s < pair[1].aos_unix_s));
+        assert!(format_visibility_windows(&windows).contains("ISS (ZARYA) (25544)"));
+    }
+
+    #[test]
+    fn test_queued_commands_go_to_the_next_window() {
+        let (mut passes, epoch) = scheduler();
+        assert!(passes.queue_command(25544, Command::system_status_request()));
+        assert!(passes.queue_command(25544, Command::switch_band(BandType::XBand)));
+        assert!(!passes.queue_command(99999, Command::system_status_request()));
+        assert_eq!(passes.queued(25544).len(), 2);
+
+        let mut events = EventScheduler::new();
+        let now = epoch as u64;
+        let scheduled = passes.schedule(&mut events, now, 86_400.0);
+        let windows = passes.visibility_windows(epoch, 86_400.0);
+        assert_eq!(scheduled.len(), windows.len());
+        assert_eq!(events.len(), scheduled.len());
+        assert!(passes.queued(25544).is_empty());
+
+        // Download plus the queued commands at the first AOS, download only after
+        let countdowns = events.countdowns(now);
+        assert_eq!(countdowns[0].id, scheduled[0].event_id);
+        assert_eq!(countdowns[0].name, "AOS ISS (ZARYA)");
+        assert_eq!(countdowns[0].kind, EventKind::ExpectedAos);
+        assert_eq!(
+            countdowns[0].remaining_secs,
+            scheduled[0].window.aos_unix_s as u64 - now
+        );
+        assert_eq!((countdowns[0].procedure_len, scheduled[0].commands), (3, 2));
+        assert!(countdowns[1..].iter().all(|c| c.procedure_len == 1));
+
+        // Refreshing the plan adds nothing already scheduled
+        assert!(passes.schedule(&mut events, now + 60, 86_400.0).is_empty());
+        assert_eq!(events.len(), scheduled.len());
+    }
+}
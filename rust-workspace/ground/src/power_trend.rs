@@ -0,0 +1,311 @@
+//! Power trending over downlinked EPS summaries
+//!
+//! Every EPS summary frame carries the modeled draw of each subsystem next to
+//! the battery state of charge. The trend keeps the last
+//! [`POWER_TREND_WINDOW`] of them and, when the battery has lost at least
+//! [`DEPLETION_SOC_DROP_PERCENT`] over the window while discharging, compares
+//! the newer half of the window with the older half. The source whose draw
+//! rose the most is named as the [`DepletionSuspect`]: a subsystem, or the
+//! unattributed remainder when the measured load grew without any subsystem
+//! accounting for it.
+//!
+//! Like the verification archive, the trend does not read the clock: the
+//! caller passes the current time in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-PWR-003: Subsystem behind an unexpected battery depletion identified
+//!   from the downlinked power attribution
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use space_comms_shared::{
+    eps::EpsSummary,
+    power_attribution::{PowerAttribution, Subsystem},
+};
+
+/// EPS summaries kept for trending, one per second
+pub const POWER_TREND_WINDOW: usize = 120;
+
+/// State of charge lost over the window that counts as a depletion, percent
+pub const DEPLETION_SOC_DROP_PERCENT: f32 = 1.0;
+
+/// Smallest rise in draw worth naming as the cause, W
+pub const MIN_EXCESS_DRAW_W: f32 = 0.5;
+
+/// Power state at one EPS summary
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PowerSample {
+    unix_ms: u64,
+    battery_soc: f32,
+    net_power_w: f32,
+    attribution: PowerAttribution,
+    unattributed_w: f32,
+}
+
+impl PowerSample {
+    /// Draw of a source, W; `None` is the unattributed remainder
+    fn draw_w(&self, source: Option<Subsystem>) -> f32 {
+        source.map_or(self.unattributed_w, |subsystem| {
+            self.attribution.draw_w(subsystem)
+        })
+    }
+}
+
+/// Most likely cause of a battery depletion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepletionSuspect {
+    /// Subsystem whose draw rose the most; `None` for load the attribution
+    /// does not account for
+    pub subsystem: Option<Subsystem>,
+    /// Rise in its mean draw between the older and newer half of the window, W
+    pub excess_w: f32,
+    /// State of charge lost over the window, percent
+    pub soc_drop_percent: f32,
+    /// Time spanned by the window, ms
+    pub span_ms: u64,
+}
+
+impl fmt::Display for DepletionSuspect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "battery down {:.1}% in {} s, {} drawing {:+.1} W more",
+            self.soc_drop_percent,
+            self.span_ms / 1000,
+            self.subsystem.map_or("unattributed load", Subsystem::name),
+            self.excess_w
+        )
+    }
+}
+
+/// Recent EPS summaries with their power attribution
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PowerTrend {
+    samples: VecDeque<PowerSample>,
+    suspect_raised: bool,
+}
+
+impl PowerTrend {
+    /// Create an empty trend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a downlinked EPS summary
+    ///
+    /// # Arguments
+    /// * `summary` - Decoded EPS summary
+    /// * `attribution` - Power attribution from the same frame
+    /// * `now_unix_ms` - Receive time, milliseconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Option<DepletionSuspect>` - The suspect when a depletion is first
+    ///   detected; `None` while none is, or while one is already raised
+    pub fn record(
+        &mut self,
+        summary: &EpsSummary,
+        attribution: &PowerAttribution,
+        now_unix_ms: u64,
+    ) -> Option<DepletionSuspect> {
+        if self.samples.len() == POWER_TREND_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(PowerSample {
+            unix_ms: now_unix_ms,
+            battery_soc: summary.battery_soc,
+            net_power_w: summary.net_power(),
+            attribution: *attribution,
+            unattributed_w: attribution.unattributed_w(summary),
+        });
+
+        let suspect = self.depletion_suspect();
+        let newly_raised = suspect.filter(|_| !self.suspect_raised);
+        self.suspect_raised = suspect.is_some();
+        newly_raised
+    }
+
+    /// Number of summaries in the window
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether no summary has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Most recent attribution, if any summary has been recorded
+    pub fn latest(&self) -> Option<&PowerAttribution> {
+        self.samples.back().map(|sample| &sample.attribution)
+    }
+
+    /// Cause of the current battery depletion, if the battery is depleting
+    ///
+    /// A depletion is a state of charge drop of at least
+    /// [`DEPLETION_SOC_DROP_PERCENT`] over the window while the latest
+    /// summary shows the battery discharging. Samples with an unreadable
+    /// state of charge or load are skipped.
+    ///
+    /// # Returns
+    /// * `Option<DepletionSuspect>` - Source whose mean draw rose most between
+    ///   the halves of the window, or `None` if there is no depletion or no
+    ///   source rose by [`MIN_EXCESS_DRAW_W`]
+    pub fn depletion_suspect(&self) -> Option<DepletionSuspect> {
+        let samples: Vec<&PowerSample> = self
+            .samples
+            .iter()
+            .filter(|s| !s.battery_soc.is_nan() && !s.unattributed_w.is_nan())
+            .collect();
+        if samples.len() < 2 {
+            return None;
+        }
+
+        let (first, last) = (samples[0], samples[samples.len() - 1]);
+        let soc_drop_percent = first.battery_soc - last.battery_soc;
+        let discharging = last.net_power_w < 0.0;
+        if soc_drop_percent < DEPLETION_SOC_DROP_PERCENT || !discharging {
+            return None;
+        }
+
+        let (older, newer) = samples.split_at(samples.len() / 2);
+        let mean = |half: &[&PowerSample], source: Option<Subsystem>| {
+            half.iter().map(|s| s.draw_w(source)).sum::<f32>() / half.len() as f32
+        };
+        let sources = Subsystem::ALL
+            .into_iter()
+            .map(Some)
+            .chain(core::iter::once(None));
+        let (subsystem, excess_w) = sources
+            .map(|source| (source, mean(newer, source) - mean(older, source)))
+            .fold((None, f32::NEG_INFINITY), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+
+        (excess_w >= MIN_EXCESS_DRAW_W).then_some(DepletionSuspect {
+            subsystem,
+            excess_w,
+            soc_drop_percent,
+            span_ms: last.unix_ms.saturating_sub(first.unix_ms),
+        })
+    }
+}
+
+/// Render the latest attribution and depletion suspect for the console
+///
+/// # Arguments
+/// * `summary` - Latest EPS summary, for the measured load power
+/// * `attribution` - Power attribution from the same frame
+/// * `suspect` - Current depletion suspect, if any
+///
+/// # Returns
+/// * `String` - One line per subsystem, the unattributed remainder, and the
+///   suspect when there is one
+pub fn format_power_attribution(
+    summary: &EpsSummary,
+    attribution: &PowerAttribution,
+    suspect: Option<&DepletionSuspect>,
+) -> String {
+    let mut out = String::new();
+    for subsystem in Subsystem::ALL {
+        out.push_str(&format!(
+            "  {:<12}{:>3} {:>6.1} W\n",
+            subsystem.name(),
+            if attribution.is_on(subsystem) {
+                "ON"
+            } else {
+                "off"
+            },
+            attribution.draw_w(subsystem)
+        ));
+    }
+    let unattributed = attribution.unattributed_w(summary);
+    if unattributed.is_nan() {
+        out.push_str("  Unattributed      -- W\n");
+    } else {
+        out.push_str(&format!("  Unattributed    {:>6.1} W\n", unattributed));
+    }
+    if let Some(suspect) = suspect {
+        out.push_str(&format!("  Depletion: {}\n", suspect));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(battery_soc: f32, load_current: f32) -> EpsSummary {
+        EpsSummary {
+            bus_voltage: 12.0,
+            battery_voltage: 27.0,
+            battery_soc,
+            solar_voltage: 0.0,
+            solar_current: 0.0,
+            load_current,
+            load_currents: [0.5, 0.5, 0.0],
+        }
+    }
+
+    fn nominal() -> PowerAttribution {
+        let mut attribution = PowerAttribution::new();
+        attribution.set(Subsystem::Obc, true, 100);
+        attribution.set(Subsystem::SBand, true, 75);
+        attribution
+    }
+
+    #[test]
+    fn test_depletion_names_subsystem_switched_on() {
+        let mut trend = PowerTrend::new();
+        let mut hot = nominal();
+        hot.set(Subsystem::KaBand, true, 95);
+
+        let mut raised = Vec::new();
+        for i in 0..20u64 {
+            let attribution = if i < 10 { nominal() } else { hot };
+            let load_a = attribution.modeled_total_w() / 12.0;
+            let soc = 80.0 - i as f32 * 0.1;
+            raised.extend(trend.record(&summary(soc, load_a), &attribution, i * 1000));
+        }
+
+        assert_eq!(raised.len(), 1, "suspect is raised once");
+        let suspect = trend.depletion_suspect().unwrap();
+        assert_eq!(suspect.subsystem, Some(Subsystem::KaBand));
+        assert!((suspect.excess_w - Subsystem::KaBand.draw_model().draw_w(95)).abs() < 1e-3);
+        assert!((suspect.soc_drop_percent - 1.9).abs() < 1e-3);
+        assert_eq!(suspect.span_ms, 19_000);
+
+        let text = format_power_attribution(&summary(78.1, 2.0), &hot, Some(&suspect));
+        assert!(text.contains("Ka-Band"));
+        assert!(text.contains("Depletion: battery down 1.9% in 19 s, Ka-Band"));
+    }
+
+    #[test]
+    fn test_depletion_blames_unattributed_load() {
+        let mut trend = PowerTrend::new();
+        for i in 0..20u64 {
+            let extra_w = if i < 10 { 0.0 } else { 6.0 };
+            let load_a = (nominal().modeled_total_w() + extra_w) / 12.0;
+            trend.record(
+                &summary(60.0 - i as f32 * 0.2, load_a),
+                &nominal(),
+                i * 1000,
+            );
+        }
+        let suspect = trend.depletion_suspect().unwrap();
+        assert_eq!(suspect.subsystem, None);
+        assert!((suspect.excess_w - 6.0).abs() < 1e-3);
+
+        // A steady battery is no depletion, whatever the draw
+        let mut steady = PowerTrend::new();
+        for i in 0..20u64 {
+            steady.record(&summary(60.0, 2.0), &nominal(), i * 1000);
+        }
+        assert!(steady.depletion_suspect().is_none());
+        assert_eq!(steady.len(), 20);
+    }
+}
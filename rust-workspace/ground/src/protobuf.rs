@@ -0,0 +1,699 @@
+//! Protobuf encoding of telemetry and dictionary commands
+//!
+//! Consumers outside Rust otherwise have to reverse-engineer the JSON the
+//! ground interfaces carry. This module gives them typed bindings instead:
+//! [`proto_file`] generates a proto3 schema from the shared telemetry types
+//! and the command dictionary, and the station encodes telemetry and
+//! commands to match it. Compile the schema with `protoc` for any language.
+//!
+//! Schema contents, in package [`PROTO_PACKAGE`]:
+//!
+//! - `TelemetryPacket`, `TelemetryData` and `Measurement`, with the band,
+//!   health and quality enums; a measurement value is a `oneof` of its kinds
+//! - One message per dictionary command, its fields the command parameters
+//!   in dictionary order: choices as a nested enum in dictionary order,
+//!   unsigned values as `uint32` or `uint64` by their maximum, optional
+//!   values as `optional`, floats as `double` and flags as `bool`
+//! - `Command`, a `oneof` of every dictionary command, numbered in
+//!   dictionary order
+//!
+//! Encoding follows the protobuf wire format directly, without generated
+//! code: every field is written, and decoding skips fields it does not know,
+//! so consumers built against an older schema keep working. Commands are
+//! decoded back through the dictionary, so a protobuf command is checked
+//! exactly like a console command. A checked-in copy of the schema is kept
+//! at `ground/proto/space_comms.proto`; the `proto` console command exports
+//! it from the running station.
+//!
+//! # Requirements Traceability
+//! - FN-PB-001: Schema generated from the telemetry types and the command
+//!   dictionary
+//! - FN-PB-002: Telemetry packets encoded to the schema
+//! - FN-PB-003: Commands encoded to the schema and decoded through the
+//!   command dictionary
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use space_comms_shared::{
+    commands::SpaceCommand,
+    telemetry::{MeasurementValue, TelemetryPacket},
+    Result, SpaceCommError,
+};
+
+use crate::dictionary::{lookup, CommandSpec, ParameterKind, COMMAND_DICTIONARY};
+
+/// Package of the generated schema
+pub const PROTO_PACKAGE: &str = "space_comms.ground.v1";
+
+/// File name of the exported schema
+pub const PROTO_FILE: &str = "space_comms.proto";
+
+/// Wire type of varint fields: integers, enums and bools
+const WIRE_VARINT: u64 = 0;
+
+/// Wire type of 64-bit fields: doubles
+const WIRE_FIXED64: u64 = 1;
+
+/// Wire type of length-delimited fields: strings, bytes and messages
+const WIRE_LEN: u64 = 2;
+
+/// Wire type of 32-bit fields
+const WIRE_FIXED32: u64 = 5;
+
+/// Enums of the telemetry messages: name and shared variant names in
+/// declaration order, which is also their wire value
+const TELEMETRY_ENUMS: &[(&str, &[&str])] = &[
+    (
+        "BandType",
+        &["UhfBand", "SBand", "XBand", "KBand", "KaBand"],
+    ),
+    (
+        "HealthStatus",
+        &["Excellent", "Good", "Fair", "Poor", "Critical", "Unknown"],
+    ),
+    (
+        "MeasurementQuality",
+        &["Good", "Suspect", "Stale", "Invalid", "NotAvailable"],
+    ),
+];
+
+/// Telemetry messages of the schema
+const TELEMETRY_MESSAGES: &str = r#"// One measurement; unset value when the measurement carries none
+message Measurement {
+  uint32 measurement_id = 1;
+  oneof value {
+    sint64 int_value = 2;
+    double float_value = 3;
+    bool bool_value = 4;
+    string string_value = 5;
+    bytes bytes_value = 6;
+  }
+  string unit = 7;
+  MeasurementQuality quality = 8;
+}
+
+// Measurements of one component at one time
+message TelemetryData {
+  // Component ID
+  uint32 source = 1;
+  // Spacecraft time, ns since epoch
+  uint64 timestamp_ns = 2;
+  repeated Measurement measurements = 3;
+  HealthStatus health_status = 4;
+}
+
+// Telemetry packet as parsed by the station
+message TelemetryPacket {
+  uint32 sequence = 1;
+  TelemetryData data = 2;
+  BandType band = 3;
+  uint32 size_bytes = 4;
+}
+"#;
+
+/// Words of a `CamelCase` or `snake_case` name; an acronym stays one word
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            words.push(String::new());
+            continue;
+        }
+        let after_lower =
+            i > 0 && (chars[i - 1].is_ascii_lowercase() || chars[i - 1].is_ascii_digit());
+        let acronym_end = i > 0
+            && chars[i - 1].is_ascii_uppercase()
+            && chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_lowercase());
+        if words.is_empty() || (c.is_ascii_uppercase() && (after_lower || acronym_end)) {
+            words.push(String::new());
+        }
+        if let Some(word) = words.last_mut() {
+            word.push(c);
+        }
+    }
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// `SCREAMING_SNAKE_CASE` of a name, for enum values
+fn screaming_snake(name: &str) -> String {
+    words(name).join("_").to_ascii_uppercase()
+}
+
+/// `snake_case` of a name, for fields
+fn snake(name: &str) -> String {
+    words(name).join("_").to_ascii_lowercase()
+}
+
+/// `CamelCase` of a name, for nested enums
+fn camel(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let lower = word.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// Enum `name` with `values` numbered in order, indented by `indent`
+fn write_enum(proto: &mut String, indent: &str, name: &str, values: &[&str]) {
+    let prefix = screaming_snake(name);
+    let _ = writeln!(proto, "{indent}enum {name} {{");
+    for (number, value) in values.iter().enumerate() {
+        let _ = writeln!(
+            proto,
+            "{indent}  {}_{} = {};",
+            prefix,
+            screaming_snake(value),
+            number
+        );
+    }
+    let _ = writeln!(proto, "{indent}}}");
+}
+
+/// Proto3 type of a command parameter
+fn field_type(name: &str, kind: ParameterKind) -> String {
+    let unsigned = |max: u64| {
+        if max <= u64::from(u32::MAX) {
+            "uint32"
+        } else {
+            "uint64"
+        }
+    };
+    match kind {
+        ParameterKind::Choice(_) => camel(name),
+        ParameterKind::Unsigned(max) => unsigned(max).to_string(),
+        ParameterKind::OptionalUnsigned(max) => format!("optional {}", unsigned(max)),
+        ParameterKind::Float => "double".to_string(),
+        ParameterKind::Flag => "bool".to_string(),
+    }
+}
+
+/// Generate the proto3 schema
+///
+/// - **ID**: FN-PB-001
+/// - **Requirement**: Describe the station's telemetry and commands to
+///   consumers in any language from the types and dictionary the station
+///   itself uses.
+/// - **Outputs**: Schema with the telemetry messages and enums, one message
+///   per dictionary command and the `Command` envelope, laid out as in the
+///   module documentation.
+/// - **Side Effects**: None.
+pub fn proto_file() -> String {
+    let mut proto = String::new();
+    proto.push_str("// Generated from the space-comms ground station dictionaries; do not edit.\n");
+    proto.push_str("syntax = \"proto3\";\n\n");
+    let _ = writeln!(proto, "package {};\n", PROTO_PACKAGE);
+
+    for (name, values) in TELEMETRY_ENUMS {
+        write_enum(&mut proto, "", name, values);
+        proto.push('\n');
+    }
+    proto.push_str(TELEMETRY_MESSAGES);
+
+    for spec in COMMAND_DICTIONARY {
+        let _ = writeln!(proto, "\nmessage {} {{", spec.name);
+        for parameter in spec.parameters {
+            if let ParameterKind::Choice(options) = parameter.kind {
+                write_enum(&mut proto, "  ", &camel(parameter.name), options);
+            }
+        }
+        for (number, parameter) in spec.parameters.iter().enumerate() {
+            let _ = writeln!(
+                proto,
+                "  {} {} = {};",
+                field_type(parameter.name, parameter.kind),
+                parameter.name,
+                number + 1
+            );
+        }
+        proto.push_str("}\n");
+    }
+
+    proto.push_str("\n// One dictionary command\nmessage Command {\n  oneof command {\n");
+    for (number, spec) in COMMAND_DICTIONARY.iter().enumerate() {
+        let _ = writeln!(
+            proto,
+            "    {} {} = {};",
+            spec.name,
+            snake(spec.name),
+            number + 1
+        );
+    }
+    proto.push_str("  }\n}\n");
+    proto
+}
+
+/// Write the schema to `dir`
+///
+/// # Arguments
+/// * `dir` - Directory to write [`PROTO_FILE`] to, created if missing
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path written, or configuration error if the
+///   directory is not writable
+pub fn export(dir: &Path) -> Result<PathBuf> {
+    let path = dir.join(PROTO_FILE);
+    let written = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, proto_file()));
+    if written.is_err() {
+        return Err(SpaceCommError::ConfigurationError {
+            parameter: "proto_export_dir",
+            value: "<unwritable>",
+            reason: "protobuf schema could not be written",
+        });
+    }
+    Ok(path)
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_key(out: &mut Vec<u8>, field: u32, wire_type: u64) {
+    put_varint(out, (u64::from(field) << 3) | wire_type);
+}
+
+fn put_uint(out: &mut Vec<u8>, field: u32, value: u64) {
+    put_key(out, field, WIRE_VARINT);
+    put_varint(out, value);
+}
+
+fn put_double(out: &mut Vec<u8>, field: u32, value: f64) {
+    put_key(out, field, WIRE_FIXED64);
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_bytes(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    put_key(out, field, WIRE_LEN);
+    put_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/// Encode a telemetry packet as a `TelemetryPacket` message
+///
+/// - **ID**: FN-PB-002
+/// - **Requirement**: Telemetry reaches consumers outside Rust as messages
+///   of the generated schema.
+/// - **Inputs**: Parsed telemetry packet.
+/// - **Outputs**: Message bytes; integer values zigzag encoded as `sint64`.
+/// - **Side Effects**: None.
+/// - **Failure Modes**: None.
+pub fn encode_telemetry(packet: &TelemetryPacket) -> Vec<u8> {
+    let mut data = Vec::new();
+    put_uint(&mut data, 1, u64::from(packet.data.source.0));
+    put_uint(&mut data, 2, packet.data.timestamp);
+    for measurement in &packet.data.measurements {
+        let mut encoded = Vec::new();
+        put_uint(&mut encoded, 1, u64::from(measurement.measurement_id));
+        match &measurement.value {
+            // sint64: zigzag, so small negative values stay short
+            MeasurementValue::Integer(v) => {
+                put_uint(&mut encoded, 2, ((v << 1) ^ (v >> 63)) as u64)
+            }
+            MeasurementValue::Float(v) => put_double(&mut encoded, 3, *v),
+            MeasurementValue::Boolean(v) => put_uint(&mut encoded, 4, u64::from(*v)),
+            MeasurementValue::String(v) => put_bytes(&mut encoded, 5, v.as_bytes()),
+            MeasurementValue::Bytes(v) => put_bytes(&mut encoded, 6, v),
+        }
+        put_bytes(&mut encoded, 7, measurement.unit.as_bytes());
+        put_uint(&mut encoded, 8, u64::from(measurement.quality.code()));
+        put_bytes(&mut data, 3, &encoded);
+    }
+    put_uint(&mut data, 4, packet.data.health_status as u64);
+
+    let mut out = Vec::new();
+    put_uint(&mut out, 1, u64::from(packet.sequence));
+    put_bytes(&mut out, 2, &data);
+    put_uint(&mut out, 3, packet.band as u64);
+    put_uint(&mut out, 4, u64::from(packet.size_bytes));
+    out
+}
+
+fn not_in_dictionary() -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter: "protobuf_command",
+        value: "<command>",
+        reason: "command is not in the command dictionary",
+    }
+}
+
+/// Encode a dictionary command as a `Command` message
+///
+/// # Arguments
+/// * `command` - Command built from the dictionary
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - Message bytes, or configuration error when the
+///   command is not in the dictionary
+///
+/// # Requirements Traceability
+/// - FN-PB-003: Commands encoded to the schema
+pub fn encode_command(command: &SpaceCommand) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(command).map_err(|_| not_in_dictionary())?;
+    let (name, fields) = value
+        .as_object()
+        .and_then(|object| object.iter().next())
+        .ok_or_else(not_in_dictionary)?;
+    let spec = lookup(name).ok_or_else(not_in_dictionary)?;
+    let number = COMMAND_DICTIONARY
+        .iter()
+        .position(|entry| entry.name == spec.name)
+        .ok_or_else(not_in_dictionary)?;
+
+    let mut message = Vec::new();
+    for (field, parameter) in (1..).zip(spec.parameters) {
+        let value = &fields[parameter.name];
+        match parameter.kind {
+            ParameterKind::Choice(options) => {
+                let index = options
+                    .iter()
+                    .position(|option| value.as_str() == Some(option))
+                    .ok_or_else(not_in_dictionary)?;
+                put_uint(&mut message, field, index as u64);
+            }
+            ParameterKind::Unsigned(_) => {
+                put_uint(&mut message, field, value.as_u64().unwrap_or(0))
+            }
+            ParameterKind::OptionalUnsigned(_) => {
+                if let Some(value) = value.as_u64() {
+                    put_uint(&mut message, field, value);
+                }
+            }
+            ParameterKind::Float => put_double(&mut message, field, value.as_f64().unwrap_or(0.0)),
+            ParameterKind::Flag => put_uint(
+                &mut message,
+                field,
+                u64::from(value.as_bool() == Some(true)),
+            ),
+        }
+    }
+
+    let mut out = Vec::new();
+    put_bytes(&mut out, number as u32 + 1, &message);
+    Ok(out)
+}
+
+/// Value of one field on the wire
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WireValue<'a> {
+    Varint(u64),
+    Fixed64([u8; 8]),
+    Fixed32([u8; 4]),
+    Bytes(&'a [u8]),
+}
+
+fn malformed() -> SpaceCommError {
+    SpaceCommError::invalid_packet("Malformed protobuf message", None)
+}
+
+fn take_varint(bytes: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or_else(malformed)?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(malformed())
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(malformed());
+    }
+    let (head, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(head)
+}
+
+/// Every field of a message, in wire order
+fn fields(mut bytes: &[u8]) -> Result<Vec<(u32, WireValue<'_>)>> {
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = take_varint(&mut bytes)?;
+        let field = u32::try_from(key >> 3).map_err(|_| malformed())?;
+        let value = match key & 0x7 {
+            WIRE_VARINT => WireValue::Varint(take_varint(&mut bytes)?),
+            WIRE_FIXED64 => {
+                WireValue::Fixed64(take(&mut bytes, 8)?.try_into().map_err(|_| malformed())?)
+            }
+            WIRE_LEN => {
+                let len = usize::try_from(take_varint(&mut bytes)?).map_err(|_| malformed())?;
+                WireValue::Bytes(take(&mut bytes, len)?)
+            }
+            WIRE_FIXED32 => {
+                WireValue::Fixed32(take(&mut bytes, 4)?.try_into().map_err(|_| malformed())?)
+            }
+            _ => return Err(malformed()),
+        };
+        if field == 0 {
+            return Err(malformed());
+        }
+        fields.push((field, value));
+    }
+    Ok(fields)
+}
+
+/// Parameter value of a dictionary command field, checked against its kind
+fn parameter_value(kind: ParameterKind, value: Option<WireValue>) -> Result<Value> {
+    let invalid = || SpaceCommError::ConfigurationError {
+        parameter: "protobuf_command",
+        value: "<parameter>",
+        reason: "parameter value out of range for the dictionary",
+    };
+    let varint = || match value {
+        None => Ok(0),
+        Some(WireValue::Varint(v)) => Ok(v),
+        Some(_) => Err(malformed()),
+    };
+    match kind {
+        ParameterKind::Choice(options) => usize::try_from(varint()?)
+            .ok()
+            .and_then(|index| options.get(index))
+            .map(|option| Value::from(*option))
+            .ok_or_else(invalid),
+        ParameterKind::Unsigned(max) => Some(varint()?)
+            .filter(|v| *v <= max)
+            .map(Value::from)
+            .ok_or_else(invalid),
+        ParameterKind::OptionalUnsigned(_) if value.is_none() => Ok(Value::Null),
+        ParameterKind::OptionalUnsigned(max) => Some(varint()?)
+            .filter(|v| *v <= max)
+            .map(Value::from)
+            .ok_or_else(invalid),
+        ParameterKind::Float => match value {
+            None => Ok(Value::from(0.0)),
+            Some(WireValue::Fixed64(bytes)) => Some(f64::from_le_bytes(bytes))
+                .filter(|v| v.is_finite())
+                .map(Value::from)
+                .ok_or_else(invalid),
+            Some(_) => Err(malformed()),
+        },
+        ParameterKind::Flag => Ok(Value::Bool(varint()? != 0)),
+    }
+}
+
+/// Decode a `Command` message
+///
+/// - **ID**: FN-PB-003
+/// - **Requirement**: Commands from protobuf consumers are checked against
+///   the command dictionary before they are uplinked.
+/// - **Inputs**: `Command` message bytes; absent fields take their proto3
+///   defaults, unknown fields are skipped, and of repeated fields the last
+///   one counts.
+/// - **Outputs**: The command, built from its dictionary entry.
+/// - **Failure Modes**: `InvalidPacket` on malformed wire data or a field
+///   of the wrong wire type; `ConfigurationError` when no command is set or
+///   values the dictionary rejects.
+pub fn decode_command(bytes: &[u8]) -> Result<SpaceCommand> {
+    let (spec, message): (&CommandSpec, &[u8]) = fields(bytes)?
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let spec = COMMAND_DICTIONARY.get(usize::try_from(field).ok()? - 1)?;
+            Some((spec, value))
+        })
+        .next_back()
+        .map(|(spec, value)| match value {
+            WireValue::Bytes(message) => Ok((spec, message)),
+            _ => Err(malformed()),
+        })
+        .ok_or_else(not_in_dictionary)??;
+
+    let fields = fields(message)?;
+    let values = (1..)
+        .zip(spec.parameters)
+        .map(|(number, parameter)| {
+            let value = fields
+                .iter()
+                .rev()
+                .find(|(field, _)| *field == number)
+                .map(|(_, value)| *value);
+            parameter_value(parameter.kind, value)
+        })
+        .collect::<Result<Vec<Value>>>()?;
+    spec.build(&values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        commands::SafeModeLevel,
+        telemetry::{Measurement, MeasurementQuality, TelemetryData},
+        types::{BandType, ComponentId, HealthStatus},
+    };
+
+    #[test]
+    fn test_schema_covers_telemetry_and_dictionary() {
+        let proto = proto_file();
+        assert_eq!(proto, include_str!("../proto/space_comms.proto"));
+        assert!(proto.contains("package space_comms.ground.v1;"));
+        assert!(proto.contains("  BAND_TYPE_S_BAND = 1;"));
+        assert!(proto.contains("  MEASUREMENT_QUALITY_NOT_AVAILABLE = 4;"));
+        assert!(proto.contains("    MODULATION_QAM16 = 3;"));
+        assert!(proto.contains("  optional uint32 duration_seconds = 2;"));
+        assert!(proto.contains("  uint64 frequency_hz = 3;"));
+        assert!(proto.contains("    SetVcSecurityPolicy set_vc_security_policy = "));
+        for spec in COMMAND_DICTIONARY {
+            assert!(
+                proto.contains(&format!("\nmessage {} {{", spec.name)),
+                "{}",
+                spec.name
+            );
+        }
+
+        // Enum values follow the shared declaration order
+        let (_, bands) = TELEMETRY_ENUMS[0];
+        for (band, number) in [BandType::UhfBand, BandType::SBand, BandType::KaBand]
+            .into_iter()
+            .zip([0, 1, 4])
+        {
+            assert_eq!(band as usize, number);
+            assert_eq!(bands[number], format!("{:?}", band));
+        }
+        let (_, qualities) = TELEMETRY_ENUMS[2];
+        for (code, name) in qualities.iter().enumerate() {
+            assert_eq!(
+                format!("{:?}", MeasurementQuality::from_code(code as u8)),
+                *name
+            );
+        }
+        assert_eq!(
+            format!("{:?}", HealthStatus::Unknown),
+            TELEMETRY_ENUMS[1].1[5]
+        );
+    }
+
+    #[test]
+    fn test_telemetry_message_fields() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(7),
+            timestamp: 1_000_000_000,
+            measurements: Default::default(),
+            health_status: HealthStatus::Fair,
+        };
+        for (id, value) in [
+            (0x0101, MeasurementValue::Float(28.5)),
+            (0x0102, MeasurementValue::Integer(-3)),
+        ] {
+            data.measurements
+                .push(Measurement {
+                    measurement_id: id,
+                    value,
+                    unit: "V",
+                    quality: MeasurementQuality::Suspect,
+                })
+                .unwrap();
+        }
+        let packet = TelemetryPacket::new(300, data, BandType::XBand);
+
+        let encoded = encode_telemetry(&packet);
+        let top = fields(&encoded).unwrap();
+        assert_eq!(top[0], (1, WireValue::Varint(300)));
+        assert_eq!(top[2], (3, WireValue::Varint(2)));
+        let WireValue::Bytes(data) = top[1].1 else {
+            panic!("data is not a message");
+        };
+        let data = fields(data).unwrap();
+        assert_eq!(data[0], (1, WireValue::Varint(7)));
+        assert_eq!(data[1], (2, WireValue::Varint(1_000_000_000)));
+        assert_eq!(data[4], (4, WireValue::Varint(2)));
+
+        let WireValue::Bytes(first) = data[2].1 else {
+            panic!("measurement is not a message");
+        };
+        let first = fields(first).unwrap();
+        assert_eq!(first[0], (1, WireValue::Varint(0x0101)));
+        assert_eq!(first[1], (3, WireValue::Fixed64(28.5f64.to_le_bytes())));
+        assert_eq!(first[2], (7, WireValue::Bytes(b"V")));
+        assert_eq!(first[3], (8, WireValue::Varint(1)));
+        let WireValue::Bytes(second) = data[3].1 else {
+            panic!("measurement is not a message");
+        };
+        // -3 zigzag encodes to 5
+        assert_eq!(fields(second).unwrap()[1], (2, WireValue::Varint(5)));
+    }
+
+    #[test]
+    fn test_command_round_trip_through_dictionary() {
+        for spec in COMMAND_DICTIONARY {
+            let words: Vec<&str> = spec
+                .parameters
+                .iter()
+                .map(|parameter| match parameter.kind {
+                    ParameterKind::Choice(options) => options[options.len() - 1],
+                    ParameterKind::Unsigned(_) | ParameterKind::OptionalUnsigned(_) => "7",
+                    ParameterKind::Float => "-2.5",
+                    ParameterKind::Flag => "true",
+                })
+                .collect();
+            let command = spec.parse(&words).unwrap();
+            let encoded = encode_command(&command).unwrap();
+            assert_eq!(decode_command(&encoded).unwrap(), command, "{}", spec.name);
+        }
+
+        // Absent fields decode as proto3 defaults; unknown fields are skipped
+        let safe_mode = COMMAND_DICTIONARY
+            .iter()
+            .position(|spec| spec.name == "ActivateSafeMode")
+            .unwrap() as u32
+            + 1;
+        let mut message = Vec::new();
+        put_uint(&mut message, 1, 1);
+        put_uint(&mut message, 9, 42);
+        let mut bytes = Vec::new();
+        put_bytes(&mut bytes, safe_mode, &message);
+        assert_eq!(
+            decode_command(&bytes).unwrap(),
+            SpaceCommand::ActivateSafeMode {
+                safe_mode_level: SafeModeLevel::Level2,
+                duration_seconds: None,
+            }
+        );
+
+        // Values the dictionary rejects, and malformed wire data
+        let mut out_of_range = Vec::new();
+        put_uint(&mut out_of_range, 1, 9);
+        let mut bytes = Vec::new();
+        put_bytes(&mut bytes, safe_mode, &out_of_range);
+        assert!(decode_command(&bytes).is_err());
+        assert!(decode_command(&[]).is_err());
+        assert!(decode_command(&[0x0A, 0x05, 0x08]).is_err());
+        assert!(decode_command(&[0x08, 0x01]).is_err());
+    }
+}
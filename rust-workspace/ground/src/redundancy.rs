@@ -0,0 +1,690 @@
+//! Ground station hot-standby redundancy
+//!
+//! Two station instances run as an active/standby pair. Only the active
+//! instance holds command authority; the standby refuses to uplink. The pair
+//! exchange sync messages over a dedicated UDP channel: the active instance
+//! replicates its uplink sequence counts and command execution verification
+//! archive, so a standby taking over continues both where the active left
+//! off, and the standby reports how much of the archive it holds.
+//!
+//! # Sync messages
+//! Each datagram is one JSON-encoded [`SyncMessage`] carrying the sender's
+//! instance ID, role and authority epoch. Every instance sends one each
+//! heartbeat period; the active instance also sends one as soon as its
+//! sequence counts change or the standby is missing archived executions, at
+//! most [`MAX_SYNC_EXECUTIONS`] executions per message.
+//!
+//! # Failover
+//! A standby that hears no active instance for the failover timeout takes
+//! over command authority with the next authority epoch, at least 2. The
+//! timeout must span at least [`MIN_HEARTBEATS_PER_TIMEOUT`] heartbeat
+//! periods, so a few lost datagrams never cause a takeover.
+//!
+//! # Split-brain protection
+//! Authority is ordered by epoch, then by the lower instance ID. An active
+//! instance hearing an active peer that outranks it steps down at once and
+//! adopts the peer's state; one hearing a peer it outranks answers
+//! immediately so the peer steps down. A pair whose sync channel is cut
+//! while both stay up therefore resolves to a single active instance as soon
+//! as the channel heals, and a restarted instance never takes authority from
+//! a running one: it starts at epoch 1 if configured active and epoch 0 if
+//! standby, below any epoch reached by a takeover.
+//!
+//! Like the SBN bridge, the link reads no clock and owns no socket: every
+//! call takes the current time in milliseconds and returns the datagram to
+//! send, which keeps it deterministic under test.
+//!
+//! # Requirements Traceability
+//! - FN-RED-001: Sequence counts and verification state replicated to the
+//!   standby
+//! - FN-RED-002: Standby takes over command authority on failure of the
+//!   active instance
+//! - FN-RED-003: Split-brain protection by authority epoch
+//! - REQ-NF-003: System Availability (hot-standby ground segment)
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+use space_comms_shared::{Result, SpaceCommError};
+
+use crate::verification::ArchivedExecution;
+
+/// Sync message format version
+pub const SYNC_PROTOCOL_VERSION: u8 = 1;
+
+/// Archived executions carried by one sync message
+pub const MAX_SYNC_EXECUTIONS: usize = 32;
+
+/// Heartbeat periods the failover timeout must span
+pub const MIN_HEARTBEATS_PER_TIMEOUT: u64 = 3;
+
+/// Role of a station instance in the pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    /// Holds command authority
+    Active,
+    /// Receives replicated state and takes over on failure of the active
+    Standby,
+}
+
+/// Redundancy configuration of one instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedundancyConfig {
+    /// Instance ID, different from the peer's; the lower ID wins a tie
+    pub instance_id: u32,
+    /// Role at startup
+    pub role: Role,
+    /// Local UDP address of the sync channel
+    pub bind_addr: SocketAddr,
+    /// Sync channel address of the peer instance; datagrams from other
+    /// addresses are rejected
+    pub peer_addr: SocketAddr,
+    /// Period of sync messages, milliseconds
+    pub heartbeat_ms: u64,
+    /// Silence of the active instance after which the standby takes over,
+    /// milliseconds
+    pub failover_timeout_ms: u64,
+}
+
+impl Default for RedundancyConfig {
+    /// Active instance 1 on the loopback sync channel, peer on the next port
+    fn default() -> Self {
+        Self {
+            instance_id: 1,
+            role: Role::Active,
+            bind_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8090),
+            peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8091),
+            heartbeat_ms: 500,
+            failover_timeout_ms: 3_000,
+        }
+    }
+}
+
+impl RedundancyConfig {
+    /// Check the heartbeat period and failover timeout
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the invalid parameter
+    pub fn validate(&self) -> Result<()> {
+        if self.heartbeat_ms == 0 {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "heartbeat_ms",
+                value: "0",
+                reason: "Sync heartbeat period must be positive",
+            });
+        }
+        if self.failover_timeout_ms < self.heartbeat_ms * MIN_HEARTBEATS_PER_TIMEOUT {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "failover_timeout_ms",
+                value: "too short",
+                reason: "Failover timeout must span at least three heartbeat periods",
+            });
+        }
+        if self.bind_addr == self.peer_addr {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "peer_addr",
+                value: "bind_addr",
+                reason: "Sync peer must be another instance",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Uplink sequence counts replicated to the standby
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceState {
+    /// Last count uplinked on each command APID, by APID
+    pub uplink: Vec<(u16, u16)>,
+    /// Last count uplinked on the emergency lane
+    pub emergency: u16,
+}
+
+/// One datagram on the sync channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncMessage {
+    /// [`SYNC_PROTOCOL_VERSION`] of the sender
+    pub version: u8,
+    /// Instance ID of the sender
+    pub instance_id: u32,
+    /// Role of the sender
+    pub role: Role,
+    /// Authority epoch of the sender
+    pub epoch: u64,
+    /// Executions in the sender's verification archive
+    pub archived_executions: usize,
+    /// Active sender's sequence counts
+    pub sequences: Option<SequenceState>,
+    /// Archive index of the first of `executions`
+    pub executions_from: usize,
+    /// Archived executions the standby is missing
+    pub executions: Vec<ArchivedExecution>,
+}
+
+impl SyncMessage {
+    /// Encode as a sync channel datagram
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|_| SpaceCommError::invalid_packet("Unencodable sync message", None))
+    }
+
+    /// Decode a sync channel datagram
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Message, or an error for a malformed datagram or
+    ///   another protocol version
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let message: Self = serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed sync message", None))?;
+        if message.version != SYNC_PROTOCOL_VERSION {
+            return Err(SpaceCommError::ProtocolError {
+                expected_version: SYNC_PROTOCOL_VERSION,
+                received_version: message.version,
+                protocol: "redundancy sync",
+            });
+        }
+        Ok(message)
+    }
+}
+
+/// State received from the active instance, for the standby to apply
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replica {
+    /// Active instance's sequence counts
+    pub sequences: SequenceState,
+    /// Archive index of the first of `executions`
+    pub executions_from: usize,
+    /// Archived executions from `executions_from` on
+    pub executions: Vec<ArchivedExecution>,
+}
+
+impl Replica {
+    /// Executions to append to an archive holding `archived` entries: those
+    /// past its end, or none if the replica starts beyond it
+    pub fn new_executions(&self, archived: usize) -> &[ArchivedExecution] {
+        match archived.checked_sub(self.executions_from) {
+            Some(held) => &self.executions[held.min(self.executions.len())..],
+            None => &[],
+        }
+    }
+}
+
+/// Why an instance changed role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleChangeReason {
+    /// No active instance heard for the failover timeout
+    ActiveSilent,
+    /// An active peer outranks this instance
+    PeerOutranks,
+}
+
+/// One change of role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoleChange {
+    /// Time of the change, milliseconds
+    pub at_ms: u64,
+    /// Role taken
+    pub role: Role,
+    /// Authority epoch after the change
+    pub epoch: u64,
+    /// Why the role changed
+    pub reason: RoleChangeReason,
+}
+
+/// Hot-standby redundancy state of one instance
+///
+/// - **ID**: FN-RED-002
+/// - **Requirement**: Exactly one instance of a pair holds command authority,
+///   and the standby takes it over when the active instance fails.
+/// - **Inputs**: Sync datagrams from the peer, the station's sequence counts
+///   and verification archive, and the current time.
+/// - **Outputs**: Sync datagrams to send, and state replicated from the
+///   active instance.
+/// - **Side Effects**: Role, authority epoch and peer state.
+/// - **Failure Modes**: Datagrams from other addresses, malformed datagrams,
+///   other protocol versions and a peer with this instance's ID are rejected
+///   with an error and change no state.
+#[derive(Debug, Clone)]
+pub struct RedundancyLink {
+    config: RedundancyConfig,
+    role: Role,
+    epoch: u64,
+    started_ms: u64,
+    /// Time an active peer was last heard from
+    active_heard_ms: Option<u64>,
+    /// Time the peer was last heard from, with its role and epoch
+    peer: Option<(u64, Role, u64)>,
+    /// Archived executions the peer is known to hold
+    peer_executions: usize,
+    last_sent_ms: Option<u64>,
+    sent_sequences: Option<SequenceState>,
+    /// A stale active peer is answered without waiting for the heartbeat
+    reply_due: bool,
+    changes: Vec<RoleChange>,
+}
+
+impl RedundancyLink {
+    /// Start in the configured role at `now_ms`
+    pub fn new(config: RedundancyConfig, now_ms: u64) -> Self {
+        let epoch = match config.role {
+            Role::Active => 1,
+            Role::Standby => 0,
+        };
+        Self {
+            role: config.role,
+            config,
+            epoch,
+            started_ms: now_ms,
+            active_heard_ms: None,
+            peer: None,
+            peer_executions: 0,
+            last_sent_ms: None,
+            sent_sequences: None,
+            reply_due: false,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Redundancy configuration
+    pub fn config(&self) -> &RedundancyConfig {
+        &self.config
+    }
+
+    /// Current role
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Current authority epoch
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Whether this instance may uplink
+    pub fn has_command_authority(&self) -> bool {
+        self.role == Role::Active
+    }
+
+    /// Peer's role and epoch if it was heard from within the failover timeout
+    pub fn peer_status(&self, now_ms: u64) -> Option<(Role, u64)> {
+        self.peer
+            .filter(|(heard, _, _)| now_ms.saturating_sub(*heard) < self.config.failover_timeout_ms)
+            .map(|(_, role, epoch)| (role, epoch))
+    }
+
+    /// Role changes since startup, oldest first
+    pub fn role_changes(&self) -> &[RoleChange] {
+        &self.changes
+    }
+
+    /// Handle a datagram from `from`
+    ///
+    /// # Arguments
+    /// * `from` - Source address of the datagram
+    /// * `bytes` - Datagram as received
+    /// * `now_ms` - Current time, milliseconds
+    ///
+    /// # Returns
+    /// * `Result<Option<Replica>>` - State to apply, if this instance is (or
+    ///   has just become) the standby of the sender
+    ///
+    /// # Requirements Traceability
+    /// - FN-RED-001: Standby adopts the active instance's state
+    /// - FN-RED-003: Outranked active instance steps down
+    pub fn receive(
+        &mut self,
+        from: SocketAddr,
+        bytes: &[u8],
+        now_ms: u64,
+    ) -> Result<Option<Replica>> {
+        if from != self.config.peer_addr {
+            return Err(SpaceCommError::invalid_packet(
+                "Sync datagram from unknown instance",
+                None,
+            ));
+        }
+        let message = SyncMessage::decode(bytes)?;
+        if message.instance_id == self.config.instance_id {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "instance_id",
+                value: "duplicate",
+                reason: "Sync peer has this instance's ID",
+            });
+        }
+
+        self.peer = Some((now_ms, message.role, message.epoch));
+        self.peer_executions = message.archived_executions;
+        if message.role == Role::Standby {
+            return Ok(None);
+        }
+
+        let outranks = message.epoch > self.epoch
+            || (message.epoch == self.epoch && message.instance_id < self.config.instance_id);
+        match self.role {
+            Role::Active if outranks => {
+                self.epoch = message.epoch;
+                self.change_role(Role::Standby, RoleChangeReason::PeerOutranks, now_ms);
+            }
+            Role::Active => {
+                self.reply_due = true;
+                return Ok(None);
+            }
+            // A message from before authority moved on
+            Role::Standby if message.epoch < self.epoch => return Ok(None),
+            Role::Standby => self.epoch = message.epoch,
+        }
+
+        self.active_heard_ms = Some(now_ms);
+        Ok(message.sequences.map(|sequences| Replica {
+            sequences,
+            executions_from: message.executions_from,
+            executions: message.executions,
+        }))
+    }
+
+    /// Take over if due and build the next sync message if one is due
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current time, milliseconds
+    /// * `sequences` - Station's current sequence counts
+    /// * `archive` - Station's archived executions
+    ///
+    /// # Returns
+    /// * `Result<Option<Vec<u8>>>` - Datagram to send to the peer, if due
+    ///
+    /// # Requirements Traceability
+    /// - FN-RED-001: State sent as soon as it changes
+    /// - FN-RED-002: Standby takes over after the failover timeout
+    pub fn poll(
+        &mut self,
+        now_ms: u64,
+        sequences: &SequenceState,
+        archive: &[ArchivedExecution],
+    ) -> Result<Option<Vec<u8>>> {
+        let active_silent_ms =
+            now_ms.saturating_sub(self.active_heard_ms.unwrap_or(self.started_ms));
+        if self.role == Role::Standby && active_silent_ms >= self.config.failover_timeout_ms {
+            // Above the epoch an instance configured active starts at
+            self.epoch = self.epoch.max(1) + 1;
+            self.change_role(Role::Active, RoleChangeReason::ActiveSilent, now_ms);
+        }
+
+        let active = self.role == Role::Active;
+        let heartbeat_due = self
+            .last_sent_ms
+            .is_none_or(|sent| now_ms.saturating_sub(sent) >= self.config.heartbeat_ms);
+        let state_changed = active
+            && (self.sent_sequences.as_ref() != Some(sequences)
+                || self.peer_executions < archive.len());
+        if !(heartbeat_due || state_changed || self.reply_due) {
+            return Ok(None);
+        }
+
+        let mut message = SyncMessage {
+            version: SYNC_PROTOCOL_VERSION,
+            instance_id: self.config.instance_id,
+            role: self.role,
+            epoch: self.epoch,
+            archived_executions: archive.len(),
+            sequences: None,
+            executions_from: 0,
+            executions: Vec::new(),
+        };
+        if active {
+            let from = self.peer_executions.min(archive.len());
+            message.sequences = Some(sequences.clone());
+            message.executions_from = from;
+            message.executions = archive[from..]
+                .iter()
+                .take(MAX_SYNC_EXECUTIONS)
+                .copied()
+                .collect();
+            // Assumed delivered; the standby's next heartbeat corrects it
+            self.peer_executions = from + message.executions.len();
+            self.sent_sequences = Some(sequences.clone());
+        }
+        self.last_sent_ms = Some(now_ms);
+        self.reply_due = false;
+        message.encode().map(Some)
+    }
+
+    fn change_role(&mut self, role: Role, reason: RoleChangeReason, now_ms: u64) {
+        self.role = role;
+        self.sent_sequences = None;
+        self.changes.push(RoleChange {
+            at_ms: now_ms,
+            role,
+            epoch: self.epoch,
+            reason,
+        });
+    }
+}
+
+/// Format the redundancy state for the console
+///
+/// # Arguments
+/// * `link` - Redundancy state
+/// * `now_ms` - Current time, milliseconds
+///
+/// # Returns
+/// * `String` - Role, epoch, peer state and role changes, one per line
+pub fn format_redundancy(link: &RedundancyLink, now_ms: u64) -> String {
+    let config = link.config();
+    let mut out = format!(
+        "Instance {}: {:?} (epoch {}), sync {} <-> {}\n",
+        config.instance_id,
+        link.role(),
+        link.epoch(),
+        config.bind_addr,
+        config.peer_addr
+    );
+    match link.peer_status(now_ms) {
+        Some((role, epoch)) => out.push_str(&format!("Peer: {:?} (epoch {})\n", role, epoch)),
+        None => out.push_str("Peer: silent\n"),
+    }
+    for change in link.role_changes() {
+        out.push_str(&format!(
+            "  {} ms: {:?} at epoch {} ({:?})\n",
+            change.at_ms, change.role, change.epoch, change.reason
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        execution_report::{ExecutionReport, ExecutionResult},
+        messaging::MessagePriority,
+    };
+
+    const A: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8090);
+    const B: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8091);
+
+    fn pair() -> (RedundancyLink, RedundancyLink) {
+        let active = RedundancyLink::new(RedundancyConfig::default(), 0);
+        let standby = RedundancyLink::new(
+            RedundancyConfig {
+                instance_id: 2,
+                role: Role::Standby,
+                bind_addr: B,
+                peer_addr: A,
+                ..RedundancyConfig::default()
+            },
+            0,
+        );
+        (active, standby)
+    }
+
+    fn execution(command_id: u32) -> ArchivedExecution {
+        ArchivedExecution {
+            received_unix_ms: 1_000,
+            report: ExecutionReport {
+                command_id,
+                sequence_count: 1,
+                priority: MessagePriority::High,
+                result: ExecutionResult::Completed,
+                execution_time_us: 250,
+                budget_ms: 10,
+            },
+        }
+    }
+
+    fn sequences(count: u16) -> SequenceState {
+        SequenceState {
+            uplink: vec![(0x002, count)],
+            emergency: 3,
+        }
+    }
+
+    #[test]
+    fn test_redundancy_config_validation() {
+        assert!(RedundancyConfig::default().validate().is_ok());
+        let short = RedundancyConfig {
+            failover_timeout_ms: 1_000,
+            ..RedundancyConfig::default()
+        };
+        assert!(short.validate().is_err());
+        let looped = RedundancyConfig {
+            peer_addr: A,
+            ..RedundancyConfig::default()
+        };
+        assert!(looped.validate().is_err());
+    }
+
+    #[test]
+    fn test_standby_replicates_active_state() {
+        let (mut active, mut standby) = pair();
+        let archive: Vec<_> = (0..40).map(execution).collect();
+
+        let datagram = active.poll(0, &sequences(7), &archive).unwrap().unwrap();
+        let replica = standby.receive(A, &datagram, 10).unwrap().unwrap();
+        assert_eq!(replica.sequences, sequences(7));
+        assert_eq!(replica.executions.len(), MAX_SYNC_EXECUTIONS);
+        assert_eq!(replica.new_executions(0), &archive[..MAX_SYNC_EXECUTIONS]);
+        assert_eq!(standby.epoch(), 1);
+        assert!(!standby.has_command_authority());
+
+        // The rest of the archive follows at once, then nothing until due
+        let rest = active.poll(20, &sequences(7), &archive).unwrap().unwrap();
+        let replica = standby.receive(A, &rest, 30).unwrap().unwrap();
+        assert_eq!(replica.executions_from, MAX_SYNC_EXECUTIONS);
+        assert_eq!(replica.new_executions(MAX_SYNC_EXECUTIONS).len(), 8);
+        assert_eq!(replica.new_executions(45), &[]);
+        assert!(active.poll(40, &sequences(7), &archive).unwrap().is_none());
+
+        // A new uplink count is sent without waiting for the heartbeat
+        let update = active.poll(50, &sequences(8), &archive).unwrap().unwrap();
+        let replica = standby.receive(A, &update, 60).unwrap().unwrap();
+        assert_eq!(replica.sequences, sequences(8));
+        assert!(replica.executions.is_empty());
+
+        // A standby reporting a shorter archive gets the missing executions
+        let heartbeat = standby
+            .poll(70, &sequences(0), &archive[..4])
+            .unwrap()
+            .unwrap();
+        assert!(active.receive(B, &heartbeat, 80).unwrap().is_none());
+        let resend = active.poll(90, &sequences(8), &archive).unwrap().unwrap();
+        let replica = standby.receive(A, &resend, 100).unwrap().unwrap();
+        assert_eq!(
+            replica.new_executions(4),
+            &archive[4..4 + MAX_SYNC_EXECUTIONS]
+        );
+        assert_eq!(active.peer_status(100), Some((Role::Standby, 1)));
+    }
+
+    #[test]
+    fn test_standby_takes_over_when_active_silent() {
+        let (mut active, mut standby) = pair();
+        let datagram = active.poll(0, &sequences(7), &[]).unwrap().unwrap();
+        standby.receive(A, &datagram, 0).unwrap();
+
+        standby.poll(2_999, &sequences(7), &[]).unwrap();
+        assert_eq!(standby.role(), Role::Standby);
+        let datagram = standby.poll(3_000, &sequences(7), &[]).unwrap().unwrap();
+        assert!(standby.has_command_authority());
+        assert_eq!(standby.epoch(), 2);
+        assert_eq!(
+            standby.role_changes(),
+            &[RoleChange {
+                at_ms: 3_000,
+                role: Role::Active,
+                epoch: 2,
+                reason: RoleChangeReason::ActiveSilent,
+            }]
+        );
+        let message = SyncMessage::decode(&datagram).unwrap();
+        assert_eq!(
+            (message.role, message.sequences),
+            (Role::Active, Some(sequences(7)))
+        );
+
+        // A standby that never hears an active instance takes over too
+        let (_, mut orphan) = pair();
+        orphan.poll(3_000, &SequenceState::default(), &[]).unwrap();
+        assert_eq!((orphan.role(), orphan.epoch()), (Role::Active, 2));
+    }
+
+    #[test]
+    fn test_split_brain_resolves_to_higher_epoch() {
+        let (mut old, mut standby) = pair();
+        let datagram = old.poll(0, &sequences(7), &[]).unwrap().unwrap();
+        standby.receive(A, &datagram, 0).unwrap();
+
+        // Sync channel cut: the standby takes over while the old active lives on
+        standby.poll(3_000, &sequences(9), &[]).unwrap();
+        assert!(old.has_command_authority() && standby.has_command_authority());
+
+        // Channel heals: the new active ignores the stale one and answers at once
+        let from_old = old.poll(3_100, &sequences(7), &[]).unwrap().unwrap();
+        assert!(standby.receive(A, &from_old, 3_110).unwrap().is_none());
+        assert!(standby.has_command_authority());
+        let answer = standby.poll(3_120, &sequences(9), &[]).unwrap().unwrap();
+
+        // The outranked instance steps down and adopts the new active's state
+        let replica = old.receive(B, &answer, 3_130).unwrap().unwrap();
+        assert_eq!(replica.sequences, sequences(9));
+        assert_eq!((old.role(), old.epoch()), (Role::Standby, 2));
+        assert_eq!(old.role_changes()[0].reason, RoleChangeReason::PeerOutranks);
+
+        // Equal epochs: the lower instance ID keeps authority
+        let (mut first, _) = pair();
+        let mut second = RedundancyLink::new(
+            RedundancyConfig {
+                instance_id: 2,
+                bind_addr: B,
+                peer_addr: A,
+                ..RedundancyConfig::default()
+            },
+            0,
+        );
+        let from_second = second.poll(0, &sequences(1), &[]).unwrap().unwrap();
+        assert!(first.receive(B, &from_second, 0).unwrap().is_none());
+        let from_first = first.poll(0, &sequences(1), &[]).unwrap().unwrap();
+        second.receive(A, &from_first, 0).unwrap();
+        assert!(first.has_command_authority());
+        assert!(!second.has_command_authority());
+    }
+
+    #[test]
+    fn test_sync_datagrams_rejected() {
+        let (mut active, mut standby) = pair();
+        let datagram = active.poll(0, &sequences(7), &[]).unwrap().unwrap();
+        assert!(standby.receive(B, &datagram, 0).is_err());
+        assert!(standby.receive(A, b"not json", 0).is_err());
+
+        let mut message = SyncMessage::decode(&datagram).unwrap();
+        message.version = 2;
+        assert!(matches!(
+            standby.receive(A, &message.encode().unwrap(), 0),
+            Err(SpaceCommError::ProtocolError { .. })
+        ));
+        message.version = SYNC_PROTOCOL_VERSION;
+        message.instance_id = 2;
+        assert!(standby.receive(A, &message.encode().unwrap(), 0).is_err());
+        assert_eq!(standby.peer_status(0), None);
+        assert_eq!(standby.epoch(), 0);
+    }
+}
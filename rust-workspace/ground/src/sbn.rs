@@ -0,0 +1,777 @@
+//! cFS Software Bus Network bridge
+//!
+//! Connects the station to the core Flight System Software Bus Network (SBN)
+//! over UDP, so cFS tooling on another host can subscribe to downlinked
+//! packets and publish commands without custom glue. Software bus messages
+//! are CCSDS space packets, so packets cross the bridge unchanged; the bridge
+//! adds the SBN framing and follows the SBN peer protocol.
+//!
+//! # Wire format
+//! Every datagram starts with the SBN header, big-endian:
+//!
+//! | Offset | Size | Field         |
+//! |--------|------|---------------|
+//! | 0      | 2    | Payload size  |
+//! | 2      | 1    | Message type  |
+//! | 3      | 4    | Processor ID  |
+//! | 7      | 4    | Spacecraft ID |
+//!
+//! The payload depends on the message type:
+//! - Subscribe / unsubscribe: NUL-padded 48-byte SBN version identifier, a
+//!   16-bit count, then a 32-bit message ID, QoS priority and QoS
+//!   reliability byte per subscription
+//! - Application: one software bus message
+//! - UDP heartbeat, announce and disconnect: empty
+//!
+//! Message IDs follow the cFE v1 mapping: the first 16 bits of the CCSDS
+//! primary header without the version, i.e. packet type, secondary header
+//! flag and APID.
+//!
+//! # Peer protocol
+//! The bridge announces itself to every configured peer until the peer is
+//! heard from, then sends its subscriptions and a heartbeat every heartbeat
+//! period. A peer silent for the peer timeout, or one that disconnects, loses
+//! its subscriptions until it is heard from again. Downlinked packets go to
+//! each connected peer subscribed to their message ID; application messages
+//! from peers must carry one of the station's command message IDs and are
+//! handed back as ground commands.
+//!
+//! Like the event scheduler, the bridge reads no clock and owns no socket:
+//! every call takes the current time in milliseconds and returns the
+//! datagrams to send, which keeps it deterministic under test.
+//!
+//! # Requirements Traceability
+//! - FN-SBN-001: SBN framing of software bus messages
+//! - FN-SBN-002: SBN peer protocol with subscriptions and heartbeats
+//! - REQ-IF-002: CCSDS Compliance (packets bridged unchanged)
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use space_comms_shared::{
+    messaging::{decode_command_packet, MessagePriority},
+    Result, SpaceCommError,
+};
+
+use crate::Command;
+
+/// Length of the SBN header
+pub const SBN_HEADER_LEN: usize = 11;
+
+/// Length of the version identifier leading subscription messages
+pub const SBN_IDENT_LEN: usize = 48;
+
+/// Version identifier sent with the bridge's subscriptions
+pub const SBN_IDENT: &str = "space-comms-ground SBN bridge";
+
+/// Encoded length of one subscription entry
+const SUBSCRIPTION_LEN: usize = 6;
+
+/// Message ID bit marking a command packet
+const COMMAND_MESSAGE_ID_FLAG: u32 = 0x1000;
+
+/// Priorities whose command APIDs the bridge subscribes to
+const COMMAND_PRIORITIES: [MessagePriority; 5] = [
+    MessagePriority::Emergency,
+    MessagePriority::Critical,
+    MessagePriority::High,
+    MessagePriority::Medium,
+    MessagePriority::Low,
+];
+
+/// SBN message type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SbnMessageType {
+    /// Subscriptions added by the sender
+    Subscribe = 0x01,
+    /// Subscriptions withdrawn by the sender
+    Unsubscribe = 0x02,
+    /// Software bus message
+    App = 0x03,
+    /// UDP module keep-alive
+    Heartbeat = 0xA0,
+    /// UDP module announcement of a starting peer
+    Announce = 0xA1,
+    /// UDP module notice of a stopping peer
+    Disconnect = 0xA2,
+}
+
+impl SbnMessageType {
+    /// Message type of a header type byte
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x01 => Some(SbnMessageType::Subscribe),
+            0x02 => Some(SbnMessageType::Unsubscribe),
+            0x03 => Some(SbnMessageType::App),
+            0xA0 => Some(SbnMessageType::Heartbeat),
+            0xA1 => Some(SbnMessageType::Announce),
+            0xA2 => Some(SbnMessageType::Disconnect),
+            _ => None,
+        }
+    }
+}
+
+/// One software bus subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subscription {
+    /// Software bus message ID
+    pub message_id: u32,
+    /// QoS priority
+    pub priority: u8,
+    /// QoS reliability
+    pub reliability: u8,
+}
+
+impl Subscription {
+    /// Subscription to `message_id` with default QoS
+    pub const fn new(message_id: u32) -> Self {
+        Self {
+            message_id,
+            priority: 0,
+            reliability: 0,
+        }
+    }
+}
+
+/// Payload of an SBN message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SbnMessage {
+    /// Subscriptions added by the sender
+    Subscribe(Vec<Subscription>),
+    /// Subscriptions withdrawn by the sender
+    Unsubscribe(Vec<Subscription>),
+    /// Software bus message, a CCSDS space packet
+    App(Vec<u8>),
+    /// UDP module keep-alive
+    Heartbeat,
+    /// UDP module announcement of a starting peer
+    Announce,
+    /// UDP module notice of a stopping peer
+    Disconnect,
+}
+
+impl SbnMessage {
+    /// Message type carried in the header
+    pub const fn message_type(&self) -> SbnMessageType {
+        match self {
+            SbnMessage::Subscribe(_) => SbnMessageType::Subscribe,
+            SbnMessage::Unsubscribe(_) => SbnMessageType::Unsubscribe,
+            SbnMessage::App(_) => SbnMessageType::App,
+            SbnMessage::Heartbeat => SbnMessageType::Heartbeat,
+            SbnMessage::Announce => SbnMessageType::Announce,
+            SbnMessage::Disconnect => SbnMessageType::Disconnect,
+        }
+    }
+}
+
+/// SBN datagram: header fields and payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbnFrame {
+    /// cFE processor ID of the sender
+    pub processor_id: u32,
+    /// cFE spacecraft ID of the sender
+    pub spacecraft_id: u32,
+    /// Message payload
+    pub message: SbnMessage,
+}
+
+impl SbnFrame {
+    /// Encode as one UDP datagram.
+    ///
+    /// - **ID**: FN-SBN-001
+    /// - **Requirement**: Software bus messages and SBN protocol messages
+    ///   framed as the SBN UDP module expects them.
+    /// - **Outputs**: SBN header followed by the payload, as laid out in the
+    ///   module documentation.
+    /// - **Failure Modes**: `InvalidPacket` when the payload exceeds the 16-bit
+    ///   size field.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut payload = Vec::new();
+        match &self.message {
+            SbnMessage::Subscribe(subscriptions) | SbnMessage::Unsubscribe(subscriptions) => {
+                let mut ident = [0u8; SBN_IDENT_LEN];
+                let len = SBN_IDENT.len().min(SBN_IDENT_LEN - 1);
+                ident[..len].copy_from_slice(&SBN_IDENT.as_bytes()[..len]);
+                payload.extend_from_slice(&ident);
+                let count = u16::try_from(subscriptions.len())
+                    .map_err(|_| SpaceCommError::invalid_packet("Too many subscriptions", None))?;
+                payload.extend_from_slice(&count.to_be_bytes());
+                for subscription in subscriptions {
+                    payload.extend_from_slice(&subscription.message_id.to_be_bytes());
+                    payload.push(subscription.priority);
+                    payload.push(subscription.reliability);
+                }
+            }
+            SbnMessage::App(packet) => payload.extend_from_slice(packet),
+            SbnMessage::Heartbeat | SbnMessage::Announce | SbnMessage::Disconnect => {}
+        }
+        let size = u16::try_from(payload.len())
+            .map_err(|_| SpaceCommError::invalid_packet("SBN payload too long", None))?;
+
+        let mut bytes = Vec::with_capacity(SBN_HEADER_LEN + payload.len());
+        bytes.extend_from_slice(&size.to_be_bytes());
+        bytes.push(self.message.message_type() as u8);
+        bytes.extend_from_slice(&self.processor_id.to_be_bytes());
+        bytes.extend_from_slice(&self.spacecraft_id.to_be_bytes());
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    /// Decode one UDP datagram
+    ///
+    /// # Arguments
+    /// * `bytes` - Datagram as received, header included
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Frame, or `InvalidPacket` when the datagram is
+    ///   truncated, its size field disagrees with its length, or its message
+    ///   type is not one the bridge handles
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SBN_HEADER_LEN {
+            return Err(SpaceCommError::invalid_packet("SBN header truncated", None));
+        }
+        let size = usize::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+        let payload = &bytes[SBN_HEADER_LEN..];
+        if payload.len() != size {
+            return Err(SpaceCommError::invalid_packet(
+                "SBN payload size mismatch",
+                Some(size as u32),
+            ));
+        }
+        let message_type = SbnMessageType::from_u8(bytes[2]).ok_or(
+            SpaceCommError::invalid_packet("Unsupported SBN message type", Some(bytes[2].into())),
+        )?;
+
+        let message = match message_type {
+            SbnMessageType::Subscribe => SbnMessage::Subscribe(decode_subscriptions(payload)?),
+            SbnMessageType::Unsubscribe => SbnMessage::Unsubscribe(decode_subscriptions(payload)?),
+            SbnMessageType::App => SbnMessage::App(payload.to_vec()),
+            SbnMessageType::Heartbeat => SbnMessage::Heartbeat,
+            SbnMessageType::Announce => SbnMessage::Announce,
+            SbnMessageType::Disconnect => SbnMessage::Disconnect,
+        };
+        Ok(Self {
+            processor_id: u32::from_be_bytes([bytes[3], bytes[4], bytes[5], bytes[6]]),
+            spacecraft_id: u32::from_be_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]),
+            message,
+        })
+    }
+}
+
+/// Subscription entries following the version identifier
+fn decode_subscriptions(payload: &[u8]) -> Result<Vec<Subscription>> {
+    let truncated = || SpaceCommError::invalid_packet("SBN subscriptions truncated", None);
+    let count = payload
+        .get(SBN_IDENT_LEN..SBN_IDENT_LEN + 2)
+        .map(|b| usize::from(u16::from_be_bytes([b[0], b[1]])))
+        .ok_or_else(truncated)?;
+    let entries = &payload[SBN_IDENT_LEN + 2..];
+    if entries.len() != count * SUBSCRIPTION_LEN {
+        return Err(truncated());
+    }
+    Ok(entries
+        .chunks_exact(SUBSCRIPTION_LEN)
+        .map(|e| Subscription {
+            message_id: u32::from_be_bytes([e[0], e[1], e[2], e[3]]),
+            priority: e[4],
+            reliability: e[5],
+        })
+        .collect())
+}
+
+/// Software bus message ID of a CCSDS space packet, if it has a header
+pub fn message_id(packet: &[u8]) -> Option<u32> {
+    packet
+        .get(..2)
+        .map(|b| u32::from(u16::from_be_bytes([b[0], b[1]]) & 0x1FFF))
+}
+
+/// Software bus message ID of the command packets on `apid`
+pub const fn command_message_id(apid: u16) -> u32 {
+    COMMAND_MESSAGE_ID_FLAG | apid as u32
+}
+
+/// SBN bridge configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbnConfig {
+    /// Local UDP address of the bridge
+    pub bind_addr: SocketAddr,
+    /// cFE processor ID the bridge presents to its peers
+    pub processor_id: u32,
+    /// cFE spacecraft ID the bridge presents to its peers
+    pub spacecraft_id: u32,
+    /// SBN UDP addresses of the peers; datagrams from other addresses are
+    /// rejected
+    pub peers: Vec<SocketAddr>,
+    /// Period of announcements and heartbeats, milliseconds
+    pub heartbeat_ms: u64,
+    /// Silence after which a peer is considered disconnected, milliseconds
+    pub peer_timeout_ms: u64,
+}
+
+impl Default for SbnConfig {
+    /// Loopback bridge on the first SBN UDP port, with no peers
+    fn default() -> Self {
+        Self {
+            bind_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 2234),
+            processor_id: 0x100,
+            spacecraft_id: 0x42,
+            peers: Vec::new(),
+            heartbeat_ms: 2_000,
+            peer_timeout_ms: 10_000,
+        }
+    }
+}
+
+/// Connection state of one SBN peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbnPeer {
+    /// SBN UDP address of the peer
+    pub addr: SocketAddr,
+    /// Processor ID from the peer's last datagram
+    pub processor_id: Option<u32>,
+    /// Peer heard from within the peer timeout
+    pub connected: bool,
+    /// Time the peer was last heard from, milliseconds
+    pub last_heard_ms: Option<u64>,
+    /// Time a datagram was last sent to the peer, milliseconds
+    last_sent_ms: Option<u64>,
+    /// Message IDs the peer subscribes to
+    pub subscriptions: BTreeSet<u32>,
+    /// Downlinked packets forwarded to the peer
+    pub packets_forwarded: u64,
+    /// Commands received from the peer
+    pub commands_received: u64,
+}
+
+impl SbnPeer {
+    fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            processor_id: None,
+            connected: false,
+            last_heard_ms: None,
+            last_sent_ms: None,
+            subscriptions: BTreeSet::new(),
+            packets_forwarded: 0,
+            commands_received: 0,
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.connected = false;
+        self.subscriptions.clear();
+    }
+}
+
+/// Software Bus Network peer protocol state
+///
+/// - **ID**: FN-SBN-002
+/// - **Requirement**: Exchange packets with cFS peers following the SBN peer
+///   protocol, without custom glue on the cFS side.
+/// - **Inputs**: Datagrams from peers, downlinked packets and the current time.
+/// - **Outputs**: Datagrams to send, and ground commands published by peers.
+/// - **Side Effects**: Peer connection state, subscriptions and counters.
+/// - **Failure Modes**: Datagrams from unconfigured addresses, malformed
+///   datagrams, and application messages that are not valid station commands
+///   are rejected with an error and change no state.
+#[derive(Debug, Clone)]
+pub struct SbnBridge {
+    config: SbnConfig,
+    peers: Vec<SbnPeer>,
+    outbox: Vec<(SocketAddr, Vec<u8>)>,
+}
+
+impl SbnBridge {
+    /// Create a bridge with every configured peer disconnected
+    pub fn new(config: SbnConfig) -> Self {
+        let peers = config.peers.iter().copied().map(SbnPeer::new).collect();
+        Self {
+            config,
+            peers,
+            outbox: Vec::new(),
+        }
+    }
+
+    /// Bridge configuration
+    pub fn config(&self) -> &SbnConfig {
+        &self.config
+    }
+
+    /// State of every configured peer
+    pub fn peers(&self) -> &[SbnPeer] {
+        &self.peers
+    }
+
+    /// Message IDs the bridge subscribes to: the station's command APIDs
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        COMMAND_PRIORITIES
+            .iter()
+            .map(|p| Subscription::new(command_message_id(p.command_apid())))
+            .collect()
+    }
+
+    /// Handle a datagram from `from`
+    ///
+    /// A peer's first datagram connects it and queues the bridge's
+    /// subscriptions to it for the next [`Self::poll`].
+    ///
+    /// # Arguments
+    /// * `from` - Source address of the datagram
+    /// * `bytes` - Datagram as received
+    /// * `now_ms` - Current time, milliseconds
+    ///
+    /// # Returns
+    /// * `Result<Option<Command>>` - Command published by the peer, if the
+    ///   datagram carried one
+    pub fn receive(
+        &mut self,
+        from: SocketAddr,
+        bytes: &[u8],
+        now_ms: u64,
+    ) -> Result<Option<Command>> {
+        let index = self.peers.iter().position(|p| p.addr == from).ok_or(
+            SpaceCommError::invalid_packet("Datagram from unknown SBN peer", None),
+        )?;
+        let frame = SbnFrame::decode(bytes)?;
+
+        let command = match &frame.message {
+            SbnMessage::App(packet) => {
+                let subscribed = message_id(packet)
+                    .is_some_and(|id| self.subscriptions().iter().any(|s| s.message_id == id));
+                if !subscribed {
+                    return Err(SpaceCommError::invalid_packet(
+                        "SBN message ID not subscribed",
+                        message_id(packet),
+                    ));
+                }
+                let fields = decode_command_packet(packet)?;
+                Some(Command::new(
+                    fields.command_id,
+                    fields.priority,
+                    fields.parameters.to_vec(),
+                ))
+            }
+            _ => None,
+        };
+
+        let peer = &mut self.peers[index];
+        peer.processor_id = Some(frame.processor_id);
+        peer.last_heard_ms = Some(now_ms);
+        if frame.message == SbnMessage::Disconnect {
+            peer.disconnect();
+            return Ok(None);
+        }
+        let newly_connected = !peer.connected;
+        peer.connected = true;
+        match frame.message {
+            SbnMessage::Subscribe(subscriptions) => {
+                peer.subscriptions
+                    .extend(subscriptions.iter().map(|s| s.message_id));
+            }
+            SbnMessage::Unsubscribe(subscriptions) => {
+                for subscription in subscriptions {
+                    peer.subscriptions.remove(&subscription.message_id);
+                }
+            }
+            _ => {}
+        }
+        if command.is_some() {
+            peer.commands_received += 1;
+        }
+
+        if newly_connected {
+            let datagram = self.frame(SbnMessage::Subscribe(self.subscriptions()))?;
+            self.outbox.push((from, datagram));
+        }
+        Ok(command)
+    }
+
+    /// Wrap a downlinked packet for every connected peer subscribed to it
+    ///
+    /// # Arguments
+    /// * `packet` - CCSDS space packet as downlinked
+    /// * `now_ms` - Current time, milliseconds
+    ///
+    /// # Returns
+    /// * `Result<Vec<(SocketAddr, Vec<u8>)>>` - Datagram per subscribed peer
+    pub fn publish(&mut self, packet: &[u8], now_ms: u64) -> Result<Vec<(SocketAddr, Vec<u8>)>> {
+        let Some(id) = message_id(packet) else {
+            return Ok(Vec::new());
+        };
+        let mut datagrams = Vec::new();
+        if self
+            .peers
+            .iter()
+            .any(|p| p.connected && p.subscriptions.contains(&id))
+        {
+            let datagram = self.frame(SbnMessage::App(packet.to_vec()))?;
+            for peer in self
+                .peers
+                .iter_mut()
+                .filter(|p| p.connected && p.subscriptions.contains(&id))
+            {
+                peer.last_sent_ms = Some(now_ms);
+                peer.packets_forwarded += 1;
+                datagrams.push((peer.addr, datagram.clone()));
+            }
+        }
+        Ok(datagrams)
+    }
+
+    /// Advance the peer protocol to `now_ms`
+    ///
+    /// Times out silent peers, then returns queued subscriptions followed by
+    /// an announcement to each disconnected peer and a heartbeat to each
+    /// connected one that has had nothing sent for a heartbeat period.
+    ///
+    /// # Arguments
+    /// * `now_ms` - Current time, milliseconds
+    ///
+    /// # Returns
+    /// * `Result<Vec<(SocketAddr, Vec<u8>)>>` - Datagrams to send, in order
+    pub fn poll(&mut self, now_ms: u64) -> Result<Vec<(SocketAddr, Vec<u8>)>> {
+        let announce = self.frame(SbnMessage::Announce)?;
+        let heartbeat = self.frame(SbnMessage::Heartbeat)?;
+        let mut datagrams = std::mem::take(&mut self.outbox);
+        for peer in &mut self.peers {
+            let silent_ms = now_ms.saturating_sub(peer.last_heard_ms.unwrap_or(0));
+            if peer.connected && silent_ms >= self.config.peer_timeout_ms {
+                peer.disconnect();
+            }
+            if datagrams.iter().any(|(addr, _)| *addr == peer.addr) {
+                peer.last_sent_ms = Some(now_ms);
+                continue;
+            }
+            let due = peer
+                .last_sent_ms
+                .is_none_or(|sent| now_ms.saturating_sub(sent) >= self.config.heartbeat_ms);
+            if due {
+                let datagram = if peer.connected {
+                    &heartbeat
+                } else {
+                    &announce
+                };
+                datagrams.push((peer.addr, datagram.clone()));
+                peer.last_sent_ms = Some(now_ms);
+            }
+        }
+        Ok(datagrams)
+    }
+
+    /// Disconnect notices to every connected peer, for a clean shutdown
+    pub fn disconnect_all(&mut self) -> Result<Vec<(SocketAddr, Vec<u8>)>> {
+        let datagram = self.frame(SbnMessage::Disconnect)?;
+        let mut datagrams = Vec::new();
+        for peer in self.peers.iter_mut().filter(|p| p.connected) {
+            peer.disconnect();
+            datagrams.push((peer.addr, datagram.clone()));
+        }
+        Ok(datagrams)
+    }
+
+    fn frame(&self, message: SbnMessage) -> Result<Vec<u8>> {
+        SbnFrame {
+            processor_id: self.config.processor_id,
+            spacecraft_id: self.config.spacecraft_id,
+            message,
+        }
+        .encode()
+    }
+}
+
+/// Format the peer table shown by the console `sbn` command
+pub fn format_sbn_peers(bridge: &SbnBridge, now_ms: u64) -> String {
+    let config = bridge.config();
+    let mut out = format!(
+        "SBN bridge {} as processor {} on spacecraft 0x{:X}\n",
+        config.bind_addr, config.processor_id, config.spacecraft_id
+    );
+    if bridge.peers().is_empty() {
+        out.push_str("  No peers configured\n");
+        return out;
+    }
+    out.push_str("  Peer                   Proc   State          Subs  Fwd      Cmds  Heard\n");
+    for peer in bridge.peers() {
+        let processor = peer
+            .processor_id
+            .map_or("-".to_string(), |id| id.to_string());
+        let heard = peer.last_heard_ms.map_or("never".to_string(), |t| {
+            format!("{:.1}s ago", now_ms.saturating_sub(t) as f64 / 1000.0)
+        });
+        out.push_str(&format!(
+            "  {:<22} {:<6} {:<14} {:<5} {:<8} {:<5} {}\n",
+            peer.addr,
+            processor,
+            if peer.connected {
+                "CONNECTED"
+            } else {
+                "DISCONNECTED"
+            },
+            peer.subscriptions.len(),
+            peer.packets_forwarded,
+            peer.commands_received,
+            heard
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_command_packet;
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket},
+        eps::EPS_APID,
+        types::{BandType, MessageId},
+    };
+
+    const PEER: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 2235);
+
+    fn bridge() -> SbnBridge {
+        SbnBridge::new(SbnConfig {
+            peers: vec![PEER],
+            ..SbnConfig::default()
+        })
+    }
+
+    fn from_peer(message: SbnMessage) -> Vec<u8> {
+        SbnFrame {
+            processor_id: 2,
+            spacecraft_id: 0x42,
+            message,
+        }
+        .encode()
+        .unwrap()
+    }
+
+    fn decoded(datagrams: &[(SocketAddr, Vec<u8>)]) -> Vec<SbnMessage> {
+        datagrams
+            .iter()
+            .map(|(_, d)| SbnFrame::decode(d).unwrap().message)
+            .collect()
+    }
+
+    #[test]
+    fn test_sbn_frame_layout_and_round_trip() {
+        let frame = SbnFrame {
+            processor_id: 0x100,
+            spacecraft_id: 0x42,
+            message: SbnMessage::Subscribe(vec![Subscription::new(0x1802)]),
+        };
+        let bytes = frame.encode().unwrap();
+        assert_eq!(bytes.len(), SBN_HEADER_LEN + SBN_IDENT_LEN + 2 + 6);
+        assert_eq!(&bytes[..11], &[0, 56, 0x01, 0, 0, 1, 0, 0, 0, 0, 0x42]);
+        assert_eq!(&bytes[59..], &[0, 1, 0, 0, 0x18, 0x02, 0, 0]);
+        assert_eq!(SbnFrame::decode(&bytes).unwrap(), frame);
+
+        let heartbeat = from_peer(SbnMessage::Heartbeat);
+        assert_eq!(heartbeat.len(), SBN_HEADER_LEN);
+        assert!(SbnFrame::decode(&heartbeat[..10]).is_err());
+        assert!(SbnFrame::decode(&bytes[..bytes.len() - 1]).is_err());
+        let mut unknown = heartbeat.clone();
+        unknown[2] = 0x04;
+        assert!(SbnFrame::decode(&unknown).is_err());
+
+        let packet = SpacePacket::new(PacketType::Telemetry, EPS_APID, 5, &[1, 2], None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert_eq!(message_id(&packet), Some(u32::from(EPS_APID)));
+        assert_eq!(command_message_id(0x003), 0x1003);
+    }
+
+    #[test]
+    fn test_sbn_peer_protocol() {
+        let mut bridge = bridge();
+        assert_eq!(
+            decoded(&bridge.poll(0).unwrap()),
+            vec![SbnMessage::Announce]
+        );
+        assert!(bridge.poll(1_000).unwrap().is_empty());
+        assert_eq!(
+            decoded(&bridge.poll(2_000).unwrap()),
+            vec![SbnMessage::Announce]
+        );
+
+        // The peer's first datagram connects it and gets our subscriptions
+        let subscribe = SbnMessage::Subscribe(vec![Subscription::new(u32::from(EPS_APID))]);
+        assert!(bridge
+            .receive(PEER, &from_peer(subscribe), 2_500)
+            .unwrap()
+            .is_none());
+        let sent = decoded(&bridge.poll(2_600).unwrap());
+        assert_eq!(sent, vec![SbnMessage::Subscribe(bridge.subscriptions())]);
+        assert_eq!(
+            decoded(&bridge.poll(4_600).unwrap()),
+            vec![SbnMessage::Heartbeat]
+        );
+        let peer = &bridge.peers()[0];
+        assert!(peer.connected);
+        assert_eq!(peer.processor_id, Some(2));
+
+        // Silence beyond the timeout drops the subscriptions
+        bridge.poll(12_500).unwrap();
+        assert!(!bridge.peers()[0].connected);
+        assert!(bridge.peers()[0].subscriptions.is_empty());
+
+        // Datagrams from unconfigured addresses change nothing
+        let stranger = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9999);
+        assert!(bridge
+            .receive(stranger, &from_peer(SbnMessage::Heartbeat), 13_000)
+            .is_err());
+        assert_eq!(bridge.peers()[0].last_heard_ms, Some(2_500));
+    }
+
+    #[test]
+    fn test_sbn_bridges_telemetry_and_commands() {
+        let mut bridge = bridge();
+        let telemetry = SpacePacket::new(PacketType::Telemetry, EPS_APID, 5, &[1, 2], None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(bridge.publish(&telemetry, 0).unwrap().is_empty());
+
+        let subscribe = SbnMessage::Subscribe(vec![Subscription::new(u32::from(EPS_APID))]);
+        bridge.receive(PEER, &from_peer(subscribe), 0).unwrap();
+        let forwarded = bridge.publish(&telemetry, 10).unwrap();
+        assert_eq!(forwarded.len(), 1);
+        assert_eq!(
+            decoded(&forwarded),
+            vec![SbnMessage::App(telemetry.to_vec())]
+        );
+
+        let unsubscribe = SbnMessage::Unsubscribe(vec![Subscription::new(u32::from(EPS_APID))]);
+        bridge.receive(PEER, &from_peer(unsubscribe), 20).unwrap();
+        assert!(bridge.publish(&telemetry, 30).unwrap().is_empty());
+
+        // Commands published by the peer come back as ground commands
+        let command = Command::switch_band(BandType::XBand);
+        let message = command.to_message(MessageId::from_value(9), 0).unwrap();
+        let packet = create_command_packet(&message).unwrap().to_bytes().unwrap();
+        let received = bridge
+            .receive(PEER, &from_peer(SbnMessage::App(packet.to_vec())), 40)
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.command_id, command.command_id);
+        assert_eq!(received.priority, command.priority);
+        assert_eq!(received.parameters, command.parameters);
+        assert_eq!(bridge.peers()[0].commands_received, 1);
+
+        // Telemetry published by the peer is not a command
+        assert!(bridge
+            .receive(PEER, &from_peer(SbnMessage::App(telemetry.to_vec())), 50)
+            .is_err());
+
+        let table = format_sbn_peers(&bridge, 1_050);
+        assert!(table.contains("127.0.0.1:2235"));
+        assert!(table.contains("CONNECTED"));
+        assert!(table.contains("1.0s ago"));
+        assert_eq!(
+            decoded(&bridge.disconnect_all().unwrap()),
+            vec![SbnMessage::Disconnect]
+        );
+    }
+}
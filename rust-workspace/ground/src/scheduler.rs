@@ -0,0 +1,379 @@
+//! Mission clock event scheduler
+//!
+//! Operators schedule timed events such as the start of a maneuver window,
+//! expected acquisition of signal, or a command load uplink deadline. The
+//! scheduler reports countdowns to every pending event, issues reminders at
+//! the configured lead times, and hands back the event's procedure (a list of
+//! ground commands) when it fires so the caller can uplink it.
+//!
+//! The scheduler does not read the clock itself: every query takes the
+//! current mission time in seconds since the Unix epoch, which keeps it
+//! deterministic under test and lets the caller drive it from any timer.
+//!
+//! # Requirements Traceability
+//! - FN-EVT-001: Operator-defined timed events with countdowns
+//! - FN-EVT-002: Reminders at per-event lead times
+//! - FN-EVT-003: Automatic procedure execution when an event fires
+
+use crate::Command;
+
+/// Kind of a scheduled mission event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// Start of a planned maneuver window
+    ManeuverWindow,
+    /// Expected acquisition of signal at the start of a pass
+    ExpectedAos,
+    /// Last moment a command load can be uplinked for its execution window
+    CommandLoadDeadline,
+    /// Any other operator-defined event
+    Other,
+}
+
+impl EventKind {
+    /// Default reminder lead times, largest first, in seconds
+    pub const fn default_reminders_secs(&self) -> &'static [u64] {
+        match self {
+            EventKind::ManeuverWindow => &[600, 60],
+            EventKind::ExpectedAos => &[300, 60],
+            EventKind::CommandLoadDeadline => &[1800, 300],
+            EventKind::Other => &[60],
+        }
+    }
+
+    /// Parse the short operator name used by the mission control console
+    ///
+    /// # Arguments
+    /// * `name` - One of `maneuver`, `aos`, `deadline`, `other`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "maneuver" => Some(EventKind::ManeuverWindow),
+            "aos" => Some(EventKind::ExpectedAos),
+            "deadline" => Some(EventKind::CommandLoadDeadline),
+            "other" => Some(EventKind::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Operator-defined timed event
+#[derive(Debug, Clone)]
+pub struct ScheduledEvent {
+    /// Scheduler-assigned identifier
+    pub id: u32,
+    /// Operator label shown in countdowns and reminders
+    pub name: String,
+    /// Event kind
+    pub kind: EventKind,
+    /// Mission time the event fires, seconds since the Unix epoch
+    pub fire_at_secs: u64,
+    /// Commands uplinked automatically when the event fires
+    pub procedure: Vec<Command>,
+    /// Reminder lead times not yet issued, largest first
+    pending_reminders: Vec<u64>,
+}
+
+/// Time remaining until a pending event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Countdown {
+    /// Event identifier
+    pub id: u32,
+    /// Event label
+    pub name: String,
+    /// Event kind
+    pub kind: EventKind,
+    /// Seconds until the event fires
+    pub remaining_secs: u64,
+    /// Number of commands in the event's procedure
+    pub procedure_len: usize,
+}
+
+/// Notification produced by [`EventScheduler::poll`]
+#[derive(Debug, Clone)]
+pub enum SchedulerNotice {
+    /// An event is within one of its reminder lead times
+    Reminder {
+        /// Event identifier
+        id: u32,
+        /// Event label
+        name: String,
+        /// Event kind
+        kind: EventKind,
+        /// Seconds until the event fires
+        remaining_secs: u64,
+    },
+    /// An event reached its fire time and was removed from the schedule
+    Fired(ScheduledEvent),
+}
+
+/// Mission clock event scheduler
+///
+/// - **ID**: FN-EVT-001
+/// - **Requirement**: Hold operator-defined events ordered by fire time and
+///   report reminders and fired events as mission time advances.
+/// - **Inputs**: Current mission time in seconds on every query.
+/// - **Outputs**: Countdowns, reminders and fired events with their procedures.
+/// - **Side Effects**: [`EventScheduler::poll`] removes fired events and marks
+///   issued reminders.
+/// - **Failure Modes**: Events scheduled in the past fire on the next poll.
+///   Reminders whose lead times have all passed collapse into one reminder.
+#[derive(Debug, Clone, Default)]
+pub struct EventScheduler {
+    /// Pending events ordered by fire time
+    events: Vec<ScheduledEvent>,
+    /// Identifier assigned to the next scheduled event
+    next_id: u32,
+}
+
+impl EventScheduler {
+    /// Create an empty scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule an event with the default reminders for its kind
+    ///
+    /// # Arguments
+    /// * `name` - Operator label
+    /// * `kind` - Event kind
+    /// * `fire_at_secs` - Mission time the event fires
+    ///
+    /// # Returns
+    /// * `u32` - Identifier of the new event
+    pub fn schedule(&mut self, name: &str, kind: EventKind, fire_at_secs: u64) -> u32 {
+        self.schedule_with_reminders(name, kind, fire_at_secs, kind.default_reminders_secs())
+    }
+
+    /// Schedule an event with explicit reminder lead times in seconds
+    ///
+    /// # Arguments
+    /// * `name` - Operator label
+    /// * `kind` - Event kind
+    /// * `fire_at_secs` - Mission time the event fires
+    /// * `reminders_secs` - Lead times at which to remind, in any order
+    ///
+    /// # Returns
+    /// * `u32` - Identifier of the new event
+    pub fn schedule_with_reminders(
+        &mut self,
+        name: &str,
+        kind: EventKind,
+        fire_at_secs: u64,
+        reminders_secs: &[u64],
+    ) -> u32 {
+        self.next_id += 1;
+        let mut pending_reminders = reminders_secs.to_vec();
+        pending_reminders.sort_unstable_by(|a, b| b.cmp(a));
+        pending_reminders.dedup();
+
+        let event = ScheduledEvent {
+            id: self.next_id,
+            name: name.to_string(),
+            kind,
+            fire_at_secs,
+            procedure: Vec::new(),
+            pending_reminders,
+        };
+        let index = self
+            .events
+            .partition_point(|e| e.fire_at_secs <= fire_at_secs);
+        self.events.insert(index, event);
+        self.next_id
+    }
+
+    /// Append a command to an event's procedure
+    ///
+    /// # Returns
+    /// * `bool` - `false` if no pending event has this identifier
+    pub fn add_procedure_step(&mut self, id: u32, command: Command) -> bool {
+        match self.events.iter_mut().find(|e| e.id == id) {
+            Some(event) => {
+                event.procedure.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a pending event
+    ///
+    /// # Returns
+    /// * `Option<ScheduledEvent>` - The cancelled event, if it was pending
+    pub fn cancel(&mut self, id: u32) -> Option<ScheduledEvent> {
+        let index = self.events.iter().position(|e| e.id == id)?;
+        Some(self.events.remove(index))
+    }
+
+    /// Countdowns to every pending event, soonest first
+    pub fn countdowns(&self, now_secs: u64) -> Vec<Countdown> {
+        self.events
+            .iter()
+            .map(|e| Countdown {
+                id: e.id,
+                name: e.name.clone(),
+                kind: e.kind,
+                remaining_secs: e.fire_at_secs.saturating_sub(now_secs),
+                procedure_len: e.procedure.len(),
+            })
+            .collect()
+    }
+
+    /// Advance the mission clock and collect due reminders and fired events
+    ///
+    /// - **ID**: FN-EVT-002 / FN-EVT-003
+    /// - **Requirement**: Issue each reminder once when its lead time is
+    ///   reached, and fire each event once when its time arrives.
+    /// - **Outputs**: Notices in fire-time order. Fired events carry their
+    ///   procedure for the caller to execute.
+    /// - **Side Effects**: Fired events leave the schedule.
+    ///
+    /// # Arguments
+    /// * `now_secs` - Current mission time, seconds since the Unix epoch
+    pub fn poll(&mut self, now_secs: u64) -> Vec<SchedulerNotice> {
+        let mut notices = Vec::new();
+
+        let due = self.events.partition_point(|e| e.fire_at_secs <= now_secs);
+        notices.extend(self.events.drain(..due).map(SchedulerNotice::Fired));
+
+        for event in self.events.iter_mut() {
+            let remaining_secs = event.fire_at_secs - now_secs;
+            let passed = event
+                .pending_reminders
+                .iter()
+                .take_while(|&&lead| remaining_secs <= lead)
+                .count();
+            if passed > 0 {
+                // Only the tightest reminder is reported if several were missed
+                event.pending_reminders.drain(..passed);
+                notices.push(SchedulerNotice::Reminder {
+                    id: event.id,
+                    name: event.name.clone(),
+                    kind: event.kind,
+                    remaining_secs,
+                });
+            }
+        }
+
+        notices
+    }
+
+    /// Number of pending events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether no events are pending
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Format a countdown as `T-HH:MM:SS`, with a day count once past 24 hours
+pub fn format_countdown(remaining_secs: u64) -> String {
+    let days = remaining_secs / 86_400;
+    let hours = (remaining_secs % 86_400) / 3_600;
+    let minutes = (remaining_secs % 3_600) / 60;
+    let seconds = remaining_secs % 60;
+    if days > 0 {
+        format!("T-{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else {
+        format!("T-{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reminders(notices: &[SchedulerNotice]) -> Vec<(u32, u64)> {
+        notices
+            .iter()
+            .filter_map(|n| match n {
+                SchedulerNotice::Reminder {
+                    id, remaining_secs, ..
+                } => Some((*id, *remaining_secs)),
+                SchedulerNotice::Fired(_) => None,
+            })
+            .collect()
+    }
+
+    fn fired(notices: &[SchedulerNotice]) -> Vec<u32> {
+        notices
+            .iter()
+            .filter_map(|n| match n {
+                SchedulerNotice::Fired(event) => Some(event.id),
+                SchedulerNotice::Reminder { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_countdowns_ordered_by_fire_time() {
+        let mut scheduler = EventScheduler::new();
+        let deadline =
+            scheduler.schedule("Load 12 deadline", EventKind::CommandLoadDeadline, 5_000);
+        let aos = scheduler.schedule("AOS Svalbard", EventKind::ExpectedAos, 2_000);
+
+        let countdowns = scheduler.countdowns(1_000);
+        assert_eq!(countdowns.len(), 2);
+        assert_eq!(countdowns[0].id, aos);
+        assert_eq!(countdowns[0].remaining_secs, 1_000);
+        assert_eq!(countdowns[1].id, deadline);
+        assert_eq!(countdowns[1].remaining_secs, 4_000);
+    }
+
+    #[test]
+    fn test_reminders_issued_once_at_lead_times() {
+        let mut scheduler = EventScheduler::new();
+        let id = scheduler.schedule("Burn 3", EventKind::ManeuverWindow, 10_000);
+
+        assert!(scheduler.poll(9_000).is_empty());
+        assert_eq!(reminders(&scheduler.poll(9_400)), vec![(id, 600)]);
+        assert!(scheduler.poll(9_500).is_empty());
+        assert_eq!(reminders(&scheduler.poll(9_950)), vec![(id, 50)]);
+        assert!(scheduler.poll(9_999).is_empty());
+    }
+
+    #[test]
+    fn test_missed_reminders_collapse() {
+        let mut scheduler = EventScheduler::new();
+        let id = scheduler.schedule("AOS", EventKind::ExpectedAos, 1_000);
+
+        // Scheduler started 30 s before AOS: both lead times already passed
+        assert_eq!(reminders(&scheduler.poll(970)), vec![(id, 30)]);
+        assert!(scheduler.poll(980).is_empty());
+    }
+
+    #[test]
+    fn test_fired_event_carries_procedure() {
+        let mut scheduler = EventScheduler::new();
+        let id = scheduler.schedule("AOS", EventKind::ExpectedAos, 1_000);
+        let other = scheduler.schedule("Later", EventKind::Other, 5_000);
+        assert!(scheduler.add_procedure_step(id, Command::system_status_request()));
+        assert!(scheduler.add_procedure_step(id, Command::telemetry_request()));
+        assert!(!scheduler.add_procedure_step(99, Command::telemetry_request()));
+
+        let notices = scheduler.poll(1_000);
+        assert_eq!(fired(&notices), vec![id]);
+        match &notices[0] {
+            SchedulerNotice::Fired(event) => {
+                let ids: Vec<u32> = event.procedure.iter().map(|c| c.command_id).collect();
+                assert_eq!(ids, vec![0x1001, 0x1002]);
+            }
+            SchedulerNotice::Reminder { .. } => panic!("expected fired event"),
+        }
+
+        // Fired events leave the schedule and never fire twice
+        assert_eq!(scheduler.len(), 1);
+        assert!(fired(&scheduler.poll(1_001)).is_empty());
+        assert!(scheduler.cancel(other).is_some());
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(0), "T-00:00:00");
+        assert_eq!(format_countdown(3_725), "T-01:02:05");
+        assert_eq!(format_countdown(90_061), "T-1d 01:01:01");
+    }
+}
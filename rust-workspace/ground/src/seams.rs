@@ -0,0 +1,377 @@
+//! Trait seams over the station's sockets, clock and telemetry history
+//!
+//! [`GroundStation`](crate::GroundStation) reaches the outside world through
+//! three traits: a [`Transport`] for each of its telemetry, command and
+//! emergency sockets, a [`Clock`] for every timestamp it records, and a
+//! [`TelemetryStore`] for the history of parsed telemetry. The production
+//! implementations are the UDP socket, the system clock and an in-memory
+//! rolling store, or the [`TelemetryArchive`](crate::telemetry_archive::TelemetryArchive)
+//! file when telemetry is archived; the mocks let a test feed frames, read back what
+//! was uplinked and step time without a network or a sleep.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-004: Fault Tolerance (receive, parse and store pipeline verified
+//!   against malformed and repeated frames)
+//! - REQ-PF-001: Command Response Time (uplinks observable without a network)
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use space_comms_shared::telemetry::TelemetryPacket;
+
+use crate::telemetry_archive::{StoredTelemetry, TelemetryQuery};
+
+/// Datagram socket the station sends and receives frames on
+pub trait Transport: Send + Sync {
+    /// Send `bytes` to `addr`, returning the number of bytes sent
+    fn send_to(&self, bytes: &[u8], addr: SocketAddr) -> io::Result<usize>;
+
+    /// Receive one datagram into `buffer`, returning its size and sender
+    ///
+    /// Returns `WouldBlock` or `TimedOut` when nothing arrives within the
+    /// transport's read timeout.
+    fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)>;
+
+    /// Address the transport receives on
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+}
+
+impl Transport for UdpSocket {
+    fn send_to(&self, bytes: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, bytes, addr)
+    }
+
+    fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buffer)
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        UdpSocket::local_addr(self)
+    }
+}
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Milliseconds since the Unix epoch
+    fn now_ms(&self) -> u64;
+
+    /// Nanoseconds since the Unix epoch
+    fn now_ns(&self) -> u64 {
+        self.now_ms().saturating_mul(1_000_000)
+    }
+}
+
+/// History of parsed telemetry packets
+pub trait TelemetryStore: Send {
+    /// Add a packet, dropping the oldest from memory if the store is full
+    fn store(&mut self, record: StoredTelemetry);
+
+    /// Packets held in memory, oldest first
+    fn packets(&self) -> Vec<TelemetryPacket>;
+
+    /// Packets selected by `query` from everything the store has kept,
+    /// oldest first
+    fn query(&self, query: &TelemetryQuery) -> Vec<StoredTelemetry>;
+
+    /// Number of packets held in memory
+    fn len(&self) -> usize;
+
+    /// Whether no packet is held
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Wall clock of the host
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    fn now_ns(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64
+    }
+}
+
+/// In-memory history keeping the last `limit` packets
+#[derive(Debug, Clone)]
+pub struct RollingTelemetryStore {
+    records: VecDeque<StoredTelemetry>,
+    limit: usize,
+}
+
+impl RollingTelemetryStore {
+    /// Empty store keeping at most `limit` packets
+    pub fn new(limit: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            limit,
+        }
+    }
+}
+
+impl TelemetryStore for RollingTelemetryStore {
+    fn store(&mut self, record: StoredTelemetry) {
+        self.records.push_back(record);
+        // Prevents unbounded memory growth during long operations
+        while self.records.len() > self.limit {
+            self.records.pop_front();
+        }
+    }
+
+    fn packets(&self) -> Vec<TelemetryPacket> {
+        self.records.iter().map(|r| r.packet.clone()).collect()
+    }
+
+    fn query(&self, query: &TelemetryQuery) -> Vec<StoredTelemetry> {
+        self.records
+            .iter()
+            .filter(|r| query.matches(r))
+            .cloned()
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+}
+
+/// Transport fed and read back by a test
+///
+/// Datagrams queued with [`Self::deliver`] are received in order; an empty
+/// queue reads as a timeout. Sent datagrams are recorded with their
+/// destination, or refused while [`Self::fail_sends`] is set.
+#[derive(Debug)]
+pub struct MockTransport {
+    local_addr: SocketAddr,
+    inbound: Mutex<VecDeque<(Vec<u8>, SocketAddr)>>,
+    sent: Mutex<Vec<(Vec<u8>, SocketAddr)>>,
+    failing: AtomicBool,
+}
+
+impl MockTransport {
+    /// Transport receiving on `local_addr`, with nothing queued
+    pub fn new(local_addr: SocketAddr) -> Self {
+        Self {
+            local_addr,
+            inbound: Mutex::new(VecDeque::new()),
+            sent: Mutex::new(Vec::new()),
+            failing: AtomicBool::new(false),
+        }
+    }
+
+    /// Queue a datagram from `from` to be received
+    pub fn deliver(&self, bytes: &[u8], from: SocketAddr) {
+        self.inbound
+            .lock()
+            .unwrap()
+            .push_back((bytes.to_vec(), from));
+    }
+
+    /// Datagrams sent so far with their destinations, oldest first
+    pub fn sent(&self) -> Vec<(Vec<u8>, SocketAddr)> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Refuse every send while `failing` is set
+    pub fn fail_sends(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+}
+
+impl Transport for MockTransport {
+    fn send_to(&self, bytes: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                "mock send failure",
+            ));
+        }
+        self.sent.lock().unwrap().push((bytes.to_vec(), addr));
+        Ok(bytes.len())
+    }
+
+    fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        let (bytes, from) = self
+            .inbound
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::WouldBlock))?;
+        // Like a datagram socket, a short buffer truncates the datagram
+        let size = bytes.len().min(buffer.len());
+        buffer[..size].copy_from_slice(&bytes[..size]);
+        Ok((size, from))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// Clock that only moves when a test moves it
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now_ms: AtomicU64,
+}
+
+impl MockClock {
+    /// Clock reading `now_ms` milliseconds since the Unix epoch
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            now_ms: AtomicU64::new(now_ms),
+        }
+    }
+
+    /// Move the clock forward by `ms` milliseconds
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::SeqCst);
+    }
+
+    /// Set the clock to `now_ms` milliseconds since the Unix epoch
+    pub fn set(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+/// Unbounded store whose packets a test can read through any clone
+///
+/// Clones share their packets, so a test keeps one clone and hands another
+/// to the station.
+#[derive(Debug, Clone, Default)]
+pub struct MockTelemetryStore {
+    records: Arc<Mutex<Vec<StoredTelemetry>>>,
+}
+
+impl MockTelemetryStore {
+    /// Empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TelemetryStore for MockTelemetryStore {
+    fn store(&mut self, record: StoredTelemetry) {
+        self.records.lock().unwrap().push(record);
+    }
+
+    fn packets(&self) -> Vec<TelemetryPacket> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.packet.clone())
+            .collect()
+    }
+
+    fn query(&self, query: &TelemetryQuery) -> Vec<StoredTelemetry> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| query.matches(r))
+            .cloned()
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        telemetry::TelemetryData,
+        types::{BandType, ComponentId, HealthStatus},
+    };
+
+    fn record(sequence: u32) -> StoredTelemetry {
+        let data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: u64::from(sequence),
+            measurements: Default::default(),
+            health_status: HealthStatus::Good,
+        };
+        StoredTelemetry {
+            received_ms: u64::from(sequence),
+            apid: 0x100,
+            frame: Vec::new(),
+            packet: TelemetryPacket::new(sequence, data, BandType::SBand),
+        }
+    }
+
+    #[test]
+    fn test_rolling_store_keeps_the_newest_packets() {
+        let mut store = RollingTelemetryStore::new(3);
+        assert!(store.is_empty());
+        for sequence in 1..=5 {
+            store.store(record(sequence));
+        }
+        let sequences: Vec<u32> = store.packets().iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![3, 4, 5]);
+        assert_eq!(store.len(), 3);
+
+        let since_4 = TelemetryQuery {
+            from_ms: Some(4),
+            ..TelemetryQuery::default()
+        };
+        assert_eq!(store.query(&since_4).len(), 2);
+    }
+
+    #[test]
+    fn test_mock_store_is_shared_between_clones() {
+        let store = MockTelemetryStore::new();
+        let mut handed_out = store.clone();
+        handed_out.store(record(1));
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.packets()[0].sequence, 1);
+    }
+
+    #[test]
+    fn test_mock_transport_and_clock() {
+        let local: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let peer: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let transport = MockTransport::new(local);
+        let mut buffer = [0u8; 4];
+
+        let empty = transport.recv_from(&mut buffer).unwrap_err();
+        assert_eq!(empty.kind(), io::ErrorKind::WouldBlock);
+        transport.deliver(&[1, 2, 3, 4, 5], peer);
+        assert_eq!(transport.recv_from(&mut buffer).unwrap(), (4, peer));
+        assert_eq!(buffer, [1, 2, 3, 4]);
+
+        assert_eq!(transport.send_to(&[9], peer).unwrap(), 1);
+        transport.fail_sends(true);
+        assert!(transport.send_to(&[8], peer).is_err());
+        assert_eq!(transport.sent(), vec![(vec![9], peer)]);
+        assert_eq!(transport.local_addr().unwrap(), local);
+
+        let clock = MockClock::new(1_000);
+        clock.advance(500);
+        assert_eq!((clock.now_ms(), clock.now_ns()), (1_500, 1_500_000_000));
+        clock.set(10);
+        assert_eq!(clock.now_ms(), 10);
+        assert!(SystemClock.now_ms() > 1_600_000_000_000);
+    }
+}
@@ -0,0 +1,729 @@
+//! Long-duration soak testing
+//!
+//! The priority stress tests run for seconds; leaks in the ground segment
+//! only show over hours of contact. [`run_soak`] runs a [`GroundStation`]
+//! against a simulated satellite on the loopback interface at a configurable
+//! command and telemetry rate, sampling the resources that could grow without
+//! bound: process memory, the telemetry history and the backlog of commands
+//! awaiting verification, the verification, audit and pass archives, and the
+//! health of every worker thread. [`evaluate`] then decides whether any of
+//! them grew unbounded.
+//!
+//! Archives grow by design, one record per event, so they are checked against
+//! the traffic that fed them rather than for a plateau. Memory may grow with
+//! the archives; growth beyond that is a leak. Queues must stay within their
+//! limits and must not trend upwards once the run has warmed up.
+//!
+//! # Requirements Traceability
+//! - FN-SOAK-001: Resources sampled throughout a long-duration run
+//! - FN-SOAK-002: Run fails on unbounded resource growth or a stopped thread
+//! - FN-SOAK-003: Stand-in satellite answering commands and downlinking housekeeping
+
+use std::fmt;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use space_comms_shared::{
+    ccsds::{PacketType, SpacePacket, TcTransferFrame},
+    cop1::{Farm, FarmVerdict},
+    execution_report::{ExecutionReport, ExecutionResult},
+    messaging::decode_command_packet,
+    telemetry::{
+        measurement_ids, Measurement, MeasurementQuality, MeasurementValue, TelemetryData,
+    },
+    types::HealthStatus,
+    Result, SpaceCommError,
+};
+
+use crate::{
+    Command, GroundStation, GroundStationConfig, SATELLITE_COMMAND_ADDR, SATELLITE_COMPONENT,
+    TELEMETRY_HISTORY_LIMIT,
+};
+
+/// Telemetry APID of the simulated satellite's housekeeping frames
+const SOAK_TELEMETRY_APID: u16 = 0x100;
+
+/// Soak run length, load and leak tolerances
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakConfig {
+    /// Length of the run
+    pub duration: Duration,
+    /// Commands uplinked per second
+    pub command_rate_hz: f64,
+    /// Telemetry frames downlinked per second
+    pub telemetry_rate_hz: f64,
+    /// Time between resource samples
+    pub sample_interval: Duration,
+    /// Start-up period excluded from growth trends
+    pub warm_up: Duration,
+    /// Memory growth allowed regardless of archive growth, bytes
+    pub memory_allowance_bytes: u64,
+    /// Memory growth allowed per archived record, bytes
+    pub bytes_per_record: u64,
+    /// Growth allowed in the backlog of commands awaiting verification
+    pub backlog_allowance: u64,
+}
+
+impl Default for SoakConfig {
+    /// One hour at 10 commands and 10 telemetry frames per second, sampled
+    /// every 10 seconds after a 5 minute warm-up
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(3_600),
+            command_rate_hz: 10.0,
+            telemetry_rate_hz: 10.0,
+            sample_interval: Duration::from_secs(10),
+            warm_up: Duration::from_secs(300),
+            memory_allowance_bytes: 16 * 1024 * 1024,
+            bytes_per_record: 1_024,
+            backlog_allowance: 100,
+        }
+    }
+}
+
+/// Sizes of the ground station's growing state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StationResources {
+    /// Telemetry packets held in the rolling history
+    pub telemetry_history: usize,
+    /// Execution reports in the verification archive
+    pub verification_records: usize,
+    /// Records in the command audit log
+    pub audit_records: usize,
+    /// Completed pass reports
+    pub pass_reports: usize,
+}
+
+impl StationResources {
+    /// Records held across all archives
+    pub fn archived_records(&self) -> usize {
+        self.verification_records + self.audit_records + self.pass_reports
+    }
+}
+
+/// Resources at one point of a soak run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceSample {
+    /// Time since the run started, milliseconds
+    pub elapsed_ms: u64,
+    /// Resident memory of the process, if the platform reports it
+    pub rss_bytes: Option<u64>,
+    /// Ground station state sizes
+    pub station: StationResources,
+    /// Commands uplinked so far
+    pub commands_sent: u64,
+    /// Execution reports the satellite has downlinked so far
+    pub reports_sent: u64,
+    /// Each worker thread and whether it is still running
+    pub threads: Vec<(&'static str, bool)>,
+}
+
+impl ResourceSample {
+    /// Commands uplinked but not yet verified by an execution report
+    pub fn backlog(&self) -> u64 {
+        self.commands_sent
+            .saturating_sub(self.station.verification_records as u64)
+    }
+}
+
+/// Reason a soak run failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoakFailure {
+    /// A worker thread exited
+    ThreadStopped {
+        /// Thread name
+        thread: &'static str,
+        /// Sample time it was first seen stopped, milliseconds
+        elapsed_ms: u64,
+    },
+    /// A bounded queue exceeded its limit
+    QueueOverflow {
+        /// Queue name
+        queue: &'static str,
+        /// Depth seen
+        depth: usize,
+        /// Depth limit
+        limit: usize,
+    },
+    /// A queue kept growing after warm-up
+    QueueGrowth {
+        /// Queue name
+        queue: &'static str,
+        /// Growth of the mean depth from the first to the last quarter
+        growth: f64,
+        /// Growth allowed
+        allowance: f64,
+    },
+    /// An archive holds more records than events fed it
+    ArchiveGrowth {
+        /// Archive name
+        archive: &'static str,
+        /// Records held
+        records: usize,
+        /// Events that should have produced them
+        events: u64,
+    },
+    /// Memory grew more than the archives account for
+    MemoryGrowth {
+        /// Growth of the mean RSS from the first to the last quarter, bytes
+        growth_bytes: f64,
+        /// Growth allowed, bytes
+        allowance_bytes: f64,
+    },
+}
+
+impl fmt::Display for SoakFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoakFailure::ThreadStopped { thread, elapsed_ms } => {
+                write!(f, "{} thread stopped by {} ms", thread, elapsed_ms)
+            }
+            SoakFailure::QueueOverflow {
+                queue,
+                depth,
+                limit,
+            } => write!(f, "{} depth {} exceeds limit {}", queue, depth, limit),
+            SoakFailure::QueueGrowth {
+                queue,
+                growth,
+                allowance,
+            } => write!(
+                f,
+                "{} grew by {:.1} after warm-up (allowed {:.1})",
+                queue, growth, allowance
+            ),
+            SoakFailure::ArchiveGrowth {
+                archive,
+                records,
+                events,
+            } => write!(
+                f,
+                "{} archive holds {} records for {} events",
+                archive, records, events
+            ),
+            SoakFailure::MemoryGrowth {
+                growth_bytes,
+                allowance_bytes,
+            } => write!(
+                f,
+                "memory grew by {:.0} bytes after warm-up (allowed {:.0})",
+                growth_bytes, allowance_bytes
+            ),
+        }
+    }
+}
+
+/// Samples and verdict of a soak run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakReport {
+    /// Configuration the run used
+    pub config: SoakConfig,
+    /// Resource samples in time order
+    pub samples: Vec<ResourceSample>,
+    /// Reasons the run failed; empty if it passed
+    pub failures: Vec<SoakFailure>,
+}
+
+impl SoakReport {
+    /// Whether no resource grew unbounded and every thread kept running
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Soak run: {} samples over {} s",
+            self.samples.len(),
+            self.samples.last().map_or(0, |s| s.elapsed_ms / 1000)
+        )?;
+        if let (Some(first), Some(last)) = (self.samples.first(), self.samples.last()) {
+            let rss = |sample: &ResourceSample| {
+                sample
+                    .rss_bytes
+                    .map_or("n/a".to_string(), |bytes| format!("{} KiB", bytes / 1024))
+            };
+            writeln!(
+                f,
+                "  commands {} / reports {}, backlog {}, telemetry history {}",
+                last.commands_sent,
+                last.reports_sent,
+                last.backlog(),
+                last.station.telemetry_history
+            )?;
+            writeln!(
+                f,
+                "  archived records {} -> {}, memory {} -> {}",
+                first.station.archived_records(),
+                last.station.archived_records(),
+                rss(first),
+                rss(last)
+            )?;
+        }
+        if self.passed() {
+            return write!(f, "PASSED");
+        }
+        for failure in &self.failures {
+            writeln!(f, "  {}", failure)?;
+        }
+        write!(f, "FAILED")
+    }
+}
+
+/// Decide whether a soak run leaked
+///
+/// - **ID**: FN-SOAK-002
+/// - **Requirement**: Fail a soak run whose resources grow unbounded or
+///   whose worker threads stop.
+/// - **Inputs**:
+///   - `config`: Warm-up and growth allowances.
+///   - `samples`: Resource samples in time order.
+/// - **Outputs**: One failure per stopped thread, overflowed queue,
+///   over-full archive and unbounded growth trend; empty if the run passed.
+///   Trends need at least eight samples after warm-up and are otherwise not
+///   judged.
+/// - **Side Effects**: None.
+pub fn evaluate(config: &SoakConfig, samples: &[ResourceSample]) -> Vec<SoakFailure> {
+    let mut failures = Vec::new();
+
+    for sample in samples {
+        for &(thread, alive) in &sample.threads {
+            let reported = failures
+                .iter()
+                .any(|f| matches!(f, SoakFailure::ThreadStopped { thread: t, .. } if *t == thread));
+            if !alive && !reported {
+                failures.push(SoakFailure::ThreadStopped {
+                    thread,
+                    elapsed_ms: sample.elapsed_ms,
+                });
+            }
+        }
+    }
+
+    if let Some(deepest) = samples.iter().map(|s| s.station.telemetry_history).max() {
+        if deepest > TELEMETRY_HISTORY_LIMIT {
+            failures.push(SoakFailure::QueueOverflow {
+                queue: "telemetry history",
+                depth: deepest,
+                limit: TELEMETRY_HISTORY_LIMIT,
+            });
+        }
+    }
+
+    if let Some(last) = samples.last() {
+        // One report per command executed; one audit record per command sent
+        // and one per report received
+        let archives = [
+            (
+                "verification",
+                last.station.verification_records,
+                last.reports_sent,
+            ),
+            (
+                "audit",
+                last.station.audit_records,
+                last.commands_sent + last.reports_sent,
+            ),
+        ];
+        for (archive, records, events) in archives {
+            if records as u64 > events {
+                failures.push(SoakFailure::ArchiveGrowth {
+                    archive,
+                    records,
+                    events,
+                });
+            }
+        }
+    }
+
+    let warm_up_ms = config.warm_up.as_millis() as u64;
+    let steady: Vec<&ResourceSample> = samples
+        .iter()
+        .filter(|sample| sample.elapsed_ms >= warm_up_ms)
+        .collect();
+    if steady.len() < 8 {
+        return failures;
+    }
+
+    let backlog_growth = quarter_growth(&steady, |s| Some(s.backlog() as f64));
+    if let Some(growth) = backlog_growth.filter(|g| *g > config.backlog_allowance as f64) {
+        failures.push(SoakFailure::QueueGrowth {
+            queue: "unverified commands",
+            growth,
+            allowance: config.backlog_allowance as f64,
+        });
+    }
+
+    let record_growth =
+        quarter_growth(&steady, |s| Some(s.station.archived_records() as f64)).unwrap_or(0.0);
+    let allowance_bytes = config.memory_allowance_bytes as f64
+        + record_growth.max(0.0) * config.bytes_per_record as f64;
+    let memory_growth = quarter_growth(&steady, |s| s.rss_bytes.map(|b| b as f64));
+    if let Some(growth_bytes) = memory_growth.filter(|g| *g > allowance_bytes) {
+        failures.push(SoakFailure::MemoryGrowth {
+            growth_bytes,
+            allowance_bytes,
+        });
+    }
+
+    failures
+}
+
+/// Mean of `metric` over the last quarter of `samples` less its mean over the
+/// first quarter, or `None` if any sample lacks the metric
+fn quarter_growth(
+    samples: &[&ResourceSample],
+    metric: impl Fn(&ResourceSample) -> Option<f64>,
+) -> Option<f64> {
+    let quarter = (samples.len() / 4).max(1);
+    let mean = |window: &[&ResourceSample]| -> Option<f64> {
+        let values = window
+            .iter()
+            .map(|s| metric(s))
+            .collect::<Option<Vec<f64>>>()?;
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    };
+    Some(mean(&samples[samples.len() - quarter..])? - mean(&samples[..quarter])?)
+}
+
+/// Resident memory of this process, bytes
+///
+/// Read from `/proc/self/status`; `None` where procfs is unavailable.
+pub fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Run a ground station against a simulated satellite and watch for leaks
+///
+/// - **ID**: FN-SOAK-001
+/// - **Requirement**: Exercise the ground station for a configurable time at
+///   a configurable load, sampling memory, queue depths, archive sizes and
+///   thread health throughout.
+/// - **Inputs**:
+///   - `config`: Run length, load, sampling and allowances.
+///   - `station`: Ground station configuration; use port 0 for the ground
+///     station sockets to avoid clashing with a running console.
+/// - **Outputs**: Samples and the [`evaluate`] verdict.
+/// - **Side Effects**: Binds the satellite command address
+///   [`SATELLITE_COMMAND_ADDR`] for the simulated satellite and starts the
+///   ground station worker threads, which keep running after the call.
+/// - **Failure Modes**: Ground station start-up failure, or the satellite
+///   command address already in use → `Err(CommunicationTimeout)`.
+pub fn run_soak(config: &SoakConfig, station: GroundStationConfig) -> Result<SoakReport> {
+    let station = GroundStation::new(station)?;
+    station.start()?;
+    let telemetry_addr = station.telemetry_addr()?;
+
+    let socket = UdpSocket::bind(SATELLITE_COMMAND_ADDR)
+        .and_then(|socket| {
+            socket.set_read_timeout(Some(Duration::from_millis(10)))?;
+            Ok(socket)
+        })
+        .map_err(|_| SpaceCommError::communication_timeout(1000, "simulated satellite bind"))?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let reports_sent = Arc::new(AtomicU64::new(0));
+    let telemetry_period = Duration::from_secs_f64(1.0 / config.telemetry_rate_hz.max(0.001));
+    let satellite = {
+        let stop = Arc::clone(&stop);
+        let reports_sent = Arc::clone(&reports_sent);
+        thread::spawn(move || {
+            simulate_satellite(
+                &socket,
+                telemetry_addr,
+                telemetry_period,
+                &stop,
+                &reports_sent,
+            )
+        })
+    };
+
+    let started = Instant::now();
+    let command_period = Duration::from_secs_f64(1.0 / config.command_rate_hz.max(0.001));
+    let mut next_command = started;
+    let mut next_sample = started;
+    let mut commands_sent = 0u64;
+    let mut samples = Vec::new();
+
+    loop {
+        let now = Instant::now();
+        let finished = now.duration_since(started) >= config.duration;
+
+        if now >= next_sample || finished {
+            let mut threads = station.thread_health();
+            threads.push(("satellite simulator", !satellite.is_finished()));
+            samples.push(ResourceSample {
+                elapsed_ms: now.duration_since(started).as_millis() as u64,
+                rss_bytes: resident_memory_bytes(),
+                station: station.resources(),
+                commands_sent,
+                reports_sent: reports_sent.load(Ordering::Relaxed),
+                threads,
+            });
+            next_sample += config.sample_interval;
+        }
+        if finished {
+            break;
+        }
+
+        if now >= next_command {
+            let command = if commands_sent.is_multiple_of(2) {
+                Command::system_status_request()
+            } else {
+                Command::telemetry_request()
+            };
+            if station.send_command(command).is_ok() {
+                commands_sent += 1;
+            }
+            next_command += command_period;
+        }
+
+        let wake = next_command.min(next_sample);
+        thread::sleep(wake.saturating_duration_since(Instant::now()));
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    let _ = satellite.join();
+
+    let failures = evaluate(config, &samples);
+    Ok(SoakReport {
+        config: *config,
+        samples,
+        failures,
+    })
+}
+
+/// Satellite side of a soak run, also run on its own as a stand-in satellite
+///
+/// - **ID**: FN-SOAK-003
+/// - **Requirement**: Give the ground station a satellite to talk to without
+///   flight hardware.
+/// - **Inputs**: `socket` bound to the satellite command address and ready
+///   with a short read timeout, the ground station's `telemetry_addr`, and
+///   the housekeeping period.
+/// - **Side Effects**: Accepts command frames through COP-1 FARM-1 and
+///   answers each frame with a CLCW, answers every accepted command with a
+///   completed execution report, counted in `reports_sent`, and downlinks a
+///   housekeeping frame every `telemetry_period` until `stop` is set.
+pub fn simulate_satellite(
+    socket: &UdpSocket,
+    telemetry_addr: SocketAddr,
+    telemetry_period: Duration,
+    stop: &AtomicBool,
+    reports_sent: &AtomicU64,
+) {
+    let mut buffer = [0u8; 4096];
+    let mut next_telemetry = Instant::now();
+    let mut sequence = 0u16;
+    let mut farm = Farm::default();
+    let mut clcw_sequence = 0u16;
+
+    while !stop.load(Ordering::Relaxed) {
+        let received = match socket.recv_from(&mut buffer) {
+            Ok((size, _)) => TcTransferFrame::from_bytes(&buffer[..size]).ok(),
+            Err(_) => None,
+        };
+        if let Some(frame) = received {
+            let verdict = farm.receive(&frame, true);
+            clcw_sequence = clcw_sequence.wrapping_add(1);
+            if let Ok(bytes) = farm
+                .clcw()
+                .to_packet(clcw_sequence)
+                .and_then(|packet| packet.to_bytes())
+            {
+                let _ = socket.send_to(&bytes, telemetry_addr);
+            }
+
+            let command = match verdict {
+                FarmVerdict::Accept => decode_command_packet(&frame.data).ok(),
+                FarmVerdict::Discard | FarmVerdict::Control => None,
+            };
+            if let Some(fields) = command {
+                let report = ExecutionReport::new(
+                    fields.command_id,
+                    fields.sequence_count,
+                    fields.priority,
+                    ExecutionResult::Completed,
+                    100,
+                );
+                let sent = report
+                    .to_packet(fields.sequence_count)
+                    .and_then(|packet| packet.to_bytes())
+                    .map(|bytes| socket.send_to(&bytes, telemetry_addr).is_ok());
+                if sent == Ok(true) {
+                    reports_sent.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        if Instant::now() >= next_telemetry {
+            sequence = (sequence + 1) & 0x3FFF;
+            if let Ok(bytes) = housekeeping_frame(sequence).and_then(|packet| packet.to_bytes()) {
+                let _ = socket.send_to(&bytes, telemetry_addr);
+            }
+            next_telemetry += telemetry_period;
+        }
+    }
+}
+
+/// Encode a nominal housekeeping frame
+fn housekeeping_frame(sequence: u16) -> Result<SpacePacket> {
+    let mut data = TelemetryData {
+        source: SATELLITE_COMPONENT,
+        timestamp: u64::from(sequence),
+        measurements: Default::default(),
+        health_status: HealthStatus::Good,
+    };
+    let _ = data.measurements.push(Measurement {
+        measurement_id: measurement_ids::BATTERY_VOLTAGE,
+        value: MeasurementValue::Float(28.0),
+        unit: "V",
+        quality: MeasurementQuality::Good,
+    });
+    let payload = data.to_payload()?;
+    SpacePacket::new(
+        PacketType::Telemetry,
+        SOAK_TELEMETRY_APID,
+        sequence,
+        &payload,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples once a second with every thread running
+    fn samples(seconds: u64, sample: impl Fn(u64) -> ResourceSample) -> Vec<ResourceSample> {
+        (0..seconds).map(sample).collect()
+    }
+
+    fn steady(t: u64) -> ResourceSample {
+        ResourceSample {
+            elapsed_ms: t * 1000,
+            rss_bytes: Some(50 << 20),
+            station: StationResources {
+                telemetry_history: 1000,
+                verification_records: (t * 10) as usize,
+                audit_records: (t * 20) as usize,
+                pass_reports: 0,
+            },
+            commands_sent: t * 10 + 2,
+            reports_sent: t * 10,
+            threads: vec![("telemetry receiver", true), ("monitor", true)],
+        }
+    }
+
+    fn config() -> SoakConfig {
+        SoakConfig {
+            warm_up: Duration::from_secs(10),
+            ..SoakConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_steady_run_passes() {
+        let mut run = samples(100, steady);
+        // Memory growing with the archives is not a leak
+        for sample in &mut run {
+            sample.rss_bytes = Some((50 << 20) + sample.elapsed_ms * 30);
+        }
+        assert_eq!(evaluate(&config(), &run), vec![]);
+    }
+
+    #[test]
+    fn test_leaks_detected() {
+        let run = samples(100, |t| ResourceSample {
+            rss_bytes: Some((50 << 20) + t * (1 << 20)),
+            commands_sent: t * 20,
+            ..steady(t)
+        });
+        let failures = evaluate(&config(), &run);
+        assert!(matches!(
+            failures.as_slice(),
+            [
+                SoakFailure::QueueGrowth {
+                    queue: "unverified commands",
+                    ..
+                },
+                SoakFailure::MemoryGrowth { .. }
+            ]
+        ));
+
+        // Too few samples after warm-up to judge a trend
+        assert_eq!(evaluate(&config(), &run[..15]), vec![]);
+    }
+
+    #[test]
+    fn test_stopped_thread_and_overfull_state_detected() {
+        let mut run = samples(20, steady);
+        run[12].threads[1].1 = false;
+        run[13].threads[1].1 = false;
+        run[5].station.telemetry_history = TELEMETRY_HISTORY_LIMIT + 1;
+        run[19].station.verification_records = 1_000;
+
+        let failures = evaluate(&config(), &run);
+        assert_eq!(
+            failures,
+            vec![
+                SoakFailure::ThreadStopped {
+                    thread: "monitor",
+                    elapsed_ms: 12_000
+                },
+                SoakFailure::QueueOverflow {
+                    queue: "telemetry history",
+                    depth: TELEMETRY_HISTORY_LIMIT + 1,
+                    limit: TELEMETRY_HISTORY_LIMIT
+                },
+                SoakFailure::ArchiveGrowth {
+                    archive: "verification",
+                    records: 1_000,
+                    events: 190
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_soak_run() {
+        let config = SoakConfig {
+            duration: Duration::from_secs(2),
+            command_rate_hz: 20.0,
+            telemetry_rate_hz: 20.0,
+            sample_interval: Duration::from_millis(200),
+            warm_up: Duration::ZERO,
+            ..SoakConfig::default()
+        };
+        let report = run_soak(
+            &config,
+            GroundStationConfig {
+                telemetry_port: 0,
+                command_port: 0,
+                emergency_port: 0,
+                ..GroundStationConfig::default()
+            },
+        )
+        .unwrap();
+
+        assert!(report.passed(), "{}", report);
+        let last = report.samples.last().unwrap();
+        assert!(last.commands_sent >= 30);
+        assert!(last.station.verification_records > 0);
+        assert!(last.station.telemetry_history > 0);
+        assert!(last.threads.iter().all(|(_, alive)| *alive));
+    }
+}
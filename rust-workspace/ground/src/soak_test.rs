@@ -0,0 +1,88 @@
+//! Ground Station Soak Test
+//!
+//! Runs the ground station against a simulated satellite for hours at a
+//! configurable load and exits non-zero if memory, queues or archives grow
+//! unbounded or a worker thread stops. The simulated satellite binds the
+//! satellite command port, so do not run this alongside the satellite.
+//!
+//! ```text
+//! soak-test --hours 8 --command-rate 20 --telemetry-rate 10
+//! ```
+//!
+//! # Requirements Traceability
+//! - FN-SOAK-001: Resources sampled throughout a long-duration run
+//! - FN-SOAK-002: Run fails on unbounded resource growth or a stopped thread
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+use space_comms_ground::{
+    soak::{run_soak, SoakConfig},
+    GroundStationConfig,
+};
+
+/// Soak test options
+#[derive(Debug, Parser)]
+#[command(name = "soak-test", about = "Long-duration ground station soak test")]
+struct Options {
+    /// Length of the run in hours
+    #[arg(long, default_value_t = 1.0)]
+    hours: f64,
+
+    /// Commands uplinked per second
+    #[arg(long, default_value_t = 10.0)]
+    command_rate: f64,
+
+    /// Telemetry frames downlinked per second
+    #[arg(long, default_value_t = 10.0)]
+    telemetry_rate: f64,
+
+    /// Seconds between resource samples
+    #[arg(long, default_value_t = 10)]
+    sample_secs: u64,
+
+    /// Seconds of start-up excluded from growth trends
+    #[arg(long, default_value_t = 300)]
+    warm_up_secs: u64,
+
+    /// Memory growth allowed beyond archive growth, MiB
+    #[arg(long, default_value_t = 16)]
+    memory_allowance_mib: u64,
+}
+
+fn main() -> ExitCode {
+    let options = Options::parse();
+    let config = SoakConfig {
+        duration: Duration::from_secs_f64(options.hours * 3_600.0),
+        command_rate_hz: options.command_rate,
+        telemetry_rate_hz: options.telemetry_rate,
+        sample_interval: Duration::from_secs(options.sample_secs.max(1)),
+        warm_up: Duration::from_secs(options.warm_up_secs),
+        memory_allowance_bytes: options.memory_allowance_mib * 1024 * 1024,
+        ..SoakConfig::default()
+    };
+
+    // Ground sockets on ephemeral ports, clear of a running console
+    let station = GroundStationConfig {
+        telemetry_port: 0,
+        command_port: 0,
+        emergency_port: 0,
+        ..GroundStationConfig::default()
+    };
+
+    match run_soak(&config, station) {
+        Ok(report) => {
+            println!("{}", report);
+            if report.passed() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("Soak test could not start: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,1289 @@
+//! Ground station configuration, state and worker threads
+//!
+//! [`GroundStation`] owns the station's seams, link configuration and every
+//! archive and tracker the downlink feeds. This module builds it and runs
+//! its worker threads; the command uplink lives in `uplink` and the downlink
+//! pipeline in `receiver`.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-001: Command Response Time (socket timeout configuration)
+//! - REQ-NF-003: System Availability (worker thread health)
+//! - REQ-NF-004: Fault Tolerance (error handling for socket creation)
+//! - REQ-FN-007: Multi-Band Communication (link bands validated at startup)
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, UdpSocket};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use space_comms_req::req;
+use space_comms_shared::{
+    cop1::{Cop1Config, Fop},
+    event_log::CompressionStats,
+    fec::FecPolicy,
+    file_downlink::FileManifest,
+    frequency_plan::FrequencyPlan,
+    link_config::{DirectionalLink, LinkConfiguration, LinkDirection},
+    link_forecast::DownlinkPolicyStats,
+    link_stats::LinkStats,
+    margin::MarginPolicy,
+    retry::RetryPolicy,
+    telemetry::{Measurement, TelemetryPacket},
+    types::BandType,
+    Result, SpaceCommError,
+};
+
+use crate::audit::AuditLog;
+use crate::diagnostics::DiagnosticsArchive;
+use crate::link_security::LinkSecurity;
+use crate::loopback::LoopbackTracker;
+use crate::mirror::{MirrorEndpoint, TelemetryMirror};
+use crate::pass_report::{PassArchive, PassReport, PassTracker};
+use crate::power_trend::PowerTrend;
+use crate::redundancy::{RedundancyConfig, RedundancyLink};
+use crate::sbn::{SbnBridge, SbnConfig};
+use crate::seams::{Clock, RollingTelemetryStore, SystemClock, TelemetryStore, Transport};
+use crate::soak::StationResources;
+use crate::telemetry_archive::{StoredTelemetry, TelemetryArchive, TelemetryQuery};
+use crate::verification::{LatencyPercentiles, VerificationArchive};
+use crate::volume_budget::{Modcod, PassBudget, PassGeometry, VolumeStatus, VolumeTracker};
+use crate::yamcs::YamcsConfig;
+use crate::{
+    display::display_stored_telemetry, link_security, telemetry_tracker::TelemetryTracker,
+    DownlinkSequences, TELEMETRY_HISTORY_LIMIT,
+};
+
+mod bridges;
+mod receiver;
+mod uplink;
+
+pub use receiver::TelemetryReceiver;
+
+/// Ground station configuration
+///
+/// Contains all necessary parameters for ground station operation including
+/// location, supported communication bands, and network configuration.
+/// REQ-FN-007: Multi-Band Communication - Defines supported frequency bands
+#[derive(Debug, Clone)]
+pub struct GroundStationConfig {
+    /// Station identifier for tracking and logging purposes
+    pub station_id: String,
+
+    /// Station location as (latitude, longitude, altitude in meters)
+    /// Used for orbital mechanics calculations and link budget analysis
+    pub location: (f64, f64, f64),
+
+    /// Supported communication bands for satellite communication
+    /// REQ-FN-007: Multi-Band Communication - Five frequency band support
+    pub supported_bands: Vec<BandType>,
+
+    /// Uplink and downlink band, power level and data rate
+    /// REQ-FN-007: Multi-Band Communication - Each direction on its own band
+    pub links: LinkConfiguration,
+
+    /// Licensed frequency assignments `ReconfigureComm` commands must tune to
+    /// FN-FRQ-001: Out-of-band and unlicensed tuning refused before uplink
+    pub frequency_plan: FrequencyPlan,
+
+    /// Bands whose frames are Reed-Solomon (255,223) coded, matching the
+    /// satellite's policy
+    /// FN-FEC-004: Coded frames corrected before parsing
+    pub fec: FecPolicy,
+
+    /// UDP listening port for incoming telemetry data from satellites
+    pub telemetry_port: u16,
+
+    /// UDP port for outgoing command transmission to satellites
+    pub command_port: u16,
+
+    /// UDP port reserved for outgoing emergency commands
+    /// REQ-FN-002: Emergency Command Set - Never shared with bulk uplink traffic
+    pub emergency_port: u16,
+
+    /// Retry policy for uplink transmissions
+    /// REQ-NF-004: Fault Tolerance - Bounded retries of transient send failures
+    pub uplink_retry: RetryPolicy,
+
+    /// COP-1 sliding window, timer and transmission limit of command uplink
+    /// frames; the emergency lane is not framed
+    /// REQ-NF-004: Fault Tolerance - Unacknowledged frames retransmitted
+    pub cop1: Cop1Config,
+
+    /// Operator recorded against sent commands until changed from the console
+    pub operator: String,
+
+    /// Append-only command audit file; `None` keeps the audit log in memory
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Directory pass reports are written to; `None` keeps them in memory
+    /// FN-PAS-002: Pass reports archived as Markdown and JSON
+    pub pass_report_dir: Option<PathBuf>,
+
+    /// Append-only telemetry archive file; `None` keeps only the rolling
+    /// history in memory
+    /// FN-ARC-001: Telemetry persisted for post-pass analysis and replay
+    pub telemetry_archive_path: Option<PathBuf>,
+
+    /// Link and power margins operators are warned below
+    /// REQ-FN-007: Multi-Band Communication - Link closure with margin
+    pub margins: MarginPolicy,
+
+    /// Validate, encode and show uplinks without sending them
+    /// FN-DRY-001: Uplink frames shown exactly as they would be transmitted
+    pub dry_run: bool,
+
+    /// Sequence count window of downlink APIDs as (APID, window) pairs;
+    /// APIDs not listed accept `DEFAULT_SEQUENCE_WINDOW` counts ahead
+    /// FN-SEQ-002: Stale and replayed frames rejected per APID
+    pub downlink_sequence_windows: Vec<(u16, u16)>,
+
+    /// cFS Software Bus Network bridge; `None` leaves it off
+    /// FN-SBN-002: Downlinked packets and peer commands exchanged with cFS
+    pub sbn: Option<SbnConfig>,
+
+    /// YAMCS measurement feed and command link; `None` leaves it off
+    /// FN-YMC-002: Measurements re-emitted for YAMCS
+    pub yamcs: Option<YamcsConfig>,
+
+    /// Hot-standby pair this instance belongs to; `None` runs it alone
+    /// FN-RED-002: Command authority fails over to the standby
+    pub redundancy: Option<RedundancyConfig>,
+
+    /// Secondary endpoints downlink frames and telemetry are mirrored to;
+    /// empty leaves mirroring off
+    /// FN-MIR-001: Science consumers kept off the operations path
+    pub mirrors: Vec<MirrorEndpoint>,
+
+    /// Master link keys, one per key slot; `None` leaves frames unprotected
+    /// until keys are loaded from the console
+    /// REQ-SC-003: Authenticated encryption of packets with rotatable keys
+    pub link_key_file: Option<PathBuf>,
+}
+
+impl Default for GroundStationConfig {
+    /// Create default ground station configuration
+    ///
+    /// Provides sensible defaults for development and testing:
+    /// - Los Angeles location for ground station positioning
+    /// - All five frequency bands supported per REQ-FN-007
+    /// - Standard UDP ports for telemetry and command channels
+    fn default() -> Self {
+        Self {
+            // Standard ground station identifier format
+            station_id: "GST-001".to_string(),
+
+            // Los Angeles coordinates (lat, lon, altitude_m)
+            // 34.0522°N, 118.2437°W, 75m elevation
+            location: (34.0522, -118.2437, 75.0),
+
+            // REQ-FN-007: Multi-Band Communication - Support all five frequency bands
+            // Listed in order of increasing frequency and data rate capability
+            supported_bands: vec![
+                BandType::UhfBand, // 0.3-3 GHz: Most reliable, limited bandwidth
+                BandType::SBand,   // 2-4 GHz: Reliable, all-weather communication
+                BandType::XBand,   // 8-12 GHz: Balanced performance and reliability
+                BandType::KBand,   // 20-30 GHz: High data rate, weather-sensitive
+                BandType::KaBand,  // 26.5-40 GHz: Maximum data rate, atmospheric effects
+            ],
+
+            // S-band command uplink, X-band downlink
+            links: LinkConfiguration::default(),
+
+            // Mission baseline licences, the same plan the satellite holds
+            frequency_plan: FrequencyPlan::default(),
+
+            // K and Ka band Reed-Solomon coded, as on the satellite
+            fec: FecPolicy::default(),
+
+            // Network configuration for ground station operations
+            telemetry_port: 8081, // Incoming telemetry from satellites
+            command_port: 8082,   // Outgoing commands to satellites
+            emergency_port: 8083, // Outgoing emergency commands only
+
+            // Three attempts with jittered exponential backoff, 100ms to 2s
+            uplink_retry: RetryPolicy::default(),
+
+            // Window of 8 frames, T1 of 2s, 3 transmissions per frame
+            cop1: Cop1Config::default(),
+
+            // Audit log in memory until a file is configured
+            operator: "operator".to_string(),
+            audit_log_path: None,
+
+            // Pass reports in memory until a directory is configured
+            pass_report_dir: None,
+
+            // Telemetry in the rolling history until an archive is configured
+            telemetry_archive_path: None,
+
+            // 3 dB link margin, 20% transmitter power in reserve
+            margins: MarginPolicy::default(),
+
+            // Commands go to the satellite
+            dry_run: false,
+
+            // Default window on every downlink APID
+            downlink_sequence_windows: Vec::new(),
+
+            // No cFS peers
+            sbn: None,
+
+            // No YAMCS server
+            yamcs: None,
+
+            // No standby pair
+            redundancy: None,
+
+            // No secondary consumers
+            mirrors: Vec::new(),
+
+            // No link keys provisioned
+            link_key_file: None,
+        }
+    }
+}
+
+/// Ground station state and operational data
+///
+/// Maintains all runtime state for ground station operations including
+/// network sockets, telemetry history, and connection status.
+/// Thread-safe design using Arc<Mutex<T>> for shared state.
+pub struct GroundStation {
+    /// Ground station configuration parameters
+    config: GroundStationConfig,
+
+    /// Transport for receiving telemetry data from satellites
+    /// REQ-PF-001: Command Response Time - Low-latency telemetry reception
+    telemetry_transport: Arc<dyn Transport>,
+
+    /// Transport for sending commands to satellites
+    /// REQ-FN-001: Priority Classification - Priority-based command transmission
+    command_transport: Arc<dyn Transport>,
+
+    /// Transport reserved for the emergency uplink lane
+    /// REQ-FN-002: Emergency Command Set - Bypasses the command socket and its retries
+    emergency_transport: Arc<dyn Transport>,
+
+    /// Source of every timestamp the station records
+    clock: Arc<dyn Clock>,
+
+    /// Thread-safe storage for received telemetry packets
+    /// Maintains rolling history for analysis and monitoring
+    telemetry_history: Arc<Mutex<Box<dyn TelemetryStore>>>,
+
+    /// Uplink sequence count of each command APID
+    /// Ensures unique identification of each transmitted command
+    command_sequences: Arc<Mutex<HashMap<u16, u16>>>,
+
+    /// Sequence number generator for the emergency lane
+    /// Separate from `command_sequences` so an in-flight uplink never holds it
+    emergency_sequence: Arc<Mutex<u16>>,
+
+    /// FOP-1 state of the command virtual channel
+    /// REQ-NF-004: Shared by the uplink paths, [`Self::service_cop1`] and the
+    /// telemetry receiver's CLCW handling
+    cop1: Arc<Mutex<Fop>>,
+
+    /// Acceptance window over the sequence counts of each downlink APID
+    /// Drops duplicated and stale frames before they overwrite newer data
+    downlink_sequences: Arc<Mutex<DownlinkSequences>>,
+
+    /// Thread-safe connection status flag
+    /// Tracks real-time connectivity with satellite systems
+    is_connected: Arc<Mutex<bool>>,
+
+    /// Uplinks shown instead of sent while set
+    /// FN-DRY-001: Toggled from the console without restarting the station
+    dry_run: Arc<Mutex<bool>>,
+
+    /// Most recent recorder file manifest received on the downlink
+    /// Used to validate retransmission requests before uplink
+    last_file_manifest: Arc<Mutex<Option<FileManifest>>>,
+
+    /// Latest value of each downlinked measurement
+    /// Flags values whose updates stop arriving as stale
+    latest_telemetry: Arc<Mutex<TelemetryTracker>>,
+
+    /// Downlinked command execution reports
+    /// REQ-PF-001: Command Response Time - Timing compliance evidence
+    verification_archive: Arc<Mutex<VerificationArchive>>,
+
+    /// Memory dumps and dwell traces reassembled from their downlinks
+    /// FN-DMP-002 / FN-DWL-002: Diagnostics reassembly
+    diagnostics: Arc<Mutex<DiagnosticsArchive>>,
+
+    /// Loopback tests in flight and measured
+    /// FN-LBK-001: Command path round trip, uplink and downlink delays
+    loopback: Arc<Mutex<LoopbackTracker>>,
+
+    /// Raw and compressed sizes of every downlinked event log block
+    /// REQ-NF-002: Memory Constraints - Onboard log compression effectiveness
+    event_log_stats: Arc<Mutex<CompressionStats>>,
+
+    /// Recent EPS summaries with their per-subsystem power attribution
+    /// FN-PWR-003: Names the subsystem behind an unexpected depletion
+    power_trend: Arc<Mutex<PowerTrend>>,
+
+    /// Sent commands and their verification outcomes
+    /// FN-AUD-001: Append-only persistence of every sent command
+    audit_log: Arc<Mutex<AuditLog>>,
+
+    /// Current uplink and downlink configuration
+    /// REQ-FN-007: Multi-Band Communication - Reconfigurable per direction
+    links: Arc<Mutex<LinkConfiguration>>,
+
+    /// Activity of the contact in progress
+    /// FN-PAS-001: Pass report generated at every LOS
+    pass_tracker: Arc<Mutex<PassTracker>>,
+
+    /// Reports of completed contacts
+    /// FN-PAS-002: Pass reports archived as Markdown and JSON
+    pass_archive: Arc<Mutex<PassArchive>>,
+
+    /// Downlink volume of the contact in progress against its budget
+    /// FN-VOL-002: Operator alerted when the backlog will not fit
+    volume_tracker: Arc<Mutex<VolumeTracker>>,
+
+    /// Frames sent, received and rejected by the station since start-up
+    /// REQ-NF-001: Link performance in the same terms as the satellite's
+    link_stats: Arc<Mutex<LinkStats>>,
+
+    /// UDP socket of the SBN bridge, when configured
+    /// FN-SBN-002: Shared by the telemetry receiver and [`Self::service_sbn`]
+    sbn_socket: Option<UdpSocket>,
+
+    /// SBN peer protocol state, when configured
+    sbn_bridge: Option<Arc<Mutex<SbnBridge>>>,
+
+    /// UDP socket YAMCS sends commands to, when configured
+    /// FN-YMC-003: Shared by the telemetry receiver and [`Self::service_yamcs`]
+    yamcs_socket: Option<UdpSocket>,
+
+    /// UDP socket of the sync channel to the redundant peer, when configured
+    redundancy_socket: Option<UdpSocket>,
+
+    /// Role and replication state of the redundant pair, when configured
+    /// FN-RED-002: Checked before every uplink
+    redundancy: Option<Arc<Mutex<RedundancyLink>>>,
+
+    /// Secondary consumers of the downlink, fed by the telemetry receiver
+    /// FN-MIR-002: Each consumer queued independently of the others
+    mirror: Arc<TelemetryMirror>,
+
+    /// Queues of the live telemetry subscribers, fed by the telemetry receiver
+    /// FN-API-004: A full queue drops the packet for that subscriber only
+    telemetry_subscribers: Arc<Mutex<Vec<SyncSender<StoredTelemetry>>>>,
+
+    /// Policy table and keys protecting the link, once keys are loaded
+    /// REQ-SC-003: Shared by the uplink paths and the telemetry receiver
+    link_security: Arc<Mutex<Option<LinkSecurity>>>,
+
+    /// Worker threads started by [`Self::start`], by name
+    /// REQ-NF-003: System Availability - Thread health observable
+    workers: Mutex<Vec<(&'static str, thread::JoinHandle<()>)>>,
+}
+
+/// Transports, clock and telemetry store a [`GroundStation`] runs on
+///
+/// [`Self::bind`] gives the production set; a test builds one from the
+/// [`seams`] mocks and passes it to [`GroundStation::with_seams`].
+pub struct StationSeams {
+    /// Downlink telemetry transport
+    pub telemetry: Arc<dyn Transport>,
+    /// Command uplink transport
+    pub command: Arc<dyn Transport>,
+    /// Emergency uplink transport
+    pub emergency: Arc<dyn Transport>,
+    /// Source of every recorded timestamp
+    pub clock: Arc<dyn Clock>,
+    /// History of parsed telemetry, archived or rolling
+    pub store: Box<dyn TelemetryStore>,
+}
+
+impl StationSeams {
+    /// Bind the station's UDP sockets on the configured ports
+    ///
+    /// # Arguments
+    /// * `config` - Ground station configuration parameters
+    ///
+    /// # Returns
+    /// * `Result<Self>` - UDP transports with the system clock and a rolling
+    ///   store of [`TELEMETRY_HISTORY_LIMIT`] packets, archived to the
+    ///   configured telemetry archive file, or error if a socket cannot be
+    ///   bound or configured or the archive cannot be opened
+    ///
+    /// # Requirements Traceability
+    /// - REQ-PF-001: Command Response Time (socket timeout configuration)
+    /// - REQ-NF-004: Fault Tolerance (error handling for socket creation)
+    /// - FN-ARC-001: Archived telemetry of earlier sessions read back
+    pub fn bind(config: &GroundStationConfig) -> Result<Self> {
+        // Create UDP sockets for bi-directional communication
+        // Bind to localhost for development/simulation environment
+        let telemetry_socket = UdpSocket::bind(format!("127.0.0.1:{}", config.telemetry_port))
+            .map_err(|e| {
+                SpaceCommError::communication_timeout(
+                    1000,
+                    &format!("Failed to bind telemetry socket: {}", e),
+                )
+            })?;
+
+        let command_socket = UdpSocket::bind(format!("127.0.0.1:{}", config.command_port))
+            .map_err(|e| {
+                SpaceCommError::communication_timeout(
+                    1000,
+                    &format!("Failed to bind command socket: {}", e),
+                )
+            })?;
+
+        let emergency_socket = UdpSocket::bind(format!("127.0.0.1:{}", config.emergency_port))
+            .map_err(|e| {
+                SpaceCommError::communication_timeout(
+                    1000,
+                    &format!("Failed to bind emergency socket: {}", e),
+                )
+            })?;
+
+        // REQ-PF-001: Command Response Time - Configure socket timeouts for responsiveness
+        // 100ms timeout prevents blocking operations while maintaining responsiveness
+        telemetry_socket
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .map_err(|e| {
+                SpaceCommError::communication_timeout(
+                    100,
+                    &format!("Failed to set telemetry timeout: {}", e),
+                )
+            })?;
+
+        command_socket
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .map_err(|e| {
+                SpaceCommError::communication_timeout(
+                    100,
+                    &format!("Failed to set command timeout: {}", e),
+                )
+            })?;
+
+        let store: Box<dyn TelemetryStore> = match &config.telemetry_archive_path {
+            Some(path) => Box::new(TelemetryArchive::open(path, TELEMETRY_HISTORY_LIMIT)?),
+            None => Box::new(RollingTelemetryStore::new(TELEMETRY_HISTORY_LIMIT)),
+        };
+
+        Ok(Self {
+            telemetry: Arc::new(telemetry_socket),
+            command: Arc::new(command_socket),
+            emergency: Arc::new(emergency_socket),
+            clock: Arc::new(SystemClock),
+            store,
+        })
+    }
+}
+
+impl GroundStation {
+    /// Create new ground station instance
+    ///
+    /// Initializes UDP sockets, configures timeouts, and prepares thread-safe
+    /// shared state for multi-threaded operation.
+    ///
+    /// # Arguments
+    /// * `config` - Ground station configuration parameters
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Ground station instance or error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-PF-001: Command Response Time (socket timeout configuration)
+    /// - REQ-NF-004: Fault Tolerance (error handling for socket creation)
+    /// - FN-AUD-001: Command history read back from the audit file
+    /// - REQ-FN-007: Multi-Band Communication (link bands validated at startup)
+    /// - FN-PAS-002: Earlier pass reports read back from the report directory
+    pub fn new(config: GroundStationConfig) -> Result<Self> {
+        let seams = StationSeams::bind(&config)?;
+        Self::with_seams(config, seams)
+    }
+
+    /// Create a ground station over the given transports, clock and store
+    ///
+    /// As [`Self::new`], but the telemetry, command and emergency sockets,
+    /// the clock and the telemetry history are supplied by the caller, so a
+    /// test can run the station on [`seams`] mocks. The SBN, YAMCS and
+    /// redundancy sockets are still bound as configured.
+    ///
+    /// # Arguments
+    /// * `config` - Ground station configuration parameters
+    /// * `seams` - Transports, clock and telemetry store to run on
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Ground station instance or error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-004: Fault Tolerance (station internals testable in isolation)
+    pub fn with_seams(config: GroundStationConfig, seams: StationSeams) -> Result<Self> {
+        config.links.validate()?;
+        config.margins.validate()?;
+        check_supported_band(&config.supported_bands, &config.links.uplink)?;
+        check_supported_band(&config.supported_bands, &config.links.downlink)?;
+
+        let audit_log = match &config.audit_log_path {
+            Some(path) => AuditLog::open(path, &config.operator)?,
+            None => AuditLog::new(&config.operator),
+        };
+        let pass_archive = match &config.pass_report_dir {
+            Some(dir) => PassArchive::open(dir)?,
+            None => PassArchive::new(),
+        };
+
+        let sbn_socket = match &config.sbn {
+            Some(sbn) => {
+                let socket = UdpSocket::bind(sbn.bind_addr).map_err(|e| {
+                    SpaceCommError::communication_timeout(
+                        1000,
+                        &format!("Failed to bind SBN socket: {}", e),
+                    )
+                })?;
+                socket
+                    .set_read_timeout(Some(Duration::from_millis(100)))
+                    .map_err(|e| {
+                        SpaceCommError::communication_timeout(
+                            100,
+                            &format!("Failed to set SBN timeout: {}", e),
+                        )
+                    })?;
+                Some(socket)
+            }
+            None => None,
+        };
+
+        let redundancy_socket = match &config.redundancy {
+            Some(redundancy) => {
+                redundancy.validate()?;
+                let socket = UdpSocket::bind(redundancy.bind_addr).map_err(|e| {
+                    SpaceCommError::communication_timeout(
+                        1000,
+                        &format!("Failed to bind redundancy socket: {}", e),
+                    )
+                })?;
+                socket
+                    .set_read_timeout(Some(Duration::from_millis(100)))
+                    .map_err(|e| {
+                        SpaceCommError::communication_timeout(
+                            100,
+                            &format!("Failed to set redundancy timeout: {}", e),
+                        )
+                    })?;
+                Some(socket)
+            }
+            None => None,
+        };
+        let mirror = TelemetryMirror::start(&config.mirrors)?;
+        let volume_tracker =
+            VolumeTracker::new(config.supported_bands.clone(), config.fec, config.margins);
+
+        let redundancy = config.redundancy.clone().map(|redundancy| {
+            Arc::new(Mutex::new(RedundancyLink::new(
+                redundancy,
+                seams.clock.now_ms(),
+            )))
+        });
+
+        let yamcs_socket = match &config.yamcs {
+            Some(yamcs) => {
+                let socket = UdpSocket::bind(yamcs.tc_addr).map_err(|e| {
+                    SpaceCommError::communication_timeout(
+                        1000,
+                        &format!("Failed to bind YAMCS socket: {}", e),
+                    )
+                })?;
+                socket
+                    .set_read_timeout(Some(Duration::from_millis(100)))
+                    .map_err(|e| {
+                        SpaceCommError::communication_timeout(
+                            100,
+                            &format!("Failed to set YAMCS timeout: {}", e),
+                        )
+                    })?;
+                Some(socket)
+            }
+            None => None,
+        };
+
+        // Initialize thread-safe shared state using Arc<Mutex<T>> pattern
+        // This enables safe concurrent access from multiple threads
+        let links = config.links;
+        let dry_run = config.dry_run;
+        let sbn_bridge = config.sbn.clone().map(SbnBridge::new);
+        let link_security = match &config.link_key_file {
+            Some(path) => Some(LinkSecurity::new(link_security::load_key_file(path)?)),
+            None => None,
+        };
+        let mut downlink_sequences = DownlinkSequences::default();
+        for &(apid, window) in &config.downlink_sequence_windows {
+            downlink_sequences.set_window(apid, window)?;
+        }
+        // Both ends start counting frames from 0
+        let mut cop1 = Fop::new(config.cop1)?;
+        cop1.start(0);
+        Ok(Self {
+            config,
+            telemetry_transport: seams.telemetry,
+            command_transport: seams.command,
+            emergency_transport: seams.emergency,
+            clock: seams.clock,
+            // Rolling telemetry history with automatic size management
+            telemetry_history: Arc::new(Mutex::new(seams.store)),
+            // Per-APID command sequences for unique identification
+            command_sequences: Arc::new(Mutex::new(HashMap::new())),
+            // Independent sequence for the emergency lane
+            emergency_sequence: Arc::new(Mutex::new(0)),
+            // AD service active with nothing sent
+            cop1: Arc::new(Mutex::new(cop1)),
+            // Configured windows; other APIDs get the default on first frame
+            downlink_sequences: Arc::new(Mutex::new(downlink_sequences)),
+            // Real-time connection status tracking
+            is_connected: Arc::new(Mutex::new(false)),
+            // Dry run as configured until changed from the console
+            dry_run: Arc::new(Mutex::new(dry_run)),
+            // No recorder manifest until the first file downlink
+            last_file_manifest: Arc::new(Mutex::new(None)),
+            // No measurements until the first telemetry frame
+            latest_telemetry: Arc::new(Mutex::new(TelemetryTracker::new())),
+            // No execution reports until the first command executes
+            verification_archive: Arc::new(Mutex::new(VerificationArchive::new())),
+            // No memory dumps or dwells until one is commanded
+            diagnostics: Arc::new(Mutex::new(DiagnosticsArchive::new())),
+            // No loopback tests until the first contact
+            loopback: Arc::new(Mutex::new(LoopbackTracker::new())),
+            // No event log blocks until the first downlink pass
+            event_log_stats: Arc::new(Mutex::new(CompressionStats::default())),
+            // No power trend until the first EPS summary
+            power_trend: Arc::new(Mutex::new(PowerTrend::new())),
+            // Link configuration as validated above
+            links: Arc::new(Mutex::new(links)),
+            // Command history from previous sessions, if persisted
+            audit_log: Arc::new(Mutex::new(audit_log)),
+            // No contact until the first downlink frame
+            pass_tracker: Arc::new(Mutex::new(PassTracker::new())),
+            // Pass reports from previous sessions, if persisted
+            pass_archive: Arc::new(Mutex::new(pass_archive)),
+            // No budget until the operator plans a pass
+            volume_tracker: Arc::new(Mutex::new(volume_tracker)),
+            // Counted from start-up
+            link_stats: Arc::new(Mutex::new(LinkStats::new())),
+            // Peers start disconnected and are announced to by service_sbn()
+            sbn_socket,
+            sbn_bridge: sbn_bridge.map(|bridge| Arc::new(Mutex::new(bridge))),
+            // Commands from YAMCS are read by service_yamcs()
+            yamcs_socket,
+            // The pair is synchronised by service_redundancy()
+            redundancy_socket,
+            redundancy,
+            // Endpoints are open; consumers get frames from the first one on
+            mirror: Arc::new(mirror),
+            // Subscribers are added by subscribe_telemetry()
+            telemetry_subscribers: Arc::new(Mutex::new(Vec::new())),
+            // Frames protected from the first one on when keys are configured
+            link_security: Arc::new(Mutex::new(link_security)),
+            // Worker threads are spawned by start()
+            workers: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Start ground station operations
+    ///
+    /// Initializes all operational threads for telemetry reception, command processing,
+    /// and system monitoring. This method spawns background threads and returns
+    /// immediately, allowing the ground station to operate concurrently.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or initialization error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-003: System Availability (robust multi-threaded architecture)
+    /// - REQ-PF-001: Command Response Time (concurrent processing threads)
+    pub fn start(&self) -> Result<()> {
+        // Display ground station startup information
+        println!("Starting Ground Station {}", self.config.station_id);
+        println!(
+            "Location: {:.4}°N, {:.4}°W, {:.1}m",
+            self.config.location.0,
+            -self.config.location.1, // Convert to West longitude
+            self.config.location.2
+        );
+
+        // Start telemetry receiver thread for incoming satellite data
+        // REQ-PF-001: Command Response Time - Dedicated thread for low-latency reception
+        self.start_telemetry_receiver()?;
+
+        // Start command processor thread for outgoing commands
+        // REQ-FN-001: Priority Classification - Handles priority-based command processing
+        self.start_command_processor()?;
+
+        // Start monitoring thread for connection status and health checks
+        // REQ-NF-003: System Availability - Continuous system health monitoring
+        self.start_monitoring()?;
+
+        println!("Ground station operational");
+        Ok(())
+    }
+
+    /// Start telemetry receiver thread
+    ///
+    /// Spawns a dedicated thread for receiving and processing telemetry data
+    /// from satellites. Implements continuous polling with timeout handling
+    /// and automatic connection status updates.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or thread creation error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-IF-002: CCSDS Compliance (CCSDS packet parsing)
+    /// - REQ-PF-001: Command Response Time (low-latency telemetry processing)
+    /// - REQ-NF-003: System Availability (robust error handling)
+    fn start_telemetry_receiver(&self) -> Result<()> {
+        let mut receiver = self.telemetry_receiver()?;
+
+        // Spawn dedicated telemetry processing thread
+        let handle = thread::spawn(move || loop {
+            receiver.poll();
+
+            // Small delay to prevent excessive CPU usage while maintaining responsiveness
+            thread::sleep(Duration::from_millis(10));
+        });
+        self.workers
+            .lock()
+            .unwrap()
+            .push(("telemetry receiver", handle));
+
+        Ok(())
+    }
+
+    /// Budget the next pass, or the pass in progress, on the current downlink
+    ///
+    /// # Arguments
+    /// * `geometry` - Elevation over the contact
+    /// * `modcod` - MODCOD the configured downlink data rate is at
+    ///
+    /// # Returns
+    /// * `PassBudget` - The budget the pass is tracked against
+    ///
+    /// # Requirements Traceability
+    /// - FN-VOL-001: Downlink capacity budgeted before AOS
+    pub fn plan_pass_volume(&self, geometry: PassGeometry, modcod: Modcod) -> PassBudget {
+        let downlink = self.links.lock().unwrap().downlink;
+        let budget = PassBudget::plan(geometry, downlink, &self.config.fec, modcod);
+        self.volume_tracker.lock().unwrap().plan(budget.clone());
+        budget
+    }
+
+    /// Get the budget of the next pass or the pass in progress, if planned
+    pub fn pass_volume_budget(&self) -> Option<PassBudget> {
+        self.volume_tracker.lock().unwrap().budget().cloned()
+    }
+
+    /// Get delivered volume against the budget of the pass in progress
+    pub fn pass_volume_status(&self) -> Option<VolumeStatus> {
+        self.volume_tracker
+            .lock()
+            .unwrap()
+            .status(self.clock.now_ms())
+    }
+
+    /// Start command processor thread
+    fn start_command_processor(&self) -> Result<()> {
+        let handle = thread::spawn(move || {
+            // Simulate command processing
+            loop {
+                thread::sleep(Duration::from_secs(5));
+                // Commands would be processed here
+            }
+        });
+        self.workers
+            .lock()
+            .unwrap()
+            .push(("command processor", handle));
+
+        Ok(())
+    }
+
+    /// Start monitoring thread
+    fn start_monitoring(&self) -> Result<()> {
+        let is_connected = Arc::clone(&self.is_connected);
+        let latest_telemetry = Arc::clone(&self.latest_telemetry);
+        let pass_tracker = Arc::clone(&self.pass_tracker);
+        let pass_archive = Arc::clone(&self.pass_archive);
+        let volume_tracker = Arc::clone(&self.volume_tracker);
+        let station_id = self.config.station_id.clone();
+        let clock = Arc::clone(&self.clock);
+
+        let handle = thread::spawn(move || {
+            let mut last_connection_check = clock.now_ms();
+
+            loop {
+                let now = clock.now_ms();
+
+                // Check connection status every 10 seconds
+                if now.saturating_sub(last_connection_check) > 10_000 {
+                    let connected = *is_connected.lock().unwrap();
+
+                    if connected {
+                        println!("Satellite link: ACTIVE");
+                        // Reset connection flag to detect future disconnections
+                        *is_connected.lock().unwrap() = false;
+                    } else {
+                        println!("Satellite link: NO SIGNAL");
+
+                        // FN-PAS-001: A silent check period with a pass open is LOS
+                        let closed = pass_tracker.lock().unwrap().close(&station_id);
+                        if let Some(report) = closed {
+                            println!("{}", report.to_markdown());
+                            if let Err(e) = pass_archive.lock().unwrap().store(report) {
+                                eprintln!("Failed to archive pass report: {}", e);
+                            }
+                        }
+                        if let Some(status) = volume_tracker.lock().unwrap().close(now) {
+                            println!("Pass volume: {}", status);
+                        }
+                    }
+
+                    let stale = latest_telemetry.lock().unwrap().stale_ids(now);
+                    if !stale.is_empty() {
+                        println!("Stale telemetry: {:04X?}", stale);
+                    }
+
+                    last_connection_check = now;
+                }
+
+                thread::sleep(Duration::from_millis(1000));
+            }
+        });
+        self.workers.lock().unwrap().push(("monitor", handle));
+
+        Ok(())
+    }
+
+    /// Get telemetry history
+    pub fn get_telemetry_history(&self) -> Vec<TelemetryPacket> {
+        self.telemetry_history.lock().unwrap().packets()
+    }
+
+    /// Find stored telemetry packets
+    ///
+    /// Searches the whole archive when one is configured, the rolling
+    /// history otherwise.
+    ///
+    /// # Arguments
+    /// * `query` - Reception time range, APID and measurement ID to select
+    ///
+    /// # Returns
+    /// * `Vec<StoredTelemetry>` - Selected packets in reception order
+    ///
+    /// # Requirements Traceability
+    /// - FN-ARC-002: Archived telemetry queried by time, APID and measurement
+    pub fn query_telemetry(&self, query: &TelemetryQuery) -> Vec<StoredTelemetry> {
+        self.telemetry_history.lock().unwrap().query(query)
+    }
+
+    /// Subscribe to telemetry packets as they are received
+    ///
+    /// Every packet the telemetry receiver stores from now on is also queued
+    /// for the subscriber. A subscriber that falls `capacity` packets behind
+    /// misses the packets that do not fit, without holding up the receiver
+    /// or other subscribers; dropping the receiver ends the subscription.
+    ///
+    /// # Arguments
+    /// * `capacity` - Packets queued for the subscriber at most
+    ///
+    /// # Returns
+    /// * `Receiver<StoredTelemetry>` - Live telemetry in reception order
+    ///
+    /// # Requirements Traceability
+    /// - FN-API-004: Live telemetry fed to external consumers
+    pub fn subscribe_telemetry(&self, capacity: usize) -> Receiver<StoredTelemetry> {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        self.telemetry_subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Replay stored telemetry packets through the displays
+    ///
+    /// Each selected packet is shown as it was when received, RF housekeeping
+    /// and EPS summaries included. Replay is display only: the latest
+    /// values, the pass in progress and the history are left untouched.
+    ///
+    /// # Arguments
+    /// * `query` - Reception time range, APID and measurement ID to select
+    ///
+    /// # Returns
+    /// * `usize` - Number of packets replayed
+    ///
+    /// # Requirements Traceability
+    /// - FN-ARC-002: Post-pass analysis of archived telemetry
+    pub fn replay_telemetry(&self, query: &TelemetryQuery) -> usize {
+        let records = self.query_telemetry(query);
+        for record in &records {
+            println!(
+                "Replay: APID 0x{:03X} received at {} ms",
+                record.apid, record.received_ms
+            );
+            display_stored_telemetry(record);
+        }
+        records.len()
+    }
+
+    /// Get connection status
+    pub fn is_connected_to_satellite(&self) -> bool {
+        *self.is_connected.lock().unwrap()
+    }
+
+    /// Get the most recent recorder file manifest, if one has been received
+    pub fn last_file_manifest(&self) -> Option<FileManifest> {
+        self.last_file_manifest.lock().unwrap().clone()
+    }
+
+    /// Get the latest value of every measurement, with overdue values marked stale
+    pub fn latest_telemetry(&self) -> Vec<Measurement> {
+        self.latest_telemetry
+            .lock()
+            .unwrap()
+            .snapshot(self.clock.now_ms())
+    }
+
+    /// Get the station's link layer statistics since start-up
+    ///
+    /// Uplinked frames and COP-1 retransmissions, downlinked frames received
+    /// and dropped by the FEC decoder, and the downlink SNR estimated from
+    /// RF housekeeping.
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-001: Link performance in the same terms as the satellite's
+    pub fn link_stats(&self) -> LinkStats {
+        *self.link_stats.lock().unwrap()
+    }
+
+    /// Get the satellite's link layer statistics from its latest downlink
+    ///
+    /// # Returns
+    /// * `Option<LinkStats>` - Statistics from the last link statistics
+    ///   frame, `None` until one has been received
+    ///
+    /// # Requirements Traceability
+    /// - REQ-PF-002: Link quality monitoring
+    pub fn satellite_link_stats(&self) -> Option<LinkStats> {
+        LinkStats::from_measurements(&self.latest_telemetry())
+    }
+
+    /// Get the satellite's recorder downlink policy and per-policy statistics
+    ///
+    /// # Returns
+    /// * `Option<DownlinkPolicyStats>` - Policy and statistics from the last
+    ///   RF housekeeping that carried them, `None` until one has been received
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-001: Downlink prioritization monitored from the ground
+    pub fn downlink_policy_stats(&self) -> Option<DownlinkPolicyStats> {
+        DownlinkPolicyStats::from_measurements(&self.latest_telemetry())
+    }
+
+    /// Get a copy of the command execution report archive
+    pub fn verification_archive(&self) -> VerificationArchive {
+        self.verification_archive.lock().unwrap().clone()
+    }
+
+    /// Rolling send-to-acknowledgment latency percentiles per priority
+    ///
+    /// # Requirements Traceability
+    /// - FN-VER-003: REQ-PF-001 compliance measured in operation
+    #[req("REQ-PF-001")]
+    pub fn latency_percentiles(&self) -> Vec<LatencyPercentiles> {
+        self.verification_archive
+            .lock()
+            .unwrap()
+            .latency_percentiles()
+    }
+
+    /// Get a copy of the reassembled memory dumps and dwell traces
+    pub fn diagnostics(&self) -> DiagnosticsArchive {
+        self.diagnostics.lock().unwrap().clone()
+    }
+
+    /// Get a copy of the loopback tests in flight and measured
+    pub fn loopback(&self) -> LoopbackTracker {
+        self.loopback.lock().unwrap().clone()
+    }
+
+    /// Get a copy of the downlink sequence count windows
+    pub fn downlink_sequences(&self) -> DownlinkSequences {
+        self.downlink_sequences.lock().unwrap().clone()
+    }
+
+    /// Get the sequence count last uplinked on each command APID
+    pub fn uplink_sequences(&self) -> Vec<(u16, u16)> {
+        let mut sequences: Vec<_> = self
+            .command_sequences
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&apid, &sequence)| (apid, sequence))
+            .collect();
+        sequences.sort_unstable();
+        sequences
+    }
+
+    /// Get a copy of the power trend over recent EPS summaries
+    pub fn power_trend(&self) -> PowerTrend {
+        self.power_trend.lock().unwrap().clone()
+    }
+
+    /// Get the compression statistics of all downlinked event log blocks
+    pub fn event_log_statistics(&self) -> CompressionStats {
+        *self.event_log_stats.lock().unwrap()
+    }
+
+    /// Get a copy of the command audit log
+    pub fn audit_log(&self) -> AuditLog {
+        self.audit_log.lock().unwrap().clone()
+    }
+
+    /// Record commands sent from now on against `operator`
+    pub fn set_operator(&self, operator: &str) {
+        self.audit_log.lock().unwrap().set_operator(operator);
+    }
+
+    /// Get the current uplink and downlink configuration
+    pub fn link_configuration(&self) -> LinkConfiguration {
+        *self.links.lock().unwrap()
+    }
+
+    /// Get the link and power margins operators are warned below
+    pub fn margin_policy(&self) -> MarginPolicy {
+        self.config.margins
+    }
+
+    /// Whether uplinks are shown instead of sent
+    pub fn is_dry_run(&self) -> bool {
+        *self.dry_run.lock().unwrap()
+    }
+
+    /// Switch dry-run mode on or off
+    ///
+    /// In dry-run mode every uplink is validated and encoded as usual and
+    /// commands are audited, but frames are printed as a hex dump and decoded
+    /// view instead of being transmitted. Sequence counts advance as they
+    /// would for a live uplink.
+    ///
+    /// # Requirements Traceability
+    /// - FN-DRY-001: Uplink frames shown exactly as they would be transmitted
+    pub fn set_dry_run(&self, dry_run: bool) {
+        *self.dry_run.lock().unwrap() = dry_run;
+    }
+
+    /// Reconfigure one link direction of the ground station
+    ///
+    /// The other direction is unchanged. Only the ground side is
+    /// reconfigured; the satellite follows a `ReconfigureComm` command.
+    ///
+    /// # Arguments
+    /// * `direction` - Uplink or downlink
+    /// * `link` - New band, power level and data rate
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or configuration error if the settings are
+    ///   invalid for the direction or the band is not supported by the station
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-007: Multi-Band Communication (asymmetric uplink/downlink)
+    #[req("REQ-FN-007")]
+    pub fn set_link(&self, direction: LinkDirection, link: DirectionalLink) -> Result<()> {
+        check_supported_band(&self.config.supported_bands, &link)?;
+        self.links.lock().unwrap().set(direction, link)
+    }
+
+    /// Get the address telemetry is received on
+    pub fn telemetry_addr(&self) -> Result<SocketAddr> {
+        self.telemetry_transport
+            .local_addr()
+            .map_err(|_| SpaceCommError::communication_timeout(0, "telemetry socket address"))
+    }
+
+    /// Get each worker thread started so far and whether it is still running
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-003: System Availability (worker thread health)
+    #[req("REQ-NF-003")]
+    pub fn thread_health(&self) -> Vec<(&'static str, bool)> {
+        self.workers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, handle)| (*name, !handle.is_finished()))
+            .collect()
+    }
+
+    /// Get the sizes of the station's telemetry history and archives
+    ///
+    /// # Requirements Traceability
+    /// - FN-SOAK-001: Resources sampled throughout a long-duration run
+    pub fn resources(&self) -> StationResources {
+        StationResources {
+            telemetry_history: self.telemetry_history.lock().unwrap().len(),
+            verification_records: self.verification_archive.lock().unwrap().entries().len(),
+            audit_records: self.audit_log.lock().unwrap().records().len(),
+            pass_reports: self.pass_archive.lock().unwrap().reports().len(),
+        }
+    }
+
+    /// Get the reports of completed contacts, oldest first
+    ///
+    /// # Requirements Traceability
+    /// - FN-PAS-002: Pass reports retrievable after LOS
+    pub fn pass_reports(&self) -> Vec<PassReport> {
+        self.pass_archive.lock().unwrap().reports().to_vec()
+    }
+}
+
+/// Check that a link direction uses a band the station supports
+fn check_supported_band(supported_bands: &[BandType], link: &DirectionalLink) -> Result<()> {
+    if supported_bands.contains(&link.band) {
+        Ok(())
+    } else {
+        Err(SpaceCommError::ConfigurationError {
+            parameter: "links",
+            value: "<band>",
+            reason: "band not supported by the ground station",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket},
+        link_forecast::DownlinkPolicy,
+        rf_housekeeping::RF_HOUSEKEEPING_APID,
+    };
+
+    use crate::command::Command;
+    use crate::test_support::{mock_station, telemetry_frame};
+
+    #[test]
+    fn test_uplink_and_downlink_configured_separately() {
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            supported_bands: vec![BandType::UhfBand, BandType::SBand, BandType::XBand],
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let links = station.link_configuration();
+        assert_eq!(
+            (links.uplink.band, links.downlink.band),
+            (BandType::SBand, BandType::XBand)
+        );
+
+        let uhf = DirectionalLink::new(BandType::UhfBand, 50, 9_600);
+        station.set_link(LinkDirection::Downlink, uhf).unwrap();
+        assert_eq!(station.link_configuration().downlink, uhf);
+        assert_eq!(station.link_configuration().uplink, links.uplink);
+
+        // UHF is the emergency lane, not a command uplink band
+        assert!(station.set_link(LinkDirection::Uplink, uhf).is_err());
+        // Ka-band is not supported by this station
+        let ka = DirectionalLink::new(BandType::KaBand, 90, 1_000_000_000);
+        assert!(station.set_link(LinkDirection::Downlink, ka).is_err());
+        assert_eq!(station.link_configuration().downlink, uhf);
+
+        let mut unsupported = GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            ..GroundStationConfig::default()
+        };
+        unsupported.links.downlink = ka;
+        unsupported.supported_bands = vec![BandType::SBand, BandType::XBand];
+        assert!(GroundStation::new(unsupported).is_err());
+    }
+
+    #[test]
+    fn test_link_stats_of_station_and_satellite() {
+        let mock = mock_station(GroundStationConfig {
+            uplink_retry: RetryPolicy::fixed(1, 0),
+            ..GroundStationConfig::default()
+        });
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        assert_eq!(mock.station.satellite_link_stats(), None);
+
+        let mut onboard = LinkStats::new();
+        onboard.frame_sent(2_000, 0);
+        onboard.frame_sent(2_000, 4_000);
+        onboard.retransmission();
+        let mut data = telemetry_frame(0, &[]);
+        onboard.append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            RF_HOUSEKEEPING_APID,
+            1,
+            &payload,
+            None,
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+        .to_vec();
+
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+        mock.telemetry.deliver(&packet, satellite);
+        while receiver.poll() {}
+
+        let station = mock.station.link_stats();
+        assert_eq!(station.frames_sent, 1);
+        assert_eq!(station.frames_received, 1);
+        assert_eq!(station.bytes_received, packet.len() as u64);
+
+        let downlinked = mock.station.satellite_link_stats().unwrap();
+        assert_eq!(downlinked.frames_sent, 2);
+        assert_eq!(downlinked.retransmissions, 1);
+        assert_eq!(downlinked.bytes_sent, 4_000);
+        assert_eq!(downlinked.window_ms, 4_000);
+    }
+
+    #[test]
+    fn test_downlink_policy_stats_from_housekeeping() {
+        let mock = mock_station(GroundStationConfig::default());
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        assert_eq!(mock.station.downlink_policy_stats(), None);
+
+        let mut stats = DownlinkPolicyStats {
+            policy: DownlinkPolicy::HighestValueFirst,
+            ..DownlinkPolicyStats::default()
+        };
+        stats.per_policy[DownlinkPolicy::HighestValueFirst as usize].contacts = 3;
+        stats.per_policy[DownlinkPolicy::HighestValueFirst as usize].bytes = 12_000;
+        let mut data = telemetry_frame(0, &[]);
+        stats.append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            RF_HOUSEKEEPING_APID,
+            1,
+            &payload,
+            None,
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+        .to_vec();
+        mock.telemetry.deliver(&packet, satellite);
+        while receiver.poll() {}
+
+        assert_eq!(mock.station.downlink_policy_stats(), Some(stats));
+    }
+}
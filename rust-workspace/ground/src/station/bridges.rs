@@ -0,0 +1,401 @@
+//! SBN, YAMCS, redundancy and mirror services of the ground station
+//!
+//! The cFS Software Bus Network bridge and the YAMCS command link are
+//! serviced from their own sockets and uplink what their peers publish;
+//! the redundancy link keeps a hot-standby pair's sequence counts and
+//! verification state in step and decides which instance holds command
+//! authority. The telemetry receiver forwards the downlink to all of them.
+//!
+//! # Requirements Traceability
+//! - FN-SBN-001..002: cFS Software Bus Network bridge
+//! - FN-YMC-002..003: YAMCS measurement feed and command link
+//! - FN-RED-001..003: Hot-standby pair and failover of command authority
+//! - FN-MIR-001..002: Downlink mirrored to secondary consumers
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+
+use space_comms_shared::{Result, SpaceCommError};
+
+use crate::mirror::{ConsumerStats, MirrorEndpoint};
+use crate::redundancy::{RedundancyLink, SequenceState};
+use crate::sbn::SbnBridge;
+use crate::yamcs::YamcsConfig;
+use crate::{command::Command, yamcs};
+
+use super::GroundStation;
+
+impl GroundStation {
+    /// Socket and bridge state handed to the telemetry receiver thread
+    pub(super) fn sbn_forwarder(&self) -> Result<Option<(UdpSocket, Arc<Mutex<SbnBridge>>)>> {
+        let (Some(socket), Some(bridge)) = (&self.sbn_socket, &self.sbn_bridge) else {
+            return Ok(None);
+        };
+        let socket = socket.try_clone().map_err(|e| {
+            SpaceCommError::communication_timeout(
+                1000,
+                &format!("Failed to clone SBN socket: {}", e),
+            )
+        })?;
+        Ok(Some((socket, Arc::clone(bridge))))
+    }
+
+    /// Service the cFS Software Bus Network bridge once
+    ///
+    /// Waits up to 100ms for a datagram from an SBN peer, uplinks any command
+    /// it publishes through [`Self::send_command`] (with sequence count, audit
+    /// and dry run as for operator commands), then sends the announcements,
+    /// subscriptions and heartbeats that are due. Meant to be called in a
+    /// loop from a dedicated thread; returns immediately when no bridge is
+    /// configured.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the error of a rejected peer datagram or
+    ///   failed command uplink
+    ///
+    /// # Requirements Traceability
+    /// - FN-SBN-002: SBN peer protocol with subscriptions and heartbeats
+    /// - REQ-FN-001: Priority Classification (peer commands keep their priority)
+    pub fn service_sbn(&self) -> Result<()> {
+        let (Some(socket), Some(bridge)) = (&self.sbn_socket, &self.sbn_bridge) else {
+            return Ok(());
+        };
+
+        let mut buffer = [0u8; 4096];
+        let command = match socket.recv_from(&mut buffer) {
+            Ok((size, addr)) => {
+                bridge
+                    .lock()
+                    .unwrap()
+                    .receive(addr, &buffer[..size], self.clock.now_ms())
+            }
+            // REQ-NF-004: Fault Tolerance - Timeout is expected when peers are quiet
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(SpaceCommError::communication_timeout(
+                100,
+                &format!("SBN receive error: {}", e),
+            )),
+        };
+
+        let datagrams = bridge.lock().unwrap().poll(self.clock.now_ms())?;
+        for (addr, datagram) in datagrams {
+            if let Err(e) = socket.send_to(&datagram, addr) {
+                eprintln!("SBN send to {} failed: {}", addr, e);
+            }
+        }
+
+        if let Some(command) = command? {
+            println!(
+                "SBN command 0x{:08X} ({:?}) from cFS peer",
+                command.command_id, command.priority
+            );
+            self.send_command(command)?;
+        }
+        Ok(())
+    }
+
+    /// Service the hot-standby sync channel once
+    ///
+    /// Waits up to 100ms for a sync datagram from the redundant peer and, on
+    /// the standby, applies the sequence counts and archived executions it
+    /// replicates. Then takes over command authority if the active instance
+    /// has been silent for the failover timeout, and sends the heartbeat or
+    /// state update that is due. Meant to be called in a loop from a
+    /// dedicated thread; returns immediately when no pair is configured.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the error of a rejected peer datagram
+    ///
+    /// # Requirements Traceability
+    /// - FN-RED-001: Sequence counts and verification state replicated
+    /// - FN-RED-002: Failover of command authority
+    /// - FN-RED-003: Split-brain protection by authority epoch
+    pub fn service_redundancy(&self) -> Result<()> {
+        let (Some(socket), Some(link)) = (&self.redundancy_socket, &self.redundancy) else {
+            return Ok(());
+        };
+
+        // MAX_SYNC_EXECUTIONS archived executions fit with room to spare
+        let mut buffer = [0u8; 16384];
+        let replica = match socket.recv_from(&mut buffer) {
+            Ok((size, addr)) => {
+                link.lock()
+                    .unwrap()
+                    .receive(addr, &buffer[..size], self.clock.now_ms())
+            }
+            // REQ-NF-004: Fault Tolerance - Timeout is expected between heartbeats
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(SpaceCommError::communication_timeout(
+                100,
+                &format!("Redundancy receive error: {}", e),
+            )),
+        };
+        if let Ok(Some(replica)) = &replica {
+            *self.command_sequences.lock().unwrap() =
+                replica.sequences.uplink.iter().copied().collect();
+            *self.emergency_sequence.lock().unwrap() = replica.sequences.emergency;
+            let mut archive = self.verification_archive.lock().unwrap();
+            for execution in replica.new_executions(archive.entries().len()) {
+                archive.record(execution.report, execution.received_unix_ms);
+            }
+        }
+
+        let sequences = SequenceState {
+            uplink: self.uplink_sequences(),
+            emergency: *self.emergency_sequence.lock().unwrap(),
+        };
+        let mut link = link.lock().unwrap();
+        let changes_before = link.role_changes().len();
+        let datagram = link.poll(
+            self.clock.now_ms(),
+            &sequences,
+            self.verification_archive.lock().unwrap().entries(),
+        )?;
+        for change in &link.role_changes()[changes_before..] {
+            println!(
+                "Redundancy: now {:?} at epoch {} ({:?})",
+                change.role, change.epoch, change.reason
+            );
+        }
+        if let Some(datagram) = datagram {
+            if let Err(e) = socket.send_to(&datagram, link.config().peer_addr) {
+                eprintln!(
+                    "Redundancy send to {} failed: {}",
+                    link.config().peer_addr,
+                    e
+                );
+            }
+        }
+        drop(link);
+
+        replica.map(|_| ())
+    }
+
+    /// Whether this instance may uplink: always, unless it is the standby
+    /// of a redundant pair
+    pub fn has_command_authority(&self) -> bool {
+        self.redundancy
+            .as_ref()
+            .is_none_or(|link| link.lock().unwrap().has_command_authority())
+    }
+
+    /// Get a copy of the redundant pair state, when configured
+    pub fn redundancy(&self) -> Option<RedundancyLink> {
+        self.redundancy
+            .as_ref()
+            .map(|link| link.lock().unwrap().clone())
+    }
+
+    /// Socket and YAMCS address handed to the telemetry receiver thread
+    pub(super) fn yamcs_forwarder(&self) -> Result<Option<(UdpSocket, SocketAddr)>> {
+        let (Some(socket), Some(yamcs)) = (&self.yamcs_socket, &self.config.yamcs) else {
+            return Ok(None);
+        };
+        let socket = socket.try_clone().map_err(|e| {
+            SpaceCommError::communication_timeout(
+                1000,
+                &format!("Failed to clone YAMCS socket: {}", e),
+            )
+        })?;
+        Ok(Some((socket, yamcs.tm_addr)))
+    }
+
+    /// Service the YAMCS command link once
+    ///
+    /// Waits up to 100ms for a command packet from YAMCS, decodes it through
+    /// the command dictionary and uplinks it through [`Self::send_command`]
+    /// (with sequence count, audit and dry run as for operator commands).
+    /// Meant to be called in a loop from a dedicated thread; returns
+    /// immediately when YAMCS is not configured.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the error of a rejected command packet or
+    ///   failed command uplink
+    ///
+    /// # Requirements Traceability
+    /// - FN-YMC-003: YAMCS commands decoded through the command dictionary
+    /// - REQ-FN-001: Priority Classification (commands keep their priority)
+    pub fn service_yamcs(&self) -> Result<()> {
+        let Some(socket) = &self.yamcs_socket else {
+            return Ok(());
+        };
+
+        let mut buffer = [0u8; 4096];
+        let command = match socket.recv_from(&mut buffer) {
+            Ok((size, _)) => yamcs::decode_command(&buffer[..size])?,
+            // REQ-NF-004: Fault Tolerance - Timeout is expected when no one is commanding
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(SpaceCommError::communication_timeout(
+                    100,
+                    &format!("YAMCS receive error: {}", e),
+                ))
+            }
+        };
+
+        let command = Command::from_space_command(&command)?;
+        println!(
+            "YAMCS command 0x{:08X} ({:?})",
+            command.command_id, command.priority
+        );
+        self.send_command(command)
+    }
+
+    /// Get the YAMCS link configuration, when configured
+    pub fn yamcs_config(&self) -> Option<&YamcsConfig> {
+        self.config.yamcs.as_ref()
+    }
+
+    /// Get a copy of the SBN bridge state, when configured
+    pub fn sbn_bridge(&self) -> Option<SbnBridge> {
+        self.sbn_bridge
+            .as_ref()
+            .map(|bridge| bridge.lock().unwrap().clone())
+    }
+
+    /// Get the mirror endpoints, empty when mirroring is off
+    pub fn mirror_endpoints(&self) -> &[MirrorEndpoint] {
+        &self.config.mirrors
+    }
+
+    /// Get the delivery counts of every mirror consumer
+    pub fn mirror_statistics(&self) -> Vec<ConsumerStats> {
+        self.mirror.statistics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::audit::AuditEvent;
+    use crate::redundancy::RedundancyConfig;
+    use crate::sbn::SbnConfig;
+    use crate::station::GroundStationConfig;
+    use crate::test_support::command_message;
+    use crate::{create_command_packet, redundancy, sbn};
+
+    #[test]
+    fn test_sbn_peer_commands_are_uplinked() {
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        peer.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            dry_run: true,
+            sbn: Some(SbnConfig {
+                bind_addr: "127.0.0.1:0".parse().unwrap(),
+                peers: vec![peer.local_addr().unwrap()],
+                ..SbnConfig::default()
+            }),
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let station_addr = station.sbn_socket.as_ref().unwrap().local_addr().unwrap();
+
+        let command = Command::telemetry_request();
+        let packet = create_command_packet(&command_message(&command, 3))
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let frame = sbn::SbnFrame {
+            processor_id: 2,
+            spacecraft_id: 0x42,
+            message: sbn::SbnMessage::App(packet.to_vec()),
+        };
+        peer.send_to(&frame.encode().unwrap(), station_addr)
+            .unwrap();
+        station.service_sbn().unwrap();
+
+        // Uplinked like an operator command, and the peer gets our subscriptions
+        let records = station.audit_log().records().to_vec();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, AuditEvent::DryRun);
+        let mut buffer = [0u8; 512];
+        let (size, _) = peer.recv_from(&mut buffer).unwrap();
+        let reply = sbn::SbnFrame::decode(&buffer[..size]).unwrap();
+        assert!(matches!(reply.message, sbn::SbnMessage::Subscribe(ref s) if s.len() == 5));
+        assert!(station.sbn_bridge().unwrap().peers()[0].connected);
+    }
+
+    #[test]
+    fn test_standby_adopts_active_sequences_and_refuses_uplinks() {
+        let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+        active
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            dry_run: true,
+            redundancy: Some(RedundancyConfig {
+                instance_id: 2,
+                role: redundancy::Role::Standby,
+                bind_addr: "127.0.0.1:0".parse().unwrap(),
+                peer_addr: active.local_addr().unwrap(),
+                ..RedundancyConfig::default()
+            }),
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let station_addr = station
+            .redundancy_socket
+            .as_ref()
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        assert!(!station.has_command_authority());
+        assert!(station.send_command(Command::telemetry_request()).is_err());
+
+        let sync = redundancy::SyncMessage {
+            version: redundancy::SYNC_PROTOCOL_VERSION,
+            instance_id: 1,
+            role: redundancy::Role::Active,
+            epoch: 1,
+            archived_executions: 0,
+            sequences: Some(SequenceState {
+                uplink: vec![(0x101, 41)],
+                emergency: 7,
+            }),
+            executions_from: 0,
+            executions: Vec::new(),
+        };
+        active
+            .send_to(&sync.encode().unwrap(), station_addr)
+            .unwrap();
+        station.service_redundancy().unwrap();
+
+        // FN-RED-001: The standby continues the active's counts, and says so
+        assert_eq!(station.uplink_sequences(), vec![(0x101, 41)]);
+        assert_eq!(*station.emergency_sequence.lock().unwrap(), 7);
+        let mut buffer = [0u8; 4096];
+        let (size, _) = active.recv_from(&mut buffer).unwrap();
+        let reply = redundancy::SyncMessage::decode(&buffer[..size]).unwrap();
+        assert_eq!((reply.role, reply.epoch), (redundancy::Role::Standby, 1));
+        assert!(station.audit_log().records().is_empty());
+    }
+}
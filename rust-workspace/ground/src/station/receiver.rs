@@ -0,0 +1,883 @@
+//! Downlink pipeline of the ground station
+//!
+//! [`TelemetryReceiver`] takes each frame off the telemetry transport,
+//! corrects it, demultiplexes TM Transfer Frames back into packets and hands
+//! every packet to the parser and archive for its APID, feeding the pass
+//! tracker, the subscribers and the SBN, YAMCS and mirror consumers on the
+//! way.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (CCSDS packet parsing)
+//! - REQ-PF-001: Command Response Time (low-latency telemetry processing)
+//! - REQ-NF-003: System Availability (robust error handling)
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::mpsc::{SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+
+use space_comms_shared::{
+    ccsds::TmTransferFrame,
+    cop1::{Clcw, Fop},
+    eps::EPS_APID,
+    event_log::CompressionStats,
+    fec::{self, FecPolicy},
+    file_downlink::FileManifest,
+    link_config::{DirectionalLink, LinkConfiguration},
+    link_stats::LinkStats,
+    margin::{MarginPolicy, MarginShortfall},
+    rf_housekeeping::{decode_rf_housekeeping, RfBandStatus},
+    telemetry::{MeasurementQuality, TelemetryData},
+    telemetry_queue::TelemetryClass,
+    vc_mux::TmDemultiplexer,
+    Result,
+};
+
+use super::GroundStation;
+use crate::audit::AuditLog;
+use crate::diagnostics::DiagnosticsArchive;
+use crate::link_security::LinkSecurity;
+use crate::loopback::LoopbackTracker;
+use crate::mirror::TelemetryMirror;
+use crate::pass_report::{estimate_snr_db, PassTracker};
+use crate::power_trend::PowerTrend;
+use crate::redundancy::RedundancyLink;
+use crate::sbn::SbnBridge;
+use crate::seams::{Clock, TelemetryStore, Transport};
+use crate::telemetry_archive::StoredTelemetry;
+use crate::verification::VerificationArchive;
+use crate::volume_budget::VolumeTracker;
+use crate::{
+    display::{
+        display_dwell_trace, display_event_log, display_execution_report, display_file_listing,
+        display_file_manifest, display_load_manifest, display_loopback_result, display_memory_dump,
+        display_stored_telemetry,
+    },
+    downlink::{
+        accept_downlink_sequence, parse_clcw, parse_dwell_batch, parse_event_log,
+        parse_execution_report, parse_file_listing, parse_file_manifest, parse_load_manifest,
+        parse_loopback_echo, parse_memory_dump_segment, record_power_trend,
+    },
+    telemetry_tracker::TelemetryTracker,
+    yamcs, DownlinkSequences,
+};
+
+impl GroundStation {
+    /// Create a telemetry receiver over this station's state
+    ///
+    /// The receiver shares the station's telemetry transport, clock, history
+    /// and displays; [`Self::start`] runs one on its own thread, and a test
+    /// drives one with [`TelemetryReceiver::poll`].
+    ///
+    /// # Returns
+    /// * `Result<TelemetryReceiver>` - Receiver, or error if the SBN or YAMCS
+    ///   socket cannot be shared with it
+    ///
+    /// # Requirements Traceability
+    /// - REQ-IF-002: CCSDS Compliance (CCSDS packet parsing)
+    /// - REQ-NF-004: Fault Tolerance (pipeline testable without a network)
+    pub fn telemetry_receiver(&self) -> Result<TelemetryReceiver> {
+        Ok(TelemetryReceiver {
+            transport: Arc::clone(&self.telemetry_transport),
+            clock: Arc::clone(&self.clock),
+            telemetry_history: Arc::clone(&self.telemetry_history),
+            is_connected: Arc::clone(&self.is_connected),
+            last_file_manifest: Arc::clone(&self.last_file_manifest),
+            latest_telemetry: Arc::clone(&self.latest_telemetry),
+            verification_archive: Arc::clone(&self.verification_archive),
+            diagnostics: Arc::clone(&self.diagnostics),
+            loopback: Arc::clone(&self.loopback),
+            downlink_sequences: Arc::clone(&self.downlink_sequences),
+            cop1: Arc::clone(&self.cop1),
+            event_log_stats: Arc::clone(&self.event_log_stats),
+            power_trend: Arc::clone(&self.power_trend),
+            audit_log: Arc::clone(&self.audit_log),
+            pass_tracker: Arc::clone(&self.pass_tracker),
+            volume_tracker: Arc::clone(&self.volume_tracker),
+            link_stats: Arc::clone(&self.link_stats),
+            links: Arc::clone(&self.links),
+            fec_policy: self.config.fec,
+            margins: self.config.margins,
+            sbn: self.sbn_forwarder()?,
+            yamcs: self.yamcs_forwarder()?,
+            redundancy: self.redundancy.clone(),
+            mirror: Arc::clone(&self.mirror),
+            telemetry_subscribers: Arc::clone(&self.telemetry_subscribers),
+            link_security: Arc::clone(&self.link_security),
+            yamcs_sequence: 0,
+            tm_demux: TmDemultiplexer::default(),
+        })
+    }
+}
+
+/// Receive, parse and store pipeline of the downlink
+///
+/// Holds its own handles on the station state it updates, so it runs on the
+/// telemetry receiver thread or, with mock seams, directly in a test.
+///
+/// # Requirements Traceability
+/// - REQ-IF-002: CCSDS Compliance (CCSDS packet parsing)
+/// - REQ-PF-001: Command Response Time (low-latency telemetry processing)
+/// - REQ-NF-003: System Availability (robust error handling)
+pub struct TelemetryReceiver {
+    transport: Arc<dyn Transport>,
+    clock: Arc<dyn Clock>,
+    telemetry_history: Arc<Mutex<Box<dyn TelemetryStore>>>,
+    is_connected: Arc<Mutex<bool>>,
+    last_file_manifest: Arc<Mutex<Option<FileManifest>>>,
+    latest_telemetry: Arc<Mutex<TelemetryTracker>>,
+    verification_archive: Arc<Mutex<VerificationArchive>>,
+    diagnostics: Arc<Mutex<DiagnosticsArchive>>,
+    loopback: Arc<Mutex<LoopbackTracker>>,
+    downlink_sequences: Arc<Mutex<DownlinkSequences>>,
+    cop1: Arc<Mutex<Fop>>,
+    event_log_stats: Arc<Mutex<CompressionStats>>,
+    power_trend: Arc<Mutex<PowerTrend>>,
+    audit_log: Arc<Mutex<AuditLog>>,
+    pass_tracker: Arc<Mutex<PassTracker>>,
+    volume_tracker: Arc<Mutex<VolumeTracker>>,
+    link_stats: Arc<Mutex<LinkStats>>,
+    links: Arc<Mutex<LinkConfiguration>>,
+    fec_policy: FecPolicy,
+    margins: MarginPolicy,
+    sbn: Option<(UdpSocket, Arc<Mutex<SbnBridge>>)>,
+    yamcs: Option<(UdpSocket, SocketAddr)>,
+    redundancy: Option<Arc<Mutex<RedundancyLink>>>,
+    mirror: Arc<TelemetryMirror>,
+    telemetry_subscribers: Arc<Mutex<Vec<SyncSender<StoredTelemetry>>>>,
+    link_security: Arc<Mutex<Option<LinkSecurity>>>,
+    yamcs_sequence: u16,
+    tm_demux: TmDemultiplexer,
+}
+
+impl TelemetryReceiver {
+    /// Receive and process at most one downlinked frame
+    ///
+    /// Waits for the transport's read timeout at most.
+    ///
+    /// # Returns
+    /// * `bool` - Whether a frame was received, whether or not it was kept
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-004: Fault Tolerance (timeouts and receive errors tolerated)
+    pub fn poll(&mut self) -> bool {
+        // Sized for the largest CCSDS packet once Reed-Solomon coded
+        let mut buffer = [0u8; fec::MAX_CODED_FRAME_LEN];
+        match self.transport.recv_from(&mut buffer) {
+            Ok((size, addr)) => {
+                self.process_frame(&buffer[..size], addr);
+                true
+            }
+            Err(e) => {
+                // REQ-NF-004: Fault Tolerance - Handle timeouts gracefully
+                // Timeout is expected when no data is available
+                if !matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) {
+                    eprintln!("Telemetry receive error: {}", e);
+                }
+                false
+            }
+        }
+    }
+
+    /// Check, dispatch and store one received frame
+    fn process_frame(&mut self, bytes: &[u8], addr: SocketAddr) {
+        let now = self.clock.now_ms();
+        println!("Received {} bytes from {}", bytes.len(), addr);
+
+        // REQ-NF-003: System Availability - Update connection status
+        *self.is_connected.lock().unwrap() = true;
+
+        // FN-PAS-001: The first frame with no pass in progress is AOS
+        let downlink = self.links.lock().unwrap().downlink;
+        {
+            let mut pass_tracker = self.pass_tracker.lock().unwrap();
+            // FN-LBK-002: Every contact starts with a loopback test
+            if !pass_tracker.in_pass() {
+                self.loopback.lock().unwrap().contact_started();
+            }
+            pass_tracker.downlinked(bytes.len(), downlink.band, now);
+        }
+
+        // FN-FEC-004: Coded bands are corrected before any frame check
+        let decoded = match self.fec_policy.decode(downlink.band, bytes) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                eprintln!("Dropped {:?} frame: {}", downlink.band, e);
+                self.link_stats.lock().unwrap().frame_error(now);
+                return;
+            }
+        };
+        self.link_stats
+            .lock()
+            .unwrap()
+            .frame_received(bytes.len(), now);
+        if decoded.corrected_symbols > 0 {
+            println!("Reed-Solomon corrected {} bytes", decoded.corrected_symbols);
+        }
+
+        // FN-VCM-003: TM Transfer Frames carry packets of one virtual channel;
+        // anything else is a bare packet
+        match TmTransferFrame::from_bytes(&decoded.bytes) {
+            Ok(frame) => self.process_transfer_frame(&frame, &downlink, now),
+            Err(_) => self.process_packet(&decoded.bytes, &downlink, now),
+        }
+    }
+
+    /// Take the CLCW and the completed packets out of a TM Transfer Frame
+    fn process_transfer_frame(
+        &mut self,
+        frame: &TmTransferFrame,
+        downlink: &DirectionalLink,
+        now: u64,
+    ) {
+        // REQ-IF-002: Every frame reports the FARM-1 state in its OCF
+        if let Some(clcw) = frame.ocf.and_then(|ocf| Clcw::from_bytes(&ocf).ok()) {
+            self.receive_clcw(&clcw, now);
+        }
+
+        let gaps = self.tm_demux.frame_gaps();
+        let mut packets = Vec::new();
+        if let Err(e) = self
+            .tm_demux
+            .receive(frame, |packet| packets.push(packet.to_vec()))
+        {
+            eprintln!("Dropped TM frame: {}", e);
+            return;
+        }
+        if self.tm_demux.frame_gaps() > gaps {
+            println!(
+                "VC {}: frame count {} out of sequence, packets resynchronized",
+                frame.virtual_channel, frame.vc_frame_count
+            );
+        }
+
+        for packet in packets {
+            self.process_packet(&packet, downlink, now);
+        }
+    }
+
+    /// Hand a CLCW to FOP-1, raising its alerts against the pass
+    fn receive_clcw(&self, clcw: &Clcw, now: u64) {
+        if let Err(e) = self.cop1.lock().unwrap().receive_clcw(clcw, now) {
+            println!("COP-1 alert: {}", e);
+            self.pass_tracker
+                .lock()
+                .unwrap()
+                .alarm(format!("COP-1: {}", e), now);
+        }
+    }
+
+    /// Check, dispatch and store one received packet
+    fn process_packet(&mut self, bytes: &[u8], downlink: &DirectionalLink, now: u64) {
+        // REQ-SC-003: Forged, replayed and undecryptable frames go no further
+        let opened = match self.link_security.lock().unwrap().as_mut() {
+            Some(security) => match security.open_downlink(bytes) {
+                Ok(opened) => Some(opened),
+                Err(e) => {
+                    eprintln!("Dropped unverified frame: {}", e);
+                    return;
+                }
+            },
+            None => None,
+        };
+        let frame = opened.as_deref().unwrap_or(bytes);
+
+        // FN-SEQ-002: Duplicated and stale frames never reach the displays
+        if !accept_downlink_sequence(&mut self.downlink_sequences.lock().unwrap(), frame) {
+            return;
+        }
+
+        // FN-VOL-002: Every accepted frame counts against the pass budget
+        {
+            let mut volume_tracker = self.volume_tracker.lock().unwrap();
+            volume_tracker.downlinked(frame.len(), now);
+            let mut pass_tracker = self.pass_tracker.lock().unwrap();
+            if let Some(alert) = volume_tracker.check(now, pass_tracker.min_snr_db()) {
+                println!("Volume warning: {}", alert);
+                pass_tracker.alarm(format!("Volume: {}", alert), now);
+            }
+        }
+
+        // FN-SBN-002: cFS peers get every accepted frame, whatever its APID
+        if let Some((socket, bridge)) = &self.sbn {
+            forward_to_sbn(socket, bridge, frame, now);
+        }
+
+        // FN-MIR-001: Secondary consumers get the same accepted frames
+        self.mirror.mirror_frame(frame);
+
+        // COP-1 reports acknowledge command uplink frames and are not telemetry
+        if let Some(clcw) = parse_clcw(frame) {
+            self.receive_clcw(&clcw, now);
+            return;
+        }
+
+        // Command-load acknowledgments share the downlink but are not telemetry
+        if let Some(manifest) = parse_load_manifest(frame) {
+            display_load_manifest(&manifest);
+            return;
+        }
+
+        // Recorder file manifests precede each file downlink
+        if let Some(manifest) = parse_file_manifest(frame) {
+            display_file_manifest(&manifest);
+            self.volume_tracker
+                .lock()
+                .unwrap()
+                .backlog_announced(manifest.files.iter().map(|f| u64::from(f.size_bytes)).sum());
+            *self.last_file_manifest.lock().unwrap() = Some(manifest);
+            return;
+        }
+
+        // Directory listings answer ListFiles commands
+        if let Some(listing) = parse_file_listing(frame) {
+            display_file_listing(&listing);
+            return;
+        }
+
+        // Each executed command is followed by its execution report
+        if let Some(report) = parse_execution_report(frame) {
+            display_execution_report(&report);
+            self.pass_tracker
+                .lock()
+                .unwrap()
+                .execution_reported(&report);
+            // FN-RED-001: A standby's archive is replicated from the active station
+            if self
+                .redundancy
+                .as_ref()
+                .is_none_or(|link| link.lock().unwrap().has_command_authority())
+            {
+                self.verification_archive
+                    .lock()
+                    .unwrap()
+                    .record(report, now);
+            }
+            if let Err(e) = self
+                .audit_log
+                .lock()
+                .unwrap()
+                .record_execution(&report, now)
+            {
+                eprintln!("Failed to audit execution report: {}", e);
+            }
+            return;
+        }
+
+        // Memory dumps and dwells arrive in pieces on their own APIDs
+        if let Some(segment) = parse_memory_dump_segment(frame) {
+            let mut diagnostics = self.diagnostics.lock().unwrap();
+            display_memory_dump(diagnostics.record_segment(&segment, now));
+            return;
+        }
+        if let Some(batch) = parse_dwell_batch(frame) {
+            let mut diagnostics = self.diagnostics.lock().unwrap();
+            display_dwell_trace(diagnostics.record_batch(&batch, now));
+            return;
+        }
+
+        // Loopback echoes close the test they answer
+        if let Some(echo) = parse_loopback_echo(frame) {
+            match self.loopback.lock().unwrap().echo_received(&echo, now) {
+                Some(result) => display_loopback_result(&result),
+                None => println!("Loopback echo {} matches no test", echo.test_id),
+            }
+            return;
+        }
+
+        // The onboard event log arrives compressed on its own APID
+        if let Some((events, stats)) = parse_event_log(frame) {
+            display_event_log(&events, &stats);
+            self.event_log_stats.lock().unwrap().accumulate(&stats);
+            return;
+        }
+
+        // REQ-IF-002: CCSDS Compliance - Parse received telemetry packet
+        match StoredTelemetry::parse(frame, now) {
+            Ok(record) => {
+                println!("Telemetry packet parsed successfully");
+                display_stored_telemetry(&record);
+                let packet = &record.packet;
+                if record.apid == EPS_APID {
+                    if let Some(suspect) =
+                        record_power_trend(&mut self.power_trend.lock().unwrap(), &packet.data, now)
+                    {
+                        println!("Power warning: {}", suspect);
+                    }
+                }
+                if let Some((_, snr_db)) = downlink_snr_db(&packet.data, downlink) {
+                    self.link_stats.lock().unwrap().snr_measured(snr_db);
+                }
+                let shortfalls = record_pass_telemetry(
+                    &mut self.pass_tracker.lock().unwrap(),
+                    &packet.data,
+                    downlink,
+                    &self.margins,
+                    now,
+                );
+                for shortfall in shortfalls {
+                    println!("Margin warning: downlink {}", shortfall);
+                }
+                self.latest_telemetry
+                    .lock()
+                    .unwrap()
+                    .update(&packet.data, now);
+
+                // FN-YMC-002: YAMCS gets each measurement as its own packet
+                if let Some((socket, addr)) = &self.yamcs {
+                    forward_to_yamcs(socket, *addr, &packet.data, &mut self.yamcs_sequence);
+                }
+                self.mirror.mirror_telemetry(packet);
+                publish_telemetry(&self.telemetry_subscribers, &record);
+
+                // Store in thread-safe telemetry history, archived if configured
+                self.telemetry_history.lock().unwrap().store(record);
+            }
+            Err(e) => {
+                eprintln!("Failed to parse telemetry packet: {}", e);
+            }
+        }
+    }
+}
+
+/// Estimate the downlink SNR from a frame's RF housekeeping
+///
+/// # Arguments
+/// * `data` - Telemetry of a received frame
+/// * `downlink` - Downlink band and data rate the frame arrived on
+///
+/// # Returns
+/// * `Option<(RfBandStatus, f64)>` - Status of the locked downlink
+///   transceiver and the SNR in dB, `None` when the frame has none
+fn downlink_snr_db(
+    data: &TelemetryData,
+    downlink: &DirectionalLink,
+) -> Option<(RfBandStatus, f64)> {
+    let status = decode_rf_housekeeping(data)
+        .into_iter()
+        .find(|status| status.band == downlink.band && status.is_locked)?;
+    let snr_db = estimate_snr_db(f64::from(status.signal_strength), downlink.data_rate_bps);
+    Some((status, snr_db))
+}
+
+/// Feed a received telemetry frame into the pass in progress
+///
+/// RF housekeeping for the downlink band gives the SNR sample, checked with
+/// the transmitter power level against the margin policy; a frame
+/// classified as an alarm is recorded with its invalid measurements.
+///
+/// # Arguments
+/// * `tracker` - Tracker of the contact in progress
+/// * `data` - Telemetry of a received frame
+/// * `downlink` - Downlink band and data rate the frame arrived on
+/// * `margins` - Link and power margins required of the downlink
+/// * `now_unix_ms` - Reception time, milliseconds since the Unix epoch
+///
+/// # Returns
+/// * `Vec<MarginShortfall>` - Margins first found short in this pass, for
+///   the operator to be warned of
+///
+/// # Requirements Traceability
+/// - FN-PAS-001: Downlink SNR and alarms summarised per pass
+/// - FN-MAR-002: Operators warned of a link below the required margin
+fn record_pass_telemetry(
+    tracker: &mut PassTracker,
+    data: &TelemetryData,
+    downlink: &DirectionalLink,
+    margins: &MarginPolicy,
+    now_unix_ms: u64,
+) -> Vec<MarginShortfall> {
+    let mut shortfalls = Vec::new();
+    if let Some((status, snr_db)) = downlink_snr_db(data, downlink) {
+        tracker.snr_measured(snr_db);
+
+        for shortfall in [
+            margins.check_link(snr_db),
+            margins.check_power(status.tx_power),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if tracker.margin_short(shortfall, now_unix_ms) {
+                shortfalls.push(shortfall);
+            }
+        }
+    }
+
+    if TelemetryClass::classify(data) == TelemetryClass::Alarm {
+        let invalid: Vec<u16> = data
+            .measurements
+            .iter()
+            .filter(|m| m.quality == MeasurementQuality::Invalid)
+            .map(|m| m.measurement_id)
+            .collect();
+        tracker.alarm(
+            format!(
+                "{:?} health, invalid measurements {:04X?}",
+                data.health_status, invalid
+            ),
+            now_unix_ms,
+        );
+    }
+    shortfalls
+}
+
+/// Queue a received telemetry packet for every live subscriber
+///
+/// A subscriber whose queue is full misses the packet; one that has gone
+/// away is removed.
+///
+/// # Arguments
+/// * `subscribers` - Queues of the live telemetry subscribers
+/// * `record` - Telemetry packet as stored
+///
+/// # Requirements Traceability
+/// - FN-API-004: Live telemetry fed to external consumers
+fn publish_telemetry(
+    subscribers: &Mutex<Vec<SyncSender<StoredTelemetry>>>,
+    record: &StoredTelemetry,
+) {
+    subscribers
+        .lock()
+        .unwrap()
+        .retain(|subscriber| match subscriber.try_send(record.clone()) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+}
+
+/// Forward a downlinked frame to the SBN peers subscribed to its message ID
+///
+/// Send failures are reported and otherwise ignored: a lost SBN datagram
+/// never holds up downlink processing.
+///
+/// # Arguments
+/// * `socket` - UDP socket of the SBN bridge
+/// * `bridge` - SBN peer protocol state
+/// * `bytes` - Raw packet bytes received from satellite
+/// * `now_ms` - Time of reception
+///
+/// # Requirements Traceability
+/// - FN-SBN-001: Packets framed for the SBN unchanged
+/// - REQ-NF-004: Fault Tolerance (downlink unaffected by peer failures)
+fn forward_to_sbn(socket: &UdpSocket, bridge: &Mutex<SbnBridge>, bytes: &[u8], now_ms: u64) {
+    let datagrams = match bridge.lock().unwrap().publish(bytes, now_ms) {
+        Ok(datagrams) => datagrams,
+        Err(e) => {
+            eprintln!("SBN forwarding failed: {}", e);
+            return;
+        }
+    };
+    for (addr, datagram) in datagrams {
+        if let Err(e) = socket.send_to(&datagram, addr) {
+            eprintln!("SBN send to {} failed: {}", addr, e);
+        }
+    }
+}
+
+/// Send the measurements of a telemetry frame to YAMCS
+///
+/// Failures are reported but never interrupt the downlink.
+///
+/// # Arguments
+/// * `socket` - UDP socket of the YAMCS link
+/// * `addr` - Address of the YAMCS telemetry data link
+/// * `data` - Decoded telemetry
+/// * `sequence` - Sequence count of the last measurement packet
+///
+/// # Requirements Traceability
+/// - FN-YMC-002: Measurement packets YAMCS can ingest
+/// - REQ-NF-004: Fault Tolerance (downlink unaffected by YAMCS failures)
+fn forward_to_yamcs(
+    socket: &UdpSocket,
+    addr: SocketAddr,
+    data: &TelemetryData,
+    sequence: &mut u16,
+) {
+    let packets = match yamcs::measurement_packets(data, sequence) {
+        Ok(packets) => packets,
+        Err(e) => {
+            eprintln!("YAMCS forwarding failed: {}", e);
+            return;
+        }
+    };
+    for packet in packets {
+        if let Err(e) = socket.send_to(&packet, addr) {
+            eprintln!("YAMCS send to {} failed: {}", addr, e);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use space_comms_shared::{
+        cop1::Farm, eps::EPS_APID, margin::MarginKind, telemetry::measurement_ids, types::BandType,
+        wire,
+    };
+
+    use crate::audit::AuditEvent;
+    use crate::dictionary;
+    use crate::station::GroundStationConfig;
+    use crate::telemetry_archive::TelemetryQuery;
+    use crate::test_support::{housekeeping_bytes, mock_station, telemetry_frame};
+    use crate::yamcs::YamcsConfig;
+
+    #[test]
+    fn test_pass_telemetry_gives_snr_and_alarms() {
+        let downlink = LinkConfiguration::default().downlink;
+        let mut tracker = PassTracker::new();
+        tracker.downlinked(64, downlink.band, 1_000);
+
+        // Only the locked downlink transceiver gives an SNR sample
+        let mut data = telemetry_frame(0, &[]);
+        for (band, is_locked, signal_strength, frequency) in [
+            (BandType::SBand, true, -60, 2_200_000_000),
+            (BandType::XBand, true, -80, 8_400_000_000),
+            (BandType::KaBand, false, -70, 32_000_000_000),
+        ] {
+            let status = RfBandStatus {
+                band,
+                is_powered: true,
+                is_locked,
+                tx_power: 50,
+                signal_strength,
+                temperature: 20,
+                frequency,
+            };
+            status.append_measurements(&mut data).unwrap();
+        }
+        assert!(record_pass_telemetry(
+            &mut tracker,
+            &data,
+            &downlink,
+            &MarginPolicy::default(),
+            1_500
+        )
+        .is_empty());
+
+        let invalid = telemetry_frame(0, &[(0x0101, MeasurementQuality::Invalid)]);
+        record_pass_telemetry(
+            &mut tracker,
+            &invalid,
+            &downlink,
+            &MarginPolicy::default(),
+            2_000,
+        );
+
+        let report = tracker.close("GST-001").unwrap();
+        // -80 dBm against the -94 dBm noise floor over 100 MHz
+        assert!((report.min_snr_db.unwrap() - 14.0).abs() < 1e-9);
+        assert_eq!(report.alarms.len(), 1);
+        assert_eq!(report.alarms[0].time_unix_ms, 2_000);
+        assert!(report.alarms[0].description.contains("0101"));
+    }
+
+    #[test]
+    fn test_pass_telemetry_warns_once_per_margin() {
+        let downlink = LinkConfiguration::default().downlink;
+        let margins = MarginPolicy::default();
+        let mut tracker = PassTracker::new();
+        tracker.downlinked(64, downlink.band, 1_000);
+
+        // 12 dB SNR and 90% power: both margins short
+        let mut data = telemetry_frame(0, &[]);
+        RfBandStatus {
+            band: downlink.band,
+            is_powered: true,
+            is_locked: true,
+            tx_power: 90,
+            signal_strength: -82,
+            temperature: 20,
+            frequency: 8_400_000_000,
+        }
+        .append_measurements(&mut data)
+        .unwrap();
+
+        let shortfalls = record_pass_telemetry(&mut tracker, &data, &downlink, &margins, 1_500);
+        let kinds: Vec<_> = shortfalls.iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, [MarginKind::Link, MarginKind::Power]);
+        assert!((shortfalls[0].margin - 2.0).abs() < 1e-9);
+
+        // Repeated shortfalls in the same pass are not warned of again
+        assert!(record_pass_telemetry(&mut tracker, &data, &downlink, &margins, 1_600).is_empty());
+        let report = tracker.close("GST-001").unwrap();
+        assert_eq!(report.alarms.len(), 2);
+        assert!(report.alarms[0]
+            .description
+            .starts_with("link margin 2.0 dB"));
+    }
+
+    #[test]
+    fn test_receiver_stores_telemetry_at_the_clock_time() {
+        let mock = mock_station(GroundStationConfig::default());
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        let id = measurement_ids::BATTERY_TEMPERATURE;
+
+        // Nothing delivered reads as a timeout
+        assert!(!receiver.poll());
+        assert!(!mock.station.is_connected_to_satellite());
+
+        mock.telemetry
+            .deliver(&housekeeping_bytes(1, id), satellite);
+        assert!(receiver.poll());
+        assert!(mock.station.is_connected_to_satellite());
+        assert_eq!(mock.store.len(), 1);
+        assert_eq!(mock.station.get_telemetry_history()[0].sequence, 1);
+        assert_eq!(
+            mock.station.latest_telemetry()[0].quality,
+            MeasurementQuality::Good
+        );
+
+        // Stored with the APID it came on, found again and replayed
+        let on_apid = |apid| TelemetryQuery {
+            apid: Some(apid),
+            ..TelemetryQuery::default()
+        };
+        let stored = mock.station.query_telemetry(&on_apid(0x100));
+        assert_eq!((stored.len(), stored[0].received_ms), (1, 10_000));
+        assert!(mock.station.query_telemetry(&on_apid(EPS_APID)).is_empty());
+        assert_eq!(mock.station.replay_telemetry(&TelemetryQuery::default()), 1);
+
+        // Received at 10 000 ms on the mock clock: stale after 300 ms more
+        mock.clock.advance(301);
+        assert_eq!(
+            mock.station.latest_telemetry()[0].quality,
+            MeasurementQuality::Stale
+        );
+    }
+
+    #[test]
+    fn test_receiver_unpacks_tm_transfer_frames() {
+        let mock = mock_station(GroundStationConfig::default());
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        let id = measurement_ids::BATTERY_TEMPERATURE;
+
+        // Housekeeping flushes at once, padded out with idle data
+        let mut mux = space_comms_shared::vc_mux::VcMultiplexer::<4096>::default();
+        mux.push(0, &housekeeping_bytes(1, id), 0).unwrap();
+        mux.push(0, &housekeeping_bytes(2, id), 0).unwrap();
+        let clcw = Farm::default().clcw().to_bytes();
+        let frame = mux.next_frame(0, Some(clcw)).unwrap().unwrap();
+        assert_eq!(frame.virtual_channel, 0);
+
+        mock.telemetry
+            .deliver(&frame.to_bytes().unwrap(), satellite);
+        assert!(receiver.poll());
+        let sequences: Vec<u32> = mock.store.packets().iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![1, 2]);
+        assert!(mock.station.is_connected_to_satellite());
+    }
+
+    #[test]
+    fn test_receiver_drops_repeated_and_malformed_frames() {
+        let mock = mock_station(GroundStationConfig::default());
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        let frame = housekeeping_bytes(5, measurement_ids::BATTERY_VOLTAGE);
+
+        mock.telemetry.deliver(&frame, satellite);
+        mock.telemetry.deliver(&frame, satellite);
+        mock.telemetry.deliver(&frame[..4], satellite);
+        let mut corrupt = housekeeping_bytes(6, measurement_ids::BATTERY_VOLTAGE);
+        corrupt[wire::PRIMARY_HEADER_LEN + 2] ^= 0xFF;
+        mock.telemetry.deliver(&corrupt, satellite);
+        while receiver.poll() {}
+
+        let sequences: Vec<u32> = mock.store.packets().iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![5]);
+    }
+
+    #[test]
+    fn test_telemetry_subscribers_fed_as_packets_arrive() {
+        let mock = mock_station(GroundStationConfig::default());
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        let id = measurement_ids::BATTERY_VOLTAGE;
+        let live = mock.station.subscribe_telemetry(1);
+        let gone = mock.station.subscribe_telemetry(1);
+        drop(gone);
+
+        // A full queue drops the newer packet; the receiver carries on
+        mock.telemetry
+            .deliver(&housekeeping_bytes(1, id), satellite);
+        mock.telemetry
+            .deliver(&housekeeping_bytes(2, id), satellite);
+        while receiver.poll() {}
+        assert_eq!(mock.store.len(), 2);
+        let record = live.try_recv().unwrap();
+        assert_eq!((record.received_ms, record.packet.sequence), (10_000, 1));
+        assert!(live.try_recv().is_err());
+        assert_eq!(mock.station.telemetry_subscribers.lock().unwrap().len(), 1);
+
+        mock.telemetry
+            .deliver(&housekeeping_bytes(3, id), satellite);
+        assert!(receiver.poll());
+        assert_eq!(live.try_recv().unwrap().packet.sequence, 3);
+    }
+
+    #[test]
+    fn test_yamcs_link_uplinks_commands_and_feeds_measurements() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            dry_run: true,
+            yamcs: Some(YamcsConfig {
+                tm_addr: server.local_addr().unwrap(),
+                tc_addr: "127.0.0.1:0".parse().unwrap(),
+            }),
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let station_addr = station.yamcs_socket.as_ref().unwrap().local_addr().unwrap();
+
+        let command = dictionary::lookup("SendStatus")
+            .unwrap()
+            .parse(&["PowerStatus", "true", "Json"])
+            .unwrap();
+        server
+            .send_to(&yamcs::encode_command(&command, 4).unwrap(), station_addr)
+            .unwrap();
+        station.service_yamcs().unwrap();
+
+        // Uplinked like an operator command
+        let records = station.audit_log().records().to_vec();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, AuditEvent::DryRun);
+        assert_eq!(records[0].command_id, command.discriminant());
+
+        let (socket, addr) = station.yamcs_forwarder().unwrap().unwrap();
+        let data = telemetry_frame(
+            5,
+            &[
+                (measurement_ids::BATTERY_VOLTAGE, MeasurementQuality::Good),
+                (measurement_ids::OPERATIONAL_MODE, MeasurementQuality::Good),
+            ],
+        );
+        let mut sequence = 0;
+        forward_to_yamcs(&socket, addr, &data, &mut sequence);
+        let mut buffer = [0u8; 64];
+        for id in [
+            measurement_ids::BATTERY_VOLTAGE,
+            measurement_ids::OPERATIONAL_MODE,
+        ] {
+            let (size, from) = server.recv_from(&mut buffer).unwrap();
+            assert_eq!(from, station_addr);
+            assert_eq!(size, 23);
+            assert_eq!(u16::from_be_bytes([buffer[14], buffer[15]]), id);
+        }
+        assert_eq!(sequence, 2);
+    }
+}
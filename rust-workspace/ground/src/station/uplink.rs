@@ -0,0 +1,1104 @@
+//! Command uplink of the ground station
+//!
+//! Commands, command loads, retransmission requests, link forecasts and
+//! autonomy rules are checked against command authority, protected under
+//! the link security policy and sent in TC Transfer Frames under COP-1. The
+//! emergency lane sends its frames repeatedly on its own transport, and
+//! every uplink is audited.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-001: Priority Classification (priority-based APID assignment)
+//! - REQ-IF-002: CCSDS Compliance (TC Transfer Frames under COP-1)
+//! - REQ-SC-003: Link keys (uplink protection and key rotation)
+
+use std::net::SocketAddr;
+
+use space_comms_req::req;
+use space_comms_shared::{
+    autonomy::{AutonomyRule, AUTONOMY_RULE_APID},
+    ccsds::{PacketType, SpacePacket, TcTransferFrame},
+    command_load::{CommandLoad, LoadConstraints, LoadManifest, COMMAND_LOAD_APID},
+    commands::SpaceCommand,
+    cop1::Fop,
+    file_downlink::{RetransmitRequest, RETRANSMIT_REQUEST_APID},
+    link_forecast::{LinkForecast, DEFAULT_DEGRADED_PERCENT, LINK_FORECAST_APID},
+    messaging::{MessagePriority, EMERGENCY_UPLINK_REPEATS},
+    retry::{AttemptRecord, RetryDecision},
+    security::{KeyRotation, KeyStore, VcSecurityPolicy},
+    sequence::next_sequence_count,
+    types::{BandType, MessageId},
+    Result, SpaceCommError,
+};
+
+use super::GroundStation;
+use crate::audit::AuditEvent;
+use crate::link_security::LinkSecurity;
+use crate::loopback::AOS_LOOPBACK_PAYLOAD;
+use crate::{
+    command::{create_command_packet, next_uplink_sequence, Command},
+    dry_run, SATELLITE_COMMAND_ADDR, SATELLITE_EMERGENCY_ADDR,
+};
+
+impl GroundStation {
+    /// Refuse uplinks from the standby of a redundant pair
+    ///
+    /// # Requirements Traceability
+    /// - FN-RED-002: Only the active instance holds command authority
+    fn check_command_authority(&self) -> Result<()> {
+        if self.has_command_authority() {
+            Ok(())
+        } else {
+            Err(SpaceCommError::ConfigurationError {
+                parameter: "command_authority",
+                value: "standby",
+                reason: "Standby station holds no command authority",
+            })
+        }
+    }
+
+    /// Refuse a command tuning outside its band or the licensed assignments
+    ///
+    /// Parameters that do not decode as a shared command tune nothing and
+    /// pass; the satellite repeats the check on acceptance.
+    ///
+    /// # Requirements Traceability
+    /// - FN-FRQ-001: Out-of-band and unlicensed tuning refused before uplink
+    fn check_frequency(&self, command: &Command) -> Result<()> {
+        match serde_json::from_slice::<SpaceCommand>(&command.parameters) {
+            Ok(space_command) => self
+                .config
+                .frequency_plan
+                .check_command(&space_command)
+                .map_err(|violation| violation.to_error()),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Send command to satellite
+    ///
+    /// Constructs and transmits a command packet to the satellite using CCSDS
+    /// protocol with proper priority handling and sequence numbering.
+    /// Emergency-priority commands are routed to [`Self::send_emergency_command`].
+    ///
+    /// # Arguments
+    /// * `command` - Command structure containing ID, priority, and parameters
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-001: Priority Classification (command priority handling)
+    /// - REQ-IF-002: CCSDS Compliance (CCSDS packet creation)
+    /// - REQ-PF-001: Command Response Time (low-latency command transmission)
+    #[req("REQ-FN-001", "REQ-IF-002", "REQ-PF-001")]
+    pub fn send_command(&self, command: Command) -> Result<()> {
+        if command.priority == MessagePriority::Emergency {
+            return self.send_emergency_command(command);
+        }
+        // FN-RED-002: Only the active station of a redundant pair uplinks
+        self.check_command_authority()?;
+        // FN-FRQ-001: Pre-flight check of the frequency a command tunes to
+        self.check_frequency(&command)?;
+
+        // Generate unique command sequence number
+        // REQ-FN-001: Priority Classification - Each command gets unique ID
+        // Held across the uplink so counts on the APID go out in order
+        let mut sequences = self.command_sequences.lock().unwrap();
+        let sequence = next_uplink_sequence(&mut sequences, command.priority.command_apid());
+
+        // Create message structure with proper priority classification
+        let timestamp = self.clock.now_ns();
+        let mut message =
+            command.to_message(MessageId::from_value(u64::from(sequence)), timestamp)?;
+        // REQ-FN-007: Multi-Band Communication - Commands go up on the uplink band
+        message.preferred_band = self.links.lock().unwrap().uplink.band;
+
+        // REQ-IF-002: CCSDS Compliance - Create standard CCSDS packet
+        let packet = create_command_packet(&message)?;
+        // REQ-SC-003: Protected as the command channel's policy requires
+        let packet_bytes = self.protect_uplink(&packet)?;
+
+        // Transmit to satellite via UDP (simulated space link)
+        // In real implementation, this would interface with RF hardware
+        // REQ-PF-001: Command Response Time - Direct socket transmission for low latency
+        let sent = self.uplink(&packet_bytes, SATELLITE_COMMAND_ADDR, "command uplink");
+        self.audit(&command, sequence, sent.is_ok());
+        sent?;
+
+        if !self.is_dry_run() {
+            println!(
+                "Command sent: ID={}, Priority={:?}",
+                command.command_id, command.priority
+            );
+        }
+        Ok(())
+    }
+
+    /// Send an emergency command on the dedicated emergency lane
+    ///
+    /// Uses the emergency socket and sequence counter, so it never waits behind
+    /// a command, load or retransmission uplink in progress. There is no retry
+    /// backoff: the frame is sent `EMERGENCY_UPLINK_REPEATS` times back to back
+    /// and the satellite discards the repeated copies.
+    ///
+    /// # Arguments
+    /// * `command` - Command with `MessagePriority::Emergency`
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if any copy was sent, validation or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-002: Emergency Command Set (dedicated lane, <1ms onboard budget)
+    /// - REQ-IF-002: CCSDS Compliance (emergency command APID)
+    #[req("REQ-FN-001", "REQ-FN-002", "REQ-IF-002", "REQ-PF-001")]
+    pub fn send_emergency_command(&self, command: Command) -> Result<()> {
+        if command.priority != MessagePriority::Emergency {
+            return Err(SpaceCommError::invalid_packet(
+                "Emergency lane accepts Emergency-priority commands only",
+                Some(command.command_id),
+            ));
+        }
+        self.check_command_authority()?;
+
+        let sequence = {
+            let mut sequence = self.emergency_sequence.lock().unwrap();
+            *sequence = next_sequence_count(*sequence);
+            *sequence
+        };
+
+        let timestamp = self.clock.now_ns();
+        let message = command.to_message(MessageId::from_value(u64::from(sequence)), timestamp)?;
+        // REQ-SC-003: Emergency commands are signed and encrypted
+        let packet_bytes = self.protect_uplink(&create_command_packet(&message)?)?;
+
+        if self.is_dry_run() {
+            print!(
+                "{}",
+                dry_run::render(
+                    "emergency command uplink",
+                    SATELLITE_EMERGENCY_ADDR,
+                    &packet_bytes
+                )
+            );
+            println!("Would be sent {} times", EMERGENCY_UPLINK_REPEATS);
+            self.audit(&command, sequence, true);
+            return Ok(());
+        }
+
+        let sent = (0..EMERGENCY_UPLINK_REPEATS)
+            .filter(|_| {
+                self.emergency_transport
+                    .send_to(&packet_bytes, SATELLITE_EMERGENCY_ADDR)
+                    .is_ok()
+            })
+            .count();
+        // The emergency lane is carried on UHF
+        self.pass_tracker
+            .lock()
+            .unwrap()
+            .uplinked(packet_bytes.len() * sent, BandType::UhfBand);
+        self.audit(&command, sequence, sent > 0);
+        if sent == 0 {
+            return Err(SpaceCommError::communication_timeout(
+                0,
+                "emergency command uplink",
+            ));
+        }
+
+        println!(
+            "Emergency command sent: ID={}, copies={}/{}",
+            command.command_id, sent, EMERGENCY_UPLINK_REPEATS
+        );
+        Ok(())
+    }
+
+    /// Send a loopback test echoed back on `band`
+    ///
+    /// The test is tracked until its echo arrives and is measured, or until
+    /// it times out and is counted lost.
+    ///
+    /// # Arguments
+    /// * `band` - Band the satellite should echo on
+    /// * `payload` - Bytes to echo, at most `MAX_LOOPBACK_PAYLOAD`
+    ///
+    /// # Returns
+    /// * `Result<u16>` - Test ID, or validation or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - FN-LBK-001: Round trip, uplink and downlink delays of the command path
+    /// - REQ-FN-007: Multi-Band Communication (echo on a chosen band)
+    #[req("REQ-FN-007")]
+    pub fn send_loopback_test(&self, band: BandType, payload: &[u8]) -> Result<u16> {
+        let request = self
+            .loopback
+            .lock()
+            .unwrap()
+            .start(band, payload, self.clock.now_ms())?;
+        let sent = Command::from_space_command(&request.to_command())
+            .and_then(|command| self.send_command(command));
+        // A dry-run or failed uplink has no echo to wait for
+        if sent.is_err() || self.is_dry_run() {
+            self.loopback.lock().unwrap().abandon(request.test_id);
+        }
+        sent.map(|()| request.test_id)
+    }
+
+    /// Send the loopback test due at the start of a contact, if any
+    ///
+    /// The echo is requested on the downlink band. Meant to be called
+    /// periodically by the console.
+    ///
+    /// # Returns
+    /// * `Result<Option<u16>>` - ID of the test sent, `None` when no contact
+    ///   has started since the last call
+    ///
+    /// # Requirements Traceability
+    /// - FN-LBK-002: Loopback test at the start of each contact
+    pub fn service_loopback(&self) -> Result<Option<u16>> {
+        if !self.loopback.lock().unwrap().take_aos_due() {
+            return Ok(None);
+        }
+        let band = self.links.lock().unwrap().downlink.band;
+        self.send_loopback_test(band, AOS_LOOPBACK_PAYLOAD)
+            .map(Some)
+    }
+
+    /// Set the onboard clock minus station clock used to split loopback
+    /// round trips into uplink and downlink delays, milliseconds
+    pub fn set_loopback_clock_offset(&self, clock_offset_ms: i64) {
+        self.loopback
+            .lock()
+            .unwrap()
+            .set_clock_offset(clock_offset_ms);
+    }
+
+    /// Uplink a command load to the onboard scheduler
+    ///
+    /// Validates the whole load against the constraint checker before anything
+    /// is transmitted. A load with any rejected entry is not uplinked; the
+    /// returned manifest lists the offending entries so the operator can fix the
+    /// load file. A clean load is sent as a single packet on the command-load
+    /// APID, and the onboard manifest acknowledgment arrives on the downlink.
+    ///
+    /// # Arguments
+    /// * `load` - Command load parsed from a load file
+    /// * `constraints` - Constraint set matching the onboard scheduler
+    ///
+    /// # Returns
+    /// * `Result<LoadManifest>` - Ground-side validation manifest or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SF-001: Command Validation (load-wide constraint check before uplink)
+    /// - REQ-IF-002: CCSDS Compliance (load carried in a single Space Packet)
+    #[req("REQ-IF-002", "REQ-SF-001")]
+    pub fn uplink_command_load(
+        &self,
+        load: &CommandLoad,
+        constraints: &LoadConstraints,
+    ) -> Result<LoadManifest> {
+        self.check_command_authority()?;
+        let now_secs = self.clock.now_ms() / 1000;
+
+        // REQ-SF-001: Refuse to uplink a partially valid load
+        let manifest = load.validate(constraints, now_secs);
+        if !manifest.is_fully_accepted() {
+            return Ok(manifest);
+        }
+
+        let mut sequences = self.command_sequences.lock().unwrap();
+        let sequence = next_uplink_sequence(&mut sequences, COMMAND_LOAD_APID);
+
+        // The spacecraft correlates its mission clock to UTC from this stamp
+        let mut load = load.clone();
+        load.uplink_time = now_secs;
+        // Released entries count on their command APID like direct commands,
+        // so their execution reports never share a count with one
+        for entry in load.entries.iter_mut() {
+            entry.sequence_count =
+                next_uplink_sequence(&mut sequences, entry.command.priority().command_apid());
+        }
+        let payload = load.to_bytes()?;
+        let packet = SpacePacket::new(
+            PacketType::Command,
+            COMMAND_LOAD_APID,
+            sequence,
+            &payload,
+            None,
+        )?;
+        let packet_bytes = self.protect_uplink(&packet)?;
+
+        self.uplink(&packet_bytes, SATELLITE_COMMAND_ADDR, "command load uplink")?;
+        if self.is_dry_run() {
+            return Ok(manifest);
+        }
+
+        println!(
+            "Command load {} uplinked: {} entries, {} bytes",
+            load.load_id,
+            load.entries.len(),
+            packet_bytes.len()
+        );
+        Ok(manifest)
+    }
+
+    /// Request retransmission of recorder files or byte ranges
+    ///
+    /// Checks the request against the most recent file manifest and uplinks it
+    /// so the recorder replays the named products on the next pass.
+    ///
+    /// # Arguments
+    /// * `request` - Files and byte ranges to retransmit
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, validation error, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-004: Fault Tolerance (completion of partially received products)
+    /// - REQ-IF-002: CCSDS Compliance (request carried in a Space Packet)
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn request_retransmission(&self, request: &RetransmitRequest) -> Result<()> {
+        self.check_command_authority()?;
+        match self.last_file_manifest.lock().unwrap().as_ref() {
+            Some(manifest) => request.validate_against(manifest)?,
+            None => {
+                return Err(SpaceCommError::invalid_packet(
+                    "No file manifest received yet",
+                    None,
+                ))
+            }
+        }
+
+        let mut sequences = self.command_sequences.lock().unwrap();
+        let sequence = next_uplink_sequence(&mut sequences, RETRANSMIT_REQUEST_APID);
+
+        let payload = request.to_bytes()?;
+        let packet = SpacePacket::new(
+            PacketType::Command,
+            RETRANSMIT_REQUEST_APID,
+            sequence,
+            &payload,
+            None,
+        )?;
+        let packet_bytes = self.protect_uplink(&packet)?;
+
+        self.uplink(
+            &packet_bytes,
+            SATELLITE_COMMAND_ADDR,
+            "retransmit request uplink",
+        )?;
+        if self.is_dry_run() {
+            return Ok(());
+        }
+
+        println!(
+            "Retransmit request for pass {} sent: {} items",
+            request.pass_id,
+            request.items.len()
+        );
+        Ok(())
+    }
+
+    /// Uplink a forecast of the capacity of upcoming contacts
+    ///
+    /// The recorder downlink scheduler uses it to decide which stored
+    /// products to send on contacts forecast to be degraded.
+    ///
+    /// # Arguments
+    /// * `forecast` - Per-contact capacity forecast, e.g. from the frequency
+    ///   band simulation's link forecast
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, validation error, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-001: Priority Classification (recorder downlink priorities)
+    /// - REQ-IF-002: CCSDS Compliance (forecast carried in a Space Packet)
+    pub fn uplink_link_forecast(&self, forecast: &LinkForecast) -> Result<()> {
+        self.check_command_authority()?;
+        forecast.validate()?;
+
+        let mut sequences = self.command_sequences.lock().unwrap();
+        let sequence = next_uplink_sequence(&mut sequences, LINK_FORECAST_APID);
+
+        let payload = forecast.to_bytes()?;
+        let packet = SpacePacket::new(
+            PacketType::Command,
+            LINK_FORECAST_APID,
+            sequence,
+            &payload,
+            None,
+        )?;
+        let packet_bytes = self.protect_uplink(&packet)?;
+
+        self.uplink(
+            &packet_bytes,
+            SATELLITE_COMMAND_ADDR,
+            "link forecast uplink",
+        )?;
+        if self.is_dry_run() {
+            return Ok(());
+        }
+
+        let degraded = forecast
+            .contacts
+            .iter()
+            .filter(|c| c.is_degraded(DEFAULT_DEGRADED_PERCENT))
+            .count();
+        println!(
+            "Link forecast {} uplinked: {} contacts, {} degraded",
+            forecast.forecast_id,
+            forecast.contacts.len(),
+            degraded
+        );
+        Ok(())
+    }
+
+    /// Uplink an onboard autonomy rule
+    ///
+    /// The satellite loads the rule disabled, replacing any rule with the
+    /// same number; the `SetAutonomyRule` command enables it.
+    ///
+    /// # Arguments
+    /// * `rule` - Triggers and the action taken when they all hold
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, validation error, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-FN-005: Medium Priority Command Set (autonomous operations)
+    /// - REQ-IF-002: CCSDS Compliance (rule carried in a Space Packet)
+    pub fn uplink_autonomy_rule(&self, rule: &AutonomyRule) -> Result<()> {
+        self.check_command_authority()?;
+        rule.validate()?;
+
+        let mut sequences = self.command_sequences.lock().unwrap();
+        let sequence = next_uplink_sequence(&mut sequences, AUTONOMY_RULE_APID);
+
+        let payload = rule.to_bytes()?;
+        let packet = SpacePacket::new(
+            PacketType::Command,
+            AUTONOMY_RULE_APID,
+            sequence,
+            &payload,
+            None,
+        )?;
+        let packet_bytes = self.protect_uplink(&packet)?;
+
+        self.uplink(
+            &packet_bytes,
+            SATELLITE_COMMAND_ADDR,
+            "autonomy rule uplink",
+        )?;
+        if self.is_dry_run() {
+            return Ok(());
+        }
+
+        println!("Autonomy rule uplinked, loaded disabled: {}", rule);
+        Ok(())
+    }
+
+    /// Service the COP-1 command uplink once
+    ///
+    /// Sends the retransmissions FOP-1 has due: the frames a CLCW asked for
+    /// again, or the whole sliding window or pending control command once
+    /// timer T1 expires. Meant to be called periodically from a dedicated
+    /// thread; nothing is sent in dry-run mode.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, the alert of a frame that reached the
+    ///   transmission limit, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-004: Fault Tolerance (unacknowledged frames retransmitted)
+    /// - REQ-IF-002: CCSDS Compliance (COP-1 FOP-1 timer and limit)
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn service_cop1(&self) -> Result<()> {
+        if self.is_dry_run() {
+            return Ok(());
+        }
+
+        let now = self.clock.now_ms();
+        let mut cop1 = self.cop1.lock().unwrap();
+        let frames = cop1.poll(now).inspect_err(|e| {
+            self.pass_tracker
+                .lock()
+                .unwrap()
+                .alarm(format!("COP-1: {}", e), now);
+        })?;
+        for frame in &frames {
+            self.link_stats.lock().unwrap().retransmission();
+            self.send_frame(frame, SATELLITE_COMMAND_ADDR, "COP-1 retransmission")?;
+        }
+        Ok(())
+    }
+
+    /// Restart the COP-1 AD service numbering frames from `sequence`
+    ///
+    /// Sends a Set V(R) control command; command uplinks are refused until a
+    /// CLCW confirms the satellite expects `sequence`. Frames still in the
+    /// sliding window are dropped. Ignored by a FARM in lockout, which needs
+    /// [`Self::unlock_cop1`] first.
+    ///
+    /// # Arguments
+    /// * `sequence` - N(S) of the next command uplink frame
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or authority or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-IF-002: CCSDS Compliance (COP-1 initialisation with Set V(R))
+    pub fn set_cop1_sequence(&self, sequence: u8) -> Result<()> {
+        self.check_command_authority()?;
+        self.send_cop1_control("COP-1 Set V(R) uplink", |fop, now| {
+            fop.set_vr(sequence, now)
+        })
+    }
+
+    /// Restart the COP-1 AD service after a FARM-1 lockout
+    ///
+    /// Sends an Unlock control command; command uplinks are refused until a
+    /// CLCW reports the lockout cleared. Frames still in the sliding window
+    /// are dropped.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or authority or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-IF-002: CCSDS Compliance (COP-1 initialisation with Unlock)
+    pub fn unlock_cop1(&self) -> Result<()> {
+        self.check_command_authority()?;
+        self.send_cop1_control("COP-1 Unlock uplink", |fop, now| fop.unlock(now))
+    }
+
+    /// Send the Type-BC frame `control` makes of the FOP; the FOP is left as
+    /// it was in dry-run mode or when the frame cannot be sent
+    fn send_cop1_control(
+        &self,
+        operation: &'static str,
+        control: impl FnOnce(&mut Fop, u64) -> Result<TcTransferFrame>,
+    ) -> Result<()> {
+        let mut cop1 = self.cop1.lock().unwrap();
+        let mut next = cop1.clone();
+        let frame = control(&mut next, self.clock.now_ms())?;
+        self.send_frame(&frame, SATELLITE_COMMAND_ADDR, operation)?;
+        if !self.is_dry_run() {
+            *cop1 = next;
+        }
+        Ok(())
+    }
+
+    /// Current COP-1 FOP-1 state of the command uplink
+    pub fn cop1(&self) -> Fop {
+        self.cop1.lock().unwrap().clone()
+    }
+
+    /// Protect uplinks and downlinks with `keys` from now on
+    ///
+    /// Replaces any keys loaded before; the policy table starts from its
+    /// default, as the satellite's does at boot.
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-003: Master keys provisioned out-of-band, never uplinked
+    pub fn enable_link_security(&self, keys: KeyStore) {
+        *self.link_security.lock().unwrap() = Some(LinkSecurity::new(keys));
+    }
+
+    /// Current link security state, `None` while frames are unprotected
+    pub fn link_security(&self) -> Option<LinkSecurity> {
+        self.link_security.lock().unwrap().clone()
+    }
+
+    /// Command a key rotation and follow it on the ground
+    ///
+    /// The rotation command is protected with the current generation; the
+    /// station moves to the new generation once it is sent, and still
+    /// accepts downlinks of the previous one until the satellite follows.
+    ///
+    /// # Arguments
+    /// * `rotation` - Key slot and the generation to move it to
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or configuration error with no keys loaded
+    ///   or a generation not above the current one, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-003: Key rotation without uplinking key material
+    pub fn rotate_keys(&self, rotation: KeyRotation) -> Result<()> {
+        // Checked before sending so a refused rotation never reaches the satellite
+        let mut rotated = self
+            .link_security()
+            .ok_or(SpaceCommError::ConfigurationError {
+                parameter: "link_keys",
+                value: "<none>",
+                reason: "No link keys loaded",
+            })?;
+        rotated.rotate(rotation)?;
+
+        self.send_command(Command::from_space_command(&rotation.to_command())?)?;
+        if !self.is_dry_run() {
+            if let Some(security) = self.link_security.lock().unwrap().as_mut() {
+                security.rotate(rotation)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Command a virtual channel security policy and follow it on the ground
+    ///
+    /// # Arguments
+    /// * `virtual_channel` - Virtual channel to reconfigure
+    /// * `policy` - New security service and key slot
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the refused policy change or transmission
+    ///   error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-001: Commandable per-virtual-channel security policy
+    #[req("REQ-SC-001")]
+    pub fn send_vc_security_policy(
+        &self,
+        virtual_channel: u8,
+        policy: VcSecurityPolicy,
+    ) -> Result<()> {
+        self.send_command(Command::set_vc_security_policy(virtual_channel, policy))?;
+        if !self.is_dry_run() {
+            if let Some(security) = self.link_security.lock().unwrap().as_mut() {
+                security.set_policy(virtual_channel, policy)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize an uplinked packet, protected when link keys are loaded
+    ///
+    /// # Requirements Traceability
+    /// - REQ-SC-003: Uplinks protected as their channel and APID require
+    fn protect_uplink(&self, packet: &SpacePacket) -> Result<Vec<u8>> {
+        match self.link_security.lock().unwrap().as_mut() {
+            Some(security) => security.seal_uplink(packet),
+            None => Ok(packet.to_bytes()?.to_vec()),
+        }
+    }
+
+    /// Transmit packet bytes to the satellite under COP-1
+    ///
+    /// The packet goes up as the next Type-AD TC Transfer Frame and stays in
+    /// the FOP-1 sliding window until a CLCW acknowledges it;
+    /// [`Self::service_cop1`] retransmits it until then. In dry-run mode the
+    /// frame is printed and not counted in the window.
+    ///
+    /// # Arguments
+    /// * `packet_bytes` - Serialized CCSDS packet
+    /// * `satellite_addr` - Uplink destination
+    /// * `operation` - Label used in logs and the timeout error
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, a resource error while the sliding window is
+    ///   full, a timeout while the AD service is stopped, or transmission error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-IF-002: CCSDS Compliance (TC Transfer Frames under COP-1)
+    /// - REQ-NF-004: Fault Tolerance (frames held until acknowledged)
+    fn uplink(
+        &self,
+        packet_bytes: &[u8],
+        satellite_addr: SocketAddr,
+        operation: &'static str,
+    ) -> Result<()> {
+        // Held across the send so frames go out in N(S) order
+        let mut cop1 = self.cop1.lock().unwrap();
+        let frame = cop1.prepare(packet_bytes)?;
+        self.send_frame(&frame, satellite_addr, operation)?;
+        if !self.is_dry_run() {
+            cop1.transmitted(frame, self.clock.now_ms());
+        }
+        Ok(())
+    }
+
+    /// Transmit a TC Transfer Frame under the configured retry policy
+    ///
+    /// Each failed attempt is logged with its retry decision. On a band the
+    /// FEC policy codes, the Reed-Solomon coded frame is what is sent. In
+    /// dry-run mode the frame is printed instead and nothing is sent.
+    ///
+    /// # Arguments
+    /// * `frame` - Frame to send
+    /// * `satellite_addr` - Uplink destination
+    /// * `operation` - Label used in logs and the timeout error
+    ///
+    /// # Requirements Traceability
+    /// - REQ-NF-004: Fault Tolerance (bounded retry of transient send failures)
+    /// - FN-FEC-003: Frames on coded bands uplinked as Reed-Solomon codeblocks
+    fn send_frame(
+        &self,
+        frame: &TcTransferFrame,
+        satellite_addr: SocketAddr,
+        operation: &'static str,
+    ) -> Result<()> {
+        let band = self.links.lock().unwrap().uplink.band;
+        let coded = frame.to_bytes_with_fec(&self.config.fec, band)?;
+        let packet_bytes = &coded[..];
+
+        if self.is_dry_run() {
+            print!(
+                "{}",
+                dry_run::render_frame(operation, satellite_addr, packet_bytes, frame)
+            );
+            return Ok(());
+        }
+
+        let mut log_attempt = |record: &AttemptRecord<'_>| match record.decision {
+            RetryDecision::RetryAfter { delay_ms } => eprintln!(
+                "{} attempt {} failed ({}), retrying in {}ms",
+                operation, record.attempt, record.error, delay_ms
+            ),
+            RetryDecision::GiveUp(reason) => eprintln!(
+                "{} attempt {} failed ({}), giving up: {:?}",
+                operation, record.attempt, record.error, reason
+            ),
+        };
+
+        space_comms_shared::retry::retry_blocking(
+            &self.config.uplink_retry,
+            &mut log_attempt,
+            |_| {
+                self.command_transport
+                    .send_to(packet_bytes, satellite_addr)
+                    .map(|_| ())
+                    .map_err(|_| SpaceCommError::communication_timeout(1000, operation))
+            },
+        )?;
+
+        self.pass_tracker
+            .lock()
+            .unwrap()
+            .uplinked(packet_bytes.len(), band);
+        self.link_stats
+            .lock()
+            .unwrap()
+            .frame_sent(packet_bytes.len(), self.clock.now_ms());
+        Ok(())
+    }
+
+    /// Record a command's uplink outcome in the audit log and the pass report
+    ///
+    /// A failed audit write is reported but never blocks commanding. A dry-run
+    /// command is audited as such and left out of the pass report.
+    ///
+    /// # Requirements Traceability
+    /// - FN-AUD-001: Every sent command is recorded, uplinked or not
+    fn audit(&self, command: &Command, sequence: u16, uplinked: bool) {
+        let event = if self.is_dry_run() {
+            AuditEvent::DryRun
+        } else if uplinked {
+            AuditEvent::Sent
+        } else {
+            AuditEvent::UplinkFailed
+        };
+        if event != AuditEvent::DryRun {
+            self.pass_tracker.lock().unwrap().command_sent(uplinked);
+        }
+        // FN-VER-003: Sent commands are timed to their execution report
+        if event == AuditEvent::Sent {
+            self.verification_archive.lock().unwrap().command_sent(
+                command.priority,
+                sequence,
+                self.clock.now_ms(),
+            );
+        }
+        if let Err(e) = self.audit_log.lock().unwrap().record_command(
+            command,
+            sequence,
+            event,
+            self.clock.now_ms(),
+        ) {
+            eprintln!("Failed to audit command {}: {}", command.command_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        command_load::{CommandLoadEntry, LoadConstraints},
+        commands::{ReportFormat, StatusType},
+        cop1::{Cop1Alert, Cop1Config, Farm, FarmVerdict, FopState},
+        link_config::LinkDirection,
+        messaging::decode_command_packet,
+        retry::RetryPolicy,
+        security::KeyStore,
+        wire,
+    };
+
+    use crate::audit::AuditEvent;
+    use crate::station::GroundStationConfig;
+    use crate::test_support::mock_station;
+
+    #[test]
+    fn test_emergency_lane_is_separate_from_command_uplink() {
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+
+        assert!(station
+            .send_emergency_command(Command::switch_band(BandType::UhfBand))
+            .is_err());
+
+        station.send_command(Command::emergency_stop()).unwrap();
+        station
+            .send_emergency_command(Command::emergency_stop())
+            .unwrap();
+        assert_eq!(*station.emergency_sequence.lock().unwrap(), 2);
+        assert!(station.command_sequences.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_audits_without_uplinking() {
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            dry_run: true,
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        assert!(station.is_dry_run());
+
+        station.send_command(Command::telemetry_request()).unwrap();
+        station
+            .send_emergency_command(Command::emergency_stop())
+            .unwrap();
+        // Validation still applies in dry run
+        assert!(station
+            .send_emergency_command(Command::telemetry_request())
+            .is_err());
+
+        let events: Vec<AuditEvent> = station
+            .audit_log()
+            .records()
+            .iter()
+            .map(|r| r.event)
+            .collect();
+        assert_eq!(events, vec![AuditEvent::DryRun, AuditEvent::DryRun]);
+
+        station.set_dry_run(false);
+        assert!(!station.is_dry_run());
+    }
+
+    #[test]
+    fn test_key_rotation_follows_the_uplink() {
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let rotation = KeyRotation {
+            key_id: 0,
+            generation: 1,
+        };
+        assert!(station.rotate_keys(rotation).is_err());
+
+        let mut keys = KeyStore::new();
+        keys.install(0, [0x5A; 32]).unwrap();
+        station.enable_link_security(keys);
+        station.send_command(Command::emergency_stop()).unwrap();
+        station.rotate_keys(rotation).unwrap();
+        assert_eq!(station.link_security().unwrap().generation(0), Some(1));
+
+        // A rotation that would roll the key back is never sent
+        assert!(station.rotate_keys(rotation).is_err());
+        assert_eq!(station.audit_log().records().len(), 2);
+    }
+
+    #[test]
+    fn test_reconfigure_frequency_checked_before_uplink() {
+        let station = GroundStation::new(GroundStationConfig {
+            telemetry_port: 0,
+            command_port: 0,
+            emergency_port: 0,
+            dry_run: true,
+            ..GroundStationConfig::default()
+        })
+        .unwrap();
+        let reconfigure = |band, frequency_hz| {
+            Command::from_space_command(&SpaceCommand::ReconfigureComm {
+                direction: LinkDirection::Downlink,
+                band,
+                frequency_hz,
+                power_level: 80,
+                data_rate_bps: 200_000_000,
+                modulation: space_comms_shared::commands::ModulationType::QPSK,
+                error_correction: true,
+            })
+            .unwrap()
+        };
+
+        station
+            .send_command(reconfigure(BandType::XBand, 8_400_000_000))
+            .unwrap();
+        let out_of_band = station.send_command(reconfigure(BandType::XBand, 13_000_000_000));
+        assert!(matches!(
+            out_of_band,
+            Err(SpaceCommError::ConfigurationError { value: "E440", .. })
+        ));
+        let unlicensed = station.send_command(reconfigure(BandType::XBand, 11_000_000_000));
+        assert!(matches!(
+            unlicensed,
+            Err(SpaceCommError::ConfigurationError { value: "E441", .. })
+        ));
+        // Refused commands are never encoded or audited
+        assert_eq!(station.audit_log().records().len(), 1);
+    }
+
+    #[test]
+    fn test_uplinks_go_out_on_their_transports() {
+        let mock = mock_station(GroundStationConfig {
+            uplink_retry: RetryPolicy::fixed(2, 0),
+            ..GroundStationConfig::default()
+        });
+
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+        let sent = mock.command.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].1, SATELLITE_COMMAND_ADDR);
+        let frame = TcTransferFrame::from_bytes(&sent[0].0).unwrap();
+        let fields = decode_command_packet(&frame.data).unwrap();
+        assert_eq!(fields.priority, MessagePriority::Low);
+
+        mock.station
+            .send_emergency_command(Command::emergency_stop())
+            .unwrap();
+        let copies = mock.emergency.sent();
+        assert_eq!(copies.len(), usize::from(EMERGENCY_UPLINK_REPEATS));
+        assert!(copies
+            .iter()
+            .all(|(bytes, addr)| *bytes == copies[0].0 && *addr == SATELLITE_EMERGENCY_ADDR));
+
+        // Both attempts refused: the uplink fails and nothing is recorded
+        mock.command.fail_sends(true);
+        assert!(mock
+            .station
+            .send_command(Command::telemetry_request())
+            .is_err());
+        assert_eq!(mock.command.sent().len(), 1);
+        assert_eq!(mock.station.telemetry_addr().unwrap().port(), 8001);
+    }
+
+    #[test]
+    fn test_command_frames_acknowledged_and_retransmitted_under_cop1() {
+        let mock = mock_station(GroundStationConfig {
+            cop1: Cop1Config {
+                timer_ms: 1_000,
+                transmission_limit: 2,
+                ..Cop1Config::default()
+            },
+            ..GroundStationConfig::default()
+        });
+        let mut receiver = mock.station.telemetry_receiver().unwrap();
+        let satellite = SocketAddr::from(([127, 0, 0, 1], 9001));
+        let mut farm = Farm::default();
+        let report = |farm: &Farm, sequence| {
+            let packet = farm.clcw().to_packet(sequence).unwrap();
+            packet.to_bytes().unwrap().to_vec()
+        };
+
+        // The first frame is lost and the second is out of order
+        mock.station
+            .send_command(Command::system_status_request())
+            .unwrap();
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+        let sent = mock.command.sent();
+        let second = TcTransferFrame::from_bytes(&sent[1].0).unwrap();
+        assert_eq!(second.sequence, 1);
+        assert_eq!(farm.receive(&second, true), FarmVerdict::Discard);
+        mock.telemetry.deliver(&report(&farm, 1), satellite);
+        while receiver.poll() {}
+
+        // The CLCW asks for both again, and they are accepted in order
+        mock.station.service_cop1().unwrap();
+        let sent = mock.command.sent();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[2].0, sent[0].0);
+        for (bytes, _) in &sent[2..] {
+            let frame = TcTransferFrame::from_bytes(bytes).unwrap();
+            assert_eq!(farm.receive(&frame, true), FarmVerdict::Accept);
+        }
+        mock.telemetry.deliver(&report(&farm, 2), satellite);
+        while receiver.poll() {}
+        assert_eq!(mock.station.cop1().outstanding(), 0);
+
+        // Unanswered through T1 twice: the transmission limit stops commanding
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+        mock.clock.advance(1_000);
+        mock.station.service_cop1().unwrap();
+        mock.clock.advance(1_000);
+        assert!(mock.station.service_cop1().is_err());
+        assert_eq!(mock.station.cop1().alert(), Some(Cop1Alert::Limit));
+        assert!(mock
+            .station
+            .send_command(Command::telemetry_request())
+            .is_err());
+
+        // Set V(R) restarts it once the satellite confirms the new sequence
+        mock.station.set_cop1_sequence(3).unwrap();
+        let control = TcTransferFrame::from_bytes(&mock.command.sent().last().unwrap().0).unwrap();
+        assert_eq!(farm.receive(&control, true), FarmVerdict::Control);
+        mock.telemetry.deliver(&report(&farm, 3), satellite);
+        while receiver.poll() {}
+        assert_eq!(mock.station.cop1().state(), FopState::Active);
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_command_load_stamped_and_counted_at_uplink() {
+        let mock = mock_station(GroundStationConfig::default());
+        mock.station
+            .send_command(Command::telemetry_request())
+            .unwrap();
+
+        let mut load = CommandLoad::new(5);
+        for sequence in 1..=2 {
+            load.push(CommandLoadEntry {
+                sequence,
+                release_time: Some(100 + u64::from(sequence)),
+                sequence_count: 0,
+                command: SpaceCommand::SendStatus {
+                    status_type: StatusType::SystemHealth,
+                    include_diagnostics: false,
+                    format: ReportFormat::Binary,
+                },
+            })
+            .unwrap();
+        }
+        let manifest = mock
+            .station
+            .uplink_command_load(&load, &LoadConstraints::default())
+            .unwrap();
+        assert!(manifest.is_fully_accepted());
+
+        // Entries count on from the direct command on their APID
+        let sent = mock.command.sent();
+        let frame = TcTransferFrame::from_bytes(&sent[1].0).unwrap();
+        let (_, data) = wire::split_frame(&frame.data).unwrap();
+        let uplinked = CommandLoad::from_bytes(data).unwrap();
+        assert_eq!(uplinked.uplink_time, 10);
+        let counts: Vec<u16> = uplinked.entries.iter().map(|e| e.sequence_count).collect();
+        assert_eq!(counts, vec![2, 3]);
+    }
+}
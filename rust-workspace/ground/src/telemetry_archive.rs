@@ -0,0 +1,362 @@
+//! Persistent telemetry archive and replay
+//!
+//! The station's telemetry history keeps the newest packets in memory only.
+//! [`TelemetryArchive`] keeps the same rolling window and also appends every
+//! accepted telemetry packet to a file, so telemetry from earlier passes and
+//! earlier sessions can be found again by reception time, APID or measurement
+//! ID, and replayed through the console displays for post-pass analysis.
+//!
+//! The archive is an append-only binary file of records, each laid out as:
+//!
+//! | Bytes | Field                                               |
+//! |-------|-----------------------------------------------------|
+//! | 8     | Reception time, ms since the Unix epoch, big-endian |
+//! | 4     | Packet length, big-endian                           |
+//! | n     | Packet as accepted, with its CCSDS header and CRC   |
+//!
+//! Packets are stored after link security has opened them and decoded again
+//! when read, so the file depends on the wire format only. A record cut short
+//! by a crash is dropped when the archive is reopened.
+//!
+//! # Requirements Traceability
+//! - FN-ARC-001: Every accepted telemetry packet persisted across restarts
+//! - FN-ARC-002: Archived telemetry queried by time, APID and measurement ID
+//!   and replayed through the displays
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use space_comms_shared::{
+    ccsds::SpacePacketHeader, telemetry::TelemetryPacket, Result, SpaceCommError,
+};
+
+use crate::parse_telemetry_packet;
+use crate::seams::{RollingTelemetryStore, TelemetryStore};
+
+/// Size of a record's reception time and length fields, bytes
+const RECORD_HEADER_LEN: usize = 12;
+
+/// A telemetry packet as received
+#[derive(Debug, Clone)]
+pub struct StoredTelemetry {
+    /// Reception time, milliseconds since the Unix epoch
+    pub received_ms: u64,
+    /// APID the packet was downlinked on
+    pub apid: u16,
+    /// Packet bytes as accepted, with header and CRC
+    pub frame: Vec<u8>,
+    /// Decoded packet
+    pub packet: TelemetryPacket,
+}
+
+impl StoredTelemetry {
+    /// Decode a received telemetry packet
+    ///
+    /// # Arguments
+    /// * `frame` - Packet bytes as accepted, after link security
+    /// * `received_ms` - Reception time, milliseconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Decoded packet, or error if it is not a valid
+    ///   telemetry packet
+    pub fn parse(frame: &[u8], received_ms: u64) -> Result<Self> {
+        let packet = parse_telemetry_packet(frame)?;
+        let header = SpacePacketHeader::from_bytes(frame)?;
+        Ok(Self {
+            received_ms,
+            apid: header.apid,
+            frame: frame.to_vec(),
+            packet,
+        })
+    }
+}
+
+/// Selection of stored telemetry; a field left `None` matches every packet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TelemetryQuery {
+    /// Earliest reception time, ms since the Unix epoch, inclusive
+    pub from_ms: Option<u64>,
+    /// Latest reception time, ms since the Unix epoch, exclusive
+    pub until_ms: Option<u64>,
+    /// APID the packet was downlinked on
+    pub apid: Option<u16>,
+    /// Measurement the packet must carry
+    pub measurement_id: Option<u16>,
+}
+
+impl TelemetryQuery {
+    /// Parse console arguments: any of `from <ms>`, `until <ms>`,
+    /// `apid <n>` and `id <n>`, numbers in decimal or `0x` hexadecimal
+    ///
+    /// # Returns
+    /// * `Option<Self>` - Query, or `None` for an unknown keyword or a
+    ///   malformed number
+    pub fn parse(args: &[&str]) -> Option<Self> {
+        let mut query = Self::default();
+        for pair in args.chunks(2) {
+            let [keyword, value] = pair else {
+                return None;
+            };
+            match *keyword {
+                "from" => query.from_ms = Some(parse_number(value)?),
+                "until" => query.until_ms = Some(parse_number(value)?),
+                "apid" => query.apid = Some(u16::try_from(parse_number(value)?).ok()?),
+                "id" => query.measurement_id = Some(u16::try_from(parse_number(value)?).ok()?),
+                _ => return None,
+            }
+        }
+        Some(query)
+    }
+
+    /// Whether a stored packet is selected
+    ///
+    /// # Requirements Traceability
+    /// - FN-ARC-002: Selection by time range, APID and measurement ID
+    pub fn matches(&self, record: &StoredTelemetry) -> bool {
+        self.from_ms.is_none_or(|from| record.received_ms >= from)
+            && self.until_ms.is_none_or(|until| record.received_ms < until)
+            && self.apid.is_none_or(|apid| record.apid == apid)
+            && self.measurement_id.is_none_or(|id| {
+                record
+                    .packet
+                    .data
+                    .measurements
+                    .iter()
+                    .any(|m| m.measurement_id == id)
+            })
+    }
+}
+
+/// Parse a decimal or `0x` hexadecimal number
+fn parse_number(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Telemetry history backed by an append-only archive file
+///
+/// The newest packets are kept in memory as by [`RollingTelemetryStore`];
+/// queries read the whole archive from disk.
+#[derive(Debug)]
+pub struct TelemetryArchive {
+    path: PathBuf,
+    file: File,
+    recent: RollingTelemetryStore,
+    archived: usize,
+}
+
+impl TelemetryArchive {
+    /// Open the archive file at `path`, reading back its newest packets
+    ///
+    /// The file is created if it does not exist, and truncated to its last
+    /// whole record if the previous session stopped part way through one.
+    ///
+    /// # Arguments
+    /// * `path` - Binary archive file
+    /// * `limit` - Packets kept in memory as the telemetry history
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Archive with its newest packets in memory, or
+    ///   configuration error if the file cannot be read or written
+    ///
+    /// # Requirements Traceability
+    /// - FN-ARC-001: Telemetry of earlier sessions read back at startup
+    pub fn open(path: impl AsRef<Path>, limit: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let unreadable = SpaceCommError::ConfigurationError {
+            parameter: "telemetry_archive_path",
+            value: "<unreadable>",
+            reason: "telemetry archive could not be read",
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .map_err(|_| unreadable.clone())?;
+
+        let mut recent = RollingTelemetryStore::new(limit);
+        let mut archived = 0;
+        let mut whole_len = 0u64;
+        let mut reader = BufReader::new(&file);
+        while let Some((received_ms, frame)) = read_record(&mut reader) {
+            archived += 1;
+            whole_len += (RECORD_HEADER_LEN + frame.len()) as u64;
+            if let Ok(record) = StoredTelemetry::parse(&frame, received_ms) {
+                recent.store(record);
+            }
+        }
+        // Appends must start on a record boundary
+        let file_len = file.metadata().map_err(|_| unreadable.clone())?.len();
+        if file_len > whole_len {
+            file.set_len(whole_len).map_err(|_| unreadable)?;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            recent,
+            archived,
+        })
+    }
+
+    /// Archive file the packets are appended to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of packets in the archive file
+    pub fn archived(&self) -> usize {
+        self.archived
+    }
+
+    /// Append one record to the archive file
+    fn append(&mut self, record: &StoredTelemetry) -> io::Result<()> {
+        let length = u32::try_from(record.frame.len()).map_err(io::Error::other)?;
+        let mut bytes = Vec::with_capacity(RECORD_HEADER_LEN + record.frame.len());
+        bytes.extend_from_slice(&record.received_ms.to_be_bytes());
+        bytes.extend_from_slice(&length.to_be_bytes());
+        bytes.extend_from_slice(&record.frame);
+        // One write per record, so a crash loses at most the record in flight
+        self.file.write_all(&bytes)
+    }
+}
+
+impl TelemetryStore for TelemetryArchive {
+    fn store(&mut self, record: StoredTelemetry) {
+        match self.append(&record) {
+            Ok(()) => self.archived += 1,
+            Err(e) => eprintln!("Failed to archive telemetry: {}", e),
+        }
+        self.recent.store(record);
+    }
+
+    fn packets(&self) -> Vec<TelemetryPacket> {
+        self.recent.packets()
+    }
+
+    fn query(&self, query: &TelemetryQuery) -> Vec<StoredTelemetry> {
+        let Ok(file) = File::open(&self.path) else {
+            return Vec::new();
+        };
+        let mut reader = BufReader::new(file);
+        std::iter::from_fn(|| read_record(&mut reader))
+            .filter(|(received_ms, _)| {
+                query.from_ms.is_none_or(|from| *received_ms >= from)
+                    && query.until_ms.is_none_or(|until| *received_ms < until)
+            })
+            .filter_map(|(received_ms, frame)| StoredTelemetry::parse(&frame, received_ms).ok())
+            .filter(|record| query.matches(record))
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.recent.len()
+    }
+}
+
+/// Read the next whole record, `None` at the end of the file or of the last
+/// whole record
+fn read_record(reader: &mut impl Read) -> Option<(u64, Vec<u8>)> {
+    let mut header = [0u8; RECORD_HEADER_LEN];
+    reader.read_exact(&mut header).ok()?;
+    let received_ms = u64::from_be_bytes(header[..8].try_into().ok()?);
+    let length = u32::from_be_bytes(header[8..].try_into().ok()?);
+
+    let mut frame = vec![0u8; length as usize];
+    reader.read_exact(&mut frame).ok()?;
+    Some((received_ms, frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        ccsds::{PacketType, SpacePacket},
+        telemetry::{Measurement, MeasurementQuality, MeasurementValue, TelemetryData},
+        types::{ComponentId, HealthStatus},
+    };
+
+    fn frame(apid: u16, sequence: u16, id: u16) -> Vec<u8> {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: Default::default(),
+            health_status: HealthStatus::Good,
+        };
+        data.measurements
+            .push(Measurement {
+                measurement_id: id,
+                value: MeasurementValue::Float(1.0),
+                unit: "",
+                quality: MeasurementQuality::Good,
+            })
+            .unwrap();
+        let payload = data.to_payload().unwrap();
+        SpacePacket::new(PacketType::Telemetry, apid, sequence, &payload, None)
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+            .to_vec()
+    }
+
+    fn record(received_ms: u64, apid: u16, id: u16) -> StoredTelemetry {
+        StoredTelemetry::parse(&frame(apid, received_ms as u16, id), received_ms).unwrap()
+    }
+
+    fn received(records: &[StoredTelemetry]) -> Vec<u64> {
+        records.iter().map(|r| r.received_ms).collect()
+    }
+
+    #[test]
+    fn test_archive_queries_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("telemetry.bin");
+
+        let mut archive = TelemetryArchive::open(&path, 2).unwrap();
+        archive.store(record(1_000, 0x100, 0x0001));
+        archive.store(record(2_000, 0x101, 0x0002));
+        archive.store(record(3_000, 0x100, 0x0002));
+        assert_eq!((archive.len(), archive.archived()), (2, 3));
+
+        let all = archive.query(&TelemetryQuery::default());
+        assert_eq!(received(&all), vec![1_000, 2_000, 3_000]);
+        let by_time = TelemetryQuery {
+            from_ms: Some(2_000),
+            until_ms: Some(3_000),
+            ..TelemetryQuery::default()
+        };
+        assert_eq!(received(&archive.query(&by_time)), vec![2_000]);
+        let by_apid = TelemetryQuery::parse(&["apid", "0x100"]).unwrap();
+        assert_eq!(received(&archive.query(&by_apid)), vec![1_000, 3_000]);
+        let by_id = TelemetryQuery::parse(&["id", "2", "from", "2500"]).unwrap();
+        assert_eq!(received(&archive.query(&by_id)), vec![3_000]);
+
+        // A record cut short by a crash is dropped and appends carry on
+        drop(archive);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0, 0, 0]).unwrap();
+        let mut reopened = TelemetryArchive::open(&path, 2).unwrap();
+        let sequences: Vec<u32> = reopened.packets().iter().map(|p| p.sequence).collect();
+        assert_eq!(sequences, vec![2_000, 3_000]);
+        reopened.store(record(4_000, 0x101, 0x0001));
+        let all = reopened.query(&TelemetryQuery::default());
+        assert_eq!(received(&all), vec![1_000, 2_000, 3_000, 4_000]);
+        assert_eq!(all[3].apid, 0x101);
+    }
+
+    #[test]
+    fn test_query_parse_rejects_malformed_arguments() {
+        assert_eq!(TelemetryQuery::parse(&[]), Some(TelemetryQuery::default()));
+        assert_eq!(TelemetryQuery::parse(&["apid"]), None);
+        assert_eq!(TelemetryQuery::parse(&["apid", "0x10000"]), None);
+        assert_eq!(TelemetryQuery::parse(&["band", "1"]), None);
+        assert_eq!(
+            TelemetryQuery::parse(&["until", "5"]).unwrap().until_ms,
+            Some(5)
+        );
+    }
+}
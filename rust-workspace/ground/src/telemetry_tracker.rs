@@ -0,0 +1,232 @@
+//! Latest downlinked measurement values with stale-data detection
+//!
+//! # Requirements Traceability
+//! - FN-TLM-003: Measurements reported stale once overdue
+
+use std::collections::HashMap;
+
+use space_comms_shared::{
+    mission_phase::MissionPhase,
+    telemetry::{
+        measurement_ids, stale_after_ms, Measurement, MeasurementQuality, MeasurementValue,
+        TelemetryData, TelemetrySet,
+    },
+    types::OperationalMode,
+};
+
+/// Latest value of every downlinked measurement with stale-data detection
+///
+/// - **ID**: FN-TLM-003
+/// - **Requirement**: Report a measurement as `Stale` once no update has
+///   arrived within [`STALE_AFTER_PERIODS`] of its dictionary period, scaled
+///   by the telemetry set the satellite is currently downlinking and the
+///   telemetry rate of its mission phase.
+/// - **Inputs**: Decoded telemetry and the ground receive time in ms.
+/// - **Outputs**: Measurements with the received quality, degraded to `Stale`
+///   when overdue. Measurements already worse than `Stale` keep their quality.
+/// - **Side Effects**: None; the received quality is never overwritten.
+/// - **Failure Modes**: Measurements outside the dictionary are never marked
+///   stale.
+///
+/// [`STALE_AFTER_PERIODS`]: space_comms_shared::telemetry::STALE_AFTER_PERIODS
+#[derive(Debug, Clone)]
+pub struct TelemetryTracker {
+    /// Latest measurement per ID with its receive time in ms
+    latest: HashMap<u16, (Measurement, u64)>,
+    /// Telemetry set implied by the last downlinked operational mode
+    set: TelemetrySet,
+    /// Last downlinked mission phase
+    phase: MissionPhase,
+}
+
+impl Default for TelemetryTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TelemetryTracker {
+    /// Create an empty tracker assuming the full telemetry set at the
+    /// nominal operations rate
+    pub fn new() -> Self {
+        Self {
+            latest: HashMap::new(),
+            set: TelemetrySet::Full,
+            phase: MissionPhase::NominalOps,
+        }
+    }
+
+    /// Mission phase the satellite last reported
+    pub fn mission_phase(&self) -> MissionPhase {
+        self.phase
+    }
+
+    /// Record every measurement of a telemetry frame
+    ///
+    /// # Arguments
+    /// * `data` - Decoded telemetry frame
+    /// * `received_ms` - Ground receive time in milliseconds
+    pub fn update(&mut self, data: &TelemetryData, received_ms: u64) {
+        for measurement in data.measurements.iter() {
+            if measurement.measurement_id == measurement_ids::OPERATIONAL_MODE {
+                // Safe mode slows the downlink; stretch the stale thresholds to match
+                self.set = match measurement.value {
+                    MeasurementValue::Integer(code) if code == OperationalMode::Safe as i64 => {
+                        TelemetrySet::SafeMode
+                    }
+                    _ => TelemetrySet::Full,
+                };
+            }
+            if measurement.measurement_id == measurement_ids::MISSION_PHASE {
+                // The phase sets the default telemetry rate; unknown codes keep the last
+                if let Some(phase) = match measurement.value {
+                    MeasurementValue::Integer(code) => {
+                        u8::try_from(code).ok().and_then(MissionPhase::from_code)
+                    }
+                    _ => None,
+                } {
+                    self.phase = phase;
+                }
+            }
+            self.latest.insert(
+                measurement.measurement_id,
+                (measurement.clone(), received_ms),
+            );
+        }
+    }
+
+    /// Latest value of a measurement with its quality as of `now_ms`
+    pub fn current(&self, measurement_id: u16, now_ms: u64) -> Option<Measurement> {
+        self.latest
+            .get(&measurement_id)
+            .map(|(measurement, received_ms)| self.aged(measurement, *received_ms, now_ms))
+    }
+
+    /// Latest value of every tracked measurement, ordered by ID
+    pub fn snapshot(&self, now_ms: u64) -> Vec<Measurement> {
+        let mut measurements: Vec<Measurement> = self
+            .latest
+            .values()
+            .map(|(measurement, received_ms)| self.aged(measurement, *received_ms, now_ms))
+            .collect();
+        measurements.sort_by_key(|m| m.measurement_id);
+        measurements
+    }
+
+    /// IDs of measurements that are stale as of `now_ms`, ordered by ID
+    pub fn stale_ids(&self, now_ms: u64) -> Vec<u16> {
+        self.snapshot(now_ms)
+            .into_iter()
+            .filter(|m| m.quality == MeasurementQuality::Stale)
+            .map(|m| m.measurement_id)
+            .collect()
+    }
+
+    fn aged(&self, measurement: &Measurement, received_ms: u64, now_ms: u64) -> Measurement {
+        let mut measurement = measurement.clone();
+        let policy = self.phase.policy();
+        if let Some(limit) = stale_after_ms(measurement.measurement_id, self.set)
+            .map(|limit| policy.telemetry_interval_ms(limit))
+        {
+            if now_ms.saturating_sub(received_ms) > limit {
+                measurement.quality = measurement.quality.worst(MeasurementQuality::Stale);
+            }
+        }
+        measurement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::telemetry_frame;
+
+    #[test]
+    fn test_tracker_marks_overdue_values_stale() {
+        let mut tracker = TelemetryTracker::new();
+        let id = measurement_ids::BATTERY_TEMPERATURE;
+        tracker.update(
+            &telemetry_frame(0, &[(id, MeasurementQuality::Good)]),
+            10_000,
+        );
+
+        // Full set: 100 ms period, stale after three missed updates
+        assert_eq!(
+            tracker.current(id, 10_300).unwrap().quality,
+            MeasurementQuality::Good
+        );
+        assert_eq!(
+            tracker.current(id, 10_301).unwrap().quality,
+            MeasurementQuality::Stale
+        );
+        assert_eq!(tracker.stale_ids(10_301), vec![id]);
+
+        // A fresh update clears the stale flag
+        tracker.update(
+            &telemetry_frame(0, &[(id, MeasurementQuality::Good)]),
+            10_400,
+        );
+        assert!(tracker.stale_ids(10_500).is_empty());
+        assert!(tracker.current(0x0042, 10_500).is_none());
+    }
+
+    #[test]
+    fn test_tracker_keeps_worse_quality_and_follows_safe_mode() {
+        let mut tracker = TelemetryTracker::new();
+        let id = measurement_ids::BATTERY_VOLTAGE;
+        let mut frame = telemetry_frame(0, &[(id, MeasurementQuality::Invalid)]);
+        frame
+            .measurements
+            .push(Measurement {
+                measurement_id: measurement_ids::OPERATIONAL_MODE,
+                value: MeasurementValue::Integer(OperationalMode::Safe as i64),
+                unit: "",
+                quality: MeasurementQuality::Good,
+            })
+            .unwrap();
+        tracker.update(&frame, 0);
+
+        // Safe-mode downlink is 50x slower, so 1 s without an update is fine
+        let mode = tracker
+            .current(measurement_ids::OPERATIONAL_MODE, 1_000)
+            .unwrap();
+        assert_eq!(mode.quality, MeasurementQuality::Good);
+        // Invalid values stay invalid rather than improving to stale
+        assert_eq!(
+            tracker.current(id, 60_000).unwrap().quality,
+            MeasurementQuality::Invalid
+        );
+        assert_eq!(
+            tracker.stale_ids(60_000),
+            vec![measurement_ids::OPERATIONAL_MODE]
+        );
+    }
+
+    #[test]
+    fn test_tracker_follows_mission_phase_rate() {
+        let mut tracker = TelemetryTracker::new();
+        let id = measurement_ids::BATTERY_TEMPERATURE;
+        let mut frame = telemetry_frame(0, &[(id, MeasurementQuality::Good)]);
+        frame
+            .measurements
+            .push(Measurement {
+                measurement_id: measurement_ids::MISSION_PHASE,
+                value: MeasurementValue::Integer(MissionPhase::Decommissioning as i64),
+                unit: "",
+                quality: MeasurementQuality::Good,
+            })
+            .unwrap();
+        tracker.update(&frame, 0);
+        assert_eq!(tracker.mission_phase(), MissionPhase::Decommissioning);
+
+        // Decommissioning reports every 500 ms, so 1 s without an update is fine
+        assert_eq!(
+            tracker.current(id, 1_000).unwrap().quality,
+            MeasurementQuality::Good
+        );
+        assert_eq!(
+            tracker.current(id, 1_501).unwrap().quality,
+            MeasurementQuality::Stale
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Fixtures shared by the unit tests of the station modules
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use space_comms_shared::{
+    ccsds::{PacketType, SpacePacket},
+    messaging::Message,
+    telemetry::{Measurement, MeasurementQuality, MeasurementValue, TelemetryData},
+    types::{HealthStatus, MessageId},
+};
+
+use crate::{
+    command::Command, seams, GroundStation, GroundStationConfig, StationSeams, SATELLITE_COMPONENT,
+};
+
+pub(crate) fn telemetry_frame(
+    timestamp: u64,
+    values: &[(u16, MeasurementQuality)],
+) -> TelemetryData {
+    let mut data = TelemetryData {
+        source: SATELLITE_COMPONENT,
+        timestamp,
+        measurements: Default::default(),
+        health_status: HealthStatus::Good,
+    };
+    for &(measurement_id, quality) in values {
+        data.measurements
+            .push(Measurement {
+                measurement_id,
+                value: MeasurementValue::Float(1.0),
+                unit: "",
+                quality,
+            })
+            .unwrap();
+    }
+    data
+}
+
+pub(crate) fn command_message(command: &Command, id: u64) -> Message {
+    command.to_message(MessageId::from_value(id), 0).unwrap()
+}
+
+/// Station on mock seams, with the mocks kept for the test
+pub(crate) struct MockStation {
+    pub(crate) station: GroundStation,
+    pub(crate) telemetry: Arc<seams::MockTransport>,
+    pub(crate) command: Arc<seams::MockTransport>,
+    pub(crate) emergency: Arc<seams::MockTransport>,
+    pub(crate) clock: Arc<seams::MockClock>,
+    pub(crate) store: seams::MockTelemetryStore,
+}
+
+pub(crate) fn mock_station(config: GroundStationConfig) -> MockStation {
+    let addr = |port| SocketAddr::from(([127, 0, 0, 1], port));
+    let telemetry = Arc::new(seams::MockTransport::new(addr(8001)));
+    let command = Arc::new(seams::MockTransport::new(addr(8002)));
+    let emergency = Arc::new(seams::MockTransport::new(addr(8003)));
+    let clock = Arc::new(seams::MockClock::new(10_000));
+    let store = seams::MockTelemetryStore::new();
+    let station = GroundStation::with_seams(
+        config,
+        StationSeams {
+            telemetry: telemetry.clone(),
+            command: command.clone(),
+            emergency: emergency.clone(),
+            clock: clock.clone(),
+            store: Box::new(store.clone()),
+        },
+    )
+    .unwrap();
+    MockStation {
+        station,
+        telemetry,
+        command,
+        emergency,
+        clock,
+        store,
+    }
+}
+
+pub(crate) fn housekeeping_bytes(sequence: u16, id: u16) -> Vec<u8> {
+    let payload = telemetry_frame(0, &[(id, MeasurementQuality::Good)])
+        .to_payload()
+        .unwrap();
+    SpacePacket::new(PacketType::Telemetry, 0x100, sequence, &payload, None)
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+        .to_vec()
+}
@@ -0,0 +1,413 @@
+//! Command execution verification archive
+//!
+//! Every command the satellite executes is followed by an execution report on
+//! the downlink. The archive keeps each report with its ground receipt time so
+//! operators can show, command by command, that execution completed within the
+//! budget of its priority. The archive exports as CSV for compliance evidence
+//! and summarises results and budget overruns for the console.
+//!
+//! The archive also times each command from uplink to its execution report,
+//! the onboard acknowledgment. Sends are matched to reports by priority and
+//! packet sequence count, and the latencies of the last [`LATENCY_WINDOW`]
+//! commands of each priority give rolling p50/p95/p99 percentiles. These
+//! measure REQ-PF-001 in operation: acknowledgment within
+//! [`ACKNOWLEDGMENT_LIMIT_MS`] for [`REQUIRED_WITHIN_LIMIT_PERCENT`] of
+//! commands.
+//!
+//! Like the scheduler, the archive does not read the clock: the caller passes
+//! the send and receipt times in milliseconds since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-VER-001: Archive of per-command execution reports
+//! - FN-VER-002: Compliance evidence export with budget verdicts
+//! - FN-VER-003: Rolling send-to-acknowledgment latency percentiles per
+//!   priority (REQ-PF-001)
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use space_comms_req::req;
+use space_comms_shared::execution_report::{ExecutionReport, ExecutionResult};
+use space_comms_shared::messaging::MessagePriority;
+
+/// CSV header written by [`VerificationArchive::to_csv`]
+pub const CSV_HEADER: &str = "received_unix_ms,command_id,sequence_count,priority,result,\
+execution_time_us,budget_ms,within_budget";
+
+/// Latencies kept per priority for the rolling percentiles
+pub const LATENCY_WINDOW: usize = 256;
+
+/// Sends without a report after this long are no longer matched, ms
+pub const ACKNOWLEDGMENT_TIMEOUT_MS: u64 = 60_000;
+
+/// REQ-PF-001 command acknowledgment limit, ms
+pub const ACKNOWLEDGMENT_LIMIT_MS: u64 = 100;
+
+/// REQ-PF-001 share of commands acknowledged within the limit, percent
+pub const REQUIRED_WITHIN_LIMIT_PERCENT: f64 = 99.5;
+
+/// Execution report as received on the ground
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedExecution {
+    /// Ground receipt time, milliseconds since the Unix epoch
+    pub received_unix_ms: u64,
+    /// Report as downlinked
+    pub report: ExecutionReport,
+}
+
+/// Counts of archived executions by outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationSummary {
+    /// Reports archived
+    pub total: usize,
+    /// Commands that completed
+    pub completed: usize,
+    /// Commands that failed during execution
+    pub failed: usize,
+    /// Commands refused before execution
+    pub rejected: usize,
+    /// Commands whose execution time exceeded their budget
+    pub overruns: usize,
+}
+
+impl VerificationSummary {
+    /// Count one execution report
+    pub fn record(&mut self, report: &ExecutionReport) {
+        self.total += 1;
+        match report.result {
+            ExecutionResult::Completed => self.completed += 1,
+            ExecutionResult::Failed => self.failed += 1,
+            ExecutionResult::Rejected => self.rejected += 1,
+        }
+        if !report.within_budget() {
+            self.overruns += 1;
+        }
+    }
+}
+
+/// Rolling send-to-acknowledgment latency of one priority
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    /// Priority the commands were sent at
+    pub priority: MessagePriority,
+    /// Latencies the percentiles are taken over
+    pub samples: usize,
+    /// Median latency, ms
+    pub p50_ms: u64,
+    /// 95th percentile latency, ms
+    pub p95_ms: u64,
+    /// 99th percentile latency, ms
+    pub p99_ms: u64,
+    /// Share of the samples within [`ACKNOWLEDGMENT_LIMIT_MS`], percent
+    pub within_limit_percent: f64,
+}
+
+impl LatencyPercentiles {
+    /// Percentiles of `latencies_ms`, or `None` without samples
+    fn of(priority: MessagePriority, latencies_ms: &VecDeque<u64>) -> Option<Self> {
+        if latencies_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = latencies_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let rank = |percent: usize| sorted[(sorted.len() * percent).div_ceil(100) - 1];
+        let within = sorted
+            .iter()
+            .filter(|&&ms| ms <= ACKNOWLEDGMENT_LIMIT_MS)
+            .count();
+        Some(Self {
+            priority,
+            samples: sorted.len(),
+            p50_ms: rank(50),
+            p95_ms: rank(95),
+            p99_ms: rank(99),
+            within_limit_percent: within as f64 * 100.0 / sorted.len() as f64,
+        })
+    }
+
+    /// Whether the priority meets REQ-PF-001 over the window
+    #[req("REQ-PF-001")]
+    pub fn is_compliant(&self) -> bool {
+        self.within_limit_percent >= REQUIRED_WITHIN_LIMIT_PERCENT
+    }
+}
+
+/// Archive of downlinked command execution reports
+#[derive(Debug, Clone, Default)]
+pub struct VerificationArchive {
+    entries: Vec<ArchivedExecution>,
+    /// Send time of commands awaiting their report, by priority and
+    /// sequence count
+    pending: BTreeMap<(MessagePriority, u16), u64>,
+    /// Latest send-to-report latencies of each priority, ms
+    latencies: BTreeMap<MessagePriority, VecDeque<u64>>,
+}
+
+impl VerificationArchive {
+    /// Create an empty archive
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note a command sent at `sent_unix_ms`, to time it to its report
+    ///
+    /// Sends older than [`ACKNOWLEDGMENT_TIMEOUT_MS`] are dropped unmatched,
+    /// so a sequence count reused after rollover is not matched to a stale
+    /// send.
+    ///
+    /// # Requirements Traceability
+    /// - FN-VER-003: Latency measured from the uplink of the command
+    pub fn command_sent(
+        &mut self,
+        priority: MessagePriority,
+        sequence_count: u16,
+        sent_unix_ms: u64,
+    ) {
+        self.pending
+            .retain(|_, sent| sent_unix_ms.saturating_sub(*sent) <= ACKNOWLEDGMENT_TIMEOUT_MS);
+        self.pending
+            .insert((priority, sequence_count), sent_unix_ms);
+    }
+
+    /// Archive a report received at `received_unix_ms`
+    ///
+    /// A report matching a noted send adds its latency to the window of its
+    /// priority.
+    ///
+    /// # Requirements Traceability
+    /// - FN-VER-001: Every downlinked report is kept in receipt order
+    /// - FN-VER-003: Send-to-acknowledgment latency per priority
+    pub fn record(&mut self, report: ExecutionReport, received_unix_ms: u64) {
+        if let Some(sent) = self
+            .pending
+            .remove(&(report.priority, report.sequence_count))
+        {
+            let window = self.latencies.entry(report.priority).or_default();
+            if window.len() == LATENCY_WINDOW {
+                window.pop_front();
+            }
+            window.push_back(received_unix_ms.saturating_sub(sent));
+        }
+        self.entries.push(ArchivedExecution {
+            received_unix_ms,
+            report,
+        });
+    }
+
+    /// Archived executions in receipt order
+    pub fn entries(&self) -> &[ArchivedExecution] {
+        &self.entries
+    }
+
+    /// Archived executions that exceeded their execution time budget
+    pub fn overruns(&self) -> impl Iterator<Item = &ArchivedExecution> {
+        self.entries.iter().filter(|e| !e.report.within_budget())
+    }
+
+    /// Count archived executions by outcome
+    pub fn summary(&self) -> VerificationSummary {
+        self.entries
+            .iter()
+            .fold(VerificationSummary::default(), |mut summary, entry| {
+                summary.record(&entry.report);
+                summary
+            })
+    }
+
+    /// Rolling latency percentiles of each priority with samples, most
+    /// urgent first
+    ///
+    /// # Requirements Traceability
+    /// - FN-VER-003: p50/p95/p99 from send to onboard acknowledgment
+    pub fn latency_percentiles(&self) -> Vec<LatencyPercentiles> {
+        self.latencies
+            .iter()
+            .rev()
+            .filter_map(|(&priority, latencies)| LatencyPercentiles::of(priority, latencies))
+            .collect()
+    }
+
+    /// Export the archive as CSV with [`CSV_HEADER`]
+    ///
+    /// # Requirements Traceability
+    /// - FN-VER-002: One row per execution with its budget verdict
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(CSV_HEADER);
+        out.push('\n');
+        for entry in &self.entries {
+            let report = &entry.report;
+            out.push_str(&format!(
+                "{},0x{:04X},{},{:?},{:?},{},{},{}\n",
+                entry.received_unix_ms,
+                report.command_id,
+                report.sequence_count,
+                report.priority,
+                report.result,
+                report.execution_time_us,
+                report.budget_ms,
+                report.within_budget()
+            ));
+        }
+        out
+    }
+}
+
+/// Latency percentiles as a console table with the REQ-PF-001 verdict
+#[req("REQ-PF-001")]
+pub fn format_latency(percentiles: &[LatencyPercentiles]) -> String {
+    if percentiles.is_empty() {
+        return String::from("No acknowledged commands timed\n");
+    }
+    let mut out = format!(
+        "{:<10} {:>7} {:>8} {:>8} {:>8} {:>8}  REQ-PF-001 (<{} ms)\n",
+        "Priority", "Samples", "p50 ms", "p95 ms", "p99 ms", "Within", ACKNOWLEDGMENT_LIMIT_MS
+    );
+    for stats in percentiles {
+        let _ = writeln!(
+            out,
+            "{:<10} {:>7} {:>8} {:>8} {:>8} {:>7.1}%  {}",
+            format!("{:?}", stats.priority),
+            stats.samples,
+            stats.p50_ms,
+            stats.p95_ms,
+            stats.p99_ms,
+            stats.within_limit_percent,
+            if stats.is_compliant() {
+                "met"
+            } else {
+                "NOT MET"
+            }
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(priority: MessagePriority, result: ExecutionResult, us: u32) -> ExecutionReport {
+        ExecutionReport::new(0x9999, 1, priority, result, us)
+    }
+
+    #[test]
+    fn test_summary_counts_results_and_overruns() {
+        let mut archive = VerificationArchive::new();
+        archive.record(
+            report(MessagePriority::Emergency, ExecutionResult::Completed, 400),
+            10,
+        );
+        archive.record(
+            report(
+                MessagePriority::Emergency,
+                ExecutionResult::Completed,
+                1_500,
+            ),
+            20,
+        );
+        archive.record(
+            report(MessagePriority::Medium, ExecutionResult::Failed, 2_000),
+            30,
+        );
+        archive.record(
+            report(MessagePriority::High, ExecutionResult::Rejected, 10),
+            40,
+        );
+
+        assert_eq!(
+            archive.summary(),
+            VerificationSummary {
+                total: 4,
+                completed: 2,
+                failed: 1,
+                rejected: 1,
+                overruns: 1,
+            }
+        );
+        let overruns: Vec<u64> = archive.overruns().map(|e| e.received_unix_ms).collect();
+        assert_eq!(overruns, vec![20]);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let mut archive = VerificationArchive::new();
+        archive.record(
+            report(
+                MessagePriority::Emergency,
+                ExecutionResult::Completed,
+                1_500,
+            ),
+            1_000,
+        );
+
+        let csv = archive.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "1000,0x9999,1,Emergency,Completed,1500,1,false");
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_latency_percentiles_per_priority() {
+        let mut archive = VerificationArchive::new();
+        // 100 High commands acknowledged 1..=100 ms after sending
+        for sequence in 1..=100u16 {
+            let sent = u64::from(sequence) * 1_000;
+            archive.command_sent(MessagePriority::High, sequence, sent);
+            let mut acknowledged = report(MessagePriority::High, ExecutionResult::Completed, 50);
+            acknowledged.sequence_count = sequence;
+            archive.record(acknowledged, sent + u64::from(sequence));
+        }
+        // One slow Critical command; an unmatched report adds no sample
+        archive.command_sent(MessagePriority::Critical, 7, 500_000);
+        let mut critical = report(MessagePriority::Critical, ExecutionResult::Completed, 50);
+        critical.sequence_count = 7;
+        archive.record(critical, 500_250);
+        archive.record(
+            report(MessagePriority::Low, ExecutionResult::Completed, 50),
+            600_000,
+        );
+
+        let percentiles = archive.latency_percentiles();
+        assert_eq!(percentiles.len(), 2);
+        assert_eq!(percentiles[0].priority, MessagePriority::Critical);
+        assert_eq!(percentiles[0].p99_ms, 250);
+        assert!(!percentiles[0].is_compliant());
+
+        let high = percentiles[1];
+        assert_eq!(
+            (high.samples, high.p50_ms, high.p95_ms, high.p99_ms),
+            (100, 50, 95, 99)
+        );
+        assert_eq!(high.within_limit_percent, 100.0);
+        assert!(high.is_compliant());
+        assert!(format_latency(&percentiles).contains("NOT MET"));
+        assert_eq!(archive.entries().len(), 102);
+    }
+
+    #[test]
+    fn test_latency_window_and_stale_sends() {
+        let mut archive = VerificationArchive::new();
+        archive.command_sent(MessagePriority::Medium, 1, 0);
+        // Sent long after: the first send is dropped unmatched
+        archive.command_sent(MessagePriority::Medium, 2, ACKNOWLEDGMENT_TIMEOUT_MS + 1);
+        archive.record(
+            report(MessagePriority::Medium, ExecutionResult::Completed, 50),
+            70_000,
+        );
+        assert!(archive.latency_percentiles().is_empty());
+
+        for sequence in 0..(LATENCY_WINDOW as u16 + 10) {
+            let sent = 100_000 + u64::from(sequence);
+            archive.command_sent(MessagePriority::Medium, sequence, sent);
+            let mut acknowledged = report(MessagePriority::Medium, ExecutionResult::Completed, 50);
+            acknowledged.sequence_count = sequence;
+            archive.record(acknowledged, sent + 20);
+        }
+        let medium = archive.latency_percentiles()[0];
+        assert_eq!(medium.samples, LATENCY_WINDOW);
+        assert_eq!(medium.p99_ms, 20);
+    }
+}
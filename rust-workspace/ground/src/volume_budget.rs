@@ -0,0 +1,655 @@
+//! Per-pass data volume budgets
+//!
+//! Before a pass, [`PassBudget::plan`] works out the volume the downlink can
+//! carry: the selected band's data rate over the time the satellite is
+//! above the elevation mask ([`PassGeometry`]), less the Reed-Solomon
+//! overhead on coded bands. During the pass the [`VolumeTracker`] counts the
+//! bytes delivered against that budget and against the backlog the
+//! spacecraft has to send, the files of its recorder manifest. As soon as
+//! the backlog left no longer fits in the capacity left, the operator gets
+//! one [`VolumeAlert`] for the pass, with the changes that would make it
+//! fit: a higher data rate on the band, another band, or a more efficient
+//! MODCOD the measured SNR can carry.
+//!
+//! The configured downlink data rate is the rate at the pass's MODCOD, so
+//! a MODCOD change scales it by the ratio of spectral efficiencies and costs
+//! the difference in required Es/N0 out of the link margin. Like the pass
+//! reports, nothing here reads the clock: callers pass times in milliseconds
+//! since the Unix epoch.
+//!
+//! # Requirements Traceability
+//! - FN-VOL-001: Downlink capacity budgeted per pass from geometry and band
+//! - FN-VOL-002: Operator alerted when the backlog will not fit, with
+//!   band and MODCOD suggestions
+//! - REQ-PF-002: Data Transfer Rates (pass volume matched to the backlog)
+
+use std::fmt;
+
+use space_comms_req::req;
+use space_comms_shared::{
+    fec::{FecPolicy, RS_BLOCK_LEN, RS_DATA_LEN},
+    link_config::DirectionalLink,
+    margin::MarginPolicy,
+    types::BandType,
+    Result, SpaceCommError,
+};
+
+/// Elevation below which the downlink is not expected to close, degrees
+pub const DEFAULT_ELEVATION_MASK_DEG: f64 = 10.0;
+
+/// Bytes per megabyte in operator displays
+const BYTES_PER_MB: f64 = 1e6;
+
+/// Modulation and coding of the downlink
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Modcod {
+    /// Name, e.g. `8PSK-2/3`
+    pub name: &'static str,
+    /// Information bits per symbol
+    pub spectral_efficiency: f64,
+    /// Es/N0 the MODCOD needs to close, dB
+    pub required_esn0_db: f64,
+}
+
+/// MODCODs the downlink can switch between, least efficient first (ideal
+/// DVB-S2 thresholds, CCSDS 131.3-B-1)
+pub const MODCODS: [Modcod; 7] = [
+    Modcod::new("QPSK-1/2", 0.988, 1.00),
+    Modcod::new("QPSK-3/4", 1.487, 4.03),
+    Modcod::new("8PSK-2/3", 1.980, 6.62),
+    Modcod::new("8PSK-5/6", 2.478, 9.35),
+    Modcod::new("16APSK-3/4", 2.967, 10.21),
+    Modcod::new("16APSK-5/6", 3.300, 11.03),
+    Modcod::new("32APSK-5/6", 4.119, 13.64),
+];
+
+impl Modcod {
+    /// Define a MODCOD
+    pub const fn new(name: &'static str, spectral_efficiency: f64, required_esn0_db: f64) -> Self {
+        Self {
+            name,
+            spectral_efficiency,
+            required_esn0_db,
+        }
+    }
+
+    /// MODCOD of the table by name, ignoring case
+    pub fn lookup(name: &str) -> Option<Modcod> {
+        MODCODS
+            .iter()
+            .find(|modcod| modcod.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+}
+
+impl Default for Modcod {
+    /// QPSK rate 1/2, the most robust MODCOD
+    fn default() -> Self {
+        MODCODS[0]
+    }
+}
+
+impl fmt::Display for Modcod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Elevation of the satellite over one contact
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassGeometry {
+    /// Contact duration, AOS to LOS, seconds
+    pub duration_s: u32,
+    /// Elevation samples evenly spaced over the contact, degrees; each
+    /// stands for an equal share of the duration
+    pub elevation_deg: Vec<f64>,
+}
+
+impl PassGeometry {
+    /// Contact with the given elevation profile
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The geometry, or a configuration error for a zero
+    ///   duration, no samples or an elevation outside ±90°
+    pub fn new(duration_s: u32, elevation_deg: Vec<f64>) -> Result<Self> {
+        let invalid = |reason| SpaceCommError::ConfigurationError {
+            parameter: "pass_geometry",
+            value: "invalid",
+            reason,
+        };
+        if duration_s == 0 || elevation_deg.is_empty() {
+            return Err(invalid("pass needs a duration and elevation samples"));
+        }
+        if elevation_deg.iter().any(|e| !(-90.0..=90.0).contains(e)) {
+            return Err(invalid("elevation must be within ±90 degrees"));
+        }
+        Ok(Self {
+            duration_s,
+            elevation_deg,
+        })
+    }
+
+    /// Contact rising to `max_elevation_deg` at mid-pass, one sample a
+    /// second along a half-sine elevation arc
+    pub fn arc(duration_s: u32, max_elevation_deg: f64) -> Result<Self> {
+        let samples = duration_s.max(1);
+        let elevation_deg = (0..samples)
+            .map(|i| {
+                let phase = (f64::from(i) + 0.5) / f64::from(samples);
+                max_elevation_deg * (std::f64::consts::PI * phase).sin()
+            })
+            .collect();
+        Self::new(duration_s, elevation_deg)
+    }
+
+    /// Time above `mask_deg` from `elapsed_s` into the contact to LOS, seconds
+    pub fn time_above_s(&self, mask_deg: f64, elapsed_s: f64) -> f64 {
+        let share = f64::from(self.duration_s) / self.elevation_deg.len() as f64;
+        self.elevation_deg
+            .iter()
+            .enumerate()
+            .filter(|(_, &elevation)| elevation >= mask_deg)
+            .map(|(i, _)| {
+                let start = i as f64 * share;
+                (start + share - start.max(elapsed_s)).clamp(0.0, share)
+            })
+            .sum()
+    }
+}
+
+/// Expected downlink capacity of one pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassBudget {
+    /// Elevation over the contact
+    pub geometry: PassGeometry,
+    /// Downlink band and data rate at `modcod`
+    pub downlink: DirectionalLink,
+    /// Modulation and coding of the downlink
+    pub modcod: Modcod,
+    /// Whether downlink frames are Reed-Solomon coded
+    pub coded: bool,
+    /// Elevation the downlink closes above, degrees
+    pub elevation_mask_deg: f64,
+}
+
+impl PassBudget {
+    /// Budget a pass on `downlink`
+    ///
+    /// - **ID**: FN-VOL-001
+    /// - **Requirement**: The volume a pass can carry is known before AOS
+    ///   (REQ-PF-002).
+    /// - **Inputs**: Pass geometry, the downlink selected for the pass, the
+    ///   station's coding policy and the MODCOD the data rate is at.
+    /// - **Outputs**: Budget above [`DEFAULT_ELEVATION_MASK_DEG`].
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: None.
+    #[req("REQ-PF-002")]
+    pub fn plan(
+        geometry: PassGeometry,
+        downlink: DirectionalLink,
+        fec: &FecPolicy,
+        modcod: Modcod,
+    ) -> Self {
+        Self {
+            geometry,
+            downlink,
+            modcod,
+            coded: fec.is_enabled(downlink.band),
+            elevation_mask_deg: DEFAULT_ELEVATION_MASK_DEG,
+        }
+    }
+
+    /// Volume the whole pass can carry, bytes
+    pub fn capacity_bytes(&self) -> u64 {
+        self.remaining_capacity_bytes(0.0)
+    }
+
+    /// Volume the pass can still carry `elapsed_s` after AOS, bytes
+    pub fn remaining_capacity_bytes(&self, elapsed_s: f64) -> u64 {
+        let seconds = self
+            .geometry
+            .time_above_s(self.elevation_mask_deg, elapsed_s);
+        (bytes_per_s(self.downlink.data_rate_bps, self.coded) * seconds) as u64
+    }
+}
+
+/// Frame bytes a data rate carries per second, less the coding overhead
+fn bytes_per_s(data_rate_bps: u64, coded: bool) -> f64 {
+    let bytes = data_rate_bps as f64 / 8.0;
+    if coded {
+        bytes * RS_DATA_LEN as f64 / RS_BLOCK_LEN as f64
+    } else {
+        bytes
+    }
+}
+
+/// Data rate carrying `bytes` in `seconds`, bits per second, rounded up to
+/// a whole kbps
+fn rate_for(bytes: u64, seconds: f64, coded: bool) -> u64 {
+    let bps = bytes as f64 / seconds / bytes_per_s(1_000, coded);
+    (bps.ceil() as u64).max(1) * 1_000
+}
+
+/// Budget against delivery at one moment of a pass
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeStatus {
+    /// Seconds since AOS
+    pub elapsed_s: f64,
+    /// Volume the whole pass was budgeted to carry, bytes
+    pub budget_bytes: u64,
+    /// Volume received so far, bytes
+    pub delivered_bytes: u64,
+    /// Volume the rest of the pass can carry, bytes
+    pub remaining_capacity_bytes: u64,
+    /// Backlog still to come down, bytes, once a manifest has announced it
+    pub backlog_bytes: Option<u64>,
+}
+
+impl VolumeStatus {
+    /// Backlog that will not fit in the rest of the pass, bytes
+    pub fn shortfall_bytes(&self) -> u64 {
+        self.backlog_bytes
+            .unwrap_or(0)
+            .saturating_sub(self.remaining_capacity_bytes)
+    }
+}
+
+impl fmt::Display for VolumeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.1}/{:.1} MB delivered at T+{:.0}s, {:.1} MB capacity left",
+            self.delivered_bytes as f64 / BYTES_PER_MB,
+            self.budget_bytes as f64 / BYTES_PER_MB,
+            self.elapsed_s,
+            self.remaining_capacity_bytes as f64 / BYTES_PER_MB
+        )?;
+        match self.backlog_bytes {
+            Some(backlog) => write!(f, ", {:.1} MB backlog", backlog as f64 / BYTES_PER_MB),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Change that would fit the backlog into the rest of the pass
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Suggestion {
+    /// Raise the downlink data rate on the same band
+    DataRate { data_rate_bps: u64 },
+    /// Move the downlink to another band
+    Band { band: BandType, data_rate_bps: u64 },
+    /// Switch MODCOD, scaling the data rate by the spectral efficiency
+    Modcod { from: Modcod, to: Modcod },
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Suggestion::DataRate { data_rate_bps } => {
+                write!(f, "raise the downlink rate to {} bps", data_rate_bps)
+            }
+            Suggestion::Band {
+                band,
+                data_rate_bps,
+            } => write!(
+                f,
+                "move the downlink to {:?} at {} bps",
+                band, data_rate_bps
+            ),
+            Suggestion::Modcod { from, to } => {
+                write!(f, "switch MODCOD from {} to {}", from, to)
+            }
+        }
+    }
+}
+
+/// Warning that the backlog will not fit in the pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeAlert {
+    /// Budget and delivery when the shortfall was found
+    pub status: VolumeStatus,
+    /// Changes that would fit the backlog, best first; empty if none would
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl fmt::Display for VolumeAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "backlog exceeds the pass by {:.1} MB ({})",
+            self.status.shortfall_bytes() as f64 / BYTES_PER_MB,
+            self.status
+        )?;
+        if self.suggestions.is_empty() {
+            return f.write_str("; no change fits it, the rest stays on board");
+        }
+        for (i, suggestion) in self.suggestions.iter().enumerate() {
+            f.write_str(if i == 0 { "; " } else { ", or " })?;
+            write!(f, "{}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Delivery of the pass in progress
+#[derive(Debug, Clone, Copy)]
+struct OpenPass {
+    aos_unix_ms: u64,
+    delivered_bytes: u64,
+    backlog: Option<(u64, u64)>,
+    alerted: bool,
+}
+
+/// Tracks delivered volume against the pass budget and the backlog
+///
+/// The budget planned before a pass applies to the next pass, or to the
+/// pass in progress, and is used up at LOS.
+#[derive(Debug, Clone)]
+pub struct VolumeTracker {
+    supported_bands: Vec<BandType>,
+    fec: FecPolicy,
+    margins: MarginPolicy,
+    budget: Option<PassBudget>,
+    pass: Option<OpenPass>,
+}
+
+impl VolumeTracker {
+    /// Tracker choosing suggestions among `supported_bands`, coded as
+    /// `fec` and kept within `margins`
+    pub fn new(supported_bands: Vec<BandType>, fec: FecPolicy, margins: MarginPolicy) -> Self {
+        Self {
+            supported_bands,
+            fec,
+            margins,
+            budget: None,
+            pass: None,
+        }
+    }
+
+    /// Budget the next pass, or the pass in progress
+    pub fn plan(&mut self, budget: PassBudget) {
+        self.budget = Some(budget);
+    }
+
+    /// Budget of the next pass or the pass in progress, if planned
+    pub fn budget(&self) -> Option<&PassBudget> {
+        self.budget.as_ref()
+    }
+
+    /// Record a received frame, starting a pass at AOS
+    pub fn downlinked(&mut self, bytes: usize, now_unix_ms: u64) {
+        let pass = self.pass.get_or_insert(OpenPass {
+            aos_unix_ms: now_unix_ms,
+            delivered_bytes: 0,
+            backlog: None,
+            alerted: false,
+        });
+        pass.delivered_bytes += bytes as u64;
+    }
+
+    /// Record the backlog announced by a recorder manifest, bytes; what
+    /// arrives from now on counts against it
+    pub fn backlog_announced(&mut self, bytes: u64) {
+        if let Some(pass) = &mut self.pass {
+            pass.backlog = Some((bytes, pass.delivered_bytes));
+        }
+    }
+
+    /// Budget against delivery of the pass in progress
+    ///
+    /// # Returns
+    /// * `Option<VolumeStatus>` - `None` outside a pass or without a budget
+    pub fn status(&self, now_unix_ms: u64) -> Option<VolumeStatus> {
+        let (pass, budget) = (self.pass.as_ref()?, self.budget.as_ref()?);
+        let elapsed_s = now_unix_ms.saturating_sub(pass.aos_unix_ms) as f64 / 1000.0;
+        Some(VolumeStatus {
+            elapsed_s,
+            budget_bytes: budget.capacity_bytes(),
+            delivered_bytes: pass.delivered_bytes,
+            remaining_capacity_bytes: budget.remaining_capacity_bytes(elapsed_s),
+            backlog_bytes: pass.backlog.map(|(backlog, delivered_before)| {
+                backlog.saturating_sub(pass.delivered_bytes - delivered_before)
+            }),
+        })
+    }
+
+    /// Check whether the backlog still fits in the pass
+    ///
+    /// - **ID**: FN-VOL-002
+    /// - **Requirement**: The operator learns during the pass that the
+    ///   backlog will not come down, while a band or MODCOD change can
+    ///   still help.
+    /// - **Inputs**: Current time, and the lowest downlink SNR measured in
+    ///   the pass, dB, if any; MODCOD changes are only suggested against a
+    ///   measured SNR.
+    /// - **Outputs**: An alert the first time in a pass the backlog exceeds
+    ///   the remaining capacity, `None` otherwise.
+    /// - **Side Effects**: Marks the pass alerted.
+    /// - **Failure Modes**: None; no budget or no announced backlog never
+    ///   alerts.
+    pub fn check(&mut self, now_unix_ms: u64, snr_db: Option<f64>) -> Option<VolumeAlert> {
+        let status = self.status(now_unix_ms)?;
+        let pass = self.pass.as_mut()?;
+        if pass.alerted || status.shortfall_bytes() == 0 {
+            return None;
+        }
+        pass.alerted = true;
+        Some(VolumeAlert {
+            suggestions: self.suggestions(&status, snr_db),
+            status,
+        })
+    }
+
+    /// Changes that would carry the backlog in the rest of the pass
+    fn suggestions(&self, status: &VolumeStatus, snr_db: Option<f64>) -> Vec<Suggestion> {
+        let (Some(budget), Some(backlog)) = (&self.budget, status.backlog_bytes) else {
+            return Vec::new();
+        };
+        let seconds = budget
+            .geometry
+            .time_above_s(budget.elevation_mask_deg, status.elapsed_s);
+        if seconds <= 0.0 {
+            return Vec::new();
+        }
+        let current = budget.downlink;
+        let mut suggestions = Vec::new();
+
+        let needed_bps = rate_for(backlog, seconds, budget.coded);
+        if needed_bps <= current.band.typical_data_rate_range().1 {
+            suggestions.push(Suggestion::DataRate {
+                data_rate_bps: needed_bps,
+            });
+        }
+
+        // The least weather-sensitive other band that can carry it
+        let band = self
+            .supported_bands
+            .iter()
+            .filter(|&&band| band != current.band)
+            .map(|&band| (band, rate_for(backlog, seconds, self.fec.is_enabled(band))))
+            .filter(|(band, bps)| *bps <= band.typical_data_rate_range().1)
+            .min_by(|(a, _), (b, _)| a.weather_sensitivity().total_cmp(&b.weather_sensitivity()));
+        if let Some((band, data_rate_bps)) = band {
+            suggestions.push(Suggestion::Band {
+                band,
+                data_rate_bps,
+            });
+        }
+
+        // The most robust MODCOD efficient enough, within the link margin
+        if let Some(snr_db) = snr_db {
+            let spare_db = snr_db - self.margins.required_snr_db();
+            let needed = budget.modcod.spectral_efficiency * needed_bps as f64
+                / current.data_rate_bps.max(1) as f64;
+            let modcod = MODCODS.iter().find(|modcod| {
+                modcod.spectral_efficiency >= needed
+                    && modcod.required_esn0_db - budget.modcod.required_esn0_db <= spare_db
+            });
+            if let Some(&to) = modcod.filter(|to| to.name != budget.modcod.name) {
+                suggestions.push(Suggestion::Modcod {
+                    from: budget.modcod,
+                    to,
+                });
+            }
+        }
+        suggestions
+    }
+
+    /// Close the pass at LOS, using up its budget
+    ///
+    /// # Returns
+    /// * `Option<VolumeStatus>` - Final status, or `None` if no pass was in
+    ///   progress or it had no budget
+    pub fn close(&mut self, los_unix_ms: u64) -> Option<VolumeStatus> {
+        let status = self.status(los_unix_ms);
+        if self.pass.take().is_some() {
+            self.budget = None;
+        }
+        status
+    }
+}
+
+/// Format a pass budget for the operator
+pub fn format_pass_budget(budget: &PassBudget) -> String {
+    let above_s = budget.geometry.time_above_s(budget.elevation_mask_deg, 0.0);
+    format!(
+        "Pass budget: {:.1} MB on {:?} at {} bps {}{}, {:.0} of {} s above {:.0}°\n",
+        budget.capacity_bytes() as f64 / BYTES_PER_MB,
+        budget.downlink.band,
+        budget.downlink.data_rate_bps,
+        budget.modcod,
+        if budget.coded { " RS(255,223)" } else { "" },
+        above_s,
+        budget.geometry.duration_s,
+        budget.elevation_mask_deg
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1_000_000;
+
+    fn budget() -> PassBudget {
+        // 600 s pass, X-band at 2 Mbps uncoded
+        PassBudget::plan(
+            PassGeometry::arc(600, 60.0).unwrap(),
+            DirectionalLink::new(BandType::XBand, 80, 2_000_000),
+            &FecPolicy::default(),
+            Modcod::default(),
+        )
+    }
+
+    fn tracker() -> VolumeTracker {
+        VolumeTracker::new(
+            vec![BandType::SBand, BandType::XBand, BandType::KaBand],
+            FecPolicy::default(),
+            MarginPolicy::default(),
+        )
+    }
+
+    #[test]
+    fn test_pass_budget_from_geometry() {
+        let geometry = PassGeometry::arc(600, 60.0).unwrap();
+        // sin⁻¹(10/60) of the arc each side is below the mask
+        let above = geometry.time_above_s(DEFAULT_ELEVATION_MASK_DEG, 0.0);
+        let expected = 600.0 * (1.0 - 2.0 * (10.0f64 / 60.0).asin() / std::f64::consts::PI);
+        assert!((above - expected).abs() <= 2.0, "{} vs {}", above, expected);
+        assert!(geometry.time_above_s(DEFAULT_ELEVATION_MASK_DEG, 300.0) < above / 2.0 + 1.0);
+        assert_eq!(
+            geometry.time_above_s(DEFAULT_ELEVATION_MASK_DEG, 600.0),
+            0.0
+        );
+        assert_eq!(
+            PassGeometry::arc(600, 5.0).unwrap().time_above_s(10.0, 0.0),
+            0.0
+        );
+        assert!(PassGeometry::new(0, vec![10.0]).is_err());
+        assert!(PassGeometry::new(10, vec![95.0]).is_err());
+
+        let plan = budget();
+        assert!(!plan.coded);
+        assert_eq!(plan.capacity_bytes(), (250_000.0 * above) as u64);
+
+        // Ka-band is coded by default: 223 of every 255 bytes carry data
+        let coded = PassBudget::plan(
+            plan.geometry.clone(),
+            DirectionalLink::new(BandType::KaBand, 80, 2_000_000),
+            &FecPolicy::default(),
+            Modcod::default(),
+        );
+        assert!(coded.coded);
+        assert_eq!(
+            coded.capacity_bytes(),
+            (250_000.0 * above * 223.0 / 255.0) as u64
+        );
+        assert!(format_pass_budget(&coded).contains("RS(255,223)"));
+    }
+
+    #[test]
+    fn test_backlog_alert_with_suggestions() {
+        let mut tracker = tracker();
+        tracker.downlinked(1_000, 0);
+        assert!(tracker.check(1_000, None).is_none(), "no budget yet");
+
+        tracker.plan(budget());
+        let capacity = tracker.budget().unwrap().capacity_bytes();
+        assert!(tracker.check(1_000, None).is_none(), "no backlog yet");
+
+        // A backlog that fits raises nothing
+        tracker.backlog_announced(capacity / 2);
+        assert!(tracker.check(100_000, Some(30.0)).is_none());
+
+        // Twice the pass, with a sixth of it gone: well over twice the rate
+        tracker.backlog_announced(2 * capacity);
+        let alert = tracker.check(100_000, Some(30.0)).unwrap();
+        assert!(alert.status.shortfall_bytes() > capacity);
+        assert!(matches!(
+            alert.suggestions[0],
+            Suggestion::DataRate { data_rate_bps } if data_rate_bps > 4_000_000
+        ));
+        assert!(matches!(
+            alert.suggestions[1],
+            Suggestion::Band {
+                band: BandType::SBand,
+                ..
+            }
+        ));
+        assert_eq!(
+            alert.suggestions[2],
+            Suggestion::Modcod {
+                from: Modcod::default(),
+                to: Modcod::lookup("8psk-5/6").unwrap(),
+            }
+        );
+        assert!(alert.to_string().contains("switch MODCOD from QPSK-1/2"));
+
+        // Once per pass
+        assert!(tracker.check(101_000, Some(30.0)).is_none());
+
+        // Delivery counts against the backlog; LOS uses up the budget
+        tracker.downlinked(MB as usize, 102_000);
+        let status = tracker.status(102_000).unwrap();
+        assert_eq!(status.backlog_bytes, Some(2 * capacity - MB));
+        assert_eq!(status.delivered_bytes, MB + 1_000);
+        assert!(tracker.close(600_000).is_some());
+        assert!(tracker.budget().is_none());
+    }
+
+    #[test]
+    fn test_modcod_limited_by_link_margin() {
+        let mut tracker = tracker();
+        tracker.plan(budget());
+        let capacity = tracker.budget().unwrap().capacity_bytes();
+        tracker.downlinked(0, 0);
+        tracker.backlog_announced(2 * capacity);
+
+        // 13 dB measured against 13 dB required leaves no room to switch
+        let alert = tracker.check(0, Some(13.0)).unwrap();
+        assert!(!alert
+            .suggestions
+            .iter()
+            .any(|s| matches!(s, Suggestion::Modcod { .. })));
+    }
+}
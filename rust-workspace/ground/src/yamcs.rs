@@ -0,0 +1,895 @@
+//! YAMCS mission database export and packet interface
+//!
+//! Lets an existing YAMCS deployment, and the web operations stack on top of
+//! it, run against this station. Three pieces:
+//!
+//! - [`mdb_xml`]: XTCE mission database generated from the telemetry
+//!   dictionary and the console command dictionary
+//! - [`instance_yaml`]: YAMCS instance configuration loading that database and
+//!   connecting a UDP telemetry link and a UDP telecommand link to the station
+//! - [`measurement_packets`] / [`decode_command`]: the packets crossing those
+//!   links
+//!
+//! Downlinked telemetry carries a variable list of tagged measurements,
+//! which XTCE containers cannot describe. The station re-emits each
+//! measurement as its own packet on [`YAMCS_MEASUREMENT_APID`]:
+//!
+//! | Offset | Size | Field                                          |
+//! |--------|------|------------------------------------------------|
+//! | 0      | 6    | CCSDS primary header                           |
+//! | 6      | 8    | Timestamp, spacecraft time in ns               |
+//! | 14     | 2    | Measurement ID                                 |
+//! | 16     | 1    | Value kind (high nibble), quality (low nibble) |
+//! | 17     | 4    | Value: `f32`, or `i32` for unitless values     |
+//! | 21     | 2    | CRC-16-CCITT                                   |
+//!
+//! In the database each measurement is a child container restricted on its
+//! measurement ID. Commands go the other way: YAMCS encodes a dictionary
+//! command's arguments in binary after the command ID, and the station
+//! decodes them back through the dictionary, so commands from YAMCS are
+//! validated, audited and uplinked like console commands.
+//!
+//! # Requirements Traceability
+//! - FN-YMC-001: Mission database and instance configuration export
+//! - FN-YMC-002: Measurement packets YAMCS can ingest
+//! - FN-YMC-003: YAMCS commands decoded through the command dictionary
+//! - REQ-IF-002: CCSDS Compliance (CCSDS packets on both links)
+
+use std::fmt::Write as _;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use space_comms_shared::{
+    ccsds::{PacketType, SpacePacket},
+    commands::SpaceCommand,
+    messaging::{decode_command_packet, MessagePriority},
+    telemetry::{
+        dictionary_entry, DictionaryEntry, MeasurementQuality, MeasurementValue, TelemetryData,
+        DICTIONARY,
+    },
+    Result, SpaceCommError,
+};
+
+use crate::dictionary::{CommandSpec, ParameterKind, COMMAND_DICTIONARY};
+
+/// APID of the measurement packets sent to YAMCS
+pub const YAMCS_MEASUREMENT_APID: u16 = 0x3F0;
+
+/// Name of the XTCE space system
+pub const SPACE_SYSTEM: &str = "SpaceComms";
+
+/// File name of the exported mission database
+pub const MDB_FILE: &str = "space-comms.xml";
+
+/// File name of the exported instance configuration
+pub const INSTANCE_FILE: &str = "yamcs.space-comms.yaml";
+
+/// Value kind nibble of a float measurement
+const VALUE_KIND_FLOAT: u8 = 0;
+
+/// Value kind nibble of an integer measurement
+const VALUE_KIND_INTEGER: u8 = 1;
+
+/// Station side of the YAMCS links
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamcsConfig {
+    /// Address YAMCS listens on for measurement packets
+    pub tm_addr: SocketAddr,
+    /// Address the station receives YAMCS commands on
+    pub tc_addr: SocketAddr,
+}
+
+impl Default for YamcsConfig {
+    /// YAMCS on this host with the telemetry and command ports of its
+    /// quickstart instance
+    fn default() -> Self {
+        Self {
+            tm_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 10015),
+            tc_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 10025),
+        }
+    }
+}
+
+/// One measurement as a YAMCS parameter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterDef {
+    /// Measurement ID
+    pub measurement_id: u16,
+    /// XTCE parameter name: dictionary group and measurement ID
+    pub name: String,
+    /// Dictionary group the measurement belongs to
+    pub group: &'static str,
+    /// Engineering unit
+    pub unit: &'static str,
+    /// Sent as `i32`; unitless values are states, flags and counters
+    pub integer: bool,
+}
+
+/// Every measurement in the telemetry dictionary, in ID order
+pub fn parameters() -> Vec<ParameterDef> {
+    let mut parameters: Vec<ParameterDef> = DICTIONARY
+        .iter()
+        .flat_map(|entry| (entry.first_id..=entry.last_id).map(move |id| parameter(entry, id)))
+        .collect();
+    parameters.sort_by_key(|p| p.measurement_id);
+    parameters
+}
+
+/// Parameter of measurement `id` in dictionary `entry`
+fn parameter(entry: &'static DictionaryEntry, id: u16) -> ParameterDef {
+    let group: String = entry
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    ParameterDef {
+        measurement_id: id,
+        name: format!("{}_{:04X}", group, id),
+        group: entry.name,
+        unit: entry.unit,
+        integer: entry.unit.is_empty(),
+    }
+}
+
+/// Encode the measurements of a telemetry frame as YAMCS measurement packets
+///
+/// - **ID**: FN-YMC-002
+/// - **Requirement**: Downlinked measurements reach YAMCS as packets its
+///   mission database describes.
+/// - **Inputs**: Decoded telemetry and the sequence count of the last packet.
+/// - **Outputs**: One packet per measurement in the dictionary, laid out as in
+///   the module documentation; values are converted to the parameter's type.
+/// - **Side Effects**: Advances `sequence` once per packet.
+/// - **Failure Modes**: Measurements outside the dictionary are skipped.
+pub fn measurement_packets(data: &TelemetryData, sequence: &mut u16) -> Result<Vec<Vec<u8>>> {
+    let mut packets = Vec::new();
+    for measurement in &data.measurements {
+        let Some(entry) = dictionary_entry(measurement.measurement_id) else {
+            continue;
+        };
+        let integer = parameter(entry, measurement.measurement_id).integer;
+        let mut quality = measurement.quality;
+        let number = match measurement.value {
+            MeasurementValue::Float(v) => v,
+            MeasurementValue::Integer(v) => v as f64,
+            MeasurementValue::Boolean(v) => f64::from(u8::from(v)),
+            _ => {
+                quality = MeasurementQuality::NotAvailable;
+                0.0
+            }
+        };
+        let (kind, value) = if integer {
+            (VALUE_KIND_INTEGER, (number.round() as i32).to_be_bytes())
+        } else {
+            (VALUE_KIND_FLOAT, (number as f32).to_be_bytes())
+        };
+
+        let mut body = Vec::with_capacity(15);
+        body.extend_from_slice(&data.timestamp.to_be_bytes());
+        body.extend_from_slice(&measurement.measurement_id.to_be_bytes());
+        body.push((kind << 4) | quality.code());
+        body.extend_from_slice(&value);
+
+        *sequence = (*sequence + 1) & 0x3FFF;
+        let packet = SpacePacket::new(
+            PacketType::Telemetry,
+            YAMCS_MEASUREMENT_APID,
+            *sequence,
+            &body,
+            None,
+        )?;
+        packets.push(packet.to_bytes()?.to_vec());
+    }
+    Ok(packets)
+}
+
+/// Command ID and priority of a dictionary command
+fn command_identity(spec: &CommandSpec) -> Result<(u32, MessagePriority)> {
+    let values: Vec<Value> = spec
+        .parameters
+        .iter()
+        .map(|parameter| match parameter.kind {
+            ParameterKind::Choice(options) => Value::from(options[0]),
+            ParameterKind::Unsigned(_) => Value::from(0u64),
+            ParameterKind::OptionalUnsigned(_) => Value::Null,
+            ParameterKind::Float => Value::from(0.0),
+            ParameterKind::Flag => Value::Bool(false),
+        })
+        .collect();
+    let command = spec.build(&values)?;
+    Ok((command.discriminant(), command.priority()))
+}
+
+/// Bits holding an unsigned parameter no greater than `max`
+const fn unsigned_bits(max: u64) -> u32 {
+    if max <= u8::MAX as u64 {
+        8
+    } else if max <= u16::MAX as u64 {
+        16
+    } else if max <= u32::MAX as u64 {
+        32
+    } else {
+        64
+    }
+}
+
+/// Encode a dictionary command as YAMCS does from the exported database
+///
+/// Argument encoding per parameter kind, big-endian: choices as a `u8` index,
+/// unsigned values in the smallest of 8, 16, 32 or 64 bits holding their
+/// maximum, optional values as a presence byte followed by the value, floats
+/// as `f64` and flags as one byte.
+///
+/// # Arguments
+/// * `command` - Command built from the dictionary
+/// * `sequence` - CCSDS sequence count
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - Command packet with CRC, or configuration error when
+///   the command is not in the dictionary
+pub fn encode_command(command: &SpaceCommand, sequence: u16) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(command).map_err(|_| not_in_dictionary())?;
+    let (name, fields) = value
+        .as_object()
+        .and_then(|object| object.iter().next())
+        .ok_or_else(not_in_dictionary)?;
+    let spec = crate::dictionary::lookup(name).ok_or_else(not_in_dictionary)?;
+
+    let mut data = command.discriminant().to_be_bytes().to_vec();
+    for parameter in spec.parameters {
+        let field = &fields[parameter.name];
+        match parameter.kind {
+            ParameterKind::Choice(options) => {
+                let index = options
+                    .iter()
+                    .position(|option| field.as_str() == Some(option))
+                    .ok_or_else(not_in_dictionary)?;
+                data.push(index as u8);
+            }
+            ParameterKind::Unsigned(max) => {
+                push_unsigned(&mut data, field.as_u64().unwrap_or(0), max);
+            }
+            ParameterKind::OptionalUnsigned(max) => {
+                data.push(u8::from(!field.is_null()));
+                push_unsigned(&mut data, field.as_u64().unwrap_or(0), max);
+            }
+            ParameterKind::Float => {
+                data.extend_from_slice(&field.as_f64().unwrap_or(0.0).to_be_bytes())
+            }
+            ParameterKind::Flag => data.push(u8::from(field.as_bool() == Some(true))),
+        }
+    }
+
+    let apid = command.priority().command_apid();
+    let packet = SpacePacket::new(PacketType::Command, apid, sequence, &data, None)?;
+    Ok(packet.to_bytes()?.to_vec())
+}
+
+fn not_in_dictionary() -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter: "yamcs_command",
+        value: "<command>",
+        reason: "command is not in the command dictionary",
+    }
+}
+
+fn push_unsigned(data: &mut Vec<u8>, value: u64, max: u64) {
+    let bytes = value.to_be_bytes();
+    let width = unsigned_bits(max) as usize / 8;
+    data.extend_from_slice(&bytes[8 - width..]);
+}
+
+/// Decode a command packet sent by YAMCS
+///
+/// - **ID**: FN-YMC-003
+/// - **Requirement**: Commands from YAMCS are checked against the command
+///   dictionary before they are uplinked.
+/// - **Inputs**: Command packet as encoded by [`encode_command`].
+/// - **Outputs**: The command, built from its dictionary entry.
+/// - **Failure Modes**: `InvalidPacket` on a bad header, CRC or APID, or
+///   arguments that are truncated or left over; `ConfigurationError` on an
+///   unknown command ID or values the dictionary rejects.
+pub fn decode_command(bytes: &[u8]) -> Result<SpaceCommand> {
+    let fields = decode_command_packet(bytes)?;
+    let spec = COMMAND_DICTIONARY
+        .iter()
+        .find(|spec| command_identity(spec).is_ok_and(|(id, _)| id == fields.command_id))
+        .ok_or_else(not_in_dictionary)?;
+
+    let truncated = || {
+        SpaceCommError::invalid_packet("YAMCS command arguments truncated", Some(fields.command_id))
+    };
+    let mut args: &[u8] = &fields.parameters;
+    let mut take = |len: usize| -> Result<&[u8]> {
+        if args.len() < len {
+            return Err(truncated());
+        }
+        let (head, rest) = args.split_at(len);
+        args = rest;
+        Ok(head)
+    };
+    let unsigned = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+    };
+
+    let mut values = Vec::with_capacity(spec.parameters.len());
+    for parameter in spec.parameters {
+        let value = match parameter.kind {
+            ParameterKind::Choice(options) => options
+                .get(usize::from(take(1)?[0]))
+                .map(|option| Value::from(*option))
+                .ok_or_else(not_in_dictionary)?,
+            ParameterKind::Unsigned(max) => {
+                Value::from(unsigned(take(unsigned_bits(max) as usize / 8)?))
+            }
+            ParameterKind::OptionalUnsigned(max) => {
+                let present = take(1)?[0] != 0;
+                let value = unsigned(take(unsigned_bits(max) as usize / 8)?);
+                if present {
+                    Value::from(value)
+                } else {
+                    Value::Null
+                }
+            }
+            ParameterKind::Float => {
+                let bytes: [u8; 8] = take(8)?.try_into().map_err(|_| truncated())?;
+                Value::from(f64::from_be_bytes(bytes))
+            }
+            ParameterKind::Flag => Value::Bool(take(1)?[0] != 0),
+        };
+        values.push(value);
+    }
+    if !args.is_empty() {
+        return Err(SpaceCommError::invalid_packet(
+            "YAMCS command has extra arguments",
+            Some(fields.command_id),
+        ));
+    }
+
+    let command = spec.build(&values)?;
+    if command.priority() != fields.priority {
+        return Err(SpaceCommError::invalid_packet(
+            "YAMCS command on the wrong APID",
+            Some(fields.command_id),
+        ));
+    }
+    Ok(command)
+}
+
+/// Escape text for an XML attribute or element
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// XTCE type name of the float parameters in `unit`
+fn float_type_name(unit: &str) -> String {
+    let unit: String = unit
+        .chars()
+        .map(|c| match c {
+            '%' => 'P',
+            c if c.is_ascii_alphanumeric() => c,
+            _ => '_',
+        })
+        .collect();
+    format!("float_{}", unit)
+}
+
+/// Generate the XTCE mission database
+///
+/// - **ID**: FN-YMC-001
+/// - **Requirement**: Describe the station's telemetry and commands to YAMCS
+///   from the dictionaries the station itself uses.
+/// - **Outputs**: XTCE 1.2 document with the CCSDS header, the measurement
+///   packet and one parameter per dictionary measurement, and one meta-command
+///   per console dictionary command with its arguments, ranges and
+///   enumerations.
+/// - **Side Effects**: None.
+pub fn mdb_xml() -> String {
+    let parameters = parameters();
+    let mut units: Vec<&str> = parameters
+        .iter()
+        .filter(|p| !p.integer)
+        .map(|p| p.unit)
+        .collect();
+    units.sort_unstable();
+    units.dedup();
+
+    let mut xml = String::new();
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<SpaceSystem name="{}" xmlns="http://www.omg.org/spec/XTCE/20180204">"#,
+        SPACE_SYSTEM
+    );
+
+    // Telemetry: header, measurement packet and one child per measurement ID
+    xml.push_str("  <TelemetryMetaData>\n    <ParameterTypeSet>\n");
+    for bits in [1, 2, 3, 4, 11, 14, 16, 64] {
+        let _ = writeln!(
+            xml,
+            r#"      <IntegerParameterType name="uint{bits}_t" signed="false"><IntegerDataEncoding sizeInBits="{bits}"/></IntegerParameterType>"#
+        );
+    }
+    xml.push_str(
+        r#"      <IntegerParameterType name="int32_t" signed="true"><IntegerDataEncoding sizeInBits="32" encoding="twosComplement"/></IntegerParameterType>
+      <EnumeratedParameterType name="quality_t">
+        <IntegerDataEncoding sizeInBits="4"/>
+        <EnumerationList>
+          <Enumeration value="0" label="Good"/>
+          <Enumeration value="1" label="Suspect"/>
+          <Enumeration value="2" label="Stale"/>
+          <Enumeration value="3" label="Invalid"/>
+          <Enumeration value="4" label="NotAvailable"/>
+        </EnumerationList>
+      </EnumeratedParameterType>
+"#,
+    );
+    for unit in &units {
+        let _ = writeln!(
+            xml,
+            r#"      <FloatParameterType name="{}" sizeInBits="32"><UnitSet><Unit>{}</Unit></UnitSet><FloatDataEncoding sizeInBits="32"/></FloatParameterType>"#,
+            float_type_name(unit),
+            xml_escape(unit)
+        );
+    }
+    xml.push_str("    </ParameterTypeSet>\n    <ParameterSet>\n");
+    for (name, type_name) in [
+        ("CCSDS_Version", "uint3_t"),
+        ("CCSDS_Type", "uint1_t"),
+        ("CCSDS_SecHdrFlag", "uint1_t"),
+        ("CCSDS_APID", "uint11_t"),
+        ("CCSDS_SeqFlags", "uint2_t"),
+        ("CCSDS_SeqCount", "uint14_t"),
+        ("CCSDS_Length", "uint16_t"),
+        ("Timestamp", "uint64_t"),
+        ("MeasurementId", "uint16_t"),
+        ("ValueKind", "uint4_t"),
+        ("Quality", "quality_t"),
+    ] {
+        let _ = writeln!(
+            xml,
+            r#"      <Parameter name="{name}" parameterTypeRef="{type_name}"/>"#
+        );
+    }
+    for parameter in &parameters {
+        let type_name = if parameter.integer {
+            "int32_t".to_string()
+        } else {
+            float_type_name(parameter.unit)
+        };
+        let _ = writeln!(
+            xml,
+            r#"      <Parameter name="{}" parameterTypeRef="{}" shortDescription="{} (measurement 0x{:04X})"/>"#,
+            parameter.name,
+            type_name,
+            xml_escape(parameter.group),
+            parameter.measurement_id
+        );
+    }
+    xml.push_str("    </ParameterSet>\n    <ContainerSet>\n");
+    xml.push_str(
+        r#"      <SequenceContainer name="CCSDSPacket" abstract="true">
+        <EntryList>
+          <ParameterRefEntry parameterRef="CCSDS_Version"/>
+          <ParameterRefEntry parameterRef="CCSDS_Type"/>
+          <ParameterRefEntry parameterRef="CCSDS_SecHdrFlag"/>
+          <ParameterRefEntry parameterRef="CCSDS_APID"/>
+          <ParameterRefEntry parameterRef="CCSDS_SeqFlags"/>
+          <ParameterRefEntry parameterRef="CCSDS_SeqCount"/>
+          <ParameterRefEntry parameterRef="CCSDS_Length"/>
+        </EntryList>
+      </SequenceContainer>
+"#,
+    );
+    let _ = write!(
+        xml,
+        r#"      <SequenceContainer name="Measurement" abstract="true">
+        <EntryList>
+          <ParameterRefEntry parameterRef="Timestamp"/>
+          <ParameterRefEntry parameterRef="MeasurementId"/>
+          <ParameterRefEntry parameterRef="ValueKind"/>
+          <ParameterRefEntry parameterRef="Quality"/>
+        </EntryList>
+        <BaseContainer containerRef="CCSDSPacket">
+          <RestrictionCriteria><Comparison parameterRef="CCSDS_APID" value="{}"/></RestrictionCriteria>
+        </BaseContainer>
+      </SequenceContainer>
+"#,
+        YAMCS_MEASUREMENT_APID
+    );
+    for parameter in &parameters {
+        let _ = write!(
+            xml,
+            r#"      <SequenceContainer name="M_{:04X}">
+        <EntryList><ParameterRefEntry parameterRef="{}"/></EntryList>
+        <BaseContainer containerRef="Measurement">
+          <RestrictionCriteria><Comparison parameterRef="MeasurementId" value="{}"/></RestrictionCriteria>
+        </BaseContainer>
+      </SequenceContainer>
+"#,
+            parameter.measurement_id, parameter.name, parameter.measurement_id
+        );
+    }
+    xml.push_str("    </ContainerSet>\n  </TelemetryMetaData>\n");
+
+    // Commands: CCSDS header and command ID, then the dictionary arguments
+    xml.push_str("  <CommandMetaData>\n    <ArgumentTypeSet>\n");
+    for bits in [11, 32] {
+        let _ = writeln!(
+            xml,
+            r#"      <IntegerArgumentType name="uint{bits}_t" signed="false"><IntegerDataEncoding sizeInBits="{bits}"/></IntegerArgumentType>"#
+        );
+    }
+    xml.push_str(
+        r#"      <BooleanArgumentType name="flag_t"><IntegerDataEncoding sizeInBits="8"/></BooleanArgumentType>
+      <FloatArgumentType name="float64_t" sizeInBits="64"><FloatDataEncoding sizeInBits="64"/></FloatArgumentType>
+"#,
+    );
+    for spec in COMMAND_DICTIONARY {
+        for parameter in spec.parameters {
+            let type_name = format!("{}_{}_t", spec.name, parameter.name);
+            match parameter.kind {
+                ParameterKind::Choice(options) => {
+                    let _ = writeln!(xml, r#"      <EnumeratedArgumentType name="{type_name}">"#);
+                    xml.push_str("        <IntegerDataEncoding sizeInBits=\"8\"/>\n        <EnumerationList>\n");
+                    for (value, label) in options.iter().enumerate() {
+                        let _ = writeln!(
+                            xml,
+                            r#"          <Enumeration value="{value}" label="{label}"/>"#
+                        );
+                    }
+                    xml.push_str("        </EnumerationList>\n      </EnumeratedArgumentType>\n");
+                }
+                ParameterKind::Unsigned(max) | ParameterKind::OptionalUnsigned(max) => {
+                    let bits = unsigned_bits(max);
+                    let _ = writeln!(
+                        xml,
+                        r#"      <IntegerArgumentType name="{type_name}" signed="false" sizeInBits="{bits}"><IntegerDataEncoding sizeInBits="{bits}"/><ValidRangeSet><ValidRange minInclusive="0" maxInclusive="{max}"/></ValidRangeSet></IntegerArgumentType>"#
+                    );
+                }
+                ParameterKind::Float | ParameterKind::Flag => {}
+            }
+        }
+    }
+    xml.push_str("    </ArgumentTypeSet>\n    <MetaCommandSet>\n");
+    xml.push_str(
+        r#"      <MetaCommand name="SpaceCommsCommand" abstract="true">
+        <ArgumentList>
+          <Argument name="ccsds_apid" argumentTypeRef="uint11_t"/>
+          <Argument name="command_id" argumentTypeRef="uint32_t"/>
+        </ArgumentList>
+        <CommandContainer name="SpaceCommsCommand">
+          <EntryList>
+            <FixedValueEntry name="ccsds_version" binaryValue="00" sizeInBits="3"/>
+            <FixedValueEntry name="ccsds_type" binaryValue="01" sizeInBits="1"/>
+            <FixedValueEntry name="ccsds_sec_hdr" binaryValue="00" sizeInBits="1"/>
+            <ArgumentRefEntry argumentRef="ccsds_apid"/>
+            <FixedValueEntry name="ccsds_seq_flags" binaryValue="03" sizeInBits="2"/>
+            <FixedValueEntry name="ccsds_seq_count" binaryValue="0000" sizeInBits="14"/>
+            <FixedValueEntry name="ccsds_length" binaryValue="0000" sizeInBits="16"/>
+            <ArgumentRefEntry argumentRef="command_id"/>
+          </EntryList>
+        </CommandContainer>
+      </MetaCommand>
+"#,
+    );
+    for spec in COMMAND_DICTIONARY {
+        // Every dictionary command builds from sample values
+        let Ok((command_id, priority)) = command_identity(spec) else {
+            continue;
+        };
+        let _ = write!(
+            xml,
+            r#"      <MetaCommand name="{}" shortDescription="{:?} priority">
+        <BaseMetaCommand metaCommandRef="SpaceCommsCommand">
+          <ArgumentAssignmentList>
+            <ArgumentAssignment argumentName="ccsds_apid" argumentValue="{}"/>
+            <ArgumentAssignment argumentName="command_id" argumentValue="{}"/>
+          </ArgumentAssignmentList>
+        </BaseMetaCommand>
+        <ArgumentList>
+"#,
+            spec.name,
+            priority,
+            priority.command_apid(),
+            command_id
+        );
+        let mut entries = String::new();
+        for parameter in spec.parameters {
+            let type_name = match parameter.kind {
+                ParameterKind::Float => "float64_t".to_string(),
+                ParameterKind::Flag => "flag_t".to_string(),
+                _ => format!("{}_{}_t", spec.name, parameter.name),
+            };
+            if let ParameterKind::OptionalUnsigned(_) = parameter.kind {
+                let _ = writeln!(
+                    xml,
+                    r#"          <Argument name="{}_present" argumentTypeRef="flag_t" initialValue="false"/>"#,
+                    parameter.name
+                );
+                let _ = writeln!(
+                    entries,
+                    r#"            <ArgumentRefEntry argumentRef="{}_present"/>"#,
+                    parameter.name
+                );
+                let _ = writeln!(
+                    xml,
+                    r#"          <Argument name="{}" argumentTypeRef="{type_name}" initialValue="0"/>"#,
+                    parameter.name
+                );
+            } else {
+                let _ = writeln!(
+                    xml,
+                    r#"          <Argument name="{}" argumentTypeRef="{type_name}"/>"#,
+                    parameter.name
+                );
+            }
+            let _ = writeln!(
+                entries,
+                r#"            <ArgumentRefEntry argumentRef="{}"/>"#,
+                parameter.name
+            );
+        }
+        let _ = write!(
+            xml,
+            r#"        </ArgumentList>
+        <CommandContainer name="{}">
+          <EntryList>
+{}          </EntryList>
+          <BaseContainer containerRef="SpaceCommsCommand"/>
+        </CommandContainer>
+      </MetaCommand>
+"#,
+            spec.name, entries
+        );
+    }
+    xml.push_str("    </MetaCommandSet>\n  </CommandMetaData>\n</SpaceSystem>\n");
+    xml
+}
+
+/// Generate the YAMCS instance configuration
+///
+/// Loads [`MDB_FILE`] from the instance's `mdb` directory and connects a UDP
+/// telemetry link on the port of `config.tm_addr` and a UDP telecommand link
+/// to `config.tc_addr`, both checking or appending the CRC-16-CCITT the
+/// station's packets carry.
+///
+/// # Arguments
+/// * `config` - Station side of the YAMCS links
+pub fn instance_yaml(config: &YamcsConfig) -> String {
+    format!(
+        r#"# YAMCS instance for the {system} ground station, generated from its dictionaries
+services:
+  - class: org.yamcs.archive.XtceTmRecorder
+  - class: org.yamcs.archive.ParameterRecorder
+  - class: org.yamcs.archive.CommandHistoryRecorder
+  - class: org.yamcs.ProcessorCreatorService
+    args:
+      name: realtime
+      type: realtime
+
+mdb:
+  - type: xtce
+    spec: mdb/{mdb}
+
+dataLinks:
+  - name: station-tm
+    class: org.yamcs.tctm.UdpTmDataLink
+    stream: tm_realtime
+    port: {tm_port}
+    packetPreprocessorClassName: org.yamcs.tctm.GenericPacketPreprocessor
+    packetPreprocessorArgs:
+      timestampOffset: -1
+      seqCountOffset: 2
+      errorDetection:
+        type: CRC-16-CCIIT
+  - name: station-tc
+    class: org.yamcs.tctm.UdpTcDataLink
+    stream: tc_realtime
+    host: {tc_host}
+    port: {tc_port}
+    commandPostprocessorClassName: org.yamcs.tctm.GenericCommandPostprocessor
+    commandPostprocessorArgs:
+      errorDetection:
+        type: CRC-16-CCIIT
+
+streamConfig:
+  tm:
+    - name: tm_realtime
+  tc:
+    - name: tc_realtime
+"#,
+        system = SPACE_SYSTEM,
+        mdb = MDB_FILE,
+        tm_port = config.tm_addr.port(),
+        tc_host = config.tc_addr.ip(),
+        tc_port = config.tc_addr.port(),
+    )
+}
+
+/// Write the mission database and instance configuration to `dir`
+///
+/// # Arguments
+/// * `dir` - Directory to write [`MDB_FILE`] and [`INSTANCE_FILE`] to,
+///   created if missing
+/// * `config` - Station side of the YAMCS links
+///
+/// # Returns
+/// * `Result<Vec<PathBuf>>` - Paths written, or configuration error if the
+///   directory is not writable
+pub fn export(dir: &Path, config: &YamcsConfig) -> Result<Vec<PathBuf>> {
+    let mdb = dir.join(MDB_FILE);
+    let instance = dir.join(INSTANCE_FILE);
+    let written = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&mdb, mdb_xml()))
+        .and_then(|_| std::fs::write(&instance, instance_yaml(config)));
+    if written.is_err() {
+        return Err(SpaceCommError::ConfigurationError {
+            parameter: "yamcs_export_dir",
+            value: "<unwritable>",
+            reason: "YAMCS mission database could not be written",
+        });
+    }
+    Ok(vec![mdb, instance])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::{
+        telemetry::{measurement_ids, Measurement},
+        types::{ComponentId, HealthStatus},
+    };
+
+    #[test]
+    fn test_mdb_covers_both_dictionaries() {
+        let xml = mdb_xml();
+        let parameters = parameters();
+        let dictionary_ids: usize = DICTIONARY
+            .iter()
+            .map(|e| usize::from(e.last_id - e.first_id) + 1)
+            .sum();
+        assert_eq!(parameters.len(), dictionary_ids);
+        assert!(parameters
+            .windows(2)
+            .all(|w| w[0].measurement_id < w[1].measurement_id));
+
+        let battery = parameters
+            .iter()
+            .find(|p| p.measurement_id == measurement_ids::BATTERY_VOLTAGE)
+            .unwrap();
+        assert_eq!(battery.name, "Bus_voltages_0010");
+        assert!(!battery.integer);
+        assert!(xml.contains(r#"<Parameter name="Bus_voltages_0010" parameterTypeRef="float_V""#));
+        assert!(xml.contains(r#"<Comparison parameterRef="MeasurementId" value="16"/>"#));
+        assert!(xml.contains(r#"<FloatParameterType name="float_P""#));
+
+        assert_eq!(
+            xml.matches("<MetaCommand ").count(),
+            COMMAND_DICTIONARY.len() + 1
+        );
+        assert!(xml.contains(r#"<Enumeration value="2" label="XBand"/>"#));
+        assert!(xml.contains(r#"<Argument name="duration_seconds_present""#));
+        for tag in [
+            "SequenceContainer",
+            "MetaCommand",
+            "EnumerationList",
+            "ArgumentList",
+        ] {
+            assert_eq!(
+                xml.matches(&format!("<{} ", tag)).count()
+                    + xml.matches(&format!("<{}>", tag)).count(),
+                xml.matches(&format!("</{}>", tag)).count(),
+                "unbalanced {}",
+                tag
+            );
+        }
+
+        let yaml = instance_yaml(&YamcsConfig::default());
+        assert!(yaml.contains("port: 10015"));
+        assert!(yaml.contains(&format!("spec: mdb/{}", MDB_FILE)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = export(&dir.path().join("yamcs"), &YamcsConfig::default()).unwrap();
+        assert_eq!(std::fs::read_to_string(&written[0]).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_measurement_packets_layout() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0x0102_0304_0506_0708,
+            measurements: Default::default(),
+            health_status: HealthStatus::Good,
+        };
+        let mut push = |id, value| {
+            data.measurements
+                .push(Measurement {
+                    measurement_id: id,
+                    value,
+                    unit: "",
+                    quality: MeasurementQuality::Suspect,
+                })
+                .unwrap();
+        };
+        push(
+            measurement_ids::BATTERY_VOLTAGE,
+            MeasurementValue::Float(28.5),
+        );
+        push(
+            measurement_ids::UPLINK_COMMANDS_ACCEPTED,
+            MeasurementValue::Float(41.6),
+        );
+        push(0x7FFF, MeasurementValue::Integer(1));
+
+        let mut sequence = 0x3FFF;
+        let packets = measurement_packets(&data, &mut sequence).unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(sequence, 1);
+
+        let voltage = &packets[0];
+        assert_eq!(voltage.len(), 23);
+        assert_eq!(
+            u16::from_be_bytes([voltage[0], voltage[1]]) & 0x7FF,
+            YAMCS_MEASUREMENT_APID
+        );
+        assert_eq!(&voltage[6..14], &data.timestamp.to_be_bytes());
+        assert_eq!(&voltage[14..17], &[0x00, 0x10, 0x01]);
+        assert_eq!(
+            f32::from_be_bytes(voltage[17..21].try_into().unwrap()),
+            28.5
+        );
+
+        // Unitless values go out as integers
+        let counter = &packets[1];
+        assert_eq!(counter[16], (VALUE_KIND_INTEGER << 4) | 1);
+        assert_eq!(i32::from_be_bytes(counter[17..21].try_into().unwrap()), 42);
+    }
+
+    #[test]
+    fn test_yamcs_commands_round_trip_through_dictionary() {
+        for spec in COMMAND_DICTIONARY {
+            let (_, priority) = command_identity(spec).unwrap();
+            let values: Vec<Value> = spec
+                .parameters
+                .iter()
+                .map(|parameter| match parameter.kind {
+                    ParameterKind::Choice(options) => Value::from(options[options.len() - 1]),
+                    ParameterKind::Unsigned(max) | ParameterKind::OptionalUnsigned(max) => {
+                        Value::from(max.min(1_000_000))
+                    }
+                    ParameterKind::Float => Value::from(12.5),
+                    ParameterKind::Flag => Value::Bool(true),
+                })
+                .collect();
+            let command = spec.build(&values).unwrap();
+            assert_eq!(command.priority(), priority);
+            let packet = encode_command(&command, 7).unwrap();
+            assert_eq!(decode_command(&packet).unwrap(), command, "{}", spec.name);
+        }
+
+        let command = crate::dictionary::lookup("ActivateSafeMode")
+            .unwrap()
+            .parse(&["Level2", "none"])
+            .unwrap();
+        let packet = encode_command(&command, 1).unwrap();
+        assert_eq!(decode_command(&packet).unwrap(), command);
+
+        // Corrupted or truncated packets are rejected
+        let mut corrupt = packet.clone();
+        corrupt[10] ^= 0xFF;
+        assert!(decode_command(&corrupt).is_err());
+        let data = &packet[6..packet.len() - 3];
+        let short = SpacePacket::new(PacketType::Command, 0x001, 2, data, None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(decode_command(&short).is_err());
+    }
+}
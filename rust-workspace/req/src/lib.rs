@@ -0,0 +1,82 @@
+//! Requirement Annotations
+//!
+//! `#[req("REQ-FN-007")]` ties a function to the requirements it implements
+//! or verifies, in a form tools can read instead of prose in doc comments.
+//! The attribute leaves the item unchanged; it only checks that every ID is
+//! well formed, so a typo fails the build rather than a trace.
+//!
+//! ```ignore
+//! use space_comms_req::req;
+//!
+//! #[req("REQ-FN-001", "REQ-FN-009")]
+//! pub fn push(&mut self, message: Message) -> Result<()> { /* ... */ }
+//! ```
+//!
+//! `space-comms trace` collects the annotations across the workspace into
+//! the requirement → function → test matrix. A test is traced to the
+//! requirements of the annotated functions it calls, or annotated itself.
+//!
+//! # Requirements Traceability
+//! - REQ-QL-001: Test coverage of every requirement, checked mechanically
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, LitStr, Token};
+
+/// Whether `id` is a requirement ID: `REQ-`, two capitals, `-`, three digits
+fn is_requirement_id(id: &str) -> bool {
+    let bytes = id.as_bytes();
+    bytes.len() == 10
+        && id.starts_with("REQ-")
+        && bytes[4..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6] == b'-'
+        && bytes[7..].iter().all(u8::is_ascii_digit)
+}
+
+/// Mark a function as implementing or verifying requirements
+///
+/// Takes one or more requirement IDs as string literals and expands to the
+/// item unchanged. An empty list or an ID not shaped `REQ-XX-NNN` is a
+/// compile error at the offending literal.
+#[proc_macro_attribute]
+pub fn req(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ids = parse_macro_input!(attr with Punctuated::<LitStr, Token![,]>::parse_terminated);
+    if ids.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "expected at least one requirement ID, as in #[req(\"REQ-FN-001\")]",
+        )
+        .to_compile_error()
+        .into();
+    }
+    for id in &ids {
+        if !is_requirement_id(&id.value()) {
+            return syn::Error::new(
+                id.span(),
+                format!(
+                    "{:?} is not a requirement ID of the form REQ-XX-NNN",
+                    id.value()
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement_id_shape() {
+        assert!(is_requirement_id("REQ-FN-007"));
+        assert!(is_requirement_id("REQ-SC-002"));
+        assert!(!is_requirement_id("REQ-FN-07"));
+        assert!(!is_requirement_id("REQ-fn-007"));
+        assert!(!is_requirement_id("FN-CLI-001"));
+        assert!(!is_requirement_id("REQ-FN-0071"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Onboard autonomy
+//!
+//! Holds the uploaded autonomy rules and carries out their actions. The
+//! command processor hands rules uploaded on the autonomy rule APID to
+//! [`accept_rule`] and the enable and disable command to [`switch_rule`];
+//! the autonomy task calls [`evaluate`] every period. Rules are evaluated
+//! against the flight rule state snapshot, the recorder fill of the mass
+//! memory and the transceiver health.
+//!
+//! Every execution is logged with the rule number as its code, so it reaches
+//! the ground in the event log, and counted; the count is downlinked in
+//! housekeeping telemetry. A recorder downlink requested by a rule starts
+//! with the next contact, once the flight rules allow transmitting on the
+//! requested band.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Command Set (routine operations automated
+//!   between contacts)
+//! - REQ-SF-001: Command validation (autonomous actions checked against
+//!   flight rules)
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    autonomy::{AutonomyAction, AutonomyEngine, AutonomyRule, AutonomyState, AutonomySwitch},
+    flight_rules::Activity,
+    link_config::{DirectionalLink, LinkDirection},
+    Result,
+};
+
+use crate::{
+    communication, downlink_plan,
+    error_handling::{self, LogLevel},
+    flight_rules, hardware, mass_memory,
+};
+
+static ENGINE: Mutex<CriticalSectionRawMutex, RefCell<AutonomyEngine>> =
+    Mutex::new(RefCell::new(AutonomyEngine::new()));
+
+static EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+
+/// Load a rule uploaded in a packet payload
+///
+/// The rule is loaded disabled; a malformed rule or a full engine is
+/// rejected and the loaded rules are kept.
+pub fn accept_rule(payload: &[u8]) -> Result<()> {
+    let rule = AutonomyRule::from_bytes(payload)?;
+    let id = rule.id;
+    ENGINE.lock(|engine| engine.borrow_mut().load(rule))?;
+    error_handling::log_with_component(
+        LogLevel::Info,
+        "Autonomy rule loaded",
+        "AUTONOMY",
+        Some(u32::from(id)),
+    );
+    Ok(())
+}
+
+/// Enable or disable a loaded rule as commanded
+pub fn switch_rule(switch: &AutonomySwitch) -> Result<()> {
+    ENGINE.lock(|engine| {
+        engine
+            .borrow_mut()
+            .set_enabled(switch.rule_id, switch.enabled)
+    })?;
+    error_handling::log_with_component(
+        LogLevel::Info,
+        if switch.enabled {
+            "Autonomy rule enabled"
+        } else {
+            "Autonomy rule disabled"
+        },
+        "AUTONOMY",
+        Some(u32::from(switch.rule_id)),
+    );
+    Ok(())
+}
+
+/// Sample the state autonomy rules are evaluated against
+async fn sample_state() -> AutonomyState {
+    AutonomyState {
+        spacecraft: flight_rules::sample_state(Instant::now().as_millis()).await,
+        recorder_fill: Some(mass_memory::fill_percent()),
+        healthy_bands: hardware::get_hardware_health()
+            .await
+            .map(|(_, healthy)| healthy),
+    }
+}
+
+/// Evaluate the enabled rules and carry out the actions of those that fire
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-005: Autonomous operations between contacts
+pub async fn evaluate() {
+    let state = sample_state().await;
+    let fired = ENGINE.lock(|engine| engine.borrow_mut().evaluate(&state));
+
+    for execution in fired.iter() {
+        EXECUTIONS.fetch_add(1, Ordering::Relaxed);
+        error_handling::log_with_component(
+            LogLevel::Info,
+            execution.action.name(),
+            "AUTONOMY",
+            Some(u32::from(execution.rule_id)),
+        );
+        match execution.action {
+            AutonomyAction::DownlinkNextContact(band) => downlink_plan::request_band(band),
+            AutonomyAction::SetRfHousekeepingInterval(interval_ms) => {
+                hardware::set_rf_housekeeping_interval_ms(interval_ms);
+            }
+        }
+    }
+}
+
+/// Move the downlink to the band an autonomy rule requested, as a contact
+/// starts
+///
+/// A band a flight rule forbids, or that the link configuration rejects,
+/// leaves the downlink where it is.
+pub async fn start_requested_downlink() {
+    let Some(band) = downlink_plan::take_requested_band() else {
+        return;
+    };
+    if flight_rules::check_action(Activity::Transmit(band))
+        .await
+        .is_err()
+    {
+        error_handling::log_warning("Autonomy downlink band refused by flight rule");
+        return;
+    }
+    let current = communication::link(LinkDirection::Downlink);
+    let link = DirectionalLink::new(band, current.power_level, current.data_rate_bps);
+    if communication::configure_link(LinkDirection::Downlink, link)
+        .await
+        .is_err()
+    {
+        error_handling::log_error("Autonomy downlink band rejected");
+    }
+}
+
+/// Autonomy rule executions since boot
+pub fn executions() -> u32 {
+    EXECUTIONS.load(Ordering::Relaxed)
+}
@@ -0,0 +1,150 @@
+//! Onboard scheduler of uplinked command loads
+//!
+//! The ground uplinks a command load on the command-load APID; the command
+//! processor hands it here instead of to the command dispatcher. Every entry
+//! is checked against the same constraints the ground validated the load
+//! with, the accepted ones are committed to the scheduler table, and the
+//! manifest listing the outcome of every entry is returned for downlink.
+//! The scheduler task releases entries as their release time arrives. The
+//! table lives behind a critical-section mutex so both tasks can use it
+//! without `unsafe`.
+//!
+//! Release times are UTC, as the ground validated them. The mission clock
+//! counts from boot, so each load re-correlates it to UTC from the load's
+//! uplink stamp before its entries are checked, and the scheduler task
+//! releases entries against the correlated time.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (command scheduling and automation)
+//! - REQ-SF-001: Command Validation (load-wide constraint checking)
+//! - REQ-PF-001: Command Response Time (release-time ordering)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    command_load::{
+        ClockCorrelation, CommandLoad, CommandLoadEntry, CommandScheduler, LoadConstraints,
+        LoadManifest,
+    },
+    Result,
+};
+
+use crate::error_handling::{self, LogLevel};
+
+/// Time-tagged commands the scheduler table holds
+const SCHEDULER_SLOTS: usize = 64;
+
+/// Event code logged when a load is only partly accepted
+const LOAD_PARTLY_REJECTED_CODE: u32 = 450;
+
+/// Scheduler table and the correlation of the mission clock to UTC
+struct Schedule {
+    table: CommandScheduler<SCHEDULER_SLOTS>,
+    clock: ClockCorrelation,
+}
+
+static SCHEDULE: Mutex<CriticalSectionRawMutex, RefCell<Schedule>> =
+    Mutex::new(RefCell::new(Schedule {
+        table: CommandScheduler::new(),
+        clock: ClockCorrelation::new(),
+    }));
+
+/// Current time, mission seconds since boot
+fn mission_seconds() -> u64 {
+    Instant::now().as_secs()
+}
+
+/// Accept an uplinked command load from a packet payload
+///
+/// Returns the manifest to downlink; a malformed load is rejected whole and
+/// leaves the table unchanged.
+pub fn accept_load(payload: &[u8]) -> Result<LoadManifest> {
+    let load = CommandLoad::from_bytes(payload)?;
+    let constraints = LoadConstraints::default();
+    let mission_secs = mission_seconds();
+    let manifest = SCHEDULE.lock(|schedule| {
+        let mut schedule = schedule.borrow_mut();
+        schedule.clock.correlate(&load, mission_secs);
+        let now_secs = schedule.clock.utc(mission_secs);
+        schedule.table.accept_load(load, &constraints, now_secs)
+    });
+
+    if manifest.is_fully_accepted() {
+        error_handling::log_info("Command load accepted");
+    } else {
+        error_handling::log_with_component(
+            LogLevel::Warning,
+            "Command load entries rejected",
+            "SCHEDULER",
+            Some(LOAD_PARTLY_REJECTED_CODE),
+        );
+    }
+    Ok(manifest)
+}
+
+/// Remove and return the next entry whose release time has arrived
+pub fn pop_due() -> Option<CommandLoadEntry> {
+    let mission_secs = mission_seconds();
+    SCHEDULE.lock(|schedule| {
+        let mut schedule = schedule.borrow_mut();
+        let now_secs = schedule.clock.utc(mission_secs);
+        schedule.table.pop_due(now_secs)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::command_load::LoadRejectReason;
+    use space_comms_shared::commands::{ReportFormat, SpaceCommand, StatusType};
+
+    /// UTC time the ground stamps the test load with, Unix seconds
+    const UPLINK_UTC: u64 = 1_700_000_000;
+
+    fn status_entry(sequence: u16, release_time: Option<u64>) -> CommandLoadEntry {
+        CommandLoadEntry {
+            sequence,
+            release_time,
+            sequence_count: 40 + sequence,
+            command: SpaceCommand::SendStatus {
+                status_type: StatusType::SystemHealth,
+                include_diagnostics: false,
+                format: ReportFormat::Binary,
+            },
+        }
+    }
+
+    #[test]
+    fn test_load_scheduled_and_released_on_correlated_clock() -> Result<()> {
+        let mut load = CommandLoad::new(9);
+        load.uplink_time = UPLINK_UTC;
+        load.push(status_entry(1, None))?;
+        load.push(status_entry(2, Some(UPLINK_UTC + 3_600)))?;
+        load.push(status_entry(3, Some(UPLINK_UTC - 60)))?;
+
+        // Release times are checked against UTC, not the clock since boot
+        let manifest = accept_load(&load.to_bytes()?)?;
+        assert_eq!(manifest.accepted.as_slice(), &[1, 2]);
+        assert_eq!(manifest.rejected.len(), 1);
+        assert_eq!(manifest.rejected[0].reason, LoadRejectReason::ReleaseInPast);
+
+        // The immediate entry is released with its uplink sequence count;
+        // the timed one waits for its release time
+        let released = pop_due()
+            .map(|entry| entry.to_command_packet())
+            .transpose()?;
+        assert_eq!(
+            released.map(|packet| packet.header.sequence_count),
+            Some(41)
+        );
+        assert!(pop_due().is_none());
+
+        // A malformed load is rejected whole
+        assert!(accept_load(b"not a load").is_err());
+        assert!(pop_due().is_none());
+        Ok(())
+    }
+}
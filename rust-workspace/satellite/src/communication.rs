@@ -14,6 +14,10 @@
 //! - REQ-NF-003: Concurrent communication handling with Embassy async
 //! - REQ-SF-002: Emergency communication protocols and failover
 //! - REQ-NF-004: Power management across communication bands
+//! - REQ-FN-007: Independent uplink and downlink band, power and data rate
+//! - REQ-FN-007: Reed-Solomon (255,223) coding enabled per band
+//! - REQ-IF-002: Command uplink in TC Transfer Frames accepted by COP-1 FARM-1
+//! - REQ-IF-002: Downlink in TM Transfer Frames on prioritized virtual channels
 //!
 //! ## NASA/DoD Standards Compliance:
 //! - **CCSDS 133.0-B-2**: Space Packet Protocol (Blue Book)
@@ -26,23 +30,64 @@
 //!
 //! ## Architecture:
 //! - Band selection algorithm based on message priority and reliability
+//! - Separate uplink and downlink configuration (e.g. S-band up, X-band down)
 //! - CCSDS packet creation and parsing for space standards compliance
+//! - Uplinked TC Transfer Frames checked by FARM-1, answered with a CLCW
+//! - Downlinked packets multiplexed into TM Transfer Frames: realtime
+//!   housekeeping at once, science and playback as frames fill, each frame
+//!   carrying the CLCW
 //! - Emergency mode with UHF fallback for maximum reliability
 //! - Power management across multiple RF bands for efficiency
 
-use embassy_time::{Duration, Timer};
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU16, Ordering};
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::{Duration, Instant, Timer};
 use heapless::Vec;
 
 use space_comms_shared::{
+    command_load::LoadManifest,
+    cop1::{Farm, FarmVerdict},
+    diagnostics::{DwellBatch, MemoryDumpSegment},
+    eps::EPS_APID,
+    fec::FecPolicy,
+    file_downlink::FileManifest,
+    formation::CROSSLINK_RANGING_APID,
+    frequency_plan::FrequencyPlan,
+    event_log::EventLogCompressor,
+    execution_report::ExecutionReport,
+    link_config::{DirectionalLink, LinkConfiguration, LinkDirection},
+    link_stats::LinkStats,
+    loopback::LoopbackRequest,
+    mass_memory::FileListing,
     messaging::{Message, MessagePriority},
-    telemetry::TelemetryPacket,
+    navigation::NAVIGATION_APID,
+    retry::{AttemptRecord, RetryDecision, RetryPolicy},
+    rf_housekeeping::RF_HOUSEKEEPING_APID,
+    security::{
+        virtual_channels, KeyRotation, KeyStore, SecurityHeader, SecurityPolicyTable,
+        SecurityService, VcSecurityPolicy, KEY_LEN,
+    },
+    telemetry::{TelemetryData, TelemetryPacket},
     types::BandType,
-    ccsds::{SpacePacket, PacketType, SpacePacketHeader},
+    ccsds::{SpacePacket, PacketType, TcTransferFrame},
+    vc_mux::VcMultiplexer,
+    wire,
     Result, SpaceCommError,
 };
 
 use crate::hardware;
-use crate::error_handling;
+use crate::error_handling::{self, LogLevel};
+
+/// Retry policy for high priority transmissions
+///
+/// Two quick retries with a 2ms fixed delay, bounded by the 10ms high priority
+/// deadline (REQ-PF-001) so a retried message is never delivered late.
+const HIGH_PRIORITY_RETRY: RetryPolicy = RetryPolicy::fixed(3, 2).with_deadline_ms(10);
+
+/// Bytes of packets queued per downlink virtual channel
+const DOWNLINK_QUEUE_LEN: usize = 4096;
 
 /// Communication band configuration
 ///
@@ -89,13 +134,42 @@ pub struct CommunicationManager {
     /// REQ-FN-007: Support for all 5 communication bands
     bands: Vec<BandConfig, 5>,
 
-    /// Current primary communication band
-    /// REQ-FN-001: Primary band selection based on priority
-    primary_band: BandType,
+    /// Uplink and downlink band, power and data rate
+    /// REQ-FN-007: Each direction selected and reconfigured independently
+    links: LinkConfiguration,
 
     /// Emergency mode flag for failover protocols
     /// REQ-SF-002: Emergency communication mode
     emergency_mode: bool,
+
+    /// Security service applied to each downlink/uplink virtual channel
+    /// REQ-SC-001: Per-virtual-channel link security policy
+    security_policies: SecurityPolicyTable,
+
+    /// Link master keys and their current generations; empty until keys are
+    /// provisioned, when frames are sent and accepted unprotected
+    /// REQ-SC-003: Authenticated encryption of packets with rotatable keys
+    keys: KeyStore,
+
+    /// Licensed frequency assignments commanded tuning must fall in
+    /// REQ-SF-001: Out-of-band and unlicensed tuning rejected on acceptance
+    frequency_plan: FrequencyPlan,
+
+    /// Bands whose frames are Reed-Solomon coded
+    /// REQ-FN-007: Error correction on the noisy K and Ka band paths
+    fec: FecPolicy,
+
+    /// COP-1 frame acceptance of the command virtual channel
+    /// REQ-SF-001: Command frames accepted once and in order
+    farm: Farm,
+
+    /// Downlinked packets queued per virtual channel, sent in TM Transfer Frames
+    /// REQ-IF-002: Housekeeping, science and playback interleaved by weight
+    downlink_mux: VcMultiplexer<DOWNLINK_QUEUE_LEN>,
+
+    /// Frames sent, received and rejected, and retransmissions
+    /// REQ-NF-001: Link performance reported in RF housekeeping
+    stats: LinkStats,
 }
 
 impl CommunicationManager {
@@ -163,8 +237,15 @@ impl CommunicationManager {
 
         Self {
             bands,
-            primary_band: BandType::SBand,  // REQ-FN-007: S-band as default primary
+            links: LinkConfiguration::default(), // REQ-FN-007: S-band uplink, X-band downlink
             emergency_mode: false,          // REQ-SF-002: Normal operation mode
+            security_policies: SecurityPolicyTable::default(), // REQ-SC-001: HK clear, science encrypted
+            keys: KeyStore::new(),          // REQ-SC-003: Provisioned by install_link_key
+            frequency_plan: FrequencyPlan::default(), // REQ-FN-007: Mission baseline licences
+            fec: FecPolicy::default(),      // REQ-FN-007: K and Ka band coded
+            farm: Farm::default(),          // REQ-SF-001: Open, expecting frame 0
+            downlink_mux: VcMultiplexer::default(), // REQ-IF-002: HK first, then science, playback
+            stats: LinkStats::new(),        // REQ-NF-001: Counted from boot
         }
     }
 
@@ -241,14 +322,94 @@ impl CommunicationManager {
     pub fn set_emergency_mode(&mut self, emergency: bool) {
         self.emergency_mode = emergency;
         if emergency {
-            self.primary_band = BandType::UhfBand; // REQ-SF-002: Switch to most reliable band
+            self.fall_back_downlink(BandType::UhfBand); // REQ-SF-002: Switch to most reliable band
+        }
+    }
+
+    /// Get the configuration of one link direction
+    ///
+    /// Parameters:
+    /// - direction: Uplink or downlink
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-FN-007: Independent uplink and downlink band selection
+    ///
+    /// Returns:
+    /// Band, power level and data rate of the direction
+    pub fn link(&self, direction: LinkDirection) -> &DirectionalLink {
+        self.links.direction(direction)
+    }
+
+    /// Reconfigure one link direction
+    ///
+    /// The other direction is left untouched. A new downlink also sets the
+    /// power level and data rate of its band's transmitter.
+    ///
+    /// Parameters:
+    /// - direction: Uplink or downlink
+    /// - link: New band, power level and data rate
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-FN-007: Asymmetric uplink/downlink band configuration
+    /// - REQ-NF-004: Per-direction transmit power
+    ///
+    /// Returns:
+    /// Result<()> indicating success, or a rejected configuration for an
+    /// invalid setting or an inactive band
+    pub fn configure_link(&mut self, direction: LinkDirection, link: DirectionalLink) -> Result<()> {
+        let band_active = self
+            .get_band_config(link.band)
+            .is_some_and(|config| config.is_active);
+        if !band_active {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "band",
+                value: "<link configuration>",
+                reason: "band not active",
+            });
+        }
+
+        self.links.set(direction, link)?;
+
+        if direction == LinkDirection::Downlink {
+            if let Some(config) = self.bands.iter_mut().find(|b| b.band_type == link.band) {
+                config.power_level = link.power_level;
+                config.data_rate = link.data_rate_bps;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the downlink to a fallback band at that band's current settings
+    ///
+    /// The uplink is unchanged: commands keep arriving on the uplink band,
+    /// and the UHF emergency lane is always serviced.
+    fn fall_back_downlink(&mut self, band: BandType) {
+        if let Some(config) = self.get_band_config(band) {
+            self.links.downlink = DirectionalLink::new(band, config.power_level, config.data_rate);
         }
     }
 }
 
-/// Global communication manager instance
+/// Global communication manager instance, empty until [`initialize`]
 /// REQ-NF-003: Thread-safe global state management for Embassy async
-static mut COMM_MANAGER: Option<CommunicationManager> = None;
+static COMM_MANAGER: Mutex<CriticalSectionRawMutex, RefCell<Option<CommunicationManager>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Run `f` on the communication manager, escalated to FDIR if used before
+/// [`initialize`]
+///
+/// `f` runs inside a critical section, so it must not await and must not
+/// call back into `with_manager`; helpers that need the manager take it as
+/// an argument instead.
+fn with_manager<R>(f: impl FnOnce(&mut CommunicationManager) -> Result<R>) -> Result<R> {
+    COMM_MANAGER.lock(|manager| match manager.borrow_mut().as_mut() {
+        Some(manager) => f(manager),
+        None => Err(error_handling::escalate_invariant(
+            "COMM",
+            "Communication manager not initialized",
+        )),
+    })
+}
 
 /// Initialize communication system
 ///
@@ -264,9 +425,7 @@ static mut COMM_MANAGER: Option<CommunicationManager> = None;
 /// Result<()> indicating success or hardware initialization failure
 pub async fn initialize() -> Result<()> {
     // Create global communication manager instance
-    unsafe {
-        COMM_MANAGER = Some(CommunicationManager::new());
-    }
+    COMM_MANAGER.lock(|manager| *manager.borrow_mut() = Some(CommunicationManager::new()));
 
     // Initialize hardware transceivers for all bands (REQ-IF-001)
     hardware::initialize_transceivers().await?;
@@ -290,14 +449,41 @@ pub async fn initialize() -> Result<()> {
 /// Returns:
 /// Result<()> indicating transmission success or failure
 pub async fn send_high_priority(message: &Message) -> Result<()> {
-    let manager = unsafe { COMM_MANAGER.as_ref().unwrap() };
-    let band = manager.select_optimal_band(message); // REQ-FN-001: Band selection
+    // REQ-FN-001: Band selection
+    let band = with_manager(|manager| Ok(manager.select_optimal_band(message)))?;
 
     // Create CCSDS-compliant packet (REQ-IF-002)
     let packet = create_message_packet(message, band)?;
 
-    // Transmit with high priority timing constraint (REQ-PF-001)
-    transmit_packet_on_band(&packet, band, Some(Duration::from_millis(10))).await
+    // Transmit with high priority timing constraint (REQ-PF-001), retrying
+    // transient transceiver failures within the same deadline (REQ-NF-004)
+    let mut retry =
+        HIGH_PRIORITY_RETRY.start(Instant::now().as_millis(), message.id.value() as u32);
+    let mut log_attempt = |record: &AttemptRecord<'_>| {
+        if let RetryDecision::GiveUp(_) = record.decision {
+            error_handling::log_warning("High priority transmission failed after retries");
+        }
+    };
+
+    loop {
+        let attempt =
+            transmit_packet_expedited(&packet, band, Some(Duration::from_millis(10))).await;
+        let error = match attempt {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        match retry.on_failure(Instant::now().as_millis(), &error, &mut log_attempt) {
+            RetryDecision::RetryAfter { delay_ms } => {
+                with_manager(|manager| {
+                    manager.stats.retransmission();
+                    Ok(())
+                })?;
+                Timer::after(Duration::from_millis(u64::from(delay_ms))).await;
+            }
+            RetryDecision::GiveUp(_) => return Err(error),
+        }
+    }
 }
 
 /// Send medium priority message
@@ -315,13 +501,12 @@ pub async fn send_high_priority(message: &Message) -> Result<()> {
 /// Returns:
 /// Result<()> indicating transmission success or failure
 pub async fn send_medium_priority(message: &Message) -> Result<()> {
-    let manager = unsafe { COMM_MANAGER.as_ref().unwrap() };
-    let band = manager.select_optimal_band(message);
+    let band = with_manager(|manager| Ok(manager.select_optimal_band(message)))?;
 
     let packet = create_message_packet(message, band)?;
 
     // Medium priority timing constraint (REQ-PF-001)
-    transmit_packet_on_band(&packet, band, Some(Duration::from_millis(100))).await
+    transmit_packet_expedited(&packet, band, Some(Duration::from_millis(100))).await
 }
 
 /// Send low priority message
@@ -339,13 +524,12 @@ pub async fn send_medium_priority(message: &Message) -> Result<()> {
 /// Returns:
 /// Result<()> indicating transmission success or failure
 pub async fn send_low_priority(message: &Message) -> Result<()> {
-    let manager = unsafe { COMM_MANAGER.as_ref().unwrap() };
-    let band = manager.select_optimal_band(message);
+    let band = with_manager(|manager| Ok(manager.select_optimal_band(message)))?;
 
     let packet = create_message_packet(message, band)?;
 
     // Low priority timing constraint (REQ-PF-001)
-    transmit_packet_on_band(&packet, band, Some(Duration::from_secs(1))).await
+    transmit_packet_expedited(&packet, band, Some(Duration::from_secs(1))).await
 }
 
 /// Transmit telemetry packet
@@ -370,6 +554,219 @@ pub async fn transmit_telemetry(packet: &TelemetryPacket) -> Result<()> {
     transmit_packet_on_band(&ccsds_packet, packet.band, None).await
 }
 
+/// Transmit an RF housekeeping frame on its dedicated APID
+///
+/// Uses the shared telemetry payload format so the ground decodes it with the
+/// same codec as regular telemetry.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-PF-002: Link quality monitoring
+pub async fn transmit_rf_housekeeping(data: &TelemetryData, sequence: u16) -> Result<()> {
+    let payload = data.to_payload()?;
+    let packet = SpacePacket::new(
+        PacketType::Telemetry,
+        RF_HOUSEKEEPING_APID,
+        sequence & 0x3FFF,
+        &payload,
+        None,
+    )?;
+
+    // Housekeeping rides the downlink band with regular telemetry
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Transmit an EPS summary frame on its dedicated APID
+///
+/// Uses the shared telemetry payload format, like RF housekeeping.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-NF-004: Power management
+pub async fn transmit_eps_summary(data: &TelemetryData, sequence: u16) -> Result<()> {
+    let payload = data.to_payload()?;
+    let packet = SpacePacket::new(
+        PacketType::Telemetry,
+        EPS_APID,
+        sequence & 0x3FFF,
+        &payload,
+        None,
+    )?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Transmit a crosslink ranging frame on its dedicated APID
+///
+/// Uses the shared telemetry payload format, like the EPS summary.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-FN-007: Multi-band communication (S-band crosslink ranging)
+pub async fn transmit_crosslink_ranging(data: &TelemetryData, sequence: u16) -> Result<()> {
+    let payload = data.to_payload()?;
+    let packet = SpacePacket::new(
+        PacketType::Telemetry,
+        CROSSLINK_RANGING_APID,
+        sequence & 0x3FFF,
+        &payload,
+        None,
+    )?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Transmit a navigation solution frame on its dedicated APID
+///
+/// Uses the shared telemetry payload format, like the crosslink ranging
+/// measurement.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-NF-001: System health monitoring (navigation state)
+pub async fn transmit_navigation(data: &TelemetryData, sequence: u16) -> Result<()> {
+    let payload = data.to_payload()?;
+    let packet = SpacePacket::new(
+        PacketType::Telemetry,
+        NAVIGATION_APID,
+        sequence & 0x3FFF,
+        &payload,
+        None,
+    )?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next execution report packet
+static EXECUTION_REPORT_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit a command execution report on its dedicated APID
+///
+/// Reports ride the downlink band with housekeeping so the ground can archive
+/// the result and measured execution time of every command.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-PF-001: Command response time evidence
+pub async fn transmit_execution_report(report: &ExecutionReport) -> Result<()> {
+    let sequence = EXECUTION_REPORT_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = report.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next event log packet
+static EVENT_LOG_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit a compressed event log block on its dedicated APID
+///
+/// Blocks ride the downlink band with housekeeping; compression keeps the
+/// onboard log from crowding telemetry out of the scarce S-band and UHF passes.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-NF-002: Compressed event log downlink
+pub async fn transmit_event_log<const N: usize>(block: &EventLogCompressor<N>) -> Result<()> {
+    let sequence = EVENT_LOG_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = block.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next memory dump packet
+static MEMORY_DUMP_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit a memory dump segment on its dedicated APID
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-FN-005: Memory dump diagnostics
+pub async fn transmit_memory_dump_segment(segment: &MemoryDumpSegment) -> Result<()> {
+    let sequence = MEMORY_DUMP_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = segment.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next dwell packet
+static DWELL_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit a batch of dwell samples on its dedicated APID
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-FN-005: Dwell diagnostics
+pub async fn transmit_dwell_batch(batch: &DwellBatch) -> Result<()> {
+    let sequence = DWELL_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = batch.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next file listing packet
+static FILE_LISTING_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit a mass memory directory listing on its dedicated APID
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-FN-005: Mass memory file listing
+pub async fn transmit_file_listing(listing: &FileListing) -> Result<()> {
+    let sequence = FILE_LISTING_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = listing.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next command load manifest packet
+static LOAD_MANIFEST_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit the manifest of an uplinked command load on the command-load APID
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-SF-001: Outcome of every load entry reported to the ground
+pub async fn transmit_load_manifest(manifest: &LoadManifest) -> Result<()> {
+    let sequence = LOAD_MANIFEST_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = manifest.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next recorder file manifest packet
+static FILE_MANIFEST_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit the manifest of a recorder pass on the file manifest APID
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS telemetry packet transmission
+/// - REQ-NF-004: Files of the pass described for retransmission requests
+pub async fn transmit_file_manifest(manifest: &FileManifest) -> Result<()> {
+    let sequence = FILE_MANIFEST_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = manifest.to_packet(sequence)?;
+
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
+
+/// Sequence count of the next loopback echo packet
+static LOOPBACK_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Echo a loopback test back on the band it asks for
+///
+/// Stamps the echo with the onboard time the command was received and the
+/// time it is handed to the transceiver.
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: End-to-end check of the requested band
+/// - REQ-PF-001: Onboard timestamps for uplink and downlink delay measurement
+pub async fn transmit_loopback_echo(request: &LoopbackRequest, received_ms: u64) -> Result<()> {
+    let sequence = LOOPBACK_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let echo = request.echo(received_ms, Instant::now().as_millis());
+    let packet = echo.to_packet(sequence)?;
+
+    transmit_packet_expedited(&packet, request.band, None).await
+}
+
 /// Create CCSDS packet from message
 ///
 /// Converts a space communication message into a CCSDS-compliant space packet
@@ -462,19 +859,14 @@ fn create_message_packet(message: &Message, band: BandType) -> Result<SpacePacke
     // Create CCSDS packet with priority-based APID assignment (REQ-FN-001)
     // CCSDS 133.0-B-2: Application Process Identifier (APID) assignment per priority
     // NASA-STD-8739.8: Deterministic APID assignment for software assurance
-    let apid = match message.priority {
-        MessagePriority::Emergency => 0x001,  // REQ-SF-002: Emergency APID per NASA-STD-8719.13A
-        MessagePriority::Critical => 0x002,   // Critical operations APID per DoD-STD-2167A
-        MessagePriority::High => 0x003,       // High priority APID
-        MessagePriority::Medium => 0x004,     // Medium priority APID
-        MessagePriority::Low => 0x005,        // Low priority APID
-    };
+    // Same mapping the ground station uses for uplinked commands
+    let apid = message.priority.command_apid();
 
     // Create CCSDS space packet per CCSDS 133.0-B-2 specification (REQ-IF-002)
     SpacePacket::new(
         PacketType::Command,
         apid,
-        message.id.value() as u16,  // Sequence number from message ID per CCSDS 133.0-B-2
+        (message.id.value() & 0x3FFF) as u16,  // 14-bit sequence count from message ID per CCSDS 133.0-B-2
         &payload_data,
         None,                       // No error control for commands (handled at link layer per CCSDS 132.0-B-2)
     )
@@ -505,75 +897,9 @@ fn create_message_packet(message: &Message, band: BandType) -> Result<SpacePacke
 /// Returns:
 /// Result<SpacePacket> CCSDS telemetry packet ready for transmission
 fn create_telemetry_packet(packet: &TelemetryPacket) -> Result<SpacePacket> {
-    // Serialize telemetry data with CCSDS format (REQ-IF-002)
-    let mut payload_data = Vec::<u8, 2048>::new();
-
-    // Add timestamp in big-endian format for network transmission
-    let timestamp_bytes = packet.data.timestamp.to_be_bytes();
-    payload_data.extend_from_slice(&timestamp_bytes).map_err(|_| {
-        SpaceCommError::memory_error(
-            space_comms_shared::error::MemoryErrorType::BufferOverflow,
-            Some(8)
-        )
-    })?;
-
-    // Add measurements count for parsing validation
-    let measurement_count = packet.data.measurements.len() as u16;
-    let count_bytes = measurement_count.to_be_bytes();
-    payload_data.extend_from_slice(&count_bytes).map_err(|_| {
-        SpaceCommError::memory_error(
-            space_comms_shared::error::MemoryErrorType::BufferOverflow,
-            Some(2)
-        )
-    })?;
-
-    // Serialize measurement data with type-specific encoding (REQ-PF-002)
-    for measurement in &packet.data.measurements {
-        // Add measurement ID for identification
-        let id_bytes = measurement.measurement_id.to_be_bytes();
-        payload_data.extend_from_slice(&id_bytes).map_err(|_| {
-            SpaceCommError::memory_error(
-                space_comms_shared::error::MemoryErrorType::BufferOverflow,
-                Some(2)
-            )
-        })?;
-
-        // Add measurement value with type-specific serialization
-        match &measurement.value {
-            // Floating-point measurements (temperature, voltage, etc.)
-            space_comms_shared::telemetry::MeasurementValue::Float(val) => {
-                let val_bytes = (*val as f32).to_be_bytes();
-                payload_data.extend_from_slice(&val_bytes).map_err(|_| {
-                    SpaceCommError::memory_error(
-                        space_comms_shared::error::MemoryErrorType::BufferOverflow,
-                        Some(4)
-                    )
-                })?;
-            }
-
-            // Integer measurements (counters, status codes, etc.)
-            space_comms_shared::telemetry::MeasurementValue::Integer(val) => {
-                let val_bytes = (*val as i32).to_be_bytes();
-                payload_data.extend_from_slice(&val_bytes).map_err(|_| {
-                    SpaceCommError::memory_error(
-                        space_comms_shared::error::MemoryErrorType::BufferOverflow,
-                        Some(4)
-                    )
-                })?;
-            }
-
-            // Other measurement types - use placeholder for future expansion
-            _ => {
-                let placeholder = [0u8; 4];
-                payload_data.extend_from_slice(&placeholder).map_err(|_| {
-                    SpaceCommError::memory_error(
-                        space_comms_shared::error::MemoryErrorType::BufferOverflow,
-                        Some(4)
-                    )
-                })?;
-            }
-        }
-    }
+    // Serialize timestamp and measurements with per-value quality flags
+    // using the shared telemetry payload format (REQ-IF-002, REQ-PF-002)
+    let payload_data = packet.data.to_payload()?;
 
     // Create CCSDS telemetry packet per CCSDS 102.0-B-5 and 133.0-B-2 (REQ-IF-002)
     SpacePacket::new(
@@ -586,32 +912,138 @@ fn create_telemetry_packet(packet: &TelemetryPacket) -> Result<SpacePacket> {
 }
 
 /// Transmit packet on specified band
+///
+/// The packet is queued on the virtual channel of its APID, and every frame
+/// the downlink multiplexer then has ready goes out on the band. Packets on
+/// a channel still filling wait for `service_downlink`.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: Packets downlinked in TM Transfer Frames
+/// - REQ-SC-003: Protected as the policy of its virtual channel and APID requires
+///
+/// Returns:
+/// Result<()>, an error for an inactive band, a full channel queue or a
+/// transceiver failure
 async fn transmit_packet_on_band(
     packet: &SpacePacket,
     band: BandType,
     timeout: Option<Duration>,
 ) -> Result<()> {
-    // Get band configuration
-    let manager = unsafe { COMM_MANAGER.as_ref().unwrap() };
-    let band_config = manager.get_band_config(band).ok_or_else(|| {
-        SpaceCommError::hardware_failure("Band not configured", 0)
+    check_band_active(band)?;
+    queue_downlink(packet)?;
+
+    transmit_ready_frames(band, timeout).await
+}
+
+/// Transmit packet on specified band without waiting for its frame to fill
+///
+/// For messages with a delivery deadline and loopback echoes, whose
+/// downlink delay is measured: everything queued on the packet's virtual
+/// channel goes out now, the last frame filled out with idle data.
+///
+/// Requirements Fulfilled:
+/// - REQ-PF-001: Message deadlines not lengthened by frame filling
+///
+/// Returns:
+/// Result<()>, errors as `transmit_packet_on_band`
+async fn transmit_packet_expedited(
+    packet: &SpacePacket,
+    band: BandType,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    check_band_active(band)?;
+    let vc = queue_downlink(packet)?;
+    with_manager(|manager| {
+        manager.downlink_mux.expedite(vc);
+        Ok(())
     })?;
 
-    if !band_config.is_active {
-        return Err(SpaceCommError::hardware_failure("Band not active", 0));
+    transmit_ready_frames(band, timeout).await
+}
+
+/// Transmit the downlink frames whose flush delay has passed
+///
+/// Called periodically, so science and playback packets queued while their
+/// channel is quiet still leave within its flush delay.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: Partly filled TM Transfer Frames completed with idle data
+/// - REQ-PF-002: Bounded latency of science and playback data
+///
+/// Returns:
+/// Result<()>, an error for a transceiver failure
+pub async fn service_downlink() -> Result<()> {
+    let band = downlink_band();
+    // Frames stay queued while the downlink band is down
+    if check_band_active(band).is_err() {
+        return Ok(());
     }
 
-    // Serialize packet
-    let packet_bytes = packet.to_bytes()?;
+    transmit_ready_frames(band, None).await
+}
+
+/// Fail unless `band` is configured and active
+fn check_band_active(band: BandType) -> Result<()> {
+    with_manager(|manager| {
+        let band_config = manager
+            .get_band_config(band)
+            .ok_or_else(|| SpaceCommError::hardware_failure("Band not configured", 0))?;
+
+        if !band_config.is_active {
+            return Err(SpaceCommError::hardware_failure("Band not active", 0));
+        }
+
+        Ok(())
+    })
+}
+
+/// Protect a packet and queue it on the virtual channel of its APID
+fn queue_downlink(packet: &SpacePacket) -> Result<u8> {
+    with_manager(|manager| {
+        // REQ-SC-003: Protected in queue order, so counters rise on each channel
+        let packet = protect_downlink(manager, packet)?;
+
+        manager
+            .downlink_mux
+            .push_packet(&packet, Instant::now().as_millis())
+    })
+}
 
-    // Select hardware transceiver and transmit
-    match band {
-        BandType::UhfBand => hardware::transmit_uhf(&packet_bytes).await,
-        BandType::SBand => hardware::transmit_s_band(&packet_bytes).await,
-        BandType::XBand => hardware::transmit_x_band(&packet_bytes).await,
-        BandType::KBand => hardware::transmit_k_band(&packet_bytes).await,
-        BandType::KaBand => hardware::transmit_ka_band(&packet_bytes).await,
-    }?;
+/// Transmit every frame the downlink multiplexer has ready on `band`
+async fn transmit_ready_frames(band: BandType, timeout: Option<Duration>) -> Result<()> {
+    loop {
+        let frame_bytes = with_manager(|manager| {
+            // REQ-IF-002: Every frame reports the FARM-1 state in its OCF
+            let clcw = manager.farm.clcw().to_bytes();
+            let Some(frame) = manager
+                .downlink_mux
+                .next_frame(Instant::now().as_millis(), Some(clcw))?
+            else {
+                return Ok(None);
+            };
+
+            // Serialize frame, Reed-Solomon coded on coded bands (CCSDS 131.0-B)
+            frame.to_bytes_with_fec(&manager.fec, band).map(Some)
+        })?;
+        // The manager is released before the transceiver is awaited
+        let Some(frame_bytes) = frame_bytes else {
+            break;
+        };
+
+        // Select hardware transceiver and transmit
+        match band {
+            BandType::UhfBand => hardware::transmit_uhf(&frame_bytes).await,
+            BandType::SBand => hardware::transmit_s_band(&frame_bytes).await,
+            BandType::XBand => hardware::transmit_x_band(&frame_bytes).await,
+            BandType::KBand => hardware::transmit_k_band(&frame_bytes).await,
+            BandType::KaBand => hardware::transmit_ka_band(&frame_bytes).await,
+        }?;
+
+        with_manager(|manager| {
+            manager.stats.frame_sent(frame_bytes.len(), Instant::now().as_millis());
+            Ok(())
+        })?;
+    }
 
     // Wait for transmission with timeout if specified
     if let Some(timeout_duration) = timeout {
@@ -622,85 +1054,524 @@ async fn transmit_packet_on_band(
 }
 
 /// Receive command packet
-pub async fn receive_command() -> Result<SpacePacket> {
-    // Check all bands for incoming commands
-    if let Ok(packet) = hardware::receive_uhf().await {
-        return parse_received_packet(&packet);
-    }
+///
+/// Polls the receiver of the configured uplink band, S-Band or X-Band. UHF is
+/// the emergency uplink lane and is serviced separately by
+/// `receive_emergency_frame`. Every TC Transfer Frame received is answered
+/// with the FARM-1 CLCW, so the ground can acknowledge or retransmit it.
+///
+/// Parameters:
+/// - buffer_available: Whether the command channel can take another command;
+///   FARM-1 holds the sequence in Wait while it cannot
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Uplink band selected independently of the downlink
+/// - REQ-IF-002: COP-1 acknowledgment of every received frame
+///
+/// Returns:
+/// Result<UplinkedCommand> with the command packet of an accepted frame, an
+/// error when nothing arrived or the frame carried no command to deliver
+pub async fn receive_command(buffer_available: bool) -> Result<UplinkedCommand> {
+    let uplink_band = link(LinkDirection::Uplink).band;
+
+    let received = match uplink_band {
+        BandType::XBand => hardware::receive_x_band()
+            .await
+            .map(|frame| parse_uplink_frame(&frame, uplink_band, buffer_available)),
+        _ => hardware::receive_s_band()
+            .await
+            .map(|frame| parse_uplink_frame(&frame, uplink_band, buffer_available)),
+    };
 
-    if let Ok(packet) = hardware::receive_s_band().await {
-        return parse_received_packet(&packet);
+    match received {
+        Ok(packet) => {
+            if transmit_clcw().await.is_err() {
+                error_handling::log_warning("CLCW transmission failed");
+            }
+            packet
+        }
+        Err(_) => Err(SpaceCommError::communication_timeout(100, "No command received")),
     }
+}
 
-    if let Ok(packet) = hardware::receive_x_band().await {
-        return parse_received_packet(&packet);
-    }
+/// Parse a frame received on the uplink band
+///
+/// Frames on a band the FEC policy codes are Reed-Solomon decoded, and up to
+/// 16 corrupted bytes per codeblock corrected, before the TC Transfer Frame is
+/// parsed. FARM-1 then decides whether the command packet it carries is
+/// delivered.
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Per-band error correction on the uplink
+/// - REQ-SF-001: Command frames accepted once and in order (COP-1 FARM-1)
+fn parse_uplink_frame(
+    bytes: &[u8],
+    band: BandType,
+    buffer_available: bool,
+) -> Result<UplinkedCommand> {
+    let now_ms = Instant::now().as_millis();
+    with_manager(|manager| {
+        let (frame, corrected_symbols) =
+            TcTransferFrame::from_bytes_with_fec(bytes, &manager.fec, band)
+                .inspect_err(|_| manager.stats.frame_error(now_ms))?;
+        if corrected_symbols > 0 {
+            error_handling::log_info("Uplink frame corrected by Reed-Solomon decoding");
+        }
 
-    Err(SpaceCommError::communication_timeout(100, "No command received"))
+        manager.stats.frame_received(bytes.len(), now_ms);
+        match manager.farm.receive(&frame, buffer_available) {
+            FarmVerdict::Accept => unprotect_uplink(manager, &parse_received_packet(&frame.data)?),
+            FarmVerdict::Control => {
+                error_handling::log_info("COP-1 control command accepted");
+                Err(SpaceCommError::invalid_packet("COP-1 control frame", None))
+            }
+            FarmVerdict::Discard => Err(SpaceCommError::invalid_packet(
+                "Frame discarded by FARM-1",
+                Some(u32::from(frame.sequence)),
+            )),
+        }
+    })
 }
 
-/// Parse received packet bytes into SpacePacket
-fn parse_received_packet(bytes: &[u8]) -> Result<SpacePacket> {
-    if bytes.len() < 6 {
-        return Err(SpaceCommError::invalid_packet("Packet too short", None));
-    }
+/// Sequence count of the CLCW packets
+static CLCW_SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// Transmit the FARM-1 report of the command virtual channel
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: COP-1 CLCW reported on the downlink
+/// - REQ-NF-004: Lost command frames requested again by the retransmit flag
+pub async fn transmit_clcw() -> Result<()> {
+    let sequence = CLCW_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let packet = with_manager(|manager| manager.farm.clcw().to_packet(sequence))?;
 
-    // Parse CCSDS header
-    let header = SpacePacketHeader::from_bytes(&bytes[0..6])?;
+    transmit_packet_on_band(&packet, downlink_band(), None).await
+}
 
-    // Extract data
-    let data_end = core::cmp::min(bytes.len(), 6 + header.data_length as usize + 1);
-    let mut data = Vec::<u8, 2048>::new();
-    data.extend_from_slice(&bytes[6..data_end]).map_err(|_| {
-        SpaceCommError::memory_error(
-            space_comms_shared::error::MemoryErrorType::BufferOverflow,
-            Some(data_end - 6)
-        )
-    })?;
+/// Receive a raw frame on the emergency uplink lane
+///
+/// The UHF receiver is reserved for the emergency virtual channel
+/// (`virtual_channels::EMERGENCY`), fed by the ground station's dedicated
+/// emergency socket. Frames are returned undecoded, with their error control,
+/// so the caller can route and CRC-check them without queueing.
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-002: Emergency commands bypass normal command queues
+pub async fn receive_emergency_frame() -> Result<Vec<u8, 512>> {
+    hardware::receive_uhf().await
+}
+
+/// Parse received packet bytes into SpacePacket
+///
+/// The frame's declared length and CRC are checked against the wire format
+/// (`space_comms_shared::wire`) before its data field is accepted.
+///
+/// Requirements Fulfilled:
+/// - REQ-IF-002: CCSDS Packet Error Control checked on every uplinked frame
+pub fn parse_received_packet(bytes: &[u8]) -> Result<SpacePacket> {
+    let (header, data) = wire::split_frame(bytes)?;
+    let crc = wire::WireReader::new(&bytes[bytes.len() - wire::ERROR_CONTROL_LEN..]).get()?;
 
     Ok(SpacePacket {
         header,
         secondary_header: None,
-        data,
-        error_control: None,
+        data: Vec::from_slice(data).map_err(|_| {
+            SpaceCommError::memory_error(
+                space_comms_shared::error::MemoryErrorType::BufferOverflow,
+                Some(data.len())
+            )
+        })?,
+        error_control: Some(crc),
     })
 }
 
 /// Check if communication system is healthy
 pub async fn is_communication_healthy() -> bool {
-    let manager = unsafe { COMM_MANAGER.as_ref().unwrap() };
-
     // Check if at least one band is active with good signal quality
-    manager.bands.iter().any(|band| {
-        band.is_active && band.signal_quality > 50
+    with_manager(|manager| {
+        Ok(manager
+            .bands
+            .iter()
+            .any(|band| band.is_active && band.signal_quality > 50))
     })
+    .unwrap_or(false)
 }
 
 /// Set emergency communication mode
+///
+/// The downlink moves to `band`; the uplink band is unchanged.
 pub async fn set_emergency_mode(band: BandType) -> Result<()> {
-    let manager = unsafe { COMM_MANAGER.as_mut().unwrap() };
-    manager.set_emergency_mode(true);
+    with_manager(|manager| {
+        manager.set_emergency_mode(true);
+        manager.fall_back_downlink(band);
+
+        // Reduce power on other bands to conserve energy
+        for band_config in &mut manager.bands {
+            if band_config.band_type != band {
+                band_config.power_level = band_config.power_level / 2;
+            }
+        }
+
+        Ok(())
+    })?;
 
     error_handling::log_warning("Emergency communication mode activated");
 
-    // Reduce power on other bands to conserve energy
-    for band_config in &mut manager.bands {
-        if band_config.band_type != band {
-            band_config.power_level = band_config.power_level / 2;
-        }
+    Ok(())
+}
+
+/// Set the security policy for a virtual channel
+///
+/// Applies a commanded change to the virtual channel security policy table.
+/// The command channel can never be set to send in the clear.
+///
+/// Parameters:
+/// - virtual_channel: Virtual channel number to reconfigure
+/// - policy: New security service and key slot for the channel
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-001: Commandable per-virtual-channel security policy
+///
+/// Returns:
+/// Result<()> indicating success or a rejected policy change
+pub async fn set_vc_security_policy(virtual_channel: u8, policy: VcSecurityPolicy) -> Result<()> {
+    with_manager(|manager| {
+        manager
+            .security_policies
+            .set_policy(virtual_channel, policy)
+    })?;
+
+    error_handling::log_info("Virtual channel security policy updated");
+
+    Ok(())
+}
+
+/// Get the current virtual channel security policy table
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-001: Security policy reported in telemetry
+pub fn security_policies() -> SecurityPolicyTable {
+    with_manager(|manager| Ok(manager.security_policies.clone())).unwrap_or_default()
+}
+
+/// Get the security service to apply to a downlinked packet
+///
+/// Always-clear virtual channels and APIDs (beacon, safe-mode telemetry) are
+/// authenticated but never encrypted, so any station can decode them.
+///
+/// Parameters:
+/// - virtual_channel: Virtual channel the packet is sent on
+/// - apid: APID of the packet
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-002: Always-clear frames decodable by any station
+///
+/// Returns:
+/// Option<SecurityService>, None for an unknown virtual channel
+pub fn downlink_security_service(virtual_channel: u8, apid: u16) -> Option<SecurityService> {
+    with_manager(|manager| {
+        Ok(manager
+            .security_policies
+            .packet_service(virtual_channel, apid))
+    })
+    .ok()
+    .flatten()
+}
+
+/// Install a link master key
+///
+/// Master keys are provisioned at integration and are never uplinked. Once
+/// any key is installed, every frame is protected and checked as the
+/// security policy table requires.
+///
+/// Parameters:
+/// - key_id: Key slot the policies refer to
+/// - master: 256-bit master key the slot's generations are derived from
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-003: Master keys provisioned out-of-band
+///
+/// Returns:
+/// Result<()>, an error for an unknown slot or an all-zero key
+pub fn install_link_key(key_id: u8, master: [u8; KEY_LEN]) -> Result<()> {
+    with_manager(|manager| manager.keys.install(key_id, master))
+}
+
+/// Rotate a link key as commanded by `RotateKeys`
+///
+/// Frames protected with the previous generation are still accepted, so
+/// commands the ground sent before it rotated are not lost.
+///
+/// Parameters:
+/// - rotation: Key slot and the generation to move it to
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-003: Key rotation without uplinking key material
+///
+/// Returns:
+/// Result<()>, an error for an empty slot or a generation not above the
+/// current one
+pub fn rotate_link_keys(rotation: &KeyRotation) -> Result<()> {
+    with_manager(|manager| rotation.apply(&mut manager.keys))?;
+
+    error_handling::log_info("Link key rotated");
+
+    Ok(())
+}
+
+/// Protect a downlinked packet once link keys are installed
+fn protect_downlink(
+    manager: &mut CommunicationManager,
+    packet: &SpacePacket,
+) -> Result<SpacePacket> {
+    if manager.keys.is_empty() {
+        return Ok(packet.clone());
+    }
+    let vc = virtual_channels::downlink(packet.header.apid);
+    manager.keys.protect(&manager.security_policies, vc, packet)
+}
+
+/// An uplinked command packet as opened
+pub struct UplinkedCommand {
+    /// Command packet with its plaintext data field
+    pub packet: SpacePacket,
+    /// Frame counter of the packet's security header; 0 for a packet sent
+    /// unprotected
+    pub auth_counter: u64,
+}
+
+/// Verify and, where its policy calls for it, decrypt an uplinked packet
+///
+/// Packets pass unchanged until link keys are installed.
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-003: Uplinks accepted only as their policy protects them
+/// - REQ-SF-001: Frame counter kept for duplicate and replay detection
+///
+/// Returns:
+/// Result<UplinkedCommand> with the plaintext data field and the frame
+/// counter, an error for a forged, replayed or undecryptable packet
+pub fn open_uplink(packet: &SpacePacket) -> Result<UplinkedCommand> {
+    with_manager(|manager| unprotect_uplink(manager, packet))
+}
+
+/// Verify and decrypt an uplinked packet with the manager already held
+fn unprotect_uplink(
+    manager: &mut CommunicationManager,
+    packet: &SpacePacket,
+) -> Result<UplinkedCommand> {
+    if manager.keys.is_empty() {
+        return Ok(UplinkedCommand {
+            packet: packet.clone(),
+            auth_counter: 0,
+        });
     }
+    let vc = virtual_channels::uplink(packet.header.apid);
+    let opened = manager.keys.unprotect(&manager.security_policies, vc, packet)?;
+    // The security header is stripped on opening; its counter is read first
+    let authenticated = manager
+        .security_policies
+        .packet_service(vc, packet.header.apid)
+        .is_some_and(SecurityService::requires_authentication);
+    let auth_counter = if authenticated {
+        SecurityHeader::from_bytes(&packet.data)?.counter
+    } else {
+        0
+    };
+    Ok(UplinkedCommand {
+        packet: opened,
+        auth_counter,
+    })
+}
+
+/// Open a raw frame from the emergency lane
+///
+/// Emergency commands are signed and encrypted; the frame is returned with
+/// its plaintext data field and a fresh CRC, ready for
+/// `decode_command_packet`.
+///
+/// Requirements Fulfilled:
+/// - REQ-SC-003: Emergency commands signed and encrypted
+/// - REQ-FN-002: Opened without queueing on the emergency lane
+///
+/// Returns:
+/// Result<Vec<u8, 512>>, an error for a malformed, forged or replayed frame
+pub fn open_emergency_frame(frame: &[u8]) -> Result<Vec<u8, 512>> {
+    let opened = open_uplink(&parse_received_packet(frame)?)?.packet.to_bytes()?;
+    Vec::from_slice(&opened).map_err(|_| {
+        SpaceCommError::memory_error(
+            space_comms_shared::error::MemoryErrorType::BufferOverflow,
+            Some(opened.len())
+        )
+    })
+}
+
+/// Check the frequency an uplinked command tunes to
+///
+/// Acceptance check of a `ReconfigureComm` against the band's legal range
+/// and the licensed assignments, repeated onboard so a command the ground
+/// did not check is still refused. A violation is logged with its error
+/// code, which the event log downlinks.
+///
+/// Parameters:
+/// - data: Command packet data field
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Tuning kept inside each band
+/// - REQ-SF-001: Out-of-band and unlicensed tuning rejected before execution
+///
+/// Returns:
+/// Result<()>, a configuration error for a refused frequency
+pub fn check_frequency(data: &[u8]) -> Result<()> {
+    with_manager(|manager| {
+        manager
+            .frequency_plan
+            .check_command_data(data)
+            .map_err(|violation| {
+                error_handling::log_with_component(
+                    LogLevel::Warning,
+                    violation.reason(),
+                    "COMM",
+                    Some(violation.code()),
+                );
+                violation.to_error()
+            })
+    })
+}
+
+/// Turn Reed-Solomon coding on one band on or off
+///
+/// The ground station must be given the same setting, or frames on the band
+/// stop parsing at one end.
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Commandable per-band error correction
+pub async fn set_fec(band: BandType, enabled: bool) -> Result<()> {
+    with_manager(|manager| {
+        manager.fec.set(band, enabled);
+        Ok(())
+    })?;
+
+    error_handling::log_info(if enabled {
+        "Reed-Solomon coding enabled"
+    } else {
+        "Reed-Solomon coding disabled"
+    });
 
     Ok(())
 }
 
+/// Get the bands whose frames are Reed-Solomon coded
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: FEC policy reported in telemetry
+pub fn fec_policy() -> FecPolicy {
+    with_manager(|manager| Ok(manager.fec)).unwrap_or_default()
+}
+
+/// Get the link layer statistics counted since boot
+///
+/// The satellite has no demodulator SNR estimate, so it is left unset and the
+/// ground's own estimate stands for the downlink.
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-001: Frames, errors, retransmissions and throughput reported
+///
+/// Returns:
+/// LinkStats of the uplink and downlink frames
+pub fn link_stats() -> LinkStats {
+    with_manager(|manager| Ok(manager.stats)).unwrap_or_default()
+}
+
 /// Switch to backup communication band
+///
+/// Moves the downlink to UHF; commands keep arriving on the uplink band.
 pub async fn switch_to_backup_band() -> Result<()> {
-    let manager = unsafe { COMM_MANAGER.as_mut().unwrap() };
-
     // Switch to UHF as backup (most reliable)
-    manager.primary_band = BandType::UhfBand;
+    with_manager(|manager| {
+        manager.fall_back_downlink(BandType::UhfBand);
+        Ok(())
+    })?;
 
     error_handling::log_info("Switched to backup communication band");
 
     Ok(())
 }
+
+/// Reconfigure one direction of the space-ground link
+///
+/// Applies a commanded `ReconfigureComm`. The other direction keeps its
+/// band, power level and data rate.
+///
+/// Parameters:
+/// - direction: Uplink or downlink
+/// - link: New band, power level and data rate
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Asymmetric uplink/downlink band configuration
+///
+/// Returns:
+/// Result<()> indicating success or a rejected configuration
+pub async fn configure_link(direction: LinkDirection, link: DirectionalLink) -> Result<()> {
+    with_manager(|manager| manager.configure_link(direction, link))?;
+
+    error_handling::log_info(match direction {
+        LinkDirection::Uplink => "Uplink band reconfigured",
+        LinkDirection::Downlink => "Downlink band reconfigured",
+    });
+
+    Ok(())
+}
+
+/// Get the current configuration of one link direction
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-007: Link configuration reported in telemetry
+pub fn link(direction: LinkDirection) -> DirectionalLink {
+    with_manager(|manager| Ok(*manager.link(direction)))
+        .unwrap_or_else(|_| *LinkConfiguration::default().direction(direction))
+}
+
+/// Band the downlink is currently configured on
+fn downlink_band() -> BandType {
+    link(LinkDirection::Downlink).band
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Master key provisioned in slot 0 on both ends of the test link
+    const MASTER_KEY: [u8; KEY_LEN] = [0x5A; KEY_LEN];
+
+    #[test]
+    fn test_emergency_frame_opened_to_its_command_packet() -> Result<()> {
+        COMM_MANAGER.lock(|manager| *manager.borrow_mut() = Some(CommunicationManager::new()));
+        install_link_key(0, MASTER_KEY)?;
+        let mut ground = KeyStore::new();
+        ground.install(0, MASTER_KEY)?;
+
+        // Sealed as the ground's emergency lane sends it
+        let apid = MessagePriority::Emergency.command_apid();
+        let command =
+            SpacePacket::new(PacketType::Command, apid, 3, b"\x00\x00\x00\x01ABORT", None)?;
+        let vc = virtual_channels::uplink(apid);
+        let frame = ground
+            .protect(&SecurityPolicyTable::default(), vc, &command)?
+            .to_bytes()?;
+
+        // Opened to the plaintext command packet with a fresh CRC
+        let opened = open_emergency_frame(&frame)?;
+        assert_eq!(opened.as_slice(), command.to_bytes()?.as_slice());
+
+        // A replayed or corrupted frame is refused
+        assert!(open_emergency_frame(&frame).is_err());
+        let mut corrupted = ground
+            .protect(&SecurityPolicyTable::default(), vc, &command)?
+            .to_bytes()?;
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0xFF;
+        assert!(open_emergency_frame(&corrupted).is_err());
+        Ok(())
+    }
+}
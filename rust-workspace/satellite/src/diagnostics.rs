@@ -0,0 +1,328 @@
+//! Memory dump and dwell execution
+//!
+//! The command processor hands `DumpMemory` and `DwellSample` commands here
+//! instead of to the command dispatcher; they are still reported like any
+//! other command. Accepting a command only records
+//! it; the diagnostics task then reads one dump segment per cycle, so a long
+//! dump never holds up telemetry, and takes dwell samples on the dwell's
+//! interval, downlinking them a batch at a time. One dump and one dwell can
+//! run at once; another request of the same kind is rejected until the
+//! running one finishes.
+//!
+//! The address space is simulated: flash holds a fixed pattern and SRAM a
+//! pattern that changes every second, so repeated dumps and memory dwells
+//! show both stable and changing contents. Measurement dwells read the
+//! temperature, voltage and current sensors directly, at their measurement
+//! IDs, rather than waiting for the telemetry collector.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (memory dump and dwell diagnostics)
+//! - REQ-NF-002: Memory Constraints (one segment or batch buffered at a time)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+use heapless::{Deque, Vec};
+
+use space_comms_shared::{
+    diagnostics::{
+        DiagnosticRequest, DwellBatch, DwellRequest, DwellSource, MemoryDumpRequest,
+        MemoryDumpSegment, DUMP_SEGMENT_DATA_LEN,
+    },
+    Result, SpaceCommError,
+};
+
+use crate::error_handling;
+use crate::hardware;
+
+/// Dwell batches held for downlink: one closed at a gap and the final one
+const READY_BATCHES: usize = 2;
+
+/// Region of the simulated address space
+struct Region {
+    base: u32,
+    length: u32,
+}
+
+impl Region {
+    fn contains(&self, address: u32, length: u32) -> bool {
+        let end = u64::from(address) + u64::from(length);
+        address >= self.base && end <= u64::from(self.base) + u64::from(self.length)
+    }
+}
+
+/// Program flash
+const FLASH: Region = Region {
+    base: 0x0800_0000,
+    length: 0x0008_0000,
+};
+
+/// Working RAM
+const SRAM: Region = Region {
+    base: 0x2000_0000,
+    length: 0x0002_0000,
+};
+
+/// Dump in progress
+struct ActiveDump {
+    dump_id: u16,
+    request: MemoryDumpRequest,
+    next_offset: u32,
+}
+
+/// Dwell in progress
+struct ActiveDwell {
+    dwell_id: u16,
+    request: DwellRequest,
+    started_ms: u64,
+    /// Slot of the next sample
+    next_index: u32,
+    batch: DwellBatch,
+}
+
+/// Running diagnostics, dwell batches awaiting downlink and the identifier
+/// for the next diagnostic
+struct Diagnostics {
+    dump: Option<ActiveDump>,
+    dwell: Option<ActiveDwell>,
+    ready: Deque<DwellBatch, READY_BATCHES>,
+    next_id: u16,
+}
+
+static DIAGNOSTICS: Mutex<CriticalSectionRawMutex, RefCell<Diagnostics>> =
+    Mutex::new(RefCell::new(Diagnostics {
+        dump: None,
+        dwell: None,
+        ready: Deque::new(),
+        next_id: 1,
+    }));
+
+/// Start a memory dump or dwell
+///
+/// The request is checked against its limits and the simulated address
+/// space or sensor list before it is accepted.
+pub fn start(request: DiagnosticRequest) -> Result<()> {
+    request.validate()?;
+    match request {
+        DiagnosticRequest::Dump(request) => start_dump(request),
+        DiagnosticRequest::Dwell(request) => start_dwell(request),
+    }
+}
+
+fn start_dump(request: MemoryDumpRequest) -> Result<()> {
+    let mapped = [FLASH, SRAM]
+        .iter()
+        .any(|region| region.contains(request.address, request.length));
+    if !mapped {
+        return Err(SpaceCommError::ConfigurationError {
+            parameter: "address",
+            value: "<address>",
+            reason: "memory dump range is not mapped",
+        });
+    }
+
+    DIAGNOSTICS.lock(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        if diagnostics.dump.is_some() {
+            return Err(busy("memory dump"));
+        }
+        let dump_id = diagnostics.take_id();
+        diagnostics.dump = Some(ActiveDump {
+            dump_id,
+            request,
+            next_offset: 0,
+        });
+        Ok(())
+    })?;
+    error_handling::log_info("Memory dump started");
+    Ok(())
+}
+
+fn start_dwell(request: DwellRequest) -> Result<()> {
+    let mapped = match request.source {
+        DwellSource::Memory => {
+            FLASH.contains(request.target, 4) || SRAM.contains(request.target, 4)
+        }
+        DwellSource::Measurement => sensor_for(request.target as u16).is_some(),
+    };
+    if !mapped {
+        return Err(SpaceCommError::ConfigurationError {
+            parameter: "target",
+            value: "<target>",
+            reason: "dwell target is not mapped or not a sensor measurement",
+        });
+    }
+
+    DIAGNOSTICS.lock(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        if diagnostics.dwell.is_some() {
+            return Err(busy("dwell"));
+        }
+        let dwell_id = diagnostics.take_id();
+        diagnostics.dwell = Some(ActiveDwell {
+            dwell_id,
+            request,
+            started_ms: Instant::now().as_millis(),
+            next_index: 0,
+            batch: DwellBatch::new(dwell_id, &request, 0),
+        });
+        Ok(())
+    })?;
+    error_handling::log_info("Dwell started");
+    Ok(())
+}
+
+impl Diagnostics {
+    fn take_id(&mut self) -> u16 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        id
+    }
+}
+
+fn busy(resource: &'static str) -> SpaceCommError {
+    SpaceCommError::ResourceExhausted {
+        resource,
+        current_usage: 1,
+        max_usage: 1,
+    }
+}
+
+/// Read the next segment of the running dump, if any
+///
+/// The dump is finished once its last segment has been read.
+pub fn next_dump_segment() -> Option<MemoryDumpSegment> {
+    DIAGNOSTICS.lock(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        let dump = diagnostics.dump.as_mut()?;
+
+        let remaining = dump.request.length - dump.next_offset;
+        let length = remaining.min(DUMP_SEGMENT_DATA_LEN as u32);
+        let start = dump.request.address + dump.next_offset;
+        let data: Vec<u8, DUMP_SEGMENT_DATA_LEN> =
+            (start..start + length).map(read_byte).collect();
+
+        let segment = MemoryDumpSegment {
+            dump_id: dump.dump_id,
+            address: dump.request.address,
+            total_length: dump.request.length,
+            offset: dump.next_offset,
+            data,
+        };
+        dump.next_offset += length;
+        if dump.next_offset == dump.request.length {
+            diagnostics.dump = None;
+        }
+        Some(segment)
+    })
+}
+
+/// Take the next sample of the running dwell if it is due
+///
+/// Each sample belongs to the interval slot it was taken in. Slots missed
+/// because the sensor is slower than the dwell interval, or because a read
+/// failed, are left as gaps: the batch is closed and the next sample starts
+/// a new one at its own slot. Full, closed and final batches wait for
+/// [`next_dwell_batch`].
+pub async fn sample_dwell() {
+    let now_ms = Instant::now().as_millis();
+    let due = DIAGNOSTICS.lock(|diagnostics| {
+        let diagnostics = diagnostics.borrow();
+        let dwell = diagnostics.dwell.as_ref()?;
+        let slot = (now_ms - dwell.started_ms) / u64::from(dwell.request.interval_ms);
+        (slot >= u64::from(dwell.next_index)).then_some((slot as u32, dwell.request))
+    });
+    let Some((slot, request)) = due else {
+        return;
+    };
+
+    // A slot past the end only closes the dwell
+    let sample = match request.source {
+        _ if slot >= request.sample_count() => None,
+        DwellSource::Memory => Some(read_word(request.target)),
+        DwellSource::Measurement => read_sensor(request.target as u16).await.map(f32::to_bits),
+    };
+
+    DIAGNOSTICS.lock(|diagnostics| {
+        let mut diagnostics = diagnostics.borrow_mut();
+        let Diagnostics { dwell, ready, .. } = &mut *diagnostics;
+        let Some(active) = dwell.as_mut() else {
+            return;
+        };
+
+        let batch = &mut active.batch;
+        let contiguous = batch.first_index + batch.samples.len() as u32 == slot;
+        if let Some(sample) = sample {
+            if !contiguous && !batch.samples.is_empty() {
+                let next = DwellBatch::new(active.dwell_id, &active.request, slot);
+                let _ = ready.push_back(core::mem::replace(batch, next));
+            }
+            if batch.samples.is_empty() {
+                batch.first_index = slot;
+            }
+            let _ = batch.samples.push(sample);
+        }
+        active.next_index = slot + 1;
+
+        let ended = active.next_index >= active.request.sample_count();
+        if ended || batch.samples.is_full() {
+            let next = DwellBatch::new(active.dwell_id, &active.request, active.next_index);
+            let mut closed = core::mem::replace(batch, next);
+            closed.last = ended;
+            let _ = ready.push_back(closed);
+        }
+        if ended {
+            *dwell = None;
+        }
+    });
+}
+
+/// Take the next dwell batch ready for downlink, oldest first
+pub fn next_dwell_batch() -> Option<DwellBatch> {
+    DIAGNOSTICS.lock(|diagnostics| diagnostics.borrow_mut().ready.pop_front())
+}
+
+/// Byte at `address` of the simulated address space; zero outside it
+fn read_byte(address: u32) -> u8 {
+    if FLASH.contains(address, 1) {
+        (address ^ (address >> 8) ^ 0xA5) as u8
+    } else if SRAM.contains(address, 1) {
+        let uptime_s = Instant::now().as_secs() as u32;
+        (address ^ uptime_s.wrapping_mul(0x9E37_79B9) >> (address % 4 * 8)) as u8
+    } else {
+        0
+    }
+}
+
+/// Big-endian word at a word-aligned `address`
+fn read_word(address: u32) -> u32 {
+    u32::from_be_bytes([
+        read_byte(address),
+        read_byte(address + 1),
+        read_byte(address + 2),
+        read_byte(address + 3),
+    ])
+}
+
+/// Sensor kind and number behind a measurement ID, as laid out by the
+/// telemetry collector
+fn sensor_for(measurement_id: u16) -> Option<(u16, u16)> {
+    match measurement_id {
+        0x0001..=0x0004 => Some((0x0001, measurement_id - 0x0001)),
+        0x0010..=0x0013 => Some((0x0010, measurement_id - 0x0010)),
+        0x0020..=0x0023 => Some((0x0020, measurement_id - 0x0020)),
+        _ => None,
+    }
+}
+
+/// Read the sensor behind `measurement_id`
+async fn read_sensor(measurement_id: u16) -> Option<f32> {
+    let (base, sensor_id) = sensor_for(measurement_id)?;
+    let reading = match base {
+        0x0001 => hardware::read_temperature_sensor(sensor_id).await,
+        0x0010 => hardware::read_voltage_sensor(sensor_id).await,
+        _ => hardware::read_current_sensor(sensor_id).await,
+    };
+    reading.ok().map(|reading| reading.value)
+}
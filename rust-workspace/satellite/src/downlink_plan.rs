@@ -0,0 +1,145 @@
+//! Forecast-driven recorder downlink planning
+//!
+//! The ground uplinks a link capacity forecast for the upcoming contacts on
+//! the link forecast APID; the command processor hands it here instead of to
+//! the command dispatcher. At the start of each forecast contact the
+//! communication manager asks for a plan: stored products in priority order
+//! that fit the forecast capacity, with low-priority products held back when
+//! the contact is forecast to be degraded. The scheduler lives behind a
+//! critical-section mutex so both tasks can use it without `unsafe`.
+//!
+//! An autonomy rule can ask for the recorder to be downlinked on a chosen
+//! band; the request is held here until the next contact starts.
+//!
+//! The ground selects the downlink policy, the order stored products are
+//! considered in, by command; it applies from the next contact planned. Each
+//! contact planned is counted against its policy for housekeeping telemetry.
+//!
+//! Mission seconds are counted from boot until a time correlation service
+//! sets the onboard clock; the ground forecasts on the same clock.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-001: Priority Classification (priority-ordered recorder downlink)
+//! - REQ-PF-002: Data Transfer Rates (contact volume matched to the forecast)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    link_forecast::{
+        ContactPlan, DownlinkPolicy, DownlinkPolicyStats, DownlinkScheduler, LinkForecast,
+        StoredProduct, DEFAULT_DEGRADED_MIN_PRIORITY, DEFAULT_DEGRADED_PERCENT,
+    },
+    types::BandType,
+    Result,
+};
+
+use crate::error_handling::{self, LogLevel};
+
+/// Products the recorder keeps track of for downlink
+const RECORDER_PRODUCTS: usize = 32;
+
+/// Event code logged when a degraded contact defers stored products
+const DEGRADED_CONTACT_CODE: u32 = 430;
+
+/// Scheduler, the last contact a plan was made for and the band requested
+/// for the next one
+struct Planner {
+    scheduler: DownlinkScheduler<RECORDER_PRODUCTS>,
+    planned_contact: Option<u32>,
+    requested_band: Option<BandType>,
+}
+
+static PLANNER: Mutex<CriticalSectionRawMutex, RefCell<Planner>> =
+    Mutex::new(RefCell::new(Planner {
+        scheduler: DownlinkScheduler::new(DEFAULT_DEGRADED_PERCENT, DEFAULT_DEGRADED_MIN_PRIORITY),
+        planned_contact: None,
+        requested_band: None,
+    }));
+
+/// Current time, mission seconds
+fn mission_seconds() -> u64 {
+    Instant::now().as_secs()
+}
+
+/// Accept an uplinked link forecast from a packet payload
+///
+/// An invalid or outdated forecast is rejected and the one in use is kept.
+pub fn accept_forecast(payload: &[u8]) -> Result<()> {
+    let forecast = LinkForecast::from_bytes(payload)?;
+    PLANNER.lock(|planner| planner.borrow_mut().scheduler.accept_forecast(forecast))?;
+    error_handling::log_info("Link forecast accepted");
+    Ok(())
+}
+
+/// Register a product stored on the recorder for downlink
+pub fn store(file_id: u32, size_bytes: u32, priority: u8, science_value: u8) -> Result<()> {
+    let product = StoredProduct {
+        file_id,
+        size_bytes,
+        priority,
+        science_value,
+        stored_s: mission_seconds(),
+    };
+    PLANNER.lock(|planner| planner.borrow_mut().scheduler.store(product))
+}
+
+/// Release a product once its downlink is complete
+pub fn complete(file_id: u32) {
+    PLANNER.lock(|planner| {
+        let _ = planner.borrow_mut().scheduler.remove(file_id);
+    });
+}
+
+/// Order the recorder downlink by `policy` from the next contact planned
+pub fn set_policy(policy: DownlinkPolicy) {
+    PLANNER.lock(|planner| planner.borrow_mut().scheduler.set_policy(policy));
+    error_handling::log_info("Downlink policy changed");
+}
+
+/// Policy in use and what each policy has planned
+pub fn policy_stats() -> DownlinkPolicyStats {
+    PLANNER.lock(|planner| *planner.borrow().scheduler.stats())
+}
+
+/// Ask for the recorder to be downlinked on `band` from the next contact
+///
+/// A later request replaces an earlier one not yet taken.
+pub fn request_band(band: BandType) {
+    PLANNER.lock(|planner| planner.borrow_mut().requested_band = Some(band));
+}
+
+/// Take the band requested for the contact that has just started
+pub fn take_requested_band() -> Option<BandType> {
+    PLANNER.lock(|planner| planner.borrow_mut().requested_band.take())
+}
+
+/// Plan for a forecast contact that has started since the last call
+///
+/// Returns `None` outside forecast contacts and for a contact already
+/// planned, so the caller can poll it every cycle.
+pub fn plan_new_contact() -> Option<ContactPlan> {
+    let plan = PLANNER.lock(|planner| {
+        let mut planner = planner.borrow_mut();
+        let plan = planner.scheduler.plan(mission_seconds());
+        let contact_id = plan.contact_id?;
+        if planner.planned_contact == Some(contact_id) {
+            return None;
+        }
+        planner.planned_contact = Some(contact_id);
+        planner.scheduler.record(&plan);
+        Some(plan)
+    })?;
+
+    if plan.degraded && plan.deferred > 0 {
+        error_handling::log_with_component(
+            LogLevel::Warning,
+            "Degraded contact forecast, low-priority products deferred",
+            "DOWNLINK",
+            Some(DEGRADED_CONTACT_CODE),
+        );
+    }
+    Some(plan)
+}
@@ -2,11 +2,35 @@
 //!
 //! Provides comprehensive error handling, logging, and fault recovery mechanisms
 //! following NASA software engineering standards.
+//!
+//! The satellite crate never panics: `unwrap` and `expect` are denied, and a
+//! condition the code cannot continue from, such as a manager used before
+//! initialization or a task that did not spawn, is raised with
+//! [`escalate_invariant`] as a software fault. Its recovery action is queued
+//! and carried out by the main loop through [`execute_pending_recovery`].
+//!
+//! The error-code thresholds at which a fault escalates to a stronger
+//! recovery action are scaled by the FDIR aggressiveness of the current
+//! mission phase: lower during LEOP and commissioning, higher during
+//! decommissioning.
+
+use core::cell::RefCell;
 
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
 use embassy_time::{Duration, Instant, Timer};
 use heapless::{String, Vec};
 
-use space_comms_shared::{SpaceCommError, error::ErrorSeverity};
+use space_comms_shared::{
+    SpaceCommError, error::ErrorSeverity,
+    event_log::{EventLevel, EventLogCompressor, EventRecord},
+};
+
+use crate::mode;
+
+/// Error code of a broken software invariant; at 900 and above a software
+/// fault escalates to safe mode in nominal operations, and a broken invariant
+/// does in every mission phase
+pub const INVARIANT_VIOLATION_CODE: u32 = 950;
 
 /// Log entry structure
 #[derive(Debug, Clone)]
@@ -23,6 +47,25 @@ pub struct LogEntry {
     pub component: String<32>,
 }
 
+impl LogEntry {
+    /// View the entry as an event record for compressed downlink
+    pub fn event_record(&self) -> EventRecord<'_> {
+        EventRecord {
+            timestamp_ms: self.timestamp,
+            level: match self.level {
+                LogLevel::Critical => EventLevel::Critical,
+                LogLevel::Error => EventLevel::Error,
+                LogLevel::Warning => EventLevel::Warning,
+                LogLevel::Info => EventLevel::Info,
+                LogLevel::Debug => EventLevel::Debug,
+            },
+            component: &self.component,
+            message: &self.message,
+            error_code: self.error_code,
+        }
+    }
+}
+
 /// Log levels
 #[derive(Debug, Clone, PartialEq)]
 pub enum LogLevel {
@@ -82,6 +125,12 @@ pub struct ErrorHandler {
     error_counts: Vec<(String<32>, u32), 16>,
     /// System health status
     system_health: SystemHealth,
+    /// Entries logged since boot
+    logged_total: u32,
+    /// Entries downlinked (or overwritten before downlink) since boot
+    downlinked_total: u32,
+    /// Recovery action raised outside a task, awaiting execution
+    pending_recovery: Option<RecoveryAction>,
 }
 
 /// System health status
@@ -113,6 +162,9 @@ impl ErrorHandler {
                 time_since_critical: 0,
                 is_safe_mode: false,
             },
+            logged_total: 0,
+            downlinked_total: 0,
+            pending_recovery: None,
         }
     }
 
@@ -131,6 +183,7 @@ impl ErrorHandler {
             self.log_buffer.remove(0);
         }
         let _ = self.log_buffer.push(entry);
+        self.logged_total = self.logged_total.wrapping_add(1);
 
         // Update health status based on log level
         match level {
@@ -192,6 +245,32 @@ impl ErrorHandler {
         &self.log_buffer[start..]
     }
 
+    /// Compress entries not yet downlinked into `compressor`, oldest first
+    ///
+    /// Stops at the first entry that does not fit, leaving it for the next
+    /// block; entries overwritten before downlink are skipped. Returns the
+    /// number of entries added.
+    pub fn compress_pending_logs<const N: usize>(
+        &mut self,
+        compressor: &mut EventLogCompressor<N>,
+    ) -> usize {
+        let pending = self.logged_total.wrapping_sub(self.downlinked_total) as usize;
+        let available = pending.min(self.log_buffer.len());
+        self.downlinked_total = self.logged_total.wrapping_sub(available as u32);
+
+        let mut added = 0;
+        for entry in &self.log_buffer[self.log_buffer.len() - available..] {
+            if compressor.push(&entry.event_record()).is_ok() {
+                added += 1;
+            } else if !compressor.is_empty() {
+                break;
+            }
+            // An entry too large for an empty block can never be sent
+            self.downlinked_total = self.downlinked_total.wrapping_add(1);
+        }
+        added
+    }
+
     /// Get active faults
     pub fn get_active_faults(&self) -> &[FaultType] {
         &self.active_faults
@@ -210,33 +289,38 @@ impl ErrorHandler {
     }
 
     /// Determine appropriate recovery action for fault
+    ///
+    /// Error-code thresholds follow the mission phase's FDIR aggressiveness.
     fn determine_recovery_action(&self, fault: &FaultType) -> RecoveryAction {
+        let fdir = mode::phase_policy().fdir;
         match fault {
             FaultType::Hardware { component, error_code } => {
-                if *error_code >= 1000 {
+                if *error_code >= fdir.threshold(1000) {
                     RecoveryAction::EmergencyShutdown
-                } else if *error_code >= 500 {
+                } else if *error_code >= fdir.threshold(500) {
                     RecoveryAction::PowerCycle(component.clone())
                 } else {
                     RecoveryAction::RestartComponent(component.clone())
                 }
             }
             FaultType::Software { module, error_code } => {
-                if *error_code >= 900 {
+                if *error_code == INVARIANT_VIOLATION_CODE
+                    || *error_code >= fdir.threshold(900)
+                {
                     RecoveryAction::SafeMode
                 } else {
                     RecoveryAction::RestartComponent(module.clone())
                 }
             }
             FaultType::Communication { band, error_code } => {
-                if *error_code >= 800 {
+                if *error_code >= fdir.threshold(800) {
                     RecoveryAction::SwitchToBackup(band.clone())
                 } else {
                     RecoveryAction::RestartComponent(band.clone())
                 }
             }
             FaultType::Power { subsystem, error_code } => {
-                if *error_code >= 700 {
+                if *error_code >= fdir.threshold(700) {
                     RecoveryAction::EmergencyShutdown
                 } else {
                     RecoveryAction::PowerCycle(subsystem.clone())
@@ -252,7 +336,7 @@ impl ErrorHandler {
                 }
             }
             FaultType::Memory { address: _, error_code } => {
-                if *error_code >= 600 {
+                if *error_code >= fdir.threshold(600) {
                     RecoveryAction::SafeMode
                 } else {
                     RecoveryAction::None
@@ -279,14 +363,20 @@ impl ErrorHandler {
     }
 }
 
-/// Global error handler instance
-static mut ERROR_HANDLER: Option<ErrorHandler> = None;
+/// Global error handler instance, empty until [`initialize`]
+static ERROR_HANDLER: Mutex<CriticalSectionRawMutex, RefCell<Option<ErrorHandler>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Run `f` on the error handler, `None` before [`initialize`]
+///
+/// `f` must not log, since logging takes the handler again.
+fn with_handler<R>(f: impl FnOnce(&mut ErrorHandler) -> R) -> Option<R> {
+    ERROR_HANDLER.lock(|handler| handler.borrow_mut().as_mut().map(f))
+}
 
 /// Initialize error handling system
 pub fn initialize() {
-    unsafe {
-        ERROR_HANDLER = Some(ErrorHandler::new());
-    }
+    ERROR_HANDLER.lock(|handler| *handler.borrow_mut() = Some(ErrorHandler::new()));
 }
 
 /// Log critical error
@@ -316,101 +406,141 @@ pub fn log_debug(message: &str) {
 
 /// Log with specific component
 pub fn log_with_component(level: LogLevel, message: &str, component: &str, error_code: Option<u32>) {
-    unsafe {
-        if let Some(handler) = ERROR_HANDLER.as_mut() {
-            handler.add_log(level, message, component, error_code);
-        }
-    }
+    with_handler(|handler| handler.add_log(level, message, component, error_code));
+}
+
+/// Compress log entries not yet downlinked into `compressor`
+///
+/// Returns the number of entries added.
+pub fn compress_pending_logs<const N: usize>(compressor: &mut EventLogCompressor<N>) -> usize {
+    with_handler(|handler| handler.compress_pending_logs(compressor)).unwrap_or(0)
 }
 
 /// Handle system fault
 pub fn handle_fault(fault: FaultType) -> RecoveryAction {
-    unsafe {
-        if let Some(handler) = ERROR_HANDLER.as_mut() {
-            let action = handler.add_fault(fault.clone());
-
-            // Log the fault
-            match &fault {
-                FaultType::Hardware { component, error_code } => {
-                    log_with_component(
-                        LogLevel::Critical,
-                        &format!("Hardware fault in {}", component),
-                        component,
-                        Some(*error_code)
-                    );
-                }
-                FaultType::Software { module, error_code } => {
-                    log_with_component(
-                        LogLevel::Error,
-                        &format!("Software fault in {}", module),
-                        module,
-                        Some(*error_code)
-                    );
-                }
-                FaultType::Communication { band, error_code } => {
-                    log_with_component(
-                        LogLevel::Warning,
-                        &format!("Communication fault on {}", band),
-                        "COMM",
-                        Some(*error_code)
-                    );
-                }
-                FaultType::Power { subsystem, error_code } => {
-                    log_with_component(
-                        LogLevel::Critical,
-                        &format!("Power fault in {}", subsystem),
-                        "POWER",
-                        Some(*error_code)
-                    );
-                }
-                FaultType::Thermal { sensor_id, temperature } => {
-                    log_with_component(
-                        LogLevel::Warning,
-                        &format!("Thermal fault: sensor {} at {}°C", sensor_id, temperature),
-                        "THERMAL",
-                        Some(*sensor_id as u32)
-                    );
-                }
-                FaultType::Memory { address, error_code } => {
-                    let addr_str = address.map(|a| format!("0x{:08X}", a)).unwrap_or_else(|| "Unknown".to_string());
-                    log_with_component(
-                        LogLevel::Error,
-                        &format!("Memory fault at {}", addr_str),
-                        "MEMORY",
-                        Some(*error_code)
-                    );
-                }
-            }
-
-            action
-        } else {
-            RecoveryAction::None
+    let Some(action) = with_handler(|handler| handler.add_fault(fault.clone())) else {
+        return RecoveryAction::None;
+    };
+
+    // Log the fault
+    match &fault {
+        FaultType::Hardware { component, error_code } => {
+            log_with_component(
+                LogLevel::Critical,
+                &format!("Hardware fault in {}", component),
+                component,
+                Some(*error_code)
+            );
+        }
+        FaultType::Software { module, error_code } => {
+            log_with_component(
+                LogLevel::Error,
+                &format!("Software fault in {}", module),
+                module,
+                Some(*error_code)
+            );
+        }
+        FaultType::Communication { band, error_code } => {
+            log_with_component(
+                LogLevel::Warning,
+                &format!("Communication fault on {}", band),
+                "COMM",
+                Some(*error_code)
+            );
+        }
+        FaultType::Power { subsystem, error_code } => {
+            log_with_component(
+                LogLevel::Critical,
+                &format!("Power fault in {}", subsystem),
+                "POWER",
+                Some(*error_code)
+            );
+        }
+        FaultType::Thermal { sensor_id, temperature } => {
+            log_with_component(
+                LogLevel::Warning,
+                &format!("Thermal fault: sensor {} at {}°C", sensor_id, temperature),
+                "THERMAL",
+                Some(*sensor_id as u32)
+            );
+        }
+        FaultType::Memory { address, error_code } => {
+            let addr_str = address.map(|a| format!("0x{:08X}", a)).unwrap_or_else(|| "Unknown".to_string());
+            log_with_component(
+                LogLevel::Error,
+                &format!("Memory fault at {}", addr_str),
+                "MEMORY",
+                Some(*error_code)
+            );
         }
     }
+
+    action
 }
 
 /// Resolve fault
 pub fn resolve_fault(fault: FaultType) {
-    unsafe {
-        if let Some(handler) = ERROR_HANDLER.as_mut() {
-            handler.remove_fault(&fault);
-            log_info("Fault resolved");
-        }
+    if with_handler(|handler| handler.remove_fault(&fault)).is_some() {
+        log_info("Fault resolved");
     }
 }
 
 /// Get system health
 pub fn get_system_health() -> SystemHealth {
-    unsafe {
-        ERROR_HANDLER.as_ref()
-            .map(|h| h.get_health().clone())
-            .unwrap_or_else(|| SystemHealth {
-                overall_health: 0,
-                critical_faults: 255,
-                warnings: 255,
-                time_since_critical: 0,
-                is_safe_mode: true,
-            })
+    with_handler(|h| h.get_health().clone()).unwrap_or_else(|| SystemHealth {
+        overall_health: 0,
+        critical_faults: 255,
+        warnings: 255,
+        time_since_critical: 0,
+        is_safe_mode: true,
+    })
+}
+
+/// Escalate a broken software invariant to FDIR
+///
+/// Used where the code would otherwise panic. Logs the violation against
+/// `module`, raises a software fault with [`INVARIANT_VIOLATION_CODE`] and
+/// queues its recovery action for [`execute_pending_recovery`]; an action
+/// already queued is kept.
+///
+/// Parameters:
+/// - module: Module or task that found the violation
+/// - invariant: What was expected to hold
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-004: Fault tolerance without loss of the flight software
+///
+/// Returns:
+/// Error describing the violation, for the caller to return
+pub fn escalate_invariant(module: &'static str, invariant: &'static str) -> SpaceCommError {
+    log_with_component(
+        LogLevel::Critical,
+        invariant,
+        module,
+        Some(INVARIANT_VIOLATION_CODE),
+    );
+    let action = handle_fault(FaultType::Software {
+        module: String::try_from(module).unwrap_or_else(|_| String::new()),
+        error_code: INVARIANT_VIOLATION_CODE,
+    });
+    with_handler(|handler| {
+        handler.pending_recovery.get_or_insert(action);
+    });
+
+    SpaceCommError::ConfigurationError {
+        parameter: module,
+        value: "unavailable",
+        reason: invariant,
+    }
+}
+
+/// Execute the recovery action queued by [`escalate_invariant`], if any
+pub async fn execute_pending_recovery() {
+    let action = with_handler(|handler| handler.pending_recovery.take()).flatten();
+    if let Some(action) = action {
+        if execute_recovery_action(action).await.is_err() {
+            log_error("Invariant violation FDIR action failed");
+        }
     }
 }
 
@@ -463,11 +593,7 @@ pub async fn execute_recovery_action(action: RecoveryAction) -> Result<(), &'sta
         }
         RecoveryAction::SafeMode => {
             log_critical("Entering safe mode");
-            unsafe {
-                if let Some(handler) = ERROR_HANDLER.as_mut() {
-                    handler.system_health.is_safe_mode = true;
-                }
-            }
+            with_handler(|handler| handler.system_health.is_safe_mode = true);
             // Safe mode would typically disable non-essential systems
             Ok(())
         }
@@ -482,11 +608,7 @@ pub async fn execute_recovery_action(action: RecoveryAction) -> Result<(), &'sta
 /// Periodic health check task
 pub async fn health_check_task() {
     loop {
-        unsafe {
-            if let Some(handler) = ERROR_HANDLER.as_mut() {
-                handler.update_health();
-            }
-        }
+        with_handler(ErrorHandler::update_health);
 
         Timer::after(Duration::from_secs(60)).await; // Every minute
     }
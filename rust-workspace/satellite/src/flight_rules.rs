@@ -0,0 +1,148 @@
+//! Flight rule enforcement
+//!
+//! Holds the onboard flight rule table and the activities in progress. The
+//! command processor calls [`check_command`] before dispatching an uplinked
+//! command, and autonomous actions call [`check_action`] before they act.
+//! The state is sampled at the moment of the check: transceiver temperatures
+//! from RF housekeeping, battery state of charge and bus voltage from the EPS,
+//! position uncertainty from the navigator, and the operational mode. A
+//! cleared command's activity counts as in progress from then on, for the
+//! time it is expected to run.
+//!
+//! Every violation is logged with the rule number as its code and counted;
+//! the count is downlinked in housekeeping telemetry. The emergency lane does
+//! not pass through here, so no rule can block an emergency command.
+//!
+//! # Requirements Traceability
+//! - REQ-SF-001: Command validation (flight rule check before execution)
+//! - REQ-SF-002: Override protection for critical safety functions
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    flight_rules::{Activity, ActivityTracker, FlightRuleTable, SpacecraftState},
+    link_config::LinkDirection,
+    navigation::NavigationMode,
+    Result,
+};
+
+use crate::{
+    communication,
+    error_handling::{self, LogLevel},
+    hardware, mode,
+};
+
+/// Flight rule table and the activities it is checked against
+struct Enforcer {
+    rules: Option<FlightRuleTable>,
+    activities: ActivityTracker,
+}
+
+static ENFORCER: Mutex<CriticalSectionRawMutex, RefCell<Enforcer>> =
+    Mutex::new(RefCell::new(Enforcer {
+        rules: None,
+        activities: ActivityTracker::new(),
+    }));
+
+static VIOLATIONS: AtomicU32 = AtomicU32::new(0);
+
+/// Sample the state flight rules are checked against
+///
+/// Autonomy rules are evaluated against the same snapshot.
+pub async fn sample_state(now_ms: u64) -> SpacecraftState {
+    let mut state = SpacecraftState::new(mode::current_mode());
+
+    for (slot, status) in state
+        .transceiver_temperature
+        .iter_mut()
+        .zip(hardware::rf_band_statuses().await.iter())
+    {
+        *slot = Some(f32::from(status.temperature));
+    }
+
+    // Unreadable EPS sensors are NaN; leave them out of the snapshot
+    let eps = hardware::read_eps_summary().await;
+    state.battery_soc = Some(eps.battery_soc).filter(|value| !value.is_nan());
+    state.bus_voltage = Some(eps.bus_voltage).filter(|value| !value.is_nan());
+
+    // A lost navigation solution has no usable uncertainty
+    let navigation = hardware::navigation_solution();
+    if navigation.mode != NavigationMode::Lost {
+        state.position_sigma = Some(navigation.position_sigma_m as f32);
+    }
+
+    state.activities = ENFORCER.lock(|enforcer| enforcer.borrow().activities.active(now_ms));
+    state.activities.insert(Activity::Transmit(
+        communication::link(LinkDirection::Downlink).band,
+    ));
+    state
+}
+
+/// Check `activity` against the table, recording it as started if allowed
+///
+/// A violation is logged and counted, and returned as the rejection error.
+async fn enforce(activity: Activity, duration_ms: u64) -> Result<()> {
+    let now_ms = Instant::now().as_millis();
+    let state = sample_state(now_ms).await;
+
+    let verdict = ENFORCER.lock(|enforcer| {
+        let mut enforcer = enforcer.borrow_mut();
+        let verdict = enforcer
+            .rules
+            .get_or_insert_with(FlightRuleTable::default)
+            .check(activity, &state);
+        if verdict.is_ok() && duration_ms > 0 {
+            enforcer.activities.begin(activity, now_ms, duration_ms);
+        }
+        verdict
+    });
+
+    verdict.map_err(|violation| {
+        VIOLATIONS.fetch_add(1, Ordering::Relaxed);
+        error_handling::log_with_component(
+            LogLevel::Warning,
+            activity.name(),
+            "FLIGHT_RULES",
+            Some(u32::from(violation.rule.id)),
+        );
+        violation.to_error()
+    })
+}
+
+/// Check an uplinked command's data field before it is dispatched
+///
+/// Commands that start no activity a rule can concern pass unchecked.
+pub async fn check_command(data: &[u8]) -> Result<()> {
+    match Activity::of_command_data(data) {
+        Some((activity, duration_ms)) => enforce(activity, duration_ms).await,
+        None => Ok(()),
+    }
+}
+
+/// Check an autonomous action before it is taken
+pub async fn check_action(activity: Activity) -> Result<()> {
+    enforce(activity, 0).await
+}
+
+/// Check that the downlink band may still transmit
+///
+/// A transceiver can heat past its limit while in use, so the health
+/// monitor calls this periodically; a violation moves the downlink to the
+/// backup band.
+pub async fn monitor_downlink() {
+    let band = communication::link(LinkDirection::Downlink).band;
+    if check_action(Activity::Transmit(band)).await.is_err()
+        && communication::switch_to_backup_band().await.is_err()
+    {
+        error_handling::log_error("Flight rule downlink fallback failed");
+    }
+}
+
+/// Commands and actions refused by a flight rule since boot
+pub fn violations() -> u32 {
+    VIOLATIONS.load(Ordering::Relaxed)
+}
@@ -28,14 +28,43 @@
 //! - Individual transceiver abstractions for each frequency band
 //! - Centralized hardware manager for coordination and control
 //! - Embassy async integration for non-blocking hardware operations
-//! - Temperature, voltage, and current sensor interfaces
+//! - Temperature, voltage, and current sensor interfaces backed by a table of
+//!   simulated sensors with noise, drift and injectable faults
+//! - Simulated GPS receiver with injectable outages, feeding the onboard
+//!   navigator and the attitude control pointing deadband
 //! - Emergency protocols for hardware protection and survival
 
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_sync::mutex::Mutex as AsyncMutex;
 use embassy_time::{Duration, Timer};
 use embedded_hal::digital::v2::{InputPin, OutputPin};
 use heapless::Vec;
 
-use space_comms_shared::{Result, SpaceCommError, types::BandType};
+use space_comms_shared::{
+    Result, SpaceCommError,
+    eps::{battery_soc_percent, EpsSummary},
+    formation::{
+        CrosslinkRanger, RangingMeasurement, RangingNoise, RelativeState,
+        LEO_550_MEAN_MOTION_RAD_S,
+    },
+    navigation::{
+        GpsNoise, GpsOutage, GpsReceiver, NavigationConfig, NavigationSolution, Navigator,
+        OrbitState,
+    },
+    rf_housekeeping::{
+        LockMonitor, LockRecoveryPolicy, LockRecoveryReport, LockRecoveryStep, RfBandStatus,
+        RF_BANDS, RF_HOUSEKEEPING_PERIOD_MS,
+    },
+    power_attribution::{PowerAttribution, Subsystem},
+    sensor_model::{FaultProfile, NoiseModel, SensorKind, SensorSpec, SensorSuite},
+    telemetry::{MeasurementQuality, QualityLimits},
+    types::BandType,
+};
+
+use crate::error_handling;
 
 /// Transceiver status structure
 ///
@@ -559,6 +588,12 @@ pub struct HardwareManager {
 
     /// Ka-Band transceiver for maximum throughput
     ka_band: KaBandTransceiver,
+
+    /// Lock-loss recovery ladder per band, in RF_BANDS order
+    lock_monitors: [LockMonitor; 5],
+
+    /// Timing and depth of the lock-loss recovery ladder
+    lock_recovery_policy: LockRecoveryPolicy,
 }
 
 impl HardwareManager {
@@ -580,6 +615,8 @@ impl HardwareManager {
             x_band: XBandTransceiver::new(),  // Science data
             k_band: KBandTransceiver::new(),  // High-rate operations
             ka_band: KaBandTransceiver::new(), // Maximum throughput
+            lock_monitors: [LockMonitor::new(); 5],
+            lock_recovery_policy: LockRecoveryPolicy::default(),
         }
     }
 
@@ -652,16 +689,146 @@ impl HardwareManager {
         Timer::after(Duration::from_millis(500)).await;
         Ok(())
     }
+
+    /// Modeled power draw of every subsystem
+    ///
+    /// The onboard computer is always on; each transceiver draws according
+    /// to its power state and transmit power level.
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-NF-004: Power draw attributed per subsystem
+    ///
+    /// Returns:
+    /// Attribution of the current power state
+    pub fn power_attribution(&self) -> PowerAttribution {
+        let mut attribution = PowerAttribution::new();
+        attribution.set(Subsystem::Obc, true, 100);
+        // get_all_statuses reports bands in RF_BANDS order
+        for (band, (_, status)) in RF_BANDS.into_iter().zip(self.get_all_statuses()) {
+            attribution.set(Subsystem::from_band(band), status.is_powered, status.tx_power);
+        }
+        attribution
+    }
+
+    /// Mutable status and enable flag of a transceiver
+    fn transceiver_mut(&mut self, band: BandType) -> (&mut TransceiverStatus, &mut bool) {
+        match band {
+            BandType::UhfBand => (&mut self.uhf.status, &mut self.uhf.enabled),
+            BandType::SBand => (&mut self.s_band.status, &mut self.s_band.enabled),
+            BandType::XBand => (&mut self.x_band.status, &mut self.x_band.enabled),
+            BandType::KBand => (&mut self.k_band.status, &mut self.k_band.enabled),
+            BandType::KaBand => (&mut self.ka_band.status, &mut self.ka_band.enabled),
+        }
+    }
+
+    /// Re-tune a transceiver
+    ///
+    /// Re-programs the synthesizer with the current tuned frequency and waits
+    /// for the PLL to settle, the first rung of the lock-loss recovery ladder.
+    ///
+    /// Parameters:
+    /// - band: Frequency band to re-tune
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-NF-004: Fault tolerance through automatic recovery
+    /// - REQ-IF-001: Hardware control interface
+    ///
+    /// Returns:
+    /// Result<()> indicating re-tune success or a powered-off transceiver
+    pub async fn retune_transceiver(&mut self, band: BandType) -> Result<()> {
+        let (status, _) = self.transceiver_mut(band);
+        if !status.is_powered {
+            return Err(SpaceCommError::hardware_failure("Cannot re-tune unpowered transceiver", 2));
+        }
+
+        // Synthesizer reload and PLL settling time (REQ-IF-001)
+        Timer::after(Duration::from_millis(50)).await;
+        Ok(())
+    }
+
+    /// Run the lock-loss recovery ladder for every transceiver
+    ///
+    /// Feeds each band's power and lock state to its LockMonitor and executes
+    /// the step it asks for: re-tune, power cycle, or disable the transceiver
+    /// once the ladder is exhausted. Disabled transceivers stay disabled until
+    /// reset_lock_recovery is called.
+    ///
+    /// Parameters:
+    /// - now_ms: Current system time in milliseconds
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-NF-004: Fault tolerance through automatic recovery
+    /// - REQ-SF-002: Hardware recovery and reset capability
+    ///
+    /// Returns:
+    /// Steps taken this pass, for logging and FDIR notification
+    pub async fn monitor_lock(&mut self, now_ms: u64) -> Vec<(BandType, LockRecoveryStep), 5> {
+        let mut steps = Vec::new();
+
+        for (i, band) in RF_BANDS.iter().copied().enumerate() {
+            let policy = self.lock_recovery_policy;
+            let (is_powered, is_locked) = {
+                let (status, _) = self.transceiver_mut(band);
+                (status.is_powered, status.is_locked)
+            };
+
+            let Some(step) = self.lock_monitors[i].update(&policy, is_powered, is_locked, now_ms)
+            else {
+                continue;
+            };
+
+            match step {
+                LockRecoveryStep::Retune => {
+                    // A failed re-tune is left to the next rung of the ladder
+                    let _ = self.retune_transceiver(band).await;
+                }
+                LockRecoveryStep::PowerCycle => {
+                    let _ = self.power_cycle_transceiver(band).await;
+                }
+                LockRecoveryStep::MarkFailed => {
+                    let (_, enabled) = self.transceiver_mut(band);
+                    *enabled = false;
+                }
+                LockRecoveryStep::Recovered => {}
+            }
+
+            // At most one step per band per pass, so this cannot overflow
+            let _ = steps.push((band, step));
+        }
+
+        steps
+    }
+
+    /// Re-enable a failed transceiver and restart its recovery ladder
+    ///
+    /// Counters are kept so the ground still sees the earlier episode.
+    pub fn reset_lock_recovery(&mut self, band: BandType) {
+        let index = RF_BANDS.iter().position(|b| *b == band).unwrap_or(0);
+        self.lock_monitors[index].reset();
+        let (_, enabled) = self.transceiver_mut(band);
+        *enabled = true;
+    }
 }
 
-/// Global hardware manager instance
-static mut HARDWARE_MANAGER: Option<HardwareManager> = None;
+/// Global hardware manager instance, empty until
+/// [`initialize_transceivers`]
+///
+/// An async mutex: transmissions, receptions and the lock recovery ladder
+/// hold the manager across their delays, and other tasks wait their turn.
+static HARDWARE_MANAGER: AsyncMutex<CriticalSectionRawMutex, Option<HardwareManager>> =
+    AsyncMutex::new(None);
+
+/// Hardware manager behind the lock, escalated to FDIR if used before
+/// [`initialize_transceivers`]
+fn initialized(manager: &mut Option<HardwareManager>) -> Result<&mut HardwareManager> {
+    manager.as_mut().ok_or_else(|| {
+        error_handling::escalate_invariant("HARDWARE", "Hardware manager not initialized")
+    })
+}
 
 /// Initialize all transceivers
 pub async fn initialize_transceivers() -> Result<()> {
-    unsafe {
-        HARDWARE_MANAGER = Some(HardwareManager::new());
-    }
+    *HARDWARE_MANAGER.lock().await = Some(HardwareManager::new());
 
     // Startup delay for all transceivers
     Timer::after(Duration::from_millis(2000)).await;
@@ -671,55 +838,67 @@ pub async fn initialize_transceivers() -> Result<()> {
 
 /// Transmit on UHF band
 pub async fn transmit_uhf(data: &[u8]) -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.uhf.transmit(data).await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.uhf.transmit(data).await
 }
 
 /// Transmit on S-Band
 pub async fn transmit_s_band(data: &[u8]) -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.s_band.transmit(data).await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.s_band.transmit(data).await
 }
 
 /// Transmit on X-Band
 pub async fn transmit_x_band(data: &[u8]) -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.x_band.transmit(data).await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.x_band.transmit(data).await
 }
 
 /// Transmit on K-Band
 pub async fn transmit_k_band(data: &[u8]) -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.k_band.transmit(data).await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.k_band.transmit(data).await
 }
 
 /// Transmit on Ka-Band
 pub async fn transmit_ka_band(data: &[u8]) -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.ka_band.transmit(data).await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.ka_band.transmit(data).await
 }
 
 /// Receive from UHF band
 pub async fn receive_uhf() -> Result<Vec<u8, 512>> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.uhf.receive().await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.uhf.receive().await
 }
 
 /// Receive from S-Band
 pub async fn receive_s_band() -> Result<Vec<u8, 2048>> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.s_band.receive().await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.s_band.receive().await
 }
 
 /// Receive from X-Band
 pub async fn receive_x_band() -> Result<Vec<u8, 4096>> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
-    manager.x_band.receive().await
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    initialized(&mut manager)?.x_band.receive().await
 }
 
 /// Get hardware health status
-pub fn get_hardware_health() -> [(&'static str, bool); 5] {
-    let manager = unsafe { HARDWARE_MANAGER.as_ref().unwrap() };
+///
+/// Every transceiver reads unhealthy if the hardware manager is not
+/// initialized.
+pub async fn get_hardware_health() -> [(&'static str, bool); 5] {
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    let Ok(manager) = initialized(&mut manager) else {
+        return [
+            ("UHF", false),
+            ("S-Band", false),
+            ("X-Band", false),
+            ("K-Band", false),
+            ("Ka-Band", false),
+        ];
+    };
     let statuses = manager.get_all_statuses();
 
     [
@@ -731,9 +910,97 @@ pub fn get_hardware_health() -> [(&'static str, bool); 5] {
     ]
 }
 
+/// RF housekeeping reporting interval in milliseconds
+static RF_HOUSEKEEPING_INTERVAL_MS: AtomicU32 = AtomicU32::new(RF_HOUSEKEEPING_PERIOD_MS as u32);
+
+/// Current RF housekeeping reporting interval in milliseconds
+pub fn rf_housekeeping_interval_ms() -> u64 {
+    u64::from(RF_HOUSEKEEPING_INTERVAL_MS.load(Ordering::Relaxed))
+}
+
+/// Set the RF housekeeping reporting interval
+///
+/// The ground marks RF values stale after three dictionary periods, so
+/// intervals longer than the dictionary period will show as stale data.
+/// Intervals are clamped to 100 ms - 60 s.
+pub fn set_rf_housekeeping_interval_ms(interval_ms: u32) {
+    RF_HOUSEKEEPING_INTERVAL_MS.store(interval_ms.clamp(100, 60_000), Ordering::Relaxed);
+}
+
+/// Snapshot of every transceiver for RF housekeeping telemetry
+///
+/// Requirements Fulfilled:
+/// - REQ-PF-002: Link quality metrics (signal strength, lock)
+/// - REQ-SF-002: Transceiver thermal monitoring
+///
+/// Every transceiver reads unpowered if the hardware manager is not
+/// initialized.
+pub async fn rf_band_statuses() -> [RfBandStatus; 5] {
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    let Ok(manager) = initialized(&mut manager) else {
+        return RF_BANDS.map(|band| RfBandStatus {
+            band,
+            is_powered: false,
+            is_locked: false,
+            tx_power: 0,
+            signal_strength: 0,
+            temperature: 0,
+            frequency: 0,
+        });
+    };
+    let statuses = manager.get_all_statuses();
+
+    // get_all_statuses reports bands in RF_BANDS order
+    core::array::from_fn(|i| {
+        let status = statuses[i].1;
+        RfBandStatus {
+            band: RF_BANDS[i],
+            is_powered: status.is_powered,
+            is_locked: status.is_locked,
+            tx_power: status.tx_power,
+            signal_strength: status.signal_strength,
+            temperature: status.temperature,
+            frequency: status.frequency,
+        }
+    })
+}
+
+/// Run one pass of the lock-loss recovery ladder on every transceiver
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-004: Fault tolerance through automatic recovery
+///
+/// Returns:
+/// Steps taken this pass
+pub async fn monitor_transceiver_lock(now_ms: u64) -> Vec<(BandType, LockRecoveryStep), 5> {
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    match initialized(&mut manager) {
+        Ok(manager) => manager.monitor_lock(now_ms).await,
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Re-enable a transceiver the recovery ladder marked failed
+pub async fn reset_lock_recovery(band: BandType) {
+    if let Ok(manager) = initialized(&mut *HARDWARE_MANAGER.lock().await) {
+        manager.reset_lock_recovery(band);
+    }
+}
+
+/// Lock recovery state and counters of every transceiver for telemetry
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-004: Recovery actions visible to the ground
+pub async fn lock_recovery_reports() -> [LockRecoveryReport; 5] {
+    let monitors = initialized(&mut *HARDWARE_MANAGER.lock().await)
+        .map_or([LockMonitor::new(); 5], |manager| manager.lock_monitors);
+    core::array::from_fn(|i| LockRecoveryReport::new(RF_BANDS[i], &monitors[i]))
+}
+
 /// Emergency hardware shutdown
 pub async fn emergency_shutdown() -> Result<()> {
-    let manager = unsafe { HARDWARE_MANAGER.as_mut().unwrap() };
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    let manager = initialized(&mut manager)?;
 
     // Power down all transceivers except UHF (emergency communications)
     manager.s_band.status.is_powered = false;
@@ -758,73 +1025,306 @@ pub struct SensorReading {
     pub units: &'static str,
     /// Timestamp (milliseconds since boot)
     pub timestamp: u64,
+    /// Quality assigned by the driver from the sensor's limits
+    pub quality: MeasurementQuality,
 }
 
-/// Read temperature sensor
-pub async fn read_temperature_sensor(sensor_id: u16) -> Result<SensorReading> {
-    // Simulate sensor reading delay
-    Timer::after(Duration::from_millis(50)).await;
-
-    // Simulate temperature reading
-    let temp = match sensor_id {
-        0 => 25.5,  // Main board
-        1 => 30.2,  // RF section
-        2 => 22.8,  // Battery
-        3 => 45.1,  // Power amplifier
-        _ => 25.0,  // Default
+/// Temperature sensor limits: physical range of the thermistors and the
+/// expected on-orbit operating range
+const TEMPERATURE_LIMITS: QualityLimits = QualityLimits {
+    valid_min: -60.0,
+    valid_max: 125.0,
+    expected_min: -40.0,
+    expected_max: 70.0,
+};
+
+/// Voltage sensor limits: ADC full scale and the widest nominal rail band
+const VOLTAGE_LIMITS: QualityLimits = QualityLimits {
+    valid_min: 0.0,
+    valid_max: 36.0,
+    expected_min: 3.0,
+    expected_max: 32.0,
+};
+
+/// Current sensor limits: shunt amplifier range and nominal load envelope
+const CURRENT_LIMITS: QualityLimits = QualityLimits {
+    valid_min: -0.5,
+    valid_max: 10.0,
+    expected_min: 0.0,
+    expected_max: 5.0,
+};
+
+/// Seed of the simulated sensor noise, fixed so runs are repeatable
+const SENSOR_NOISE_SEED: u32 = 0x5EED_0001;
+
+/// Simulated sensors
+///
+/// Add a fault profile here (or inject one at runtime with
+/// [`set_sensor_fault`]) to exercise telemetry limits and FDIR, e.g.
+/// `.with_fault(FaultProfile::Stuck { from_ms: 600_000 })`.
+const SENSOR_TABLE: [SensorSpec; 14] = [
+    SensorSpec::new(SensorKind::Temperature, 0, "Main board", 25.5)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.2 }),
+    SensorSpec::new(SensorKind::Temperature, 1, "RF section", 30.2)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.3 })
+        .with_drift(0.4),
+    SensorSpec::new(SensorKind::Temperature, 2, "Battery", 22.8)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.1 }),
+    SensorSpec::new(SensorKind::Temperature, 3, "Power amplifier", 45.1)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.5 })
+        .with_drift(1.2),
+    SensorSpec::new(SensorKind::Voltage, 0, "Main bus", 12.1)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.05 }),
+    SensorSpec::new(SensorKind::Voltage, 1, "Digital supply", 5.05)
+        .with_noise(NoiseModel::Uniform { amplitude: 0.02 }),
+    SensorSpec::new(SensorKind::Voltage, 2, "Analog supply", 3.32)
+        .with_noise(NoiseModel::Uniform { amplitude: 0.01 }),
+    SensorSpec::new(SensorKind::Voltage, 3, "Battery", 28.5)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.05 })
+        .with_drift(-0.05),
+    SensorSpec::new(SensorKind::Voltage, 4, "Solar array", 32.0)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.2 }),
+    SensorSpec::new(SensorKind::Current, 0, "Total system", 2.15)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.04 }),
+    SensorSpec::new(SensorKind::Current, 1, "Digital section", 0.85)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.02 }),
+    SensorSpec::new(SensorKind::Current, 2, "RF section", 0.45)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.02 }),
+    SensorSpec::new(SensorKind::Current, 3, "Transmitter", 0.95)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.03 }),
+    SensorSpec::new(SensorKind::Current, 4, "Solar array", 1.8)
+        .with_noise(NoiseModel::Gaussian { sigma: 0.05 }),
+];
+
+static SENSORS: Mutex<CriticalSectionRawMutex, RefCell<SensorSuite<{ SENSOR_TABLE.len() }>>> =
+    Mutex::new(RefCell::new(SensorSuite::new(SENSOR_TABLE, SENSOR_NOISE_SEED)));
+
+/// Read sensor `sensor_id` of `kind` from the sensor table
+///
+/// Waits the sensor's conversion time, then classifies the reading against
+/// the limits of its kind.
+async fn read_sensor(kind: SensorKind, sensor_id: u16) -> Result<SensorReading> {
+    let (conversion_ms, limits) = match kind {
+        SensorKind::Temperature => (50, TEMPERATURE_LIMITS),
+        SensorKind::Voltage => (30, VOLTAGE_LIMITS),
+        SensorKind::Current => (40, CURRENT_LIMITS),
     };
+    Timer::after(Duration::from_millis(conversion_ms)).await;
+
+    let timestamp = embassy_time::Instant::now().as_millis();
+    let value = SENSORS.lock(|sensors| sensors.borrow_mut().read(kind, sensor_id, timestamp))?;
 
     Ok(SensorReading {
         sensor_id,
-        value: temp,
-        units: "C",
-        timestamp: embassy_time::Instant::now().as_millis(),
+        value,
+        units: kind.units(),
+        timestamp,
+        quality: limits.classify(value as f64),
     })
 }
 
+/// Read temperature sensor
+pub async fn read_temperature_sensor(sensor_id: u16) -> Result<SensorReading> {
+    read_sensor(SensorKind::Temperature, sensor_id).await
+}
+
 /// Read voltage sensor
 pub async fn read_voltage_sensor(sensor_id: u16) -> Result<SensorReading> {
-    Timer::after(Duration::from_millis(30)).await;
-
-    let voltage = match sensor_id {
-        0 => 12.1,  // Main bus
-        1 => 5.05,  // Digital supply
-        2 => 3.32,  // Analog supply
-        3 => 28.5,  // Battery
-        _ => 12.0,  // Default
-    };
-
-    Ok(SensorReading {
-        sensor_id,
-        value: voltage,
-        units: "V",
-        timestamp: embassy_time::Instant::now().as_millis(),
-    })
+    read_sensor(SensorKind::Voltage, sensor_id).await
 }
 
 /// Read current sensor
 pub async fn read_current_sensor(sensor_id: u16) -> Result<SensorReading> {
-    Timer::after(Duration::from_millis(40)).await;
-
-    let current = match sensor_id {
-        0 => 2.15,  // Total system
-        1 => 0.85,  // Digital section
-        2 => 0.45,  // RF section
-        3 => 0.95,  // Transmitter
-        _ => 1.0,   // Default
-    };
+    read_sensor(SensorKind::Current, sensor_id).await
+}
 
-    Ok(SensorReading {
-        sensor_id,
-        value: current,
-        units: "A",
-        timestamp: embassy_time::Instant::now().as_millis(),
+/// Voltage sensor on the regulated main bus
+const MAIN_BUS_VOLTAGE_SENSOR: u16 = 0;
+/// Voltage sensor across the battery
+const BATTERY_VOLTAGE_SENSOR: u16 = 3;
+/// Voltage and current sensors on the solar array
+const SOLAR_ARRAY_SENSOR: u16 = 4;
+/// Current sensor on the total system load
+const TOTAL_LOAD_CURRENT_SENSOR: u16 = 0;
+
+/// Read the power system sensors into an EPS summary
+///
+/// A sensor that cannot be read is reported as NaN rather than holding back
+/// the rest of the summary. State of charge is estimated from the battery
+/// voltage.
+pub async fn read_eps_summary() -> EpsSummary {
+    let value = |reading: Result<SensorReading>| reading.map_or(f32::NAN, |r| r.value);
+
+    let battery_voltage = value(read_voltage_sensor(BATTERY_VOLTAGE_SENSOR).await);
+    EpsSummary {
+        bus_voltage: value(read_voltage_sensor(MAIN_BUS_VOLTAGE_SENSOR).await),
+        battery_voltage,
+        battery_soc: battery_soc_percent(battery_voltage),
+        solar_voltage: value(read_voltage_sensor(SOLAR_ARRAY_SENSOR).await),
+        solar_current: value(read_current_sensor(SOLAR_ARRAY_SENSOR).await),
+        load_current: value(read_current_sensor(TOTAL_LOAD_CURRENT_SENSOR).await),
+        // Digital, RF and transmitter loads are current sensors 1 to 3
+        load_currents: [
+            value(read_current_sensor(1).await),
+            value(read_current_sensor(2).await),
+            value(read_current_sensor(3).await),
+        ],
+    }
+}
+
+/// Modeled power draw of every subsystem for the EPS summary frame
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-004: Power draw attributed per subsystem
+pub async fn power_attribution() -> PowerAttribution {
+    initialized(&mut *HARDWARE_MANAGER.lock().await)
+        .map_or(PowerAttribution::new(), |manager| manager.power_attribution())
+}
+
+/// Inject a fault into a simulated sensor, or clear it with `None`
+///
+/// Fault timelines are in milliseconds since boot.
+pub fn set_sensor_fault(
+    kind: SensorKind,
+    sensor_id: u16,
+    fault: Option<FaultProfile>,
+) -> Result<()> {
+    SENSORS.lock(|sensors| sensors.borrow_mut().set_fault(kind, sensor_id, fault))
+}
+
+/// Formation partner offsets at boot: 500 m ahead, circling 100 m radially
+/// and 50 m cross-track on a drift-free relative orbit
+const FORMATION_PARTNER_OFFSET_M: [f64; 3] = [100.0, 500.0, 50.0];
+
+/// Seed of the crosslink ranging noise
+const CROSSLINK_NOISE_SEED: u32 = 0x5EED_0002;
+
+/// Simulated crosslink ranger, with the time since boot its relative motion
+/// was last propagated to, ms
+static CROSSLINK: Mutex<CriticalSectionRawMutex, RefCell<(CrosslinkRanger, u64)>> =
+    Mutex::new(RefCell::new((
+        CrosslinkRanger::new(
+            RelativeState {
+                position_m: FORMATION_PARTNER_OFFSET_M,
+                velocity_m_s: [
+                    0.0,
+                    -2.0 * LEO_550_MEAN_MOTION_RAD_S * FORMATION_PARTNER_OFFSET_M[0],
+                    0.0,
+                ],
+            },
+            LEO_550_MEAN_MOTION_RAD_S,
+            RangingNoise {
+                reference_range_m: 1_000.0,
+                range_sigma_m: 0.5,
+                range_rate_sigma_m_s: 0.002,
+                range_bias_m: 0.0,
+                max_range_m: 50_000.0,
+            },
+            CROSSLINK_NOISE_SEED,
+        ),
+        0,
+    )));
+
+/// Range and range rate to the formation partner over the S-band crosslink
+///
+/// Propagates the simulated relative motion up to now before measuring, so
+/// the measurement is right however irregularly it is called.
+pub fn read_crosslink_ranging() -> RangingMeasurement {
+    let now_ms = embassy_time::Instant::now().as_millis();
+    CROSSLINK.lock(|crosslink| {
+        let (ranger, propagated_ms) = &mut *crosslink.borrow_mut();
+        ranger.advance(now_ms.saturating_sub(*propagated_ms) as f64 / 1_000.0);
+        *propagated_ms = now_ms;
+        ranger.measure()
     })
 }
 
+/// True orbit at boot: circular at 550 km, inclined 97.6 degrees (sun
+/// synchronous), ascending node on the inertial x axis
+const GPS_TRUE_ORBIT: OrbitState = OrbitState {
+    position_m: [6_928_137.0, 0.0, 0.0],
+    velocity_m_s: [0.0, -1_003.176, 7_518.458],
+};
+
+/// Drag deceleration of the true orbit at 550 km, m/s²; the navigator does
+/// not model it
+const GPS_TRUE_DRAG_M_S2: f64 = 2e-6;
+
+/// Seed of the GPS receiver noise
+const GPS_NOISE_SEED: u32 = 0x5EED_0003;
+
+/// Simulated GPS receiver, with the time since boot its true orbit was last
+/// propagated to, ms
+static GPS: Mutex<CriticalSectionRawMutex, RefCell<(GpsReceiver, u64)>> =
+    Mutex::new(RefCell::new((
+        GpsReceiver::new(
+            GPS_TRUE_ORBIT,
+            GPS_TRUE_DRAG_M_S2,
+            GpsNoise {
+                position_sigma_m: 5.0,
+                velocity_sigma_m_s: 0.05,
+            },
+            GPS_NOISE_SEED,
+        ),
+        0,
+    )));
+
+static NAVIGATOR: Mutex<CriticalSectionRawMutex, RefCell<Navigator>> =
+    Mutex::new(RefCell::new(Navigator::new(NavigationConfig::DEFAULT)));
+
+/// Attitude control pointing deadband, millidegrees
+static POINTING_DEADBAND_MDEG: AtomicU32 = AtomicU32::new(100);
+
+/// Take a GPS fix, if the receiver has one, and update the navigation
+/// solution with it
+///
+/// During an outage the navigator carries the last solution forward on
+/// propagated orbit knowledge.
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-004: Navigation through a GPS outage
+pub fn update_navigation() -> NavigationSolution {
+    let now_ms = embassy_time::Instant::now().as_millis();
+    let fix = GPS.lock(|gps| {
+        let (receiver, propagated_ms) = &mut *gps.borrow_mut();
+        receiver.advance(now_ms.saturating_sub(*propagated_ms) as f64 / 1_000.0);
+        *propagated_ms = now_ms;
+        receiver.fix(now_ms)
+    });
+    NAVIGATOR.lock(|navigator| navigator.borrow_mut().update(now_ms, fix))
+}
+
+/// Navigation solution as of the last update
+pub fn navigation_solution() -> NavigationSolution {
+    NAVIGATOR.lock(|navigator| navigator.borrow().solution())
+}
+
+/// Inject a GPS outage, or clear it with `None`
+///
+/// Outage windows are in milliseconds since boot.
+pub fn set_gps_outage(outage: Option<GpsOutage>) {
+    GPS.lock(|gps| gps.borrow_mut().0.set_outage(outage));
+}
+
+/// Set the attitude control pointing deadband, degrees
+pub fn set_pointing_deadband(deadband_deg: f64) {
+    let millidegrees = (deadband_deg * 1_000.0).clamp(0.0, f64::from(u32::MAX)) as u32;
+    POINTING_DEADBAND_MDEG.store(millidegrees, Ordering::Relaxed);
+}
+
+/// Attitude control pointing deadband, degrees
+pub fn pointing_deadband_deg() -> f64 {
+    f64::from(POINTING_DEADBAND_MDEG.load(Ordering::Relaxed)) / 1_000.0
+}
+
 /// Check if hardware is in safe state
-pub fn is_hardware_safe() -> bool {
-    let manager = unsafe { HARDWARE_MANAGER.as_ref().unwrap() };
+///
+/// Hardware the manager has not initialized is never safe.
+pub async fn is_hardware_safe() -> bool {
+    let mut manager = HARDWARE_MANAGER.lock().await;
+    let Ok(manager) = initialized(&mut manager) else {
+        return false;
+    };
     let statuses = manager.get_all_statuses();
 
     // Check temperatures are within safe range
@@ -0,0 +1,72 @@
+//! Priority inversion instrumentation
+//!
+//! Tasks bracket their lower-priority processing with [`enter`] / [`exit`],
+//! and the real-time paths call [`check_wait`] when a Critical or Emergency
+//! message starts processing. A wait behind a lower-priority section is
+//! logged with the section's name and counted; the counters are downlinked in
+//! housekeeping telemetry. The detector lives behind a critical-section mutex
+//! so every task can update it without `unsafe`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-010: Real-Time Constraints (runtime evidence of inversion-free
+//!   real-time processing)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    messaging::MessagePriority,
+    priority_inversion::{
+        Inversion, InversionCounters, InversionDetector, InversionPolicy, Section,
+    },
+};
+
+use crate::error_handling;
+
+static DETECTOR: Mutex<CriticalSectionRawMutex, RefCell<InversionDetector>> =
+    Mutex::new(RefCell::new(InversionDetector::new()));
+
+/// Mark `section` as running work at `priority`
+pub fn enter(section: Section, priority: MessagePriority) {
+    let now_us = Instant::now().as_micros();
+    DETECTOR.lock(|detector| detector.borrow_mut().enter(section, priority, now_us));
+}
+
+/// Mark `section` as finished
+pub fn exit(section: Section) {
+    let now_us = Instant::now().as_micros();
+    DETECTOR.lock(|detector| detector.borrow_mut().exit(section, now_us));
+}
+
+/// Check a real-time message that became ready at `ready` and starts now
+///
+/// Logs the section that blocked it when the wait was an inversion.
+pub fn check_wait(priority: MessagePriority, ready: Instant) -> Option<Inversion> {
+    let now_us = Instant::now().as_micros();
+    let inversion = DETECTOR.lock(|detector| {
+        detector.borrow_mut().check_wait(
+            priority,
+            ready.as_micros(),
+            now_us,
+            &InversionPolicy::default(),
+        )
+    })?;
+
+    error_handling::log_warning(match inversion.section {
+        Section::MessageQueue => "Priority inversion: waited behind message queue",
+        Section::CommandProcessor => "Priority inversion: waited behind command processor",
+        Section::CommunicationManager => {
+            "Priority inversion: waited behind communication manager"
+        }
+        Section::TelemetryCollector => "Priority inversion: waited behind telemetry collector",
+        Section::Housekeeping => "Priority inversion: waited behind housekeeping",
+    });
+    Some(inversion)
+}
+
+/// Inversion counters since boot
+pub fn counters() -> InversionCounters {
+    DETECTOR.lock(|detector| *detector.borrow().counters())
+}
@@ -11,49 +11,118 @@
 //! - Hardware abstraction layer for RF transceivers
 //! - Fault tolerance with watchdog timers
 //! - CCSDS-compliant packet processing
+//! - COP-1 FARM-1 acceptance of uplinked TC Transfer Frames
+//! - Priority inversion instrumentation on the real-time paths
+//! - Telemetry queue that sheds housekeeping before alarms and events
+//! - Compressed event log downlink
+//! - Recorder downlink planned from the uplinked link capacity forecast
+//! - Mass memory file system with per-data-type quotas and retention
+//! - Memory dump and dwell diagnostics
+//! - Electrical power system summary telemetry
+//! - GPS navigation with a propagated-orbit fallback that widens the pointing
+//!   deadband and raises FDIR once the solution is lost
+//! - Flight rules checked before commands and autonomous actions
+//! - Uploaded autonomy rules acting on telemetry conditions, enabled and
+//!   disabled by command
+//! - AES-256-GCM link protection with commanded key rotation
+//! - No `unwrap`/`expect` in flight code: broken invariants escalate to FDIR
 //!
 //! # Requirements Traceability
 //! - REQ-FN-010: Real-Time Constraints (Embassy async runtime with task timing)
 //! - REQ-NF-002: Memory Constraints (heapless collections, static allocation)
 //! - REQ-NF-005: Cross-Platform Support (ARM Cortex-M target)
 //! - REQ-SF-002: Watchdog Protection (watchdog timer implementation)
+//! - REQ-SC-003: Link Security (protected frames, key rotation by command)
 
-#![no_std]
-#![no_main]
+// Unit tests run on the host, under the standard test harness
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(test), no_main)]
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
+// No unwrap or expect in flight code: failures go through FDIR (REQ-NF-004)
+#![deny(clippy::unwrap_used, clippy::expect_used)]
 
 // External crate imports
+use core::cell::Cell;
+
 use cortex_m;
-use embassy_executor::Spawner;
-use embassy_time::{Duration, Timer};
-use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_executor::{SpawnToken, Spawner};
+use embassy_time::{Duration, Instant, Timer};
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
 use embassy_sync::channel::{Channel, Receiver, Sender};
 use heapless::String;
 
 // Internal module imports
+mod autonomy;
 mod communication;
 mod hardware;
 mod error_handling;
 mod command;
+mod command_schedule;
+mod diagnostics;
+mod downlink_plan;
+mod flight_rules;
+mod inversion;
+mod mass_memory;
+mod mode;
+mod recorder;
+mod telemetry_queue;
 mod watchdog;
 mod hardware;
 mod error_handling;
 
 // Shared library imports
 use space_comms_shared::{
-    messaging::{Message, MessagePriority, PriorityQueue},
-    telemetry::{TelemetryData, TelemetryPacket},
-    types::{ComponentId, BandType, HealthStatus},
-    ccsds::{SpacePacket, PacketType},
+    autonomy::{AutonomySwitch, AUTONOMY_PERIOD_MS, AUTONOMY_RULE_APID},
+    command_dedup::{CommandKey, DuplicateFilter, DEDUP_CAPACITY},
+    command_load::COMMAND_LOAD_APID,
+    diagnostics::DiagnosticRequest,
+    eps::EPS_PERIOD_MS,
+    formation::CROSSLINK_RANGING_PERIOD_MS,
+    event_log::EventLogCompressor,
+    execution_report::{ExecutionReport, ExecutionResult},
+    file_downlink::RETRANSMIT_REQUEST_APID,
+    flight_rules::Activity,
+    link_config::LinkDirection,
+    link_forecast::{DownlinkPolicy, LINK_FORECAST_APID},
+    loopback::LoopbackRequest,
+    mass_memory::FileRequest,
+    mission_phase::MissionPhase,
+    navigation::{NavigationMode, PointingDeadband, NAVIGATION_PERIOD_MS},
+    priority_inversion::Section,
+    security::KeyRotation,
+    messaging::{
+        decode_command_packet, is_emergency_frame, EmergencyDuplicateFilter, Message,
+        MessagePriority, PriorityQueue,
+    },
+    telemetry::{
+        measurement_ids, Measurement, MeasurementQuality, MeasurementValue, TelemetryData,
+        TelemetryPacket, TelemetrySet,
+    },
+    types::{ComponentId, BandType, HealthStatus, OperationalMode},
+    ccsds::{SpacePacket, SpacePacketHeader, PacketType},
+    rf_housekeeping::LockRecoveryStep,
+    sequence::SequenceWindows,
+    wire,
     Result, SpaceCommError,
 };
 
+/// Uplink APIDs whose sequence counts are tracked
+const UPLINK_SEQUENCE_APIDS: usize = 16;
+
+/// Uplink APIDs accepting fewer counts ahead than the default window: the
+/// emergency lane and command loads are never sent in bursts, so a large
+/// jump there is more likely a replay than lost frames
+const UPLINK_SEQUENCE_WINDOWS: [(u16, u16); 2] = [
+    (MessagePriority::Emergency.command_apid(), 8),
+    (COMMAND_LOAD_APID, 16),
+];
+
 /// Maximum number of messages in priority queue (embedded constraint)
 const MAX_QUEUE_SIZE: usize = 32;
 
 /// Maximum number of telemetry measurements per packet
-const MAX_TELEMETRY_MEASUREMENTS: usize = 16;
+const MAX_TELEMETRY_MEASUREMENTS: usize = 32;
 
 /// System heartbeat interval in milliseconds
 const HEARTBEAT_INTERVAL_MS: u64 = 1000;
@@ -61,34 +130,70 @@ const HEARTBEAT_INTERVAL_MS: u64 = 1000;
 /// Critical message processing interval in milliseconds
 const CRITICAL_PROCESSING_INTERVAL_MS: u64 = 1;
 
+/// Emergency uplink lane polling interval in milliseconds
+const EMERGENCY_UPLINK_INTERVAL_MS: u64 = 1;
+
+/// Transceiver lock sampling interval in milliseconds
+const LOCK_MONITOR_INTERVAL_MS: u64 = 500;
+
+/// Event log downlink interval in milliseconds
+const EVENT_LOG_DOWNLINK_INTERVAL_MS: u64 = 5000;
+
+/// Largest compressed event log block, bytes; with the packet header and CRC
+/// it fits a 512-byte frame and holds even a maximum-length log entry
+const EVENT_LOG_BLOCK_LEN: usize = 496;
+
+/// FDIR error code for a transceiver that exhausted its lock recovery ladder
+/// (>= 800 selects a switch to the backup band)
+const LOCK_RECOVERY_FAILED_CODE: u32 = 850;
+
+/// FDIR error code for a navigation solution lost in a GPS outage (< 500
+/// restarts the GPS receiver)
+const NAVIGATION_LOST_CODE: u32 = 420;
+
+/// Pointing deadband of the attitude controller against navigation
+/// uncertainty
+const POINTING_DEADBAND: PointingDeadband = PointingDeadband::DEFAULT;
+
+/// Diagnostics task interval in milliseconds; the shortest dwell interval
+const DIAGNOSTICS_INTERVAL_MS: u64 = 10;
+
+/// Command scheduler release interval in milliseconds; release times are
+/// whole seconds
+const SCHEDULER_TICK_MS: u64 = 1000;
+
 /// Telemetry transmission interval in milliseconds
 const TELEMETRY_INTERVAL_MS: u64 = 100;
 
 /// Communication channels for inter-task messaging
+/// Opened commands the command channel holds
+const COMMAND_CHANNEL_DEPTH: usize = 8;
+
 type MessageChannel = Channel<CriticalSectionRawMutex, Message, 16>;
-type TelemetryChannel = Channel<CriticalSectionRawMutex, TelemetryPacket, 8>;
-type CommandChannel = Channel<CriticalSectionRawMutex, SpacePacket, 8>;
+type CommandChannel =
+    Channel<CriticalSectionRawMutex, communication::UplinkedCommand, COMMAND_CHANNEL_DEPTH>;
 
 /// Global channels for task communication
 static MESSAGE_QUEUE_CHANNEL: MessageChannel = Channel::new();
-static TELEMETRY_CHANNEL: TelemetryChannel = Channel::new();
 static COMMAND_CHANNEL: CommandChannel = Channel::new();
 
+/// Commands waiting in the command channel, which reports no fill level
+static QUEUED_COMMANDS: Mutex<CriticalSectionRawMutex, Cell<usize>> = Mutex::new(Cell::new(0));
+
 /// System health monitor
-static mut SYSTEM_HEALTH: HealthStatus = HealthStatus::Unknown;
+static SYSTEM_HEALTH: Mutex<CriticalSectionRawMutex, Cell<HealthStatus>> =
+    Mutex::new(Cell::new(HealthStatus::Unknown));
 
 /// Main entry point for the satellite system
 /// REQ-FN-010: Real-Time Constraints - Embassy async runtime for deterministic scheduling
-#[embassy_executor::main]
+#[cfg_attr(not(test), embassy_executor::main)]
 async fn main(spawner: Spawner) {
     // Initialize error handling system
     error_handling::initialize();
     error_handling::log_info("Satellite system starting up");
 
     // Initialize system health
-    unsafe {
-        SYSTEM_HEALTH = HealthStatus::Good;
-    }
+    set_system_health(HealthStatus::Good);
 
     // Initialize hardware transceivers
     match hardware::initialize_transceivers().await {
@@ -113,26 +218,123 @@ async fn main(spawner: Spawner) {
 
     // Spawn high-priority tasks
     // REQ-FN-010: Real-Time Constraints - Task spawning with priority-based scheduling
-    spawner.spawn(critical_message_processor()).unwrap();  // Emergency/Critical processing
-    spawner.spawn(telemetry_collector()).unwrap();         // Real-time telemetry
-    spawner.spawn(command_processor()).unwrap();           // Command execution
+    spawn_task(&spawner, emergency_uplink_handler(), "emergency_uplink_handler");     // Emergency command lane
+    spawn_task(&spawner, critical_message_processor(), "critical_message_processor"); // Emergency/Critical processing
+    spawn_task(&spawner, telemetry_collector(), "telemetry_collector");               // Real-time telemetry
+    spawn_task(&spawner, command_processor(), "command_processor");                   // Command execution
+    spawn_task(&spawner, command_scheduler(), "command_scheduler");                   // Time-tagged command loads
 
     // Spawn medium-priority tasks
-    spawner.spawn(communication_manager()).unwrap();       // RF communication management
-    spawner.spawn(health_monitor()).unwrap();              // System health monitoring
-    spawner.spawn(error_handling::health_check_task()).unwrap(); // Error detection
+    spawn_task(&spawner, communication_manager(), "communication_manager");           // RF communication management
+    spawn_task(&spawner, rf_housekeeping_reporter(), "rf_housekeeping_reporter");     // Transceiver RF metrics
+    spawn_task(&spawner, eps_reporter(), "eps_reporter");                             // Power system summary
+    spawn_task(&spawner, crosslink_reporter(), "crosslink_reporter");                 // Formation range and range rate
+    spawn_task(&spawner, navigation_reporter(), "navigation_reporter");               // GPS or propagated orbit
+    spawn_task(&spawner, autonomy_executor(), "autonomy_executor");                   // Autonomy rules
+    spawn_task(&spawner, event_log_downlink(), "event_log_downlink");                 // Compressed event log
+    spawn_task(&spawner, diagnostics_downlink(), "diagnostics_downlink");             // Memory dumps and dwells
+    spawn_task(&spawner, transceiver_lock_monitor(), "transceiver_lock_monitor");     // Lock-loss recovery
+    spawn_task(&spawner, health_monitor(), "health_monitor");                         // System health monitoring
+    spawn_task(&spawner, error_handling::health_check_task(), "health_check_task");   // Error detection
 
     // Spawn low-priority tasks
-    spawner.spawn(housekeeping_task()).unwrap();           // Routine maintenance
-    spawner.spawn(system_heartbeat()).unwrap();            // System heartbeat
+    spawn_task(&spawner, housekeeping_task(), "housekeeping_task");                   // Routine maintenance
+    spawn_task(&spawner, system_heartbeat(), "system_heartbeat");                     // System heartbeat
 
-    // Main loop - should never exit
+    // Main loop - should never exit; carries out FDIR actions raised by
+    // invariant violations, even if the health tasks failed to spawn
     loop {
         Timer::after(Duration::from_secs(1)).await;
+        error_handling::execute_pending_recovery().await;
         watchdog::reset();
     }
 }
 
+/// Spawn a task, escalating a failure to FDIR instead of panicking
+///
+/// A task that does not spawn leaves the system degraded rather than lost:
+/// the remaining tasks run and the main loop enters safe mode.
+fn spawn_task<S>(spawner: &Spawner, token: SpawnToken<S>, name: &'static str) {
+    if spawner.spawn(token).is_err() {
+        let _ = error_handling::escalate_invariant(name, "Task failed to spawn");
+    }
+}
+
+/// Emergency uplink handler - dedicated emergency command lane (1000Hz)
+///
+/// Services the emergency virtual channel on its own receiver. Emergency
+/// command frames are CRC-checked, de-duplicated and executed directly,
+/// bypassing the command channel, the priority queue and band selection, so
+/// bulk uplink traffic can never delay them. Any other frame arriving on the
+/// lane is handed to the normal command path.
+/// REQ-FN-002: Emergency Command Set - <1ms processing time
+/// REQ-FN-010: Real-Time Constraints - Dedicated emergency lane
+#[embassy_executor::task]
+async fn emergency_uplink_handler() {
+    let mut duplicates = EmergencyDuplicateFilter::new();
+    let mut next_poll = Instant::now();
+
+    loop {
+        if let Ok(frame) = communication::receive_emergency_frame().await {
+            if is_emergency_frame(&frame) {
+                // Any delay past the scheduled poll was spent behind other work
+                inversion::check_wait(MessagePriority::Emergency, next_poll);
+                let started = Instant::now();
+                // REQ-SC-003: Repeated copies are dropped unopened; their
+                // frame counter would be refused as a replay
+                let repeat = SpacePacketHeader::from_bytes(&frame)
+                    .is_ok_and(|header| duplicates.is_repeat(header.sequence_count));
+                let decoded = if repeat {
+                    Ok(None)
+                } else {
+                    communication::open_emergency_frame(&frame)
+                        .and_then(|opened| decode_command_packet(&opened))
+                        .map(Some)
+                };
+                match decoded {
+                    Ok(Some(fields)) if duplicates.accept(fields.sequence_count) => {
+                        let outcome = command::execute_critical_command(
+                            fields.command_id,
+                            &fields.parameters,
+                        )
+                        .await;
+                        match &outcome {
+                            Ok(_) => mode::record_command_accepted(),
+                            Err(_) => {
+                                mode::record_command_rejected();
+                                error_handling::log_error("Emergency command failed");
+                            }
+                        }
+                        report_execution(
+                            fields.command_id,
+                            fields.sequence_count,
+                            fields.priority,
+                            &outcome,
+                            started,
+                        )
+                        .await;
+                    }
+                    Ok(_) => {} // Repeated copy of a command already executed
+                    Err(_) => {
+                        mode::record_command_rejected();
+                        error_handling::log_error("Emergency frame rejected");
+                    }
+                }
+            } else if let Ok(command) = communication::parse_received_packet(&frame)
+                .and_then(|packet| communication::open_uplink(&packet))
+            {
+                if !queue_command(command) {
+                    error_handling::log_warning("Command channel full, dropping command");
+                }
+            }
+        }
+
+        let interval = Duration::from_millis(EMERGENCY_UPLINK_INTERVAL_MS);
+        next_poll = Instant::now() + interval;
+        Timer::after(interval).await;
+    }
+}
+
 /// Critical message processor - highest priority task (1000Hz)
 ///
 /// Processes emergency and critical priority messages with minimal latency.
@@ -144,11 +346,14 @@ async fn main(spawner: Spawner) {
 async fn critical_message_processor() {
     let mut queue: PriorityQueue<MAX_QUEUE_SIZE> = PriorityQueue::new();
     let receiver = MESSAGE_QUEUE_CHANNEL.receiver();
+    let mut next_poll = Instant::now();
 
     loop {
         // Check for new messages
         if let Ok(message) = receiver.try_receive() {
             if message.priority >= MessagePriority::Critical {
+                // REQ-FN-010: Waits past the scheduled poll are inversion candidates
+                inversion::check_wait(message.priority, next_poll);
                 if let Err(e) = process_critical_message(&message).await {
                     error_handling::log_error("Critical message processing failed", &e);
                 }
@@ -160,11 +365,16 @@ async fn critical_message_processor() {
             }
         }
 
+        // Queued work delays the next poll, so the deadline is set before it
+        next_poll = Instant::now() + Duration::from_millis(CRITICAL_PROCESSING_INTERVAL_MS);
+
         // Process queued messages if no critical messages pending
         if let Some(message) = queue.pop() {
+            inversion::enter(Section::MessageQueue, message.priority);
             if let Err(e) = process_message(&message).await {
                 error_handling::log_error("Message processing failed", &e);
             }
+            inversion::exit(Section::MessageQueue);
         }
 
         // Critical timing requirement: process at 1000Hz
@@ -182,7 +392,12 @@ async fn process_critical_message(message: &Message) -> Result<()> {
         }
         space_comms_shared::messaging::MessagePayload::Command { command_id, parameters } => {
             // Execute critical commands immediately
-            command::execute_critical_command(*command_id, parameters).await?;
+            let started = Instant::now();
+            let outcome = command::execute_critical_command(*command_id, parameters).await;
+            let sequence_count = (message.id.value() & 0x3FFF) as u16;
+            report_execution(*command_id, sequence_count, message.priority, &outcome, started)
+                .await;
+            outcome?;
         }
         _ => {
             // Other message types shouldn't be critical, but handle gracefully
@@ -192,6 +407,34 @@ async fn process_critical_message(message: &Message) -> Result<()> {
     Ok(())
 }
 
+/// Downlink the execution report of a command that started at `started`
+///
+/// The execution time is measured before the report is transmitted; commands
+/// that overran their priority's budget are also logged.
+/// REQ-PF-001: Command Response Time - Per-command timing evidence
+async fn report_execution<T>(
+    command_id: u32,
+    sequence_count: u16,
+    priority: MessagePriority,
+    outcome: &Result<T>,
+    started: Instant,
+) {
+    let elapsed_us = u32::try_from(started.elapsed().as_micros()).unwrap_or(u32::MAX);
+    let report = ExecutionReport::new(
+        command_id,
+        sequence_count,
+        priority,
+        ExecutionResult::of(outcome),
+        elapsed_us,
+    );
+    if !report.within_budget() {
+        error_handling::log_warning("Command exceeded its execution time budget");
+    }
+    if communication::transmit_execution_report(&report).await.is_err() {
+        error_handling::log_error("Execution report transmission failed");
+    }
+}
+
 /// Process regular priority messages
 async fn process_message(message: &Message) -> Result<()> {
     match message.priority {
@@ -212,31 +455,472 @@ async fn process_message(message: &Message) -> Result<()> {
 
 /// Telemetry collection task (100Hz)
 ///
-/// Collects system telemetry data and packages it for transmission.
+/// Collects system telemetry data and packages it for transmission at the
+/// default rate of the mission phase. While the mode manager reports safe
+/// mode, only the minimal safe-mode set is collected and the rate drops
+/// accordingly; the full set resumes on exit. An alarm or
+/// event frame that finds the downlink queue full of protected frames is
+/// retried before anything new is collected.
 #[embassy_executor::task]
 async fn telemetry_collector() {
-    let sender = TELEMETRY_CHANNEL.sender();
     let mut sequence_counter: u32 = 0;
+    let mut active_set = TelemetrySet::Full;
+    let mut pending: Option<TelemetryPacket> = None;
 
     loop {
+        let set = mode::telemetry_set();
+        if let Some(packet) = pending.take() {
+            pending = telemetry_queue::push(packet);
+            Timer::after(Duration::from_millis(telemetry_interval_ms(set))).await;
+            continue;
+        }
+
+        if set != active_set {
+            error_handling::log_info(match set {
+                TelemetrySet::SafeMode => "Telemetry switched to safe-mode set",
+                TelemetrySet::Full => "Telemetry restored to full set",
+            });
+            active_set = set;
+        }
+
         // Collect telemetry from various subsystems
-        let telemetry = collect_system_telemetry().await;
+        inversion::enter(Section::TelemetryCollector, MessagePriority::Medium);
+        let telemetry = match set {
+            TelemetrySet::Full => collect_telemetry_data().await,
+            TelemetrySet::SafeMode => collect_safe_mode_telemetry().await,
+        };
+        inversion::exit(Section::TelemetryCollector);
 
         // Create telemetry packet
         let packet = TelemetryPacket::new(
             sequence_counter,
             telemetry,
-            BandType::SBand, // Default to S-Band for telemetry
+            communication::link(LinkDirection::Downlink).band, // REQ-FN-007: Downlink band
         );
 
-        // Send to communication manager
-        if let Err(_) = sender.try_send(packet) {
-            error_handling::log_warning("Telemetry channel full, dropping packet");
-        }
+        // Queue for the communication manager; overflow drops housekeeping first
+        pending = telemetry_queue::push(packet);
 
         sequence_counter = sequence_counter.wrapping_add(1);
 
-        Timer::after(Duration::from_millis(TELEMETRY_INTERVAL_MS)).await;
+        Timer::after(Duration::from_millis(telemetry_interval_ms(set))).await;
+    }
+}
+
+/// Telemetry collection interval for `set` at the mission phase's default rate
+fn telemetry_interval_ms(set: TelemetrySet) -> u64 {
+    set.interval_ms(mode::phase_policy().telemetry_interval_ms(TELEMETRY_INTERVAL_MS))
+}
+
+/// Event log downlink task
+///
+/// Compresses the log entries logged since the last pass into blocks and
+/// downlinks them, one block per packet, until the backlog is drained. A
+/// block that fails to transmit is not resent; the failure itself is logged
+/// and goes down with the next pass.
+/// REQ-NF-002: Compressed event log downlink
+#[embassy_executor::task]
+async fn event_log_downlink() {
+    loop {
+        loop {
+            let mut block = EventLogCompressor::<EVENT_LOG_BLOCK_LEN>::new();
+            if error_handling::compress_pending_logs(&mut block) == 0 {
+                break;
+            }
+            if communication::transmit_event_log(&block).await.is_err() {
+                error_handling::log_error("Event log transmission failed");
+                break;
+            }
+        }
+
+        Timer::after(Duration::from_millis(EVENT_LOG_DOWNLINK_INTERVAL_MS)).await;
+    }
+}
+
+/// Diagnostics downlink task
+///
+/// Downlinks one segment of a running memory dump per cycle, so a long dump
+/// shares the downlink with telemetry, and takes the samples of a running
+/// dwell as they fall due, downlinking each batch once it is complete. Also
+/// downlinks a requested mass memory file listing and removes files past
+/// their retention.
+/// REQ-FN-005: Memory dump and dwell diagnostics, file listings
+#[embassy_executor::task]
+async fn diagnostics_downlink() {
+    loop {
+        if let Some(segment) = diagnostics::next_dump_segment() {
+            if communication::transmit_memory_dump_segment(&segment).await.is_err() {
+                error_handling::log_error("Memory dump transmission failed");
+            }
+        }
+
+        diagnostics::sample_dwell().await;
+        while let Some(batch) = diagnostics::next_dwell_batch() {
+            if communication::transmit_dwell_batch(&batch).await.is_err() {
+                error_handling::log_error("Dwell transmission failed");
+            }
+        }
+
+        if let Some(listing) = mass_memory::take_listing() {
+            if communication::transmit_file_listing(&listing).await.is_err() {
+                error_handling::log_error("File listing transmission failed");
+            }
+        }
+        mass_memory::expire();
+
+        Timer::after(Duration::from_millis(DIAGNOSTICS_INTERVAL_MS)).await;
+    }
+}
+
+/// RF housekeeping task
+///
+/// Downlinks every transceiver's power, lock, transmit power, signal strength,
+/// temperature and frequency at the configurable housekeeping interval, on
+/// its own APID and independent of the main telemetry rate, followed by each
+/// transceiver's lock recovery state and counters, then the link layer
+/// statistics of the communication manager with the recorder downlink
+/// policy and its statistics.
+/// REQ-PF-002: Link quality monitoring
+#[embassy_executor::task]
+async fn rf_housekeeping_reporter() {
+    let mut sequence: u16 = 0;
+
+    loop {
+        let mut data = TelemetryData {
+            source: ComponentId::new(0x0001), // Satellite system ID
+            timestamp: get_system_time_ns(),
+            measurements: Vec::new(),
+            health_status: get_system_health(),
+        };
+
+        for status in hardware::rf_band_statuses().await.iter() {
+            if status.append_measurements(&mut data).is_err() {
+                error_handling::log_error("RF housekeeping frame overflow");
+            }
+        }
+
+        if communication::transmit_rf_housekeeping(&data, sequence).await.is_err() {
+            error_handling::log_error("RF housekeeping transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        // Lock recovery state and counters follow in their own frame
+        data.measurements.clear();
+        for report in hardware::lock_recovery_reports().await.iter() {
+            if report.append_measurements(&mut data).is_err() {
+                error_handling::log_error("Lock recovery frame overflow");
+            }
+        }
+
+        if communication::transmit_rf_housekeeping(&data, sequence).await.is_err() {
+            error_handling::log_error("Lock recovery transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        // Link layer statistics follow in their own frame
+        data.measurements.clear();
+        if communication::link_stats()
+            .append_measurements(&mut data)
+            .is_err()
+        {
+            error_handling::log_error("Link statistics frame overflow");
+        }
+        if downlink_plan::policy_stats()
+            .append_measurements(&mut data)
+            .is_err()
+        {
+            error_handling::log_error("Downlink policy statistics frame overflow");
+        }
+
+        if communication::transmit_rf_housekeeping(&data, sequence).await.is_err() {
+            error_handling::log_error("Link statistics transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        Timer::after(Duration::from_millis(hardware::rf_housekeeping_interval_ms())).await;
+    }
+}
+
+/// EPS summary reporting task
+///
+/// Downlinks bus and battery voltage, state of charge, solar array output,
+/// per-load currents and the modeled draw of each subsystem as one frame on
+/// the EPS APID every EPS period.
+/// REQ-NF-004: Power management
+#[embassy_executor::task]
+async fn eps_reporter() {
+    let mut sequence: u16 = 0;
+
+    loop {
+        let mut data = TelemetryData {
+            source: ComponentId::new(0x0001), // Satellite system ID
+            timestamp: get_system_time_ns(),
+            measurements: Vec::new(),
+            health_status: get_system_health(),
+        };
+
+        let summary = hardware::read_eps_summary().await;
+        if summary.append_measurements(&mut data).is_err() {
+            error_handling::log_error("EPS summary frame overflow");
+        }
+        if hardware::power_attribution()
+            .await
+            .append_measurements(&mut data)
+            .is_err()
+        {
+            error_handling::log_error("Power attribution frame overflow");
+        }
+
+        if communication::transmit_eps_summary(&data, sequence).await.is_err() {
+            error_handling::log_error("EPS summary transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        Timer::after(Duration::from_millis(EPS_PERIOD_MS)).await;
+    }
+}
+
+/// Crosslink ranging reporting task
+///
+/// Downlinks the range and range rate to the formation partner, with their
+/// 1-sigma errors, as one frame on the crosslink ranging APID every period.
+/// REQ-FN-007: Multi-Band Communication - S-band crosslink ranging
+#[embassy_executor::task]
+async fn crosslink_reporter() {
+    let mut sequence: u16 = 0;
+
+    loop {
+        let mut data = TelemetryData {
+            source: ComponentId::new(0x0001), // Satellite system ID
+            timestamp: get_system_time_ns(),
+            measurements: Vec::new(),
+            health_status: get_system_health(),
+        };
+
+        let measurement = hardware::read_crosslink_ranging();
+        if measurement.append_measurements(&mut data).is_err() {
+            error_handling::log_error("Crosslink ranging frame overflow");
+        }
+
+        if communication::transmit_crosslink_ranging(&data, sequence).await.is_err() {
+            error_handling::log_error("Crosslink ranging transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        Timer::after(Duration::from_millis(CROSSLINK_RANGING_PERIOD_MS)).await;
+    }
+}
+
+/// Navigation task
+///
+/// Updates the navigation solution from the GPS receiver, falling back to
+/// propagated orbit knowledge during an outage, and downlinks it on the
+/// navigation APID every period. The attitude controller's pointing
+/// deadband follows the position uncertainty; a lost solution is reported
+/// to FDIR as a GPS receiver fault.
+/// REQ-NF-004: Fault tolerance - navigation through a GPS outage
+#[embassy_executor::task]
+async fn navigation_reporter() {
+    let mut sequence: u16 = 0;
+    let mut mode = NavigationMode::Lost;
+
+    loop {
+        let solution = hardware::update_navigation();
+        if solution.mode != mode {
+            match solution.mode {
+                NavigationMode::Gps => error_handling::log_info("GPS fix acquired"),
+                NavigationMode::Propagated => {
+                    error_handling::log_warning("GPS outage, navigating on propagated orbit");
+                }
+                NavigationMode::Lost => {
+                    error_handling::log_critical("Navigation solution lost");
+                    let fault = error_handling::FaultType::Hardware {
+                        component: String::from("GPS receiver"),
+                        error_code: NAVIGATION_LOST_CODE,
+                    };
+                    let action = error_handling::handle_fault(fault);
+                    if error_handling::execute_recovery_action(action).await.is_err() {
+                        error_handling::log_error("Navigation FDIR action failed");
+                    }
+                }
+            }
+            mode = solution.mode;
+        }
+        hardware::set_pointing_deadband(POINTING_DEADBAND.deadband_deg(&solution));
+
+        let mut data = TelemetryData {
+            source: ComponentId::new(0x0001), // Satellite system ID
+            timestamp: get_system_time_ns(),
+            measurements: Vec::new(),
+            health_status: get_system_health(),
+        };
+        if solution.append_measurements(&mut data).is_err() {
+            error_handling::log_error("Navigation frame overflow");
+        }
+
+        if communication::transmit_navigation(&data, sequence).await.is_err() {
+            error_handling::log_error("Navigation transmission failed");
+        }
+        sequence = sequence.wrapping_add(1);
+
+        Timer::after(Duration::from_millis(NAVIGATION_PERIOD_MS)).await;
+    }
+}
+
+/// Autonomy task
+///
+/// Evaluates the enabled autonomy rules every period and carries out the
+/// actions of those whose triggers have come to hold. Autonomy acts on
+/// nominal telemetry only; faults are left to FDIR.
+/// REQ-FN-005: Routine operations automated between contacts
+#[embassy_executor::task]
+async fn autonomy_executor() {
+    loop {
+        autonomy::evaluate().await;
+        Timer::after(Duration::from_millis(AUTONOMY_PERIOD_MS)).await;
+    }
+}
+
+/// Transceiver lock monitor task
+///
+/// Samples every transceiver's carrier lock and runs the lock-loss recovery
+/// ladder: re-tune, power cycle, then disable the transceiver and report a
+/// communication fault to FDIR. Counters reach the ground via RF housekeeping.
+/// REQ-NF-004: Fault tolerance through automatic recovery
+#[embassy_executor::task]
+async fn transceiver_lock_monitor() {
+    loop {
+        let now_ms = embassy_time::Instant::now().as_millis();
+
+        for (band, step) in hardware::monitor_transceiver_lock(now_ms).await {
+            match step {
+                LockRecoveryStep::Retune => {
+                    error_handling::log_warning("Transceiver lock lost, re-tuning");
+                }
+                LockRecoveryStep::PowerCycle => {
+                    error_handling::log_warning("Transceiver lock not recovered, power cycling");
+                }
+                LockRecoveryStep::MarkFailed => {
+                    error_handling::log_critical("Transceiver lock recovery exhausted, disabled");
+                    let fault = error_handling::FaultType::Communication {
+                        band: String::from(band_name(band)),
+                        error_code: LOCK_RECOVERY_FAILED_CODE,
+                    };
+                    let action = error_handling::handle_fault(fault);
+                    if error_handling::execute_recovery_action(action).await.is_err() {
+                        error_handling::log_error("Lock recovery FDIR action failed");
+                    }
+                }
+                LockRecoveryStep::Recovered => {
+                    error_handling::log_info("Transceiver lock recovered");
+                }
+            }
+        }
+
+        Timer::after(Duration::from_millis(LOCK_MONITOR_INTERVAL_MS)).await;
+    }
+}
+
+/// Short name of a band for fault reports
+fn band_name(band: BandType) -> &'static str {
+    match band {
+        BandType::UhfBand => "UHF",
+        BandType::SBand => "S-Band",
+        BandType::XBand => "X-Band",
+        BandType::KBand => "K-Band",
+        BandType::KaBand => "Ka-Band",
+    }
+}
+
+/// Collect the minimal safe-mode telemetry set
+///
+/// Battery voltage and temperatures, operational mode, mission phase, last
+/// reset reason and
+/// uplink command counters — enough for the ground to diagnose the fault and
+/// command recovery without powering non-essential sensors.
+async fn collect_safe_mode_telemetry() -> TelemetryData {
+    let mut measurements = Vec::<Measurement, MAX_TELEMETRY_MEASUREMENTS>::new();
+
+    let mut push = |measurement_id: u16,
+                    value: MeasurementValue,
+                    unit: &'static str,
+                    quality: MeasurementQuality| {
+        let _ = measurements.push(Measurement {
+            measurement_id,
+            value,
+            unit,
+            quality,
+        });
+    };
+
+    if let Ok(reading) = hardware::read_voltage_sensor(0).await {
+        push(
+            measurement_ids::BATTERY_VOLTAGE,
+            MeasurementValue::Float(reading.value as f64),
+            reading.units,
+            reading.quality,
+        );
+    }
+    if let Ok(reading) = hardware::read_temperature_sensor(0).await {
+        push(
+            measurement_ids::BATTERY_TEMPERATURE,
+            MeasurementValue::Float(reading.value as f64),
+            reading.units,
+            reading.quality,
+        );
+    }
+    if let Ok(reading) = hardware::read_temperature_sensor(1).await {
+        push(
+            measurement_ids::OBC_TEMPERATURE,
+            MeasurementValue::Float(reading.value as f64),
+            reading.units,
+            reading.quality,
+        );
+    }
+
+    let (accepted, rejected) = mode::uplink_counters();
+    push(
+        measurement_ids::OPERATIONAL_MODE,
+        MeasurementValue::Integer(mode::current_mode() as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+    push(
+        measurement_ids::MISSION_PHASE,
+        MeasurementValue::Integer(mode::current_phase() as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+    push(
+        measurement_ids::LAST_RESET_REASON,
+        MeasurementValue::Integer(mode::last_reset_reason_code() as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+    push(
+        measurement_ids::UPLINK_COMMANDS_ACCEPTED,
+        MeasurementValue::Integer(accepted as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+    push(
+        measurement_ids::UPLINK_COMMANDS_REJECTED,
+        MeasurementValue::Integer(rejected as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+    push(
+        measurement_ids::UPLINK_DUPLICATES_RECEIVED,
+        MeasurementValue::Integer(mode::duplicate_commands() as i64),
+        "",
+        MeasurementQuality::Good,
+    );
+
+    TelemetryData {
+        source: ComponentId::new(0x0001), // Satellite system ID
+        timestamp: get_system_time_ns(),
+        measurements,
+        health_status: get_system_health(),
     }
 }
 
@@ -251,7 +935,7 @@ async fn collect_telemetry_data() -> TelemetryData {
                 measurement_id: 0x0001 + sensor_id,
                 value: space_comms_shared::telemetry::MeasurementValue::Float(reading.value),
                 unit: reading.units,
-                quality: space_comms_shared::telemetry::MeasurementQuality::Good,
+                quality: reading.quality,
             };
             let _ = measurements.push(measurement);
         }
@@ -264,7 +948,7 @@ async fn collect_telemetry_data() -> TelemetryData {
                 measurement_id: 0x0010 + sensor_id,
                 value: space_comms_shared::telemetry::MeasurementValue::Float(reading.value),
                 unit: reading.units,
-                quality: space_comms_shared::telemetry::MeasurementQuality::Good,
+                quality: reading.quality,
             };
             let _ = measurements.push(measurement);
         }
@@ -277,33 +961,265 @@ async fn collect_telemetry_data() -> TelemetryData {
                 measurement_id: 0x0020 + sensor_id,
                 value: space_comms_shared::telemetry::MeasurementValue::Float(reading.value),
                 unit: reading.units,
-                quality: space_comms_shared::telemetry::MeasurementQuality::Good,
+                quality: reading.quality,
             };
             let _ = measurements.push(measurement);
         }
     }
 
-    TelemetryData {
+    // Virtual channel security policies (REQ-SC-001)
+    for (vc, policy) in communication::security_policies().iter() {
+        let _ = measurements.push(Measurement {
+            measurement_id: measurement_ids::VC_SECURITY_POLICY_BASE + vc as u16,
+            value: MeasurementValue::Integer(policy.report_code() as i64),
+            unit: "",
+            quality: MeasurementQuality::Good,
+        });
+    }
+
+    // Mission phase (REQ-FN-004)
+    let _ = measurements.push(Measurement {
+        measurement_id: measurement_ids::MISSION_PHASE,
+        value: MeasurementValue::Integer(mode::current_phase() as i64),
+        unit: "",
+        quality: MeasurementQuality::Good,
+    });
+
+    // Retransmitted commands discarded by the command processor (REQ-SF-001)
+    let _ = measurements.push(Measurement {
+        measurement_id: measurement_ids::UPLINK_DUPLICATES_RECEIVED,
+        value: MeasurementValue::Integer(mode::duplicate_commands() as i64),
+        unit: "",
+        quality: MeasurementQuality::Good,
+    });
+
+    let mut data = TelemetryData {
         source: ComponentId::new(0x0001), // Satellite system ID
         timestamp: get_system_time_ns(),
         measurements,
         health_status: get_system_health(),
+    };
+
+    // Commands and actions refused by flight rules (REQ-SF-001)
+    let _ = data.measurements.push(Measurement {
+        measurement_id: measurement_ids::FLIGHT_RULE_VIOLATIONS,
+        value: MeasurementValue::Integer(flight_rules::violations() as i64),
+        unit: "",
+        quality: MeasurementQuality::Good,
+    });
+
+    // Autonomy rule executions (REQ-FN-005)
+    let _ = data.measurements.push(Measurement {
+        measurement_id: measurement_ids::AUTONOMY_EXECUTIONS,
+        value: MeasurementValue::Integer(autonomy::executions() as i64),
+        unit: "",
+        quality: MeasurementQuality::Good,
+    });
+
+    // Priority inversion counters (REQ-FN-010)
+    if inversion::counters().append_measurements(&mut data).is_err() {
+        error_handling::log_error("Priority inversion counters overflow telemetry frame");
     }
+
+    // Telemetry queue drop counters per class (REQ-FN-007)
+    if telemetry_queue::counters().append_measurements(&mut data).is_err() {
+        error_handling::log_error("Telemetry drop counters overflow telemetry frame");
+    }
+
+    data
 }
 
 /// Command processor task
 ///
-/// Processes incoming commands from ground stations.
+/// Processes incoming commands from ground stations. A retransmitted copy of
+/// a command already accepted is counted and discarded without executing or
+/// reporting it again. A sequence count behind the last one accepted on its
+/// APID, or too far ahead of it, is rejected as stale or replayed. A command
+/// outside the mission phase's command set, or that would break a flight
+/// rule, is rejected and reported without executing. A phase command switches
+/// the mission phase. Uploaded autonomy rules are loaded, disabled, rather
+/// than executed. Command loads go to the onboard scheduler, which answers
+/// with a manifest of the entries it accepted, and retransmission requests
+/// to the recorder.
+/// REQ-SF-001: Command Validation - Each command executed at most once
 #[embassy_executor::task]
 async fn command_processor() {
-    let receiver = COMMAND_CHANNEL.receiver();
+    let mut duplicates: DuplicateFilter<DEDUP_CAPACITY> = DuplicateFilter::default();
+    let mut sequences: SequenceWindows<UPLINK_SEQUENCE_APIDS> = SequenceWindows::default();
+    for (apid, window) in UPLINK_SEQUENCE_WINDOWS {
+        if sequences.set_window(apid, window).is_err() {
+            error_handling::log_error("Uplink sequence window not configured");
+        }
+    }
 
     loop {
-        if let Ok(packet) = receiver.receive().await {
-            if let Err(e) = command::process_command_packet(&packet).await {
-                error_handling::log_error("Command processing failed", &e);
+        let communication::UplinkedCommand {
+            packet,
+            auth_counter,
+        } = dequeue_command().await;
+        let started = Instant::now();
+
+        let key = CommandKey::new(
+            packet.header.apid,
+            u64::from(packet.header.sequence_count),
+            auth_counter,
+        );
+        if !duplicates.accept(key, started.as_millis()) {
+            mode::record_command_duplicate();
+            error_handling::log_info("Duplicate command discarded");
+            continue;
+        }
+
+        // Two consecutive counts outside the window follow a ground restart
+        match sequences.check(packet.header.apid, packet.header.sequence_count) {
+            Ok(verdict) if !verdict.is_accepted() => {
+                mode::record_command_rejected();
+                error_handling::log_warning("Command sequence count outside window");
+                continue;
             }
+            Ok(_) => {}
+            Err(_) => error_handling::log_warning("Uplink sequence windows full"),
         }
+
+        // Link forecasts steer the recorder downlink rather than executing
+        if packet.header.apid == LINK_FORECAST_APID {
+            match downlink_plan::accept_forecast(&packet.data) {
+                Ok(()) => mode::record_command_accepted(),
+                Err(_) => {
+                    mode::record_command_rejected();
+                    error_handling::log_error("Link forecast rejected");
+                }
+            }
+            continue;
+        }
+
+        // Command loads are committed to the scheduler and acknowledged
+        // with their manifest
+        if packet.header.apid == COMMAND_LOAD_APID {
+            match command_schedule::accept_load(&packet.data) {
+                Ok(manifest) => {
+                    mode::record_command_accepted();
+                    if communication::transmit_load_manifest(&manifest).await.is_err() {
+                        error_handling::log_error("Load manifest transmission failed");
+                    }
+                }
+                Err(_) => {
+                    mode::record_command_rejected();
+                    error_handling::log_error("Command load rejected");
+                }
+            }
+            continue;
+        }
+
+        // Retransmission requests re-queue recorder files for playback
+        if packet.header.apid == RETRANSMIT_REQUEST_APID {
+            match recorder::accept_retransmit(&packet.data) {
+                Ok(()) => mode::record_command_accepted(),
+                Err(_) => {
+                    mode::record_command_rejected();
+                    error_handling::log_error("Retransmission request rejected");
+                }
+            }
+            continue;
+        }
+
+        // Autonomy rules are loaded disabled rather than executing
+        if packet.header.apid == AUTONOMY_RULE_APID {
+            match autonomy::accept_rule(&packet.data) {
+                Ok(()) => mode::record_command_accepted(),
+                Err(_) => {
+                    mode::record_command_rejected();
+                    error_handling::log_error("Autonomy rule rejected");
+                }
+            }
+            continue;
+        }
+
+        execute_command(&packet, started).await;
+    }
+}
+
+/// Check and dispatch an uplinked or released command, then report its
+/// execution
+///
+/// Commands outside the mission phase's command set, or that would break a
+/// flight rule, are rejected. Commands that are not recognised here go to the
+/// command dispatcher.
+/// REQ-SF-001: Command Validation - Phase and flight-rule checks
+/// REQ-PF-001: Command Response Time - Per-command execution report
+async fn execute_command(packet: &SpacePacket, started: Instant) {
+    let priority = MessagePriority::from_command_apid(packet.header.apid);
+    inversion::enter(
+        Section::CommandProcessor,
+        priority.unwrap_or(MessagePriority::Medium),
+    );
+    let checked = match mode::current_phase().check_command_data(&packet.data) {
+        Ok(()) => match flight_rules::check_command(&packet.data).await {
+            Ok(()) => communication::check_frequency(&packet.data),
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+    let outcome = match checked {
+        // Commands outside the mission phase's command set, that would
+        // break a flight rule or tune outside the licensed frequencies
+        // are never dispatched
+        Err(e) => Err(e),
+        // Phase changes apply from the next command on
+        Ok(()) => match MissionPhase::from_command_data(&packet.data) {
+            Some(phase) => phase.map(|phase| {
+                mode::enter_phase(phase);
+            }),
+            None => match (
+                DiagnosticRequest::from_command_data(&packet.data),
+                LoopbackRequest::from_command_data(&packet.data),
+                KeyRotation::from_command_data(&packet.data),
+                AutonomySwitch::from_command_data(&packet.data),
+                FileRequest::from_command_data(&packet.data),
+                DownlinkPolicy::from_command_data(&packet.data),
+            ) {
+                // Memory dumps and dwells run in the diagnostics task
+                (Some(request), _, _, _, _, _) => request.and_then(diagnostics::start),
+                // Loopback tests are echoed at once, stamped with their arrival
+                (None, Some(Ok(request)), _, _, _, _) => {
+                    communication::transmit_loopback_echo(&request, started.as_millis()).await
+                }
+                (None, Some(Err(e)), _, _, _, _) => Err(e),
+                // Key rotations apply from the next frame on
+                (None, None, Some(rotation), _, _, _) => {
+                    rotation.and_then(|rotation| communication::rotate_link_keys(&rotation))
+                }
+                // Autonomy rules start or stop at the next evaluation
+                (None, None, None, Some(switch), _, _) => {
+                    switch.and_then(|switch| autonomy::switch_rule(&switch))
+                }
+                // File system commands act on the mass memory at once
+                (None, None, None, None, Some(request), _) => {
+                    request.and_then(mass_memory::execute)
+                }
+                // Downlink policies apply from the next contact planned
+                (None, None, None, None, None, Some(policy)) => {
+                    policy.map(downlink_plan::set_policy)
+                }
+                (None, None, None, None, None, None) => {
+                    command::process_command_packet(packet).await.map(|_| ())
+                }
+            },
+        },
+    };
+    inversion::exit(Section::CommandProcessor);
+    match &outcome {
+        Ok(_) => mode::record_command_accepted(),
+        Err(_) => {
+            mode::record_command_rejected();
+            error_handling::log_error("Command processing failed");
+        }
+    }
+
+    // Command loads and retransmission requests are acknowledged separately
+    if let (Some(priority), Some(command_id)) = (priority, wire::command_id(&packet.data)) {
+        let sequence_count = packet.header.sequence_count;
+        report_execution(command_id, sequence_count, priority, &outcome, started).await;
     }
 }
 
@@ -312,43 +1228,94 @@ async fn command_processor() {
 /// Handles communication across different frequency bands.
 #[embassy_executor::task]
 async fn communication_manager() {
-    let telemetry_receiver = TELEMETRY_CHANNEL.receiver();
-
     loop {
+        inversion::enter(Section::CommunicationManager, MessagePriority::Medium);
+
         // Process telemetry transmission
-        if let Ok(packet) = telemetry_receiver.try_receive() {
+        if let Some(packet) = telemetry_queue::pop() {
             if let Err(e) = communication::transmit_telemetry(&packet).await {
                 error_handling::log_error("Telemetry transmission failed", &e);
             }
         }
 
+        // Send science and playback frames whose flush delay has passed
+        if communication::service_downlink().await.is_err() {
+            error_handling::log_error("Downlink frame transmission failed");
+        }
+
+        // Plan the recorder downlink as each forecast contact starts
+        if let Some(plan) = downlink_plan::plan_new_contact() {
+            error_handling::log_info("Recorder downlink planned for forecast contact");
+            recorder::start_pass(&plan);
+            autonomy::start_requested_downlink().await;
+        }
+
+        // Play back the next recorder file; each one finished updates the
+        // pass manifest the ground requests retransmissions against
+        if let Some(manifest) = recorder::play_next() {
+            if communication::transmit_file_manifest(&manifest).await.is_err() {
+                error_handling::log_error("File manifest transmission failed");
+            }
+        }
+
         // Listen for incoming commands
-        if let Ok(command_packet) = communication::receive_command().await {
-            let sender = COMMAND_CHANNEL.sender();
-            if let Err(_) = sender.try_send(command_packet) {
+        // REQ-SF-001: FARM-1 waits rather than accept a command it must drop
+        let buffer_available = QUEUED_COMMANDS.lock(Cell::get) < COMMAND_CHANNEL_DEPTH;
+        if let Ok(command_packet) = communication::receive_command(buffer_available).await {
+            if !queue_command(command_packet) {
                 error_handling::log_warning("Command channel full, dropping command");
             }
         }
 
+        inversion::exit(Section::CommunicationManager);
+
         Timer::after(Duration::from_millis(10)).await;
     }
 }
 
+/// Command scheduler task
+///
+/// Releases the entries of uplinked command loads as their release time
+/// arrives and executes them as if they had just been uplinked.
+/// REQ-FN-005: Medium Priority Commands - Time-tagged command execution
+#[embassy_executor::task]
+async fn command_scheduler() {
+    loop {
+        while let Some(entry) = command_schedule::pop_due() {
+            let started = Instant::now();
+            match entry.to_command_packet() {
+                Ok(packet) => execute_command(&packet, started).await,
+                Err(_) => {
+                    mode::record_command_rejected();
+                    error_handling::log_error("Scheduled command not encoded");
+                }
+            }
+        }
+
+        Timer::after(Duration::from_millis(SCHEDULER_TICK_MS)).await;
+    }
+}
+
 /// System health monitor task
 ///
-/// Monitors system health and updates global health status.
+/// Monitors system health and updates global health status, and moves the
+/// downlink off a band that a flight rule no longer allows to transmit.
 #[embassy_executor::task]
 async fn health_monitor() {
     loop {
         let health = assess_system_health().await;
+        flight_rules::monitor_downlink().await;
 
-        unsafe {
-            SYSTEM_HEALTH = health;
-        }
+        set_system_health(health);
 
         // If health is critical, trigger emergency procedures
         if health == HealthStatus::Critical {
             emergency_procedures().await;
+        } else if mode::current_mode() == OperationalMode::Safe
+            && matches!(health, HealthStatus::Excellent | HealthStatus::Good)
+        {
+            // Health recovered - leave safe mode and restore full telemetry
+            let _ = exit_safe_mode().await;
         }
 
         Timer::after(Duration::from_secs(5)).await;
@@ -361,6 +1328,8 @@ async fn health_monitor() {
 #[embassy_executor::task]
 async fn housekeeping_task() {
     loop {
+        inversion::enter(Section::Housekeeping, MessagePriority::Low);
+
         // Perform routine maintenance
         maintenance_operations().await;
 
@@ -370,6 +1339,8 @@ async fn housekeeping_task() {
         // Update system statistics
         update_system_statistics().await;
 
+        inversion::exit(Section::Housekeeping);
+
         Timer::after(Duration::from_secs(60)).await;
     }
 }
@@ -454,18 +1425,41 @@ async fn assess_system_health() -> HealthStatus {
 
 /// Get current system health status
 fn get_system_health() -> HealthStatus {
-    unsafe { SYSTEM_HEALTH }
+    SYSTEM_HEALTH.lock(Cell::get)
+}
+
+/// Set current system health status
+fn set_system_health(health: HealthStatus) {
+    SYSTEM_HEALTH.lock(|status| status.set(health));
+}
+
+/// Queue an opened command for the command processor; false when full
+fn queue_command(command: communication::UplinkedCommand) -> bool {
+    QUEUED_COMMANDS.lock(|queued| {
+        let sent = COMMAND_CHANNEL.try_send(command).is_ok();
+        if sent {
+            queued.set(queued.get() + 1);
+        }
+        sent
+    })
+}
+
+/// Take the next queued command off the command channel
+async fn dequeue_command() -> communication::UplinkedCommand {
+    let command = COMMAND_CHANNEL.receive().await;
+    QUEUED_COMMANDS.lock(|queued| queued.set(queued.get().saturating_sub(1)));
+    command
 }
 
 /// Get system time in nanoseconds (placeholder implementation)
 fn get_system_time_ns() -> u64 {
     // TODO: Implement proper time synchronization
     // For now, use a simple counter
-    static mut TIME_COUNTER: u64 = 0;
-    unsafe {
-        TIME_COUNTER += 1_000_000; // Increment by 1ms
-        TIME_COUNTER
-    }
+    static TIME_COUNTER: Mutex<CriticalSectionRawMutex, Cell<u64>> = Mutex::new(Cell::new(0));
+    TIME_COUNTER.lock(|counter| {
+        counter.set(counter.get() + 1_000_000); // Increment by 1ms
+        counter.get()
+    })
 }
 
 /// Activate safe mode
@@ -481,6 +1475,20 @@ async fn activate_safe_mode() -> Result<()> {
     // Use only S-band for communication
     communication::set_emergency_mode(BandType::SBand).await?;
 
+    // Telemetry collector follows the mode manager to the safe-mode set
+    mode::enter_mode(OperationalMode::Safe);
+
+    Ok(())
+}
+
+/// Exit safe mode and resume normal operations
+///
+/// Restores the full telemetry set at the nominal rate.
+async fn exit_safe_mode() -> Result<()> {
+    error_handling::log_info("Exiting safe mode");
+
+    mode::enter_mode(OperationalMode::Normal);
+
     Ok(())
 }
 
@@ -489,7 +1497,8 @@ async fn reduce_functionality() -> Result<()> {
     error_handling::log_warning("Reducing system functionality");
 
     // Reduce telemetry frequency
-    // Switch to backup communication band
+    // Switch to backup communication band, unless a flight rule forbids it
+    flight_rules::check_action(Activity::Transmit(BandType::UhfBand)).await?;
     communication::switch_to_backup_band().await?;
 
     Ok(())
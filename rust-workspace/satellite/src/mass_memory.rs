@@ -0,0 +1,264 @@
+//! Mass memory file system
+//!
+//! Holds the onboard mass memory: one directory per data type, each with a
+//! byte quota and an optional retention. The command processor hands
+//! `StoreData`, `ListFiles`, `DeleteFile` and `SetRetention` commands here
+//! instead of to the command dispatcher; they are still reported like any
+//! other command. Every file written is registered with the recorder
+//! downlink planner under its file ID, and released from it again when the
+//! file is deleted or expires, so the planner only ever schedules files that
+//! exist; the recorder releases it once it has been played back. A
+//! requested listing is held until the diagnostics task downlinks it;
+//! expired files are removed by the same task once a second.
+//!
+//! Payload products are simulated: each data type is written at a fixed
+//! size, so the fill level and quotas behave as they would in flight.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (file storage, listing, deletion
+//!   and retention)
+//! - REQ-NF-002: Memory Constraints (per-directory quotas, fixed file table)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    commands::DataType,
+    file_downlink::{FileCrc, FileEntry},
+    mass_memory::{FileListing, FileRequest, MassMemory},
+    Result,
+};
+
+use crate::downlink_plan;
+use crate::error_handling::{self, LogLevel};
+
+/// Files the mass memory keeps track of
+const MAX_FILES: usize = 64;
+
+/// Simulated product contents are read back this many bytes at a time
+const READ_BLOCK_LEN: usize = 256;
+
+/// Event code logged when a file is removed at the end of its retention
+const FILE_EXPIRED_CODE: u32 = 440;
+
+/// File system, the listing waiting for downlink and the last second expiry
+/// ran in
+struct Storage {
+    memory: MassMemory<MAX_FILES>,
+    listing: Option<FileListing>,
+    expired_s: u64,
+}
+
+static STORAGE: Mutex<CriticalSectionRawMutex, RefCell<Storage>> =
+    Mutex::new(RefCell::new(Storage {
+        memory: MassMemory::new(),
+        listing: None,
+        expired_s: 0,
+    }));
+
+/// Current time, mission seconds
+fn mission_seconds() -> u64 {
+    Instant::now().as_secs()
+}
+
+/// Size of a simulated product of `data_type`, bytes
+const fn product_size(data_type: DataType) -> u32 {
+    match data_type {
+        DataType::Telemetry => 64 * 1024,
+        DataType::Science => 2 * 1024 * 1024,
+        DataType::Images => 4 * 1024 * 1024,
+        DataType::Logs => 32 * 1024,
+        DataType::Configuration => 4 * 1024,
+        DataType::Diagnostic => 128 * 1024,
+    }
+}
+
+/// Downlink priority of a product of `data_type`, 0 lowest
+const fn downlink_priority(data_type: DataType) -> u8 {
+    match data_type {
+        DataType::Configuration | DataType::Diagnostic => 3,
+        DataType::Telemetry | DataType::Logs => 2,
+        DataType::Science => 1,
+        DataType::Images => 0,
+    }
+}
+
+/// Carry out a file system command
+///
+/// Requirements Fulfilled:
+/// - REQ-FN-005: File storage, listing, deletion and retention by command
+pub fn execute(request: FileRequest) -> Result<()> {
+    match request {
+        FileRequest::Store {
+            data_type,
+            location,
+            science_value,
+        } => {
+            let size_bytes = product_size(data_type);
+            let file_id = STORAGE.lock(|storage| {
+                storage.borrow_mut().memory.store(
+                    data_type,
+                    size_bytes,
+                    location,
+                    science_value,
+                    mission_seconds(),
+                )
+            })?;
+            // The file stays listed and deletable even if the planner is full
+            let priority = downlink_priority(data_type);
+            if downlink_plan::store(file_id, size_bytes, priority, science_value).is_err() {
+                error_handling::log_warning("Stored file not scheduled for downlink");
+            }
+            Ok(())
+        }
+        FileRequest::List(data_type) => {
+            STORAGE.lock(|storage| {
+                let mut storage = storage.borrow_mut();
+                storage.listing = Some(storage.memory.listing(data_type));
+            });
+            Ok(())
+        }
+        FileRequest::Delete { data_type, file_id } => {
+            STORAGE.lock(|storage| storage.borrow_mut().memory.delete(data_type, file_id))?;
+            downlink_plan::complete(file_id);
+            Ok(())
+        }
+        FileRequest::SetRetention {
+            data_type,
+            retention_s,
+        } => {
+            STORAGE.lock(|storage| {
+                storage
+                    .borrow_mut()
+                    .memory
+                    .set_retention(data_type, retention_s)
+            });
+            Ok(())
+        }
+    }
+}
+
+/// Take the listing waiting for downlink
+pub fn take_listing() -> Option<FileListing> {
+    STORAGE.lock(|storage| storage.borrow_mut().listing.take())
+}
+
+/// Manifest entry of a stored file, `None` once it is deleted or expired
+///
+/// The CRC is computed outside the critical section, a block at a time.
+/// Simulated products read back as zeros.
+pub fn manifest_entry(file_id: u32) -> Option<FileEntry> {
+    let size_bytes = STORAGE.lock(|storage| {
+        storage
+            .borrow()
+            .memory
+            .file(file_id)
+            .map(|file| file.size_bytes)
+    })?;
+
+    let block = [0u8; READ_BLOCK_LEN];
+    let mut crc = FileCrc::new();
+    let mut remaining = size_bytes as usize;
+    while remaining > 0 {
+        let len = remaining.min(READ_BLOCK_LEN);
+        crc.update(&block[..len]);
+        remaining -= len;
+    }
+
+    Some(FileEntry {
+        file_id,
+        size_bytes,
+        crc: crc.value(),
+    })
+}
+
+/// Remove the files past their directory's retention
+///
+/// Runs at most once per mission second; each file removed is released from
+/// the downlink planner and logged.
+///
+/// Requirements Fulfilled:
+/// - REQ-NF-002: Mass memory reclaimed at the end of each file's retention
+pub fn expire() {
+    let now_s = mission_seconds();
+    let expired = STORAGE.lock(|storage| {
+        let mut storage = storage.borrow_mut();
+        if storage.expired_s == now_s {
+            return None;
+        }
+        storage.expired_s = now_s;
+        Some(storage.memory.expire(now_s))
+    });
+
+    for file in expired.iter().flatten() {
+        downlink_plan::complete(file.file_id);
+        error_handling::log_with_component(
+            LogLevel::Info,
+            "Retention expired, file removed",
+            "MASS_MEMORY",
+            Some(FILE_EXPIRED_CODE),
+        );
+    }
+}
+
+/// Mass memory fill, percent of its capacity
+pub fn fill_percent() -> f32 {
+    STORAGE.lock(|storage| storage.borrow().memory.fill_percent())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::commands::StorageLocation;
+    use space_comms_shared::mass_memory::DEFAULT_QUOTAS;
+    use space_comms_shared::SpaceCommError;
+
+    fn store(data_type: DataType) -> Result<()> {
+        execute(FileRequest::Store {
+            data_type,
+            location: StorageLocation::NonVolatileMemory,
+            science_value: 2,
+        })
+    }
+
+    #[test]
+    fn test_directory_quota_enforced() -> Result<()> {
+        let images_quota = DEFAULT_QUOTAS[DataType::Images as usize];
+        let fit = images_quota / product_size(DataType::Images);
+        for _ in 0..fit {
+            store(DataType::Images)?;
+        }
+
+        // The full directory refuses another product; the others still accept
+        let refused = store(DataType::Images);
+        assert!(matches!(
+            refused,
+            Err(SpaceCommError::ResourceExhausted {
+                resource: "images",
+                ..
+            })
+        ));
+        store(DataType::Configuration)?;
+
+        // Deleting a file makes room for one more
+        let newest = STORAGE.lock(|storage| {
+            storage
+                .borrow()
+                .memory
+                .list(DataType::Images)
+                .last()
+                .map(|file| file.file_id)
+        });
+        let file_id = newest.ok_or(SpaceCommError::invalid_packet("No image stored", None))?;
+        execute(FileRequest::Delete {
+            data_type: DataType::Images,
+            file_id,
+        })?;
+        assert!(manifest_entry(file_id).is_none());
+        store(DataType::Images)?;
+        assert!(store(DataType::Images).is_err());
+        Ok(())
+    }
+}
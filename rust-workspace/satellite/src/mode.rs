@@ -0,0 +1,153 @@
+//! Operational mode manager
+//!
+//! Tracks the spacecraft operational mode and mission phase together with
+//! the small set of counters that must survive into safe-mode telemetry: the
+//! cause of the last processor reset and the uplink command
+//! accept/reject/duplicate counts. State is kept in atomics so any task can
+//! read it without locking.
+//!
+//! The mission phase starts in LEOP at every boot; after a processor reset
+//! later in the mission the ground commands the phase again.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-002: Emergency Command Set (safe mode entry and exit)
+//! - REQ-FN-004: High Priority Operations (ground-commanded mission phase)
+//! - REQ-NF-004: Fault Tolerance (mode-dependent telemetry selection)
+
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+
+use space_comms_shared::{
+    mission_phase::{MissionPhase, PhasePolicy},
+    telemetry::TelemetrySet,
+    types::OperationalMode,
+};
+
+use crate::error_handling;
+
+/// Cause of the most recent processor reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ResetReason {
+    /// Cold power-on
+    PowerOn = 0,
+    /// Watchdog expiry
+    Watchdog = 1,
+    /// Reset commanded from the ground
+    Commanded = 2,
+    /// Brown-out detected on the supply rail
+    BrownOut = 3,
+    /// Unrecoverable software fault
+    SoftwareFault = 4,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(OperationalMode::Normal as u8);
+static PHASE: AtomicU8 = AtomicU8::new(MissionPhase::Leop as u8);
+static LAST_RESET_REASON: AtomicU8 = AtomicU8::new(ResetReason::PowerOn as u8);
+static COMMANDS_ACCEPTED: AtomicU32 = AtomicU32::new(0);
+static COMMANDS_REJECTED: AtomicU32 = AtomicU32::new(0);
+static COMMANDS_DUPLICATE: AtomicU32 = AtomicU32::new(0);
+
+/// Current operational mode
+pub fn current_mode() -> OperationalMode {
+    match MODE.load(Ordering::Acquire) {
+        1 => OperationalMode::Degraded,
+        2 => OperationalMode::Emergency,
+        3 => OperationalMode::Maintenance,
+        4 => OperationalMode::Safe,
+        _ => OperationalMode::Normal,
+    }
+}
+
+/// Switch to a new operational mode
+///
+/// Returns the previous mode. Mode changes are logged because they alter the
+/// telemetry set and rate seen by the ground.
+pub fn enter_mode(mode: OperationalMode) -> OperationalMode {
+    let previous = current_mode();
+    MODE.store(mode as u8, Ordering::Release);
+
+    if previous != mode {
+        error_handling::log_info(match mode {
+            OperationalMode::Safe => "Mode manager: entered safe mode",
+            OperationalMode::Emergency => "Mode manager: entered emergency mode",
+            OperationalMode::Normal => "Mode manager: returned to normal mode",
+            _ => "Mode manager: mode changed",
+        });
+    }
+
+    previous
+}
+
+/// Telemetry set the collector should produce in the current mode
+pub fn telemetry_set() -> TelemetrySet {
+    TelemetrySet::for_mode(current_mode())
+}
+
+/// Current mission phase
+pub fn current_phase() -> MissionPhase {
+    MissionPhase::from_code(PHASE.load(Ordering::Acquire)).unwrap_or(MissionPhase::Leop)
+}
+
+/// Policy of the current mission phase
+pub fn phase_policy() -> PhasePolicy {
+    current_phase().policy()
+}
+
+/// Switch to a new mission phase
+///
+/// Returns the previous phase. Phase changes are logged because they alter
+/// the telemetry rate, the commands accepted and how early FDIR escalates.
+pub fn enter_phase(phase: MissionPhase) -> MissionPhase {
+    let previous = current_phase();
+    PHASE.store(phase as u8, Ordering::Release);
+
+    if previous != phase {
+        error_handling::log_info(match phase {
+            MissionPhase::Leop => "Mode manager: entered LEOP phase",
+            MissionPhase::Commissioning => "Mode manager: entered commissioning phase",
+            MissionPhase::NominalOps => "Mode manager: entered nominal operations phase",
+            MissionPhase::Extended => "Mode manager: entered extended operations phase",
+            MissionPhase::Decommissioning => "Mode manager: entered decommissioning phase",
+        });
+    }
+
+    previous
+}
+
+/// Record the reset cause reported by the boot loader
+pub fn set_last_reset_reason(reason: ResetReason) {
+    LAST_RESET_REASON.store(reason as u8, Ordering::Release);
+}
+
+/// Cause of the most recent reset, as its telemetry code
+pub fn last_reset_reason_code() -> u8 {
+    LAST_RESET_REASON.load(Ordering::Acquire)
+}
+
+/// Count an uplinked command that passed validation
+pub fn record_command_accepted() {
+    COMMANDS_ACCEPTED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Count an uplinked command that was rejected
+pub fn record_command_rejected() {
+    COMMANDS_REJECTED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Count an uplinked command discarded as a retransmitted duplicate
+pub fn record_command_duplicate() {
+    COMMANDS_DUPLICATE.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Uplinked commands discarded as duplicates since boot
+pub fn duplicate_commands() -> u32 {
+    COMMANDS_DUPLICATE.load(Ordering::Relaxed)
+}
+
+/// Uplink counters as (accepted, rejected)
+pub fn uplink_counters() -> (u32, u32) {
+    (
+        COMMANDS_ACCEPTED.load(Ordering::Relaxed),
+        COMMANDS_REJECTED.load(Ordering::Relaxed),
+    )
+}
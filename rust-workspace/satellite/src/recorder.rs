@@ -0,0 +1,245 @@
+//! Recorder playback, file manifests and selective retransmission
+//!
+//! When the downlink planner plans a forecast contact, the communication
+//! manager hands the plan here and its files are played back one per cycle.
+//! As each file finishes it is released from the planner and added to the
+//! manifest of the pass, and the manifest so far is returned for downlink on
+//! the file manifest APID. The ground answers with a retransmission request
+//! naming the files or byte ranges it did not receive intact; the command
+//! processor hands it here and, once checked against the manifest of the pass
+//! it names, its items are played back ahead of the rest of the recorder.
+//!
+//! Payload products are simulated, as in the mass memory: playback is logged
+//! rather than transmitted.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (data storage and management)
+//! - REQ-NF-004: Fault Tolerance (recovery of partially received products)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use heapless::Deque;
+
+use space_comms_shared::{
+    error::MemoryErrorType,
+    file_downlink::{
+        FileManifest, RetransmitItem, RetransmitRequest, MAX_MANIFEST_FILES, MAX_RETRANSMIT_ITEMS,
+    },
+    link_forecast::ContactPlan,
+    Result, SpaceCommError,
+};
+
+use crate::downlink_plan;
+use crate::error_handling::{self, LogLevel};
+use crate::mass_memory;
+
+/// Event code logged when a requested retransmission is played back
+const RETRANSMISSION_CODE: u32 = 460;
+
+/// Pass being played back, the files of it still to play, the manifest of
+/// the last pass and the retransmissions waiting
+struct Recorder {
+    pass: Option<FileManifest>,
+    pass_files: Deque<u32, MAX_MANIFEST_FILES>,
+    last_pass: Option<FileManifest>,
+    retransmissions: Deque<RetransmitItem, MAX_RETRANSMIT_ITEMS>,
+}
+
+static RECORDER: Mutex<CriticalSectionRawMutex, RefCell<Recorder>> =
+    Mutex::new(RefCell::new(Recorder {
+        pass: None,
+        pass_files: Deque::new(),
+        last_pass: None,
+        retransmissions: Deque::new(),
+    }));
+
+/// What the next playback cycle plays
+enum Playback {
+    Retransmission(RetransmitItem),
+    PassFile(u32),
+}
+
+/// Start playing back the files planned for a contact
+///
+/// The contact is the pass the manifest names. Files of an earlier pass not
+/// yet played stay with the planner for a later contact.
+pub fn start_pass(plan: &ContactPlan) {
+    let Some(pass_id) = plan.contact_id else {
+        return;
+    };
+
+    RECORDER.lock(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.pass = Some(FileManifest::new(pass_id));
+        recorder.pass_files.clear();
+        for &file_id in plan.files.iter() {
+            // Same capacity as the plan's file list, so this cannot overflow
+            let _ = recorder.pass_files.push_back(file_id);
+        }
+    });
+}
+
+/// Play back the next retransmission or file of the pass
+///
+/// Returns the manifest of the pass, for downlink, when a file of it has
+/// finished. Files deleted since they were planned are skipped.
+pub fn play_next() -> Option<FileManifest> {
+    let next = RECORDER.lock(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        match recorder.retransmissions.pop_front() {
+            Some(item) => Some(Playback::Retransmission(item)),
+            None => recorder.pass_files.pop_front().map(Playback::PassFile),
+        }
+    })?;
+
+    match next {
+        Playback::Retransmission(item) => {
+            error_handling::log_with_component(
+                LogLevel::Info,
+                if item.range.is_some() {
+                    "Byte range retransmitted"
+                } else {
+                    "File retransmitted"
+                },
+                "RECORDER",
+                Some(RETRANSMISSION_CODE),
+            );
+            None
+        }
+        Playback::PassFile(file_id) => {
+            let entry = mass_memory::manifest_entry(file_id)?;
+            downlink_plan::complete(file_id);
+            RECORDER.lock(|recorder| {
+                let mut recorder = recorder.borrow_mut();
+                let manifest = recorder.pass.as_mut()?;
+                // A pass holds at most as many files as a manifest describes
+                let _ = manifest.push(entry);
+                let manifest = manifest.clone();
+                recorder.last_pass = Some(manifest.clone());
+                Some(manifest)
+            })
+        }
+    }
+}
+
+/// Accept an uplinked retransmission request from a packet payload
+///
+/// The request must name the last pass downlinked, and every file and range
+/// in it must be in that pass's manifest; otherwise, or if it does not fit
+/// the playback queue, it is rejected whole.
+pub fn accept_retransmit(payload: &[u8]) -> Result<()> {
+    let request = RetransmitRequest::from_bytes(payload)?;
+    RECORDER.lock(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        match recorder.last_pass.as_ref() {
+            Some(manifest) => request.validate_against(manifest)?,
+            None => {
+                return Err(SpaceCommError::invalid_packet(
+                    "No pass downlinked yet",
+                    Some(request.pass_id),
+                ))
+            }
+        }
+
+        let free = recorder.retransmissions.capacity() - recorder.retransmissions.len();
+        if request.items.len() > free {
+            return Err(SpaceCommError::memory_error(
+                MemoryErrorType::BufferOverflow,
+                Some(MAX_RETRANSMIT_ITEMS),
+            ));
+        }
+        for item in request.items.iter() {
+            let _ = recorder.retransmissions.push_back(*item);
+        }
+        Ok(())
+    })?;
+
+    error_handling::log_info("Retransmission request accepted");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use space_comms_shared::commands::{DataType, StorageLocation};
+    use space_comms_shared::link_forecast::DownlinkPolicy;
+    use space_comms_shared::mass_memory::FileRequest;
+
+    /// Pass the test contact is planned for
+    const PASS_ID: u32 = 5;
+
+    /// File ID no stored file has
+    const MISSING_FILE_ID: u32 = 9_999;
+
+    /// Store a log file and return its ID from the directory listing
+    fn store_log_file() -> Result<u32> {
+        mass_memory::execute(FileRequest::Store {
+            data_type: DataType::Logs,
+            location: StorageLocation::NonVolatileMemory,
+            science_value: 1,
+        })?;
+        mass_memory::execute(FileRequest::List(DataType::Logs))?;
+        mass_memory::take_listing()
+            .and_then(|listing| listing.files.last().map(|file| file.file_id))
+            .ok_or(SpaceCommError::invalid_packet(
+                "Stored file not listed",
+                None,
+            ))
+    }
+
+    #[test]
+    fn test_pass_manifest_and_retransmission() -> Result<()> {
+        let file_id = store_log_file()?;
+        let mut retransmit = RetransmitRequest::new(PASS_ID);
+        retransmit.request_file(file_id)?;
+
+        // Nothing can be retransmitted before a pass has been downlinked
+        assert!(accept_retransmit(&retransmit.to_bytes()?).is_err());
+
+        let mut plan = ContactPlan {
+            contact_id: Some(PASS_ID),
+            policy: DownlinkPolicy::default(),
+            degraded: false,
+            files: heapless::Vec::new(),
+            planned_bytes: 0,
+            deferred: 0,
+        };
+        let _ = plan.files.push(file_id);
+        let _ = plan.files.push(MISSING_FILE_ID);
+        start_pass(&plan);
+
+        // Each file played adds to the pass manifest; a deleted file is
+        // skipped without one
+        let manifest = play_next().ok_or(SpaceCommError::invalid_packet(
+            "No manifest after the first file",
+            None,
+        ))?;
+        assert_eq!(manifest.pass_id, PASS_ID);
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(
+            manifest.file(file_id),
+            mass_memory::manifest_entry(file_id).as_ref()
+        );
+        assert!(play_next().is_none());
+        assert!(play_next().is_none());
+
+        // Requests must name that pass and only files in its manifest
+        let mut unknown_file = RetransmitRequest::new(PASS_ID);
+        unknown_file.request_file(MISSING_FILE_ID)?;
+        assert!(accept_retransmit(&unknown_file.to_bytes()?).is_err());
+        let mut other_pass = RetransmitRequest::new(PASS_ID + 1);
+        other_pass.request_file(file_id)?;
+        assert!(accept_retransmit(&other_pass.to_bytes()?).is_err());
+
+        accept_retransmit(&retransmit.to_bytes()?)?;
+        let queued = RECORDER.lock(|recorder| recorder.borrow().retransmissions.len());
+        assert_eq!(queued, 1);
+
+        // The retransmission plays ahead of anything else and empties the queue
+        assert!(play_next().is_none());
+        let queued = RECORDER.lock(|recorder| recorder.borrow().retransmissions.len());
+        assert_eq!(queued, 0);
+        Ok(())
+    }
+}
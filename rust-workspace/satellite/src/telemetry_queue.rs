@@ -0,0 +1,80 @@
+//! Telemetry downlink queue
+//!
+//! The telemetry collector queues frames here and the communication manager
+//! drains them. On overflow the oldest housekeeping frame is dropped first;
+//! alarm and event frames are never dropped, and a protected frame that finds
+//! the queue full of protected frames stays with the collector to retry.
+//! A drop rate above the policy raises an onboard event, and the per-class
+//! drop counters are downlinked in housekeeping telemetry. The queue lives
+//! behind a critical-section mutex so both tasks can use it without `unsafe`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: Medium Priority Telemetry (housekeeping yields under link
+//!   congestion)
+//! - REQ-FN-007: Fault Detection (alarm telemetry survives overflow; drop
+//!   rate event)
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{raw::CriticalSectionRawMutex, Mutex};
+use embassy_time::Instant;
+
+use space_comms_shared::{
+    telemetry::TelemetryPacket,
+    telemetry_queue::{DropCounters, DropRatePolicy, TelemetryClass, TelemetryQueue},
+};
+
+use crate::error_handling::{self, LogLevel};
+
+/// Frames buffered between the collector and the communication manager
+const TELEMETRY_QUEUE_DEPTH: usize = 8;
+
+/// Drops tolerated before the onboard event: five in ten seconds, the
+/// `DropRatePolicy` default
+const DROP_RATE_POLICY: DropRatePolicy = DropRatePolicy {
+    window_ms: 10_000,
+    max_drops: 5,
+};
+
+/// Event code logged when the telemetry drop rate exceeds the policy
+const TELEMETRY_DROP_RATE_CODE: u32 = 420;
+
+static QUEUE: Mutex<CriticalSectionRawMutex, RefCell<TelemetryQueue<TELEMETRY_QUEUE_DEPTH>>> =
+    Mutex::new(RefCell::new(TelemetryQueue::new(DROP_RATE_POLICY)));
+
+/// Queue a frame for downlink, classified by its content
+///
+/// Returns a protected frame the queue cannot accept yet so the caller can
+/// retry it; housekeeping frames are always taken.
+pub fn push(packet: TelemetryPacket) -> Option<TelemetryPacket> {
+    let now_ms = Instant::now().as_millis();
+    let class = TelemetryClass::classify(&packet.data);
+    let (refused, rate_exceeded) = QUEUE.lock(|queue| {
+        let mut queue = queue.borrow_mut();
+        if !queue.accepts(class) {
+            return (Some(packet), false);
+        }
+        let _ = queue.push(class, packet, now_ms);
+        (None, queue.take_rate_exceeded(now_ms))
+    });
+
+    if rate_exceeded {
+        error_handling::log_with_component(
+            LogLevel::Warning,
+            "Telemetry drop rate exceeded, housekeeping being shed",
+            "TELEMETRY",
+            Some(TELEMETRY_DROP_RATE_CODE),
+        );
+    }
+    refused
+}
+
+/// Take the next frame for downlink, alarms first
+pub fn pop() -> Option<TelemetryPacket> {
+    QUEUE.lock(|queue| queue.borrow_mut().pop()).map(|(_, packet)| packet)
+}
+
+/// Drop counters since boot
+pub fn counters() -> DropCounters {
+    QUEUE.lock(|queue| *queue.borrow().counters())
+}
@@ -0,0 +1,602 @@
+//! Onboard autonomy rules
+//!
+//! An autonomy rule lets the spacecraft take a routine operational decision
+//! between contacts instead of waiting for the ground: "if the recorder is
+//! more than 80 % full and X-band is healthy, downlink the recorder on X-band
+//! at the next contact". A rule is a short script of up to
+//! [`AUTONOMY_MAX_TRIGGERS`] [`Trigger`]s, all of which must hold, and one
+//! [`AutonomyAction`]. Rules are plain data: the ground uploads them on
+//! [`AUTONOMY_RULE_APID`] and enables or disables them with
+//! [`SpaceCommand::SetAutonomyRule`]. An uploaded rule starts disabled.
+//!
+//! Autonomy is not FDIR and not a flight rule. FDIR reacts to faults and
+//! flight rules forbid activities; autonomy acts on nominal telemetry, so it
+//! defers to both. Its actions are checked against the flight rule table like
+//! any other autonomous action, and a trigger on a reading the state does not
+//! hold never holds, so a failed sensor keeps the rule quiet rather than
+//! acting on a guess.
+//!
+//! Rules fire on the edge: once when their triggers come to hold, and again
+//! only after the triggers have stopped holding. Every execution is returned
+//! from [`AutonomyEngine::evaluate`] for the satellite to carry out, log with
+//! the rule number as its code, and count in housekeeping telemetry at
+//! [`measurement_ids::AUTONOMY_EXECUTIONS`].
+//!
+//! [`SpaceCommand::SetAutonomyRule`]: crate::commands::SpaceCommand::SetAutonomyRule
+//! [`measurement_ids::AUTONOMY_EXECUTIONS`]: crate::telemetry::measurement_ids::AUTONOMY_EXECUTIONS
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: High Priority Command Set (rules enabled and disabled by
+//!   command)
+//! - REQ-FN-005: Medium Priority Command Set (routine operations automated
+//!   between contacts)
+//! - REQ-SF-001: Command Validation (rules validated on upload, actions
+//!   checked against flight rules)
+
+use core::fmt;
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+use space_comms_req::req;
+
+use crate::commands::SpaceCommand;
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::flight_rules::{Parameter, SpacecraftState};
+use crate::rf_housekeeping::RF_BANDS;
+use crate::types::{BandType, OperationalMode};
+use crate::wire;
+
+/// APID autonomy rules are uploaded on
+pub const AUTONOMY_RULE_APID: u16 = 0x019;
+
+/// Command ID of [`SpaceCommand::SetAutonomyRule`]
+pub const SET_AUTONOMY_RULE_COMMAND_ID: u32 = 0x0028;
+
+/// Number of rules the autonomy engine can hold
+pub const AUTONOMY_MAX_RULES: usize = 16;
+
+/// Number of triggers one rule can combine
+pub const AUTONOMY_MAX_TRIGGERS: usize = 4;
+
+/// Largest serialized rule, bytes
+pub const MAX_AUTONOMY_RULE_BYTES: usize = 512;
+
+/// Interval at which the satellite evaluates the rules, ms
+pub const AUTONOMY_PERIOD_MS: u64 = 1000;
+
+/// Quantity an autonomy trigger can compare against a limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quantity {
+    /// A spacecraft state parameter, as flight rules see it
+    State(Parameter),
+    /// Recorder fill, percent of the mass memory capacity
+    RecorderFill,
+}
+
+/// Condition an autonomy rule waits for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Trigger {
+    /// Quantity above the limit
+    Above {
+        /// Quantity compared
+        quantity: Quantity,
+        /// Limit, in the quantity's unit
+        limit: f32,
+    },
+    /// Quantity below the limit
+    Below {
+        /// Quantity compared
+        quantity: Quantity,
+        /// Limit, in the quantity's unit
+        limit: f32,
+    },
+    /// Transceiver of a band powered and locked
+    BandHealthy(BandType),
+    /// Spacecraft in an operational mode
+    InMode(OperationalMode),
+}
+
+impl Trigger {
+    /// Whether the trigger holds in `state`
+    ///
+    /// A limit on a quantity the state has no reading for does not hold.
+    pub fn holds(&self, state: &AutonomyState) -> bool {
+        match *self {
+            Trigger::Above { quantity, limit } => {
+                state.quantity(quantity).is_some_and(|value| value > limit)
+            }
+            Trigger::Below { quantity, limit } => {
+                state.quantity(quantity).is_some_and(|value| value < limit)
+            }
+            Trigger::BandHealthy(band) => state.band_healthy(band),
+            Trigger::InMode(mode) => state.spacecraft.mode == mode,
+        }
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Trigger::Above { quantity, limit } => write!(f, "{:?} > {}", quantity, limit),
+            Trigger::Below { quantity, limit } => write!(f, "{:?} < {}", quantity, limit),
+            Trigger::BandHealthy(band) => write!(f, "{:?} healthy", band),
+            Trigger::InMode(mode) => write!(f, "{:?} mode", mode),
+        }
+    }
+}
+
+/// What a rule does when it fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutonomyAction {
+    /// Move the recorder downlink to a band from the start of the next
+    /// contact
+    DownlinkNextContact(BandType),
+    /// Set the RF housekeeping reporting interval, ms
+    SetRfHousekeepingInterval(u32),
+}
+
+impl AutonomyAction {
+    /// Short name for logs
+    pub const fn name(&self) -> &'static str {
+        match self {
+            AutonomyAction::DownlinkNextContact(_) => "downlink at next contact",
+            AutonomyAction::SetRfHousekeepingInterval(_) => "set RF housekeeping interval",
+        }
+    }
+}
+
+/// One autonomy rule: `then` is taken when every trigger in `when` holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutonomyRule {
+    /// Rule number, unique within the engine
+    pub id: u16,
+    /// Triggers, all of which must hold
+    pub when: Vec<Trigger, AUTONOMY_MAX_TRIGGERS>,
+    /// Action taken when the rule fires
+    pub then: AutonomyAction,
+}
+
+impl AutonomyRule {
+    /// Create a rule
+    ///
+    /// A rule needs at least one trigger and at most
+    /// [`AUTONOMY_MAX_TRIGGERS`].
+    pub fn new(id: u16, when: &[Trigger], then: AutonomyAction) -> Result<Self> {
+        let rule = Self {
+            id,
+            when: Vec::from_slice(when).map_err(|_| SpaceCommError::ResourceExhausted {
+                resource: "autonomy rule triggers",
+                current_usage: when.len() as u32,
+                max_usage: AUTONOMY_MAX_TRIGGERS as u32,
+            })?,
+            then,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// Check the rule can be loaded
+    pub fn validate(&self) -> Result<()> {
+        if self.when.is_empty() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "autonomy_rule",
+                value: "no triggers",
+                reason: "An autonomy rule needs at least one trigger",
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether every trigger holds in `state`
+    pub fn holds(&self, state: &AutonomyState) -> bool {
+        self.when.iter().all(|trigger| trigger.holds(state))
+    }
+
+    /// Serialize the rule for uplink
+    pub fn to_bytes(&self) -> Result<Vec<u8, MAX_AUTONOMY_RULE_BYTES>> {
+        let bytes = serde_json::to_vec(self).map_err(|_| {
+            SpaceCommError::invalid_packet("Failed to serialize autonomy rule", None)
+        })?;
+        Vec::from_slice(&bytes).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+        })
+    }
+
+    /// Parse and validate a rule received over the uplink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rule: Self = serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed autonomy rule", None))?;
+        rule.validate()?;
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for AutonomyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AR-{:03}: if ", self.id)?;
+        for (index, trigger) in self.when.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" and ")?;
+            }
+            write!(f, "{}", trigger)?;
+        }
+        match self.then {
+            AutonomyAction::DownlinkNextContact(band) => {
+                write!(f, " then downlink on {:?} at next contact", band)
+            }
+            AutonomyAction::SetRfHousekeepingInterval(interval_ms) => {
+                write!(f, " then RF housekeeping every {} ms", interval_ms)
+            }
+        }
+    }
+}
+
+/// Snapshot of the state autonomy rules are evaluated against
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutonomyState {
+    /// Spacecraft state, as sampled for flight rules
+    pub spacecraft: SpacecraftState,
+    /// Recorder fill, percent of the mass memory capacity
+    pub recorder_fill: Option<f32>,
+    /// Transceiver health per band in [`RF_BANDS`] order
+    pub healthy_bands: [bool; 5],
+}
+
+impl AutonomyState {
+    /// State with no readings, no healthy band and nothing in progress
+    pub const fn new(mode: OperationalMode) -> Self {
+        Self {
+            spacecraft: SpacecraftState::new(mode),
+            recorder_fill: None,
+            healthy_bands: [false; 5],
+        }
+    }
+
+    /// Reading of a quantity, if the snapshot holds one
+    pub fn quantity(&self, quantity: Quantity) -> Option<f32> {
+        match quantity {
+            Quantity::State(parameter) => self.spacecraft.parameter(parameter),
+            Quantity::RecorderFill => self.recorder_fill,
+        }
+    }
+
+    /// Whether a band's transceiver is healthy
+    pub fn band_healthy(&self, band: BandType) -> bool {
+        RF_BANDS
+            .iter()
+            .position(|&candidate| candidate == band)
+            .is_some_and(|index| self.healthy_bands[index])
+    }
+}
+
+/// Rule enable or disable commanded by [`SpaceCommand::SetAutonomyRule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutonomySwitch {
+    /// Rule number
+    pub rule_id: u16,
+    /// Whether the rule is to run
+    pub enabled: bool,
+}
+
+impl AutonomySwitch {
+    /// Switch commanded in a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for an autonomy
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != SET_AUTONOMY_RULE_COMMAND_ID {
+            return None;
+        }
+        let switch = match serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..]) {
+            Ok(SpaceCommand::SetAutonomyRule { rule_id, enabled }) => Ok(Self { rule_id, enabled }),
+            _ => Err(SpaceCommError::invalid_packet(
+                "Malformed autonomy rule command",
+                Some(command_id),
+            )),
+        };
+        Some(switch)
+    }
+}
+
+/// A rule that fired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutonomyExecution {
+    /// Rule that fired
+    pub rule_id: u16,
+    /// Action to take
+    pub action: AutonomyAction,
+    /// Times the rule has fired since it was loaded, this one included
+    pub count: u32,
+}
+
+/// Loaded rule and its evaluation state
+#[derive(Debug, Clone, PartialEq)]
+struct Slot {
+    rule: AutonomyRule,
+    enabled: bool,
+    holding: bool,
+    executions: u32,
+}
+
+/// Onboard autonomy engine
+///
+/// - **ID**: MOD-AUT-001
+/// - **Requirement**: Take uploaded condition → action decisions onboard,
+///   under ground control of which rules run (REQ-FN-004, REQ-FN-005).
+/// - **Purpose**: Act on routine conditions between contacts, where waiting
+///   for the ground would waste a contact or fill the recorder.
+/// - **Failure Modes**: Unknown rule numbers → `Err(ConfigurationError)`;
+///   a full engine → `Err(ResourceExhausted)`. The engine is unchanged.
+/// - **Constraints**: Fixed size, no heap allocation; O(rules × triggers)
+///   evaluation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutonomyEngine {
+    slots: Vec<Slot, AUTONOMY_MAX_RULES>,
+}
+
+impl AutonomyEngine {
+    /// Engine with no rules
+    pub const fn new() -> Self {
+        Self { slots: Vec::new() }
+    }
+
+    /// Load a rule, disabled
+    ///
+    /// A rule with the number of one already loaded replaces it, and is
+    /// disabled until commanded on again.
+    pub fn load(&mut self, rule: AutonomyRule) -> Result<()> {
+        rule.validate()?;
+        let slot = Slot {
+            rule,
+            enabled: false,
+            holding: false,
+            executions: 0,
+        };
+        match self.slots.iter_mut().find(|s| s.rule.id == slot.rule.id) {
+            Some(existing) => {
+                *existing = slot;
+                Ok(())
+            }
+            None => self
+                .slots
+                .push(slot)
+                .map_err(|_| SpaceCommError::ResourceExhausted {
+                    resource: "autonomy rule table",
+                    current_usage: AUTONOMY_MAX_RULES as u32,
+                    max_usage: AUTONOMY_MAX_RULES as u32,
+                }),
+        }
+    }
+
+    /// Remove rule `id`, returning it
+    pub fn remove(&mut self, id: u16) -> Option<AutonomyRule> {
+        let index = self.slots.iter().position(|slot| slot.rule.id == id)?;
+        Some(self.slots.remove(index).rule)
+    }
+
+    /// Rule `id`, if loaded
+    pub fn rule(&self, id: u16) -> Option<&AutonomyRule> {
+        self.slot(id).map(|slot| &slot.rule)
+    }
+
+    /// Whether rule `id` is loaded and enabled
+    pub fn is_enabled(&self, id: u16) -> bool {
+        self.slot(id).is_some_and(|slot| slot.enabled)
+    }
+
+    /// Times rule `id` has fired since it was loaded
+    pub fn executions(&self, id: u16) -> u32 {
+        self.slot(id).map_or(0, |slot| slot.executions)
+    }
+
+    /// Loaded rules in the order they were loaded
+    pub fn iter(&self) -> impl Iterator<Item = &AutonomyRule> + '_ {
+        self.slots.iter().map(|slot| &slot.rule)
+    }
+
+    /// Enable or disable rule `id`
+    ///
+    /// An enabled rule whose triggers already hold fires at the next
+    /// evaluation.
+    #[req("REQ-FN-004")]
+    pub fn set_enabled(&mut self, id: u16, enabled: bool) -> Result<()> {
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| slot.rule.id == id)
+            .ok_or(SpaceCommError::ConfigurationError {
+                parameter: "autonomy_rule",
+                value: "unknown id",
+                reason: "No autonomy rule with this number is loaded",
+            })?;
+        slot.enabled = enabled;
+        slot.holding = false;
+        Ok(())
+    }
+
+    /// Evaluate the enabled rules against `state`
+    ///
+    /// - **ID**: FN-AUT-001
+    /// - **Requirement**: Fire each enabled rule once when its triggers come
+    ///   to hold (REQ-FN-005).
+    /// - **Outputs**: The rules that fired, in load order, with their
+    ///   actions.
+    /// - **Side Effects**: Records which rules hold and counts executions;
+    ///   callers carry out, log and report the actions.
+    /// - **Constraints**: O(rules × triggers).
+    /// - **Verification**: Unit tests `test_recorder_rule_fires_once_per_edge`,
+    ///   `test_missing_reading_never_fires`.
+    #[req("REQ-FN-005")]
+    pub fn evaluate(
+        &mut self,
+        state: &AutonomyState,
+    ) -> Vec<AutonomyExecution, AUTONOMY_MAX_RULES> {
+        let mut fired = Vec::new();
+        for slot in self.slots.iter_mut().filter(|slot| slot.enabled) {
+            let holds = slot.rule.holds(state);
+            if holds && !slot.holding {
+                slot.executions = slot.executions.saturating_add(1);
+                // One slot per rule, so this cannot overflow
+                let _ = fired.push(AutonomyExecution {
+                    rule_id: slot.rule.id,
+                    action: slot.rule.then,
+                    count: slot.executions,
+                });
+            }
+            slot.holding = holds;
+        }
+        fired
+    }
+
+    fn slot(&self, id: u16) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.rule.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder_rule() -> AutonomyRule {
+        AutonomyRule::new(
+            1,
+            &[
+                Trigger::Above {
+                    quantity: Quantity::RecorderFill,
+                    limit: 80.0,
+                },
+                Trigger::BandHealthy(BandType::XBand),
+            ],
+            AutonomyAction::DownlinkNextContact(BandType::XBand),
+        )
+        .unwrap()
+    }
+
+    fn state(recorder_fill: Option<f32>) -> AutonomyState {
+        let mut state = AutonomyState::new(OperationalMode::Normal);
+        state.recorder_fill = recorder_fill;
+        state.healthy_bands = [true; 5];
+        state
+    }
+
+    #[test]
+    fn test_recorder_rule_fires_once_per_edge() {
+        let mut engine = AutonomyEngine::new();
+        engine.load(recorder_rule()).unwrap();
+        assert_eq!(
+            recorder_rule().to_string(),
+            "AR-001: if RecorderFill > 80 and XBand healthy then downlink on XBand at next contact"
+        );
+
+        // Uploaded rules wait for the enable command
+        assert!(engine.evaluate(&state(Some(90.0))).is_empty());
+        engine.set_enabled(1, true).unwrap();
+        assert!(engine.is_enabled(1));
+
+        let fired = engine.evaluate(&state(Some(90.0)));
+        assert_eq!(
+            fired.as_slice(),
+            &[AutonomyExecution {
+                rule_id: 1,
+                action: AutonomyAction::DownlinkNextContact(BandType::XBand),
+                count: 1,
+            }]
+        );
+        assert!(engine.evaluate(&state(Some(95.0))).is_empty());
+
+        // X-band failing clears the edge; recovering fires again
+        let mut x_failed = state(Some(95.0));
+        x_failed.healthy_bands[2] = false;
+        assert!(engine.evaluate(&x_failed).is_empty());
+        assert_eq!(engine.evaluate(&state(Some(95.0)))[0].count, 2);
+        assert_eq!(engine.executions(1), 2);
+
+        engine.set_enabled(1, false).unwrap();
+        assert!(engine.evaluate(&state(Some(40.0))).is_empty());
+        assert!(engine.evaluate(&state(Some(90.0))).is_empty());
+    }
+
+    #[test]
+    fn test_missing_reading_never_fires() {
+        let mut engine = AutonomyEngine::new();
+        engine.load(recorder_rule()).unwrap();
+        engine.set_enabled(1, true).unwrap();
+        assert!(engine.evaluate(&state(None)).is_empty());
+
+        let battery = AutonomyRule::new(
+            2,
+            &[Trigger::Below {
+                quantity: Quantity::State(Parameter::BatteryStateOfCharge),
+                limit: 50.0,
+            }],
+            AutonomyAction::SetRfHousekeepingInterval(10_000),
+        )
+        .unwrap();
+        engine.load(battery).unwrap();
+        engine.set_enabled(2, true).unwrap();
+        let mut low = state(None);
+        assert!(engine.evaluate(&low).is_empty());
+        low.spacecraft.battery_soc = Some(35.0);
+        assert_eq!(engine.evaluate(&low)[0].rule_id, 2);
+    }
+
+    #[test]
+    fn test_rule_upload_and_table_limits() {
+        let rule = recorder_rule();
+        let bytes = rule.to_bytes().unwrap();
+        assert_eq!(AutonomyRule::from_bytes(&bytes).unwrap(), rule);
+        assert!(AutonomyRule::from_bytes(b"{").is_err());
+        assert!(AutonomyRule::new(3, &[], rule.then).is_err());
+        assert!(AutonomyRule::new(3, &[rule.when[0]; 5], rule.then).is_err());
+
+        let mut engine = AutonomyEngine::new();
+        engine.load(rule.clone()).unwrap();
+        engine.set_enabled(1, true).unwrap();
+        // Reloading replaces the rule and disables it
+        engine.load(rule.clone()).unwrap();
+        assert_eq!(engine.iter().count(), 1);
+        assert!(!engine.is_enabled(1));
+        assert!(engine.set_enabled(7, true).is_err());
+
+        for id in 100..(100 + AUTONOMY_MAX_RULES as u16 - 1) {
+            let mut other = rule.clone();
+            other.id = id;
+            engine.load(other).unwrap();
+        }
+        let mut extra = rule.clone();
+        extra.id = 99;
+        assert!(matches!(
+            engine.load(extra),
+            Err(SpaceCommError::ResourceExhausted { .. })
+        ));
+        assert_eq!(engine.remove(1), Some(rule));
+        assert_eq!(engine.rule(1), None);
+    }
+
+    #[test]
+    fn test_switch_from_command_data() {
+        let command = SpaceCommand::SetAutonomyRule {
+            rule_id: 4,
+            enabled: true,
+        };
+        assert_eq!(command.discriminant(), SET_AUTONOMY_RULE_COMMAND_ID);
+        let mut data = std::vec::Vec::from(SET_AUTONOMY_RULE_COMMAND_ID.to_be_bytes());
+        data.extend(serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            AutonomySwitch::from_command_data(&data).unwrap().unwrap(),
+            AutonomySwitch {
+                rule_id: 4,
+                enabled: true,
+            }
+        );
+
+        let mut malformed = SET_AUTONOMY_RULE_COMMAND_ID.to_be_bytes().to_vec();
+        malformed.extend(b"{}");
+        assert!(AutonomySwitch::from_command_data(&malformed)
+            .unwrap()
+            .is_err());
+        assert!(AutonomySwitch::from_command_data(&0x0026u32.to_be_bytes()).is_none());
+    }
+}
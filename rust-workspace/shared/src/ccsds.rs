@@ -4,10 +4,19 @@
 //! - Space Packet Protocol (CCSDS 133.0-B-1)
 //! - Space Data Link Protocol (CCSDS 132.0-B-2)
 //! - Advanced Orbiting Systems Networks (CCSDS 135.0-B-4)
+//! - TC Space Data Link Protocol transfer frames (CCSDS 232.0-B-4), sent
+//!   under COP-1 (see [`crate::cop1`])
+//! - TM Space Data Link Protocol transfer frames (CCSDS 132.0-B-3), carrying
+//!   the downlink on virtual channels (see [`crate::vc_mux`])
+//! - Reed-Solomon coding (CCSDS 131.0-B) of packets and frames on the bands
+//!   a [`FecPolicy`] codes (see [`crate::fec`])
 
 use serde::{Deserialize, Serialize};
 use crate::error::{Result, SpaceCommError};
-use crate::types::{PacketId, ComponentId};
+use crate::fec::{FecPolicy, MAX_CODED_FRAME_LEN};
+use crate::types::{BandType, ComponentId, PacketId};
+use crate::wire::{WireReader, WireWriter};
+use space_comms_req::req;
 
 /// CCSDS Space Packet primary header (6 bytes)
 ///
@@ -95,6 +104,7 @@ impl SpacePacketHeader {
     }
 
     /// Serialize header to bytes (big-endian, CCSDS standard)
+    #[req("REQ-IF-002")]
     pub fn to_bytes(&self) -> [u8; 6] {
         let mut bytes = [0u8; 6];
 
@@ -119,6 +129,7 @@ impl SpacePacketHeader {
     }
 
     /// Deserialize header from bytes
+    #[req("REQ-IF-002")]
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < 6 {
             return Err(SpaceCommError::invalid_packet(
@@ -234,6 +245,7 @@ impl SpacePacket {
     /// - **Verification**: Unit test `test_space_packet_creation` must pass;
     ///   `verify_crc()` must return `true` immediately after construction.
     /// - **References**: CCSDS 133.0-B-2 §4.1; CCSDS 132.0-B-2 §4.1.4.
+    #[req("REQ-IF-002")]
     pub fn new(
         packet_type: PacketType,
         apid: u16,
@@ -345,6 +357,7 @@ impl SpacePacket {
     }
 
     /// Serialize packet to bytes
+    #[req("REQ-IF-002")]
     pub fn to_bytes(&self) -> Result<heapless::Vec<u8, 4096>> {
         let mut bytes = heapless::Vec::new();
 
@@ -393,6 +406,21 @@ impl SpacePacket {
         Ok(bytes)
     }
 
+    /// Serialize the packet as sent on `band` under `policy`
+    ///
+    /// - **ID**: FN-FEC-005
+    /// - **Requirement**: Packets sent outside a transfer frame on coded
+    ///   bands go out as Reed-Solomon codeblocks (REQ-FN-007).
+    /// - **Outputs**: [`SpacePacket::to_bytes`], coded where `policy` codes
+    ///   `band`.
+    pub fn to_bytes_with_fec(
+        &self,
+        policy: &FecPolicy,
+        band: BandType,
+    ) -> Result<heapless::Vec<u8, MAX_CODED_FRAME_LEN>> {
+        policy.encode(band, &self.to_bytes()?)
+    }
+
     /// Get unique packet identifier
     pub fn packet_id(&self) -> PacketId {
         // Combine APID and sequence count for unique ID
@@ -460,7 +488,7 @@ const CRC16_TABLE: [u16; 256] = {
 /// - **Verification**: Cross-validate against reference vector:
 ///   `crc16_ccitt(0xFFFF, b"123456789")` must equal `0x29B1`.
 /// - **References**: CCSDS 132.0-B-2 §4.1.4; ITU-T V.42 Annex B.
-fn crc16_ccitt(mut crc: u16, data: &[u8]) -> u16 {
+pub(crate) fn crc16_ccitt(mut crc: u16, data: &[u8]) -> u16 {
     for &byte in data {
         let idx = (((crc >> 8) ^ (byte as u16)) & 0xFF) as usize;
         crc = (crc << 8) ^ CRC16_TABLE[idx];
@@ -484,6 +512,512 @@ pub struct VirtualChannelAccessUnit {
     pub packets: heapless::Vec<SpacePacket, 8>,
 }
 
+/// TC Transfer Frame primary header length, bytes (CCSDS 232.0-B-4 §4.1.2)
+pub const TC_PRIMARY_HEADER_LEN: usize = 5;
+
+/// TC Frame Error Control Field length, bytes
+pub const TC_FECF_LEN: usize = 2;
+
+/// Largest TC Transfer Frame, bytes
+pub const TC_MAX_FRAME_LEN: usize = 1024;
+
+/// Largest TC Transfer Frame data field, bytes
+pub const TC_MAX_DATA_LEN: usize = TC_MAX_FRAME_LEN - TC_PRIMARY_HEADER_LEN - TC_FECF_LEN;
+
+/// Service carrying a TC Transfer Frame, from its bypass and control flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TcFrameType {
+    /// Type-AD: sequence-controlled data, accepted in order under COP-1
+    Ad,
+    /// Type-BD: expedited data, bypassing the FARM-1 sequence checks
+    Bd,
+    /// Type-BC: COP-1 control command (Unlock or Set V(R))
+    Bc,
+}
+
+/// CCSDS TC Transfer Frame (CCSDS 232.0-B-4)
+///
+/// Carries one or more space packets, or a COP-1 control command, on a
+/// virtual channel of the command uplink. Frames on this link always carry
+/// the Frame Error Control Field.
+///
+/// | Bits  | Field                                  |
+/// |-------|----------------------------------------|
+/// | 2     | Version, `00`                          |
+/// | 1     | Bypass flag, set for BD and BC frames  |
+/// | 1     | Control command flag, set for BC       |
+/// | 2     | Spare, `00`                            |
+/// | 10    | Spacecraft ID                          |
+/// | 6     | Virtual channel ID                     |
+/// | 10    | Frame length, total octets less one    |
+/// | 8     | Frame sequence number N(S)             |
+/// | ...   | Data field                             |
+/// | 16    | FECF, CRC-16/CCITT-FALSE               |
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TcTransferFrame {
+    /// Service carrying the frame
+    pub frame_type: TcFrameType,
+
+    /// Spacecraft Identifier (10 bits)
+    pub spacecraft_id: u16,
+
+    /// Virtual Channel Identifier (6 bits)
+    pub virtual_channel: u8,
+
+    /// Frame sequence number N(S); significant for Type-AD frames only
+    pub sequence: u8,
+
+    /// Frame data field
+    pub data: heapless::Vec<u8, TC_MAX_DATA_LEN>,
+}
+
+impl TcTransferFrame {
+    /// Create a TC Transfer Frame
+    ///
+    /// - **ID**: FN-TCF-001
+    /// - **Requirement**: Frame command data for the COP-1 uplink as CCSDS
+    ///   232.0-B-4 lays it out (REQ-IF-002).
+    /// - **Inputs**:
+    ///   - `frame_type`: Type-AD, BD or BC.
+    ///   - `spacecraft_id`: `0x000`–`0x3FF`.
+    ///   - `virtual_channel`: `0x00`–`0x3F`.
+    ///   - `sequence`: N(S); BD and BC frames are sent with 0.
+    ///   - `data`: Data field, at most [`TC_MAX_DATA_LEN`] bytes.
+    /// - **Failure Modes**: `InvalidPacket` for an identifier out of range;
+    ///   `MemoryError` for a data field too long.
+    pub fn new(
+        frame_type: TcFrameType,
+        spacecraft_id: u16,
+        virtual_channel: u8,
+        sequence: u8,
+        data: &[u8],
+    ) -> Result<Self> {
+        if spacecraft_id > 0x3FF {
+            return Err(SpaceCommError::invalid_packet(
+                "Spacecraft ID exceeds 10-bit maximum",
+                Some(u32::from(spacecraft_id)),
+            ));
+        }
+        if virtual_channel > 0x3F {
+            return Err(SpaceCommError::invalid_packet(
+                "Virtual channel ID exceeds 6-bit maximum",
+                Some(u32::from(virtual_channel)),
+            ));
+        }
+        let data = heapless::Vec::from_slice(data).map_err(|_| {
+            SpaceCommError::memory_error(
+                crate::error::MemoryErrorType::BufferOverflow,
+                Some(data.len()),
+            )
+        })?;
+
+        Ok(Self {
+            frame_type,
+            spacecraft_id,
+            virtual_channel,
+            sequence,
+            data,
+        })
+    }
+
+    /// Total frame length including header and FECF, bytes
+    pub fn len(&self) -> usize {
+        TC_PRIMARY_HEADER_LEN + self.data.len() + TC_FECF_LEN
+    }
+
+    /// Whether the data field is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Serialize the frame, FECF included
+    #[req("REQ-IF-002")]
+    pub fn to_bytes(&self) -> Result<heapless::Vec<u8, TC_MAX_FRAME_LEN>> {
+        let (bypass, control) = match self.frame_type {
+            TcFrameType::Ad => (0u8, 0u8),
+            TcFrameType::Bd => (1, 0),
+            TcFrameType::Bc => (1, 1),
+        };
+        let frame_length = (self.len() - 1) as u16;
+
+        let mut writer = WireWriter::<TC_MAX_FRAME_LEN>::new();
+        writer
+            .put(bypass << 5 | control << 4 | (self.spacecraft_id >> 8) as u8)
+            .and_then(|w| w.put(self.spacecraft_id as u8))
+            .and_then(|w| w.put(self.virtual_channel << 2 | (frame_length >> 8) as u8))
+            .and_then(|w| w.put(frame_length as u8))
+            .and_then(|w| w.put(self.sequence))
+            .and_then(|w| w.put_bytes(&self.data))?;
+        let mut bytes = writer.finish();
+        let crc = crc16_ccitt(0xFFFF, &bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes()).map_err(|_| {
+            SpaceCommError::memory_error(
+                crate::error::MemoryErrorType::BufferOverflow,
+                Some(TC_FECF_LEN),
+            )
+        })?;
+        Ok(bytes)
+    }
+
+    /// Parse and check a received frame
+    ///
+    /// - **ID**: FN-TCF-002
+    /// - **Requirement**: Accept a TC Transfer Frame only when its version,
+    ///   declared length and FECF are correct (REQ-IF-002).
+    /// - **Inputs**: Frame bytes, header to FECF.
+    /// - **Outputs**: The frame with its data field.
+    /// - **Failure Modes**: `InvalidPacket` for a short frame, a version other
+    ///   than `00`, a control command without the bypass flag, a frame length
+    ///   that disagrees with the bytes received, or an FECF mismatch.
+    #[req("REQ-IF-002")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < TC_PRIMARY_HEADER_LEN + TC_FECF_LEN {
+            return Err(SpaceCommError::invalid_packet("TC frame too short", None));
+        }
+        let mut reader = WireReader::new(bytes);
+        let first = reader.get::<u16>()?;
+        let second = reader.get::<u16>()?;
+        let sequence = reader.get::<u8>()?;
+
+        if first >> 14 != 0 {
+            return Err(SpaceCommError::invalid_packet(
+                "Invalid TC frame version number",
+                None,
+            ));
+        }
+        let frame_type = match (first >> 13 & 1, first >> 12 & 1) {
+            (0, 0) => TcFrameType::Ad,
+            (1, 0) => TcFrameType::Bd,
+            (1, _) => TcFrameType::Bc,
+            _ => {
+                return Err(SpaceCommError::invalid_packet(
+                    "TC control command without bypass flag",
+                    None,
+                ))
+            }
+        };
+        if usize::from(second & 0x3FF) + 1 != bytes.len() {
+            return Err(SpaceCommError::invalid_packet(
+                "TC frame length disagrees with frame",
+                Some(u32::from(second & 0x3FF)),
+            ));
+        }
+        let (body, fecf) = bytes.split_at(bytes.len() - TC_FECF_LEN);
+        if crc16_ccitt(0xFFFF, body) != WireReader::new(fecf).get::<u16>()? {
+            return Err(SpaceCommError::invalid_packet(
+                "TC frame FECF mismatch",
+                Some(u32::from(sequence)),
+            ));
+        }
+
+        Self::new(
+            frame_type,
+            first & 0x3FF,
+            (second >> 10) as u8,
+            sequence,
+            &body[TC_PRIMARY_HEADER_LEN..],
+        )
+    }
+
+    /// Serialize the frame as sent on `band` under `policy`
+    ///
+    /// - **ID**: FN-FEC-006
+    /// - **Requirement**: Frames on coded bands are uplinked as
+    ///   Reed-Solomon codeblocks (REQ-FN-007).
+    /// - **Outputs**: [`TcTransferFrame::to_bytes`], coded where `policy`
+    ///   codes `band`.
+    #[req("REQ-FN-007")]
+    pub fn to_bytes_with_fec(
+        &self,
+        policy: &FecPolicy,
+        band: BandType,
+    ) -> Result<heapless::Vec<u8, MAX_CODED_FRAME_LEN>> {
+        policy.encode(band, &self.to_bytes()?)
+    }
+
+    /// Correct and parse a frame received on `band` under `policy`
+    ///
+    /// - **ID**: FN-FEC-007
+    /// - **Requirement**: Coded frames are corrected before the frame is
+    ///   checked (REQ-FN-007).
+    /// - **Outputs**: The frame and the symbols Reed-Solomon decoding
+    ///   corrected.
+    /// - **Failure Modes**: Those of [`FecPolicy::decode`] and
+    ///   [`TcTransferFrame::from_bytes`].
+    #[req("REQ-FN-007")]
+    pub fn from_bytes_with_fec(
+        bytes: &[u8],
+        policy: &FecPolicy,
+        band: BandType,
+    ) -> Result<(Self, usize)> {
+        let decoded = policy.decode(band, bytes)?;
+        Ok((Self::from_bytes(&decoded.bytes)?, decoded.corrected_symbols))
+    }
+}
+
+/// TM Transfer Frame primary header length, bytes (CCSDS 132.0-B-3 §4.1.2)
+pub const TM_PRIMARY_HEADER_LEN: usize = 6;
+
+/// TM Operational Control Field length, bytes; carries the CLCW
+pub const TM_OCF_LEN: usize = 4;
+
+/// TM Frame Error Control Field length, bytes
+pub const TM_FECF_LEN: usize = 2;
+
+/// Length of every TM Transfer Frame on this link, bytes: five
+/// Reed-Solomon codeblocks of data, so a coded frame is exactly five
+/// codeblocks
+pub const TM_FRAME_LEN: usize = 5 * crate::fec::RS_DATA_LEN;
+
+/// Largest TM Transfer Frame data field, bytes, when the frame has no OCF
+pub const TM_MAX_DATA_LEN: usize = TM_FRAME_LEN - TM_PRIMARY_HEADER_LEN - TM_FECF_LEN;
+
+/// First header pointer of a frame in which no packet starts
+pub const TM_FHP_NO_PACKET: u16 = 0x7FF;
+
+/// First header pointer of a frame carrying idle data only
+pub const TM_FHP_IDLE: u16 = 0x7FE;
+
+/// APID of the idle packets that fill out a frame sent before its data
+/// field is full
+pub const IDLE_APID: u16 = 0x7FF;
+
+/// CCSDS TM Transfer Frame (CCSDS 132.0-B-3)
+///
+/// Carries the packets of one virtual channel of the downlink, run back to
+/// back across frames. Frames on this link are [`TM_FRAME_LEN`] bytes long,
+/// have no secondary header, and always carry the Frame Error Control Field.
+///
+/// | Bits  | Field                                         |
+/// |-------|-----------------------------------------------|
+/// | 2     | Version, `00`                                 |
+/// | 10    | Spacecraft ID                                 |
+/// | 3     | Virtual channel ID                            |
+/// | 1     | OCF flag, set when the frame carries an OCF   |
+/// | 8     | Master channel frame count                    |
+/// | 8     | Virtual channel frame count                   |
+/// | 1     | Secondary header flag, `0`                    |
+/// | 1     | Synchronisation flag, `0` (packets)           |
+/// | 1     | Packet order flag, `0`                        |
+/// | 2     | Segment length ID, `11`                       |
+/// | 11    | First header pointer                          |
+/// | ...   | Data field                                    |
+/// | 32    | OCF, when flagged                             |
+/// | 16    | FECF, CRC-16/CCITT-FALSE                      |
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TmTransferFrame {
+    /// Spacecraft Identifier (10 bits)
+    pub spacecraft_id: u16,
+
+    /// Virtual Channel Identifier (3 bits)
+    pub virtual_channel: u8,
+
+    /// Frames sent on the master channel before this one, modulo 256
+    pub master_frame_count: u8,
+
+    /// Frames sent on this virtual channel before this one, modulo 256
+    pub vc_frame_count: u8,
+
+    /// Offset in the data field of the first packet header, or
+    /// [`TM_FHP_NO_PACKET`] / [`TM_FHP_IDLE`]
+    pub first_header_pointer: u16,
+
+    /// Operational Control Field; the FARM-1 CLCW on this link
+    pub ocf: Option<[u8; TM_OCF_LEN]>,
+
+    /// Frame data field
+    pub data: heapless::Vec<u8, TM_MAX_DATA_LEN>,
+}
+
+impl TmTransferFrame {
+    /// Data field length of a frame with or without an OCF, bytes
+    pub const fn data_len(has_ocf: bool) -> usize {
+        if has_ocf {
+            TM_MAX_DATA_LEN - TM_OCF_LEN
+        } else {
+            TM_MAX_DATA_LEN
+        }
+    }
+
+    /// Create a TM Transfer Frame with zero frame counts
+    ///
+    /// - **ID**: FN-TMF-001
+    /// - **Requirement**: Frame downlink packets as CCSDS 132.0-B-3 lays
+    ///   them out (REQ-IF-002).
+    /// - **Inputs**:
+    ///   - `spacecraft_id`: `0x000`–`0x3FF`.
+    ///   - `virtual_channel`: `0`–`7`.
+    ///   - `first_header_pointer`: Offset of the first packet header in
+    ///     `data`, [`TM_FHP_NO_PACKET`] or [`TM_FHP_IDLE`].
+    ///   - `ocf`: Operational Control Field, if the frame carries one.
+    ///   - `data`: Data field, exactly [`Self::data_len`] bytes.
+    /// - **Outputs**: The frame; the multiplexer sets the frame counts.
+    /// - **Failure Modes**: `InvalidPacket` for an identifier out of range,
+    ///   a data field of the wrong length, or a first header pointer past
+    ///   the end of the data field.
+    #[req("REQ-IF-002")]
+    pub fn new(
+        spacecraft_id: u16,
+        virtual_channel: u8,
+        first_header_pointer: u16,
+        ocf: Option<[u8; TM_OCF_LEN]>,
+        data: &[u8],
+    ) -> Result<Self> {
+        if spacecraft_id > 0x3FF {
+            return Err(SpaceCommError::invalid_packet(
+                "Spacecraft ID exceeds 10-bit maximum",
+                Some(u32::from(spacecraft_id)),
+            ));
+        }
+        if virtual_channel > 0x07 {
+            return Err(SpaceCommError::invalid_packet(
+                "Virtual channel ID exceeds 3-bit maximum",
+                Some(u32::from(virtual_channel)),
+            ));
+        }
+        if data.len() != Self::data_len(ocf.is_some()) {
+            return Err(SpaceCommError::invalid_packet(
+                "TM frame data field is not the fixed length",
+                Some(data.len() as u32),
+            ));
+        }
+        if first_header_pointer < TM_FHP_IDLE && usize::from(first_header_pointer) >= data.len() {
+            return Err(SpaceCommError::invalid_packet(
+                "First header pointer beyond the data field",
+                Some(u32::from(first_header_pointer)),
+            ));
+        }
+        let data = heapless::Vec::from_slice(data).map_err(|_| {
+            SpaceCommError::memory_error(
+                crate::error::MemoryErrorType::BufferOverflow,
+                Some(data.len()),
+            )
+        })?;
+
+        Ok(Self {
+            spacecraft_id,
+            virtual_channel,
+            master_frame_count: 0,
+            vc_frame_count: 0,
+            first_header_pointer,
+            ocf,
+            data,
+        })
+    }
+
+    /// Serialize the frame, FECF included
+    #[req("REQ-IF-002")]
+    pub fn to_bytes(&self) -> Result<heapless::Vec<u8, TM_FRAME_LEN>> {
+        let mut writer = WireWriter::<TM_FRAME_LEN>::new();
+        writer
+            .put(
+                self.spacecraft_id << 4
+                    | u16::from(self.virtual_channel) << 1
+                    | u16::from(self.ocf.is_some()),
+            )
+            .and_then(|w| w.put(self.master_frame_count))
+            .and_then(|w| w.put(self.vc_frame_count))
+            .and_then(|w| w.put(0b11 << 11 | self.first_header_pointer))
+            .and_then(|w| w.put_bytes(&self.data))?;
+        if let Some(ocf) = &self.ocf {
+            writer.put_bytes(ocf)?;
+        }
+        let mut bytes = writer.finish();
+        let crc = crc16_ccitt(0xFFFF, &bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes()).map_err(|_| {
+            SpaceCommError::memory_error(
+                crate::error::MemoryErrorType::BufferOverflow,
+                Some(TM_FECF_LEN),
+            )
+        })?;
+        Ok(bytes)
+    }
+
+    /// Parse and check a received frame
+    ///
+    /// - **ID**: FN-TMF-002
+    /// - **Requirement**: Accept a TM Transfer Frame only when its length,
+    ///   version, data field status and FECF are those of this link
+    ///   (REQ-IF-002).
+    /// - **Inputs**: Frame bytes, header to FECF.
+    /// - **Outputs**: The frame with its data field and OCF.
+    /// - **Failure Modes**: `InvalidPacket` for a frame other than
+    ///   [`TM_FRAME_LEN`] bytes, a version other than `00`, an FECF
+    ///   mismatch, a secondary header or synchronisation flag, or a first
+    ///   header pointer past the end of the data field.
+    #[req("REQ-IF-002")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != TM_FRAME_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "TM frame is not the fixed length",
+                Some(bytes.len() as u32),
+            ));
+        }
+        let mut reader = WireReader::new(bytes);
+        let identifier = reader.get::<u16>()?;
+        let master_frame_count = reader.get::<u8>()?;
+        let vc_frame_count = reader.get::<u8>()?;
+        let status = reader.get::<u16>()?;
+
+        if identifier >> 14 != 0 {
+            return Err(SpaceCommError::invalid_packet(
+                "Invalid TM frame version number",
+                None,
+            ));
+        }
+        let (body, fecf) = bytes.split_at(bytes.len() - TM_FECF_LEN);
+        if crc16_ccitt(0xFFFF, body) != WireReader::new(fecf).get::<u16>()? {
+            return Err(SpaceCommError::invalid_packet(
+                "TM frame FECF mismatch",
+                Some(u32::from(vc_frame_count)),
+            ));
+        }
+        if status >> 14 != 0 {
+            return Err(SpaceCommError::invalid_packet(
+                "TM frame secondary header or synchronised data not used on this link",
+                None,
+            ));
+        }
+
+        let has_ocf = identifier & 1 == 1;
+        let data_end = TM_PRIMARY_HEADER_LEN + Self::data_len(has_ocf);
+        let ocf = if has_ocf {
+            let mut ocf = [0u8; TM_OCF_LEN];
+            ocf.copy_from_slice(&body[data_end..]);
+            Some(ocf)
+        } else {
+            None
+        };
+        let mut frame = Self::new(
+            identifier >> 4 & 0x3FF,
+            (identifier >> 1 & 0x07) as u8,
+            status & 0x7FF,
+            ocf,
+            &body[TM_PRIMARY_HEADER_LEN..data_end],
+        )?;
+        frame.master_frame_count = master_frame_count;
+        frame.vc_frame_count = vc_frame_count;
+        Ok(frame)
+    }
+
+    /// Serialize the frame as sent on `band` under `policy`
+    ///
+    /// - **ID**: FN-FEC-008
+    /// - **Requirement**: Frames on coded bands are downlinked as
+    ///   Reed-Solomon codeblocks (REQ-FN-007); a coded frame is exactly
+    ///   five codeblocks.
+    /// - **Outputs**: [`TmTransferFrame::to_bytes`], coded where `policy`
+    ///   codes `band`.
+    #[req("REQ-FN-007")]
+    pub fn to_bytes_with_fec(
+        &self,
+        policy: &FecPolicy,
+        band: BandType,
+    ) -> Result<heapless::Vec<u8, MAX_CODED_FRAME_LEN>> {
+        policy.encode(band, &self.to_bytes()?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -554,4 +1088,111 @@ mod tests {
         let crc = crc16_ccitt(0xFFFF, data);
         assert_eq!(crc, 0x29B1); // Known result for this test vector
     }
+
+    #[test]
+    fn test_tc_frame_roundtrip() {
+        let frame = TcTransferFrame::new(TcFrameType::Ad, 0x2A5, 7, 200, b"command").unwrap();
+        let bytes = frame.to_bytes().unwrap();
+        assert_eq!(bytes.len(), frame.len());
+        // Version 00, no bypass, SCID 0x2A5, VC 7, length 14 - 1, N(S) 200
+        assert_eq!(&bytes[..5], &[0x02, 0xA5, 0x1C, 0x0D, 200]);
+        assert_eq!(TcTransferFrame::from_bytes(&bytes).unwrap(), frame);
+
+        let control = TcTransferFrame::new(TcFrameType::Bc, 1, 7, 0, &[0]).unwrap();
+        let bytes = control.to_bytes().unwrap();
+        assert_eq!(bytes[0] >> 4, 0b0011);
+        assert_eq!(
+            TcTransferFrame::from_bytes(&bytes).unwrap().frame_type,
+            TcFrameType::Bc
+        );
+    }
+
+    #[test]
+    fn test_tc_frame_checks() {
+        assert!(TcTransferFrame::new(TcFrameType::Ad, 0x400, 0, 0, &[]).is_err());
+        assert!(TcTransferFrame::new(TcFrameType::Ad, 0, 0x40, 0, &[]).is_err());
+        assert!(TcTransferFrame::new(TcFrameType::Bd, 0, 0, 0, &[0; TC_MAX_DATA_LEN + 1]).is_err());
+
+        let bytes = TcTransferFrame::new(TcFrameType::Bd, 5, 2, 0, b"data")
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let mut corrupted = bytes.clone();
+        corrupted[6] ^= 0x01;
+        assert!(TcTransferFrame::from_bytes(&corrupted).is_err());
+        assert!(TcTransferFrame::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(TcTransferFrame::from_bytes(&bytes[..4]).is_err());
+
+        // Control command flag without the bypass flag
+        let mut unbypassed = bytes.clone();
+        unbypassed[0] = 0x10;
+        assert!(TcTransferFrame::from_bytes(&unbypassed).is_err());
+    }
+
+    #[test]
+    fn test_tm_transfer_frame_round_trip() {
+        let data: heapless::Vec<u8, TM_MAX_DATA_LEN> = (0..TmTransferFrame::data_len(true))
+            .map(|i| i as u8)
+            .collect();
+        let mut frame = TmTransferFrame::new(0x2A5, 2, 40, Some([1, 2, 3, 4]), &data).unwrap();
+        frame.master_frame_count = 255;
+        frame.vc_frame_count = 17;
+        let bytes = frame.to_bytes().unwrap();
+        assert_eq!(bytes.len(), TM_FRAME_LEN);
+        assert_eq!(TmTransferFrame::from_bytes(&bytes).unwrap(), frame);
+
+        let bare =
+            TmTransferFrame::new(1, 0, TM_FHP_NO_PACKET, None, &[0; TM_MAX_DATA_LEN]).unwrap();
+        let bytes = bare.to_bytes().unwrap();
+        assert_eq!(bytes.len(), TM_FRAME_LEN);
+        assert_eq!(TmTransferFrame::from_bytes(&bytes).unwrap(), bare);
+    }
+
+    #[test]
+    fn test_tm_transfer_frame_rejects_malformed() {
+        let data = [0u8; TM_MAX_DATA_LEN];
+        assert!(TmTransferFrame::new(0x400, 0, 0, None, &data).is_err());
+        assert!(TmTransferFrame::new(0, 8, 0, None, &data).is_err());
+        assert!(TmTransferFrame::new(0, 0, 0, Some([0; TM_OCF_LEN]), &data).is_err());
+        assert!(TmTransferFrame::new(0, 0, TM_MAX_DATA_LEN as u16, None, &data).is_err());
+
+        let bytes = TmTransferFrame::new(5, 1, 0, None, &data)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let mut corrupted = bytes.clone();
+        corrupted[100] ^= 0x01;
+        assert!(TmTransferFrame::from_bytes(&corrupted).is_err());
+        assert!(TmTransferFrame::from_bytes(&bytes[..TM_FRAME_LEN - 1]).is_err());
+
+        // A space packet is never taken for a frame
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 1, b"hk", None).unwrap();
+        assert!(TmTransferFrame::from_bytes(&packet.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_frames_coded_per_band() {
+        let policy = FecPolicy::default();
+        let frame = TcTransferFrame::new(TcFrameType::Ad, 0x2A, 1, 7, b"command").unwrap();
+
+        // Uncoded bands carry the frame as it is
+        let plain = frame.to_bytes_with_fec(&policy, BandType::SBand).unwrap();
+        assert_eq!(&plain[..], &frame.to_bytes().unwrap()[..]);
+
+        // Coded bands correct a corrupted symbol before the FECF is checked
+        let mut coded = frame.to_bytes_with_fec(&policy, BandType::KaBand).unwrap();
+        assert_eq!(coded.len(), crate::fec::coded_len(frame.len()));
+        coded[3] ^= 0xFF;
+        let (received, corrected) =
+            TcTransferFrame::from_bytes_with_fec(&coded, &policy, BandType::KaBand).unwrap();
+        assert_eq!(received, frame);
+        assert_eq!(corrected, 1);
+        assert!(TcTransferFrame::from_bytes_with_fec(&coded, &policy, BandType::SBand).is_err());
+
+        let tm = TmTransferFrame::new(5, 1, 0, None, &[0x5A; TM_MAX_DATA_LEN]).unwrap();
+        let coded = tm.to_bytes_with_fec(&policy, BandType::KBand).unwrap();
+        assert_eq!(coded.len(), 5 * crate::fec::RS_BLOCK_LEN);
+        let decoded = policy.decode(BandType::KBand, &coded).unwrap();
+        assert_eq!(TmTransferFrame::from_bytes(&decoded.bytes).unwrap(), tm);
+    }
 }
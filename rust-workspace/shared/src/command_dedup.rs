@@ -0,0 +1,181 @@
+//! Duplicate command detection
+//!
+//! Retransmissions put the same command on the uplink more than once: the
+//! ground retries a send it believes failed, or resends a command whose
+//! execution report it never saw. Executing the copy again would repeat a
+//! maneuver or toggle a switch back, so the satellite remembers the commands
+//! it has accepted and discards any that arrive again.
+//!
+//! A command is identified by a [`CommandKey`]: its source, its message ID and
+//! the authentication counter of the frame that carried it. Keys are kept in
+//! a sliding window, both in time ([`DuplicateFilter::window_ms`]) and in
+//! count (the filter capacity), so memory stays bounded and a message ID that
+//! wraps around long after its first use is accepted again. Discarded copies
+//! are counted for housekeeping telemetry at
+//! [`measurement_ids::UPLINK_DUPLICATES_RECEIVED`].
+//!
+//! # Requirements Traceability
+//! - REQ-SF-001: Command validation (each command executed at most once)
+//! - REQ-NF-004: Fault Tolerance (retransmission is safe to retry)
+//!
+//! [`measurement_ids::UPLINK_DUPLICATES_RECEIVED`]:
+//! crate::telemetry::measurement_ids::UPLINK_DUPLICATES_RECEIVED
+
+use heapless::Deque;
+
+use crate::messaging::Message;
+
+/// Time a command is remembered after it was accepted, ms
+pub const DEDUP_WINDOW_MS: u64 = 60_000;
+
+/// Commands remembered at once by the onboard filter
+pub const DEDUP_CAPACITY: usize = 64;
+
+/// Identity of an uplinked command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CommandKey {
+    /// Originating lane: the command APID of an uplinked packet, or the
+    /// sending component of a routed message
+    pub source: u16,
+    /// Packet sequence count or message ID
+    pub message_id: u64,
+    /// Frame counter of the carrying packet's security header; 0 for packets
+    /// sent unprotected
+    pub auth_counter: u64,
+}
+
+impl CommandKey {
+    /// Create a key
+    pub const fn new(source: u16, message_id: u64, auth_counter: u64) -> Self {
+        Self {
+            source,
+            message_id,
+            auth_counter,
+        }
+    }
+
+    /// Key of a routed command message
+    pub const fn for_message(message: &Message, auth_counter: u64) -> Self {
+        Self::new(message.source.value(), message.id.value(), auth_counter)
+    }
+}
+
+/// Sliding-window filter of recently accepted commands
+///
+/// - **ID**: MOD-DUP-001
+/// - **Requirement**: Execute a retransmitted command at most once and count
+///   the copies discarded (REQ-SF-001).
+/// - **Failure Modes**: A copy arriving after its key has left the window is
+///   accepted again; size the window to the longest retransmission delay.
+/// - **Constraints**: Fixed capacity `N`, no heap allocation; O(N) lookup.
+#[derive(Debug, Clone)]
+pub struct DuplicateFilter<const N: usize> {
+    seen: Deque<(CommandKey, u64), N>,
+    window_ms: u64,
+    duplicates: u32,
+}
+
+impl<const N: usize> Default for DuplicateFilter<N> {
+    /// Filter remembering commands for [`DEDUP_WINDOW_MS`]
+    fn default() -> Self {
+        Self::new(DEDUP_WINDOW_MS)
+    }
+}
+
+impl<const N: usize> DuplicateFilter<N> {
+    /// Create a filter remembering commands for `window_ms`
+    pub const fn new(window_ms: u64) -> Self {
+        Self {
+            seen: Deque::new(),
+            window_ms,
+            duplicates: 0,
+        }
+    }
+
+    /// Time a command is remembered after it was accepted, ms
+    pub const fn window_ms(&self) -> u64 {
+        self.window_ms
+    }
+
+    /// Check a received command, remembering it if it is new
+    ///
+    /// - **ID**: FN-DUP-001
+    /// - **Requirement**: Accept the first copy of a command and discard
+    ///   copies with the same key within the window (REQ-SF-001).
+    /// - **Inputs**:
+    ///   - `key`: Identity of the received command.
+    ///   - `now_ms`: Receive time on a monotonic clock, ms.
+    /// - **Outputs**: `true` if the command should be executed, `false` if it
+    ///   is a duplicate.
+    /// - **Side Effects**: Forgets keys older than the window, and the oldest
+    ///   key when full; counts each duplicate.
+    pub fn accept(&mut self, key: CommandKey, now_ms: u64) -> bool {
+        while let Some(&(_, accepted_ms)) = self.seen.front() {
+            if now_ms.saturating_sub(accepted_ms) < self.window_ms {
+                break;
+            }
+            self.seen.pop_front();
+        }
+
+        if self.seen.iter().any(|(seen, _)| *seen == key) {
+            self.duplicates = self.duplicates.saturating_add(1);
+            return false;
+        }
+
+        if self.seen.is_full() {
+            self.seen.pop_front();
+        }
+        let _ = self.seen.push_back((key, now_ms));
+        true
+    }
+
+    /// Duplicates discarded since the filter was created
+    pub const fn duplicates(&self) -> u32 {
+        self.duplicates
+    }
+
+    /// Commands currently remembered
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no command is remembered
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_discarded_within_window() {
+        let mut filter = DuplicateFilter::<4>::new(1_000);
+        let key = CommandKey::new(0x010, 7, 0);
+
+        assert!(filter.accept(key, 0));
+        assert!(!filter.accept(key, 500));
+        assert_eq!(filter.duplicates(), 1);
+
+        // Any differing field is a different command
+        assert!(filter.accept(CommandKey::new(0x011, 7, 0), 500));
+        assert!(filter.accept(CommandKey::new(0x010, 8, 0), 500));
+        assert!(filter.accept(CommandKey::new(0x010, 7, 1), 500));
+
+        // Once out of the window the key is accepted again
+        assert!(filter.accept(key, 1_000));
+        assert_eq!(filter.duplicates(), 1);
+    }
+
+    #[test]
+    fn test_oldest_key_forgotten_when_full() {
+        let mut filter = DuplicateFilter::<2>::new(DEDUP_WINDOW_MS);
+        for id in 0..3 {
+            assert!(filter.accept(CommandKey::new(0x010, id, 0), id));
+        }
+        assert_eq!(filter.len(), 2);
+        assert!(!filter.accept(CommandKey::new(0x010, 2, 0), 3));
+        assert!(filter.accept(CommandKey::new(0x010, 0, 0), 3));
+    }
+}
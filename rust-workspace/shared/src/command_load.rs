@@ -0,0 +1,625 @@
+//! Time-tagged command loads for batch uplink
+//!
+//! A command load is a file of commands prepared on the ground, each with an
+//! optional release time, that is validated as a whole and uplinked as a single
+//! unit to the onboard scheduler. The scheduler answers with a [`LoadManifest`]
+//! listing which entries were accepted and which were rejected (and why), so
+//! operators never have to guess what actually made it into the onboard table.
+//!
+//! Release times are UTC, in Unix seconds, on both ends. The ground stamps
+//! each load with its UTC time at uplink, and the spacecraft correlates its
+//! mission clock to UTC from that stamp with a [`ClockCorrelation`] before
+//! the load is checked and scheduled.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (command scheduling and automation)
+//! - REQ-SF-001: Command Validation (load-wide constraint checking)
+//! - REQ-IF-002: CCSDS Compliance (load and manifest serialization)
+//! - REQ-PF-001: Command Response Time (release-time ordering)
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::SpaceCommand;
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::messaging::MessagePriority;
+use crate::wire::WireWriter;
+
+/// Maximum number of entries in a single command load
+pub const MAX_LOAD_ENTRIES: usize = 32;
+
+/// Maximum serialized size of a command load (one CCSDS packet data field)
+pub const MAX_LOAD_BYTES: usize = 2048;
+
+/// APID reserved for command-load uplink and manifest downlink packets
+pub const COMMAND_LOAD_APID: u16 = 0x010;
+
+/// A single command within a command load
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandLoadEntry {
+    /// Entry number within the load, unique per load
+    pub sequence: u16,
+    /// Release time in Unix seconds; `None` releases on receipt
+    pub release_time: Option<u64>,
+    /// Sequence count of the released command packet, drawn by the ground
+    /// from the command counter of the entry's APID at uplink
+    #[serde(default)]
+    pub sequence_count: u16,
+    /// Command to execute at release time
+    pub command: SpaceCommand,
+}
+
+impl CommandLoadEntry {
+    /// Command packet the scheduler dispatches when the entry is released
+    ///
+    /// The packet is laid out as the ground uplinks the command directly:
+    /// on the command APID of its priority, with the command ID followed by
+    /// the command serialized as JSON, and the sequence count the ground
+    /// assigned it, so its execution report cannot be mistaken for that of a
+    /// directly uplinked command. The entry number stays in the manifest.
+    pub fn to_command_packet(&self) -> Result<SpacePacket> {
+        let parameters = serde_json::to_vec(&self.command)
+            .map_err(|_| SpaceCommError::invalid_packet("Failed to serialize load entry", None))?;
+        let mut data = WireWriter::<MAX_LOAD_BYTES>::new();
+        data.put(self.command.discriminant())?
+            .put_bytes(&parameters)?;
+
+        SpacePacket::new(
+            PacketType::Command,
+            self.command.priority().command_apid(),
+            self.sequence_count,
+            &data.finish(),
+            None,
+        )
+    }
+}
+
+/// A batch of commands uplinked and validated as a unit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandLoad {
+    /// Operator-assigned load identifier, echoed in the manifest
+    pub load_id: u32,
+    /// UTC time the ground uplinked the load, Unix seconds; 0 until stamped
+    #[serde(default)]
+    pub uplink_time: u64,
+    /// Entries in the order they appear in the load file
+    pub entries: Vec<CommandLoadEntry, MAX_LOAD_ENTRIES>,
+}
+
+/// Reasons an individual load entry can be rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LoadRejectReason {
+    /// Another entry in the load uses the same sequence number
+    DuplicateSequence,
+    /// Release time is earlier than the earliest permitted release
+    ReleaseInPast,
+    /// Release time is beyond the scheduler planning horizon
+    BeyondHorizon,
+    /// Release is too close to the previous timed entry
+    SpacingViolation,
+    /// Emergency commands must be sent directly, not from a load
+    EmergencyNotPermitted,
+    /// `ScheduleOperation` cannot be nested inside a load
+    NestedSchedule,
+    /// Onboard scheduler table has no free slots
+    SchedulerFull,
+}
+
+/// A rejected entry and the constraint it violated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedEntry {
+    /// Sequence number of the rejected entry
+    pub sequence: u16,
+    /// Constraint that rejected it
+    pub reason: LoadRejectReason,
+}
+
+/// Acknowledgment listing accepted and rejected entries of a load
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadManifest {
+    /// Identifier of the load this manifest answers
+    pub load_id: u32,
+    /// Sequence numbers of accepted entries
+    pub accepted: Vec<u16, MAX_LOAD_ENTRIES>,
+    /// Rejected entries with reasons
+    pub rejected: Vec<RejectedEntry, MAX_LOAD_ENTRIES>,
+}
+
+impl LoadManifest {
+    fn new(load_id: u32) -> Self {
+        Self {
+            load_id,
+            accepted: Vec::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    /// True when every entry in the load was accepted
+    pub fn is_fully_accepted(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// Serialize the manifest for downlink
+    pub fn to_bytes(&self) -> Result<Vec<u8, MAX_LOAD_BYTES>> {
+        serialize_bounded(self)
+    }
+
+    /// Parse a manifest received from the spacecraft
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed load manifest", None))
+    }
+
+    /// Wrap the manifest in a Space Packet on [`COMMAND_LOAD_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            COMMAND_LOAD_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes()?,
+            None,
+        )
+    }
+
+    // Capacity matches MAX_LOAD_ENTRIES and each entry lands in exactly one list,
+    // so these pushes cannot overflow for a well-formed load.
+    fn accept(&mut self, sequence: u16) {
+        let _ = self.accepted.push(sequence);
+    }
+
+    fn reject(&mut self, sequence: u16, reason: LoadRejectReason) {
+        let _ = self.rejected.push(RejectedEntry { sequence, reason });
+    }
+}
+
+/// Constraint checker applied to every entry of a command load
+///
+/// The same checker runs on the ground before uplink and onboard before the
+/// entries are committed to the scheduler table, so a load that passes on the
+/// ground is rejected onboard only if spacecraft state has changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadConstraints {
+    /// Longest permitted gap between now and a release time (seconds)
+    pub max_horizon_secs: u64,
+    /// Minimum separation between consecutive timed releases (seconds)
+    pub min_spacing_secs: u64,
+    /// Whether Emergency-priority commands may appear in a load
+    pub allow_emergency: bool,
+}
+
+impl Default for LoadConstraints {
+    fn default() -> Self {
+        Self {
+            max_horizon_secs: 7 * 24 * 3600, // One week of stored commanding
+            min_spacing_secs: 1,
+            allow_emergency: false,
+        }
+    }
+}
+
+impl LoadConstraints {
+    /// Check a single entry in isolation against the current time
+    pub fn check_entry(&self, entry: &CommandLoadEntry, now_secs: u64) -> Option<LoadRejectReason> {
+        if !self.allow_emergency && entry.command.priority() == MessagePriority::Emergency {
+            return Some(LoadRejectReason::EmergencyNotPermitted);
+        }
+
+        if matches!(entry.command, SpaceCommand::ScheduleOperation { .. }) {
+            return Some(LoadRejectReason::NestedSchedule);
+        }
+
+        if let Some(release) = entry.release_time {
+            if release < now_secs {
+                return Some(LoadRejectReason::ReleaseInPast);
+            }
+            if release - now_secs > self.max_horizon_secs {
+                return Some(LoadRejectReason::BeyondHorizon);
+            }
+        }
+
+        None
+    }
+}
+
+impl CommandLoad {
+    /// Create an empty load
+    pub const fn new(load_id: u32) -> Self {
+        Self {
+            load_id,
+            uplink_time: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Append an entry to the load
+    pub fn push(&mut self, entry: CommandLoadEntry) -> Result<()> {
+        self.entries.push(entry).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_LOAD_ENTRIES))
+        })
+    }
+
+    /// Validate every entry of the load against the constraint checker.
+    ///
+    /// - **ID**: FN-CLD-001
+    /// - **Requirement**: Evaluate the whole load before any entry is committed,
+    ///   producing a manifest that accounts for every entry. REQ-SF-001.
+    /// - **Purpose**: Let the ground reject a bad load before spending uplink
+    ///   time on it, and let the spacecraft report exactly what it accepted.
+    /// - **Inputs**:
+    ///   - `constraints`: Constraint set shared by ground and flight software.
+    ///   - `now_secs`: Current Unix time in seconds.
+    /// - **Outputs**: A [`LoadManifest`] in which each entry appears exactly once,
+    ///   in either `accepted` or `rejected`.
+    /// - **Side Effects**: None — read-only.
+    /// - **Failure Modes**: None; constraint violations are reported per entry.
+    /// - **Constraints**: O(n²) in the number of entries (n ≤ 32) for duplicate
+    ///   detection; no heap allocation.
+    /// - **References**: REQ-SF-001; ECSS-E-ST-70-41C §6.11 (time-based scheduling).
+    pub fn validate(&self, constraints: &LoadConstraints, now_secs: u64) -> LoadManifest {
+        let mut manifest = LoadManifest::new(self.load_id);
+
+        for (entry, verdict) in self
+            .entries
+            .iter()
+            .zip(self.verdicts(constraints, now_secs))
+        {
+            match verdict {
+                Some(reason) => manifest.reject(entry.sequence, reason),
+                None => manifest.accept(entry.sequence),
+            }
+        }
+
+        manifest
+    }
+
+    /// Per-entry verdicts in load order; `None` means the entry passed
+    fn verdicts(
+        &self,
+        constraints: &LoadConstraints,
+        now_secs: u64,
+    ) -> Vec<Option<LoadRejectReason>, MAX_LOAD_ENTRIES> {
+        let mut verdicts = Vec::new();
+        let mut last_release: Option<u64> = None;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let duplicate = self.entries[..index]
+                .iter()
+                .any(|earlier| earlier.sequence == entry.sequence);
+
+            let verdict = if duplicate {
+                Some(LoadRejectReason::DuplicateSequence)
+            } else {
+                constraints.check_entry(entry, now_secs)
+            };
+
+            // Spacing is measured only between entries that are themselves accepted
+            let verdict = verdict.or_else(|| match (last_release, entry.release_time) {
+                (Some(prev), Some(release))
+                    if release.abs_diff(prev) < constraints.min_spacing_secs =>
+                {
+                    Some(LoadRejectReason::SpacingViolation)
+                }
+                _ => None,
+            });
+
+            if verdict.is_none() && entry.release_time.is_some() {
+                last_release = entry.release_time;
+            }
+
+            // Same capacity as `entries`, so this cannot overflow
+            let _ = verdicts.push(verdict);
+        }
+
+        verdicts
+    }
+
+    /// Serialize the load for uplink in a single packet data field
+    pub fn to_bytes(&self) -> Result<Vec<u8, MAX_LOAD_BYTES>> {
+        serialize_bounded(self)
+    }
+
+    /// Parse a load received over the uplink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed command load", None))
+    }
+}
+
+/// Onboard correlation of the mission clock to UTC
+///
+/// The mission clock counts seconds from boot. Each command load carries
+/// the ground's UTC time at uplink; the load is received the same second to
+/// within the one-way light time, so the stamp fixes the UTC time of boot.
+/// A later load re-correlates the clock, absorbing oscillator drift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClockCorrelation {
+    utc_at_boot: Option<u64>,
+}
+
+impl ClockCorrelation {
+    /// A clock not yet correlated
+    pub const fn new() -> Self {
+        Self { utc_at_boot: None }
+    }
+
+    /// Correlate to a load received at mission time `mission_secs`
+    ///
+    /// A load without an uplink stamp leaves the correlation unchanged.
+    pub fn correlate(&mut self, load: &CommandLoad, mission_secs: u64) {
+        if load.uplink_time != 0 {
+            self.utc_at_boot = Some(load.uplink_time.saturating_sub(mission_secs));
+        }
+    }
+
+    /// UTC time, Unix seconds, at mission time `mission_secs`
+    ///
+    /// Until the first stamped load the mission clock is all there is, so a
+    /// timed entry of an uncorrelated load is rejected as beyond the horizon
+    /// rather than released at the wrong time.
+    pub const fn utc(&self, mission_secs: u64) -> u64 {
+        match self.utc_at_boot {
+            Some(utc_at_boot) => utc_at_boot + mission_secs,
+            None => mission_secs,
+        }
+    }
+}
+
+/// Onboard table of time-tagged commands released by the scheduler
+pub struct CommandScheduler<const N: usize> {
+    pending: Vec<CommandLoadEntry, N>,
+}
+
+impl<const N: usize> Default for CommandScheduler<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> CommandScheduler<N> {
+    /// Create an empty scheduler
+    pub const fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Accept a command load into the scheduler table.
+    ///
+    /// - **ID**: FN-CLD-002
+    /// - **Requirement**: Commit every entry that passes the constraint checker
+    ///   and report the outcome of every entry in a manifest acknowledgment.
+    /// - **Inputs**: The received `load`, the onboard `constraints`, and the
+    ///   current UTC time `now_secs`, from the [`ClockCorrelation`].
+    /// - **Outputs**: [`LoadManifest`] to downlink on [`COMMAND_LOAD_APID`].
+    /// - **Postconditions**: Accepted entries are pending in release order;
+    ///   rejected entries have no effect on the table.
+    /// - **Failure Modes**: Entries that pass validation but do not fit in the
+    ///   table are rejected with `SchedulerFull` rather than dropped silently.
+    pub fn accept_load(
+        &mut self,
+        load: CommandLoad,
+        constraints: &LoadConstraints,
+        now_secs: u64,
+    ) -> LoadManifest {
+        let verdicts = load.verdicts(constraints, now_secs);
+        let mut manifest = LoadManifest::new(load.load_id);
+
+        for (entry, verdict) in load.entries.into_iter().zip(verdicts) {
+            let sequence = entry.sequence;
+            match verdict {
+                Some(reason) => manifest.reject(sequence, reason),
+                None if self.insert(entry) => manifest.accept(sequence),
+                None => manifest.reject(sequence, LoadRejectReason::SchedulerFull),
+            }
+        }
+
+        manifest
+    }
+
+    /// Remove and return the next entry whose release time has arrived by
+    /// UTC time `now_secs`
+    pub fn pop_due(&mut self, now_secs: u64) -> Option<CommandLoadEntry> {
+        let due = self
+            .pending
+            .first()
+            .is_some_and(|e| e.release_time.is_none_or(|t| t <= now_secs));
+
+        if due {
+            Some(self.pending.remove(0))
+        } else {
+            None
+        }
+    }
+
+    /// Number of entries waiting for release
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Discard all pending entries
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Insert keeping the table sorted by release time (immediate entries first)
+    fn insert(&mut self, entry: CommandLoadEntry) -> bool {
+        if self.pending.is_full() {
+            return false;
+        }
+
+        let key = entry.release_time.unwrap_or(0);
+        let position = self
+            .pending
+            .iter()
+            .position(|e| e.release_time.unwrap_or(0) > key)
+            .unwrap_or(self.pending.len());
+
+        self.pending.insert(position, entry).is_ok()
+    }
+}
+
+fn serialize_bounded<T: Serialize>(value: &T) -> Result<Vec<u8, MAX_LOAD_BYTES>> {
+    let bytes = serde_json::to_vec(value)
+        .map_err(|_| SpaceCommError::invalid_packet("Failed to serialize command load", None))?;
+
+    Vec::from_slice(&bytes).map_err(|_| {
+        SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{EmergencyReason, ReportFormat, StatusType};
+
+    fn status_entry(sequence: u16, release_time: Option<u64>) -> CommandLoadEntry {
+        CommandLoadEntry {
+            sequence,
+            release_time,
+            sequence_count: 0,
+            command: SpaceCommand::SendStatus {
+                status_type: StatusType::SystemHealth,
+                include_diagnostics: false,
+                format: ReportFormat::Binary,
+            },
+        }
+    }
+
+    #[test]
+    fn test_valid_load_fully_accepted() {
+        let mut load = CommandLoad::new(7);
+        load.push(status_entry(1, None)).unwrap();
+        load.push(status_entry(2, Some(1_000))).unwrap();
+        load.push(status_entry(3, Some(1_010))).unwrap();
+
+        let manifest = load.validate(&LoadConstraints::default(), 900);
+        assert!(manifest.is_fully_accepted());
+        assert_eq!(manifest.accepted.len(), 3);
+        assert_eq!(manifest.load_id, 7);
+    }
+
+    #[test]
+    fn test_rejections_are_reported_per_entry() {
+        let mut load = CommandLoad::new(1);
+        load.push(status_entry(1, Some(100))).unwrap(); // in the past
+        load.push(status_entry(2, Some(1_000))).unwrap();
+        load.push(status_entry(2, Some(2_000))).unwrap(); // duplicate
+        load.push(CommandLoadEntry {
+            sequence: 4,
+            release_time: None,
+            sequence_count: 0,
+            command: SpaceCommand::EmergencyAbort {
+                reason: EmergencyReason::SystemFailure,
+                confirmation_code: 0,
+            },
+        })
+        .unwrap();
+
+        let manifest = load.validate(&LoadConstraints::default(), 500);
+        assert_eq!(manifest.accepted.as_slice(), &[2]);
+        assert_eq!(manifest.rejected.len(), 3);
+        assert_eq!(manifest.rejected[0].reason, LoadRejectReason::ReleaseInPast);
+        assert_eq!(
+            manifest.rejected[1].reason,
+            LoadRejectReason::DuplicateSequence
+        );
+        assert_eq!(
+            manifest.rejected[2].reason,
+            LoadRejectReason::EmergencyNotPermitted
+        );
+    }
+
+    #[test]
+    fn test_scheduler_releases_in_time_order() {
+        let mut load = CommandLoad::new(3);
+        load.push(status_entry(1, Some(2_000))).unwrap();
+        load.push(status_entry(2, Some(1_500))).unwrap();
+        load.push(status_entry(3, None)).unwrap();
+
+        let mut scheduler: CommandScheduler<4> = CommandScheduler::new();
+        let manifest = scheduler.accept_load(load, &LoadConstraints::default(), 1_000);
+        assert!(manifest.is_fully_accepted());
+
+        assert_eq!(scheduler.pop_due(1_000).unwrap().sequence, 3);
+        assert!(scheduler.pop_due(1_000).is_none());
+        assert_eq!(scheduler.pop_due(1_600).unwrap().sequence, 2);
+        assert_eq!(scheduler.pop_due(2_000).unwrap().sequence, 1);
+        assert_eq!(scheduler.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_scheduler_full_rejects_overflow() {
+        let mut load = CommandLoad::new(4);
+        load.push(status_entry(1, Some(1_000))).unwrap();
+        load.push(status_entry(2, Some(1_100))).unwrap();
+
+        let mut scheduler: CommandScheduler<1> = CommandScheduler::new();
+        let manifest = scheduler.accept_load(load, &LoadConstraints::default(), 900);
+        assert_eq!(manifest.accepted.as_slice(), &[1]);
+        assert_eq!(manifest.rejected[0].reason, LoadRejectReason::SchedulerFull);
+    }
+
+    #[test]
+    fn test_released_entry_and_manifest_packets() {
+        let mut entry = status_entry(5, None);
+        entry.sequence_count = 0x0123;
+        let packet = entry.to_command_packet().unwrap();
+        assert_eq!(packet.header.packet_type, PacketType::Command);
+        assert_eq!(packet.header.apid, entry.command.priority().command_apid());
+        assert_eq!(packet.header.sequence_count, 0x0123);
+        assert_eq!(
+            crate::wire::command_id(&packet.data),
+            Some(entry.command.discriminant())
+        );
+        let command: SpaceCommand =
+            serde_json::from_slice(&packet.data[crate::wire::COMMAND_ID_LEN..]).unwrap();
+        assert_eq!(command, entry.command);
+
+        let mut load = CommandLoad::new(9);
+        load.push(entry).unwrap();
+        let manifest = load.validate(&LoadConstraints::default(), 0);
+        let packet = manifest.to_packet(0x4001).unwrap();
+        assert_eq!(packet.header.apid, COMMAND_LOAD_APID);
+        assert_eq!(packet.header.sequence_count, 1);
+        assert_eq!(LoadManifest::from_bytes(&packet.data).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_ground_validated_load_released_onboard() {
+        // Ground validates and stamps the load at UTC 1_700_000_000
+        let uplink_time = 1_700_000_000;
+        let mut load = CommandLoad::new(11);
+        load.push(status_entry(1, Some(uplink_time + 600))).unwrap();
+        load.push(status_entry(2, Some(uplink_time + 60))).unwrap();
+        assert!(load
+            .validate(&LoadConstraints::default(), uplink_time)
+            .is_fully_accepted());
+        load.uplink_time = uplink_time;
+
+        // Spacecraft receives it 500 s after boot
+        let received = CommandLoad::from_bytes(&load.to_bytes().unwrap()).unwrap();
+        let mut clock = ClockCorrelation::new();
+        clock.correlate(&received, 500);
+        let mut scheduler: CommandScheduler<4> = CommandScheduler::new();
+        let manifest = scheduler.accept_load(received, &LoadConstraints::default(), clock.utc(500));
+        assert!(manifest.is_fully_accepted());
+
+        assert!(scheduler.pop_due(clock.utc(559)).is_none());
+        assert_eq!(scheduler.pop_due(clock.utc(560)).unwrap().sequence, 2);
+        assert!(scheduler.pop_due(clock.utc(1_099)).is_none());
+        assert_eq!(scheduler.pop_due(clock.utc(1_100)).unwrap().sequence, 1);
+    }
+
+    #[test]
+    fn test_uncorrelated_clock_rejects_utc_entries() {
+        let mut load = CommandLoad::new(12);
+        load.push(status_entry(1, Some(1_700_000_600))).unwrap();
+
+        let mut clock = ClockCorrelation::new();
+        clock.correlate(&load, 500);
+        let mut scheduler: CommandScheduler<4> = CommandScheduler::new();
+        let manifest = scheduler.accept_load(load, &LoadConstraints::default(), clock.utc(500));
+        assert_eq!(manifest.rejected[0].reason, LoadRejectReason::BeyondHorizon);
+    }
+}
@@ -10,8 +10,11 @@
 //! - REQ-FN-001: Priority-based command classification (Emergency, Critical, High, Medium, Low)
 //! - REQ-FN-002: Emergency protocol commands (EmergencyAbort, EmergencyHalt, ActivateSafeMode)
 //! - REQ-FN-003: Critical system commands (AbortMission, CollisionAvoidance, AttitudeControl)
-//! - REQ-FN-004: High priority operations (UpdateOrbit, ReconfigureComm, Deploy)
-//! - REQ-FN-005: Medium priority commands (RequestTelemetry, UpdateConfig, CalibrateInstrument)
+//! - REQ-FN-004: High priority operations (UpdateOrbit, ReconfigureComm, Deploy,
+//!   SetMissionPhase, RotateKeys, SetAutonomyRule)
+//! - REQ-FN-005: Medium priority commands (RequestTelemetry, UpdateConfig, CalibrateInstrument,
+//!   DumpMemory, DwellSample, LoopbackTest, ListFiles, DeleteFile, SetRetention,
+//!   SetDownlinkPolicy)
 //! - REQ-FN-006: Low priority operations (SendStatus, UpdateTime, PerformMaintenance)
 //! - REQ-FN-007: Multi-band communication support with band selection
 //! - REQ-PF-001: Command response time requirements (1ms-10s based on priority)
@@ -37,8 +40,18 @@ use core::fmt;
 use heapless::{String, Vec};
 use serde::{Deserialize, Serialize};
 
+use crate::autonomy::SET_AUTONOMY_RULE_COMMAND_ID;
+use crate::diagnostics::{DwellSource, DUMP_MEMORY_COMMAND_ID, DWELL_SAMPLE_COMMAND_ID};
 use crate::error::{Result, SpaceCommError};
+use crate::link_config::{DirectionalLink, LinkDirection};
+use crate::link_forecast::{DownlinkPolicy, SET_DOWNLINK_POLICY_COMMAND_ID};
+use crate::loopback::{LOOPBACK_TEST_COMMAND_ID, MAX_LOOPBACK_PAYLOAD};
+use crate::mass_memory::{
+    DELETE_FILE_COMMAND_ID, LIST_FILES_COMMAND_ID, SET_RETENTION_COMMAND_ID, STORE_DATA_COMMAND_ID,
+};
 use crate::messaging::{Message, MessagePayload, MessagePriority};
+use crate::mission_phase::{MissionPhase, SET_MISSION_PHASE_COMMAND_ID};
+use crate::security::{SecurityService, ROTATE_KEYS_COMMAND_ID};
 use crate::types::{BandType, ComponentId, MessageId};
 
 /// Comprehensive space mission command types with NASA-standard classifications
@@ -52,6 +65,7 @@ use crate::types::{BandType, ComponentId, MessageId};
 /// - REQ-PF-001: Response time constraints (1ms-10s based on priority)
 /// - REQ-SF-001: Command validation and confirmation requirements
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SpaceCommand {
     // ==================== EMERGENCY PRIORITY COMMANDS ====================
     // REQ-FN-002: Emergency protocol commands for immediate response
@@ -67,6 +81,7 @@ pub enum SpaceCommand {
     /// REQ-FN-002: Emergency halt with subsystem specification
     /// REQ-SF-002: Override protection for critical safety functions
     EmergencyHalt {
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<SubsystemId>", length(max = 16)))]
         subsystems: Vec<SubsystemId, 16>,
         override_code: u64,
     },
@@ -83,6 +98,7 @@ pub enum SpaceCommand {
     /// REQ-FN-002: Emergency power management protocol
     /// REQ-NF-004: Battery threshold monitoring and protection
     EmergencyPowerDown {
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<SubsystemId>", length(max = 8)))]
         systems_to_preserve: Vec<SubsystemId, 8>,
         battery_threshold_percent: u8,
     },
@@ -102,6 +118,7 @@ pub enum SpaceCommand {
     /// REQ-SF-001: Command validation for mission-critical operations
     AbortMission {
         mission_id: u32,
+        #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 128)))]
         abort_reason: String<128>,
         preserve_data: bool,
     },
@@ -139,6 +156,7 @@ pub enum SpaceCommand {
     /// REQ-FN-003: Communication redundancy and failover
     /// REQ-FN-007: Multi-band communication support
     SwitchCommBackup {
+        #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 64)))]
         primary_failure: String<64>,
         backup_band: BandType,
         power_level_percent: u8,
@@ -167,14 +185,16 @@ pub enum SpaceCommand {
         true_anomaly: f64,  // degrees
     },
 
-    /// Reconfigure communication band settings
+    /// Reconfigure the band settings of one link direction
     /// REQ-FN-004: Communication system reconfiguration
-    /// REQ-FN-007: Multi-band frequency management
+    /// REQ-FN-007: Multi-band frequency management, uplink and downlink independent
     /// REQ-IF-002: CCSDS-compliant modulation and error correction
     ReconfigureComm {
+        direction: LinkDirection,
         band: BandType,
         frequency_hz: u64,
-        power_level: u8, // 0-100%
+        power_level: u8,    // 0-100%
+        data_rate_bps: u64, // bits per second
         modulation: ModulationType,
         error_correction: bool,
     },
@@ -194,6 +214,7 @@ pub enum SpaceCommand {
     /// REQ-PF-002: Data rate management and performance
     StartDataCollection {
         instrument: InstrumentId,
+        #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 32)))]
         collection_mode: String<32>,
         duration_seconds: u32,
         data_rate_mbps: f32,
@@ -206,9 +227,34 @@ pub enum SpaceCommand {
         solar_panel_orientation: [f32; 3],
         battery_mode: BatteryMode,
         power_budget_watts: f32,
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<SubsystemId>", length(max = 16)))]
         load_shedding_priority: Vec<SubsystemId, 16>,
     },
 
+    /// Set the link security policy for one virtual channel
+    /// REQ-FN-004: Communication system reconfiguration
+    /// REQ-SC-001: Per-virtual-channel link security policy
+    SetVcSecurityPolicy {
+        virtual_channel: u8,
+        service: SecurityService,
+        key_id: u8,
+    },
+
+    /// Move the mission to a new phase
+    /// REQ-FN-004: High priority operations
+    /// REQ-SF-001: Phase selects the commands accepted from then on
+    SetMissionPhase { phase: MissionPhase },
+
+    /// Move a link security key slot to a new key generation
+    /// REQ-FN-004: Communication system reconfiguration
+    /// REQ-SC-003: Key rotation without uplinking key material
+    RotateKeys { key_id: u8, generation: u32 },
+
+    /// Enable or disable an uploaded onboard autonomy rule
+    /// REQ-FN-004: High priority operations
+    /// REQ-SF-001: Ground control over which autonomous actions may run
+    SetAutonomyRule { rule_id: u16, enabled: bool },
+
     // ==================== MEDIUM PRIORITY COMMANDS ====================
     // REQ-FN-005: Medium priority commands for normal operations
     /// Request telemetry data
@@ -225,7 +271,9 @@ pub enum SpaceCommand {
     /// REQ-FN-005: Configuration management and updates
     /// REQ-SF-001: Configuration validation and backup
     UpdateConfig {
+        #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 32)))]
         config_id: String<32>,
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<u8>", length(max = 512)))]
         parameters: Vec<u8, 512>,
         apply_immediately: bool,
         backup_current: bool,
@@ -237,6 +285,7 @@ pub enum SpaceCommand {
     CalibrateInstrument {
         instrument: InstrumentId,
         calibration_type: CalibrationType,
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<f32>", length(max = 16)))]
         reference_values: Vec<f32, 16>,
         temperature_compensation: bool,
     },
@@ -259,8 +308,56 @@ pub enum SpaceCommand {
         storage_location: StorageLocation,
         compression_level: u8,
         encryption: bool,
+        science_value: u8, // higher is worth more; orders HighestValueFirst downlinks
+    },
+
+    /// Dump onboard memory to the ground
+    /// REQ-FN-005: Diagnostics on request
+    /// REQ-IF-002: Dump downlinked in CCSDS segments
+    DumpMemory { address: u32, length: u32 },
+
+    /// Sample a memory word or measurement at a high rate for a bounded time
+    /// REQ-FN-005: Diagnostics on request
+    /// REQ-PF-002: Sample interval down to 10 ms
+    DwellSample {
+        source: DwellSource,
+        target: u32,
+        interval_ms: u16,
+        duration_s: u16,
+    },
+
+    /// Echo a payload back over a chosen band with onboard timestamps
+    /// REQ-FN-007: End-to-end check of the command path on a band
+    /// REQ-PF-001: Uplink and downlink delays measured
+    LoopbackTest {
+        test_id: u16,
+        band: BandType,
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<u8>", length(max = 64)))]
+        payload: Vec<u8, MAX_LOOPBACK_PAYLOAD>,
     },
 
+    /// Downlink a listing of one mass memory directory
+    /// REQ-FN-005: Data storage and management
+    ListFiles { data_type: DataType },
+
+    /// Delete one file from the mass memory
+    /// REQ-FN-005: Data storage and management
+    /// REQ-SF-001: Deletion confirmed, naming the file's directory
+    DeleteFile { data_type: DataType, file_id: u32 },
+
+    /// Keep the files of one mass memory directory for a limited time
+    /// REQ-FN-005: Data storage and management
+    /// REQ-NF-002: Memory freed as files expire
+    SetRetention {
+        data_type: DataType,
+        retention_s: u32, // 0 keeps files until deleted
+    },
+
+    /// Choose the order stored products are downlinked in
+    /// REQ-FN-005: Data storage and management
+    /// REQ-FN-001: Downlink ordered by priority, age or science value
+    SetDownlinkPolicy { policy: DownlinkPolicy },
+
     // ==================== LOW PRIORITY COMMANDS ====================
     // REQ-FN-006: Low priority operations for housekeeping
     /// Send status report
@@ -296,7 +393,9 @@ pub enum SpaceCommand {
     LogEvent {
         event_type: EventType,
         severity: EventSeverity,
+        #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 256)))]
         description: String<256>,
+        #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<u8>", length(max = 128)))]
         associated_data: Vec<u8, 128>,
     },
 }
@@ -307,6 +406,7 @@ pub enum SpaceCommand {
 /// Emergency reasons for abort and halt commands
 /// REQ-FN-002: Emergency situation classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EmergencyReason {
     SystemFailure,
     PowerCritical,
@@ -321,6 +421,7 @@ pub enum EmergencyReason {
 /// Safe mode levels for emergency operation
 /// REQ-FN-002: Safe mode hierarchy and system preservation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SafeModeLevel {
     Level1, // Minimal operations, communications only
     Level2, // Basic attitude control + communications
@@ -331,6 +432,7 @@ pub enum SafeModeLevel {
 /// Satellite subsystem identifiers
 /// REQ-FN-003: Subsystem control and management
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SubsystemId {
     Power,
     Communications,
@@ -353,6 +455,7 @@ pub enum SubsystemId {
 /// Orbital maneuver types for collision avoidance and orbit changes
 /// REQ-FN-003: Orbital mechanics and collision avoidance
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ManeuverType {
     OrbitRaise,
     OrbitLower,
@@ -366,6 +469,7 @@ pub enum ManeuverType {
 /// Attitude control modes for spacecraft orientation
 /// REQ-FN-003: Attitude control system capabilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AttitudeMode {
     Inertial,
     EarthPointing,
@@ -378,6 +482,7 @@ pub enum AttitudeMode {
 /// System reset types for component recovery
 /// REQ-FN-003: System reset and recovery mechanisms
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ResetType {
     SoftReset,
     HardReset,
@@ -390,6 +495,7 @@ pub enum ResetType {
 /// REQ-FN-007: Multi-band communication modulation support
 /// REQ-IF-002: CCSDS-compliant modulation schemes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ModulationType {
     BPSK,
     QPSK,
@@ -402,6 +508,7 @@ pub enum ModulationType {
 /// Deployable component types
 /// REQ-FN-004: Deployable mechanism control
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum DeployableType {
     SolarPanel,
     Antenna,
@@ -414,6 +521,7 @@ pub enum DeployableType {
 /// Science instrument identifiers
 /// REQ-FN-004: Science instrument control and management
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum InstrumentId {
     Camera,
     Spectrometer,
@@ -430,6 +538,7 @@ pub enum InstrumentId {
 /// Battery operation modes
 /// REQ-NF-004: Power management and battery control
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BatteryMode {
     Charging,
     Discharging,
@@ -441,6 +550,7 @@ pub enum BatteryMode {
 /// Telemetry data types for system monitoring
 /// REQ-FN-005: Telemetry data classification and collection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TelemetryType {
     Health,
     Position,
@@ -457,6 +567,7 @@ pub enum TelemetryType {
 /// Instrument calibration types
 /// REQ-FN-005: Instrument calibration procedures
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CalibrationType {
     Bias,
     Scale,
@@ -469,6 +580,7 @@ pub enum CalibrationType {
 /// Data storage categories
 /// REQ-FN-005: Data classification and storage management
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum DataType {
     Telemetry,
     Science,
@@ -481,6 +593,7 @@ pub enum DataType {
 /// Storage location options
 /// REQ-FN-005: Storage hierarchy and management
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StorageLocation {
     VolatileMemory,
     NonVolatileMemory,
@@ -491,6 +604,7 @@ pub enum StorageLocation {
 /// Status report types
 /// REQ-FN-006: System status monitoring and reporting
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StatusType {
     SystemHealth,
     MissionStatus,
@@ -503,6 +617,7 @@ pub enum StatusType {
 /// Report output formats
 /// REQ-IF-002: Data formatting and transmission standards
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ReportFormat {
     Binary,
     Json,
@@ -513,6 +628,7 @@ pub enum ReportFormat {
 /// Time synchronization sources
 /// REQ-FN-006: Time accuracy and synchronization
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TimeSource {
     GroundStation,
     Gps,
@@ -524,6 +640,7 @@ pub enum TimeSource {
 /// Maintenance operation types
 /// REQ-FN-006: Preventive maintenance procedures
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MaintenanceType {
     SystemCheck,
     Calibration,
@@ -536,6 +653,7 @@ pub enum MaintenanceType {
 /// System event types for logging
 /// REQ-FN-006: Event classification and logging
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EventType {
     SystemStart,
     SystemShutdown,
@@ -552,6 +670,7 @@ pub enum EventType {
 /// Event severity levels
 /// REQ-FN-006: Event prioritization and severity classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EventSeverity {
     Critical,
     High,
@@ -598,6 +717,10 @@ impl SpaceCommand {
             SpaceCommand::Deploy { .. } => MessagePriority::High,
             SpaceCommand::StartDataCollection { .. } => MessagePriority::High,
             SpaceCommand::ConfigurePower { .. } => MessagePriority::High,
+            SpaceCommand::SetVcSecurityPolicy { .. } => MessagePriority::High,
+            SpaceCommand::SetMissionPhase { .. } => MessagePriority::High,
+            SpaceCommand::RotateKeys { .. } => MessagePriority::High,
+            SpaceCommand::SetAutonomyRule { .. } => MessagePriority::High,
 
             // Medium Priority - Normal operations (REQ-FN-005)
             // Must execute within 1 second for operational efficiency
@@ -606,6 +729,13 @@ impl SpaceCommand {
             SpaceCommand::CalibrateInstrument { .. } => MessagePriority::Medium,
             SpaceCommand::ScheduleOperation { .. } => MessagePriority::Medium,
             SpaceCommand::StoreData { .. } => MessagePriority::Medium,
+            SpaceCommand::DumpMemory { .. } => MessagePriority::Medium,
+            SpaceCommand::DwellSample { .. } => MessagePriority::Medium,
+            SpaceCommand::LoopbackTest { .. } => MessagePriority::Medium,
+            SpaceCommand::ListFiles { .. } => MessagePriority::Medium,
+            SpaceCommand::DeleteFile { .. } => MessagePriority::Medium,
+            SpaceCommand::SetRetention { .. } => MessagePriority::Medium,
+            SpaceCommand::SetDownlinkPolicy { .. } => MessagePriority::Medium,
 
             // Low Priority - Routine operations (REQ-FN-006)
             // Must execute within 10 seconds for housekeeping
@@ -637,6 +767,30 @@ impl SpaceCommand {
         }
     }
 
+    /// Get the link direction settings a `ReconfigureComm` command applies
+    ///
+    /// Requirements Fulfilled:
+    /// - REQ-FN-007: Independent uplink and downlink reconfiguration
+    ///
+    /// Returns:
+    /// Direction and its new band, power level and data rate, or `None` for
+    /// any other command
+    pub fn link_settings(&self) -> Option<(LinkDirection, DirectionalLink)> {
+        match *self {
+            SpaceCommand::ReconfigureComm {
+                direction,
+                band,
+                power_level,
+                data_rate_bps,
+                ..
+            } => Some((
+                direction,
+                DirectionalLink::new(band, power_level, data_rate_bps),
+            )),
+            _ => None,
+        }
+    }
+
     /// Get maximum allowed execution time in milliseconds
     ///
     /// Defines hard real-time constraints for command execution based on
@@ -649,13 +803,7 @@ impl SpaceCommand {
     /// Returns:
     /// Maximum execution time in milliseconds
     pub fn max_execution_time_ms(&self) -> u32 {
-        match self.priority() {
-            MessagePriority::Emergency => 1, // Must execute within 1ms (REQ-PF-001)
-            MessagePriority::Critical => 10, // Must execute within 10ms
-            MessagePriority::High => 100,    // Must execute within 100ms
-            MessagePriority::Medium => 1000, // Must execute within 1 second
-            MessagePriority::Low => 10000,   // Must execute within 10 seconds
-        }
+        self.priority().max_execution_time_ms()
     }
 
     /// Check if command requires confirmation before execution
@@ -678,7 +826,12 @@ impl SpaceCommand {
                 | SpaceCommand::AbortMission { .. }     // REQ-SF-001: Mission abort confirmation
                 | SpaceCommand::CollisionAvoidance { .. } // REQ-SF-001: Maneuver confirmation
                 | SpaceCommand::ResetSystem { .. }      // REQ-SF-001: Reset confirmation
-                | SpaceCommand::Deploy { .. } // REQ-SF-001: Deployment confirmation
+                | SpaceCommand::Deploy { .. }           // REQ-SF-001: Deployment confirmation
+                | SpaceCommand::SetVcSecurityPolicy { .. } // REQ-SC-001: Security downgrade confirmation
+                | SpaceCommand::SetMissionPhase { .. }  // REQ-SF-001: Phase change confirmation
+                | SpaceCommand::RotateKeys { .. }       // REQ-SC-003: Key rotation confirmation
+                | SpaceCommand::SetAutonomyRule { .. }  // REQ-SF-001: Autonomy change confirmation
+                | SpaceCommand::DeleteFile { .. }       // REQ-SF-001: Deletion confirmation
         )
     }
 
@@ -715,11 +868,22 @@ impl SpaceCommand {
             SpaceCommand::Deploy { .. } => "Deploy solar panel or antenna",
             SpaceCommand::StartDataCollection { .. } => "Start science data collection",
             SpaceCommand::ConfigurePower { .. } => "Configure power management",
+            SpaceCommand::SetVcSecurityPolicy { .. } => "Set virtual channel security policy",
+            SpaceCommand::SetMissionPhase { .. } => "Set mission phase",
+            SpaceCommand::RotateKeys { .. } => "Rotate link security keys",
+            SpaceCommand::SetAutonomyRule { .. } => "Enable or disable autonomy rule",
             SpaceCommand::RequestTelemetry { .. } => "Request telemetry data",
             SpaceCommand::UpdateConfig { .. } => "Update software configuration",
             SpaceCommand::CalibrateInstrument { .. } => "Calibrate instrument or sensor",
             SpaceCommand::ScheduleOperation { .. } => "Schedule future operation",
             SpaceCommand::StoreData { .. } => "Store data to onboard memory",
+            SpaceCommand::DumpMemory { .. } => "Dump onboard memory",
+            SpaceCommand::DwellSample { .. } => "Dwell on memory word or measurement",
+            SpaceCommand::LoopbackTest { .. } => "Echo payload for command path self-test",
+            SpaceCommand::ListFiles { .. } => "List mass memory directory",
+            SpaceCommand::DeleteFile { .. } => "Delete mass memory file",
+            SpaceCommand::SetRetention { .. } => "Set mass memory directory retention",
+            SpaceCommand::SetDownlinkPolicy { .. } => "Set recorder downlink policy",
             SpaceCommand::SendStatus { .. } => "Send status report",
             SpaceCommand::UpdateTime { .. } => "Update time synchronization",
             SpaceCommand::PerformMaintenance { .. } => "Perform routine maintenance",
@@ -944,13 +1108,24 @@ impl SpaceCommand {
             SpaceCommand::Deploy { .. } => 0x0022,
             SpaceCommand::StartDataCollection { .. } => 0x0023,
             SpaceCommand::ConfigurePower { .. } => 0x0024,
+            SpaceCommand::SetVcSecurityPolicy { .. } => 0x0025,
+            SpaceCommand::SetMissionPhase { .. } => SET_MISSION_PHASE_COMMAND_ID,
+            SpaceCommand::RotateKeys { .. } => ROTATE_KEYS_COMMAND_ID,
+            SpaceCommand::SetAutonomyRule { .. } => SET_AUTONOMY_RULE_COMMAND_ID,
 
             // Medium Priority Commands (0x0030-0x003F) - REQ-FN-005
             SpaceCommand::RequestTelemetry { .. } => 0x0030,
             SpaceCommand::UpdateConfig { .. } => 0x0031,
             SpaceCommand::CalibrateInstrument { .. } => 0x0032,
             SpaceCommand::ScheduleOperation { .. } => 0x0033,
-            SpaceCommand::StoreData { .. } => 0x0034,
+            SpaceCommand::StoreData { .. } => STORE_DATA_COMMAND_ID,
+            SpaceCommand::DumpMemory { .. } => DUMP_MEMORY_COMMAND_ID,
+            SpaceCommand::DwellSample { .. } => DWELL_SAMPLE_COMMAND_ID,
+            SpaceCommand::LoopbackTest { .. } => LOOPBACK_TEST_COMMAND_ID,
+            SpaceCommand::ListFiles { .. } => LIST_FILES_COMMAND_ID,
+            SpaceCommand::DeleteFile { .. } => DELETE_FILE_COMMAND_ID,
+            SpaceCommand::SetRetention { .. } => SET_RETENTION_COMMAND_ID,
+            SpaceCommand::SetDownlinkPolicy { .. } => SET_DOWNLINK_POLICY_COMMAND_ID,
 
             // Low Priority Commands (0x0040-0x004F) - REQ-FN-006
             SpaceCommand::SendStatus { .. } => 0x0040,
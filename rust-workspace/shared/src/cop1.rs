@@ -0,0 +1,1018 @@
+//! COP-1 retransmission control of the command uplink
+//!
+//! Communications Operation Procedure-1 (CCSDS 232.1-B-2) delivers Type-AD
+//! TC Transfer Frames ([`crate::ccsds::TcTransferFrame`]) in order and
+//! exactly once. The ground runs the Frame Operation Procedure, [`Fop`]:
+//! it numbers each frame with N(S), keeps every frame sent but not yet
+//! acknowledged in a sliding window, and retransmits the window when the
+//! satellite asks for it or when timer T1 expires, giving up with an alert
+//! after the transmission limit. The satellite runs the Frame Acceptance and
+//! Reporting Mechanism, [`Farm`]: it accepts the frame numbered V(R) and
+//! discards the rest, and reports V(R) and its retransmit, wait and lockout
+//! flags in a Communications Link Control Word, [`Clcw`], downlinked on
+//! [`CLCW_APID`] after every frame.
+//!
+//! Type-BD frames bypass the sequence checks. Type-BC frames carry the
+//! control commands of [`TcControlCommand`]: Unlock clears a FARM lockout,
+//! and Set V(R) resynchronises both ends.
+//!
+//! # CLCW
+//! | Bits | Field                                |
+//! |------|--------------------------------------|
+//! | 1    | Control word type, `0`               |
+//! | 2    | Version, `00`                        |
+//! | 3    | Status, `000`                        |
+//! | 2    | COP in effect, `01` (COP-1)          |
+//! | 6    | Virtual channel ID                   |
+//! | 2    | Spare                                |
+//! | 1    | No RF available                      |
+//! | 1    | No bit lock                          |
+//! | 1    | Lockout                              |
+//! | 1    | Wait                                 |
+//! | 1    | Retransmit                           |
+//! | 2    | FARM-B counter                       |
+//! | 1    | Spare                                |
+//! | 8    | Report value N(R)                    |
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (TC Transfer Frames under COP-1)
+//! - REQ-NF-004: Fault Tolerance (lost command frames retransmitted)
+//! - REQ-SF-001: Command validation (each frame accepted once, in order)
+
+use heapless::{Deque, Vec};
+
+use crate::ccsds::{PacketType, SpacePacket, TcFrameType, TcTransferFrame};
+use crate::error::{Result, SpaceCommError};
+use crate::security::virtual_channels;
+use crate::wire::{WireReader, WireWriter};
+use space_comms_req::req;
+
+/// APID of the CLCW packet downlinked after each received frame
+pub const CLCW_APID: u16 = 0x104;
+
+/// CLCW length, bytes
+pub const CLCW_LEN: usize = 4;
+
+/// Spacecraft ID the command uplink is framed for
+pub const SPACECRAFT_ID: u16 = 0x2A5;
+
+/// Largest FOP-1 sliding window, frames
+pub const FOP_MAX_WINDOW: usize = 16;
+
+/// Default FOP-1 sliding window K, frames
+pub const DEFAULT_FOP_WINDOW: u8 = 8;
+
+/// Default FARM-1 sliding window width W, frame sequence numbers
+pub const DEFAULT_FARM_WINDOW: u8 = 16;
+
+/// Default retransmission timer T1, ms
+pub const DEFAULT_TIMER_MS: u64 = 2_000;
+
+/// Default transmission limit: transmissions of a frame before the alert
+pub const DEFAULT_TRANSMISSION_LIMIT: u8 = 3;
+
+/// Set V(R) control command leading octets (CCSDS 232.0-B-4 §4.1.3.3)
+const SET_VR_PREFIX: [u8; 2] = [0x82, 0x00];
+
+/// COP-1 control command carried by a Type-BC frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcControlCommand {
+    /// Clear the FARM-1 lockout
+    Unlock,
+    /// Set the FARM-1 receiver frame sequence number V(R)
+    SetVr(u8),
+}
+
+impl TcControlCommand {
+    /// Data field of the Type-BC frame carrying the command
+    pub fn to_data(self) -> Vec<u8, 3> {
+        let mut data = Vec::new();
+        let _ = match self {
+            Self::Unlock => data.extend_from_slice(&[0x00]),
+            Self::SetVr(value) => data
+                .extend_from_slice(&SET_VR_PREFIX)
+                .and_then(|_| data.push(value).map_err(|_| ())),
+        };
+        data
+    }
+
+    /// Parse a Type-BC data field; `None` for anything else
+    pub fn from_data(data: &[u8]) -> Option<Self> {
+        match data {
+            [0x00] => Some(Self::Unlock),
+            [0x82, 0x00, value] => Some(Self::SetVr(*value)),
+            _ => None,
+        }
+    }
+}
+
+/// Communications Link Control Word reported by FARM-1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Clcw {
+    /// Virtual channel the report is for
+    pub virtual_channel: u8,
+    /// No RF available on the uplink
+    pub no_rf_available: bool,
+    /// No bit lock on the uplink
+    pub no_bit_lock: bool,
+    /// FARM-1 is in lockout; AD frames are discarded until Unlock
+    pub lockout: bool,
+    /// FARM-1 has no buffer for the next AD frame
+    pub wait: bool,
+    /// An AD frame was discarded; resend from N(R)
+    pub retransmit: bool,
+    /// Type-BD and BC frames accepted, modulo 4
+    pub farm_b_counter: u8,
+    /// Report value N(R): the next AD frame sequence number expected
+    pub report_value: u8,
+}
+
+impl Clcw {
+    /// Encode the word in its 4 bytes
+    pub fn to_bytes(&self) -> [u8; CLCW_LEN] {
+        let flags = u8::from(self.no_rf_available) << 7
+            | u8::from(self.no_bit_lock) << 6
+            | u8::from(self.lockout) << 5
+            | u8::from(self.wait) << 4
+            | u8::from(self.retransmit) << 3
+            | (self.farm_b_counter & 0x03) << 1;
+        [
+            0b0000_0001,
+            (self.virtual_channel & 0x3F) << 2,
+            flags,
+            self.report_value,
+        ]
+    }
+
+    /// Decode a word; an error for anything but a COP-1 CLCW
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = WireReader::new(bytes);
+        let first = reader.get::<u8>()?;
+        let vcid = reader.get::<u8>()?;
+        let flags = reader.get::<u8>()?;
+        let report_value = reader.get::<u8>()?;
+        if first != 0b0000_0001 {
+            return Err(SpaceCommError::invalid_packet(
+                "Not a COP-1 CLCW",
+                Some(u32::from(first)),
+            ));
+        }
+
+        Ok(Self {
+            virtual_channel: vcid >> 2,
+            no_rf_available: flags & 0x80 != 0,
+            no_bit_lock: flags & 0x40 != 0,
+            lockout: flags & 0x20 != 0,
+            wait: flags & 0x10 != 0,
+            retransmit: flags & 0x08 != 0,
+            farm_b_counter: (flags >> 1) & 0x03,
+            report_value,
+        })
+    }
+
+    /// Telemetry packet carrying the word on [`CLCW_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        let mut writer = WireWriter::<CLCW_LEN>::new();
+        writer.put_bytes(&self.to_bytes())?;
+        SpacePacket::new(
+            PacketType::Telemetry,
+            CLCW_APID,
+            sequence_count & 0x3FFF,
+            &writer.finish(),
+            None,
+        )
+    }
+}
+
+/// FARM-1 state (CCSDS 232.1-B-2 §6.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FarmState {
+    /// Accepting AD frames in sequence
+    Open,
+    /// No buffer for the next AD frame
+    Wait,
+    /// An AD frame outside both windows arrived; AD frames are discarded
+    Lockout,
+}
+
+/// What FARM-1 did with a received frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FarmVerdict {
+    /// Deliver the frame's data field
+    Accept,
+    /// Drop the frame; the CLCW tells the ground why
+    Discard,
+    /// A control command was carried out; nothing to deliver
+    Control,
+}
+
+/// Frame Acceptance and Reporting Mechanism of the satellite
+///
+/// - **ID**: MOD-COP-001
+/// - **Requirement**: Deliver Type-AD frames exactly once and in order, and
+///   report what is expected next (REQ-SF-001, REQ-IF-002).
+/// - **Failure Modes**: An AD frame more than half the window away from
+///   V(R) locks the FARM out until an Unlock control command.
+/// - **Constraints**: No heap allocation; holds no frames.
+#[derive(Debug, Clone)]
+pub struct Farm {
+    spacecraft_id: u16,
+    virtual_channel: u8,
+    window: u8,
+    state: FarmState,
+    receiver_sequence: u8,
+    retransmit: bool,
+    farm_b_counter: u8,
+}
+
+impl Default for Farm {
+    /// FARM for [`SPACECRAFT_ID`] on the command virtual channel, with the
+    /// default window
+    fn default() -> Self {
+        Self {
+            spacecraft_id: SPACECRAFT_ID,
+            virtual_channel: virtual_channels::COMMAND,
+            window: DEFAULT_FARM_WINDOW,
+            state: FarmState::Open,
+            receiver_sequence: 0,
+            retransmit: false,
+            farm_b_counter: 0,
+        }
+    }
+}
+
+impl Farm {
+    /// Create a FARM, open and expecting frame 0
+    ///
+    /// - **ID**: FN-COP-001
+    /// - **Inputs**:
+    ///   - `spacecraft_id`, `virtual_channel`: Frames for anything else are
+    ///     discarded without a report.
+    ///   - `window`: Sliding window width W, even, 2–254; half is the
+    ///     positive window, half the negative.
+    /// - **Failure Modes**: `ConfigurationError` for an odd or out of range
+    ///   window.
+    pub fn new(spacecraft_id: u16, virtual_channel: u8, window: u8) -> Result<Self> {
+        if !(2..=254).contains(&window) || window & 1 != 0 {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "farm_window",
+                value: "<window>",
+                reason: "FARM-1 window width must be even, from 2 to 254",
+            });
+        }
+
+        Ok(Self {
+            spacecraft_id,
+            virtual_channel,
+            window,
+            state: FarmState::Open,
+            receiver_sequence: 0,
+            retransmit: false,
+            farm_b_counter: 0,
+        })
+    }
+
+    /// Current state
+    pub fn state(&self) -> FarmState {
+        self.state
+    }
+
+    /// Receiver frame sequence number V(R): the next AD frame expected
+    pub fn receiver_sequence(&self) -> u8 {
+        self.receiver_sequence
+    }
+
+    /// Check a received frame
+    ///
+    /// - **ID**: FN-COP-002
+    /// - **Requirement**: Apply the FARM-1 state table to a received frame
+    ///   (CCSDS 232.1-B-2 §6.3).
+    /// - **Inputs**:
+    ///   - `frame`: Frame that passed its FECF check.
+    ///   - `buffer_available`: Whether an accepted frame can be delivered;
+    ///     a FARM in Wait reopens once it is.
+    /// - **Outputs**: Whether to deliver the frame.
+    /// - **Side Effects**: Advances V(R) on an accepted AD frame; sets the
+    ///   retransmit flag on a gap or a frame without buffer; enters lockout
+    ///   on a frame outside both windows; counts BD and BC frames.
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn receive(&mut self, frame: &TcTransferFrame, buffer_available: bool) -> FarmVerdict {
+        if frame.spacecraft_id != self.spacecraft_id
+            || frame.virtual_channel != self.virtual_channel
+        {
+            return FarmVerdict::Discard;
+        }
+        if self.state == FarmState::Wait && buffer_available {
+            self.state = FarmState::Open;
+        }
+
+        match frame.frame_type {
+            TcFrameType::Bd if buffer_available => {
+                self.count_bypass();
+                FarmVerdict::Accept
+            }
+            TcFrameType::Bd => FarmVerdict::Discard,
+            TcFrameType::Bc => match TcControlCommand::from_data(&frame.data) {
+                Some(TcControlCommand::Unlock) => {
+                    self.state = FarmState::Open;
+                    self.retransmit = false;
+                    self.count_bypass();
+                    FarmVerdict::Control
+                }
+                Some(TcControlCommand::SetVr(value)) => {
+                    // Set V(R) is ignored, though counted, while locked out
+                    if self.state != FarmState::Lockout {
+                        self.state = FarmState::Open;
+                        self.receiver_sequence = value;
+                        self.retransmit = false;
+                    }
+                    self.count_bypass();
+                    FarmVerdict::Control
+                }
+                None => FarmVerdict::Discard,
+            },
+            TcFrameType::Ad => self.receive_sequenced(frame.sequence, buffer_available),
+        }
+    }
+
+    fn receive_sequenced(&mut self, sequence: u8, buffer_available: bool) -> FarmVerdict {
+        if self.state == FarmState::Lockout {
+            return FarmVerdict::Discard;
+        }
+        let ahead = sequence.wrapping_sub(self.receiver_sequence);
+        let behind = self.receiver_sequence.wrapping_sub(sequence);
+        let half = self.window / 2;
+
+        if ahead == 0 {
+            if self.state == FarmState::Open && buffer_available {
+                self.receiver_sequence = self.receiver_sequence.wrapping_add(1);
+                self.retransmit = false;
+                return FarmVerdict::Accept;
+            }
+            self.state = FarmState::Wait;
+            self.retransmit = true;
+        } else if ahead < half {
+            // Frames were lost: ask for everything from V(R) again
+            self.retransmit = true;
+        } else if behind > half {
+            self.state = FarmState::Lockout;
+        }
+        // Anything within the negative window was accepted before
+        FarmVerdict::Discard
+    }
+
+    fn count_bypass(&mut self) {
+        self.farm_b_counter = (self.farm_b_counter + 1) & 0x03;
+    }
+
+    /// Current report
+    pub fn clcw(&self) -> Clcw {
+        Clcw {
+            virtual_channel: self.virtual_channel,
+            no_rf_available: false,
+            no_bit_lock: false,
+            lockout: self.state == FarmState::Lockout,
+            wait: self.state == FarmState::Wait,
+            retransmit: self.retransmit,
+            farm_b_counter: self.farm_b_counter,
+            report_value: self.receiver_sequence,
+        }
+    }
+}
+
+/// FOP-1 parameters of the ground station
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cop1Config {
+    /// Spacecraft ID frames are sent to
+    pub spacecraft_id: u16,
+    /// Virtual channel frames are sent on
+    pub virtual_channel: u8,
+    /// Sliding window K: AD frames sent but not yet acknowledged, at most
+    /// [`FOP_MAX_WINDOW`]
+    pub window: u8,
+    /// Timer T1: retransmit when nothing is acknowledged for this long, ms
+    pub timer_ms: u64,
+    /// Transmissions of a frame, first included, before the limit alert
+    pub transmission_limit: u8,
+}
+
+impl Default for Cop1Config {
+    fn default() -> Self {
+        Self {
+            spacecraft_id: SPACECRAFT_ID,
+            virtual_channel: virtual_channels::COMMAND,
+            window: DEFAULT_FOP_WINDOW,
+            timer_ms: DEFAULT_TIMER_MS,
+            transmission_limit: DEFAULT_TRANSMISSION_LIMIT,
+        }
+    }
+}
+
+impl Cop1Config {
+    /// Check the parameters
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |parameter, reason| SpaceCommError::ConfigurationError {
+            parameter,
+            value: "<cop1>",
+            reason,
+        };
+        if self.spacecraft_id > 0x3FF {
+            return Err(invalid("spacecraft_id", "exceeds 10-bit maximum"));
+        }
+        if self.virtual_channel > 0x3F {
+            return Err(invalid("virtual_channel", "exceeds 6-bit maximum"));
+        }
+        if self.window == 0 || usize::from(self.window) > FOP_MAX_WINDOW {
+            return Err(invalid(
+                "window",
+                "FOP-1 window must be from 1 to 16 frames",
+            ));
+        }
+        if self.timer_ms == 0 {
+            return Err(invalid("timer_ms", "timer T1 must be positive"));
+        }
+        if self.transmission_limit == 0 {
+            return Err(invalid("transmission_limit", "at least one transmission"));
+        }
+        Ok(())
+    }
+}
+
+/// FOP-1 state (CCSDS 232.1-B-2 §5.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FopState {
+    /// S1: frames acknowledged as sent
+    Active,
+    /// S2: the FARM asked for a retransmission
+    RetransmitWithoutWait,
+    /// S3: retransmission held until the FARM has a buffer again
+    RetransmitWithWait,
+    /// S5: a control command was sent; waiting for the CLCW to confirm it
+    Initialising,
+    /// S6: AD service stopped, after an alert or before it was started
+    Initial,
+}
+
+/// Reason FOP-1 stopped the AD service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cop1Alert {
+    /// A frame reached the transmission limit unacknowledged
+    Limit,
+    /// The CLCW reported FARM-1 lockout
+    Lockout,
+    /// The CLCW acknowledged a frame never sent
+    InvalidReport,
+}
+
+impl core::fmt::Display for Cop1Alert {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Limit => "transmission limit reached",
+            Self::Lockout => "FARM-1 lockout",
+            Self::InvalidReport => "CLCW N(R) outside the sent window",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SentFrame {
+    frame: TcTransferFrame,
+    transmissions: u8,
+}
+
+/// Frame Operation Procedure of the ground station
+///
+/// - **ID**: MOD-COP-002
+/// - **Requirement**: Retransmit Type-AD frames until the FARM acknowledges
+///   them, within a sliding window and a transmission limit (REQ-NF-004).
+/// - **Failure Modes**: A lockout report, an impossible acknowledgment or a
+///   frame reaching the transmission limit stops the AD service with a
+///   [`Cop1Alert`] and purges the window; the operator restarts it with
+///   Unlock or Set V(R).
+/// - **Constraints**: Window of at most [`FOP_MAX_WINDOW`] frames, no heap
+///   allocation.
+#[derive(Debug, Clone)]
+pub struct Fop {
+    config: Cop1Config,
+    state: FopState,
+    transmitter_sequence: u8,
+    expected_ack: u8,
+    sent: Deque<SentFrame, FOP_MAX_WINDOW>,
+    control: Option<SentFrame>,
+    timer_started_ms: Option<u64>,
+    retransmit_pending: bool,
+    alert: Option<Cop1Alert>,
+}
+
+impl Fop {
+    /// Create a FOP in the Initial state
+    pub fn new(config: Cop1Config) -> Result<Self> {
+        config.validate()?;
+
+        Ok(Self {
+            config,
+            state: FopState::Initial,
+            transmitter_sequence: 0,
+            expected_ack: 0,
+            sent: Deque::new(),
+            control: None,
+            timer_started_ms: None,
+            retransmit_pending: false,
+            alert: None,
+        })
+    }
+
+    /// Parameters in use
+    pub fn config(&self) -> &Cop1Config {
+        &self.config
+    }
+
+    /// Current state
+    pub fn state(&self) -> FopState {
+        self.state
+    }
+
+    /// Transmitter frame sequence number V(S): N(S) of the next AD frame
+    pub fn transmitter_sequence(&self) -> u8 {
+        self.transmitter_sequence
+    }
+
+    /// NN(R): N(S) of the oldest frame not yet acknowledged
+    pub fn expected_ack(&self) -> u8 {
+        self.expected_ack
+    }
+
+    /// AD frames sent and not yet acknowledged
+    pub fn outstanding(&self) -> usize {
+        self.sent.len()
+    }
+
+    /// Alert that last stopped the AD service, if any
+    pub fn alert(&self) -> Option<Cop1Alert> {
+        self.alert
+    }
+
+    /// Start the AD service without a CLCW check, numbering from `sequence`
+    ///
+    /// For a FARM known to expect `sequence`, such as both ends starting
+    /// from 0.
+    pub fn start(&mut self, sequence: u8) {
+        self.purge();
+        self.transmitter_sequence = sequence;
+        self.expected_ack = sequence;
+        self.state = FopState::Active;
+    }
+
+    /// Start the AD service with a Set V(R) control command
+    ///
+    /// Returns the Type-BC frame to send; the service is Active once a CLCW
+    /// reports V(R) = `sequence`.
+    pub fn set_vr(&mut self, sequence: u8, now_ms: u64) -> Result<TcTransferFrame> {
+        self.purge();
+        self.transmitter_sequence = sequence;
+        self.expected_ack = sequence;
+        self.send_control(TcControlCommand::SetVr(sequence), now_ms)
+    }
+
+    /// Start the AD service with an Unlock control command
+    ///
+    /// Returns the Type-BC frame to send; the service is Active once a CLCW
+    /// reports no lockout and V(R) = V(S).
+    pub fn unlock(&mut self, now_ms: u64) -> Result<TcTransferFrame> {
+        self.purge();
+        self.send_control(TcControlCommand::Unlock, now_ms)
+    }
+
+    fn send_control(&mut self, command: TcControlCommand, now_ms: u64) -> Result<TcTransferFrame> {
+        let frame = TcTransferFrame::new(
+            TcFrameType::Bc,
+            self.config.spacecraft_id,
+            self.config.virtual_channel,
+            0,
+            &command.to_data(),
+        )?;
+        self.control = Some(SentFrame {
+            frame: frame.clone(),
+            transmissions: 1,
+        });
+        self.timer_started_ms = Some(now_ms);
+        self.alert = None;
+        self.state = FopState::Initialising;
+        Ok(frame)
+    }
+
+    /// Frame the next AD data unit without sending it
+    ///
+    /// - **ID**: FN-COP-003
+    /// - **Requirement**: Number AD frames in sequence within the sliding
+    ///   window K (CCSDS 232.1-B-2 §5.2).
+    /// - **Inputs**: `data`: Frame data field, usually a command packet.
+    /// - **Outputs**: Type-AD frame numbered V(S); pass it to
+    ///   [`Self::transmitted`] once it is on the link.
+    /// - **Failure Modes**: `CommunicationTimeout` while the AD service is
+    ///   not started; `ResourceExhausted` while K frames are unacknowledged;
+    ///   frame errors for an oversized data field.
+    pub fn prepare(&self, data: &[u8]) -> Result<TcTransferFrame> {
+        if matches!(self.state, FopState::Initial | FopState::Initialising) {
+            return Err(SpaceCommError::communication_timeout(
+                self.config.timer_ms,
+                "COP-1 AD service not active",
+            ));
+        }
+        if self.sent.len() >= usize::from(self.config.window) {
+            return Err(SpaceCommError::ResourceExhausted {
+                resource: "COP-1 sliding window",
+                current_usage: self.sent.len() as u32,
+                max_usage: u32::from(self.config.window),
+            });
+        }
+        TcTransferFrame::new(
+            TcFrameType::Ad,
+            self.config.spacecraft_id,
+            self.config.virtual_channel,
+            self.transmitter_sequence,
+            data,
+        )
+    }
+
+    /// Record a frame from [`Self::prepare`] as sent at `now_ms`
+    pub fn transmitted(&mut self, frame: TcTransferFrame, now_ms: u64) {
+        if frame.sequence != self.transmitter_sequence || self.sent.is_full() {
+            return;
+        }
+        self.transmitter_sequence = self.transmitter_sequence.wrapping_add(1);
+        let _ = self.sent.push_back(SentFrame {
+            frame,
+            transmissions: 1,
+        });
+        self.timer_started_ms.get_or_insert(now_ms);
+    }
+
+    /// Frame an expedited Type-BD data unit
+    ///
+    /// BD frames are neither numbered nor retransmitted.
+    pub fn expedite(&self, data: &[u8]) -> Result<TcTransferFrame> {
+        TcTransferFrame::new(
+            TcFrameType::Bd,
+            self.config.spacecraft_id,
+            self.config.virtual_channel,
+            0,
+            data,
+        )
+    }
+
+    /// Process a CLCW received on the downlink
+    ///
+    /// - **ID**: FN-COP-004
+    /// - **Requirement**: Release acknowledged frames and schedule the
+    ///   retransmissions the FARM asks for (CCSDS 232.1-B-2 §5.2).
+    /// - **Inputs**:
+    ///   - `clcw`: Report; reports for other virtual channels are ignored.
+    ///   - `now_ms`: Receive time, ms.
+    /// - **Side Effects**: Releases frames below N(R), restarts timer T1 on
+    ///   progress, and marks the window for retransmission when the
+    ///   retransmit flag is newly raised; [`Self::poll`] sends it.
+    /// - **Failure Modes**: A lockout report or an N(R) outside the sent
+    ///   window raises a [`Cop1Alert`], stops the AD service and returns an
+    ///   `InvalidPacket` error.
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn receive_clcw(&mut self, clcw: &Clcw, now_ms: u64) -> Result<()> {
+        if clcw.virtual_channel != self.config.virtual_channel || self.state == FopState::Initial {
+            return Ok(());
+        }
+
+        if self.state == FopState::Initialising {
+            if !clcw.lockout && clcw.report_value == self.transmitter_sequence {
+                self.control = None;
+                self.timer_started_ms = None;
+                self.state = FopState::Active;
+            }
+            return Ok(());
+        }
+
+        if clcw.lockout {
+            return Err(self.raise(Cop1Alert::Lockout));
+        }
+        let acknowledged = clcw.report_value.wrapping_sub(self.expected_ack);
+        let outstanding = self.transmitter_sequence.wrapping_sub(self.expected_ack);
+        if acknowledged > outstanding {
+            return Err(self.raise(Cop1Alert::InvalidReport));
+        }
+
+        for _ in 0..acknowledged {
+            self.sent.pop_front();
+        }
+        self.expected_ack = clcw.report_value;
+        if acknowledged > 0 {
+            self.timer_started_ms = (!self.sent.is_empty()).then_some(now_ms);
+        }
+
+        let previous = self.state;
+        self.state = match (clcw.retransmit && !self.sent.is_empty(), clcw.wait) {
+            (false, _) => FopState::Active,
+            (true, true) => FopState::RetransmitWithWait,
+            (true, false) => FopState::RetransmitWithoutWait,
+        };
+        // A raised flag is answered once; the timer covers a lost answer
+        if self.state == FopState::RetransmitWithoutWait
+            && (previous != FopState::RetransmitWithoutWait || acknowledged > 0)
+        {
+            self.retransmit_pending = true;
+        }
+        Ok(())
+    }
+
+    /// Frames to send now: requested retransmissions, and the whole window
+    /// or pending control command once timer T1 expires
+    ///
+    /// - **ID**: FN-COP-005
+    /// - **Requirement**: Retransmit unacknowledged frames up to the
+    ///   transmission limit (REQ-NF-004).
+    /// - **Inputs**: `now_ms`: Current time, ms.
+    /// - **Outputs**: Frames to put on the link, oldest first.
+    /// - **Side Effects**: Counts a transmission of each frame returned and
+    ///   restarts timer T1.
+    /// - **Failure Modes**: A frame that would exceed the transmission limit
+    ///   raises [`Cop1Alert::Limit`], stops the AD service and returns a
+    ///   `CommunicationTimeout` error.
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn poll(&mut self, now_ms: u64) -> Result<Vec<TcTransferFrame, FOP_MAX_WINDOW>> {
+        let mut frames = Vec::new();
+        let expired = self
+            .timer_started_ms
+            .is_some_and(|started| now_ms.saturating_sub(started) >= self.config.timer_ms);
+
+        if self.state == FopState::Initialising {
+            if let (true, Some(control)) = (expired, self.control.as_mut()) {
+                if control.transmissions >= self.config.transmission_limit {
+                    return Err(self.raise(Cop1Alert::Limit));
+                }
+                control.transmissions += 1;
+                let _ = frames.push(control.frame.clone());
+                self.timer_started_ms = Some(now_ms);
+            }
+            return Ok(frames);
+        }
+        if self.state == FopState::RetransmitWithWait
+            || self.sent.is_empty()
+            || !(expired || self.retransmit_pending)
+        {
+            return Ok(frames);
+        }
+
+        let limit = self.config.transmission_limit;
+        if self.sent.iter().any(|sent| sent.transmissions >= limit) {
+            return Err(self.raise(Cop1Alert::Limit));
+        }
+        for sent in self.sent.iter_mut() {
+            sent.transmissions += 1;
+            let _ = frames.push(sent.frame.clone());
+        }
+        self.retransmit_pending = false;
+        self.timer_started_ms = Some(now_ms);
+        Ok(frames)
+    }
+
+    fn raise(&mut self, alert: Cop1Alert) -> SpaceCommError {
+        self.purge();
+        self.alert = Some(alert);
+        self.state = FopState::Initial;
+        match alert {
+            Cop1Alert::Limit => SpaceCommError::communication_timeout(
+                self.config.timer_ms * u64::from(self.config.transmission_limit),
+                "COP-1 transmission limit reached",
+            ),
+            Cop1Alert::Lockout => {
+                SpaceCommError::invalid_packet("CLCW reports FARM-1 lockout", None)
+            }
+            Cop1Alert::InvalidReport => {
+                SpaceCommError::invalid_packet("CLCW N(R) outside the sent window", None)
+            }
+        }
+    }
+
+    fn purge(&mut self) {
+        self.sent.clear();
+        self.control = None;
+        self.timer_started_ms = None;
+        self.retransmit_pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Cop1Config {
+        Cop1Config {
+            window: 4,
+            timer_ms: 100,
+            transmission_limit: 2,
+            ..Cop1Config::default()
+        }
+    }
+
+    fn farm() -> Farm {
+        Farm::new(SPACECRAFT_ID, virtual_channels::COMMAND, 8).unwrap()
+    }
+
+    /// Send the next AD frame at `now_ms`
+    fn send(fop: &mut Fop, data: &[u8], now_ms: u64) -> TcTransferFrame {
+        let frame = fop.prepare(data).unwrap();
+        fop.transmitted(frame.clone(), now_ms);
+        frame
+    }
+
+    #[test]
+    fn test_clcw_and_control_command_encoding() {
+        let clcw = Clcw {
+            virtual_channel: 7,
+            lockout: true,
+            retransmit: true,
+            farm_b_counter: 2,
+            report_value: 0x5A,
+            ..Clcw::default()
+        };
+        let bytes = clcw.to_bytes();
+        assert_eq!(bytes, [0x01, 0x1C, 0x2C, 0x5A]);
+        assert_eq!(Clcw::from_bytes(&bytes).unwrap(), clcw);
+        assert!(Clcw::from_bytes(&[0x81, 0, 0, 0]).is_err());
+        assert!(Clcw::from_bytes(&bytes[..3]).is_err());
+
+        let packet = clcw.to_packet(3).unwrap();
+        assert_eq!(packet.header.apid, CLCW_APID);
+        assert_eq!(packet.data.as_slice(), &bytes);
+
+        for command in [TcControlCommand::Unlock, TcControlCommand::SetVr(200)] {
+            assert_eq!(
+                TcControlCommand::from_data(&command.to_data()),
+                Some(command)
+            );
+        }
+        assert_eq!(TcControlCommand::from_data(&[0x82, 0x01, 0]), None);
+    }
+
+    #[test]
+    fn test_configuration_checked() {
+        assert!(Farm::new(SPACECRAFT_ID, 7, 7).is_err());
+        assert!(Farm::new(SPACECRAFT_ID, 7, 0).is_err());
+        assert_eq!(
+            Farm::default().clcw().virtual_channel,
+            virtual_channels::COMMAND
+        );
+        assert!(Fop::new(Cop1Config {
+            window: 17,
+            ..config()
+        })
+        .is_err());
+        assert!(Fop::new(Cop1Config {
+            transmission_limit: 0,
+            ..config()
+        })
+        .is_err());
+        assert!(Fop::new(Cop1Config::default()).is_ok());
+    }
+
+    #[test]
+    fn test_frames_accepted_in_order() {
+        let mut fop = Fop::new(config()).unwrap();
+        let mut farm = farm();
+        assert!(fop.prepare(b"early").is_err());
+        fop.start(0);
+
+        for (i, data) in [b"one", b"two", b"six"].iter().enumerate() {
+            let frame = send(&mut fop, *data, 0);
+            assert_eq!(frame.sequence, i as u8);
+            assert_eq!(farm.receive(&frame, true), FarmVerdict::Accept);
+        }
+        assert_eq!(fop.outstanding(), 3);
+        fop.receive_clcw(&farm.clcw(), 10).unwrap();
+        assert_eq!((fop.outstanding(), fop.expected_ack()), (0, 3));
+        assert!(fop.poll(1_000).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lost_frame_retransmitted_from_report() {
+        let mut fop = Fop::new(config()).unwrap();
+        let mut farm = farm();
+        fop.start(250);
+        let set = TcControlCommand::SetVr(250).to_data();
+        let set = TcTransferFrame::new(TcFrameType::Bc, SPACECRAFT_ID, 7, 0, &set).unwrap();
+        assert_eq!(farm.receive(&set, true), FarmVerdict::Control);
+
+        let first = send(&mut fop, b"a", 0);
+        let second = send(&mut fop, b"b", 0);
+        // The first frame is lost; the second is ahead of V(R)
+        assert_eq!(farm.receive(&second, true), FarmVerdict::Discard);
+        let clcw = farm.clcw();
+        assert!(clcw.retransmit);
+        assert_eq!(clcw.report_value, 250);
+
+        fop.receive_clcw(&clcw, 10).unwrap();
+        assert_eq!(fop.state(), FopState::RetransmitWithoutWait);
+        let resent = fop.poll(10).unwrap();
+        assert_eq!(resent.as_slice(), &[first, second]);
+        // The same report again is not answered twice
+        fop.receive_clcw(&clcw, 20).unwrap();
+        assert!(fop.poll(20).unwrap().is_empty());
+
+        for frame in &resent {
+            assert_eq!(farm.receive(frame, true), FarmVerdict::Accept);
+        }
+        // A late duplicate inside the negative window is dropped quietly
+        assert_eq!(farm.receive(&resent[0], true), FarmVerdict::Discard);
+        fop.receive_clcw(&farm.clcw(), 30).unwrap();
+        assert_eq!(fop.state(), FopState::Active);
+        assert_eq!((fop.outstanding(), fop.transmitter_sequence()), (0, 252));
+    }
+
+    #[test]
+    fn test_window_timer_and_limit() {
+        let mut fop = Fop::new(config()).unwrap();
+        fop.start(0);
+        for _ in 0..4 {
+            send(&mut fop, b"x", 0);
+        }
+        assert!(fop.prepare(b"x").is_err());
+
+        // Nothing acknowledged: T1 resends the window, then the limit alerts
+        assert!(fop.poll(99).unwrap().is_empty());
+        assert_eq!(fop.poll(100).unwrap().len(), 4);
+        assert!(fop.poll(200).is_err());
+        assert_eq!(fop.alert(), Some(Cop1Alert::Limit));
+        assert_eq!((fop.state(), fop.outstanding()), (FopState::Initial, 0));
+    }
+
+    #[test]
+    fn test_wait_holds_retransmission() {
+        let mut fop = Fop::new(config()).unwrap();
+        let mut farm = farm();
+        fop.start(0);
+
+        let frame = send(&mut fop, b"x", 0);
+        assert_eq!(farm.receive(&frame, false), FarmVerdict::Discard);
+        assert_eq!(farm.state(), FarmState::Wait);
+        fop.receive_clcw(&farm.clcw(), 10).unwrap();
+        assert_eq!(fop.state(), FopState::RetransmitWithWait);
+        assert!(fop.poll(500).unwrap().is_empty());
+
+        // The buffer frees up and the retransmission is accepted
+        let bypass = fop.expedite(b"bd").unwrap();
+        assert_eq!(farm.receive(&bypass, true), FarmVerdict::Accept);
+        fop.receive_clcw(&farm.clcw(), 600).unwrap();
+        let resent = fop.poll(600).unwrap();
+        assert_eq!(farm.receive(&resent[0], true), FarmVerdict::Accept);
+        assert_eq!(farm.clcw().farm_b_counter, 1);
+    }
+
+    #[test]
+    fn test_lockout_and_recovery() {
+        let mut fop = Fop::new(config()).unwrap();
+        let mut farm = farm();
+        fop.start(0);
+
+        // Far outside both windows of an 8-wide FARM
+        let stray = TcTransferFrame::new(TcFrameType::Ad, SPACECRAFT_ID, 7, 100, b"x").unwrap();
+        assert_eq!(farm.receive(&stray, true), FarmVerdict::Discard);
+        assert_eq!(farm.state(), FarmState::Lockout);
+        let frame = send(&mut fop, b"x", 0);
+        assert_eq!(farm.receive(&frame, true), FarmVerdict::Discard);
+        assert!(fop.receive_clcw(&farm.clcw(), 10).is_err());
+        assert_eq!(fop.alert(), Some(Cop1Alert::Lockout));
+
+        // Set V(R) is ignored while locked out; Unlock is not
+        let set = fop.set_vr(40, 20).unwrap();
+        assert_eq!(farm.receive(&set, true), FarmVerdict::Control);
+        assert_eq!(farm.state(), FarmState::Lockout);
+        let unlock = fop.unlock(30).unwrap();
+        assert_eq!(farm.receive(&unlock, true), FarmVerdict::Control);
+        fop.receive_clcw(&farm.clcw(), 40).unwrap();
+        assert_eq!(fop.state(), FopState::Initialising);
+        // The control command is resent on T1 until confirmed
+        assert_eq!(fop.poll(130).unwrap().as_slice(), &[unlock]);
+        let set = fop.set_vr(40, 140).unwrap();
+        assert_eq!(farm.receive(&set, true), FarmVerdict::Control);
+        fop.receive_clcw(&farm.clcw(), 150).unwrap();
+        assert_eq!(fop.state(), FopState::Active);
+        assert_eq!(send(&mut fop, b"x", 160).sequence, 40);
+    }
+
+    #[test]
+    fn test_impossible_report_and_foreign_frames() {
+        let mut fop = Fop::new(config()).unwrap();
+        let mut farm = farm();
+        fop.start(0);
+        send(&mut fop, b"x", 0);
+        let report = Clcw {
+            virtual_channel: virtual_channels::COMMAND,
+            report_value: 5,
+            ..Clcw::default()
+        };
+        assert!(fop.receive_clcw(&report, 10).is_err());
+        assert_eq!(fop.alert(), Some(Cop1Alert::InvalidReport));
+
+        let other = TcTransferFrame::new(TcFrameType::Ad, SPACECRAFT_ID, 3, 0, b"x").unwrap();
+        assert_eq!(farm.receive(&other, true), FarmVerdict::Discard);
+        assert_eq!(farm.receiver_sequence(), 0);
+    }
+}
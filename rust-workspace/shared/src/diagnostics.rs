@@ -0,0 +1,596 @@
+//! Memory dump and dwell diagnostics
+//!
+//! Two standard diagnostics commands read the spacecraft's state directly.
+//! A memory dump ([`SpaceCommand::DumpMemory`]) reads a range of the onboard
+//! address space and downlinks it as [`MemoryDumpSegment`]s on
+//! [`MEMORY_DUMP_APID`]; a dwell ([`SpaceCommand::DwellSample`]) samples one
+//! memory word or telemetry measurement at a fixed interval for a bounded
+//! time and downlinks the samples in [`DwellBatch`]es on [`DWELL_APID`].
+//! Each segment and batch says where it belongs, so the ground can reassemble
+//! the dump or trace from whatever arrives and name what is still missing.
+//!
+//! Segments and batches use fixed layouts in the [`crate::wire`] format:
+//!
+//! | Bytes  | Dump segment   |
+//! |--------|----------------|
+//! | 0..2   | `dump_id`      |
+//! | 2..6   | `address`      |
+//! | 6..10  | `total_length` |
+//! | 10..14 | `offset`       |
+//! | 14..   | data           |
+//!
+//! | Bytes  | Dwell batch           |
+//! |--------|-----------------------|
+//! | 0..2   | `dwell_id`            |
+//! | 2      | `source`              |
+//! | 3      | bit 0: last batch     |
+//! | 4..8   | `target`              |
+//! | 8..10  | `interval_ms`         |
+//! | 10..14 | `first_index`         |
+//! | 14..   | samples, 4 bytes each |
+//!
+//! Measurement samples carry the value as `f32` bits; memory samples carry
+//! the word as read.
+//!
+//! [`SpaceCommand::DumpMemory`]: crate::commands::SpaceCommand::DumpMemory
+//! [`SpaceCommand::DwellSample`]: crate::commands::SpaceCommand::DwellSample
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (diagnostics on request)
+//! - REQ-IF-002: CCSDS Compliance (segments and batches carried in Space Packets)
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::wire::{self, WireReader, WireWriter};
+
+/// APID used to downlink memory dump segments
+pub const MEMORY_DUMP_APID: u16 = 0x016;
+
+/// APID used to downlink dwell sample batches
+pub const DWELL_APID: u16 = 0x017;
+
+/// Longest memory dump one command may request, bytes
+pub const MAX_DUMP_LENGTH: u32 = 65_536;
+
+/// Dump bytes carried per segment
+pub const DUMP_SEGMENT_DATA_LEN: usize = 256;
+
+/// Samples carried per dwell batch
+pub const DWELL_BATCH_SAMPLES: usize = 32;
+
+/// Shortest dwell sample interval, milliseconds
+pub const MIN_DWELL_INTERVAL_MS: u16 = 10;
+
+/// Longest dwell, seconds
+pub const MAX_DWELL_DURATION_S: u16 = 600;
+
+/// Command ID of [`SpaceCommand::DumpMemory`]
+pub const DUMP_MEMORY_COMMAND_ID: u32 = 0x0035;
+
+/// Command ID of [`SpaceCommand::DwellSample`]
+pub const DWELL_SAMPLE_COMMAND_ID: u32 = 0x0036;
+
+/// Segment and batch header length, bytes
+const DIAGNOSTIC_HEADER_LEN: usize = 14;
+
+/// What a dwell samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum DwellSource {
+    /// 32-bit memory word at an address
+    Memory = 0,
+    /// Telemetry measurement by measurement ID
+    Measurement = 1,
+}
+
+impl DwellSource {
+    /// Source from its `#[repr(u8)]` value
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Memory),
+            1 => Some(Self::Measurement),
+            _ => None,
+        }
+    }
+}
+
+/// Memory dump requested by [`SpaceCommand::DumpMemory`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryDumpRequest {
+    /// First address to read
+    pub address: u32,
+    /// Bytes to read
+    pub length: u32,
+}
+
+impl MemoryDumpRequest {
+    /// Check the request before it is uplinked or executed
+    ///
+    /// - **ID**: FN-DMP-001
+    /// - **Requirement**: A dump reads a bounded range that stays inside the
+    ///   32-bit address space.
+    /// - **Outputs**: `Ok(())` for 1 to [`MAX_DUMP_LENGTH`] bytes ending at
+    ///   or below `0xFFFF_FFFF`.
+    /// - **Failure Modes**: Empty, oversized or wrapping range →
+    ///   `Err(ConfigurationError)`.
+    pub fn validate(&self) -> Result<()> {
+        if self.length == 0 || self.length > MAX_DUMP_LENGTH {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "length",
+                value: "<length>",
+                reason: "memory dump length must be 1 to 65536 bytes",
+            });
+        }
+        if self.address.checked_add(self.length - 1).is_none() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "address",
+                value: "<address>",
+                reason: "memory dump runs past the end of the address space",
+            });
+        }
+        Ok(())
+    }
+
+    /// Segments the dump is downlinked in
+    pub fn segment_count(&self) -> u32 {
+        self.length.div_ceil(DUMP_SEGMENT_DATA_LEN as u32)
+    }
+}
+
+/// Dwell requested by [`SpaceCommand::DwellSample`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DwellRequest {
+    /// What is sampled
+    pub source: DwellSource,
+    /// Memory address or measurement ID
+    pub target: u32,
+    /// Sample interval, milliseconds
+    pub interval_ms: u16,
+    /// Dwell duration, seconds
+    pub duration_s: u16,
+}
+
+impl DwellRequest {
+    /// Check the request before it is uplinked or executed
+    ///
+    /// - **ID**: FN-DWL-001
+    /// - **Requirement**: A dwell samples no faster than
+    ///   [`MIN_DWELL_INTERVAL_MS`] and stops within [`MAX_DWELL_DURATION_S`].
+    /// - **Outputs**: `Ok(())` for an interval and duration within the limits,
+    ///   a word-aligned memory address, or a 16-bit measurement ID.
+    /// - **Failure Modes**: Out-of-range interval, duration or target →
+    ///   `Err(ConfigurationError)`.
+    pub fn validate(&self) -> Result<()> {
+        if self.interval_ms < MIN_DWELL_INTERVAL_MS {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "interval_ms",
+                value: "<interval>",
+                reason: "dwell interval must be at least 10 ms",
+            });
+        }
+        if self.duration_s == 0 || self.duration_s > MAX_DWELL_DURATION_S {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "duration_s",
+                value: "<duration>",
+                reason: "dwell duration must be 1 to 600 seconds",
+            });
+        }
+        match self.source {
+            DwellSource::Memory if !self.target.is_multiple_of(4) => {
+                Err(SpaceCommError::ConfigurationError {
+                    parameter: "target",
+                    value: "<address>",
+                    reason: "dwell memory address must be word-aligned",
+                })
+            }
+            DwellSource::Measurement if self.target > u32::from(u16::MAX) => {
+                Err(SpaceCommError::ConfigurationError {
+                    parameter: "target",
+                    value: "<measurement>",
+                    reason: "measurement IDs are 16 bits",
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Samples taken over the whole dwell
+    pub fn sample_count(&self) -> u32 {
+        (u32::from(self.duration_s) * 1000).div_ceil(u32::from(self.interval_ms))
+    }
+}
+
+/// Diagnostics request carried by a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticRequest {
+    /// Memory dump
+    Dump(MemoryDumpRequest),
+    /// Dwell
+    Dwell(DwellRequest),
+}
+
+impl DiagnosticRequest {
+    /// Request carried by `command`, or `None` for any other command
+    pub fn from_command(command: &SpaceCommand) -> Option<Self> {
+        match *command {
+            SpaceCommand::DumpMemory { address, length } => {
+                Some(Self::Dump(MemoryDumpRequest { address, length }))
+            }
+            SpaceCommand::DwellSample {
+                source,
+                target,
+                interval_ms,
+                duration_s,
+            } => Some(Self::Dwell(DwellRequest {
+                source,
+                target,
+                interval_ms,
+                duration_s,
+            })),
+            _ => None,
+        }
+    }
+
+    /// Request carried by a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a dump or
+    /// dwell command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != DUMP_MEMORY_COMMAND_ID && command_id != DWELL_SAMPLE_COMMAND_ID {
+            return None;
+        }
+        let request = serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..])
+            .ok()
+            .and_then(|command| Self::from_command(&command))
+            .filter(|request| request.command_id() == command_id)
+            .ok_or(SpaceCommError::invalid_packet(
+                "Malformed diagnostics command",
+                Some(command_id),
+            ));
+        Some(request)
+    }
+
+    /// Command ID of the command carrying the request
+    pub const fn command_id(&self) -> u32 {
+        match self {
+            Self::Dump(_) => DUMP_MEMORY_COMMAND_ID,
+            Self::Dwell(_) => DWELL_SAMPLE_COMMAND_ID,
+        }
+    }
+
+    /// Check the request; see [`MemoryDumpRequest::validate`] and
+    /// [`DwellRequest::validate`]
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Dump(request) => request.validate(),
+            Self::Dwell(request) => request.validate(),
+        }
+    }
+}
+
+/// One downlinked piece of a memory dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryDumpSegment {
+    /// Dump this segment belongs to
+    pub dump_id: u16,
+    /// First address of the dump
+    pub address: u32,
+    /// Length of the whole dump, bytes
+    pub total_length: u32,
+    /// Offset of this segment's data within the dump
+    pub offset: u32,
+    /// Bytes read
+    pub data: Vec<u8, DUMP_SEGMENT_DATA_LEN>,
+}
+
+impl MemoryDumpSegment {
+    /// Address of the segment's first byte
+    pub fn segment_address(&self) -> u32 {
+        self.address.wrapping_add(self.offset)
+    }
+
+    /// Serialize the segment in the fixed downlink layout
+    pub fn to_bytes(&self) -> Result<Vec<u8, { DIAGNOSTIC_HEADER_LEN + DUMP_SEGMENT_DATA_LEN }>> {
+        let mut bytes = WireWriter::new();
+        bytes
+            .put(self.dump_id)?
+            .put(self.address)?
+            .put(self.total_length)?
+            .put(self.offset)?
+            .put_bytes(&self.data)?;
+        Ok(bytes.finish())
+    }
+
+    /// Parse a segment from the packet data field
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < DIAGNOSTIC_HEADER_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "Memory dump segment too short",
+                None,
+            ));
+        }
+        let mut reader = WireReader::new(bytes);
+        let (dump_id, address, total_length, offset) =
+            (reader.get()?, reader.get()?, reader.get()?, reader.get()?);
+        let data = Vec::from_slice(reader.rest())
+            .map_err(|_| SpaceCommError::invalid_packet("Memory dump segment too long", None))?;
+        let segment = Self {
+            dump_id,
+            address,
+            total_length,
+            offset,
+            data,
+        };
+        let end = u64::from(segment.offset) + segment.data.len() as u64;
+        if end > u64::from(segment.total_length) {
+            return Err(SpaceCommError::invalid_packet(
+                "Memory dump segment past end of dump",
+                Some(segment.offset),
+            ));
+        }
+        Ok(segment)
+    }
+
+    /// Wrap the segment in a telemetry packet on [`MEMORY_DUMP_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            MEMORY_DUMP_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes()?,
+            None,
+        )
+    }
+}
+
+/// Consecutive samples of one dwell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DwellBatch {
+    /// Dwell the samples belong to
+    pub dwell_id: u16,
+    /// What was sampled
+    pub source: DwellSource,
+    /// Memory address or measurement ID
+    pub target: u32,
+    /// Sample interval, milliseconds
+    pub interval_ms: u16,
+    /// Index of the first sample in the dwell
+    pub first_index: u32,
+    /// Whether this is the last batch of the dwell
+    pub last: bool,
+    /// Raw samples: memory words, or measurement values as `f32` bits
+    pub samples: Vec<u32, DWELL_BATCH_SAMPLES>,
+}
+
+impl DwellBatch {
+    /// Empty batch continuing `request` from sample `first_index`
+    pub fn new(dwell_id: u16, request: &DwellRequest, first_index: u32) -> Self {
+        Self {
+            dwell_id,
+            source: request.source,
+            target: request.target,
+            interval_ms: request.interval_ms,
+            first_index,
+            last: false,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Sample `index` of the batch as a number: the memory word, or the
+    /// measurement value
+    pub fn value(&self, index: usize) -> Option<f64> {
+        let raw = *self.samples.get(index)?;
+        Some(match self.source {
+            DwellSource::Memory => f64::from(raw),
+            DwellSource::Measurement => f64::from(f32::from_bits(raw)),
+        })
+    }
+
+    /// Time of sample `index` of the batch, milliseconds from the dwell start
+    pub fn offset_ms(&self, index: usize) -> u64 {
+        (u64::from(self.first_index) + index as u64) * u64::from(self.interval_ms)
+    }
+
+    /// Serialize the batch in the fixed downlink layout
+    pub fn to_bytes(&self) -> Vec<u8, { DIAGNOSTIC_HEADER_LEN + 4 * DWELL_BATCH_SAMPLES }> {
+        let mut bytes = WireWriter::new();
+        // Capacity covers the header and a full batch
+        let _ = bytes
+            .put(self.dwell_id)
+            .and_then(|b| b.put(self.source as u8))
+            .and_then(|b| b.put(self.last))
+            .and_then(|b| b.put(self.target))
+            .and_then(|b| b.put(self.interval_ms))
+            .and_then(|b| b.put(self.first_index));
+        for &sample in &self.samples {
+            let _ = bytes.put(sample);
+        }
+        bytes.finish()
+    }
+
+    /// Parse a batch from the packet data field
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < DIAGNOSTIC_HEADER_LEN
+            || !(bytes.len() - DIAGNOSTIC_HEADER_LEN).is_multiple_of(4)
+        {
+            return Err(SpaceCommError::invalid_packet(
+                "Malformed dwell batch",
+                None,
+            ));
+        }
+        let mut reader = WireReader::new(bytes);
+        let dwell_id = reader.get()?;
+        let source = DwellSource::from_code(reader.get()?)
+            .ok_or(SpaceCommError::invalid_packet("Unknown dwell source", None))?;
+        let last = reader.get::<u8>()? & 0x01 != 0;
+        let (target, interval_ms, first_index) = (reader.get()?, reader.get()?, reader.get()?);
+        let mut samples = Vec::new();
+        while reader.remaining() > 0 {
+            samples
+                .push(reader.get()?)
+                .map_err(|_| SpaceCommError::invalid_packet("Dwell batch too long", None))?;
+        }
+        Ok(Self {
+            dwell_id,
+            source,
+            target,
+            interval_ms,
+            first_index,
+            last,
+            samples,
+        })
+    }
+
+    /// Wrap the batch in a telemetry packet on [`DWELL_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            DWELL_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes(),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_validation() {
+        let dump = MemoryDumpRequest {
+            address: 0x2000_0000,
+            length: 1000,
+        };
+        assert!(dump.validate().is_ok());
+        assert_eq!(dump.segment_count(), 4);
+        assert!(MemoryDumpRequest { length: 0, ..dump }.validate().is_err());
+        assert!(MemoryDumpRequest {
+            length: MAX_DUMP_LENGTH + 1,
+            ..dump
+        }
+        .validate()
+        .is_err());
+        assert!(MemoryDumpRequest {
+            address: 0xFFFF_FF00,
+            length: 0x100
+        }
+        .validate()
+        .is_ok());
+        assert!(MemoryDumpRequest {
+            address: 0xFFFF_FF00,
+            length: 0x101
+        }
+        .validate()
+        .is_err());
+
+        let dwell = DwellRequest {
+            source: DwellSource::Measurement,
+            target: 0x0010,
+            interval_ms: 100,
+            duration_s: 5,
+        };
+        assert!(dwell.validate().is_ok());
+        assert_eq!(dwell.sample_count(), 50);
+        assert!(DwellRequest {
+            interval_ms: 5,
+            ..dwell
+        }
+        .validate()
+        .is_err());
+        assert!(DwellRequest {
+            duration_s: 601,
+            ..dwell
+        }
+        .validate()
+        .is_err());
+        assert!(DwellRequest {
+            target: 0x1_0000,
+            ..dwell
+        }
+        .validate()
+        .is_err());
+        let memory = DwellRequest {
+            source: DwellSource::Memory,
+            target: 0x2000_0002,
+            ..dwell
+        };
+        assert!(memory.validate().is_err());
+
+        let command = SpaceCommand::DwellSample {
+            source: DwellSource::Measurement,
+            target: 0x0010,
+            interval_ms: 100,
+            duration_s: 5,
+        };
+        assert_eq!(
+            DiagnosticRequest::from_command(&command),
+            Some(DiagnosticRequest::Dwell(dwell))
+        );
+        assert_eq!(command.discriminant(), DWELL_SAMPLE_COMMAND_ID);
+
+        let mut data = std::vec::Vec::from(DWELL_SAMPLE_COMMAND_ID.to_be_bytes());
+        data.extend(serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            DiagnosticRequest::from_command_data(&data)
+                .unwrap()
+                .unwrap(),
+            DiagnosticRequest::Dwell(dwell)
+        );
+        data[3] = DUMP_MEMORY_COMMAND_ID as u8;
+        assert!(DiagnosticRequest::from_command_data(&data)
+            .unwrap()
+            .is_err());
+        assert!(DiagnosticRequest::from_command_data(&[0, 0, 0x20, 0x01, 2]).is_none());
+    }
+
+    #[test]
+    fn test_segment_and_batch_round_trip() {
+        let segment = MemoryDumpSegment {
+            dump_id: 3,
+            address: 0x2000_0000,
+            total_length: 300,
+            offset: 256,
+            data: Vec::from_slice(&[0xAB; 44]).unwrap(),
+        };
+        assert_eq!(segment.segment_address(), 0x2000_0100);
+        let packet = segment.to_packet(9).unwrap();
+        assert_eq!(packet.header.apid, MEMORY_DUMP_APID);
+        assert_eq!(
+            MemoryDumpSegment::from_bytes(&packet.data).unwrap(),
+            segment
+        );
+
+        // Data past the declared dump length is rejected
+        let mut long = segment.to_bytes().unwrap();
+        long.push(0).unwrap();
+        assert!(MemoryDumpSegment::from_bytes(&long).is_err());
+        assert!(MemoryDumpSegment::from_bytes(&long[..13]).is_err());
+
+        let request = DwellRequest {
+            source: DwellSource::Measurement,
+            target: 0x0010,
+            interval_ms: 50,
+            duration_s: 1,
+        };
+        let mut batch = DwellBatch::new(7, &request, 16);
+        batch.samples.push(28.5f32.to_bits()).unwrap();
+        batch.samples.push(28.25f32.to_bits()).unwrap();
+        batch.last = true;
+        assert_eq!(batch.value(1), Some(28.25));
+        assert_eq!(batch.offset_ms(1), 850);
+
+        let packet = batch.to_packet(1).unwrap();
+        assert_eq!(packet.header.apid, DWELL_APID);
+        assert_eq!(DwellBatch::from_bytes(&packet.data).unwrap(), batch);
+        assert!(DwellBatch::from_bytes(&packet.data[..15]).is_err());
+    }
+}
@@ -0,0 +1,407 @@
+//! Electrical power system (EPS) summary telemetry
+//!
+//! Bus and battery voltage, battery state of charge, solar array output and
+//! the current drawn by each load are downlinked together as one
+//! [`EpsSummary`], instead of as raw sensor readings scattered over the
+//! generic voltage and current IDs. Like RF housekeeping the summary rides an
+//! ordinary [`TelemetryData`] frame on its own APID, so every field keeps its
+//! quality flag, dictionary entry and stale detection.
+//!
+//! Measurement IDs from [`measurement_ids::EPS_BASE`], grouped by unit so
+//! each group is one dictionary range:
+//!
+//! | Offset | Field                                  | Unit |
+//! |--------|----------------------------------------|------|
+//! | 0x0    | Regulated bus voltage                  | V    |
+//! | 0x1    | Battery voltage                        | V    |
+//! | 0x2    | Solar array voltage                    | V    |
+//! | 0x4    | Solar array current                    | A    |
+//! | 0x5    | Total load current                     | A    |
+//! | 0x6    | Digital section current                | A    |
+//! | 0x7    | RF section current                     | A    |
+//! | 0x8    | Transmitter current                    | A    |
+//! | 0xC    | Battery state of charge                | %    |
+//!
+//! A field whose sensor could not be read is sent as NaN, which reads
+//! `Invalid`; the rest of the summary is still usable.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-004: Power Management (power budget visible as one product)
+//! - REQ-NF-001: System Health Monitoring (EPS limits and quality flags)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementValue, QualityLimits, TelemetryData,
+};
+
+/// APID of the EPS summary packet, next to RF housekeeping
+pub const EPS_APID: u16 = 0x102;
+
+/// EPS summary reporting period, ms
+pub const EPS_PERIOD_MS: u64 = 1_000;
+
+/// Battery voltage to state of charge for the 7-cell Li-ion pack, as
+/// (voltage, percent) points in rising voltage order
+const SOC_CURVE: [(f32, f32); 7] = [
+    (24.5, 0.0),
+    (25.9, 10.0),
+    (26.6, 25.0),
+    (27.3, 50.0),
+    (28.0, 70.0),
+    (28.7, 90.0),
+    (29.4, 100.0),
+];
+
+/// Field of the EPS summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EpsField {
+    /// Regulated bus voltage, V
+    BusVoltage,
+    /// Battery voltage, V
+    BatteryVoltage,
+    /// Solar array voltage, V
+    SolarVoltage,
+    /// Solar array current, A
+    SolarCurrent,
+    /// Total load current, A
+    LoadCurrent,
+    /// Digital section current, A
+    DigitalCurrent,
+    /// RF section current, A
+    RfCurrent,
+    /// Transmitter current, A
+    TransmitterCurrent,
+    /// Battery state of charge, percent
+    BatterySoc,
+}
+
+impl EpsField {
+    /// Every field in measurement ID order
+    pub const ALL: [EpsField; 9] = [
+        EpsField::BusVoltage,
+        EpsField::BatteryVoltage,
+        EpsField::SolarVoltage,
+        EpsField::SolarCurrent,
+        EpsField::LoadCurrent,
+        EpsField::DigitalCurrent,
+        EpsField::RfCurrent,
+        EpsField::TransmitterCurrent,
+        EpsField::BatterySoc,
+    ];
+
+    /// Individual loads, in measurement ID order
+    pub const LOADS: [EpsField; 3] = [
+        EpsField::DigitalCurrent,
+        EpsField::RfCurrent,
+        EpsField::TransmitterCurrent,
+    ];
+
+    /// Measurement ID of this field
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::EPS_BASE
+            + match self {
+                EpsField::BusVoltage => 0x0,
+                EpsField::BatteryVoltage => 0x1,
+                EpsField::SolarVoltage => 0x2,
+                EpsField::SolarCurrent => 0x4,
+                EpsField::LoadCurrent => 0x5,
+                EpsField::DigitalCurrent => 0x6,
+                EpsField::RfCurrent => 0x7,
+                EpsField::TransmitterCurrent => 0x8,
+                EpsField::BatterySoc => 0xC,
+            }
+    }
+
+    /// Field a measurement ID belongs to, if it is an EPS summary ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.measurement_id() == measurement_id)
+    }
+
+    /// Display name
+    pub const fn name(self) -> &'static str {
+        match self {
+            EpsField::BusVoltage => "Bus",
+            EpsField::BatteryVoltage => "Battery",
+            EpsField::SolarVoltage => "Solar array",
+            EpsField::SolarCurrent => "Solar array",
+            EpsField::LoadCurrent => "Total load",
+            EpsField::DigitalCurrent => "Digital",
+            EpsField::RfCurrent => "RF",
+            EpsField::TransmitterCurrent => "Transmitter",
+            EpsField::BatterySoc => "State of charge",
+        }
+    }
+
+    /// Engineering unit
+    pub const fn unit(self) -> &'static str {
+        match self {
+            EpsField::BusVoltage | EpsField::BatteryVoltage | EpsField::SolarVoltage => "V",
+            EpsField::BatterySoc => "%",
+            _ => "A",
+        }
+    }
+
+    /// Limit definition for this field
+    ///
+    /// Solar array output down to zero is expected: the array produces
+    /// nothing in eclipse.
+    pub const fn limits(self) -> QualityLimits {
+        let (valid_min, valid_max, expected_min, expected_max) = match self {
+            EpsField::BusVoltage => (0.0, 36.0, 11.0, 13.0),
+            EpsField::BatteryVoltage => (0.0, 36.0, 24.5, 29.4),
+            EpsField::SolarVoltage => (0.0, 50.0, 0.0, 40.0),
+            EpsField::SolarCurrent => (-0.5, 10.0, 0.0, 6.0),
+            EpsField::LoadCurrent => (-0.5, 10.0, 0.0, 5.0),
+            EpsField::DigitalCurrent | EpsField::RfCurrent | EpsField::TransmitterCurrent => {
+                (-0.5, 10.0, 0.0, 3.0)
+            }
+            EpsField::BatterySoc => (0.0, 100.0, 20.0, 100.0),
+        };
+        QualityLimits {
+            valid_min,
+            valid_max,
+            expected_min,
+            expected_max,
+        }
+    }
+
+    /// Dictionary entries covering the EPS summary: voltages, currents and
+    /// state of charge
+    pub const fn dictionary_entries() -> [DictionaryEntry; 3] {
+        [
+            group_dictionary_entry(EpsField::BusVoltage, 0x3, "EPS voltages"),
+            group_dictionary_entry(EpsField::SolarCurrent, 0xB, "EPS currents"),
+            group_dictionary_entry(EpsField::BatterySoc, 0xC, "Battery state of charge"),
+        ]
+    }
+}
+
+/// Dictionary entry from `first` up to `last_offset` from the EPS base
+const fn group_dictionary_entry(
+    first: EpsField,
+    last_offset: u16,
+    name: &'static str,
+) -> DictionaryEntry {
+    DictionaryEntry {
+        first_id: first.measurement_id(),
+        last_id: measurement_ids::EPS_BASE + last_offset,
+        name,
+        unit: first.unit(),
+        period_ms: EPS_PERIOD_MS,
+    }
+}
+
+/// Power system state at one instant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EpsSummary {
+    /// Regulated bus voltage, V
+    pub bus_voltage: f32,
+    /// Battery voltage, V
+    pub battery_voltage: f32,
+    /// Battery state of charge, percent
+    pub battery_soc: f32,
+    /// Solar array voltage, V
+    pub solar_voltage: f32,
+    /// Solar array current, A
+    pub solar_current: f32,
+    /// Total load current on the bus, A
+    pub load_current: f32,
+    /// Current of each load in [`EpsField::LOADS`] order, A
+    pub load_currents: [f32; 3],
+}
+
+impl EpsSummary {
+    /// Value of one field
+    pub fn value(&self, field: EpsField) -> f32 {
+        match field {
+            EpsField::BusVoltage => self.bus_voltage,
+            EpsField::BatteryVoltage => self.battery_voltage,
+            EpsField::SolarVoltage => self.solar_voltage,
+            EpsField::SolarCurrent => self.solar_current,
+            EpsField::LoadCurrent => self.load_current,
+            EpsField::DigitalCurrent => self.load_currents[0],
+            EpsField::RfCurrent => self.load_currents[1],
+            EpsField::TransmitterCurrent => self.load_currents[2],
+            EpsField::BatterySoc => self.battery_soc,
+        }
+    }
+
+    /// Solar array output, W
+    pub fn solar_power(&self) -> f32 {
+        self.solar_voltage * self.solar_current
+    }
+
+    /// Power drawn by the loads, W
+    pub fn load_power(&self) -> f32 {
+        self.bus_voltage * self.load_current
+    }
+
+    /// Power left over for the battery, W: positive while charging,
+    /// negative while discharging
+    pub fn net_power(&self) -> f32 {
+        self.solar_power() - self.load_power()
+    }
+
+    /// Append one measurement per field, each flagged against its limits
+    ///
+    /// - **ID**: FN-EPS-001
+    /// - **Requirement**: Report the power system as one telemetry product
+    ///   with a quality for every field (REQ-NF-004).
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold all nine
+    ///   measurements; measurements pushed before the overflow remain.
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for field in EpsField::ALL {
+            let value = self.value(field);
+            data.measurements
+                .push(Measurement {
+                    measurement_id: field.measurement_id(),
+                    value: MeasurementValue::Float(f64::from(value)),
+                    unit: field.unit(),
+                    quality: field.limits().classify(f64::from(value)),
+                })
+                .map_err(|_| {
+                    SpaceCommError::memory_error(
+                        MemoryErrorType::BufferOverflow,
+                        Some(data.measurements.len()),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the summary from downlinked measurements
+    ///
+    /// Returns `None` unless every field is present.
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let value = |field: EpsField| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == field.measurement_id())
+                .and_then(|m| match m.value {
+                    MeasurementValue::Float(v) => Some(v as f32),
+                    _ => None,
+                })
+        };
+
+        Some(Self {
+            bus_voltage: value(EpsField::BusVoltage)?,
+            battery_voltage: value(EpsField::BatteryVoltage)?,
+            battery_soc: value(EpsField::BatterySoc)?,
+            solar_voltage: value(EpsField::SolarVoltage)?,
+            solar_current: value(EpsField::SolarCurrent)?,
+            load_current: value(EpsField::LoadCurrent)?,
+            load_currents: [
+                value(EpsField::DigitalCurrent)?,
+                value(EpsField::RfCurrent)?,
+                value(EpsField::TransmitterCurrent)?,
+            ],
+        })
+    }
+}
+
+/// EPS summary carried by a frame, if it is complete
+pub fn decode_eps(data: &TelemetryData) -> Option<EpsSummary> {
+    EpsSummary::from_measurements(&data.measurements)
+}
+
+/// Battery state of charge from its open-circuit voltage
+///
+/// Interpolates the pack's discharge curve and clamps to 0-100%. NaN for a
+/// NaN voltage.
+pub fn battery_soc_percent(voltage: f32) -> f32 {
+    let (first, last) = (SOC_CURVE[0], SOC_CURVE[SOC_CURVE.len() - 1]);
+    if voltage.is_nan() {
+        return f32::NAN;
+    }
+    if voltage <= first.0 {
+        return first.1;
+    }
+    SOC_CURVE
+        .windows(2)
+        .find(|pair| voltage <= pair[1].0)
+        .map_or(last.1, |pair| {
+            let ((v0, s0), (v1, s1)) = (pair[0], pair[1]);
+            s0 + (s1 - s0) * (voltage - v0) / (v1 - v0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::{dictionary_entry, MeasurementQuality};
+    use crate::types::{ComponentId, HealthStatus};
+
+    fn summary() -> EpsSummary {
+        EpsSummary {
+            bus_voltage: 12.0,
+            battery_voltage: 28.0,
+            battery_soc: battery_soc_percent(28.0),
+            solar_voltage: 32.0,
+            solar_current: 1.5,
+            load_current: 2.0,
+            load_currents: [0.75, 0.5, f32::NAN],
+        }
+    }
+
+    #[test]
+    fn test_eps_round_trip_and_limits() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        summary().append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+        let mut frame =
+            TelemetryData::from_payload(ComponentId::new(1), HealthStatus::Good, &payload).unwrap();
+
+        let decoded = decode_eps(&frame).unwrap();
+        assert_eq!(decoded.bus_voltage, 12.0);
+        assert_eq!(decoded.battery_soc, 70.0);
+        assert_eq!(decoded.load_currents[..2], [0.75, 0.5]);
+        assert!(decoded.load_currents[2].is_nan());
+        assert_eq!(decoded.solar_power(), 48.0);
+        assert_eq!(decoded.net_power(), 24.0);
+
+        let quality = |field: EpsField| {
+            let id = field.measurement_id();
+            frame
+                .measurements
+                .iter()
+                .find(|m| m.measurement_id == id)
+                .unwrap()
+                .quality
+        };
+        assert_eq!(quality(EpsField::SolarCurrent), MeasurementQuality::Good);
+        assert_eq!(
+            quality(EpsField::TransmitterCurrent),
+            MeasurementQuality::Invalid
+        );
+
+        for field in EpsField::ALL {
+            let entry = dictionary_entry(field.measurement_id()).unwrap();
+            assert_eq!(entry.unit, field.unit());
+            assert_eq!(
+                EpsField::from_measurement_id(field.measurement_id()),
+                Some(field)
+            );
+        }
+
+        frame.measurements.pop();
+        assert!(decode_eps(&frame).is_none());
+    }
+
+    #[test]
+    fn test_battery_soc_curve() {
+        assert_eq!(battery_soc_percent(20.0), 0.0);
+        assert_eq!(battery_soc_percent(27.3), 50.0);
+        assert!((battery_soc_percent(28.35) - 80.0).abs() < 1e-3);
+        assert_eq!(battery_soc_percent(30.0), 100.0);
+        assert!(battery_soc_percent(f32::NAN).is_nan());
+    }
+}
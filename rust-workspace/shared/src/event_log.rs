@@ -0,0 +1,552 @@
+//! Onboard event log compression for downlink
+//!
+//! Event records repeat the same component names and messages and are logged
+//! milliseconds apart, so they compress well with two simple steps that need
+//! no heap:
+//!
+//! - **Delta timestamps**: each record carries the milliseconds since the
+//!   previous record as a LEB128 varint (the first record carries its
+//!   absolute time).
+//! - **Dictionary strings**: the first occurrence of a component or message
+//!   in a block is sent literally and assigned the next dictionary code;
+//!   repeats are sent as the one-byte code.
+//!
+//! Each block carries its own dictionary, so a lost downlink frame never
+//! prevents decoding the next one. Blocks are downlinked on
+//! [`EVENT_LOG_APID`] with this layout:
+//!
+//! | Field        | Encoding                                              |
+//! |--------------|-------------------------------------------------------|
+//! | record count | `u16`, in the [`crate::wire`] byte order              |
+//! | per record   | flags, timestamp delta, component, message, [code]    |
+//! | flags        | bits 0-2 [`EventLevel`] code, bit 3 error code present |
+//! | string       | dictionary code `0..DICTIONARY_CAPACITY`, or [`LITERAL_TAG`] + varint length + UTF-8 |
+//! | error code   | varint, only when flagged                             |
+//!
+//! [`CompressionStats`] compares each block against the uncompressed record
+//! layout ([`EventRecord::raw_len`]) so the ground can report the ratio.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-002: Memory Constraints (fixed-capacity encoder, zero-copy decoder)
+//! - REQ-IF-002: CCSDS Compliance (blocks carried in Space Packets)
+
+use heapless::Vec;
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::wire::{WireReader, WireValue, WIRE_BYTE_ORDER};
+
+/// APID used to downlink compressed event log blocks
+pub const EVENT_LOG_APID: u16 = 0x014;
+
+/// Distinct strings a block can map to codes; later new strings stay literal
+pub const DICTIONARY_CAPACITY: usize = 64;
+
+/// String tag introducing a literal instead of a dictionary code
+pub const LITERAL_TAG: u8 = 0xFF;
+
+/// Fixed part of an uncompressed record: timestamp (8), level (1), error code
+/// (4), component and message lengths (1 each)
+const RAW_RECORD_FIXED_LEN: usize = 15;
+
+/// Flag bit marking a record with an error code
+const ERROR_CODE_FLAG: u8 = 0x08;
+
+/// Severity of an event record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EventLevel {
+    /// Critical errors requiring immediate attention
+    Critical = 0,
+    /// Error conditions
+    Error = 1,
+    /// Warning conditions
+    Warning = 2,
+    /// Informational messages
+    Info = 3,
+    /// Debug information
+    Debug = 4,
+}
+
+impl EventLevel {
+    /// Code carried in the record flags
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Decode a level code
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(EventLevel::Critical),
+            1 => Some(EventLevel::Error),
+            2 => Some(EventLevel::Warning),
+            3 => Some(EventLevel::Info),
+            4 => Some(EventLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+/// One event log record; strings borrow from the log or the received block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventRecord<'a> {
+    /// Milliseconds since boot
+    pub timestamp_ms: u64,
+    /// Severity
+    pub level: EventLevel,
+    /// Component that logged the event
+    pub component: &'a str,
+    /// Event message
+    pub message: &'a str,
+    /// Error code, if any
+    pub error_code: Option<u32>,
+}
+
+impl EventRecord<'_> {
+    /// Size of the record in the uncompressed layout, the baseline for
+    /// [`CompressionStats`]
+    pub const fn raw_len(&self) -> usize {
+        RAW_RECORD_FIXED_LEN + self.component.len() + self.message.len()
+    }
+}
+
+/// Raw and compressed sizes of downlinked event records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Records compressed
+    pub records: u32,
+    /// Size of the records in the uncompressed layout, bytes
+    pub raw_bytes: u32,
+    /// Size of the compressed blocks, bytes
+    pub compressed_bytes: u32,
+}
+
+impl CompressionStats {
+    /// Compressed size as a fraction of the raw size; 1.0 before any record
+    pub fn ratio(&self) -> f32 {
+        if self.raw_bytes == 0 {
+            1.0
+        } else {
+            self.compressed_bytes as f32 / self.raw_bytes as f32
+        }
+    }
+
+    /// Add another block's statistics
+    pub fn accumulate(&mut self, other: &CompressionStats) {
+        self.records = self.records.saturating_add(other.records);
+        self.raw_bytes = self.raw_bytes.saturating_add(other.raw_bytes);
+        self.compressed_bytes = self.compressed_bytes.saturating_add(other.compressed_bytes);
+    }
+}
+
+/// Builds one compressed block of at most `N` bytes
+#[derive(Debug, Clone)]
+pub struct EventLogCompressor<const N: usize> {
+    block: Vec<u8, N>,
+    /// Offset and length of each dictionary string within `block`
+    dictionary: Vec<(usize, usize), DICTIONARY_CAPACITY>,
+    last_timestamp_ms: u64,
+    stats: CompressionStats,
+}
+
+impl<const N: usize> Default for EventLogCompressor<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventLogCompressor<N> {
+    /// Empty block
+    pub fn new() -> Self {
+        let mut block = Vec::new();
+        // Record count, patched as records are added
+        let _ = block.extend_from_slice(&[0, 0]);
+        Self {
+            block,
+            dictionary: Vec::new(),
+            last_timestamp_ms: 0,
+            stats: CompressionStats {
+                compressed_bytes: 2,
+                ..CompressionStats::default()
+            },
+        }
+    }
+
+    /// Records in the block
+    pub fn len(&self) -> usize {
+        self.stats.records as usize
+    }
+
+    /// Whether the block has no records
+    pub fn is_empty(&self) -> bool {
+        self.stats.records == 0
+    }
+
+    /// Statistics of the block so far
+    pub const fn stats(&self) -> &CompressionStats {
+        &self.stats
+    }
+
+    /// Compressed block
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.block
+    }
+
+    /// Append a record to the block
+    ///
+    /// - **ID**: FN-EVL-001
+    /// - **Requirement**: Compress event records with delta timestamps and
+    ///   dictionary-coded strings before downlink (REQ-NF-002).
+    /// - **Inputs**: Record; timestamps must not decrease within a block.
+    /// - **Outputs**: `Ok` once the record is in the block.
+    /// - **Side Effects**: Extends the block and its dictionary; updates
+    ///   [`stats`](Self::stats).
+    /// - **Failure Modes**: Buffer overflow when the record does not fit, or
+    ///   a timestamp earlier than the previous record's; the block is left
+    ///   unchanged so it can be downlinked as is.
+    pub fn push(&mut self, record: &EventRecord<'_>) -> Result<()> {
+        if self.stats.records == u32::from(u16::MAX) {
+            return Err(overflow());
+        }
+        let delta = record
+            .timestamp_ms
+            .checked_sub(self.last_timestamp_ms)
+            .ok_or_else(|| SpaceCommError::invalid_packet("Event timestamps out of order", None))?;
+
+        let (block_len, dictionary_len) = (self.block.len(), self.dictionary.len());
+        let encoded = self.encode(record, delta);
+        if encoded.is_err() {
+            self.block.truncate(block_len);
+            self.dictionary.truncate(dictionary_len);
+            return encoded;
+        }
+
+        self.last_timestamp_ms = record.timestamp_ms;
+        self.stats.records += 1;
+        self.stats.raw_bytes = self.stats.raw_bytes.saturating_add(record.raw_len() as u32);
+        self.stats.compressed_bytes = self.block.len() as u32;
+        (self.stats.records as u16).encode(WIRE_BYTE_ORDER, &mut self.block[..2]);
+        Ok(())
+    }
+
+    /// Wrap the block in a telemetry packet on [`EVENT_LOG_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            EVENT_LOG_APID,
+            sequence_count & 0x3FFF,
+            &self.block,
+            None,
+        )
+    }
+
+    fn encode(&mut self, record: &EventRecord<'_>, delta: u64) -> Result<()> {
+        let mut flags = record.level.code();
+        if record.error_code.is_some() {
+            flags |= ERROR_CODE_FLAG;
+        }
+        self.block.push(flags).map_err(|_| overflow())?;
+        write_varint(&mut self.block, delta)?;
+        self.encode_string(record.component)?;
+        self.encode_string(record.message)?;
+        if let Some(code) = record.error_code {
+            write_varint(&mut self.block, u64::from(code))?;
+        }
+        Ok(())
+    }
+
+    fn encode_string(&mut self, value: &str) -> Result<()> {
+        let block = &self.block;
+        let known = self
+            .dictionary
+            .iter()
+            .position(|&(at, len)| &block[at..at + len] == value.as_bytes());
+        if let Some(code) = known {
+            return self.block.push(code as u8).map_err(|_| overflow());
+        }
+
+        self.block.push(LITERAL_TAG).map_err(|_| overflow())?;
+        write_varint(&mut self.block, value.len() as u64)?;
+        let at = self.block.len();
+        self.block
+            .extend_from_slice(value.as_bytes())
+            .map_err(|_| overflow())?;
+        // A full dictionary leaves further new strings literal
+        let _ = self.dictionary.push((at, value.len()));
+        Ok(())
+    }
+}
+
+/// Decodes the records of a compressed block without copying strings
+#[derive(Debug, Clone)]
+pub struct EventLogDecoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    remaining: u16,
+    dictionary: Vec<&'a str, DICTIONARY_CAPACITY>,
+    last_timestamp_ms: u64,
+}
+
+impl<'a> EventLogDecoder<'a> {
+    /// Start decoding a block (the packet data field)
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let remaining = WireReader::new(bytes)
+            .get::<u16>()
+            .map_err(|_| SpaceCommError::invalid_packet("Event log block too short", None))?;
+        Ok(Self {
+            bytes,
+            position: 2,
+            remaining,
+            dictionary: Vec::new(),
+            last_timestamp_ms: 0,
+        })
+    }
+
+    /// Records not yet decoded
+    pub const fn remaining(&self) -> u16 {
+        self.remaining
+    }
+
+    /// Statistics of a block, decoding every record
+    pub fn stats(bytes: &'a [u8]) -> Result<CompressionStats> {
+        let mut stats = CompressionStats {
+            compressed_bytes: bytes.len() as u32,
+            ..CompressionStats::default()
+        };
+        for record in Self::new(bytes)? {
+            let record = record?;
+            stats.records += 1;
+            stats.raw_bytes = stats.raw_bytes.saturating_add(record.raw_len() as u32);
+        }
+        Ok(stats)
+    }
+
+    fn decode(&mut self) -> Result<EventRecord<'a>> {
+        let flags = self.read_byte()?;
+        let level =
+            EventLevel::from_code(flags & 0x07).ok_or_else(|| malformed("Unknown event level"))?;
+        let delta = self.read_varint()?;
+        let timestamp_ms = self
+            .last_timestamp_ms
+            .checked_add(delta)
+            .ok_or_else(|| malformed("Event timestamp overflow"))?;
+        let component = self.read_string()?;
+        let message = self.read_string()?;
+        let error_code = if flags & ERROR_CODE_FLAG != 0 {
+            let code = self.read_varint()?;
+            Some(u32::try_from(code).map_err(|_| malformed("Event error code too large"))?)
+        } else {
+            None
+        };
+
+        self.last_timestamp_ms = timestamp_ms;
+        Ok(EventRecord {
+            timestamp_ms,
+            level,
+            component,
+            message,
+            error_code,
+        })
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        let byte = *self
+            .bytes
+            .get(self.position)
+            .ok_or_else(|| malformed("Event log block truncated"))?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(malformed("Event log varint too long"))
+    }
+
+    fn read_string(&mut self) -> Result<&'a str> {
+        let tag = self.read_byte()?;
+        if tag != LITERAL_TAG {
+            return self
+                .dictionary
+                .get(usize::from(tag))
+                .copied()
+                .ok_or_else(|| malformed("Unknown event dictionary code"));
+        }
+
+        let len =
+            usize::try_from(self.read_varint()?).map_err(|_| malformed("Event string too long"))?;
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| malformed("Event log block truncated"))?;
+        let value = core::str::from_utf8(&self.bytes[self.position..end])
+            .map_err(|_| malformed("Event string is not UTF-8"))?;
+        self.position = end;
+        let _ = self.dictionary.push(value);
+        Ok(value)
+    }
+}
+
+impl<'a> Iterator for EventLogDecoder<'a> {
+    type Item = Result<EventRecord<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let record = self.decode();
+        if record.is_err() {
+            // Nothing after a malformed record can be located
+            self.remaining = 0;
+        }
+        Some(record)
+    }
+}
+
+fn write_varint<const N: usize>(out: &mut Vec<u8, N>, mut value: u64) -> Result<()> {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return out.push(byte).map_err(|_| overflow());
+        }
+        out.push(byte | 0x80).map_err(|_| overflow())?;
+    }
+}
+
+fn overflow() -> SpaceCommError {
+    SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, None)
+}
+
+fn malformed(reason: &'static str) -> SpaceCommError {
+    SpaceCommError::invalid_packet(reason, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        timestamp_ms: u64,
+        component: &'static str,
+        message: &'static str,
+    ) -> EventRecord<'static> {
+        EventRecord {
+            timestamp_ms,
+            level: EventLevel::Warning,
+            component,
+            message,
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn test_event_log_round_trip() {
+        let records = [
+            record(1_000_000, "COMM", "Telemetry transmission failed"),
+            EventRecord {
+                level: EventLevel::Critical,
+                error_code: Some(850),
+                ..record(1_000_010, "TELEMETRY", "Telemetry drop rate exceeded")
+            },
+            record(1_000_500, "COMM", "Telemetry transmission failed"),
+            record(1_000_500, "SYSTEM", "Telemetry transmission failed"),
+        ];
+
+        let mut compressor = EventLogCompressor::<256>::new();
+        for r in &records {
+            compressor.push(r).unwrap();
+        }
+        assert_eq!(compressor.len(), 4);
+
+        let packet = compressor.to_packet(1).unwrap();
+        assert_eq!(packet.header.apid, EVENT_LOG_APID);
+        let decoded: Vec<EventRecord<'_>, 4> = EventLogDecoder::new(&packet.data)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(decoded.as_slice(), &records);
+
+        assert_eq!(
+            EventLogDecoder::stats(&packet.data).unwrap(),
+            *compressor.stats()
+        );
+    }
+
+    #[test]
+    fn test_repeated_records_compress() {
+        let mut compressor = EventLogCompressor::<512>::new();
+        for i in 0..20 {
+            compressor
+                .push(&record(
+                    5_000 + i * 100,
+                    "COMM",
+                    "Telemetry transmission failed",
+                ))
+                .unwrap();
+        }
+
+        let stats = compressor.stats();
+        assert_eq!(stats.records, 20);
+        assert_eq!(stats.raw_bytes, 20 * (15 + 4 + 29));
+        assert_eq!(stats.compressed_bytes as usize, compressor.as_bytes().len());
+        // Repeats cost flags, a two-byte delta and two codes
+        assert!(stats.ratio() < 0.2, "ratio {}", stats.ratio());
+
+        let mut total = CompressionStats::default();
+        total.accumulate(stats);
+        total.accumulate(stats);
+        assert_eq!(total.records, 40);
+        assert_eq!(total.ratio(), stats.ratio());
+    }
+
+    #[test]
+    fn test_full_block_and_malformed_input() {
+        let mut compressor = EventLogCompressor::<48>::new();
+        compressor
+            .push(&record(1, "COMM", "Telemetry transmission failed"))
+            .unwrap();
+        let before = compressor.as_bytes().len();
+        assert!(compressor
+            .push(&record(2, "HARDWARE", "Transceiver lock lost"))
+            .is_err());
+        assert_eq!(compressor.as_bytes().len(), before);
+        // Repeats still fit after a rejected record
+        compressor
+            .push(&record(3, "COMM", "Telemetry transmission failed"))
+            .unwrap();
+        assert!(compressor
+            .push(&record(2, "COMM", "Telemetry transmission failed"))
+            .is_err());
+
+        let bytes = compressor.as_bytes();
+        let truncated = &bytes[..bytes.len() - 1];
+        let results: Vec<Result<EventRecord<'_>>, 2> =
+            EventLogDecoder::new(truncated).unwrap().collect();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+
+        let mut bad_code = [0u8, 1, EventLevel::Info.code(), 0, 7, 7];
+        assert!(EventLogDecoder::new(&bad_code)
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_err());
+        bad_code[2] = 6;
+        assert!(EventLogDecoder::new(&bad_code)
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(EventLogDecoder::new(&[0]).is_err());
+    }
+}
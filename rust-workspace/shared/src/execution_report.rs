@@ -0,0 +1,265 @@
+//! Per-command execution reports
+//!
+//! After executing a command the satellite downlinks an [`ExecutionReport`]
+//! on its own APID: the command ID (the [`SpaceCommand`] discriminant on the
+//! wire), the uplink sequence count and priority, a result code, and the
+//! measured execution time alongside the priority's execution budget. The
+//! ground archives the reports as evidence that each command met its timing
+//! requirement.
+//!
+//! Reports use a fixed layout of [`EXECUTION_REPORT_LEN`] bytes in the
+//! [`crate::wire`] format:
+//!
+//! | Bytes  | Field               |
+//! |--------|---------------------|
+//! | 0..4   | `command_id`        |
+//! | 4..6   | `sequence_count`    |
+//! | 6      | `priority`          |
+//! | 7      | `result`            |
+//! | 8..12  | `execution_time_us` |
+//! | 12..16 | `budget_ms`         |
+//!
+//! # Requirements Traceability
+//! - REQ-PF-001: Command Response Time (measured execution time vs. budget)
+//! - REQ-IF-002: CCSDS Compliance (report carried in a Space Packet)
+
+use serde::{Deserialize, Serialize};
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::messaging::MessagePriority;
+use crate::wire::{WireReader, WireWriter};
+
+/// APID used to downlink command execution reports
+pub const EXECUTION_REPORT_APID: u16 = 0x013;
+
+/// Serialized size of an execution report
+pub const EXECUTION_REPORT_LEN: usize = 16;
+
+/// Result code of an executed command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ExecutionResult {
+    /// Command ran to completion
+    Completed = 0,
+    /// Command ran but its execution failed
+    Failed = 1,
+    /// Command was refused before execution (invalid, unauthenticated or
+    /// misconfigured)
+    Rejected = 2,
+}
+
+impl ExecutionResult {
+    /// Result code carried in the report
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Decode a result code
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(ExecutionResult::Completed),
+            1 => Some(ExecutionResult::Failed),
+            2 => Some(ExecutionResult::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Classify the outcome of a command handler
+    pub fn of<T>(outcome: &Result<T>) -> Self {
+        match outcome {
+            Ok(_) => ExecutionResult::Completed,
+            Err(
+                SpaceCommError::InvalidPacket { .. }
+                | SpaceCommError::CryptographicError { .. }
+                | SpaceCommError::ConfigurationError { .. },
+            ) => ExecutionResult::Rejected,
+            Err(_) => ExecutionResult::Failed,
+        }
+    }
+}
+
+/// Execution report for one command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    /// Command identifier as uplinked
+    pub command_id: u32,
+    /// Sequence count of the uplinked command packet
+    pub sequence_count: u16,
+    /// Priority the command was executed at
+    pub priority: MessagePriority,
+    /// Result code
+    pub result: ExecutionResult,
+    /// Measured execution time in microseconds
+    pub execution_time_us: u32,
+    /// Maximum execution time allowed for `priority`, in milliseconds
+    pub budget_ms: u32,
+}
+
+impl ExecutionReport {
+    /// Report a command executed at `priority`, budgeted by
+    /// [`MessagePriority::max_execution_time_ms`].
+    ///
+    /// - **ID**: FN-EXR-001
+    /// - **Requirement**: Every executed command is reported with its result
+    ///   and measured execution time against its budget (REQ-PF-001).
+    /// - **Inputs**: Command ID, packet sequence count, priority, result,
+    ///   measured execution time in microseconds.
+    /// - **Outputs**: A report whose `budget_ms` matches the priority.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: None.
+    pub const fn new(
+        command_id: u32,
+        sequence_count: u16,
+        priority: MessagePriority,
+        result: ExecutionResult,
+        execution_time_us: u32,
+    ) -> Self {
+        Self {
+            command_id,
+            sequence_count,
+            priority,
+            result,
+            execution_time_us,
+            budget_ms: priority.max_execution_time_ms(),
+        }
+    }
+
+    /// Report an executed [`SpaceCommand`], identified by its discriminant
+    pub fn for_command(
+        command: &SpaceCommand,
+        sequence_count: u16,
+        result: ExecutionResult,
+        execution_time_us: u32,
+    ) -> Self {
+        Self::new(
+            command.discriminant(),
+            sequence_count,
+            command.priority(),
+            result,
+            execution_time_us,
+        )
+    }
+
+    /// Whether the measured execution time is within the budget
+    pub const fn within_budget(&self) -> bool {
+        self.execution_time_us as u64 <= self.budget_ms as u64 * 1000
+    }
+
+    /// Serialize the report in the fixed downlink layout
+    pub fn to_bytes(&self) -> [u8; EXECUTION_REPORT_LEN] {
+        let mut writer = WireWriter::<EXECUTION_REPORT_LEN>::new();
+        // Capacity is exactly the fixed layout
+        let _ = writer
+            .put(self.command_id)
+            .and_then(|w| w.put(self.sequence_count))
+            .and_then(|w| w.put(self.priority as u8))
+            .and_then(|w| w.put(self.result.code()))
+            .and_then(|w| w.put(self.execution_time_us))
+            .and_then(|w| w.put(self.budget_ms));
+        let mut bytes = [0u8; EXECUTION_REPORT_LEN];
+        bytes.copy_from_slice(&writer.finish());
+        bytes
+    }
+
+    /// Parse a report from the packet data field
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < EXECUTION_REPORT_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "Execution report too short",
+                None,
+            ));
+        }
+        let mut reader = WireReader::new(bytes);
+        let (command_id, sequence_count) = (reader.get()?, reader.get()?);
+        let priority = priority_from_code(reader.get()?).ok_or(
+            SpaceCommError::invalid_packet("Unknown execution report priority", None),
+        )?;
+        let result = ExecutionResult::from_code(reader.get()?).ok_or(
+            SpaceCommError::invalid_packet("Unknown execution result code", None),
+        )?;
+
+        Ok(Self {
+            command_id,
+            sequence_count,
+            priority,
+            result,
+            execution_time_us: reader.get()?,
+            budget_ms: reader.get()?,
+        })
+    }
+
+    /// Wrap the report in a telemetry packet on [`EXECUTION_REPORT_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            EXECUTION_REPORT_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes(),
+            None,
+        )
+    }
+}
+
+/// Priority from its `#[repr(u8)]` value
+const fn priority_from_code(code: u8) -> Option<MessagePriority> {
+    match code {
+        1 => Some(MessagePriority::Low),
+        2 => Some(MessagePriority::Medium),
+        3 => Some(MessagePriority::High),
+        4 => Some(MessagePriority::Critical),
+        5 => Some(MessagePriority::Emergency),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::EmergencyReason;
+
+    #[test]
+    fn test_execution_report_round_trip() {
+        let report = ExecutionReport::new(
+            0x2001,
+            0x0123,
+            MessagePriority::High,
+            ExecutionResult::Completed,
+            42_000,
+        );
+        assert_eq!(report.budget_ms, 100);
+        assert!(report.within_budget());
+
+        let packet = report.to_packet(7).unwrap();
+        assert_eq!(packet.header.apid, EXECUTION_REPORT_APID);
+        assert_eq!(packet.header.packet_type, PacketType::Telemetry);
+        assert_eq!(ExecutionReport::from_bytes(&packet.data).unwrap(), report);
+
+        let mut bytes = report.to_bytes();
+        bytes[7] = 9;
+        assert!(ExecutionReport::from_bytes(&bytes).is_err());
+        bytes[7] = 0;
+        bytes[6] = 0;
+        assert!(ExecutionReport::from_bytes(&bytes).is_err());
+        assert!(ExecutionReport::from_bytes(&bytes[..15]).is_err());
+    }
+
+    #[test]
+    fn test_execution_report_budget_and_results() {
+        let abort = SpaceCommand::EmergencyAbort {
+            reason: EmergencyReason::SystemFailure,
+            confirmation_code: 0xDEADBEEF,
+        };
+        let report = ExecutionReport::for_command(&abort, 1, ExecutionResult::Completed, 1_001);
+        assert_eq!(report.command_id, abort.discriminant());
+        assert_eq!(report.budget_ms, abort.max_execution_time_ms());
+        assert!(!report.within_budget());
+
+        assert_eq!(ExecutionResult::of(&Ok(())), ExecutionResult::Completed);
+        let rejected: Result<()> = Err(SpaceCommError::invalid_packet("bad", None));
+        assert_eq!(ExecutionResult::of(&rejected), ExecutionResult::Rejected);
+        let failed: Result<()> = Err(SpaceCommError::communication_timeout(10, "uplink"));
+        assert_eq!(ExecutionResult::of(&failed), ExecutionResult::Failed);
+    }
+}
@@ -0,0 +1,613 @@
+//! CCSDS Reed-Solomon (255,223) forward error correction
+//!
+//! K- and Ka-band paths fade with rain and scintillation, and a single
+//! corrupted byte fails a frame's CRC and throws the whole frame away. The
+//! Reed-Solomon (255,223) code of CCSDS 131.0-B adds 32 check symbols to
+//! every 223 data bytes and corrects up to 16 bad bytes anywhere in the
+//! 255-byte codeblock, which turns most of those frames back into good ones.
+//!
+//! - **Code**: symbols in GF(2⁸) with field polynomial
+//!   x⁸ + x⁷ + x² + x + 1, generator roots α^(11·j) for j = 112..=143, and
+//!   symbols sent in the Berlekamp dual basis, as CCSDS 131.0-B specifies.
+//! - **Codeblocks**: [`encode_block`] and [`decode_block`] work on one
+//!   codeblock in place, data first and the 32 check symbols last. Blocks
+//!   shorter than 255 bytes are shortened codeblocks, the leading data
+//!   symbols taken as zero and not sent.
+//! - **Frames**: [`encode_frame`] splits a serialized frame into 223-byte
+//!   pieces, the last one shortened, and appends each piece's check symbols;
+//!   [`decode_frame`] reverses it.
+//! - **Policy**: [`FecPolicy`] says which bands carry coded frames. Both
+//!   ends of a link must agree on it; by default only K and Ka band do.
+//!
+//! The tables are built at compile time and nothing allocates, so the codec
+//! is available without `std`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (per-band error correction on
+//!   noisy K and Ka band paths)
+//! - REQ-SF-002: Data Integrity (correction of corrupted symbols before the
+//!   frame check)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::types::BandType;
+
+/// Symbols in a full codeblock
+pub const RS_BLOCK_LEN: usize = 255;
+
+/// Data symbols in a full codeblock
+pub const RS_DATA_LEN: usize = 223;
+
+/// Check symbols in every codeblock
+pub const RS_PARITY_LEN: usize = RS_BLOCK_LEN - RS_DATA_LEN;
+
+/// Symbol errors a codeblock can correct
+pub const RS_MAX_CORRECTABLE: usize = RS_PARITY_LEN / 2;
+
+/// Largest frame [`encode_frame`] takes, the largest serialized
+/// [`SpacePacket`](crate::ccsds::SpacePacket)
+pub const MAX_FRAME_LEN: usize = 4096;
+
+/// Largest coded frame, [`MAX_FRAME_LEN`] with the check symbols of every
+/// codeblock it spans
+pub const MAX_CODED_FRAME_LEN: usize = coded_len(MAX_FRAME_LEN);
+
+/// Field polynomial x⁸ + x⁷ + x² + x + 1
+const FIELD_POLY: u16 = 0x187;
+
+/// Exponent of the first generator root, in powers of [`ROOT_STEP`]
+const FIRST_ROOT: i64 = 112;
+
+/// Step between generator roots, in powers of α
+const ROOT_STEP: i64 = 11;
+
+/// Order of the multiplicative group of GF(2⁸)
+const FIELD_ORDER: i64 = 255;
+
+/// Powers of α: `ALPHA_TO[i]` = α^i
+const ALPHA_TO: [u8; 255] = {
+    let mut table = [0u8; 255];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        table[i] = x as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= FIELD_POLY;
+        }
+        i += 1;
+    }
+    table
+};
+
+/// Logarithms to base α: `INDEX_OF[α^i]` = i; `INDEX_OF[0]` is unused
+const INDEX_OF: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 255 {
+        table[ALPHA_TO[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Generator polynomial, coefficient of x^i at index i
+const GENERATOR: [u8; RS_PARITY_LEN + 1] = {
+    let mut g = [0u8; RS_PARITY_LEN + 1];
+    g[0] = 1;
+    let mut j = 0;
+    while j < RS_PARITY_LEN {
+        // Multiply by (x + β), β the next root
+        let root = ((FIRST_ROOT + j as i64) * ROOT_STEP % FIELD_ORDER) as usize;
+        let mut i = j + 1;
+        while i > 0 {
+            g[i] = g[i - 1] ^ const_mul(g[i], ALPHA_TO[root]);
+            i -= 1;
+        }
+        g[0] = const_mul(g[0], ALPHA_TO[root]);
+        j += 1;
+    }
+    g
+};
+
+/// Dual basis images of the conventional basis bits, most significant
+/// first (CCSDS 131.0-B Annex F)
+const DUAL_BASIS: [u8; 8] = [0x8d, 0xef, 0xec, 0x86, 0xfa, 0x99, 0xaf, 0x7b];
+
+/// Conventional to dual basis
+const TO_DUAL: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut symbol = 0u8;
+        let mut bit = 0;
+        while bit < 8 {
+            if i & (1 << bit) != 0 {
+                symbol ^= DUAL_BASIS[7 - bit];
+            }
+            bit += 1;
+        }
+        table[i] = symbol;
+        i += 1;
+    }
+    table
+};
+
+/// Dual to conventional basis
+const FROM_DUAL: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[TO_DUAL[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// GF(2⁸) product, for the compile-time tables
+const fn const_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let exponent = (INDEX_OF[a as usize] as usize + INDEX_OF[b as usize] as usize) % 255;
+    ALPHA_TO[exponent]
+}
+
+/// GF(2⁸) product
+fn mul(a: u8, b: u8) -> u8 {
+    const_mul(a, b)
+}
+
+/// GF(2⁸) quotient; `b` must not be zero
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+    let exponent = INDEX_OF[a as usize] as i64 - INDEX_OF[b as usize] as i64;
+    alpha_pow(exponent)
+}
+
+/// α^exponent, any integer exponent
+fn alpha_pow(exponent: i64) -> u8 {
+    ALPHA_TO[exponent.rem_euclid(FIELD_ORDER) as usize]
+}
+
+/// Value of the polynomial `coefficients` (x^i at index i) at `x`
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |value, &coefficient| mul(value, x) ^ coefficient)
+}
+
+/// Check the length of a codeblock.
+fn check_block_len(len: usize) -> Result<()> {
+    if len <= RS_PARITY_LEN || len > RS_BLOCK_LEN {
+        return Err(SpaceCommError::invalid_packet(
+            "Reed-Solomon codeblock must be 33 to 255 bytes",
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/// Fill in the check symbols of one codeblock.
+///
+/// - **ID**: FN-FEC-001
+/// - **Requirement**: Frames on coded bands carry Reed-Solomon (255,223)
+///   check symbols per CCSDS 131.0-B.
+/// - **Inputs**: `block`: data symbols, then [`RS_PARITY_LEN`] bytes whose
+///   contents are overwritten; 33 to 255 bytes, shorter blocks being
+///   shortened codeblocks.
+/// - **Failure Modes**: A block of another length → `InvalidPacket`.
+pub fn encode_block(block: &mut [u8]) -> Result<()> {
+    check_block_len(block.len())?;
+    let (data, check) = block.split_at_mut(block.len() - RS_PARITY_LEN);
+
+    // Division of data(x)·x³² by the generator, in the conventional basis
+    let mut parity = [0u8; RS_PARITY_LEN];
+    for &symbol in data.iter() {
+        let feedback = FROM_DUAL[symbol as usize] ^ parity[0];
+        parity.copy_within(1.., 0);
+        parity[RS_PARITY_LEN - 1] = 0;
+        if feedback != 0 {
+            for (i, p) in parity.iter_mut().enumerate() {
+                *p ^= mul(feedback, GENERATOR[RS_PARITY_LEN - 1 - i]);
+            }
+        }
+    }
+
+    for (out, p) in check.iter_mut().zip(parity) {
+        *out = TO_DUAL[p as usize];
+    }
+    Ok(())
+}
+
+/// Correct one codeblock in place.
+///
+/// - **ID**: FN-FEC-002
+/// - **Requirement**: Up to [`RS_MAX_CORRECTABLE`] corrupted symbols in a
+///   codeblock are corrected before the frame check.
+/// - **Inputs**: `block`: a codeblock as [`encode_block`] makes it.
+/// - **Outputs**: Symbols corrected; the block is left as received when
+///   there are none or they cannot be corrected.
+/// - **Failure Modes**: A block of a length [`encode_block`] does not take
+///   → `InvalidPacket`; more errors than the code corrects, where detected
+///   → `IntegrityError`.
+pub fn decode_block(block: &mut [u8]) -> Result<usize> {
+    check_block_len(block.len())?;
+    let len = block.len();
+
+    let mut received = [0u8; RS_BLOCK_LEN];
+    for (r, &symbol) in received.iter_mut().zip(block.iter()) {
+        *r = FROM_DUAL[symbol as usize];
+    }
+    let received = &mut received[..len];
+
+    // Syndromes; symbol i is the coefficient of x^(len-1-i)
+    let mut syndromes = [0u8; RS_PARITY_LEN];
+    for (j, s) in syndromes.iter_mut().enumerate() {
+        let root = alpha_pow((FIRST_ROOT + j as i64) * ROOT_STEP);
+        *s = received.iter().fold(0, |value, &r| mul(value, root) ^ r);
+    }
+    if syndromes.iter().all(|&s| s == 0) {
+        return Ok(0);
+    }
+
+    // Error locator by Berlekamp-Massey
+    let mut locator = [0u8; RS_PARITY_LEN + 1];
+    let mut previous = [0u8; RS_PARITY_LEN + 1];
+    locator[0] = 1;
+    previous[0] = 1;
+    let mut degree = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1u8;
+    for r in 0..RS_PARITY_LEN {
+        let discrepancy =
+            (1..=degree).fold(syndromes[r], |d, i| d ^ mul(locator[i], syndromes[r - i]));
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = div(discrepancy, previous_discrepancy);
+        let before = locator;
+        for i in shift..=RS_PARITY_LEN {
+            locator[i] ^= mul(scale, previous[i - shift]);
+        }
+        if 2 * degree <= r {
+            degree = r + 1 - degree;
+            previous = before;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    if degree > RS_MAX_CORRECTABLE {
+        return Err(uncorrectable());
+    }
+
+    // Error evaluator, syndromes times locator mod x³²
+    let mut evaluator = [0u8; RS_PARITY_LEN];
+    for (i, e) in evaluator.iter_mut().enumerate() {
+        *e = (0..=i.min(degree)).fold(0, |acc, k| acc ^ mul(locator[k], syndromes[i - k]));
+    }
+
+    // Formal derivative of the locator: only odd powers survive
+    let mut derivative = [0u8; RS_PARITY_LEN];
+    for i in (1..=degree).step_by(2) {
+        derivative[i - 1] = locator[i];
+    }
+
+    // Chien search over the positions present in the block, with Forney's
+    // formula for each magnitude
+    let mut corrections = [(0usize, 0u8); RS_MAX_CORRECTABLE];
+    let mut found = 0;
+    for position in 0..len {
+        let power = (len - 1 - position) as i64 * ROOT_STEP;
+        let inverse = alpha_pow(-power);
+        if evaluate(&locator[..=degree], inverse) != 0 {
+            continue;
+        }
+        let denominator = evaluate(&derivative[..degree], inverse);
+        if denominator == 0 || found == degree {
+            return Err(uncorrectable());
+        }
+        let magnitude = mul(
+            alpha_pow(power * (1 - FIRST_ROOT)),
+            div(evaluate(&evaluator, inverse), denominator),
+        );
+        corrections[found] = (position, magnitude);
+        found += 1;
+    }
+    // Roots falling outside a shortened block mean too many errors
+    if found != degree {
+        return Err(uncorrectable());
+    }
+
+    for &(position, magnitude) in &corrections[..found] {
+        received[position] ^= magnitude;
+        block[position] = TO_DUAL[received[position] as usize];
+    }
+    Ok(found)
+}
+
+/// Error for a codeblock with more errors than the code corrects.
+fn uncorrectable() -> SpaceCommError {
+    SpaceCommError::IntegrityError {
+        check_type: "Reed-Solomon (255,223)",
+        expected: "at most 16 symbol errors per codeblock",
+        calculated: "uncorrectable codeblock",
+    }
+}
+
+/// Length of the coded frame for a frame of `frame_len` bytes.
+pub const fn coded_len(frame_len: usize) -> usize {
+    frame_len + frame_len.div_ceil(RS_DATA_LEN) * RS_PARITY_LEN
+}
+
+/// Encode a serialized frame.
+///
+/// - **ID**: FN-FEC-003
+/// - **Requirement**: Whole frames are sent as a run of codeblocks.
+/// - **Outputs**: Each 223-byte piece of `frame` followed by its check
+///   symbols; the last piece is a shortened codeblock. [`coded_len`] bytes
+///   in all.
+/// - **Failure Modes**: An empty frame or one over [`MAX_FRAME_LEN`] →
+///   `InvalidPacket`.
+pub fn encode_frame(frame: &[u8]) -> Result<heapless::Vec<u8, MAX_CODED_FRAME_LEN>> {
+    if frame.is_empty() || frame.len() > MAX_FRAME_LEN {
+        return Err(SpaceCommError::invalid_packet(
+            "Frame to encode must be 1 to 4096 bytes",
+            None,
+        ));
+    }
+    let mut coded = heapless::Vec::new();
+    for piece in frame.chunks(RS_DATA_LEN) {
+        let start = coded.len();
+        coded
+            .extend_from_slice(piece)
+            .and_then(|()| coded.extend_from_slice(&[0u8; RS_PARITY_LEN]))
+            .map_err(|()| {
+                SpaceCommError::memory_error(
+                    MemoryErrorType::BufferOverflow,
+                    Some(MAX_CODED_FRAME_LEN),
+                )
+            })?;
+        encode_block(&mut coded[start..])?;
+    }
+    Ok(coded)
+}
+
+/// A frame recovered by [`decode_frame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFrame {
+    /// The frame, check symbols removed
+    pub bytes: heapless::Vec<u8, MAX_FRAME_LEN>,
+    /// Symbols corrected across all codeblocks
+    pub corrected_symbols: usize,
+}
+
+/// Decode a coded frame.
+///
+/// - **ID**: FN-FEC-004
+/// - **Requirement**: Coded frames are corrected and stripped of their
+///   check symbols before the frame is parsed.
+/// - **Failure Modes**: A length no frame encodes to → `InvalidPacket`; an
+///   uncorrectable codeblock → `IntegrityError`.
+pub fn decode_frame(coded: &[u8]) -> Result<DecodedFrame> {
+    let remainder = coded.len() % RS_BLOCK_LEN;
+    if coded.is_empty()
+        || coded.len() > MAX_CODED_FRAME_LEN
+        || (remainder != 0 && remainder <= RS_PARITY_LEN)
+    {
+        return Err(SpaceCommError::invalid_packet(
+            "Coded frame length is not a run of Reed-Solomon codeblocks",
+            None,
+        ));
+    }
+    let mut block = [0u8; RS_BLOCK_LEN];
+    let mut decoded = DecodedFrame {
+        bytes: heapless::Vec::new(),
+        corrected_symbols: 0,
+    };
+    for piece in coded.chunks(RS_BLOCK_LEN) {
+        let block = &mut block[..piece.len()];
+        block.copy_from_slice(piece);
+        decoded.corrected_symbols += decode_block(block)?;
+        decoded
+            .bytes
+            .extend_from_slice(&block[..block.len() - RS_PARITY_LEN])
+            .map_err(|()| {
+                SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_FRAME_LEN))
+            })?;
+    }
+    Ok(decoded)
+}
+
+/// Which bands send frames Reed-Solomon coded.
+///
+/// - **ID**: MOD-FEC-001
+/// - **Requirement**: Error correction is switched on per band, so the
+///   fading K and Ka band paths pay for it and clean UHF, S and X band
+///   paths need not.
+/// - **Constraints**: Both ends of a link need the same policy; a frame
+///   coded on one end and not the other fails to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FecPolicy {
+    /// Code UHF band frames
+    pub uhf_band: bool,
+    /// Code S band frames
+    pub s_band: bool,
+    /// Code X band frames
+    pub x_band: bool,
+    /// Code K band frames
+    pub k_band: bool,
+    /// Code Ka band frames
+    pub ka_band: bool,
+}
+
+impl Default for FecPolicy {
+    fn default() -> Self {
+        Self {
+            uhf_band: false,
+            s_band: false,
+            x_band: false,
+            k_band: true,
+            ka_band: true,
+        }
+    }
+}
+
+impl FecPolicy {
+    /// Policy coding no band.
+    pub const fn disabled() -> Self {
+        Self {
+            uhf_band: false,
+            s_band: false,
+            x_band: false,
+            k_band: false,
+            ka_band: false,
+        }
+    }
+
+    /// Whether frames on `band` are coded.
+    pub const fn is_enabled(&self, band: BandType) -> bool {
+        match band {
+            BandType::UhfBand => self.uhf_band,
+            BandType::SBand => self.s_band,
+            BandType::XBand => self.x_band,
+            BandType::KBand => self.k_band,
+            BandType::KaBand => self.ka_band,
+        }
+    }
+
+    /// Turn coding on `band` on or off.
+    pub fn set(&mut self, band: BandType, enabled: bool) {
+        let flag = match band {
+            BandType::UhfBand => &mut self.uhf_band,
+            BandType::SBand => &mut self.s_band,
+            BandType::XBand => &mut self.x_band,
+            BandType::KBand => &mut self.k_band,
+            BandType::KaBand => &mut self.ka_band,
+        };
+        *flag = enabled;
+    }
+
+    /// Bytes to send for `frame` on `band`: the coded frame where `band` is
+    /// coded, `frame` unchanged otherwise.
+    pub fn encode(
+        &self,
+        band: BandType,
+        frame: &[u8],
+    ) -> Result<heapless::Vec<u8, MAX_CODED_FRAME_LEN>> {
+        if self.is_enabled(band) {
+            return encode_frame(frame);
+        }
+        let mut bytes = heapless::Vec::new();
+        bytes.extend_from_slice(frame).map_err(|()| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_CODED_FRAME_LEN))
+        })?;
+        Ok(bytes)
+    }
+
+    /// Frame received as `bytes` on `band`: decoded where `band` is coded,
+    /// `bytes` unchanged otherwise.
+    pub fn decode(&self, band: BandType, bytes: &[u8]) -> Result<DecodedFrame> {
+        if self.is_enabled(band) {
+            return decode_frame(bytes);
+        }
+        let mut decoded = DecodedFrame {
+            bytes: heapless::Vec::new(),
+            corrected_symbols: 0,
+        };
+        decoded.bytes.extend_from_slice(bytes).map_err(|()| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_FRAME_LEN))
+        })?;
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(len: usize) -> heapless::Vec<u8, MAX_FRAME_LEN> {
+        (0..len).map(|i| (i * 7 + 3) as u8).collect()
+    }
+
+    #[test]
+    fn generator_roots_are_the_ccsds_roots() {
+        for j in 0..RS_PARITY_LEN as i64 {
+            let root = alpha_pow((FIRST_ROOT + j) * ROOT_STEP);
+            assert_eq!(evaluate(&GENERATOR, root), 0);
+        }
+        // The generator is palindromic, as CCSDS chose the roots to make it
+        for i in 0..=RS_PARITY_LEN {
+            assert_eq!(GENERATOR[i], GENERATOR[RS_PARITY_LEN - i]);
+        }
+        for i in 0..256 {
+            assert_eq!(FROM_DUAL[TO_DUAL[i] as usize] as usize, i);
+        }
+    }
+
+    #[test]
+    fn frame_round_trips_through_full_and_shortened_blocks() {
+        let original = frame(1000);
+        let coded = encode_frame(&original).unwrap();
+        assert_eq!(coded.len(), coded_len(1000));
+        assert_eq!(coded.len(), 1000 + 5 * RS_PARITY_LEN);
+        assert_eq!(&coded[..RS_DATA_LEN], &original[..RS_DATA_LEN]);
+
+        let decoded = decode_frame(&coded).unwrap();
+        assert_eq!(decoded.bytes, original);
+        assert_eq!(decoded.corrected_symbols, 0);
+    }
+
+    #[test]
+    fn corrects_up_to_sixteen_errors_per_block() {
+        let original = frame(300);
+        let mut coded = encode_frame(&original).unwrap();
+        // 16 errors in the full block, 16 in the shortened one, check
+        // symbols included
+        for i in 0..RS_MAX_CORRECTABLE {
+            coded[i * 15 + 2] ^= 0xA5;
+            coded[RS_BLOCK_LEN + i * 7] ^= (i as u8) + 1;
+        }
+        let decoded = decode_frame(&coded).unwrap();
+        assert_eq!(decoded.bytes, original);
+        assert_eq!(decoded.corrected_symbols, 2 * RS_MAX_CORRECTABLE);
+    }
+
+    #[test]
+    fn rejects_more_errors_than_it_corrects() {
+        let original = frame(RS_DATA_LEN);
+        let mut coded = encode_frame(&original).unwrap();
+        for i in 0..=RS_MAX_CORRECTABLE {
+            coded[i * 13] ^= 0x3C;
+        }
+        let result = decode_frame(&coded);
+        assert!(result.map_or(true, |decoded| decoded.bytes != original));
+        assert!(decode_frame(&coded[..RS_PARITY_LEN]).is_err());
+    }
+
+    #[test]
+    fn policy_codes_only_enabled_bands() {
+        let policy = FecPolicy::default();
+        let original = frame(64);
+        assert_eq!(policy.encode(BandType::SBand, &original).unwrap(), original);
+        let coded = policy.encode(BandType::KaBand, &original).unwrap();
+        assert_eq!(coded.len(), 64 + RS_PARITY_LEN);
+        assert_eq!(
+            policy.decode(BandType::KaBand, &coded).unwrap().bytes,
+            original
+        );
+
+        let mut policy = FecPolicy::disabled();
+        policy.set(BandType::XBand, true);
+        assert!(policy.is_enabled(BandType::XBand));
+        assert!(!policy.is_enabled(BandType::KBand));
+    }
+}
@@ -0,0 +1,494 @@
+//! Recorder file downlink manifests and selective retransmission
+//!
+//! As each stored product finishes downlinking, the onboard recorder sends a
+//! [`FileManifest`] describing every file of the pass so far (ID, size, CRC). The
+//! ground tracks which byte ranges of each file actually arrived with a
+//! [`ReceptionTracker`] and, for anything incomplete or failing its CRC, builds a
+//! [`RetransmitRequest`] naming whole files or specific byte ranges. The request
+//! is uplinked before the next pass so partially received products can be
+//! completed without replaying the whole recorder.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (data storage and management)
+//! - REQ-IF-002: CCSDS Compliance (manifest and request serialization)
+//! - REQ-NF-004: Fault Tolerance (recovery of partially received products)
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::ccsds::{crc16_ccitt, PacketType, SpacePacket};
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+
+/// Maximum number of files described by one manifest
+pub const MAX_MANIFEST_FILES: usize = 32;
+
+/// Maximum number of items in one retransmission request
+pub const MAX_RETRANSMIT_ITEMS: usize = 16;
+
+/// Maximum number of disjoint received ranges tracked per file
+pub const MAX_TRACKED_RANGES: usize = 32;
+
+/// APID used to downlink recorder file manifests
+pub const FILE_MANIFEST_APID: u16 = 0x011;
+
+/// APID used to uplink retransmission requests
+pub const RETRANSMIT_REQUEST_APID: u16 = 0x012;
+
+/// Half-open byte range `[offset, offset + length)` within a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    /// First byte of the range
+    pub offset: u32,
+    /// Number of bytes in the range
+    pub length: u32,
+}
+
+impl ByteRange {
+    /// Create a new byte range
+    pub const fn new(offset: u32, length: u32) -> Self {
+        Self { offset, length }
+    }
+
+    /// One past the last byte of the range
+    pub const fn end(&self) -> u32 {
+        self.offset.saturating_add(self.length)
+    }
+}
+
+/// Description of one recorder file included in a downlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// Recorder file identifier
+    pub file_id: u32,
+    /// Total file size in bytes
+    pub size_bytes: u32,
+    /// CRC-16/CCITT-FALSE over the complete file contents
+    pub crc: u16,
+}
+
+impl FileEntry {
+    /// Describe a file, computing its CRC from the contents
+    pub fn from_contents(file_id: u32, contents: &[u8]) -> Self {
+        Self {
+            file_id,
+            size_bytes: contents.len() as u32,
+            crc: file_crc(contents),
+        }
+    }
+}
+
+/// Manifest of the recorder files downlinked in a pass
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileManifest {
+    /// Pass (or downlink session) this manifest belongs to
+    pub pass_id: u32,
+    /// Files of this pass downlinked so far
+    pub files: Vec<FileEntry, MAX_MANIFEST_FILES>,
+}
+
+impl FileManifest {
+    /// Create an empty manifest for a pass
+    pub const fn new(pass_id: u32) -> Self {
+        Self {
+            pass_id,
+            files: Vec::new(),
+        }
+    }
+
+    /// Add a file to the manifest
+    pub fn push(&mut self, entry: FileEntry) -> Result<()> {
+        self.files.push(entry).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_MANIFEST_FILES))
+        })
+    }
+
+    /// Look up a file by ID
+    pub fn file(&self, file_id: u32) -> Option<&FileEntry> {
+        self.files.iter().find(|f| f.file_id == file_id)
+    }
+
+    /// Serialize the manifest for downlink
+    pub fn to_bytes(&self) -> Result<Vec<u8, 2048>> {
+        let bytes = serde_json::to_vec(self).map_err(|_| {
+            SpaceCommError::invalid_packet("Failed to serialize file manifest", None)
+        })?;
+        Vec::from_slice(&bytes).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+        })
+    }
+
+    /// Parse a manifest received on the downlink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed file manifest", None))
+    }
+
+    /// Wrap the manifest in a Space Packet on [`FILE_MANIFEST_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            FILE_MANIFEST_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes()?,
+            None,
+        )
+    }
+}
+
+/// One item of a retransmission request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetransmitItem {
+    /// File to retransmit
+    pub file_id: u32,
+    /// Byte range to retransmit; `None` requests the whole file
+    pub range: Option<ByteRange>,
+}
+
+/// Ground request to retransmit files or byte ranges on the next pass
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetransmitRequest {
+    /// Pass whose manifest the request refers to
+    pub pass_id: u32,
+    /// Files and ranges to send again
+    pub items: Vec<RetransmitItem, MAX_RETRANSMIT_ITEMS>,
+}
+
+impl RetransmitRequest {
+    /// Create an empty request for a pass
+    pub const fn new(pass_id: u32) -> Self {
+        Self {
+            pass_id,
+            items: Vec::new(),
+        }
+    }
+
+    /// Request retransmission of a whole file
+    pub fn request_file(&mut self, file_id: u32) -> Result<()> {
+        self.push(RetransmitItem {
+            file_id,
+            range: None,
+        })
+    }
+
+    /// Request retransmission of a byte range of a file
+    pub fn request_range(&mut self, file_id: u32, range: ByteRange) -> Result<()> {
+        self.push(RetransmitItem {
+            file_id,
+            range: Some(range),
+        })
+    }
+
+    /// Check every item against the manifest it refers to.
+    ///
+    /// - **ID**: FN-FDL-001
+    /// - **Requirement**: Reject requests for unknown files or ranges that fall
+    ///   outside the file before recorder playback is scheduled. REQ-NF-004.
+    /// - **Inputs**: `manifest` — manifest of the pass named by `pass_id`.
+    /// - **Outputs**: `Ok(())` when every item is servable; otherwise
+    ///   `Err(InvalidPacket)` describing the first bad item.
+    /// - **Side Effects**: None — read-only.
+    pub fn validate_against(&self, manifest: &FileManifest) -> Result<()> {
+        if self.pass_id != manifest.pass_id {
+            return Err(SpaceCommError::invalid_packet(
+                "Retransmit request refers to a different pass",
+                Some(self.pass_id),
+            ));
+        }
+
+        for item in self.items.iter() {
+            let file = manifest
+                .file(item.file_id)
+                .ok_or(SpaceCommError::invalid_packet(
+                    "Retransmit request names unknown file",
+                    Some(item.file_id),
+                ))?;
+
+            if let Some(range) = item.range {
+                if range.length == 0 || range.end() > file.size_bytes {
+                    return Err(SpaceCommError::invalid_packet(
+                        "Retransmit range outside file",
+                        Some(item.file_id),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the request for uplink
+    pub fn to_bytes(&self) -> Result<Vec<u8, 1024>> {
+        let bytes = serde_json::to_vec(self).map_err(|_| {
+            SpaceCommError::invalid_packet("Failed to serialize retransmit request", None)
+        })?;
+        Vec::from_slice(&bytes).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+        })
+    }
+
+    /// Parse a request received on the uplink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed retransmit request", None))
+    }
+
+    fn push(&mut self, item: RetransmitItem) -> Result<()> {
+        self.items.push(item).map_err(|_| {
+            SpaceCommError::memory_error(
+                MemoryErrorType::BufferOverflow,
+                Some(MAX_RETRANSMIT_ITEMS),
+            )
+        })
+    }
+}
+
+/// Ground-side record of which byte ranges of a file have arrived
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceptionTracker {
+    entry: FileEntry,
+    /// Sorted, non-overlapping, non-adjacent received ranges
+    received: Vec<ByteRange, MAX_TRACKED_RANGES>,
+}
+
+impl ReceptionTracker {
+    /// Start tracking a file announced in a manifest
+    pub const fn new(entry: FileEntry) -> Self {
+        Self {
+            entry,
+            received: Vec::new(),
+        }
+    }
+
+    /// Manifest entry for the tracked file
+    pub const fn entry(&self) -> &FileEntry {
+        &self.entry
+    }
+
+    /// Record that a byte range has been received.
+    ///
+    /// - **ID**: FN-FDL-002
+    /// - **Requirement**: Maintain the set of received bytes as merged ranges so
+    ///   gaps can be computed regardless of segment arrival order or duplicates.
+    /// - **Inputs**: `range` — received segment, clipped to the file size.
+    /// - **Outputs**: `Ok(())`, or `Err(MemoryError)` if more than
+    ///   `MAX_TRACKED_RANGES` disjoint ranges would be needed.
+    /// - **Postconditions**: Stored ranges remain sorted and disjoint.
+    pub fn record(&mut self, range: ByteRange) -> Result<()> {
+        let start = range.offset.min(self.entry.size_bytes);
+        let end = range.end().min(self.entry.size_bytes);
+        if start >= end {
+            return Ok(());
+        }
+
+        let mut merged = ByteRange::new(start, end - start);
+        let mut kept: Vec<ByteRange, MAX_TRACKED_RANGES> = Vec::new();
+
+        for existing in self.received.iter() {
+            if existing.end() < merged.offset || existing.offset > merged.end() {
+                // Disjoint and not touching: keep as-is
+                let _ = kept.push(*existing);
+            } else {
+                let lo = existing.offset.min(merged.offset);
+                let hi = existing.end().max(merged.end());
+                merged = ByteRange::new(lo, hi - lo);
+            }
+        }
+
+        let position = kept
+            .iter()
+            .position(|r| r.offset > merged.offset)
+            .unwrap_or(kept.len());
+        kept.insert(position, merged).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_TRACKED_RANGES))
+        })?;
+
+        self.received = kept;
+        Ok(())
+    }
+
+    /// Byte ranges not yet received
+    pub fn missing_ranges(&self) -> Vec<ByteRange, MAX_TRACKED_RANGES> {
+        let mut missing = Vec::new();
+        let mut cursor = 0u32;
+
+        for range in self.received.iter() {
+            if range.offset > cursor {
+                let _ = missing.push(ByteRange::new(cursor, range.offset - cursor));
+            }
+            cursor = range.end();
+        }
+
+        if cursor < self.entry.size_bytes {
+            let _ = missing.push(ByteRange::new(cursor, self.entry.size_bytes - cursor));
+        }
+
+        missing
+    }
+
+    /// True when every byte of the file has been received
+    pub fn is_complete(&self) -> bool {
+        self.entry.size_bytes == 0 || self.missing_ranges().is_empty()
+    }
+
+    /// Check reassembled contents against the manifest CRC and size
+    pub fn verify(&self, contents: &[u8]) -> bool {
+        contents.len() as u32 == self.entry.size_bytes && file_crc(contents) == self.entry.crc
+    }
+
+    /// Add the items needed to complete this file to a retransmission request
+    ///
+    /// A file whose contents failed verification is requested in full; an
+    /// incomplete file has each missing range requested individually.
+    pub fn append_requests(
+        &self,
+        request: &mut RetransmitRequest,
+        contents_verified: bool,
+    ) -> Result<()> {
+        if self.is_complete() {
+            if contents_verified {
+                return Ok(());
+            }
+            return request.request_file(self.entry.file_id);
+        }
+
+        for gap in self.missing_ranges().iter() {
+            request.request_range(self.entry.file_id, *gap)?;
+        }
+        Ok(())
+    }
+}
+
+/// CRC-16/CCITT-FALSE over complete file contents
+pub fn file_crc(contents: &[u8]) -> u16 {
+    crc16_ccitt(0xFFFF, contents)
+}
+
+/// CRC-16/CCITT-FALSE of a file read in chunks, for files too large to
+/// hold in memory at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileCrc(u16);
+
+impl FileCrc {
+    /// CRC of an empty file
+    pub const fn new() -> Self {
+        Self(0xFFFF)
+    }
+
+    /// Add the next chunk of the file
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0 = crc16_ccitt(self.0, chunk);
+    }
+
+    /// CRC of the chunks added so far
+    pub const fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl Default for FileCrc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_with(file_id: u32, size: u32) -> FileManifest {
+        let mut manifest = FileManifest::new(42);
+        manifest
+            .push(FileEntry {
+                file_id,
+                size_bytes: size,
+                crc: 0,
+            })
+            .unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_tracker_merges_out_of_order_segments() {
+        let mut tracker = ReceptionTracker::new(FileEntry::from_contents(1, &[0u8; 100]));
+        tracker.record(ByteRange::new(50, 25)).unwrap();
+        tracker.record(ByteRange::new(0, 20)).unwrap();
+        tracker.record(ByteRange::new(20, 10)).unwrap();
+
+        let missing = tracker.missing_ranges();
+        assert_eq!(
+            missing.as_slice(),
+            &[ByteRange::new(30, 20), ByteRange::new(75, 25)]
+        );
+
+        tracker.record(ByteRange::new(25, 80)).unwrap();
+        assert!(tracker.is_complete());
+    }
+
+    #[test]
+    fn test_incomplete_file_requests_only_gaps() {
+        let mut tracker = ReceptionTracker::new(FileEntry::from_contents(7, &[1u8; 64]));
+        tracker.record(ByteRange::new(0, 32)).unwrap();
+
+        let mut request = RetransmitRequest::new(42);
+        tracker.append_requests(&mut request, false).unwrap();
+        assert_eq!(request.items.len(), 1);
+        assert_eq!(request.items[0].range, Some(ByteRange::new(32, 32)));
+    }
+
+    #[test]
+    fn test_crc_failure_requests_whole_file() {
+        let contents = [3u8; 16];
+        let mut tracker = ReceptionTracker::new(FileEntry::from_contents(9, &contents));
+        tracker.record(ByteRange::new(0, 16)).unwrap();
+
+        let mut corrupted = contents;
+        corrupted[4] ^= 0xFF;
+        assert!(tracker.verify(&contents));
+        assert!(!tracker.verify(&corrupted));
+
+        let mut request = RetransmitRequest::new(42);
+        tracker.append_requests(&mut request, false).unwrap();
+        assert_eq!(
+            request.items[0],
+            RetransmitItem {
+                file_id: 9,
+                range: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_request_validation_against_manifest() {
+        let manifest = manifest_with(5, 1000);
+
+        let mut ok = RetransmitRequest::new(42);
+        ok.request_range(5, ByteRange::new(900, 100)).unwrap();
+        assert!(ok.validate_against(&manifest).is_ok());
+
+        let mut out_of_bounds = RetransmitRequest::new(42);
+        out_of_bounds
+            .request_range(5, ByteRange::new(900, 101))
+            .unwrap();
+        assert!(out_of_bounds.validate_against(&manifest).is_err());
+
+        let mut unknown = RetransmitRequest::new(42);
+        unknown.request_file(6).unwrap();
+        assert!(unknown.validate_against(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_chunked_crc_and_manifest_packet() {
+        let contents = [0x5Au8; 300];
+        let mut crc = FileCrc::new();
+        for chunk in contents.chunks(64) {
+            crc.update(chunk);
+        }
+        assert_eq!(crc.value(), file_crc(&contents));
+
+        let manifest = manifest_with(5, 300);
+        let packet = manifest.to_packet(7).unwrap();
+        assert_eq!(packet.header.apid, FILE_MANIFEST_APID);
+        assert_eq!(packet.header.sequence_count, 7);
+        assert_eq!(FileManifest::from_bytes(&packet.data).unwrap(), manifest);
+    }
+}
@@ -0,0 +1,661 @@
+//! Operational constraints (flight rules)
+//!
+//! A flight rule forbids one spacecraft [`Activity`] while a [`Condition`]
+//! holds: "no Ka-band transmission while the Ka transceiver is above 60 °C",
+//! "no propulsion while a deployment is in progress". Rules are plain data,
+//! serializable with the rest of the mission configuration, and are held in a
+//! fixed-size [`FlightRuleTable`].
+//!
+//! Onboard, every uplinked command is mapped to the activity it starts
+//! ([`Activity::of_command`]) and checked against the table before it
+//! executes; autonomous actions such as a backup band switch are checked the
+//! same way. The check is made against a [`SpacecraftState`] snapshot taken
+//! at that moment. A reading the snapshot does not hold counts as violating
+//! any limit placed on it, so a failed sensor keeps the activity forbidden
+//! rather than silently lifting the rule.
+//!
+//! Activities that last (a deployment, a burn, a slew) are tracked by an
+//! [`ActivityTracker`] for the time they are expected to run, so rules can
+//! refer to them while they are in progress. Refusals are counted in
+//! housekeeping telemetry at
+//! [`measurement_ids::FLIGHT_RULE_VIOLATIONS`](crate::telemetry::measurement_ids::FLIGHT_RULE_VIOLATIONS).
+//!
+//! # Requirements Traceability
+//! - REQ-SF-001: Command validation (commands rejected when they would break
+//!   a flight rule)
+//! - REQ-SF-002: Override protection for critical safety functions
+//! - REQ-NF-004: Power and thermal protection of the transceivers
+
+use core::fmt;
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::link_config::LinkDirection;
+use crate::rf_housekeeping::RF_BANDS;
+use crate::types::{BandType, OperationalMode};
+use crate::wire;
+
+/// Number of rules the flight rule table can hold
+pub const MAX_FLIGHT_RULES: usize = 16;
+
+/// Time a propulsion command is treated as in progress, ms
+pub const PROPULSION_HOLD_MS: u64 = 60_000;
+
+/// Time an attitude command is treated as in progress, ms
+pub const SLEW_HOLD_MS: u64 = 30_000;
+
+/// Shortest time a deployment is treated as in progress, ms
+pub const MIN_DEPLOYMENT_HOLD_MS: u64 = 10_000;
+
+/// Spacecraft activity a flight rule can forbid or depend on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Activity {
+    /// Transmitting on a band
+    Transmit(BandType),
+    /// Thruster firing
+    Propulsion,
+    /// Moving a deployable (solar panel, antenna, boom)
+    Deployment,
+    /// Attitude slew
+    Slew,
+}
+
+impl Activity {
+    /// Number of distinct activities, one per band for `Transmit`
+    pub const COUNT: usize = RF_BANDS.len() + 3;
+
+    /// Index of the activity, `0..COUNT`
+    pub fn index(&self) -> usize {
+        match self {
+            Activity::Transmit(band) => RF_BANDS
+                .iter()
+                .position(|candidate| candidate == band)
+                .unwrap_or(0),
+            Activity::Propulsion => RF_BANDS.len(),
+            Activity::Deployment => RF_BANDS.len() + 1,
+            Activity::Slew => RF_BANDS.len() + 2,
+        }
+    }
+
+    /// Short name for logs and rejections
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Activity::Transmit(BandType::UhfBand) => "UHF transmission",
+            Activity::Transmit(BandType::SBand) => "S-band transmission",
+            Activity::Transmit(BandType::XBand) => "X-band transmission",
+            Activity::Transmit(BandType::KBand) => "K-band transmission",
+            Activity::Transmit(BandType::KaBand) => "Ka-band transmission",
+            Activity::Propulsion => "propulsion",
+            Activity::Deployment => "deployment",
+            Activity::Slew => "slew",
+        }
+    }
+
+    /// Activity a command starts, with the time it is expected to run, ms
+    ///
+    /// - **ID**: FN-FLR-001
+    /// - **Requirement**: Map every command that a flight rule can concern
+    ///   to the activity it starts (REQ-SF-001).
+    /// - **Outputs**: `None` for commands no rule can forbid. Transmissions
+    ///   last as long as the band is selected and report 0 ms; deployments
+    ///   run for their commanded angle at their commanded rate.
+    pub fn of_command(command: &SpaceCommand) -> Option<(Activity, u64)> {
+        match command {
+            SpaceCommand::CollisionAvoidance { .. } => {
+                Some((Activity::Propulsion, PROPULSION_HOLD_MS))
+            }
+            SpaceCommand::Deploy {
+                deployment_angle,
+                deployment_rate,
+                ..
+            } => {
+                let seconds = if *deployment_rate > 0.0 {
+                    (deployment_angle.abs() / deployment_rate) as u64
+                } else {
+                    0
+                };
+                Some((
+                    Activity::Deployment,
+                    (seconds * 1_000).max(MIN_DEPLOYMENT_HOLD_MS),
+                ))
+            }
+            SpaceCommand::AttitudeControl { .. }
+            | SpaceCommand::EmergencyAttitudeRecovery { .. } => {
+                Some((Activity::Slew, SLEW_HOLD_MS))
+            }
+            SpaceCommand::ReconfigureComm {
+                direction: LinkDirection::Downlink,
+                band,
+                ..
+            }
+            | SpaceCommand::SwitchCommBackup {
+                backup_band: band, ..
+            }
+            | SpaceCommand::LoopbackTest { band, .. } => Some((Activity::Transmit(*band), 0)),
+            _ => None,
+        }
+    }
+
+    /// Activity started by the command in a command packet data field
+    ///
+    /// Data that does not decode as a command starts no activity; the
+    /// command dispatcher rejects it.
+    pub fn of_command_data(data: &[u8]) -> Option<(Activity, u64)> {
+        wire::command_id(data)?;
+        serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..])
+            .ok()
+            .and_then(|command| Self::of_command(&command))
+    }
+}
+
+/// Spacecraft state quantity a flight rule can limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Parameter {
+    /// Transceiver (power amplifier) temperature of a band, °C
+    TransceiverTemperature(BandType),
+    /// Battery state of charge, percent
+    BatteryStateOfCharge,
+    /// Regulated bus voltage, V
+    BusVoltage,
+    /// Navigation position uncertainty, 1-sigma, m; see [`crate::navigation`]
+    PositionUncertainty,
+}
+
+/// Condition under which a flight rule forbids its activity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// Parameter above the limit
+    Above {
+        /// Parameter compared
+        parameter: Parameter,
+        /// Limit, in the parameter's unit
+        limit: f32,
+    },
+    /// Parameter below the limit
+    Below {
+        /// Parameter compared
+        parameter: Parameter,
+        /// Limit, in the parameter's unit
+        limit: f32,
+    },
+    /// Another activity in progress
+    During(Activity),
+    /// Spacecraft in an operational mode
+    InMode(OperationalMode),
+}
+
+impl Condition {
+    /// Whether the condition holds in `state`
+    ///
+    /// A limit on a parameter the state has no reading for holds.
+    pub fn holds(&self, state: &SpacecraftState) -> bool {
+        match *self {
+            Condition::Above { parameter, limit } => {
+                state.parameter(parameter).is_none_or(|value| value > limit)
+            }
+            Condition::Below { parameter, limit } => {
+                state.parameter(parameter).is_none_or(|value| value < limit)
+            }
+            Condition::During(activity) => state.activities.contains(activity),
+            Condition::InMode(mode) => state.mode == mode,
+        }
+    }
+
+    /// Reason given when a command is rejected under this condition
+    const fn reason(&self) -> &'static str {
+        match self {
+            Condition::Above { .. } => "Flight rule forbids this above the parameter limit",
+            Condition::Below { .. } => "Flight rule forbids this below the parameter limit",
+            Condition::During(_) => "Flight rule forbids this during another activity",
+            Condition::InMode(_) => "Flight rule forbids this in the current mode",
+        }
+    }
+}
+
+/// One operational constraint: `forbids` may not start while `when` holds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FlightRule {
+    /// Rule number, unique within the table
+    pub id: u16,
+    /// Activity the rule forbids
+    pub forbids: Activity,
+    /// Condition under which it is forbidden
+    pub when: Condition,
+}
+
+impl FlightRule {
+    /// Create a rule
+    pub const fn new(id: u16, forbids: Activity, when: Condition) -> Self {
+        Self { id, forbids, when }
+    }
+}
+
+impl fmt::Display for FlightRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FR-{:03}: no {} ", self.id, self.forbids.name())?;
+        match self.when {
+            Condition::Above { parameter, limit } => write!(f, "while {:?} > {}", parameter, limit),
+            Condition::Below { parameter, limit } => write!(f, "while {:?} < {}", parameter, limit),
+            Condition::During(activity) => write!(f, "during {}", activity.name()),
+            Condition::InMode(mode) => write!(f, "in {:?} mode", mode),
+        }
+    }
+}
+
+/// Set of activities in progress
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivitySet(u16);
+
+impl ActivitySet {
+    /// Add an activity
+    pub fn insert(&mut self, activity: Activity) {
+        self.0 |= 1 << activity.index();
+    }
+
+    /// Whether an activity is in progress
+    pub fn contains(&self, activity: Activity) -> bool {
+        self.0 & (1 << activity.index()) != 0
+    }
+}
+
+/// Tracks activities for the time they are expected to run
+#[derive(Debug, Clone, Default)]
+pub struct ActivityTracker {
+    ends_ms: [Option<u64>; Activity::COUNT],
+}
+
+impl ActivityTracker {
+    /// Create a tracker with nothing in progress
+    pub const fn new() -> Self {
+        Self {
+            ends_ms: [None; Activity::COUNT],
+        }
+    }
+
+    /// Record `activity` as running from `now_ms` for `duration_ms`
+    ///
+    /// An activity already running keeps the later of the two end times.
+    pub fn begin(&mut self, activity: Activity, now_ms: u64, duration_ms: u64) {
+        let end = now_ms.saturating_add(duration_ms);
+        let slot = &mut self.ends_ms[activity.index()];
+        *slot = Some(slot.map_or(end, |current| current.max(end)));
+    }
+
+    /// Record `activity` as finished
+    pub fn end(&mut self, activity: Activity) {
+        self.ends_ms[activity.index()] = None;
+    }
+
+    /// Activities still running at `now_ms`
+    pub fn active(&self, now_ms: u64) -> ActivitySet {
+        let mut set = ActivitySet::default();
+        for activity in ALL_ACTIVITIES {
+            if matches!(self.ends_ms[activity.index()], Some(end) if now_ms < end) {
+                set.insert(activity);
+            }
+        }
+        set
+    }
+}
+
+/// Every activity in index order
+const ALL_ACTIVITIES: [Activity; Activity::COUNT] = [
+    Activity::Transmit(RF_BANDS[0]),
+    Activity::Transmit(RF_BANDS[1]),
+    Activity::Transmit(RF_BANDS[2]),
+    Activity::Transmit(RF_BANDS[3]),
+    Activity::Transmit(RF_BANDS[4]),
+    Activity::Propulsion,
+    Activity::Deployment,
+    Activity::Slew,
+];
+
+/// Snapshot of the state flight rules are checked against
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpacecraftState {
+    /// Transceiver temperature per band in [`RF_BANDS`] order, °C
+    pub transceiver_temperature: [Option<f32>; 5],
+    /// Battery state of charge, percent
+    pub battery_soc: Option<f32>,
+    /// Regulated bus voltage, V
+    pub bus_voltage: Option<f32>,
+    /// Navigation position uncertainty, m; `None` without a usable solution
+    pub position_sigma: Option<f32>,
+    /// Operational mode
+    pub mode: OperationalMode,
+    /// Activities in progress
+    pub activities: ActivitySet,
+}
+
+impl SpacecraftState {
+    /// State with no readings and nothing in progress
+    pub const fn new(mode: OperationalMode) -> Self {
+        Self {
+            transceiver_temperature: [None; 5],
+            battery_soc: None,
+            bus_voltage: None,
+            position_sigma: None,
+            mode,
+            activities: ActivitySet(0),
+        }
+    }
+
+    /// Reading of a parameter, if the snapshot holds one
+    pub fn parameter(&self, parameter: Parameter) -> Option<f32> {
+        match parameter {
+            Parameter::TransceiverTemperature(band) => RF_BANDS
+                .iter()
+                .position(|&candidate| candidate == band)
+                .and_then(|index| self.transceiver_temperature[index]),
+            Parameter::BatteryStateOfCharge => self.battery_soc,
+            Parameter::BusVoltage => self.bus_voltage,
+            Parameter::PositionUncertainty => self.position_sigma,
+        }
+    }
+}
+
+/// A flight rule an activity would break
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FlightRuleViolation {
+    /// Rule broken
+    pub rule: FlightRule,
+    /// Reading that broke a parameter limit; `None` for activity and mode
+    /// conditions, and for a missing reading
+    pub value: Option<f32>,
+}
+
+impl FlightRuleViolation {
+    /// Error the violating command is rejected with
+    pub const fn to_error(&self) -> SpaceCommError {
+        SpaceCommError::ConfigurationError {
+            parameter: "flight_rule",
+            value: self.rule.forbids.name(),
+            reason: self.rule.when.reason(),
+        }
+    }
+}
+
+impl fmt::Display for FlightRuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.rule)?;
+        if let Some(value) = self.value {
+            write!(f, " (reading {})", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Onboard flight rule table
+///
+/// - **ID**: MOD-FLR-001
+/// - **Requirement**: Reject any command or autonomous action that would
+///   break a flight rule (REQ-SF-001, REQ-SF-002).
+/// - **Purpose**: Keep the constraints operators otherwise enforce by
+///   procedure onboard, where a wrong or stale command cannot bypass them.
+/// - **Failure Modes**: Duplicate rule numbers → `Err(ConfigurationError)`;
+///   a full table → `Err(ResourceExhausted)`. The table is unchanged.
+/// - **Constraints**: Fixed size, no heap allocation.
+///
+/// The default table holds the mission baseline rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlightRuleTable {
+    rules: Vec<FlightRule, MAX_FLIGHT_RULES>,
+}
+
+impl FlightRuleTable {
+    /// Table with no rules
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule
+    pub fn add(&mut self, rule: FlightRule) -> Result<()> {
+        if self.rule(rule.id).is_some() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "flight_rule",
+                value: "duplicate id",
+                reason: "A flight rule with this number is already loaded",
+            });
+        }
+        self.rules
+            .push(rule)
+            .map_err(|_| SpaceCommError::ResourceExhausted {
+                resource: "flight rule table",
+                current_usage: MAX_FLIGHT_RULES as u32,
+                max_usage: MAX_FLIGHT_RULES as u32,
+            })
+    }
+
+    /// Remove rule `id`, returning it
+    pub fn remove(&mut self, id: u16) -> Option<FlightRule> {
+        let index = self.rules.iter().position(|rule| rule.id == id)?;
+        Some(self.rules.remove(index))
+    }
+
+    /// Rule `id`, if loaded
+    pub fn rule(&self, id: u16) -> Option<&FlightRule> {
+        self.rules.iter().find(|rule| rule.id == id)
+    }
+
+    /// Loaded rules in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &FlightRule> + '_ {
+        self.rules.iter()
+    }
+
+    /// Check whether `activity` may start in `state`
+    ///
+    /// - **ID**: FN-FLR-002
+    /// - **Requirement**: Forbid an activity while any rule naming it has its
+    ///   condition met (REQ-SF-001).
+    /// - **Outputs**: `Ok(())`, or the first rule, in table order, that
+    ///   forbids the activity.
+    /// - **Side Effects**: None; callers log and count violations.
+    /// - **Constraints**: O(rules).
+    /// - **Verification**: Unit tests `test_ka_transmit_forbidden_when_hot`,
+    ///   `test_propulsion_forbidden_during_deployment`.
+    pub fn check(
+        &self,
+        activity: Activity,
+        state: &SpacecraftState,
+    ) -> core::result::Result<(), FlightRuleViolation> {
+        match self
+            .rules
+            .iter()
+            .find(|rule| rule.forbids == activity && rule.when.holds(state))
+        {
+            Some(rule) => Err(FlightRuleViolation {
+                rule: *rule,
+                value: match rule.when {
+                    Condition::Above { parameter, .. } | Condition::Below { parameter, .. } => {
+                        state.parameter(parameter)
+                    }
+                    _ => None,
+                },
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the activity a command starts; commands no rule can concern pass
+    pub fn check_command(
+        &self,
+        command: &SpaceCommand,
+        state: &SpacecraftState,
+    ) -> core::result::Result<(), FlightRuleViolation> {
+        match Activity::of_command(command) {
+            Some((activity, _)) => self.check(activity, state),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for FlightRuleTable {
+    /// Mission baseline rules
+    fn default() -> Self {
+        const BASELINE: [FlightRule; 5] = [
+            FlightRule::new(
+                1,
+                Activity::Transmit(BandType::KaBand),
+                Condition::Above {
+                    parameter: Parameter::TransceiverTemperature(BandType::KaBand),
+                    limit: 60.0,
+                },
+            ),
+            FlightRule::new(
+                2,
+                Activity::Transmit(BandType::KBand),
+                Condition::Above {
+                    parameter: Parameter::TransceiverTemperature(BandType::KBand),
+                    limit: 60.0,
+                },
+            ),
+            FlightRule::new(
+                3,
+                Activity::Propulsion,
+                Condition::During(Activity::Deployment),
+            ),
+            FlightRule::new(
+                4,
+                Activity::Deployment,
+                Condition::During(Activity::Propulsion),
+            ),
+            FlightRule::new(
+                5,
+                Activity::Propulsion,
+                Condition::Below {
+                    parameter: Parameter::BatteryStateOfCharge,
+                    limit: 30.0,
+                },
+            ),
+        ];
+
+        let mut table = Self::new();
+        for rule in BASELINE {
+            let _ = table.add(rule);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nominal_state() -> SpacecraftState {
+        let mut state = SpacecraftState::new(OperationalMode::Normal);
+        state.transceiver_temperature = [Some(25.0); 5];
+        state.battery_soc = Some(80.0);
+        state.bus_voltage = Some(28.0);
+        state.position_sigma = Some(8.7);
+        state
+    }
+
+    #[test]
+    fn test_ka_transmit_forbidden_when_hot() {
+        let table = FlightRuleTable::default();
+        let mut state = nominal_state();
+        let ka = Activity::Transmit(BandType::KaBand);
+        assert!(table.check(ka, &state).is_ok());
+
+        state.transceiver_temperature[4] = Some(65.0);
+        let violation = table.check(ka, &state).unwrap_err();
+        assert_eq!(violation.rule.id, 1);
+        assert_eq!(violation.value, Some(65.0));
+        assert!(table
+            .check(Activity::Transmit(BandType::SBand), &state)
+            .is_ok());
+
+        // A missing reading keeps the activity forbidden
+        state.transceiver_temperature[4] = None;
+        assert!(table.check(ka, &state).is_err());
+    }
+
+    #[test]
+    fn test_propulsion_forbidden_during_deployment() {
+        let table = FlightRuleTable::default();
+        let mut tracker = ActivityTracker::new();
+        let deploy = SpaceCommand::Deploy {
+            deployable: crate::commands::DeployableType::SolarPanel,
+            deployment_angle: 90.0,
+            deployment_rate: 3.0,
+            force_limit: 10.0,
+        };
+        let (activity, duration) = Activity::of_command(&deploy).unwrap();
+        assert_eq!((activity, duration), (Activity::Deployment, 30_000));
+
+        let mut data = std::vec::Vec::from(deploy.discriminant().to_be_bytes());
+        data.extend(serde_json::to_vec(&deploy).unwrap());
+        assert_eq!(Activity::of_command_data(&data), Some((activity, duration)));
+        tracker.begin(activity, 1_000, duration);
+
+        let mut state = nominal_state();
+        state.activities = tracker.active(5_000);
+        let violation = table.check(Activity::Propulsion, &state).unwrap_err();
+        assert_eq!(violation.rule.id, 3);
+        assert_eq!(
+            violation.to_error(),
+            SpaceCommError::ConfigurationError {
+                parameter: "flight_rule",
+                value: "propulsion",
+                reason: "Flight rule forbids this during another activity",
+            }
+        );
+
+        state.activities = tracker.active(31_000);
+        assert!(table.check(Activity::Propulsion, &state).is_ok());
+        state.battery_soc = Some(20.0);
+        assert_eq!(
+            table
+                .check(Activity::Propulsion, &state)
+                .unwrap_err()
+                .rule
+                .id,
+            5
+        );
+    }
+
+    #[test]
+    fn test_table_add_and_remove() {
+        let mut table = FlightRuleTable::default();
+        let rule = FlightRule::new(10, Activity::Slew, Condition::InMode(OperationalMode::Safe));
+        table.add(rule).unwrap();
+        assert!(table.add(rule).is_err());
+        assert_eq!(rule.to_string(), "FR-010: no slew in Safe mode");
+
+        let mut state = nominal_state();
+        state.mode = OperationalMode::Safe;
+        assert!(table.check(Activity::Slew, &state).is_err());
+
+        // Navigation degraded by a GPS outage holds off burns
+        let burn = FlightRule::new(
+            11,
+            Activity::Propulsion,
+            Condition::Above {
+                parameter: Parameter::PositionUncertainty,
+                limit: 1_000.0,
+            },
+        );
+        table.add(burn).unwrap();
+        assert!(table.check(Activity::Propulsion, &state).is_ok());
+        state.position_sigma = Some(2_500.0);
+        assert_eq!(
+            table.check(Activity::Propulsion, &state).unwrap_err().value,
+            Some(2_500.0)
+        );
+        assert_eq!(table.remove(11), Some(burn));
+        assert_eq!(table.remove(10), Some(rule));
+        assert!(table.check(Activity::Slew, &state).is_ok());
+
+        for id in 100..(100 + MAX_FLIGHT_RULES as u16) {
+            let _ = table.add(FlightRule::new(id, Activity::Slew, rule.when));
+        }
+        assert_eq!(table.iter().count(), MAX_FLIGHT_RULES);
+        assert!(matches!(
+            table.add(rule),
+            Err(SpaceCommError::ResourceExhausted { .. })
+        ));
+    }
+}
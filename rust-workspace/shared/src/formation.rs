@@ -0,0 +1,518 @@
+//! Formation flying crosslink ranging
+//!
+//! In a close formation the S-band crosslink doubles as a relative
+//! navigation sensor: a ranging tone turned around by the partner satellite
+//! gives the range to it, and the Doppler of the carrier gives the range
+//! rate. Both are downlinked as one [`RangingMeasurement`] with their 1-sigma
+//! errors, so formation-keeping studies can weight each sample. Like the EPS
+//! summary the measurement rides an ordinary [`TelemetryData`] frame on its
+//! own APID.
+//!
+//! The simulated satellite propagates the partner's motion relative to its
+//! own orbit with the Clohessy-Wiltshire equations and adds ranging noise
+//! from a [`RangingNoise`] model. Received power falls with the square of
+//! the range, so both sigmas grow linearly with range from their values at
+//! the reference range.
+//!
+//! Measurement IDs from [`measurement_ids::CROSSLINK_RANGING_BASE`]:
+//!
+//! | Offset | Field                    | Unit |
+//! |--------|--------------------------|------|
+//! | 0x0    | Range                    | m    |
+//! | 0x1    | Range error, 1-sigma     | m    |
+//! | 0x4    | Range rate               | m/s  |
+//! | 0x5    | Range rate error, 1-sigma| m/s  |
+//!
+//! Out of lock, beyond the ranging limit, every field is sent as NaN, which
+//! reads `Invalid`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (S-band crosslink ranging)
+//! - REQ-NF-001: System Health Monitoring (formation geometry telemetry)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::sensor_model::NoiseSource;
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementValue, QualityLimits, TelemetryData,
+};
+
+/// APID of the crosslink ranging packet, next to the EPS summary
+pub const CROSSLINK_RANGING_APID: u16 = 0x103;
+
+/// Crosslink ranging reporting period, ms
+pub const CROSSLINK_RANGING_PERIOD_MS: u64 = 1_000;
+
+/// Mean motion of a 550 km circular orbit, rad/s
+pub const LEO_550_MEAN_MOTION_RAD_S: f64 = 0.001_107_8;
+
+/// Field of the crosslink ranging measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RangingField {
+    /// Range to the partner satellite, m
+    Range,
+    /// Range error, 1-sigma, m
+    RangeSigma,
+    /// Range rate, positive while separating, m/s
+    RangeRate,
+    /// Range rate error, 1-sigma, m/s
+    RangeRateSigma,
+}
+
+impl RangingField {
+    /// Every field in measurement ID order
+    pub const ALL: [RangingField; 4] = [
+        RangingField::Range,
+        RangingField::RangeSigma,
+        RangingField::RangeRate,
+        RangingField::RangeRateSigma,
+    ];
+
+    /// Measurement ID of this field
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::CROSSLINK_RANGING_BASE
+            + match self {
+                RangingField::Range => 0x0,
+                RangingField::RangeSigma => 0x1,
+                RangingField::RangeRate => 0x4,
+                RangingField::RangeRateSigma => 0x5,
+            }
+    }
+
+    /// Field a measurement ID belongs to, if it is a crosslink ranging ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.measurement_id() == measurement_id)
+    }
+
+    /// Display name
+    pub const fn name(self) -> &'static str {
+        match self {
+            RangingField::Range => "Range",
+            RangingField::RangeSigma => "Range sigma",
+            RangingField::RangeRate => "Range rate",
+            RangingField::RangeRateSigma => "Range rate sigma",
+        }
+    }
+
+    /// Engineering unit
+    pub const fn unit(self) -> &'static str {
+        match self {
+            RangingField::Range | RangingField::RangeSigma => "m",
+            RangingField::RangeRate | RangingField::RangeRateSigma => "m/s",
+        }
+    }
+
+    /// Limit definition for this field
+    ///
+    /// The expected ranges are those of a close formation; a partner beyond
+    /// a few kilometres or closing fast is flagged for the operators.
+    pub const fn limits(self) -> QualityLimits {
+        let (valid_min, valid_max, expected_min, expected_max) = match self {
+            RangingField::Range => (0.0, 100_000.0, 20.0, 5_000.0),
+            RangingField::RangeSigma => (0.0, 1_000.0, 0.0, 10.0),
+            RangingField::RangeRate => (-1_000.0, 1_000.0, -5.0, 5.0),
+            RangingField::RangeRateSigma => (0.0, 100.0, 0.0, 0.1),
+        };
+        QualityLimits {
+            valid_min,
+            valid_max,
+            expected_min,
+            expected_max,
+        }
+    }
+
+    /// Dictionary entries covering the measurement: range and range rate
+    pub const fn dictionary_entries() -> [DictionaryEntry; 2] {
+        [
+            DictionaryEntry {
+                first_id: RangingField::Range.measurement_id(),
+                last_id: measurement_ids::CROSSLINK_RANGING_BASE + 0x3,
+                name: "Crosslink range",
+                unit: RangingField::Range.unit(),
+                period_ms: CROSSLINK_RANGING_PERIOD_MS,
+            },
+            DictionaryEntry {
+                first_id: RangingField::RangeRate.measurement_id(),
+                last_id: measurement_ids::CROSSLINK_RANGING_BASE + 0x7,
+                name: "Crosslink range rate",
+                unit: RangingField::RangeRate.unit(),
+                period_ms: CROSSLINK_RANGING_PERIOD_MS,
+            },
+        ]
+    }
+}
+
+/// Range and range rate to the partner satellite at one instant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangingMeasurement {
+    /// Range, m
+    pub range_m: f32,
+    /// Range error, 1-sigma, m
+    pub range_sigma_m: f32,
+    /// Range rate, positive while separating, m/s
+    pub range_rate_m_s: f32,
+    /// Range rate error, 1-sigma, m/s
+    pub range_rate_sigma_m_s: f32,
+}
+
+impl RangingMeasurement {
+    /// Measurement sent while the ranging loop is out of lock
+    pub const NO_LOCK: Self = Self {
+        range_m: f32::NAN,
+        range_sigma_m: f32::NAN,
+        range_rate_m_s: f32::NAN,
+        range_rate_sigma_m_s: f32::NAN,
+    };
+
+    /// Value of one field
+    pub fn value(&self, field: RangingField) -> f32 {
+        match field {
+            RangingField::Range => self.range_m,
+            RangingField::RangeSigma => self.range_sigma_m,
+            RangingField::RangeRate => self.range_rate_m_s,
+            RangingField::RangeRateSigma => self.range_rate_sigma_m_s,
+        }
+    }
+
+    /// Whether the ranging loop was locked
+    pub fn is_locked(&self) -> bool {
+        !self.range_m.is_nan()
+    }
+
+    /// Append one measurement per field, each flagged against its limits
+    ///
+    /// - **ID**: FN-XLR-001
+    /// - **Requirement**: Report range and range rate with their errors as
+    ///   one telemetry product (REQ-FN-007).
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold all four
+    ///   measurements; measurements pushed before the overflow remain.
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for field in RangingField::ALL {
+            let value = self.value(field);
+            data.measurements
+                .push(Measurement {
+                    measurement_id: field.measurement_id(),
+                    value: MeasurementValue::Float(f64::from(value)),
+                    unit: field.unit(),
+                    quality: field.limits().classify(f64::from(value)),
+                })
+                .map_err(|_| {
+                    SpaceCommError::memory_error(
+                        MemoryErrorType::BufferOverflow,
+                        Some(data.measurements.len()),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the measurement from downlinked measurements
+    ///
+    /// Returns `None` unless every field is present.
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let value = |field: RangingField| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == field.measurement_id())
+                .and_then(|m| match m.value {
+                    MeasurementValue::Float(v) => Some(v as f32),
+                    _ => None,
+                })
+        };
+
+        Some(Self {
+            range_m: value(RangingField::Range)?,
+            range_sigma_m: value(RangingField::RangeSigma)?,
+            range_rate_m_s: value(RangingField::RangeRate)?,
+            range_rate_sigma_m_s: value(RangingField::RangeRateSigma)?,
+        })
+    }
+}
+
+/// Crosslink ranging measurement carried by a frame, if it is complete
+pub fn decode_crosslink_ranging(data: &TelemetryData) -> Option<RangingMeasurement> {
+    RangingMeasurement::from_measurements(&data.measurements)
+}
+
+/// Ranging error model of the crosslink
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangingNoise {
+    /// Range at which the sigmas below apply, m
+    pub reference_range_m: f32,
+    /// Range error at the reference range, 1-sigma, m
+    pub range_sigma_m: f32,
+    /// Range rate error at the reference range, 1-sigma, m/s
+    pub range_rate_sigma_m_s: f32,
+    /// Constant range error, e.g. uncalibrated transponder delay, m
+    pub range_bias_m: f32,
+    /// Range beyond which the ranging loop cannot hold lock, m
+    pub max_range_m: f32,
+}
+
+impl Default for RangingNoise {
+    /// S-band crosslink with 0.5 m and 2 mm/s errors at 1 km, locked to 50 km
+    fn default() -> Self {
+        Self {
+            reference_range_m: 1_000.0,
+            range_sigma_m: 0.5,
+            range_rate_sigma_m_s: 0.002,
+            range_bias_m: 0.0,
+            max_range_m: 50_000.0,
+        }
+    }
+}
+
+impl RangingNoise {
+    /// Range and range rate sigmas at `range_m`
+    ///
+    /// Ranging errors scale with the inverse square root of the carrier to
+    /// noise density, which falls with the square of the range.
+    pub fn sigmas_at(&self, range_m: f32) -> (f32, f32) {
+        let scale = range_m / self.reference_range_m;
+        (
+            self.range_sigma_m * scale,
+            self.range_rate_sigma_m_s * scale,
+        )
+    }
+}
+
+/// Partner position and velocity relative to the own satellite
+///
+/// Local-vertical local-horizontal frame: x radial (away from the Earth),
+/// y along-track, z cross-track (orbit normal).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RelativeState {
+    /// Position, m
+    pub position_m: [f64; 3],
+    /// Velocity, m/s
+    pub velocity_m_s: [f64; 3],
+}
+
+impl RelativeState {
+    /// Drift-free relative orbit through the given offsets
+    ///
+    /// The along-track velocity that cancels secular drift for the radial
+    /// offset is set, so the partner circulates around the own satellite
+    /// instead of walking away from it.
+    pub fn bounded(
+        mean_motion_rad_s: f64,
+        radial_m: f64,
+        along_track_m: f64,
+        cross_track_m: f64,
+    ) -> Self {
+        Self {
+            position_m: [radial_m, along_track_m, cross_track_m],
+            velocity_m_s: [0.0, -2.0 * mean_motion_rad_s * radial_m, 0.0],
+        }
+    }
+
+    /// Distance to the partner, m
+    pub fn range_m(&self) -> f64 {
+        sqrt(dot(self.position_m, self.position_m))
+    }
+
+    /// Rate of change of the range, positive while separating, m/s
+    pub fn range_rate_m_s(&self) -> f64 {
+        let range = self.range_m();
+        if range > 0.0 {
+            dot(self.position_m, self.velocity_m_s) / range
+        } else {
+            0.0
+        }
+    }
+
+    /// Advance by `dt_s` under the Clohessy-Wiltshire equations
+    ///
+    /// Fourth-order Runge-Kutta; steps up to a few seconds keep the error
+    /// well below the ranging noise in low Earth orbit.
+    pub fn propagate(&mut self, mean_motion_rad_s: f64, dt_s: f64) {
+        let n = mean_motion_rad_s;
+        let derivative = |s: [f64; 6]| {
+            [
+                s[3],
+                s[4],
+                s[5],
+                3.0 * n * n * s[0] + 2.0 * n * s[4],
+                -2.0 * n * s[3],
+                -n * n * s[2],
+            ]
+        };
+        let offset = |s: [f64; 6], d: [f64; 6], h: f64| {
+            let mut out = s;
+            for (o, d) in out.iter_mut().zip(d) {
+                *o += d * h;
+            }
+            out
+        };
+
+        let s = [
+            self.position_m[0],
+            self.position_m[1],
+            self.position_m[2],
+            self.velocity_m_s[0],
+            self.velocity_m_s[1],
+            self.velocity_m_s[2],
+        ];
+        let k1 = derivative(s);
+        let k2 = derivative(offset(s, k1, dt_s / 2.0));
+        let k3 = derivative(offset(s, k2, dt_s / 2.0));
+        let k4 = derivative(offset(s, k3, dt_s));
+        let mut next = s;
+        for i in 0..6 {
+            next[i] += dt_s / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+        self.position_m = [next[0], next[1], next[2]];
+        self.velocity_m_s = [next[3], next[4], next[5]];
+    }
+}
+
+/// Simulated crosslink ranging to a formation partner
+#[derive(Debug, Clone)]
+pub struct CrosslinkRanger {
+    state: RelativeState,
+    mean_motion_rad_s: f64,
+    noise: RangingNoise,
+    source: NoiseSource,
+}
+
+impl CrosslinkRanger {
+    /// Ranger starting from `state` on an orbit of `mean_motion_rad_s`, with
+    /// noise seeded by `seed`
+    pub const fn new(
+        state: RelativeState,
+        mean_motion_rad_s: f64,
+        noise: RangingNoise,
+        seed: u32,
+    ) -> Self {
+        Self {
+            state,
+            mean_motion_rad_s,
+            noise,
+            source: NoiseSource::new(seed),
+        }
+    }
+
+    /// True relative state
+    pub const fn state(&self) -> RelativeState {
+        self.state
+    }
+
+    /// Advance the relative motion by `dt_s`
+    pub fn advance(&mut self, dt_s: f64) {
+        self.state.propagate(self.mean_motion_rad_s, dt_s);
+    }
+
+    /// Measure range and range rate at the current state
+    ///
+    /// - **ID**: FN-XLR-002
+    /// - **Requirement**: Simulated crosslink ranging follows the relative
+    ///   motion with range-dependent noise (REQ-FN-007).
+    /// - **Outputs**: The measurement with its sigmas, or
+    ///   [`RangingMeasurement::NO_LOCK`] beyond the ranging limit.
+    /// - **Side Effects**: Advances the noise source while locked.
+    pub fn measure(&mut self) -> RangingMeasurement {
+        let range_m = self.state.range_m() as f32;
+        if range_m > self.noise.max_range_m {
+            return RangingMeasurement::NO_LOCK;
+        }
+        let (range_sigma_m, range_rate_sigma_m_s) = self.noise.sigmas_at(range_m);
+        RangingMeasurement {
+            range_m: range_m
+                + self.noise.range_bias_m
+                + range_sigma_m * self.source.next_gaussian(),
+            range_sigma_m,
+            range_rate_m_s: self.state.range_rate_m_s() as f32
+                + range_rate_sigma_m_s * self.source.next_gaussian(),
+            range_rate_sigma_m_s,
+        }
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Square root by Newton's method, as `f64::sqrt` needs `std`
+fn sqrt(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let mut root = if value > 1.0 { value } else { 1.0 };
+    for _ in 0..64 {
+        let next = 0.5 * (root + value / root);
+        if next >= root {
+            break;
+        }
+        root = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::{dictionary_entry, MeasurementQuality};
+    use crate::types::{ComponentId, HealthStatus};
+
+    #[test]
+    fn test_bounded_relative_orbit_closes() {
+        let n = LEO_550_MEAN_MOTION_RAD_S;
+        let start = RelativeState::bounded(n, 100.0, 500.0, 50.0);
+        let mut state = start;
+        assert!((state.range_m() - 512.35).abs() < 0.01);
+
+        let period_s = 2.0 * core::f64::consts::PI / n;
+        let steps = 1_000;
+        for _ in 0..steps {
+            state.propagate(n, period_s / f64::from(steps));
+        }
+        for axis in 0..3 {
+            assert!((state.position_m[axis] - start.position_m[axis]).abs() < 0.01);
+        }
+        assert!((sqrt(2.0) - core::f64::consts::SQRT_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ranging_round_trip_and_lock() {
+        let noise = RangingNoise::default();
+        assert_eq!(noise.sigmas_at(2_000.0), (1.0, 0.004));
+
+        let state = RelativeState::bounded(LEO_550_MEAN_MOTION_RAD_S, 0.0, 1_000.0, 0.0);
+        let mut ranger = CrosslinkRanger::new(state, LEO_550_MEAN_MOTION_RAD_S, noise, 7);
+        let measurement = ranger.measure();
+        assert!(measurement.is_locked());
+        assert!((measurement.range_m - 1_000.0).abs() < 4.0 * noise.range_sigma_m);
+        assert!(measurement.range_rate_m_s.abs() < 4.0 * noise.range_rate_sigma_m_s);
+
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        measurement.append_measurements(&mut data).unwrap();
+        RangingMeasurement::NO_LOCK
+            .append_measurements(&mut data)
+            .unwrap();
+        let decoded = decode_crosslink_ranging(&data).unwrap();
+        assert_eq!(decoded, measurement);
+        assert_eq!(data.measurements[0].quality, MeasurementQuality::Good);
+        assert_eq!(data.measurements[4].quality, MeasurementQuality::Invalid);
+
+        for field in RangingField::ALL {
+            let entry = dictionary_entry(field.measurement_id()).unwrap();
+            assert_eq!(entry.unit, field.unit());
+            assert_eq!(
+                RangingField::from_measurement_id(field.measurement_id()),
+                Some(field)
+            );
+        }
+
+        let far = RelativeState::bounded(LEO_550_MEAN_MOTION_RAD_S, 0.0, 60_000.0, 0.0);
+        let mut ranger = CrosslinkRanger::new(far, LEO_550_MEAN_MOTION_RAD_S, noise, 7);
+        assert!(!ranger.measure().is_locked());
+    }
+}
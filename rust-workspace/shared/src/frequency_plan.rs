@@ -0,0 +1,407 @@
+//! Regulatory band limits and licensed frequency assignments
+//!
+//! A `ReconfigureComm` command tunes one link direction to a frequency. Two
+//! checks stand between that number and the transmitter:
+//!
+//! 1. The frequency must lie in the legal range of the commanded band
+//!    ([`BandType::frequency_range`]); anything else is out-of-band tuning.
+//! 2. The frequency must fall inside an assignment the mission holds a
+//!    licence for, in a [`FrequencyPlan`]. Assignments can be restricted to
+//!    one link direction, since allocations are usually split into
+//!    Earth-to-space and space-to-Earth segments.
+//!
+//! The ground runs the same check before a command goes up and the satellite
+//! runs it again on acceptance, so a command built by hand or uplinked from a
+//! station with a stale plan is still refused. Each outcome has its own error
+//! code ([`OUT_OF_BAND_CODE`], [`UNLICENSED_FREQUENCY_CODE`]), logged onboard
+//! and downlinked with the event log.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (tuning kept inside each band)
+//! - REQ-SF-001: Command validation (out-of-band and unlicensed tuning
+//!   rejected before execution)
+
+use core::fmt;
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::link_config::LinkDirection;
+use crate::types::BandType;
+use crate::wire;
+
+/// Maximum licensed assignments a frequency plan holds
+pub const MAX_LICENSED_ASSIGNMENTS: usize = 16;
+
+/// Error code of a frequency outside the commanded band's legal range
+pub const OUT_OF_BAND_CODE: u32 = 440;
+
+/// Error code of an in-band frequency no licensed assignment covers
+pub const UNLICENSED_FREQUENCY_CODE: u32 = 441;
+
+/// Frequency range the mission is licensed to use on one band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicensedAssignment {
+    /// Band the assignment belongs to
+    pub band: BandType,
+    /// Direction the assignment is licensed for; `None` for both
+    pub direction: Option<LinkDirection>,
+    /// Lowest licensed frequency, Hz
+    pub low_hz: u64,
+    /// Highest licensed frequency, Hz
+    pub high_hz: u64,
+}
+
+impl LicensedAssignment {
+    /// Create an assignment
+    pub const fn new(
+        band: BandType,
+        direction: Option<LinkDirection>,
+        low_hz: u64,
+        high_hz: u64,
+    ) -> Self {
+        Self {
+            band,
+            direction,
+            low_hz,
+            high_hz,
+        }
+    }
+
+    /// Whether the assignment covers tuning `band` to `frequency_hz` in
+    /// `direction`
+    pub fn covers(&self, direction: LinkDirection, band: BandType, frequency_hz: u64) -> bool {
+        self.band == band
+            && self.direction.is_none_or(|licensed| licensed == direction)
+            && (self.low_hz..=self.high_hz).contains(&frequency_hz)
+    }
+}
+
+/// Reason a commanded frequency is refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrequencyViolation {
+    /// Outside the legal range of the commanded band
+    OutOfBand,
+    /// In band, but not covered by any licensed assignment
+    Unlicensed,
+}
+
+impl FrequencyViolation {
+    /// Error code logged and downlinked for the violation
+    pub const fn code(self) -> u32 {
+        match self {
+            FrequencyViolation::OutOfBand => OUT_OF_BAND_CODE,
+            FrequencyViolation::Unlicensed => UNLICENSED_FREQUENCY_CODE,
+        }
+    }
+
+    /// Why the frequency is refused
+    pub const fn reason(self) -> &'static str {
+        match self {
+            FrequencyViolation::OutOfBand => "frequency outside the band's legal range",
+            FrequencyViolation::Unlicensed => "frequency not covered by a licensed assignment",
+        }
+    }
+
+    /// Error the command is rejected with; its value is the error code
+    pub const fn to_error(self) -> SpaceCommError {
+        SpaceCommError::ConfigurationError {
+            parameter: "frequency_hz",
+            value: match self {
+                FrequencyViolation::OutOfBand => "E440",
+                FrequencyViolation::Unlicensed => "E441",
+            },
+            reason: self.reason(),
+        }
+    }
+}
+
+impl fmt::Display for FrequencyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{}: {}", self.code(), self.reason())
+    }
+}
+
+/// Licensed frequency assignments
+///
+/// - **ID**: MOD-FRQ-001
+/// - **Requirement**: Refuse tuning outside the band's legal range or the
+///   mission's licences, on the ground and onboard (REQ-FN-007, REQ-SF-001).
+/// - **Purpose**: One table of assignments both segments check
+///   `ReconfigureComm` against.
+/// - **Failure Modes**: An assignment outside its band or with its limits
+///   reversed → `Err(ConfigurationError)`; a full table →
+///   `Err(ResourceExhausted)`. The plan is unchanged.
+/// - **Constraints**: Fixed size, no heap allocation.
+///
+/// The default plan holds the mission's baseline licences.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrequencyPlan {
+    assignments: Vec<LicensedAssignment, MAX_LICENSED_ASSIGNMENTS>,
+}
+
+impl FrequencyPlan {
+    /// Plan with no licensed assignments
+    pub const fn new() -> Self {
+        Self {
+            assignments: Vec::new(),
+        }
+    }
+
+    /// Add a licensed assignment
+    pub fn add(&mut self, assignment: LicensedAssignment) -> Result<()> {
+        let (band_low, band_high) = assignment.band.frequency_range();
+        if assignment.low_hz > assignment.high_hz
+            || assignment.low_hz < band_low
+            || assignment.high_hz > band_high
+        {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "licensed_assignment",
+                value: "<frequency plan>",
+                reason: "assignment must lie within its band's legal range",
+            });
+        }
+        self.assignments
+            .push(assignment)
+            .map_err(|_| SpaceCommError::ResourceExhausted {
+                resource: "frequency plan",
+                current_usage: MAX_LICENSED_ASSIGNMENTS as u32,
+                max_usage: MAX_LICENSED_ASSIGNMENTS as u32,
+            })
+    }
+
+    /// Remove all assignments on `band`, returning how many were removed
+    pub fn remove_band(&mut self, band: BandType) -> usize {
+        let before = self.assignments.len();
+        self.assignments
+            .retain(|assignment| assignment.band != band);
+        before - self.assignments.len()
+    }
+
+    /// Assignments in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &LicensedAssignment> + '_ {
+        self.assignments.iter()
+    }
+
+    /// Check tuning `band` to `frequency_hz` in `direction`
+    ///
+    /// - **ID**: FN-FRQ-001
+    /// - **Requirement**: Accept a frequency only inside the band's legal
+    ///   range and a licensed assignment (REQ-FN-007, REQ-SF-001).
+    /// - **Outputs**: `Ok(())`, or the first check the frequency fails: band
+    ///   range before licence.
+    /// - **Side Effects**: None; callers log and report the violation code.
+    /// - **Constraints**: O(assignments).
+    /// - **Verification**: Unit tests `test_out_of_band_before_unlicensed`,
+    ///   `test_assignments_are_per_direction`.
+    pub fn check(
+        &self,
+        direction: LinkDirection,
+        band: BandType,
+        frequency_hz: u64,
+    ) -> core::result::Result<(), FrequencyViolation> {
+        let (band_low, band_high) = band.frequency_range();
+        if !(band_low..=band_high).contains(&frequency_hz) {
+            return Err(FrequencyViolation::OutOfBand);
+        }
+        if self
+            .assignments
+            .iter()
+            .any(|assignment| assignment.covers(direction, band, frequency_hz))
+        {
+            Ok(())
+        } else {
+            Err(FrequencyViolation::Unlicensed)
+        }
+    }
+
+    /// Check the frequency a command tunes to; commands that tune nothing pass
+    pub fn check_command(
+        &self,
+        command: &SpaceCommand,
+    ) -> core::result::Result<(), FrequencyViolation> {
+        match *command {
+            SpaceCommand::ReconfigureComm {
+                direction,
+                band,
+                frequency_hz,
+                ..
+            } => self.check(direction, band, frequency_hz),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the command in a command packet data field
+    ///
+    /// Data that does not decode as a command tunes nothing; the command
+    /// dispatcher rejects it.
+    pub fn check_command_data(&self, data: &[u8]) -> core::result::Result<(), FrequencyViolation> {
+        if wire::command_id(data).is_none() {
+            return Ok(());
+        }
+        match serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..]) {
+            Ok(command) => self.check_command(&command),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl Default for FrequencyPlan {
+    /// Mission baseline licences: UHF amateur-satellite, S-band TT&C split
+    /// up and down, X-band EESS and deep-space downlink, K-band EESS
+    /// downlink and Ka-band deep-space downlink
+    fn default() -> Self {
+        const UP: Option<LinkDirection> = Some(LinkDirection::Uplink);
+        const DOWN: Option<LinkDirection> = Some(LinkDirection::Downlink);
+        const BASELINE: [LicensedAssignment; 6] = [
+            LicensedAssignment::new(BandType::UhfBand, None, 435_000_000, 438_000_000),
+            LicensedAssignment::new(BandType::SBand, UP, 2_025_000_000, 2_110_000_000),
+            LicensedAssignment::new(BandType::SBand, DOWN, 2_200_000_000, 2_290_000_000),
+            LicensedAssignment::new(BandType::XBand, DOWN, 8_025_000_000, 8_500_000_000),
+            LicensedAssignment::new(BandType::KBand, DOWN, 25_500_000_000, 27_000_000_000),
+            LicensedAssignment::new(BandType::KaBand, DOWN, 31_800_000_000, 32_300_000_000),
+        ];
+
+        let mut plan = Self::new();
+        for assignment in BASELINE {
+            // The baseline fits the table and every assignment is in band
+            let _ = plan.add(assignment);
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::ModulationType;
+
+    fn reconfigure(direction: LinkDirection, band: BandType, frequency_hz: u64) -> SpaceCommand {
+        SpaceCommand::ReconfigureComm {
+            direction,
+            band,
+            frequency_hz,
+            power_level: 80,
+            data_rate_bps: 2_000_000,
+            modulation: ModulationType::QPSK,
+            error_correction: true,
+        }
+    }
+
+    #[test]
+    fn test_out_of_band_before_unlicensed() {
+        let plan = FrequencyPlan::default();
+        let downlink = LinkDirection::Downlink;
+
+        assert_eq!(plan.check(downlink, BandType::XBand, 8_400_000_000), Ok(()));
+        // 7.19 GHz is an X-band uplink elsewhere, but below this band's range
+        let low = plan.check(downlink, BandType::XBand, 7_190_000_000);
+        assert_eq!(low, Err(FrequencyViolation::OutOfBand));
+        assert_eq!(low.unwrap_err().code(), OUT_OF_BAND_CODE);
+        // In band, outside the licence
+        let unlicensed = plan.check(downlink, BandType::XBand, 10_000_000_000);
+        assert_eq!(unlicensed, Err(FrequencyViolation::Unlicensed));
+        assert_eq!(unlicensed.unwrap_err().code(), UNLICENSED_FREQUENCY_CODE);
+        assert!(matches!(
+            unlicensed.unwrap_err().to_error(),
+            SpaceCommError::ConfigurationError { value: "E441", .. }
+        ));
+
+        // An empty plan licenses nothing, but band limits still come first
+        let empty = FrequencyPlan::new();
+        assert_eq!(
+            empty.check(downlink, BandType::XBand, 8_400_000_000),
+            Err(FrequencyViolation::Unlicensed)
+        );
+        assert_eq!(
+            empty.check(downlink, BandType::XBand, 0),
+            Err(FrequencyViolation::OutOfBand)
+        );
+    }
+
+    #[test]
+    fn test_assignments_are_per_direction() {
+        let mut plan = FrequencyPlan::default();
+        let uplink = reconfigure(LinkDirection::Uplink, BandType::SBand, 2_050_000_000);
+        let downlink = reconfigure(LinkDirection::Downlink, BandType::SBand, 2_050_000_000);
+        assert_eq!(plan.check_command(&uplink), Ok(()));
+        assert_eq!(
+            plan.check_command(&downlink),
+            Err(FrequencyViolation::Unlicensed)
+        );
+
+        // UHF is licensed both ways
+        let uhf = reconfigure(LinkDirection::Uplink, BandType::UhfBand, 436_500_000);
+        assert_eq!(plan.check_command(&uhf), Ok(()));
+        assert_eq!(plan.remove_band(BandType::UhfBand), 1);
+        assert_eq!(
+            plan.check_command(&uhf),
+            Err(FrequencyViolation::Unlicensed)
+        );
+
+        // Other commands tune nothing
+        let halt = SpaceCommand::EmergencyHalt {
+            subsystems: Vec::new(),
+            override_code: 0,
+        };
+        assert_eq!(plan.check_command(&halt), Ok(()));
+    }
+
+    #[test]
+    fn test_assignments_must_fit_their_band() {
+        let mut plan = FrequencyPlan::new();
+        let x_uplink = Some(LinkDirection::Uplink);
+        assert!(plan
+            .add(LicensedAssignment::new(
+                BandType::XBand,
+                x_uplink,
+                7_145_000_000,
+                7_190_000_000
+            ))
+            .is_err());
+        assert!(plan
+            .add(LicensedAssignment::new(
+                BandType::XBand,
+                x_uplink,
+                8_500_000_000,
+                8_450_000_000
+            ))
+            .is_err());
+        assert_eq!(plan.iter().count(), 0);
+
+        for step in 0..MAX_LICENSED_ASSIGNMENTS as u64 {
+            let low = 8_000_000_000 + step * 1_000_000;
+            plan.add(LicensedAssignment::new(
+                BandType::XBand,
+                None,
+                low,
+                low + 500_000,
+            ))
+            .unwrap();
+        }
+        assert!(matches!(
+            plan.add(LicensedAssignment::new(
+                BandType::XBand,
+                None,
+                9_000_000_000,
+                9_000_000_000
+            )),
+            Err(SpaceCommError::ResourceExhausted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_command_data_checked_after_the_command_id() {
+        let plan = FrequencyPlan::default();
+        let command = reconfigure(LinkDirection::Downlink, BandType::KaBand, 40_500_000_000);
+        let mut data = std::vec::Vec::from(command.discriminant().to_be_bytes());
+        data.extend_from_slice(&serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            plan.check_command_data(&data),
+            Err(FrequencyViolation::OutOfBand)
+        );
+        assert_eq!(plan.check_command_data(&data[..2]), Ok(()));
+    }
+}
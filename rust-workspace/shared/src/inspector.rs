@@ -0,0 +1,763 @@
+//! Packet inspector
+//!
+//! Takes the raw bytes of a CCSDS Space Packet as they cross the link and
+//! breaks them down field by field: every primary header field with its bit
+//! position and value, the APID's name and payload format from the
+//! [`APID_REGISTRY`], an attempt to decode the payload in that format, and
+//! the Packet Error Control check. Nothing is assumed about where the bytes
+//! came from, so the same breakdown serves live consoles, captured traffic
+//! and test failures, and a malformed packet is described rather than
+//! rejected: only a frame too short to hold a primary header is an error.
+//!
+//! Frames are laid out as described in [`crate::wire`]: the 6-byte primary
+//! header, the data field, then the 2-byte CRC-16/CCITT-FALSE over
+//! everything before it.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (annotated Space Packet breakdown)
+//! - REQ-NF-004: Fault Tolerance (corrupted packets described, not dropped)
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::autonomy::{AutonomyRule, AUTONOMY_RULE_APID};
+use crate::ccsds::{crc16_ccitt, PacketType, SpacePacketHeader};
+use crate::command_load::{CommandLoad, LoadManifest, COMMAND_LOAD_APID};
+use crate::diagnostics::{DwellBatch, MemoryDumpSegment, DWELL_APID, MEMORY_DUMP_APID};
+use crate::eps::EPS_APID;
+use crate::error::{Result, SpaceCommError};
+use crate::event_log::{EventLogDecoder, EVENT_LOG_APID};
+use crate::execution_report::{ExecutionReport, EXECUTION_REPORT_APID};
+use crate::file_downlink::{
+    FileManifest, RetransmitRequest, FILE_MANIFEST_APID, RETRANSMIT_REQUEST_APID,
+};
+use crate::formation::CROSSLINK_RANGING_APID;
+use crate::link_forecast::{LinkForecast, LINK_FORECAST_APID};
+use crate::loopback::{LoopbackEcho, LOOPBACK_APID};
+use crate::mass_memory::{directory_name, FileListing, FILE_LISTING_APID};
+use crate::messaging::MessagePriority;
+use crate::navigation::NAVIGATION_APID;
+use crate::rf_housekeeping::RF_HOUSEKEEPING_APID;
+use crate::telemetry::{TelemetryData, TELEMETRY_APID};
+use crate::types::{ComponentId, HealthStatus};
+use crate::wire::{self, WireReader, ERROR_CONTROL_LEN, PRIMARY_HEADER_LEN};
+
+/// How a payload is encoded on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    /// Command ID and parameters, as encoded by `Message::to_command_packet`
+    Command,
+    /// JSON [`CommandLoad`]
+    CommandLoad,
+    /// JSON [`LoadManifest`]
+    LoadManifest,
+    /// JSON [`FileManifest`]
+    FileManifest,
+    /// JSON [`RetransmitRequest`]
+    RetransmitRequest,
+    /// JSON [`FileListing`]
+    FileListing,
+    /// Fixed-layout [`ExecutionReport`]
+    ExecutionReport,
+    /// Compressed event log block
+    EventLog,
+    /// JSON [`LinkForecast`]
+    LinkForecast,
+    /// JSON [`AutonomyRule`]
+    AutonomyRule,
+    /// Timestamp and measurements, as encoded by `TelemetryData::to_payload`
+    Telemetry,
+    /// Fixed-layout [`MemoryDumpSegment`]
+    MemoryDump,
+    /// Fixed-layout [`DwellBatch`]
+    Dwell,
+    /// Fixed-layout [`LoopbackEcho`]
+    LoopbackEcho,
+}
+
+/// One APID in use on the link
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApidEntry {
+    /// Application Process Identifier
+    pub apid: u16,
+    /// Packet type the APID carries this traffic in
+    pub packet_type: PacketType,
+    /// Name shown to operators
+    pub name: &'static str,
+    /// Payload encoding
+    pub format: PayloadFormat,
+}
+
+const fn entry(
+    apid: u16,
+    packet_type: PacketType,
+    name: &'static str,
+    format: PayloadFormat,
+) -> ApidEntry {
+    ApidEntry {
+        apid,
+        packet_type,
+        name,
+        format,
+    }
+}
+
+/// Every APID in use, by APID and packet type; the command load APID
+/// carries loads up and their manifests down
+pub const APID_REGISTRY: &[ApidEntry] = {
+    use PacketType::{Command, Telemetry};
+    &[
+        entry(
+            MessagePriority::Emergency.command_apid(),
+            Command,
+            "Emergency command",
+            PayloadFormat::Command,
+        ),
+        entry(
+            MessagePriority::Critical.command_apid(),
+            Command,
+            "Critical command",
+            PayloadFormat::Command,
+        ),
+        entry(
+            MessagePriority::High.command_apid(),
+            Command,
+            "High priority command",
+            PayloadFormat::Command,
+        ),
+        entry(
+            MessagePriority::Medium.command_apid(),
+            Command,
+            "Medium priority command",
+            PayloadFormat::Command,
+        ),
+        entry(
+            MessagePriority::Low.command_apid(),
+            Command,
+            "Low priority command",
+            PayloadFormat::Command,
+        ),
+        entry(
+            COMMAND_LOAD_APID,
+            Command,
+            "Command load",
+            PayloadFormat::CommandLoad,
+        ),
+        entry(
+            COMMAND_LOAD_APID,
+            Telemetry,
+            "Load manifest",
+            PayloadFormat::LoadManifest,
+        ),
+        entry(
+            FILE_MANIFEST_APID,
+            Telemetry,
+            "File manifest",
+            PayloadFormat::FileManifest,
+        ),
+        entry(
+            RETRANSMIT_REQUEST_APID,
+            Command,
+            "Retransmit request",
+            PayloadFormat::RetransmitRequest,
+        ),
+        entry(
+            FILE_LISTING_APID,
+            Telemetry,
+            "File listing",
+            PayloadFormat::FileListing,
+        ),
+        entry(
+            EXECUTION_REPORT_APID,
+            Telemetry,
+            "Execution report",
+            PayloadFormat::ExecutionReport,
+        ),
+        entry(
+            EVENT_LOG_APID,
+            Telemetry,
+            "Event log",
+            PayloadFormat::EventLog,
+        ),
+        entry(
+            LINK_FORECAST_APID,
+            Command,
+            "Link forecast",
+            PayloadFormat::LinkForecast,
+        ),
+        entry(
+            AUTONOMY_RULE_APID,
+            Command,
+            "Autonomy rule",
+            PayloadFormat::AutonomyRule,
+        ),
+        entry(
+            MEMORY_DUMP_APID,
+            Telemetry,
+            "Memory dump",
+            PayloadFormat::MemoryDump,
+        ),
+        entry(DWELL_APID, Telemetry, "Dwell", PayloadFormat::Dwell),
+        entry(
+            LOOPBACK_APID,
+            Telemetry,
+            "Loopback echo",
+            PayloadFormat::LoopbackEcho,
+        ),
+        entry(
+            TELEMETRY_APID,
+            Telemetry,
+            "Telemetry",
+            PayloadFormat::Telemetry,
+        ),
+        entry(
+            RF_HOUSEKEEPING_APID,
+            Telemetry,
+            "RF housekeeping",
+            PayloadFormat::Telemetry,
+        ),
+        entry(EPS_APID, Telemetry, "EPS summary", PayloadFormat::Telemetry),
+        entry(
+            CROSSLINK_RANGING_APID,
+            Telemetry,
+            "Crosslink ranging",
+            PayloadFormat::Telemetry,
+        ),
+        entry(
+            NAVIGATION_APID,
+            Telemetry,
+            "Navigation solution",
+            PayloadFormat::Telemetry,
+        ),
+    ]
+};
+
+/// Registry entry for traffic of `packet_type` on `apid`, if it is in use
+pub fn lookup_apid(apid: u16, packet_type: PacketType) -> Option<&'static ApidEntry> {
+    APID_REGISTRY
+        .iter()
+        .find(|e| e.apid == apid && e.packet_type == packet_type)
+}
+
+/// One primary header field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderField {
+    /// Field name as in CCSDS 133.0-B
+    pub name: &'static str,
+    /// First bit of the field, counted from the start of the packet
+    pub bit_offset: u8,
+    /// Field width, bits
+    pub bit_len: u8,
+    /// Field value
+    pub value: u16,
+}
+
+/// Result of the Packet Error Control check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrcCheck {
+    /// Stored CRC matches the packet
+    Valid(u16),
+    /// Stored CRC does not match the packet
+    Mismatch {
+        /// CRC carried by the packet
+        stored: u16,
+        /// CRC computed over the packet
+        computed: u16,
+    },
+    /// Frame too short to carry a CRC
+    Missing,
+}
+
+/// What a payload decoded to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadSummary {
+    /// Command ID and parameter count
+    Command {
+        /// Command identifier
+        command_id: u32,
+        /// Parameter bytes after the ID
+        parameter_bytes: usize,
+    },
+    /// Command load ID and entry count
+    CommandLoad {
+        /// Load identifier
+        load_id: u32,
+        /// Time-tagged entries
+        entries: usize,
+    },
+    /// Load manifest outcome
+    LoadManifest {
+        /// Load identifier
+        load_id: u32,
+        /// Entries accepted onboard
+        accepted: usize,
+        /// Entries rejected onboard
+        rejected: usize,
+    },
+    /// Files listed for a pass
+    FileManifest {
+        /// Pass identifier
+        pass_id: u32,
+        /// Files in the manifest
+        files: usize,
+    },
+    /// Mass memory directory listed
+    FileListing {
+        /// Name of the directory
+        directory: &'static str,
+        /// Files in the directory
+        files: u16,
+        /// Bytes held by the directory
+        used_bytes: u32,
+    },
+    /// Retransmission items for a pass
+    RetransmitRequest {
+        /// Pass identifier
+        pass_id: u32,
+        /// Files or byte ranges requested
+        items: usize,
+    },
+    /// Decoded execution report
+    ExecutionReport(ExecutionReport),
+    /// Records in an event log block
+    EventLog {
+        /// Records in the block
+        records: usize,
+    },
+    /// Forecast ID and contact count
+    LinkForecast {
+        /// Forecast identifier
+        forecast_id: u32,
+        /// Contacts forecast
+        contacts: usize,
+    },
+    /// Autonomy rule number and trigger count
+    AutonomyRule {
+        /// Rule number
+        rule_id: u16,
+        /// Triggers combined by the rule
+        triggers: usize,
+    },
+    /// Memory dump segment position
+    MemoryDump {
+        /// Dump identifier
+        dump_id: u16,
+        /// Address of the segment's first byte
+        address: u32,
+        /// Bytes in the segment
+        bytes: usize,
+    },
+    /// Dwell batch position
+    Dwell {
+        /// Dwell identifier
+        dwell_id: u16,
+        /// Index of the first sample
+        first_index: u32,
+        /// Samples in the batch
+        samples: usize,
+    },
+    /// Loopback echo identity
+    LoopbackEcho {
+        /// Test identifier
+        test_id: u16,
+        /// Onboard turnaround, milliseconds
+        turnaround_ms: u64,
+        /// Payload bytes echoed
+        bytes: usize,
+    },
+    /// Telemetry frame contents
+    Telemetry {
+        /// Frame timestamp
+        timestamp: u64,
+        /// Measurements in the frame
+        measurements: usize,
+    },
+}
+
+/// Outcome of the payload decode attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadDecode {
+    /// Payload decoded in the registered format
+    Decoded(PayloadSummary),
+    /// Payload did not decode in the registered format
+    Failed(SpaceCommError),
+    /// APID and packet type not in the registry; no decode attempted
+    Unregistered,
+}
+
+/// Annotated breakdown of one packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketInspection {
+    /// Decoded primary header
+    pub header: SpacePacketHeader,
+    /// Primary header fields in wire order
+    pub fields: [HeaderField; 7],
+    /// Registry entry for the APID and packet type
+    pub apid: Option<&'static ApidEntry>,
+    /// Byte range of the data field within the frame
+    pub payload: Range<usize>,
+    /// Frame length, bytes
+    pub frame_len: usize,
+    /// Packet Error Control check
+    pub crc: CrcCheck,
+    /// Payload decode attempt
+    pub decode: PayloadDecode,
+}
+
+impl PacketInspection {
+    /// Whether the packet is intact and decodes in its registered format
+    pub fn is_clean(&self) -> bool {
+        matches!(self.crc, CrcCheck::Valid(_)) && matches!(self.decode, PayloadDecode::Decoded(_))
+    }
+
+    /// Data field length declared by the header, bytes
+    pub fn declared_data_len(&self) -> usize {
+        usize::from(self.header.data_length) + 1
+    }
+}
+
+/// Break a raw packet down into annotated fields
+///
+/// - **ID**: FN-INS-001
+/// - **Requirement**: Give one structured description of any packet on the
+///   link, whether or not it is well formed (REQ-IF-002).
+/// - **Inputs**: Frame bytes: primary header, data field and 2-byte CRC.
+/// - **Outputs**: Header fields, registry entry, payload range, CRC check and
+///   payload decode attempt. A CRC mismatch or undecodable payload is
+///   reported in the inspection, not as an error.
+/// - **Side Effects**: None.
+/// - **Failure Modes**: Fewer than 6 bytes, or a version other than 0 →
+///   `Err(InvalidPacket)`.
+pub fn inspect(bytes: &[u8]) -> Result<PacketInspection> {
+    let header = SpacePacketHeader::from_bytes(bytes)?;
+    let fields = header_fields(&header);
+    let apid = lookup_apid(header.apid, header.packet_type);
+
+    let (payload, crc) = if bytes.len() >= PRIMARY_HEADER_LEN + ERROR_CONTROL_LEN {
+        let body_end = bytes.len() - ERROR_CONTROL_LEN;
+        let stored = WireReader::new(&bytes[body_end..]).get::<u16>()?;
+        let computed = crc16_ccitt(0xFFFF, &bytes[..body_end]);
+        let crc = if stored == computed {
+            CrcCheck::Valid(stored)
+        } else {
+            CrcCheck::Mismatch { stored, computed }
+        };
+        (PRIMARY_HEADER_LEN..body_end, crc)
+    } else {
+        (PRIMARY_HEADER_LEN..bytes.len(), CrcCheck::Missing)
+    };
+
+    let decode = match apid {
+        Some(entry) => match decode_payload(entry.format, &bytes[payload.clone()]) {
+            Ok(summary) => PayloadDecode::Decoded(summary),
+            Err(e) => PayloadDecode::Failed(e),
+        },
+        None => PayloadDecode::Unregistered,
+    };
+
+    Ok(PacketInspection {
+        header,
+        fields,
+        apid,
+        payload,
+        frame_len: bytes.len(),
+        crc,
+        decode,
+    })
+}
+
+fn header_fields(header: &SpacePacketHeader) -> [HeaderField; 7] {
+    let field = |name, bit_offset, bit_len, value| HeaderField {
+        name,
+        bit_offset,
+        bit_len,
+        value,
+    };
+    [
+        field("Packet Version Number", 0, 3, u16::from(header.version)),
+        field("Packet Type", 3, 1, header.packet_type as u16),
+        field(
+            "Secondary Header Flag",
+            4,
+            1,
+            u16::from(header.secondary_header_flag),
+        ),
+        field("APID", 5, 11, header.apid),
+        field("Sequence Flags", 16, 2, header.sequence_flags as u16),
+        field("Packet Sequence Count", 18, 14, header.sequence_count),
+        field("Packet Data Length", 32, 16, header.data_length),
+    ]
+}
+
+fn decode_payload(format: PayloadFormat, payload: &[u8]) -> Result<PayloadSummary> {
+    Ok(match format {
+        PayloadFormat::Command => {
+            let command_id = wire::command_id(payload).ok_or(SpaceCommError::invalid_packet(
+                "Command payload too short",
+                None,
+            ))?;
+            PayloadSummary::Command {
+                command_id,
+                parameter_bytes: payload.len() - wire::COMMAND_ID_LEN,
+            }
+        }
+        PayloadFormat::CommandLoad => {
+            let load = CommandLoad::from_bytes(payload)?;
+            PayloadSummary::CommandLoad {
+                load_id: load.load_id,
+                entries: load.entries.len(),
+            }
+        }
+        PayloadFormat::LoadManifest => {
+            let manifest = LoadManifest::from_bytes(payload)?;
+            PayloadSummary::LoadManifest {
+                load_id: manifest.load_id,
+                accepted: manifest.accepted.len(),
+                rejected: manifest.rejected.len(),
+            }
+        }
+        PayloadFormat::FileManifest => {
+            let manifest = FileManifest::from_bytes(payload)?;
+            PayloadSummary::FileManifest {
+                pass_id: manifest.pass_id,
+                files: manifest.files.len(),
+            }
+        }
+        PayloadFormat::FileListing => {
+            let listing = FileListing::from_bytes(payload)?;
+            PayloadSummary::FileListing {
+                directory: directory_name(listing.directory.data_type),
+                files: listing.directory.file_count,
+                used_bytes: listing.directory.used_bytes,
+            }
+        }
+        PayloadFormat::RetransmitRequest => {
+            let request = RetransmitRequest::from_bytes(payload)?;
+            PayloadSummary::RetransmitRequest {
+                pass_id: request.pass_id,
+                items: request.items.len(),
+            }
+        }
+        PayloadFormat::ExecutionReport => {
+            PayloadSummary::ExecutionReport(ExecutionReport::from_bytes(payload)?)
+        }
+        PayloadFormat::EventLog => {
+            let mut records = 0;
+            for record in EventLogDecoder::new(payload)? {
+                record?;
+                records += 1;
+            }
+            PayloadSummary::EventLog { records }
+        }
+        PayloadFormat::LinkForecast => {
+            let forecast = LinkForecast::from_bytes(payload)?;
+            PayloadSummary::LinkForecast {
+                forecast_id: forecast.forecast_id,
+                contacts: forecast.contacts.len(),
+            }
+        }
+        PayloadFormat::AutonomyRule => {
+            let rule = AutonomyRule::from_bytes(payload)?;
+            PayloadSummary::AutonomyRule {
+                rule_id: rule.id,
+                triggers: rule.when.len(),
+            }
+        }
+        PayloadFormat::MemoryDump => {
+            let segment = MemoryDumpSegment::from_bytes(payload)?;
+            PayloadSummary::MemoryDump {
+                dump_id: segment.dump_id,
+                address: segment.segment_address(),
+                bytes: segment.data.len(),
+            }
+        }
+        PayloadFormat::Dwell => {
+            let batch = DwellBatch::from_bytes(payload)?;
+            PayloadSummary::Dwell {
+                dwell_id: batch.dwell_id,
+                first_index: batch.first_index,
+                samples: batch.samples.len(),
+            }
+        }
+        PayloadFormat::LoopbackEcho => {
+            let echo = LoopbackEcho::from_bytes(payload)?;
+            PayloadSummary::LoopbackEcho {
+                test_id: echo.test_id,
+                turnaround_ms: echo.transmitted_ms.saturating_sub(echo.received_ms),
+                bytes: echo.payload.len(),
+            }
+        }
+        PayloadFormat::Telemetry => {
+            // Source and health are not on the wire; placeholders only
+            let data =
+                TelemetryData::from_payload(ComponentId::new(0), HealthStatus::Good, payload)?;
+            PayloadSummary::Telemetry {
+                timestamp: data.timestamp,
+                measurements: data.measurements.len(),
+            }
+        }
+    })
+}
+
+impl fmt::Display for PacketInspection {
+    /// Multi-line breakdown: one line per header field with its bits, then
+    /// APID name, payload, decode result and CRC
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Primary header:")?;
+        for field in &self.fields {
+            writeln!(
+                f,
+                "  bits {:>2}-{:<2} {:<22} {:0width$b} = {}",
+                field.bit_offset,
+                field.bit_offset + field.bit_len - 1,
+                field.name,
+                field.value,
+                field.value,
+                width = usize::from(field.bit_len)
+            )?;
+        }
+
+        match self.apid {
+            Some(entry) => writeln!(
+                f,
+                "APID 0x{:03X}: {} ({:?})",
+                self.header.apid, entry.name, self.header.packet_type
+            )?,
+            None => writeln!(
+                f,
+                "APID 0x{:03X}: not registered for {:?} packets",
+                self.header.apid, self.header.packet_type
+            )?,
+        }
+
+        write!(
+            f,
+            "Payload: bytes {}..{} ({} bytes",
+            self.payload.start,
+            self.payload.end,
+            self.payload.len()
+        )?;
+        if self.payload.len() != self.declared_data_len() {
+            write!(f, ", header declares {}", self.declared_data_len())?;
+        }
+        writeln!(f, ")")?;
+
+        match &self.decode {
+            PayloadDecode::Decoded(summary) => writeln!(f, "Decoded: {:?}", summary)?,
+            PayloadDecode::Failed(e) => writeln!(f, "Decode failed: {}", e)?,
+            PayloadDecode::Unregistered => writeln!(f, "Decode not attempted")?,
+        }
+
+        match self.crc {
+            CrcCheck::Valid(crc) => write!(f, "CRC 0x{:04X} valid", crc),
+            CrcCheck::Mismatch { stored, computed } => write!(
+                f,
+                "CRC 0x{:04X} MISMATCH, computed 0x{:04X}",
+                stored, computed
+            ),
+            CrcCheck::Missing => write!(f, "CRC missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccsds::SpacePacket;
+    use crate::execution_report::ExecutionResult;
+
+    fn frame(packet_type: PacketType, apid: u16, data: &[u8]) -> std::vec::Vec<u8> {
+        SpacePacket::new(packet_type, apid, 42, data, None)
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_registry_is_unambiguous() {
+        for (index, entry) in APID_REGISTRY.iter().enumerate() {
+            assert!(entry.apid <= 0x7FF);
+            assert_eq!(lookup_apid(entry.apid, entry.packet_type), Some(entry));
+            assert!(!APID_REGISTRY[index + 1..]
+                .iter()
+                .any(|other| other.apid == entry.apid && other.packet_type == entry.packet_type));
+        }
+        assert_eq!(
+            lookup_apid(COMMAND_LOAD_APID, PacketType::Telemetry).map(|e| e.format),
+            Some(PayloadFormat::LoadManifest)
+        );
+        assert_eq!(lookup_apid(0x7FF, PacketType::Command), None);
+    }
+
+    #[test]
+    fn test_inspect_command_packet() {
+        let apid = MessagePriority::High.command_apid();
+        let bytes = frame(PacketType::Command, apid, &[0x00, 0x00, 0x20, 0x01, 0x02]);
+        let inspection = inspect(&bytes).unwrap();
+
+        assert!(inspection.is_clean());
+        assert_eq!(inspection.apid.unwrap().name, "High priority command");
+        assert_eq!(inspection.fields[3].value, 0x003);
+        assert_eq!(inspection.payload, 6..bytes.len() - 2);
+        assert_eq!(
+            inspection.decode,
+            PayloadDecode::Decoded(PayloadSummary::Command {
+                command_id: 0x2001,
+                parameter_bytes: 1
+            })
+        );
+
+        let text = inspection.to_string();
+        assert!(
+            text.contains("bits  5-15 APID                   00000000011 = 3"),
+            "{}",
+            text
+        );
+        assert!(text.contains("APID 0x003: High priority command (Command)"));
+        assert!(text.ends_with("valid"));
+    }
+
+    #[test]
+    fn test_inspect_reports_corruption_and_unknown_apids() {
+        let report = ExecutionReport {
+            command_id: 0x1001,
+            sequence_count: 3,
+            priority: MessagePriority::Medium,
+            result: ExecutionResult::Completed,
+            execution_time_us: 250,
+            budget_ms: 100,
+        };
+        let mut bytes = report.to_packet(1).unwrap().to_bytes().unwrap().to_vec();
+        assert_eq!(
+            inspect(&bytes).unwrap().decode,
+            PayloadDecode::Decoded(PayloadSummary::ExecutionReport(report))
+        );
+
+        // A flipped payload bit fails the CRC but the payload is still shown
+        bytes[13] ^= 0x01;
+        let corrupted = inspect(&bytes).unwrap();
+        assert!(matches!(corrupted.crc, CrcCheck::Mismatch { .. }));
+        assert!(matches!(corrupted.decode, PayloadDecode::Decoded(_)));
+        assert!(!corrupted.is_clean());
+        assert!(corrupted.to_string().contains("MISMATCH"));
+
+        // Malformed JSON on a registered APID fails to decode
+        let load = frame(PacketType::Command, COMMAND_LOAD_APID, b"{not json");
+        assert!(matches!(
+            inspect(&load).unwrap().decode,
+            PayloadDecode::Failed(_)
+        ));
+
+        let unknown = inspect(&frame(PacketType::Telemetry, 0x321, &[1, 2, 3])).unwrap();
+        assert_eq!(unknown.apid, None);
+        assert_eq!(unknown.decode, PayloadDecode::Unregistered);
+        assert!(matches!(unknown.crc, CrcCheck::Valid(_)));
+
+        // Header only: no CRC, no payload
+        let header_only = inspect(&bytes[..6]).unwrap();
+        assert_eq!(header_only.crc, CrcCheck::Missing);
+        assert!(header_only.payload.is_empty());
+        assert!(inspect(&bytes[..5]).is_err());
+    }
+}
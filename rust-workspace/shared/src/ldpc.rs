@@ -0,0 +1,510 @@
+//! CCSDS low-density parity-check (LDPC) coding
+//!
+//! High-rate Ka-band downlinks outgrow Reed-Solomon and convolutional
+//! coding: at rate 7/8 the CCSDS C2 code (CCSDS 131.0-B) reaches a bit
+//! error rate of 10⁻⁶ at an Eb/N0 near 4 dB, several dB below what a
+//! Reed-Solomon code of the same rate needs. This module encodes and decodes
+//! it, or any other quasi-cyclic LDPC code given by its circulants.
+//!
+//! - **Code**: [`LdpcCode::c2`] builds the C2 parity-check matrix, a 2 x 16
+//!   array of 511 x 511 circulants of weight two (8176 bits per codeword,
+//!   every check on 32 bits and every bit in 4 checks). The matrix has rank
+//!   1020, so a codeword carries 7156 information bits.
+//! - **Encoding**: systematic, from a generator derived once by Gaussian
+//!   elimination of the parity-check matrix. Information bits keep their
+//!   order in the codeword positions [`LdpcCode::information_positions`].
+//! - **Decoding**: normalized min-sum belief propagation over channel
+//!   log-likelihood ratios (positive favours 0), stopping as soon as every
+//!   parity check is satisfied.
+//!
+//! Bits are passed one per byte, 0 or 1. The codec needs heap allocation and
+//! is built with the `ldpc` feature, which implies `std`.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-002: Data Transfer Rates (near-capacity coding for high-rate links)
+//! - REQ-FN-007: Multi-Band Communication (Ka-band high-rate downlinks)
+
+use crate::error::{Result, SpaceCommError};
+
+/// Size of the C2 circulants
+pub const C2_CIRCULANT_SIZE: usize = 511;
+
+/// Circulant columns of the C2 parity-check matrix
+pub const C2_BLOCK_COLUMNS: usize = 16;
+
+/// Positions of the ones in the first row of each C2 circulant, block row by
+/// block row (CCSDS 131.0-B)
+const C2_POSITIONS: [[[u16; 2]; C2_BLOCK_COLUMNS]; 2] = [
+    [
+        [0, 176],
+        [12, 239],
+        [0, 352],
+        [24, 431],
+        [0, 392],
+        [151, 409],
+        [0, 351],
+        [9, 359],
+        [0, 307],
+        [53, 329],
+        [0, 207],
+        [18, 281],
+        [0, 399],
+        [202, 457],
+        [0, 247],
+        [36, 261],
+    ],
+    [
+        [99, 471],
+        [130, 473],
+        [198, 435],
+        [260, 478],
+        [215, 420],
+        [282, 481],
+        [48, 396],
+        [193, 445],
+        [273, 430],
+        [302, 451],
+        [96, 379],
+        [191, 386],
+        [244, 467],
+        [364, 470],
+        [51, 382],
+        [192, 414],
+    ],
+];
+
+/// Decoder iterations before giving up, when the caller has no preference
+pub const DEFAULT_MAX_ITERATIONS: usize = 50;
+
+/// Normalization of min-sum check messages, compensating for min-sum
+/// overestimating their reliability
+const MIN_SUM_SCALE: f32 = 0.75;
+
+/// Parity bit of the generator: its codeword position and the information
+/// bits it sums, as a bit set over information bit indices
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParityEquation {
+    position: usize,
+    information_mask: Vec<u64>,
+}
+
+/// Binary LDPC code given by its parity checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LdpcCode {
+    /// Codeword length, bits
+    n: usize,
+    /// Codeword positions checked by each parity check
+    checks: Vec<Vec<usize>>,
+    /// Codeword positions of the information bits, in order
+    information_positions: Vec<usize>,
+    /// One equation per independent parity check
+    parity: Vec<ParityEquation>,
+}
+
+/// Result of decoding one codeword
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LdpcDecoding {
+    /// Decoded codeword bits, 0 or 1
+    pub codeword: Vec<u8>,
+    /// Iterations run
+    pub iterations: usize,
+    /// Whether every parity check is satisfied; when not, `codeword` is the
+    /// decoder's last hard decision
+    pub converged: bool,
+}
+
+impl LdpcCode {
+    /// The CCSDS C2 (8176, 7156) code
+    pub fn c2() -> Result<Self> {
+        let blocks: Vec<&[u16]> = C2_POSITIONS
+            .iter()
+            .flat_map(|row| row.iter().map(|positions| &positions[..]))
+            .collect();
+        Self::from_circulants(C2_CIRCULANT_SIZE, C2_BLOCK_COLUMNS, &blocks)
+    }
+
+    /// Quasi-cyclic code from its circulants
+    ///
+    /// # Arguments
+    /// * `circulant_size` - Rows and columns of each circulant
+    /// * `block_columns` - Circulants per block row
+    /// * `blocks` - Positions of the ones in the first row of each circulant,
+    ///   block rows one after another; each following row is the previous
+    ///   one shifted right by one
+    ///
+    /// # Returns
+    /// * `Result<LdpcCode>` - Code with its systematic generator, or error
+    ///   for an empty or ragged circulant table
+    pub fn from_circulants(
+        circulant_size: usize,
+        block_columns: usize,
+        blocks: &[&[u16]],
+    ) -> Result<Self> {
+        if circulant_size == 0 || block_columns == 0 || !blocks.len().is_multiple_of(block_columns)
+        {
+            return Err(SpaceCommError::invalid_packet(
+                "LDPC circulant table does not fill whole block rows",
+                None,
+            ));
+        }
+
+        let mut checks = Vec::with_capacity(blocks.len() / block_columns * circulant_size);
+        for block_row in blocks.chunks(block_columns) {
+            for row in 0..circulant_size {
+                let mut check: Vec<usize> = block_row
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(column, positions)| {
+                        positions.iter().map(move |&p| {
+                            column * circulant_size + (row + usize::from(p)) % circulant_size
+                        })
+                    })
+                    .collect();
+                check.sort_unstable();
+                checks.push(check);
+            }
+        }
+        Self::from_checks(block_columns * circulant_size, checks)
+    }
+
+    /// Code from the codeword positions of each parity check
+    ///
+    /// Derives the systematic generator by Gaussian elimination over GF(2).
+    /// Dependent checks are kept for decoding and skipped for encoding.
+    ///
+    /// # Returns
+    /// * `Result<LdpcCode>` - Code, or error for a position outside the
+    ///   codeword or a check matrix leaving no information bits
+    pub fn from_checks(n: usize, checks: Vec<Vec<usize>>) -> Result<Self> {
+        if checks.iter().flatten().any(|&position| position >= n) {
+            return Err(SpaceCommError::invalid_packet(
+                "LDPC check outside the codeword",
+                None,
+            ));
+        }
+
+        let words = n.div_ceil(64);
+        let mut rows: Vec<Vec<u64>> = checks
+            .iter()
+            .map(|check| {
+                let mut row = vec![0u64; words];
+                for &position in check {
+                    // Repeated positions cancel, as they do in GF(2)
+                    row[position / 64] ^= 1 << (position % 64);
+                }
+                row
+            })
+            .collect();
+
+        // Reduced row echelon form, taking pivots from the last column down
+        // so parity bits gather at the end of the codeword
+        let mut pivots = Vec::new();
+        for column in (0..n).rev() {
+            let rank = pivots.len();
+            let (word, bit) = (column / 64, 1u64 << (column % 64));
+            let Some(found) = (rank..rows.len()).find(|&r| rows[r][word] & bit != 0) else {
+                continue;
+            };
+            rows.swap(rank, found);
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != rank && row[word] & bit != 0 {
+                    row.iter_mut().zip(&pivot_row).for_each(|(a, b)| *a ^= b);
+                }
+            }
+            pivots.push(column);
+            if pivots.len() == rows.len() {
+                break;
+            }
+        }
+
+        let mut is_pivot = vec![false; n];
+        for &column in &pivots {
+            is_pivot[column] = true;
+        }
+        let information_positions: Vec<usize> = (0..n).filter(|&c| !is_pivot[c]).collect();
+        if information_positions.is_empty() {
+            return Err(SpaceCommError::invalid_packet(
+                "LDPC check matrix leaves no information bits",
+                None,
+            ));
+        }
+
+        let info_words = information_positions.len().div_ceil(64);
+        let parity = pivots
+            .iter()
+            .zip(&rows)
+            .map(|(&position, row)| {
+                let mut information_mask = vec![0u64; info_words];
+                for (index, &column) in information_positions.iter().enumerate() {
+                    if row[column / 64] & (1 << (column % 64)) != 0 {
+                        information_mask[index / 64] |= 1 << (index % 64);
+                    }
+                }
+                ParityEquation {
+                    position,
+                    information_mask,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            n,
+            checks,
+            information_positions,
+            parity,
+        })
+    }
+
+    /// Codeword length, bits
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Information bits per codeword
+    pub fn k(&self) -> usize {
+        self.information_positions.len()
+    }
+
+    /// Code rate k/n
+    pub fn rate(&self) -> f64 {
+        self.k() as f64 / self.n as f64
+    }
+
+    /// Parity checks, each as the codeword positions it covers
+    pub fn checks(&self) -> &[Vec<usize>] {
+        &self.checks
+    }
+
+    /// Codeword positions of the information bits, in order
+    pub fn information_positions(&self) -> &[usize] {
+        &self.information_positions
+    }
+
+    /// Encode `k` information bits into a codeword
+    ///
+    /// - **ID**: FN-LDPC-001
+    /// - **Requirement**: Systematic encoding of the CCSDS C2 code
+    ///   (REQ-PF-002).
+    /// - **Failure Modes**: `InvalidPacket` unless exactly `k` bits are
+    ///   given.
+    pub fn encode(&self, information: &[u8]) -> Result<Vec<u8>> {
+        if information.len() != self.k() {
+            return Err(SpaceCommError::invalid_packet(
+                "LDPC information block of the wrong length",
+                None,
+            ));
+        }
+
+        let mut packed = vec![0u64; self.k().div_ceil(64)];
+        let mut codeword = vec![0u8; self.n];
+        for (index, (&bit, &position)) in information
+            .iter()
+            .zip(&self.information_positions)
+            .enumerate()
+        {
+            let bit = bit & 1;
+            codeword[position] = bit;
+            packed[index / 64] |= u64::from(bit) << (index % 64);
+        }
+        for equation in &self.parity {
+            let ones: u32 = equation
+                .information_mask
+                .iter()
+                .zip(&packed)
+                .map(|(mask, bits)| (mask & bits).count_ones())
+                .sum();
+            codeword[equation.position] = (ones & 1) as u8;
+        }
+        Ok(codeword)
+    }
+
+    /// Information bits of a codeword
+    pub fn information(&self, codeword: &[u8]) -> Vec<u8> {
+        self.information_positions
+            .iter()
+            .map(|&position| codeword.get(position).copied().unwrap_or(0))
+            .collect()
+    }
+
+    /// Whether every parity check of `codeword` is satisfied
+    pub fn is_codeword(&self, codeword: &[u8]) -> bool {
+        codeword.len() == self.n
+            && self
+                .checks
+                .iter()
+                .all(|check| check.iter().fold(0, |sum, &p| sum ^ codeword[p]) & 1 == 0)
+    }
+
+    /// Decode channel log-likelihood ratios
+    ///
+    /// - **ID**: FN-LDPC-002
+    /// - **Requirement**: Soft-decision decoding of the CCSDS C2 code
+    ///   (REQ-PF-002).
+    /// - **Inputs**:
+    ///   - `llrs`: One log-likelihood ratio per codeword bit; positive
+    ///     favours 0, magnitude is confidence.
+    ///   - `max_iterations`: Belief propagation iterations at most.
+    /// - **Outputs**: Codeword, iterations run and whether it satisfies every
+    ///   check.
+    /// - **Failure Modes**: `InvalidPacket` unless exactly `n` ratios are
+    ///   given. A codeword that does not converge is reported, not an error.
+    pub fn decode(&self, llrs: &[f32], max_iterations: usize) -> Result<LdpcDecoding> {
+        if llrs.len() != self.n {
+            return Err(SpaceCommError::invalid_packet(
+                "LDPC codeword of the wrong length",
+                None,
+            ));
+        }
+
+        let hard = |posterior: &[f32]| -> Vec<u8> {
+            posterior.iter().map(|&l| u8::from(l < 0.0)).collect()
+        };
+        let mut posterior = llrs.to_vec();
+        let mut codeword = hard(&posterior);
+        if self.is_codeword(&codeword) {
+            return Ok(LdpcDecoding {
+                codeword,
+                iterations: 0,
+                converged: true,
+            });
+        }
+
+        // Check-to-bit messages, one per edge in check order
+        let mut messages: Vec<Vec<f32>> = self
+            .checks
+            .iter()
+            .map(|check| vec![0.0; check.len()])
+            .collect();
+        for iteration in 1..=max_iterations {
+            for (check, outgoing) in self.checks.iter().zip(messages.iter_mut()) {
+                // Bit-to-check messages exclude what this check last sent
+                let incoming: Vec<f32> = check
+                    .iter()
+                    .zip(outgoing.iter())
+                    .map(|(&p, &sent)| posterior[p] - sent)
+                    .collect();
+                let (mut min1, mut min2, mut min_index) = (f32::INFINITY, f32::INFINITY, 0);
+                let mut negative = false;
+                for (i, &m) in incoming.iter().enumerate() {
+                    negative ^= m < 0.0;
+                    let magnitude = m.abs();
+                    if magnitude < min1 {
+                        (min2, min1, min_index) = (min1, magnitude, i);
+                    } else if magnitude < min2 {
+                        min2 = magnitude;
+                    }
+                }
+                for (i, (&p, sent)) in check.iter().zip(outgoing.iter_mut()).enumerate() {
+                    let magnitude = if i == min_index { min2 } else { min1 };
+                    let sign = if negative ^ (incoming[i] < 0.0) {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+                    let message = MIN_SUM_SCALE * sign * magnitude;
+                    posterior[p] += message - *sent;
+                    *sent = message;
+                }
+            }
+
+            codeword = hard(&posterior);
+            if self.is_codeword(&codeword) {
+                return Ok(LdpcDecoding {
+                    codeword,
+                    iterations: iteration,
+                    converged: true,
+                });
+            }
+        }
+
+        Ok(LdpcDecoding {
+            codeword,
+            iterations: max_iterations,
+            converged: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Deterministic pseudo-random information bits
+    fn information(k: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..k)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                ((state >> 16) & 1) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_c2_structure_and_encoding() {
+        let code = LdpcCode::c2().unwrap();
+        assert_eq!((code.n(), code.k()), (8176, 7156));
+        assert!((code.rate() - 0.875).abs() < 0.001);
+        assert!(code.checks().iter().all(|check| check.len() == 32));
+
+        let mut column_weights = vec![0usize; code.n()];
+        let mut check_pairs = HashSet::new();
+        let mut checks_of = vec![Vec::new(); code.n()];
+        for (c, check) in code.checks().iter().enumerate() {
+            for &p in check {
+                column_weights[p] += 1;
+                checks_of[p].push(c);
+            }
+        }
+        assert!(column_weights.iter().all(|&w| w == 4));
+        for checks in &checks_of {
+            for (i, &a) in checks.iter().enumerate() {
+                for &b in &checks[i + 1..] {
+                    assert!(
+                        check_pairs.insert((a, b)),
+                        "4-cycle through checks {} and {}",
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+
+        let info = information(code.k(), 7);
+        let codeword = code.encode(&info).unwrap();
+        assert!(code.is_codeword(&codeword));
+        assert_eq!(code.information(&codeword), info);
+        assert!(code.encode(&info[1..]).is_err());
+    }
+
+    #[test]
+    fn test_c2_decodes_noisy_codeword() {
+        let code = LdpcCode::c2().unwrap();
+        let codeword = code.encode(&information(code.k(), 11)).unwrap();
+
+        // Confident channel values with 60 weak, wrong ones spread over the
+        // codeword
+        let mut llrs: Vec<f32> = codeword
+            .iter()
+            .map(|&bit| if bit == 0 { 4.0 } else { -4.0 })
+            .collect();
+        for i in 0..60 {
+            let p = (i * 131 + 17) % code.n();
+            llrs[p] = if codeword[p] == 0 { -0.5 } else { 0.5 };
+        }
+
+        let decoded = code.decode(&llrs, DEFAULT_MAX_ITERATIONS).unwrap();
+        assert!(decoded.converged);
+        assert!(decoded.iterations >= 1);
+        assert_eq!(decoded.codeword, codeword);
+        assert!(code.decode(&llrs[1..], 10).is_err());
+
+        let clean: Vec<f32> = codeword
+            .iter()
+            .map(|&bit| if bit == 0 { 1.0 } else { -1.0 })
+            .collect();
+        assert_eq!(code.decode(&clean, 10).unwrap().iterations, 0);
+    }
+}
@@ -6,9 +6,54 @@
 //! ## Features
 //! - CCSDS-compliant packet structures with auto-CRC integrity protection
 //! - Priority-based messaging protocols with TTL enforcement
+//! - TC Transfer Frames sent under COP-1 (FOP-1/FARM-1) acknowledgment and
+//!   retransmission
+//! - TM Transfer Frames interleaving housekeeping, science and playback
+//!   virtual channels on the downlink by weight
 //! - HMAC-SHA256 command authentication
+//! - AES-256-GCM packet protection with per-APID policies and key rotation
+//! - Time-tagged command loads with manifest acknowledgment
+//! - Recorder file manifests with selective retransmission
+//! - Mass memory file system with a directory, quota and retention per data
+//!   type
+//! - Uplinked link capacity forecasts steering the recorder downlink
+//! - Per-command execution reports with measured execution time
+//! - Memory dump and dwell diagnostics downlinked in reassemblable pieces
+//! - Command path loopback self-test measuring uplink and downlink delays
+//! - Sliding-window discarding of retransmitted duplicate commands
+//! - Delta and dictionary compression of the onboard event log for downlink
+//! - Priority inversion detection with housekeeping counters
+//! - Table-driven simulated sensors with noise, drift and injectable faults
+//! - Telemetry queue that drops housekeeping before alarms and events
+//! - Per-band transceiver (RF) housekeeping telemetry with limit definitions
+//! - Electrical power system (EPS) summary telemetry with battery state of charge
+//! - Per-subsystem power draw attribution downlinked with the EPS summary
+//! - Formation flying crosslink range and range rate with noise models
+//! - GPS navigation falling back to propagated orbit knowledge in an outage,
+//!   with a pointing deadband widened by the position uncertainty
+//! - Independent uplink and downlink band, power and data rate settings
+//! - Link layer statistics shared by the simulation, the satellite and the
+//!   ground station
+//! - Mission-configurable link and power margin policy
+//! - Mission phases setting telemetry rates, allowed commands and FDIR
+//!   aggressiveness
+//! - Orbital lifetime under drag and deorbit burn planning for 25-year disposal
+//! - Onboard flight rules rejecting commands that break operational constraints
+//! - Uploaded onboard autonomy rules acting on telemetry conditions between
+//!   contacts, enabled and disabled by command
+//! - Band limits and licensed frequency assignments checked on reconfiguration
+//! - Packet inspector giving an annotated breakdown of raw frames by APID
+//! - One wire format definition (byte order, packing, frame checks) for every
+//!   on-air structure
 //! - Error correction and fault tolerance types
+//! - CCSDS Reed-Solomon (255,223) coding of frames, enabled per band
+//! - Retry policies with backoff, jitter and deadlines
 //! - Security and cryptographic primitives
+//! - Rollover-safe packet sequence count windows per APID
+//! - CCSDS C2 LDPC encoding and soft-decision decoding (`ldpc` feature)
+//! - JSON Schemas of the command and telemetry types (`schema` feature)
+//! - Binary settings layered from a file and the environment (`settings`
+//!   feature)
 //! - Aerospace-standard data types
 
 #![cfg_attr(feature = "no-std", no_std)]
@@ -24,19 +69,59 @@ extern crate std;
 #[cfg(not(feature = "std"))]
 extern crate core as std;
 
+pub mod autonomy;
 pub mod ccsds;
+pub mod command_dedup;
+pub mod command_load;
 pub mod commands;
+pub mod cop1;
+pub mod diagnostics;
+pub mod eps;
 pub mod error;
+pub mod event_log;
+pub mod execution_report;
+pub mod fec;
+pub mod file_downlink;
+pub mod flight_rules;
+pub mod formation;
+pub mod frequency_plan;
+pub mod inspector;
+#[cfg(feature = "ldpc")]
+pub mod ldpc;
+pub mod link_config;
+pub mod link_forecast;
+pub mod link_stats;
+pub mod loopback;
+pub mod margin;
+pub mod mass_memory;
 pub mod messaging;
+pub mod mission_phase;
+pub mod navigation;
+pub mod orbit;
+pub mod power_attribution;
+pub mod priority_inversion;
+pub mod retry;
+pub mod rf_housekeeping;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod security;
+pub mod sequence;
+#[cfg(feature = "settings")]
+pub mod settings;
+pub mod sensor_model;
 pub mod telemetry;
+pub mod telemetry_queue;
 pub mod time;
 pub mod types;
+pub mod vc_mux;
+pub mod wire;
 
 // Re-export commonly used types
+pub use command_load::{CommandLoad, CommandScheduler, LoadConstraints, LoadManifest};
 pub use commands::{SpaceCommand, CommandBuilder};
 pub use error::{Result, SpaceCommError};
 pub use messaging::{Message, MessagePriority, PriorityQueue};
-pub use security::{AuthTag, CommandAuthenticator, DIGEST_LEN};
+pub use retry::{RetryPolicy, RetryState};
+pub use security::{AuthTag, CommandAuthenticator, KeyStore, DIGEST_LEN};
 pub use telemetry::{TelemetryData, TelemetryPacket};
 pub use types::{BandType, ComponentId, MessageId, PacketId};
@@ -0,0 +1,242 @@
+//! Per-direction link configuration
+//!
+//! Missions rarely use the same band both ways: commands go up on S-band
+//! while science comes down on X- or Ka-band. A [`LinkConfiguration`] holds a
+//! separate band, transmit power level and data rate for the uplink and the
+//! downlink, so each direction is selected and reconfigured on its own.
+//!
+//! The uplink is limited to [`UPLINK_BANDS`], the bands the satellite has
+//! command receivers for. UHF is not among them: it is the emergency uplink
+//! lane and is never the routine command band.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (independent uplink/downlink bands)
+//! - REQ-NF-004: Power management per transmit direction
+//! - REQ-PF-002: Data Transfer Rates (per-direction data rate)
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SpaceCommError};
+use crate::types::BandType;
+
+/// Bands the satellite can receive routine commands on
+pub const UPLINK_BANDS: [BandType; 2] = [BandType::SBand, BandType::XBand];
+
+/// Direction of a space-ground link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LinkDirection {
+    /// Ground to satellite: commands and command loads
+    Uplink,
+    /// Satellite to ground: telemetry, reports and science data
+    Downlink,
+}
+
+impl fmt::Display for LinkDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkDirection::Uplink => write!(f, "uplink"),
+            LinkDirection::Downlink => write!(f, "downlink"),
+        }
+    }
+}
+
+/// Band, power and data rate of one link direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectionalLink {
+    /// Band the direction is carried on
+    pub band: BandType,
+    /// Transmitter power level, 0-100%
+    pub power_level: u8,
+    /// Data rate in bits per second
+    pub data_rate_bps: u64,
+}
+
+impl DirectionalLink {
+    /// Create a direction configuration
+    pub const fn new(band: BandType, power_level: u8, data_rate_bps: u64) -> Self {
+        Self {
+            band,
+            power_level,
+            data_rate_bps,
+        }
+    }
+
+    /// Check that the settings are usable in `direction`
+    ///
+    /// - **ID**: FN-LNK-001
+    /// - **Requirement**: Reject a direction configuration the link cannot
+    ///   carry before it is applied (REQ-FN-007).
+    /// - **Inputs**:
+    ///   - `direction`: Direction the settings are for.
+    /// - **Outputs**: `Ok(())` if usable.
+    /// - **Failure Modes**: Power above 100%, zero data rate, data rate above
+    ///   the band maximum, or an uplink band outside [`UPLINK_BANDS`] →
+    ///   `Err(ConfigurationError)`.
+    pub fn validate(&self, direction: LinkDirection) -> Result<()> {
+        if self.power_level > 100 {
+            return Err(link_error("power_level", "power level exceeds 100%"));
+        }
+        if self.data_rate_bps == 0 {
+            return Err(link_error("data_rate_bps", "data rate must be non-zero"));
+        }
+        if self.data_rate_bps > self.band.typical_data_rate_range().1 {
+            return Err(link_error(
+                "data_rate_bps",
+                "data rate exceeds the band maximum",
+            ));
+        }
+        if direction == LinkDirection::Uplink && !UPLINK_BANDS.contains(&self.band) {
+            return Err(link_error("band", "no command receiver on this band"));
+        }
+        Ok(())
+    }
+}
+
+/// Configuration error about a link setting
+const fn link_error(parameter: &'static str, reason: &'static str) -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter,
+        value: "<link configuration>",
+        reason,
+    }
+}
+
+/// Uplink and downlink settings of a space-ground link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkConfiguration {
+    /// Ground to satellite
+    pub uplink: DirectionalLink,
+    /// Satellite to ground
+    pub downlink: DirectionalLink,
+}
+
+impl Default for LinkConfiguration {
+    /// S-band TT&C uplink at 2 Mbps, X-band downlink at 100 Mbps
+    fn default() -> Self {
+        Self {
+            uplink: DirectionalLink::new(BandType::SBand, 75, 2_000_000),
+            downlink: DirectionalLink::new(BandType::XBand, 80, 100_000_000),
+        }
+    }
+}
+
+impl LinkConfiguration {
+    /// Settings of one direction
+    pub const fn direction(&self, direction: LinkDirection) -> &DirectionalLink {
+        match direction {
+            LinkDirection::Uplink => &self.uplink,
+            LinkDirection::Downlink => &self.downlink,
+        }
+    }
+
+    /// Replace the settings of one direction, leaving the other untouched
+    ///
+    /// - **ID**: FN-LNK-002
+    /// - **Requirement**: Reconfigure each direction independently
+    ///   (REQ-FN-007).
+    /// - **Inputs**:
+    ///   - `direction`: Direction to reconfigure.
+    ///   - `link`: New band, power level and data rate.
+    /// - **Outputs**: `Ok(())` once applied.
+    /// - **Failure Modes**: As [`DirectionalLink::validate`]; the
+    ///   configuration is unchanged on error.
+    pub fn set(&mut self, direction: LinkDirection, link: DirectionalLink) -> Result<()> {
+        link.validate(direction)?;
+        match direction {
+            LinkDirection::Uplink => self.uplink = link,
+            LinkDirection::Downlink => self.downlink = link,
+        }
+        Ok(())
+    }
+
+    /// Check both directions
+    pub fn validate(&self) -> Result<()> {
+        self.uplink.validate(LinkDirection::Uplink)?;
+        self.downlink.validate(LinkDirection::Downlink)
+    }
+
+    /// Whether the two directions use different bands
+    pub fn is_asymmetric(&self) -> bool {
+        self.uplink.band != self.downlink.band
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_asymmetric_and_valid() {
+        let links = LinkConfiguration::default();
+        assert!(links.validate().is_ok());
+        assert!(links.is_asymmetric());
+        assert_eq!(links.direction(LinkDirection::Uplink).band, BandType::SBand);
+        assert_eq!(
+            links.direction(LinkDirection::Downlink).band,
+            BandType::XBand
+        );
+    }
+
+    #[test]
+    fn test_set_changes_one_direction() {
+        let mut links = LinkConfiguration::default();
+        let ka = DirectionalLink::new(BandType::KaBand, 95, 10_000_000_000);
+        links.set(LinkDirection::Downlink, ka).unwrap();
+        assert_eq!(links.downlink, ka);
+        assert_eq!(links.uplink, LinkConfiguration::default().uplink);
+
+        // Ka-band has no command receiver
+        assert!(links.set(LinkDirection::Uplink, ka).is_err());
+        assert_eq!(links.uplink.band, BandType::SBand);
+    }
+
+    #[test]
+    fn test_invalid_settings_rejected() {
+        let uplink = LinkDirection::Uplink;
+        assert!(DirectionalLink::new(BandType::SBand, 101, 2_000_000)
+            .validate(uplink)
+            .is_err());
+        assert!(DirectionalLink::new(BandType::SBand, 50, 0)
+            .validate(uplink)
+            .is_err());
+        assert!(DirectionalLink::new(BandType::SBand, 50, 200_000_000)
+            .validate(uplink)
+            .is_err());
+        assert!(DirectionalLink::new(BandType::UhfBand, 50, 9_600)
+            .validate(uplink)
+            .is_err());
+        assert!(DirectionalLink::new(BandType::UhfBand, 50, 9_600)
+            .validate(LinkDirection::Downlink)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_reconfigure_command_carries_direction() {
+        use crate::commands::{ModulationType, SpaceCommand};
+
+        let command = SpaceCommand::ReconfigureComm {
+            direction: LinkDirection::Downlink,
+            band: BandType::KaBand,
+            frequency_hz: 32_000_000_000,
+            power_level: 90,
+            data_rate_bps: 1_000_000_000,
+            modulation: ModulationType::QAM16,
+            error_correction: true,
+        };
+        assert_eq!(
+            command.link_settings(),
+            Some((
+                LinkDirection::Downlink,
+                DirectionalLink::new(BandType::KaBand, 90, 1_000_000_000)
+            ))
+        );
+        let halt = SpaceCommand::EmergencyHalt {
+            subsystems: heapless::Vec::new(),
+            override_code: 0,
+        };
+        assert_eq!(halt.link_settings(), None);
+    }
+}
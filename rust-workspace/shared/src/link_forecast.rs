@@ -0,0 +1,832 @@
+//! Link capacity forecasts and forecast-driven downlink planning
+//!
+//! Rain and low-elevation passes shrink what a contact can carry, and a
+//! recorder that downlinks in storage order spends a degraded contact on
+//! bulk products while urgent ones wait for the next pass. The ground
+//! predicts the volume each upcoming contact can carry from the pass
+//! geometry and the weather forecast, and uplinks it as a [`LinkForecast`].
+//! Onboard, the [`DownlinkScheduler`] uses the forecast for the contact in
+//! progress to choose which stored products go down: highest priority first,
+//! only what is expected to fit, and nothing below the degraded-contact
+//! priority floor when the contact is forecast to be degraded. Products left
+//! out stay stored for a better contact.
+//!
+//! Times are mission seconds on the same clock as command load release
+//! times. Without a forecast covering the current time the scheduler plans
+//! as if the contact were unconstrained.
+//!
+//! The order products are considered in is the scheduler's
+//! [`DownlinkPolicy`], selected by [`SpaceCommand::SetDownlinkPolicy`]:
+//! downlink priority, the age of the product either way, or the science
+//! value the product was tagged with when it was stored. Whatever the
+//! policy, the degraded-contact priority floor still applies. The scheduler
+//! counts the contacts, files and volume planned under each policy, so the
+//! ground can compare them; the satellite downlinks the counts with the RF
+//! housekeeping, from [`measurement_ids::DOWNLINK_POLICY_BASE`]:
+//!
+//! | Offset      | Field                                  | Unit |
+//! |-------------|----------------------------------------|------|
+//! | 0x0         | Policy in use (`DownlinkPolicy` code)  |      |
+//! | 0x1 + 3·p   | Contacts planned under policy `p`      |      |
+//! | 0x2 + 3·p   | Files planned under policy `p`         |      |
+//! | 0x3 + 3·p   | Volume planned under policy `p`        | kB   |
+//!
+//! # Requirements Traceability
+//! - REQ-FN-001: Priority Classification (priority-ordered recorder downlink)
+//! - REQ-PF-002: Data Transfer Rates (contact volume matched to the forecast)
+//! - REQ-IF-002: CCSDS Compliance (forecast carried in a Space Packet)
+//! - REQ-FN-005: Medium Priority Commands (downlink policy selected by the
+//!   ground)
+//!
+//! [`SpaceCommand::SetDownlinkPolicy`]: crate::commands::SpaceCommand::SetDownlinkPolicy
+//! [`measurement_ids::DOWNLINK_POLICY_BASE`]: crate::telemetry::measurement_ids::DOWNLINK_POLICY_BASE
+
+use core::cmp::Ordering;
+use core::fmt;
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::SpaceCommand;
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::file_downlink::MAX_MANIFEST_FILES;
+use crate::rf_housekeeping::RF_HOUSEKEEPING_PERIOD_MS;
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    TelemetryData,
+};
+use crate::wire;
+
+/// Maximum number of contacts in one forecast; keeps the serialized
+/// forecast inside one Space Packet
+pub const MAX_FORECAST_CONTACTS: usize = 12;
+
+/// Largest serialized forecast, bytes
+pub const MAX_FORECAST_BYTES: usize = 2048;
+
+/// APID used to uplink link capacity forecasts
+pub const LINK_FORECAST_APID: u16 = 0x015;
+
+/// Forecast capacity, as a percentage of nominal, below which a contact is
+/// degraded
+pub const DEFAULT_DEGRADED_PERCENT: u8 = 50;
+
+/// Lowest product priority still downlinked on a degraded contact
+pub const DEFAULT_DEGRADED_MIN_PRIORITY: u8 = 128;
+
+/// Command ID of [`SpaceCommand::SetDownlinkPolicy`]
+pub const SET_DOWNLINK_POLICY_COMMAND_ID: u32 = 0x003B;
+
+/// Order in which stored products are considered for downlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum DownlinkPolicy {
+    /// Highest downlink priority first, oldest first within a priority
+    #[default]
+    Priority = 0,
+    /// Most recently stored first, so every contact carries the latest data
+    NewestFirst = 1,
+    /// Highest science value first, oldest first within a value
+    HighestValueFirst = 2,
+    /// Oldest first, so the recorder empties in the order it filled and
+    /// products go down before a directory's retention removes them
+    OldestFirst = 3,
+}
+
+impl DownlinkPolicy {
+    /// Every policy, in code order
+    pub const ALL: [DownlinkPolicy; 4] = [
+        DownlinkPolicy::Priority,
+        DownlinkPolicy::NewestFirst,
+        DownlinkPolicy::HighestValueFirst,
+        DownlinkPolicy::OldestFirst,
+    ];
+
+    /// Policy with telemetry code `code`
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| *policy as u8 == code)
+    }
+
+    /// Operator-facing name
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DownlinkPolicy::Priority => "priority",
+            DownlinkPolicy::NewestFirst => "newest first",
+            DownlinkPolicy::HighestValueFirst => "highest value first",
+            DownlinkPolicy::OldestFirst => "oldest first",
+        }
+    }
+
+    /// Order of `a` against `b`; `Less` when `a` goes down first
+    ///
+    /// Ties on the policy's own key fall back to downlink priority, then to
+    /// the file ID, so a plan is the same whatever order products were
+    /// stored in.
+    fn compare(&self, a: &StoredProduct, b: &StoredProduct) -> Ordering {
+        let by_priority = b.priority.cmp(&a.priority);
+        let oldest = a.stored_s.cmp(&b.stored_s);
+        match self {
+            DownlinkPolicy::Priority => by_priority.then(oldest),
+            DownlinkPolicy::NewestFirst => oldest.reverse().then(by_priority),
+            DownlinkPolicy::HighestValueFirst => b
+                .science_value
+                .cmp(&a.science_value)
+                .then(by_priority)
+                .then(oldest),
+            DownlinkPolicy::OldestFirst => oldest.then(by_priority),
+        }
+        .then(a.file_id.cmp(&b.file_id))
+    }
+
+    /// Policy commanded in a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a policy
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != SET_DOWNLINK_POLICY_COMMAND_ID {
+            return None;
+        }
+        let policy = match serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..]) {
+            Ok(SpaceCommand::SetDownlinkPolicy { policy }) => Ok(policy),
+            _ => Err(SpaceCommError::invalid_packet(
+                "Malformed downlink policy command",
+                Some(command_id),
+            )),
+        };
+        Some(policy)
+    }
+}
+
+impl fmt::Display for DownlinkPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Expected capacity of one upcoming contact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactForecast {
+    /// Contact identifier, matching the pass ID of its file manifest
+    pub contact_id: u32,
+    /// Acquisition of signal, mission seconds
+    pub start_s: u64,
+    /// Contact duration, seconds
+    pub duration_s: u32,
+    /// Volume the contact is forecast to carry, bytes
+    pub capacity_bytes: u64,
+    /// Volume the contact would carry in nominal weather, bytes
+    pub nominal_capacity_bytes: u64,
+}
+
+impl ContactForecast {
+    /// Loss of signal, mission seconds
+    pub const fn end_s(&self) -> u64 {
+        self.start_s.saturating_add(self.duration_s as u64)
+    }
+
+    /// Whether the contact is in progress at `now_s`
+    pub const fn contains(&self, now_s: u64) -> bool {
+        self.start_s <= now_s && now_s < self.end_s()
+    }
+
+    /// Forecast capacity as a percentage of nominal, at most 100
+    pub fn capacity_percent(&self) -> u8 {
+        if self.nominal_capacity_bytes == 0 {
+            return 0;
+        }
+        let percent = self.capacity_bytes.saturating_mul(100) / self.nominal_capacity_bytes;
+        percent.min(100) as u8
+    }
+
+    /// Whether the forecast capacity is below `degraded_percent` of nominal
+    pub fn is_degraded(&self, degraded_percent: u8) -> bool {
+        self.capacity_percent() < degraded_percent
+    }
+
+    /// Capacity left from `now_s` to the end of the contact, bytes,
+    /// assuming the forecast volume is spread evenly over the contact
+    pub fn remaining_bytes(&self, now_s: u64) -> u64 {
+        if self.duration_s == 0 || now_s >= self.end_s() {
+            return 0;
+        }
+        let remaining_s = self.end_s() - now_s.max(self.start_s);
+        let scaled =
+            u128::from(self.capacity_bytes) * u128::from(remaining_s) / u128::from(self.duration_s);
+        scaled as u64
+    }
+}
+
+/// Forecast of the capacity of upcoming contacts, uplinked before them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkForecast {
+    /// Forecast identifier; a newer forecast replaces an older one
+    pub forecast_id: u32,
+    /// Time the forecast was made, mission seconds
+    pub issued_s: u64,
+    /// Contacts in time order
+    pub contacts: Vec<ContactForecast, MAX_FORECAST_CONTACTS>,
+}
+
+impl LinkForecast {
+    /// Create an empty forecast
+    pub const fn new(forecast_id: u32, issued_s: u64) -> Self {
+        Self {
+            forecast_id,
+            issued_s,
+            contacts: Vec::new(),
+        }
+    }
+
+    /// Add the next contact
+    pub fn push(&mut self, contact: ContactForecast) -> Result<()> {
+        self.contacts.push(contact).map_err(|_| {
+            SpaceCommError::memory_error(
+                MemoryErrorType::BufferOverflow,
+                Some(MAX_FORECAST_CONTACTS),
+            )
+        })
+    }
+
+    /// Check the forecast before uplink or use
+    ///
+    /// - **ID**: FN-FCS-001
+    /// - **Requirement**: Only a consistent forecast steers the recorder
+    ///   downlink.
+    /// - **Outputs**: `Ok(())` if contacts are in time order, do not overlap
+    ///   and have distinct IDs.
+    /// - **Failure Modes**: Overlapping or out-of-order contacts, or a
+    ///   repeated contact ID → `Err(InvalidPacket)`.
+    pub fn validate(&self) -> Result<()> {
+        for (index, contact) in self.contacts.iter().enumerate() {
+            let earlier = &self.contacts[..index];
+            if earlier.iter().any(|c| c.contact_id == contact.contact_id) {
+                return Err(SpaceCommError::invalid_packet(
+                    "Repeated contact in link forecast",
+                    Some(contact.contact_id),
+                ));
+            }
+            if earlier.last().is_some_and(|c| c.end_s() > contact.start_s) {
+                return Err(SpaceCommError::invalid_packet(
+                    "Link forecast contacts overlap or are out of order",
+                    Some(contact.contact_id),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Contact in progress at `now_s`
+    pub fn contact_at(&self, now_s: u64) -> Option<&ContactForecast> {
+        self.contacts.iter().find(|c| c.contains(now_s))
+    }
+
+    /// Contact in progress at `now_s`, or else the next one to start
+    pub fn next_contact(&self, now_s: u64) -> Option<&ContactForecast> {
+        self.contacts.iter().find(|c| now_s < c.end_s())
+    }
+
+    /// Serialize the forecast for uplink
+    pub fn to_bytes(&self) -> Result<Vec<u8, MAX_FORECAST_BYTES>> {
+        let bytes = serde_json::to_vec(self).map_err(|_| {
+            SpaceCommError::invalid_packet("Failed to serialize link forecast", None)
+        })?;
+        Vec::from_slice(&bytes).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+        })
+    }
+
+    /// Parse a forecast received over the uplink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed link forecast", None))
+    }
+}
+
+/// Recorder product waiting for downlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredProduct {
+    /// Recorder file identifier
+    pub file_id: u32,
+    /// File size, bytes
+    pub size_bytes: u32,
+    /// Downlink priority; higher goes first
+    pub priority: u8,
+    /// Science value tagged when the product was stored; higher is worth more
+    pub science_value: u8,
+    /// Time the product was stored, mission seconds
+    pub stored_s: u64,
+}
+
+impl StoredProduct {
+    /// Time since the product was stored at `now_s`, seconds
+    pub const fn age_s(&self, now_s: u64) -> u64 {
+        now_s.saturating_sub(self.stored_s)
+    }
+}
+
+/// Products chosen for one contact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactPlan {
+    /// Contact planned for; `None` when no forecast covers it
+    pub contact_id: Option<u32>,
+    /// Policy the files were ordered by
+    pub policy: DownlinkPolicy,
+    /// Whether the contact is forecast to be degraded
+    pub degraded: bool,
+    /// Files to downlink, in order
+    pub files: Vec<u32, MAX_MANIFEST_FILES>,
+    /// Volume of the planned files, bytes
+    pub planned_bytes: u64,
+    /// Products left stored for a later contact
+    pub deferred: usize,
+}
+
+/// Downlinks planned under one policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PolicyStats {
+    /// Contacts planned
+    pub contacts: u32,
+    /// Files planned
+    pub files: u32,
+    /// Volume planned, bytes
+    pub bytes: u64,
+}
+
+/// Policy in use and what each policy has planned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DownlinkPolicyStats {
+    /// Policy in use
+    pub policy: DownlinkPolicy,
+    /// Statistics per policy, in code order
+    pub per_policy: [PolicyStats; 4],
+}
+
+impl DownlinkPolicyStats {
+    /// Statistics of `policy`
+    pub const fn for_policy(&self, policy: DownlinkPolicy) -> &PolicyStats {
+        &self.per_policy[policy as usize]
+    }
+
+    /// Append the policy and statistics to a housekeeping frame
+    ///
+    /// Volumes are downlinked to the whole kilobyte and saturate rather
+    /// than wrap.
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        let base = measurement_ids::DOWNLINK_POLICY_BASE;
+        push_counter(data, base, u64::from(self.policy as u8), "")?;
+        for policy in DownlinkPolicy::ALL {
+            let stats = self.for_policy(policy);
+            let first = Self::first_id(policy);
+            push_counter(data, first, u64::from(stats.contacts), "")?;
+            push_counter(data, first + 1, u64::from(stats.files), "")?;
+            push_counter(data, first + 2, stats.bytes / 1_000, "kB")?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the statistics from downlinked measurements; `None` unless
+    /// the policy in use is present
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let value = |id: u16| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == id)
+                .and_then(|m| match m.value {
+                    MeasurementValue::Integer(v) => u64::try_from(v).ok(),
+                    _ => None,
+                })
+        };
+
+        let code = value(measurement_ids::DOWNLINK_POLICY_BASE)?;
+        let mut stats = Self {
+            policy: DownlinkPolicy::from_code(u8::try_from(code).ok()?)?,
+            per_policy: [PolicyStats::default(); 4],
+        };
+        for policy in DownlinkPolicy::ALL {
+            let first = Self::first_id(policy);
+            stats.per_policy[policy as usize] = PolicyStats {
+                contacts: value(first).unwrap_or(0) as u32,
+                files: value(first + 1).unwrap_or(0) as u32,
+                bytes: value(first + 2).unwrap_or(0) * 1_000,
+            };
+        }
+        Some(stats)
+    }
+
+    /// Dictionary entries covering the policy in use and the statistics
+    pub const fn dictionary_entries() -> [DictionaryEntry; 2] {
+        let base = measurement_ids::DOWNLINK_POLICY_BASE;
+        [
+            DictionaryEntry {
+                first_id: base,
+                last_id: base,
+                name: "Downlink policy",
+                unit: "",
+                period_ms: RF_HOUSEKEEPING_PERIOD_MS,
+            },
+            DictionaryEntry {
+                first_id: base + 1,
+                last_id: base + 0x0F,
+                name: "Downlink policy statistics",
+                unit: "",
+                period_ms: RF_HOUSEKEEPING_PERIOD_MS,
+            },
+        ]
+    }
+
+    /// Measurement ID of the first statistic of `policy`
+    const fn first_id(policy: DownlinkPolicy) -> u16 {
+        measurement_ids::DOWNLINK_POLICY_BASE + 1 + 3 * policy as u16
+    }
+}
+
+impl fmt::Display for DownlinkPolicyStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "policy {}", self.policy)?;
+        for policy in DownlinkPolicy::ALL {
+            let stats = self.for_policy(policy);
+            write!(
+                f,
+                "; {}: {} contacts, {} files, {:.1} MB",
+                policy,
+                stats.contacts,
+                stats.files,
+                stats.bytes as f64 / 1_000_000.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Append one integer counter, Good quality, saturating at `i32::MAX`
+fn push_counter(data: &mut TelemetryData, id: u16, value: u64, unit: &'static str) -> Result<()> {
+    data.measurements
+        .push(Measurement {
+            measurement_id: id,
+            value: MeasurementValue::Integer(value.min(i32::MAX as u64) as i64),
+            unit,
+            quality: MeasurementQuality::Good,
+        })
+        .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, None))
+}
+
+/// Onboard recorder downlink planner steered by the link forecast
+///
+/// - **ID**: MOD-FCS-001
+/// - **Requirement**: Send the most important stored data a degraded contact
+///   can carry, and keep the rest for a better contact (REQ-FN-001,
+///   REQ-PF-002).
+/// - **Failure Modes**: A contact that delivers less than forecast loses the
+///   tail of the plan; the file downlink manifest and retransmission requests
+///   recover it on a later pass.
+/// - **Constraints**: Fixed capacity `N`, no heap allocation; O(N log N)
+///   planning.
+#[derive(Debug, Clone)]
+pub struct DownlinkScheduler<const N: usize> {
+    products: Vec<StoredProduct, N>,
+    forecast: Option<LinkForecast>,
+    degraded_percent: u8,
+    degraded_min_priority: u8,
+    stats: DownlinkPolicyStats,
+}
+
+impl<const N: usize> Default for DownlinkScheduler<N> {
+    /// Scheduler with [`DEFAULT_DEGRADED_PERCENT`] and
+    /// [`DEFAULT_DEGRADED_MIN_PRIORITY`]
+    fn default() -> Self {
+        Self::new(DEFAULT_DEGRADED_PERCENT, DEFAULT_DEGRADED_MIN_PRIORITY)
+    }
+}
+
+impl<const N: usize> DownlinkScheduler<N> {
+    /// Create a scheduler treating contacts below `degraded_percent` of
+    /// nominal as degraded, and sending only products of at least
+    /// `degraded_min_priority` on them, under [`DownlinkPolicy::Priority`]
+    pub const fn new(degraded_percent: u8, degraded_min_priority: u8) -> Self {
+        const NONE: PolicyStats = PolicyStats {
+            contacts: 0,
+            files: 0,
+            bytes: 0,
+        };
+        Self {
+            products: Vec::new(),
+            forecast: None,
+            degraded_percent,
+            degraded_min_priority,
+            stats: DownlinkPolicyStats {
+                policy: DownlinkPolicy::Priority,
+                per_policy: [NONE; 4],
+            },
+        }
+    }
+
+    /// Add a stored product
+    pub fn store(&mut self, product: StoredProduct) -> Result<()> {
+        self.products
+            .push(product)
+            .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(N)))
+    }
+
+    /// Remove a product once its downlink is complete
+    pub fn remove(&mut self, file_id: u32) -> Option<StoredProduct> {
+        let index = self.products.iter().position(|p| p.file_id == file_id)?;
+        Some(self.products.swap_remove(index))
+    }
+
+    /// Products waiting for downlink
+    pub fn len(&self) -> usize {
+        self.products.len()
+    }
+
+    /// Whether no product is waiting
+    pub fn is_empty(&self) -> bool {
+        self.products.is_empty()
+    }
+
+    /// Forecast in use, if one has been accepted
+    pub const fn forecast(&self) -> Option<&LinkForecast> {
+        self.forecast.as_ref()
+    }
+
+    /// Policy plans are ordered by
+    pub const fn policy(&self) -> DownlinkPolicy {
+        self.stats.policy
+    }
+
+    /// Order plans by `policy` from the next plan on
+    pub fn set_policy(&mut self, policy: DownlinkPolicy) {
+        self.stats.policy = policy;
+    }
+
+    /// Policy in use and what each policy has planned
+    pub const fn stats(&self) -> &DownlinkPolicyStats {
+        &self.stats
+    }
+
+    /// Count a plan that was carried out against the policy it was ordered by
+    ///
+    /// [`DownlinkScheduler::plan`] does not count its plans itself, so a
+    /// caller polling it counts each contact once.
+    pub fn record(&mut self, plan: &ContactPlan) {
+        let stats = &mut self.stats.per_policy[plan.policy as usize];
+        stats.contacts = stats.contacts.saturating_add(1);
+        stats.files = stats.files.saturating_add(plan.files.len() as u32);
+        stats.bytes = stats.bytes.saturating_add(plan.planned_bytes);
+    }
+
+    /// Accept an uplinked forecast
+    ///
+    /// - **ID**: FN-FCS-002
+    /// - **Requirement**: The latest valid forecast steers the downlink.
+    /// - **Side Effects**: Replaces the forecast in use unless it is newer
+    ///   than `forecast`.
+    /// - **Failure Modes**: An invalid forecast, or one older than the
+    ///   forecast in use → `Err(InvalidPacket)`; the forecast in use is kept.
+    pub fn accept_forecast(&mut self, forecast: LinkForecast) -> Result<()> {
+        forecast.validate()?;
+        if let Some(current) = &self.forecast {
+            if forecast.forecast_id < current.forecast_id {
+                return Err(SpaceCommError::invalid_packet(
+                    "Link forecast older than the one in use",
+                    Some(forecast.forecast_id),
+                ));
+            }
+        }
+        self.forecast = Some(forecast);
+        Ok(())
+    }
+
+    /// Choose the products to downlink from `now_s`
+    ///
+    /// - **ID**: FN-FCS-003
+    /// - **Requirement**: Pre-prioritize stored data by the forecast capacity
+    ///   of the contact (REQ-FN-001, REQ-PF-002).
+    /// - **Inputs**:
+    ///   - `now_s`: Current time, mission seconds.
+    /// - **Outputs**: Files in the order of the policy in use that fit the
+    ///   capacity left in the contact in progress. A product that does not
+    ///   fit is skipped, so a smaller one behind it may still use the
+    ///   capacity. On a degraded contact products below the priority floor
+    ///   are deferred regardless of capacity and policy.
+    /// - **Side Effects**: None; products leave the scheduler through
+    ///   [`DownlinkScheduler::remove`].
+    pub fn plan(&self, now_s: u64) -> ContactPlan {
+        let contact = self.forecast.as_ref().and_then(|f| f.contact_at(now_s));
+        let degraded = contact.is_some_and(|c| c.is_degraded(self.degraded_percent));
+        let mut remaining = contact.map_or(u64::MAX, |c| c.remaining_bytes(now_s));
+
+        let policy = self.stats.policy;
+        let mut ordered = self.products.clone();
+        ordered.sort_unstable_by(|a, b| policy.compare(a, b));
+
+        let mut plan = ContactPlan {
+            contact_id: contact.map(|c| c.contact_id),
+            policy,
+            degraded,
+            files: Vec::new(),
+            planned_bytes: 0,
+            deferred: 0,
+        };
+        for product in &ordered {
+            let size = u64::from(product.size_bytes);
+            let eligible = !degraded || product.priority >= self.degraded_min_priority;
+            if eligible && size <= remaining && plan.files.push(product.file_id).is_ok() {
+                remaining -= size;
+                plan.planned_bytes += size;
+            } else {
+                plan.deferred += 1;
+            }
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact(contact_id: u32, start_s: u64, capacity_bytes: u64) -> ContactForecast {
+        ContactForecast {
+            contact_id,
+            start_s,
+            duration_s: 100,
+            capacity_bytes,
+            nominal_capacity_bytes: 10_000,
+        }
+    }
+
+    fn product(file_id: u32, size_bytes: u32, priority: u8, stored_s: u64) -> StoredProduct {
+        StoredProduct {
+            file_id,
+            size_bytes,
+            priority,
+            science_value: 0,
+            stored_s,
+        }
+    }
+
+    #[test]
+    fn test_forecast_validation_and_round_trip() {
+        let mut forecast = LinkForecast::new(1, 0);
+        forecast.push(contact(1, 1_000, 10_000)).unwrap();
+        forecast.push(contact(2, 2_000, 3_000)).unwrap();
+        assert!(forecast.validate().is_ok());
+        assert_eq!(forecast.contact_at(1_050).unwrap().contact_id, 1);
+        assert_eq!(forecast.contact_at(1_500), None);
+        assert_eq!(forecast.next_contact(1_500).unwrap().contact_id, 2);
+        assert!(forecast.contacts[1].is_degraded(DEFAULT_DEGRADED_PERCENT));
+        assert_eq!(forecast.contacts[0].remaining_bytes(1_075), 2_500);
+
+        let bytes = forecast.to_bytes().unwrap();
+        assert_eq!(LinkForecast::from_bytes(&bytes).unwrap(), forecast);
+
+        // A full forecast still fits one packet
+        let mut full = LinkForecast::new(u32::MAX, u64::MAX / 2);
+        for i in 0..MAX_FORECAST_CONTACTS as u32 {
+            let mut c = contact(
+                u32::MAX - i,
+                u64::MAX / 2 + u64::from(i) * 1_000,
+                u64::MAX / 4,
+            );
+            c.nominal_capacity_bytes = u64::MAX / 4;
+            full.push(c).unwrap();
+        }
+        assert!(full.to_bytes().is_ok());
+
+        let mut overlapping = LinkForecast::new(2, 0);
+        overlapping.push(contact(1, 1_000, 10_000)).unwrap();
+        overlapping.push(contact(2, 1_050, 10_000)).unwrap();
+        assert!(overlapping.validate().is_err());
+    }
+
+    #[test]
+    fn test_degraded_contact_defers_low_priority_products() {
+        let mut scheduler = DownlinkScheduler::<8>::default();
+        scheduler.store(product(1, 800, 50, 10)).unwrap(); // bulk science
+        scheduler.store(product(2, 1_000, 200, 30)).unwrap(); // urgent
+        scheduler.store(product(3, 2_000, 200, 20)).unwrap(); // urgent, older
+        scheduler.store(product(4, 1_500, 150, 5)).unwrap();
+
+        // No forecast: everything goes, highest priority and oldest first
+        let plan = scheduler.plan(1_000);
+        assert_eq!(plan.contact_id, None);
+        assert_eq!(plan.files.as_slice(), &[3, 2, 4, 1]);
+
+        let mut forecast = LinkForecast::new(1, 0);
+        forecast.push(contact(1, 1_000, 10_000)).unwrap();
+        forecast.push(contact(2, 2_000, 4_000)).unwrap();
+        scheduler.accept_forecast(forecast).unwrap();
+
+        // Nominal contact carries all 5.3 kB
+        let plan = scheduler.plan(1_000);
+        assert_eq!((plan.contact_id, plan.degraded), (Some(1), false));
+        assert_eq!(plan.files.len(), 4);
+
+        // Degraded contact: the bulk product is deferred though it would fit
+        let plan = scheduler.plan(2_000);
+        assert!(plan.degraded);
+        assert_eq!(plan.files.as_slice(), &[3, 2]);
+        assert_eq!(plan.planned_bytes, 3_000);
+        assert_eq!(plan.deferred, 2);
+
+        // Older forecasts are refused
+        assert!(scheduler.accept_forecast(LinkForecast::new(0, 0)).is_err());
+        assert_eq!(scheduler.forecast().unwrap().forecast_id, 1);
+        assert_eq!(scheduler.remove(3).unwrap().size_bytes, 2_000);
+        assert_eq!(scheduler.len(), 3);
+    }
+
+    #[test]
+    fn test_policies_order_by_age_and_science_value() {
+        let mut scheduler = DownlinkScheduler::<8>::default();
+        let tagged = |file_id, priority, science_value, stored_s| StoredProduct {
+            science_value,
+            ..product(file_id, 1_000, priority, stored_s)
+        };
+        scheduler.store(tagged(1, 200, 10, 30)).unwrap();
+        scheduler.store(tagged(2, 50, 250, 10)).unwrap();
+        scheduler.store(tagged(3, 150, 90, 50)).unwrap();
+        scheduler.store(tagged(4, 150, 250, 20)).unwrap();
+        assert_eq!(scheduler.policy(), DownlinkPolicy::Priority);
+
+        let order = |scheduler: &DownlinkScheduler<8>| scheduler.plan(1_000).files;
+        assert_eq!(order(&scheduler).as_slice(), &[1, 4, 3, 2]);
+        scheduler.set_policy(DownlinkPolicy::NewestFirst);
+        assert_eq!(order(&scheduler).as_slice(), &[3, 1, 4, 2]);
+        scheduler.set_policy(DownlinkPolicy::OldestFirst);
+        assert_eq!(order(&scheduler).as_slice(), &[2, 4, 1, 3]);
+        // Equal values fall back to downlink priority
+        scheduler.set_policy(DownlinkPolicy::HighestValueFirst);
+        assert_eq!(order(&scheduler).as_slice(), &[4, 2, 3, 1]);
+
+        // The priority floor of a degraded contact holds under every policy
+        let mut forecast = LinkForecast::new(1, 0);
+        forecast.push(contact(1, 2_000, 4_000)).unwrap();
+        scheduler.accept_forecast(forecast).unwrap();
+        let plan = scheduler.plan(2_000);
+        assert_eq!(plan.policy, DownlinkPolicy::HighestValueFirst);
+        assert_eq!(plan.files.as_slice(), &[4, 3, 1]);
+        assert_eq!(tagged(2, 50, 250, 10).age_s(2_000), 1_990);
+    }
+
+    #[test]
+    fn test_policy_stats_round_trip() {
+        let mut scheduler = DownlinkScheduler::<8>::default();
+        scheduler.store(product(1, 1_500, 200, 0)).unwrap();
+        scheduler.store(product(2, 2_500, 100, 0)).unwrap();
+        let plan = scheduler.plan(100);
+        scheduler.record(&plan);
+        scheduler.set_policy(DownlinkPolicy::NewestFirst);
+        let plan = scheduler.plan(200);
+        scheduler.record(&plan);
+        scheduler.record(&plan);
+
+        let stats = *scheduler.stats();
+        assert_eq!(stats.policy, DownlinkPolicy::NewestFirst);
+        assert_eq!(
+            *stats.for_policy(DownlinkPolicy::Priority),
+            PolicyStats {
+                contacts: 1,
+                files: 2,
+                bytes: 4_000
+            }
+        );
+        assert_eq!(stats.for_policy(DownlinkPolicy::NewestFirst).contacts, 2);
+        assert_eq!(stats.for_policy(DownlinkPolicy::OldestFirst).files, 0);
+
+        let mut data = TelemetryData {
+            source: crate::types::ComponentId::new(1),
+            timestamp: 0,
+            measurements: Vec::new(),
+            health_status: crate::types::HealthStatus::Good,
+        };
+        stats.append_measurements(&mut data).unwrap();
+        assert_eq!(data.measurements.len(), 13);
+        for measurement in &data.measurements {
+            assert!(crate::telemetry::dictionary_entry(measurement.measurement_id).is_some());
+        }
+        assert_eq!(
+            DownlinkPolicyStats::from_measurements(&data.measurements),
+            Some(stats)
+        );
+        assert_eq!(DownlinkPolicyStats::from_measurements(&[]), None);
+        assert!(stats
+            .to_string()
+            .starts_with("policy newest first; priority: 1 contacts"));
+
+        let command = SpaceCommand::SetDownlinkPolicy {
+            policy: DownlinkPolicy::OldestFirst,
+        };
+        let mut data = std::vec::Vec::from(command.discriminant().to_be_bytes());
+        data.extend(serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            DownlinkPolicy::from_command_data(&data).unwrap().unwrap(),
+            DownlinkPolicy::OldestFirst
+        );
+        data.truncate(wire::COMMAND_ID_LEN + 2);
+        assert!(DownlinkPolicy::from_command_data(&data).unwrap().is_err());
+    }
+}
@@ -0,0 +1,469 @@
+//! Link layer statistics
+//!
+//! One set of link layer metrics for every source: the simulation channel
+//! model fills a [`LinkStats`] from a study, the satellite communication
+//! manager from the frames it transmits and receives, and the ground station
+//! from its uplink and downlink. Analyses and displays then compare like with
+//! like whichever side of the link the numbers come from.
+//!
+//! A frame counts as received once it passes error correction and its frame
+//! checks, and as an error when it is dropped by them. Retransmissions are counted as frames sent as well. Throughput is
+//! taken over the window from the first frame counted to the latest.
+//!
+//! The satellite downlinks its statistics with the RF housekeeping, from
+//! [`measurement_ids::LINK_STATS_BASE`]:
+//!
+//! | Offset | Field                  | Unit |
+//! |--------|------------------------|------|
+//! | 0x0    | Frames sent            |      |
+//! | 0x1    | Frames received        |      |
+//! | 0x2    | Frame errors           |      |
+//! | 0x3    | Retransmissions        |      |
+//! | 0x4    | Bytes sent             | kB   |
+//! | 0x5    | Bytes received         | kB   |
+//! | 0x6    | Statistics window      | s    |
+//! | 0x7    | SNR estimate, if any   | dB   |
+//!
+//! Byte counters and the window are downlinked to the whole kilobyte and
+//! second, so they outlast a mission in the 32-bit telemetry values;
+//! counters saturate rather than wrap.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-001: Throughput Performance (frame and byte throughput measured
+//!   the same way in simulation and operations)
+//! - REQ-PF-002: Data Transfer Rates (link statistics downlinked for the
+//!   ground to compare with its own)
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+use space_comms_req::req;
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::rf_housekeeping::RF_HOUSEKEEPING_PERIOD_MS;
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    TelemetryData,
+};
+
+/// Weight of a new SNR sample in the smoothed estimate
+const SNR_SMOOTHING: f64 = 0.25;
+
+/// Field of the downlinked link statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkStatsField {
+    /// Frames sent, retransmissions included
+    FramesSent,
+    /// Frames received that passed their checks
+    FramesReceived,
+    /// Frames received and dropped by their checks
+    FrameErrors,
+    /// Frames sent again
+    Retransmissions,
+    /// Bytes sent, kB
+    BytesSent,
+    /// Bytes received in frames that passed their checks, kB
+    BytesReceived,
+    /// Time from the first frame counted to the latest, s
+    Window,
+    /// Smoothed SNR estimate, dB
+    Snr,
+}
+
+impl LinkStatsField {
+    /// Every field in measurement ID order
+    pub const ALL: [LinkStatsField; 8] = [
+        LinkStatsField::FramesSent,
+        LinkStatsField::FramesReceived,
+        LinkStatsField::FrameErrors,
+        LinkStatsField::Retransmissions,
+        LinkStatsField::BytesSent,
+        LinkStatsField::BytesReceived,
+        LinkStatsField::Window,
+        LinkStatsField::Snr,
+    ];
+
+    /// Measurement ID of this field
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::LINK_STATS_BASE + self as u16
+    }
+
+    /// Field a measurement ID belongs to, if it is a link statistics ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.measurement_id() == measurement_id)
+    }
+
+    /// Engineering unit
+    pub const fn unit(self) -> &'static str {
+        match self {
+            LinkStatsField::FramesSent
+            | LinkStatsField::FramesReceived
+            | LinkStatsField::FrameErrors
+            | LinkStatsField::Retransmissions => "",
+            LinkStatsField::BytesSent | LinkStatsField::BytesReceived => "kB",
+            LinkStatsField::Window => "s",
+            LinkStatsField::Snr => "dB",
+        }
+    }
+
+    /// Dictionary entries covering the statistics: frame counters, byte
+    /// counters, window and SNR
+    pub const fn dictionary_entries() -> [DictionaryEntry; 4] {
+        [
+            Self::range_entry(
+                LinkStatsField::FramesSent,
+                LinkStatsField::Retransmissions,
+                "Link frame counters",
+            ),
+            Self::range_entry(
+                LinkStatsField::BytesSent,
+                LinkStatsField::BytesReceived,
+                "Link byte counters",
+            ),
+            Self::range_entry(
+                LinkStatsField::Window,
+                LinkStatsField::Window,
+                "Link statistics window",
+            ),
+            Self::range_entry(
+                LinkStatsField::Snr,
+                LinkStatsField::Snr,
+                "Link SNR estimate",
+            ),
+        ]
+    }
+
+    /// Dictionary entry for the fields `first` to `last`, in `first`'s unit
+    const fn range_entry(first: Self, last: Self, name: &'static str) -> DictionaryEntry {
+        DictionaryEntry {
+            first_id: first.measurement_id(),
+            last_id: last.measurement_id(),
+            name,
+            unit: first.unit(),
+            period_ms: RF_HOUSEKEEPING_PERIOD_MS,
+        }
+    }
+}
+
+/// Link layer statistics of one end of a link
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LinkStats {
+    /// Frames sent, retransmissions included
+    pub frames_sent: u64,
+    /// Frames received that passed their checks
+    pub frames_received: u64,
+    /// Frames received and dropped by their checks
+    pub frame_errors: u64,
+    /// Frames sent again
+    pub retransmissions: u64,
+    /// Bytes sent
+    pub bytes_sent: u64,
+    /// Bytes received in frames that passed their checks
+    pub bytes_received: u64,
+    /// Time from the first frame counted to the latest, ms
+    pub window_ms: u64,
+    /// Smoothed SNR estimate, dB; `None` where the source measures none
+    pub snr_db: Option<f64>,
+    /// Time of the first frame counted on the source's clock, ms; `None`
+    /// before any frame, or for statistics not counted frame by frame
+    pub started_ms: Option<u64>,
+}
+
+impl LinkStats {
+    /// Statistics with nothing counted
+    pub const fn new() -> Self {
+        Self {
+            frames_sent: 0,
+            frames_received: 0,
+            frame_errors: 0,
+            retransmissions: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            window_ms: 0,
+            snr_db: None,
+            started_ms: None,
+        }
+    }
+
+    /// Extend the window to `now_ms`
+    fn observe(&mut self, now_ms: u64) {
+        let started = *self.started_ms.get_or_insert(now_ms);
+        self.window_ms = self.window_ms.max(now_ms.saturating_sub(started));
+    }
+
+    /// Count a frame sent
+    ///
+    /// - **ID**: FN-LST-001
+    /// - **Requirement**: Count frames and bytes each way, and frames
+    ///   dropped by their checks, identically at every source (REQ-NF-001).
+    /// - **Inputs**: Frame length as sent, coding included; time on the
+    ///   source's clock, ms.
+    /// - **Side Effects**: Extends the window to `now_ms`.
+    #[req("REQ-NF-001")]
+    pub fn frame_sent(&mut self, bytes: usize, now_ms: u64) {
+        self.frames_sent += 1;
+        self.bytes_sent += bytes as u64;
+        self.observe(now_ms);
+    }
+
+    /// Count a frame received that passed its checks
+    pub fn frame_received(&mut self, bytes: usize, now_ms: u64) {
+        self.frames_received += 1;
+        self.bytes_received += bytes as u64;
+        self.observe(now_ms);
+    }
+
+    /// Count a frame received and dropped by its checks
+    pub fn frame_error(&mut self, now_ms: u64) {
+        self.frame_errors += 1;
+        self.observe(now_ms);
+    }
+
+    /// Count a frame sent again; the frame itself is counted by
+    /// [`Self::frame_sent`]
+    pub fn retransmission(&mut self) {
+        self.retransmissions += 1;
+    }
+
+    /// Fold an SNR sample into the smoothed estimate
+    ///
+    /// The first sample is taken as it is; NaN samples are ignored.
+    pub fn snr_measured(&mut self, snr_db: f64) {
+        if snr_db.is_nan() {
+            return;
+        }
+        self.snr_db = Some(match self.snr_db {
+            Some(estimate) => estimate + SNR_SMOOTHING * (snr_db - estimate),
+            None => snr_db,
+        });
+    }
+
+    /// Fraction of received frames dropped by their checks
+    pub fn frame_error_rate(&self) -> f64 {
+        let received = self.frames_received + self.frame_errors;
+        if received == 0 {
+            0.0
+        } else {
+            self.frame_errors as f64 / received as f64
+        }
+    }
+
+    /// Bits sent per second over the window; 0 for an empty window
+    pub fn tx_throughput_bps(&self) -> f64 {
+        self.throughput_bps(self.bytes_sent)
+    }
+
+    /// Bits received per second over the window; 0 for an empty window
+    pub fn rx_throughput_bps(&self) -> f64 {
+        self.throughput_bps(self.bytes_received)
+    }
+
+    fn throughput_bps(&self, bytes: u64) -> f64 {
+        if self.window_ms == 0 {
+            0.0
+        } else {
+            bytes as f64 * 8.0 * 1_000.0 / self.window_ms as f64
+        }
+    }
+
+    /// Append one measurement per field; the SNR only if there is an
+    /// estimate
+    ///
+    /// - **ID**: FN-LST-002
+    /// - **Requirement**: Downlink the satellite's link statistics in the
+    ///   form the ground keeps its own (REQ-PF-002).
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold every
+    ///   field; measurements pushed before the overflow remain.
+    #[req("REQ-PF-002")]
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for field in LinkStatsField::ALL {
+            let value = match field {
+                LinkStatsField::FramesSent => self.frames_sent,
+                LinkStatsField::FramesReceived => self.frames_received,
+                LinkStatsField::FrameErrors => self.frame_errors,
+                LinkStatsField::Retransmissions => self.retransmissions,
+                LinkStatsField::BytesSent => self.bytes_sent / 1_000,
+                LinkStatsField::BytesReceived => self.bytes_received / 1_000,
+                LinkStatsField::Window => self.window_ms / 1_000,
+                LinkStatsField::Snr => continue,
+            };
+            let value = value.min(i32::MAX as u64) as i64;
+            push(data, field, MeasurementValue::Integer(value))?;
+        }
+        if let Some(snr_db) = self.snr_db {
+            push(data, LinkStatsField::Snr, MeasurementValue::Float(snr_db))?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the statistics from downlinked measurements
+    ///
+    /// Returns `None` unless every counter and the window are present; a
+    /// missing SNR reads as no estimate. Byte counts and the window come
+    /// back to the kilobyte and second they were downlinked to.
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let find = |field: LinkStatsField| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == field.measurement_id())
+        };
+        let counter = |field: LinkStatsField| match find(field)?.value {
+            MeasurementValue::Integer(v) => u64::try_from(v).ok(),
+            _ => None,
+        };
+
+        Some(Self {
+            frames_sent: counter(LinkStatsField::FramesSent)?,
+            frames_received: counter(LinkStatsField::FramesReceived)?,
+            frame_errors: counter(LinkStatsField::FrameErrors)?,
+            retransmissions: counter(LinkStatsField::Retransmissions)?,
+            bytes_sent: counter(LinkStatsField::BytesSent)? * 1_000,
+            bytes_received: counter(LinkStatsField::BytesReceived)? * 1_000,
+            window_ms: counter(LinkStatsField::Window)? * 1_000,
+            snr_db: find(LinkStatsField::Snr).and_then(|m| match m.value {
+                MeasurementValue::Float(v) => Some(v),
+                _ => None,
+            }),
+            started_ms: None,
+        })
+    }
+}
+
+/// Push one field's measurement
+fn push(data: &mut TelemetryData, field: LinkStatsField, value: MeasurementValue) -> Result<()> {
+    data.measurements
+        .push(Measurement {
+            measurement_id: field.measurement_id(),
+            value,
+            unit: field.unit(),
+            quality: MeasurementQuality::Good,
+        })
+        .map_err(|_| {
+            SpaceCommError::memory_error(
+                MemoryErrorType::BufferOverflow,
+                Some(data.measurements.len()),
+            )
+        })
+}
+
+/// Link statistics carried by a frame, if they are complete
+pub fn decode_link_stats(data: &TelemetryData) -> Option<LinkStats> {
+    LinkStats::from_measurements(&data.measurements)
+}
+
+impl fmt::Display for LinkStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} frames sent ({} retransmitted), {} received, {} errors ({:.1}%), \
+             TX {:.1} kbit/s, RX {:.1} kbit/s over {:.1} s",
+            self.frames_sent,
+            self.retransmissions,
+            self.frames_received,
+            self.frame_errors,
+            self.frame_error_rate() * 100.0,
+            self.tx_throughput_bps() / 1_000.0,
+            self.rx_throughput_bps() / 1_000.0,
+            self.window_ms as f64 / 1_000.0
+        )?;
+        match self.snr_db {
+            Some(snr_db) => write!(f, ", SNR {:.1} dB", snr_db),
+            None => write!(f, ", no SNR estimate"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::dictionary_entry;
+    use crate::types::{ComponentId, HealthStatus};
+
+    #[test]
+    fn test_counts_and_throughput() {
+        let mut stats = LinkStats::new();
+        assert_eq!(stats.tx_throughput_bps(), 0.0);
+        assert_eq!(stats.frame_error_rate(), 0.0);
+
+        stats.frame_sent(1_000, 10_000);
+        stats.frame_sent(1_000, 10_500);
+        stats.retransmission();
+        stats.frame_received(250, 11_000);
+        stats.frame_error(12_000);
+
+        assert_eq!(stats.started_ms, Some(10_000));
+        assert_eq!(stats.window_ms, 2_000);
+        assert_eq!(stats.frames_sent, 2);
+        assert_eq!(stats.retransmissions, 1);
+        assert_eq!(stats.tx_throughput_bps(), 8_000.0);
+        assert_eq!(stats.rx_throughput_bps(), 1_000.0);
+        assert_eq!(stats.frame_error_rate(), 0.5);
+
+        stats.snr_measured(10.0);
+        stats.snr_measured(f64::NAN);
+        stats.snr_measured(14.0);
+        assert_eq!(stats.snr_db, Some(11.0));
+        assert_eq!(
+            stats.to_string(),
+            "2 frames sent (1 retransmitted), 1 received, 1 errors (50.0%), \
+             TX 8.0 kbit/s, RX 1.0 kbit/s over 2.0 s, SNR 11.0 dB"
+        );
+    }
+
+    #[test]
+    fn test_link_stats_round_trip() {
+        let mut stats = LinkStats::new();
+        stats.frame_sent(11_150, 0);
+        stats.frame_sent(1_115, 1_000);
+        stats.frame_received(64, 3_400);
+
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        stats.append_measurements(&mut data).unwrap();
+        assert_eq!(data.measurements.len(), 7, "no SNR estimate to send");
+        let payload = data.to_payload().unwrap();
+        let mut frame =
+            TelemetryData::from_payload(ComponentId::new(1), HealthStatus::Good, &payload).unwrap();
+        let decoded = decode_link_stats(&frame).unwrap();
+        assert_eq!(decoded.frames_sent, 2);
+        assert_eq!(decoded.frames_received, 1);
+        assert_eq!(decoded.bytes_sent, 12_000);
+        assert_eq!(decoded.bytes_received, 0);
+        assert_eq!(decoded.window_ms, 3_000);
+        assert_eq!(decoded.snr_db, None);
+
+        stats.frames_sent = u64::MAX;
+        frame.measurements.clear();
+        stats.append_measurements(&mut frame).unwrap();
+        let payload = frame.to_payload().unwrap();
+        let saturated =
+            TelemetryData::from_payload(ComponentId::new(1), HealthStatus::Good, &payload).unwrap();
+        assert_eq!(
+            decode_link_stats(&saturated).unwrap().frames_sent,
+            i32::MAX as u64
+        );
+
+        stats.snr_measured(12.5);
+        frame.measurements.clear();
+        stats.append_measurements(&mut frame).unwrap();
+        assert_eq!(decode_link_stats(&frame).unwrap().snr_db, Some(12.5));
+
+        for field in LinkStatsField::ALL {
+            let entry = dictionary_entry(field.measurement_id()).unwrap();
+            assert_eq!(entry.unit, field.unit());
+            assert_eq!(
+                LinkStatsField::from_measurement_id(field.measurement_id()),
+                Some(field)
+            );
+        }
+
+        frame.measurements.remove(0);
+        assert!(decode_link_stats(&frame).is_none());
+    }
+}
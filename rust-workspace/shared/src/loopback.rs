@@ -0,0 +1,336 @@
+//! Command path loopback self-test
+//!
+//! A [`SpaceCommand::LoopbackTest`] carries a test ID, the band to answer on
+//! and up to [`MAX_LOOPBACK_PAYLOAD`] bytes of payload. The satellite echoes
+//! the payload back as a [`LoopbackEcho`] on [`LOOPBACK_APID`], over the
+//! requested band, stamped with the onboard times the command was received
+//! and the echo sent. The ground knows when it sent the command and received
+//! the echo, and turns the four timestamps into a [`LoopbackResult`]: round
+//! trip, onboard turnaround and, through the offset of the onboard clock,
+//! uplink and downlink delays and their asymmetry.
+//!
+//! Echoes use a fixed layout in the [`crate::wire`] format:
+//!
+//! | Bytes  | Echo                 |
+//! |--------|----------------------|
+//! | 0..2   | `test_id`            |
+//! | 2      | band code            |
+//! | 3..11  | `received_ms`        |
+//! | 11..19 | `transmitted_ms`     |
+//! | 19..   | payload              |
+//!
+//! Band codes run from 0 (UHF) to 4 (Ka), in [`RF_BANDS`] order.
+//!
+//! [`SpaceCommand::LoopbackTest`]: crate::commands::SpaceCommand::LoopbackTest
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (end-to-end check of a chosen band)
+//! - REQ-PF-001: Command Response Time (measured uplink and downlink delays)
+//! - REQ-IF-002: CCSDS Compliance (echo carried in a Space Packet)
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::rf_housekeeping::RF_BANDS;
+use crate::types::BandType;
+use crate::wire::{self, WireReader, WireWriter};
+
+/// APID used to downlink loopback echoes
+pub const LOOPBACK_APID: u16 = 0x018;
+
+/// Command ID of [`SpaceCommand::LoopbackTest`]
+pub const LOOPBACK_TEST_COMMAND_ID: u32 = 0x0037;
+
+/// Longest payload one loopback test may carry, bytes
+pub const MAX_LOOPBACK_PAYLOAD: usize = 64;
+
+/// Echo header length, bytes
+const ECHO_HEADER_LEN: usize = 19;
+
+/// Loopback test requested by [`SpaceCommand::LoopbackTest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopbackRequest {
+    /// Ground-assigned ID matching the echo to the test
+    pub test_id: u16,
+    /// Band the echo is sent on
+    pub band: BandType,
+    /// Bytes to echo back
+    pub payload: Vec<u8, MAX_LOOPBACK_PAYLOAD>,
+}
+
+impl LoopbackRequest {
+    /// Request echoing `payload` on `band`
+    ///
+    /// Returns an error for a payload longer than [`MAX_LOOPBACK_PAYLOAD`].
+    pub fn new(test_id: u16, band: BandType, payload: &[u8]) -> Result<Self> {
+        let payload = Vec::from_slice(payload).map_err(|_| SpaceCommError::ConfigurationError {
+            parameter: "payload",
+            value: "too long",
+            reason: "Loopback payload is at most 64 bytes",
+        })?;
+        Ok(Self {
+            test_id,
+            band,
+            payload,
+        })
+    }
+
+    /// Request carried by `command`, or `None` for any other command
+    pub fn from_command(command: &SpaceCommand) -> Option<Self> {
+        match command {
+            SpaceCommand::LoopbackTest {
+                test_id,
+                band,
+                payload,
+            } => Some(Self {
+                test_id: *test_id,
+                band: *band,
+                payload: payload.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Request carried by a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a loopback
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != LOOPBACK_TEST_COMMAND_ID {
+            return None;
+        }
+        let request = serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..])
+            .ok()
+            .and_then(|command| Self::from_command(&command))
+            .ok_or(SpaceCommError::invalid_packet(
+                "Malformed loopback command",
+                Some(command_id),
+            ));
+        Some(request)
+    }
+
+    /// Command carrying the request
+    pub fn to_command(&self) -> SpaceCommand {
+        SpaceCommand::LoopbackTest {
+            test_id: self.test_id,
+            band: self.band,
+            payload: self.payload.clone(),
+        }
+    }
+
+    /// Echo of the request, received and sent at the given onboard times
+    pub fn echo(&self, received_ms: u64, transmitted_ms: u64) -> LoopbackEcho {
+        LoopbackEcho {
+            test_id: self.test_id,
+            band: self.band,
+            received_ms,
+            transmitted_ms,
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+/// Satellite's answer to a loopback test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopbackEcho {
+    /// Test this echo answers
+    pub test_id: u16,
+    /// Band the echo was sent on
+    pub band: BandType,
+    /// Onboard time the command was received, milliseconds
+    pub received_ms: u64,
+    /// Onboard time the echo was sent, milliseconds
+    pub transmitted_ms: u64,
+    /// Payload as received
+    pub payload: Vec<u8, MAX_LOOPBACK_PAYLOAD>,
+}
+
+impl LoopbackEcho {
+    /// Serialize the echo in the fixed downlink layout
+    pub fn to_bytes(&self) -> Result<Vec<u8, { ECHO_HEADER_LEN + MAX_LOOPBACK_PAYLOAD }>> {
+        let mut bytes = WireWriter::new();
+        bytes
+            .put(self.test_id)?
+            .put(band_code(self.band))?
+            .put(self.received_ms)?
+            .put(self.transmitted_ms)?
+            .put_bytes(&self.payload)?;
+        Ok(bytes.finish())
+    }
+
+    /// Parse an echo from the packet data field
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < ECHO_HEADER_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "Loopback echo too short",
+                None,
+            ));
+        }
+        let mut reader = WireReader::new(bytes);
+        let test_id = reader.get()?;
+        let code = reader.get::<u8>()?;
+        let band = RF_BANDS
+            .get(usize::from(code))
+            .copied()
+            .ok_or(SpaceCommError::invalid_packet(
+                "Loopback echo on unknown band",
+                Some(u32::from(code)),
+            ))?;
+        let (received_ms, transmitted_ms) = (reader.get()?, reader.get()?);
+        let payload = Vec::from_slice(reader.rest())
+            .map_err(|_| SpaceCommError::invalid_packet("Loopback echo too long", None))?;
+        Ok(Self {
+            test_id,
+            band,
+            received_ms,
+            transmitted_ms,
+            payload,
+        })
+    }
+
+    /// Wrap the echo in a telemetry packet on [`LOOPBACK_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            LOOPBACK_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes()?,
+            None,
+        )
+    }
+}
+
+/// Delays measured by one loopback test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoopbackResult {
+    /// Test measured
+    pub test_id: u16,
+    /// Band the echo came back on
+    pub band: BandType,
+    /// Command sent to echo received, less the onboard turnaround, ms
+    pub round_trip_ms: u64,
+    /// Command received to echo sent onboard, ms
+    pub turnaround_ms: u64,
+    /// Command sent to command received, ms
+    pub uplink_ms: i64,
+    /// Echo sent to echo received, ms
+    pub downlink_ms: i64,
+    /// Whether the echoed payload matches the payload sent
+    pub payload_intact: bool,
+}
+
+impl LoopbackResult {
+    /// Measure a test from its echo
+    ///
+    /// - **ID**: FN-LBK-001
+    /// - **Requirement**: Measure the round trip of the command path and
+    ///   split it into uplink and downlink delays.
+    /// - **Inputs**:
+    ///   - `echo`: Echo received.
+    ///   - `payload`: Payload the test was sent with.
+    ///   - `sent_ms` / `received_ms`: Ground times the command was sent and
+    ///     the echo received, milliseconds.
+    ///   - `clock_offset_ms`: Onboard clock minus ground clock, milliseconds.
+    /// - **Outputs**: Round trip and turnaround, which need no clock
+    ///   correlation, and one-way delays, which are only as good as
+    ///   `clock_offset_ms`.
+    /// - **Failure Modes**: None; clocks running backwards give a zero round
+    ///   trip and negative one-way delays rather than an error.
+    pub fn measure(
+        echo: &LoopbackEcho,
+        payload: &[u8],
+        sent_ms: u64,
+        received_ms: u64,
+        clock_offset_ms: i64,
+    ) -> Self {
+        let turnaround_ms = echo.transmitted_ms.saturating_sub(echo.received_ms);
+        let onboard_received = echo.received_ms as i64 - clock_offset_ms;
+        let onboard_transmitted = echo.transmitted_ms as i64 - clock_offset_ms;
+        Self {
+            test_id: echo.test_id,
+            band: echo.band,
+            round_trip_ms: received_ms
+                .saturating_sub(sent_ms)
+                .saturating_sub(turnaround_ms),
+            turnaround_ms,
+            uplink_ms: onboard_received - sent_ms as i64,
+            downlink_ms: received_ms as i64 - onboard_transmitted,
+            payload_intact: echo.payload.as_slice() == payload,
+        }
+    }
+
+    /// Uplink delay minus downlink delay, milliseconds
+    pub fn asymmetry_ms(&self) -> i64 {
+        self.uplink_ms - self.downlink_ms
+    }
+}
+
+/// Wire code of a band: its index in [`RF_BANDS`]
+fn band_code(band: BandType) -> u8 {
+    RF_BANDS
+        .iter()
+        .position(|&candidate| candidate == band)
+        .unwrap_or(0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> LoopbackRequest {
+        LoopbackRequest {
+            test_id: 7,
+            band: BandType::XBand,
+            payload: Vec::from_slice(b"AOS link check").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_echo_roundtrip() {
+        let echo = request().echo(1_000, 1_004);
+        let packet = echo.to_packet(3).unwrap();
+        assert_eq!(packet.header.apid, LOOPBACK_APID);
+        assert_eq!(LoopbackEcho::from_bytes(&packet.data).unwrap(), echo);
+
+        let mut bytes = echo.to_bytes().unwrap();
+        bytes[2] = 5;
+        assert!(LoopbackEcho::from_bytes(&bytes).is_err());
+        assert!(LoopbackEcho::from_bytes(&bytes[..ECHO_HEADER_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_request_from_command_data() {
+        let command = request().to_command();
+        assert_eq!(command.discriminant(), LOOPBACK_TEST_COMMAND_ID);
+
+        let mut data = std::vec::Vec::from(LOOPBACK_TEST_COMMAND_ID.to_be_bytes());
+        data.extend(serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            LoopbackRequest::from_command_data(&data).unwrap().unwrap(),
+            request()
+        );
+        data.truncate(10);
+        assert!(LoopbackRequest::from_command_data(&data).unwrap().is_err());
+        assert!(LoopbackRequest::from_command_data(&[0, 0, 0, 0x35, 2]).is_none());
+    }
+
+    #[test]
+    fn test_measure_splits_delays() {
+        // Onboard clock 500 ms ahead; 120 ms up, 5 ms onboard, 80 ms down
+        let echo = request().echo(10_620, 10_625);
+        let result = LoopbackResult::measure(&echo, b"AOS link check", 10_000, 10_205, 500);
+        assert_eq!(result.round_trip_ms, 200);
+        assert_eq!(result.turnaround_ms, 5);
+        assert_eq!((result.uplink_ms, result.downlink_ms), (120, 80));
+        assert_eq!(result.asymmetry_ms(), 40);
+        assert!(result.payload_intact);
+
+        let corrupted = LoopbackResult::measure(&echo, b"AOS link chock", 10_000, 10_205, 500);
+        assert!(!corrupted.payload_intact);
+    }
+}
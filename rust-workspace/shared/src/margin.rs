@@ -0,0 +1,216 @@
+//! Link and power margin policy
+//!
+//! A link that closes with no margin fails on the first fade, and a
+//! transmitter run at full power has nothing left for a pass at low
+//! elevation. A [`MarginPolicy`] holds the margins a mission requires: the
+//! SNR a link must keep above its closure threshold, and the fraction of
+//! transmitter power held in reserve. Every component that judges a link
+//! applies the same policy, so the ground station warns operators at the same
+//! point where the simulation stops calling a link a success.
+//!
+//! The policy is serialized with the same field names as the frequency band
+//! simulation's `MarginPolicy`, so one mission policy file configures both.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (link closure with margin)
+//! - REQ-NF-004: Power management per transmit direction (power reserve)
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SpaceCommError};
+
+/// SNR at which a link closes with no margin, dB
+pub const LINK_CLOSURE_SNR_DB: f64 = 10.0;
+
+/// Default SNR required above closure, dB
+pub const DEFAULT_LINK_MARGIN_DB: f64 = 3.0;
+
+/// Default fraction of transmitter power held in reserve
+pub const DEFAULT_POWER_MARGIN: f64 = 0.2;
+
+/// Margin a shortfall was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarginKind {
+    /// SNR above the closure threshold
+    Link,
+    /// Transmitter power held in reserve
+    Power,
+}
+
+/// A margin below the policy requirement
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MarginShortfall {
+    /// Margin found short
+    pub kind: MarginKind,
+    /// Margin measured: dB for link margin, 0-1 for power margin
+    pub margin: f64,
+    /// Margin the policy requires, in the same unit
+    pub required: f64,
+}
+
+impl fmt::Display for MarginShortfall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            MarginKind::Link => write!(
+                f,
+                "link margin {:.1} dB below required {:.1} dB",
+                self.margin, self.required
+            ),
+            MarginKind::Power => write!(
+                f,
+                "power margin {:.0}% below required {:.0}%",
+                self.margin * 100.0,
+                self.required * 100.0
+            ),
+        }
+    }
+}
+
+/// Margins a mission requires of its links
+///
+/// - **ID**: MOD-MAR-001
+/// - **Requirement**: One configurable set of required margins applied
+///   wherever a link is judged (REQ-FN-007, REQ-NF-004).
+/// - **Constraints**: No heap allocation; usable in no_std builds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarginPolicy {
+    /// SNR at which the link closes with no margin, dB
+    pub closure_snr_db: f64,
+    /// SNR required above `closure_snr_db`, dB
+    pub link_margin_db: f64,
+    /// Fraction of transmitter power held in reserve, 0-1
+    pub power_margin: f64,
+}
+
+impl Default for MarginPolicy {
+    /// 3 dB link margin and 20% power margin
+    fn default() -> Self {
+        Self {
+            closure_snr_db: LINK_CLOSURE_SNR_DB,
+            link_margin_db: DEFAULT_LINK_MARGIN_DB,
+            power_margin: DEFAULT_POWER_MARGIN,
+        }
+    }
+}
+
+impl MarginPolicy {
+    /// Check that the policy is usable
+    ///
+    /// - **ID**: FN-MAR-001
+    /// - **Requirement**: Reject a policy that cannot be met before it is
+    ///   applied.
+    /// - **Outputs**: `Ok(())` if usable.
+    /// - **Failure Modes**: A negative or non-finite link margin, or a power
+    ///   margin outside 0-1 → `Err(ConfigurationError)`.
+    pub fn validate(&self) -> Result<()> {
+        if !self.closure_snr_db.is_finite() {
+            return Err(margin_error("closure_snr_db", "closure SNR must be finite"));
+        }
+        if !(self.link_margin_db.is_finite() && self.link_margin_db >= 0.0) {
+            return Err(margin_error(
+                "link_margin_db",
+                "link margin must be zero or more",
+            ));
+        }
+        if !(0.0..1.0).contains(&self.power_margin) {
+            return Err(margin_error(
+                "power_margin",
+                "power margin must be at least 0 and below 1",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Lowest SNR that meets the link margin, dB
+    pub fn required_snr_db(&self) -> f64 {
+        self.closure_snr_db + self.link_margin_db
+    }
+
+    /// Check a measured SNR against the link margin
+    ///
+    /// - **ID**: FN-MAR-002
+    /// - **Requirement**: Flag a link running below the required margin.
+    /// - **Inputs**:
+    ///   - `snr_db`: Measured or predicted SNR, dB.
+    /// - **Outputs**: The shortfall, or `None` if the margin is met.
+    pub fn check_link(&self, snr_db: f64) -> Option<MarginShortfall> {
+        let margin = snr_db - self.closure_snr_db;
+        (margin < self.link_margin_db).then_some(MarginShortfall {
+            kind: MarginKind::Link,
+            margin,
+            required: self.link_margin_db,
+        })
+    }
+
+    /// Check a transmitter power level against the power margin
+    ///
+    /// - **ID**: FN-MAR-003
+    /// - **Requirement**: Flag a transmitter run with less reserve than
+    ///   required.
+    /// - **Inputs**:
+    ///   - `power_level`: Transmitter power level, 0-100%.
+    /// - **Outputs**: The shortfall, or `None` if the margin is met.
+    pub fn check_power(&self, power_level: u8) -> Option<MarginShortfall> {
+        let margin = f64::from(100 - power_level.min(100)) / 100.0;
+        (margin < self.power_margin).then_some(MarginShortfall {
+            kind: MarginKind::Power,
+            margin,
+            required: self.power_margin,
+        })
+    }
+}
+
+/// Configuration error about a margin setting
+const fn margin_error(parameter: &'static str, reason: &'static str) -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter,
+        value: "<margin policy>",
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_and_power_margins() {
+        let policy = MarginPolicy::default();
+        assert!(policy.validate().is_ok());
+        assert!((policy.required_snr_db() - 13.0).abs() < 1e-9);
+
+        assert_eq!(policy.check_link(13.0), None);
+        let short = policy.check_link(11.5).unwrap();
+        assert_eq!(short.kind, MarginKind::Link);
+        assert!((short.margin - 1.5).abs() < 1e-9);
+        assert_eq!(
+            short.to_string(),
+            "link margin 1.5 dB below required 3.0 dB"
+        );
+
+        assert_eq!(policy.check_power(80), None);
+        let short = policy.check_power(95).unwrap();
+        assert_eq!(short.kind, MarginKind::Power);
+        assert_eq!(short.to_string(), "power margin 5% below required 20%");
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let policy = |link_margin_db, power_margin| MarginPolicy {
+            link_margin_db,
+            power_margin,
+            ..MarginPolicy::default()
+        };
+        assert!(policy(0.0, 0.0).validate().is_ok());
+        assert!(policy(-1.0, 0.2).validate().is_err());
+        assert!(policy(f64::NAN, 0.2).validate().is_err());
+        assert!(policy(3.0, 1.0).validate().is_err());
+
+        // Fields left out of a policy file keep their defaults
+        let parsed: MarginPolicy = serde_json::from_str(r#"{"link_margin_db": 6.0}"#).unwrap();
+        assert_eq!(parsed, policy(6.0, DEFAULT_POWER_MARGIN));
+    }
+}
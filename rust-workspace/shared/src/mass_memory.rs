@@ -0,0 +1,791 @@
+//! Mass memory file system
+//!
+//! The onboard mass memory holds what the spacecraft stores for later
+//! downlink. Rather than each user keeping its own idea of what is stored,
+//! [`MassMemory`] gives it one small file system: named files in one
+//! directory per [`DataType`], each directory with a byte quota and an
+//! optional retention period. Files are written by
+//! [`SpaceCommand::StoreData`] and by any onboard function that records a
+//! product, such as the recorder downlink; all of them see the same files,
+//! quotas and file IDs.
+//!
+//! The ground manages the file system with three commands:
+//!
+//! - [`SpaceCommand::ListFiles`] downlinks a [`FileListing`] of one directory
+//!   on [`FILE_LISTING_APID`]: its usage, quota and retention, and its files.
+//! - [`SpaceCommand::DeleteFile`] deletes one file by ID.
+//! - [`SpaceCommand::SetRetention`] sets how long a directory keeps its
+//!   files; [`MassMemory::expire`] deletes files past their retention.
+//!
+//! A write that would take a directory over its quota is refused, so a burst
+//! of one data type can never crowd out the others.
+//!
+//! [`SpaceCommand::StoreData`]: crate::commands::SpaceCommand::StoreData
+//! [`SpaceCommand::ListFiles`]: crate::commands::SpaceCommand::ListFiles
+//! [`SpaceCommand::DeleteFile`]: crate::commands::SpaceCommand::DeleteFile
+//! [`SpaceCommand::SetRetention`]: crate::commands::SpaceCommand::SetRetention
+//!
+//! # Requirements Traceability
+//! - REQ-FN-005: Medium Priority Commands (data storage and management)
+//! - REQ-NF-002: Memory Constraints (per-data-type quotas and retention)
+
+use core::fmt::{self, Write as _};
+
+use heapless::{String, Vec};
+use serde::{Deserialize, Serialize};
+use space_comms_req::req;
+
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::{DataType, SpaceCommand, StorageLocation};
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::wire;
+
+/// APID used to downlink directory listings
+pub const FILE_LISTING_APID: u16 = 0x01A;
+
+/// Command ID of [`SpaceCommand::ListFiles`]
+pub const LIST_FILES_COMMAND_ID: u32 = 0x0038;
+
+/// Command ID of [`SpaceCommand::DeleteFile`]
+pub const DELETE_FILE_COMMAND_ID: u32 = 0x0039;
+
+/// Command ID of [`SpaceCommand::SetRetention`]
+pub const SET_RETENTION_COMMAND_ID: u32 = 0x003A;
+
+/// Command ID of [`SpaceCommand::StoreData`]
+pub const STORE_DATA_COMMAND_ID: u32 = 0x0034;
+
+/// Longest file name, bytes
+pub const MAX_FILE_NAME_LEN: usize = 24;
+
+/// Files described by one listing; a fuller directory is listed truncated
+pub const MAX_LISTING_FILES: usize = 16;
+
+/// Largest serialized listing, bytes
+pub const MAX_LISTING_BYTES: usize = 2048;
+
+/// Total mass memory capacity, bytes
+pub const MASS_MEMORY_CAPACITY_BYTES: u32 = 256 * 1024 * 1024;
+
+/// Every data type, in directory order
+pub const DATA_TYPES: [DataType; 6] = [
+    DataType::Telemetry,
+    DataType::Science,
+    DataType::Images,
+    DataType::Logs,
+    DataType::Configuration,
+    DataType::Diagnostic,
+];
+
+/// Default quota of each directory, bytes, in [`DATA_TYPES`] order; together
+/// they fill the mass memory
+pub const DEFAULT_QUOTAS: [u32; 6] = [
+    32 * 1024 * 1024, // Telemetry
+    96 * 1024 * 1024, // Science
+    96 * 1024 * 1024, // Images
+    16 * 1024 * 1024, // Logs
+    4 * 1024 * 1024,  // Configuration
+    12 * 1024 * 1024, // Diagnostic
+];
+
+/// Name of the directory holding files of `data_type`
+pub const fn directory_name(data_type: DataType) -> &'static str {
+    match data_type {
+        DataType::Telemetry => "telemetry",
+        DataType::Science => "science",
+        DataType::Images => "images",
+        DataType::Logs => "logs",
+        DataType::Configuration => "configuration",
+        DataType::Diagnostic => "diagnostic",
+    }
+}
+
+/// Usage and limits of one directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Directory {
+    /// Data type the directory holds
+    pub data_type: DataType,
+    /// Bytes the directory may hold
+    pub quota_bytes: u32,
+    /// Bytes held
+    pub used_bytes: u32,
+    /// Files held
+    pub file_count: u16,
+    /// Seconds a file is kept after it is written; `None` keeps files until
+    /// they are deleted
+    pub retention_s: Option<u32>,
+}
+
+impl Directory {
+    /// Bytes still free under the quota
+    pub const fn free_bytes(&self) -> u32 {
+        self.quota_bytes.saturating_sub(self.used_bytes)
+    }
+}
+
+/// One file in the mass memory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileRecord {
+    /// File identifier, unique across directories and never reused
+    pub file_id: u32,
+    /// Directory the file is in
+    pub data_type: DataType,
+    /// Name, unique within the directory
+    pub name: String<MAX_FILE_NAME_LEN>,
+    /// File size in bytes
+    pub size_bytes: u32,
+    /// Storage the file was written to
+    pub location: StorageLocation,
+    /// Science value the file was tagged with; higher is worth more
+    pub science_value: u8,
+    /// Mission time the file was written, seconds
+    pub created_s: u64,
+}
+
+impl FileRecord {
+    /// Time since the file was written at `now_s`, seconds
+    pub const fn age_s(&self, now_s: u64) -> u64 {
+        now_s.saturating_sub(self.created_s)
+    }
+
+    /// Whether the file is past a retention of `retention_s` at `now_s`
+    pub const fn is_expired(&self, retention_s: u32, now_s: u64) -> bool {
+        self.age_s(now_s) >= retention_s as u64
+    }
+}
+
+impl fmt::Display for FileRecord {
+    /// Path of the file, `/<directory>/<name>`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/{}", directory_name(self.data_type), self.name)
+    }
+}
+
+/// File system over the mass memory, holding up to `N` files
+#[derive(Debug, Clone)]
+pub struct MassMemory<const N: usize> {
+    files: Vec<FileRecord, N>,
+    directories: [Directory; 6],
+    next_file_id: u32,
+}
+
+impl<const N: usize> Default for MassMemory<N> {
+    /// File system with [`DEFAULT_QUOTAS`] and no retention
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> MassMemory<N> {
+    /// Empty file system with [`DEFAULT_QUOTAS`] and no retention
+    pub const fn new() -> Self {
+        const fn empty(data_type: DataType, quota_bytes: u32) -> Directory {
+            Directory {
+                data_type,
+                quota_bytes,
+                used_bytes: 0,
+                file_count: 0,
+                retention_s: None,
+            }
+        }
+        Self {
+            files: Vec::new(),
+            directories: [
+                empty(DataType::Telemetry, DEFAULT_QUOTAS[0]),
+                empty(DataType::Science, DEFAULT_QUOTAS[1]),
+                empty(DataType::Images, DEFAULT_QUOTAS[2]),
+                empty(DataType::Logs, DEFAULT_QUOTAS[3]),
+                empty(DataType::Configuration, DEFAULT_QUOTAS[4]),
+                empty(DataType::Diagnostic, DEFAULT_QUOTAS[5]),
+            ],
+            next_file_id: 1,
+        }
+    }
+
+    /// Usage and limits of the directory holding `data_type`
+    pub const fn directory(&self, data_type: DataType) -> &Directory {
+        &self.directories[data_type as usize]
+    }
+
+    /// Bytes held across every directory
+    pub fn used_bytes(&self) -> u32 {
+        self.directories.iter().map(|d| d.used_bytes).sum()
+    }
+
+    /// Mass memory fill, percent of [`MASS_MEMORY_CAPACITY_BYTES`]
+    pub fn fill_percent(&self) -> f32 {
+        self.used_bytes() as f32 * 100.0 / MASS_MEMORY_CAPACITY_BYTES as f32
+    }
+
+    /// Write a file named `name` to the directory of `data_type`
+    ///
+    /// - **ID**: FN-MMF-001
+    /// - **Requirement**: Files are stored by data type within the
+    ///   directory's quota (REQ-FN-005, REQ-NF-002).
+    /// - **Outputs**: The new file's ID.
+    /// - **Side Effects**: Adds the file and charges its size to the
+    ///   directory.
+    /// - **Failure Modes**: An empty name, one longer than
+    ///   [`MAX_FILE_NAME_LEN`], containing `/` or already in the directory
+    ///   → `Err(ConfigurationError)`; a write over the directory's quota →
+    ///   `Err(ResourceExhausted)`; `N` files already held →
+    ///   `Err(MemoryError)`. Nothing is written on failure.
+    #[req("REQ-FN-005")]
+    pub fn create(
+        &mut self,
+        data_type: DataType,
+        name: &str,
+        size_bytes: u32,
+        location: StorageLocation,
+        science_value: u8,
+        now_s: u64,
+    ) -> Result<u32> {
+        let mut file_name = String::new();
+        if name.is_empty() || name.contains('/') || file_name.push_str(name).is_err() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "name",
+                value: "file name",
+                reason: "File names must be 1-24 bytes and may not contain '/'",
+            });
+        }
+        if self.find(data_type, name).is_some() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "name",
+                value: "file name",
+                reason: "File already exists in the directory",
+            });
+        }
+        self.create_named(
+            data_type,
+            file_name,
+            size_bytes,
+            location,
+            science_value,
+            now_s,
+        )
+    }
+
+    /// Write a product to the directory of `data_type`, named after its
+    /// file ID
+    ///
+    /// Fails as [`MassMemory::create`] does.
+    pub fn store(
+        &mut self,
+        data_type: DataType,
+        size_bytes: u32,
+        location: StorageLocation,
+        science_value: u8,
+        now_s: u64,
+    ) -> Result<u32> {
+        let mut name = String::new();
+        write!(name, "{:06}.dat", self.next_file_id).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(MAX_FILE_NAME_LEN))
+        })?;
+        self.create_named(data_type, name, size_bytes, location, science_value, now_s)
+    }
+
+    fn create_named(
+        &mut self,
+        data_type: DataType,
+        name: String<MAX_FILE_NAME_LEN>,
+        size_bytes: u32,
+        location: StorageLocation,
+        science_value: u8,
+        now_s: u64,
+    ) -> Result<u32> {
+        let directory = &self.directories[data_type as usize];
+        if size_bytes > directory.free_bytes() {
+            return Err(SpaceCommError::ResourceExhausted {
+                resource: directory_name(data_type),
+                current_usage: directory.used_bytes,
+                max_usage: directory.quota_bytes,
+            });
+        }
+        let file_id = self.next_file_id;
+        self.files
+            .push(FileRecord {
+                file_id,
+                data_type,
+                name,
+                size_bytes,
+                location,
+                science_value,
+                created_s: now_s,
+            })
+            .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(N)))?;
+        self.next_file_id = self.next_file_id.wrapping_add(1).max(1);
+        let directory = &mut self.directories[data_type as usize];
+        directory.used_bytes += size_bytes;
+        directory.file_count += 1;
+        Ok(file_id)
+    }
+
+    /// File with ID `file_id`
+    pub fn file(&self, file_id: u32) -> Option<&FileRecord> {
+        self.files.iter().find(|f| f.file_id == file_id)
+    }
+
+    /// File named `name` in the directory of `data_type`
+    pub fn find(&self, data_type: DataType, name: &str) -> Option<&FileRecord> {
+        self.files
+            .iter()
+            .find(|f| f.data_type == data_type && f.name == name)
+    }
+
+    /// Files in the directory of `data_type`, oldest first
+    pub fn list(&self, data_type: DataType) -> impl Iterator<Item = &FileRecord> + '_ {
+        let mut files: Vec<&FileRecord, N> = self
+            .files
+            .iter()
+            .filter(|f| f.data_type == data_type)
+            .collect();
+        files.sort_unstable_by_key(|f| (f.created_s, f.file_id));
+        files.into_iter()
+    }
+
+    /// Delete file `file_id` from the directory of `data_type`
+    ///
+    /// Naming the directory guards against deleting a file of another data
+    /// type by a mistyped ID.
+    ///
+    /// - **ID**: FN-MMF-002
+    /// - **Requirement**: The ground deletes stored files (REQ-FN-005).
+    /// - **Outputs**: The deleted file.
+    /// - **Side Effects**: Frees the file's size in its directory.
+    /// - **Failure Modes**: No such file in that directory →
+    ///   `Err(ConfigurationError)`.
+    #[req("REQ-FN-005")]
+    pub fn delete(&mut self, data_type: DataType, file_id: u32) -> Result<FileRecord> {
+        let index = self
+            .files
+            .iter()
+            .position(|f| f.file_id == file_id && f.data_type == data_type)
+            .ok_or(SpaceCommError::ConfigurationError {
+                parameter: "file_id",
+                value: "file ID",
+                reason: "No such file in the directory",
+            })?;
+        Ok(self.remove_at(index))
+    }
+
+    fn remove_at(&mut self, index: usize) -> FileRecord {
+        let file = self.files.swap_remove(index);
+        let directory = &mut self.directories[file.data_type as usize];
+        directory.used_bytes -= file.size_bytes;
+        directory.file_count -= 1;
+        file
+    }
+
+    /// Keep files of `data_type` for `retention_s` seconds after they are
+    /// written, or until deleted for `None`
+    ///
+    /// Files already past the new retention go at the next
+    /// [`MassMemory::expire`].
+    pub fn set_retention(&mut self, data_type: DataType, retention_s: Option<u32>) {
+        self.directories[data_type as usize].retention_s = retention_s;
+    }
+
+    /// Limit the directory of `data_type` to `quota_bytes`
+    ///
+    /// Fails with `Err(ResourceExhausted)`, leaving the quota unchanged, if
+    /// the directory already holds more.
+    pub fn set_quota(&mut self, data_type: DataType, quota_bytes: u32) -> Result<()> {
+        let directory = &mut self.directories[data_type as usize];
+        if quota_bytes < directory.used_bytes {
+            return Err(SpaceCommError::ResourceExhausted {
+                resource: directory_name(data_type),
+                current_usage: directory.used_bytes,
+                max_usage: quota_bytes,
+            });
+        }
+        directory.quota_bytes = quota_bytes;
+        Ok(())
+    }
+
+    /// Delete every file past its directory's retention at `now_s`
+    ///
+    /// - **ID**: FN-MMF-003
+    /// - **Requirement**: Directories keep files no longer than their
+    ///   retention (REQ-NF-002).
+    /// - **Outputs**: The deleted files, so their users can forget them.
+    /// - **Side Effects**: Frees their sizes in their directories.
+    #[req("REQ-NF-002")]
+    pub fn expire(&mut self, now_s: u64) -> Vec<FileRecord, N> {
+        let mut expired = Vec::new();
+        let mut index = 0;
+        while index < self.files.len() {
+            let file = &self.files[index];
+            let retention = self.directories[file.data_type as usize].retention_s;
+            if retention.is_some_and(|retention_s| file.is_expired(retention_s, now_s)) {
+                // Cannot overflow: at most N files are held
+                let _ = expired.push(self.remove_at(index));
+            } else {
+                index += 1;
+            }
+        }
+        expired
+    }
+
+    /// Listing of the directory of `data_type` for downlink
+    pub fn listing(&self, data_type: DataType) -> FileListing {
+        let mut files = Vec::new();
+        let mut truncated = false;
+        for file in self.list(data_type) {
+            let entry = ListingEntry {
+                file_id: file.file_id,
+                name: file.name.clone(),
+                size_bytes: file.size_bytes,
+                science_value: file.science_value,
+                created_s: file.created_s,
+            };
+            if files.push(entry).is_err() {
+                truncated = true;
+                break;
+            }
+        }
+        FileListing {
+            directory: *self.directory(data_type),
+            files,
+            truncated,
+        }
+    }
+}
+
+/// One file of a listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListingEntry {
+    /// File identifier
+    pub file_id: u32,
+    /// File name
+    pub name: String<MAX_FILE_NAME_LEN>,
+    /// File size in bytes
+    pub size_bytes: u32,
+    /// Science value the file was tagged with
+    pub science_value: u8,
+    /// Mission time the file was written, seconds
+    pub created_s: u64,
+}
+
+/// Directory listing downlinked in answer to [`SpaceCommand::ListFiles`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileListing {
+    /// Usage and limits of the directory
+    pub directory: Directory,
+    /// Files in the directory, oldest first
+    pub files: Vec<ListingEntry, MAX_LISTING_FILES>,
+    /// Whether the directory holds more files than the listing
+    pub truncated: bool,
+}
+
+impl FileListing {
+    /// Serialize the listing for downlink
+    pub fn to_bytes(&self) -> Result<Vec<u8, MAX_LISTING_BYTES>> {
+        let bytes = serde_json::to_vec(self).map_err(|_| {
+            SpaceCommError::invalid_packet("Failed to serialize file listing", None)
+        })?;
+        Vec::from_slice(&bytes).map_err(|_| {
+            SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(bytes.len()))
+        })
+    }
+
+    /// Parse a listing received on the downlink
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|_| SpaceCommError::invalid_packet("Malformed file listing", None))
+    }
+
+    /// Wrap the listing in a Space Packet on [`FILE_LISTING_APID`]
+    pub fn to_packet(&self, sequence_count: u16) -> Result<SpacePacket> {
+        SpacePacket::new(
+            PacketType::Telemetry,
+            FILE_LISTING_APID,
+            sequence_count & 0x3FFF,
+            &self.to_bytes()?,
+            None,
+        )
+    }
+}
+
+/// File system operation commanded by the ground
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileRequest {
+    /// Store a product of a data type
+    Store {
+        /// Directory written to
+        data_type: DataType,
+        /// Storage written to
+        location: StorageLocation,
+        /// Science value the product is tagged with
+        science_value: u8,
+    },
+    /// Downlink a listing of a directory
+    List(DataType),
+    /// Delete one file
+    Delete {
+        /// Directory the file is in
+        data_type: DataType,
+        /// File to delete
+        file_id: u32,
+    },
+    /// Set a directory's retention
+    SetRetention {
+        /// Directory changed
+        data_type: DataType,
+        /// New retention, seconds; `None` keeps files until deleted
+        retention_s: Option<u32>,
+    },
+}
+
+impl FileRequest {
+    /// Request carried by `command`, or `None` for any other command
+    pub fn from_command(command: &SpaceCommand) -> Option<Self> {
+        match *command {
+            SpaceCommand::StoreData {
+                data_type,
+                storage_location,
+                science_value,
+                ..
+            } => Some(Self::Store {
+                data_type,
+                location: storage_location,
+                science_value,
+            }),
+            SpaceCommand::ListFiles { data_type } => Some(Self::List(data_type)),
+            SpaceCommand::DeleteFile { data_type, file_id } => {
+                Some(Self::Delete { data_type, file_id })
+            }
+            SpaceCommand::SetRetention {
+                data_type,
+                retention_s,
+            } => Some(Self::SetRetention {
+                data_type,
+                retention_s: (retention_s > 0).then_some(retention_s),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Request carried by a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a file system
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if !matches!(
+            command_id,
+            STORE_DATA_COMMAND_ID
+                | LIST_FILES_COMMAND_ID
+                | DELETE_FILE_COMMAND_ID
+                | SET_RETENTION_COMMAND_ID
+        ) {
+            return None;
+        }
+        let request = serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..])
+            .ok()
+            .filter(|command| command.discriminant() == command_id)
+            .and_then(|command| Self::from_command(&command))
+            .ok_or(SpaceCommError::invalid_packet(
+                "Malformed file system command",
+                Some(command_id),
+            ));
+        Some(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestMemory = MassMemory<8>;
+
+    #[test]
+    fn test_files_are_named_per_directory() {
+        let mut memory = TestMemory::new();
+        let location = StorageLocation::NonVolatileMemory;
+        let image = memory
+            .create(DataType::Images, "pass_0042.raw", 1000, location, 0, 10)
+            .unwrap();
+        // The same name may be used in another directory, not in the same one
+        memory
+            .create(DataType::Science, "pass_0042.raw", 500, location, 0, 11)
+            .unwrap();
+        assert!(matches!(
+            memory.create(DataType::Images, "pass_0042.raw", 1, location, 0, 12),
+            Err(SpaceCommError::ConfigurationError { .. })
+        ));
+        for name in ["", "a/b", "a_name_that_is_far_too_long_for_the_table"] {
+            assert!(memory
+                .create(DataType::Logs, name, 1, location, 0, 12)
+                .is_err());
+        }
+
+        let file = memory.find(DataType::Images, "pass_0042.raw").unwrap();
+        assert_eq!(file.file_id, image);
+        assert_eq!(file.to_string(), "/images/pass_0042.raw");
+        let product = memory.store(DataType::Logs, 10, location, 0, 13).unwrap();
+        assert_eq!(memory.file(product).unwrap().name, "000003.dat");
+
+        let images = memory.directory(DataType::Images);
+        assert_eq!((images.used_bytes, images.file_count), (1000, 1));
+        assert_eq!(memory.used_bytes(), 1510);
+    }
+
+    #[test]
+    fn test_quota_refuses_writes_of_one_data_type() {
+        let mut memory = TestMemory::new();
+        let location = StorageLocation::VolatileMemory;
+        memory.set_quota(DataType::Diagnostic, 1000).unwrap();
+        memory
+            .store(DataType::Diagnostic, 800, location, 0, 0)
+            .unwrap();
+        assert!(matches!(
+            memory.store(DataType::Diagnostic, 201, location, 0, 0),
+            Err(SpaceCommError::ResourceExhausted {
+                current_usage: 800,
+                max_usage: 1000,
+                ..
+            })
+        ));
+        // Other data types are unaffected
+        memory
+            .store(DataType::Science, 201, location, 0, 0)
+            .unwrap();
+        assert!(memory.set_quota(DataType::Diagnostic, 500).is_err());
+        assert_eq!(memory.directory(DataType::Diagnostic).quota_bytes, 1000);
+        assert_eq!(
+            DEFAULT_QUOTAS.iter().sum::<u32>(),
+            MASS_MEMORY_CAPACITY_BYTES
+        );
+
+        for _ in 0..6 {
+            memory.store(DataType::Logs, 1, location, 0, 0).unwrap();
+        }
+        assert!(matches!(
+            memory.store(DataType::Logs, 1, location, 0, 0),
+            Err(SpaceCommError::MemoryError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_delete_and_retention() {
+        let mut memory = TestMemory::new();
+        let location = StorageLocation::NonVolatileMemory;
+        let old = memory
+            .store(DataType::Telemetry, 100, location, 0, 0)
+            .unwrap();
+        let new = memory
+            .store(DataType::Telemetry, 100, location, 0, 500)
+            .unwrap();
+        let kept = memory
+            .store(DataType::Science, 100, location, 0, 0)
+            .unwrap();
+
+        // Deleting by ID needs the right directory
+        assert!(memory.delete(DataType::Science, old).is_err());
+        assert_eq!(
+            memory.delete(DataType::Science, kept).unwrap().file_id,
+            kept
+        );
+        assert_eq!(memory.directory(DataType::Science).used_bytes, 0);
+
+        memory.set_retention(DataType::Telemetry, Some(600));
+        assert!(memory.expire(599).is_empty());
+        let expired = memory.expire(600);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].file_id, old);
+        assert!(memory.file(new).is_some());
+        assert_eq!(memory.directory(DataType::Telemetry).file_count, 1);
+
+        memory.set_retention(DataType::Telemetry, None);
+        assert!(memory.expire(u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_listing_round_trip() {
+        let mut memory = MassMemory::<32>::new();
+        let location = StorageLocation::NonVolatileMemory;
+        for now_s in (0..20).rev() {
+            memory
+                .store(DataType::Images, 4096, location, 0, now_s)
+                .unwrap();
+        }
+        memory.set_retention(DataType::Images, Some(3600));
+
+        let listing = memory.listing(DataType::Images);
+        assert!(listing.truncated);
+        assert_eq!(listing.files.len(), MAX_LISTING_FILES);
+        assert_eq!(listing.files[0].created_s, 0);
+        assert_eq!(listing.directory.file_count, 20);
+        assert_eq!(listing.directory.retention_s, Some(3600));
+
+        let packet = listing.to_packet(5).unwrap();
+        assert_eq!(packet.header.apid, FILE_LISTING_APID);
+        assert_eq!(FileListing::from_bytes(&packet.data).unwrap(), listing);
+        assert!(!memory.listing(DataType::Logs).truncated);
+    }
+
+    #[test]
+    fn test_file_requests_from_command_data() {
+        let encode = |command: &SpaceCommand| {
+            let mut data = std::vec::Vec::from(command.discriminant().to_be_bytes());
+            data.extend(serde_json::to_vec(command).unwrap());
+            data
+        };
+
+        let command = SpaceCommand::SetRetention {
+            data_type: DataType::Logs,
+            retention_s: 0,
+        };
+        assert_eq!(command.discriminant(), SET_RETENTION_COMMAND_ID);
+        assert_eq!(
+            FileRequest::from_command_data(&encode(&command))
+                .unwrap()
+                .unwrap(),
+            FileRequest::SetRetention {
+                data_type: DataType::Logs,
+                retention_s: None
+            }
+        );
+
+        let command = SpaceCommand::DeleteFile {
+            data_type: DataType::Science,
+            file_id: 7,
+        };
+        assert_eq!(command.discriminant(), DELETE_FILE_COMMAND_ID);
+        assert_eq!(
+            FileRequest::from_command_data(&encode(&command))
+                .unwrap()
+                .unwrap(),
+            FileRequest::Delete {
+                data_type: DataType::Science,
+                file_id: 7
+            }
+        );
+
+        let command = SpaceCommand::StoreData {
+            data_type: DataType::Images,
+            storage_location: StorageLocation::BackupStorage,
+            compression_level: 3,
+            encryption: false,
+            science_value: 200,
+        };
+        assert_eq!(command.discriminant(), STORE_DATA_COMMAND_ID);
+        assert!(FileRequest::from_command_data(&encode(&command)).is_some());
+
+        let command = SpaceCommand::ListFiles {
+            data_type: DataType::Images,
+        };
+        let mut malformed = encode(&command);
+        malformed.truncate(wire::COMMAND_ID_LEN + 3);
+        assert!(FileRequest::from_command_data(&malformed).unwrap().is_err());
+        assert!(
+            FileRequest::from_command_data(&LIST_FILES_COMMAND_ID.to_be_bytes()[..2]).is_none()
+        );
+        let other = SpaceCommand::SetAutonomyRule {
+            rule_id: 1,
+            enabled: true,
+        };
+        assert!(FileRequest::from_command_data(&encode(&other)).is_none());
+    }
+}
@@ -13,8 +13,11 @@ use core::cmp::Ordering;
 use heapless::binary_heap::{BinaryHeap, Max};
 use serde::{Deserialize, Serialize};
 
+use crate::ccsds::{PacketType, SpacePacket, SpacePacketHeader};
 use crate::error::{MemoryErrorType, Result, SpaceCommError};
 use crate::types::{BandType, ComponentId, MessageId};
+use crate::wire::{self, WireReader, WireWriter};
+use space_comms_req::req;
 
 /// Message priority levels following NASA mission-critical classification
 /// REQ-FN-001: Priority Classification - Five-tier priority system
@@ -61,6 +64,7 @@ impl MessagePriority {
 
     /// Get the maximum acceptable latency in milliseconds
     /// REQ-FN-010: Real-Time Constraints - Processing latency requirements
+    #[req("REQ-FN-010")]
     pub const fn max_latency_ms(&self) -> u32 {
         match self {
             MessagePriority::Low => 10000,   // 10 seconds - REQ-FN-006
@@ -71,10 +75,47 @@ impl MessagePriority {
         }
     }
 
+    /// Maximum allowed command execution time in milliseconds
+    /// REQ-PF-001: Command Response Time - Execution budget per priority
+    #[req("REQ-PF-001")]
+    pub const fn max_execution_time_ms(&self) -> u32 {
+        match self {
+            MessagePriority::Emergency => 1, // Must execute within 1ms
+            MessagePriority::Critical => 10, // Must execute within 10ms
+            MessagePriority::High => 100,    // Must execute within 100ms
+            MessagePriority::Medium => 1000, // Must execute within 1 second
+            MessagePriority::Low => 10000,   // Must execute within 10 seconds
+        }
+    }
+
     /// Check if this priority requires real-time processing
     pub const fn is_real_time(&self) -> bool {
         matches!(self, MessagePriority::Critical | MessagePriority::Emergency)
     }
+
+    /// CCSDS APID carrying uplinked commands of this priority
+    /// REQ-IF-002: CCSDS Compliance - Priority-based APID assignment
+    pub const fn command_apid(&self) -> u16 {
+        match self {
+            MessagePriority::Emergency => 0x001,
+            MessagePriority::Critical => 0x002,
+            MessagePriority::High => 0x003,
+            MessagePriority::Medium => 0x004,
+            MessagePriority::Low => 0x005,
+        }
+    }
+
+    /// Priority of a command received on `apid`, if it is a command APID
+    pub const fn from_command_apid(apid: u16) -> Option<Self> {
+        match apid {
+            0x001 => Some(MessagePriority::Emergency),
+            0x002 => Some(MessagePriority::Critical),
+            0x003 => Some(MessagePriority::High),
+            0x004 => Some(MessagePriority::Medium),
+            0x005 => Some(MessagePriority::Low),
+            _ => None,
+        }
+    }
 }
 
 /// Core message structure for space communication
@@ -175,6 +216,154 @@ impl MessagePayload {
     }
 }
 
+/// Command fields recovered from an uplinked command packet
+///
+/// Wire layout shared by ground and satellite: priority-based APID, sequence
+/// count from the low 14 bits of the message ID, and a data field holding the
+/// `u32` command ID followed by the raw parameters, in the [`crate::wire`]
+/// format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandPacketFields {
+    /// Priority implied by the packet APID
+    pub priority: MessagePriority,
+    /// CCSDS packet sequence count
+    pub sequence_count: u16,
+    /// Command identifier
+    pub command_id: u32,
+    /// Command parameters
+    pub parameters: heapless::Vec<u8, 256>,
+}
+
+impl Message {
+    /// Encode a command message as a CCSDS command packet.
+    ///
+    /// - **ID**: FN-MSG-001
+    /// - **Requirement**: Ground and satellite use one command wire format
+    ///   (REQ-IF-002, REQ-FN-001).
+    /// - **Outputs**: Command packet on `priority.command_apid()` with the
+    ///   layout described on [`CommandPacketFields`].
+    /// - **Failure Modes**: `InvalidPacket` when the payload is not a command.
+    #[req("REQ-IF-002")]
+    pub fn to_command_packet(&self) -> Result<SpacePacket> {
+        let MessagePayload::Command {
+            command_id,
+            parameters,
+        } = &self.payload
+        else {
+            return Err(SpaceCommError::invalid_packet(
+                "Message payload is not a command",
+                None,
+            ));
+        };
+
+        let mut data = WireWriter::<260>::new();
+        data.put(*command_id)?.put_bytes(parameters)?;
+
+        SpacePacket::new(
+            PacketType::Command,
+            self.priority.command_apid(),
+            (self.id.value() & 0x3FFF) as u16,
+            &data.finish(),
+            None,
+        )
+    }
+}
+
+/// Decode a serialized command packet produced by [`Message::to_command_packet`].
+///
+/// - **ID**: FN-MSG-002
+/// - **Requirement**: Reject uplinked packets that are corrupted or not on a
+///   command APID before the command is dispatched (REQ-IF-002, REQ-SF-001).
+/// - **Inputs**: Packet bytes including the trailing 2-byte error control.
+/// - **Outputs**: The command fields.
+/// - **Failure Modes**: `InvalidPacket` on a short packet, telemetry packet,
+///   unknown APID, frame not as laid out in [`crate::wire`] (length or CRC
+///   mismatch), or oversized parameters.
+#[req("REQ-IF-002")]
+pub fn decode_command_packet(bytes: &[u8]) -> Result<CommandPacketFields> {
+    let header = SpacePacketHeader::from_bytes(bytes)?;
+    if header.packet_type != PacketType::Command || header.secondary_header_flag {
+        return Err(SpaceCommError::invalid_packet("Not a command packet", None));
+    }
+    let priority = MessagePriority::from_command_apid(header.apid)
+        .ok_or(SpaceCommError::invalid_packet("Unknown command APID", None))?;
+
+    // Header, 4-byte command ID, 2-byte error control
+    if bytes.len() < wire::PRIMARY_HEADER_LEN + wire::COMMAND_ID_LEN + wire::ERROR_CONTROL_LEN {
+        return Err(SpaceCommError::invalid_packet(
+            "Command packet too short",
+            None,
+        ));
+    }
+    let (_, data) = wire::split_frame(bytes)?;
+    let mut reader = WireReader::new(data);
+    let command_id = reader.get()?;
+    let parameters = heapless::Vec::from_slice(reader.rest())
+        .map_err(|_| SpaceCommError::invalid_packet("Command parameters too long", None))?;
+
+    Ok(CommandPacketFields {
+        priority,
+        sequence_count: header.sequence_count,
+        command_id,
+        parameters,
+    })
+}
+
+/// Copies of each frame sent on the emergency uplink lane
+///
+/// The lane has no acknowledgement or retry queue, so the ground sends every
+/// emergency frame this many times back to back and the satellite drops the
+/// duplicates with an [`EmergencyDuplicateFilter`].
+pub const EMERGENCY_UPLINK_REPEATS: u8 = 3;
+
+/// Whether `bytes` start with the header of an emergency command packet.
+///
+/// Header-only check so the emergency lane can route a frame without decoding
+/// or CRC-checking it first; [`decode_command_packet`] still validates it.
+#[req("REQ-FN-002")]
+pub fn is_emergency_frame(bytes: &[u8]) -> bool {
+    SpacePacketHeader::from_bytes(bytes).is_ok_and(|header| {
+        header.packet_type == PacketType::Command
+            && header.apid == MessagePriority::Emergency.command_apid()
+    })
+}
+
+/// Drops repeated copies of emergency frames
+///
+/// Emergency frames are sent [`EMERGENCY_UPLINK_REPEATS`] times with the same
+/// sequence count; only the first copy of each sequence count is accepted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmergencyDuplicateFilter {
+    last_sequence: Option<u16>,
+}
+
+impl EmergencyDuplicateFilter {
+    /// Filter that accepts the next frame whatever its sequence count
+    pub const fn new() -> Self {
+        Self {
+            last_sequence: None,
+        }
+    }
+
+    /// Whether a frame with `sequence_count` repeats the last one accepted,
+    /// without recording it
+    ///
+    /// Lets a repeated copy of a protected frame be dropped before it is
+    /// opened, where its frame counter would be refused as a replay.
+    pub fn is_repeat(&self, sequence_count: u16) -> bool {
+        self.last_sequence == Some(sequence_count)
+    }
+
+    /// Record a decoded emergency frame; `false` if it repeats the last one
+    pub fn accept(&mut self, sequence_count: u16) -> bool {
+        if self.last_sequence == Some(sequence_count) {
+            return false;
+        }
+        self.last_sequence = Some(sequence_count);
+        true
+    }
+}
+
 /// Priority queue message wrapper for heap ordering
 #[derive(Debug, Clone)]
 pub struct PriorityMessage {
@@ -208,26 +397,124 @@ impl Ord for PriorityMessage {
     }
 }
 
+/// Action taken when a priority level is over its quota or the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DropPolicy {
+    /// Reject the incoming message; queued messages are untouched
+    DropNewest,
+    /// Discard the oldest queued message of the same priority to make room
+    DropOldest,
+    /// Never drop the incoming message; evict the newest message of the
+    /// lowest priority below it instead. Fails only if nothing lower is queued.
+    NeverDrop,
+}
+
+/// Capacity quota and drop policy for one priority level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityQuota {
+    /// Maximum messages of this priority held at once
+    pub max_messages: usize,
+    /// Action when the quota or the whole queue is exhausted
+    pub policy: DropPolicy,
+}
+
+impl PriorityQuota {
+    /// Quota with no per-priority limit (bounded only by queue capacity)
+    pub const fn unlimited(policy: DropPolicy) -> Self {
+        Self {
+            max_messages: usize::MAX,
+            policy,
+        }
+    }
+}
+
+/// Per-priority quotas for a [`PriorityQueue`], indexed by priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueQuotas {
+    /// Quotas for Low, Medium, High, Critical, Emergency (in that order)
+    pub quotas: [PriorityQuota; 5],
+}
+
+impl QueueQuotas {
+    /// Default policy: routine traffic drops newest, High drops its own oldest,
+    /// Critical and Emergency are never dropped and evict lower priorities.
+    pub const DEFAULT: Self = Self {
+        quotas: [
+            PriorityQuota::unlimited(DropPolicy::DropNewest), // Low
+            PriorityQuota::unlimited(DropPolicy::DropNewest), // Medium
+            PriorityQuota::unlimited(DropPolicy::DropOldest), // High
+            PriorityQuota::unlimited(DropPolicy::NeverDrop),  // Critical
+            PriorityQuota::unlimited(DropPolicy::NeverDrop),  // Emergency
+        ],
+    };
+
+    /// Quota for a priority level
+    pub const fn quota(&self, priority: MessagePriority) -> PriorityQuota {
+        self.quotas[priority_index(priority)]
+    }
+
+    /// Replace the quota for a priority level
+    pub const fn with_quota(mut self, priority: MessagePriority, quota: PriorityQuota) -> Self {
+        self.quotas[priority_index(priority)] = quota;
+        self
+    }
+}
+
+impl Default for QueueQuotas {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Array index for per-priority bookkeeping (Low = 0 … Emergency = 4)
+const fn priority_index(priority: MessagePriority) -> usize {
+    priority as usize - 1
+}
+
 /// Fixed-size priority queue for embedded systems
 ///
 /// This queue maintains messages in priority order with FIFO semantics
-/// within each priority level.
+/// within each priority level. Each priority level may be capped by a
+/// [`PriorityQuota`] so that a flood of routine traffic cannot crowd out
+/// critical messages under queue pressure.
 pub struct PriorityQueue<const N: usize> {
     /// Binary heap for priority ordering
     heap: BinaryHeap<PriorityMessage, Max, N>,
     /// Sequence counter for FIFO ordering
     sequence_counter: u64,
+    /// Per-priority quotas and drop policies
+    quotas: QueueQuotas,
+    /// Messages currently queued, per priority
+    counts: [usize; 5],
+    /// Incoming messages rejected, per priority
+    dropped: [u32; 5],
+    /// Queued messages discarded to make room, per priority
+    evicted: [u32; 5],
 }
 
 impl<const N: usize> PriorityQueue<N> {
-    /// Create a new priority queue
+    /// Create a new priority queue with the default quotas
     pub const fn new() -> Self {
+        Self::with_quotas(QueueQuotas::DEFAULT)
+    }
+
+    /// Create a new priority queue with explicit per-priority quotas
+    pub const fn with_quotas(quotas: QueueQuotas) -> Self {
         Self {
             heap: BinaryHeap::new(),
             sequence_counter: 0,
+            quotas,
+            counts: [0; 5],
+            dropped: [0; 5],
+            evicted: [0; 5],
         }
     }
 
+    /// Quotas currently applied by this queue
+    pub const fn quotas(&self) -> &QueueQuotas {
+        &self.quotas
+    }
+
     /// Enqueue a message, maintaining priority-heap ordering.
     ///
     /// - **ID**: FN-MQ-001
@@ -238,17 +525,65 @@ impl<const N: usize> PriorityQueue<N> {
     ///   queue from multiple task contexts.
     /// - **Inputs**:
     ///   - `message`: The `Message` to enqueue; must have a valid `priority` field.
-    /// - **Outputs**: `Ok(())` on success; `Err(MemoryError::BufferOverflow)` when
-    ///   the queue is full (capacity = `N`).
-    /// - **Preconditions**: `self.len() < N`.
-    /// - **Postconditions**: `self.len()` is increased by one; internal `sequence_counter`
-    ///   is incremented (wrapping) to preserve FIFO order within equal priorities.
-    /// - **Side Effects**: Mutates `self.heap` and `self.sequence_counter`.
-    /// - **Failure Modes**: Queue full → returns `Err`; never panics.
-    /// - **Constraints**: O(log N) time per insertion; no dynamic allocation.
-    /// - **Verification**: Fuzz inputs at capacity boundary (N-1 and N messages).
+    /// - **Outputs**: `Ok(())` on success; `Err(ResourceExhausted)` when the
+    ///   message's priority is over quota and its policy rejects it;
+    ///   `Err(MemoryError::BufferOverflow)` when the queue is full and no
+    ///   message can be evicted under the policy.
+    /// - **Preconditions**: None — quota and capacity are enforced here.
+    /// - **Postconditions**: On success `self.len()` is increased by one, or is
+    ///   unchanged if a message was evicted to make room; internal
+    ///   `sequence_counter` is incremented (wrapping) to preserve FIFO order.
+    /// - **Side Effects**: Mutates `self.heap`, `self.sequence_counter`, and the
+    ///   per-priority drop/eviction counters reported by `statistics()`.
+    /// - **Failure Modes**: Quota or capacity exhausted → returns `Err`; never panics.
+    ///   A `NeverDrop` message only fails when every queued message has equal or
+    ///   higher priority.
+    /// - **Constraints**: O(log N) per insertion; O(N log N) when an eviction
+    ///   rebuilds the heap. No dynamic allocation.
+    /// - **Verification**: Fuzz inputs at capacity boundary (N-1 and N messages);
+    ///   flood with Medium traffic and assert Critical messages survive.
     /// - **References**: REQ-FN-009; ECSS-E-ST-70-41C §6.
+    #[req("REQ-FN-001", "REQ-FN-009")]
     pub fn push(&mut self, message: Message) -> Result<()> {
+        let priority = message.priority;
+        let index = priority_index(priority);
+        let quota = self.quotas.quota(priority);
+
+        // Per-priority quota. NeverDrop levels are exempt by definition.
+        if self.counts[index] >= quota.max_messages {
+            match quota.policy {
+                DropPolicy::DropNewest => {
+                    self.dropped[index] = self.dropped[index].saturating_add(1);
+                    return Err(SpaceCommError::ResourceExhausted {
+                        resource: "priority queue quota",
+                        current_usage: self.counts[index] as u32,
+                        max_usage: quota.max_messages as u32,
+                    });
+                }
+                DropPolicy::DropOldest => {
+                    self.evict(|pm| pm.message.priority == priority, false);
+                }
+                DropPolicy::NeverDrop => {}
+            }
+        }
+
+        // Whole-queue capacity
+        if self.heap.len() >= N {
+            let made_room = match quota.policy {
+                DropPolicy::DropNewest => false,
+                DropPolicy::DropOldest => self.evict(|pm| pm.message.priority == priority, false),
+                DropPolicy::NeverDrop => self.evict_below(priority),
+            };
+
+            if !made_room {
+                self.dropped[index] = self.dropped[index].saturating_add(1);
+                return Err(SpaceCommError::memory_error(
+                    MemoryErrorType::BufferOverflow,
+                    Some(N),
+                ));
+            }
+        }
+
         let priority_message = PriorityMessage {
             message,
             sequence: self.sequence_counter,
@@ -258,12 +593,76 @@ impl<const N: usize> PriorityQueue<N> {
 
         self.heap
             .push(priority_message)
-            .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(N)))
+            .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(N)))?;
+        self.counts[index] += 1;
+        Ok(())
+    }
+
+    /// Evict the newest message of the lowest priority strictly below `priority`
+    fn evict_below(&mut self, priority: MessagePriority) -> bool {
+        let lowest = [
+            MessagePriority::Low,
+            MessagePriority::Medium,
+            MessagePriority::High,
+            MessagePriority::Critical,
+        ]
+        .into_iter()
+        .find(|p| *p < priority && self.counts[priority_index(*p)] > 0);
+
+        match lowest {
+            Some(victim) => self.evict(|pm| pm.message.priority == victim, true),
+            None => false,
+        }
+    }
+
+    /// Remove one queued message matching `select`: the newest if `newest`,
+    /// otherwise the oldest. Rebuilds the heap; returns whether one was removed.
+    fn evict(&mut self, select: impl Fn(&PriorityMessage) -> bool, newest: bool) -> bool {
+        let mut kept = heapless::Vec::<PriorityMessage, N>::new();
+        let mut victim: Option<PriorityMessage> = None;
+
+        while let Some(pm) = self.heap.pop() {
+            if !select(&pm) {
+                let _ = kept.push(pm);
+                continue;
+            }
+
+            let replace = match &victim {
+                None => true,
+                Some(current) => (pm.sequence > current.sequence) == newest,
+            };
+
+            if replace {
+                if let Some(previous) = victim.replace(pm) {
+                    let _ = kept.push(previous);
+                }
+            } else {
+                let _ = kept.push(pm);
+            }
+        }
+
+        // Safe because every kept message came out of the heap above
+        for pm in kept {
+            let _ = self.heap.push(pm);
+        }
+
+        match victim {
+            Some(pm) => {
+                let index = priority_index(pm.message.priority);
+                self.counts[index] -= 1;
+                self.evicted[index] = self.evicted[index].saturating_add(1);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Remove and return the highest priority message (no TTL check).
+    #[req("REQ-FN-001", "REQ-FN-009")]
     pub fn pop(&mut self) -> Option<Message> {
-        self.heap.pop().map(|pm| pm.message)
+        let pm = self.heap.pop()?;
+        self.counts[priority_index(pm.message.priority)] -= 1;
+        Some(pm.message)
     }
 
     /// Remove and return the highest-priority **non-expired** message.
@@ -293,9 +692,11 @@ impl<const N: usize> PriorityQueue<N> {
     /// - **Verification**: Test with a mixture of expired (ttl=1, age=2) and valid
     ///   messages; assert only valid messages are returned.
     /// - **References**: REQ-FN-009; REQ-FN-010; ECSS-E-ST-70-41C §6.3.
+    #[req("REQ-FN-009")]
     pub fn pop_valid(&mut self, current_time_secs: u64) -> Option<Message> {
         loop {
             let pm = self.heap.pop()?;
+            self.counts[priority_index(pm.message.priority)] -= 1;
             let msg = &pm.message;
 
             // ttl_seconds == 0 means no expiry; otherwise check elapsed time.
@@ -341,6 +742,7 @@ impl<const N: usize> PriorityQueue<N> {
     /// Remove expired messages based on TTL
     ///
     /// This method should be called periodically to clean up expired messages.
+    #[req("REQ-FN-009")]
     pub fn remove_expired(&mut self, current_time_seconds: u64) {
         // Note: BinaryHeap doesn't support efficient removal of arbitrary elements
         // For a production system, consider using a more sophisticated data structure
@@ -367,33 +769,26 @@ impl<const N: usize> PriorityQueue<N> {
         }
 
         // Rebuild the heap with non-expired messages
+        self.counts = [0; 5];
         for priority_message in temp_messages {
+            self.counts[priority_index(priority_message.message.priority)] += 1;
             let _ = self.heap.push(priority_message); // Safe because we started with these messages
         }
     }
 
     /// Get statistics about the queue contents
     pub fn statistics(&self) -> QueueStatistics {
-        let mut stats = QueueStatistics::default();
-
-        // Count messages by priority
-        // Note: This is inefficient as it requires cloning the heap
-        // In a production system, maintain separate counters
-        let temp_heap = self.heap.clone();
-        for priority_message in temp_heap.into_vec() {
-            match priority_message.message.priority {
-                MessagePriority::Low => stats.low_priority += 1,
-                MessagePriority::Medium => stats.medium_priority += 1,
-                MessagePriority::High => stats.high_priority += 1,
-                MessagePriority::Critical => stats.critical_priority += 1,
-                MessagePriority::Emergency => stats.emergency_priority += 1,
-            }
+        QueueStatistics {
+            total: self.len(),
+            capacity: N,
+            low_priority: self.counts[0],
+            medium_priority: self.counts[1],
+            high_priority: self.counts[2],
+            critical_priority: self.counts[3],
+            emergency_priority: self.counts[4],
+            dropped: self.dropped,
+            evicted: self.evicted,
         }
-
-        stats.total = self.len();
-        stats.capacity = N;
-
-        stats
     }
 }
 
@@ -420,6 +815,10 @@ pub struct QueueStatistics {
     pub critical_priority: usize,
     /// Number of emergency priority messages
     pub emergency_priority: usize,
+    /// Incoming messages rejected by quota or capacity, per priority (Low first)
+    pub dropped: [u32; 5],
+    /// Queued messages evicted to make room, per priority (Low first)
+    pub evicted: [u32; 5],
 }
 
 impl QueueStatistics {
@@ -436,6 +835,21 @@ impl QueueStatistics {
     pub fn is_near_capacity(&self, threshold_percent: f32) -> bool {
         self.utilization_percent() >= threshold_percent
     }
+
+    /// Messages of `priority` rejected on push
+    pub const fn dropped_for(&self, priority: MessagePriority) -> u32 {
+        self.dropped[priority_index(priority)]
+    }
+
+    /// Messages of `priority` evicted from the queue
+    pub const fn evicted_for(&self, priority: MessagePriority) -> u32 {
+        self.evicted[priority_index(priority)]
+    }
+
+    /// Total messages lost to queue pressure (dropped plus evicted)
+    pub fn total_lost(&self) -> u32 {
+        self.dropped.iter().chain(self.evicted.iter()).sum()
+    }
 }
 
 #[cfg(test)]
@@ -548,4 +962,157 @@ mod tests {
         assert_eq!(stats.emergency_priority, 1);
         assert_eq!(stats.utilization_percent(), 30.0);
     }
+
+    #[test]
+    fn test_medium_flood_cannot_evict_critical() {
+        let quotas = QueueQuotas::DEFAULT.with_quota(
+            MessagePriority::Medium,
+            PriorityQuota {
+                max_messages: 2,
+                policy: DropPolicy::DropNewest,
+            },
+        );
+        let mut queue: PriorityQueue<4> = PriorityQueue::with_quotas(quotas);
+
+        queue
+            .push(create_test_message(MessagePriority::Critical, 1))
+            .unwrap();
+        for id in 2..10 {
+            let _ = queue.push(create_test_message(MessagePriority::Medium, id));
+        }
+
+        let stats = queue.statistics();
+        assert_eq!(stats.critical_priority, 1);
+        assert_eq!(stats.medium_priority, 2);
+        assert_eq!(stats.dropped_for(MessagePriority::Medium), 6);
+        assert_eq!(queue.pop().unwrap().priority, MessagePriority::Critical);
+    }
+
+    #[test]
+    fn test_emergency_evicts_newest_low_when_full() {
+        let mut queue: PriorityQueue<3> = PriorityQueue::new();
+        queue
+            .push(create_test_message(MessagePriority::Low, 1))
+            .unwrap();
+        queue
+            .push(create_test_message(MessagePriority::Low, 2))
+            .unwrap();
+        queue
+            .push(create_test_message(MessagePriority::High, 3))
+            .unwrap();
+
+        queue
+            .push(create_test_message(MessagePriority::Emergency, 4))
+            .unwrap();
+
+        let stats = queue.statistics();
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.evicted_for(MessagePriority::Low), 1);
+        assert_eq!(queue.pop().unwrap().id.value(), 4);
+        assert_eq!(queue.pop().unwrap().id.value(), 3);
+        assert_eq!(queue.pop().unwrap().id.value(), 1);
+    }
+
+    #[test]
+    fn test_drop_oldest_replaces_own_priority() {
+        let mut queue: PriorityQueue<2> = PriorityQueue::new();
+        queue
+            .push(create_test_message(MessagePriority::High, 1))
+            .unwrap();
+        queue
+            .push(create_test_message(MessagePriority::High, 2))
+            .unwrap();
+        queue
+            .push(create_test_message(MessagePriority::High, 3))
+            .unwrap();
+
+        assert_eq!(queue.statistics().evicted_for(MessagePriority::High), 1);
+        assert_eq!(queue.pop().unwrap().id.value(), 2);
+        assert_eq!(queue.pop().unwrap().id.value(), 3);
+    }
+
+    #[test]
+    fn test_command_packet_round_trip() {
+        let mut message = create_test_message(MessagePriority::High, 0x4005);
+        message.payload = MessagePayload::Command {
+            command_id: 0x2001,
+            parameters: heapless::Vec::from_slice(&[2, 7]).unwrap(),
+        };
+
+        let packet = message.to_command_packet().unwrap();
+        assert_eq!(packet.header.apid, MessagePriority::High.command_apid());
+        assert_eq!(packet.header.sequence_count, 0x0005); // low 14 bits of the ID
+
+        let fields = decode_command_packet(&packet.to_bytes().unwrap()).unwrap();
+        assert_eq!(fields.priority, MessagePriority::High);
+        assert_eq!(fields.sequence_count, 0x0005);
+        assert_eq!(fields.command_id, 0x2001);
+        assert_eq!(fields.parameters.as_slice(), &[2, 7]);
+    }
+
+    #[test]
+    fn test_command_packet_rejects_corruption_and_non_commands() {
+        let mut message = create_test_message(MessagePriority::Emergency, 1);
+        assert!(message.to_command_packet().is_err()); // Raw payload
+
+        message.payload = MessagePayload::Command {
+            command_id: 0x9999,
+            parameters: heapless::Vec::from_slice(&[1]).unwrap(),
+        };
+        let mut bytes = message.to_command_packet().unwrap().to_bytes().unwrap();
+        bytes[7] ^= 0x01;
+        assert!(decode_command_packet(&bytes).is_err());
+
+        let telemetry = SpacePacket::new(PacketType::Telemetry, 0x001, 1, &[0; 6], None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(decode_command_packet(&telemetry).is_err());
+
+        for priority in [
+            MessagePriority::Low,
+            MessagePriority::Medium,
+            MessagePriority::High,
+            MessagePriority::Critical,
+            MessagePriority::Emergency,
+        ] {
+            assert_eq!(
+                MessagePriority::from_command_apid(priority.command_apid()),
+                Some(priority)
+            );
+        }
+        assert_eq!(MessagePriority::from_command_apid(0x100), None);
+    }
+
+    #[test]
+    fn test_emergency_frame_routing_and_duplicates() {
+        let mut message = create_test_message(MessagePriority::Emergency, 0x0042);
+        message.payload = MessagePayload::Command {
+            command_id: 0x9999,
+            parameters: heapless::Vec::new(),
+        };
+        let emergency = message.to_command_packet().unwrap().to_bytes().unwrap();
+        assert!(is_emergency_frame(&emergency));
+
+        message.priority = MessagePriority::Critical;
+        let critical = message.to_command_packet().unwrap().to_bytes().unwrap();
+        assert!(!is_emergency_frame(&critical));
+
+        let telemetry = SpacePacket::new(PacketType::Telemetry, 0x001, 1, &[0; 6], None)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(!is_emergency_frame(&telemetry));
+        assert!(!is_emergency_frame(&emergency[..3]));
+
+        let mut filter = EmergencyDuplicateFilter::new();
+        let sequence = decode_command_packet(&emergency).unwrap().sequence_count;
+        assert!(!filter.is_repeat(sequence));
+        assert!(filter.accept(sequence));
+        for _ in 1..EMERGENCY_UPLINK_REPEATS {
+            assert!(filter.is_repeat(sequence));
+            assert!(!filter.accept(sequence));
+        }
+        assert!(filter.accept(sequence + 1));
+    }
 }
@@ -0,0 +1,331 @@
+//! Mission phases
+//!
+//! A mission moves through launch and early orbit (LEOP), commissioning,
+//! nominal operations, extended operations and decommissioning, and the
+//! spacecraft should not behave the same in all of them. The ground commands
+//! the phase with [`SpaceCommand::SetMissionPhase`]; the satellite downlinks
+//! it at [`measurement_ids::MISSION_PHASE`] and applies the phase's
+//! [`PhasePolicy`]:
+//!
+//! - **Telemetry rate**: the default collection interval, as a percentage of
+//!   the nominal one. LEOP reports twice as often; extended operations and
+//!   decommissioning report less to spare an ageing power system.
+//! - **Command set**: commands refused in the phase, by command ID. Payload
+//!   operations wait for commissioning, deployments are refused once it is
+//!   over, and decommissioning accepts little beyond passivation.
+//! - **FDIR aggressiveness**: how early fault detection, isolation and
+//!   recovery escalates, as a scale on its error-code thresholds. LEOP
+//!   escalates early; decommissioning tolerates faults rather than let safe
+//!   mode interrupt passivation.
+//!
+//! Emergency commands and the phase command itself are accepted in every
+//! phase, so the ground can always recover the spacecraft or leave a phase
+//! whose restrictions are in the way.
+//!
+//! [`SpaceCommand::SetMissionPhase`]: crate::commands::SpaceCommand::SetMissionPhase
+//! [`measurement_ids::MISSION_PHASE`]: crate::telemetry::measurement_ids::MISSION_PHASE
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: High Priority Operations (ground-commanded phase changes)
+//! - REQ-SF-001: Command Validation (per-phase allowed command sets)
+//! - REQ-NF-004: Fault Tolerance (per-phase FDIR escalation thresholds)
+
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::wire;
+
+/// Command ID of [`SpaceCommand::SetMissionPhase`]
+pub const SET_MISSION_PHASE_COMMAND_ID: u32 = 0x0026;
+
+/// Highest emergency command ID; emergency commands are never refused
+const LAST_EMERGENCY_COMMAND_ID: u32 = 0x000F;
+
+/// Commands refused during LEOP: orbit changes and payload operations
+const LEOP_REFUSED: &[u32] = &[
+    0x0020, // UpdateOrbit
+    0x0023, // StartDataCollection
+    0x0032, // CalibrateInstrument
+    0x0033, // ScheduleOperation
+];
+
+/// Commands refused once commissioning is over: deployments
+const OPERATIONS_REFUSED: &[u32] = &[
+    0x0022, // Deploy
+];
+
+/// Commands refused during decommissioning: deployments and payload
+/// operations
+const DECOMMISSIONING_REFUSED: &[u32] = &[
+    0x0022, // Deploy
+    0x0023, // StartDataCollection
+    0x0032, // CalibrateInstrument
+    0x0033, // ScheduleOperation
+    0x0034, // StoreData
+];
+
+/// Phase of the mission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum MissionPhase {
+    /// Launch and early orbit: separation, detumble, first acquisition
+    Leop = 0,
+    /// Checkout of the platform and payload
+    Commissioning = 1,
+    /// Routine operations within the design life
+    NominalOps = 2,
+    /// Operations beyond the design life
+    Extended = 3,
+    /// Deorbit and passivation
+    Decommissioning = 4,
+}
+
+impl MissionPhase {
+    /// Every phase, in mission order
+    pub const ALL: [MissionPhase; 5] = [
+        MissionPhase::Leop,
+        MissionPhase::Commissioning,
+        MissionPhase::NominalOps,
+        MissionPhase::Extended,
+        MissionPhase::Decommissioning,
+    ];
+
+    /// Phase of a telemetry code, `None` for an unknown code
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(MissionPhase::Leop),
+            1 => Some(MissionPhase::Commissioning),
+            2 => Some(MissionPhase::NominalOps),
+            3 => Some(MissionPhase::Extended),
+            4 => Some(MissionPhase::Decommissioning),
+            _ => None,
+        }
+    }
+
+    /// Short name for logs and displays
+    pub const fn name(&self) -> &'static str {
+        match self {
+            MissionPhase::Leop => "LEOP",
+            MissionPhase::Commissioning => "Commissioning",
+            MissionPhase::NominalOps => "Nominal Ops",
+            MissionPhase::Extended => "Extended",
+            MissionPhase::Decommissioning => "Decommissioning",
+        }
+    }
+
+    /// Behaviour the spacecraft adopts in this phase
+    pub const fn policy(&self) -> PhasePolicy {
+        match self {
+            MissionPhase::Leop => PhasePolicy {
+                telemetry_interval_percent: 50,
+                refused_commands: LEOP_REFUSED,
+                fdir: FdirAggressiveness::Aggressive,
+            },
+            MissionPhase::Commissioning => PhasePolicy {
+                telemetry_interval_percent: 100,
+                refused_commands: &[],
+                fdir: FdirAggressiveness::Aggressive,
+            },
+            MissionPhase::NominalOps => PhasePolicy {
+                telemetry_interval_percent: 100,
+                refused_commands: OPERATIONS_REFUSED,
+                fdir: FdirAggressiveness::Standard,
+            },
+            MissionPhase::Extended => PhasePolicy {
+                telemetry_interval_percent: 200,
+                refused_commands: OPERATIONS_REFUSED,
+                fdir: FdirAggressiveness::Standard,
+            },
+            MissionPhase::Decommissioning => PhasePolicy {
+                telemetry_interval_percent: 500,
+                refused_commands: DECOMMISSIONING_REFUSED,
+                fdir: FdirAggressiveness::Tolerant,
+            },
+        }
+    }
+
+    /// Phase commanded in a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a phase
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != SET_MISSION_PHASE_COMMAND_ID {
+            return None;
+        }
+        let phase = match serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..]) {
+            Ok(SpaceCommand::SetMissionPhase { phase }) => Ok(phase),
+            _ => Err(SpaceCommError::invalid_packet(
+                "Malformed mission phase command",
+                Some(command_id),
+            )),
+        };
+        Some(phase)
+    }
+
+    /// Check an uplinked command's data field against the phase's command
+    /// set
+    ///
+    /// Data too short to hold a command ID passes; the command dispatcher
+    /// rejects it.
+    pub fn check_command_data(&self, data: &[u8]) -> Result<()> {
+        match wire::command_id(data) {
+            Some(command_id) if !self.policy().allows(command_id) => {
+                Err(SpaceCommError::ConfigurationError {
+                    parameter: "mission_phase",
+                    value: self.name(),
+                    reason: "Command not allowed in this mission phase",
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for MissionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How early FDIR escalates a fault to a stronger recovery action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FdirAggressiveness {
+    /// Escalate at half the nominal error-code thresholds
+    Aggressive,
+    /// Escalate at the nominal thresholds
+    Standard,
+    /// Escalate at one and a half times the nominal thresholds
+    Tolerant,
+}
+
+impl FdirAggressiveness {
+    /// Error-code threshold to apply in place of `nominal`
+    pub const fn threshold(&self, nominal: u32) -> u32 {
+        match self {
+            FdirAggressiveness::Aggressive => nominal / 2,
+            FdirAggressiveness::Standard => nominal,
+            FdirAggressiveness::Tolerant => nominal.saturating_add(nominal / 2),
+        }
+    }
+}
+
+/// Behaviour of the spacecraft in one mission phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhasePolicy {
+    /// Default telemetry collection interval, percent of nominal
+    pub telemetry_interval_percent: u64,
+    /// Command IDs refused in the phase
+    pub refused_commands: &'static [u32],
+    /// FDIR escalation thresholds
+    pub fdir: FdirAggressiveness,
+}
+
+impl PhasePolicy {
+    /// Telemetry collection interval given the nominal interval, at least
+    /// 1 ms
+    pub const fn telemetry_interval_ms(&self, nominal_ms: u64) -> u64 {
+        let interval_ms = nominal_ms * self.telemetry_interval_percent / 100;
+        if interval_ms == 0 {
+            1
+        } else {
+            interval_ms
+        }
+    }
+
+    /// Whether a command is accepted in the phase
+    ///
+    /// Emergency commands and [`SET_MISSION_PHASE_COMMAND_ID`] always are.
+    pub fn allows(&self, command_id: u32) -> bool {
+        command_id <= LAST_EMERGENCY_COMMAND_ID
+            || command_id == SET_MISSION_PHASE_COMMAND_ID
+            || !self.refused_commands.contains(&command_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{DataType, StorageLocation};
+
+    fn command_data(command: &SpaceCommand) -> Vec<u8> {
+        let mut data = Vec::from(command.discriminant().to_be_bytes());
+        data.extend(serde_json::to_vec(command).unwrap());
+        data
+    }
+
+    #[test]
+    fn test_phase_command_roundtrip() {
+        let command = SpaceCommand::SetMissionPhase {
+            phase: MissionPhase::Extended,
+        };
+        assert_eq!(command.discriminant(), SET_MISSION_PHASE_COMMAND_ID);
+
+        let mut data = command_data(&command);
+        assert_eq!(
+            MissionPhase::from_command_data(&data).unwrap().unwrap(),
+            MissionPhase::Extended
+        );
+        data.truncate(10);
+        assert!(MissionPhase::from_command_data(&data).unwrap().is_err());
+        assert!(MissionPhase::from_command_data(&[0, 0, 0, 0x37, 2]).is_none());
+
+        for phase in MissionPhase::ALL {
+            assert_eq!(MissionPhase::from_code(phase as u8), Some(phase));
+        }
+        assert_eq!(MissionPhase::from_code(5), None);
+    }
+
+    #[test]
+    fn test_policies_shape_rates_commands_and_fdir() {
+        let leop = MissionPhase::Leop.policy();
+        let nominal = MissionPhase::NominalOps.policy();
+        let decommissioning = MissionPhase::Decommissioning.policy();
+
+        assert_eq!(leop.telemetry_interval_ms(100), 50);
+        assert_eq!(nominal.telemetry_interval_ms(100), 100);
+        assert_eq!(decommissioning.telemetry_interval_ms(100), 500);
+        assert_eq!(leop.telemetry_interval_ms(1), 1);
+
+        // Deployments belong to LEOP; payload operations wait for checkout
+        let deploy = 0x0022;
+        let collect = 0x0023;
+        assert!(leop.allows(deploy) && !leop.allows(collect));
+        assert!(!nominal.allows(deploy) && nominal.allows(collect));
+        assert!(MissionPhase::Commissioning.policy().allows(deploy));
+
+        // Emergency commands and phase changes pass everywhere
+        for phase in MissionPhase::ALL {
+            let policy = phase.policy();
+            assert!(policy.allows(0x0003));
+            assert!(policy.allows(SET_MISSION_PHASE_COMMAND_ID));
+        }
+
+        let data = command_data(&SpaceCommand::StoreData {
+            data_type: DataType::Science,
+            storage_location: StorageLocation::NonVolatileMemory,
+            compression_level: 3,
+            encryption: false,
+            science_value: 0,
+        });
+        assert_eq!(
+            MissionPhase::Decommissioning.check_command_data(&data),
+            Err(SpaceCommError::ConfigurationError {
+                parameter: "mission_phase",
+                value: "Decommissioning",
+                reason: "Command not allowed in this mission phase",
+            })
+        );
+        assert!(MissionPhase::NominalOps.check_command_data(&data).is_ok());
+        assert!(MissionPhase::Leop.check_command_data(&[0, 1]).is_ok());
+
+        assert_eq!(leop.fdir.threshold(900), 450);
+        assert_eq!(nominal.fdir.threshold(900), 900);
+        assert_eq!(decommissioning.fdir.threshold(900), 1350);
+    }
+}
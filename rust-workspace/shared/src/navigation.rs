@@ -0,0 +1,894 @@
+//! Onboard navigation with a GPS-denied fallback
+//!
+//! The [`Navigator`] takes its orbit from the GPS receiver while the
+//! receiver has a fix. When the receiver reports an outage it keeps
+//! navigating on propagated orbit knowledge: the last solution is carried
+//! forward under two-body gravity, and its covariance grows with the time
+//! since the fix, from the fix's velocity error and an unmodeled
+//! acceleration (drag, solar pressure, gravity harmonics). Once the position
+//! uncertainty passes [`NavigationConfig::max_position_sigma_m`] the solution
+//! is reported as lost.
+//!
+//! | Mode                          | Position source   | Telemetry quality |
+//! |-------------------------------|-------------------|-------------------|
+//! | [`NavigationMode::Gps`]        | GPS fix           | By limits         |
+//! | [`NavigationMode::Propagated`] | Propagated orbit  | `Suspect` at best |
+//! | [`NavigationMode::Lost`]       | None usable       | `Invalid`         |
+//!
+//! The solution is downlinked on its own APID like the crosslink ranging
+//! measurement, and onboard consumers react to its uncertainty: the
+//! [`PointingDeadband`] widens with it, and flight rules can limit it through
+//! [`Parameter::PositionUncertainty`](crate::flight_rules::Parameter::PositionUncertainty).
+//!
+//! The simulated [`GpsReceiver`] flies the true orbit with a small drag
+//! deceleration the navigator does not model, so propagated knowledge really
+//! drifts from the truth during an outage.
+//!
+//! Measurement IDs from [`measurement_ids::NAVIGATION_BASE`]:
+//!
+//! | Offset | Field                              | Unit |
+//! |--------|------------------------------------|------|
+//! | 0x0    | Navigation mode                    |      |
+//! | 0x1    | Position x (Earth-centred inertial)| m    |
+//! | 0x2    | Position y                         | m    |
+//! | 0x3    | Position z                         | m    |
+//! | 0x4    | Position uncertainty, 1-sigma RSS  | m    |
+//! | 0x5    | Time since the last GPS fix        | s    |
+//!
+//! # Requirements Traceability
+//! - REQ-NF-001: System Health Monitoring (navigation state and quality
+//!   telemetry)
+//! - REQ-NF-004: Fault tolerance (propagated navigation through a GPS
+//!   outage)
+//! - REQ-PF-002: Precision orbital mechanics calculations (orbit and
+//!   covariance propagation)
+
+use serde::{Deserialize, Serialize};
+use space_comms_req::req;
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::orbit::EARTH_MU_KM3_S2;
+use crate::sensor_model::NoiseSource;
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    QualityLimits, TelemetryData,
+};
+
+/// APID of the navigation solution packet, after the COP-1 CLCW
+pub const NAVIGATION_APID: u16 = 0x105;
+
+/// Navigation reporting period, ms
+pub const NAVIGATION_PERIOD_MS: u64 = 1_000;
+
+/// Earth gravitational parameter, m³/s²
+const EARTH_MU_M3_S2: f64 = EARTH_MU_KM3_S2 * 1e9;
+
+/// Longest orbit propagation step, s
+const MAX_STEP_S: f64 = 10.0;
+
+/// Source of the navigation solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NavigationMode {
+    /// Orbit from a current GPS fix
+    Gps,
+    /// GPS outage: orbit propagated from the last fix
+    Propagated,
+    /// No fix yet, or propagated too long to be used
+    Lost,
+}
+
+impl NavigationMode {
+    /// Telemetry code of the mode
+    pub const fn code(self) -> u8 {
+        match self {
+            NavigationMode::Gps => 0,
+            NavigationMode::Propagated => 1,
+            NavigationMode::Lost => 2,
+        }
+    }
+
+    /// Mode from its telemetry code; unknown codes decode as `Lost`
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            0 => NavigationMode::Gps,
+            1 => NavigationMode::Propagated,
+            _ => NavigationMode::Lost,
+        }
+    }
+
+    /// Best quality a position reported in this mode can carry
+    ///
+    /// Propagated knowledge is degraded and reads `Suspect` even within
+    /// limits.
+    pub const fn position_quality(self) -> MeasurementQuality {
+        match self {
+            NavigationMode::Gps => MeasurementQuality::Good,
+            NavigationMode::Propagated => MeasurementQuality::Suspect,
+            NavigationMode::Lost => MeasurementQuality::Invalid,
+        }
+    }
+}
+
+/// Field of the navigation solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NavigationField {
+    /// [`NavigationMode`] code
+    Mode,
+    /// Position along the inertial x axis, m
+    PositionX,
+    /// Position along the inertial y axis, m
+    PositionY,
+    /// Position along the inertial z axis, m
+    PositionZ,
+    /// Position uncertainty, 1-sigma root sum square of the axes, m
+    PositionSigma,
+    /// Time since the last GPS fix, s
+    SinceFix,
+}
+
+impl NavigationField {
+    /// Every field in measurement ID order
+    pub const ALL: [NavigationField; 6] = [
+        NavigationField::Mode,
+        NavigationField::PositionX,
+        NavigationField::PositionY,
+        NavigationField::PositionZ,
+        NavigationField::PositionSigma,
+        NavigationField::SinceFix,
+    ];
+
+    /// Measurement ID of this field
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::NAVIGATION_BASE + self as u16
+    }
+
+    /// Field a measurement ID belongs to, if it is a navigation ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.measurement_id() == measurement_id)
+    }
+
+    /// Display name
+    pub const fn name(self) -> &'static str {
+        match self {
+            NavigationField::Mode => "Navigation mode",
+            NavigationField::PositionX => "Position x",
+            NavigationField::PositionY => "Position y",
+            NavigationField::PositionZ => "Position z",
+            NavigationField::PositionSigma => "Position sigma",
+            NavigationField::SinceFix => "Time since GPS fix",
+        }
+    }
+
+    /// Engineering unit
+    pub const fn unit(self) -> &'static str {
+        match self {
+            NavigationField::Mode => "",
+            NavigationField::PositionX
+            | NavigationField::PositionY
+            | NavigationField::PositionZ
+            | NavigationField::PositionSigma => "m",
+            NavigationField::SinceFix => "s",
+        }
+    }
+
+    /// Limit definition for this field
+    ///
+    /// Positions are expected within low Earth orbit; an uncertainty above
+    /// that of a GPS fix, or a fix older than a few periods, is flagged.
+    pub const fn limits(self) -> QualityLimits {
+        let (valid_min, valid_max, expected_min, expected_max) = match self {
+            NavigationField::Mode => (0.0, 2.0, 0.0, 0.0),
+            NavigationField::PositionX
+            | NavigationField::PositionY
+            | NavigationField::PositionZ => {
+                (-50_000_000.0, 50_000_000.0, -8_500_000.0, 8_500_000.0)
+            }
+            NavigationField::PositionSigma => (0.0, 1e9, 0.0, 100.0),
+            NavigationField::SinceFix => (0.0, 1e9, 0.0, 10.0),
+        };
+        QualityLimits {
+            valid_min,
+            valid_max,
+            expected_min,
+            expected_max,
+        }
+    }
+
+    /// Dictionary entries covering the solution: mode, position and
+    /// uncertainty, and fix age
+    pub const fn dictionary_entries() -> [DictionaryEntry; 3] {
+        [
+            DictionaryEntry {
+                first_id: NavigationField::Mode.measurement_id(),
+                last_id: NavigationField::Mode.measurement_id(),
+                name: "Navigation mode",
+                unit: NavigationField::Mode.unit(),
+                period_ms: NAVIGATION_PERIOD_MS,
+            },
+            DictionaryEntry {
+                first_id: NavigationField::PositionX.measurement_id(),
+                last_id: NavigationField::PositionSigma.measurement_id(),
+                name: "Navigation position",
+                unit: NavigationField::PositionX.unit(),
+                period_ms: NAVIGATION_PERIOD_MS,
+            },
+            DictionaryEntry {
+                first_id: NavigationField::SinceFix.measurement_id(),
+                last_id: NavigationField::SinceFix.measurement_id(),
+                name: "Time since GPS fix",
+                unit: NavigationField::SinceFix.unit(),
+                period_ms: NAVIGATION_PERIOD_MS,
+            },
+        ]
+    }
+}
+
+/// Navigation solution at one instant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NavigationSolution {
+    /// Source of the solution
+    pub mode: NavigationMode,
+    /// Earth-centred inertial position, m
+    pub position_m: [f64; 3],
+    /// Position uncertainty, 1-sigma root sum square of the axes, m
+    pub position_sigma_m: f64,
+    /// Time since the last GPS fix, s
+    pub since_fix_s: f64,
+}
+
+impl NavigationSolution {
+    /// Solution reported before the first GPS fix
+    pub const NO_FIX: Self = Self {
+        mode: NavigationMode::Lost,
+        position_m: [f64::NAN; 3],
+        position_sigma_m: f64::NAN,
+        since_fix_s: f64::NAN,
+    };
+
+    /// Value of one field
+    pub fn value(&self, field: NavigationField) -> f64 {
+        match field {
+            NavigationField::Mode => f64::from(self.mode.code()),
+            NavigationField::PositionX => self.position_m[0],
+            NavigationField::PositionY => self.position_m[1],
+            NavigationField::PositionZ => self.position_m[2],
+            NavigationField::PositionSigma => self.position_sigma_m,
+            NavigationField::SinceFix => self.since_fix_s,
+        }
+    }
+
+    /// Append one measurement per field
+    ///
+    /// - **ID**: FN-NAV-001
+    /// - **Requirement**: Report the navigation mode with the solution, and
+    ///   flag a position that is not from a current fix (REQ-NF-001).
+    /// - **Outputs**: Position and uncertainty are flagged against their
+    ///   limits and no better than [`NavigationMode::position_quality`]; the
+    ///   mode is always `Good`.
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold all six
+    ///   measurements; measurements pushed before the overflow remain.
+    #[req("REQ-NF-001")]
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for field in NavigationField::ALL {
+            let value = self.value(field);
+            let (value, quality) = match field {
+                NavigationField::Mode => (
+                    MeasurementValue::Integer(i64::from(self.mode.code())),
+                    MeasurementQuality::Good,
+                ),
+                NavigationField::SinceFix => (
+                    MeasurementValue::Float(value),
+                    field.limits().classify(value),
+                ),
+                _ => (
+                    MeasurementValue::Float(value),
+                    field
+                        .limits()
+                        .classify(value)
+                        .worst(self.mode.position_quality()),
+                ),
+            };
+            data.measurements
+                .push(Measurement {
+                    measurement_id: field.measurement_id(),
+                    value,
+                    unit: field.unit(),
+                    quality,
+                })
+                .map_err(|_| {
+                    SpaceCommError::memory_error(
+                        MemoryErrorType::BufferOverflow,
+                        Some(data.measurements.len()),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the solution from downlinked measurements
+    ///
+    /// Returns `None` unless every field is present.
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let find = |field: NavigationField| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == field.measurement_id())
+                .map(|m| &m.value)
+        };
+        let value = |field: NavigationField| match find(field)? {
+            MeasurementValue::Float(v) => Some(*v),
+            _ => None,
+        };
+        let mode = match find(NavigationField::Mode)? {
+            MeasurementValue::Integer(code) => {
+                NavigationMode::from_code(u8::try_from(*code).unwrap_or(u8::MAX))
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            mode,
+            position_m: [
+                value(NavigationField::PositionX)?,
+                value(NavigationField::PositionY)?,
+                value(NavigationField::PositionZ)?,
+            ],
+            position_sigma_m: value(NavigationField::PositionSigma)?,
+            since_fix_s: value(NavigationField::SinceFix)?,
+        })
+    }
+}
+
+/// Navigation solution carried by a frame, if it is complete
+pub fn decode_navigation(data: &TelemetryData) -> Option<NavigationSolution> {
+    NavigationSolution::from_measurements(&data.measurements)
+}
+
+/// Position and velocity in the Earth-centred inertial frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrbitState {
+    /// Position, m
+    pub position_m: [f64; 3],
+    /// Velocity, m/s
+    pub velocity_m_s: [f64; 3],
+}
+
+impl OrbitState {
+    /// Distance from the centre of the Earth, m
+    pub fn radius_m(&self) -> f64 {
+        norm(self.position_m)
+    }
+
+    /// Advance by `dt_s` under two-body gravity and a deceleration of
+    /// `drag_m_s2` against the velocity
+    ///
+    /// Fourth-order Runge-Kutta in steps of at most ten seconds.
+    pub fn propagate(&mut self, dt_s: f64, drag_m_s2: f64) {
+        let derivative = |s: [f64; 6]| {
+            let position = [s[0], s[1], s[2]];
+            let velocity = [s[3], s[4], s[5]];
+            let r = norm(position);
+            let speed = norm(velocity);
+            let gravity = -EARTH_MU_M3_S2 / (r * r * r);
+            let drag = if speed > 0.0 { -drag_m_s2 / speed } else { 0.0 };
+            [
+                s[3],
+                s[4],
+                s[5],
+                gravity * s[0] + drag * s[3],
+                gravity * s[1] + drag * s[4],
+                gravity * s[2] + drag * s[5],
+            ]
+        };
+        let offset = |s: [f64; 6], d: [f64; 6], h: f64| {
+            let mut out = s;
+            for (o, d) in out.iter_mut().zip(d) {
+                *o += d * h;
+            }
+            out
+        };
+
+        let steps = (dt_s / MAX_STEP_S).ceil().max(1.0) as u32;
+        let h = dt_s / f64::from(steps);
+        let mut s = [
+            self.position_m[0],
+            self.position_m[1],
+            self.position_m[2],
+            self.velocity_m_s[0],
+            self.velocity_m_s[1],
+            self.velocity_m_s[2],
+        ];
+        for _ in 0..steps {
+            let k1 = derivative(s);
+            let k2 = derivative(offset(s, k1, h / 2.0));
+            let k3 = derivative(offset(s, k2, h / 2.0));
+            let k4 = derivative(offset(s, k3, h));
+            for i in 0..6 {
+                s[i] += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+            }
+        }
+        self.position_m = [s[0], s[1], s[2]];
+        self.velocity_m_s = [s[3], s[4], s[5]];
+    }
+}
+
+/// One GPS receiver solution
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GpsFix {
+    /// Measured orbit
+    pub state: OrbitState,
+    /// Position error per axis, 1-sigma, m
+    pub position_sigma_m: f64,
+    /// Velocity error per axis, 1-sigma, m/s
+    pub velocity_sigma_m_s: f64,
+}
+
+/// Error model of the GPS receiver's single-point solution
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GpsNoise {
+    /// Position error per axis, 1-sigma, m
+    pub position_sigma_m: f64,
+    /// Velocity error per axis, 1-sigma, m/s
+    pub velocity_sigma_m_s: f64,
+}
+
+impl Default for GpsNoise {
+    /// Spaceborne L1 receiver: 5 m and 5 cm/s per axis
+    fn default() -> Self {
+        Self {
+            position_sigma_m: 5.0,
+            velocity_sigma_m_s: 0.05,
+        }
+    }
+}
+
+/// Window during which the GPS receiver has no fix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpsOutage {
+    /// Start of the outage, milliseconds since boot
+    pub from_ms: u64,
+    /// Length of the outage, milliseconds
+    pub duration_ms: u64,
+}
+
+impl GpsOutage {
+    /// Whether the receiver is out at `elapsed_ms` since boot
+    pub fn covers(&self, elapsed_ms: u64) -> bool {
+        elapsed_ms >= self.from_ms && elapsed_ms - self.from_ms < self.duration_ms
+    }
+}
+
+/// Simulated GPS receiver on the true orbit
+#[derive(Debug, Clone)]
+pub struct GpsReceiver {
+    truth: OrbitState,
+    drag_m_s2: f64,
+    noise: GpsNoise,
+    outage: Option<GpsOutage>,
+    source: NoiseSource,
+}
+
+impl GpsReceiver {
+    /// Receiver on a true orbit starting at `truth`, decelerated by
+    /// `drag_m_s2`, with noise seeded by `seed`
+    pub const fn new(truth: OrbitState, drag_m_s2: f64, noise: GpsNoise, seed: u32) -> Self {
+        Self {
+            truth,
+            drag_m_s2,
+            noise,
+            outage: None,
+            source: NoiseSource::new(seed),
+        }
+    }
+
+    /// True orbit
+    pub const fn truth(&self) -> OrbitState {
+        self.truth
+    }
+
+    /// Inject an outage, or clear it with `None`
+    pub fn set_outage(&mut self, outage: Option<GpsOutage>) {
+        self.outage = outage;
+    }
+
+    /// Advance the true orbit by `dt_s`
+    pub fn advance(&mut self, dt_s: f64) {
+        self.truth.propagate(dt_s, self.drag_m_s2);
+    }
+
+    /// Fix at `elapsed_ms` since boot
+    ///
+    /// - **ID**: FN-NAV-002
+    /// - **Requirement**: The simulated receiver reports the true orbit with
+    ///   its noise, and nothing during an outage (REQ-NF-004).
+    /// - **Outputs**: `None` while an outage covers `elapsed_ms`.
+    /// - **Side Effects**: Advances the noise source when a fix is made.
+    #[req("REQ-NF-004")]
+    pub fn fix(&mut self, elapsed_ms: u64) -> Option<GpsFix> {
+        if self.outage.is_some_and(|outage| outage.covers(elapsed_ms)) {
+            return None;
+        }
+        let mut state = self.truth;
+        for axis in 0..3 {
+            state.position_m[axis] +=
+                self.noise.position_sigma_m * f64::from(self.source.next_gaussian());
+            state.velocity_m_s[axis] +=
+                self.noise.velocity_sigma_m_s * f64::from(self.source.next_gaussian());
+        }
+        Some(GpsFix {
+            state,
+            position_sigma_m: self.noise.position_sigma_m,
+            velocity_sigma_m_s: self.noise.velocity_sigma_m_s,
+        })
+    }
+}
+
+/// Settings of the navigator
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NavigationConfig {
+    /// Spectral density of the unmodeled acceleration per axis, m²/s³
+    pub process_noise_m2_s3: f64,
+    /// Position uncertainty (1-sigma RSS) beyond which a propagated
+    /// solution is reported as lost, m
+    pub max_position_sigma_m: f64,
+}
+
+impl NavigationConfig {
+    /// Default settings, usable in a `const` context
+    pub const DEFAULT: Self = Self {
+        process_noise_m2_s3: 1e-8,
+        max_position_sigma_m: 10_000.0,
+    };
+}
+
+impl Default for NavigationConfig {
+    /// Unmodeled acceleration of 1e-8 m²/s³, lost beyond 10 km
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Onboard navigator falling back to propagated orbit knowledge
+///
+/// - **ID**: MOD-NAV-001
+/// - **Requirement**: Keep a position solution, with an honest uncertainty,
+///   through a GPS outage (REQ-NF-004, REQ-PF-002).
+/// - **Purpose**: Let pointing and autonomy degrade gracefully instead of
+///   failing the moment the receiver loses its fix.
+/// - **Failure Modes**: None; a solution that can no longer be used is
+///   reported as [`NavigationMode::Lost`].
+/// - **Constraints**: No heap allocation.
+///
+/// The covariance is kept per axis as the position variance, the
+/// position-velocity covariance and the velocity variance, which a white
+/// acceleration noise grows between fixes; the axes share one set of values.
+#[derive(Debug, Clone)]
+pub struct Navigator {
+    config: NavigationConfig,
+    estimate: Option<OrbitState>,
+    covariance: [f64; 3],
+    updated_ms: u64,
+    fix_ms: u64,
+    mode: NavigationMode,
+}
+
+impl Navigator {
+    /// Navigator with no solution until the first fix
+    pub const fn new(config: NavigationConfig) -> Self {
+        Self {
+            config,
+            estimate: None,
+            covariance: [0.0; 3],
+            updated_ms: 0,
+            fix_ms: 0,
+            mode: NavigationMode::Lost,
+        }
+    }
+
+    /// Settings in use
+    pub const fn config(&self) -> &NavigationConfig {
+        &self.config
+    }
+
+    /// Current orbit estimate, if there has been a fix
+    pub const fn estimate(&self) -> Option<OrbitState> {
+        self.estimate
+    }
+
+    /// Position uncertainty, 1-sigma root sum square of the axes, m
+    pub fn position_sigma_m(&self) -> f64 {
+        (3.0 * self.covariance[0]).sqrt()
+    }
+
+    /// Bring the solution up to `now_ms` with the receiver's fix, if any
+    ///
+    /// - **ID**: FN-NAV-003
+    /// - **Requirement**: Navigate on GPS while it has a fix, on propagated
+    ///   orbit knowledge with growing uncertainty while it does not, and
+    ///   report a solution too uncertain to use as lost (REQ-NF-004).
+    /// - **Inputs**: Time since boot, and the fix made at that time or
+    ///   `None` during an outage.
+    /// - **Outputs**: The solution in its new mode.
+    /// - **Side Effects**: Propagates the estimate and its covariance from
+    ///   the previous update; a fix replaces both.
+    #[req("REQ-NF-004", "REQ-PF-002")]
+    pub fn update(&mut self, now_ms: u64, fix: Option<GpsFix>) -> NavigationSolution {
+        let dt_s = now_ms.saturating_sub(self.updated_ms) as f64 / 1_000.0;
+        self.updated_ms = self.updated_ms.max(now_ms);
+
+        if let Some(estimate) = self.estimate.as_mut() {
+            estimate.propagate(dt_s, 0.0);
+            let q = self.config.process_noise_m2_s3;
+            let [pp, pv, vv] = self.covariance;
+            self.covariance = [
+                pp + 2.0 * pv * dt_s + vv * dt_s * dt_s + q * dt_s * dt_s * dt_s / 3.0,
+                pv + vv * dt_s + q * dt_s * dt_s / 2.0,
+                vv + q * dt_s,
+            ];
+        }
+
+        self.mode = match fix {
+            Some(fix) => {
+                self.estimate = Some(fix.state);
+                self.covariance = [
+                    fix.position_sigma_m * fix.position_sigma_m,
+                    0.0,
+                    fix.velocity_sigma_m_s * fix.velocity_sigma_m_s,
+                ];
+                self.fix_ms = now_ms;
+                NavigationMode::Gps
+            }
+            None if self.estimate.is_none()
+                || self.position_sigma_m() > self.config.max_position_sigma_m =>
+            {
+                NavigationMode::Lost
+            }
+            None => NavigationMode::Propagated,
+        };
+        self.solution()
+    }
+
+    /// Solution as of the last update
+    pub fn solution(&self) -> NavigationSolution {
+        match self.estimate {
+            Some(estimate) => NavigationSolution {
+                mode: self.mode,
+                position_m: estimate.position_m,
+                position_sigma_m: self.position_sigma_m(),
+                since_fix_s: self.updated_ms.saturating_sub(self.fix_ms) as f64 / 1_000.0,
+            },
+            None => NavigationSolution::NO_FIX,
+        }
+    }
+}
+
+/// Attitude control deadband widened with navigation uncertainty
+///
+/// Pointing at a ground target needs to know where the spacecraft is: a
+/// position error of sigma seen from a target at the slant range is an
+/// angular error of atan(sigma / range). Holding the attitude tighter than
+/// that only spends actuator effort, so the deadband grows by that angle,
+/// up to a ceiling that also applies once the solution is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PointingDeadband {
+    /// Deadband with a GPS fix, degrees
+    pub nominal_deg: f64,
+    /// Widest deadband, degrees
+    pub max_deg: f64,
+    /// Typical slant range to the pointing target, m
+    pub target_range_m: f64,
+}
+
+impl PointingDeadband {
+    /// Default deadband, usable in a `const` context
+    pub const DEFAULT: Self = Self {
+        nominal_deg: 0.1,
+        max_deg: 2.0,
+        target_range_m: 1_000_000.0,
+    };
+
+    /// Deadband for `solution`, degrees
+    ///
+    /// - **ID**: FN-NAV-004
+    /// - **Requirement**: Widen the pointing deadband as navigation degrades
+    ///   (REQ-NF-004).
+    /// - **Outputs**: The nominal deadband plus the pointing error of the
+    ///   position uncertainty, capped at `max_deg`; `max_deg` when lost.
+    #[req("REQ-NF-004")]
+    pub fn deadband_deg(&self, solution: &NavigationSolution) -> f64 {
+        if solution.mode == NavigationMode::Lost || solution.position_sigma_m.is_nan() {
+            return self.max_deg;
+        }
+        let error_deg = (solution.position_sigma_m / self.target_range_m)
+            .atan()
+            .to_degrees();
+        (self.nominal_deg + error_deg).min(self.max_deg)
+    }
+}
+
+impl Default for PointingDeadband {
+    /// 0.1 degree with GPS, at most 2 degrees, target at 1000 km
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::dictionary_entry;
+    use crate::types::{ComponentId, HealthStatus};
+
+    /// Circular 550 km orbit in the equatorial plane
+    fn leo() -> OrbitState {
+        let radius_m = 6_928_137.0;
+        OrbitState {
+            position_m: [radius_m, 0.0, 0.0],
+            velocity_m_s: [0.0, (EARTH_MU_M3_S2 / radius_m).sqrt(), 0.0],
+        }
+    }
+
+    fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+        norm([a[0] - b[0], a[1] - b[1], a[2] - b[2]])
+    }
+
+    #[test]
+    fn test_circular_orbit_closes() {
+        let start = leo();
+        let mut state = start;
+        let period_s =
+            2.0 * core::f64::consts::PI * (start.radius_m().powi(3) / EARTH_MU_M3_S2).sqrt();
+        state.propagate(period_s, 0.0);
+        assert!(distance(state.position_m, start.position_m) < 1.0);
+        assert!((state.radius_m() - start.radius_m()).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_outage_falls_back_to_propagation() {
+        let mut receiver = GpsReceiver::new(leo(), 1e-5, GpsNoise::default(), 7);
+        let config = NavigationConfig {
+            process_noise_m2_s3: 1e-8,
+            max_position_sigma_m: 250.0,
+        };
+        let mut navigator = Navigator::new(config);
+        let solution = navigator.update(0, None);
+        assert_eq!(solution.mode, NavigationMode::Lost);
+        assert!(solution.position_sigma_m.is_nan());
+
+        let solution = navigator.update(0, receiver.fix(0));
+        assert_eq!(solution.mode, NavigationMode::Gps);
+        assert!((solution.position_sigma_m - 5.0 * 3f64.sqrt()).abs() < 1e-9);
+
+        receiver.set_outage(Some(GpsOutage {
+            from_ms: 60_000,
+            duration_ms: 3_600_000,
+        }));
+        let mut now_ms = 0;
+        let mut sigmas = Vec::new();
+        while now_ms < 1_800_000 {
+            now_ms += 60_000;
+            receiver.advance(60.0);
+            let solution = navigator.update(now_ms, receiver.fix(now_ms));
+            if now_ms >= 60_000 {
+                assert_eq!(solution.mode, NavigationMode::Propagated);
+                sigmas.push(solution.position_sigma_m);
+            }
+        }
+        // Uncertainty grows, and covers the real error of the propagation
+        assert!(sigmas.windows(2).all(|w| w[1] > w[0]));
+        let solution = navigator.solution();
+        assert_eq!(solution.since_fix_s, 1_800.0);
+        let error = distance(solution.position_m, receiver.truth().position_m);
+        assert!(
+            error > 5.0 && error < 3.0 * solution.position_sigma_m,
+            "error {}",
+            error
+        );
+
+        // Past the limit the solution is lost, until the receiver is back
+        while now_ms < 3_600_000 {
+            now_ms += 60_000;
+            receiver.advance(60.0);
+            navigator.update(now_ms, receiver.fix(now_ms));
+        }
+        assert_eq!(navigator.solution().mode, NavigationMode::Lost);
+        now_ms += 60_000;
+        receiver.advance(60.0);
+        assert_eq!(
+            navigator.update(now_ms, receiver.fix(now_ms)).mode,
+            NavigationMode::Gps
+        );
+        assert_eq!(navigator.solution().since_fix_s, 0.0);
+    }
+
+    #[test]
+    fn test_telemetry_flags_degraded_position() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        let fix = NavigationSolution {
+            mode: NavigationMode::Gps,
+            position_m: [6_928_137.0, 0.0, 0.0],
+            position_sigma_m: 8.7,
+            since_fix_s: 0.0,
+        };
+        let propagated = NavigationSolution {
+            mode: NavigationMode::Propagated,
+            since_fix_s: 600.0,
+            ..fix
+        };
+        fix.append_measurements(&mut data).unwrap();
+        assert_eq!(decode_navigation(&data), Some(fix));
+        assert!(data
+            .measurements
+            .iter()
+            .all(|m| m.quality == MeasurementQuality::Good));
+
+        data.measurements.clear();
+        propagated.append_measurements(&mut data).unwrap();
+        assert_eq!(decode_navigation(&data), Some(propagated));
+        let quality = |data: &TelemetryData, field: NavigationField| {
+            data.measurements[field as usize].quality
+        };
+        assert_eq!(
+            quality(&data, NavigationField::Mode),
+            MeasurementQuality::Good
+        );
+        assert_eq!(
+            quality(&data, NavigationField::PositionX),
+            MeasurementQuality::Suspect
+        );
+        assert_eq!(
+            quality(&data, NavigationField::PositionSigma),
+            MeasurementQuality::Suspect
+        );
+
+        data.measurements.clear();
+        NavigationSolution::NO_FIX
+            .append_measurements(&mut data)
+            .unwrap();
+        assert_eq!(
+            quality(&data, NavigationField::PositionY),
+            MeasurementQuality::Invalid
+        );
+
+        for field in NavigationField::ALL {
+            let entry = dictionary_entry(field.measurement_id()).unwrap();
+            assert_eq!(entry.unit, field.unit());
+            assert_eq!(
+                NavigationField::from_measurement_id(field.measurement_id()),
+                Some(field)
+            );
+        }
+    }
+
+    #[test]
+    fn test_deadband_widens_with_uncertainty() {
+        let deadband = PointingDeadband::default();
+        let mut solution = NavigationSolution {
+            mode: NavigationMode::Gps,
+            position_m: [6_928_137.0, 0.0, 0.0],
+            position_sigma_m: 8.7,
+            since_fix_s: 0.0,
+        };
+        assert!((deadband.deadband_deg(&solution) - 0.1).abs() < 0.001);
+
+        solution.mode = NavigationMode::Propagated;
+        solution.position_sigma_m = 10_000.0;
+        let widened = deadband.deadband_deg(&solution);
+        assert!((widened - 0.673).abs() < 0.001, "deadband {}", widened);
+
+        solution.position_sigma_m = 100_000.0;
+        assert_eq!(deadband.deadband_deg(&solution), 2.0);
+        assert_eq!(deadband.deadband_deg(&NavigationSolution::NO_FIX), 2.0);
+    }
+}
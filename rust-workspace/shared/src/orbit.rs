@@ -0,0 +1,670 @@
+//! Orbit decay and end-of-life disposal planning
+//!
+//! A spacecraft left in low Earth orbit at the end of its mission must
+//! re-enter within [`DISPOSAL_LIFETIME_YEARS`] of disposal. This module
+//! estimates orbital lifetime, plans the perigee-lowering burns that bring
+//! it within that limit, checks the propellant they need against what is
+//! left, and turns the plan into a time-tagged [`CommandLoad`].
+//!
+//! - **Decay**: drag from an exponential atmosphere (mean solar activity)
+//!   acts along the velocity; its effect on semi-major axis and
+//!   eccentricity is averaged over each orbit and integrated until perigee
+//!   falls to [`REENTRY_ALTITUDE_KM`].
+//! - **Burns**: retrograde burns at apogee lower perigee and leave apogee
+//!   where it is. The perigee that just meets the lifetime limit is found by
+//!   bisection; a change larger than the per-burn limit is split into equal
+//!   burns, whose velocity changes add up exactly because every burn is made
+//!   at the same apogee.
+//! - **Propellant**: each burn draws propellant by the rocket equation from
+//!   the mass left after the burns before it.
+//!
+//! The command load switches the mission to decommissioning and then
+//! releases one deorbit maneuver per burn, each a whole number of
+//! revolutions after the one before so that every burn falls at apogee.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-003: Critical System Commands (deorbit maneuver sequence)
+//! - REQ-PF-002: Precision orbital mechanics calculations (drag decay,
+//!   burn planning and propellant budget)
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::command_load::{CommandLoad, CommandLoadEntry};
+use crate::commands::{ManeuverType, SpaceCommand};
+use crate::error::{Result, SpaceCommError};
+use crate::mission_phase::MissionPhase;
+
+/// Earth gravitational parameter, km³/s²
+pub const EARTH_MU_KM3_S2: f64 = 398_600.441_8;
+
+/// Earth equatorial radius, km
+pub const EARTH_RADIUS_KM: f64 = 6_378.137;
+
+/// Standard gravity, m/s²
+pub const STANDARD_GRAVITY_M_S2: f64 = 9.806_65;
+
+/// Perigee altitude at which the spacecraft is taken to re-enter, km
+pub const REENTRY_ALTITUDE_KM: f64 = 100.0;
+
+/// Longest orbital lifetime after disposal, years
+pub const DISPOSAL_LIFETIME_YEARS: f64 = 25.0;
+
+/// Lifetimes beyond this are reported as unbounded, years
+pub const LIFETIME_HORIZON_YEARS: f64 = 200.0;
+
+/// Most burns one deorbit plan holds
+pub const MAX_DEORBIT_BURNS: usize = 16;
+
+/// Seconds in a Julian year
+const SECONDS_PER_YEAR: f64 = 365.25 * 86_400.0;
+
+/// Eccentric anomaly samples per orbit when averaging drag
+const ORBIT_SAMPLES: usize = 48;
+
+/// Longest decay integration step, s
+const MAX_STEP_S: f64 = 10.0 * 86_400.0;
+
+/// Largest change of perigee or apogee altitude per decay step, m
+const MAX_STEP_DECAY_M: f64 = 2_000.0;
+
+/// Perigee altitude to which the lifetime bisection is resolved, km
+const PERIGEE_TOLERANCE_KM: f64 = 0.5;
+
+/// Exponential atmosphere: base altitude (km), density at the base (kg/m³)
+/// and scale height (km) of each band
+const ATMOSPHERE: [(f64, f64, f64); 22] = [
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+    (1100.0, 1.564e-15, 268.00),
+];
+
+/// Atmospheric density at an altitude, kg/m³
+///
+/// Below the lowest band the lowest band's profile is extended downwards.
+pub fn atmospheric_density_kg_m3(altitude_km: f64) -> f64 {
+    let (base_km, density, scale_km) = ATMOSPHERE
+        .iter()
+        .rev()
+        .find(|(base_km, _, _)| altitude_km >= *base_km)
+        .copied()
+        .unwrap_or(ATMOSPHERE[0]);
+    density * (-(altitude_km - base_km) / scale_km).exp()
+}
+
+/// Orbit described by its perigee and apogee altitudes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Orbit {
+    /// Perigee altitude, km
+    pub perigee_altitude_km: f64,
+    /// Apogee altitude, km
+    pub apogee_altitude_km: f64,
+}
+
+impl Orbit {
+    /// Orbit between two altitudes, km
+    ///
+    /// Returns an error unless both altitudes are finite and positive and
+    /// perigee is no higher than apogee.
+    pub fn new(perigee_altitude_km: f64, apogee_altitude_km: f64) -> Result<Self> {
+        let valid = perigee_altitude_km > 0.0
+            && perigee_altitude_km <= apogee_altitude_km
+            && apogee_altitude_km.is_finite();
+        if !valid {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "orbit",
+                value: "altitudes",
+                reason: "Perigee must be above the surface and no higher than apogee",
+            });
+        }
+        Ok(Self {
+            perigee_altitude_km,
+            apogee_altitude_km,
+        })
+    }
+
+    /// Circular orbit at an altitude, km
+    pub fn circular(altitude_km: f64) -> Result<Self> {
+        Self::new(altitude_km, altitude_km)
+    }
+
+    /// Perigee radius, km
+    pub fn perigee_radius_km(&self) -> f64 {
+        EARTH_RADIUS_KM + self.perigee_altitude_km
+    }
+
+    /// Apogee radius, km
+    pub fn apogee_radius_km(&self) -> f64 {
+        EARTH_RADIUS_KM + self.apogee_altitude_km
+    }
+
+    /// Semi-major axis, km
+    pub fn semi_major_axis_km(&self) -> f64 {
+        (self.perigee_radius_km() + self.apogee_radius_km()) / 2.0
+    }
+
+    /// Eccentricity
+    pub fn eccentricity(&self) -> f64 {
+        (self.apogee_radius_km() - self.perigee_radius_km())
+            / (self.apogee_radius_km() + self.perigee_radius_km())
+    }
+
+    /// Orbital period, s
+    pub fn period_s(&self) -> f64 {
+        2.0 * core::f64::consts::PI * (self.semi_major_axis_km().powi(3) / EARTH_MU_KM3_S2).sqrt()
+    }
+
+    /// Speed at apogee, m/s
+    pub fn apogee_speed_m_s(&self) -> f64 {
+        let apogee = self.apogee_radius_km();
+        (EARTH_MU_KM3_S2 * (2.0 / apogee - 1.0 / self.semi_major_axis_km())).sqrt() * 1_000.0
+    }
+
+    /// Orbit left by changing the apogee speed to `speed_m_s`
+    fn with_apogee_speed(&self, speed_m_s: f64) -> Self {
+        let apogee = self.apogee_radius_km();
+        let speed = speed_m_s / 1_000.0;
+        let perigee = 2.0 / (2.0 / apogee - speed * speed / EARTH_MU_KM3_S2) - apogee;
+        Self {
+            perigee_altitude_km: perigee - EARTH_RADIUS_KM,
+            apogee_altitude_km: self.apogee_altitude_km,
+        }
+    }
+}
+
+/// Drag properties of the spacecraft
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DragProperties {
+    /// Mean cross-section area facing the flow, m²
+    pub area_m2: f64,
+    /// Drag coefficient
+    pub drag_coefficient: f64,
+}
+
+impl Default for DragProperties {
+    fn default() -> Self {
+        Self {
+            area_m2: 1.0,
+            drag_coefficient: 2.2,
+        }
+    }
+}
+
+/// Orbit-averaged rates of change of semi-major axis (m/s) and
+/// eccentricity (1/s) under drag
+fn decay_rates(semi_major_axis_m: f64, eccentricity: f64, ballistic_m2_kg: f64) -> (f64, f64) {
+    let mu = EARTH_MU_KM3_S2 * 1e9;
+    let (mut da, mut de) = (0.0, 0.0);
+    for sample in 0..ORBIT_SAMPLES {
+        let anomaly = 2.0 * core::f64::consts::PI * (sample as f64 + 0.5) / ORBIT_SAMPLES as f64;
+        let cos_e = anomaly.cos();
+        let radius = semi_major_axis_m * (1.0 - eccentricity * cos_e);
+        let speed_sq = mu * (2.0 / radius - 1.0 / semi_major_axis_m);
+        let speed = speed_sq.max(0.0).sqrt();
+        let density = atmospheric_density_kg_m3(radius / 1_000.0 - EARTH_RADIUS_KM);
+        // Tangential deceleration, and the share of the orbit's time spent here
+        let drag = 0.5 * density * speed_sq * ballistic_m2_kg;
+        let weight = (1.0 - eccentricity * cos_e) / ORBIT_SAMPLES as f64;
+        let cos_true = (cos_e - eccentricity) / (1.0 - eccentricity * cos_e);
+        da -= weight * 2.0 * semi_major_axis_m * semi_major_axis_m * speed * drag / mu;
+        if speed > 0.0 {
+            de -= weight * 2.0 * (eccentricity + cos_true) * drag / speed;
+        }
+    }
+    (da, de)
+}
+
+/// Time for drag to bring an orbit down to [`REENTRY_ALTITUDE_KM`], years
+///
+/// - **ID**: FN-ORB-001
+/// - **Requirement**: Orbital lifetime of a spacecraft of `mass_kg` with
+///   the given drag properties, from orbit-averaged drag decay.
+/// - **Outputs**: Lifetime in years, or `None` beyond
+///   [`LIFETIME_HORIZON_YEARS`].
+pub fn orbital_lifetime_years(orbit: &Orbit, drag: &DragProperties, mass_kg: f64) -> Option<f64> {
+    let ballistic = drag.drag_coefficient * drag.area_m2 / mass_kg;
+    let horizon_s = LIFETIME_HORIZON_YEARS * SECONDS_PER_YEAR;
+    let mut semi_major_axis = orbit.semi_major_axis_km() * 1_000.0;
+    let mut eccentricity = orbit.eccentricity();
+    let mut elapsed_s = 0.0;
+
+    while elapsed_s <= horizon_s {
+        let perigee_altitude_km =
+            semi_major_axis * (1.0 - eccentricity) / 1_000.0 - EARTH_RADIUS_KM;
+        if perigee_altitude_km <= REENTRY_ALTITUDE_KM {
+            return Some(elapsed_s / SECONDS_PER_YEAR);
+        }
+
+        let (da, de) = decay_rates(semi_major_axis, eccentricity, ballistic);
+        let perigee_rate = da * (1.0 - eccentricity) - semi_major_axis * de;
+        let apogee_rate = da * (1.0 + eccentricity) + semi_major_axis * de;
+        let fastest = perigee_rate.abs().max(apogee_rate.abs());
+        let step_s = if fastest > 0.0 {
+            (MAX_STEP_DECAY_M / fastest).min(MAX_STEP_S)
+        } else {
+            MAX_STEP_S
+        };
+
+        semi_major_axis += da * step_s;
+        eccentricity = (eccentricity + de * step_s).max(0.0);
+        elapsed_s += step_s;
+    }
+    None
+}
+
+/// Propulsion system and the propellant left in it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Propulsion {
+    /// Propellant remaining, kg
+    pub propellant_kg: f64,
+    /// Specific impulse, s
+    pub specific_impulse_s: f64,
+    /// Thrust, N
+    pub thrust_n: f64,
+}
+
+impl Propulsion {
+    /// Effective exhaust velocity, m/s
+    pub fn exhaust_velocity_m_s(&self) -> f64 {
+        self.specific_impulse_s * STANDARD_GRAVITY_M_S2
+    }
+
+    /// Propellant a velocity change draws from a spacecraft of
+    /// `initial_mass_kg`, kg
+    pub fn propellant_for_kg(&self, delta_v_m_s: f64, initial_mass_kg: f64) -> f64 {
+        initial_mass_kg * (1.0 - (-delta_v_m_s / self.exhaust_velocity_m_s()).exp())
+    }
+
+    /// Time to burn `propellant_kg` at full thrust, s
+    pub fn burn_duration_s(&self, propellant_kg: f64) -> f64 {
+        propellant_kg * self.exhaust_velocity_m_s() / self.thrust_n
+    }
+
+    /// Velocity change the remaining propellant gives a spacecraft of
+    /// `dry_mass_kg`, m/s
+    pub fn available_delta_v_m_s(&self, dry_mass_kg: f64) -> f64 {
+        self.exhaust_velocity_m_s() * ((dry_mass_kg + self.propellant_kg) / dry_mass_kg).ln()
+    }
+}
+
+/// One retrograde burn at apogee
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Burn {
+    /// Velocity change, m/s
+    pub delta_v_m_s: f64,
+    /// Propellant drawn, kg
+    pub propellant_kg: f64,
+    /// Burn duration at full thrust, s
+    pub duration_s: f64,
+    /// Orbit after the burn
+    pub orbit_after: Orbit,
+}
+
+/// Propellant needed for a plan against propellant available
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PropellantBudget {
+    /// Propellant the burns draw, kg
+    pub required_kg: f64,
+    /// Propellant remaining before the burns, kg
+    pub available_kg: f64,
+    /// Velocity change the remaining propellant gives, m/s
+    pub available_delta_v_m_s: f64,
+}
+
+impl PropellantBudget {
+    /// Propellant left after the burns, negative when short, kg
+    pub fn margin_kg(&self) -> f64 {
+        self.available_kg - self.required_kg
+    }
+
+    /// Whether the propellant covers the burns
+    pub fn is_sufficient(&self) -> bool {
+        self.margin_kg() >= 0.0
+    }
+}
+
+/// Burns bringing an orbit within the disposal lifetime
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeorbitPlan {
+    /// Orbit before disposal
+    pub initial: Orbit,
+    /// Orbit after the last burn
+    pub target: Orbit,
+    /// Lifetime before disposal, years; `None` beyond the horizon
+    pub initial_lifetime_years: Option<f64>,
+    /// Lifetime after the last burn, years
+    pub target_lifetime_years: Option<f64>,
+    /// Total velocity change, m/s
+    pub delta_v_m_s: f64,
+    /// Burns in execution order; empty when the orbit already complies
+    pub burns: Vec<Burn, MAX_DEORBIT_BURNS>,
+    /// Propellant needed against propellant available
+    pub budget: PropellantBudget,
+}
+
+impl DeorbitPlan {
+    /// Command load carrying out the plan
+    ///
+    /// - **ID**: FN-ORB-003
+    /// - **Requirement**: Time-tagged maneuver sequence for the plan: the
+    ///   mission phase set to decommissioning on receipt, then one
+    ///   [`ManeuverType::Deorbit`] maneuver per burn. The first burn is
+    ///   released at `first_burn_unix_s`, which must be an apogee passage;
+    ///   each following burn a whole number of revolutions of the orbit
+    ///   before it, and at least `min_spacing_s`, later.
+    /// - **Outputs**: The load; burn velocity changes are in the local
+    ///   orbital frame, along the velocity first, so a retrograde burn is
+    ///   `[-Δv, 0, 0]`.
+    /// - **Failure Modes**: None in practice: a plan's burns and the phase
+    ///   command fit in one load.
+    pub fn command_load(
+        &self,
+        load_id: u32,
+        first_burn_unix_s: u64,
+        min_spacing_s: u64,
+    ) -> Result<CommandLoad> {
+        let mut load = CommandLoad::new(load_id);
+        load.push(CommandLoadEntry {
+            sequence: 0,
+            release_time: None,
+            sequence_count: 0,
+            command: SpaceCommand::SetMissionPhase {
+                phase: MissionPhase::Decommissioning,
+            },
+        })?;
+
+        let mut release = first_burn_unix_s;
+        let mut orbit = self.initial;
+        for (index, burn) in self.burns.iter().enumerate() {
+            if index > 0 {
+                let period = orbit.period_s();
+                let revolutions = (min_spacing_s as f64 / period).ceil().max(1.0);
+                release += (revolutions * period).round() as u64;
+            }
+            load.push(CommandLoadEntry {
+                sequence: index as u16 + 1,
+                release_time: Some(release),
+                sequence_count: 0,
+                command: SpaceCommand::CollisionAvoidance {
+                    debris_id: 0,
+                    maneuver_type: ManeuverType::Deorbit,
+                    delta_v: [-burn.delta_v_m_s as f32, 0.0, 0.0],
+                    execution_time: release,
+                },
+            })?;
+            orbit = burn.orbit_after;
+        }
+        Ok(load)
+    }
+}
+
+/// Disposal planner for one spacecraft
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DeorbitPlanner {
+    /// Mass without propellant, kg
+    pub dry_mass_kg: f64,
+    /// Drag properties
+    pub drag: DragProperties,
+    /// Propulsion system
+    pub propulsion: Propulsion,
+    /// Largest velocity change of one burn, m/s
+    pub max_burn_delta_v_m_s: f64,
+    /// Lifetime the plan must meet, years
+    pub lifetime_limit_years: f64,
+}
+
+impl DeorbitPlanner {
+    /// Planner for the [`DISPOSAL_LIFETIME_YEARS`] limit with no limit on
+    /// the size of a burn
+    pub fn new(dry_mass_kg: f64, drag: DragProperties, propulsion: Propulsion) -> Self {
+        Self {
+            dry_mass_kg,
+            drag,
+            propulsion,
+            max_burn_delta_v_m_s: f64::INFINITY,
+            lifetime_limit_years: DISPOSAL_LIFETIME_YEARS,
+        }
+    }
+
+    /// Limit the velocity change of one burn, m/s
+    pub fn with_max_burn_delta_v(mut self, max_burn_delta_v_m_s: f64) -> Self {
+        self.max_burn_delta_v_m_s = max_burn_delta_v_m_s;
+        self
+    }
+
+    /// Meet a lifetime limit other than [`DISPOSAL_LIFETIME_YEARS`], years
+    pub fn with_lifetime_limit(mut self, lifetime_limit_years: f64) -> Self {
+        self.lifetime_limit_years = lifetime_limit_years;
+        self
+    }
+
+    /// Mass the lifetime is estimated at: the wet mass, which decays
+    /// slowest, kg
+    fn decay_mass_kg(&self) -> f64 {
+        self.dry_mass_kg + self.propulsion.propellant_kg
+    }
+
+    fn complies(&self, orbit: &Orbit) -> bool {
+        orbital_lifetime_years(orbit, &self.drag, self.decay_mass_kg())
+            .is_some_and(|years| years <= self.lifetime_limit_years)
+    }
+
+    /// Plan the disposal of a spacecraft in `orbit`
+    ///
+    /// - **ID**: FN-ORB-002
+    /// - **Requirement**: Highest perigee, at the current apogee, whose
+    ///   lifetime meets the limit; the retrograde apogee burns reaching it,
+    ///   each within the per-burn limit; and the propellant they draw.
+    /// - **Outputs**: The plan. A plan the propellant does not cover is still
+    ///   returned, flagged by its budget.
+    /// - **Failure Modes**: `ConfigurationError` if the burn limit is not
+    ///   positive, or if the plan needs more than [`MAX_DEORBIT_BURNS`]
+    ///   burns.
+    pub fn plan(&self, orbit: &Orbit) -> Result<DeorbitPlan> {
+        if self.max_burn_delta_v_m_s.is_nan() || self.max_burn_delta_v_m_s <= 0.0 {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "max_burn_delta_v",
+                value: "not positive",
+                reason: "Burns must be allowed some velocity change",
+            });
+        }
+
+        let target = if self.complies(orbit) {
+            *orbit
+        } else {
+            // Lowest perigee meets the limit at once; bisect towards the highest
+            let (mut low, mut high) = (REENTRY_ALTITUDE_KM, orbit.perigee_altitude_km);
+            while high - low > PERIGEE_TOLERANCE_KM {
+                let middle = (low + high) / 2.0;
+                let candidate = Orbit {
+                    perigee_altitude_km: middle,
+                    ..*orbit
+                };
+                if self.complies(&candidate) {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            Orbit {
+                perigee_altitude_km: low,
+                ..*orbit
+            }
+        };
+
+        let delta_v = orbit.apogee_speed_m_s() - target.apogee_speed_m_s();
+        let burn_count = if delta_v > 0.0 {
+            ((delta_v / self.max_burn_delta_v_m_s).ceil() as usize).max(1)
+        } else {
+            0
+        };
+        if burn_count > MAX_DEORBIT_BURNS {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "max_burn_delta_v",
+                value: "too small",
+                reason: "Deorbit needs more burns than a plan holds",
+            });
+        }
+
+        let mut burns = Vec::new();
+        let mut mass = self.decay_mass_kg();
+        let mut required = 0.0;
+        for index in 1..=burn_count {
+            let burn_delta_v = delta_v / burn_count as f64;
+            let propellant = self.propulsion.propellant_for_kg(burn_delta_v, mass);
+            let orbit_after = if index == burn_count {
+                target
+            } else {
+                orbit.with_apogee_speed(orbit.apogee_speed_m_s() - burn_delta_v * index as f64)
+            };
+            // Capacity checked against burn_count above
+            let _ = burns.push(Burn {
+                delta_v_m_s: burn_delta_v,
+                propellant_kg: propellant,
+                duration_s: self.propulsion.burn_duration_s(propellant),
+                orbit_after,
+            });
+            mass -= propellant;
+            required += propellant;
+        }
+
+        Ok(DeorbitPlan {
+            initial: *orbit,
+            target,
+            initial_lifetime_years: orbital_lifetime_years(orbit, &self.drag, self.decay_mass_kg()),
+            target_lifetime_years: orbital_lifetime_years(
+                &target,
+                &self.drag,
+                self.decay_mass_kg(),
+            ),
+            delta_v_m_s: delta_v.max(0.0),
+            burns,
+            budget: PropellantBudget {
+                required_kg: required,
+                available_kg: self.propulsion.propellant_kg,
+                available_delta_v_m_s: self.propulsion.available_delta_v_m_s(self.dry_mass_kg),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planner(propellant_kg: f64) -> DeorbitPlanner {
+        DeorbitPlanner::new(
+            200.0,
+            DragProperties::default(),
+            Propulsion {
+                propellant_kg,
+                specific_impulse_s: 220.0,
+                thrust_n: 1.0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_lifetime_falls_with_altitude() {
+        let drag = DragProperties::default();
+        assert!(atmospheric_density_kg_m3(300.0) > atmospheric_density_kg_m3(400.0));
+
+        let low = orbital_lifetime_years(&Orbit::circular(350.0).unwrap(), &drag, 200.0).unwrap();
+        let mid = orbital_lifetime_years(&Orbit::circular(550.0).unwrap(), &drag, 200.0).unwrap();
+        assert!(low < 2.0, "{}", low);
+        assert!(mid > low && mid < DISPOSAL_LIFETIME_YEARS, "{}", mid);
+        assert_eq!(
+            orbital_lifetime_years(&Orbit::circular(1_000.0).unwrap(), &drag, 200.0),
+            None
+        );
+
+        // A low perigee brings an eccentric orbit down despite its apogee
+        let eccentric = Orbit::new(250.0, 800.0).unwrap();
+        assert!(orbital_lifetime_years(&eccentric, &drag, 200.0).unwrap() < 5.0);
+        assert!(Orbit::new(500.0, 400.0).is_err());
+    }
+
+    #[test]
+    fn test_plan_meets_limit_within_budget() {
+        let orbit = Orbit::circular(800.0).unwrap();
+        let plan = planner(20.0)
+            .with_max_burn_delta_v(40.0)
+            .plan(&orbit)
+            .unwrap();
+
+        assert!(plan.initial_lifetime_years.is_none_or(|years| years > 25.0));
+        let lifetime = plan.target_lifetime_years.unwrap();
+        assert!(lifetime <= 25.0 && lifetime > 20.0, "{}", lifetime);
+        assert_eq!(plan.target.apogee_altitude_km, 800.0);
+        assert!(plan.target.perigee_altitude_km < 800.0);
+
+        // Equal burns within the limit, adding up to the total
+        assert!(plan.burns.len() > 1);
+        let total: f64 = plan.burns.iter().map(|burn| burn.delta_v_m_s).sum();
+        assert!((total - plan.delta_v_m_s).abs() < 1e-9);
+        assert!(plan.burns.iter().all(|burn| burn.delta_v_m_s <= 40.0));
+        let last = plan.burns.last().unwrap();
+        assert_eq!(last.orbit_after, plan.target);
+        assert!(plan.budget.is_sufficient());
+        assert!(plan.budget.available_delta_v_m_s > plan.delta_v_m_s);
+
+        let load = plan.command_load(7, 1_000_000, 3_600).unwrap();
+        assert_eq!(load.entries.len(), plan.burns.len() + 1);
+        assert!(matches!(
+            load.entries[0].command,
+            SpaceCommand::SetMissionPhase {
+                phase: MissionPhase::Decommissioning
+            }
+        ));
+        let releases: std::vec::Vec<u64> = load.entries[1..]
+            .iter()
+            .map(|entry| entry.release_time.unwrap())
+            .collect();
+        assert_eq!(releases[0], 1_000_000);
+        assert!(releases.windows(2).all(|pair| pair[1] - pair[0] >= 3_600));
+        match load.entries[1].command {
+            SpaceCommand::CollisionAvoidance {
+                maneuver_type: ManeuverType::Deorbit,
+                delta_v,
+                ..
+            } => assert!(delta_v[0] < 0.0),
+            ref other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_plan_flags_short_propellant_and_compliant_orbits() {
+        let short = planner(1.0).plan(&Orbit::circular(800.0).unwrap()).unwrap();
+        assert!(!short.budget.is_sufficient());
+        assert!(short.budget.margin_kg() < 0.0);
+
+        let compliant = planner(1.0).plan(&Orbit::circular(400.0).unwrap()).unwrap();
+        assert!(compliant.burns.is_empty());
+        assert_eq!(compliant.delta_v_m_s, 0.0);
+        assert_eq!(compliant.command_load(1, 0, 0).unwrap().entries.len(), 1);
+
+        assert!(planner(20.0)
+            .with_max_burn_delta_v(1.0)
+            .plan(&Orbit::circular(800.0).unwrap())
+            .is_err());
+    }
+}
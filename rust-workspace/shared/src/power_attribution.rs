@@ -0,0 +1,329 @@
+//! Per-subsystem power attribution telemetry
+//!
+//! The EPS summary measures how much current the loads draw, but not who is
+//! drawing it. The power manager knows which subsystems are switched on and
+//! at what level, and a [`DrawModel`] per subsystem turns that into a
+//! modeled draw. The breakdown rides the EPS summary frame on
+//! [`crate::eps::EPS_APID`], so the ground always has the attribution that
+//! goes with a battery reading and can name the subsystem behind an
+//! unexpected depletion. Whatever the measured load power exceeds the
+//! modeled total by is unattributed: a load the model does not know about,
+//! or a subsystem drawing more than it should.
+//!
+//! Measurement IDs from [`measurement_ids::POWER_ATTRIBUTION_BASE`], one per
+//! subsystem in [`Subsystem::ALL`] order, in watts. A subsystem that is off
+//! reports 0 W.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-004: Power Management (power budget attributed per subsystem)
+//! - REQ-NF-001: System Health Monitoring (unexpected draw traceable to its
+//!   source)
+
+use serde::{Deserialize, Serialize};
+
+use crate::eps::{EpsSummary, EPS_PERIOD_MS};
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    TelemetryData,
+};
+use crate::types::BandType;
+
+/// Number of subsystems with a modeled draw
+pub const SUBSYSTEM_COUNT: usize = 6;
+
+/// Subsystem whose power draw is attributed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Subsystem {
+    /// Onboard computer, on whenever the satellite is
+    Obc,
+    /// UHF transceiver
+    Uhf,
+    /// S-band transceiver
+    SBand,
+    /// X-band transceiver
+    XBand,
+    /// K-band transceiver
+    KBand,
+    /// Ka-band transceiver
+    KaBand,
+}
+
+impl Subsystem {
+    /// Every subsystem in measurement ID order
+    pub const ALL: [Subsystem; SUBSYSTEM_COUNT] = [
+        Subsystem::Obc,
+        Subsystem::Uhf,
+        Subsystem::SBand,
+        Subsystem::XBand,
+        Subsystem::KBand,
+        Subsystem::KaBand,
+    ];
+
+    /// Transceiver subsystem of a band
+    pub const fn from_band(band: BandType) -> Self {
+        match band {
+            BandType::UhfBand => Subsystem::Uhf,
+            BandType::SBand => Subsystem::SBand,
+            BandType::XBand => Subsystem::XBand,
+            BandType::KBand => Subsystem::KBand,
+            BandType::KaBand => Subsystem::KaBand,
+        }
+    }
+
+    /// Position in [`Subsystem::ALL`]
+    const fn index(self) -> usize {
+        match self {
+            Subsystem::Obc => 0,
+            Subsystem::Uhf => 1,
+            Subsystem::SBand => 2,
+            Subsystem::XBand => 3,
+            Subsystem::KBand => 4,
+            Subsystem::KaBand => 5,
+        }
+    }
+
+    /// Measurement ID of this subsystem's draw
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::POWER_ATTRIBUTION_BASE + self.index() as u16
+    }
+
+    /// Subsystem a measurement ID belongs to, if it is an attribution ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|subsystem| subsystem.measurement_id() == measurement_id)
+    }
+
+    /// Display name
+    pub const fn name(self) -> &'static str {
+        match self {
+            Subsystem::Obc => "OBC",
+            Subsystem::Uhf => "UHF",
+            Subsystem::SBand => "S-Band",
+            Subsystem::XBand => "X-Band",
+            Subsystem::KBand => "K-Band",
+            Subsystem::KaBand => "Ka-Band",
+        }
+    }
+
+    /// Modeled draw of this subsystem
+    ///
+    /// Transceivers draw their standby power whenever powered, plus a share
+    /// of their transmit power in proportion to the commanded level. The
+    /// onboard computer draws the same at any level.
+    pub const fn draw_model(self) -> DrawModel {
+        let (standby_w, full_power_w) = match self {
+            Subsystem::Obc => (4.0, 4.0),
+            Subsystem::Uhf => (0.3, 2.0),
+            Subsystem::SBand => (1.0, 6.0),
+            Subsystem::XBand => (2.0, 10.0),
+            Subsystem::KBand => (2.5, 14.0),
+            Subsystem::KaBand => (3.0, 16.0),
+        };
+        DrawModel {
+            standby_w,
+            full_power_w,
+        }
+    }
+
+    /// Dictionary entry covering every subsystem's draw
+    pub const fn dictionary_entry() -> DictionaryEntry {
+        DictionaryEntry {
+            first_id: measurement_ids::POWER_ATTRIBUTION_BASE,
+            last_id: measurement_ids::POWER_ATTRIBUTION_BASE + 0x0F,
+            name: "Subsystem power draw",
+            unit: "W",
+            period_ms: EPS_PERIOD_MS,
+        }
+    }
+}
+
+/// Modeled power draw of a subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DrawModel {
+    /// Draw while on at 0% level, W
+    pub standby_w: f32,
+    /// Draw while on at 100% level, W
+    pub full_power_w: f32,
+}
+
+impl DrawModel {
+    /// Draw at a level, W; levels above 100% count as 100%
+    pub fn draw_w(&self, level_percent: u8) -> f32 {
+        let level = f32::from(level_percent.min(100)) / 100.0;
+        self.standby_w + (self.full_power_w - self.standby_w) * level
+    }
+}
+
+/// Modeled power draw of every subsystem at one instant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PowerAttribution {
+    /// Draw of each subsystem in [`Subsystem::ALL`] order, W; 0 while off
+    draws_w: [f32; SUBSYSTEM_COUNT],
+}
+
+impl Default for PowerAttribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerAttribution {
+    /// Attribution with every subsystem off
+    pub const fn new() -> Self {
+        Self {
+            draws_w: [0.0; SUBSYSTEM_COUNT],
+        }
+    }
+
+    /// Record a subsystem's power state
+    ///
+    /// - **ID**: FN-PWR-001
+    /// - **Requirement**: Track which subsystems are on and their modeled
+    ///   draw (REQ-NF-004).
+    /// - **Failure Modes**: None; a subsystem switched off draws 0 W
+    ///   whatever its level.
+    pub fn set(&mut self, subsystem: Subsystem, on: bool, level_percent: u8) {
+        self.draws_w[subsystem.index()] = if on {
+            subsystem.draw_model().draw_w(level_percent)
+        } else {
+            0.0
+        };
+    }
+
+    /// Modeled draw of one subsystem, W
+    pub fn draw_w(&self, subsystem: Subsystem) -> f32 {
+        self.draws_w[subsystem.index()]
+    }
+
+    /// Whether a subsystem is drawing power
+    pub fn is_on(&self, subsystem: Subsystem) -> bool {
+        self.draw_w(subsystem) > 0.0
+    }
+
+    /// Modeled draw of all subsystems, W
+    pub fn modeled_total_w(&self) -> f32 {
+        self.draws_w.iter().sum()
+    }
+
+    /// Measured load power the model does not account for, W
+    ///
+    /// Negative when the subsystems draw less than modeled; NaN when the
+    /// load current could not be measured.
+    pub fn unattributed_w(&self, summary: &EpsSummary) -> f32 {
+        summary.load_power() - self.modeled_total_w()
+    }
+
+    /// Append one measurement per subsystem
+    ///
+    /// - **ID**: FN-PWR-002
+    /// - **Requirement**: Downlink the per-subsystem breakdown alongside the
+    ///   EPS summary (REQ-NF-004).
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold all six
+    ///   measurements; measurements pushed before the overflow remain.
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for subsystem in Subsystem::ALL {
+            data.measurements
+                .push(Measurement {
+                    measurement_id: subsystem.measurement_id(),
+                    value: MeasurementValue::Float(f64::from(self.draw_w(subsystem))),
+                    unit: "W",
+                    quality: MeasurementQuality::Good,
+                })
+                .map_err(|_| {
+                    SpaceCommError::memory_error(
+                        MemoryErrorType::BufferOverflow,
+                        Some(data.measurements.len()),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild the attribution from downlinked measurements
+    ///
+    /// Returns `None` unless every subsystem is present.
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let mut attribution = Self::new();
+        for subsystem in Subsystem::ALL {
+            let draw = measurements
+                .iter()
+                .find(|m| m.measurement_id == subsystem.measurement_id())
+                .and_then(|m| match m.value {
+                    MeasurementValue::Float(v) => Some(v as f32),
+                    _ => None,
+                })?;
+            attribution.draws_w[subsystem.index()] = draw;
+        }
+        Some(attribution)
+    }
+}
+
+/// Power attribution carried by a frame, if it is complete
+pub fn decode_power_attribution(data: &TelemetryData) -> Option<PowerAttribution> {
+    PowerAttribution::from_measurements(&data.measurements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::dictionary_entry;
+    use crate::types::{ComponentId, HealthStatus};
+
+    #[test]
+    fn test_attribution_tracks_power_state() {
+        let mut attribution = PowerAttribution::new();
+        attribution.set(Subsystem::Obc, true, 100);
+        attribution.set(Subsystem::from_band(BandType::SBand), true, 50);
+        attribution.set(Subsystem::XBand, false, 80);
+
+        assert_eq!(attribution.draw_w(Subsystem::Obc), 4.0);
+        assert_eq!(attribution.draw_w(Subsystem::SBand), 3.5);
+        assert!(!attribution.is_on(Subsystem::XBand));
+        assert_eq!(attribution.modeled_total_w(), 7.5);
+        assert_eq!(Subsystem::Uhf.draw_model().draw_w(200), 2.0);
+
+        let summary = EpsSummary {
+            bus_voltage: 12.0,
+            battery_voltage: 28.0,
+            battery_soc: 70.0,
+            solar_voltage: 32.0,
+            solar_current: 1.5,
+            load_current: 1.0,
+            load_currents: [0.5, 0.25, 0.25],
+        };
+        assert_eq!(attribution.unattributed_w(&summary), 4.5);
+    }
+
+    #[test]
+    fn test_attribution_round_trip() {
+        let mut attribution = PowerAttribution::new();
+        attribution.set(Subsystem::Obc, true, 0);
+        attribution.set(Subsystem::KaBand, true, 95);
+
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        attribution.append_measurements(&mut data).unwrap();
+        let payload = data.to_payload().unwrap();
+        let mut frame =
+            TelemetryData::from_payload(ComponentId::new(1), HealthStatus::Good, &payload).unwrap();
+
+        assert_eq!(decode_power_attribution(&frame), Some(attribution));
+        for subsystem in Subsystem::ALL {
+            let entry = dictionary_entry(subsystem.measurement_id()).unwrap();
+            assert_eq!(entry.unit, "W");
+            assert_eq!(
+                Subsystem::from_measurement_id(subsystem.measurement_id()),
+                Some(subsystem)
+            );
+        }
+
+        frame.measurements.pop();
+        assert!(decode_power_attribution(&frame).is_none());
+    }
+}
@@ -0,0 +1,397 @@
+//! Priority inversion detection
+//!
+//! Lower-priority processing is bracketed as a [`Section`] (queued message
+//! handling, command execution, telemetry transmission, ...). When a Critical
+//! or Emergency message starts processing, the time it waited since it became
+//! ready is checked against every section held at a lower priority during
+//! that wait. A wait of at least [`InversionPolicy::min_wait_us`] that
+//! overlaps such a hold is a priority inversion; it is counted against the
+//! section that blocked the longest, so operators can see which section to
+//! fix.
+//!
+//! Counters are downlinked in housekeeping telemetry from
+//! [`measurement_ids::PRIORITY_INVERSION_BASE`]:
+//!
+//! | Offset        | Measurement                                  |
+//! |---------------|----------------------------------------------|
+//! | 0             | Inversions detected since boot               |
+//! | 1             | Longest inverted wait, µs                    |
+//! | 8 + section   | Inversions blamed on `Section::code`         |
+//!
+//! # Requirements Traceability
+//! - REQ-FN-010: Real-Time Constraints (evidence that real-time messages are
+//!   not blocked behind lower-priority work)
+//! - REQ-FN-002 / REQ-FN-003: Emergency and Critical command latency
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::messaging::MessagePriority;
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    TelemetryData, NOMINAL_PERIOD_MS,
+};
+
+/// Offset of the per-section counters from the base measurement ID
+const SECTION_COUNTER_OFFSET: u16 = 8;
+
+/// Instrumented processing section that can delay real-time messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Section {
+    /// Critical processor handling a queued lower-priority message
+    MessageQueue = 0,
+    /// Command processor executing an uplinked command
+    CommandProcessor = 1,
+    /// Communication manager transmitting telemetry or polling receivers
+    CommunicationManager = 2,
+    /// Telemetry collector sampling subsystems
+    TelemetryCollector = 3,
+    /// Housekeeping maintenance
+    Housekeeping = 4,
+}
+
+impl Section {
+    /// Number of sections
+    pub const COUNT: usize = 5;
+
+    /// Every section in code order
+    pub const ALL: [Section; Section::COUNT] = [
+        Section::MessageQueue,
+        Section::CommandProcessor,
+        Section::CommunicationManager,
+        Section::TelemetryCollector,
+        Section::Housekeeping,
+    ];
+
+    /// Telemetry code of the section
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Decode a section from its telemetry code
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Section::MessageQueue),
+            1 => Some(Section::CommandProcessor),
+            2 => Some(Section::CommunicationManager),
+            3 => Some(Section::TelemetryCollector),
+            4 => Some(Section::Housekeeping),
+            _ => None,
+        }
+    }
+
+    /// Operator-facing name
+    pub const fn name(self) -> &'static str {
+        match self {
+            Section::MessageQueue => "message queue",
+            Section::CommandProcessor => "command processor",
+            Section::CommunicationManager => "communication manager",
+            Section::TelemetryCollector => "telemetry collector",
+            Section::Housekeeping => "housekeeping",
+        }
+    }
+
+    /// Measurement ID of the inversion counter for this section
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::PRIORITY_INVERSION_BASE + SECTION_COUNTER_OFFSET + self as u16
+    }
+}
+
+/// Detection thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InversionPolicy {
+    /// Shortest wait counted as an inversion, µs
+    pub min_wait_us: u64,
+}
+
+impl Default for InversionPolicy {
+    /// A tenth of the Emergency 1ms budget.
+    fn default() -> Self {
+        Self { min_wait_us: 100 }
+    }
+}
+
+/// A detected priority inversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inversion {
+    /// Section that blocked the real-time message the longest
+    pub section: Section,
+    /// Priority the section was held at
+    pub held_priority: MessagePriority,
+    /// Priority of the blocked message
+    pub blocked_priority: MessagePriority,
+    /// Time the message waited, µs
+    pub waited_us: u64,
+    /// Part of the wait the section was held, µs
+    pub held_us: u64,
+}
+
+/// Inversion counters since boot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InversionCounters {
+    /// Inversions detected
+    pub total: u32,
+    /// Longest inverted wait, µs
+    pub max_wait_us: u32,
+    /// Inversions blamed on each section, indexed by `Section::code`
+    pub per_section: [u32; Section::COUNT],
+}
+
+impl InversionCounters {
+    /// Inversions blamed on `section`
+    pub const fn for_section(&self, section: Section) -> u32 {
+        self.per_section[section as usize]
+    }
+
+    /// Append the counters to a housekeeping frame
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        let base = measurement_ids::PRIORITY_INVERSION_BASE;
+        push_counter(data, base, self.total, "")?;
+        push_counter(data, base + 1, self.max_wait_us, "us")?;
+        for section in Section::ALL {
+            push_counter(
+                data,
+                section.measurement_id(),
+                self.for_section(section),
+                "",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Read counters from a downlinked frame; `None` if the frame has none
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let value = |id: u16| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == id)
+                .and_then(|m| match m.value {
+                    MeasurementValue::Integer(v) => u32::try_from(v).ok(),
+                    _ => None,
+                })
+        };
+
+        let base = measurement_ids::PRIORITY_INVERSION_BASE;
+        let mut counters = Self {
+            total: value(base)?,
+            max_wait_us: value(base + 1).unwrap_or(0),
+            per_section: [0; Section::COUNT],
+        };
+        for section in Section::ALL {
+            counters.per_section[section as usize] = value(section.measurement_id()).unwrap_or(0);
+        }
+        Some(counters)
+    }
+
+    /// Dictionary entry covering every inversion measurement
+    pub const fn dictionary_entry() -> DictionaryEntry {
+        DictionaryEntry {
+            first_id: measurement_ids::PRIORITY_INVERSION_BASE,
+            last_id: measurement_ids::PRIORITY_INVERSION_BASE + 0x0F,
+            name: "Priority inversions",
+            unit: "",
+            period_ms: NOMINAL_PERIOD_MS,
+        }
+    }
+}
+
+/// Append one integer counter, Good quality
+fn push_counter(data: &mut TelemetryData, id: u16, value: u32, unit: &'static str) -> Result<()> {
+    data.measurements
+        .push(Measurement {
+            measurement_id: id,
+            value: MeasurementValue::Integer(i64::from(value)),
+            unit,
+            quality: MeasurementQuality::Good,
+        })
+        .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, None))
+}
+
+/// Most recent hold of one section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hold {
+    priority: MessagePriority,
+    entered_us: u64,
+    /// `None` while the section is still held
+    exited_us: Option<u64>,
+}
+
+/// Tracks section holds and counts priority inversions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InversionDetector {
+    holds: [Option<Hold>; Section::COUNT],
+    counters: InversionCounters,
+}
+
+impl InversionDetector {
+    /// Detector with no holds and zeroed counters
+    pub const fn new() -> Self {
+        Self {
+            holds: [None; Section::COUNT],
+            counters: InversionCounters {
+                total: 0,
+                max_wait_us: 0,
+                per_section: [0; Section::COUNT],
+            },
+        }
+    }
+
+    /// Counters since boot
+    pub const fn counters(&self) -> &InversionCounters {
+        &self.counters
+    }
+
+    /// Mark `section` held at `priority` from `now_us`
+    pub fn enter(&mut self, section: Section, priority: MessagePriority, now_us: u64) {
+        self.holds[section as usize] = Some(Hold {
+            priority,
+            entered_us: now_us,
+            exited_us: None,
+        });
+    }
+
+    /// Mark `section` released at `now_us`
+    pub fn exit(&mut self, section: Section, now_us: u64) {
+        if let Some(hold) = &mut self.holds[section as usize] {
+            hold.exited_us.get_or_insert(now_us);
+        }
+    }
+
+    /// Check the wait of a message that became ready at `ready_us` and
+    /// started processing at `started_us`.
+    ///
+    /// - **ID**: FN-PIN-001
+    /// - **Requirement**: Detect Critical/Emergency messages that waited
+    ///   behind lower-priority processing and attribute the wait to the
+    ///   blocking section (REQ-FN-010).
+    /// - **Inputs**: Message priority, ready and start times in µs, policy.
+    /// - **Outputs**: The inversion, if the wait is at least
+    ///   `policy.min_wait_us` and overlaps a lower-priority hold; the section
+    ///   held longest within the wait is blamed.
+    /// - **Side Effects**: Counts a detected inversion.
+    /// - **Failure Modes**: Non-real-time priorities and `started_us` before
+    ///   `ready_us` are never inversions.
+    pub fn check_wait(
+        &mut self,
+        priority: MessagePriority,
+        ready_us: u64,
+        started_us: u64,
+        policy: &InversionPolicy,
+    ) -> Option<Inversion> {
+        let waited_us = started_us.checked_sub(ready_us)?;
+        if !priority.is_real_time() || waited_us < policy.min_wait_us {
+            return None;
+        }
+
+        let (section, hold, held_us) = Section::ALL
+            .iter()
+            .filter_map(|&section| {
+                let hold = self.holds[section as usize]?;
+                if hold.priority >= priority {
+                    return None;
+                }
+                let start = hold.entered_us.max(ready_us);
+                let end = hold.exited_us.unwrap_or(started_us).min(started_us);
+                let held_us = end.checked_sub(start).filter(|&d| d > 0)?;
+                Some((section, hold, held_us))
+            })
+            .max_by_key(|&(_, _, held_us)| held_us)?;
+
+        let counters = &mut self.counters;
+        counters.total = counters.total.saturating_add(1);
+        counters.per_section[section as usize] =
+            counters.per_section[section as usize].saturating_add(1);
+        let waited = u32::try_from(waited_us).unwrap_or(u32::MAX);
+        counters.max_wait_us = counters.max_wait_us.max(waited);
+
+        Some(Inversion {
+            section,
+            held_priority: hold.priority,
+            blocked_priority: priority,
+            waited_us,
+            held_us,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ComponentId, HealthStatus};
+
+    #[test]
+    fn test_wait_behind_lower_priority_section_is_counted() {
+        let policy = InversionPolicy::default();
+        let mut detector = InversionDetector::new();
+
+        detector.enter(Section::TelemetryCollector, MessagePriority::Medium, 0);
+        detector.exit(Section::TelemetryCollector, 200);
+        detector.enter(Section::CommandProcessor, MessagePriority::Low, 300);
+
+        // Ready at 100, started at 1_000 while the command processor is still held
+        let inversion = detector
+            .check_wait(MessagePriority::Emergency, 100, 1_000, &policy)
+            .unwrap();
+        assert_eq!(inversion.section, Section::CommandProcessor);
+        assert_eq!(inversion.held_priority, MessagePriority::Low);
+        assert_eq!(inversion.waited_us, 900);
+        assert_eq!(inversion.held_us, 700);
+
+        let counters = detector.counters();
+        assert_eq!(counters.total, 1);
+        assert_eq!(counters.max_wait_us, 900);
+        assert_eq!(counters.for_section(Section::CommandProcessor), 1);
+        assert_eq!(counters.for_section(Section::TelemetryCollector), 0);
+    }
+
+    #[test]
+    fn test_short_waits_and_equal_priorities_are_not_inversions() {
+        let policy = InversionPolicy::default();
+        let mut detector = InversionDetector::new();
+        detector.enter(Section::MessageQueue, MessagePriority::Critical, 0);
+
+        // Held at the same priority as the waiting Critical message
+        assert!(detector
+            .check_wait(MessagePriority::Critical, 0, 5_000, &policy)
+            .is_none());
+        detector.exit(Section::MessageQueue, 10);
+
+        detector.enter(Section::Housekeeping, MessagePriority::Low, 0);
+        // Below the minimum wait
+        assert!(detector
+            .check_wait(MessagePriority::Emergency, 0, 50, &policy)
+            .is_none());
+        // Not a real-time priority
+        assert!(detector
+            .check_wait(MessagePriority::High, 0, 5_000, &policy)
+            .is_none());
+        // Hold released before the message became ready
+        detector.exit(Section::Housekeeping, 100);
+        assert!(detector
+            .check_wait(MessagePriority::Emergency, 200, 5_000, &policy)
+            .is_none());
+
+        assert_eq!(detector.counters().total, 0);
+    }
+
+    #[test]
+    fn test_counters_round_trip_through_telemetry() {
+        let policy = InversionPolicy::default();
+        let mut detector = InversionDetector::new();
+        detector.enter(Section::CommunicationManager, MessagePriority::Medium, 0);
+        detector.check_wait(MessagePriority::Critical, 0, 2_500, &policy);
+
+        let mut data = TelemetryData {
+            source: ComponentId::new(0x0001),
+            timestamp: 0,
+            measurements: Default::default(),
+            health_status: HealthStatus::Good,
+        };
+        assert!(InversionCounters::from_measurements(&data.measurements).is_none());
+        detector.counters().append_measurements(&mut data).unwrap();
+
+        let decoded = InversionCounters::from_measurements(&data.measurements).unwrap();
+        assert_eq!(&decoded, detector.counters());
+        assert_eq!(decoded.for_section(Section::CommunicationManager), 1);
+        assert_eq!(decoded.max_wait_us, 2_500);
+    }
+}
@@ -0,0 +1,444 @@
+//! Reusable retry policies with backoff, jitter and deadlines
+//!
+//! Flight and ground code both need to retry transient failures (link
+//! timeouts, busy transceivers, exhausted buffers) without each call site
+//! inventing its own loop. A [`RetryPolicy`] describes *how* to retry; a
+//! [`RetryState`] tracks one operation's attempts and answers, after each
+//! failure, whether and how long to wait before the next attempt. Because the
+//! state machine never sleeps itself, it works equally with blocking threads,
+//! embassy timers, or a cooperative scheduler.
+//!
+//! Each decision is reported to a [`RetryObserver`] so callers can log every
+//! attempt through whatever logging facility they already use.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-004: Fault Tolerance (bounded recovery from transient faults)
+//! - REQ-PF-001: Command Response Time (deadline-bounded retries)
+//! - REQ-NF-002: Memory Constraints (no heap allocation, no_std)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SpaceCommError};
+
+/// Delay growth between consecutive attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackoffStrategy {
+    /// Same delay before every retry
+    Fixed {
+        /// Delay in milliseconds
+        delay_ms: u32,
+    },
+    /// Delay grows by a constant increment
+    Linear {
+        /// Delay before the first retry
+        initial_ms: u32,
+        /// Added for each subsequent retry
+        increment_ms: u32,
+        /// Upper bound on any single delay
+        max_delay_ms: u32,
+    },
+    /// Delay multiplies by an integer factor
+    Exponential {
+        /// Delay before the first retry
+        initial_ms: u32,
+        /// Growth factor per retry (2 doubles the delay)
+        multiplier: u32,
+        /// Upper bound on any single delay
+        max_delay_ms: u32,
+    },
+}
+
+impl BackoffStrategy {
+    /// Delay before retry number `retry` (1 = first retry), without jitter
+    pub fn delay_ms(&self, retry: u8) -> u32 {
+        let step = u32::from(retry.saturating_sub(1));
+        match *self {
+            BackoffStrategy::Fixed { delay_ms } => delay_ms,
+            BackoffStrategy::Linear {
+                initial_ms,
+                increment_ms,
+                max_delay_ms,
+            } => initial_ms
+                .saturating_add(increment_ms.saturating_mul(step))
+                .min(max_delay_ms),
+            BackoffStrategy::Exponential {
+                initial_ms,
+                multiplier,
+                max_delay_ms,
+            } => {
+                let mut delay = initial_ms;
+                for _ in 0..step {
+                    delay = delay.saturating_mul(multiplier);
+                    if delay >= max_delay_ms {
+                        break;
+                    }
+                }
+                delay.min(max_delay_ms)
+            }
+        }
+    }
+}
+
+/// Randomization applied to each backoff delay
+///
+/// Jitter keeps several nodes that failed together from retrying in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Jitter {
+    /// Use the backoff delay exactly
+    None,
+    /// Uniformly random delay in `[0, delay]`
+    Full,
+    /// Delay varied by up to ±`percent` of its value
+    Proportional {
+        /// Maximum deviation as a percentage (0-100)
+        percent: u8,
+    },
+}
+
+/// How an operation is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts including the first (1 = no retries)
+    pub max_attempts: u8,
+    /// Delay growth between attempts
+    pub backoff: BackoffStrategy,
+    /// Randomization of each delay
+    pub jitter: Jitter,
+    /// Give up once this much time has elapsed since the first attempt
+    pub deadline_ms: Option<u64>,
+    /// Retry errors that `SpaceCommError::is_recoverable` reports as permanent
+    pub retry_permanent_errors: bool,
+}
+
+impl RetryPolicy {
+    /// Single attempt, no retries
+    pub const NONE: Self = Self::fixed(1, 0);
+
+    /// Retry with a constant delay
+    pub const fn fixed(max_attempts: u8, delay_ms: u32) -> Self {
+        Self {
+            max_attempts,
+            backoff: BackoffStrategy::Fixed { delay_ms },
+            jitter: Jitter::None,
+            deadline_ms: None,
+            retry_permanent_errors: false,
+        }
+    }
+
+    /// Retry with doubling delay, capped at `max_delay_ms`
+    pub const fn exponential(max_attempts: u8, initial_ms: u32, max_delay_ms: u32) -> Self {
+        Self {
+            max_attempts,
+            backoff: BackoffStrategy::Exponential {
+                initial_ms,
+                multiplier: 2,
+                max_delay_ms,
+            },
+            jitter: Jitter::None,
+            deadline_ms: None,
+            retry_permanent_errors: false,
+        }
+    }
+
+    /// Set the jitter applied to each delay
+    pub const fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Bound the whole operation by a deadline measured from the first attempt
+    pub const fn with_deadline_ms(mut self, deadline_ms: u64) -> Self {
+        self.deadline_ms = Some(deadline_ms);
+        self
+    }
+
+    /// Retry even errors classified as non-recoverable
+    pub const fn retrying_permanent_errors(mut self) -> Self {
+        self.retry_permanent_errors = true;
+        self
+    }
+
+    /// Begin tracking a new operation started at `start_ms`
+    ///
+    /// `seed` drives jitter; any value works, but nodes sharing a policy should
+    /// use different seeds (e.g. component ID mixed with a counter).
+    pub const fn start(&self, start_ms: u64, seed: u32) -> RetryState {
+        RetryState {
+            policy: *self,
+            attempts: 0,
+            start_ms,
+            rng: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 100 ms doubling backoff capped at 2 s, ±20 % jitter
+    fn default() -> Self {
+        Self::exponential(3, 100, 2_000).with_jitter(Jitter::Proportional { percent: 20 })
+    }
+}
+
+/// Why a retry sequence stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GiveUpReason {
+    /// `max_attempts` reached
+    AttemptsExhausted,
+    /// Next attempt would start after the deadline
+    DeadlineExceeded,
+    /// Error is not worth retrying
+    PermanentError,
+}
+
+/// Outcome of a failed attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetryDecision {
+    /// Wait this long, then try again
+    RetryAfter {
+        /// Delay in milliseconds
+        delay_ms: u32,
+    },
+    /// Stop and report the last error
+    GiveUp(GiveUpReason),
+}
+
+/// Record of one failed attempt, passed to observers
+#[derive(Debug, Clone, Copy)]
+pub struct AttemptRecord<'a> {
+    /// Attempt number that failed (1 = first attempt)
+    pub attempt: u8,
+    /// Milliseconds since the first attempt started
+    pub elapsed_ms: u64,
+    /// Error returned by the attempt
+    pub error: &'a SpaceCommError,
+    /// What happens next
+    pub decision: RetryDecision,
+}
+
+/// Hook for logging or counting retry attempts
+pub trait RetryObserver {
+    /// Called once for every failed attempt, after the decision is made
+    fn on_attempt(&mut self, record: &AttemptRecord<'_>);
+}
+
+/// Observer that ignores every attempt
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl RetryObserver for NoopObserver {
+    fn on_attempt(&mut self, _record: &AttemptRecord<'_>) {}
+}
+
+impl<F: FnMut(&AttemptRecord<'_>)> RetryObserver for F {
+    fn on_attempt(&mut self, record: &AttemptRecord<'_>) {
+        self(record)
+    }
+}
+
+/// Attempt bookkeeping for one retried operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryState {
+    policy: RetryPolicy,
+    attempts: u8,
+    start_ms: u64,
+    rng: u32,
+}
+
+impl RetryState {
+    /// Number of attempts that have failed so far
+    pub const fn attempts(&self) -> u8 {
+        self.attempts
+    }
+
+    /// Record a failed attempt and decide what to do next.
+    ///
+    /// - **ID**: FN-RTY-001
+    /// - **Requirement**: Bound every retry sequence by attempt count and
+    ///   deadline, and never retry errors that cannot succeed. REQ-NF-004.
+    /// - **Inputs**:
+    ///   - `now_ms`: Current time on the same clock as `start_ms`.
+    ///   - `error`: Error returned by the attempt.
+    ///   - `observer`: Notified with the attempt record and decision.
+    /// - **Outputs**: `RetryAfter { delay_ms }` or `GiveUp(reason)`.
+    /// - **Postconditions**: `attempts()` increases by one.
+    /// - **Side Effects**: Advances the jitter generator; calls `observer`.
+    /// - **Failure Modes**: A clock that runs backwards is treated as zero
+    ///   elapsed time, which can only lengthen (never shorten) the sequence up
+    ///   to `max_attempts`.
+    /// - **Constraints**: O(1) time (O(attempts) for exponential backoff);
+    ///   no allocation.
+    pub fn on_failure(
+        &mut self,
+        now_ms: u64,
+        error: &SpaceCommError,
+        observer: &mut impl RetryObserver,
+    ) -> RetryDecision {
+        self.attempts = self.attempts.saturating_add(1);
+        let elapsed_ms = now_ms.saturating_sub(self.start_ms);
+
+        let decision = if !self.policy.retry_permanent_errors && !error.is_recoverable() {
+            RetryDecision::GiveUp(GiveUpReason::PermanentError)
+        } else if self.attempts >= self.policy.max_attempts {
+            RetryDecision::GiveUp(GiveUpReason::AttemptsExhausted)
+        } else {
+            let delay_ms = self.jittered(self.policy.backoff.delay_ms(self.attempts));
+            match self.policy.deadline_ms {
+                Some(deadline) if elapsed_ms + u64::from(delay_ms) > deadline => {
+                    RetryDecision::GiveUp(GiveUpReason::DeadlineExceeded)
+                }
+                _ => RetryDecision::RetryAfter { delay_ms },
+            }
+        };
+
+        observer.on_attempt(&AttemptRecord {
+            attempt: self.attempts,
+            elapsed_ms,
+            error,
+            decision,
+        });
+
+        decision
+    }
+
+    fn jittered(&mut self, delay_ms: u32) -> u32 {
+        match self.policy.jitter {
+            Jitter::None => delay_ms,
+            Jitter::Full => self.next_random() % delay_ms.saturating_add(1),
+            Jitter::Proportional { percent } => {
+                let spread = delay_ms / 100 * u32::from(percent.min(100));
+                if spread == 0 {
+                    return delay_ms;
+                }
+                let offset = self.next_random() % (2 * spread + 1);
+                (delay_ms - spread).saturating_add(offset)
+            }
+        }
+    }
+
+    /// xorshift32: cheap, deterministic, good enough to decorrelate retries
+    fn next_random(&mut self) -> u32 {
+        let mut x = self.rng;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng = x;
+        x
+    }
+}
+
+/// Run `operation` under `policy`, sleeping the current thread between attempts
+///
+/// The closure receives the attempt number (1-based). Returns the first
+/// success, or the error from the final attempt.
+#[cfg(feature = "std")]
+pub fn retry_blocking<T>(
+    policy: &RetryPolicy,
+    observer: &mut impl RetryObserver,
+    mut operation: impl FnMut(u8) -> Result<T>,
+) -> Result<T> {
+    let clock = std::time::Instant::now();
+    let seed = crate::time::current_time_nanos() as u32;
+    let mut state = policy.start(0, seed);
+
+    loop {
+        let error = match operation(state.attempts() + 1) {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        let now_ms = clock.elapsed().as_millis() as u64;
+        match state.on_failure(now_ms, &error, observer) {
+            RetryDecision::RetryAfter { delay_ms } => {
+                std::thread::sleep(std::time::Duration::from_millis(u64::from(delay_ms)));
+            }
+            RetryDecision::GiveUp(_) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeout() -> SpaceCommError {
+        SpaceCommError::communication_timeout(100, "test")
+    }
+
+    #[test]
+    fn test_backoff_strategies() {
+        let exp = BackoffStrategy::Exponential {
+            initial_ms: 100,
+            multiplier: 2,
+            max_delay_ms: 500,
+        };
+        assert_eq!(exp.delay_ms(1), 100);
+        assert_eq!(exp.delay_ms(2), 200);
+        assert_eq!(exp.delay_ms(3), 400);
+        assert_eq!(exp.delay_ms(4), 500);
+
+        let linear = BackoffStrategy::Linear {
+            initial_ms: 10,
+            increment_ms: 5,
+            max_delay_ms: 18,
+        };
+        assert_eq!(linear.delay_ms(2), 15);
+        assert_eq!(linear.delay_ms(3), 18);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let mut state = RetryPolicy::fixed(3, 50).start(0, 1);
+        let mut log = 0u8;
+        let mut observer = |_: &AttemptRecord<'_>| log += 1;
+
+        assert_eq!(
+            state.on_failure(0, &timeout(), &mut observer),
+            RetryDecision::RetryAfter { delay_ms: 50 }
+        );
+        assert_eq!(
+            state.on_failure(50, &timeout(), &mut observer),
+            RetryDecision::RetryAfter { delay_ms: 50 }
+        );
+        assert_eq!(
+            state.on_failure(100, &timeout(), &mut observer),
+            RetryDecision::GiveUp(GiveUpReason::AttemptsExhausted)
+        );
+        assert_eq!(log, 3);
+    }
+
+    #[test]
+    fn test_permanent_errors_and_deadline() {
+        let mut state = RetryPolicy::fixed(5, 10).start(0, 1);
+        let integrity = SpaceCommError::invalid_packet("bad", None);
+        assert_eq!(
+            state.on_failure(0, &integrity, &mut NoopObserver),
+            RetryDecision::GiveUp(GiveUpReason::PermanentError)
+        );
+
+        let mut state = RetryPolicy::fixed(5, 100)
+            .with_deadline_ms(150)
+            .start(1_000, 1);
+        assert!(matches!(
+            state.on_failure(1_000, &timeout(), &mut NoopObserver),
+            RetryDecision::RetryAfter { .. }
+        ));
+        assert_eq!(
+            state.on_failure(1_100, &timeout(), &mut NoopObserver),
+            RetryDecision::GiveUp(GiveUpReason::DeadlineExceeded)
+        );
+    }
+
+    #[test]
+    fn test_jitter_stays_in_bounds() {
+        let policy =
+            RetryPolicy::fixed(100, 1_000).with_jitter(Jitter::Proportional { percent: 10 });
+        let mut state = policy.start(0, 12345);
+        for _ in 0..50 {
+            if let RetryDecision::RetryAfter { delay_ms } =
+                state.on_failure(0, &timeout(), &mut NoopObserver)
+            {
+                assert!((900..=1_100).contains(&delay_ms));
+            }
+        }
+    }
+}
@@ -0,0 +1,897 @@
+//! RF housekeeping telemetry
+//!
+//! Transceiver state (power, carrier lock, transmit power, received signal
+//! strength, temperature and tuned frequency) for every band is downlinked
+//! as an ordinary [`TelemetryData`] frame on its own APID, at a rate
+//! independent of the main telemetry collector. Each field of each band has
+//! its own measurement ID so the values flow through the telemetry payload
+//! codec, quality flags, dictionary and stale detection unchanged.
+//!
+//! Measurement IDs are laid out field-major from
+//! [`measurement_ids::RF_HOUSEKEEPING_BASE`]: field `f` of band `b` is
+//! `base + 8 * f + b`, with bands in [`RF_BANDS`] order. Each field therefore
+//! occupies one contiguous dictionary range with a single unit.
+//!
+//! The module also holds the carrier lock-loss recovery ladder
+//! ([`LockMonitor`]): once lock has been lost for a confirmation period the
+//! transceiver is re-tuned, then power cycled, then marked failed. Its state
+//! and counters are downlinked in a second frame on the same APID, laid out
+//! the same way from [`measurement_ids::LOCK_RECOVERY_BASE`].
+//!
+//! # Requirements Traceability
+//! - REQ-PF-002: Link quality monitoring (signal strength and lock telemetry)
+//! - REQ-SF-002: Thermal monitoring for hardware protection
+//! - REQ-FN-007: Multi-Band Communication (per-band transceiver status)
+//! - REQ-NF-004: Fault Tolerance (automatic transceiver recovery)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::telemetry::{
+    measurement_ids, DictionaryEntry, Measurement, MeasurementQuality, MeasurementValue,
+    QualityLimits, TelemetryData,
+};
+use crate::types::BandType;
+
+/// APID of the RF housekeeping packet, next to the standard telemetry APID
+pub const RF_HOUSEKEEPING_APID: u16 = 0x101;
+
+/// Default RF housekeeping reporting period, ms
+pub const RF_HOUSEKEEPING_PERIOD_MS: u64 = 1_000;
+
+/// Bands in measurement ID order
+pub const RF_BANDS: [BandType; 5] = [
+    BandType::UhfBand,
+    BandType::SBand,
+    BandType::XBand,
+    BandType::KBand,
+    BandType::KaBand,
+];
+
+/// Measurement ID stride between consecutive fields
+const FIELD_STRIDE: u16 = 8;
+
+/// Measurement ID of field number `field` for `band` in a field-major range
+const fn field_measurement_id(base: u16, field: u16, band: BandType) -> u16 {
+    base + field * FIELD_STRIDE + band_index(band)
+}
+
+/// Field number and band of a measurement ID in a field-major range
+fn split_measurement_id(
+    base: u16,
+    fields: usize,
+    measurement_id: u16,
+) -> Option<(usize, BandType)> {
+    let offset = measurement_id.checked_sub(base)?;
+    let field = usize::from(offset / FIELD_STRIDE);
+    let band = RF_BANDS.get(usize::from(offset % FIELD_STRIDE))?;
+    (field < fields).then_some((field, *band))
+}
+
+/// Dictionary entry covering field number `field` for every band
+const fn field_dictionary_entry(
+    base: u16,
+    field: u16,
+    name: &'static str,
+    unit: &'static str,
+) -> DictionaryEntry {
+    let first_id = base + field * FIELD_STRIDE;
+    DictionaryEntry {
+        first_id,
+        last_id: first_id + RF_BANDS.len() as u16 - 1,
+        name,
+        unit,
+        period_ms: RF_HOUSEKEEPING_PERIOD_MS,
+    }
+}
+
+/// Push one integer measurement, mapping a full frame to `BufferOverflow`
+fn push_measurement(
+    data: &mut TelemetryData,
+    measurement_id: u16,
+    value: i64,
+    unit: &'static str,
+    quality: MeasurementQuality,
+) -> Result<()> {
+    data.measurements
+        .push(Measurement {
+            measurement_id,
+            value: MeasurementValue::Integer(value),
+            unit,
+            quality,
+        })
+        .map_err(|_| {
+            SpaceCommError::memory_error(
+                MemoryErrorType::BufferOverflow,
+                Some(data.measurements.len()),
+            )
+        })
+}
+
+/// Integer value of a measurement in `measurements`, if present
+fn integer_value(measurements: &[Measurement], measurement_id: u16) -> Option<i64> {
+    measurements
+        .iter()
+        .find(|m| m.measurement_id == measurement_id)
+        .and_then(|m| match m.value {
+            MeasurementValue::Integer(v) => Some(v),
+            _ => None,
+        })
+}
+
+/// Position of a band in [`RF_BANDS`]
+const fn band_index(band: BandType) -> u16 {
+    match band {
+        BandType::UhfBand => 0,
+        BandType::SBand => 1,
+        BandType::XBand => 2,
+        BandType::KBand => 3,
+        BandType::KaBand => 4,
+    }
+}
+
+/// Transceiver status field reported in RF housekeeping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RfField {
+    /// Transceiver powered (1) or off (0)
+    Powered,
+    /// Carrier lock acquired (1) or lost (0)
+    Locked,
+    /// Transmit power, percent of maximum
+    TxPower,
+    /// Received signal strength, dBm
+    SignalStrength,
+    /// Transceiver temperature, °C
+    Temperature,
+    /// Tuned frequency, kHz
+    Frequency,
+}
+
+impl RfField {
+    /// Every field in measurement ID order
+    pub const ALL: [RfField; 6] = [
+        RfField::Powered,
+        RfField::Locked,
+        RfField::TxPower,
+        RfField::SignalStrength,
+        RfField::Temperature,
+        RfField::Frequency,
+    ];
+
+    /// Measurement ID of this field for `band`
+    pub const fn measurement_id(self, band: BandType) -> u16 {
+        field_measurement_id(measurement_ids::RF_HOUSEKEEPING_BASE, self as u16, band)
+    }
+
+    /// Field and band a measurement ID belongs to, if it is an RF
+    /// housekeeping ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<(Self, BandType)> {
+        let base = measurement_ids::RF_HOUSEKEEPING_BASE;
+        split_measurement_id(base, Self::ALL.len(), measurement_id)
+            .map(|(field, band)| (Self::ALL[field], band))
+    }
+
+    /// Engineering unit
+    pub const fn unit(self) -> &'static str {
+        match self {
+            RfField::Powered | RfField::Locked => "",
+            RfField::TxPower => "%",
+            RfField::SignalStrength => "dBm",
+            RfField::Temperature => "C",
+            RfField::Frequency => "kHz",
+        }
+    }
+
+    /// Limit definition for this field on `band`, or `None` for state flags
+    ///
+    /// Frequency is valid anywhere in the band and expected there too; a
+    /// transceiver tuned outside its band reads `Invalid`.
+    pub fn limits(self, band: BandType) -> Option<QualityLimits> {
+        match self {
+            RfField::Powered | RfField::Locked => None,
+            RfField::TxPower => Some(TX_POWER_LIMITS),
+            RfField::SignalStrength => Some(SIGNAL_STRENGTH_LIMITS),
+            RfField::Temperature => Some(TRANSCEIVER_TEMPERATURE_LIMITS),
+            RfField::Frequency => {
+                let (low_hz, high_hz) = band.frequency_range();
+                let (low_khz, high_khz) = ((low_hz / 1_000) as f64, (high_hz / 1_000) as f64);
+                Some(QualityLimits {
+                    valid_min: low_khz,
+                    valid_max: high_khz,
+                    expected_min: low_khz,
+                    expected_max: high_khz,
+                })
+            }
+        }
+    }
+
+    /// Dictionary entry covering this field for every band
+    pub const fn dictionary_entry(self) -> DictionaryEntry {
+        let name = match self {
+            RfField::Powered => "RF power state",
+            RfField::Locked => "RF carrier lock",
+            RfField::TxPower => "RF transmit power",
+            RfField::SignalStrength => "RF signal strength",
+            RfField::Temperature => "RF temperature",
+            RfField::Frequency => "RF frequency",
+        };
+        field_dictionary_entry(
+            measurement_ids::RF_HOUSEKEEPING_BASE,
+            self as u16,
+            name,
+            self.unit(),
+        )
+    }
+}
+
+/// Transmit power limits: sustained drive above 90% is outside the thermal
+/// design envelope
+pub const TX_POWER_LIMITS: QualityLimits = QualityLimits {
+    valid_min: 0.0,
+    valid_max: 100.0,
+    expected_min: 0.0,
+    expected_max: 90.0,
+};
+
+/// Received signal strength limits: detector range and usable link margin
+pub const SIGNAL_STRENGTH_LIMITS: QualityLimits = QualityLimits {
+    valid_min: -150.0,
+    valid_max: 0.0,
+    expected_min: -130.0,
+    expected_max: -50.0,
+};
+
+/// Transceiver temperature limits: sensor range and qualified operating range
+pub const TRANSCEIVER_TEMPERATURE_LIMITS: QualityLimits = QualityLimits {
+    valid_min: -60.0,
+    valid_max: 125.0,
+    expected_min: -20.0,
+    expected_max: 60.0,
+};
+
+/// Status of one band's transceiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RfBandStatus {
+    /// Band the transceiver serves
+    pub band: BandType,
+    /// Transceiver powered
+    pub is_powered: bool,
+    /// Carrier lock acquired
+    pub is_locked: bool,
+    /// Transmit power, percent of maximum
+    pub tx_power: u8,
+    /// Received signal strength, dBm
+    pub signal_strength: i16,
+    /// Transceiver temperature, °C
+    pub temperature: i16,
+    /// Tuned frequency, Hz
+    pub frequency: u64,
+}
+
+impl RfBandStatus {
+    /// Powered but without carrier lock
+    pub const fn lock_lost(&self) -> bool {
+        self.is_powered && !self.is_locked
+    }
+
+    /// Value of one field as downlinked
+    fn field_value(&self, field: RfField) -> i64 {
+        match field {
+            RfField::Powered => i64::from(self.is_powered),
+            RfField::Locked => i64::from(self.is_locked),
+            RfField::TxPower => i64::from(self.tx_power),
+            RfField::SignalStrength => i64::from(self.signal_strength),
+            RfField::Temperature => i64::from(self.temperature),
+            RfField::Frequency => (self.frequency / 1_000) as i64,
+        }
+    }
+
+    /// Append one measurement per field, each flagged against its limits
+    ///
+    /// - **ID**: FN-RFH-001
+    /// - **Requirement**: Report every transceiver status field with a
+    ///   quality from its limit definition.
+    /// - **Failure Modes**: `BufferOverflow` if `data` cannot hold all six
+    ///   measurements; measurements pushed before the overflow remain.
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for field in RfField::ALL {
+            let value = self.field_value(field);
+            let quality = field
+                .limits(self.band)
+                .map_or(MeasurementQuality::Good, |l| l.classify(value as f64));
+            push_measurement(
+                data,
+                field.measurement_id(self.band),
+                value,
+                field.unit(),
+                quality,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild a band's status from downlinked measurements
+    ///
+    /// Returns `None` unless every field of `band` is present.
+    pub fn from_measurements(band: BandType, measurements: &[Measurement]) -> Option<Self> {
+        let value = |field: RfField| integer_value(measurements, field.measurement_id(band));
+
+        Some(Self {
+            band,
+            is_powered: value(RfField::Powered)? != 0,
+            is_locked: value(RfField::Locked)? != 0,
+            tx_power: value(RfField::TxPower)?.clamp(0, i64::from(u8::MAX)) as u8,
+            signal_strength: value(RfField::SignalStrength)?
+                .clamp(i64::from(i16::MIN), i64::from(i16::MAX))
+                as i16,
+            temperature: value(RfField::Temperature)?
+                .clamp(i64::from(i16::MIN), i64::from(i16::MAX)) as i16,
+            frequency: value(RfField::Frequency)?.max(0) as u64 * 1_000,
+        })
+    }
+}
+
+/// Every band whose status is complete in an RF housekeeping frame
+pub fn decode_rf_housekeeping(data: &TelemetryData) -> heapless::Vec<RfBandStatus, 5> {
+    RF_BANDS
+        .iter()
+        .filter_map(|band| RfBandStatus::from_measurements(*band, &data.measurements))
+        .collect()
+}
+
+/// Stage of the lock-loss recovery ladder for one transceiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockRecoveryState {
+    /// Locked, or powered off on purpose
+    Nominal,
+    /// Lock lost, waiting out the confirmation period
+    LockLost,
+    /// Re-tuned; waiting for lock to return
+    Retuning,
+    /// Power cycled; waiting for lock to return
+    PowerCycling,
+    /// Ladder exhausted; transceiver disabled and reported to FDIR
+    Failed,
+}
+
+impl LockRecoveryState {
+    /// Telemetry code
+    pub const fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// State from its telemetry code
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(LockRecoveryState::Nominal),
+            1 => Some(LockRecoveryState::LockLost),
+            2 => Some(LockRecoveryState::Retuning),
+            3 => Some(LockRecoveryState::PowerCycling),
+            4 => Some(LockRecoveryState::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Action the hardware manager must take for a transceiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockRecoveryStep {
+    /// Re-program the synthesizer to the tuned frequency
+    Retune,
+    /// Power cycle the transceiver
+    PowerCycle,
+    /// Disable the transceiver and notify FDIR
+    MarkFailed,
+    /// Lock returned after one or more recovery actions
+    Recovered,
+}
+
+/// Timing and depth of the lock-loss recovery ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockRecoveryPolicy {
+    /// How long lock must stay lost before each ladder step, ms
+    pub confirm_ms: u64,
+    /// Re-tune attempts before power cycling
+    pub retune_attempts: u8,
+    /// Power cycle attempts before marking the transceiver failed
+    pub power_cycle_attempts: u8,
+}
+
+impl Default for LockRecoveryPolicy {
+    /// Two-second confirmation, two re-tunes, one power cycle
+    fn default() -> Self {
+        Self {
+            confirm_ms: 2_000,
+            retune_attempts: 2,
+            power_cycle_attempts: 1,
+        }
+    }
+}
+
+/// Lock-loss and recovery counters since boot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockRecoveryCounters {
+    /// Times carrier lock was lost while powered
+    pub lock_losses: u32,
+    /// Re-tunes performed
+    pub retunes: u32,
+    /// Power cycles performed
+    pub power_cycles: u32,
+    /// Times lock returned after a recovery action
+    pub recoveries: u32,
+}
+
+/// Lock-loss recovery ladder for one transceiver
+///
+/// - **ID**: FN-RFH-002
+/// - **Requirement**: On lock loss sustained for
+///   [`LockRecoveryPolicy::confirm_ms`], re-tune, then power cycle, then mark
+///   the transceiver failed, waiting a confirmation period after each action.
+/// - **Inputs**: Power and lock state sampled by the hardware manager, and the
+///   current time in ms.
+/// - **Outputs**: The next [`LockRecoveryStep`] to execute, if any.
+/// - **Side Effects**: Updates the ladder state and counters.
+/// - **Failure Modes**: `Failed` is sticky until [`LockMonitor::reset`];
+///   a transceiver powered off on purpose returns to `Nominal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockMonitor {
+    /// Current ladder stage
+    state: LockRecoveryState,
+    /// Start of the current confirmation window, ms
+    window_start_ms: u64,
+    /// Re-tunes performed in the current episode
+    retunes: u8,
+    /// Power cycles performed in the current episode
+    power_cycles: u8,
+    /// Counters since boot
+    counters: LockRecoveryCounters,
+}
+
+impl Default for LockMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LockMonitor {
+    /// Monitor for a locked transceiver
+    pub const fn new() -> Self {
+        Self {
+            state: LockRecoveryState::Nominal,
+            window_start_ms: 0,
+            retunes: 0,
+            power_cycles: 0,
+            counters: LockRecoveryCounters {
+                lock_losses: 0,
+                retunes: 0,
+                power_cycles: 0,
+                recoveries: 0,
+            },
+        }
+    }
+
+    /// Current ladder stage
+    pub const fn state(&self) -> LockRecoveryState {
+        self.state
+    }
+
+    /// Counters since boot
+    pub const fn counters(&self) -> LockRecoveryCounters {
+        self.counters
+    }
+
+    /// Clear a failed episode so the ladder can run again, keeping counters
+    pub fn reset(&mut self) {
+        self.state = LockRecoveryState::Nominal;
+        self.retunes = 0;
+        self.power_cycles = 0;
+    }
+
+    /// Feed one sample and get the next recovery step, if one is due
+    ///
+    /// # Arguments
+    /// * `policy` - Ladder timing and depth
+    /// * `is_powered` - Transceiver power state
+    /// * `is_locked` - Carrier lock state
+    /// * `now_ms` - Current time in ms
+    pub fn update(
+        &mut self,
+        policy: &LockRecoveryPolicy,
+        is_powered: bool,
+        is_locked: bool,
+        now_ms: u64,
+    ) -> Option<LockRecoveryStep> {
+        if self.state == LockRecoveryState::Failed {
+            return None;
+        }
+
+        if !is_powered || is_locked {
+            let acted = self.retunes > 0 || self.power_cycles > 0;
+            self.reset();
+            if is_powered && acted {
+                self.counters.recoveries += 1;
+                return Some(LockRecoveryStep::Recovered);
+            }
+            return None;
+        }
+
+        if self.state == LockRecoveryState::Nominal {
+            self.state = LockRecoveryState::LockLost;
+            self.window_start_ms = now_ms;
+            self.counters.lock_losses += 1;
+            return None;
+        }
+
+        if now_ms.saturating_sub(self.window_start_ms) < policy.confirm_ms {
+            return None;
+        }
+
+        self.window_start_ms = now_ms;
+        if self.retunes < policy.retune_attempts {
+            self.retunes += 1;
+            self.counters.retunes += 1;
+            self.state = LockRecoveryState::Retuning;
+            Some(LockRecoveryStep::Retune)
+        } else if self.power_cycles < policy.power_cycle_attempts {
+            self.power_cycles += 1;
+            self.counters.power_cycles += 1;
+            self.state = LockRecoveryState::PowerCycling;
+            Some(LockRecoveryStep::PowerCycle)
+        } else {
+            self.state = LockRecoveryState::Failed;
+            Some(LockRecoveryStep::MarkFailed)
+        }
+    }
+}
+
+/// Lock recovery field reported per band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryField {
+    /// [`LockRecoveryState`] code
+    State,
+    /// Lock losses since boot
+    LockLosses,
+    /// Re-tunes since boot
+    Retunes,
+    /// Power cycles since boot
+    PowerCycles,
+    /// Recoveries since boot
+    Recoveries,
+}
+
+impl RecoveryField {
+    /// Every field in measurement ID order
+    pub const ALL: [RecoveryField; 5] = [
+        RecoveryField::State,
+        RecoveryField::LockLosses,
+        RecoveryField::Retunes,
+        RecoveryField::PowerCycles,
+        RecoveryField::Recoveries,
+    ];
+
+    /// Measurement ID of this field for `band`
+    pub const fn measurement_id(self, band: BandType) -> u16 {
+        field_measurement_id(measurement_ids::LOCK_RECOVERY_BASE, self as u16, band)
+    }
+
+    /// Field and band a measurement ID belongs to, if it is a lock recovery ID
+    pub fn from_measurement_id(measurement_id: u16) -> Option<(Self, BandType)> {
+        let base = measurement_ids::LOCK_RECOVERY_BASE;
+        split_measurement_id(base, Self::ALL.len(), measurement_id)
+            .map(|(field, band)| (Self::ALL[field], band))
+    }
+
+    /// Dictionary entry covering this field for every band
+    pub const fn dictionary_entry(self) -> DictionaryEntry {
+        let name = match self {
+            RecoveryField::State => "RF lock recovery state",
+            RecoveryField::LockLosses => "RF lock losses",
+            RecoveryField::Retunes => "RF re-tunes",
+            RecoveryField::PowerCycles => "RF power cycles",
+            RecoveryField::Recoveries => "RF lock recoveries",
+        };
+        field_dictionary_entry(measurement_ids::LOCK_RECOVERY_BASE, self as u16, name, "")
+    }
+}
+
+/// Lock recovery state and counters of one band as downlinked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockRecoveryReport {
+    /// Band the transceiver serves
+    pub band: BandType,
+    /// Current ladder stage
+    pub state: LockRecoveryState,
+    /// Counters since boot
+    pub counters: LockRecoveryCounters,
+}
+
+impl LockRecoveryReport {
+    /// Report for `band` from its monitor
+    pub const fn new(band: BandType, monitor: &LockMonitor) -> Self {
+        Self {
+            band,
+            state: monitor.state,
+            counters: monitor.counters,
+        }
+    }
+
+    /// Append one measurement per field; a failed transceiver's state is
+    /// flagged `Invalid` so it stands out in ground displays
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        let counters = &self.counters;
+        for field in RecoveryField::ALL {
+            let (value, quality) = match field {
+                RecoveryField::State => (
+                    i64::from(self.state.code()),
+                    if self.state == LockRecoveryState::Failed {
+                        MeasurementQuality::Invalid
+                    } else {
+                        MeasurementQuality::Good
+                    },
+                ),
+                RecoveryField::LockLosses => {
+                    (i64::from(counters.lock_losses), MeasurementQuality::Good)
+                }
+                RecoveryField::Retunes => (i64::from(counters.retunes), MeasurementQuality::Good),
+                RecoveryField::PowerCycles => {
+                    (i64::from(counters.power_cycles), MeasurementQuality::Good)
+                }
+                RecoveryField::Recoveries => {
+                    (i64::from(counters.recoveries), MeasurementQuality::Good)
+                }
+            };
+            push_measurement(data, field.measurement_id(self.band), value, "", quality)?;
+        }
+        Ok(())
+    }
+
+    /// Rebuild a band's report from downlinked measurements
+    ///
+    /// Returns `None` unless every field of `band` is present and the state
+    /// code is known.
+    pub fn from_measurements(band: BandType, measurements: &[Measurement]) -> Option<Self> {
+        let value = |field: RecoveryField| integer_value(measurements, field.measurement_id(band));
+        let count = |field| value(field).map(|v| v.clamp(0, i64::from(u32::MAX)) as u32);
+
+        Some(Self {
+            band,
+            state: LockRecoveryState::from_code(u8::try_from(value(RecoveryField::State)?).ok()?)?,
+            counters: LockRecoveryCounters {
+                lock_losses: count(RecoveryField::LockLosses)?,
+                retunes: count(RecoveryField::Retunes)?,
+                power_cycles: count(RecoveryField::PowerCycles)?,
+                recoveries: count(RecoveryField::Recoveries)?,
+            },
+        })
+    }
+}
+
+/// Every band whose lock recovery report is complete in a frame
+pub fn decode_lock_recovery(data: &TelemetryData) -> heapless::Vec<LockRecoveryReport, 5> {
+    RF_BANDS
+        .iter()
+        .filter_map(|band| LockRecoveryReport::from_measurements(*band, &data.measurements))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ComponentId, HealthStatus};
+
+    fn status(band: BandType) -> RfBandStatus {
+        RfBandStatus {
+            band,
+            is_powered: true,
+            is_locked: true,
+            tx_power: 50,
+            signal_strength: -90,
+            temperature: 25,
+            frequency: band.frequency_range().0 + 1_000_000,
+        }
+    }
+
+    fn frame() -> TelemetryData {
+        TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        }
+    }
+
+    #[test]
+    fn test_measurement_id_layout() {
+        assert_eq!(RfField::Powered.measurement_id(BandType::UhfBand), 0x0050);
+        assert_eq!(RfField::Locked.measurement_id(BandType::SBand), 0x0059);
+        assert_eq!(RfField::Frequency.measurement_id(BandType::KaBand), 0x007C);
+
+        for field in RfField::ALL {
+            for band in RF_BANDS {
+                let id = field.measurement_id(band);
+                assert_eq!(RfField::from_measurement_id(id), Some((field, band)));
+                assert_eq!(
+                    crate::telemetry::dictionary_entry(id).unwrap().unit,
+                    field.unit()
+                );
+            }
+        }
+        assert_eq!(RfField::from_measurement_id(0x0055), None);
+        assert_eq!(RfField::from_measurement_id(0x0040), None);
+    }
+
+    #[test]
+    fn test_round_trip_through_payload() {
+        let mut data = frame();
+        for band in RF_BANDS {
+            status(band).append_measurements(&mut data).unwrap();
+        }
+        assert_eq!(data.measurements.len(), 30);
+        assert!(data
+            .measurements
+            .iter()
+            .all(|m| m.quality == MeasurementQuality::Good));
+
+        let payload = data.to_payload().unwrap();
+        let decoded =
+            TelemetryData::from_payload(data.source, HealthStatus::Good, &payload).unwrap();
+        let bands = decode_rf_housekeeping(&decoded);
+        assert_eq!(bands.len(), 5);
+        for (decoded, band) in bands.iter().zip(RF_BANDS) {
+            assert_eq!(*decoded, status(band));
+        }
+    }
+
+    #[test]
+    fn test_limits_flag_out_of_range_fields() {
+        let mut hot = status(BandType::XBand);
+        hot.temperature = 75;
+        hot.signal_strength = -140;
+        hot.frequency = 1_000_000_000; // tuned outside X-band
+
+        let mut data = frame();
+        hot.append_measurements(&mut data).unwrap();
+        let quality = |field: RfField| {
+            let id = field.measurement_id(BandType::XBand);
+            data.measurements
+                .iter()
+                .find(|m| m.measurement_id == id)
+                .unwrap()
+                .quality
+        };
+        assert_eq!(quality(RfField::Temperature), MeasurementQuality::Suspect);
+        assert_eq!(
+            quality(RfField::SignalStrength),
+            MeasurementQuality::Suspect
+        );
+        assert_eq!(quality(RfField::Frequency), MeasurementQuality::Invalid);
+        assert_eq!(quality(RfField::Locked), MeasurementQuality::Good);
+    }
+
+    /// Run the ladder with lock permanently lost, sampling every 500 ms
+    fn run_ladder(monitor: &mut LockMonitor, until_ms: u64) -> Vec<(u64, LockRecoveryStep)> {
+        let policy = LockRecoveryPolicy::default();
+        (0..=until_ms)
+            .step_by(500)
+            .filter_map(|t| {
+                monitor
+                    .update(&policy, true, false, t)
+                    .map(|step| (t, step))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recovery_ladder_order() {
+        let mut monitor = LockMonitor::new();
+        let steps = run_ladder(&mut monitor, 20_000);
+        assert_eq!(
+            steps,
+            vec![
+                (2_000, LockRecoveryStep::Retune),
+                (4_000, LockRecoveryStep::Retune),
+                (6_000, LockRecoveryStep::PowerCycle),
+                (8_000, LockRecoveryStep::MarkFailed),
+            ]
+        );
+        assert_eq!(monitor.state(), LockRecoveryState::Failed);
+        assert_eq!(
+            monitor.counters(),
+            LockRecoveryCounters {
+                lock_losses: 1,
+                retunes: 2,
+                power_cycles: 1,
+                recoveries: 0,
+            }
+        );
+
+        // Failed is sticky even if lock returns, until reset
+        let policy = LockRecoveryPolicy::default();
+        assert_eq!(monitor.update(&policy, true, true, 21_000), None);
+        assert_eq!(monitor.state(), LockRecoveryState::Failed);
+        monitor.reset();
+        assert_eq!(monitor.state(), LockRecoveryState::Nominal);
+    }
+
+    #[test]
+    fn test_recovery_after_retune_and_short_dropout() {
+        let policy = LockRecoveryPolicy::default();
+        let mut monitor = LockMonitor::new();
+
+        // Short dropout inside the confirmation window: no action, no recovery
+        assert_eq!(monitor.update(&policy, true, false, 0), None);
+        assert_eq!(monitor.state(), LockRecoveryState::LockLost);
+        assert_eq!(monitor.update(&policy, true, true, 1_000), None);
+        assert_eq!(monitor.counters().recoveries, 0);
+
+        // Sustained loss, re-tune, then lock returns
+        assert_eq!(monitor.update(&policy, true, false, 5_000), None);
+        assert_eq!(
+            monitor.update(&policy, true, false, 7_000),
+            Some(LockRecoveryStep::Retune)
+        );
+        assert_eq!(
+            monitor.update(&policy, true, true, 7_500),
+            Some(LockRecoveryStep::Recovered)
+        );
+        assert_eq!(monitor.state(), LockRecoveryState::Nominal);
+        assert_eq!(monitor.counters().lock_losses, 2);
+        assert_eq!(monitor.counters().recoveries, 1);
+
+        // A transceiver switched off on purpose is not chased
+        assert_eq!(monitor.update(&policy, false, false, 8_000), None);
+        assert_eq!(monitor.update(&policy, false, false, 20_000), None);
+        assert_eq!(monitor.state(), LockRecoveryState::Nominal);
+    }
+
+    #[test]
+    fn test_lock_recovery_report_round_trip() {
+        let mut monitor = LockMonitor::new();
+        run_ladder(&mut monitor, 10_000);
+
+        let mut data = frame();
+        for band in RF_BANDS {
+            let report = if band == BandType::KaBand {
+                LockRecoveryReport::new(band, &monitor)
+            } else {
+                LockRecoveryReport::new(band, &LockMonitor::new())
+            };
+            report.append_measurements(&mut data).unwrap();
+        }
+        assert_eq!(data.measurements.len(), 25);
+
+        let payload = data.to_payload().unwrap();
+        let decoded =
+            TelemetryData::from_payload(data.source, HealthStatus::Good, &payload).unwrap();
+        let reports = decode_lock_recovery(&decoded);
+        assert_eq!(reports.len(), 5);
+        assert_eq!(
+            reports[4],
+            LockRecoveryReport::new(BandType::KaBand, &monitor)
+        );
+        assert_eq!(reports[4].state, LockRecoveryState::Failed);
+        assert_eq!(reports[0].state, LockRecoveryState::Nominal);
+
+        let state_id = RecoveryField::State.measurement_id(BandType::KaBand);
+        let state = decoded
+            .measurements
+            .iter()
+            .find(|m| m.measurement_id == state_id)
+            .unwrap();
+        assert_eq!(state.quality, MeasurementQuality::Invalid);
+        assert!(crate::telemetry::dictionary_entry(state_id).is_some());
+        assert_eq!(
+            RecoveryField::from_measurement_id(state_id),
+            Some((RecoveryField::State, BandType::KaBand))
+        );
+    }
+
+    #[test]
+    fn test_lock_lost_only_when_powered() {
+        let mut band = status(BandType::SBand);
+        band.is_locked = false;
+        assert!(band.lock_lost());
+        band.is_powered = false;
+        assert!(!band.lock_lost());
+    }
+}
@@ -0,0 +1,115 @@
+//! JSON Schemas of the public payload types
+//!
+//! Commands and telemetry cross into tooling this crate does not control:
+//! ground software in other languages, operations web front ends and REST
+//! gateways. Their schemas are generated from the Rust definitions, so the
+//! schema a payload is validated against is always the one the flight and
+//! ground code deserialize with. Fixed-capacity fields appear as arrays and
+//! strings with a `maxItems` or `maxLength` of their capacity.
+//!
+//! Only built with the `schema` feature.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (authoritative command and telemetry formats)
+//! - REQ-SF-001: Command validation (payloads checked before they are accepted)
+
+use std::path::{Path, PathBuf};
+
+use schemars::{schema::RootSchema, schema_for};
+
+use crate::commands::SpaceCommand;
+use crate::error::{Result, SpaceCommError};
+use crate::telemetry::{Measurement, TelemetryData, TelemetryPacket};
+
+/// Schema of every public payload type, by type name
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("SpaceCommand", schema_for!(SpaceCommand)),
+        ("TelemetryPacket", schema_for!(TelemetryPacket)),
+        ("TelemetryData", schema_for!(TelemetryData)),
+        ("Measurement", schema_for!(Measurement)),
+    ]
+}
+
+/// Write every schema to `dir` as `<type>.schema.json`
+///
+/// # Arguments
+/// * `dir` - Directory to write to, created if missing
+///
+/// # Returns
+/// * `Result<Vec<PathBuf>>` - Paths written, or configuration error if the
+///   directory is not writable
+pub fn write_schemas(dir: &Path) -> Result<Vec<PathBuf>> {
+    write_all(dir, schemas())
+}
+
+/// Write named schemas to `dir` as pretty-printed JSON
+///
+/// Shared with crates that generate schemas of their own types.
+pub fn write_all(dir: &Path, schemas: Vec<(&'static str, RootSchema)>) -> Result<Vec<PathBuf>> {
+    let unwritable = SpaceCommError::ConfigurationError {
+        parameter: "schema_dir",
+        value: "<unwritable>",
+        reason: "JSON Schema could not be written",
+    };
+    std::fs::create_dir_all(dir).map_err(|_| unwritable.clone())?;
+    let mut written = Vec::new();
+    for (name, schema) in schemas {
+        let path = dir.join(format!("{}.schema.json", name));
+        let json = serde_json::to_string_pretty(&schema).map_err(|_| unwritable.clone())?;
+        std::fs::write(&path, json).map_err(|_| unwritable.clone())?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn schema_json(name: &str) -> Value {
+        let (_, schema) = schemas().into_iter().find(|(n, _)| *n == name).unwrap();
+        serde_json::to_value(schema).unwrap()
+    }
+
+    #[test]
+    fn test_command_schema_covers_variants_and_capacities() {
+        let schema = schema_json("SpaceCommand");
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 36);
+
+        let abort = variants
+            .iter()
+            .find(|v| v["required"][0] == "AbortMission")
+            .unwrap();
+        let reason = &abort["properties"]["AbortMission"]["properties"]["abort_reason"];
+        assert_eq!(reason["type"], "string");
+        assert_eq!(reason["maxLength"], 128);
+
+        // Supporting enums are shared definitions
+        let definitions = schema["definitions"].as_object().unwrap();
+        assert!(definitions.contains_key("SubsystemId"));
+        assert!(definitions.contains_key("BandType"));
+    }
+
+    #[test]
+    fn test_telemetry_schema_and_export() {
+        let schema = schema_json("TelemetryData");
+        let measurements = &schema["properties"]["measurements"];
+        assert_eq!(measurements["type"], "array");
+        assert_eq!(measurements["maxItems"], 32);
+        assert!(schema["required"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::from("health_status")));
+
+        let dir = std::env::temp_dir().join(format!("schemas-{}", std::process::id()));
+        let written = write_schemas(&dir).unwrap();
+        assert_eq!(written.len(), schemas().len());
+        let text = std::fs::read_to_string(dir.join("TelemetryPacket.schema.json")).unwrap();
+        let packet: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(packet["title"], "TelemetryPacket");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
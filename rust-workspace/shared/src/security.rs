@@ -1,27 +1,55 @@
 //! Command authentication and message integrity for space communication systems.
 //!
 //! Provides HMAC-SHA256-based signing and verification primitives that protect
-//! command uplinks and telemetry downlinks from tampering and replay attacks.
+//! command uplinks and telemetry downlinks from tampering and replay attacks,
+//! and AES-256-GCM protection of Space Packet data fields under the policy
+//! of each virtual channel and APID.
 //!
 //! # Design Constraints
 //! - No heap allocation; operates on fixed-size arrays compatible with `no_std`.
 //! - HMAC-SHA256 digest size is 32 bytes; callers must allocate accordingly.
-//! - Key material is caller-supplied; this module never stores secret keys.
+//! - Virtual channel and APID policies refer to keys by slot number only.
+//!   Key material lives in a [`KeyStore`]: a master key per slot, provisioned
+//!   out-of-band, from which each key generation is derived. Rotation moves
+//!   both ends to a new generation by command; no key is ever uplinked.
+//!
+//! # Protected Packet Layout
+//! A protected data field is a 13-byte security header (key slot, 4-byte key
+//! generation, 8-byte frame counter), the payload, encrypted or not, and a
+//! 16-byte GCM tag. Generation and counter form the GCM nonce, and the
+//! packet type, APID and sequence count are authenticated with it. Each end
+//! counts its own frames from zero, so commands and telemetry are sealed
+//! under separate keys derived for their direction.
 //!
 //! # Requirements Traceability
 //! - REQ-SF-001: Command validation and confirmation requirements
 //! - REQ-SF-002: Override protection for critical safety functions
+//! - REQ-SC-001: Per-virtual-channel link security policy
+//! - REQ-SC-002: Always-clear frames decodable by any station
+//! - REQ-SC-003: Authenticated encryption of packet data with key rotation
 //!
 //! # Standards References
 //! - FIPS PUB 198-1: The Keyed-Hash Message Authentication Code (HMAC)
 //! - FIPS PUB 180-4: Secure Hash Standard (SHA-2 family)
+//! - NIST SP 800-38D: Galois/Counter Mode (GCM) and GMAC
+//! - NIST SP 800-108: Key Derivation Using Pseudorandom Functions
+//! - CCSDS 355.0-B-2: Space Data Link Security Protocol
 //! - DoD 8570.01-M: Information Assurance Workforce Improvement Program
 
+use core::fmt;
+
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce, Tag};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use serde::{Deserialize, Serialize};
 
+use crate::ccsds::{PacketType, SpacePacket};
+use crate::commands::SpaceCommand;
 use crate::error::{Result, SpaceCommError, CryptoOperation};
+use crate::messaging::MessagePriority;
+use crate::wire;
+use space_comms_req::req;
 
 /// HMAC-SHA256 output length in bytes.
 pub const DIGEST_LEN: usize = 32;
@@ -79,6 +107,7 @@ impl CommandAuthenticator {
     /// - **Constraints**: O(|message|) time; O(1) additional space.
     /// - **Verification**: `sign(k, m)` then `verify(k, m, tag)` must return `Ok(true)`.
     /// - **References**: FIPS PUB 198-1 §3.
+    #[req("REQ-SC-001")]
     pub fn sign(key: &[u8], message: &[u8]) -> Result<AuthTag> {
         if key.is_empty() {
             return Err(SpaceCommError::CryptographicError {
@@ -87,7 +116,7 @@ impl CommandAuthenticator {
             });
         }
 
-        let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|_| {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).map_err(|_| {
             SpaceCommError::CryptographicError {
                 operation: CryptoOperation::Signing,
                 details: "Invalid HMAC key length",
@@ -117,6 +146,7 @@ impl CommandAuthenticator {
     /// - **Side Effects**: None.
     /// - **Constraints**: O(|message|) time; comparison is constant-time.
     /// - **References**: FIPS PUB 198-1 §3; NIST SP 800-107 Rev.1.
+    #[req("REQ-SC-001")]
     pub fn verify(key: &[u8], message: &[u8], tag: &AuthTag) -> Result<bool> {
         if key.is_empty() {
             return Err(SpaceCommError::CryptographicError {
@@ -125,7 +155,7 @@ impl CommandAuthenticator {
             });
         }
 
-        let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|_| {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).map_err(|_| {
             SpaceCommError::CryptographicError {
                 operation: CryptoOperation::Verification,
                 details: "Invalid HMAC key length",
@@ -139,6 +169,1085 @@ impl CommandAuthenticator {
     }
 }
 
+/// Number of virtual channels covered by the security policy table.
+pub const MAX_VIRTUAL_CHANNELS: usize = 8;
+
+/// Number of APIDs the security policy table can mark always-clear.
+pub const MAX_CLEAR_APIDS: usize = 8;
+
+/// Number of APIDs the security policy table can give their own policy.
+pub const MAX_APID_POLICIES: usize = 8;
+
+/// Highest CCSDS application process identifier (11 bits).
+const MAX_APID: u16 = 0x7FF;
+
+/// Well-known virtual channel assignments.
+pub mod virtual_channels {
+    /// Housekeeping telemetry, readable by quick-look stations
+    pub const HOUSEKEEPING: u8 = 0;
+    /// Science, report and diagnostic data
+    pub const SCIENCE: u8 = 1;
+    /// Recorder playback: file manifests, the event log and memory dumps
+    pub const PLAYBACK: u8 = 2;
+    /// Emergency command lane, serviced ahead of the normal command uplink
+    pub const EMERGENCY: u8 = 6;
+    /// Command uplink; may never be configured below `Authenticated`
+    pub const COMMAND: u8 = 7;
+
+    /// Channel an uplinked packet with `apid` is sent on: emergency
+    /// commands on `EMERGENCY`, everything else on `COMMAND`.
+    pub const fn uplink(apid: u16) -> u8 {
+        if apid == crate::messaging::MessagePriority::Emergency.command_apid() {
+            EMERGENCY
+        } else {
+            COMMAND
+        }
+    }
+
+    /// Channel a downlinked packet with `apid` is sent on: housekeeping
+    /// APIDs (0x100-0x1FF) on `HOUSEKEEPING`, recorded data played back on
+    /// `PLAYBACK`, and report and diagnostic packets on `SCIENCE`.
+    pub const fn downlink(apid: u16) -> u8 {
+        match apid {
+            0x100..=0x1FF => HOUSEKEEPING,
+            crate::file_downlink::FILE_MANIFEST_APID
+            | crate::event_log::EVENT_LOG_APID
+            | crate::diagnostics::MEMORY_DUMP_APID => PLAYBACK,
+            _ => SCIENCE,
+        }
+    }
+}
+
+/// Security service applied to frames on a virtual channel.
+///
+/// Ordered from weakest to strongest so policies can be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[repr(u8)]
+pub enum SecurityService {
+    /// No protection; frames are sent in the clear
+    Clear = 0,
+    /// Frames carry an HMAC-SHA256 tag
+    Authenticated = 1,
+    /// Frames are encrypted and authenticated
+    AuthenticatedEncryption = 2,
+}
+
+impl SecurityService {
+    /// Decode a service from its wire/telemetry code.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(SecurityService::Clear),
+            1 => Some(SecurityService::Authenticated),
+            2 => Some(SecurityService::AuthenticatedEncryption),
+            _ => None,
+        }
+    }
+
+    /// Whether frames must carry an authentication tag.
+    pub const fn requires_authentication(self) -> bool {
+        !matches!(self, SecurityService::Clear)
+    }
+
+    /// Whether frame data must be encrypted.
+    pub const fn requires_encryption(self) -> bool {
+        matches!(self, SecurityService::AuthenticatedEncryption)
+    }
+}
+
+/// Security policy for a single virtual channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VcSecurityPolicy {
+    /// Service applied to every frame on the channel
+    pub service: SecurityService,
+    /// Key slot used for the channel; ignored for `Clear`
+    pub key_id: u8,
+}
+
+impl VcSecurityPolicy {
+    /// Policy sending frames in the clear.
+    pub const CLEAR: Self = Self::new(SecurityService::Clear, 0);
+
+    /// Create a policy using `service` with the key in slot `key_id`.
+    pub const fn new(service: SecurityService, key_id: u8) -> Self {
+        Self { service, key_id }
+    }
+
+    /// Telemetry encoding: service code in the high byte, key slot in the low byte.
+    pub const fn report_code(&self) -> u16 {
+        ((self.service as u16) << 8) | self.key_id as u16
+    }
+
+    /// Decode a policy from its telemetry encoding.
+    pub const fn from_report_code(code: u16) -> Option<Self> {
+        match SecurityService::from_code((code >> 8) as u8) {
+            Some(service) => Some(Self::new(service, code as u8)),
+            None => None,
+        }
+    }
+}
+
+/// Per-virtual-channel security policy table.
+///
+/// - **ID**: MOD-SEC-002
+/// - **Requirement**: Apply a configurable security service to each virtual
+///   channel, keeping the command channel authenticated at all times (REQ-SC-001).
+/// - **Purpose**: Let housekeeping reach quick-look stations in the clear while
+///   science data stays encrypted and commands stay authenticated.
+/// - **Rationale**: A single link-wide setting forces either every station to
+///   hold keys or every channel to go unprotected.
+/// - **Assumptions**: Key slots are provisioned out-of-band; the table only
+///   names them.
+/// - **Failure Modes**: Invalid channel numbers and command channel downgrades
+///   are rejected with `Err(ConfigurationError)` and leave the table unchanged.
+/// - **Constraints**: Fixed size, no heap allocation.
+/// - **References**: CCSDS 355.0-B-2 (Space Data Link Security Protocol).
+///
+/// Virtual channels and APIDs can also be marked always-clear (REQ-SC-002):
+/// their frames are never encrypted, whatever the channel policy, so beacons
+/// and safe-mode telemetry stay decodable by stations holding no keys. They
+/// are still authenticated with the channel's key when the channel requires
+/// protection at all.
+///
+/// An APID can carry its own policy, which replaces its channel's
+/// (REQ-SC-003): emergency commands encrypted while the rest of the command
+/// channel is only authenticated, or low-priority telemetry sent clear on an
+/// authenticated channel. The command channel stays authenticated and
+/// always-clear marks still apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityPolicyTable {
+    policies: [VcSecurityPolicy; MAX_VIRTUAL_CHANNELS],
+    clear_channels: [bool; MAX_VIRTUAL_CHANNELS],
+    clear_apids: [Option<u16>; MAX_CLEAR_APIDS],
+    apid_policies: [Option<(u16, VcSecurityPolicy)>; MAX_APID_POLICIES],
+}
+
+impl SecurityPolicyTable {
+    /// Policy for virtual channel `vc`, if the channel exists.
+    pub fn policy(&self, vc: u8) -> Option<VcSecurityPolicy> {
+        self.policies.get(vc as usize).copied()
+    }
+
+    /// Replace the policy for virtual channel `vc`.
+    ///
+    /// - **ID**: FN-SEC-003
+    /// - **Requirement**: Allow the policy table to be changed by command while
+    ///   never leaving the command channel unauthenticated (REQ-SC-001).
+    /// - **Inputs**:
+    ///   - `vc`: Virtual channel number, `0..MAX_VIRTUAL_CHANNELS`.
+    ///   - `policy`: New policy for the channel.
+    /// - **Outputs**: `Ok(previous)` — the policy that was replaced.
+    /// - **Preconditions**: None.
+    /// - **Postconditions**: On success `policy(vc) == Some(policy)`.
+    /// - **Failure Modes**: Unknown channel, or `Clear` on the command channel
+    ///   → `Err(ConfigurationError)`; the table is unchanged.
+    /// - **Side Effects**: None beyond the table entry.
+    /// - **Constraints**: O(1).
+    /// - **Verification**: Unit test `test_command_channel_cannot_be_cleared`.
+    pub fn set_policy(&mut self, vc: u8, policy: VcSecurityPolicy) -> Result<VcSecurityPolicy> {
+        let slot = self.policies.get_mut(vc as usize).ok_or(
+            SpaceCommError::ConfigurationError {
+                parameter: "virtual_channel",
+                value: "out of range",
+                reason: "No security policy slot for this virtual channel",
+            },
+        )?;
+
+        if vc == virtual_channels::COMMAND && !policy.service.requires_authentication() {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "security_service",
+                value: "Clear",
+                reason: "Command channel must always be authenticated",
+            });
+        }
+
+        Ok(core::mem::replace(slot, policy))
+    }
+
+    /// Check that a received frame meets its channel's policy.
+    ///
+    /// - **ID**: FN-SEC-004
+    /// - **Requirement**: Reject frames that arrive with weaker protection than
+    ///   their virtual channel requires (REQ-SC-001).
+    /// - **Inputs**:
+    ///   - `vc`: Virtual channel the frame arrived on.
+    ///   - `service`: Protection actually present on the frame.
+    /// - **Outputs**: `Ok(())` if `service` is at least the configured service.
+    /// - **Failure Modes**: Unknown channel or insufficient protection
+    ///   → `Err(CryptographicError)`.
+    /// - **Side Effects**: None.
+    #[req("REQ-SC-001")]
+    pub fn check_frame(&self, vc: u8, service: SecurityService) -> Result<()> {
+        match self.policy(vc) {
+            Some(policy) if service >= policy.service => Ok(()),
+            Some(_) => Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame protection below virtual channel policy",
+            }),
+            None => Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame on unknown virtual channel",
+            }),
+        }
+    }
+
+    /// Mark virtual channel `vc` always-clear, or remove the mark.
+    ///
+    /// - **ID**: FN-SEC-005
+    /// - **Requirement**: Let frames that every station must decode opt out
+    ///   of encryption while staying authenticated (REQ-SC-002).
+    /// - **Inputs**:
+    ///   - `vc`: Virtual channel number, `0..MAX_VIRTUAL_CHANNELS`.
+    ///   - `clear`: Whether the channel's frames are always-clear.
+    /// - **Outputs**: `Ok(previous)` — whether the channel was always-clear.
+    /// - **Failure Modes**: Unknown channel, or the command channel
+    ///   → `Err(ConfigurationError)`; the table is unchanged.
+    /// - **Side Effects**: None beyond the table entry.
+    pub fn set_channel_always_clear(&mut self, vc: u8, clear: bool) -> Result<bool> {
+        let slot = self.clear_channels.get_mut(vc as usize).ok_or(
+            SpaceCommError::ConfigurationError {
+                parameter: "virtual_channel",
+                value: "out of range",
+                reason: "No security policy slot for this virtual channel",
+            },
+        )?;
+
+        if vc == virtual_channels::COMMAND {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "virtual_channel",
+                value: "command",
+                reason: "Command channel cannot opt out of its security policy",
+            });
+        }
+
+        Ok(core::mem::replace(slot, clear))
+    }
+
+    /// Mark `apid` always-clear on every virtual channel.
+    ///
+    /// - **ID**: FN-SEC-006
+    /// - **Requirement**: Let individual packet types (beacon, safe-mode
+    ///   telemetry) opt out of encryption while staying authenticated
+    ///   (REQ-SC-002).
+    /// - **Inputs**: `apid`: CCSDS APID, `0..=0x7FF`.
+    /// - **Outputs**: `Ok(())`; marking an APID twice is not an error.
+    /// - **Failure Modes**: APID out of range, or `MAX_CLEAR_APIDS` already
+    ///   marked → `Err(ConfigurationError)`; the table is unchanged.
+    /// - **Side Effects**: None beyond the table entry.
+    pub fn add_clear_apid(&mut self, apid: u16) -> Result<()> {
+        if apid > MAX_APID {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "apid",
+                value: "out of range",
+                reason: "APID must fit in 11 bits",
+            });
+        }
+        if self.clear_apids.contains(&Some(apid)) {
+            return Ok(());
+        }
+
+        let slot = self
+            .clear_apids
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(SpaceCommError::ConfigurationError {
+                parameter: "clear_apids",
+                value: "full",
+                reason: "No free always-clear APID slot",
+            })?;
+        *slot = Some(apid);
+        Ok(())
+    }
+
+    /// Remove the always-clear mark from `apid`; returns whether it was marked.
+    pub fn remove_clear_apid(&mut self, apid: u16) -> bool {
+        match self
+            .clear_apids
+            .iter_mut()
+            .find(|slot| **slot == Some(apid))
+        {
+            Some(slot) => {
+                *slot = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether packets with `apid` on virtual channel `vc` are always-clear.
+    pub fn is_always_clear(&self, vc: u8, apid: u16) -> bool {
+        self.clear_channels
+            .get(vc as usize)
+            .copied()
+            .unwrap_or(false)
+            || self.clear_apids.contains(&Some(apid))
+    }
+
+    /// Give `apid` its own policy on every virtual channel.
+    ///
+    /// - **ID**: FN-SEC-009
+    /// - **Requirement**: Let individual packet types be protected more or
+    ///   less strongly than their channel, without ever sending commands
+    ///   unauthenticated (REQ-SC-003).
+    /// - **Inputs**:
+    ///   - `apid`: CCSDS APID, `0..=0x7FF`.
+    ///   - `policy`: Service and key slot for the APID's packets.
+    /// - **Outputs**: `Ok(previous)` — the APID's previous policy, if any.
+    /// - **Failure Modes**: APID out of range, `Clear` for a command APID, or
+    ///   `MAX_APID_POLICIES` already set → `Err(ConfigurationError)`; the
+    ///   table is unchanged.
+    /// - **Side Effects**: None beyond the table entry.
+    pub fn set_apid_policy(
+        &mut self,
+        apid: u16,
+        policy: VcSecurityPolicy,
+    ) -> Result<Option<VcSecurityPolicy>> {
+        if apid > MAX_APID {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "apid",
+                value: "out of range",
+                reason: "APID must fit in 11 bits",
+            });
+        }
+        if MessagePriority::from_command_apid(apid).is_some()
+            && !policy.service.requires_authentication()
+        {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "security_service",
+                value: "Clear",
+                reason: "Command APIDs must always be authenticated",
+            });
+        }
+
+        if let Some((_, existing)) = self
+            .apid_policies
+            .iter_mut()
+            .flatten()
+            .find(|(marked, _)| *marked == apid)
+        {
+            return Ok(Some(core::mem::replace(existing, policy)));
+        }
+        let slot = self
+            .apid_policies
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(SpaceCommError::ConfigurationError {
+                parameter: "apid_policies",
+                value: "full",
+                reason: "No free APID policy slot",
+            })?;
+        *slot = Some((apid, policy));
+        Ok(None)
+    }
+
+    /// Remove the policy of `apid`, returning it; its packets follow their
+    /// channel's policy again.
+    pub fn remove_apid_policy(&mut self, apid: u16) -> Option<VcSecurityPolicy> {
+        let slot = self
+            .apid_policies
+            .iter_mut()
+            .find(|slot| matches!(slot, Some((marked, _)) if *marked == apid))?;
+        slot.take().map(|(_, policy)| policy)
+    }
+
+    /// Policy set for `apid`, if it has its own.
+    pub fn apid_policy(&self, apid: u16) -> Option<VcSecurityPolicy> {
+        self.apid_policies
+            .iter()
+            .flatten()
+            .find(|(marked, _)| *marked == apid)
+            .map(|(_, policy)| *policy)
+    }
+
+    /// Iterate over `(APID, policy)` pairs of APIDs with their own policy.
+    pub fn apid_policies(&self) -> impl Iterator<Item = (u16, VcSecurityPolicy)> + '_ {
+        self.apid_policies.iter().flatten().copied()
+    }
+
+    /// Policy to apply to a packet with `apid` sent on virtual channel `vc`.
+    ///
+    /// - **ID**: FN-SEC-010
+    /// - **Requirement**: Protect each packet as its APID, else its channel,
+    ///   calls for, never encrypting always-clear packets nor leaving the
+    ///   command channel unauthenticated (REQ-SC-001, REQ-SC-002, REQ-SC-003).
+    /// - **Outputs**: The APID's policy if it has one, else the channel's,
+    ///   reduced to `Authenticated` for always-clear packets and raised to
+    ///   it on the command channel; `None` for an unknown channel.
+    /// - **Side Effects**: None.
+    pub fn packet_policy(&self, vc: u8, apid: u16) -> Option<VcSecurityPolicy> {
+        let mut policy = self.policy(vc)?;
+        if let Some(own) = self.apid_policy(apid) {
+            policy = own;
+        }
+        if self.is_always_clear(vc, apid) {
+            policy.service = policy.service.min(SecurityService::Authenticated);
+        }
+        if vc == virtual_channels::COMMAND {
+            policy.service = policy.service.max(SecurityService::Authenticated);
+        }
+        Some(policy)
+    }
+
+    /// Service to apply to a packet with `apid` sent on virtual channel `vc`.
+    ///
+    /// - **ID**: FN-SEC-007
+    /// - **Requirement**: Never encrypt always-clear frames (REQ-SC-002).
+    /// - **Outputs**: The service of [`Self::packet_policy`]; `None` for an
+    ///   unknown channel.
+    /// - **Side Effects**: None.
+    pub fn packet_service(&self, vc: u8, apid: u16) -> Option<SecurityService> {
+        self.packet_policy(vc, apid).map(|policy| policy.service)
+    }
+
+    /// Check that a received packet carries exactly the protection its
+    /// channel and APID call for.
+    ///
+    /// - **ID**: FN-SEC-008
+    /// - **Requirement**: Reject frames weaker than their channel policy, and
+    ///   always-clear frames that arrive encrypted (REQ-SC-001, REQ-SC-002).
+    /// - **Inputs**:
+    ///   - `vc`: Virtual channel the frame arrived on.
+    ///   - `apid`: APID of the packet it carries.
+    ///   - `service`: Protection actually present on the frame.
+    /// - **Outputs**: `Ok(())` if the frame is acceptable.
+    /// - **Failure Modes**: Unknown channel, insufficient protection, or an
+    ///   encrypted always-clear frame → `Err(CryptographicError)`.
+    /// - **Side Effects**: None.
+    pub fn check_packet(&self, vc: u8, apid: u16, service: SecurityService) -> Result<()> {
+        let required = self
+            .packet_service(vc, apid)
+            .ok_or(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame on unknown virtual channel",
+            })?;
+
+        if service < required {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame protection below virtual channel policy",
+            });
+        }
+        if service.requires_encryption() && self.is_always_clear(vc, apid) {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Always-clear frame arrived encrypted",
+            });
+        }
+        Ok(())
+    }
+
+    /// Iterate over the APIDs marked always-clear.
+    pub fn clear_apids(&self) -> impl Iterator<Item = u16> + '_ {
+        self.clear_apids.iter().flatten().copied()
+    }
+
+    /// Iterate over `(virtual channel, policy)` pairs for reporting.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, VcSecurityPolicy)> + '_ {
+        self.policies
+            .iter()
+            .enumerate()
+            .map(|(vc, policy)| (vc as u8, *policy))
+    }
+}
+
+impl Default for SecurityPolicyTable {
+    /// Housekeeping in the clear, science and playback encrypted with key
+    /// slot 1, and every other channel (including commands) authenticated
+    /// with key slot 0.
+    /// Housekeeping, which carries the safe-mode telemetry set, is marked
+    /// always-clear so it stays readable if its policy is later tightened.
+    /// Emergency commands are encrypted as well as authenticated with key
+    /// slot 0.
+    fn default() -> Self {
+        let authenticated = VcSecurityPolicy::new(SecurityService::Authenticated, 0);
+        let mut policies = [authenticated; MAX_VIRTUAL_CHANNELS];
+        policies[virtual_channels::HOUSEKEEPING as usize] = VcSecurityPolicy::CLEAR;
+        let encrypted = VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 1);
+        policies[virtual_channels::SCIENCE as usize] = encrypted;
+        policies[virtual_channels::PLAYBACK as usize] = encrypted;
+        let mut clear_channels = [false; MAX_VIRTUAL_CHANNELS];
+        clear_channels[virtual_channels::HOUSEKEEPING as usize] = true;
+        let mut apid_policies = [None; MAX_APID_POLICIES];
+        apid_policies[0] = Some((
+            MessagePriority::Emergency.command_apid(),
+            VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 0),
+        ));
+        Self {
+            policies,
+            clear_channels,
+            clear_apids: [None; MAX_CLEAR_APIDS],
+            apid_policies,
+        }
+    }
+}
+
+/// AES-256 key length in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// GCM authentication tag length in bytes.
+pub const GCM_TAG_LEN: usize = 16;
+
+/// Security header ahead of a protected payload: key slot, key generation
+/// and frame counter.
+pub const SECURITY_HEADER_LEN: usize = 13;
+
+/// Bytes protection adds to a packet data field.
+pub const SECURITY_OVERHEAD: usize = SECURITY_HEADER_LEN + GCM_TAG_LEN;
+
+/// Number of key slots in a [`KeyStore`].
+pub const MAX_KEY_SLOTS: usize = 8;
+
+/// Command ID of [`SpaceCommand::RotateKeys`].
+pub const ROTATE_KEYS_COMMAND_ID: u32 = 0x0027;
+
+/// Largest Space Packet data field in bytes.
+const MAX_DATA_LEN: usize = 2048;
+
+/// Authenticated packet identification: packet type, APID and sequence count.
+const PACKET_AAD_LEN: usize = 5;
+
+/// Packet identification, security header and data field.
+const SEAL_BUFFER_LEN: usize = PACKET_AAD_LEN + MAX_DATA_LEN;
+
+/// Label binding derived keys to packet protection (NIST SP 800-108).
+const KDF_LABEL: &[u8] = b"space-comms packet key";
+
+/// Security header carried ahead of a protected payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityHeader {
+    /// Key slot the packet is protected with
+    pub key_id: u8,
+    /// Generation of the slot's key
+    pub generation: u32,
+    /// Frame counter, never repeated under one key generation
+    pub counter: u64,
+}
+
+impl SecurityHeader {
+    /// Encode as sent: key slot, then generation and counter big-endian.
+    pub fn to_bytes(&self) -> [u8; SECURITY_HEADER_LEN] {
+        let mut bytes = [0u8; SECURITY_HEADER_LEN];
+        bytes[0] = self.key_id;
+        bytes[1..].copy_from_slice(&self.nonce());
+        bytes
+    }
+
+    /// Decode the header at the start of a protected data field.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SECURITY_HEADER_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "Security header truncated",
+                None,
+            ));
+        }
+        let mut generation = [0u8; 4];
+        let mut counter = [0u8; 8];
+        generation.copy_from_slice(&bytes[1..5]);
+        counter.copy_from_slice(&bytes[5..SECURITY_HEADER_LEN]);
+        Ok(Self {
+            key_id: bytes[0],
+            generation: u32::from_be_bytes(generation),
+            counter: u64::from_be_bytes(counter),
+        })
+    }
+
+    /// GCM nonce: generation then counter (NIST SP 800-38D §8.2.1).
+    fn nonce(&self) -> [u8; 12] {
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&self.generation.to_be_bytes());
+        nonce[4..].copy_from_slice(&self.counter.to_be_bytes());
+        nonce
+    }
+}
+
+/// One generation of a slot's keys and its frame counters.
+#[derive(Clone)]
+struct SessionKey {
+    generation: u32,
+    /// Key of each direction, indexed by packet type: telemetry, command
+    keys: [[u8; KEY_LEN]; 2],
+    /// Last frame counter sent
+    sent: u64,
+    /// Highest frame counter accepted on each virtual channel
+    received: [Option<u64>; MAX_VIRTUAL_CHANNELS],
+}
+
+impl SessionKey {
+    /// Derive generation `generation` of slot `key_id` from its master key
+    /// with HMAC-SHA256 in counter mode (NIST SP 800-108 §4.1), one key per
+    /// direction: both ends count frames from zero, so a slot carrying
+    /// commands and telemetry would otherwise repeat nonces under one key.
+    fn derive(master: &[u8; KEY_LEN], key_id: u8, generation: u32) -> Result<Self> {
+        let mut keys = [[0u8; KEY_LEN]; 2];
+        for (direction, key) in keys.iter_mut().enumerate() {
+            let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(master).map_err(|_| {
+                SpaceCommError::CryptographicError {
+                    operation: CryptoOperation::KeyGeneration,
+                    details: "Invalid master key length",
+                }
+            })?;
+            mac.update(&1u32.to_be_bytes());
+            mac.update(KDF_LABEL);
+            mac.update(&[direction as u8, key_id]);
+            mac.update(&generation.to_be_bytes());
+            mac.update(&((KEY_LEN * 8) as u32).to_be_bytes());
+            *key = mac.finalize().into_bytes().into();
+        }
+        Ok(Self {
+            generation,
+            keys,
+            sent: 0,
+            received: [None; MAX_VIRTUAL_CHANNELS],
+        })
+    }
+
+    /// Cipher under the key of the direction `packet_type` travels
+    fn cipher(&self, packet_type: PacketType) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(
+            &self.keys[packet_type as usize],
+        ))
+    }
+}
+
+/// Master key of a slot with its current and previous key generations.
+#[derive(Clone)]
+struct KeySlot {
+    master: [u8; KEY_LEN],
+    current: SessionKey,
+    previous: Option<SessionKey>,
+}
+
+/// AES-256-GCM key store protecting Space Packet data fields.
+///
+/// - **ID**: MOD-SEC-003
+/// - **Requirement**: Encrypt and authenticate packet data fields under the
+///   key slot their policy names, and rotate keys by command (REQ-SC-003).
+/// - **Purpose**: Stop commands and telemetry crossing the link in
+///   plaintext, and let a key be retired without uplinking a new one.
+/// - **Rationale**: AES-256-GCM gives confidentiality and integrity in one
+///   pass, and with an empty plaintext is GMAC for authenticate-only
+///   policies, as CCSDS 355.0-B-2 specifies. Deriving each generation from a
+///   master key provisioned before launch makes rotation a counter bump
+///   both ends apply.
+/// - **Assumptions**: Master keys are provisioned out-of-band into the same
+///   slots at both ends.
+/// - **Failure Modes**: Missing keys, unknown generations, replayed
+///   counters and failed tags → `Err(CryptographicError)`; nothing is
+///   returned for a packet that does not verify.
+/// - **Constraints**: Fixed size, no heap allocation; packets are staged in
+///   a stack buffer of one maximum data field.
+/// - **References**: NIST SP 800-38D; NIST SP 800-108; CCSDS 355.0-B-2.
+///
+/// Each generation has its own frame counter and a key per direction, so a
+/// nonce never repeats under a key. The previous generation stays accepted after a rotation so frames
+/// already in flight still open; the one before it is forgotten. Received
+/// counters must increase on each virtual channel: a replayed or reordered
+/// frame is rejected. Channels sharing a slot are multiplexed onto the link
+/// in their own order, so each is checked on its own; a packet cannot be
+/// replayed onto another channel, as its APID, which fixes its channel, is
+/// authenticated with it.
+#[derive(Clone, Default)]
+pub struct KeyStore {
+    slots: [Option<KeySlot>; MAX_KEY_SLOTS],
+}
+
+impl fmt::Debug for KeyStore {
+    /// Lists the installed slots and their generations, never key material.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.slots
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(key_id, slot)| Some((key_id, slot.as_ref()?.current.generation))),
+            )
+            .finish()
+    }
+}
+
+impl KeyStore {
+    /// Empty key store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install `master` in slot `key_id` at generation 0, replacing any key
+    /// the slot held.
+    ///
+    /// - **ID**: FN-SEC-011
+    /// - **Requirement**: Load the master keys provisioned for each slot
+    ///   (REQ-SC-003).
+    /// - **Inputs**:
+    ///   - `key_id`: Key slot, `0..MAX_KEY_SLOTS`.
+    ///   - `master`: 256-bit master key.
+    /// - **Outputs**: `Ok(())`.
+    /// - **Failure Modes**: Unknown slot → `Err(ConfigurationError)`; an
+    ///   all-zero key → `Err(CryptographicError)`.
+    /// - **Side Effects**: Frame counters of the slot restart.
+    pub fn install(&mut self, key_id: u8, master: [u8; KEY_LEN]) -> Result<()> {
+        if master == [0u8; KEY_LEN] {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::KeyGeneration,
+                details: "Master key must not be all zeros",
+            });
+        }
+        let slot =
+            self.slots
+                .get_mut(key_id as usize)
+                .ok_or(SpaceCommError::ConfigurationError {
+                    parameter: "key_id",
+                    value: "out of range",
+                    reason: "No such key slot",
+                })?;
+        *slot = Some(KeySlot {
+            current: SessionKey::derive(&master, key_id, 0)?,
+            master,
+            previous: None,
+        });
+        Ok(())
+    }
+
+    /// Whether no key is installed in any slot.
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Remove the key in slot `key_id`; returns whether one was installed.
+    pub fn remove(&mut self, key_id: u8) -> bool {
+        self.slots
+            .get_mut(key_id as usize)
+            .and_then(Option::take)
+            .is_some()
+    }
+
+    /// Current key generation of slot `key_id`, if a key is installed.
+    pub fn generation(&self, key_id: u8) -> Option<u32> {
+        Some(
+            self.slots
+                .get(key_id as usize)?
+                .as_ref()?
+                .current
+                .generation,
+        )
+    }
+
+    /// Move slot `key_id` to key generation `generation`.
+    ///
+    /// - **ID**: FN-SEC-012
+    /// - **Requirement**: Retire a key by command without uplinking key
+    ///   material (REQ-SC-003).
+    /// - **Inputs**:
+    ///   - `key_id`: Key slot to rotate.
+    ///   - `generation`: New generation, above the current one.
+    /// - **Outputs**: `Ok(())`; packets are sealed with the new generation
+    ///   from now on.
+    /// - **Failure Modes**: No key in the slot → `Err(CryptographicError)`;
+    ///   a generation not above the current one → `Err(ConfigurationError)`,
+    ///   so a replayed rotation command cannot roll keys back.
+    /// - **Side Effects**: The current generation becomes the previous one;
+    ///   the one before it is dropped.
+    pub fn rotate(&mut self, key_id: u8, generation: u32) -> Result<()> {
+        let slot = self.slot_mut(key_id, CryptoOperation::KeyGeneration)?;
+        if generation <= slot.current.generation {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "key_generation",
+                value: "not increasing",
+                reason: "Key generation must increase on rotation",
+            });
+        }
+        let next = SessionKey::derive(&slot.master, key_id, generation)?;
+        slot.previous = Some(core::mem::replace(&mut slot.current, next));
+        Ok(())
+    }
+
+    /// Protect a packet's data field as `policy` requires.
+    ///
+    /// - **ID**: FN-SEC-013
+    /// - **Requirement**: Encrypt and authenticate, or only authenticate, a
+    ///   packet's data field with the current generation of the policy's key
+    ///   slot (REQ-SC-003).
+    /// - **Inputs**:
+    ///   - `packet`: Packet to protect.
+    ///   - `policy`: Service and key slot to apply.
+    /// - **Outputs**: `Ok(packet)` whose data field is the security header,
+    ///   the payload (encrypted under `AuthenticatedEncryption`) and the GCM
+    ///   tag; an unchanged copy under `Clear`.
+    /// - **Failure Modes**: No key in the slot, or the frame counter
+    ///   exhausted → `Err(CryptographicError)`; a payload too large to take
+    ///   the security overhead → `Err(InvalidPacket)`.
+    /// - **Side Effects**: Advances the slot's frame counter.
+    #[req("REQ-SC-002")]
+    pub fn seal(&mut self, packet: &SpacePacket, policy: VcSecurityPolicy) -> Result<SpacePacket> {
+        if !policy.service.requires_authentication() {
+            return Ok(packet.clone());
+        }
+        if packet.data.len() + SECURITY_OVERHEAD > MAX_DATA_LEN {
+            return Err(SpaceCommError::invalid_packet(
+                "Payload too large for the security overhead",
+                Some(packet.data.len() as u32),
+            ));
+        }
+
+        let session = &mut self
+            .slot_mut(policy.key_id, CryptoOperation::Encryption)?
+            .current;
+        if session.sent == u64::MAX {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Encryption,
+                details: "Frame counter exhausted; rotate the key",
+            });
+        }
+        session.sent += 1;
+        let header = SecurityHeader {
+            key_id: policy.key_id,
+            generation: session.generation,
+            counter: session.sent,
+        };
+
+        let mut buffer = heapless::Vec::<u8, SEAL_BUFFER_LEN>::new();
+        stage(&mut buffer, &packet_aad(packet))?;
+        stage(&mut buffer, &header.to_bytes())?;
+        stage(&mut buffer, &packet.data)?;
+
+        let cipher = session.cipher(packet.header.packet_type);
+        let nonce = header.nonce();
+        let sealed = if policy.service.requires_encryption() {
+            let (aad, payload) = buffer.split_at_mut(PACKET_AAD_LEN + SECURITY_HEADER_LEN);
+            cipher.encrypt_in_place_detached(Nonce::from_slice(&nonce), aad, payload)
+        } else {
+            cipher.encrypt_in_place_detached(Nonce::from_slice(&nonce), &buffer, &mut [])
+        };
+        let tag = sealed.map_err(|_| SpaceCommError::CryptographicError {
+            operation: CryptoOperation::Encryption,
+            details: "AES-GCM sealing failed",
+        })?;
+        stage(&mut buffer, &tag)?;
+
+        SpacePacket::new(
+            packet.header.packet_type,
+            packet.header.apid,
+            packet.header.sequence_count,
+            &buffer[PACKET_AAD_LEN..],
+            packet.secondary_header.clone(),
+        )
+    }
+
+    /// Verify, and decrypt if `policy` calls for it, a protected packet.
+    ///
+    /// - **ID**: FN-SEC-014
+    /// - **Requirement**: Accept a packet only if it was protected as its
+    ///   policy requires, with a known key generation and a frame counter
+    ///   not seen before (REQ-SC-003).
+    /// - **Inputs**:
+    ///   - `packet`: Packet as received.
+    ///   - `policy`: Service and key slot the packet must be protected with.
+    ///   - `vc`: Virtual channel received on, whose counters it is checked
+    ///     against.
+    /// - **Outputs**: `Ok(packet)` with the plaintext payload; an unchanged
+    ///   copy under `Clear`.
+    /// - **Failure Modes**: A short data field, another key slot, an unknown
+    ///   generation, a counter not above the last on `vc`, an unknown `vc`,
+    ///   or a failed tag (including a packet protected with another service)
+    ///   → `Err(CryptographicError)`.
+    /// - **Side Effects**: Records the frame counter as received on `vc`.
+    #[req("REQ-SC-002")]
+    pub fn open(
+        &mut self,
+        packet: &SpacePacket,
+        policy: VcSecurityPolicy,
+        vc: u8,
+    ) -> Result<SpacePacket> {
+        if !policy.service.requires_authentication() {
+            return Ok(packet.clone());
+        }
+        let vc = usize::from(vc);
+        if vc >= MAX_VIRTUAL_CHANNELS {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame on unknown virtual channel",
+            });
+        }
+        let data = &packet.data;
+        if data.len() < SECURITY_OVERHEAD {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Protected packet shorter than its security overhead",
+            });
+        }
+        let header = SecurityHeader::from_bytes(data)?;
+        if header.key_id != policy.key_id {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Packet protected with another key slot",
+            });
+        }
+
+        let slot = self.slot_mut(header.key_id, CryptoOperation::Decryption)?;
+        let session = if slot.current.generation == header.generation {
+            &mut slot.current
+        } else {
+            match slot.previous.as_mut() {
+                Some(previous) if previous.generation == header.generation => previous,
+                _ => {
+                    return Err(SpaceCommError::CryptographicError {
+                        operation: CryptoOperation::Decryption,
+                        details: "Packet protected with an unknown key generation",
+                    })
+                }
+            }
+        };
+        if session.received[vc].is_some_and(|highest| header.counter <= highest) {
+            return Err(SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Replayed or stale frame counter",
+            });
+        }
+
+        let (body, tag) = data.split_at(data.len() - GCM_TAG_LEN);
+        let mut buffer = heapless::Vec::<u8, SEAL_BUFFER_LEN>::new();
+        stage(&mut buffer, &packet_aad(packet))?;
+        stage(&mut buffer, body)?;
+
+        let cipher = session.cipher(packet.header.packet_type);
+        let nonce = header.nonce();
+        let opened = if policy.service.requires_encryption() {
+            let (aad, payload) = buffer.split_at_mut(PACKET_AAD_LEN + SECURITY_HEADER_LEN);
+            cipher.decrypt_in_place_detached(
+                Nonce::from_slice(&nonce),
+                aad,
+                payload,
+                Tag::from_slice(tag),
+            )
+        } else {
+            cipher.decrypt_in_place_detached(
+                Nonce::from_slice(&nonce),
+                &buffer,
+                &mut [],
+                Tag::from_slice(tag),
+            )
+        };
+        opened.map_err(|_| SpaceCommError::CryptographicError {
+            operation: CryptoOperation::Verification,
+            details: "Packet authentication failed",
+        })?;
+        session.received[vc] = Some(header.counter);
+
+        SpacePacket::new(
+            packet.header.packet_type,
+            packet.header.apid,
+            packet.header.sequence_count,
+            &buffer[PACKET_AAD_LEN + SECURITY_HEADER_LEN..],
+            packet.secondary_header.clone(),
+        )
+    }
+
+    /// Protect a packet sent on virtual channel `vc` as `policies` requires
+    /// for its channel and APID.
+    pub fn protect(
+        &mut self,
+        policies: &SecurityPolicyTable,
+        vc: u8,
+        packet: &SpacePacket,
+    ) -> Result<SpacePacket> {
+        let policy = policies.packet_policy(vc, packet.header.apid).ok_or(
+            SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Encryption,
+                details: "Frame on unknown virtual channel",
+            },
+        )?;
+        self.seal(packet, policy)
+    }
+
+    /// Open a packet received on virtual channel `vc`, which must be
+    /// protected as `policies` requires for its channel and APID.
+    pub fn unprotect(
+        &mut self,
+        policies: &SecurityPolicyTable,
+        vc: u8,
+        packet: &SpacePacket,
+    ) -> Result<SpacePacket> {
+        let policy = policies.packet_policy(vc, packet.header.apid).ok_or(
+            SpaceCommError::CryptographicError {
+                operation: CryptoOperation::Verification,
+                details: "Frame on unknown virtual channel",
+            },
+        )?;
+        self.open(packet, policy, vc)
+    }
+
+    fn slot_mut(&mut self, key_id: u8, operation: CryptoOperation) -> Result<&mut KeySlot> {
+        self.slots
+            .get_mut(key_id as usize)
+            .and_then(Option::as_mut)
+            .ok_or(SpaceCommError::CryptographicError {
+                operation,
+                details: "No key installed in slot",
+            })
+    }
+}
+
+/// Packet type, APID and sequence count, authenticated with the payload.
+fn packet_aad(packet: &SpacePacket) -> [u8; PACKET_AAD_LEN] {
+    let mut aad = [0u8; PACKET_AAD_LEN];
+    aad[0] = packet.header.packet_type as u8;
+    aad[1..3].copy_from_slice(&packet.header.apid.to_be_bytes());
+    aad[3..].copy_from_slice(&packet.header.sequence_count.to_be_bytes());
+    aad
+}
+
+fn stage(buffer: &mut heapless::Vec<u8, SEAL_BUFFER_LEN>, bytes: &[u8]) -> Result<()> {
+    buffer.extend_from_slice(bytes).map_err(|_| {
+        SpaceCommError::memory_error(
+            crate::error::MemoryErrorType::BufferOverflow,
+            Some(bytes.len()),
+        )
+    })
+}
+
+/// Key rotation commanded by [`SpaceCommand::RotateKeys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    /// Key slot to rotate
+    pub key_id: u8,
+    /// Key generation to move to
+    pub generation: u32,
+}
+
+impl KeyRotation {
+    /// Command carrying the rotation.
+    pub fn to_command(&self) -> SpaceCommand {
+        SpaceCommand::RotateKeys {
+            key_id: self.key_id,
+            generation: self.generation,
+        }
+    }
+
+    /// Rotation commanded in a command packet's data field: the command ID,
+    /// then the command serialized as JSON
+    ///
+    /// Returns `None` for any other command, and an error for a rotation
+    /// command whose parameters do not decode.
+    pub fn from_command_data(data: &[u8]) -> Option<Result<Self>> {
+        let command_id = wire::command_id(data)?;
+        if command_id != ROTATE_KEYS_COMMAND_ID {
+            return None;
+        }
+        let rotation = match serde_json::from_slice::<SpaceCommand>(&data[wire::COMMAND_ID_LEN..]) {
+            Ok(SpaceCommand::RotateKeys { key_id, generation }) => Ok(Self { key_id, generation }),
+            _ => Err(SpaceCommError::invalid_packet(
+                "Malformed key rotation command",
+                Some(command_id),
+            )),
+        };
+        Some(rotation)
+    }
+
+    /// Apply the rotation to `keys`.
+    pub fn apply(&self, keys: &mut KeyStore) -> Result<()> {
+        keys.rotate(self.key_id, self.generation)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,4 +1284,472 @@ mod tests {
         let result = CommandAuthenticator::sign(b"", b"data");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_default_policy_table() {
+        let table = SecurityPolicyTable::default();
+        assert_eq!(
+            table.policy(virtual_channels::HOUSEKEEPING).unwrap().service,
+            SecurityService::Clear
+        );
+        assert!(table
+            .policy(virtual_channels::SCIENCE)
+            .unwrap()
+            .service
+            .requires_encryption());
+        assert_eq!(
+            table.policy(virtual_channels::PLAYBACK),
+            table.policy(virtual_channels::SCIENCE)
+        );
+        assert!(table
+            .policy(virtual_channels::COMMAND)
+            .unwrap()
+            .service
+            .requires_authentication());
+        assert!(table.policy(MAX_VIRTUAL_CHANNELS as u8).is_none());
+    }
+
+    #[test]
+    fn test_command_channel_cannot_be_cleared() {
+        let mut table = SecurityPolicyTable::default();
+        assert!(table
+            .set_policy(virtual_channels::COMMAND, VcSecurityPolicy::CLEAR)
+            .is_err());
+        assert!(table
+            .set_policy(MAX_VIRTUAL_CHANNELS as u8, VcSecurityPolicy::CLEAR)
+            .is_err());
+
+        let previous = table
+            .set_policy(virtual_channels::SCIENCE, VcSecurityPolicy::CLEAR)
+            .unwrap();
+        assert_eq!(previous.service, SecurityService::AuthenticatedEncryption);
+        assert_eq!(
+            table.policy(virtual_channels::SCIENCE),
+            Some(VcSecurityPolicy::CLEAR)
+        );
+    }
+
+    #[test]
+    fn test_check_frame_and_report_code() {
+        let table = SecurityPolicyTable::default();
+        assert!(table.check_frame(virtual_channels::HOUSEKEEPING, SecurityService::Clear).is_ok());
+        assert!(table.check_frame(virtual_channels::SCIENCE, SecurityService::Authenticated).is_err());
+        assert!(table.check_frame(virtual_channels::COMMAND, SecurityService::Clear).is_err());
+
+        let policy = table.policy(virtual_channels::SCIENCE).unwrap();
+        assert_eq!(policy.report_code(), 0x0201);
+        assert_eq!(VcSecurityPolicy::from_report_code(0x0201), Some(policy));
+        assert_eq!(VcSecurityPolicy::from_report_code(0x0901), None);
+    }
+
+    #[test]
+    fn test_always_clear_frames_are_authenticated_only() {
+        let mut table = SecurityPolicyTable::default();
+        let beacon = 0x7F0;
+        assert_eq!(
+            table.packet_service(virtual_channels::SCIENCE, beacon),
+            Some(SecurityService::AuthenticatedEncryption)
+        );
+
+        table.add_clear_apid(beacon).unwrap();
+        table.add_clear_apid(beacon).unwrap();
+        assert_eq!(table.clear_apids().count(), 1);
+        assert_eq!(
+            table.packet_service(virtual_channels::SCIENCE, beacon),
+            Some(SecurityService::Authenticated)
+        );
+        assert!(table
+            .check_packet(
+                virtual_channels::SCIENCE,
+                beacon,
+                SecurityService::Authenticated
+            )
+            .is_ok());
+        assert!(table
+            .check_packet(virtual_channels::SCIENCE, beacon, SecurityService::Clear)
+            .is_err());
+        assert!(table
+            .check_packet(
+                virtual_channels::SCIENCE,
+                beacon,
+                SecurityService::AuthenticatedEncryption
+            )
+            .is_err());
+        // Other APIDs on the channel stay encrypted
+        assert!(table
+            .check_packet(
+                virtual_channels::SCIENCE,
+                0x100,
+                SecurityService::Authenticated
+            )
+            .is_err());
+
+        assert!(table.remove_clear_apid(beacon));
+        assert!(!table.is_always_clear(virtual_channels::SCIENCE, beacon));
+        assert!(table.add_clear_apid(0x800).is_err());
+    }
+
+    #[test]
+    fn test_always_clear_channels() {
+        let mut table = SecurityPolicyTable::default();
+        assert!(table.is_always_clear(virtual_channels::HOUSEKEEPING, 0x100));
+        assert!(table
+            .set_channel_always_clear(virtual_channels::COMMAND, true)
+            .is_err());
+        assert!(table
+            .set_channel_always_clear(MAX_VIRTUAL_CHANNELS as u8, true)
+            .is_err());
+
+        // Tightening housekeeping adds authentication but never encryption
+        table
+            .set_policy(
+                virtual_channels::HOUSEKEEPING,
+                VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 1),
+            )
+            .unwrap();
+        assert_eq!(
+            table.packet_service(virtual_channels::HOUSEKEEPING, 0x100),
+            Some(SecurityService::Authenticated)
+        );
+
+        for apid in 0..MAX_CLEAR_APIDS as u16 {
+            table.add_clear_apid(apid).unwrap();
+        }
+        assert!(table.add_clear_apid(0x7F0).is_err());
+    }
+
+    fn keys() -> KeyStore {
+        let mut keys = KeyStore::new();
+        keys.install(0, [0x5A; KEY_LEN]).unwrap();
+        keys.install(1, [0xC3; KEY_LEN]).unwrap();
+        keys
+    }
+
+    fn command_packet(sequence_count: u16, data: &[u8]) -> SpacePacket {
+        SpacePacket::new(
+            crate::ccsds::PacketType::Command,
+            MessagePriority::Emergency.command_apid(),
+            sequence_count,
+            data,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encrypted_packet_round_trip() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let policy = VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 0);
+        let packet = command_packet(3, b"EMERGENCY_ABORT:0xDEADBEEF");
+
+        let sealed = ground.seal(&packet, policy).unwrap();
+        assert_eq!(sealed.data.len(), packet.data.len() + SECURITY_OVERHEAD);
+        assert!(sealed.verify_crc());
+        assert!(!sealed
+            .data
+            .windows(packet.data.len())
+            .any(|window| window == &packet.data[..]));
+        let header = SecurityHeader::from_bytes(&sealed.data).unwrap();
+        assert_eq!(
+            (header.key_id, header.generation, header.counter),
+            (0, 0, 1)
+        );
+
+        let opened = satellite.open(&sealed, policy, 0).unwrap();
+        assert_eq!(opened.data, packet.data);
+        assert_eq!(opened.header.apid, packet.header.apid);
+
+        // Replays, other key slots and other services are all refused
+        assert!(satellite.open(&sealed, policy, 0).is_err());
+        let next = ground.seal(&packet, policy).unwrap();
+        assert!(satellite
+            .open(
+                &next,
+                VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 1),
+                0
+            )
+            .is_err());
+        assert!(satellite
+            .open(
+                &next,
+                VcSecurityPolicy::new(SecurityService::Authenticated, 0),
+                0
+            )
+            .is_err());
+        assert!(satellite.open(&next, policy, 0).is_ok());
+    }
+
+    #[test]
+    fn test_directions_sealed_under_separate_keys() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let policy = VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 0);
+        let command = command_packet(3, b"SAME PAYLOAD BOTH WAYS");
+        let telemetry = SpacePacket::new(
+            PacketType::Telemetry,
+            command.header.apid,
+            command.header.sequence_count,
+            &command.data,
+            None,
+        )
+        .unwrap();
+
+        // Both ends seal their first frame on the slot with counter 1
+        let uplink = ground.seal(&command, policy).unwrap();
+        let downlink = satellite.seal(&telemetry, policy).unwrap();
+        assert_eq!(
+            SecurityHeader::from_bytes(&uplink.data).unwrap(),
+            SecurityHeader::from_bytes(&downlink.data).unwrap()
+        );
+        let ciphertext = |packet: &SpacePacket| {
+            packet.data[SECURITY_HEADER_LEN..packet.data.len() - GCM_TAG_LEN].to_vec()
+        };
+        assert_ne!(ciphertext(&uplink), ciphertext(&downlink));
+
+        assert_eq!(
+            satellite.open(&uplink, policy, 0).unwrap().data,
+            command.data
+        );
+        assert_eq!(
+            ground.open(&downlink, policy, 0).unwrap().data,
+            command.data
+        );
+    }
+
+    #[test]
+    fn test_counters_checked_per_virtual_channel() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let policy = VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 1);
+        let packet = command_packet(1, b"PLAYBACK");
+        let first = ground.seal(&packet, policy).unwrap();
+        let second = ground.seal(&packet, policy).unwrap();
+        let third = ground.seal(&packet, policy).unwrap();
+
+        // One slot, two channels multiplexed out of counter order
+        let (science, playback) = (virtual_channels::SCIENCE, virtual_channels::PLAYBACK);
+        assert!(satellite.open(&first, policy, playback).is_ok());
+        assert!(satellite.open(&third, policy, science).is_ok());
+        assert!(satellite.open(&second, policy, playback).is_ok());
+
+        // Each channel still refuses replayed and reordered counters
+        assert!(satellite.open(&second, policy, playback).is_err());
+        assert!(satellite.open(&second, policy, science).is_err());
+        assert!(satellite
+            .open(&third, policy, MAX_VIRTUAL_CHANNELS as u8)
+            .is_err());
+    }
+
+    #[test]
+    fn test_authenticated_packet_rejects_tampering() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let policy = VcSecurityPolicy::new(SecurityService::Authenticated, 1);
+        let packet = command_packet(4, b"ORBIT_UPDATE");
+
+        let sealed = ground.seal(&packet, policy).unwrap();
+        assert_eq!(
+            &sealed.data[SECURITY_HEADER_LEN..SECURITY_HEADER_LEN + packet.data.len()],
+            &packet.data[..]
+        );
+
+        let mut tampered = sealed.clone();
+        tampered.data[SECURITY_HEADER_LEN] ^= 0x01;
+        assert!(satellite.open(&tampered, policy, 0).is_err());
+        // The sequence count is authenticated with the payload
+        let mut renumbered = sealed.clone();
+        renumbered.header.sequence_count = 5;
+        assert!(satellite.open(&renumbered, policy, 0).is_err());
+        assert_eq!(
+            satellite.open(&sealed, policy, 0).unwrap().data,
+            packet.data
+        );
+
+        // Clear policies pass packets through untouched
+        let clear = ground.seal(&packet, VcSecurityPolicy::CLEAR).unwrap();
+        assert_eq!(clear.data, packet.data);
+        assert!(KeyStore::new().seal(&packet, policy).is_err());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let policy = VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 0);
+        let packet = command_packet(1, b"ROTATE");
+        let in_flight = ground.seal(&packet, policy).unwrap();
+
+        let rotation = KeyRotation {
+            key_id: 0,
+            generation: 1,
+        };
+        rotation.apply(&mut ground).unwrap();
+        rotation.apply(&mut satellite).unwrap();
+        assert_eq!(ground.generation(0), Some(1));
+        assert!(
+            rotation.apply(&mut satellite).is_err(),
+            "no rollback or replay"
+        );
+
+        // Frames sealed before the rotation still open, new ones use generation 1
+        let rotated = ground.seal(&packet, policy).unwrap();
+        assert_eq!(
+            SecurityHeader::from_bytes(&rotated.data)
+                .unwrap()
+                .generation,
+            1
+        );
+        assert!(satellite.open(&rotated, policy, 0).is_ok());
+        assert!(satellite.open(&in_flight, policy, 0).is_ok());
+
+        // Two rotations on, generation 0 is forgotten
+        let stale = keys().seal(&packet, policy).unwrap();
+        KeyRotation {
+            key_id: 0,
+            generation: 2,
+        }
+        .apply(&mut satellite)
+        .unwrap();
+        assert!(satellite.open(&stale, policy, 0).is_err());
+
+        // A station out of step cannot open the new generation
+        assert!(keys().open(&rotated, policy, 0).is_err());
+        assert!(KeyStore::new().rotate(0, 1).is_err());
+        assert!(KeyStore::new()
+            .install(MAX_KEY_SLOTS as u8, [1; KEY_LEN])
+            .is_err());
+        assert!(KeyStore::new().install(0, [0; KEY_LEN]).is_err());
+        assert!(
+            !format!("{:?}", ground).contains("90"),
+            "no key bytes in debug output"
+        );
+    }
+
+    #[test]
+    fn test_rotation_command_data() {
+        let rotation = KeyRotation {
+            key_id: 2,
+            generation: 7,
+        };
+        let command = rotation.to_command();
+        assert_eq!(command.discriminant(), ROTATE_KEYS_COMMAND_ID);
+        assert!(command.requires_confirmation());
+
+        let mut data = ROTATE_KEYS_COMMAND_ID.to_be_bytes().to_vec();
+        data.extend(serde_json::to_vec(&command).unwrap());
+        assert_eq!(
+            KeyRotation::from_command_data(&data).unwrap().unwrap(),
+            rotation
+        );
+
+        let mut malformed = ROTATE_KEYS_COMMAND_ID.to_be_bytes().to_vec();
+        malformed.extend(b"{}");
+        assert!(KeyRotation::from_command_data(&malformed).unwrap().is_err());
+        assert!(KeyRotation::from_command_data(&0x0026u32.to_be_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_apid_policies() {
+        let mut table = SecurityPolicyTable::default();
+        let emergency = MessagePriority::Emergency.command_apid();
+        let low = MessagePriority::Low.command_apid();
+        assert_eq!(
+            table.packet_service(virtual_channels::COMMAND, emergency),
+            Some(SecurityService::AuthenticatedEncryption)
+        );
+        assert_eq!(
+            table.packet_service(virtual_channels::COMMAND, low),
+            Some(SecurityService::Authenticated)
+        );
+
+        // Command APIDs can never be sent clear
+        assert!(table.set_apid_policy(low, VcSecurityPolicy::CLEAR).is_err());
+        assert!(table
+            .set_apid_policy(0x800, VcSecurityPolicy::CLEAR)
+            .is_err());
+
+        // Low-priority telemetry opts out of its channel's protection
+        let status = 0x120;
+        assert_eq!(
+            table.set_apid_policy(status, VcSecurityPolicy::CLEAR),
+            Ok(None)
+        );
+        assert_eq!(
+            table.packet_service(virtual_channels::SCIENCE, status),
+            Some(SecurityService::Clear)
+        );
+        // ... but not on the command channel
+        assert_eq!(
+            table.packet_service(virtual_channels::COMMAND, status),
+            Some(SecurityService::Authenticated)
+        );
+        assert!(table
+            .check_packet(virtual_channels::SCIENCE, status, SecurityService::Clear)
+            .is_ok());
+
+        // Always-clear marks still keep an encrypting APID policy readable
+        let beacon = 0x7F0;
+        table
+            .set_apid_policy(
+                beacon,
+                VcSecurityPolicy::new(SecurityService::AuthenticatedEncryption, 1),
+            )
+            .unwrap();
+        table.add_clear_apid(beacon).unwrap();
+        assert_eq!(
+            table.packet_policy(virtual_channels::SCIENCE, beacon),
+            Some(VcSecurityPolicy::new(SecurityService::Authenticated, 1))
+        );
+
+        assert_eq!(
+            table.remove_apid_policy(status),
+            Some(VcSecurityPolicy::CLEAR)
+        );
+        assert_eq!(table.apid_policies().count(), 2);
+        for apid in 0x200..0x200 + MAX_APID_POLICIES as u16 - 2 {
+            table
+                .set_apid_policy(apid, VcSecurityPolicy::CLEAR)
+                .unwrap();
+        }
+        assert!(table
+            .set_apid_policy(0x300, VcSecurityPolicy::CLEAR)
+            .is_err());
+    }
+
+    #[test]
+    fn test_protect_follows_policy_table() {
+        let (mut ground, mut satellite) = (keys(), keys());
+        let table = SecurityPolicyTable::default();
+        let packet = command_packet(9, b"SAFE_MODE");
+
+        let sealed = ground
+            .protect(&table, virtual_channels::EMERGENCY, &packet)
+            .unwrap();
+        assert_eq!(sealed.data.len(), packet.data.len() + SECURITY_OVERHEAD);
+        assert!(satellite
+            .unprotect(&table, virtual_channels::HOUSEKEEPING, &sealed)
+            .is_err());
+        assert_eq!(
+            satellite
+                .unprotect(&table, virtual_channels::EMERGENCY, &sealed)
+                .unwrap()
+                .data,
+            packet.data
+        );
+        assert!(ground
+            .protect(&table, MAX_VIRTUAL_CHANNELS as u8, &packet)
+            .is_err());
+
+        assert_eq!(
+            virtual_channels::uplink(packet.header.apid),
+            virtual_channels::EMERGENCY
+        );
+        assert_eq!(virtual_channels::uplink(0x010), virtual_channels::COMMAND);
+        assert_eq!(
+            virtual_channels::downlink(0x102),
+            virtual_channels::HOUSEKEEPING
+        );
+        assert_eq!(virtual_channels::downlink(0x013), virtual_channels::SCIENCE);
+        assert_eq!(
+            virtual_channels::downlink(0x014),
+            virtual_channels::PLAYBACK
+        );
+        assert!(KeyStore::new().is_empty() && !ground.is_empty());
+    }
 }
@@ -0,0 +1,382 @@
+//! Simulated sensor models
+//!
+//! Simulated sensors are declared in a table of [`SensorSpec`]s rather than
+//! hard-coded in the drivers: each has a nominal value, a noise model, a
+//! linear drift and an optional [`FaultProfile`]. A [`SensorSuite`] holds the
+//! table with a deterministic noise source, so the same seed reproduces the
+//! same readings, and lets faults be injected and cleared while running for
+//! telemetry and FDIR testing.
+//!
+//! Values are computed from the time since boot alone, so a sensor read at
+//! irregular intervals still follows its drift and fault timeline.
+//!
+//! # Requirements Traceability
+//! - REQ-NF-001: System Health Monitoring (realistic sensor inputs)
+//! - FN-SNS-001: Data-driven sensor table with noise, drift and faults
+
+use crate::error::{Result, SpaceCommError};
+
+/// Hardware error code reported for a reading lost to a dropout fault
+pub const SENSOR_DROPOUT_ERROR_CODE: u32 = 0x5E01;
+
+/// Milliseconds per hour, the unit of [`SensorSpec::drift_per_hour`]
+const MS_PER_HOUR: f32 = 3_600_000.0;
+
+/// Scale from a sum of four uniform variates on [-1, 1) to unit variance
+const GAUSSIAN_SCALE: f32 = 0.866_025_4;
+
+/// Physical quantity a sensor measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    /// Thermistor, degrees Celsius
+    Temperature,
+    /// Rail voltage, volts
+    Voltage,
+    /// Load current, amperes
+    Current,
+}
+
+impl SensorKind {
+    /// Units of a reading
+    pub const fn units(self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "C",
+            SensorKind::Voltage => "V",
+            SensorKind::Current => "A",
+        }
+    }
+}
+
+/// Random variation added to every reading
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoiseModel {
+    /// Noise-free readings
+    None,
+    /// Uniform noise within plus or minus `amplitude`
+    Uniform {
+        /// Largest deviation from the clean value
+        amplitude: f32,
+    },
+    /// Approximately normal noise with standard deviation `sigma`, bounded
+    /// at about 3.5 sigma
+    Gaussian {
+        /// Standard deviation
+        sigma: f32,
+    },
+}
+
+/// Failure a sensor exhibits on a timeline measured from boot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FaultProfile {
+    /// From `from_ms` on, the reading freezes at the noise-free value it had
+    /// at that time
+    Stuck {
+        /// Time the sensor sticks, milliseconds since boot
+        from_ms: u64,
+    },
+    /// For the first `width_ms` of every `every_ms`, readings jump by
+    /// `magnitude`
+    Spike {
+        /// Spike period, milliseconds
+        every_ms: u64,
+        /// Spike duration within each period, milliseconds
+        width_ms: u64,
+        /// Offset added during a spike
+        magnitude: f32,
+    },
+    /// No reading between `from_ms` and `from_ms + duration_ms`
+    Dropout {
+        /// Start of the dropout, milliseconds since boot
+        from_ms: u64,
+        /// Length of the dropout, milliseconds
+        duration_ms: u64,
+    },
+}
+
+/// Declaration of one simulated sensor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorSpec {
+    /// Quantity measured
+    pub kind: SensorKind,
+    /// Sensor number within its kind
+    pub sensor_id: u16,
+    /// Where the sensor sits, for logs and hardware errors
+    pub name: &'static str,
+    /// Reading at boot without noise
+    pub nominal: f32,
+    /// Variation added to each reading
+    pub noise: NoiseModel,
+    /// Linear change of the clean reading per hour since boot
+    pub drift_per_hour: f32,
+    /// Failure injected into the sensor, if any
+    pub fault: Option<FaultProfile>,
+}
+
+impl SensorSpec {
+    /// Noise-free, drift-free, healthy sensor
+    pub const fn new(kind: SensorKind, sensor_id: u16, name: &'static str, nominal: f32) -> Self {
+        Self {
+            kind,
+            sensor_id,
+            name,
+            nominal,
+            noise: NoiseModel::None,
+            drift_per_hour: 0.0,
+            fault: None,
+        }
+    }
+
+    /// Same sensor with `noise`
+    pub const fn with_noise(mut self, noise: NoiseModel) -> Self {
+        self.noise = noise;
+        self
+    }
+
+    /// Same sensor drifting by `drift_per_hour`
+    pub const fn with_drift(mut self, drift_per_hour: f32) -> Self {
+        self.drift_per_hour = drift_per_hour;
+        self
+    }
+
+    /// Same sensor exhibiting `fault`
+    pub const fn with_fault(mut self, fault: FaultProfile) -> Self {
+        self.fault = Some(fault);
+        self
+    }
+
+    /// Noise-free reading at `elapsed_ms` since boot
+    pub fn clean_value(&self, elapsed_ms: u64) -> f32 {
+        self.nominal + self.drift_per_hour * (elapsed_ms as f32 / MS_PER_HOUR)
+    }
+
+    /// Reading at `elapsed_ms` since boot
+    ///
+    /// - **ID**: FN-SNS-001
+    /// - **Requirement**: Simulated sensors follow their declared nominal
+    ///   value, noise, drift and fault profile (REQ-NF-001).
+    /// - **Inputs**: Time since boot and the suite's noise source.
+    /// - **Outputs**: The reading, or `None` during a dropout.
+    /// - **Side Effects**: Advances `noise` unless the reading is stuck or
+    ///   dropped.
+    pub fn sample(&self, elapsed_ms: u64, noise: &mut NoiseSource) -> Option<f32> {
+        let mut value = match self.fault {
+            Some(FaultProfile::Stuck { from_ms }) if elapsed_ms >= from_ms => {
+                return Some(self.clean_value(from_ms));
+            }
+            Some(FaultProfile::Dropout {
+                from_ms,
+                duration_ms,
+            }) if elapsed_ms >= from_ms && elapsed_ms - from_ms < duration_ms => return None,
+            _ => self.clean_value(elapsed_ms),
+        };
+
+        value += match self.noise {
+            NoiseModel::None => 0.0,
+            NoiseModel::Uniform { amplitude } => amplitude * noise.next_unit(),
+            NoiseModel::Gaussian { sigma } => sigma * noise.next_gaussian(),
+        };
+
+        if let Some(FaultProfile::Spike {
+            every_ms,
+            width_ms,
+            magnitude,
+        }) = self.fault
+        {
+            if every_ms > 0 && elapsed_ms % every_ms < width_ms {
+                value += magnitude;
+            }
+        }
+        Some(value)
+    }
+}
+
+/// Deterministic pseudo-random source for sensor noise (xorshift32)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoiseSource {
+    state: u32,
+}
+
+impl NoiseSource {
+    /// Source starting from `seed`; a zero seed is replaced, as xorshift
+    /// would stay at zero
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+
+    /// Next value, uniform on [-1, 1)
+    pub fn next_unit(&mut self) -> f32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        // 24 bits fit an f32 mantissa exactly
+        (x >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+    }
+
+    /// Next value, approximately normal with unit standard deviation
+    pub fn next_gaussian(&mut self) -> f32 {
+        let sum: f32 = (0..4).map(|_| self.next_unit()).sum();
+        GAUSSIAN_SCALE * sum
+    }
+}
+
+/// Table of simulated sensors with their noise source
+#[derive(Debug, Clone)]
+pub struct SensorSuite<const N: usize> {
+    sensors: [SensorSpec; N],
+    noise: NoiseSource,
+}
+
+impl<const N: usize> SensorSuite<N> {
+    /// Suite reading `sensors` with noise seeded by `seed`
+    ///
+    /// When a kind and sensor number appear twice, the first entry is used.
+    pub const fn new(sensors: [SensorSpec; N], seed: u32) -> Self {
+        Self {
+            sensors,
+            noise: NoiseSource::new(seed),
+        }
+    }
+
+    /// Sensors as currently configured
+    pub fn sensors(&self) -> &[SensorSpec] {
+        &self.sensors
+    }
+
+    /// Sensor `sensor_id` of `kind`
+    pub fn sensor(&self, kind: SensorKind, sensor_id: u16) -> Option<&SensorSpec> {
+        self.sensors
+            .iter()
+            .find(|spec| spec.kind == kind && spec.sensor_id == sensor_id)
+    }
+
+    /// Read sensor `sensor_id` of `kind` at `elapsed_ms` since boot
+    ///
+    /// # Errors
+    /// - `ConfigurationError` when the table has no such sensor
+    /// - `HardwareFailure` with [`SENSOR_DROPOUT_ERROR_CODE`] during a dropout
+    pub fn read(&mut self, kind: SensorKind, sensor_id: u16, elapsed_ms: u64) -> Result<f32> {
+        let spec = *self.sensor(kind, sensor_id).ok_or(unknown_sensor())?;
+        spec.sample(elapsed_ms, &mut self.noise)
+            .ok_or(SpaceCommError::HardwareFailure {
+                component: spec.name,
+                error_code: SENSOR_DROPOUT_ERROR_CODE,
+            })
+    }
+
+    /// Inject `fault` into sensor `sensor_id` of `kind`, or clear its fault
+    /// with `None`
+    ///
+    /// - **ID**: FN-SNS-002
+    /// - **Requirement**: Faults can be injected into and cleared from a
+    ///   running sensor suite for FDIR testing.
+    /// - **Failure Modes**: `ConfigurationError` when the table has no such
+    ///   sensor.
+    pub fn set_fault(
+        &mut self,
+        kind: SensorKind,
+        sensor_id: u16,
+        fault: Option<FaultProfile>,
+    ) -> Result<()> {
+        let spec = self
+            .sensors
+            .iter_mut()
+            .find(|spec| spec.kind == kind && spec.sensor_id == sensor_id)
+            .ok_or(unknown_sensor())?;
+        spec.fault = fault;
+        Ok(())
+    }
+}
+
+fn unknown_sensor() -> SpaceCommError {
+    SpaceCommError::ConfigurationError {
+        parameter: "sensor_id",
+        value: "<sensor>",
+        reason: "no sensor of that kind and number in the sensor table",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: [SensorSpec; 2] = [
+        SensorSpec::new(SensorKind::Temperature, 0, "Main board", 25.0)
+            .with_noise(NoiseModel::Gaussian { sigma: 0.5 })
+            .with_drift(2.0),
+        SensorSpec::new(SensorKind::Voltage, 0, "Main bus", 12.0),
+    ];
+
+    #[test]
+    fn test_noise_and_drift() {
+        let mut suite = SensorSuite::new(TABLE, 7);
+        let hour_ms = 3_600_000;
+        let readings: Vec<f32> = (0..200)
+            .map(|_| suite.read(SensorKind::Temperature, 0, hour_ms).unwrap())
+            .collect();
+        let mean = readings.iter().sum::<f32>() / readings.len() as f32;
+        assert!((mean - 27.0).abs() < 0.15, "mean {}", mean);
+        assert!(readings.iter().all(|r| (r - 27.0).abs() <= 0.5 * 3.5));
+        assert!(readings.windows(2).any(|w| w[0] != w[1]));
+
+        // The same seed reproduces the same readings
+        let mut replay = SensorSuite::new(TABLE, 7);
+        assert_eq!(
+            replay.read(SensorKind::Temperature, 0, hour_ms).unwrap(),
+            readings[0]
+        );
+        assert_eq!(suite.read(SensorKind::Voltage, 0, hour_ms).unwrap(), 12.0);
+        assert!(suite.read(SensorKind::Current, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_fault_profiles() {
+        let mut suite = SensorSuite::new(TABLE, 7);
+        let voltage = |suite: &mut SensorSuite<2>, ms| suite.read(SensorKind::Voltage, 0, ms);
+
+        let spike = FaultProfile::Spike {
+            every_ms: 1_000,
+            width_ms: 100,
+            magnitude: 4.0,
+        };
+        suite
+            .set_fault(SensorKind::Voltage, 0, Some(spike))
+            .unwrap();
+        assert_eq!(voltage(&mut suite, 2_050).unwrap(), 16.0);
+        assert_eq!(voltage(&mut suite, 2_150).unwrap(), 12.0);
+
+        let dropout = FaultProfile::Dropout {
+            from_ms: 5_000,
+            duration_ms: 1_000,
+        };
+        suite
+            .set_fault(SensorKind::Voltage, 0, Some(dropout))
+            .unwrap();
+        assert_eq!(
+            voltage(&mut suite, 5_500),
+            Err(SpaceCommError::HardwareFailure {
+                component: "Main bus",
+                error_code: SENSOR_DROPOUT_ERROR_CODE,
+            })
+        );
+        assert_eq!(voltage(&mut suite, 6_000).unwrap(), 12.0);
+
+        let stuck = FaultProfile::Stuck { from_ms: 1_800_000 };
+        suite
+            .set_fault(SensorKind::Temperature, 0, Some(stuck))
+            .unwrap();
+        let later = suite.read(SensorKind::Temperature, 0, 7_200_000).unwrap();
+        assert_eq!(later, 26.0);
+        assert_eq!(
+            suite.read(SensorKind::Temperature, 0, 9_000_000).unwrap(),
+            later
+        );
+
+        suite.set_fault(SensorKind::Voltage, 0, None).unwrap();
+        assert_eq!(voltage(&mut suite, 5_500).unwrap(), 12.0);
+        assert!(suite.set_fault(SensorKind::Current, 3, None).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! Packet sequence count windows
+//!
+//! Every CCSDS packet carries a 14-bit sequence count that its sender
+//! increments per APID and that wraps from 0x3FFF back to 0. A receiver
+//! compares the count of each packet with the last one it accepted on the
+//! same APID to tell a packet in sequence from a gap, a late or duplicated
+//! copy, or a count so far off that the sender must have restarted.
+//!
+//! Counts are compared modulo [`SEQUENCE_COUNT_MODULUS`]: the signed
+//! distance from one count to the next is taken as the shorter way around,
+//! so 0x3FFF is one behind 0 and rollover needs no special case. A
+//! [`SequenceWindow`] accepts counts up to its window size ahead of the last
+//! one, and rejects counts at or behind it. A sender restart puts counts
+//! outside the window; two consecutive counts there resynchronise the window
+//! instead of locking the APID out until the counter wraps back.
+//!
+//! # Requirements Traceability
+//! - REQ-SF-001: Command validation (stale and replayed counts rejected)
+//! - REQ-NF-004: Fault Tolerance (recovery from sender restarts)
+
+use heapless::Vec;
+
+use crate::error::{Result, SpaceCommError};
+
+/// Sequence counts before the 14-bit counter wraps
+pub const SEQUENCE_COUNT_MODULUS: u16 = 0x4000;
+
+/// Mask of the sequence count bits
+pub const SEQUENCE_COUNT_MASK: u16 = SEQUENCE_COUNT_MODULUS - 1;
+
+/// Counts accepted ahead of the last one when no window is configured
+pub const DEFAULT_SEQUENCE_WINDOW: u16 = 64;
+
+/// Largest window size: half the count space, less the count behind
+pub const MAX_SEQUENCE_WINDOW: u16 = SEQUENCE_COUNT_MODULUS / 2 - 1;
+
+/// Count following `count`, wrapping at [`SEQUENCE_COUNT_MODULUS`]
+pub const fn next_sequence_count(count: u16) -> u16 {
+    count.wrapping_add(1) & SEQUENCE_COUNT_MASK
+}
+
+/// Signed distance from `from` to `to`, the shorter way around
+///
+/// - **ID**: FN-SEQ-001
+/// - **Requirement**: Compare sequence counts across rollover without
+///   special cases (REQ-SF-001).
+/// - **Inputs**:
+///   - `from`: Reference count; bits above the mask are ignored.
+///   - `to`: Count compared with it; bits above the mask are ignored.
+/// - **Outputs**: Offset in -8192..=8191; positive when `to` is ahead.
+pub const fn sequence_offset(from: u16, to: u16) -> i16 {
+    let forward = to.wrapping_sub(from) & SEQUENCE_COUNT_MASK;
+    if forward < SEQUENCE_COUNT_MODULUS / 2 {
+        forward as i16
+    } else {
+        forward as i16 - SEQUENCE_COUNT_MODULUS as i16
+    }
+}
+
+/// Outcome of checking a received sequence count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceVerdict {
+    /// First count seen on the APID
+    First,
+    /// One ahead of the last count
+    InSequence,
+    /// Ahead within the window, with counts skipped
+    Gap {
+        /// Counts skipped
+        missed: u16,
+    },
+    /// Outside the window, but following the previous rejected count: the
+    /// sender restarted and the window now follows it
+    Resynchronized {
+        /// Offset from the last count accepted before the restart
+        offset: i16,
+    },
+    /// At or behind the last count: a duplicate when `by` is 0, otherwise a
+    /// late or replayed packet
+    Behind {
+        /// Counts behind the last one
+        by: u16,
+    },
+    /// Too far ahead or behind to place
+    OutOfWindow {
+        /// Offset from the last count
+        offset: i16,
+    },
+}
+
+impl SequenceVerdict {
+    /// Whether the packet should be processed
+    pub const fn is_accepted(&self) -> bool {
+        !matches!(self, Self::Behind { .. } | Self::OutOfWindow { .. })
+    }
+}
+
+/// Counters kept by a [`SequenceWindow`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SequenceStats {
+    /// Packets accepted
+    pub accepted: u32,
+    /// Counts skipped by accepted packets
+    pub missed: u32,
+    /// Packets rejected as behind or out of window
+    pub rejected: u32,
+    /// Resynchronisations after a sender restart
+    pub resyncs: u32,
+}
+
+/// Acceptance window over the sequence counts of one APID
+///
+/// - **ID**: MOD-SEQ-001
+/// - **Requirement**: Accept counts ahead of the last one within the window
+///   and reject stale or replayed counts, across rollover (REQ-SF-001).
+/// - **Failure Modes**: A gap larger than the window costs the next packet,
+///   which is rejected before the one after it resynchronises.
+/// - **Constraints**: `window` in 1..=[`MAX_SEQUENCE_WINDOW`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceWindow {
+    window: u16,
+    last: Option<u16>,
+    candidate: Option<u16>,
+    stats: SequenceStats,
+}
+
+impl Default for SequenceWindow {
+    /// Window of [`DEFAULT_SEQUENCE_WINDOW`] counts
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_SEQUENCE_WINDOW,
+            last: None,
+            candidate: None,
+            stats: SequenceStats::default(),
+        }
+    }
+}
+
+impl SequenceWindow {
+    /// Create a window accepting up to `window` counts ahead of the last
+    ///
+    /// # Errors
+    /// `ConfigurationError` if `window` is 0 or above
+    /// [`MAX_SEQUENCE_WINDOW`]
+    pub fn new(window: u16) -> Result<Self> {
+        if window == 0 || window > MAX_SEQUENCE_WINDOW {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "sequence_window",
+                value: "out of range",
+                reason: "window must be 1 to 8191 counts",
+            });
+        }
+        Ok(Self {
+            window,
+            ..Self::default()
+        })
+    }
+
+    /// Counts accepted ahead of the last one
+    pub const fn window(&self) -> u16 {
+        self.window
+    }
+
+    /// Last count accepted
+    pub const fn last(&self) -> Option<u16> {
+        self.last
+    }
+
+    /// Counters since the window was created or reset
+    pub const fn stats(&self) -> SequenceStats {
+        self.stats
+    }
+
+    /// Check a received count, following it if accepted
+    ///
+    /// - **ID**: FN-SEQ-002
+    /// - **Requirement**: Classify each count against the last accepted one
+    ///   and resynchronise after a sender restart (REQ-SF-001, REQ-NF-004).
+    /// - **Inputs**:
+    ///   - `count`: Sequence count of the received packet.
+    /// - **Outputs**: Verdict; the packet is processed only if
+    ///   [`SequenceVerdict::is_accepted`].
+    /// - **Side Effects**: Moves the window to an accepted count; remembers a
+    ///   rejected out-of-window count as a resynchronisation candidate;
+    ///   updates the counters.
+    pub fn check(&mut self, count: u16) -> SequenceVerdict {
+        let count = count & SEQUENCE_COUNT_MASK;
+        let Some(last) = self.last else {
+            self.accept(count, 0);
+            return SequenceVerdict::First;
+        };
+
+        let offset = sequence_offset(last, count);
+        let verdict = if offset == 1 {
+            SequenceVerdict::InSequence
+        } else if offset > 1 && offset as u16 <= self.window {
+            SequenceVerdict::Gap {
+                missed: offset as u16 - 1,
+            }
+        } else if offset <= 0 && offset.unsigned_abs() <= self.window {
+            SequenceVerdict::Behind {
+                by: offset.unsigned_abs(),
+            }
+        } else if self.candidate == Some(count.wrapping_sub(1) & SEQUENCE_COUNT_MASK) {
+            SequenceVerdict::Resynchronized { offset }
+        } else {
+            SequenceVerdict::OutOfWindow { offset }
+        };
+
+        match verdict {
+            SequenceVerdict::Gap { missed } => self.accept(count, missed),
+            SequenceVerdict::Resynchronized { .. } => {
+                self.stats.resyncs = self.stats.resyncs.saturating_add(1);
+                self.accept(count, 0);
+            }
+            SequenceVerdict::OutOfWindow { .. } => {
+                self.candidate = Some(count);
+                self.stats.rejected = self.stats.rejected.saturating_add(1);
+            }
+            SequenceVerdict::Behind { .. } => {
+                self.stats.rejected = self.stats.rejected.saturating_add(1);
+            }
+            _ => self.accept(count, 0),
+        }
+        verdict
+    }
+
+    /// Forget the last count and counters, keeping the window size
+    pub fn reset(&mut self) {
+        *self = Self {
+            window: self.window,
+            ..Self::default()
+        };
+    }
+
+    fn accept(&mut self, count: u16, missed: u16) {
+        self.last = Some(count);
+        self.candidate = None;
+        self.stats.accepted = self.stats.accepted.saturating_add(1);
+        self.stats.missed = self.stats.missed.saturating_add(u32::from(missed));
+    }
+}
+
+/// Sequence windows for up to `N` APIDs
+///
+/// An APID gets a window of the default size the first time a count arrives
+/// on it, unless [`SequenceWindows::set_window`] configured it beforehand.
+#[derive(Debug, Clone)]
+pub struct SequenceWindows<const N: usize> {
+    default_window: u16,
+    windows: Vec<(u16, SequenceWindow), N>,
+}
+
+impl<const N: usize> Default for SequenceWindows<N> {
+    /// Windows of [`DEFAULT_SEQUENCE_WINDOW`] counts
+    fn default() -> Self {
+        Self {
+            default_window: DEFAULT_SEQUENCE_WINDOW,
+            windows: Vec::new(),
+        }
+    }
+}
+
+impl<const N: usize> SequenceWindows<N> {
+    /// Create windows of `default_window` counts for APIDs not configured
+    ///
+    /// # Errors
+    /// `ConfigurationError` if `default_window` is out of range
+    pub fn new(default_window: u16) -> Result<Self> {
+        SequenceWindow::new(default_window)?;
+        Ok(Self {
+            default_window,
+            windows: Vec::new(),
+        })
+    }
+
+    /// Window size for APIDs not configured
+    pub const fn default_window(&self) -> u16 {
+        self.default_window
+    }
+
+    /// Configure the window size of `apid`, keeping its last count
+    ///
+    /// # Errors
+    /// `ConfigurationError` if `window` is out of range;
+    /// `ResourceExhausted` if `N` APIDs are already tracked
+    pub fn set_window(&mut self, apid: u16, window: u16) -> Result<()> {
+        let mut configured = SequenceWindow::new(window)?;
+        if let Some((_, existing)) = self.windows.iter_mut().find(|(a, _)| *a == apid) {
+            configured.last = existing.last;
+            configured.stats = existing.stats;
+            *existing = configured;
+            return Ok(());
+        }
+        self.windows
+            .push((apid, configured))
+            .map_err(|_| Self::exhausted())
+    }
+
+    /// Check a count received on `apid`
+    ///
+    /// # Errors
+    /// `ResourceExhausted` if `apid` is new and `N` APIDs are already tracked
+    pub fn check(&mut self, apid: u16, count: u16) -> Result<SequenceVerdict> {
+        if let Some((_, window)) = self.windows.iter_mut().find(|(a, _)| *a == apid) {
+            return Ok(window.check(count));
+        }
+        let mut window = SequenceWindow::new(self.default_window)?;
+        let verdict = window.check(count);
+        self.windows
+            .push((apid, window))
+            .map_err(|_| Self::exhausted())?;
+        Ok(verdict)
+    }
+
+    /// Window of `apid`, if tracked
+    pub fn window(&self, apid: u16) -> Option<&SequenceWindow> {
+        self.windows
+            .iter()
+            .find(|(a, _)| *a == apid)
+            .map(|(_, window)| window)
+    }
+
+    /// Tracked APIDs and their windows, in first-seen order
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &SequenceWindow)> {
+        self.windows.iter().map(|(apid, window)| (*apid, window))
+    }
+
+    /// Forget the last count of every APID, keeping window sizes
+    pub fn reset(&mut self) {
+        for (_, window) in self.windows.iter_mut() {
+            window.reset();
+        }
+    }
+
+    fn exhausted() -> SpaceCommError {
+        SpaceCommError::ResourceExhausted {
+            resource: "sequence windows",
+            current_usage: N as u32,
+            max_usage: N as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_across_rollover() {
+        assert_eq!(next_sequence_count(0x3FFF), 0);
+        assert_eq!(sequence_offset(0x3FFF, 0), 1);
+        assert_eq!(sequence_offset(0, 0x3FFF), -1);
+        assert_eq!(sequence_offset(0x3FF0, 0x0010), 0x20);
+        assert_eq!(sequence_offset(10, 10), 0);
+        assert_eq!(sequence_offset(0, 0x1FFF), 0x1FFF);
+        assert_eq!(sequence_offset(0, 0x2000), -0x2000);
+    }
+
+    #[test]
+    fn test_window_verdicts_and_resync() {
+        let mut window = SequenceWindow::new(8).unwrap();
+        assert_eq!(window.check(0x3FFE), SequenceVerdict::First);
+        assert_eq!(window.check(0x3FFF), SequenceVerdict::InSequence);
+        assert_eq!(window.check(2), SequenceVerdict::Gap { missed: 2 });
+        assert_eq!(window.check(2), SequenceVerdict::Behind { by: 0 });
+        assert_eq!(window.check(0x3FFF), SequenceVerdict::Behind { by: 3 });
+
+        // A restarted sender is followed after two consecutive counts
+        assert_eq!(
+            window.check(500),
+            SequenceVerdict::OutOfWindow { offset: 498 }
+        );
+        assert_eq!(
+            window.check(501),
+            SequenceVerdict::Resynchronized { offset: 499 }
+        );
+        assert_eq!(window.check(502), SequenceVerdict::InSequence);
+
+        let stats = window.stats();
+        assert_eq!(stats.accepted, 5);
+        assert_eq!(stats.missed, 2);
+        assert_eq!(stats.rejected, 3);
+        assert_eq!(stats.resyncs, 1);
+        assert!(SequenceWindow::new(0).is_err());
+        assert!(SequenceWindow::new(MAX_SEQUENCE_WINDOW + 1).is_err());
+    }
+
+    #[test]
+    fn test_windows_per_apid() {
+        let mut windows = SequenceWindows::<2>::new(4).unwrap();
+        windows.set_window(0x001, 1).unwrap();
+        windows.check(0x001, 10).unwrap();
+        assert_eq!(
+            windows.check(0x001, 12).unwrap(),
+            SequenceVerdict::OutOfWindow { offset: 2 }
+        );
+        windows.check(0x100, 10).unwrap();
+        assert_eq!(
+            windows.check(0x100, 12).unwrap(),
+            SequenceVerdict::Gap { missed: 1 }
+        );
+        assert_eq!(windows.window(0x100).unwrap().window(), 4);
+        assert!(windows.check(0x101, 0).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Layered settings of the workspace binaries
+//!
+//! The `space-comms` subcommands, the `ground-station` console and the
+//! interactive simulator read one settings file and one set of environment
+//! variables, so a station is configured once whichever binary runs it.
+//! Settings come from four layers, each overriding the one before:
+//!
+//! 1. The binary's built-in defaults
+//! 2. The configuration file: `--config <PATH>`, else `$SPACE_COMMS_CONFIG`,
+//!    else `space-comms.toml` in the working directory if there is one.
+//!    TOML, JSON or YAML by extension
+//! 3. Environment variables `SPACE_COMMS_<SECTION>__<KEY>`, e.g.
+//!    `SPACE_COMMS_GROUND__TELEMETRY_PORT=9000`
+//! 4. The binary's flags
+//!
+//! The file has one section per subject (`[ground]`, `[satellite_sim]`,
+//! `[simulate]`). A binary reads the section it needs with [`section`],
+//! taking the keys it knows and leaving the rest to the binaries that use
+//! them.
+//!
+//! Only built with the `settings` feature.
+//!
+//! # Requirements Traceability
+//! - FN-CLI-003: Settings layered from defaults, file, environment and flags
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use config::{Config, ConfigError, Environment, File};
+use serde::de::DeserializeOwned;
+
+/// Prefix of the configuration environment variables
+pub const ENV_PREFIX: &str = "SPACE_COMMS";
+
+/// Separator of section and key in environment variable names
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Environment variable naming the configuration file
+pub const CONFIG_ENV: &str = "SPACE_COMMS_CONFIG";
+
+/// Configuration file read from the working directory if none is named
+pub const DEFAULT_CONFIG_FILE: &str = "space-comms.toml";
+
+/// Command-line flag naming the configuration file
+pub const CONFIG_FLAG: &str = "--config";
+
+/// Configuration file to read: the one named, else the one in `env`, else
+/// the default file if it exists
+pub fn config_file(named: Option<&Path>, env: &HashMap<String, String>) -> Option<PathBuf> {
+    named
+        .map(Path::to_path_buf)
+        .or_else(|| env.get(CONFIG_ENV).map(PathBuf::from))
+        .or_else(|| {
+            let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+            default.is_file().then_some(default)
+        })
+}
+
+/// Environment variable setting dotted `key`, e.g. `ground.telemetry_port`
+pub fn env_var(key: &str) -> String {
+    format!(
+        "{}_{}",
+        ENV_PREFIX,
+        key.replace('.', ENV_SEPARATOR).to_uppercase()
+    )
+}
+
+/// Source of the settings in `env`
+///
+/// Only `SPACE_COMMS_*` variables are read, and not the one naming the
+/// file. Values are parsed as numbers and booleans where they can be; the
+/// dotted keys in `list_keys` take comma-separated lists.
+pub fn environment(env: HashMap<String, String>, list_keys: &[&str]) -> Environment {
+    let variables: config::Map<String, String> = env
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX) && name != CONFIG_ENV)
+        .collect();
+    let mut environment = Environment::with_prefix(ENV_PREFIX)
+        .prefix_separator("_")
+        .separator(ENV_SEPARATOR)
+        .try_parsing(true)
+        .list_separator(",")
+        .source(Some(variables));
+    for key in list_keys {
+        environment = environment.with_list_parse_key(key);
+    }
+    environment
+}
+
+/// Read one section of the settings below the flags
+///
+/// - **ID**: FN-CLI-003
+/// - **Requirement**: Every binary resolves its settings the same way,
+///   defaults < file < environment < flags.
+/// - **Inputs**: Section name; the file named on the command line, if any;
+///   the process environment.
+/// - **Outputs**: The section, with `T::default()` where neither the file
+///   nor the environment sets it. `T` must be `#[serde(default)]` for keys
+///   to default one by one.
+/// - **Failure Modes**: A named file that is missing or unreadable, or a
+///   value of the wrong type → `Err`. Keys `T` does not have are ignored:
+///   they belong to other binaries.
+pub fn section<T: DeserializeOwned + Default>(
+    name: &str,
+    named: Option<&Path>,
+    env: HashMap<String, String>,
+) -> Result<T, String> {
+    let mut builder = Config::builder();
+    if let Some(path) = config_file(named, &env) {
+        builder = builder.add_source(File::from(path.as_path()).required(true));
+    }
+    let config = builder
+        .add_source(environment(env, &[]))
+        .build()
+        .map_err(|e| format!("configuration: {}", e))?;
+    match config.get(name) {
+        Ok(section) => Ok(section),
+        Err(ConfigError::NotFound(_)) => Ok(T::default()),
+        Err(e) => Err(format!("configuration: {}", e)),
+    }
+}
+
+/// File named by the last `--config <PATH>` of the process arguments
+pub fn config_from_args() -> Option<PathBuf> {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2)
+        .rev()
+        .find(|pair| pair[0] == CONFIG_FLAG)
+        .map(|pair| PathBuf::from(&pair[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(default)]
+    struct Station {
+        operator: String,
+        telemetry_port: u16,
+        dry_run: bool,
+    }
+
+    impl Default for Station {
+        fn default() -> Self {
+            Self {
+                operator: "console".to_string(),
+                telemetry_port: 8001,
+                dry_run: false,
+            }
+        }
+    }
+
+    fn env(variables: &[(&str, &str)]) -> HashMap<String, String> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_section_layers_file_and_environment_over_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("station.toml");
+        std::fs::write(
+            &path,
+            "[ground]\noperator = \"night shift\"\ntelemetry_port = 9000\nduration_s = 30\n\
+             [simulate]\ndistance_km = 2000.0\n",
+        )
+        .unwrap();
+
+        // Keys of other binaries are left to them
+        let station: Station = section(
+            "ground",
+            Some(&path),
+            env(&[("SPACE_COMMS_GROUND__TELEMETRY_PORT", "9100")]),
+        )
+        .unwrap();
+        assert_eq!(
+            station,
+            Station {
+                operator: "night shift".to_string(),
+                telemetry_port: 9100,
+                dry_run: false,
+            }
+        );
+        assert_eq!(
+            env_var("ground.telemetry_port"),
+            "SPACE_COMMS_GROUND__TELEMETRY_PORT"
+        );
+
+        // No file and no variables: the binary's defaults
+        let station: Station = section("ground", None, HashMap::new()).unwrap();
+        assert_eq!(station, Station::default());
+    }
+
+    #[test]
+    fn test_section_rejects_bad_values_and_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.toml");
+        assert!(section::<Station>("ground", Some(&missing), HashMap::new()).is_err());
+
+        let bad = env(&[("SPACE_COMMS_GROUND__TELEMETRY_PORT", "soon")]);
+        assert!(section::<Station>("ground", None, bad).is_err());
+    }
+}
@@ -4,11 +4,142 @@
 //! used throughout the space communication system.
 
 use serde::{Deserialize, Serialize};
-use crate::types::{ComponentId, HealthStatus, BandType};
-use crate::error::Result;
+use crate::types::{ComponentId, HealthStatus, BandType, OperationalMode};
+use crate::error::{Result, SpaceCommError};
+use crate::eps::EpsField;
+use crate::formation::RangingField;
+use crate::link_forecast::DownlinkPolicyStats;
+use crate::link_stats::LinkStatsField;
+use crate::navigation::NavigationField;
+use crate::power_attribution::Subsystem;
+use crate::priority_inversion::InversionCounters;
+use crate::rf_housekeeping::{RecoveryField, RfField};
+use crate::wire::{WireReader, WireWriter};
+
+/// Well-known measurement identifiers
+///
+/// Ranges follow the onboard collector layout: 0x0001-0x000F temperatures,
+/// 0x0010-0x001F bus voltages, 0x0020-0x002F currents, 0x0030-0x004F status
+/// and housekeeping values, 0x0050-0x007F RF housekeeping, 0x0080-0x00A7
+/// transceiver lock recovery, 0x00B0-0x00BF priority inversion counters,
+/// 0x00C0-0x00CF the EPS summary, 0x00D0-0x00D7 crosslink ranging,
+/// 0x00E0-0x00EF per-subsystem power attribution, 0x00F0-0x00F5 the
+/// navigation solution and 0x0100-0x0107 link layer statistics.
+pub mod measurement_ids {
+    /// Battery (primary bus) voltage, V
+    pub const BATTERY_VOLTAGE: u16 = 0x0010;
+    /// Battery pack temperature, °C
+    pub const BATTERY_TEMPERATURE: u16 = 0x0001;
+    /// On-board computer temperature, °C
+    pub const OBC_TEMPERATURE: u16 = 0x0002;
+    /// Current operational mode (`OperationalMode` discriminant)
+    pub const OPERATIONAL_MODE: u16 = 0x0030;
+    /// Cause of the most recent processor reset
+    pub const LAST_RESET_REASON: u16 = 0x0031;
+    /// Uplinked commands accepted since boot
+    pub const UPLINK_COMMANDS_ACCEPTED: u16 = 0x0032;
+    /// Uplinked commands rejected since boot
+    pub const UPLINK_COMMANDS_REJECTED: u16 = 0x0033;
+    /// Housekeeping frames dropped on telemetry queue overflow; the event and
+    /// alarm counters follow (see [`crate::telemetry_queue::TelemetryClass`])
+    pub const TELEMETRY_DROPS_BASE: u16 = 0x0034;
+    /// Uplinked commands discarded as duplicates since boot; see
+    /// [`crate::command_dedup`]
+    pub const UPLINK_DUPLICATES_RECEIVED: u16 = 0x0037;
+    /// Commands and autonomous actions refused by a flight rule since boot;
+    /// see [`crate::flight_rules`]
+    pub const FLIGHT_RULE_VIOLATIONS: u16 = 0x0038;
+    /// Current mission phase (`MissionPhase` discriminant); see
+    /// [`crate::mission_phase`]
+    pub const MISSION_PHASE: u16 = 0x0039;
+    /// Autonomy rule executions since boot; see [`crate::autonomy`]
+    pub const AUTONOMY_EXECUTIONS: u16 = 0x003A;
+    /// Security policy of virtual channel 0; channel `n` reports at this
+    /// ID plus `n` (`VcSecurityPolicy::report_code` encoding)
+    pub const VC_SECURITY_POLICY_BASE: u16 = 0x0040;
+    /// First RF housekeeping measurement; see [`crate::rf_housekeeping`]
+    pub const RF_HOUSEKEEPING_BASE: u16 = 0x0050;
+    /// First transceiver lock recovery measurement; see
+    /// [`crate::rf_housekeeping::RecoveryField`]
+    pub const LOCK_RECOVERY_BASE: u16 = 0x0080;
+    /// First priority inversion counter; see [`crate::priority_inversion`]
+    pub const PRIORITY_INVERSION_BASE: u16 = 0x00B0;
+    /// First EPS summary measurement; see [`crate::eps`]
+    pub const EPS_BASE: u16 = 0x00C0;
+    /// First crosslink ranging measurement; see [`crate::formation`]
+    pub const CROSSLINK_RANGING_BASE: u16 = 0x00D0;
+    /// First per-subsystem power draw; see [`crate::power_attribution`]
+    pub const POWER_ATTRIBUTION_BASE: u16 = 0x00E0;
+    /// First navigation solution measurement; see [`crate::navigation`]
+    pub const NAVIGATION_BASE: u16 = 0x00F0;
+    /// First link layer statistic; see [`crate::link_stats`]
+    pub const LINK_STATS_BASE: u16 = 0x0100;
+    /// Recorder downlink policy in use, followed by the statistics of each
+    /// policy; see [`crate::link_forecast`]
+    pub const DOWNLINK_POLICY_BASE: u16 = 0x0110;
+}
+
+/// APID of the standard telemetry packet
+pub const TELEMETRY_APID: u16 = 0x100;
+
+/// Selection of measurements the collector downlinks
+///
+/// In safe mode only the values operators need to diagnose and recover the
+/// spacecraft are sent, at a reduced rate, to save power and link time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TelemetrySet {
+    /// Every measurement the collector produces
+    Full,
+    /// Battery, temperatures, mode, mission phase, last reset reason and
+    /// uplink counters
+    SafeMode,
+}
+
+impl TelemetrySet {
+    /// Measurements included in the safe-mode set
+    pub const SAFE_MODE_IDS: [u16; 9] = [
+        measurement_ids::BATTERY_VOLTAGE,
+        measurement_ids::BATTERY_TEMPERATURE,
+        measurement_ids::OBC_TEMPERATURE,
+        measurement_ids::OPERATIONAL_MODE,
+        measurement_ids::MISSION_PHASE,
+        measurement_ids::LAST_RESET_REASON,
+        measurement_ids::UPLINK_COMMANDS_ACCEPTED,
+        measurement_ids::UPLINK_COMMANDS_REJECTED,
+        measurement_ids::UPLINK_DUPLICATES_RECEIVED,
+    ];
+
+    /// Collection interval multiplier applied in safe mode
+    pub const SAFE_MODE_INTERVAL_FACTOR: u64 = 50;
+
+    /// Set to collect in the given operational mode
+    pub const fn for_mode(mode: OperationalMode) -> Self {
+        match mode {
+            OperationalMode::Safe | OperationalMode::Emergency => TelemetrySet::SafeMode,
+            _ => TelemetrySet::Full,
+        }
+    }
+
+    /// Whether a measurement belongs to this set
+    pub fn includes(&self, measurement_id: u16) -> bool {
+        match self {
+            TelemetrySet::Full => true,
+            TelemetrySet::SafeMode => Self::SAFE_MODE_IDS.contains(&measurement_id),
+        }
+    }
+
+    /// Collection interval for this set given the nominal interval
+    pub const fn interval_ms(&self, nominal_ms: u64) -> u64 {
+        match self {
+            TelemetrySet::Full => nominal_ms,
+            TelemetrySet::SafeMode => nominal_ms * Self::SAFE_MODE_INTERVAL_FACTOR,
+        }
+    }
+}
 
 /// Telemetry data point
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TelemetryData {
     /// Component that generated this telemetry
     pub source: ComponentId,
@@ -17,14 +148,112 @@ pub struct TelemetryData {
     pub timestamp: u64,
 
     /// Telemetry measurements
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<Measurement>", length(max = 32)))]
     pub measurements: heapless::Vec<Measurement, 32>,
 
     /// Overall system health status
     pub health_status: HealthStatus,
 }
 
+impl TelemetryData {
+    /// Drop every measurement not in `set`
+    pub fn retain_set(&mut self, set: TelemetrySet) {
+        self.measurements.retain(|m| set.includes(m.measurement_id));
+    }
+
+    /// Encode the timestamp and measurements as a telemetry packet data field
+    ///
+    /// Layout: timestamp (u64), measurement count (u16), then per
+    /// measurement its ID (u16), a flags byte (value kind in the high nibble,
+    /// quality code in the low nibble) and a 4-byte value, in the
+    /// [`crate::wire`] byte order. Floats are sent as `f32`, integers and
+    /// booleans as `i32`; other value types are not carried and go out as
+    /// `NotAvailable`.
+    pub fn to_payload(&self) -> Result<heapless::Vec<u8, 2048>> {
+        let mut payload = WireWriter::new();
+        payload
+            .put(self.timestamp)?
+            .put(self.measurements.len() as u16)?;
+
+        for measurement in &self.measurements {
+            let mut quality = measurement.quality;
+            let (kind, bits) = match &measurement.value {
+                MeasurementValue::Float(v) => (VALUE_KIND_FLOAT, (*v as f32).to_bits()),
+                MeasurementValue::Integer(v) => (VALUE_KIND_INTEGER, *v as i32 as u32),
+                MeasurementValue::Boolean(v) => (VALUE_KIND_INTEGER, u32::from(*v)),
+                _ => {
+                    quality = MeasurementQuality::NotAvailable;
+                    (VALUE_KIND_INTEGER, 0)
+                }
+            };
+            payload
+                .put(measurement.measurement_id)?
+                .put((kind << 4) | quality.code())?
+                .put(bits)?;
+        }
+
+        Ok(payload.finish())
+    }
+
+    /// Decode a data field produced by [`TelemetryData::to_payload`]
+    ///
+    /// Units come from the telemetry dictionary; measurements outside it
+    /// decode with an empty unit.
+    pub fn from_payload(
+        source: ComponentId,
+        health_status: HealthStatus,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        if bytes.len() < 10 {
+            return Err(SpaceCommError::invalid_packet("Telemetry payload too short", None));
+        }
+        let mut reader = WireReader::new(bytes);
+        let timestamp = reader.get::<u64>()?;
+        let count = usize::from(reader.get::<u16>()?);
+        if reader.remaining() < count * MEASUREMENT_WIRE_SIZE {
+            return Err(SpaceCommError::invalid_packet("Telemetry payload truncated", None));
+        }
+
+        let mut measurements = heapless::Vec::new();
+        for _ in 0..count {
+            let measurement_id = reader.get::<u16>()?;
+            let flags = reader.get::<u8>()?;
+            let bits = reader.get::<u32>()?;
+            let value = match flags >> 4 {
+                VALUE_KIND_FLOAT => MeasurementValue::Float(f64::from(f32::from_bits(bits))),
+                _ => MeasurementValue::Integer(i64::from(bits as i32)),
+            };
+            measurements
+                .push(Measurement {
+                    measurement_id,
+                    value,
+                    unit: dictionary_entry(measurement_id).map_or("", |e| e.unit),
+                    quality: MeasurementQuality::from_code(flags & 0x0F),
+                })
+                .map_err(|_| {
+                    SpaceCommError::invalid_packet("Too many measurements in payload", None)
+                })?;
+        }
+
+        Ok(Self {
+            source,
+            timestamp,
+            measurements,
+            health_status,
+        })
+    }
+}
+
+/// Value kind codes carried in the measurement flags byte
+const VALUE_KIND_FLOAT: u8 = 0;
+const VALUE_KIND_INTEGER: u8 = 1;
+
+/// Encoded size of one measurement: ID, flags, value
+const MEASUREMENT_WIRE_SIZE: usize = 7;
+
 /// Individual measurement
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Measurement {
     /// Measurement type identifier
     pub measurement_id: u16,
@@ -41,6 +270,7 @@ pub struct Measurement {
 
 /// Measurement value types
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MeasurementValue {
     /// Integer value
     Integer(i64),
@@ -49,26 +279,231 @@ pub enum MeasurementValue {
     /// Boolean value
     Boolean(bool),
     /// String value
-    String(heapless::String<64>),
+    String(
+        #[cfg_attr(feature = "schema", schemars(with = "String", length(max = 64)))]
+        heapless::String<64>,
+    ),
     /// Raw bytes
-    Bytes(heapless::Vec<u8, 128>),
+    Bytes(
+        #[cfg_attr(feature = "schema", schemars(with = "Vec<u8>", length(max = 128)))]
+        heapless::Vec<u8, 128>,
+    ),
 }
 
 /// Measurement quality indicators
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Declared from best to worst, so the derived ordering ranks quality and
+/// `max` picks the worse of two flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MeasurementQuality {
-    /// Good quality measurement
+    /// Within the expected operating range
     Good,
-    /// Questionable quality
-    Questionable,
-    /// Bad quality measurement
-    Bad,
+    /// Physically possible but outside the expected operating range
+    Suspect,
+    /// No update within the dictionary period
+    Stale,
+    /// Outside the sensor's physical range, or the read failed
+    Invalid,
     /// Measurement not available
     NotAvailable,
 }
 
+impl MeasurementQuality {
+    /// Wire code carried in the measurement flags byte
+    pub const fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Quality from its wire code; unknown codes decode as `Invalid`
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            0 => MeasurementQuality::Good,
+            1 => MeasurementQuality::Suspect,
+            2 => MeasurementQuality::Stale,
+            4 => MeasurementQuality::NotAvailable,
+            _ => MeasurementQuality::Invalid,
+        }
+    }
+
+    /// The worse of two quality flags
+    pub fn worst(self, other: Self) -> Self {
+        self.max(other)
+    }
+
+    /// Whether the value may be used in limit checks and derived parameters
+    /// without operator review
+    pub const fn is_usable(&self) -> bool {
+        matches!(self, MeasurementQuality::Good | MeasurementQuality::Suspect)
+    }
+}
+
+/// Plausibility limits a sensor driver applies to a raw reading
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QualityLimits {
+    /// Lowest value the sensor can physically report
+    pub valid_min: f64,
+    /// Highest value the sensor can physically report
+    pub valid_max: f64,
+    /// Lowest value expected in normal operation
+    pub expected_min: f64,
+    /// Highest value expected in normal operation
+    pub expected_max: f64,
+}
+
+impl QualityLimits {
+    /// Quality of a raw reading against these limits
+    ///
+    /// - **ID**: FN-TLM-001
+    /// - **Requirement**: Sensor drivers flag every reading with a quality
+    ///   (REQ-NF-001).
+    /// - **Outputs**: `Invalid` for NaN or values outside the physical range,
+    ///   `Suspect` outside the expected range, otherwise `Good`.
+    pub fn classify(&self, value: f64) -> MeasurementQuality {
+        if value.is_nan() || value < self.valid_min || value > self.valid_max {
+            MeasurementQuality::Invalid
+        } else if value < self.expected_min || value > self.expected_max {
+            MeasurementQuality::Suspect
+        } else {
+            MeasurementQuality::Good
+        }
+    }
+}
+
+/// Quality of a parameter derived from `inputs`: the worst input quality
+///
+/// A parameter with no inputs is `NotAvailable`.
+pub fn derived_quality<I>(inputs: I) -> MeasurementQuality
+where
+    I: IntoIterator<Item = MeasurementQuality>,
+{
+    inputs.into_iter().max().unwrap_or(MeasurementQuality::NotAvailable)
+}
+
+impl Measurement {
+    /// Derived parameter computed from `inputs`, inheriting their worst quality
+    pub fn derived(
+        measurement_id: u16,
+        value: MeasurementValue,
+        unit: &'static str,
+        inputs: &[&Measurement],
+    ) -> Self {
+        Self {
+            measurement_id,
+            value,
+            unit,
+            quality: derived_quality(inputs.iter().map(|m| m.quality)),
+        }
+    }
+}
+
+/// Telemetry dictionary entry covering a contiguous range of measurement IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DictionaryEntry {
+    /// First measurement ID in the range
+    pub first_id: u16,
+    /// Last measurement ID in the range (inclusive)
+    pub last_id: u16,
+    /// Parameter group name
+    pub name: &'static str,
+    /// Engineering unit of every measurement in the range
+    pub unit: &'static str,
+    /// Nominal reporting period in the full telemetry set, ms
+    pub period_ms: u64,
+}
+
+/// Nominal telemetry collection period, ms
+pub const NOMINAL_PERIOD_MS: u64 = 100;
+
+/// Missed periods after which a value is marked stale
+pub const STALE_AFTER_PERIODS: u64 = 3;
+
+/// Telemetry dictionary: expected reporting period per measurement range
+pub const DICTIONARY: [DictionaryEntry; 32] = [
+    DictionaryEntry {
+        first_id: 0x0001,
+        last_id: 0x000F,
+        name: "Temperatures",
+        unit: "C",
+        period_ms: NOMINAL_PERIOD_MS,
+    },
+    DictionaryEntry {
+        first_id: 0x0010,
+        last_id: 0x001F,
+        name: "Bus voltages",
+        unit: "V",
+        period_ms: NOMINAL_PERIOD_MS,
+    },
+    DictionaryEntry {
+        first_id: 0x0020,
+        last_id: 0x002F,
+        name: "Currents",
+        unit: "A",
+        period_ms: NOMINAL_PERIOD_MS,
+    },
+    DictionaryEntry {
+        first_id: 0x0030,
+        last_id: 0x003F,
+        name: "Status",
+        unit: "",
+        period_ms: NOMINAL_PERIOD_MS,
+    },
+    DictionaryEntry {
+        first_id: measurement_ids::VC_SECURITY_POLICY_BASE,
+        last_id: measurement_ids::VC_SECURITY_POLICY_BASE + 0x0F,
+        name: "Link security",
+        unit: "",
+        period_ms: NOMINAL_PERIOD_MS,
+    },
+    RfField::Powered.dictionary_entry(),
+    RfField::Locked.dictionary_entry(),
+    RfField::TxPower.dictionary_entry(),
+    RfField::SignalStrength.dictionary_entry(),
+    RfField::Temperature.dictionary_entry(),
+    RfField::Frequency.dictionary_entry(),
+    RecoveryField::State.dictionary_entry(),
+    RecoveryField::LockLosses.dictionary_entry(),
+    RecoveryField::Retunes.dictionary_entry(),
+    RecoveryField::PowerCycles.dictionary_entry(),
+    RecoveryField::Recoveries.dictionary_entry(),
+    InversionCounters::dictionary_entry(),
+    EpsField::dictionary_entries()[0],
+    EpsField::dictionary_entries()[1],
+    EpsField::dictionary_entries()[2],
+    RangingField::dictionary_entries()[0],
+    RangingField::dictionary_entries()[1],
+    Subsystem::dictionary_entry(),
+    NavigationField::dictionary_entries()[0],
+    NavigationField::dictionary_entries()[1],
+    NavigationField::dictionary_entries()[2],
+    LinkStatsField::dictionary_entries()[0],
+    LinkStatsField::dictionary_entries()[1],
+    LinkStatsField::dictionary_entries()[2],
+    LinkStatsField::dictionary_entries()[3],
+    DownlinkPolicyStats::dictionary_entries()[0],
+    DownlinkPolicyStats::dictionary_entries()[1],
+];
+
+/// Dictionary entry for a measurement ID
+pub fn dictionary_entry(measurement_id: u16) -> Option<&'static DictionaryEntry> {
+    DICTIONARY
+        .iter()
+        .find(|e| (e.first_id..=e.last_id).contains(&measurement_id))
+}
+
+/// Age after which a measurement is stale, ms, or `None` if it is not in the
+/// dictionary
+///
+/// The allowance scales with the collection interval of the active set, so
+/// safe-mode telemetry is not flagged for its slower cadence.
+pub fn stale_after_ms(measurement_id: u16, set: TelemetrySet) -> Option<u64> {
+    dictionary_entry(measurement_id)
+        .map(|e| set.interval_ms(e.period_ms) * STALE_AFTER_PERIODS)
+}
+
 /// Complete telemetry packet
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TelemetryPacket {
     /// Packet sequence number
     pub sequence: u32,
@@ -99,3 +534,117 @@ impl TelemetryPacket {
 }
 
 // TODO: Implement remaining telemetry functionality
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(measurement_id: u16) -> Measurement {
+        Measurement {
+            measurement_id,
+            value: MeasurementValue::Integer(0),
+            unit: "",
+            quality: MeasurementQuality::Good,
+        }
+    }
+
+    #[test]
+    fn test_safe_mode_set_selection() {
+        assert_eq!(TelemetrySet::for_mode(OperationalMode::Safe), TelemetrySet::SafeMode);
+        assert_eq!(TelemetrySet::for_mode(OperationalMode::Normal), TelemetrySet::Full);
+        assert_eq!(TelemetrySet::SafeMode.interval_ms(100), 5_000);
+        assert_eq!(TelemetrySet::Full.interval_ms(100), 100);
+    }
+
+    #[test]
+    fn test_retain_safe_mode_set() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        for id in [0x0001, 0x0003, 0x0010, 0x0020, 0x0031] {
+            data.measurements.push(measurement(id)).unwrap();
+        }
+
+        data.retain_set(TelemetrySet::SafeMode);
+        let ids: heapless::Vec<u16, 8> = data.measurements.iter().map(|m| m.measurement_id).collect();
+        assert_eq!(ids.as_slice(), &[0x0001, 0x0010, 0x0031]);
+    }
+
+    #[test]
+    fn test_quality_limits_classify() {
+        let limits = QualityLimits {
+            valid_min: -60.0,
+            valid_max: 125.0,
+            expected_min: -40.0,
+            expected_max: 70.0,
+        };
+        assert_eq!(limits.classify(20.0), MeasurementQuality::Good);
+        assert_eq!(limits.classify(90.0), MeasurementQuality::Suspect);
+        assert_eq!(limits.classify(200.0), MeasurementQuality::Invalid);
+        assert_eq!(limits.classify(f64::NAN), MeasurementQuality::Invalid);
+    }
+
+    #[test]
+    fn test_derived_inherits_worst_quality() {
+        let zero = || MeasurementValue::Float(0.0);
+        let mut voltage = measurement(measurement_ids::BATTERY_VOLTAGE);
+        let mut current = measurement(0x0020);
+        current.quality = MeasurementQuality::Stale;
+        let power = Measurement::derived(0x0040, zero(), "W", &[&voltage, &current]);
+        assert_eq!(power.quality, MeasurementQuality::Stale);
+
+        voltage.quality = MeasurementQuality::Invalid;
+        let power = Measurement::derived(0x0040, zero(), "W", &[&voltage, &current]);
+        assert_eq!(power.quality, MeasurementQuality::Invalid);
+        assert!(!power.quality.is_usable());
+
+        assert_eq!(derived_quality([]), MeasurementQuality::NotAvailable);
+    }
+
+    #[test]
+    fn test_stale_threshold_follows_dictionary_and_set() {
+        let id = measurement_ids::BATTERY_TEMPERATURE;
+        assert_eq!(stale_after_ms(id, TelemetrySet::Full), Some(300));
+        assert_eq!(stale_after_ms(id, TelemetrySet::SafeMode), Some(15_000));
+        assert_eq!(stale_after_ms(0x7FFF, TelemetrySet::Full), None);
+    }
+
+    #[test]
+    fn test_payload_round_trip_keeps_quality() {
+        let mut data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 42_000,
+            measurements: heapless::Vec::new(),
+            health_status: HealthStatus::Good,
+        };
+        data.measurements.push(Measurement {
+            measurement_id: measurement_ids::BATTERY_VOLTAGE,
+            value: MeasurementValue::Float(28.5),
+            unit: "V",
+            quality: MeasurementQuality::Suspect,
+        }).unwrap();
+        data.measurements.push(Measurement {
+            measurement_id: 0x0031,
+            value: MeasurementValue::Integer(-7),
+            unit: "",
+            quality: MeasurementQuality::Good,
+        }).unwrap();
+
+        let payload = data.to_payload().unwrap();
+        assert_eq!(payload.len(), 10 + 2 * 7);
+
+        let source = ComponentId::new(1);
+        let decoded = TelemetryData::from_payload(source, HealthStatus::Good, &payload).unwrap();
+        assert_eq!(decoded.timestamp, 42_000);
+        assert_eq!(decoded.measurements.len(), 2);
+        assert!(matches!(decoded.measurements[0].value, MeasurementValue::Float(v) if v == 28.5));
+        assert_eq!(decoded.measurements[0].unit, "V");
+        assert_eq!(decoded.measurements[0].quality, MeasurementQuality::Suspect);
+        assert!(matches!(decoded.measurements[1].value, MeasurementValue::Integer(-7)));
+
+        assert!(TelemetryData::from_payload(source, HealthStatus::Good, &payload[..15]).is_err());
+    }
+}
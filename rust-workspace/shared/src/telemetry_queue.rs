@@ -0,0 +1,454 @@
+//! Telemetry downlink queue with priority-aware overflow handling
+//!
+//! The collector produces frames faster than a degraded link can drain them.
+//! When the queue is full, the oldest low-rate housekeeping frame is dropped
+//! to make room; alarm and event frames are never dropped. If the queue holds
+//! nothing but protected frames, an incoming housekeeping frame is dropped
+//! instead, and an incoming protected frame is refused so the producer keeps
+//! it and retries; producers check [`TelemetryQueue::accepts`] before pushing.
+//!
+//! Every drop is counted against the class of the dropped frame. Counters are
+//! downlinked in housekeeping telemetry from
+//! [`measurement_ids::TELEMETRY_DROPS_BASE`] plus [`TelemetryClass::code`], and
+//! a [`DropRatePolicy`] flags a sustained drop rate so the satellite can raise
+//! an onboard event.
+//!
+//! Like the inversion detector, the queue does not read the clock: callers
+//! pass the current time in milliseconds.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: Medium Priority Telemetry (housekeeping yields to alarms and
+//!   events under link congestion)
+//! - REQ-FN-007: Fault Detection (alarm telemetry is never lost to overflow)
+
+use heapless::Vec;
+
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::telemetry::{
+    measurement_ids, Measurement, MeasurementQuality, MeasurementValue, TelemetryData,
+    TelemetryPacket,
+};
+use crate::types::HealthStatus;
+
+/// Downlink class of a telemetry frame, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum TelemetryClass {
+    /// Routine low-rate housekeeping; dropped first on overflow
+    Housekeeping = 0,
+    /// Frame reporting an off-nominal condition; never dropped
+    Event = 1,
+    /// Frame reporting a critical condition; never dropped
+    Alarm = 2,
+}
+
+impl TelemetryClass {
+    /// Number of classes
+    pub const COUNT: usize = 3;
+
+    /// Every class in code order
+    pub const ALL: [TelemetryClass; TelemetryClass::COUNT] = [
+        TelemetryClass::Housekeeping,
+        TelemetryClass::Event,
+        TelemetryClass::Alarm,
+    ];
+
+    /// Telemetry code of the class
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Operator-facing name
+    pub const fn name(self) -> &'static str {
+        match self {
+            TelemetryClass::Housekeeping => "housekeeping",
+            TelemetryClass::Event => "event",
+            TelemetryClass::Alarm => "alarm",
+        }
+    }
+
+    /// Whether frames of this class are protected from overflow drops
+    pub const fn is_protected(self) -> bool {
+        !matches!(self, TelemetryClass::Housekeeping)
+    }
+
+    /// Measurement ID of the drop counter for this class
+    pub const fn measurement_id(self) -> u16 {
+        measurement_ids::TELEMETRY_DROPS_BASE + self as u16
+    }
+
+    /// Classify a frame by its health and measurement quality
+    ///
+    /// Critical health or an invalid measurement is an alarm; poor health or
+    /// a suspect measurement is an event; anything else is housekeeping.
+    pub fn classify(data: &TelemetryData) -> Self {
+        let quality = |q: MeasurementQuality| data.measurements.iter().any(|m| m.quality == q);
+
+        if data.health_status == HealthStatus::Critical || quality(MeasurementQuality::Invalid) {
+            TelemetryClass::Alarm
+        } else if data.health_status == HealthStatus::Poor || quality(MeasurementQuality::Suspect) {
+            TelemetryClass::Event
+        } else {
+            TelemetryClass::Housekeeping
+        }
+    }
+}
+
+/// Threshold for raising a drop-rate event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropRatePolicy {
+    /// Length of the measurement window, ms
+    pub window_ms: u64,
+    /// Drops tolerated within one window
+    pub max_drops: u32,
+}
+
+impl Default for DropRatePolicy {
+    /// Five drops in ten seconds, i.e. more than 0.5% of the nominal 100 Hz
+    /// telemetry rate.
+    fn default() -> Self {
+        Self {
+            window_ms: 10_000,
+            max_drops: 5,
+        }
+    }
+}
+
+/// Result of queuing a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Frame queued without dropping anything
+    Queued,
+    /// Frame queued after dropping the oldest housekeeping frame
+    EvictedHousekeeping,
+    /// Incoming housekeeping frame dropped; the queue holds only protected
+    /// frames
+    Dropped,
+}
+
+impl PushOutcome {
+    /// Whether a frame was dropped
+    pub const fn dropped(self) -> bool {
+        !matches!(self, PushOutcome::Queued)
+    }
+}
+
+/// Drop counters since boot, indexed by `TelemetryClass::code`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DropCounters {
+    /// Frames dropped per class
+    pub per_class: [u32; TelemetryClass::COUNT],
+}
+
+impl DropCounters {
+    /// Frames of `class` dropped
+    pub const fn for_class(&self, class: TelemetryClass) -> u32 {
+        self.per_class[class as usize]
+    }
+
+    /// Frames dropped across all classes
+    pub fn total(&self) -> u32 {
+        self.per_class
+            .iter()
+            .fold(0, |acc, &n| acc.saturating_add(n))
+    }
+
+    /// Append the counters to a housekeeping frame
+    pub fn append_measurements(&self, data: &mut TelemetryData) -> Result<()> {
+        for class in TelemetryClass::ALL {
+            data.measurements
+                .push(Measurement {
+                    measurement_id: class.measurement_id(),
+                    value: MeasurementValue::Integer(i64::from(self.for_class(class))),
+                    unit: "",
+                    quality: MeasurementQuality::Good,
+                })
+                .map_err(|_| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, None))?;
+        }
+        Ok(())
+    }
+
+    /// Read counters from a downlinked frame; `None` if the frame has none
+    pub fn from_measurements(measurements: &[Measurement]) -> Option<Self> {
+        let value = |class: TelemetryClass| {
+            measurements
+                .iter()
+                .find(|m| m.measurement_id == class.measurement_id())
+                .and_then(|m| match m.value {
+                    MeasurementValue::Integer(v) => u32::try_from(v).ok(),
+                    _ => None,
+                })
+        };
+
+        let mut counters = Self::default();
+        let mut found = false;
+        for class in TelemetryClass::ALL {
+            if let Some(n) = value(class) {
+                counters.per_class[class as usize] = n;
+                found = true;
+            }
+        }
+        found.then_some(counters)
+    }
+}
+
+/// Bounded telemetry queue that drops housekeeping before alarms and events
+#[derive(Debug, Clone)]
+pub struct TelemetryQueue<const N: usize> {
+    frames: Vec<(TelemetryClass, TelemetryPacket), N>,
+    counters: DropCounters,
+    policy: DropRatePolicy,
+    window_start_ms: u64,
+    window_drops: u32,
+    rate_exceeded: bool,
+}
+
+impl<const N: usize> TelemetryQueue<N> {
+    /// Empty queue with zeroed counters
+    pub const fn new(policy: DropRatePolicy) -> Self {
+        Self {
+            frames: Vec::new(),
+            counters: DropCounters {
+                per_class: [0; TelemetryClass::COUNT],
+            },
+            policy,
+            window_start_ms: 0,
+            window_drops: 0,
+            rate_exceeded: false,
+        }
+    }
+
+    /// Frames waiting for downlink
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames are waiting
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Drop counters since boot
+    pub const fn counters(&self) -> &DropCounters {
+        &self.counters
+    }
+
+    /// Whether a frame of `class` can be queued now
+    ///
+    /// Only a protected frame is ever refused, when the queue is full of
+    /// protected frames.
+    pub fn accepts(&self, class: TelemetryClass) -> bool {
+        !class.is_protected()
+            || !self.frames.is_full()
+            || self.frames.iter().any(|(queued, _)| !queued.is_protected())
+    }
+
+    /// Queue a frame of `class` at `now_ms`
+    ///
+    /// - **ID**: FN-TLQ-001
+    /// - **Requirement**: On overflow drop the oldest housekeeping frame
+    ///   first and never drop alarm or event telemetry (REQ-FN-007).
+    /// - **Inputs**: Frame class, frame, current time in ms.
+    /// - **Outputs**: How the frame was queued.
+    /// - **Side Effects**: Counts each dropped frame against its class and
+    ///   towards the drop-rate window.
+    /// - **Failure Modes**: A protected frame the queue does not
+    ///   [`accepts`](Self::accepts) is refused with a buffer overflow error
+    ///   and not counted as a drop; callers keep such frames and retry.
+    pub fn push(
+        &mut self,
+        class: TelemetryClass,
+        packet: TelemetryPacket,
+        now_ms: u64,
+    ) -> Result<PushOutcome> {
+        let mut outcome = PushOutcome::Queued;
+        if self.frames.is_full() {
+            let oldest = self
+                .frames
+                .iter()
+                .position(|(queued, _)| !queued.is_protected());
+            match oldest {
+                Some(index) => {
+                    self.frames.remove(index);
+                    outcome = PushOutcome::EvictedHousekeeping;
+                }
+                None if class.is_protected() => {
+                    return Err(SpaceCommError::memory_error(
+                        MemoryErrorType::BufferOverflow,
+                        None,
+                    ))
+                }
+                None => {
+                    self.record_drop(class, now_ms);
+                    return Ok(PushOutcome::Dropped);
+                }
+            }
+            self.record_drop(TelemetryClass::Housekeeping, now_ms);
+        }
+
+        // Room was made above, so the push cannot fail
+        let _ = self.frames.push((class, packet));
+        Ok(outcome)
+    }
+
+    /// Take the next frame for downlink: the oldest frame of the highest
+    /// queued class
+    pub fn pop(&mut self) -> Option<(TelemetryClass, TelemetryPacket)> {
+        let top = self.frames.iter().map(|(class, _)| *class).max()?;
+        let index = self.frames.iter().position(|(class, _)| *class == top)?;
+        Some(self.frames.remove(index))
+    }
+
+    /// Report a drop rate above the policy, once per window
+    ///
+    /// - **ID**: FN-TLQ-002
+    /// - **Requirement**: A sustained telemetry drop rate raises an onboard
+    ///   event (REQ-FN-007).
+    /// - **Inputs**: Current time in ms.
+    /// - **Outputs**: `true` the first time the drops within the current
+    ///   window exceed `policy.max_drops`.
+    /// - **Side Effects**: Starts a new window once the current one elapses.
+    /// - **Failure Modes**: None.
+    pub fn take_rate_exceeded(&mut self, now_ms: u64) -> bool {
+        self.roll_window(now_ms);
+        if !self.rate_exceeded && self.window_drops > self.policy.max_drops {
+            self.rate_exceeded = true;
+            return true;
+        }
+        false
+    }
+
+    fn record_drop(&mut self, class: TelemetryClass, now_ms: u64) {
+        self.roll_window(now_ms);
+        let count = &mut self.counters.per_class[class as usize];
+        *count = count.saturating_add(1);
+        self.window_drops = self.window_drops.saturating_add(1);
+    }
+
+    fn roll_window(&mut self, now_ms: u64) {
+        if now_ms.saturating_sub(self.window_start_ms) >= self.policy.window_ms {
+            self.window_start_ms = now_ms;
+            self.window_drops = 0;
+            self.rate_exceeded = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BandType, ComponentId};
+
+    fn frame(sequence: u32, health_status: HealthStatus) -> TelemetryPacket {
+        let data = TelemetryData {
+            source: ComponentId::new(1),
+            timestamp: 0,
+            measurements: Vec::new(),
+            health_status,
+        };
+        TelemetryPacket::new(sequence, data, BandType::SBand)
+    }
+
+    fn push(queue: &mut TelemetryQueue<3>, sequence: u32, health: HealthStatus) -> PushOutcome {
+        let packet = frame(sequence, health);
+        let class = TelemetryClass::classify(&packet.data);
+        queue.push(class, packet, 0).unwrap()
+    }
+
+    #[test]
+    fn test_overflow_drops_oldest_housekeeping_first() {
+        let mut queue = TelemetryQueue::<3>::new(DropRatePolicy::default());
+        assert_eq!(push(&mut queue, 0, HealthStatus::Good), PushOutcome::Queued);
+        assert_eq!(
+            push(&mut queue, 1, HealthStatus::Critical),
+            PushOutcome::Queued
+        );
+        assert_eq!(push(&mut queue, 2, HealthStatus::Good), PushOutcome::Queued);
+
+        // Full: frame 0 (oldest housekeeping) makes room for the event
+        assert_eq!(
+            push(&mut queue, 3, HealthStatus::Poor),
+            PushOutcome::EvictedHousekeeping
+        );
+        // Full: frame 2 makes room for the housekeeping frame
+        assert_eq!(
+            push(&mut queue, 4, HealthStatus::Good),
+            PushOutcome::EvictedHousekeeping
+        );
+        assert_eq!(queue.counters().for_class(TelemetryClass::Housekeeping), 2);
+
+        // Alarm first, then event, then housekeeping
+        let order: Vec<(TelemetryClass, u32), 3> =
+            core::iter::from_fn(|| queue.pop().map(|(c, p)| (c, p.sequence))).collect();
+        assert_eq!(
+            order.as_slice(),
+            &[
+                (TelemetryClass::Alarm, 1),
+                (TelemetryClass::Event, 3),
+                (TelemetryClass::Housekeeping, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_protected_frames_are_never_dropped() {
+        let mut queue = TelemetryQueue::<3>::new(DropRatePolicy::default());
+        for sequence in 0..3 {
+            push(&mut queue, sequence, HealthStatus::Critical);
+        }
+
+        // Housekeeping is dropped rather than any alarm
+        assert_eq!(
+            push(&mut queue, 3, HealthStatus::Good),
+            PushOutcome::Dropped
+        );
+        // A protected frame is refused for the producer to retry
+        assert!(queue.accepts(TelemetryClass::Housekeeping));
+        assert!(!queue.accepts(TelemetryClass::Event));
+        assert!(queue
+            .push(TelemetryClass::Event, frame(4, HealthStatus::Poor), 0)
+            .is_err());
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.counters().total(), 1);
+        assert_eq!(queue.counters().for_class(TelemetryClass::Alarm), 0);
+        assert_eq!(queue.counters().for_class(TelemetryClass::Event), 0);
+
+        let mut data = frame(5, HealthStatus::Good).data;
+        queue.counters().append_measurements(&mut data).unwrap();
+        assert_eq!(
+            DropCounters::from_measurements(&data.measurements),
+            Some(*queue.counters())
+        );
+        assert_eq!(DropCounters::from_measurements(&[]), None);
+    }
+
+    #[test]
+    fn test_drop_rate_raised_once_per_window() {
+        let policy = DropRatePolicy {
+            window_ms: 1_000,
+            max_drops: 2,
+        };
+        let mut queue = TelemetryQueue::<1>::new(policy);
+        let push_at = |queue: &mut TelemetryQueue<1>, sequence: u32, now_ms: u64| {
+            let packet = frame(sequence, HealthStatus::Good);
+            queue
+                .push(TelemetryClass::Housekeeping, packet, now_ms)
+                .unwrap()
+        };
+
+        push_at(&mut queue, 0, 0);
+        push_at(&mut queue, 1, 100);
+        push_at(&mut queue, 2, 200);
+        assert!(!queue.take_rate_exceeded(250));
+        push_at(&mut queue, 3, 300);
+        assert!(queue.take_rate_exceeded(350));
+        push_at(&mut queue, 4, 400);
+        assert!(!queue.take_rate_exceeded(450));
+
+        // A new window starts clean
+        push_at(&mut queue, 5, 1_000);
+        assert!(!queue.take_rate_exceeded(1_100));
+        assert_eq!(queue.counters().for_class(TelemetryClass::Housekeeping), 5);
+    }
+}
@@ -50,6 +50,7 @@ impl MessageId {
 
 /// Unique identifier for system components
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ComponentId(pub u16);
 
 impl ComponentId {
@@ -69,6 +70,7 @@ impl ComponentId {
 /// Represents the different frequency bands used in satellite communication.
 /// Each band has specific characteristics and use cases.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BandType {
     /// UHF Band (300 MHz - 3 GHz)
     /// Primary use: Emergency communication and backup systems
@@ -242,6 +244,7 @@ impl PowerLevel {
 
 /// System health status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HealthStatus {
     /// System is operating optimally
     Excellent,
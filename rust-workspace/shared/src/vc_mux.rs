@@ -0,0 +1,705 @@
+//! Virtual channel multiplexing of the telemetry downlink
+//!
+//! Telemetry leaves the spacecraft in fixed-length TM Transfer Frames
+//! ([`TmTransferFrame`]), each carrying packets of one virtual channel:
+//!
+//! | VC | Channel               | Carries                                      | Weight | Flush |
+//! |----|-----------------------|----------------------------------------------|--------|-------|
+//! | 0  | Realtime housekeeping | APIDs 0x100-0x1FF, including the CLCW        | 4      | 0 s   |
+//! | 1  | Science               | Execution reports, dwells, loopback echoes   | 2      | 1 s   |
+//! | 2  | Playback              | File manifests, event log, memory dumps      | 1      | 5 s   |
+//!
+//! [`virtual_channels::downlink`] assigns each APID its channel. Packets
+//! are queued per channel and run back to back across frame boundaries,
+//! the first header pointer marking where the first packet starting in a
+//! frame begins. A channel is ready once it holds a full data field, its
+//! oldest packet has waited its flush delay, or it has been expedited; a
+//! frame sent before its data field is full is filled out with an idle
+//! packet. Ready channels share the downlink by smooth weighted round
+//! robin, so under contention housekeeping gets four frames in seven and
+//! playback still gets one.
+//!
+//! [`TmDemultiplexer`] reverses this on the ground. A gap in a channel's
+//! frame count drops the packet it cut, and the first header pointer of the
+//! next frame brings the channel back in step.
+//!
+//! Like the telemetry queue, neither reads the clock: callers pass the
+//! current time in milliseconds.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (TM Space Data Link Protocol, CCSDS
+//!   132.0-B-3)
+//! - REQ-FN-009: Message Queue Management (per-channel queues served by
+//!   weight)
+//! - REQ-NF-001: Throughput Performance (packets packed back to back across
+//!   frames)
+//! - REQ-NF-004: Fault Tolerance (resynchronization after lost frames)
+
+use heapless::{Deque, Vec};
+
+use crate::ccsds::{
+    PacketType, SpacePacket, SpacePacketHeader, TmTransferFrame, IDLE_APID, TM_FHP_IDLE,
+    TM_FHP_NO_PACKET, TM_MAX_DATA_LEN, TM_OCF_LEN,
+};
+use crate::cop1::SPACECRAFT_ID;
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+use crate::security::virtual_channels;
+use crate::wire::{self, ERROR_CONTROL_LEN, PRIMARY_HEADER_LEN};
+use space_comms_req::req;
+
+/// Virtual channels multiplexed onto the downlink: housekeeping, science
+/// and playback
+pub const VC_COUNT: usize = 3;
+
+/// Longest packet carried, bytes: header, a full data field and CRC
+pub const MAX_PACKET_LEN: usize = PRIMARY_HEADER_LEN + 2048 + ERROR_CONTROL_LEN;
+
+/// Packets a channel can hold queued
+pub const MAX_QUEUED_PACKETS: usize = 64;
+
+/// Shortest whole idle packet: header, one byte of data and CRC
+const MIN_IDLE_LEN: usize = PRIMARY_HEADER_LEN + 1 + ERROR_CONTROL_LEN;
+
+/// Data field byte of idle packets
+const IDLE_FILL: u8 = 0x55;
+
+/// Service weights and flush delays, indexed by virtual channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VcMuxConfig {
+    /// Frames a channel is sent per round while every channel is ready
+    pub weights: [u8; VC_COUNT],
+    /// Longest a queued packet waits for its frame to fill, ms
+    pub flush_ms: [u64; VC_COUNT],
+}
+
+impl Default for VcMuxConfig {
+    /// Housekeeping sent at once with weight 4; science filled for up to a
+    /// second with weight 2; playback filled for up to five seconds with
+    /// weight 1.
+    fn default() -> Self {
+        Self {
+            weights: [4, 2, 1],
+            flush_ms: [0, 1_000, 5_000],
+        }
+    }
+}
+
+impl VcMuxConfig {
+    /// Check that every channel is served
+    ///
+    /// # Errors
+    /// `ConfigurationError` for a zero weight, which would starve its
+    /// channel.
+    pub fn validate(&self) -> Result<()> {
+        if self.weights.contains(&0) {
+            return Err(SpaceCommError::ConfigurationError {
+                parameter: "vc_weights",
+                value: "0",
+                reason: "Every virtual channel needs a non-zero weight",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// APID of the packet starting `bytes`, if its first two bytes are there
+fn apid(bytes: &[u8]) -> Option<u16> {
+    match bytes {
+        [first, second, ..] => Some(u16::from_be_bytes([*first, *second]) & 0x7FF),
+        _ => None,
+    }
+}
+
+/// Packets queued on one virtual channel
+#[derive(Debug, Default)]
+struct Channel<const N: usize> {
+    /// Packet bytes back to back, the rest of the packet in progress first
+    bytes: Deque<u8, N>,
+    /// Queue time of each packet not yet started, ms
+    queued_ms: Deque<u64, MAX_QUEUED_PACKETS>,
+    /// Bytes of the packet in progress still to send
+    in_progress: usize,
+    /// Queue time of the packet in progress, ms
+    in_progress_ms: u64,
+    /// Frames sent, modulo 256
+    frame_count: u8,
+    /// Whether queued data goes out without waiting to fill a frame
+    expedited: bool,
+    /// Smooth weighted round robin credit
+    credit: i32,
+}
+
+impl<const N: usize> Channel<N> {
+    /// Queue time of the oldest packet not yet sent in full, ms
+    fn oldest_ms(&self) -> Option<u64> {
+        if self.in_progress > 0 {
+            Some(self.in_progress_ms)
+        } else {
+            self.queued_ms.front().copied()
+        }
+    }
+
+    /// Whether the channel has a frame to send
+    fn is_ready(&self, now_ms: u64, flush_ms: u64, data_len: usize) -> bool {
+        self.oldest_ms().is_some_and(|oldest| {
+            self.expedited
+                || self.bytes.len() >= data_len
+                || now_ms.saturating_sub(oldest) >= flush_ms
+        })
+    }
+
+    /// Length of the packet whose header is at the front of the queue
+    fn front_packet_len(&self) -> usize {
+        let mut header = [0u8; PRIMARY_HEADER_LEN];
+        for (slot, byte) in header.iter_mut().zip(self.bytes.iter()) {
+            *slot = *byte;
+        }
+        // Headers are checked as packets are queued
+        SpacePacketHeader::from_bytes(&header)
+            .map_or(self.bytes.len(), |header| wire::frame_len(&header))
+    }
+
+    /// Move queued bytes into `data` until it is `data_len` long
+    ///
+    /// Returns the offset in `data` of the first packet header placed.
+    fn fill(&mut self, data: &mut Vec<u8, TM_MAX_DATA_LEN>, data_len: usize) -> Option<usize> {
+        let mut first_header = None;
+        while data.len() < data_len && !self.bytes.is_empty() {
+            if self.in_progress == 0 {
+                first_header.get_or_insert(data.len());
+                self.in_progress = self.front_packet_len();
+                self.in_progress_ms = self.queued_ms.pop_front().unwrap_or_default();
+            }
+            let take = self.in_progress.min(data_len - data.len());
+            for _ in 0..take {
+                if let Some(byte) = self.bytes.pop_front() {
+                    let _ = data.push(byte);
+                }
+            }
+            self.in_progress -= take;
+        }
+        first_header
+    }
+}
+
+/// Fill the rest of `data` with an idle packet
+///
+/// With fewer than a whole idle packet's bytes left, the packet is cut
+/// short; the receiver drops the fragment when the next frame's first
+/// header pointer shows it ended early.
+fn pad_with_idle(data: &mut Vec<u8, TM_MAX_DATA_LEN>, data_len: usize) -> Result<()> {
+    let space = data_len - data.len();
+    let fill = [IDLE_FILL; TM_MAX_DATA_LEN];
+    let fill_len = space.max(MIN_IDLE_LEN) - PRIMARY_HEADER_LEN - ERROR_CONTROL_LEN;
+    let idle = SpacePacket::new(PacketType::Telemetry, IDLE_APID, 0, &fill[..fill_len], None)?
+        .to_bytes()?;
+    data.extend_from_slice(&idle[..space])
+        .map_err(|()| SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(space)))
+}
+
+/// Downlink virtual channel multiplexer
+///
+/// - **ID**: MOD-VCM-001
+/// - **Requirement**: Interleave housekeeping, science and playback
+///   packets on the downlink in TM Transfer Frames, realtime housekeeping
+///   first (REQ-IF-002, REQ-FN-009).
+/// - **Constraints**: Each channel queues at most `N` bytes and
+///   [`MAX_QUEUED_PACKETS`] packets; no heap allocation.
+#[derive(Debug)]
+pub struct VcMultiplexer<const N: usize> {
+    spacecraft_id: u16,
+    config: VcMuxConfig,
+    channels: [Channel<N>; VC_COUNT],
+    master_frame_count: u8,
+}
+
+impl<const N: usize> Default for VcMultiplexer<N> {
+    /// Multiplexer for [`SPACECRAFT_ID`] with the default weights and
+    /// flush delays
+    fn default() -> Self {
+        Self {
+            spacecraft_id: SPACECRAFT_ID,
+            config: VcMuxConfig::default(),
+            channels: Default::default(),
+            master_frame_count: 0,
+        }
+    }
+}
+
+impl<const N: usize> VcMultiplexer<N> {
+    /// Create a multiplexer
+    ///
+    /// # Errors
+    /// `InvalidPacket` for a spacecraft ID above 10 bits;
+    /// `ConfigurationError` for a zero channel weight.
+    pub fn new(spacecraft_id: u16, config: VcMuxConfig) -> Result<Self> {
+        if spacecraft_id > 0x3FF {
+            return Err(SpaceCommError::invalid_packet(
+                "Spacecraft ID exceeds 10-bit maximum",
+                Some(u32::from(spacecraft_id)),
+            ));
+        }
+        config.validate()?;
+        Ok(Self {
+            spacecraft_id,
+            config,
+            ..Self::default()
+        })
+    }
+
+    /// Weights and flush delays in use
+    pub fn config(&self) -> &VcMuxConfig {
+        &self.config
+    }
+
+    /// Bytes queued on virtual channel `vc`
+    pub fn queued_bytes(&self, vc: u8) -> usize {
+        self.channels
+            .get(usize::from(vc))
+            .map_or(0, |channel| channel.bytes.len())
+    }
+
+    /// Queue a serialized packet on virtual channel `vc`
+    ///
+    /// - **ID**: FN-VCM-001
+    /// - **Requirement**: Hold each channel's packets, in order, until the
+    ///   channel is sent (REQ-FN-009).
+    /// - **Inputs**:
+    ///   - `vc`: Virtual channel, `0..VC_COUNT`.
+    ///   - `packet`: Packet bytes, header to CRC.
+    ///   - `now_ms`: Queue time, ms.
+    /// - **Failure Modes**: `InvalidPacket` for a channel not multiplexed, or
+    ///   a packet whose length disagrees with its header (which includes
+    ///   every packet with an empty data field); `ResourceExhausted` when
+    ///   the channel's queue cannot take it.
+    #[req("REQ-FN-009")]
+    pub fn push(&mut self, vc: u8, packet: &[u8], now_ms: u64) -> Result<()> {
+        let channel = self.channels.get_mut(usize::from(vc)).ok_or_else(|| {
+            SpaceCommError::invalid_packet("Virtual channel not multiplexed", Some(u32::from(vc)))
+        })?;
+        let header = SpacePacketHeader::from_bytes(packet)?;
+        if wire::frame_len(&header) != packet.len() {
+            return Err(SpaceCommError::invalid_packet(
+                "Packet length disagrees with its header",
+                Some(packet.len() as u32),
+            ));
+        }
+        if N - channel.bytes.len() < packet.len() || channel.queued_ms.is_full() {
+            return Err(SpaceCommError::ResourceExhausted {
+                resource: "virtual channel queue",
+                current_usage: channel.bytes.len() as u32,
+                max_usage: N as u32,
+            });
+        }
+
+        for &byte in packet {
+            let _ = channel.bytes.push_back(byte);
+        }
+        let _ = channel.queued_ms.push_back(now_ms);
+        Ok(())
+    }
+
+    /// Queue a packet on the virtual channel of its APID
+    ///
+    /// Returns the channel it was queued on; errors as [`Self::push`].
+    #[req("REQ-FN-009")]
+    pub fn push_packet(&mut self, packet: &SpacePacket, now_ms: u64) -> Result<u8> {
+        let vc = virtual_channels::downlink(packet.header.apid);
+        self.push(vc, &packet.to_bytes()?, now_ms)?;
+        Ok(vc)
+    }
+
+    /// Send everything queued on `vc` without waiting to fill a frame
+    ///
+    /// Lasts until the channel empties.
+    pub fn expedite(&mut self, vc: u8) {
+        if let Some(channel) = self.channels.get_mut(usize::from(vc)) {
+            channel.expedited = true;
+        }
+    }
+
+    /// Next frame to send, if any channel is ready
+    ///
+    /// - **ID**: FN-VCM-002
+    /// - **Requirement**: Give ready channels the downlink in proportion to
+    ///   their weights, packing packets back to back across frames
+    ///   (REQ-IF-002, REQ-FN-009, REQ-NF-001).
+    /// - **Inputs**:
+    ///   - `now_ms`: Current time, ms, checked against the flush delays.
+    ///   - `ocf`: Operational Control Field to send, normally the CLCW.
+    /// - **Outputs**: `Ok(None)` when no channel is ready; the frame with
+    ///   its master and virtual channel frame counts otherwise.
+    /// - **Side Effects**: Dequeues the bytes sent and advances the frame
+    ///   counts.
+    #[req("REQ-IF-002", "REQ-FN-009", "REQ-NF-001")]
+    pub fn next_frame(
+        &mut self,
+        now_ms: u64,
+        ocf: Option<[u8; TM_OCF_LEN]>,
+    ) -> Result<Option<TmTransferFrame>> {
+        let data_len = TmTransferFrame::data_len(ocf.is_some());
+        let mut ready = [false; VC_COUNT];
+        for (vc, channel) in self.channels.iter().enumerate() {
+            ready[vc] = channel.is_ready(now_ms, self.config.flush_ms[vc], data_len);
+        }
+
+        // Smooth weighted round robin; ties go to the lower channel
+        let mut total = 0;
+        let mut chosen: Option<usize> = None;
+        for vc in (0..VC_COUNT).filter(|&vc| ready[vc]) {
+            let weight = i32::from(self.config.weights[vc]);
+            total += weight;
+            self.channels[vc].credit += weight;
+            if chosen.is_none_or(|best| self.channels[vc].credit > self.channels[best].credit) {
+                chosen = Some(vc);
+            }
+        }
+        let Some(vc) = chosen else {
+            return Ok(None);
+        };
+
+        let channel = &mut self.channels[vc];
+        channel.credit -= total;
+        let mut data = Vec::<u8, TM_MAX_DATA_LEN>::new();
+        let mut first_header = channel.fill(&mut data, data_len);
+        if data.len() < data_len {
+            first_header.get_or_insert(data.len());
+            pad_with_idle(&mut data, data_len)?;
+        }
+        if channel.bytes.is_empty() {
+            channel.expedited = false;
+        }
+
+        let first_header_pointer = first_header.map_or(TM_FHP_NO_PACKET, |offset| offset as u16);
+        let mut frame = TmTransferFrame::new(
+            self.spacecraft_id,
+            vc as u8,
+            first_header_pointer,
+            ocf,
+            &data,
+        )?;
+        frame.master_frame_count = self.master_frame_count;
+        frame.vc_frame_count = channel.frame_count;
+        self.master_frame_count = self.master_frame_count.wrapping_add(1);
+        channel.frame_count = channel.frame_count.wrapping_add(1);
+        Ok(Some(frame))
+    }
+}
+
+/// Reassembly state of one virtual channel
+#[derive(Debug, Default)]
+struct Reassembly {
+    /// Bytes of the packet being reassembled
+    pending: Vec<u8, MAX_PACKET_LEN>,
+    /// Frame count expected next
+    expected: Option<u8>,
+    /// Whether `pending` starts at a packet header
+    in_sync: bool,
+}
+
+impl Reassembly {
+    /// Drop the partial packet, returning whether it was more than idle fill
+    fn discard(&mut self) -> bool {
+        let lost = apid(&self.pending).is_some_and(|apid| apid != IDLE_APID);
+        self.pending.clear();
+        lost
+    }
+
+    /// Append `bytes`, delivering each packet completed
+    ///
+    /// Returns the packets lost to a corrupt header, which also puts the
+    /// channel out of step until the next first header pointer.
+    fn feed(&mut self, mut bytes: &[u8], deliver: &mut impl FnMut(&[u8])) -> u32 {
+        while !bytes.is_empty() {
+            let wanted = if self.pending.len() < PRIMARY_HEADER_LEN {
+                PRIMARY_HEADER_LEN
+            } else {
+                match SpacePacketHeader::from_bytes(&self.pending) {
+                    Ok(header) if wire::frame_len(&header) <= MAX_PACKET_LEN => {
+                        wire::frame_len(&header)
+                    }
+                    _ => {
+                        self.pending.clear();
+                        self.in_sync = false;
+                        return 1;
+                    }
+                }
+            };
+            let take = (wanted - self.pending.len()).min(bytes.len());
+            let _ = self.pending.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if wanted > PRIMARY_HEADER_LEN && self.pending.len() == wanted {
+                if apid(&self.pending) != Some(IDLE_APID) {
+                    deliver(&self.pending);
+                }
+                self.pending.clear();
+            }
+        }
+        0
+    }
+}
+
+/// Ground-side virtual channel demultiplexer
+///
+/// - **ID**: MOD-VCM-002
+/// - **Requirement**: Recover the packets of each virtual channel from TM
+///   Transfer Frames, losing no more than the packets a missing frame cut
+///   (REQ-IF-002, REQ-NF-004).
+#[derive(Debug)]
+pub struct TmDemultiplexer {
+    spacecraft_id: u16,
+    channels: [Reassembly; VC_COUNT],
+    frame_gaps: u32,
+    lost_packets: u32,
+}
+
+impl Default for TmDemultiplexer {
+    /// Demultiplexer for [`SPACECRAFT_ID`]
+    fn default() -> Self {
+        Self::new(SPACECRAFT_ID)
+    }
+}
+
+impl TmDemultiplexer {
+    /// Create a demultiplexer for frames from `spacecraft_id`
+    pub fn new(spacecraft_id: u16) -> Self {
+        Self {
+            spacecraft_id,
+            channels: Default::default(),
+            frame_gaps: 0,
+            lost_packets: 0,
+        }
+    }
+
+    /// Virtual channel frame count gaps seen
+    pub fn frame_gaps(&self) -> u32 {
+        self.frame_gaps
+    }
+
+    /// Partial packets dropped at a gap or a corrupt header
+    pub fn lost_packets(&self) -> u32 {
+        self.lost_packets
+    }
+
+    /// Take in a received frame, delivering each packet it completes
+    ///
+    /// - **ID**: FN-VCM-003
+    /// - **Requirement**: Deliver every packet of a channel whose bytes all
+    ///   arrived, in order, and resynchronize at the first header pointer
+    ///   after a lost frame (REQ-IF-002, REQ-NF-004).
+    /// - **Inputs**:
+    ///   - `frame`: Frame as received and checked.
+    ///   - `deliver`: Called with each complete packet, header to CRC; idle
+    ///     packets are not delivered.
+    /// - **Side Effects**: Counts frame count gaps and lost packets.
+    /// - **Failure Modes**: `InvalidPacket` for a frame from another
+    ///   spacecraft or on a channel not multiplexed.
+    #[req("REQ-IF-002", "REQ-NF-004")]
+    pub fn receive(
+        &mut self,
+        frame: &TmTransferFrame,
+        mut deliver: impl FnMut(&[u8]),
+    ) -> Result<()> {
+        if frame.spacecraft_id != self.spacecraft_id {
+            return Err(SpaceCommError::invalid_packet(
+                "TM frame from another spacecraft",
+                Some(u32::from(frame.spacecraft_id)),
+            ));
+        }
+        let channel = self
+            .channels
+            .get_mut(usize::from(frame.virtual_channel))
+            .ok_or_else(|| {
+                SpaceCommError::invalid_packet(
+                    "Virtual channel not multiplexed",
+                    Some(u32::from(frame.virtual_channel)),
+                )
+            })?;
+
+        if channel
+            .expected
+            .is_some_and(|expected| expected != frame.vc_frame_count)
+        {
+            self.frame_gaps += 1;
+            self.lost_packets += u32::from(channel.discard());
+            channel.in_sync = false;
+        }
+        channel.expected = Some(frame.vc_frame_count.wrapping_add(1));
+        if frame.first_header_pointer == TM_FHP_IDLE {
+            return Ok(());
+        }
+
+        let (continuation, packets) = if frame.first_header_pointer == TM_FHP_NO_PACKET {
+            (&frame.data[..], None)
+        } else {
+            let at = usize::from(frame.first_header_pointer).min(frame.data.len());
+            let (continuation, packets) = frame.data.split_at(at);
+            (continuation, Some(packets))
+        };
+        if channel.in_sync {
+            self.lost_packets += channel.feed(continuation, &mut deliver);
+        }
+        if let Some(packets) = packets {
+            // A packet still incomplete at the first header pointer was cut short
+            self.lost_packets += u32::from(channel.discard());
+            channel.in_sync = true;
+            self.lost_packets += channel.feed(packets, &mut deliver);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::MEMORY_DUMP_APID;
+    use crate::execution_report::EXECUTION_REPORT_APID;
+    use crate::telemetry::TELEMETRY_APID;
+    use std::vec::Vec as StdVec;
+
+    fn packet(apid: u16, sequence: u16, len: usize) -> StdVec<u8> {
+        let data: StdVec<u8> = (0..len).map(|i| (i as u8) ^ (sequence as u8)).collect();
+        SpacePacket::new(PacketType::Telemetry, apid, sequence, &data, None)
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+            .to_vec()
+    }
+
+    fn demux_all(frames: &[TmTransferFrame]) -> (TmDemultiplexer, [StdVec<StdVec<u8>>; VC_COUNT]) {
+        let mut demux = TmDemultiplexer::default();
+        let mut delivered: [StdVec<StdVec<u8>>; VC_COUNT] = Default::default();
+        for frame in frames {
+            let vc = usize::from(frame.virtual_channel);
+            demux
+                .receive(frame, |bytes| delivered[vc].push(bytes.to_vec()))
+                .unwrap();
+        }
+        (demux, delivered)
+    }
+
+    #[test]
+    fn test_housekeeping_sent_at_once_science_waits_to_fill() {
+        let mut mux = VcMultiplexer::<4096>::default();
+        let hk = packet(TELEMETRY_APID, 1, 40);
+        mux.push(virtual_channels::HOUSEKEEPING, &hk, 0).unwrap();
+        let frame = mux.next_frame(0, Some([0; TM_OCF_LEN])).unwrap().unwrap();
+        assert_eq!(frame.virtual_channel, virtual_channels::HOUSEKEEPING);
+        assert_eq!(frame.first_header_pointer, 0);
+        assert_eq!(&frame.data[..hk.len()], &hk[..]);
+        assert_eq!(apid(&frame.data[hk.len()..]), Some(IDLE_APID));
+        assert!(mux.next_frame(0, None).unwrap().is_none());
+
+        let report = SpacePacket::new(
+            PacketType::Telemetry,
+            EXECUTION_REPORT_APID,
+            1,
+            b"done",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            mux.push_packet(&report, 0).unwrap(),
+            virtual_channels::SCIENCE
+        );
+        assert!(mux.next_frame(999, None).unwrap().is_none());
+        let frame = mux.next_frame(1_000, None).unwrap().unwrap();
+        assert_eq!(frame.virtual_channel, virtual_channels::SCIENCE);
+        assert_eq!((frame.master_frame_count, frame.vc_frame_count), (1, 0));
+
+        // Expedited data does not wait for its flush delay
+        let dump =
+            SpacePacket::new(PacketType::Telemetry, MEMORY_DUMP_APID, 1, b"dump", None).unwrap();
+        let vc = mux.push_packet(&dump, 2_000).unwrap();
+        assert_eq!(vc, virtual_channels::PLAYBACK);
+        mux.expedite(vc);
+        let frame = mux.next_frame(2_000, None).unwrap().unwrap();
+        assert_eq!(frame.virtual_channel, virtual_channels::PLAYBACK);
+        assert_eq!(mux.queued_bytes(vc), 0);
+    }
+
+    #[test]
+    fn test_channels_interleaved_by_weight_and_demultiplexed() {
+        let mut mux = VcMultiplexer::<8192>::default();
+        let mut pushed: [StdVec<StdVec<u8>>; VC_COUNT] = Default::default();
+        for (vc, sent) in pushed.iter_mut().enumerate() {
+            for sequence in 0..9 {
+                let bytes = packet(0x100 + vc as u16, sequence, 500);
+                mux.push(vc as u8, &bytes, 0).unwrap();
+                sent.push(bytes);
+            }
+        }
+
+        let mut frames = StdVec::new();
+        while let Some(frame) = mux.next_frame(0, None).unwrap() {
+            frames.push(frame);
+        }
+        let order: StdVec<u8> = frames.iter().take(7).map(|f| f.virtual_channel).collect();
+        assert_eq!(order, [0, 1, 0, 2, 0, 1, 0]);
+        // Packets span frames: the second frame of a channel starts mid-packet
+        assert_eq!(frames[2].first_header_pointer, 3 * 508 - 1107);
+
+        // Flushed leftovers follow once their delay passes
+        while let Some(frame) = mux.next_frame(5_000, None).unwrap() {
+            frames.push(frame);
+        }
+        assert!((0..VC_COUNT as u8).all(|vc| mux.queued_bytes(vc) == 0));
+        let (demux, delivered) = demux_all(&frames);
+        assert_eq!(delivered, pushed);
+        assert_eq!((demux.frame_gaps(), demux.lost_packets()), (0, 0));
+    }
+
+    #[test]
+    fn test_demultiplexer_resynchronizes_after_lost_frame() {
+        let mut mux = VcMultiplexer::<8192>::default();
+        let sent: StdVec<StdVec<u8>> = (0..8)
+            .map(|sequence| packet(0x013, sequence, 400))
+            .collect();
+        for bytes in &sent {
+            mux.push(virtual_channels::SCIENCE, bytes, 0).unwrap();
+        }
+        let mut frames = StdVec::new();
+        while let Some(frame) = mux.next_frame(1_000, None).unwrap() {
+            frames.push(frame);
+        }
+        assert_eq!(frames.len(), 3);
+
+        // Frame 1 carries the end of packet 2 through the start of packet 5
+        frames.remove(1);
+        let (demux, delivered) = demux_all(&frames);
+        let delivered = &delivered[usize::from(virtual_channels::SCIENCE)];
+        assert_eq!(delivered[..2], sent[..2]);
+        assert_eq!(delivered[2..], sent[6..]);
+        assert_eq!((demux.frame_gaps(), demux.lost_packets()), (1, 1));
+
+        // Frames for another spacecraft are refused
+        let mut foreign = frames[0].clone();
+        foreign.spacecraft_id = 1;
+        assert!(TmDemultiplexer::default()
+            .receive(&foreign, |_| {})
+            .is_err());
+    }
+
+    #[test]
+    fn test_push_rejects_what_cannot_be_framed() {
+        let mut mux = VcMultiplexer::<64>::default();
+        let bytes = packet(TELEMETRY_APID, 1, 40);
+        assert!(mux.push(VC_COUNT as u8, &bytes, 0).is_err());
+        assert!(mux.push(0, &bytes[..bytes.len() - 1], 0).is_err());
+        let empty = SpacePacket::new(PacketType::Telemetry, TELEMETRY_APID, 2, b"", None).unwrap();
+        assert!(mux.push_packet(&empty, 0).is_err());
+
+        mux.push(0, &bytes, 0).unwrap();
+        assert!(matches!(
+            mux.push(0, &bytes, 0),
+            Err(SpaceCommError::ResourceExhausted { .. })
+        ));
+
+        let starved = VcMuxConfig {
+            weights: [4, 0, 1],
+            ..VcMuxConfig::default()
+        };
+        assert!(VcMultiplexer::<64>::new(SPACECRAFT_ID, starved).is_err());
+        assert!(VcMultiplexer::<64>::new(0x400, VcMuxConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,409 @@
+//! On-air wire format
+//!
+//! The one definition of how structures are laid out in bytes on the space
+//! link. Every fixed-layout encoder and decoder in the crate goes through
+//! [`WireWriter`] and [`WireReader`], so byte order and field encodings are
+//! decided here and nowhere else.
+//!
+//! # Byte order
+//! Multi-byte fields are sent in [`WIRE_BYTE_ORDER`], big-endian (network
+//! byte order), as CCSDS 133.0-B-2 requires for the primary header. Readers
+//! and writers can be built for [`ByteOrder::LittleEndian`] to handle
+//! foreign formats, but nothing on this link uses it.
+//!
+//! # Alignment
+//! None. Fields are packed back to back with no padding, so a field may start
+//! at any byte offset. Decoders copy fields out rather than casting.
+//!
+//! # Field encodings
+//! | Type          | Encoding                                             |
+//! |---------------|------------------------------------------------------|
+//! | `u8`..`u64`   | Unsigned binary, `size_of` bytes                     |
+//! | `i16`..`i64`  | Two's complement, `size_of` bytes                    |
+//! | `f32` / `f64` | IEEE 754 binary32 / binary64 bit pattern             |
+//! | `bool`        | One byte, 0 or 1; decoders treat any non-zero as set |
+//! | enum          | One-byte code defined by the enum's `code()`         |
+//! | byte string   | Raw bytes running to the end of the data field       |
+//!
+//! # Frames
+//! A frame is a CCSDS Space Packet: the [`PRIMARY_HEADER_LEN`]-byte primary
+//! header, the data field, then an [`ERROR_CONTROL_LEN`]-byte
+//! CRC-16/CCITT-FALSE over everything before it. Frames on this link carry no
+//! secondary header, and the Packet Data Length field counts the data field
+//! only, less one; the trailing CRC is not included. [`split_frame`] checks
+//! all of this and returns the data field.
+//!
+//! Frames are downlinked back to back in fixed-length TM Transfer Frames,
+//! one virtual channel per transfer frame; [`frame_len`] finds where each
+//! ends. The transfer frame layout is defined in [`crate::ccsds`], the
+//! channels in [`crate::vc_mux`].
+//!
+//! # Structures
+//! | Data field                        | Defined in                  | Encoding           |
+//! |-----------------------------------|-----------------------------|--------------------|
+//! | Primary header                    | [`crate::ccsds`]            | Bit-packed, 6 bytes|
+//! | Command (ID, then parameters)     | [`crate::messaging`]        | `u32` + JSON       |
+//! | Telemetry frame                   | [`crate::telemetry`]        | Fixed layout       |
+//! | Execution report                  | [`crate::execution_report`] | Fixed layout       |
+//! | Memory dump segment, dwell batch  | [`crate::diagnostics`]      | Fixed layout       |
+//! | Loopback echo                     | [`crate::loopback`]         | Fixed layout       |
+//! | CLCW                              | [`crate::cop1`]             | Bit-packed, 4 bytes|
+//! | Event log block                   | [`crate::event_log`]        | `u16` + varints    |
+//! | Command load and load manifest    | [`crate::command_load`]     | JSON               |
+//! | File manifest, retransmit request | [`crate::file_downlink`]    | JSON               |
+//! | Link forecast                     | [`crate::link_forecast`]    | JSON               |
+//!
+//! Each module documents its own field table; RF housekeeping, the EPS
+//! summary and crosslink ranging are telemetry frames on their own APIDs.
+//!
+//! # Requirements Traceability
+//! - REQ-IF-002: CCSDS Compliance (network byte order, Packet Error Control)
+//! - REQ-NF-004: Fault Tolerance (truncated and corrupted frames rejected)
+
+use heapless::Vec;
+
+use crate::ccsds::{crc16_ccitt, SpacePacketHeader};
+use crate::error::{MemoryErrorType, Result, SpaceCommError};
+
+/// Byte order of multi-byte fields on the link
+pub const WIRE_BYTE_ORDER: ByteOrder = ByteOrder::BigEndian;
+
+/// Primary header length, bytes
+pub const PRIMARY_HEADER_LEN: usize = 6;
+
+/// Packet Error Control length, bytes
+pub const ERROR_CONTROL_LEN: usize = 2;
+
+/// Command ID length at the start of a command data field, bytes
+pub const COMMAND_ID_LEN: usize = 4;
+
+/// Order of the bytes of a multi-byte field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Most significant byte first
+    BigEndian,
+    /// Least significant byte first
+    LittleEndian,
+}
+
+/// Value with a fixed-size wire encoding
+pub trait WireValue: Sized + Copy {
+    /// Encoded size, bytes
+    const SIZE: usize;
+
+    /// Write the value into `out`, which is exactly `SIZE` bytes
+    fn encode(self, order: ByteOrder, out: &mut [u8]);
+
+    /// Read a value from `bytes`, which is exactly `SIZE` bytes
+    fn decode(order: ByteOrder, bytes: &[u8]) -> Self;
+}
+
+macro_rules! wire_value {
+    ($($ty:ty),*) => {
+        $(
+            impl WireValue for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn encode(self, order: ByteOrder, out: &mut [u8]) {
+                    out.copy_from_slice(&match order {
+                        ByteOrder::BigEndian => self.to_be_bytes(),
+                        ByteOrder::LittleEndian => self.to_le_bytes(),
+                    });
+                }
+
+                fn decode(order: ByteOrder, bytes: &[u8]) -> Self {
+                    let mut raw = [0u8; core::mem::size_of::<$ty>()];
+                    raw.copy_from_slice(bytes);
+                    match order {
+                        ByteOrder::BigEndian => <$ty>::from_be_bytes(raw),
+                        ByteOrder::LittleEndian => <$ty>::from_le_bytes(raw),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+wire_value!(u8, u16, u32, u64, i16, i32, i64, f32, f64);
+
+impl WireValue for bool {
+    const SIZE: usize = 1;
+
+    fn encode(self, _order: ByteOrder, out: &mut [u8]) {
+        out[0] = u8::from(self);
+    }
+
+    fn decode(_order: ByteOrder, bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+/// Appends fields to a bounded buffer in wire order
+#[derive(Debug, Clone)]
+pub struct WireWriter<const N: usize> {
+    bytes: Vec<u8, N>,
+    order: ByteOrder,
+}
+
+impl<const N: usize> WireWriter<N> {
+    /// Empty writer in [`WIRE_BYTE_ORDER`]
+    pub fn new() -> Self {
+        Self::with_order(WIRE_BYTE_ORDER)
+    }
+
+    /// Empty writer in `order`
+    pub fn with_order(order: ByteOrder) -> Self {
+        Self {
+            bytes: Vec::new(),
+            order,
+        }
+    }
+
+    /// Append a fixed-size field
+    ///
+    /// Returns a buffer overflow error, leaving the buffer unchanged, when
+    /// the field does not fit.
+    pub fn put<T: WireValue>(&mut self, value: T) -> Result<&mut Self> {
+        let at = self.bytes.len();
+        self.bytes
+            .resize_default(at + T::SIZE)
+            .map_err(|_| overflow(T::SIZE))?;
+        value.encode(self.order, &mut self.bytes[at..]);
+        Ok(self)
+    }
+
+    /// Append raw bytes
+    pub fn put_bytes(&mut self, bytes: &[u8]) -> Result<&mut Self> {
+        self.bytes
+            .extend_from_slice(bytes)
+            .map_err(|_| overflow(bytes.len()))?;
+        Ok(self)
+    }
+
+    /// Bytes written so far
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether nothing has been written
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The encoded bytes
+    pub fn finish(self) -> Vec<u8, N> {
+        self.bytes
+    }
+}
+
+impl<const N: usize> Default for WireWriter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Takes fields from the front of a byte slice in wire order
+#[derive(Debug, Clone)]
+pub struct WireReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    order: ByteOrder,
+}
+
+impl<'a> WireReader<'a> {
+    /// Reader over `bytes` in [`WIRE_BYTE_ORDER`]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_order(bytes, WIRE_BYTE_ORDER)
+    }
+
+    /// Reader over `bytes` in `order`
+    pub fn with_order(bytes: &'a [u8], order: ByteOrder) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            order,
+        }
+    }
+
+    /// Take a fixed-size field
+    ///
+    /// Returns an invalid packet error, consuming nothing, when fewer than
+    /// `T::SIZE` bytes remain.
+    pub fn get<T: WireValue>(&mut self) -> Result<T> {
+        let bytes = self.take(T::SIZE)?;
+        Ok(T::decode(self.order, bytes))
+    }
+
+    /// Take the next `len` bytes
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + len)
+            .ok_or(SpaceCommError::invalid_packet(
+                "Wire field truncated",
+                Some(self.position as u32),
+            ))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// Take every remaining byte
+    pub fn rest(&mut self) -> &'a [u8] {
+        let bytes = &self.bytes[self.position..];
+        self.position = self.bytes.len();
+        bytes
+    }
+
+    /// Bytes not yet taken
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    /// Offset of the next field
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Split a frame into its primary header and data field
+///
+/// - **ID**: FN-WIR-001
+/// - **Requirement**: Accept a frame only as laid out in the module
+///   documentation (REQ-IF-002).
+/// - **Inputs**: Frame bytes: primary header, data field and 2-byte CRC.
+/// - **Outputs**: The primary header and the data field.
+/// - **Failure Modes**: `InvalidPacket` for a frame shorter than a header and
+///   CRC, a secondary header, a Packet Data Length that disagrees with the
+///   frame length, or a CRC mismatch.
+pub fn split_frame(bytes: &[u8]) -> Result<(SpacePacketHeader, &[u8])> {
+    let header = SpacePacketHeader::from_bytes(bytes)?;
+    if bytes.len() < PRIMARY_HEADER_LEN + ERROR_CONTROL_LEN {
+        return Err(SpaceCommError::invalid_packet("Frame too short", None));
+    }
+    if header.secondary_header_flag {
+        return Err(SpaceCommError::invalid_packet(
+            "Secondary header not used on this link",
+            None,
+        ));
+    }
+
+    let (body, crc) = bytes.split_at(bytes.len() - ERROR_CONTROL_LEN);
+    let data = &body[PRIMARY_HEADER_LEN..];
+    // An empty data field is declared as length 0, like a 1-byte one
+    if usize::from(header.data_length) != data.len().saturating_sub(1) {
+        return Err(SpaceCommError::invalid_packet(
+            "Packet Data Length disagrees with frame length",
+            Some(u32::from(header.data_length)),
+        ));
+    }
+    if crc16_ccitt(0xFFFF, body) != WireReader::new(crc).get::<u16>()? {
+        return Err(SpaceCommError::invalid_packet(
+            "Frame CRC mismatch",
+            Some(u32::from(header.sequence_count)),
+        ));
+    }
+
+    Ok((header, data))
+}
+
+/// Length on the link of the frame `header` starts
+///
+/// Counts the header, the declared data field and the CRC. A frame with an
+/// empty data field declares the same length as a one-byte one, so it is
+/// the one frame this does not delimit.
+pub fn frame_len(header: &SpacePacketHeader) -> usize {
+    PRIMARY_HEADER_LEN + usize::from(header.data_length) + 1 + ERROR_CONTROL_LEN
+}
+
+/// Command ID at the start of a command data field
+///
+/// Returns `None` for a data field too short to hold one.
+pub fn command_id(data: &[u8]) -> Option<u32> {
+    WireReader::new(data).get().ok()
+}
+
+fn overflow(size: usize) -> SpaceCommError {
+    SpaceCommError::memory_error(MemoryErrorType::BufferOverflow, Some(size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccsds::{PacketType, SpacePacket};
+
+    #[test]
+    fn test_fields_roundtrip_in_both_orders() {
+        for order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut writer = WireWriter::<32>::with_order(order);
+            writer
+                .put(0xA5u8)
+                .and_then(|w| w.put(0x1234u16))
+                .and_then(|w| w.put(-2i32))
+                .and_then(|w| w.put(1.5f32))
+                .and_then(|w| w.put(u64::MAX - 1))
+                .and_then(|w| w.put(true))
+                .and_then(|w| w.put_bytes(b"ok"))
+                .unwrap();
+            assert_eq!(writer.len(), 22);
+            let bytes = writer.finish();
+
+            let mut reader = WireReader::with_order(&bytes, order);
+            assert_eq!(reader.get::<u8>().unwrap(), 0xA5);
+            assert_eq!(reader.get::<u16>().unwrap(), 0x1234);
+            assert_eq!(reader.get::<i32>().unwrap(), -2);
+            assert_eq!(reader.get::<f32>().unwrap(), 1.5);
+            assert_eq!(reader.get::<u64>().unwrap(), u64::MAX - 1);
+            assert!(reader.get::<bool>().unwrap());
+            assert_eq!(reader.rest(), b"ok");
+            assert_eq!(reader.remaining(), 0);
+        }
+    }
+
+    #[test]
+    fn test_link_is_big_endian_and_packed() {
+        let mut writer = WireWriter::<8>::new();
+        writer.put(0x0102u16).and_then(|w| w.put(0x0304_0506u32)).unwrap();
+        assert_eq!(writer.finish().as_slice(), &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_bounds_are_errors() {
+        let mut writer = WireWriter::<3>::new();
+        writer.put(1u16).unwrap();
+        assert!(writer.put(1u16).is_err());
+        assert_eq!(writer.len(), 2);
+
+        let mut reader = WireReader::new(&[0, 1, 2]);
+        assert!(reader.get::<u32>().is_err());
+        assert_eq!(reader.get::<u16>().unwrap(), 1);
+        assert!(reader.take(2).is_err());
+        assert_eq!(reader.position(), 2);
+
+        assert_eq!(command_id(&[0, 0, 0, 0x37, b'{']), Some(0x37));
+        assert_eq!(command_id(&[0, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_split_frame_checks_layout() {
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 9, b"data", None).unwrap();
+        let bytes = packet.to_bytes().unwrap();
+        let (header, data) = split_frame(&bytes).unwrap();
+        assert_eq!((header.apid, header.sequence_count), (0x100, 9));
+        assert_eq!(data, b"data");
+
+        let mut corrupted = bytes.clone();
+        corrupted[7] ^= 0x01;
+        assert!(split_frame(&corrupted).is_err());
+        assert!(split_frame(&bytes[..bytes.len() - 1]).is_err());
+        assert!(split_frame(&bytes[..7]).is_err());
+    }
+
+    #[test]
+    fn test_frame_len_from_header() {
+        let packet = SpacePacket::new(PacketType::Telemetry, 0x100, 9, b"data", None).unwrap();
+        let bytes = packet.to_bytes().unwrap();
+        assert_eq!(frame_len(&packet.header), bytes.len());
+        let empty = SpacePacket::new(PacketType::Telemetry, 0x100, 9, b"", None).unwrap();
+        assert_ne!(frame_len(&empty.header), empty.to_bytes().unwrap().len());
+    }
+}
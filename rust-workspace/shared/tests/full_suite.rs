@@ -601,10 +601,11 @@ mod telemetry_tests {
 
     #[test]
     fn test_measurement_quality_variants_exist() {
-        let _: [MeasurementQuality; 4] = [
+        let _: [MeasurementQuality; 5] = [
             MeasurementQuality::Good,
-            MeasurementQuality::Questionable,
-            MeasurementQuality::Bad,
+            MeasurementQuality::Suspect,
+            MeasurementQuality::Stale,
+            MeasurementQuality::Invalid,
             MeasurementQuality::NotAvailable,
         ];
     }
@@ -18,6 +18,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::modcod::{modcod_table, FecScheme};
+
 // ─────────────────────────────────────────────────────────────────────────────
 // 1. BEAMFORMING
 // ─────────────────────────────────────────────────────────────────────────────
@@ -462,6 +464,9 @@ pub struct AmcConditions {
     pub bandwidth_mhz: f64,
     /// Whether the link operates under a latency constraint favouring robustness.
     pub latency_sensitive: bool,
+    /// Forward error correction family the MODCOD table is built for.
+    #[serde(default)]
+    pub fec: FecScheme,
 }
 
 /// AMC selection and achievable throughput result.
@@ -471,6 +476,8 @@ pub struct AmcResult {
     pub modulation: ModulationScheme,
     /// Selected FEC coding rate.
     pub coding_rate: CodingRate,
+    /// FEC family of the selected code.
+    pub fec: FecScheme,
     /// Achievable information throughput in Mbps.
     pub throughput_mbps: f64,
     /// Spectral efficiency in bits/s/Hz.
@@ -481,45 +488,27 @@ pub struct AmcResult {
 
 /// Select the best (modulation, coding rate) pair for the current link SNR.
 ///
-/// The algorithm walks from the highest-order MCS downward and picks the first
-/// combination whose required Eb/N0 (after coding gain) is met by the link SNR
-/// with at least 2 dB of margin.
+/// The algorithm walks the MODCOD table of the link's FEC family from the
+/// highest-order MCS downward and picks the first combination whose required
+/// Eb/N0 (after coding gain) is met by the link SNR with at least 2 dB of
+/// margin.
 pub fn select_amc(conditions: &AmcConditions) -> AmcResult {
     const MARGIN_DB: f64 = 2.0;
 
-    let schemes = [
-        ModulationScheme::Qam256,
-        ModulationScheme::Qam64,
-        ModulationScheme::Qam16,
-        ModulationScheme::Qpsk,
-        ModulationScheme::Bpsk,
-    ];
-    let rates = [
-        CodingRate::Rate7_8,
-        CodingRate::Rate5_6,
-        CodingRate::Rate3_4,
-        CodingRate::Rate2_3,
-        CodingRate::Rate1_2,
-    ];
-
-    // Start with minimum if no better option fits.
-    let mut best_mod = ModulationScheme::Bpsk;
-    let mut best_rate = CodingRate::Rate1_2;
-
-    'outer: for &mod_scheme in &schemes {
-        for &code_rate in &rates {
-            // Effective Eb/N0 requirement = modulation threshold − coding gain.
-            let required_eb_n0 = mod_scheme.min_eb_n0_db() - code_rate.coding_gain_db();
-            if conditions.latency_sensitive && mod_scheme > ModulationScheme::Qam16 {
-                continue; // Safety: high-order QAM adds re-try latency
-            }
-            if conditions.link_snr_db >= required_eb_n0 + MARGIN_DB {
-                best_mod = mod_scheme;
-                best_rate = code_rate;
-                break 'outer;
-            }
+    let table = modcod_table(conditions.fec);
+    // Start with minimum if no better option fits; the table ends with it.
+    let mut best = table[table.len() - 1];
+
+    for &modcod in &table {
+        if conditions.latency_sensitive && modcod.modulation > ModulationScheme::Qam16 {
+            continue; // Safety: high-order QAM adds re-try latency
+        }
+        if conditions.link_snr_db >= modcod.required_eb_n0_db + MARGIN_DB {
+            best = modcod;
+            break;
         }
     }
+    let (best_mod, best_rate) = (best.modulation, best.coding_rate);
 
     // Shannon-limited throughput after FEC overhead.
     let symbol_rate_msps = conditions.bandwidth_mhz; // Assume Nyquist: 1 sym/s per Hz
@@ -527,12 +516,12 @@ pub fn select_amc(conditions: &AmcConditions) -> AmcResult {
     let throughput_mbps = raw_rate_mbps * best_rate.ratio();
     let spectral_efficiency = throughput_mbps / conditions.bandwidth_mhz;
 
-    let required_eb_n0 = best_mod.min_eb_n0_db() - best_rate.coding_gain_db();
-    let link_margin_db = conditions.link_snr_db - required_eb_n0;
+    let link_margin_db = conditions.link_snr_db - best.required_eb_n0_db;
 
     AmcResult {
         modulation: best_mod,
         coding_rate: best_rate,
+        fec: best.fec,
         throughput_mbps,
         spectral_efficiency,
         link_margin_db,
@@ -702,6 +691,7 @@ pub fn run_advanced_rf_demo() -> AdvancedRfLinkSummary {
         link_snr_db: 30.0,
         bandwidth_mhz: 500.0,
         latency_sensitive: false,
+        fec: FecScheme::Legacy,
     };
     let amc = select_amc(&amc_conditions);
 
@@ -0,0 +1,318 @@
+//! Ground Antenna Noise Temperature Module
+//!
+//! A ground antenna pointed at zenith sees cold sky: a few kelvin of cosmic
+//! background plus the emission of one air mass. Lower down it looks through
+//! more atmosphere, and below 10-15° the edge of its pattern and the feed
+//! spillover start to pick up the 290 K ground. The antenna temperature, and
+//! with it the system noise temperature, climbs steeply towards the horizon,
+//! so G/T falls at the start and end of every pass even where path loss and
+//! attenuation alone would still close the link.
+//!
+//! [`AntennaTemperatureCurve`] is the antenna temperature against elevation,
+//! a table interpolated linearly between its points and configured per
+//! antenna; [`AntennaTemperatureCurve::typical`] gives a reflector's curve
+//! in each band. A [`GroundAntenna`] adds it to its receiver noise for the
+//! system noise temperature and G/T at any elevation.
+//!
+//! The link model's `BandCharacteristics::noise_temperature_k` is the system
+//! noise temperature at zenith; [`FrequencyBand::at_elevation`] raises it by
+//! the antenna's excess over zenith, which is how the simulation
+//! (`FrequencyBand::simulate_transmission_at`) and pass planning
+//! (`forecast::ForecastStudy::with_ground_antenna`) see low-elevation SNR
+//! degradation. Without a ground antenna both keep the fixed noise
+//! temperature.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (elevation-dependent ground
+//!   antenna noise temperature and G/T)
+//! - REQ-PF-002: Data Transfer Rates (low-elevation capacity in pass
+//!   planning)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BandType, FrequencyBand};
+
+/// Elevation of the top of the sky, degrees.
+const ZENITH_DEG: f64 = 90.0;
+
+/// Errors of antenna noise configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AntennaNoiseError {
+    /// The antenna temperature table is not usable.
+    InvalidCurve(&'static str),
+    /// The receiver noise temperature is not usable.
+    InvalidReceiver(&'static str),
+}
+
+impl fmt::Display for AntennaNoiseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AntennaNoiseError::InvalidCurve(reason) => {
+                write!(f, "invalid antenna temperature curve: {}", reason)
+            }
+            AntennaNoiseError::InvalidReceiver(reason) => {
+                write!(f, "invalid receiver noise: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AntennaNoiseError {}
+
+/// Antenna temperature at one elevation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AntennaTemperaturePoint {
+    /// Elevation, degrees.
+    pub elevation_deg: f64,
+    /// Antenna temperature, K.
+    pub temperature_k: f64,
+}
+
+/// Shorthand for the preset tables.
+const fn point(elevation_deg: f64, temperature_k: f64) -> AntennaTemperaturePoint {
+    AntennaTemperaturePoint {
+        elevation_deg,
+        temperature_k,
+    }
+}
+
+/// Antenna temperature against elevation.
+///
+/// - **ID**: MOD-ANT-001
+/// - **Requirement**: Each ground antenna's noise temperature follows its
+///   own measured or modelled curve, cold sky at zenith rising to ground
+///   spillover at the horizon.
+/// - **Constraints**: At least one point, elevations strictly increasing
+///   within 0-90°, temperatures finite and non-negative. Outside the table
+///   the nearest point's temperature applies.
+///
+/// Configuration files give the table as a list of points; it is checked
+/// as it is read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "Vec<AntennaTemperaturePoint>",
+    into = "Vec<AntennaTemperaturePoint>"
+)]
+pub struct AntennaTemperatureCurve {
+    points: Vec<AntennaTemperaturePoint>,
+}
+
+impl TryFrom<Vec<AntennaTemperaturePoint>> for AntennaTemperatureCurve {
+    type Error = AntennaNoiseError;
+
+    fn try_from(points: Vec<AntennaTemperaturePoint>) -> Result<Self, Self::Error> {
+        Self::new(points)
+    }
+}
+
+impl From<AntennaTemperatureCurve> for Vec<AntennaTemperaturePoint> {
+    fn from(curve: AntennaTemperatureCurve) -> Self {
+        curve.points
+    }
+}
+
+impl AntennaTemperatureCurve {
+    /// Curve through `points`, in increasing elevation.
+    ///
+    /// - **Failure Modes**: No points, an elevation outside 0-90° or not
+    ///   above the one before, or a negative or non-finite temperature →
+    ///   `InvalidCurve`.
+    pub fn new(points: Vec<AntennaTemperaturePoint>) -> Result<Self, AntennaNoiseError> {
+        if points.is_empty() {
+            return Err(AntennaNoiseError::InvalidCurve("the table has no points"));
+        }
+        if !points
+            .iter()
+            .all(|p| (0.0..=ZENITH_DEG).contains(&p.elevation_deg))
+        {
+            return Err(AntennaNoiseError::InvalidCurve(
+                "elevations must be from 0° to 90°",
+            ));
+        }
+        if points
+            .windows(2)
+            .any(|pair| pair[1].elevation_deg <= pair[0].elevation_deg)
+        {
+            return Err(AntennaNoiseError::InvalidCurve(
+                "elevations must be strictly increasing",
+            ));
+        }
+        if !points
+            .iter()
+            .all(|p| p.temperature_k.is_finite() && p.temperature_k >= 0.0)
+        {
+            return Err(AntennaNoiseError::InvalidCurve(
+                "temperatures must be finite and not negative",
+            ));
+        }
+        Ok(Self { points })
+    }
+
+    /// Typical curve of a reflector antenna in `band`.
+    ///
+    /// Galactic noise dominates at UHF, cold sky and spillover at S and X,
+    /// and atmospheric emission, rising sharply at low elevation, at K and
+    /// Ka.
+    pub fn typical(band: BandType) -> Self {
+        let points: &[AntennaTemperaturePoint] = match band {
+            BandType::UHFBand => &[
+                point(0.0, 250.0),
+                point(5.0, 170.0),
+                point(10.0, 130.0),
+                point(20.0, 100.0),
+                point(30.0, 90.0),
+                point(45.0, 85.0),
+                point(60.0, 82.0),
+                point(90.0, 80.0),
+            ],
+            BandType::SBand => &[
+                point(0.0, 120.0),
+                point(5.0, 60.0),
+                point(10.0, 35.0),
+                point(20.0, 22.0),
+                point(30.0, 18.0),
+                point(45.0, 15.0),
+                point(60.0, 14.0),
+                point(90.0, 12.0),
+            ],
+            BandType::XBand => &[
+                point(0.0, 130.0),
+                point(5.0, 70.0),
+                point(10.0, 42.0),
+                point(20.0, 27.0),
+                point(30.0, 22.0),
+                point(45.0, 19.0),
+                point(60.0, 18.0),
+                point(90.0, 17.0),
+            ],
+            BandType::KBand => &[
+                point(0.0, 230.0),
+                point(5.0, 150.0),
+                point(10.0, 100.0),
+                point(20.0, 60.0),
+                point(30.0, 44.0),
+                point(45.0, 35.0),
+                point(60.0, 31.0),
+                point(90.0, 28.0),
+            ],
+            BandType::KaBand => &[
+                point(0.0, 240.0),
+                point(5.0, 165.0),
+                point(10.0, 110.0),
+                point(20.0, 65.0),
+                point(30.0, 48.0),
+                point(45.0, 37.0),
+                point(60.0, 33.0),
+                point(90.0, 30.0),
+            ],
+        };
+        Self {
+            points: points.to_vec(),
+        }
+    }
+
+    /// Points of the table, in increasing elevation.
+    pub fn points(&self) -> &[AntennaTemperaturePoint] {
+        &self.points
+    }
+
+    /// Antenna temperature at `elevation_deg`, K.
+    pub fn temperature_k(&self, elevation_deg: f64) -> f64 {
+        let upper = self
+            .points
+            .iter()
+            .position(|p| p.elevation_deg >= elevation_deg);
+        match upper {
+            None => self.points[self.points.len() - 1].temperature_k,
+            Some(0) => self.points[0].temperature_k,
+            Some(index) => {
+                let (low, high) = (self.points[index - 1], self.points[index]);
+                let fraction =
+                    (elevation_deg - low.elevation_deg) / (high.elevation_deg - low.elevation_deg);
+                low.temperature_k + fraction * (high.temperature_k - low.temperature_k)
+            }
+        }
+    }
+
+    /// Antenna temperature at `elevation_deg` above the one at zenith, K.
+    pub fn excess_over_zenith_k(&self, elevation_deg: f64) -> f64 {
+        self.temperature_k(elevation_deg) - self.temperature_k(ZENITH_DEG)
+    }
+}
+
+/// Ground antenna noise model.
+///
+/// - **ID**: MOD-ANT-002
+/// - **Requirement**: System noise temperature and G/T of a ground antenna
+///   follow the elevation it points at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroundAntenna {
+    /// Antenna name, for reports.
+    pub name: String,
+    /// Noise of the feed, LNA and following stages referred to the antenna
+    /// port, K.
+    pub receiver_noise_k: f64,
+    /// Antenna temperature against elevation.
+    pub curve: AntennaTemperatureCurve,
+}
+
+impl GroundAntenna {
+    /// Antenna with the typical curve of `band` and `receiver_noise_k` of
+    /// receiver noise.
+    pub fn typical(name: &str, band: BandType, receiver_noise_k: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            receiver_noise_k,
+            curve: AntennaTemperatureCurve::typical(band),
+        }
+    }
+
+    /// Check that the receiver noise is usable; the curve is checked when
+    /// it is built.
+    ///
+    /// - **Failure Modes**: A negative or non-finite receiver noise →
+    ///   `InvalidReceiver`.
+    pub fn validate(&self) -> Result<(), AntennaNoiseError> {
+        if !(self.receiver_noise_k.is_finite() && self.receiver_noise_k >= 0.0) {
+            return Err(AntennaNoiseError::InvalidReceiver(
+                "receiver noise must be finite and not negative",
+            ));
+        }
+        Ok(())
+    }
+
+    /// System noise temperature at `elevation_deg`, K.
+    pub fn system_noise_k(&self, elevation_deg: f64) -> f64 {
+        self.receiver_noise_k + self.curve.temperature_k(elevation_deg)
+    }
+
+    /// G/T at `elevation_deg` with `gain_dbi` of antenna gain, dB/K.
+    pub fn g_over_t_db_k(&self, gain_dbi: f64, elevation_deg: f64) -> f64 {
+        gain_dbi - 10.0 * self.system_noise_k(elevation_deg).log10()
+    }
+
+    /// Loss of G/T at `elevation_deg` against zenith, dB.
+    pub fn degradation_db(&self, elevation_deg: f64) -> f64 {
+        10.0 * (self.system_noise_k(elevation_deg) / self.system_noise_k(ZENITH_DEG)).log10()
+    }
+}
+
+impl FrequencyBand {
+    /// This band as received by `antenna` at `elevation_deg`.
+    ///
+    /// - **ID**: FN-ANT-001
+    /// - **Requirement**: Low-elevation portions of a pass see the rise in
+    ///   system noise temperature of the ground antenna (REQ-FN-008).
+    /// - **Outputs**: A copy of the band whose noise temperature, taken as
+    ///   the zenith system noise temperature, is raised by the antenna's
+    ///   excess over zenith at `elevation_deg`.
+    /// - **Side Effects**: None.
+    pub fn at_elevation(&self, antenna: &GroundAntenna, elevation_deg: f64) -> FrequencyBand {
+        let mut band = self.clone();
+        band.characteristics.noise_temperature_k +=
+            antenna.curve.excess_over_zenith_k(elevation_deg);
+        band
+    }
+}
@@ -3,6 +3,11 @@
 //! This module simulates various atmospheric phenomena that affect
 //! satellite-ground communication, including rain fade, atmospheric
 //! absorption, scintillation, and ionospheric effects.
+//!
+//! [`itu_p618`] is the ITU-R P.618 slant-path rain attenuation model used by
+//! the link simulation.
+
+pub mod itu_p618;
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -120,12 +125,8 @@ impl RainFadeModel {
             return 0.0;
         }
 
-        // ITU-R P.838 coefficients for rain attenuation
-        let (k_h, k_v, alpha_h, alpha_v) = Self::get_itu_coefficients(frequency_ghz);
-
-        // Use horizontal polarization coefficients (conservative estimate)
-        let k = k_h;
-        let alpha = alpha_h;
+        // ITU-R P.838 coefficients, horizontal polarization (conservative estimate)
+        let (k, _, alpha, _) = Self::get_itu_coefficients(frequency_ghz);
 
         // Specific attenuation (dB/km)
         let gamma_r = k * rain_rate_mm_hour.powf(alpha);
@@ -604,7 +605,6 @@ impl WeatherPatternGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use approx::assert_relative_eq;
 
     #[test]
     fn test_rain_fade_calculation() {
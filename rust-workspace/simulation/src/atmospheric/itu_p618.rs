@@ -0,0 +1,342 @@
+//! ITU-R P.618 Rain Attenuation Module
+//!
+//! Slant-path rain attenuation by the step-by-step method of ITU-R P.618-13
+//! §2.2.1.1: the path from the station up to the rain height is shortened
+//! by the horizontal and vertical reduction factors for the non-uniform
+//! rain cell, and multiplied by the specific attenuation of ITU-R P.838-3.
+//! The P.838-3 coefficients come from its regressions in frequency, not a
+//! table of breakpoints, and are combined for the polarization tilt and the
+//! elevation of the path.
+//!
+//! P.618 predicts the attenuation exceeded for 0.01% of an average year
+//! from the rain rate exceeded for 0.01%. Applied to the rain rate of one
+//! scenario, as in the link model, it gives the attenuation of a rain event
+//! of that rate with the path reductions of an intense event. The rain
+//! height is the latitude model of ITU-R P.839-2 in place of the P.839-4
+//! digital map.
+//!
+//! `FrequencyBand::simulate_transmission_with_rain_model` uses the model
+//! with `RainModel::ItuP618`; the link model otherwise keeps its per-band
+//! rain multipliers.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (ITU-R P.618/P.838 slant-path
+//!   rain attenuation)
+
+use serde::{Deserialize, Serialize};
+
+/// Effective Earth radius for slant paths below 5° elevation, km.
+const EFFECTIVE_EARTH_RADIUS_KM: f64 = 8500.0;
+
+/// Frequencies the P.838-3 regressions cover, GHz.
+const REGRESSION_RANGE_GHZ: (f64, f64) = (1.0, 1000.0);
+
+/// One Gaussian term `a·exp(-((log10 f - b) / c)²)` of a P.838-3 regression.
+type Term = (f64, f64, f64);
+
+/// P.838-3 regression: Gaussian terms plus `m·log10 f + c`.
+struct Regression<const N: usize> {
+    terms: [Term; N],
+    m: f64,
+    c: f64,
+}
+
+impl<const N: usize> Regression<N> {
+    fn at(&self, log_f: f64) -> f64 {
+        self.terms
+            .iter()
+            .map(|&(a, b, c)| a * (-((log_f - b) / c).powi(2)).exp())
+            .sum::<f64>()
+            + self.m * log_f
+            + self.c
+    }
+}
+
+/// log10 k_H, ITU-R P.838-3 Table 1.
+const LOG_K_H: Regression<4> = Regression {
+    terms: [
+        (-5.33980, -0.10008, 1.13098),
+        (-0.35351, 1.26970, 0.45400),
+        (-0.23789, 0.86036, 0.15354),
+        (-0.94158, 0.64552, 0.16817),
+    ],
+    m: -0.18961,
+    c: 0.71147,
+};
+
+/// log10 k_V, ITU-R P.838-3 Table 2.
+const LOG_K_V: Regression<4> = Regression {
+    terms: [
+        (-3.80595, 0.56934, 0.81061),
+        (-3.44965, -0.22911, 0.51059),
+        (-0.39902, 0.73042, 0.11899),
+        (0.50167, 1.07319, 0.27195),
+    ],
+    m: -0.16398,
+    c: 0.63297,
+};
+
+/// α_H, ITU-R P.838-3 Table 3.
+const ALPHA_H: Regression<5> = Regression {
+    terms: [
+        (-0.14318, 1.82442, -0.55187),
+        (0.29591, 0.77564, 0.19822),
+        (0.32177, 0.63773, 0.13164),
+        (-5.37610, -0.96230, 1.47828),
+        (16.1721, -3.29980, 3.43990),
+    ],
+    m: 0.67849,
+    c: -1.95537,
+};
+
+/// α_V, ITU-R P.838-3 Table 4.
+const ALPHA_V: Regression<5> = Regression {
+    terms: [
+        (-0.07771, 2.33840, -0.76284),
+        (0.56727, 0.95545, 0.54039),
+        (-0.20238, 1.14520, 0.26809),
+        (-48.2991, 0.791669, 0.116226),
+        (48.5833, 0.791459, 0.116479),
+    ],
+    m: -0.053739,
+    c: 0.83433,
+};
+
+/// Polarization of the link.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Polarization {
+    /// Linear, horizontal.
+    Horizontal,
+    /// Linear, vertical.
+    Vertical,
+    /// Circular, either hand.
+    Circular,
+    /// Linear, tilted from the horizontal by `tilt_deg` degrees.
+    Linear { tilt_deg: f64 },
+}
+
+impl Polarization {
+    /// Tilt angle τ of P.838-3 relative to the horizontal, degrees;
+    /// circular polarization is 45°.
+    pub fn tilt_deg(self) -> f64 {
+        match self {
+            Polarization::Horizontal => 0.0,
+            Polarization::Vertical => 90.0,
+            Polarization::Circular => 45.0,
+            Polarization::Linear { tilt_deg } => tilt_deg,
+        }
+    }
+}
+
+/// Coefficients of the specific attenuation `γ = k·R^α`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RainCoefficients {
+    /// k, (dB/km)/(mm/h)^α.
+    pub k: f64,
+    /// α.
+    pub alpha: f64,
+}
+
+impl RainCoefficients {
+    /// P.838-3 coefficients at a frequency, elevation and polarization.
+    ///
+    /// - **ID**: FN-ATM-003
+    /// - **Requirement**: Specific rain attenuation follows ITU-R P.838-3
+    ///   for any frequency, path elevation and polarization tilt.
+    /// - **Inputs**: Frequency, GHz, clamped to the regressions' 1-1000
+    ///   GHz; path elevation, degrees; polarization.
+    /// - **Outputs**: k and α combined from the horizontal and vertical
+    ///   regressions by P.838-3 equations (4) and (5).
+    /// - **Side Effects**: None.
+    pub fn at(frequency_ghz: f64, elevation_deg: f64, polarization: Polarization) -> Self {
+        let log_f = frequency_ghz
+            .clamp(REGRESSION_RANGE_GHZ.0, REGRESSION_RANGE_GHZ.1)
+            .log10();
+        let k_h = 10f64.powf(LOG_K_H.at(log_f));
+        let k_v = 10f64.powf(LOG_K_V.at(log_f));
+        let (alpha_h, alpha_v) = (ALPHA_H.at(log_f), ALPHA_V.at(log_f));
+
+        let mix = elevation_deg.to_radians().cos().powi(2)
+            * (2.0 * polarization.tilt_deg().to_radians()).cos();
+        let k = (k_h + k_v + (k_h - k_v) * mix) / 2.0;
+        let alpha =
+            (k_h * alpha_h + k_v * alpha_v + (k_h * alpha_h - k_v * alpha_v) * mix) / (2.0 * k);
+        RainCoefficients { k, alpha }
+    }
+
+    /// Specific attenuation in rain of `rain_rate_mm_hour`, dB/km.
+    pub fn specific_attenuation_db_km(&self, rain_rate_mm_hour: f64) -> f64 {
+        self.k * rain_rate_mm_hour.max(0.0).powf(self.alpha)
+    }
+}
+
+/// Mean rain height above sea level at a latitude, km (ITU-R P.839-2).
+pub fn rain_height_km(latitude_deg: f64) -> f64 {
+    match latitude_deg {
+        lat if lat > 23.0 => (5.0 - 0.075 * (lat - 23.0)).max(0.0),
+        lat if lat >= -21.0 => 5.0,
+        lat if lat >= -71.0 => 5.0 + 0.1 * (lat + 21.0),
+        _ => 0.0,
+    }
+}
+
+/// Ground end of a slant path through rain.
+///
+/// - **ID**: MOD-ATM-001
+/// - **Requirement**: Rain attenuation follows from where the station is
+///   and how the link is polarized, not only from the band.
+/// - **Constraints**: Latitude within ±90°, altitude and polarization tilt
+///   finite; see `RainPath::validate`.
+///
+/// The default is a sea-level station at 45°N on a circularly polarized
+/// link.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RainPath {
+    /// Station latitude, degrees, north positive.
+    pub latitude_deg: f64,
+    /// Station altitude above sea level, km.
+    pub station_altitude_km: f64,
+    /// Polarization of the link.
+    pub polarization: Polarization,
+}
+
+impl Default for RainPath {
+    fn default() -> Self {
+        RainPath {
+            latitude_deg: 45.0,
+            station_altitude_km: 0.0,
+            polarization: Polarization::Circular,
+        }
+    }
+}
+
+impl RainPath {
+    /// Rain attenuation along the path, dB.
+    ///
+    /// - **ID**: FN-ATM-004
+    /// - **Requirement**: Slant-path rain attenuation by ITU-R P.618-13
+    ///   §2.2.1.1 steps 1 to 8, for the link model and pass planning.
+    /// - **Inputs**: Frequency, GHz; rain rate, mm/h; elevation of the
+    ///   path, degrees, 0-90.
+    /// - **Outputs**: Attenuation, dB, never negative; 0 without rain or
+    ///   with the station above the rain height.
+    /// - **Side Effects**: None.
+    pub fn attenuation_db(
+        &self,
+        frequency_ghz: f64,
+        rain_rate_mm_hour: f64,
+        elevation_deg: f64,
+    ) -> f64 {
+        // Step 1: rain height above the station
+        let rain_depth_km = rain_height_km(self.latitude_deg) - self.station_altitude_km;
+        if rain_rate_mm_hour <= 0.0 || rain_depth_km <= 0.0 {
+            return 0.0;
+        }
+        let theta = elevation_deg.clamp(0.0, 90.0);
+        let (sin_theta, cos_theta) = theta.to_radians().sin_cos();
+
+        // Step 2: slant path below the rain height, with Earth curvature
+        // at low elevation
+        let slant_km = if theta >= 5.0 {
+            rain_depth_km / sin_theta
+        } else {
+            2.0 * rain_depth_km
+                / ((sin_theta.powi(2) + 2.0 * rain_depth_km / EFFECTIVE_EARTH_RADIUS_KM).sqrt()
+                    + sin_theta)
+        };
+
+        // Step 3: its horizontal projection
+        let ground_km = slant_km * cos_theta;
+
+        // Step 5: specific attenuation
+        let gamma = RainCoefficients::at(frequency_ghz, theta, self.polarization)
+            .specific_attenuation_db_km(rain_rate_mm_hour);
+
+        // Step 6: horizontal reduction factor
+        let f = frequency_ghz;
+        let horizontal = 1.0
+            / (1.0 + 0.78 * (ground_km * gamma / f).sqrt()
+                - 0.38 * (1.0 - (-2.0 * ground_km).exp()));
+
+        // Step 7: vertical adjustment factor
+        let zeta_deg = (rain_depth_km / (ground_km * horizontal))
+            .atan()
+            .to_degrees();
+        let rain_km = if zeta_deg > theta {
+            ground_km * horizontal / cos_theta
+        } else {
+            rain_depth_km / sin_theta
+        };
+        let chi = (36.0 - self.latitude_deg.abs()).max(0.0);
+        let vertical = 1.0
+            / (1.0
+                + sin_theta.sqrt()
+                    * (31.0 * (1.0 - (-(theta / (1.0 + chi))).exp()) * (rain_km * gamma).sqrt()
+                        / f.powi(2)
+                        - 0.45));
+
+        // Step 8: effective path length
+        (gamma * rain_km * vertical).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_p838_coefficients() {
+        // ITU-R P.838-3 tabulated values
+        let cases = [
+            (10.0, 0.01217, 1.2571, 0.01129, 1.2156),
+            (20.0, 0.09164, 1.0568, 0.09611, 0.9847),
+            (30.0, 0.2403, 0.9485, 0.2291, 0.9129),
+        ];
+        for (f, k_h, alpha_h, k_v, alpha_v) in cases {
+            let h = RainCoefficients::at(f, 0.0, Polarization::Horizontal);
+            let v = RainCoefficients::at(f, 0.0, Polarization::Vertical);
+            assert_relative_eq!(h.k, k_h, max_relative = 0.01);
+            assert_relative_eq!(h.alpha, alpha_h, max_relative = 0.01);
+            assert_relative_eq!(v.k, k_v, max_relative = 0.01);
+            assert_relative_eq!(v.alpha, alpha_v, max_relative = 0.01);
+        }
+
+        // Circular lies between the linear polarizations
+        let c = RainCoefficients::at(20.0, 30.0, Polarization::Circular);
+        assert!(c.k > 0.09164_f64.min(0.09611) && c.k < 0.09611);
+    }
+
+    #[test]
+    fn test_slant_path_attenuation() {
+        let path = RainPath::default();
+
+        // Clear sky, and a station above the rain
+        assert_eq!(path.attenuation_db(20.0, 0.0, 30.0), 0.0);
+        let summit = RainPath {
+            station_altitude_km: 5.0,
+            ..path
+        };
+        assert_eq!(summit.attenuation_db(20.0, 25.0, 30.0), 0.0);
+
+        // Grows with rain rate and frequency, falls with elevation
+        let ku = path.attenuation_db(12.0, 25.0, 30.0);
+        assert!(ku > 1.0 && ku < 10.0, "{}", ku);
+        assert!(path.attenuation_db(12.0, 50.0, 30.0) > ku);
+        assert!(path.attenuation_db(30.0, 25.0, 30.0) > ku);
+        assert!(path.attenuation_db(12.0, 25.0, 60.0) < ku);
+
+        // Finite down to the horizon
+        let horizon = path.attenuation_db(30.0, 25.0, 0.0);
+        assert!(horizon.is_finite() && horizon > path.attenuation_db(30.0, 25.0, 10.0));
+    }
+
+    #[test]
+    fn test_rain_height() {
+        assert_eq!(rain_height_km(0.0), 5.0);
+        assert_relative_eq!(rain_height_km(43.0), 3.5);
+        assert_relative_eq!(rain_height_km(-41.0), 3.0);
+        assert_eq!(rain_height_km(-80.0), 0.0);
+        assert_eq!(rain_height_km(90.0), 0.0);
+    }
+}
@@ -0,0 +1,247 @@
+//! Simulation Result Cache Module
+//!
+//! Mission planning and parameter sweeps simulate the same configurations
+//! over and over: every planner run scores each band under the same
+//! reference environments, and overlapping sweeps share most of their
+//! points. `SimulationCache` memoizes `FrequencyBand::simulate_transmission_with`
+//! keyed by everything its result depends on — `MODEL_VERSION`, the band
+//! definition, the margin policy, the transmission parameters and the
+//! environment, compared bit for bit. A result is therefore never reused
+//! once the band definition changes, or once the link model changes and
+//! `MODEL_VERSION` is bumped.
+//!
+//! The cache holds at most its capacity of results and evicts the least
+//! recently used first. It is optional: `ParameterSweep::with_cache` and
+//! `BandScorer::with_cache` share one across runs, and without one every
+//! run is simulated. It may be shared between threads, including by the
+//! `parallel` sweep. Inputs failing validation are never cached.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (memoized repeat simulations)
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::margin::MarginPolicy;
+use crate::validation::ValidationError;
+use crate::{
+    BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult,
+    MODEL_VERSION,
+};
+
+/// Results held by `SimulationCache::default()`.
+pub const DEFAULT_CACHE_CAPACITY: usize = 4096;
+
+/// Everything a simulation result depends on; floating point values by bit
+/// pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    model_version: u32,
+    band: BandType,
+    values: [u64; 27],
+    available_power_watts: Option<u64>,
+}
+
+impl CacheKey {
+    fn new(
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Self {
+        let values = [
+            band.frequency_range.min_ghz,
+            band.frequency_range.max_ghz,
+            band.characteristics.max_data_rate_mbps,
+            band.characteristics.power_efficiency,
+            band.characteristics.antenna_gain_dbi,
+            band.characteristics.noise_temperature_k,
+            band.limitations.rain_fade_susceptibility,
+            band.limitations.weather_dependence,
+            band.limitations.pointing_accuracy_required,
+            margins.closure_snr_db,
+            margins.link_margin_db,
+            margins.power_margin,
+            params.distance_km,
+            params.data_size_mb,
+            params.required_data_rate_mbps,
+            params.elevation_angle_degrees,
+            params.transmit_power_watts,
+            params.antenna_diameter_meters,
+            params.range_rate_m_s,
+            params.range_acceleration_m_s2,
+            environment.rain_rate_mm_hour,
+            environment.cloud_cover_percent,
+            environment.atmospheric_pressure_mb,
+            environment.temperature_celsius,
+            environment.humidity_percent,
+            environment.ionospheric_activity,
+            environment.solar_activity,
+        ];
+        Self {
+            model_version: MODEL_VERSION,
+            band: band.name,
+            values: values.map(f64::to_bits),
+            available_power_watts: margins.available_power_watts.map(f64::to_bits),
+        }
+    }
+}
+
+/// Cache hit and eviction counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Simulations answered from the cache.
+    pub hits: u64,
+    /// Simulations run because no result was cached.
+    pub misses: u64,
+    /// Results dropped to stay within capacity.
+    pub evictions: u64,
+    /// Results held now.
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Fraction of simulations answered from the cache, 0-1.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Result and last-use tick by key.
+    entries: HashMap<CacheKey, (TransmissionResult, u64)>,
+    /// Keys by last-use tick, least recent first.
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl CacheState {
+    /// Cached result of `key`, marked most recently used.
+    fn hit(&mut self, key: &CacheKey) -> Option<TransmissionResult> {
+        let (result, used) = self.entries.get_mut(key)?;
+        self.tick += 1;
+        self.recency.remove(used);
+        *used = self.tick;
+        self.recency.insert(self.tick, key.clone());
+        self.stats.hits += 1;
+        Some(result.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, result: TransmissionResult, capacity: usize) {
+        if capacity == 0 || self.entries.contains_key(&key) {
+            return;
+        }
+        while self.entries.len() >= capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (result, self.tick));
+    }
+}
+
+/// Bounded, least-recently-used cache of simulation results.
+#[derive(Debug)]
+pub struct SimulationCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl Default for SimulationCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl SimulationCache {
+    /// Cache holding at most `capacity` results; 0 caches nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Most results held at once.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Simulate `band` as `FrequencyBand::simulate_transmission_with`,
+    /// reusing the cached result of an identical configuration.
+    ///
+    /// - **ID**: FN-CACHE-001
+    /// - **Requirement**: Repeated configurations are not simulated again
+    ///   unless the band definition or link model changed (REQ-FN-008).
+    /// - **Outputs**: The same result an uncached simulation returns.
+    /// - **Side Effects**: Caches the result, evicting the least recently
+    ///   used one when full; updates the statistics.
+    /// - **Failure Modes**: `ValidationError` for out-of-range inputs, which
+    ///   are not cached.
+    pub fn simulate(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<TransmissionResult, ValidationError> {
+        let key = CacheKey::new(band, params, environment, margins);
+        if let Some(result) = self.state().hit(&key) {
+            return Ok(result);
+        }
+
+        // Simulated without the lock so threads do not wait on each other
+        let result = band.simulate_transmission_with(params, environment, margins)?;
+        let mut state = self.state();
+        state.stats.misses += 1;
+        state.insert(key, result.clone(), self.capacity);
+        Ok(result)
+    }
+
+    /// Hits, misses and evictions since creation or the last `clear`.
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state();
+        CacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    /// Drop every cached result and reset the statistics.
+    pub fn clear(&self) {
+        *self.state() = CacheState::default();
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        // No caller code runs under the lock, so a poisoned lock still
+        // guards consistent maps
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Simulate through `cache` when there is one.
+pub(crate) fn simulate(
+    cache: Option<&SimulationCache>,
+    band: &FrequencyBand,
+    params: &TransmissionParameters,
+    environment: &EnvironmentalConditions,
+    margins: &MarginPolicy,
+) -> Result<TransmissionResult, ValidationError> {
+    match cache {
+        Some(cache) => cache.simulate(band, params, environment, margins),
+        None => band.simulate_transmission_with(params, environment, margins),
+    }
+}
@@ -0,0 +1,470 @@
+//! Downlink Capacity Sizing Module
+//!
+//! Queueing analysis of the onboard recorder against a contact schedule:
+//! given data arrivals (see `traffic`) and the passes available for downlink,
+//! it simulates the recorder queue and reports
+//!
+//! - backlog at the end of each contact and its long-run growth rate
+//! - the queueing delay distribution (arrival to end of downlink)
+//! - per-band contact time, capacity, and the rate each band would need to
+//!   carry the whole offered load on its own
+//!
+//! Products are served in priority order (alerts, then housekeeping, then
+//! science), first-come first-served within a class.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-002: Data Transfer Rates (downlink sizing)
+//! - REQ-FN-001: Priority Classification (priority-ordered downlink)
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{Catalog, Localize};
+use crate::traffic::{total_volume_mb, DataArrival, DataClass};
+use crate::{BandType, FrequencyBand};
+
+/// A scheduled downlink contact.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ContactWindow {
+    /// Start of the contact, seconds from the start of the run.
+    pub start_s: f64,
+    /// End of the contact, seconds from the start of the run.
+    pub end_s: f64,
+    /// Band used for the contact.
+    pub band: BandType,
+}
+
+impl ContactWindow {
+    /// Contact duration, seconds.
+    pub fn duration_s(&self) -> f64 {
+        (self.end_s - self.start_s).max(0.0)
+    }
+}
+
+/// Queueing delay distribution, seconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DelayDistribution {
+    /// Median delay.
+    pub p50_s: f64,
+    /// 90th percentile delay.
+    pub p90_s: f64,
+    /// 99th percentile delay.
+    pub p99_s: f64,
+    /// Largest observed delay.
+    pub max_s: f64,
+    /// Mean delay.
+    pub mean_s: f64,
+}
+
+impl DelayDistribution {
+    /// Summarise a set of delays; all zero when empty.
+    fn from_delays(mut delays: Vec<f64>) -> Self {
+        if delays.is_empty() {
+            return Self::default();
+        }
+        delays.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        // Nearest-rank percentile
+        let percentile = |p: f64| {
+            let rank = (p * delays.len() as f64).ceil().max(1.0) as usize;
+            delays[rank.min(delays.len()) - 1]
+        };
+
+        Self {
+            p50_s: percentile(0.50),
+            p90_s: percentile(0.90),
+            p99_s: percentile(0.99),
+            max_s: delays[delays.len() - 1],
+            mean_s: delays.iter().sum::<f64>() / delays.len() as f64,
+        }
+    }
+}
+
+/// Per-band sizing figures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandCapacity {
+    /// Band.
+    pub band: BandType,
+    /// Configured downlink rate, Mbps.
+    pub rate_mbps: f64,
+    /// Total scheduled contact time on the band, seconds.
+    pub contact_s: f64,
+    /// Volume the band can carry over the schedule, MB.
+    pub capacity_mb: f64,
+    /// Rate needed for this band alone to carry the whole offered load, Mbps.
+    pub required_rate_alone_mbps: f64,
+}
+
+/// Result of a capacity study.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityReport {
+    /// Length of the analysed period, seconds.
+    pub duration_s: f64,
+    /// Volume generated onboard, MB.
+    pub offered_mb: f64,
+    /// Volume downlinked, MB.
+    pub delivered_mb: f64,
+    /// Volume still on the recorder at the end, MB.
+    pub final_backlog_mb: f64,
+    /// Backlog at the end of each contact: (time s, MB).
+    pub backlog_mb: Vec<(f64, f64)>,
+    /// Backlog growth between the first and last contact, MB/day.
+    pub backlog_growth_mb_per_day: f64,
+    /// Delay distribution of delivered products.
+    pub delay: DelayDistribution,
+    /// Delay distribution per data class.
+    pub delay_by_class: Vec<(DataClass, DelayDistribution)>,
+    /// Products not delivered by the end of the period.
+    pub undelivered_products: usize,
+    /// Offered load divided by total schedule capacity.
+    pub utilisation: f64,
+    /// Per-band sizing figures.
+    pub bands: Vec<BandCapacity>,
+}
+
+impl CapacityReport {
+    /// Whether the schedule keeps up with the offered load.
+    pub fn is_sustainable(&self) -> bool {
+        self.utilisation < 1.0 && self.backlog_growth_mb_per_day <= 0.0
+    }
+}
+
+impl fmt::Display for CapacityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for CapacityReport {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        let label = |key| format!("{:<18}:", catalog.text(key));
+        writeln!(f, "{}", catalog.text("capacity.title"))?;
+        writeln!(
+            f,
+            "{} {:.1} h",
+            label("capacity.period"),
+            self.duration_s / 3600.0
+        )?;
+        writeln!(f, "{} {:.1} MB", label("capacity.offered"), self.offered_mb)?;
+        writeln!(
+            f,
+            "{} {:.1} MB",
+            label("capacity.delivered"),
+            self.delivered_mb
+        )?;
+        writeln!(
+            f,
+            "{} {:.1} MB",
+            label("capacity.final_backlog"),
+            self.final_backlog_mb
+        )?;
+        writeln!(
+            f,
+            "{} {:.1} {}",
+            label("capacity.backlog_growth"),
+            self.backlog_growth_mb_per_day,
+            catalog.text("capacity.per_day")
+        )?;
+        writeln!(
+            f,
+            "{} {:.1}%",
+            label("capacity.utilisation"),
+            self.utilisation * 100.0
+        )?;
+        writeln!(
+            f,
+            "{} {}",
+            label("capacity.sustainable"),
+            catalog.text(if self.is_sustainable() {
+                "capacity.yes"
+            } else {
+                "capacity.no"
+            })
+        )?;
+        writeln!(f, "\n{}", catalog.text("capacity.delay_heading"))?;
+        writeln!(
+            f,
+            "{:<14} {:>10} {:>10} {:>10} {:>10}",
+            catalog.text("capacity.class"),
+            "p50",
+            "p90",
+            "p99",
+            "max"
+        )?;
+        writeln!(
+            f,
+            "{:<14} {:>10.0} {:>10.0} {:>10.0} {:>10.0}",
+            catalog.text("capacity.all_classes"),
+            self.delay.p50_s,
+            self.delay.p90_s,
+            self.delay.p99_s,
+            self.delay.max_s
+        )?;
+        for (class, delay) in &self.delay_by_class {
+            writeln!(
+                f,
+                "{:<14} {:>10.0} {:>10.0} {:>10.0} {:>10.0}",
+                format!("{:?}", class),
+                delay.p50_s,
+                delay.p90_s,
+                delay.p99_s,
+                delay.max_s
+            )?;
+        }
+        writeln!(f, "\n{}", catalog.text("capacity.band_heading"))?;
+        writeln!(
+            f,
+            "{:<10} {:>12} {:>12} {:>14} {:>18}",
+            catalog.text("capacity.band"),
+            catalog.text("capacity.rate"),
+            catalog.text("capacity.contact"),
+            catalog.text("capacity.capacity"),
+            catalog.text("capacity.needed_alone")
+        )?;
+        for band in &self.bands {
+            writeln!(
+                f,
+                "{:<10} {:>12.1} {:>12.0} {:>14.1} {:>18.2}",
+                band.band.to_string(),
+                band.rate_mbps,
+                band.contact_s,
+                band.capacity_mb,
+                band.required_rate_alone_mbps
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Product waiting on the recorder.
+#[derive(Debug, Clone, Copy)]
+struct Queued {
+    class: DataClass,
+    arrival_s: f64,
+    remaining_mb: f64,
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    /// Highest class first, then earliest arrival.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.class.cmp(&other.class).then_with(|| {
+            other
+                .arrival_s
+                .partial_cmp(&self.arrival_s)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+/// Downlink capacity study configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityStudy {
+    /// Downlink rate for each band, Mbps.
+    pub rates_mbps: HashMap<BandType, f64>,
+}
+
+impl Default for CapacityStudy {
+    /// Each band at its standard maximum data rate.
+    fn default() -> Self {
+        Self {
+            rates_mbps: FrequencyBand::get_standard_bands()
+                .into_iter()
+                .map(|band| (band.name, band.characteristics.max_data_rate_mbps))
+                .collect(),
+        }
+    }
+}
+
+impl CapacityStudy {
+    /// Downlink rate configured for `band`, Mbps (0 if unset).
+    pub fn rate_mbps(&self, band: BandType) -> f64 {
+        self.rates_mbps.get(&band).copied().unwrap_or(0.0)
+    }
+
+    /// Simulate the recorder queue and produce a sizing report.
+    ///
+    /// - **ID**: FN-CAP-001
+    /// - **Requirement**: Compute backlog growth, queueing delay distribution
+    ///   and required downlink capacity per band (REQ-PF-002).
+    /// - **Inputs**:
+    ///   - `arrivals`: Data products, sorted by time (as from `MissionProfile::generate`).
+    ///   - `contacts`: Downlink contacts; need not be sorted, must not overlap.
+    ///   - `duration_s`: Length of the analysed period, seconds.
+    /// - **Outputs**: `CapacityReport` for the period.
+    /// - **Side Effects**: None.
+    /// - **Constraints**: O((A + C) log A) for A arrivals and C contacts.
+    pub fn analyse(
+        &self,
+        arrivals: &[DataArrival],
+        contacts: &[ContactWindow],
+        duration_s: f64,
+    ) -> CapacityReport {
+        let mut contacts = contacts.to_vec();
+        contacts.sort_by(|a, b| a.start_s.partial_cmp(&b.start_s).unwrap_or(Ordering::Equal));
+
+        let mut queue = BinaryHeap::new();
+        let mut next_arrival = 0;
+        let mut delays: Vec<(DataClass, f64)> = Vec::new();
+        let mut delivered_mb = 0.0;
+        let mut backlog_mb = Vec::with_capacity(contacts.len());
+
+        let enqueue_until = |queue: &mut BinaryHeap<Queued>, t: f64, next: &mut usize| {
+            while let Some(arrival) = arrivals.get(*next).filter(|a| a.time_s <= t) {
+                queue.push(Queued {
+                    class: arrival.class,
+                    arrival_s: arrival.time_s,
+                    remaining_mb: arrival.size_mb,
+                });
+                *next += 1;
+            }
+        };
+
+        for contact in &contacts {
+            let rate_mb_s = self.rate_mbps(contact.band) / 8.0;
+            let mut t = contact.start_s;
+
+            while t < contact.end_s && rate_mb_s > 0.0 {
+                enqueue_until(&mut queue, t, &mut next_arrival);
+                let upcoming = arrivals
+                    .get(next_arrival)
+                    .map_or(f64::INFINITY, |a| a.time_s);
+
+                let Some(mut head) = queue.pop() else {
+                    // Idle until the next arrival or the end of the contact
+                    t = upcoming.min(contact.end_s);
+                    continue;
+                };
+
+                let finish = t + head.remaining_mb / rate_mb_s;
+                let stop = contact.end_s.min(upcoming);
+                if finish <= stop {
+                    delivered_mb += head.remaining_mb;
+                    delays.push((head.class, finish - head.arrival_s));
+                    t = finish;
+                } else {
+                    // Preempted by the end of the contact or a new arrival
+                    let sent = (stop - t) * rate_mb_s;
+                    head.remaining_mb -= sent;
+                    delivered_mb += sent;
+                    queue.push(head);
+                    t = stop;
+                }
+            }
+
+            enqueue_until(&mut queue, contact.end_s, &mut next_arrival);
+            let backlog: f64 = queue.iter().map(|q| q.remaining_mb).sum();
+            backlog_mb.push((contact.end_s, backlog));
+        }
+
+        let offered_mb = total_volume_mb(arrivals);
+        let undelivered_products = queue.len() + arrivals.len() - next_arrival;
+
+        let backlog_growth_mb_per_day = match (backlog_mb.first(), backlog_mb.last()) {
+            (Some(first), Some(last)) if last.0 > first.0 => {
+                (last.1 - first.1) / (last.0 - first.0) * 86_400.0
+            }
+            _ => 0.0,
+        };
+
+        let mut bands: Vec<BandCapacity> = Vec::new();
+        for contact in &contacts {
+            match bands.iter_mut().find(|b| b.band == contact.band) {
+                Some(entry) => entry.contact_s += contact.duration_s(),
+                None => bands.push(BandCapacity {
+                    band: contact.band,
+                    rate_mbps: self.rate_mbps(contact.band),
+                    contact_s: contact.duration_s(),
+                    capacity_mb: 0.0,
+                    required_rate_alone_mbps: 0.0,
+                }),
+            }
+        }
+        for band in &mut bands {
+            band.capacity_mb = band.rate_mbps * band.contact_s / 8.0;
+            band.required_rate_alone_mbps = if band.contact_s > 0.0 {
+                offered_mb * 8.0 / band.contact_s
+            } else {
+                f64::INFINITY
+            };
+        }
+
+        let total_capacity_mb: f64 = bands.iter().map(|b| b.capacity_mb).sum();
+        let utilisation = if total_capacity_mb > 0.0 {
+            offered_mb / total_capacity_mb
+        } else {
+            f64::INFINITY
+        };
+
+        let mut classes: Vec<DataClass> = delays.iter().map(|(class, _)| *class).collect();
+        classes.sort();
+        classes.dedup();
+        let delay_by_class = classes
+            .into_iter()
+            .rev()
+            .map(|class| {
+                let class_delays = delays
+                    .iter()
+                    .filter(|(c, _)| *c == class)
+                    .map(|(_, d)| *d)
+                    .collect();
+                (class, DelayDistribution::from_delays(class_delays))
+            })
+            .collect();
+
+        CapacityReport {
+            duration_s,
+            offered_mb,
+            delivered_mb,
+            final_backlog_mb: (offered_mb - delivered_mb).max(0.0),
+            backlog_mb,
+            backlog_growth_mb_per_day,
+            delay: DelayDistribution::from_delays(delays.into_iter().map(|(_, d)| d).collect()),
+            delay_by_class,
+            undelivered_products,
+            utilisation,
+            bands,
+        }
+    }
+}
+
+/// Regular contact schedule: `contacts_per_day` evenly spaced passes of
+/// `contact_s` seconds on `band` over `duration_s`.
+pub fn regular_contacts(
+    duration_s: f64,
+    contacts_per_day: u32,
+    contact_s: f64,
+    band: BandType,
+) -> Vec<ContactWindow> {
+    if contacts_per_day == 0 {
+        return Vec::new();
+    }
+    let spacing = 86_400.0 / f64::from(contacts_per_day);
+    let mut contacts = Vec::new();
+    let mut start = spacing / 2.0;
+    while start + contact_s <= duration_s {
+        contacts.push(ContactWindow {
+            start_s: start,
+            end_s: start + contact_s,
+            band,
+        });
+        start += spacing;
+    }
+    contacts
+}
@@ -0,0 +1,361 @@
+//! Deep Space Link Module
+//!
+//! Beyond the Moon the link model of `FrequencyBand` stops being useful: it
+//! assumes LEO-scale ranges, a fixed ground antenna gain and interactive
+//! protocols. This module models the link the way deep-space missions are
+//! actually budgeted:
+//!
+//! - **Light time**: one-way delays of seconds at the Moon and minutes to
+//!   hours beyond it. Once the round trip exceeds
+//!   [`MAX_INTERACTIVE_ROUND_TRIP_S`] protocols stop waiting for replies
+//!   ([`ProtocolMode::TimerBased`]): commands go out open loop and each reply
+//!   is expected in a window computed from the light time
+//!   ([`ResponseExpectation`]).
+//! - **Ground antennas**: DSN-style 34 m beam waveguide and 70 m presets
+//!   ([`DsnAntenna`]), with gain from aperture and frequency and the system
+//!   noise temperature of a cryogenic receiver.
+//! - **Data rate**: the highest rate that keeps the required Eb/N0 plus
+//!   margin at the received Pr/N0, typically kbps to a few Mbps.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (S, X and Ka deep-space allocations)
+//! - REQ-FN-008: Frequency Band Simulation (deep-space ranges and light time)
+//! - REQ-PF-002: Data Transfer Rates (rates supported at deep-space range)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{Catalog, Localize};
+use crate::BandType;
+
+/// Speed of light in vacuum, km/s.
+const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+
+/// Boltzmann's constant, dBW/K/Hz.
+const BOLTZMANN_DBW_K_HZ: f64 = -228.6;
+
+/// Range at which ITU-R defines deep space to begin, km.
+pub const DEEP_SPACE_THRESHOLD_KM: f64 = 2.0e6;
+
+/// Longest round-trip light time at which protocols still wait for replies,
+/// seconds.
+pub const MAX_INTERACTIVE_ROUND_TRIP_S: f64 = 10.0;
+
+/// Shortest window a reply is accepted in after it falls due, seconds.
+pub const MIN_RESPONSE_WINDOW_S: f64 = 5.0;
+
+/// One-way light time over `distance_km`, seconds.
+pub fn light_time_s(distance_km: f64) -> f64 {
+    distance_km / SPEED_OF_LIGHT_KM_S
+}
+
+/// Round-trip light time over `distance_km`, seconds.
+pub fn round_trip_light_time_s(distance_km: f64) -> f64 {
+    2.0 * light_time_s(distance_km)
+}
+
+/// Typical mission targets and their Earth ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeepSpaceTarget {
+    /// Lunar orbit, mean Earth-Moon distance.
+    Moon,
+    /// Sun-Earth L2 halo orbit.
+    SunEarthL2,
+    /// Mars at mean distance from Earth.
+    Mars,
+    /// Jupiter at mean distance from Earth.
+    Jupiter,
+}
+
+impl DeepSpaceTarget {
+    /// Every target, nearest first.
+    pub const ALL: [DeepSpaceTarget; 4] = [
+        DeepSpaceTarget::Moon,
+        DeepSpaceTarget::SunEarthL2,
+        DeepSpaceTarget::Mars,
+        DeepSpaceTarget::Jupiter,
+    ];
+
+    /// Earth range, km.
+    pub fn distance_km(self) -> f64 {
+        match self {
+            DeepSpaceTarget::Moon => 384_400.0,
+            DeepSpaceTarget::SunEarthL2 => 1.5e6,
+            DeepSpaceTarget::Mars => 2.25e8,
+            DeepSpaceTarget::Jupiter => 7.8e8,
+        }
+    }
+}
+
+impl fmt::Display for DeepSpaceTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeepSpaceTarget::Moon => "Moon",
+            DeepSpaceTarget::SunEarthL2 => "Sun-Earth L2",
+            DeepSpaceTarget::Mars => "Mars",
+            DeepSpaceTarget::Jupiter => "Jupiter",
+        })
+    }
+}
+
+/// How protocols cope with the round-trip light time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProtocolMode {
+    /// Each exchange waits for its reply before the next is sent.
+    Interactive,
+    /// No handshakes: commands and files go out open loop and each reply is
+    /// expected in a window derived from the light time.
+    TimerBased,
+}
+
+impl ProtocolMode {
+    /// Mode for a link with the given round-trip light time.
+    pub fn for_round_trip(round_trip_s: f64) -> Self {
+        if round_trip_s > MAX_INTERACTIVE_ROUND_TRIP_S {
+            ProtocolMode::TimerBased
+        } else {
+            ProtocolMode::Interactive
+        }
+    }
+
+    /// Time to send `commands` commands taking `command_s` each, until the
+    /// last reply is back, seconds.
+    ///
+    /// Interactive exchanges pay the round trip per command; timer-based
+    /// operation pays it once.
+    pub fn session_time_s(self, commands: usize, command_s: f64, round_trip_s: f64) -> f64 {
+        let n = commands as f64;
+        match self {
+            ProtocolMode::Interactive => n * (command_s + round_trip_s),
+            ProtocolMode::TimerBased if commands == 0 => 0.0,
+            ProtocolMode::TimerBased => n * command_s + round_trip_s,
+        }
+    }
+}
+
+impl fmt::Display for ProtocolMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for ProtocolMode {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(catalog.text(match self {
+            ProtocolMode::Interactive => "protocol.interactive",
+            ProtocolMode::TimerBased => "protocol.timer_based",
+        }))
+    }
+}
+
+/// State of an expected reply at a given time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExpectationStatus {
+    /// Not due yet: the command or the reply is still in flight.
+    InFlight,
+    /// Inside its acceptance window.
+    Due,
+    /// Window closed without the reply; only now is it reported missing.
+    Overdue,
+}
+
+/// When the reply to an open-loop command is expected.
+///
+/// Timer-based protocols never time out a command on a fixed interval; the
+/// reply window opens one round trip plus the onboard turnaround after the
+/// command is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResponseExpectation {
+    /// Time the command left the ground, seconds.
+    pub sent_s: f64,
+    /// Earliest time the reply can arrive, seconds.
+    pub due_s: f64,
+    /// Time after which the reply is overdue, seconds.
+    pub deadline_s: f64,
+}
+
+impl ResponseExpectation {
+    /// Expectation for a command sent at `sent_s` over a link with the
+    /// given round trip, executed in `turnaround_s` onboard.
+    ///
+    /// - **ID**: FN-DSL-001
+    /// - **Requirement**: Replies over deep-space links are expected from the
+    ///   light time rather than a fixed timeout (REQ-FN-008).
+    /// - **Inputs**: `tolerance` is the fraction of the round trip added to
+    ///   the window for range uncertainty and ground processing; the window
+    ///   is at least `MIN_RESPONSE_WINDOW_S`.
+    /// - **Outputs**: The reply window.
+    /// - **Side Effects**: None.
+    pub fn new(sent_s: f64, round_trip_s: f64, turnaround_s: f64, tolerance: f64) -> Self {
+        let due_s = sent_s + round_trip_s + turnaround_s;
+        Self {
+            sent_s,
+            due_s,
+            deadline_s: due_s + (round_trip_s * tolerance).max(MIN_RESPONSE_WINDOW_S),
+        }
+    }
+
+    /// State of the reply at `now_s`.
+    pub fn status(&self, now_s: f64) -> ExpectationStatus {
+        if now_s < self.due_s {
+            ExpectationStatus::InFlight
+        } else if now_s <= self.deadline_s {
+            ExpectationStatus::Due
+        } else {
+            ExpectationStatus::Overdue
+        }
+    }
+}
+
+/// DSN-style ground antenna presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DsnAntenna {
+    /// 34 m beam waveguide antenna: S, X and Ka band.
+    Dsn34m,
+    /// 70 m antenna: S and X band, the most sensitive aperture.
+    Dsn70m,
+}
+
+impl DsnAntenna {
+    /// Both presets, smallest first.
+    pub const ALL: [DsnAntenna; 2] = [DsnAntenna::Dsn34m, DsnAntenna::Dsn70m];
+
+    /// Dish diameter, m.
+    pub fn diameter_m(self) -> f64 {
+        match self {
+            DsnAntenna::Dsn34m => 34.0,
+            DsnAntenna::Dsn70m => 70.0,
+        }
+    }
+
+    /// Aperture efficiency and zenith system noise temperature (K) on
+    /// `band`, or `None` if the antenna has no receiver for it.
+    fn receiver(self, band: BandType) -> Option<(f64, f64)> {
+        match (self, band) {
+            (DsnAntenna::Dsn34m, BandType::SBand) => Some((0.68, 38.0)),
+            (DsnAntenna::Dsn34m, BandType::XBand) => Some((0.72, 33.0)),
+            (DsnAntenna::Dsn34m, BandType::KaBand) => Some((0.55, 45.0)),
+            (DsnAntenna::Dsn70m, BandType::SBand) => Some((0.70, 22.0)),
+            (DsnAntenna::Dsn70m, BandType::XBand) => Some((0.70, 20.0)),
+            _ => None,
+        }
+    }
+
+    /// Receive gain on `band`, dBi.
+    pub fn gain_dbi(self, band: BandType) -> Option<f64> {
+        let (efficiency, _) = self.receiver(band)?;
+        let wavelength_km = SPEED_OF_LIGHT_KM_S / (deep_space_frequency_ghz(band)? * 1e9);
+        let aperture = std::f64::consts::PI * self.diameter_m() / (wavelength_km * 1000.0);
+        Some(10.0 * (efficiency * aperture * aperture).log10())
+    }
+
+    /// Receiver figure of merit on `band`, dB/K.
+    pub fn g_over_t_db_k(self, band: BandType) -> Option<f64> {
+        let (_, noise_k) = self.receiver(band)?;
+        Some(self.gain_dbi(band)? - 10.0 * noise_k.log10())
+    }
+}
+
+impl fmt::Display for DsnAntenna {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DsnAntenna::Dsn34m => "34 m",
+            DsnAntenna::Dsn70m => "70 m",
+        })
+    }
+}
+
+/// Space-to-Earth deep-space allocation of a band, GHz, or `None` for bands
+/// without one.
+pub fn deep_space_frequency_ghz(band: BandType) -> Option<f64> {
+    match band {
+        BandType::SBand => Some(2.295),
+        BandType::XBand => Some(8.425),
+        BandType::KaBand => Some(32.05),
+        _ => None,
+    }
+}
+
+/// Spacecraft-to-DSN downlink.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DeepSpaceLink {
+    /// Downlink band.
+    pub band: BandType,
+    /// Spacecraft transmitter output, W.
+    pub transmit_power_watts: f64,
+    /// Spacecraft high-gain antenna gain, dBi.
+    pub spacecraft_gain_dbi: f64,
+    /// Receiving ground antenna.
+    pub ground: DsnAntenna,
+    /// Pointing, polarization and implementation losses, dB.
+    pub losses_db: f64,
+    /// Eb/N0 the decoder needs at the target frame error rate, dB.
+    pub required_eb_n0_db: f64,
+    /// Margin held above the required Eb/N0, dB.
+    pub margin_db: f64,
+}
+
+impl Default for DeepSpaceLink {
+    /// X-band downlink from a 20 W transmitter through a 3 m high-gain
+    /// antenna to a 34 m station, turbo coded (Eb/N0 1 dB) with 3 dB margin.
+    fn default() -> Self {
+        Self {
+            band: BandType::XBand,
+            transmit_power_watts: 20.0,
+            spacecraft_gain_dbi: 46.0,
+            ground: DsnAntenna::Dsn34m,
+            losses_db: 2.0,
+            required_eb_n0_db: 1.0,
+            margin_db: 3.0,
+        }
+    }
+}
+
+/// Budget of a deep-space downlink at one range.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DeepSpaceBudget {
+    /// One-way light time, seconds.
+    pub light_time_s: f64,
+    /// Free-space path loss, dB.
+    pub path_loss_db: f64,
+    /// Received power to noise density, dB-Hz.
+    pub pr_n0_dbhz: f64,
+    /// Highest data rate keeping the required Eb/N0 plus margin, bps.
+    pub max_data_rate_bps: f64,
+    /// How protocols must run over the link.
+    pub protocol: ProtocolMode,
+}
+
+impl DeepSpaceLink {
+    /// Budget the downlink at `distance_km`.
+    ///
+    /// - **ID**: FN-DSL-002
+    /// - **Requirement**: Deep-space data rates follow from the received
+    ///   Pr/N0 and the required Eb/N0 (REQ-PF-002).
+    /// - **Inputs**: Range, km.
+    /// - **Outputs**: The budget, or `None` if the band has no deep-space
+    ///   allocation or the ground antenna has no receiver for it.
+    /// - **Side Effects**: None.
+    /// - **Verification**: Unit test `test_mars_downlink_rates`.
+    pub fn evaluate(&self, distance_km: f64) -> Option<DeepSpaceBudget> {
+        let frequency_ghz = deep_space_frequency_ghz(self.band)?;
+        let g_over_t = self.ground.g_over_t_db_k(self.band)?;
+
+        let wavelength_km = SPEED_OF_LIGHT_KM_S / (frequency_ghz * 1e9);
+        let path_loss_db =
+            20.0 * (4.0 * std::f64::consts::PI * distance_km / wavelength_km).log10();
+        let eirp_dbw = 10.0 * self.transmit_power_watts.log10() + self.spacecraft_gain_dbi;
+        let pr_n0_dbhz = eirp_dbw - path_loss_db - self.losses_db + g_over_t - BOLTZMANN_DBW_K_HZ;
+        let rate_db_hz = pr_n0_dbhz - self.required_eb_n0_db - self.margin_db;
+
+        Some(DeepSpaceBudget {
+            light_time_s: light_time_s(distance_km),
+            path_loss_db,
+            pr_n0_dbhz,
+            max_data_rate_bps: 10f64.powf(rate_db_hz / 10.0),
+            protocol: ProtocolMode::for_round_trip(round_trip_light_time_s(distance_km)),
+        })
+    }
+}
+
@@ -0,0 +1,210 @@
+//! Antenna Deployment Dynamics Module
+//!
+//! Models the high-gain antenna deployment sequence commanded by the `Deploy`
+//! space command and its effect on the link budget during early orbit:
+//!
+//! - Before deployment every band is limited to the low-gain antenna path.
+//! - Deployment takes the commanded time (`deployment_angle / deployment_rate`).
+//! - On completion the mechanism may fully deploy, partially deploy (reduced
+//!   gain) or fail outright, with configurable probabilities.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: Deployable mechanism control (`Deploy` command)
+//! - REQ-FN-008: Frequency Band Simulation (link budget under deployment state)
+//! - REQ-SF-001: Deployment validation and fault outcomes
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::margin::MarginPolicy;
+use crate::validation::ValidationError;
+use crate::{EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult};
+
+/// Deployment state of the high-gain antenna.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeploymentState {
+    /// Stowed for launch; only the low-gain path is available.
+    Stowed,
+    /// Deployment in progress; gain stays at the low-gain path until complete.
+    Deploying {
+        /// Simulation time the `Deploy` command was executed, seconds.
+        started_s: f64,
+        /// Commanded deployment duration, seconds.
+        duration_s: f64,
+    },
+    /// Fully deployed and latched.
+    Deployed,
+    /// Mechanism stalled part way; reflector only partly usable.
+    PartiallyDeployed {
+        /// Fraction of the high-gain improvement realised (0.0–1.0).
+        fraction: f64,
+    },
+    /// Mechanism failed; the spacecraft remains on the low-gain path.
+    Failed,
+}
+
+/// Configuration of the deployment fault model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentConfig {
+    /// Gain of the low-gain (omni/patch) antenna path in dBi.
+    pub low_gain_antenna_dbi: f64,
+    /// Probability that deployment fails completely (0.0–1.0).
+    pub failure_probability: f64,
+    /// Probability that deployment stalls part way (0.0–1.0).
+    pub partial_failure_probability: f64,
+    /// Smallest fraction of gain improvement realised on a partial deployment.
+    pub min_partial_fraction: f64,
+}
+
+impl Default for DeploymentConfig {
+    fn default() -> Self {
+        Self {
+            low_gain_antenna_dbi: 2.0,
+            failure_probability: 0.02,
+            partial_failure_probability: 0.05,
+            min_partial_fraction: 0.3,
+        }
+    }
+}
+
+/// Simulated high-gain antenna deployment mechanism.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntennaDeployment {
+    /// Fault model configuration.
+    pub config: DeploymentConfig,
+    /// Current deployment state.
+    pub state: DeploymentState,
+}
+
+impl AntennaDeployment {
+    /// Create a stowed antenna with the given fault model.
+    pub fn new(config: DeploymentConfig) -> Self {
+        Self {
+            config,
+            state: DeploymentState::Stowed,
+        }
+    }
+
+    /// Execute a `Deploy` command at simulation time `now_s`.
+    ///
+    /// - **ID**: FN-DEP-001
+    /// - **Requirement**: Deployment takes the commanded time (REQ-FN-004).
+    /// - **Inputs**:
+    ///   - `now_s`: Simulation time of command execution, seconds.
+    ///   - `deployment_angle_deg`: Commanded hinge angle, degrees.
+    ///   - `deployment_rate_deg_s`: Commanded hinge rate, degrees/second.
+    /// - **Outputs**: `true` if deployment started; `false` if the antenna was
+    ///   not stowed or the rate is not positive.
+    /// - **Postconditions**: On `true`, state is `Deploying` with
+    ///   `duration_s = deployment_angle_deg / deployment_rate_deg_s`.
+    /// - **Side Effects**: None beyond the state change.
+    pub fn command_deploy(
+        &mut self,
+        now_s: f64,
+        deployment_angle_deg: f64,
+        deployment_rate_deg_s: f64,
+    ) -> bool {
+        if self.state != DeploymentState::Stowed || deployment_rate_deg_s <= 0.0 {
+            return false;
+        }
+
+        self.state = DeploymentState::Deploying {
+            started_s: now_s,
+            duration_s: deployment_angle_deg.abs() / deployment_rate_deg_s,
+        };
+        true
+    }
+
+    /// Advance the mechanism to simulation time `now_s`.
+    ///
+    /// - **ID**: FN-DEP-002
+    /// - **Requirement**: Deployment may partially fail with configurable
+    ///   probability (REQ-SF-001).
+    /// - **Inputs**:
+    ///   - `now_s`: Current simulation time, seconds.
+    ///   - `rng`: Random source used to draw the deployment outcome once the
+    ///     commanded duration has elapsed.
+    /// - **Outputs**: The state after the update.
+    /// - **Postconditions**: Once complete, state is `Deployed`,
+    ///   `PartiallyDeployed` or `Failed` and no longer changes.
+    /// - **Side Effects**: Consumes random numbers only on completion.
+    pub fn advance<R: Rng + ?Sized>(&mut self, now_s: f64, rng: &mut R) -> DeploymentState {
+        if let DeploymentState::Deploying {
+            started_s,
+            duration_s,
+        } = self.state
+        {
+            if now_s >= started_s + duration_s {
+                let draw: f64 = rng.gen();
+                let failure = self.config.failure_probability.clamp(0.0, 1.0);
+                let partial = self.config.partial_failure_probability.clamp(0.0, 1.0);
+
+                self.state = if draw < failure {
+                    DeploymentState::Failed
+                } else if draw < failure + partial {
+                    let min = self.config.min_partial_fraction.clamp(0.0, 1.0);
+                    DeploymentState::PartiallyDeployed {
+                        fraction: min + (1.0 - min) * rng.gen::<f64>(),
+                    }
+                } else {
+                    DeploymentState::Deployed
+                };
+            }
+        }
+
+        self.state
+    }
+
+    /// Fraction of the high-gain improvement currently available (0.0–1.0).
+    pub fn gain_fraction(&self) -> f64 {
+        match self.state {
+            DeploymentState::Deployed => 1.0,
+            DeploymentState::PartiallyDeployed { fraction } => fraction.clamp(0.0, 1.0),
+            DeploymentState::Stowed
+            | DeploymentState::Deploying { .. }
+            | DeploymentState::Failed => 0.0,
+        }
+    }
+
+    /// Antenna gain available on `band` in the current state, dBi.
+    ///
+    /// Interpolates (in dB) between the low-gain path and the band's nominal
+    /// high-gain antenna. A band whose nominal gain is below the low-gain
+    /// path is never improved by the low-gain antenna.
+    pub fn effective_gain_dbi(&self, band: &FrequencyBand) -> f64 {
+        let high_gain = band.characteristics.antenna_gain_dbi;
+        let low_gain = self.config.low_gain_antenna_dbi.min(high_gain);
+        low_gain + (high_gain - low_gain) * self.gain_fraction()
+    }
+
+    /// Simulate a transmission on `band` with the gain the antenna provides now.
+    ///
+    /// - **ID**: FN-DEP-003
+    /// - **Requirement**: Link budgets reflect the deployment state (REQ-FN-008).
+    /// - **Outputs**: `TransmissionResult` from `FrequencyBand::simulate_transmission`
+    ///   using `effective_gain_dbi` in place of the nominal antenna gain.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: `ValidationError` listing every out-of-range input.
+    pub fn simulate_transmission(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+    ) -> Result<TransmissionResult, ValidationError> {
+        self.simulate_transmission_with(band, params, environment, &MarginPolicy::default())
+    }
+
+    /// Simulate a transmission as `simulate_transmission`, declaring success
+    /// only if `margins` are met.
+    pub fn simulate_transmission_with(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<TransmissionResult, ValidationError> {
+        let mut limited = band.clone();
+        limited.characteristics.antenna_gain_dbi = self.effective_gain_dbi(band);
+        limited.simulate_transmission_with(params, environment, margins)
+    }
+}
@@ -0,0 +1,147 @@
+//! Doppler Shift and Carrier Tracking Module
+//!
+//! A LEO satellite closes on the ground station at up to 7 km/s as it rises
+//! and recedes as fast as it sets. The carrier arrives shifted by
+//! f_d = −f_c·ṙ/c, tens of kHz at S-band and hundreds at X-band and above,
+//! sweeping through zero at culmination at a rate f_c·r̈/c that peaks there.
+//!
+//! The ground receiver's carrier loop has to acquire and follow that. A
+//! [`CarrierLoop`] is a second-order phase-locked loop with a frequency
+//! search range for acquisition:
+//!
+//! - **Acquisition**: a shift outside the search range is never acquired,
+//!   and the transmission fails whatever the SNR.
+//! - **Tracking**: the loop follows a constant shift with no phase error,
+//!   but lags a Doppler rate by a steady-state phase error
+//!   θ = 2π·ḟ/ω_n². Coherent demodulation recovers cos θ of the signal
+//!   amplitude, a loss of −20·log10(cos θ) dB off the SNR. Past
+//!   [`MAX_PHASE_ERROR_RAD`] cycle slips make the loop lose lock.
+//!
+//! [`CarrierLoop::typical`] gives each band's loop, wider at the higher
+//! bands whose Doppler is larger. `FrequencyBand::simulate_transmission`
+//! takes the range rate and range acceleration from the transmission
+//! parameters; `FrequencyBand::simulate_pass` derives them from the pass
+//! geometry. A stationary satellite, range rate and acceleration zero, gives
+//! the same result as a link without Doppler.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (Doppler shift and rate of LEO
+//!   passes, carrier tracking loss and loss of lock)
+
+use serde::{Deserialize, Serialize};
+
+use crate::BandType;
+
+/// Speed of light, m/s.
+pub const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
+/// Steady-state phase error beyond which the loop is taken as out of lock,
+/// rad. Noise makes a second-order loop slip cycles well before the π/2
+/// limit of its phase detector.
+pub const MAX_PHASE_ERROR_RAD: f64 = 1.0;
+
+/// Ratio of loop noise bandwidth to natural frequency, Hz per rad/s, of a
+/// second-order loop with damping 1/√2.
+const NOISE_BANDWIDTH_PER_NATURAL_FREQUENCY: f64 = 0.53;
+
+/// Doppler shift of a carrier at `carrier_hz` for a range rate, Hz.
+///
+/// A closing satellite (negative range rate) raises the received frequency.
+pub fn doppler_shift_hz(carrier_hz: f64, range_rate_m_s: f64) -> f64 {
+    -carrier_hz * range_rate_m_s / SPEED_OF_LIGHT_M_S
+}
+
+/// Rate of change of the Doppler shift for a range acceleration, Hz/s.
+pub fn doppler_rate_hz_s(carrier_hz: f64, range_acceleration_m_s2: f64) -> f64 {
+    -carrier_hz * range_acceleration_m_s2 / SPEED_OF_LIGHT_M_S
+}
+
+/// Ground receiver carrier loop of one band.
+///
+/// - **ID**: MOD-DOP-001
+/// - **Requirement**: The receiver acquires the carrier within its search
+///   range and tracks its Doppler rate with a steady-state phase error.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CarrierLoop {
+    /// One-sided loop noise bandwidth, Hz.
+    pub loop_bandwidth_hz: f64,
+    /// Largest frequency offset either side of nominal the receiver
+    /// searches on acquisition, Hz.
+    pub acquisition_range_hz: f64,
+}
+
+impl CarrierLoop {
+    /// Typical carrier loop of a ground receiver in `band`.
+    pub fn typical(band: BandType) -> Self {
+        let (loop_bandwidth_hz, acquisition_range_hz) = match band {
+            BandType::UHFBand => (50.0, 50e3),
+            BandType::SBand => (100.0, 100e3),
+            BandType::XBand => (300.0, 300e3),
+            BandType::KBand => (1_000.0, 600e3),
+            BandType::KaBand => (2_000.0, 1e6),
+        };
+        Self {
+            loop_bandwidth_hz,
+            acquisition_range_hz,
+        }
+    }
+
+    /// Acquire and track a carrier at `carrier_hz` with the given range
+    /// rate and acceleration.
+    ///
+    /// - **ID**: FN-DOP-001
+    /// - **Requirement**: Doppler shift and rate of the relative motion, the
+    ///   loss of coherent demodulation to the loop's phase error, and loss
+    ///   of lock beyond the search range or the phase error limit
+    ///   (REQ-FN-008).
+    /// - **Inputs**: Carrier frequency, Hz; range rate, m/s, positive
+    ///   receding; range acceleration, m/s².
+    /// - **Outputs**: `DopplerTracking`; the tracking loss is zero when the
+    ///   carrier is not locked, as no demodulation takes place.
+    /// - **Side Effects**: None.
+    pub fn track(
+        &self,
+        carrier_hz: f64,
+        range_rate_m_s: f64,
+        range_acceleration_m_s2: f64,
+    ) -> DopplerTracking {
+        let shift_hz = doppler_shift_hz(carrier_hz, range_rate_m_s);
+        let rate_hz_s = doppler_rate_hz_s(carrier_hz, range_acceleration_m_s2);
+
+        let natural_frequency_rad_s =
+            self.loop_bandwidth_hz / NOISE_BANDWIDTH_PER_NATURAL_FREQUENCY;
+        let phase_error_rad =
+            (2.0 * std::f64::consts::PI * rate_hz_s).abs() / natural_frequency_rad_s.powi(2);
+
+        let locked =
+            shift_hz.abs() <= self.acquisition_range_hz && phase_error_rad <= MAX_PHASE_ERROR_RAD;
+        let tracking_loss_db = if locked && phase_error_rad > 0.0 {
+            -20.0 * phase_error_rad.cos().log10()
+        } else {
+            0.0
+        };
+
+        DopplerTracking {
+            shift_hz,
+            rate_hz_s,
+            phase_error_rad,
+            tracking_loss_db,
+            locked,
+        }
+    }
+}
+
+/// Doppler seen by a carrier loop.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DopplerTracking {
+    /// Doppler shift of the received carrier, Hz.
+    pub shift_hz: f64,
+    /// Rate of change of the Doppler shift, Hz/s.
+    pub rate_hz_s: f64,
+    /// Steady-state phase error of the loop, rad.
+    pub phase_error_rad: f64,
+    /// SNR lost to the phase error, dB.
+    pub tracking_loss_db: f64,
+    /// Whether the carrier is acquired and held.
+    pub locked: bool,
+}
@@ -0,0 +1,260 @@
+//! Fast Math Module
+//!
+//! Large Monte Carlo studies spend most of their time in the link model's
+//! `log10` and `powf` calls. This module provides cheaper approximations of
+//! the functions the model uses, with scalar and slice forms, and the
+//! `MathPath` switch choosing between them and the standard library.
+//!
+//! Accuracy, for finite arguments whose results are normal:
+//! - `log2`, `log10`: absolute error below 1e-10 (below 1e-9 dB in any
+//!   `10 * log10` level)
+//! - `exp2`, `pow10`: relative error below 1e-9 for `pow10` arguments of
+//!   magnitude up to 300
+//!
+//! Zero, negative, subnormal, infinite and NaN arguments, and results out
+//! of the normal range, fall back to the standard library, so special
+//! values behave exactly as with `MathPath::Precise`. The link model's SNR
+//! sums three logarithmic terms, so on the fast path it moves by less than
+//! 1e-8 dB, and a link closure decision can only change for an SNR that
+//! close to its threshold.
+//!
+//! `log2`: the argument is split into exponent and mantissa, the mantissa
+//! folded into [√½, √2) and its logarithm taken from the `atanh` series
+//! through the t¹¹ term. `exp2`: the argument is split at the nearest integer
+//! and 2 raised to the fraction with a degree-8 Taylor polynomial.
+//!
+//! The slice forms process four values at a time with `std::simd` when the
+//! `simd` feature is enabled (nightly toolchain only), and give the same
+//! results as the scalar forms either way.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (fast statistical link studies)
+
+use std::f64::consts::{LN_2, LOG10_2, LOG2_10, LOG2_E, SQRT_2};
+
+use serde::{Deserialize, Serialize};
+
+const MANTISSA_BITS: u64 = 0x000f_ffff_ffff_ffff;
+const EXPONENT_ONE_BITS: u64 = 0x3ff0_0000_0000_0000;
+/// Arguments of `exp2` whose result is normal.
+const EXP2_RANGE: (f64, f64) = (-1022.0, 1023.0);
+
+/// Math used for the link model's logarithms and powers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MathPath {
+    /// Standard library functions.
+    #[default]
+    Precise,
+    /// This module's approximations, within its documented bounds.
+    Fast,
+}
+
+impl MathPath {
+    /// Base-10 logarithm.
+    pub fn log10(self, x: f64) -> f64 {
+        match self {
+            MathPath::Precise => x.log10(),
+            MathPath::Fast => log10(x),
+        }
+    }
+
+    /// Base-2 logarithm.
+    pub fn log2(self, x: f64) -> f64 {
+        match self {
+            MathPath::Precise => x.log2(),
+            MathPath::Fast => log2(x),
+        }
+    }
+
+    /// 10 raised to `x`.
+    pub fn pow10(self, x: f64) -> f64 {
+        match self {
+            MathPath::Precise => 10.0_f64.powf(x),
+            MathPath::Fast => pow10(x),
+        }
+    }
+
+    /// Replace every value with its base-2 logarithm.
+    pub fn log2_slice(self, values: &mut [f64]) {
+        match self {
+            MathPath::Precise => values.iter_mut().for_each(|v| *v = v.log2()),
+            MathPath::Fast => log2_slice(values),
+        }
+    }
+
+    /// Replace every value `v` with 10 raised to `v`.
+    pub fn pow10_slice(self, values: &mut [f64]) {
+        match self {
+            MathPath::Precise => values.iter_mut().for_each(|v| *v = 10.0_f64.powf(*v)),
+            MathPath::Fast => pow10_slice(values),
+        }
+    }
+}
+
+/// `atanh` series of ln((1 + t) / (1 - t)) / 2t in t², highest power
+/// first.
+const LN_SERIES: [f64; 6] = [1.0 / 11.0, 1.0 / 9.0, 1.0 / 7.0, 1.0 / 5.0, 1.0 / 3.0, 1.0];
+/// Taylor series of eʸ, highest power first.
+const EXP_SERIES: [f64; 9] = [
+    1.0 / 40320.0,
+    1.0 / 5040.0,
+    1.0 / 720.0,
+    1.0 / 120.0,
+    1.0 / 24.0,
+    1.0 / 6.0,
+    1.0 / 2.0,
+    1.0,
+    1.0,
+];
+
+/// Natural logarithm of a mantissa folded into [√½, √2).
+fn ln_mantissa(m: f64) -> f64 {
+    let t = (m - 1.0) / (m + 1.0);
+    let t2 = t * t;
+    let series = LN_SERIES[1..]
+        .iter()
+        .fold(LN_SERIES[0], |sum, c| c + t2 * sum);
+    2.0 * t * series
+}
+
+/// 2 raised to a fraction in [-½, ½].
+fn exp2_fraction(f: f64) -> f64 {
+    let y = f * LN_2;
+    EXP_SERIES[1..]
+        .iter()
+        .fold(EXP_SERIES[0], |sum, c| c + y * sum)
+}
+
+/// Fast base-2 logarithm.
+pub fn log2(x: f64) -> f64 {
+    if !(x.is_normal() && x > 0.0) {
+        return x.log2();
+    }
+    let bits = x.to_bits();
+    let mut exponent = ((bits >> 52) & 0x7ff) as f64 - 1023.0;
+    let mut mantissa = f64::from_bits((bits & MANTISSA_BITS) | EXPONENT_ONE_BITS);
+    if mantissa > SQRT_2 {
+        mantissa *= 0.5;
+        exponent += 1.0;
+    }
+    exponent + ln_mantissa(mantissa) * LOG2_E
+}
+
+/// Fast base-10 logarithm.
+pub fn log10(x: f64) -> f64 {
+    log2(x) * LOG10_2
+}
+
+/// Fast 2 raised to `x`.
+pub fn exp2(x: f64) -> f64 {
+    if !(x > EXP2_RANGE.0 && x < EXP2_RANGE.1) {
+        return x.exp2();
+    }
+    let whole = x.round();
+    let scale = f64::from_bits(((whole as i64 + 1023) as u64) << 52);
+    scale * exp2_fraction(x - whole)
+}
+
+/// Fast 10 raised to `x`.
+pub fn pow10(x: f64) -> f64 {
+    exp2(x * LOG2_10)
+}
+
+/// Replace every value with its fast base-2 logarithm.
+pub fn log2_slice(values: &mut [f64]) {
+    #[cfg(feature = "simd")]
+    let values = lanes::each_chunk(values, lanes::log2);
+    values.iter_mut().for_each(|v| *v = log2(*v));
+}
+
+/// Replace every value with its fast base-10 logarithm.
+pub fn log10_slice(values: &mut [f64]) {
+    log2_slice(values);
+    values.iter_mut().for_each(|v| *v *= LOG10_2);
+}
+
+/// Replace every value `v` with fast 2 raised to `v`.
+pub fn exp2_slice(values: &mut [f64]) {
+    #[cfg(feature = "simd")]
+    let values = lanes::each_chunk(values, lanes::exp2);
+    values.iter_mut().for_each(|v| *v = exp2(*v));
+}
+
+/// Replace every value `v` with fast 10 raised to `v`.
+pub fn pow10_slice(values: &mut [f64]) {
+    values.iter_mut().for_each(|v| *v *= LOG2_10);
+    exp2_slice(values);
+}
+
+/// `std::simd` forms of the approximations, four lanes at a time, with the
+/// same operations as the scalar forms.
+#[cfg(feature = "simd")]
+mod lanes {
+    use std::simd::prelude::*;
+    use std::simd::StdFloat;
+
+    use super::{
+        EXP2_RANGE, EXPONENT_ONE_BITS, EXP_SERIES, LN_2, LN_SERIES, LOG2_E, MANTISSA_BITS, SQRT_2,
+    };
+
+    const LANES: usize = 4;
+
+    /// Apply `op` to every whole chunk of `values`; returns the remainder
+    /// shorter than a chunk.
+    pub(super) fn each_chunk(values: &mut [f64], op: fn(&mut [f64])) -> &mut [f64] {
+        let mut chunks = values.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            op(chunk);
+        }
+        chunks.into_remainder()
+    }
+
+    /// Fast base-2 logarithm of a chunk in place.
+    pub(super) fn log2(chunk: &mut [f64]) {
+        let x = f64x4::from_slice(chunk);
+        if !(x.is_normal() & x.simd_gt(f64x4::splat(0.0))).all() {
+            // Special values fall back per value
+            return chunk.iter_mut().for_each(|v| *v = super::log2(*v));
+        }
+        let bits = x.to_bits();
+        let exponent = ((bits >> 52) & u64x4::splat(0x7ff)).cast::<f64>() - f64x4::splat(1023.0);
+        let mantissa = f64x4::from_bits(
+            (bits & u64x4::splat(MANTISSA_BITS)) | u64x4::splat(EXPONENT_ONE_BITS),
+        );
+        let high = mantissa.simd_gt(f64x4::splat(SQRT_2));
+        let mantissa = high.select(mantissa * f64x4::splat(0.5), mantissa);
+        let exponent = high.select(exponent + f64x4::splat(1.0), exponent);
+
+        let one = f64x4::splat(1.0);
+        let t = (mantissa - one) / (mantissa + one);
+        let t2 = t * t;
+        let series = LN_SERIES[1..]
+            .iter()
+            .fold(f64x4::splat(LN_SERIES[0]), |sum, &c| {
+                f64x4::splat(c) + t2 * sum
+            });
+        let ln_mantissa = f64x4::splat(2.0) * t * series;
+        (exponent + ln_mantissa * f64x4::splat(LOG2_E)).copy_to_slice(chunk);
+    }
+
+    /// Fast 2 raised to each value of a chunk in place.
+    pub(super) fn exp2(chunk: &mut [f64]) {
+        let x = f64x4::from_slice(chunk);
+        let in_range =
+            x.simd_gt(f64x4::splat(EXP2_RANGE.0)) & x.simd_lt(f64x4::splat(EXP2_RANGE.1));
+        if !in_range.all() {
+            // Special values fall back per value
+            return chunk.iter_mut().for_each(|v| *v = super::exp2(*v));
+        }
+        let whole = x.round();
+        let scale =
+            f64x4::from_bits((whole.cast::<i64>() + i64x4::splat(1023)).cast::<u64>() << 52);
+        let y = (x - whole) * f64x4::splat(LN_2);
+        let polynomial = EXP_SERIES[1..]
+            .iter()
+            .fold(f64x4::splat(EXP_SERIES[0]), |sum, &c| {
+                f64x4::splat(c) + y * sum
+            });
+        (scale * polynomial).copy_to_slice(chunk);
+    }
+}
@@ -0,0 +1,265 @@
+//! Link Capacity Forecast Module
+//!
+//! Predicts the volume each upcoming contact can carry, for uplink to the
+//! satellite ahead of the passes. `ForecastStudy` walks each contact's pass
+//! geometry (`tracking::generate_pass`), takes the weather at every sample
+//! from a `WeatherGenerator`, and simulates the band at that range and
+//! elevation; the samples that meet the mission's margins contribute their
+//! data rate for one step. The same pass in the base conditions gives the
+//! nominal capacity the forecast is compared against. With a ground antenna
+//! (`with_ground_antenna`) each sample also sees the antenna's system noise
+//! temperature at its elevation, so the low ends of a pass carry less.
+//!
+//! `LinkForecast` serializes with the same field names as the ground
+//! station's `space_comms_shared::link_forecast::LinkForecast`, so a forecast
+//! written with `to_json` is the file the ground station's `forecast` console
+//! command uplinks. Onboard, contacts forecast below the degraded threshold
+//! carry only high-priority products.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-002: Data Transfer Rates (per-contact capacity forecast)
+//! - REQ-FN-008: Frequency Band Simulation (weather and geometry driven link prediction)
+
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::antenna_noise::GroundAntenna;
+use crate::margin::MarginPolicy;
+use crate::tracking::generate_pass;
+use crate::validation::ValidationError;
+use crate::weather::WeatherGenerator;
+use crate::{EnvironmentalConditions, FrequencyBand, TransmissionParameters};
+
+/// Most contacts in one forecast; matches the onboard limit.
+pub const MAX_FORECAST_CONTACTS: usize = 12;
+
+/// Forecast capacity, as a percentage of nominal, below which a contact is
+/// degraded; matches the onboard default.
+pub const DEFAULT_DEGRADED_PERCENT: u8 = 50;
+
+/// An upcoming contact to forecast.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContactOpportunity {
+    /// Contact identifier, matching the pass ID of its file manifest.
+    pub contact_id: u32,
+    /// Acquisition of signal, mission seconds.
+    pub start_s: u64,
+    /// Culmination elevation of the pass, degrees.
+    pub max_elevation_deg: f64,
+}
+
+/// Expected capacity of one upcoming contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactForecast {
+    /// Contact identifier.
+    pub contact_id: u32,
+    /// Acquisition of signal, mission seconds.
+    pub start_s: u64,
+    /// Contact duration, seconds.
+    pub duration_s: u32,
+    /// Volume the contact is forecast to carry, bytes.
+    pub capacity_bytes: u64,
+    /// Volume the contact would carry in the base conditions, bytes.
+    pub nominal_capacity_bytes: u64,
+}
+
+impl ContactForecast {
+    /// Forecast capacity as a percentage of nominal, at most 100.
+    pub fn capacity_percent(&self) -> u8 {
+        if self.nominal_capacity_bytes == 0 {
+            return 0;
+        }
+        let percent = self.capacity_bytes.saturating_mul(100) / self.nominal_capacity_bytes;
+        percent.min(100) as u8
+    }
+
+    /// Whether the forecast capacity is below `degraded_percent` of nominal.
+    pub fn is_degraded(&self, degraded_percent: u8) -> bool {
+        self.capacity_percent() < degraded_percent
+    }
+}
+
+/// Forecast of the capacity of upcoming contacts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkForecast {
+    /// Forecast identifier; onboard, a newer forecast replaces an older one.
+    pub forecast_id: u32,
+    /// Time the forecast was made, mission seconds.
+    pub issued_s: u64,
+    /// Contacts in time order.
+    pub contacts: Vec<ContactForecast>,
+}
+
+impl LinkForecast {
+    /// Contacts forecast below `degraded_percent` of nominal.
+    pub fn degraded(&self, degraded_percent: u8) -> impl Iterator<Item = &ContactForecast> {
+        self.contacts
+            .iter()
+            .filter(move |c| c.is_degraded(degraded_percent))
+    }
+
+    /// Forecast as the JSON file uplinked by the ground station.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Contact capacity forecast for one band and orbit.
+///
+/// - **ID**: MOD-FCS-002
+/// - **Requirement**: Predict the capacity of upcoming contacts from pass
+///   geometry and weather, judged with the mission's margins.
+/// - **Constraints**: Circular orbit and spherical Earth, as for
+///   `generate_pass`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastStudy {
+    /// Circular orbit altitude, km.
+    pub altitude_km: f64,
+    /// Minimum usable elevation, degrees.
+    pub elevation_mask_deg: f64,
+    /// Pass sample spacing, seconds.
+    pub step_s: f64,
+    /// Transmitter power, W.
+    pub transmit_power_watts: f64,
+    /// Ground antenna diameter, m.
+    pub antenna_diameter_meters: f64,
+    /// Lowest data rate worth keeping the link for, Mbps.
+    pub required_data_rate_mbps: f64,
+    /// Margins a sample's link must keep to carry data.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+    /// Ground antenna whose noise temperature follows the sample
+    /// elevation; `None` keeps the band's fixed noise temperature.
+    #[serde(default)]
+    pub ground_antenna: Option<GroundAntenna>,
+}
+
+impl Default for ForecastStudy {
+    /// 550 km orbit, 10° mask, 10 s steps, 50 W into a 3 m dish.
+    fn default() -> Self {
+        Self {
+            altitude_km: 550.0,
+            elevation_mask_deg: 10.0,
+            step_s: 10.0,
+            transmit_power_watts: 50.0,
+            antenna_diameter_meters: 3.0,
+            required_data_rate_mbps: 1.0,
+            margins: MarginPolicy::default(),
+            ground_antenna: None,
+        }
+    }
+}
+
+impl ForecastStudy {
+    /// Judge samples against the mission's `margins`.
+    pub fn with_margins(mut self, margins: MarginPolicy) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Receive through `antenna`, with its noise temperature at each
+    /// sample's elevation.
+    pub fn with_ground_antenna(mut self, antenna: GroundAntenna) -> Self {
+        self.ground_antenna = Some(antenna);
+        self
+    }
+
+    /// Forecast the capacity of one contact.
+    ///
+    /// Weather is drawn at each sample time, so `weather` must not be asked
+    /// for an earlier contact afterwards. Fails if the study's parameters or
+    /// any sampled conditions are out of range.
+    pub fn forecast_contact(
+        &self,
+        band: &FrequencyBand,
+        contact: &ContactOpportunity,
+        weather: &mut dyn WeatherGenerator,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> Result<ContactForecast, ValidationError> {
+        let pass = generate_pass(
+            self.altitude_km,
+            contact.max_elevation_deg,
+            self.elevation_mask_deg,
+            self.step_s,
+        );
+
+        let mut capacity_bytes = 0.0;
+        let mut nominal_capacity_bytes = 0.0;
+        for sample in &pass {
+            let params = TransmissionParameters {
+                distance_km: sample.range_km,
+                data_size_mb: 1.0,
+                required_data_rate_mbps: self.required_data_rate_mbps,
+                elevation_angle_degrees: sample.pointing.elevation_deg,
+                transmit_power_watts: self.transmit_power_watts,
+                antenna_diameter_meters: self.antenna_diameter_meters,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            };
+            let environment =
+                weather.conditions_at(contact.start_s as f64 + sample.time_s, base, rng);
+            capacity_bytes += self.sample_bytes(band, &params, &environment)?;
+            nominal_capacity_bytes += self.sample_bytes(band, &params, base)?;
+        }
+
+        Ok(ContactForecast {
+            contact_id: contact.contact_id,
+            start_s: contact.start_s,
+            duration_s: (pass.len() as f64 * self.step_s).round() as u32,
+            capacity_bytes: capacity_bytes as u64,
+            nominal_capacity_bytes: nominal_capacity_bytes as u64,
+        })
+    }
+
+    /// Forecast the capacity of upcoming contacts.
+    ///
+    /// - **ID**: FN-FCS-004
+    /// - **Requirement**: Give the satellite the expected capacity of each
+    ///   upcoming contact so it can choose what to downlink on degraded ones.
+    /// - **Inputs**: The band, the contacts, a weather model, the base
+    ///   conditions it varies and its generator.
+    /// - **Outputs**: One forecast per contact in start order, at most
+    ///   `MAX_FORECAST_CONTACTS`; later contacts are left for the next
+    ///   forecast. Wrap them in a `LinkForecast` for uplink.
+    /// - **Side Effects**: Advances `weather` to the last contact forecast.
+    /// - **Failure Modes**: `ValidationError` from the first contact whose
+    ///   inputs are out of range.
+    pub fn forecast(
+        &self,
+        band: &FrequencyBand,
+        contacts: &[ContactOpportunity],
+        weather: &mut dyn WeatherGenerator,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> Result<Vec<ContactForecast>, ValidationError> {
+        let mut upcoming: Vec<&ContactOpportunity> = contacts.iter().collect();
+        upcoming.sort_by_key(|c| c.start_s);
+        upcoming
+            .into_iter()
+            .take(MAX_FORECAST_CONTACTS)
+            .map(|contact| self.forecast_contact(band, contact, weather, base, rng))
+            .collect()
+    }
+
+    /// Bytes carried in one step at these conditions; none unless the
+    /// margins are met.
+    fn sample_bytes(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+    ) -> Result<f64, ValidationError> {
+        let result = match &self.ground_antenna {
+            Some(antenna) => {
+                band.simulate_transmission_at(antenna, params, environment, &self.margins)?
+            }
+            None => band.simulate_transmission_with(params, environment, &self.margins)?,
+        };
+        Ok(if result.success {
+            result.actual_data_rate_mbps * 1e6 / 8.0 * self.step_s
+        } else {
+            0.0
+        })
+    }
+}
@@ -0,0 +1,278 @@
+//! Formation Flying Crosslink Ranging Module
+//!
+//! Relative navigation for close formations from S-band crosslink ranging.
+//! The chief turns around a ranging code from the deputy: the code delay
+//! gives the inter-satellite range and the Doppler of the coherent carrier
+//! gives the range rate. Both are measured with errors set by the carrier
+//! to noise density (C/N0) of the crosslink, which falls with the square of
+//! the range:
+//!
+//! - Range, from a delay-locked loop with one-chip early-late spacing:
+//!   `σ = L_chip / 2 · sqrt(B_L / (2 · C/N0))`, the half because the signal
+//!   travels the range twice.
+//! - Range rate, from the Cramér-Rao bound on the frequency estimated over
+//!   the integration time `T`: `σ = λ / 2 · sqrt(3 / (2π² · C/N0 · T³))`.
+//!
+//! Below the lock threshold no measurement is produced. `FormationRanging`
+//! steps the two orbits through time and returns the true and measured
+//! geometry with the sigmas of every sample, for formation-keeping studies.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (S-band crosslink ranging)
+//! - REQ-FN-008: Frequency Band Simulation (ranging noise from link budget)
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::occultation::{line_of_sight_clear, CircularOrbit, DEFAULT_ATMOSPHERE_MARGIN_KM};
+use crate::stats::RunningStats;
+
+/// Speed of light in vacuum, m/s.
+const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
+/// Boltzmann's constant, dBW/K/Hz.
+const BOLTZMANN_DBW_K_HZ: f64 = -228.6;
+
+/// Time step of the central difference giving the true range rate, seconds.
+const RANGE_RATE_STEP_S: f64 = 2.0;
+
+/// S-band crosslink terminal used for ranging.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CrosslinkTerminal {
+    /// Carrier frequency, GHz.
+    pub frequency_ghz: f64,
+    /// Transmitted EIRP, dBW.
+    pub eirp_dbw: f64,
+    /// Receiver figure of merit, dB/K.
+    pub g_over_t_db_k: f64,
+    /// Implementation and pointing losses, dB.
+    pub losses_db: f64,
+    /// Ranging code chip rate, chips/s.
+    pub chip_rate_cps: f64,
+    /// Delay-locked loop noise bandwidth, Hz.
+    pub dll_bandwidth_hz: f64,
+    /// Doppler integration time, seconds.
+    pub integration_s: f64,
+    /// C/N0 below which the ranging loops cannot hold lock, dB-Hz.
+    pub lock_threshold_dbhz: f64,
+}
+
+impl Default for CrosslinkTerminal {
+    /// 2.2 GHz low-gain crosslink at 10 mW with a 1 Mcps ranging code, holding
+    /// lock out to about 60 km.
+    fn default() -> Self {
+        Self {
+            frequency_ghz: 2.2,
+            eirp_dbw: -20.0,
+            g_over_t_db_k: -35.0,
+            losses_db: 3.0,
+            chip_rate_cps: 1.0e6,
+            dll_bandwidth_hz: 1.0,
+            integration_s: 1.0,
+            lock_threshold_dbhz: 35.0,
+        }
+    }
+}
+
+impl CrosslinkTerminal {
+    /// Carrier to noise density at `range_m`, dB-Hz.
+    pub fn cn0_dbhz(&self, range_m: f64) -> f64 {
+        let wavelength_m = SPEED_OF_LIGHT_M_S / (self.frequency_ghz * 1e9);
+        let path_loss_db = 20.0 * (4.0 * std::f64::consts::PI * range_m / wavelength_m).log10();
+        self.eirp_dbw - path_loss_db - self.losses_db + self.g_over_t_db_k - BOLTZMANN_DBW_K_HZ
+    }
+
+    /// Range and range rate sigmas at a given C/N0, m and m/s.
+    ///
+    /// - **ID**: FN-FRM-001
+    /// - **Requirement**: Crosslink ranging errors follow from the link
+    ///   budget (REQ-FN-008).
+    /// - **Inputs**: C/N0, dB-Hz.
+    /// - **Outputs**: `(range_sigma_m, range_rate_sigma_m_s)`, both two-way.
+    pub fn sigmas(&self, cn0_dbhz: f64) -> (f64, f64) {
+        let cn0 = 10f64.powf(cn0_dbhz / 10.0);
+        let chip_m = SPEED_OF_LIGHT_M_S / self.chip_rate_cps;
+        let wavelength_m = SPEED_OF_LIGHT_M_S / (self.frequency_ghz * 1e9);
+        let t = self.integration_s;
+        let range_sigma_m = chip_m / 2.0 * (self.dll_bandwidth_hz / (2.0 * cn0)).sqrt();
+        let frequency_sigma_hz =
+            (3.0 / (2.0 * std::f64::consts::PI.powi(2) * cn0 * t.powi(3))).sqrt();
+        (range_sigma_m, wavelength_m / 2.0 * frequency_sigma_hz)
+    }
+}
+
+/// One ranging epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangingSample {
+    /// Simulation time, seconds.
+    pub time_s: f64,
+    /// True inter-satellite range, m.
+    pub true_range_m: f64,
+    /// True range rate, positive while separating, m/s.
+    pub true_range_rate_m_s: f64,
+    /// Crosslink C/N0, dB-Hz.
+    pub cn0_dbhz: f64,
+    /// Range error, 1-sigma, m.
+    pub range_sigma_m: f64,
+    /// Range rate error, 1-sigma, m/s.
+    pub range_rate_sigma_m_s: f64,
+    /// Measured range and range rate; `None` out of lock or behind the Earth.
+    pub measured: Option<(f64, f64)>,
+}
+
+/// Statistics of a ranging run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangingSummary {
+    /// Epochs simulated.
+    pub epochs: usize,
+    /// Epochs with a measurement.
+    pub locked_epochs: usize,
+    /// Shortest and longest true range, m.
+    pub range_span_m: (f64, f64),
+    /// RMS range error of the measurements, m.
+    pub range_rms_error_m: f64,
+    /// RMS range rate error of the measurements, m/s.
+    pub range_rate_rms_error_m_s: f64,
+    /// Mean predicted range sigma of the measurements, m.
+    pub mean_range_sigma_m: f64,
+    /// Mean predicted range rate sigma of the measurements, m/s.
+    pub mean_range_rate_sigma_m_s: f64,
+}
+
+/// Crosslink ranging between a chief and a deputy satellite.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FormationRanging {
+    /// Satellite making the measurement.
+    pub chief: CircularOrbit,
+    /// Formation partner.
+    pub deputy: CircularOrbit,
+    /// Crosslink terminal of both satellites.
+    pub terminal: CrosslinkTerminal,
+    /// Seed of the measurement noise.
+    pub seed: u64,
+}
+
+impl FormationRanging {
+    /// True range at `t_s`, m.
+    pub fn range_m(&self, t_s: f64) -> f64 {
+        let (a, b) = (self.chief.position(t_s), self.deputy.position(t_s));
+        let d = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() * 1000.0
+    }
+
+    /// Range and range rate at every `step_s` from `start_s` to `end_s`.
+    ///
+    /// - **ID**: FN-FRM-002
+    /// - **Requirement**: Expose inter-satellite range and range rate with
+    ///   their noise for formation-keeping studies (REQ-FN-007).
+    /// - **Outputs**: One sample per epoch in time order; repeated runs with
+    ///   the same seed give the same samples.
+    /// - **Side Effects**: None.
+    pub fn run(&self, start_s: f64, end_s: f64, step_s: f64) -> Vec<RangingSample> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut samples = Vec::new();
+        if step_s <= 0.0 {
+            return samples;
+        }
+
+        let mut t_s = start_s;
+        while t_s <= end_s {
+            let true_range_m = self.range_m(t_s);
+            let true_range_rate_m_s = (self.range_m(t_s + RANGE_RATE_STEP_S / 2.0)
+                - self.range_m(t_s - RANGE_RATE_STEP_S / 2.0))
+                / RANGE_RATE_STEP_S;
+            let cn0_dbhz = self.terminal.cn0_dbhz(true_range_m);
+            let (range_sigma_m, range_rate_sigma_m_s) = self.terminal.sigmas(cn0_dbhz);
+
+            let visible = line_of_sight_clear(
+                self.chief.position(t_s),
+                self.deputy.position(t_s),
+                DEFAULT_ATMOSPHERE_MARGIN_KM,
+            );
+            let measured = (visible && cn0_dbhz >= self.terminal.lock_threshold_dbhz).then(|| {
+                (
+                    true_range_m + range_sigma_m * gaussian(&mut rng),
+                    true_range_rate_m_s + range_rate_sigma_m_s * gaussian(&mut rng),
+                )
+            });
+
+            samples.push(RangingSample {
+                time_s: t_s,
+                true_range_m,
+                true_range_rate_m_s,
+                cn0_dbhz,
+                range_sigma_m,
+                range_rate_sigma_m_s,
+                measured,
+            });
+            t_s += step_s;
+        }
+        samples
+    }
+}
+
+/// Summarise the geometry and measurement errors of a run.
+pub fn summarize_ranging(samples: &[RangingSample]) -> RangingSummary {
+    let mut range_error = RunningStats::new();
+    let mut rate_error = RunningStats::new();
+    let mut range_sigma = RunningStats::new();
+    let mut rate_sigma = RunningStats::new();
+    let mut range_span_m = (f64::INFINITY, f64::NEG_INFINITY);
+
+    for sample in samples {
+        range_span_m.0 = range_span_m.0.min(sample.true_range_m);
+        range_span_m.1 = range_span_m.1.max(sample.true_range_m);
+        if let Some((range_m, range_rate_m_s)) = sample.measured {
+            range_error.push((range_m - sample.true_range_m).powi(2));
+            rate_error.push((range_rate_m_s - sample.true_range_rate_m_s).powi(2));
+            range_sigma.push(sample.range_sigma_m);
+            rate_sigma.push(sample.range_rate_sigma_m_s);
+        }
+    }
+
+    RangingSummary {
+        epochs: samples.len(),
+        locked_epochs: range_error.count() as usize,
+        range_span_m,
+        range_rms_error_m: range_error.mean().map_or(0.0, f64::sqrt),
+        range_rate_rms_error_m_s: rate_error.mean().map_or(0.0, f64::sqrt),
+        mean_range_sigma_m: range_sigma.mean().unwrap_or(0.0),
+        mean_range_rate_sigma_m_s: rate_sigma.mean().unwrap_or(0.0),
+    }
+}
+
+/// Standard normal variate by the Box-Muller transform.
+pub(crate) fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Run crosslink ranging over a representative close formation for two
+/// orbits, sampled every 10 s.
+///
+/// The deputy trails the chief by under 1 km in a 550 km, 53° orbit, with a
+/// small RAAN offset that adds a cross-track oscillation of about 400 m.
+pub fn run_formation_ranging_demo() -> (Vec<RangingSample>, RangingSummary) {
+    let chief = CircularOrbit {
+        altitude_km: 550.0,
+        inclination_deg: 53.0,
+        raan_deg: 0.0,
+        phase_deg: 0.0,
+    };
+    let deputy = CircularOrbit {
+        raan_deg: 0.004,
+        phase_deg: -0.0083,
+        ..chief
+    };
+    let ranging = FormationRanging {
+        chief,
+        deputy,
+        terminal: CrosslinkTerminal::default(),
+        seed: 7,
+    };
+    let samples = ranging.run(0.0, 2.0 * chief.period_s(), 10.0);
+    let summary = summarize_ranging(&samples);
+    (samples, summary)
+}
@@ -0,0 +1,605 @@
+//! Hybrid ARQ Module
+//!
+//! Plain automatic repeat request resends a frame whenever it arrives with
+//! any error, so on a marginal link almost every frame is sent again and
+//! again and throughput collapses. Hybrid ARQ layers retransmission on top
+//! of the FEC and interleaving of [`crate::interleaving`]:
+//!
+//! - **FEC first**: each frame is `depth` interleaved RS(255,223) codewords,
+//!   so scattered errors and short bursts are corrected without any
+//!   retransmission.
+//! - **Selective NAK**: after each round the receiver reports the frame
+//!   numbers it still lacks in a [`NakBitmap`], and only those are resent.
+//! - **Combining**: the receiver keeps every failed copy of a frame
+//!   ([`HarqReceiver`]) and combines them symbol by symbol. Copies that
+//!   agree keep their value, a majority wins, and a tie becomes an erasure,
+//!   which costs the RS decoder half as much as an error. Each new copy is
+//!   also tried alone, and a codeword that failed in every copy alone
+//!   usually decodes from the majority of three.
+//!
+//! [`measure_arq`] runs windows of frames through a [`GilbertElliott`]
+//! channel under either [`ArqScheme`] and reports delivered frames,
+//! transmissions and throughput efficiency ([`ArqReport`]). Both schemes
+//! retransmit selectively, so the comparison isolates what FEC and combining
+//! buy: plain ARQ wins the code rate back on a clean link, hybrid ARQ keeps
+//! delivering as the link degrades. [`ArqReport::link_stats`] gives a study
+//! the link layer statistics the satellite and ground station keep.
+//!
+//! As in [`crate::interleaving`], the decoder is modeled by its correction
+//! capability, so the receiver is handed the transmitted codewords to count
+//! errors against. Combining is on hard symbol decisions; soft (Chase)
+//! combining of the demodulator output would only do better.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (hybrid ARQ over burst error channels)
+//! - REQ-PF-002: Data Transfer Rates (throughput efficiency against plain ARQ)
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use space_comms_shared::link_stats::LinkStats;
+
+use crate::interleaving::{BlockCode, CodingChain, CodingError, GilbertElliott, InterleaverConfig};
+use crate::monte_carlo::MonteCarlo;
+use crate::BandType;
+
+/// Frames one NAK bitmap covers.
+pub const NAK_WINDOW: usize = 64;
+
+/// Encoded length of a [`NakBitmap`]: 4-byte base frame number and 8-byte
+/// bitmap, big-endian.
+pub const NAK_LEN: usize = 12;
+
+/// Good-state bit error rates swept by [`run_harq_demo`], from a clean link
+/// to a marginal one.
+pub const DEMO_GOOD_STATE_BERS: [f64; 4] = [1e-6, 1e-4, 1e-3, 5e-3];
+
+/// Error in the ARQ configuration, a NAK or a received frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArqError {
+    /// Frame number outside the window of a NAK bitmap.
+    OutsideWindow {
+        /// First frame number of the window.
+        base: u32,
+        /// Frame number given.
+        frame: u32,
+    },
+    /// Encoded NAK of the wrong length.
+    NakLength(usize),
+    /// ARQ parameters are not usable.
+    InvalidConfig(&'static str),
+    /// Coding chain or channel error.
+    Coding(CodingError),
+}
+
+impl fmt::Display for ArqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArqError::OutsideWindow { base, frame } => write!(
+                f,
+                "frame {} is outside the NAK window starting at {}",
+                frame, base
+            ),
+            ArqError::NakLength(len) => {
+                write!(f, "NAK of {} bytes, expected {}", len, NAK_LEN)
+            }
+            ArqError::InvalidConfig(reason) => write!(f, "invalid ARQ configuration: {}", reason),
+            ArqError::Coding(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ArqError {}
+
+impl From<CodingError> for ArqError {
+    fn from(error: CodingError) -> Self {
+        ArqError::Coding(error)
+    }
+}
+
+// ─── NAK Bitmap ──────────────────────────────────────────────────────────────
+
+/// Frame numbers a receiver still lacks, relative to a window base.
+///
+/// Bit `i` set means frame `base + i` is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NakBitmap {
+    /// First frame number of the window.
+    pub base: u32,
+    /// Missing frames, bit `i` for frame `base + i`.
+    pub bits: u64,
+}
+
+impl NakBitmap {
+    /// Bitmap with no frames missing.
+    pub fn new(base: u32) -> Self {
+        Self { base, bits: 0 }
+    }
+
+    /// Mark `frame` missing.
+    pub fn mark_missing(&mut self, frame: u32) -> Result<(), ArqError> {
+        let offset = frame.wrapping_sub(self.base) as usize;
+        if offset >= NAK_WINDOW {
+            return Err(ArqError::OutsideWindow {
+                base: self.base,
+                frame,
+            });
+        }
+        self.bits |= 1 << offset;
+        Ok(())
+    }
+
+    /// Whether `frame` is marked missing.
+    pub fn is_missing(&self, frame: u32) -> bool {
+        let offset = frame.wrapping_sub(self.base) as usize;
+        offset < NAK_WINDOW && self.bits & (1 << offset) != 0
+    }
+
+    /// Missing frame numbers in ascending order.
+    pub fn missing(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..NAK_WINDOW as u32)
+            .filter(move |offset| self.bits & (1 << offset) != 0)
+            .map(move |offset| self.base.wrapping_add(offset))
+    }
+
+    /// Number of frames missing.
+    pub fn count(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Whether every frame of the window arrived.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Encode for the return link.
+    pub fn to_bytes(&self) -> [u8; NAK_LEN] {
+        let mut bytes = [0u8; NAK_LEN];
+        bytes[..4].copy_from_slice(&self.base.to_be_bytes());
+        bytes[4..].copy_from_slice(&self.bits.to_be_bytes());
+        bytes
+    }
+
+    /// Decode a NAK received on the return link.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ArqError> {
+        if bytes.len() != NAK_LEN {
+            return Err(ArqError::NakLength(bytes.len()));
+        }
+        let mut base = [0u8; 4];
+        let mut bits = [0u8; 8];
+        base.copy_from_slice(&bytes[..4]);
+        bits.copy_from_slice(&bytes[4..]);
+        Ok(Self {
+            base: u32::from_be_bytes(base),
+            bits: u64::from_be_bytes(bits),
+        })
+    }
+}
+
+// ─── Combining Receiver ──────────────────────────────────────────────────────
+
+/// Copies of a frame that has not decoded yet.
+#[derive(Debug, Clone, Default)]
+struct PendingFrame {
+    /// Every received copy, deinterleaved into codewords.
+    copies: Vec<Vec<u8>>,
+    /// Codewords already decoded from some combination of copies.
+    decoded: Vec<bool>,
+}
+
+/// Hybrid ARQ receiver storing failed frames and combining retransmissions.
+#[derive(Debug, Clone)]
+pub struct HarqReceiver {
+    code: BlockCode,
+    pending: BTreeMap<u32, PendingFrame>,
+    delivered: BTreeSet<u32>,
+}
+
+impl HarqReceiver {
+    /// Receiver decoding codewords of `code`.
+    pub fn new(code: BlockCode) -> Self {
+        Self {
+            code,
+            pending: BTreeMap::new(),
+            delivered: BTreeSet::new(),
+        }
+    }
+
+    /// Receive a copy of `frame`, returning whether the frame is delivered.
+    ///
+    /// - **ID**: FN-ARQ-001
+    /// - **Requirement**: Keep failed copies of a frame and decode each
+    ///   codeword from the new copy alone or the combination of every copy
+    ///   received so far.
+    /// - **Inputs**:
+    ///   - `frame`: Frame number.
+    ///   - `received`: Deinterleaved codewords as received.
+    ///   - `sent`: Codewords as transmitted, to count errors against.
+    /// - **Outputs**: `true` once every codeword of the frame has decoded.
+    /// - **Side Effects**: Stores the copy until the frame is delivered, then
+    ///   drops all of them.
+    /// - **Failure Modes**: `FrameLength` when `received` and `sent` differ
+    ///   in length or are not whole codewords.
+    pub fn receive(&mut self, frame: u32, received: &[u8], sent: &[u8]) -> Result<bool, ArqError> {
+        if self.delivered.contains(&frame) {
+            return Ok(true);
+        }
+        if received.len() != sent.len()
+            || sent.is_empty()
+            || !sent.len().is_multiple_of(self.code.n)
+        {
+            return Err(CodingError::FrameLength {
+                expected: sent.len().max(self.code.n),
+                actual: received.len(),
+            }
+            .into());
+        }
+
+        let codewords = sent.len() / self.code.n;
+        let pending = self.pending.entry(frame).or_default();
+        if pending.decoded.len() != codewords {
+            *pending = PendingFrame {
+                copies: Vec::new(),
+                decoded: vec![false; codewords],
+            };
+        }
+        pending.copies.push(received.to_vec());
+
+        let code = self.code;
+        for (index, decoded) in pending.decoded.iter_mut().enumerate() {
+            if !*decoded {
+                let symbols = index * code.n..(index + 1) * code.n;
+                let latest = pending.copies.len() - 1;
+                *decoded = [&pending.copies[latest..], &pending.copies[..]]
+                    .into_iter()
+                    .any(|copies| {
+                        let (errors, erasures) = combine(copies, sent, symbols.clone());
+                        code.corrects_with_erasures(errors, erasures)
+                    });
+            }
+        }
+
+        if pending.decoded.iter().all(|&decoded| decoded) {
+            self.pending.remove(&frame);
+            self.delivered.insert(frame);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Whether `frame` has been delivered.
+    pub fn is_delivered(&self, frame: u32) -> bool {
+        self.delivered.contains(&frame)
+    }
+
+    /// Failed copies held for `frame`.
+    pub fn stored_copies(&self, frame: u32) -> usize {
+        self.pending.get(&frame).map_or(0, |p| p.copies.len())
+    }
+
+    /// NAK of the frames `base..base + count` not delivered yet, including
+    /// frames never received.
+    pub fn nak(&self, base: u32, count: usize) -> Result<NakBitmap, ArqError> {
+        if count > NAK_WINDOW {
+            return Err(ArqError::InvalidConfig("NAK covers at most 64 frames"));
+        }
+        let mut nak = NakBitmap::new(base);
+        for frame in (0..count as u32).map(|offset| base.wrapping_add(offset)) {
+            if !self.delivered.contains(&frame) {
+                nak.mark_missing(frame)?;
+            }
+        }
+        Ok(nak)
+    }
+}
+
+/// Symbol errors and erasures left after combining `copies` over `symbols`.
+///
+/// Each symbol takes the value most copies agree on; a tie between values
+/// is an erasure.
+fn combine(copies: &[Vec<u8>], sent: &[u8], symbols: std::ops::Range<usize>) -> (usize, usize) {
+    let mut errors = 0;
+    let mut erasures = 0;
+    let mut votes: Vec<(u8, usize)> = Vec::with_capacity(copies.len());
+    for symbol in symbols {
+        votes.clear();
+        for copy in copies {
+            match votes.iter_mut().find(|(value, _)| *value == copy[symbol]) {
+                Some((_, count)) => *count += 1,
+                None => votes.push((copy[symbol], 1)),
+            }
+        }
+        let best = votes.iter().map(|&(_, count)| count).max().unwrap_or(0);
+        let mut winners = votes.iter().filter(|&&(_, count)| count == best);
+        match (winners.next(), winners.next()) {
+            (Some(&(value, _)), None) if value != sent[symbol] => errors += 1,
+            (Some(_), Some(_)) => erasures += 1,
+            _ => {}
+        }
+    }
+    (errors, erasures)
+}
+
+// ─── ARQ Study ───────────────────────────────────────────────────────────────
+
+/// Retransmission scheme under test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArqScheme {
+    /// Uncoded frames checked by CRC; a frame with any error is resent and
+    /// the failed copy discarded.
+    Plain,
+    /// FEC-coded, interleaved frames; failed copies are kept and combined
+    /// with their retransmissions.
+    Hybrid,
+}
+
+impl fmt::Display for ArqScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArqScheme::Plain => write!(f, "plain ARQ"),
+            ArqScheme::Hybrid => write!(f, "hybrid ARQ"),
+        }
+    }
+}
+
+/// Window and retransmission limits shared by both schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArqConfig {
+    /// Frames sent before the first NAK, at most [`NAK_WINDOW`].
+    pub window: usize,
+    /// Transmission rounds per window, the first included; frames still
+    /// missing after the last round are lost.
+    pub max_rounds: usize,
+}
+
+impl Default for ArqConfig {
+    fn default() -> Self {
+        Self {
+            window: 32,
+            max_rounds: 4,
+        }
+    }
+}
+
+impl ArqConfig {
+    /// Check the window and round limits.
+    pub fn validate(&self) -> Result<(), ArqError> {
+        if self.window == 0 || self.window > NAK_WINDOW {
+            return Err(ArqError::InvalidConfig("window must be 1 to 64 frames"));
+        }
+        if self.max_rounds == 0 {
+            return Err(ArqError::InvalidConfig("at least one transmission round"));
+        }
+        Ok(())
+    }
+}
+
+/// Delivery and throughput of one ARQ scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArqReport {
+    /// Band whose channel was simulated.
+    pub band: BandType,
+    /// Scheme simulated.
+    pub scheme: ArqScheme,
+    /// Frames offered.
+    pub frames: usize,
+    /// Frames delivered within the round limit.
+    pub delivered: usize,
+    /// Frame transmissions, retransmissions included.
+    pub transmissions: usize,
+    /// Bits sent over the channel.
+    pub channel_bits: usize,
+    /// User data bits delivered.
+    pub payload_bits_delivered: usize,
+}
+
+impl ArqReport {
+    /// Fraction of channel bits that carried delivered user data.
+    pub fn throughput_efficiency(&self) -> f64 {
+        ratio(self.payload_bits_delivered, self.channel_bits)
+    }
+
+    /// Fraction of frames lost after the last round.
+    pub fn frame_loss_rate(&self) -> f64 {
+        ratio(self.frames - self.delivered, self.frames)
+    }
+
+    /// Mean transmissions per offered frame.
+    pub fn transmissions_per_frame(&self) -> f64 {
+        ratio(self.transmissions, self.frames)
+    }
+
+    /// Link layer statistics of the study, as sent at `data_rate_bps`.
+    ///
+    /// - **ID**: FN-ARQ-003
+    /// - **Requirement**: Express ARQ studies in the link statistics the
+    ///   satellite and ground station report, so simulated and operational
+    ///   links compare directly.
+    /// - **Outputs**: Every transmission is a frame sent; a delivered frame
+    ///   is a frame received with its payload, and every other copy a frame
+    ///   error. The window is the channel time of all transmissions. No SNR
+    ///   estimate: the channel is modeled by its bit error rates.
+    pub fn link_stats(&self, data_rate_bps: u64) -> LinkStats {
+        let window_ms = (self.channel_bits as u128 * 1_000) / u128::from(data_rate_bps.max(1));
+        LinkStats {
+            frames_sent: self.transmissions as u64,
+            frames_received: self.delivered as u64,
+            frame_errors: (self.transmissions - self.delivered) as u64,
+            retransmissions: (self.transmissions - self.frames) as u64,
+            bytes_sent: (self.channel_bits / 8) as u64,
+            bytes_received: (self.payload_bits_delivered / 8) as u64,
+            window_ms: u64::try_from(window_ms).unwrap_or(u64::MAX),
+            ..LinkStats::new()
+        }
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+impl fmt::Display for ArqReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<9} {:<10} efficiency {:>5.1}%, {:.2} tx/frame, loss {:.2e} ({}/{})",
+            self.band.to_string(),
+            self.scheme.to_string(),
+            self.throughput_efficiency() * 100.0,
+            self.transmissions_per_frame(),
+            self.frame_loss_rate(),
+            self.frames - self.delivered,
+            self.frames
+        )
+    }
+}
+
+/// Frames, transmissions and bits of one window.
+struct WindowOutcome {
+    delivered: usize,
+    transmissions: usize,
+    channel_bits: usize,
+}
+
+/// Send one window of frames, resending what each NAK reports missing.
+fn run_window(
+    scheme: ArqScheme,
+    chain: &CodingChain,
+    channel: &GilbertElliott,
+    config: &ArqConfig,
+    rng: &mut StdRng,
+) -> WindowOutcome {
+    let payload_len = chain.code.k * chain.interleaver.depth();
+    let frame_len = match scheme {
+        ArqScheme::Plain => payload_len,
+        ArqScheme::Hybrid => chain.frame_len(),
+    };
+    let frames: Vec<Vec<u8>> = (0..config.window)
+        .map(|_| {
+            let mut frame = vec![0u8; frame_len];
+            rng.fill(frame.as_mut_slice());
+            frame
+        })
+        .collect();
+
+    let mut receiver = HarqReceiver::new(chain.code);
+    let mut transmissions = 0;
+    for _ in 0..config.max_rounds {
+        let nak = receiver
+            .nak(0, config.window)
+            .unwrap_or_else(|error| unreachable!("window validated: {}", error));
+        if nak.is_empty() {
+            break;
+        }
+        for number in nak.missing() {
+            let sent = &frames[number as usize];
+            transmissions += 1;
+            match scheme {
+                ArqScheme::Plain => {
+                    let mut copy = sent.clone();
+                    if channel.corrupt(&mut copy, rng) == 0 {
+                        receiver.delivered.insert(number);
+                    }
+                }
+                ArqScheme::Hybrid => {
+                    let received = chain
+                        .encode(sent)
+                        .map(|mut frame| {
+                            channel.corrupt(&mut frame, rng);
+                            frame
+                        })
+                        .and_then(|frame| chain.decode(&frame))
+                        .unwrap_or_else(|error| {
+                            unreachable!("frame sized by the chain: {}", error)
+                        });
+                    receiver
+                        .receive(number, &received, sent)
+                        .unwrap_or_else(|error| {
+                            unreachable!("frame sized by the chain: {}", error)
+                        });
+                }
+            }
+        }
+    }
+
+    WindowOutcome {
+        delivered: receiver.delivered.len(),
+        transmissions,
+        channel_bits: transmissions * frame_len * 8,
+    }
+}
+
+/// Measure delivery and throughput of `scheme` over `channel`.
+///
+/// - **ID**: FN-ARQ-002
+/// - **Requirement**: Report how much of the channel carries delivered
+///   data under plain and hybrid ARQ, so their degradation on marginal
+///   links can be compared.
+/// - **Inputs**:
+///   - `band`: Band the report is labelled with.
+///   - `scheme`: Plain or hybrid ARQ.
+///   - `chain`: Code and interleaver of hybrid frames; plain frames carry
+///     the same payload uncoded.
+///   - `channel`: Burst error channel.
+///   - `config`: Window and round limits.
+///   - `study`: One trial per window; reproducible from its seed.
+/// - **Outputs**: Frames delivered, transmissions and channel bits.
+/// - **Failure Modes**: `InvalidConfig` for an unusable window or round
+///   limit; `Coding` for an unusable channel model.
+pub fn measure_arq(
+    band: BandType,
+    scheme: ArqScheme,
+    chain: &CodingChain,
+    channel: &GilbertElliott,
+    config: &ArqConfig,
+    study: &MonteCarlo,
+) -> Result<ArqReport, ArqError> {
+    config.validate()?;
+    channel.validate()?;
+    let outcomes = study.run(|_, rng| run_window(scheme, chain, channel, config, rng));
+
+    let delivered: usize = outcomes.iter().map(|o| o.delivered).sum();
+    Ok(ArqReport {
+        band,
+        scheme,
+        frames: outcomes.len() * config.window,
+        delivered,
+        transmissions: outcomes.iter().map(|o| o.transmissions).sum(),
+        channel_bits: outcomes.iter().map(|o| o.channel_bits).sum(),
+        payload_bits_delivered: delivered * chain.code.k * chain.interleaver.depth() * 8,
+    })
+}
+
+/// Plain and hybrid ARQ on X-band bursts at each of
+/// [`DEMO_GOOD_STATE_BERS`], for the simulation report.
+pub fn run_harq_demo(
+    interleaver: &InterleaverConfig,
+    config: &ArqConfig,
+    study: &MonteCarlo,
+) -> Result<Vec<(f64, ArqReport, ArqReport)>, ArqError> {
+    interleaver.validate()?;
+    let band = BandType::XBand;
+    let chain = CodingChain::for_band(interleaver, band)?;
+    DEMO_GOOD_STATE_BERS
+        .into_iter()
+        .map(|ber| {
+            let channel = GilbertElliott {
+                ber_good: ber,
+                ..GilbertElliott::for_band(band)
+            };
+            Ok((
+                ber,
+                measure_arq(band, ArqScheme::Plain, &chain, &channel, config, study)?,
+                measure_arq(band, ArqScheme::Hybrid, &chain, &channel, config, study)?,
+            ))
+        })
+        .collect()
+}
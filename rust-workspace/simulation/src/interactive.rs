@@ -2,18 +2,87 @@
 //!
 //! A user-friendly interactive demonstration of frequency band characteristics
 //! and their performance under various conditions.
+//!
+//! The real-time simulation takes adjustments of the rain rate, transmit
+//! power and distance typed while it runs, each followed by Enter, and marks
+//! them on its timeline.
+//!
+//! Planner output is localized: set `FREQ_SIM_LANG` (e.g. `es`) to pick a
+//! built-in catalog, or `FREQ_SIM_CATALOG` to the path of a catalog file.
+//!
+//! The real-time simulation starts from the link in the `[simulate]` section
+//! of the settings file (`--config <file>`, `$SPACE_COMMS_CONFIG` or
+//! `./space-comms.toml`) and the `SPACE_COMMS_SIMULATE__*` variables, as
+//! `space-comms simulate` does; keys not set keep the demo's defaults.
+
+use frequency_band_simulation::cache::SimulationCache;
+use frequency_band_simulation::deep_space::{
+    self, DeepSpaceLink, DsnAntenna, ProtocolMode, DEEP_SPACE_THRESHOLD_KM,
+};
+use frequency_band_simulation::locale::{Catalog, Localize};
+use frequency_band_simulation::margin::MarginPolicy;
+use frequency_band_simulation::progress::CancelToken;
+use frequency_band_simulation::scoring::{BandScorer, CriteriaWeights, Criterion, Rating};
+use frequency_band_simulation::tuning::{Adjustment, LiveTuning};
+use frequency_band_simulation::validation::validate_inputs;
+use frequency_band_simulation::weather::{MarkovRainModel, WeatherGenerator};
+use frequency_band_simulation::{
+    BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use space_comms_shared::settings;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+/// Starting link of the real-time simulation, from the `[simulate]` section
+/// of the settings
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+struct RunSettings {
+    /// Slant range, km
+    distance_km: f64,
+    /// Elevation of the ground antenna, degrees
+    elevation_deg: f64,
+    /// Transmitter power, W
+    power_w: f64,
+    /// Ground antenna diameter, m
+    antenna_m: f64,
+    /// Volume to send, MB
+    data_mb: f64,
+    /// Data rate required, Mbps
+    rate_mbps: f64,
+    /// Cloud cover, percent
+    cloud_percent: f64,
+}
 
-mod atmospheric;
-mod bands;
-mod interference;
-
-use bands::{BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters};
-use std::collections::HashMap;
-use std::io::{self, Write};
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            distance_km: 1000.0,
+            elevation_deg: 35.0,
+            power_w: 100.0,
+            antenna_m: 3.0,
+            data_mb: 100.0,
+            rate_mbps: 200.0,
+            cloud_percent: 20.0,
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let named = settings::config_from_args();
+    let run: RunSettings =
+        settings::section("simulate", named.as_deref(), std::env::vars().collect())?;
+
     display_welcome();
 
+    // Planner runs for the same mission reuse earlier results
+    let cache = Arc::new(SimulationCache::default());
+
     loop {
         display_main_menu();
 
@@ -22,15 +91,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             2 => run_band_selection_demo()?,
             3 => run_weather_comparison()?,
             4 => run_distance_analysis()?,
-            5 => run_mission_scenario_planner()?,
-            6 => run_real_time_simulation()?,
+            5 => run_mission_scenario_planner(&cache)?,
+            6 => {
+                // Waits for Enter itself, as its control reader owns stdin
+                run_real_time_simulation(&run)?;
+                continue;
+            }
             7 => display_band_characteristics(),
             8 => run_educational_mode()?,
+            9 => run_link_budget_report()?,
             0 => {
                 println!("👋 Thanks for using the Frequency Band Simulator!");
                 break;
             }
-            _ => println!("❌ Invalid choice. Please select 0-8."),
+            _ => println!("❌ Invalid choice. Please select 0-9."),
         }
 
         wait_for_enter();
@@ -59,6 +133,7 @@ fn display_main_menu() {
     println!("  6. ⏰ Real-time Simulation - Watch dynamic conditions");
     println!("  7. 📚 Band Characteristics - Learn about each band");
     println!("  8. 🎓 Educational Mode - Guided learning experience");
+    println!("  9. 🧾 Link Budget - Itemized budget of one band");
     println!("  0. 🚪 Exit");
     print!("\nYour choice: ");
     io::stdout().flush().unwrap();
@@ -84,6 +159,8 @@ fn run_quick_demo() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: 35.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     println!("📡 Mission Parameters:");
@@ -143,7 +220,7 @@ fn run_quick_demo() -> Result<(), Box<dyn std::error::Error>> {
         println!("{}", "-".repeat(30));
 
         for band in &bands {
-            let result = band.simulate_transmission(&params, &environment);
+            let result = band.simulate_transmission(&params, &environment)?;
 
             let status = if result.success {
                 if result.transmission_efficiency > 0.8 {
@@ -236,11 +313,13 @@ fn run_band_selection_demo() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: elevation,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     let environment = EnvironmentalConditions {
         rain_rate_mm_hour: rain_rate,
-        cloud_cover_percent: rain_rate * 3.0,
+        cloud_cover_percent: (rain_rate * 3.0).min(100.0),
         atmospheric_pressure_mb: 1013.25,
         temperature_celsius: 20.0,
         humidity_percent: 60.0,
@@ -248,6 +327,11 @@ fn run_band_selection_demo() -> Result<(), Box<dyn std::error::Error>> {
         solar_activity: 0.1,
     };
 
+    if let Err(error) = validate_inputs(&params, &environment) {
+        println!("❌ {}", error);
+        return Ok(());
+    }
+
     println!("\n📊 Comparison Results:");
     println!(
         "{:<12} {:>10} {:>12} {:>10} {:>12} {:>10}",
@@ -260,7 +344,7 @@ fn run_band_selection_demo() -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "-".repeat(75));
 
     for band in selected_bands {
-        let result = band.simulate_transmission(&params, &environment);
+        let result = band.simulate_transmission(&params, &environment)?;
 
         println!(
             "{:<12} {:>10} {:>12.0} {:>10.1} {:>12.1} {:>10.1}%",
@@ -289,6 +373,8 @@ fn run_weather_comparison() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: 30.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     // Different weather conditions
@@ -325,7 +411,7 @@ fn run_weather_comparison() -> Result<(), Box<dyn std::error::Error>> {
         print!("{:<15}", weather_name);
 
         for band in &bands {
-            let result = band.simulate_transmission(&params, &environment);
+            let result = band.simulate_transmission(&params, &environment)?;
             let efficiency = (result.transmission_efficiency * 100.0) as i32;
 
             let color = if efficiency >= 80 {
@@ -379,6 +465,8 @@ fn run_distance_analysis() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 45.0,
                 transmit_power_watts: 100.0,
                 antenna_diameter_meters: 3.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
             };
 
             let environment = EnvironmentalConditions {
@@ -391,7 +479,7 @@ fn run_distance_analysis() -> Result<(), Box<dyn std::error::Error>> {
                 solar_activity: 0.1,
             };
 
-            let result = band.simulate_transmission(&params, &environment);
+            let result = band.simulate_transmission(&params, &environment)?;
             let rate = result.actual_data_rate_mbps as i32;
 
             print!(" {:>7}M", rate);
@@ -408,25 +496,39 @@ fn run_distance_analysis() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
-    println!("\n🛰️ Mission Scenario Planner");
+fn run_mission_scenario_planner(
+    cache: &Arc<SimulationCache>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Planner text comes from the catalog picked by FREQ_SIM_LANG / LANG
+    let catalog = Catalog::from_env();
+    let mission_line = |icon: &str, key: &str| {
+        format!(
+            "{} {} ({})",
+            icon,
+            catalog.text(key),
+            catalog.text(&format!("{}.summary", key))
+        )
+    };
+
+    println!("\n🛰️ {}", catalog.text("planner.title"));
     println!("{}", "=".repeat(40));
 
-    println!("Let's plan your satellite communication mission!\n");
+    println!("{}\n", catalog.text("planner.intro"));
 
     // Mission type selection
-    println!("Select mission type:");
-    println!("  1. 📡 Earth Observation (high data volume)");
-    println!("  2. 🗺️  Navigation/GPS (reliable positioning)");
-    println!("  3. 📞 Communications (voice/data relay)");
-    println!("  4. 🛡️  Military/Defense (secure, reliable)");
-    println!("  5. 🚀 Deep Space (extreme distance)");
+    println!("{}", catalog.text("planner.select_mission"));
+    println!("  1. {}", mission_line("📡", "mission.earth_observation"));
+    println!("  2. {}", mission_line("🗺️ ", "mission.navigation"));
+    println!("  3. {}", mission_line("📞", "mission.communications"));
+    println!("  4. {}", mission_line("🛡️ ", "mission.defense"));
+    println!("  5. {}", mission_line("🚀", "mission.deep_space"));
+    println!("  6. {}", mission_line("🌙", "mission.lunar"));
 
     let mission_type = get_user_choice()?;
 
-    let (mission_name, params, requirements) = match mission_type {
+    let (mission_key, params, weights) = match mission_type {
         1 => (
-            "Earth Observation",
+            "mission.earth_observation",
             TransmissionParameters {
                 distance_km: 800.0,
                 data_size_mb: 1000.0,
@@ -434,12 +536,20 @@ fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 45.0,
                 transmit_power_watts: 150.0,
                 antenna_diameter_meters: 4.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            },
+            CriteriaWeights {
+                availability: 0.25,
+                throughput: 0.4,
+                power: 0.1,
+                latency: 0.05,
+                weather_robustness: 0.2,
             },
-            "High data rate, weather resilience important",
         ),
 
         2 => (
-            "Navigation/GPS",
+            "mission.navigation",
             TransmissionParameters {
                 distance_km: 20000.0,
                 data_size_mb: 1.0,
@@ -447,12 +557,20 @@ fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 60.0,
                 transmit_power_watts: 50.0,
                 antenna_diameter_meters: 1.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            },
+            CriteriaWeights {
+                availability: 0.45,
+                throughput: 0.05,
+                power: 0.3,
+                latency: 0.1,
+                weather_robustness: 0.1,
             },
-            "Global coverage, low power, high reliability",
         ),
 
         3 => (
-            "Communications",
+            "mission.communications",
             TransmissionParameters {
                 distance_km: 36000.0,
                 data_size_mb: 100.0,
@@ -460,12 +578,14 @@ fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 30.0,
                 transmit_power_watts: 200.0,
                 antenna_diameter_meters: 3.5,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
             },
-            "Consistent quality, moderate data rates",
+            CriteriaWeights::default(),
         ),
 
         4 => (
-            "Military/Defense",
+            "mission.defense",
             TransmissionParameters {
                 distance_km: 1500.0,
                 data_size_mb: 200.0,
@@ -473,12 +593,20 @@ fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 25.0,
                 transmit_power_watts: 250.0,
                 antenna_diameter_meters: 5.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            },
+            CriteriaWeights {
+                availability: 0.4,
+                throughput: 0.1,
+                power: 0.1,
+                latency: 0.1,
+                weather_robustness: 0.3,
             },
-            "Anti-jamming, secure, all-weather",
         ),
 
         5 => (
-            "Deep Space",
+            "mission.deep_space",
             TransmissionParameters {
                 distance_km: 150000000.0, // 150 million km
                 data_size_mb: 50.0,
@@ -486,137 +614,266 @@ fn run_mission_scenario_planner() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 45.0,
                 transmit_power_watts: 400.0,
                 antenna_diameter_meters: 70.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            },
+            CriteriaWeights {
+                availability: 0.5,
+                throughput: 0.05,
+                power: 0.35,
+                latency: 0.0,
+                weather_robustness: 0.1,
+            },
+        ),
+
+        6 => (
+            "mission.lunar",
+            TransmissionParameters {
+                distance_km: 384400.0, // Mean Earth-Moon distance
+                data_size_mb: 200.0,
+                required_data_rate_mbps: 2.0,
+                elevation_angle_degrees: 30.0,
+                transmit_power_watts: 100.0,
+                antenna_diameter_meters: 34.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            },
+            CriteriaWeights {
+                availability: 0.4,
+                throughput: 0.2,
+                power: 0.2,
+                latency: 0.05,
+                weather_robustness: 0.15,
             },
-            "Maximum sensitivity, very long range",
         ),
 
         _ => {
-            println!("❌ Invalid mission type.");
+            println!("❌ {}", catalog.text("planner.invalid_mission"));
             return Ok(());
         }
     };
 
-    println!("\n🎯 Mission: {}", mission_name);
-    println!("Requirements: {}", requirements);
-    println!("Distance: {:.0} km", params.distance_km);
-    println!("Data volume: {:.0} MB", params.data_size_mb);
-    println!("Required rate: {:.1} Mbps", params.required_data_rate_mbps);
-
-    // Test environmental conditions
-    let environments = vec![
-        (
-            "Optimal",
-            EnvironmentalConditions {
-                rain_rate_mm_hour: 0.0,
-                cloud_cover_percent: 0.0,
-                atmospheric_pressure_mb: 1013.25,
-                temperature_celsius: 20.0,
-                humidity_percent: 30.0,
-                ionospheric_activity: 0.05,
-                solar_activity: 0.05,
-            },
-        ),
-        (
-            "Typical",
-            EnvironmentalConditions {
-                rain_rate_mm_hour: 2.0,
-                cloud_cover_percent: 40.0,
-                atmospheric_pressure_mb: 1010.0,
-                temperature_celsius: 15.0,
-                humidity_percent: 70.0,
-                ionospheric_activity: 0.2,
-                solar_activity: 0.2,
-            },
-        ),
-        (
-            "Adverse",
-            EnvironmentalConditions {
-                rain_rate_mm_hour: 15.0,
-                cloud_cover_percent: 100.0,
-                atmospheric_pressure_mb: 1000.0,
-                temperature_celsius: 10.0,
-                humidity_percent: 95.0,
-                ionospheric_activity: 0.6,
-                solar_activity: 0.5,
-            },
-        ),
-    ];
-
-    println!("\n📊 Band Suitability Analysis:");
+    let mission_name = catalog.text(mission_key);
+    let requirements_key = format!("{}.requirements", mission_key);
+    let requirements = catalog.text(&requirements_key);
     println!(
-        "{:<12} {:>10} {:>10} {:>10} {:>15}",
-        "Band", "Optimal", "Typical", "Adverse", "Recommendation"
+        "\n🎯 {}",
+        catalog.format("planner.mission", &[("name", &mission_name)])
+    );
+    println!(
+        "{}",
+        catalog.format("planner.requirements", &[("requirements", &requirements)])
+    );
+    println!(
+        "{}",
+        catalog.format(
+            "planner.distance",
+            &[("km", &format!("{:.0}", params.distance_km))]
+        )
+    );
+    println!(
+        "{}",
+        catalog.format(
+            "planner.data_volume",
+            &[("mb", &format!("{:.0}", params.data_size_mb))]
+        )
+    );
+    println!(
+        "{}",
+        catalog.format(
+            "planner.required_rate",
+            &[("mbps", &format!("{:.1}", params.required_data_rate_mbps))]
+        )
     );
-    println!("{}", "-".repeat(70));
 
+    // Shared scoring logic: Optimal, Typical and Adverse conditions
+    let scorer = BandScorer::new(weights).with_cache(Arc::clone(cache));
     let bands = FrequencyBand::get_standard_bands();
-    let mut recommendations = Vec::new();
+    let recommendations = scorer.recommend(&bands, &params)?;
 
-    for band in &bands {
-        print!("{:<12}", format!("{}", band.name));
+    println!("\n📊 {}", catalog.text("planner.analysis"));
+    print!("{:<12}", catalog.text("planner.band"));
+    for criterion in Criterion::ALL {
+        print!(" {:>12}", criterion.localized(&catalog));
+    }
+    println!(
+        " {:>8} {:>15}",
+        catalog.text("planner.overall"),
+        catalog.text("planner.recommendation")
+    );
+    println!("{}", "-".repeat(105));
 
-        let mut scores = Vec::new();
-        for (_, environment) in &environments {
-            let result = band.simulate_transmission(&params, environment);
-            let score = if result.success {
-                (result.transmission_efficiency * 100.0) as i32
-            } else {
-                0
-            };
-            scores.push(score);
-            print!(" {:>9}%", score);
+    for rec in &recommendations {
+        print!("{:<12}", rec.band.to_string());
+        for criterion in Criterion::ALL {
+            print!(" {:>11.0}%", rec.scores.get(criterion) * 100.0);
         }
-
-        // Calculate overall suitability
-        let avg_score = scores.iter().sum::<i32>() as f32 / scores.len() as f32;
-        let min_score = *scores.iter().min().unwrap();
-
-        let recommendation = if avg_score >= 80.0 && min_score >= 60 {
-            "🟢 EXCELLENT"
-        } else if avg_score >= 60.0 && min_score >= 40 {
-            "🟡 GOOD"
-        } else if avg_score >= 40.0 {
-            "🟠 FAIR"
-        } else {
-            "🔴 POOR"
+        let icon = match rec.rating {
+            Rating::Excellent => "🟢",
+            Rating::Good => "🟡",
+            Rating::Fair => "🟠",
+            Rating::Poor => "🔴",
         };
-
-        recommendations.push((band.name, avg_score, recommendation));
-        println!(" {:>15}", recommendation);
+        println!(
+            " {:>7.0}% {:>15}",
+            rec.overall * 100.0,
+            format!("{} {}", icon, rec.rating.localized(&catalog))
+        );
     }
 
-    // Sort recommendations by score
-    recommendations.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-    println!("\n🏆 Recommended Bands for {} Mission:", mission_name);
-    for (i, (band_name, score, status)) in recommendations.iter().take(3).enumerate() {
+    println!(
+        "\n🏆 {}",
+        catalog.format("planner.recommended", &[("mission", &mission_name)])
+    );
+    for (i, rec) in recommendations.iter().take(3).enumerate() {
         println!(
-            "  {}. {} - {:.0}% avg performance {}",
-            i + 1,
-            band_name,
-            score,
-            status
+            "  {}",
+            catalog.format(
+                "planner.recommended_entry",
+                &[
+                    ("rank", &(i + 1)),
+                    ("band", &rec.band),
+                    ("overall", &format!("{:.0}", rec.overall * 100.0)),
+                    ("rating", &rec.rating.localized(&catalog)),
+                ]
+            )
         );
     }
 
+    // Beyond near-Earth range, budget against DSN antennas and light time
+    if params.distance_km >= DEEP_SPACE_THRESHOLD_KM || mission_key == "mission.lunar" {
+        print_deep_space_analysis(&catalog, params.distance_km);
+    }
+
     Ok(())
 }
 
-fn run_real_time_simulation() -> Result<(), Box<dyn std::error::Error>> {
+/// Commands in the session used to compare protocol modes
+const PLANNER_SESSION_COMMANDS: usize = 10;
+
+fn print_deep_space_analysis(catalog: &Catalog, distance_km: f64) {
+    let light_time = deep_space::light_time_s(distance_km);
+    let round_trip = deep_space::round_trip_light_time_s(distance_km);
+    let protocol = ProtocolMode::for_round_trip(round_trip);
+
+    println!("\n🛰️ {}", catalog.text("planner.deep_space"));
+    println!(
+        "{}",
+        catalog.format(
+            "planner.light_time",
+            &[
+                ("seconds", &format!("{:.1}", light_time)),
+                ("minutes", &format!("{:.1}", light_time / 60.0)),
+            ]
+        )
+    );
+    println!(
+        "{}",
+        catalog.format("planner.protocol", &[("mode", &protocol.localized(catalog))])
+    );
+    println!(
+        "{}",
+        catalog.format(
+            "planner.session",
+            &[
+                ("commands", &PLANNER_SESSION_COMMANDS),
+                (
+                    "interactive",
+                    &format!(
+                        "{:.0}",
+                        ProtocolMode::Interactive.session_time_s(
+                            PLANNER_SESSION_COMMANDS,
+                            1.0,
+                            round_trip
+                        )
+                    )
+                ),
+                (
+                    "timer_based",
+                    &format!(
+                        "{:.0}",
+                        ProtocolMode::TimerBased.session_time_s(
+                            PLANNER_SESSION_COMMANDS,
+                            1.0,
+                            round_trip
+                        )
+                    )
+                ),
+            ]
+        )
+    );
+
+    println!(
+        "\n{:<12} {:>14} {:>14}",
+        catalog.text("planner.band"),
+        DsnAntenna::Dsn34m,
+        DsnAntenna::Dsn70m
+    );
+    println!("{}", "-".repeat(42));
+    for band in [BandType::SBand, BandType::XBand, BandType::KaBand] {
+        print!("{:<12}", band.to_string());
+        for ground in DsnAntenna::ALL {
+            let link = DeepSpaceLink {
+                band,
+                ground,
+                ..DeepSpaceLink::default()
+            };
+            match link.evaluate(distance_km) {
+                Some(budget) => print!(" {:>14}", format_rate(budget.max_data_rate_bps)),
+                None => print!(" {:>14}", catalog.text("planner.not_supported")),
+            }
+        }
+        println!();
+    }
+    println!("{}", catalog.text("planner.deep_space_link"));
+}
+
+/// Data rate with a unit suited to its size
+fn format_rate(bps: f64) -> String {
+    if bps >= 1e6 {
+        format!("{:.1} Mbps", bps / 1e6)
+    } else if bps >= 1e3 {
+        format!("{:.1} kbps", bps / 1e3)
+    } else {
+        format!("{:.0} bps", bps)
+    }
+}
+
+fn run_real_time_simulation(run: &RunSettings) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n⏰ Real-time Simulation");
     println!("{}", "=".repeat(35));
     println!("Watch how band performance changes with dynamic weather!");
-    println!("Press Ctrl+C to stop the simulation.\n");
+    println!("Adjust the run as it goes, typing a command and Enter:");
+    println!("  • rain <mm/h> | rain auto  hold a rain rate, or return to the weather");
+    println!("  • power <W>                transmit power");
+    println!("  • distance <km>            distance to the satellite");
+    println!("  A signed value steps, e.g. power -20; q stops the simulation.\n");
 
     let bands = FrequencyBand::get_standard_bands();
 
-    let params = TransmissionParameters {
-        distance_km: 1000.0,
-        data_size_mb: 100.0,
-        required_data_rate_mbps: 200.0,
-        elevation_angle_degrees: 35.0,
-        transmit_power_watts: 100.0,
-        antenna_diameter_meters: 3.0,
+    let mut tuning = LiveTuning::new(TransmissionParameters {
+        distance_km: run.distance_km,
+        data_size_mb: run.data_mb,
+        required_data_rate_mbps: run.rate_mbps,
+        elevation_angle_degrees: run.elevation_deg,
+        transmit_power_watts: run.power_w,
+        antenna_diameter_meters: run.antenna_m,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
+    });
+
+    // Rain comes and goes as a Markov chain of dry, stratiform and
+    // convective spells
+    let mut weather = MarkovRainModel::default();
+    let mut rng = StdRng::from_entropy();
+    let base = EnvironmentalConditions {
+        rain_rate_mm_hour: 0.0,
+        cloud_cover_percent: run.cloud_percent,
+        atmospheric_pressure_mb: 1013.25,
+        temperature_celsius: 20.0,
+        humidity_percent: 50.0,
+        ionospheric_activity: 0.2,
+        solar_activity: 0.2,
     };
 
     let mut time = 0.0;
@@ -628,19 +885,40 @@ fn run_real_time_simulation() -> Result<(), Box<dyn std::error::Error>> {
     );
     println!("{}", "-".repeat(75));
 
-    for step in 0..20 {
+    let stop = CancelToken::new();
+    let commands = spawn_control_reader(stop.clone());
+    let mut stopped = false;
+
+    for _step in 0..20 {
         // 10 hours simulation
         time += time_step;
 
-        // Simulate changing weather (sine wave pattern with random variations)
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let modelled = weather.conditions_at(time * 3600.0, &base, &mut rng);
 
-        let base_rain = 5.0
-            * (time * 2.0 * std::f64::consts::PI / 24.0)
-                .sin()
-                .max(0.0_f64);
-        let rain_rate = base_rain + rng.gen_range(-2.0..2.0).max(0.0_f64);
+        // Commands typed since the last step take effect from this one
+        for line in commands.try_iter() {
+            if is_stop_command(&line) {
+                stopped = true;
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line
+                .parse::<Adjustment>()
+                .and_then(|adjustment| tuning.apply(time, adjustment, &modelled))
+            {
+                Ok(annotation) => println!("  ▶ {}", annotation),
+                Err(error) => println!("  ✖ {}", error),
+            }
+        }
+        if stopped {
+            println!("  ■ {:.1}h stopped", time);
+            break;
+        }
+
+        let environment = tuning.environment(&modelled);
+        let rain_rate = environment.rain_rate_mm_hour;
 
         let weather_desc = if rain_rate < 0.5 {
             "Clear"
@@ -652,20 +930,10 @@ fn run_real_time_simulation() -> Result<(), Box<dyn std::error::Error>> {
             "Heavy"
         };
 
-        let environment = EnvironmentalConditions {
-            rain_rate_mm_hour: rain_rate,
-            cloud_cover_percent: rain_rate * 8.0,
-            atmospheric_pressure_mb: 1013.25 - rain_rate,
-            temperature_celsius: 20.0 - rain_rate * 0.5,
-            humidity_percent: 50.0 + rain_rate * 3.0,
-            ionospheric_activity: 0.1 + rng.gen_range(0.0..0.3),
-            solar_activity: 0.1 + rng.gen_range(0.0..0.2),
-        };
-
         print!("{:>5.1}h {:>10}", time, weather_desc);
 
         for band in &bands {
-            let result = band.simulate_transmission(&params, &environment);
+            let result = band.simulate_transmission(tuning.parameters(), &environment)?;
             let rate = (result.actual_data_rate_mbps / 10.0) as i32; // Scale for display
             print!(" {:>9}M", rate);
         }
@@ -675,14 +943,61 @@ fn run_real_time_simulation() -> Result<(), Box<dyn std::error::Error>> {
         std::thread::sleep(std::time::Duration::from_millis(500));
     }
 
+    if !tuning.annotations().is_empty() {
+        println!("\n🎛️  Adjustments:");
+        for annotation in tuning.annotations() {
+            println!("  • {}", annotation);
+        }
+    }
+
     println!("\n💡 Observations:");
     println!("  • Weather patterns significantly affect higher frequency bands");
     println!("  • S-Band and UHF-Band maintain consistent performance");
     println!("  • Ka-Band shows highest variability with weather changes");
 
+    // The reader is blocked on the next line unless a stop command ended
+    // it; the Enter answering this prompt releases it
+    stop.cancel();
+    print!("\nPress Enter to continue...");
+    io::stdout().flush()?;
+    let reader_ended = stopped || commands.iter().any(|line| is_stop_command(&line));
+    if reader_ended {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+    }
+
     Ok(())
 }
 
+/// Whether a line typed during the real-time simulation stops it.
+fn is_stop_command(line: &str) -> bool {
+    matches!(line.trim(), "q" | "quit")
+}
+
+/// Read lines typed during the real-time simulation on a thread of their
+/// own, so the simulation steps on without waiting for input.
+///
+/// The reader ends after a stop command, at end of input, or after the
+/// first line read once `stop` is cancelled.
+fn spawn_control_reader(stop: CancelToken) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = io::stdin();
+        loop {
+            let mut line = String::new();
+            match stdin.lock().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let done = is_stop_command(&line) || stop.is_cancelled();
+            if sender.send(line).is_err() || done {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
 fn display_band_characteristics() {
     println!("\n📚 Frequency Band Characteristics");
     println!("{}", "=".repeat(50));
@@ -702,7 +1017,6 @@ fn display_band_characteristics() {
             BandType::SBand => "Telemetry, tracking, and command (TT&C)",
             BandType::XBand => "Medium-speed data and radar",
             BandType::UHFBand => "Emergency and backup communications",
-            _ => "General communications",
         };
         println!("   Purpose: {}", purpose);
 
@@ -760,7 +1074,6 @@ fn display_band_characteristics() {
                 vec!["Excellent reliability", "Low power", "All-weather"],
                 vec!["Very limited bandwidth", "Large antennas", "Interference"],
             ),
-            _ => (vec![], vec![]),
         };
 
         println!("   Advantages: {}", pros_cons.0.join(", "));
@@ -788,6 +1101,8 @@ fn run_educational_mode() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: 45.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     let clear_env = EnvironmentalConditions {
@@ -806,7 +1121,7 @@ fn run_educational_mode() -> Result<(), Box<dyn std::error::Error>> {
     for &distance in &[500.0, 1000.0, 2000.0, 4000.0] {
         let mut test_params = params.clone();
         test_params.distance_km = distance;
-        let result = s_band.simulate_transmission(&test_params, &clear_env);
+        let result = s_band.simulate_transmission(&test_params, &clear_env)?;
         println!(
             "  {:.0} km: {:.0} Mbps",
             distance, result.actual_data_rate_mbps
@@ -834,8 +1149,8 @@ fn run_educational_mode() -> Result<(), Box<dyn std::error::Error>> {
 
     let bands = FrequencyBand::get_standard_bands();
     for band in &bands {
-        let clear_result = band.simulate_transmission(&params, &clear_env);
-        let rain_result = band.simulate_transmission(&params, &rainy_env);
+        let clear_result = band.simulate_transmission(&params, &clear_env)?;
+        let rain_result = band.simulate_transmission(&params, &rainy_env)?;
         let degradation = ((clear_result.actual_data_rate_mbps - rain_result.actual_data_rate_mbps)
             / clear_result.actual_data_rate_mbps
             * 100.0) as i32;
@@ -888,6 +1203,54 @@ fn run_educational_mode() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn run_link_budget_report() -> Result<(), Box<dyn std::error::Error>> {
+    println!("\n🧾 Link Budget Report");
+    println!("{}", "=".repeat(40));
+
+    let catalog = Catalog::from_env();
+    let bands = FrequencyBand::get_standard_bands();
+
+    println!("Available frequency bands:");
+    for (i, band) in bands.iter().enumerate() {
+        println!("  {}. {}", i + 1, band.name);
+    }
+
+    let choice = get_parameter("Band", 1.0)? as usize;
+    let Some(band) = choice.checked_sub(1).and_then(|i| bands.get(i)) else {
+        println!("❌ Invalid band.");
+        return Ok(());
+    };
+
+    let params = TransmissionParameters {
+        distance_km: get_parameter("Distance (km)", 1000.0)?,
+        data_size_mb: 100.0,
+        required_data_rate_mbps: get_parameter("Data rate (Mbps)", 100.0)?,
+        elevation_angle_degrees: get_parameter("Elevation angle (degrees)", 30.0)?,
+        transmit_power_watts: get_parameter("Transmit power (W)", 100.0)?,
+        antenna_diameter_meters: get_parameter("Antenna diameter (m)", 3.0)?,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
+    };
+    let rain_rate = get_parameter("Rain rate (mm/hour)", 0.0)?;
+
+    let environment = EnvironmentalConditions {
+        rain_rate_mm_hour: rain_rate,
+        cloud_cover_percent: (rain_rate * 3.0).min(100.0),
+        atmospheric_pressure_mb: 1013.25,
+        temperature_celsius: 20.0,
+        humidity_percent: 60.0,
+        ionospheric_activity: 0.2,
+        solar_activity: 0.1,
+    };
+
+    match band.compute_link_budget(&params, &environment, &MarginPolicy::default()) {
+        Ok(budget) => println!("\n{}", budget.localized(&catalog)),
+        Err(error) => println!("❌ {}", error),
+    }
+
+    Ok(())
+}
+
 fn get_parameter(prompt: &str, default: f64) -> Result<f64, Box<dyn std::error::Error>> {
     print!("{} [{}]: ", prompt, default);
     io::stdout().flush()?;
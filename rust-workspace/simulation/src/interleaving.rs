@@ -0,0 +1,643 @@
+//! Interleaving and Randomization Module
+//!
+//! Reed-Solomon codes correct a fixed number of symbol errors per codeword,
+//! so they handle scattered errors well and bursts badly: a rain fade or
+//! scintillation dip that wipes out 30 consecutive bytes sinks an RS(255,223)
+//! codeword however clean the rest of it was. This module models the coding
+//! layers between FEC and framing that fix that, in transmit order:
+//!
+//! - **Block interleaving**: `depth` codewords are sent symbol by symbol in
+//!   turn (CCSDS 131.0-B interleaving depth I), so a burst of `b` bytes
+//!   costs each codeword only about `b / depth` of them
+//!   ([`BlockInterleaver`]).
+//! - **Pseudo-randomization**: the frame is XORed with the CCSDS
+//!   pseudo-random sequence, h(x) = x⁸ + x⁷ + x⁵ + x³ + 1 seeded with all
+//!   ones, so long runs of identical bits cannot cost the receiver its
+//!   symbol lock ([`randomize`]).
+//!
+//! Burst errors come from a [`GilbertElliott`] channel: a good state with
+//! rare errors and a bad state with frequent ones, with presets per band.
+//! Interleaver depth is configured per band ([`InterleaverConfig`]), and
+//! [`measure_residual_errors`] runs a Monte Carlo study of frames through
+//! the whole chain, reporting the residual frame and codeword error rates
+//! that remain after decoding ([`ResidualErrorReport`]).
+//!
+//! The decoder is modeled by its correction capability: a codeword decodes
+//! when it has at most `t` symbol errors, as a bounded-distance RS decoder
+//! does. Codeword contents are not RS-encoded.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (burst error channel model)
+//! - REQ-PF-002: Data Transfer Rates (residual frame error rate per band)
+
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::monte_carlo::MonteCarlo;
+use crate::BandType;
+
+/// Interleaving depths allowed by CCSDS 131.0-B for Reed-Solomon codes.
+pub const ALLOWED_DEPTHS: [usize; 6] = [1, 2, 3, 4, 5, 8];
+
+/// Period of the CCSDS pseudo-random sequence, bytes.
+pub const RANDOMIZER_PERIOD: usize = 255;
+
+/// Error configuring the coding layers or the channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodingError {
+    /// Interleaving depth not in [`ALLOWED_DEPTHS`].
+    InvalidDepth(usize),
+    /// Channel model parameters are not usable.
+    InvalidChannel(&'static str),
+    /// Frame or codeword block of the wrong length.
+    FrameLength {
+        /// Bytes expected.
+        expected: usize,
+        /// Bytes given.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for CodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodingError::InvalidDepth(depth) => write!(
+                f,
+                "interleaving depth {} is not one of {:?}",
+                depth, ALLOWED_DEPTHS
+            ),
+            CodingError::InvalidChannel(reason) => write!(f, "invalid channel model: {}", reason),
+            CodingError::FrameLength { expected, actual } => {
+                write!(f, "frame of {} bytes, expected {}", actual, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodingError {}
+
+// ─── Pseudo-Randomizer ───────────────────────────────────────────────────────
+
+/// One period of the CCSDS pseudo-random sequence.
+///
+/// Generated by the LFSR h(x) = x⁸ + x⁷ + x⁵ + x³ + 1 from the all-ones
+/// state; starts `FF 48 0E C0 9A`.
+pub fn pseudo_random_sequence() -> [u8; RANDOMIZER_PERIOD] {
+    let mut sequence = [0u8; RANDOMIZER_PERIOD];
+    let mut state: u8 = 0xFF;
+    for byte in sequence.iter_mut() {
+        for _ in 0..8 {
+            *byte = (*byte << 1) | (state & 1);
+            let feedback = (state ^ (state >> 3) ^ (state >> 5) ^ (state >> 7)) & 1;
+            state = (state >> 1) | (feedback << 7);
+        }
+    }
+    sequence
+}
+
+/// XOR a frame with the pseudo-random sequence from its first byte.
+///
+/// - **ID**: FN-ILV-002
+/// - **Requirement**: Guarantee bit transitions on the channel whatever the
+///   data, as CCSDS 131.0-B pseudo-randomization does.
+/// - **Side Effects**: Modifies `frame` in place. The operation is its own
+///   inverse: applying it twice restores the frame.
+pub fn randomize(frame: &mut [u8]) {
+    let sequence = pseudo_random_sequence();
+    for (byte, mask) in frame.iter_mut().zip(sequence.iter().cycle()) {
+        *byte ^= mask;
+    }
+}
+
+// ─── Block Interleaver ───────────────────────────────────────────────────────
+
+/// Symbol-by-symbol interleaver of `depth` codewords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockInterleaver {
+    depth: usize,
+}
+
+impl BlockInterleaver {
+    /// Interleaver of `depth` codewords; `depth` must be in
+    /// [`ALLOWED_DEPTHS`].
+    pub fn new(depth: usize) -> Result<Self, CodingError> {
+        if ALLOWED_DEPTHS.contains(&depth) {
+            Ok(Self { depth })
+        } else {
+            Err(CodingError::InvalidDepth(depth))
+        }
+    }
+
+    /// Codewords interleaved together.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Interleave `depth` codewords laid end to end.
+    ///
+    /// - **ID**: FN-ILV-001
+    /// - **Requirement**: Spread a channel burst over every codeword of the
+    ///   frame so each keeps within its correction capability.
+    /// - **Inputs**:
+    ///   - `codewords`: `depth` codewords of equal length, one after another.
+    /// - **Outputs**: Symbol `j` of codeword `i` at position `j * depth + i`.
+    /// - **Failure Modes**: `FrameLength` unless the length is a multiple of
+    ///   the depth.
+    pub fn interleave(&self, codewords: &[u8]) -> Result<Vec<u8>, CodingError> {
+        let n = self.codeword_len(codewords.len())?;
+        let mut frame = vec![0u8; codewords.len()];
+        for (index, &symbol) in codewords.iter().enumerate() {
+            let (codeword, position) = (index / n, index % n);
+            frame[position * self.depth + codeword] = symbol;
+        }
+        Ok(frame)
+    }
+
+    /// Undo [`BlockInterleaver::interleave`].
+    pub fn deinterleave(&self, frame: &[u8]) -> Result<Vec<u8>, CodingError> {
+        let n = self.codeword_len(frame.len())?;
+        let mut codewords = vec![0u8; frame.len()];
+        for (index, &symbol) in frame.iter().enumerate() {
+            let (position, codeword) = (index / self.depth, index % self.depth);
+            codewords[codeword * n + position] = symbol;
+        }
+        Ok(codewords)
+    }
+
+    /// Length of each codeword in a block of `len` bytes.
+    fn codeword_len(&self, len: usize) -> Result<usize, CodingError> {
+        if len.is_multiple_of(self.depth) {
+            Ok(len / self.depth)
+        } else {
+            Err(CodingError::FrameLength {
+                expected: (len / self.depth + 1) * self.depth,
+                actual: len,
+            })
+        }
+    }
+}
+
+/// Interleaving depth per band.
+///
+/// Higher bands see longer rain and scintillation bursts, so they default
+/// to deeper interleaving at the cost of a longer frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterleaverConfig {
+    /// UHF depth.
+    pub uhf: usize,
+    /// S-band depth.
+    pub s_band: usize,
+    /// X-band depth.
+    pub x_band: usize,
+    /// K-band depth.
+    pub k_band: usize,
+    /// Ka-band depth.
+    pub ka_band: usize,
+}
+
+impl Default for InterleaverConfig {
+    fn default() -> Self {
+        Self {
+            uhf: 1,
+            s_band: 2,
+            x_band: 4,
+            k_band: 5,
+            ka_band: 8,
+        }
+    }
+}
+
+impl InterleaverConfig {
+    /// Same depth on every band.
+    pub fn uniform(depth: usize) -> Result<Self, CodingError> {
+        BlockInterleaver::new(depth)?;
+        Ok(Self {
+            uhf: depth,
+            s_band: depth,
+            x_band: depth,
+            k_band: depth,
+            ka_band: depth,
+        })
+    }
+
+    /// Configured depth of `band`.
+    pub fn depth(&self, band: BandType) -> usize {
+        match band {
+            BandType::UHFBand => self.uhf,
+            BandType::SBand => self.s_band,
+            BandType::XBand => self.x_band,
+            BandType::KBand => self.k_band,
+            BandType::KaBand => self.ka_band,
+        }
+    }
+
+    /// Change the depth of one band.
+    pub fn with_depth(mut self, band: BandType, depth: usize) -> Result<Self, CodingError> {
+        BlockInterleaver::new(depth)?;
+        *match band {
+            BandType::UHFBand => &mut self.uhf,
+            BandType::SBand => &mut self.s_band,
+            BandType::XBand => &mut self.x_band,
+            BandType::KBand => &mut self.k_band,
+            BandType::KaBand => &mut self.ka_band,
+        } = depth;
+        Ok(self)
+    }
+
+    /// Check every depth, e.g. after loading the configuration from JSON.
+    pub fn validate(&self) -> Result<(), CodingError> {
+        for depth in [
+            self.uhf,
+            self.s_band,
+            self.x_band,
+            self.k_band,
+            self.ka_band,
+        ] {
+            BlockInterleaver::new(depth)?;
+        }
+        Ok(())
+    }
+}
+
+// ─── Burst Error Channel ─────────────────────────────────────────────────────
+
+/// Two-state Gilbert-Elliott burst error channel.
+///
+/// Transition probabilities and error rates are per bit. The mean burst
+/// lasts `1 / p_bad_to_good` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GilbertElliott {
+    /// Probability of entering the bad state after a good bit.
+    pub p_good_to_bad: f64,
+    /// Probability of returning to the good state after a bad bit.
+    pub p_bad_to_good: f64,
+    /// Bit error rate in the good state.
+    pub ber_good: f64,
+    /// Bit error rate in the bad state.
+    pub ber_bad: f64,
+}
+
+impl GilbertElliott {
+    /// Channel with bursts of `mean_burst_bits` starting on average every
+    /// `mean_gap_bits` of good channel.
+    pub fn with_bursts(
+        mean_burst_bits: f64,
+        mean_gap_bits: f64,
+        ber_good: f64,
+        ber_bad: f64,
+    ) -> Self {
+        Self {
+            p_good_to_bad: 1.0 / mean_gap_bits,
+            p_bad_to_good: 1.0 / mean_burst_bits,
+            ber_good,
+            ber_bad,
+        }
+    }
+
+    /// Typical burst behaviour of a band: UHF and S-band see short, rare
+    /// bursts; Ka-band long, frequent ones from rain and scintillation.
+    pub fn for_band(band: BandType) -> Self {
+        let (burst_bits, gap_bits) = match band {
+            BandType::UHFBand => (16.0, 100_000.0),
+            BandType::SBand => (32.0, 100_000.0),
+            BandType::XBand => (64.0, 50_000.0),
+            BandType::KBand => (128.0, 40_000.0),
+            BandType::KaBand => (256.0, 30_000.0),
+        };
+        Self::with_bursts(burst_bits, gap_bits, 1e-6, 0.3)
+    }
+
+    /// Check the probabilities.
+    pub fn validate(&self) -> Result<(), CodingError> {
+        let transition = |p: f64| p > 0.0 && p <= 1.0;
+        let rate = |p: f64| (0.0..=1.0).contains(&p);
+        if !transition(self.p_good_to_bad) || !transition(self.p_bad_to_good) {
+            return Err(CodingError::InvalidChannel(
+                "transition probabilities must be in (0, 1]",
+            ));
+        }
+        if !rate(self.ber_good) || !rate(self.ber_bad) {
+            return Err(CodingError::InvalidChannel(
+                "bit error rates must be in [0, 1]",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Long-run fraction of bits sent in the bad state.
+    pub fn bad_state_fraction(&self) -> f64 {
+        self.p_good_to_bad / (self.p_good_to_bad + self.p_bad_to_good)
+    }
+
+    /// Long-run bit error rate.
+    pub fn mean_bit_error_rate(&self) -> f64 {
+        let bad = self.bad_state_fraction();
+        (1.0 - bad) * self.ber_good + bad * self.ber_bad
+    }
+
+    /// Flip bits of `data` as the channel would, returning the bits flipped.
+    ///
+    /// The channel starts in a state drawn from its long-run distribution.
+    /// State sojourns and error gaps are drawn as geometric run lengths
+    /// rather than bit by bit.
+    pub fn corrupt(&self, data: &mut [u8], rng: &mut StdRng) -> usize {
+        let total_bits = data.len() * 8;
+        let mut bad = rng.gen_bool(self.bad_state_fraction().clamp(0.0, 1.0));
+        let mut bit = 0;
+        let mut flipped = 0;
+        while bit < total_bits {
+            let (p_leave, ber) = if bad {
+                (self.p_bad_to_good, self.ber_bad)
+            } else {
+                (self.p_good_to_bad, self.ber_good)
+            };
+            let end = bit
+                .saturating_add(geometric(rng, p_leave).saturating_add(1))
+                .min(total_bits);
+            let mut position = bit.saturating_add(geometric(rng, ber));
+            while position < end {
+                data[position / 8] ^= 0x80 >> (position % 8);
+                flipped += 1;
+                position = position.saturating_add(geometric(rng, ber).saturating_add(1));
+            }
+            bit = end;
+            bad = !bad;
+        }
+        flipped
+    }
+}
+
+/// Failures before the first success of trials succeeding with probability
+/// `p`.
+fn geometric(rng: &mut StdRng, p: f64) -> usize {
+    if p <= 0.0 {
+        usize::MAX
+    } else if p >= 1.0 {
+        0
+    } else {
+        let u: f64 = rng.gen();
+        ((1.0 - u).ln() / (1.0 - p).ln()) as usize
+    }
+}
+
+// ─── Coding Chain ────────────────────────────────────────────────────────────
+
+/// Block code, modeled by its correction capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCode {
+    /// Codeword length, symbols.
+    pub n: usize,
+    /// Information symbols per codeword.
+    pub k: usize,
+    /// Symbol errors corrected per codeword.
+    pub t: usize,
+}
+
+/// CCSDS Reed-Solomon (255,223) code over 8-bit symbols.
+pub const RS_255_223: BlockCode = BlockCode {
+    n: 255,
+    k: 223,
+    t: 16,
+};
+
+impl BlockCode {
+    /// Code rate k/n.
+    pub fn rate(&self) -> f64 {
+        self.k as f64 / self.n as f64
+    }
+
+    /// Whether a codeword with `symbol_errors` errors decodes.
+    pub fn corrects(&self, symbol_errors: usize) -> bool {
+        symbol_errors <= self.t
+    }
+
+    /// Whether a codeword with `errors` symbol errors and `erasures` symbols
+    /// marked unreliable decodes: each erasure costs one parity symbol, each
+    /// error two.
+    pub fn corrects_with_erasures(&self, errors: usize, erasures: usize) -> bool {
+        2 * errors + erasures <= self.n - self.k
+    }
+}
+
+/// FEC, interleaving and randomization of one band's frames.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CodingChain {
+    /// Block code applied before interleaving.
+    pub code: BlockCode,
+    /// Interleaver between FEC and randomization.
+    pub interleaver: BlockInterleaver,
+    /// Whether frames are pseudo-randomized.
+    pub randomize: bool,
+}
+
+impl CodingChain {
+    /// RS(255,223) with pseudo-randomization and the configured depth of
+    /// `band`.
+    pub fn for_band(config: &InterleaverConfig, band: BandType) -> Result<Self, CodingError> {
+        Ok(Self {
+            code: RS_255_223,
+            interleaver: BlockInterleaver::new(config.depth(band))?,
+            randomize: true,
+        })
+    }
+
+    /// Bytes of one frame: `depth` codewords.
+    pub fn frame_len(&self) -> usize {
+        self.code.n * self.interleaver.depth()
+    }
+
+    /// Interleave and randomize `depth` codewords into a frame.
+    pub fn encode(&self, codewords: &[u8]) -> Result<Vec<u8>, CodingError> {
+        self.check_len(codewords.len())?;
+        let mut frame = self.interleaver.interleave(codewords)?;
+        if self.randomize {
+            randomize(&mut frame);
+        }
+        Ok(frame)
+    }
+
+    /// Derandomize and deinterleave a received frame into its codewords.
+    pub fn decode(&self, frame: &[u8]) -> Result<Vec<u8>, CodingError> {
+        self.check_len(frame.len())?;
+        let mut frame = frame.to_vec();
+        if self.randomize {
+            randomize(&mut frame);
+        }
+        self.interleaver.deinterleave(&frame)
+    }
+
+    /// Send one frame of random codewords over `channel`.
+    pub fn transmit_frame(&self, channel: &GilbertElliott, rng: &mut StdRng) -> FrameOutcome {
+        let mut codewords = vec![0u8; self.frame_len()];
+        rng.fill(codewords.as_mut_slice());
+
+        let mut frame = self
+            .encode(&codewords)
+            .unwrap_or_else(|error| unreachable!("frame sized by the chain: {}", error));
+        let bit_errors = channel.corrupt(&mut frame, rng);
+        let received = self
+            .decode(&frame)
+            .unwrap_or_else(|error| unreachable!("frame sized by the chain: {}", error));
+
+        let failed_codewords = codewords
+            .chunks(self.code.n)
+            .zip(received.chunks(self.code.n))
+            .filter(|(sent, got)| {
+                let symbol_errors = sent.iter().zip(got.iter()).filter(|(a, b)| a != b).count();
+                !self.code.corrects(symbol_errors)
+            })
+            .count();
+        FrameOutcome {
+            bit_errors,
+            failed_codewords,
+        }
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), CodingError> {
+        if len == self.frame_len() {
+            Ok(())
+        } else {
+            Err(CodingError::FrameLength {
+                expected: self.frame_len(),
+                actual: len,
+            })
+        }
+    }
+}
+
+/// Result of sending one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameOutcome {
+    /// Bits the channel flipped.
+    pub bit_errors: usize,
+    /// Codewords left with more errors than the code corrects.
+    pub failed_codewords: usize,
+}
+
+// ─── Residual Error Study ────────────────────────────────────────────────────
+
+/// Residual error rates measured after decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResidualErrorReport {
+    /// Band whose channel was simulated.
+    pub band: BandType,
+    /// Interleaving depth used.
+    pub depth: usize,
+    /// Frames sent.
+    pub frames: usize,
+    /// Frames with at least one undecodable codeword.
+    pub frame_errors: usize,
+    /// Codewords sent.
+    pub codewords: usize,
+    /// Codewords that did not decode.
+    pub codeword_errors: usize,
+    /// Bits sent over the channel.
+    pub channel_bits: usize,
+    /// Bits the channel flipped.
+    pub channel_bit_errors: usize,
+}
+
+impl ResidualErrorReport {
+    /// Fraction of frames lost after decoding.
+    pub fn frame_error_rate(&self) -> f64 {
+        ratio(self.frame_errors, self.frames)
+    }
+
+    /// Fraction of codewords lost after decoding; comparable across depths.
+    pub fn codeword_error_rate(&self) -> f64 {
+        ratio(self.codeword_errors, self.codewords)
+    }
+
+    /// Bit error rate of the channel before decoding.
+    pub fn channel_bit_error_rate(&self) -> f64 {
+        ratio(self.channel_bit_errors, self.channel_bits)
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+impl fmt::Display for ResidualErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<9} I={} channel BER {:.2e}, residual FER {:.2e} ({}/{}), codeword error rate {:.2e}",
+            self.band.to_string(),
+            self.depth,
+            self.channel_bit_error_rate(),
+            self.frame_error_rate(),
+            self.frame_errors,
+            self.frames,
+            self.codeword_error_rate()
+        )
+    }
+}
+
+/// Measure the residual error rates of `chain` over `channel`.
+///
+/// - **ID**: FN-ILV-003
+/// - **Requirement**: Report the frame error rate left after FEC,
+///   interleaving and randomization for a band's burst channel.
+/// - **Inputs**:
+///   - `band`: Band the report is labelled with.
+///   - `chain`: Coding layers under test.
+///   - `channel`: Burst error channel.
+///   - `study`: One trial per frame; reproducible from its seed.
+/// - **Outputs**: Frame, codeword and channel bit error counts.
+/// - **Failure Modes**: `InvalidChannel` for an unusable channel model.
+pub fn measure_residual_errors(
+    band: BandType,
+    chain: &CodingChain,
+    channel: &GilbertElliott,
+    study: &MonteCarlo,
+) -> Result<ResidualErrorReport, CodingError> {
+    channel.validate()?;
+    let outcomes = study.run(|_, rng| chain.transmit_frame(channel, rng));
+
+    let depth = chain.interleaver.depth();
+    Ok(ResidualErrorReport {
+        band,
+        depth,
+        frames: outcomes.len(),
+        frame_errors: outcomes.iter().filter(|o| o.failed_codewords > 0).count(),
+        codewords: outcomes.len() * depth,
+        codeword_errors: outcomes.iter().map(|o| o.failed_codewords).sum(),
+        channel_bits: outcomes.len() * chain.frame_len() * 8,
+        channel_bit_errors: outcomes.iter().map(|o| o.bit_errors).sum(),
+    })
+}
+
+/// Residual error rates of every band without interleaving and at its
+/// configured depth, for the simulation report.
+pub fn run_burst_error_demo(
+    config: &InterleaverConfig,
+    study: &MonteCarlo,
+) -> Result<Vec<(ResidualErrorReport, ResidualErrorReport)>, CodingError> {
+    config.validate()?;
+    [
+        BandType::UHFBand,
+        BandType::SBand,
+        BandType::XBand,
+        BandType::KBand,
+        BandType::KaBand,
+    ]
+    .into_iter()
+    .map(|band| {
+        let channel = GilbertElliott::for_band(band);
+        let plain = CodingChain::for_band(&InterleaverConfig::uniform(1)?, band)?;
+        let interleaved = CodingChain::for_band(config, band)?;
+        Ok((
+            measure_residual_errors(band, &plain, &channel, study)?,
+            measure_residual_errors(band, &interleaved, &channel, study)?,
+        ))
+    })
+    .collect()
+}
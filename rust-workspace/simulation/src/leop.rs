@@ -0,0 +1,345 @@
+//! Launch and Early Orbit Phase (LEOP) Scenario Module
+//!
+//! Provides a canned LEOP timeline for integration testing and operator
+//! training. The scenario scripts the first hours after separation:
+//!
+//! 1. **Separation**        — spacecraft released, transmitter silent
+//! 2. **Detumble**          — autonomous rate damping, no ground contact
+//! 3. **First acquisition** — first pass on the low-gain UHF path
+//! 4. **Deployment**        — solar array and high-gain antenna deployment
+//! 5. **Commissioning**     — checkout pass on each higher band in turn
+//!
+//! Running the scenario produces, for every event, the link conditions seen
+//! by the ground and the commands operators are expected to send. Weather is
+//! fixed unless the scenario selects a [`WeatherModel`], in which case each
+//! event sees the model's conditions at its time.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-004: Deployable mechanism control (`Deploy` command)
+//! - REQ-FN-007: Multi-Band Communication (commissioning band checkouts)
+//! - REQ-FN-008: Frequency Band Simulation (link conditions per event)
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::deployment::{AntennaDeployment, DeploymentConfig, DeploymentState};
+use crate::margin::MarginPolicy;
+use crate::validation::ValidationError;
+use crate::weather::WeatherModel;
+use crate::{
+    BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult,
+};
+
+/// Mission phase an event belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LeopPhase {
+    /// Separation from the launch vehicle.
+    Separation,
+    /// Autonomous detumbling; no ground contact.
+    Detumble,
+    /// First ground contact on the low-gain UHF path.
+    FirstAcquisition,
+    /// Deployment of solar array and high-gain antenna.
+    Deployment,
+    /// Checkout of each communication band.
+    Commissioning,
+}
+
+/// Command the operators are expected to send at an event.
+///
+/// Each variant corresponds to the `SpaceCommand` named in `command_name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExpectedCommand {
+    /// Request a health telemetry snapshot.
+    RequestTelemetry,
+    /// Synchronise the onboard clock.
+    UpdateTime,
+    /// Deploy the solar array.
+    DeploySolarArray,
+    /// Deploy the high-gain antenna.
+    DeployAntenna {
+        /// Commanded hinge angle, degrees.
+        deployment_angle_deg: f64,
+        /// Commanded hinge rate, degrees/second.
+        deployment_rate_deg_s: f64,
+    },
+    /// Switch the link to another band.
+    ReconfigureComm {
+        /// Band to switch to.
+        band: BandType,
+    },
+}
+
+impl ExpectedCommand {
+    /// Name of the corresponding `SpaceCommand` variant.
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            ExpectedCommand::RequestTelemetry => "RequestTelemetry",
+            ExpectedCommand::UpdateTime => "UpdateTime",
+            ExpectedCommand::DeploySolarArray | ExpectedCommand::DeployAntenna { .. } => "Deploy",
+            ExpectedCommand::ReconfigureComm { .. } => "ReconfigureComm",
+        }
+    }
+}
+
+/// Geometry of a ground contact at an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactGeometry {
+    /// Band used for the contact.
+    pub band: BandType,
+    /// Slant range to the ground station, km.
+    pub distance_km: f64,
+    /// Elevation above the station horizon, degrees.
+    pub elevation_angle_degrees: f64,
+}
+
+/// A scripted event in the LEOP timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeopEvent {
+    /// Time since separation, seconds.
+    pub time_s: f64,
+    /// Phase the event belongs to.
+    pub phase: LeopPhase,
+    /// Operator-facing description.
+    pub description: String,
+    /// Ground contact at this event, if any.
+    pub contact: Option<ContactGeometry>,
+    /// Commands operators are expected to send.
+    pub expected_commands: Vec<ExpectedCommand>,
+}
+
+/// Outcome of one event when the scenario is run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeopStep {
+    /// The scripted event.
+    pub event: LeopEvent,
+    /// Antenna deployment state at the event time.
+    pub deployment_state: DeploymentState,
+    /// Weather at the ground station at the event time.
+    pub environment: EnvironmentalConditions,
+    /// Link simulation for the contact, if there is one.
+    pub link: Option<TransmissionResult>,
+}
+
+/// A complete LEOP scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeopScenario {
+    /// Events in time order.
+    pub events: Vec<LeopEvent>,
+    /// Antenna deployment fault model.
+    pub deployment: DeploymentConfig,
+    /// Weather at the ground station throughout the scenario, or the base
+    /// conditions of `weather` when it is set.
+    pub environment: EnvironmentalConditions,
+    /// Time-varying weather model; `None` keeps `environment` fixed.
+    #[serde(default)]
+    pub weather: Option<WeatherModel>,
+    /// Spacecraft transmit power, watts.
+    pub transmit_power_watts: f64,
+    /// Data volume per contact used for the link simulation, MB.
+    pub data_size_mb: f64,
+    /// Minimum data rate for a contact to count as successful, Mbps.
+    pub required_data_rate_mbps: f64,
+    /// Margins a contact must keep to count as successful.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+}
+
+impl LeopScenario {
+    /// The canned LEOP timeline.
+    ///
+    /// - **ID**: FN-LEOP-001
+    /// - **Requirement**: Provide a scripted LEOP sequence of link conditions
+    ///   and expected commands (REQ-FN-004, REQ-FN-007).
+    /// - **Outputs**: Scenario with separation at t = 0, first UHF acquisition
+    ///   about 50 minutes later, deployment on the same pass, and one
+    ///   commissioning pass per higher band on subsequent orbits.
+    /// - **Side Effects**: None.
+    pub fn standard() -> Self {
+        let contact = |band, distance_km, elevation_angle_degrees| {
+            Some(ContactGeometry {
+                band,
+                distance_km,
+                elevation_angle_degrees,
+            })
+        };
+
+        let mut events = vec![
+            LeopEvent {
+                time_s: 0.0,
+                phase: LeopPhase::Separation,
+                description: "Separation from launch vehicle".to_string(),
+                contact: None,
+                expected_commands: vec![],
+            },
+            LeopEvent {
+                time_s: 60.0,
+                phase: LeopPhase::Detumble,
+                description: "Autonomous detumble started".to_string(),
+                contact: None,
+                expected_commands: vec![],
+            },
+            LeopEvent {
+                time_s: 2700.0,
+                phase: LeopPhase::Detumble,
+                description: "Body rates below 0.5 deg/s".to_string(),
+                contact: None,
+                expected_commands: vec![],
+            },
+            LeopEvent {
+                time_s: 3000.0,
+                phase: LeopPhase::FirstAcquisition,
+                description: "First acquisition on low-gain UHF".to_string(),
+                contact: contact(BandType::UHFBand, 600.0, 60.0),
+                expected_commands: vec![
+                    ExpectedCommand::RequestTelemetry,
+                    ExpectedCommand::UpdateTime,
+                ],
+            },
+            LeopEvent {
+                time_s: 3240.0,
+                phase: LeopPhase::Deployment,
+                description: "Solar array and high-gain antenna deployment".to_string(),
+                contact: contact(BandType::UHFBand, 650.0, 50.0),
+                expected_commands: vec![
+                    ExpectedCommand::DeploySolarArray,
+                    ExpectedCommand::DeployAntenna {
+                        deployment_angle_deg: 90.0,
+                        deployment_rate_deg_s: 3.0,
+                    },
+                ],
+            },
+            LeopEvent {
+                time_s: 3420.0,
+                phase: LeopPhase::Deployment,
+                description: "Deployment status check".to_string(),
+                contact: contact(BandType::UHFBand, 1100.0, 20.0),
+                expected_commands: vec![ExpectedCommand::RequestTelemetry],
+            },
+        ];
+
+        // One commissioning pass per orbit (~95 min), most robust band first
+        let checkouts = [
+            BandType::SBand,
+            BandType::XBand,
+            BandType::KBand,
+            BandType::KaBand,
+        ];
+        for (orbit, band) in checkouts.into_iter().enumerate() {
+            events.push(LeopEvent {
+                time_s: 3000.0 + 5700.0 * (orbit as f64 + 1.0),
+                phase: LeopPhase::Commissioning,
+                description: format!("{} checkout", band),
+                contact: contact(band, 900.0, 35.0),
+                expected_commands: vec![
+                    ExpectedCommand::ReconfigureComm { band },
+                    ExpectedCommand::RequestTelemetry,
+                ],
+            });
+        }
+
+        Self {
+            events,
+            deployment: DeploymentConfig::default(),
+            environment: EnvironmentalConditions {
+                rain_rate_mm_hour: 0.0,
+                cloud_cover_percent: 20.0,
+                atmospheric_pressure_mb: 1013.0,
+                temperature_celsius: 15.0,
+                humidity_percent: 50.0,
+                ionospheric_activity: 0.2,
+                solar_activity: 0.2,
+            },
+            transmit_power_watts: 40.0,
+            data_size_mb: 1.0,
+            required_data_rate_mbps: 0.0096,
+            margins: MarginPolicy::default(),
+            weather: None,
+        }
+    }
+
+    /// Run the scenario, producing link conditions for every event.
+    ///
+    /// - **ID**: FN-LEOP-002
+    /// - **Requirement**: Link conditions follow the deployment state, so
+    ///   contacts before deployment use the low-gain path (REQ-FN-008).
+    /// - **Inputs**:
+    ///   - `rng`: Random source for the deployment outcome and weather; seed
+    ///     it for repeatable runs.
+    /// - **Outputs**: One `LeopStep` per event, in time order.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: `ValidationError` from the first contact whose
+    ///   link inputs are out of range.
+    pub fn run<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<LeopStep>, ValidationError> {
+        let bands = FrequencyBand::get_standard_bands();
+        let mut antenna = AntennaDeployment::new(self.deployment.clone());
+        // Weather draws from its own generator, seeded once up front, so the
+        // number of weather draws does not shift the deployment outcome
+        let mut weather = self
+            .weather
+            .as_ref()
+            .map(|model| (model.generator(), StdRng::seed_from_u64(rng.gen())));
+
+        self.events
+            .iter()
+            .map(|event| {
+                antenna.advance(event.time_s, rng);
+                let environment = match weather.as_mut() {
+                    Some((generator, weather_rng)) => {
+                        generator.conditions_at(event.time_s, &self.environment, weather_rng)
+                    }
+                    None => self.environment.clone(),
+                };
+
+                let link = event
+                    .contact
+                    .as_ref()
+                    .and_then(|contact| {
+                        let band = bands.iter().find(|b| b.name == contact.band)?;
+                        let params = TransmissionParameters {
+                            distance_km: contact.distance_km,
+                            data_size_mb: self.data_size_mb,
+                            required_data_rate_mbps: self.required_data_rate_mbps,
+                            elevation_angle_degrees: contact.elevation_angle_degrees,
+                            transmit_power_watts: self.transmit_power_watts,
+                            antenna_diameter_meters: 0.0,
+                            range_rate_m_s: 0.0,
+                            range_acceleration_m_s2: 0.0,
+                        };
+                        Some(antenna.simulate_transmission_with(
+                            band,
+                            &params,
+                            &environment,
+                            &self.margins,
+                        ))
+                    })
+                    .transpose()?;
+
+                let deployment_state = antenna.state;
+
+                // Commands take effect after the contact they are sent on
+                for command in &event.expected_commands {
+                    if let ExpectedCommand::DeployAntenna {
+                        deployment_angle_deg,
+                        deployment_rate_deg_s,
+                    } = command
+                    {
+                        antenna.command_deploy(
+                            event.time_s,
+                            *deployment_angle_deg,
+                            *deployment_rate_deg_s,
+                        );
+                    }
+                }
+
+                Ok(LeopStep {
+                    event: event.clone(),
+                    deployment_state,
+                    environment,
+                    link,
+                })
+            })
+            .collect()
+    }
+}
@@ -11,15 +11,93 @@
 //! - REQ-FN-010: Anti-Jam / LPI/LPD Communication (DSSS, FHSS)
 //! - REQ-PF-003: Link Capacity Optimisation (AMC, polarization diversity)
 //! - REQ-SE-002: Interference Mitigation (adaptive beamforming, null steering)
+//! - REQ-FN-004: Deployable mechanism control (antenna deployment dynamics, LEOP scenario)
+//! - REQ-PF-002: Data Transfer Rates (ground antenna tracking loss and contact time)
+//! - REQ-FN-007: Multi-Band Communication (Earth blockage of ISL and relay links)
+//! - REQ-PF-002: Data Transfer Rates (mission traffic profiles and downlink capacity sizing)
+//! - REQ-FN-008: Frequency Band Simulation (versioned simulation record export)
+//! - REQ-FN-007: Multi-Band Communication (weighted criterion-of-merit band recommendation)
+//! - REQ-FN-008: Frequency Band Simulation (parameter sweeps with long-format export)
+//! - REQ-FN-008: Frequency Band Simulation (reproducible Monte Carlo link studies)
+//! - REQ-FN-008: Frequency Band Simulation (constant-memory streaming statistics)
+//! - REQ-FN-007: Multi-Band Communication (asymmetric uplink/downlink link budgets)
+//! - REQ-FN-008: Frequency Band Simulation (localized report and planner text)
+//! - REQ-FN-008: Frequency Band Simulation (mission link and power margin policy)
+//! - REQ-FN-008: Frequency Band Simulation (time-varying weather models)
+//! - REQ-FN-008: Frequency Band Simulation (ITU rain zones by station location)
+//! - REQ-PF-002: Data Transfer Rates (per-contact link capacity forecasts)
+//! - REQ-FN-007: Multi-Band Communication (constellation time transfer over ISLs)
+//! - REQ-FN-007: Multi-Band Communication (formation flying crosslink ranging)
+//! - REQ-FN-008: Frequency Band Simulation (JSON Schemas of the public types)
+//! - REQ-FN-008: Frequency Band Simulation (input validation at every entry point)
+//! - REQ-FN-008: Frequency Band Simulation (bounded result cache for repeated runs)
+//! - REQ-FN-008: Frequency Band Simulation (fast batched math for Monte Carlo studies)
+//! - REQ-FN-008: Frequency Band Simulation (progress reporting and cancellation of long runs)
+//! - REQ-FN-008: Frequency Band Simulation (lunar and deep-space links with DSN antennas)
+//! - REQ-FN-007: Multi-Band Communication (shadow-mode evaluation of selection policies)
+//! - REQ-FN-008: Frequency Band Simulation (burst errors, interleaving and randomization)
+//! - REQ-PF-003: Link Capacity Optimisation (MODCOD table with LDPC performance curves)
+//! - REQ-SE-002: Interference Mitigation (receiver AGC, compression and desensitization)
+//! - REQ-FN-008: Frequency Band Simulation (ground antenna noise temperature vs elevation)
+//! - REQ-FN-008: Frequency Band Simulation (ITU-R P.618 slant-path rain attenuation)
+//! - REQ-FN-008: Frequency Band Simulation (SGP4 propagation of TLEs for pass range and elevation)
+//! - REQ-PF-002: Data Transfer Rates (hybrid ARQ with selective NAK and combining)
+//! - REQ-FN-008: Frequency Band Simulation (Doppler shift and carrier tracking loss)
+//! - REQ-FN-008: Frequency Band Simulation (live tuning of running simulations)
+
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub mod advanced_rf;
-
+pub mod antenna_noise;
+pub mod atmospheric;
+pub mod cache;
+pub mod capacity;
+pub mod deep_space;
+pub mod deployment;
+pub mod doppler;
+pub mod fast_math;
+pub mod forecast;
+pub mod formation;
+pub mod harq;
+pub mod interleaving;
+pub mod leop;
+pub mod link_budget;
+pub mod locale;
+pub mod margin;
+pub mod modcod;
+pub mod monte_carlo;
+pub mod occultation;
+pub mod orbit;
+pub mod progress;
+pub mod rain_zone;
+pub mod receiver;
+pub mod record;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod scoring;
+pub mod shadow;
+pub mod stats;
+pub mod sweep;
+pub mod time_transfer;
+pub mod tracking;
+pub mod traffic;
+pub mod tuning;
+pub mod validation;
+pub mod weather;
+
+use antenna_noise::GroundAntenna;
+use atmospheric::itu_p618::RainPath;
+use doppler::{CarrierLoop, DopplerTracking};
+use fast_math::MathPath;
+use margin::MarginPolicy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use validation::{validate_inputs, ValidationError};
 
 /// Types of frequency bands
 /// REQ-FN-007: Multi-Band Communication - Five frequency bands for space communication
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BandType {
     KBand,   // 20-30 GHz: High data rate, weather-sensitive
     KaBand,  // 26.5-40 GHz: Maximum data rate, atmospheric effects
@@ -47,6 +125,13 @@ pub struct FrequencyRange {
     pub max_ghz: f64,
 }
 
+impl FrequencyRange {
+    /// Centre of the range, GHz
+    pub fn center_ghz(&self) -> f64 {
+        (self.min_ghz + self.max_ghz) / 2.0
+    }
+}
+
 /// Band characteristics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BandCharacteristics {
@@ -56,9 +141,18 @@ pub struct BandCharacteristics {
     pub noise_temperature_k: f64,
 }
 
+/// Operational limitations of a band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandLimitations {
+    pub rain_fade_susceptibility: f64,   // 0.0 to 1.0
+    pub weather_dependence: f64,         // 0.0 to 1.0
+    pub pointing_accuracy_required: f64, // degrees
+}
+
 /// Environmental conditions
 /// REQ-FN-008: Frequency Band Simulation - Atmospheric effects modeling
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EnvironmentalConditions {
     pub rain_rate_mm_hour: f64,       // Rain attenuation effects
     pub cloud_cover_percent: f64,     // Cloud absorption
@@ -71,6 +165,7 @@ pub struct EnvironmentalConditions {
 
 /// Transmission parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransmissionParameters {
     pub distance_km: f64,
     pub data_size_mb: f64,
@@ -78,10 +173,13 @@ pub struct TransmissionParameters {
     pub elevation_angle_degrees: f64,
     pub transmit_power_watts: f64,
     pub antenna_diameter_meters: f64,
+    pub range_rate_m_s: f64, // Positive receding, zero when stationary
+    pub range_acceleration_m_s2: f64, // Rate of change of the range rate
 }
 
 /// Results of transmission simulation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransmissionResult {
     pub success: bool,
     pub actual_data_rate_mbps: f64,
@@ -91,6 +189,46 @@ pub struct TransmissionResult {
     pub weather_impact_factor: f64,
     pub signal_to_noise_ratio_db: f64,
     pub path_loss_db: f64,
+    pub rain_attenuation_db: f64, // Rain share of the weather impact
+    pub doppler_shift_hz: f64,
+    pub doppler_rate_hz_s: f64,
+    pub carrier_tracking_loss_db: f64, // Included in the SNR
+    pub carrier_locked: bool,          // Unlocked links carry no data
+}
+
+/// Rain attenuation model of the link budget
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RainModel {
+    /// Per-band multiple of the rain rate
+    #[default]
+    Simple,
+    /// ITU-R P.618 attenuation on the path from this ground station, see
+    /// `atmospheric::itu_p618`
+    ItuP618(RainPath),
+}
+
+/// Version of the link model in `FrequencyBand::simulate_transmission_with`
+///
+/// Bump whenever a model change alters the result for the same inputs, so
+/// results cached under the old model are not reused
+pub const MODEL_VERSION: u32 = 2;
+
+/// Environment-independent terms of one band's link budget
+struct LinkTerms {
+    center_freq_ghz: f64,
+    elevation_deg: f64,
+    path_loss_db: f64,
+    tx_power_dbm: f64,
+    noise_power_dbm: f64,
+    bandwidth_mhz: f64,
+    doppler: DopplerTracking,
+}
+
+/// Attenuation by the weather, dB
+#[derive(Clone, Copy)]
+struct WeatherLoss {
+    total_db: f64,
+    rain_db: f64,
 }
 
 /// Frequency band definition
@@ -99,6 +237,7 @@ pub struct FrequencyBand {
     pub name: BandType,
     pub frequency_range: FrequencyRange,
     pub characteristics: BandCharacteristics,
+    pub limitations: BandLimitations,
 }
 
 impl FrequencyBand {
@@ -120,6 +259,11 @@ impl FrequencyBand {
                     antenna_gain_dbi: 45.0,
                     noise_temperature_k: 500.0,
                 },
+                limitations: BandLimitations {
+                    rain_fade_susceptibility: 0.8,
+                    weather_dependence: 0.7,
+                    pointing_accuracy_required: 0.1,
+                },
             },
             // Ka-Band (26.5-40 GHz) - REQ-PF-002: 2000 Mbps clear weather
             FrequencyBand {
@@ -134,6 +278,11 @@ impl FrequencyBand {
                     antenna_gain_dbi: 50.0,
                     noise_temperature_k: 600.0,
                 },
+                limitations: BandLimitations {
+                    rain_fade_susceptibility: 0.95,
+                    weather_dependence: 0.9,
+                    pointing_accuracy_required: 0.05,
+                },
             },
             // S-Band (2-4 GHz) - REQ-PF-002: 100 Mbps all weather
             FrequencyBand {
@@ -148,6 +297,11 @@ impl FrequencyBand {
                     antenna_gain_dbi: 25.0,
                     noise_temperature_k: 300.0,
                 },
+                limitations: BandLimitations {
+                    rain_fade_susceptibility: 0.2,
+                    weather_dependence: 0.2,
+                    pointing_accuracy_required: 1.0,
+                },
             },
             // X-Band (8-12 GHz) - REQ-PF-002: 500 Mbps all weather
             FrequencyBand {
@@ -162,6 +316,11 @@ impl FrequencyBand {
                     antenna_gain_dbi: 35.0,
                     noise_temperature_k: 400.0,
                 },
+                limitations: BandLimitations {
+                    rain_fade_susceptibility: 0.4,
+                    weather_dependence: 0.4,
+                    pointing_accuracy_required: 0.3,
+                },
             },
             // UHF-Band (0.3-3 GHz)
             FrequencyBand {
@@ -176,49 +335,228 @@ impl FrequencyBand {
                     antenna_gain_dbi: 15.0,
                     noise_temperature_k: 200.0,
                 },
+                limitations: BandLimitations {
+                    rain_fade_susceptibility: 0.1,
+                    weather_dependence: 0.1,
+                    pointing_accuracy_required: 5.0,
+                },
             },
         ]
     }
 
-    /// Simulate transmission for this frequency band
+    /// Simulate transmission for this frequency band under the default
+    /// `MarginPolicy`
+    ///
+    /// Fails with every out-of-range field if the inputs are invalid
     pub fn simulate_transmission(
         &self,
         params: &TransmissionParameters,
         environment: &EnvironmentalConditions,
+    ) -> Result<TransmissionResult, ValidationError> {
+        self.simulate_transmission_with(params, environment, &MarginPolicy::default())
+    }
+
+    /// Simulate transmission for this frequency band, declaring success only
+    /// if `margins` are met
+    ///
+    /// Fails with every out-of-range field if the inputs are invalid
+    pub fn simulate_transmission_with(
+        &self,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<TransmissionResult, ValidationError> {
+        self.simulate_transmission_using(params, environment, margins, MathPath::Precise)
+    }
+
+    /// Simulate transmission as `simulate_transmission_with`, received by
+    /// `antenna` at the parameters' elevation
+    ///
+    /// The band's noise temperature rises by the antenna's excess over
+    /// zenith, see `FrequencyBand::at_elevation`. Fails with every
+    /// out-of-range field if the inputs are invalid
+    pub fn simulate_transmission_at(
+        &self,
+        antenna: &GroundAntenna,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<TransmissionResult, ValidationError> {
+        self.at_elevation(antenna, params.elevation_angle_degrees)
+            .simulate_transmission_with(params, environment, margins)
+    }
+
+    /// Simulate transmission as `simulate_transmission_with`, evaluating
+    /// logarithms and powers with `math`
+    pub fn simulate_transmission_using(
+        &self,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+        math: MathPath,
+    ) -> Result<TransmissionResult, ValidationError> {
+        validate_inputs(params, environment)?;
+        Ok(self.simulate(params, environment, margins, math, &RainModel::Simple))
+    }
+
+    /// Simulate transmission as `simulate_transmission_with`, attenuated by
+    /// rain as `rain` models it
+    ///
+    /// `RainModel::Simple` gives the same result as
+    /// `simulate_transmission_with`. Fails with every out-of-range field of
+    /// the inputs, then of the rain path, if either is invalid
+    pub fn simulate_transmission_with_rain_model(
+        &self,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+        rain: &RainModel,
+    ) -> Result<TransmissionResult, ValidationError> {
+        validate_inputs(params, environment)?;
+        if let RainModel::ItuP618(path) = rain {
+            path.validate()?;
+        }
+        Ok(self.simulate(params, environment, margins, MathPath::Precise, rain))
+    }
+
+    /// One transmission with validated inputs
+    fn simulate(
+        &self,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+        math: MathPath,
+        rain: &RainModel,
     ) -> TransmissionResult {
+        let terms = self.link_terms(params, math);
+        let (snr_db, weather) = self.snr_db(&terms, environment, rain);
+
+        // Calculate achievable data rate based on Shannon-Hartley theorem
+        let achievable_rate = terms.bandwidth_mhz * math.log2(1.0 + math.pow10(snr_db / 10.0));
+
+        self.transmission_result(params, margins, &terms, snr_db, weather, achievable_rate)
+    }
+
+    /// Simulate one transmission per environment, with the same parameters
+    ///
+    /// - **ID**: FN-SIM-004
+    /// - **Requirement**: Large studies evaluate the link model over batches
+    ///   of environments, with the environment-independent terms computed
+    ///   once and the logarithms and powers over whole slices (REQ-FN-008)
+    /// - **Inputs**: Parameters, the environments, margins, and the `math`
+    ///   path; `MathPath::Fast` trades accuracy within the bounds documented
+    ///   in `fast_math` for speed
+    /// - **Outputs**: One result per environment, in order; with
+    ///   `MathPath::Precise`, identical to `simulate_transmission_with`
+    /// - **Side Effects**: None
+    /// - **Failure Modes**: `ValidationError` of the first environment whose
+    ///   inputs are out of range, or of `params` if there are no environments
+    pub fn simulate_batch(
+        &self,
+        params: &TransmissionParameters,
+        environments: &[EnvironmentalConditions],
+        margins: &MarginPolicy,
+        math: MathPath,
+    ) -> Result<Vec<TransmissionResult>, ValidationError> {
+        if environments.is_empty() {
+            params.validate()?;
+        }
+        for environment in environments {
+            validate_inputs(params, environment)?;
+        }
+
+        let terms = self.link_terms(params, math);
+        let (snr_db, weather): (Vec<f64>, Vec<WeatherLoss>) = environments
+            .iter()
+            .map(|environment| self.snr_db(&terms, environment, &RainModel::Simple))
+            .unzip();
+
+        // Shannon-Hartley over the whole batch
+        let mut capacity: Vec<f64> = snr_db.iter().map(|snr| snr / 10.0).collect();
+        math.pow10_slice(&mut capacity);
+        capacity.iter_mut().for_each(|linear| *linear += 1.0);
+        math.log2_slice(&mut capacity);
+
+        Ok(snr_db
+            .iter()
+            .zip(&weather)
+            .zip(&capacity)
+            .map(|((&snr_db, &weather), &bits_per_hz)| {
+                let achievable_rate = terms.bandwidth_mhz * bits_per_hz;
+                self.transmission_result(params, margins, &terms, snr_db, weather, achievable_rate)
+            })
+            .collect())
+    }
+
+    /// Link budget terms that do not depend on the environment
+    fn link_terms(&self, params: &TransmissionParameters, math: MathPath) -> LinkTerms {
         // Calculate center frequency
-        let center_freq_ghz = (self.frequency_range.min_ghz + self.frequency_range.max_ghz) / 2.0;
+        let center_freq_ghz = self.frequency_range.center_ghz();
 
         // Calculate path loss using Friis equation
         let path_loss_db = 20.0
-            * (4.0 * std::f64::consts::PI * params.distance_km * 1000.0 * center_freq_ghz * 1e9
-                / 299792458.0)
-                .log10();
+            * math.log10(
+                4.0 * std::f64::consts::PI * params.distance_km * 1000.0 * center_freq_ghz * 1e9
+                    / 299792458.0,
+            );
+
+        LinkTerms {
+            center_freq_ghz,
+            elevation_deg: params.elevation_angle_degrees,
+            path_loss_db,
+            tx_power_dbm: 10.0 * math.log10(params.transmit_power_watts) + 30.0,
+            noise_power_dbm: 10.0
+                * math.log10(1.38e-23 * self.characteristics.noise_temperature_k * 1e6)
+                + 30.0,
+            bandwidth_mhz: (self.frequency_range.max_ghz - self.frequency_range.min_ghz) * 1000.0,
+            doppler: CarrierLoop::typical(self.name).track(
+                center_freq_ghz * 1e9,
+                params.range_rate_m_s,
+                params.range_acceleration_m_s2,
+            ),
+        }
+    }
 
+    /// SNR and weather impact in `environment`, dB
+    fn snr_db(
+        &self,
+        terms: &LinkTerms,
+        environment: &EnvironmentalConditions,
+        rain: &RainModel,
+    ) -> (f64, WeatherLoss) {
         // Calculate weather impact
-        let weather_impact = self.calculate_weather_impact(center_freq_ghz, environment);
+        let weather = self.calculate_weather_impact(terms, environment, rain);
 
         // Calculate atmospheric attenuation
-        let atmospheric_loss_db = self.calculate_atmospheric_loss(center_freq_ghz, environment);
+        let atmospheric_loss_db =
+            self.calculate_atmospheric_loss(terms.center_freq_ghz, environment);
 
         // Total loss
-        let total_loss_db = path_loss_db + atmospheric_loss_db + weather_impact;
+        let total_loss_db = terms.path_loss_db + atmospheric_loss_db + weather.total_db;
 
-        // Calculate received power
-        let tx_power_dbm = 10.0 * params.transmit_power_watts.log10() + 30.0;
-        let rx_power_dbm = tx_power_dbm + self.characteristics.antenna_gain_dbi - total_loss_db;
-
-        // Calculate SNR
-        let noise_power_dbm =
-            10.0 * (1.38e-23 * self.characteristics.noise_temperature_k * 1e6).log10() + 30.0;
-        let snr_db = rx_power_dbm - noise_power_dbm;
-
-        // Calculate achievable data rate based on Shannon-Hartley theorem
-        let bandwidth_mhz = (self.frequency_range.max_ghz - self.frequency_range.min_ghz) * 1000.0;
-        let achievable_rate = bandwidth_mhz * (1.0 + 10.0_f64.powf(snr_db / 10.0)).log2();
+        // Calculate received power and SNR
+        let rx_power_dbm =
+            terms.tx_power_dbm + self.characteristics.antenna_gain_dbi - total_loss_db;
+        let snr_db = rx_power_dbm - terms.noise_power_dbm - terms.doppler.tracking_loss_db;
+        (snr_db, weather)
+    }
 
-        // Apply band limitations
-        let actual_data_rate = achievable_rate.min(self.characteristics.max_data_rate_mbps);
+    /// Result of a transmission at `snr_db` able to carry `achievable_rate`
+    fn transmission_result(
+        &self,
+        params: &TransmissionParameters,
+        margins: &MarginPolicy,
+        terms: &LinkTerms,
+        snr_db: f64,
+        weather: WeatherLoss,
+        achievable_rate: f64,
+    ) -> TransmissionResult {
+        // Apply band limitations; nothing gets through without carrier lock
+        let actual_data_rate = if terms.doppler.locked {
+            achievable_rate.min(self.characteristics.max_data_rate_mbps)
+        } else {
+            0.0
+        };
 
         // Calculate efficiency
         let efficiency = if achievable_rate > 0.0 {
@@ -227,8 +565,17 @@ impl FrequencyBand {
             0.0
         };
 
-        // Check if transmission is successful
-        let success = snr_db > 10.0 && actual_data_rate >= params.required_data_rate_mbps;
+        // Calculate power consumption
+        let power_consumption = params.transmit_power_watts / self.characteristics.power_efficiency;
+
+        // Successful only with the mission's link and power margins
+        let success = terms.doppler.locked
+            && margins.accepts(
+                snr_db,
+                actual_data_rate,
+                params.required_data_rate_mbps,
+                power_consumption,
+            );
 
         // Calculate transmission time and latency
         let transmission_time_ms = if actual_data_rate > 0.0 {
@@ -240,37 +587,54 @@ impl FrequencyBand {
         let propagation_delay_ms = params.distance_km / 299.792458; // Speed of light
         let total_latency = transmission_time_ms + propagation_delay_ms;
 
-        // Calculate power consumption
-        let power_consumption = params.transmit_power_watts / self.characteristics.power_efficiency;
-
         TransmissionResult {
             success,
             actual_data_rate_mbps: actual_data_rate,
             total_latency_ms: total_latency,
             power_consumption_watts: power_consumption,
             transmission_efficiency: efficiency,
-            weather_impact_factor: weather_impact / 100.0,
+            weather_impact_factor: weather.total_db / 100.0,
             signal_to_noise_ratio_db: snr_db,
-            path_loss_db,
+            path_loss_db: terms.path_loss_db,
+            rain_attenuation_db: weather.rain_db,
+            doppler_shift_hz: terms.doppler.shift_hz,
+            doppler_rate_hz_s: terms.doppler.rate_hz_s,
+            carrier_tracking_loss_db: terms.doppler.tracking_loss_db,
+            carrier_locked: terms.doppler.locked,
         }
     }
 
     fn calculate_weather_impact(
         &self,
-        frequency_ghz: f64,
+        terms: &LinkTerms,
         environment: &EnvironmentalConditions,
-    ) -> f64 {
-        let rain_attenuation = match self.name {
+        rain: &RainModel,
+    ) -> WeatherLoss {
+        let rain_attenuation = match rain {
+            RainModel::Simple => self.calculate_rain_attenuation(environment),
+            RainModel::ItuP618(path) => path.attenuation_db(
+                terms.center_freq_ghz,
+                environment.rain_rate_mm_hour,
+                terms.elevation_deg,
+            ),
+        };
+
+        let cloud_attenuation = environment.cloud_cover_percent * terms.center_freq_ghz * 0.001;
+
+        WeatherLoss {
+            total_db: rain_attenuation + cloud_attenuation,
+            rain_db: rain_attenuation,
+        }
+    }
+
+    fn calculate_rain_attenuation(&self, environment: &EnvironmentalConditions) -> f64 {
+        match self.name {
             BandType::KaBand => environment.rain_rate_mm_hour * 2.5, // Very sensitive
             BandType::KBand => environment.rain_rate_mm_hour * 1.8,  // High sensitivity
             BandType::XBand => environment.rain_rate_mm_hour * 0.8,  // Moderate sensitivity
             BandType::SBand => environment.rain_rate_mm_hour * 0.2,  // Low sensitivity
             BandType::UHFBand => environment.rain_rate_mm_hour * 0.05, // Very low sensitivity
-        };
-
-        let cloud_attenuation = environment.cloud_cover_percent * frequency_ghz * 0.001;
-
-        rain_attenuation + cloud_attenuation
+        }
     }
 
     fn calculate_atmospheric_loss(
@@ -334,6 +698,7 @@ pub struct BandScore {
 /// - **Side Effects**: No external I/O; result is heap-allocated via `Vec`.
 /// - **Failure Modes**: All bands may return `meets_requirement = false` under
 ///   severe atmospheric conditions — the caller must handle this gracefully.
+///   Out-of-range inputs return a `ValidationError` listing every invalid field.
 /// - **Constraints**: O(B) where B = number of bands; B = 5 for this system.
 /// - **Verification**: Under clear-sky conditions, Ka-Band should score highest;
 ///   under tropical-storm conditions (rain ≥ 100 mm/h), UHF should score highest.
@@ -342,21 +707,43 @@ pub fn score_bands_for_conditions(
     bands: &[FrequencyBand],
     params: &TransmissionParameters,
     environment: &EnvironmentalConditions,
-) -> Vec<BandScore> {
+) -> Result<Vec<BandScore>, ValidationError> {
+    score_bands_with_margins(bands, params, environment, &MarginPolicy::default())
+}
+
+/// Rank bands as `score_bands_for_conditions`, under the mission's `margins`.
+///
+/// - **ID**: FN-SIM-003
+/// - **Requirement**: Band ranking applies the same link and power margins
+///   as the simulation's success criterion.
+/// - **Inputs**: As `score_bands_for_conditions`, plus `margins`.
+/// - **Outputs**: `Vec<BandScore>` sorted descending by `composite_score`;
+///   `meets_requirement` is set only for bands meeting `margins`.
+/// - **Side Effects**: None.
+/// - **Failure Modes**: Out-of-range inputs return a `ValidationError`
+///   listing every invalid field.
+pub fn score_bands_with_margins(
+    bands: &[FrequencyBand],
+    params: &TransmissionParameters,
+    environment: &EnvironmentalConditions,
+    margins: &MarginPolicy,
+) -> Result<Vec<BandScore>, ValidationError> {
+    validate_inputs(params, environment)?;
+
     // Shared geometry: propagation delay does not vary per band.
     let propagation_delay_ms = params.distance_km / 299.792_458;
 
     let mut scores: Vec<BandScore> = bands
         .iter()
         .map(|band| {
-            let result = band.simulate_transmission(params, environment);
+            let result = band.simulate_transmission_with(params, environment, margins)?;
 
             // Composite score: normalise rate (0–1) weighted by SNR quality.
-            // Heavily penalise bands that fail the SNR threshold (< 10 dB).
+            // Heavily penalise bands short of the required link margin.
             let rate_score = (result.actual_data_rate_mbps
                 / band.characteristics.max_data_rate_mbps)
                 .min(1.0);
-            let snr_weight = if result.signal_to_noise_ratio_db > 10.0 {
+            let snr_weight = if margins.link_ok(result.signal_to_noise_ratio_db) {
                 (result.signal_to_noise_ratio_db / 40.0).min(1.0)
             } else {
                 0.1 // Heavily penalise below-threshold bands
@@ -366,15 +753,15 @@ pub fn score_bands_for_conditions(
                 - ((result.total_latency_ms - propagation_delay_ms) / 10_000.0).clamp(0.0, 0.5);
             let composite_score = rate_score * snr_weight * latency_penalty;
 
-            BandScore {
+            Ok(BandScore {
                 band: band.name,
                 achievable_rate_mbps: result.actual_data_rate_mbps,
                 snr_db: result.signal_to_noise_ratio_db,
                 meets_requirement: result.success,
                 composite_score,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, ValidationError>>()?;
 
     // Sort descending: best band first.
     scores.sort_by(|a, b| {
@@ -383,7 +770,7 @@ pub fn score_bands_for_conditions(
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    scores
+    Ok(scores)
 }
 
 pub fn run_basic_demo() {
@@ -398,6 +785,8 @@ pub fn run_basic_demo() {
         elevation_angle_degrees: 35.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     let clear_weather = EnvironmentalConditions {
@@ -423,8 +812,10 @@ pub fn run_basic_demo() {
     // Score all bands in a single pass for each condition set (Optimization 4).
     // Each call to score_bands_for_conditions() replaces five independent
     // simulate_transmission() calls that recalculated shared geometry separately.
-    let clear_scores = score_bands_for_conditions(&bands, &params, &clear_weather);
-    let storm_scores = score_bands_for_conditions(&bands, &params, &stormy_weather);
+    let clear_scores =
+        score_bands_for_conditions(&bands, &params, &clear_weather).expect("demo inputs are valid");
+    let storm_scores = score_bands_for_conditions(&bands, &params, &stormy_weather)
+        .expect("demo inputs are valid");
 
     println!("\nClear Weather Performance (ranked by composite score):");
     println!(
@@ -502,6 +893,8 @@ mod tests {
             elevation_angle_degrees: 45.0,
             transmit_power_watts: 100.0,
             antenna_diameter_meters: 3.0,
+            range_rate_m_s: 0.0,
+            range_acceleration_m_s2: 0.0,
         };
 
         let clear_conditions = EnvironmentalConditions {
@@ -514,7 +907,9 @@ mod tests {
             solar_activity: 0.1,
         };
 
-        let result = s_band.simulate_transmission(&params, &clear_conditions);
+        let result = s_band
+            .simulate_transmission(&params, &clear_conditions)
+            .unwrap();
         assert!(result.success);
         assert!(result.actual_data_rate_mbps > 0.0);
         assert!(result.signal_to_noise_ratio_db > 10.0);
@@ -531,6 +926,8 @@ mod tests {
             elevation_angle_degrees: 30.0,
             transmit_power_watts: 150.0,
             antenna_diameter_meters: 4.0,
+            range_rate_m_s: 0.0,
+            range_acceleration_m_s2: 0.0,
         };
 
         let clear = EnvironmentalConditions {
@@ -553,8 +950,8 @@ mod tests {
             solar_activity: 0.2,
         };
 
-        let clear_result = ka_band.simulate_transmission(&params, &clear);
-        let rainy_result = ka_band.simulate_transmission(&params, &rainy);
+        let clear_result = ka_band.simulate_transmission(&params, &clear).unwrap();
+        let rainy_result = ka_band.simulate_transmission(&params, &rainy).unwrap();
 
         // Ka-Band should be significantly affected by rain
         assert!(clear_result.actual_data_rate_mbps > rainy_result.actual_data_rate_mbps);
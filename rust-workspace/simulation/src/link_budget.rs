@@ -0,0 +1,490 @@
+//! Asymmetric Link Budget Module
+//!
+//! Missions commonly command on S-band while downlinking on X- or Ka-band,
+//! with a high-power ground transmitter on the way up and a few watts of
+//! spacecraft power on the way down. `AsymmetricLink` holds the band,
+//! transmit power, data rate and per-pass volume of each direction, and
+//! `evaluate` works out a separate budget for the uplink and the downlink
+//! over the same pass geometry and weather.
+//!
+//! A pass is only useful if both directions close, so the budget reports the
+//! limiting direction: the one with the smaller SNR margin. Each direction
+//! closes only if it keeps the link's `MarginPolicy`; the power margin
+//! applies to the spacecraft downlink transmitter, as the ground uplink
+//! transmitter is not limited by spacecraft power.
+//!
+//! `FrequencyBand::compute_link_budget` itemizes one direction the way a
+//! link budget table does: EIRP, path, atmospheric, rain, cloud and pointing
+//! losses, G/T, C/N0, Eb/N0 and margin. Its SNR is the simulation's, less
+//! the pointing loss, which the simulated transmission does not model.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (independent uplink/downlink bands)
+//! - REQ-FN-008: Frequency Band Simulation (itemized link budget)
+//! - REQ-PF-002: Data Transfer Rates (per-direction data rates)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fast_math::MathPath;
+use crate::locale::{Catalog, Localize};
+use crate::margin::MarginPolicy;
+use crate::validation::{validate_inputs, ValidationError};
+use crate::{
+    BandType, EnvironmentalConditions, FrequencyBand, RainModel, TransmissionParameters,
+    TransmissionResult,
+};
+
+pub use crate::margin::LINK_CLOSURE_SNR_DB;
+
+/// Direction of a space-ground link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LinkDirection {
+    /// Ground to satellite.
+    Uplink,
+    /// Satellite to ground.
+    Downlink,
+}
+
+impl fmt::Display for LinkDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for LinkDirection {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(catalog.text(match self {
+            LinkDirection::Uplink => "link.uplink",
+            LinkDirection::Downlink => "link.downlink",
+        }))
+    }
+}
+
+/// Settings of one link direction.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DirectionSettings {
+    /// Band the direction is carried on.
+    pub band: BandType,
+    /// Transmitter output power, W.
+    pub transmit_power_watts: f64,
+    /// Data rate the direction must sustain, Mbps.
+    pub data_rate_mbps: f64,
+    /// Data to move in this direction per pass, MB.
+    pub pass_volume_mb: f64,
+}
+
+/// Uplink and downlink settings of one space-ground link.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AsymmetricLink {
+    /// Ground to satellite.
+    pub uplink: DirectionSettings,
+    /// Satellite to ground.
+    pub downlink: DirectionSettings,
+    /// Margins each direction must keep to close.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+}
+
+impl Default for AsymmetricLink {
+    /// S-band command uplink from a 200 W ground transmitter at 2 Mbps;
+    /// X-band science downlink from a 20 W spacecraft transmitter at 150 Mbps.
+    fn default() -> Self {
+        Self {
+            uplink: DirectionSettings {
+                band: BandType::SBand,
+                transmit_power_watts: 200.0,
+                data_rate_mbps: 2.0,
+                pass_volume_mb: 1.0,
+            },
+            downlink: DirectionSettings {
+                band: BandType::XBand,
+                transmit_power_watts: 20.0,
+                data_rate_mbps: 150.0,
+                pass_volume_mb: 4_000.0,
+            },
+            margins: MarginPolicy::default(),
+        }
+    }
+}
+
+impl AsymmetricLink {
+    /// Settings of one direction.
+    pub fn direction(&self, direction: LinkDirection) -> &DirectionSettings {
+        match direction {
+            LinkDirection::Uplink => &self.uplink,
+            LinkDirection::Downlink => &self.downlink,
+        }
+    }
+
+    /// Whether the two directions use different bands.
+    pub fn is_asymmetric(&self) -> bool {
+        self.uplink.band != self.downlink.band
+    }
+
+    /// Budget both directions over one pass.
+    ///
+    /// - **ID**: FN-LB-001
+    /// - **Requirement**: Evaluate uplink and downlink separately, each with
+    ///   its own band, power and data rate (REQ-FN-007).
+    /// - **Inputs**:
+    ///   - `bands`: Band definitions to look the two bands up in, e.g.
+    ///     `FrequencyBand::get_standard_bands()`.
+    ///   - `distance_km`, `elevation_angle_degrees`: Pass geometry, the same
+    ///     both ways.
+    ///   - `environment`: Weather along the path, the same both ways.
+    /// - **Outputs**: Budget per direction, or `None` if either band has no
+    ///   definition in `bands`.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: `ValidationError` listing every out-of-range
+    ///   input of either direction's simulation.
+    pub fn evaluate(
+        &self,
+        bands: &[FrequencyBand],
+        distance_km: f64,
+        elevation_angle_degrees: f64,
+        environment: &EnvironmentalConditions,
+    ) -> Result<Option<AsymmetricLinkBudget>, ValidationError> {
+        let uplink_margins = MarginPolicy {
+            available_power_watts: None,
+            ..self.margins
+        };
+        let budget = |settings: &DirectionSettings, margins: &MarginPolicy| {
+            let Some(band) = bands.iter().find(|b| b.name == settings.band) else {
+                return Ok(None);
+            };
+            let params = TransmissionParameters {
+                distance_km,
+                data_size_mb: settings.pass_volume_mb,
+                required_data_rate_mbps: settings.data_rate_mbps,
+                elevation_angle_degrees,
+                transmit_power_watts: settings.transmit_power_watts,
+                antenna_diameter_meters: 0.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
+            };
+            let result = band.simulate_transmission_with(&params, environment, margins)?;
+            Ok(Some(DirectionBudget {
+                band: settings.band,
+                margin_db: margins.link_margin(result.signal_to_noise_ratio_db),
+                result,
+            }))
+        };
+
+        let (Some(uplink), Some(downlink)) = (
+            budget(&self.uplink, &uplink_margins)?,
+            budget(&self.downlink, &self.margins)?,
+        ) else {
+            return Ok(None);
+        };
+        Ok(Some(AsymmetricLinkBudget { uplink, downlink }))
+    }
+}
+
+/// Budget of one link direction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectionBudget {
+    /// Band the direction is carried on.
+    pub band: BandType,
+    /// SNR above the policy's closure threshold, dB; negative if the link does not
+    /// close on signal.
+    pub margin_db: f64,
+    /// Simulated transmission.
+    pub result: TransmissionResult,
+}
+
+/// Uplink and downlink budgets over one pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsymmetricLinkBudget {
+    /// Ground to satellite.
+    pub uplink: DirectionBudget,
+    /// Satellite to ground.
+    pub downlink: DirectionBudget,
+}
+
+impl AsymmetricLinkBudget {
+    /// Budget of one direction.
+    pub fn direction(&self, direction: LinkDirection) -> &DirectionBudget {
+        match direction {
+            LinkDirection::Uplink => &self.uplink,
+            LinkDirection::Downlink => &self.downlink,
+        }
+    }
+
+    /// Whether both directions close at their required data rates.
+    pub fn closes(&self) -> bool {
+        self.uplink.result.success && self.downlink.result.success
+    }
+
+    /// Direction with the smaller SNR margin.
+    pub fn limiting_direction(&self) -> LinkDirection {
+        if self.uplink.margin_db <= self.downlink.margin_db {
+            LinkDirection::Uplink
+        } else {
+            LinkDirection::Downlink
+        }
+    }
+}
+
+impl fmt::Display for AsymmetricLinkBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for AsymmetricLinkBudget {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        for (direction, budget) in [
+            (LinkDirection::Uplink, &self.uplink),
+            (LinkDirection::Downlink, &self.downlink),
+        ] {
+            writeln!(
+                f,
+                "{:<8} {:<8} {:>7.1} Mbps  SNR {:>6.1} dB  {} {:>+6.1} dB  {}",
+                direction.localized(catalog),
+                budget.band.to_string(),
+                budget.result.actual_data_rate_mbps,
+                budget.result.signal_to_noise_ratio_db,
+                catalog.text("link.margin"),
+                budget.margin_db,
+                catalog.text(if budget.result.success {
+                    "link.closes"
+                } else {
+                    "link.fails"
+                })
+            )?;
+        }
+        f.write_str(&catalog.format(
+            "link.limiting_direction",
+            &[("direction", &self.limiting_direction().localized(catalog))],
+        ))
+    }
+}
+
+/// Boltzmann's constant as the link model takes it, J/K.
+const BOLTZMANN_J_K: f64 = 1.38e-23;
+
+/// Noise bandwidth the link model's SNR is referred to, dB-Hz (1 MHz).
+const NOISE_BANDWIDTH_DB_HZ: f64 = 60.0;
+
+/// Half-power beamwidth of a parabolic antenna, degrees per wavelength per
+/// diameter.
+const BEAMWIDTH_DEG_PER_WAVELENGTH: f64 = 70.0;
+
+/// Itemized link budget of one band, each term as a link budget table
+/// lists it.
+///
+/// The link model credits the band's antenna gain once, at the receiver, so
+/// the EIRP is the transmit power.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkBudget {
+    /// Band budgeted.
+    pub band: BandType,
+    /// Centre frequency, GHz.
+    pub frequency_ghz: f64,
+    /// Transmitter output power, dBW.
+    pub transmit_power_dbw: f64,
+    /// Effective isotropic radiated power, dBW.
+    pub eirp_dbw: f64,
+    /// Free-space path loss, dB.
+    pub path_loss_db: f64,
+    /// Oxygen and water vapour absorption, dB.
+    pub atmospheric_loss_db: f64,
+    /// Rain attenuation, dB.
+    pub rain_loss_db: f64,
+    /// Cloud attenuation, dB.
+    pub cloud_loss_db: f64,
+    /// Loss from the band's pointing accuracy over the antenna's beamwidth,
+    /// dB; zero without an antenna diameter.
+    pub pointing_loss_db: f64,
+    /// Loss of coherent demodulation to the carrier loop's phase error
+    /// tracking the Doppler rate, dB; see `doppler`.
+    pub carrier_tracking_loss_db: f64,
+    /// Receive antenna gain, dBi.
+    pub receive_gain_dbi: f64,
+    /// Carrier power at the receiver, dBW.
+    pub received_power_dbw: f64,
+    /// Receiver system noise temperature, K.
+    pub system_noise_temperature_k: f64,
+    /// Receive figure of merit, dB/K.
+    pub g_over_t_db_k: f64,
+    /// Carrier to noise density, dB-Hz.
+    pub c_n0_dbhz: f64,
+    /// Data rate the link must sustain, bit/s.
+    pub data_rate_bps: f64,
+    /// Energy per bit to noise density at the data rate, dB.
+    pub eb_n0_db: f64,
+    /// SNR in the model's 1 MHz noise bandwidth, dB.
+    pub snr_db: f64,
+    /// SNR the link needs to close, dB.
+    pub required_snr_db: f64,
+    /// SNR above the required SNR, dB; negative if the link does not close.
+    pub margin_db: f64,
+}
+
+impl LinkBudget {
+    /// Sum of the path, atmospheric, rain, cloud, pointing and carrier
+    /// tracking losses, dB.
+    pub fn total_loss_db(&self) -> f64 {
+        self.path_loss_db
+            + self.atmospheric_loss_db
+            + self.rain_loss_db
+            + self.cloud_loss_db
+            + self.pointing_loss_db
+            + self.carrier_tracking_loss_db
+    }
+
+    /// Whether the link closes on signal.
+    pub fn closes(&self) -> bool {
+        self.margin_db >= 0.0
+    }
+}
+
+impl FrequencyBand {
+    /// Itemized link budget of a transmission on this band.
+    ///
+    /// - **ID**: FN-LB-002
+    /// - **Requirement**: Report every term of a band's link budget, from
+    ///   EIRP to margin, so a result can be checked line by line
+    ///   (REQ-FN-008).
+    /// - **Inputs**:
+    ///   - `params`: Transmission; `antenna_diameter_meters` sets the
+    ///     beamwidth the pointing loss is taken over.
+    ///   - `environment`: Weather along the path.
+    ///   - `margins`: Policy whose closure threshold the margin is above.
+    /// - **Outputs**: The budget; its SNR plus its pointing loss is the SNR
+    ///   of `simulate_transmission_with` for the same inputs.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: `ValidationError` listing every out-of-range
+    ///   input.
+    pub fn compute_link_budget(
+        &self,
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<LinkBudget, ValidationError> {
+        validate_inputs(params, environment)?;
+
+        let terms = self.link_terms(params, MathPath::Precise);
+        let weather = self.calculate_weather_impact(&terms, environment, &RainModel::Simple);
+        let atmospheric_loss_db =
+            self.calculate_atmospheric_loss(terms.center_freq_ghz, environment);
+        let pointing_loss_db = self.pointing_loss_db(params.antenna_diameter_meters);
+
+        let transmit_power_dbw = terms.tx_power_dbm - 30.0;
+        let eirp_dbw = transmit_power_dbw;
+        let receive_gain_dbi = self.characteristics.antenna_gain_dbi;
+        let system_noise_temperature_k = self.characteristics.noise_temperature_k;
+        let g_over_t_db_k = receive_gain_dbi - 10.0 * system_noise_temperature_k.log10();
+        let data_rate_bps = params.required_data_rate_mbps * 1e6;
+
+        let mut budget = LinkBudget {
+            band: self.name,
+            frequency_ghz: terms.center_freq_ghz,
+            transmit_power_dbw,
+            eirp_dbw,
+            path_loss_db: terms.path_loss_db,
+            atmospheric_loss_db,
+            rain_loss_db: weather.rain_db,
+            cloud_loss_db: weather.total_db - weather.rain_db,
+            pointing_loss_db,
+            carrier_tracking_loss_db: terms.doppler.tracking_loss_db,
+            receive_gain_dbi,
+            received_power_dbw: 0.0,
+            system_noise_temperature_k,
+            g_over_t_db_k,
+            c_n0_dbhz: 0.0,
+            data_rate_bps,
+            eb_n0_db: 0.0,
+            snr_db: 0.0,
+            required_snr_db: margins.closure_snr_db,
+            margin_db: 0.0,
+        };
+        budget.received_power_dbw = eirp_dbw - budget.total_loss_db() + receive_gain_dbi;
+        budget.c_n0_dbhz =
+            eirp_dbw - budget.total_loss_db() + g_over_t_db_k - 10.0 * BOLTZMANN_J_K.log10();
+        budget.eb_n0_db = budget.c_n0_dbhz - 10.0 * data_rate_bps.log10();
+        budget.snr_db = budget.c_n0_dbhz - NOISE_BANDWIDTH_DB_HZ;
+        budget.margin_db = margins.link_margin(budget.snr_db);
+        Ok(budget)
+    }
+
+    /// Loss from mispointing by the band's required pointing accuracy, dB:
+    /// 12 (error / half-power beamwidth)².
+    fn pointing_loss_db(&self, antenna_diameter_m: f64) -> f64 {
+        if antenna_diameter_m <= 0.0 {
+            return 0.0;
+        }
+        let wavelength_m = 299_792_458.0 / (self.frequency_range.center_ghz() * 1e9);
+        let beamwidth_deg = BEAMWIDTH_DEG_PER_WAVELENGTH * wavelength_m / antenna_diameter_m;
+        12.0 * (self.limitations.pointing_accuracy_required / beamwidth_deg).powi(2)
+    }
+}
+
+impl fmt::Display for LinkBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for LinkBudget {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(
+            f,
+            "{}",
+            catalog.format(
+                "budget.title",
+                &[
+                    ("band", &self.band),
+                    ("frequency", &format!("{:.2}", self.frequency_ghz)),
+                ],
+            )
+        )?;
+        let rows: [(&str, f64, &str); 18] = [
+            ("budget.transmit_power", self.transmit_power_dbw, "dBW"),
+            ("budget.eirp", self.eirp_dbw, "dBW"),
+            ("budget.path_loss", -self.path_loss_db, "dB"),
+            ("budget.atmospheric_loss", -self.atmospheric_loss_db, "dB"),
+            ("budget.rain_loss", -self.rain_loss_db, "dB"),
+            ("budget.cloud_loss", -self.cloud_loss_db, "dB"),
+            ("budget.pointing_loss", -self.pointing_loss_db, "dB"),
+            (
+                "budget.carrier_tracking_loss",
+                -self.carrier_tracking_loss_db,
+                "dB",
+            ),
+            ("budget.receive_gain", self.receive_gain_dbi, "dBi"),
+            ("budget.received_power", self.received_power_dbw, "dBW"),
+            (
+                "budget.noise_temperature",
+                self.system_noise_temperature_k,
+                "K",
+            ),
+            ("budget.g_over_t", self.g_over_t_db_k, "dB/K"),
+            ("budget.c_n0", self.c_n0_dbhz, "dB-Hz"),
+            (
+                "budget.data_rate",
+                10.0 * self.data_rate_bps.log10(),
+                "dB-bps",
+            ),
+            ("budget.eb_n0", self.eb_n0_db, "dB"),
+            ("budget.snr", self.snr_db, "dB"),
+            ("budget.required_snr", self.required_snr_db, "dB"),
+            ("budget.margin", self.margin_db, "dB"),
+        ];
+        for (key, value, unit) in rows {
+            writeln!(f, "  {:<26} {:>9.2} {}", catalog.text(key), value, unit)?;
+        }
+        write!(
+            f,
+            "  {:<26} {}",
+            catalog.text("budget.result"),
+            catalog.text(if self.closes() {
+                "link.closes"
+            } else {
+                "link.fails"
+            })
+        )
+    }
+}
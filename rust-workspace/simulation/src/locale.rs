@@ -0,0 +1,214 @@
+//! Report Localization Module
+//!
+//! Key-value message catalogs for the text of simulation reports and planner
+//! output. A [`Catalog`] maps message keys such as `capacity.title` to
+//! templates in one language; `{name}` placeholders in a template are filled
+//! from named arguments. English is the default catalog, and any key missing
+//! from another catalog falls back to the English text, so a partial
+//! translation still renders a complete report.
+//!
+//! Catalogs are JSON documents of the form
+//! `{"language": "es", "messages": {"capacity.title": "..."}}`. The English
+//! and Spanish catalogs are built in (`locales/` in this crate); further
+//! languages are loaded at run time with [`Catalog::load`]. Start a new
+//! translation from `locales/en.json` and check it with
+//! [`Catalog::missing_keys`].
+//!
+//! Report types implement [`Localize`]; their `Display` output is the English
+//! rendering, and `report.localized(&catalog)` renders in any catalog.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (localized report and planner text)
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Languages with a built-in catalog.
+pub const BUILTIN_LANGUAGES: [&str; 2] = ["en", "es"];
+
+const ENGLISH_JSON: &str = include_str!("../locales/en.json");
+const SPANISH_JSON: &str = include_str!("../locales/es.json");
+
+/// Error loading a message catalog.
+#[derive(Debug)]
+pub enum CatalogError {
+    /// The catalog file could not be read.
+    Io(std::io::Error),
+    /// The input is not a valid catalog.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(e) => write!(f, "cannot read message catalog: {}", e),
+            CatalogError::Json(e) => write!(f, "invalid message catalog: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+impl From<std::io::Error> for CatalogError {
+    fn from(e: std::io::Error) -> Self {
+        CatalogError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CatalogError {
+    fn from(e: serde_json::Error) -> Self {
+        CatalogError::Json(e)
+    }
+}
+
+/// Message templates of one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Catalog {
+    /// Language tag, e.g. `en` or `pt-BR`.
+    pub language: String,
+    /// Template per message key.
+    pub messages: BTreeMap<String, String>,
+}
+
+impl Catalog {
+    /// The built-in English catalog.
+    pub fn english() -> &'static Catalog {
+        static ENGLISH: OnceLock<Catalog> = OnceLock::new();
+        ENGLISH.get_or_init(|| {
+            Catalog::from_json(ENGLISH_JSON).expect("built-in English catalog is valid")
+        })
+    }
+
+    /// Built-in catalog for `language`, if there is one.
+    ///
+    /// Matching ignores case and any region subtag, so `es-MX` selects the
+    /// Spanish catalog.
+    pub fn builtin(language: &str) -> Option<Catalog> {
+        let primary = language
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Catalog::english().clone()),
+            "es" => Catalog::from_json(SPANISH_JSON).ok(),
+            _ => None,
+        }
+    }
+
+    /// Parse a catalog from its JSON form.
+    pub fn from_json(json: &str) -> Result<Catalog, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Read a catalog from a JSON file.
+    ///
+    /// - **ID**: FN-LOC-001
+    /// - **Requirement**: Reports can be produced in languages not built in.
+    /// - **Inputs**: Path of a catalog JSON file.
+    /// - **Outputs**: The catalog.
+    /// - **Side Effects**: Reads the file.
+    /// - **Failure Modes**: `CatalogError` if the file cannot be read or is
+    ///   not a catalog.
+    pub fn load(path: impl AsRef<Path>) -> Result<Catalog, CatalogError> {
+        Ok(Catalog::from_json(&fs::read_to_string(path)?)?)
+    }
+
+    /// Catalog selected by the environment.
+    ///
+    /// `FREQ_SIM_CATALOG` names a catalog file to load; otherwise
+    /// `FREQ_SIM_LANG`, then `LANG`, selects a built-in catalog. Falls back
+    /// to English when neither yields a catalog.
+    pub fn from_env() -> Catalog {
+        if let Ok(path) = std::env::var("FREQ_SIM_CATALOG") {
+            match Catalog::load(&path) {
+                Ok(catalog) => return catalog,
+                Err(e) => eprintln!("⚠️  {} ({}), using English", e, path),
+            }
+        }
+        ["FREQ_SIM_LANG", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|language| Catalog::builtin(&language))
+            .unwrap_or_else(|| Catalog::english().clone())
+    }
+
+    /// Template for `key`.
+    ///
+    /// Falls back to the English template, then to the key itself, so a
+    /// missing translation never drops text from a report.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .or_else(|| Catalog::english().messages.get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Template for `key` with `{name}` placeholders filled from `args`.
+    ///
+    /// - **ID**: FN-LOC-002
+    /// - **Requirement**: Localized text carries the figures of the report.
+    /// - **Inputs**: Message key; placeholder names and their values.
+    /// - **Outputs**: The filled-in message.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: Placeholders without an argument are left as
+    ///   written.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut message = self.text(key).to_string();
+        for (name, value) in args {
+            message = message.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        message
+    }
+
+    /// English keys this catalog does not translate.
+    pub fn missing_keys(&self) -> Vec<&'static str> {
+        Catalog::english()
+            .messages
+            .keys()
+            .filter(|key| !self.messages.contains_key(key.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::english().clone()
+    }
+}
+
+/// Report or label that can be rendered from a message catalog.
+pub trait Localize {
+    /// Write the text of `self` in the language of `catalog`.
+    fn write_localized(&self, catalog: &Catalog, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// `Display` adapter rendering `self` in the language of `catalog`.
+    fn localized<'a>(&'a self, catalog: &'a Catalog) -> Localized<'a, Self> {
+        Localized {
+            value: self,
+            catalog,
+        }
+    }
+}
+
+/// A value rendered from a message catalog; see [`Localize::localized`].
+pub struct Localized<'a, T: ?Sized> {
+    value: &'a T,
+    catalog: &'a Catalog,
+}
+
+impl<T: Localize + ?Sized> fmt::Display for Localized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Render first so width and alignment apply to the whole text
+        let mut text = String::new();
+        self.value.write_localized(self.catalog, &mut text)?;
+        f.pad(&text)
+    }
+}
@@ -1,8 +1,23 @@
+//! Frequency band simulation demonstration (`freq_sim`)
+//!
+//! Deprecated: runs one fixed demonstration and reads no settings. Use
+//! `space-comms simulate`, which takes its link from the layered settings
+//! file, environment and flags, for configurable runs.
+
 use frequency_band_simulation::advanced_rf;
+use frequency_band_simulation::formation;
+use frequency_band_simulation::harq::{self, ArqConfig};
+use frequency_band_simulation::interleaving::{self, InterleaverConfig};
+use frequency_band_simulation::monte_carlo::MonteCarlo;
+use frequency_band_simulation::receiver::{
+    pulsed_jammer, FrontEndSummary, ReceivedPower, ReceiverFrontEnd,
+};
+use frequency_band_simulation::time_transfer;
 use frequency_band_simulation::*;
 
 /// Main entry point for frequency band simulation
 fn main() {
+    eprintln!("freq_sim is deprecated: use `space-comms simulate` for configurable runs");
     println!("===== SPACE FREQUENCY BAND SIMULATION =====");
     println!("Simulating satellite-ground communications across different bands\n");
 
@@ -44,7 +59,7 @@ fn main() {
     // --- AMC ---
     println!("5. ADAPTIVE MODULATION & CODING (30 dB SNR, 500 MHz)");
     println!("   Modulation     : {:?}", summary.amc.modulation);
-    println!("   Coding Rate    : {:?}", summary.amc.coding_rate);
+    println!("   Coding Rate    : {:?} ({})", summary.amc.coding_rate, summary.amc.fec);
     println!("   Throughput     : {:.0} Mbps", summary.amc.throughput_mbps);
     println!("   Spectral Eff.  : {:.2} bps/Hz", summary.amc.spectral_efficiency);
     println!("   Link Margin    : {:.1} dB\n", summary.amc.link_margin_db);
@@ -56,6 +71,112 @@ fn main() {
     println!("   Capacity ×     : {:.1}×", summary.polarization.capacity_multiplier);
     println!("   Faraday Correct: {}\n", summary.polarization.faraday_correction_needed);
 
+    println!("================================================================");
+    println!("===== CONSTELLATION TIME TRANSFER =====\n");
+
+    // --- Per-satellite clock offsets from the reference timescale ---
+    let report = time_transfer::run_time_transfer_demo();
+    println!("7 satellites, two-way ISL exchanges every 60 s for 3 h, SAT-1 reference");
+    println!("   Satellite  Final (ns)  Max (ns)   RMS (ns)   Free-running epochs");
+    for node in report.summary() {
+        println!(
+            "   {:<10} {:>10.1}  {:>9.1}  {:>9.1}  {}",
+            node.name,
+            node.final_offset_s * 1e9,
+            node.max_abs_offset_s * 1e9,
+            node.rms_offset_s * 1e9,
+            node.free_running_epochs
+        );
+    }
+    println!();
+
+    println!("===== FORMATION FLYING CROSSLINK RANGING =====\n");
+
+    // --- Inter-satellite range and range rate from S-band crosslink ranging ---
+    let (samples, ranging) = formation::run_formation_ranging_demo();
+    println!("Deputy <1 km behind a 550 km chief, two orbits sampled every 10 s");
+    println!(
+        "   Range span: {:.0} m to {:.0} m, {}/{} epochs in lock",
+        ranging.range_span_m.0, ranging.range_span_m.1, ranging.locked_epochs, ranging.epochs
+    );
+    println!(
+        "   Range error:      RMS {:.2} cm    (predicted 1σ {:.2} cm)",
+        ranging.range_rms_error_m * 1e2,
+        ranging.mean_range_sigma_m * 1e2
+    );
+    println!(
+        "   Range rate error: RMS {:.2} µm/s  (predicted 1σ {:.2} µm/s)",
+        ranging.range_rate_rms_error_m_s * 1e6,
+        ranging.mean_range_rate_sigma_m_s * 1e6
+    );
+    if let Some(first) = samples.first() {
+        println!("   Crosslink C/N0 at start: {:.1} dB-Hz", first.cn0_dbhz);
+    }
+    println!();
+
+    println!("===== BURST-ERROR RESILIENCE =====\n");
+
+    // --- Residual frame error rates with and without interleaving ---
+    let config = InterleaverConfig::default();
+    match interleaving::run_burst_error_demo(&config, &MonteCarlo::new(500, 7)) {
+        Ok(reports) => {
+            println!("RS(255,223) + CCSDS randomizer over Gilbert-Elliott burst channels, 500 frames");
+            for (plain, interleaved) in reports {
+                println!("   {}", plain);
+                println!("   {}", interleaved);
+            }
+        }
+        Err(e) => println!("   Burst error study failed: {}", e),
+    }
+    println!();
+
+    // --- Plain against hybrid ARQ as the good-state bit error rate rises ---
+    match harq::run_harq_demo(&config, &ArqConfig::default(), &MonteCarlo::new(50, 7)) {
+        Ok(rows) => {
+            println!("X-band ARQ, 32-frame windows, up to 4 rounds, 50 windows");
+            for (ber, plain, hybrid) in rows {
+                println!("   good-state BER {:.0e}", ber);
+                println!("      {}", plain);
+                println!("      {}", hybrid);
+            }
+        }
+        Err(e) => println!("   ARQ study failed: {}", e),
+    }
+    println!();
+
+    println!("===== RECEIVER FRONT END =====\n");
+
+    // --- Near-far: adjacent-channel interferer 80 dB down in the channel filter ---
+    let front_end = ReceiverFrontEnd::default();
+    println!("-100 dBm wanted signal, interferer with 80 dB channel rejection");
+    match front_end.near_far_sweep(-100.0, 80.0, &[-90.0, -70.0, -50.0, -30.0, -15.0]) {
+        Ok(outputs) => {
+            for (level, out) in [-90, -70, -50, -30, -15].iter().zip(outputs) {
+                println!(
+                    "   {:>4} dBm: gain {:>5.1} dB, SNR {:>6.1} dB (additive model {:>5.1} dB)",
+                    level, out.gain_db, out.snr_db, out.additive_snr_db
+                );
+            }
+        }
+        Err(e) => println!("   Near-far sweep failed: {}", e),
+    }
+
+    // --- Pulsed jammer: 20 ms on in every 100 ms ---
+    let jammer = ReceivedPower {
+        signal_dbm: -100.0,
+        interferer_dbm: -45.0,
+        interferer_rejection_db: 80.0,
+    };
+    let samples = pulsed_jammer(-100.0, jammer, 0.02, 0.1, 0.3, 0.0005);
+    match front_end.run(&samples) {
+        Ok(outputs) => println!(
+            "   Pulsed jammer: {}",
+            FrontEndSummary::from_outputs(&outputs)
+        ),
+        Err(e) => println!("   Pulsed jammer run failed: {}", e),
+    }
+    println!();
+
     println!("================================================================");
     println!("Simulation completed successfully!");
 }
@@ -86,6 +86,8 @@ fn main() {
         elevation_angle_degrees: 30.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     println!("\n📡 Transmission Parameters:");
@@ -177,6 +179,8 @@ fn run_batch_simulation(duration_hours: f64) -> Result<(), Box<dyn std::error::E
         elevation_angle_degrees: 45.0,
         transmit_power_watts: 150.0,
         antenna_diameter_meters: 4.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
 
     // CSV output preparation
@@ -286,6 +290,8 @@ fn interactive_transmission_simulation(bands: &[FrequencyBand]) -> Result<(), Bo
         elevation_angle_degrees: elevation,
         transmit_power_watts: power,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
     
     let environment = EnvironmentalConditions {
@@ -320,6 +326,8 @@ fn interactive_band_comparison(bands: &[FrequencyBand]) -> Result<(), Box<dyn st
         elevation_angle_degrees: 30.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
     
     // Test across different rain rates
@@ -368,6 +376,8 @@ fn interactive_weather_analysis(bands: &[FrequencyBand]) -> Result<(), Box<dyn s
         elevation_angle_degrees: 45.0,
         transmit_power_watts: 120.0,
         antenna_diameter_meters: 3.5,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
     
     for (weather_name, atmospheric_conditions) in weather_scenarios {
@@ -518,6 +528,8 @@ fn run_stress_test() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: 10.0, // Low elevation (worst case)
         transmit_power_watts: 50.0, // Limited power
         antenna_diameter_meters: 2.0, // Smaller antenna
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
     
     for (condition_name, environment) in extreme_conditions {
@@ -571,6 +583,8 @@ fn run_band_comparison() -> Result<(), Box<dyn std::error::Error>> {
                 elevation_angle_degrees: 30.0,
                 transmit_power_watts: 100.0,
                 antenna_diameter_meters: 3.0,
+                range_rate_m_s: 0.0,
+                range_acceleration_m_s2: 0.0,
             };
             
             let environment = EnvironmentalConditions {
@@ -613,6 +627,8 @@ fn run_weather_impact_analysis() -> Result<(), Box<dyn std::error::Error>> {
         elevation_angle_degrees: 30.0,
         transmit_power_watts: 100.0,
         antenna_diameter_meters: 3.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     };
     
     println!("\nTransmission Success Rate vs Rain Rate:");
@@ -0,0 +1,128 @@
+//! Budget Margin Policy Module
+//!
+//! A simulated link that closes with no margin would fail on the first fade
+//! in operation. `MarginPolicy` holds the margins a mission requires: the SNR
+//! a link must keep above its closure threshold, and the fraction of the
+//! available power held in reserve. `FrequencyBand::simulate_transmission_with`
+//! declares a transmission successful only if the policy is met, and every
+//! study that judges links (band scoring, link budgets, sweeps, Monte Carlo
+//! availability) carries the policy it applies, so a mission's margins are
+//! set in one place instead of as thresholds scattered through the code.
+//!
+//! The policy serializes with the same field names as the ground station's
+//! margin policy (`space_comms_shared::margin`), so one mission policy file
+//! configures both; the simulation adds the power the margin is taken from.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (link closure with margin)
+//! - REQ-FN-008: Frequency Band Simulation (consistent success criteria)
+
+use serde::{Deserialize, Serialize};
+
+/// SNR at which a link closes with no margin, dB.
+pub const LINK_CLOSURE_SNR_DB: f64 = 10.0;
+
+/// Default SNR required above closure, dB.
+pub const DEFAULT_LINK_MARGIN_DB: f64 = 3.0;
+
+/// Default fraction of the available power held in reserve.
+pub const DEFAULT_POWER_MARGIN: f64 = 0.2;
+
+/// Margins a mission requires of its links.
+///
+/// - **ID**: MOD-MAR-002
+/// - **Requirement**: One configurable set of required margins applied
+///   wherever the simulation judges a link.
+/// - **Constraints**: The power margin is only checked when
+///   `available_power_watts` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarginPolicy {
+    /// SNR at which the link closes with no margin, dB.
+    pub closure_snr_db: f64,
+    /// SNR required above `closure_snr_db`, dB.
+    pub link_margin_db: f64,
+    /// Fraction of `available_power_watts` held in reserve, 0-1.
+    pub power_margin: f64,
+    /// Power available to the transmitter, W; `None` skips the power check.
+    pub available_power_watts: Option<f64>,
+}
+
+impl Default for MarginPolicy {
+    /// 3 dB link margin and 20% power margin, with no power limit.
+    fn default() -> Self {
+        Self {
+            closure_snr_db: LINK_CLOSURE_SNR_DB,
+            link_margin_db: DEFAULT_LINK_MARGIN_DB,
+            power_margin: DEFAULT_POWER_MARGIN,
+            available_power_watts: None,
+        }
+    }
+}
+
+impl MarginPolicy {
+    /// Policy that only requires the link to close, with no power limit.
+    pub fn closure_only() -> Self {
+        Self {
+            link_margin_db: 0.0,
+            power_margin: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// Same policy with transmitter power limited to `watts`.
+    pub fn with_available_power(mut self, watts: f64) -> Self {
+        self.available_power_watts = Some(watts);
+        self
+    }
+
+    /// Lowest SNR that meets the link margin, dB.
+    pub fn required_snr_db(&self) -> f64 {
+        self.closure_snr_db + self.link_margin_db
+    }
+
+    /// SNR above the closure threshold, dB; negative if the link does not
+    /// close.
+    pub fn link_margin(&self, snr_db: f64) -> f64 {
+        snr_db - self.closure_snr_db
+    }
+
+    /// Whether `snr_db` meets the link margin.
+    pub fn link_ok(&self, snr_db: f64) -> bool {
+        self.link_margin(snr_db) >= self.link_margin_db
+    }
+
+    /// Fraction of the available power left unused by `consumption_watts`,
+    /// if the available power is known.
+    pub fn power_margin(&self, consumption_watts: f64) -> Option<f64> {
+        self.available_power_watts
+            .map(|available| 1.0 - consumption_watts / available)
+    }
+
+    /// Whether `consumption_watts` meets the power margin.
+    pub fn power_ok(&self, consumption_watts: f64) -> bool {
+        self.power_margin(consumption_watts)
+            .is_none_or(|margin| margin >= self.power_margin)
+    }
+
+    /// Whether a transmission meets every margin and its required data rate.
+    ///
+    /// - **ID**: FN-MAR-004
+    /// - **Requirement**: A transmission is successful only if it keeps the
+    ///   required link and power margins.
+    /// - **Inputs**: SNR (dB), achieved and required data rates (Mbps) and
+    ///   power consumption (W).
+    /// - **Outputs**: `true` if all are met.
+    /// - **Side Effects**: None.
+    pub fn accepts(
+        &self,
+        snr_db: f64,
+        actual_rate_mbps: f64,
+        required_rate_mbps: f64,
+        consumption_watts: f64,
+    ) -> bool {
+        self.link_ok(snr_db)
+            && actual_rate_mbps >= required_rate_mbps
+            && self.power_ok(consumption_watts)
+    }
+}
@@ -0,0 +1,261 @@
+//! MODCOD Table Module
+//!
+//! Adaptive coding and modulation picks from a table of modulation and
+//! coding pairs (MODCODs), each with the Eb/N0 it needs. This module builds
+//! that table for a forward error correction family:
+//!
+//! - **Legacy**: Reed-Solomon concatenated with convolutional coding, with
+//!   the fixed coding gains of `CodingRate::coding_gain_db`.
+//! - **LDPC**: CCSDS LDPC codes (the C2 code at rate 7/8), whose gain is
+//!   read from a bit error rate [`PerformanceCurve`] at [`TARGET_BER`].
+//!   The gain over uncoded BPSK is several dB larger than legacy coding at
+//!   every rate, which is what lets high-rate Ka-band links close with
+//!   dense modulations.
+//!
+//! With the `ldpc` feature, [`measure_ldpc_curve`] measures curve points by
+//! Monte Carlo through the shared C2 encoder and decoder over an AWGN BPSK
+//! channel, to check the tabulated curve or replace it.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-003: Link Capacity Optimisation (MODCOD table per FEC family)
+//! - REQ-PF-002: Data Transfer Rates (LDPC for high-rate Ka-band links)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::advanced_rf::{CodingRate, ModulationScheme};
+
+/// Bit error rate a MODCOD's required Eb/N0 is read at.
+pub const TARGET_BER: f64 = 1e-6;
+
+/// Modulations in the table, highest order first.
+const MODULATIONS: [ModulationScheme; 5] = [
+    ModulationScheme::Qam256,
+    ModulationScheme::Qam64,
+    ModulationScheme::Qam16,
+    ModulationScheme::Qpsk,
+    ModulationScheme::Bpsk,
+];
+
+/// Coding rates in the table, highest first.
+const RATES: [CodingRate; 5] = [
+    CodingRate::Rate7_8,
+    CodingRate::Rate5_6,
+    CodingRate::Rate3_4,
+    CodingRate::Rate2_3,
+    CodingRate::Rate1_2,
+];
+
+/// Forward error correction family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FecScheme {
+    /// Reed-Solomon concatenated with convolutional coding.
+    #[default]
+    Legacy,
+    /// CCSDS LDPC codes.
+    Ldpc,
+}
+
+impl fmt::Display for FecScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FecScheme::Legacy => "RS+CC",
+            FecScheme::Ldpc => "LDPC",
+        })
+    }
+}
+
+/// One point of a bit error rate curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurvePoint {
+    /// Eb/N0, dB.
+    pub eb_n0_db: f64,
+    /// Bit error rate after decoding.
+    pub bit_error_rate: f64,
+}
+
+/// Decoded bit error rate against Eb/N0, in rising Eb/N0 order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceCurve {
+    /// Measured or tabulated points.
+    pub points: Vec<CurvePoint>,
+}
+
+impl PerformanceCurve {
+    /// Curve from (Eb/N0 dB, bit error rate) pairs.
+    pub fn from_points(points: &[(f64, f64)]) -> Self {
+        Self {
+            points: points
+                .iter()
+                .map(|&(eb_n0_db, bit_error_rate)| CurvePoint {
+                    eb_n0_db,
+                    bit_error_rate,
+                })
+                .collect(),
+        }
+    }
+
+    /// Eb/N0 at which the bit error rate falls to `target`, dB.
+    ///
+    /// Interpolates linearly in log error rate between the points either
+    /// side. `None` when the curve never reaches `target` or starts below
+    /// it; points with a zero error rate are skipped.
+    pub fn required_eb_n0_db(&self, target: f64) -> Option<f64> {
+        let points: Vec<&CurvePoint> = self
+            .points
+            .iter()
+            .filter(|p| p.bit_error_rate > 0.0)
+            .collect();
+        points.windows(2).find_map(|pair| {
+            let (a, b) = (pair[0], pair[1]);
+            if a.bit_error_rate < target || b.bit_error_rate > target {
+                return None;
+            }
+            let (la, lb) = (a.bit_error_rate.log10(), b.bit_error_rate.log10());
+            let fraction = if la == lb {
+                0.0
+            } else {
+                (la - target.log10()) / (la - lb)
+            };
+            Some(a.eb_n0_db + fraction * (b.eb_n0_db - a.eb_n0_db))
+        })
+    }
+}
+
+/// Tabulated LDPC performance at `rate` over AWGN with BPSK.
+///
+/// Rate 7/8 is the CCSDS C2 code; the others follow the CCSDS AR4JA family
+/// and comparable codes, about 1 dB from capacity at 10⁻⁶.
+pub fn ldpc_curve(rate: CodingRate) -> PerformanceCurve {
+    let waterfall_db = match rate {
+        CodingRate::Rate1_2 => 0.8,
+        CodingRate::Rate2_3 => 1.6,
+        CodingRate::Rate3_4 => 2.1,
+        CodingRate::Rate5_6 => 2.7,
+        CodingRate::Rate7_8 => 3.3,
+    };
+    PerformanceCurve::from_points(&[
+        (waterfall_db, 1e-2),
+        (waterfall_db + 0.4, 1e-4),
+        (waterfall_db + 0.7, 1e-6),
+        (waterfall_db + 1.0, 1e-8),
+    ])
+}
+
+/// Eb/N0 gain over uncoded BPSK of a code family at `rate`, dB.
+pub fn coding_gain_db(fec: FecScheme, rate: CodingRate) -> f64 {
+    match fec {
+        FecScheme::Legacy => rate.coding_gain_db(),
+        FecScheme::Ldpc => {
+            let uncoded = ModulationScheme::Bpsk.min_eb_n0_db();
+            ldpc_curve(rate)
+                .required_eb_n0_db(TARGET_BER)
+                .map_or(rate.coding_gain_db(), |required| uncoded - required)
+        }
+    }
+}
+
+/// One entry of the MODCOD table.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModCod {
+    /// Modulation.
+    pub modulation: ModulationScheme,
+    /// Code rate.
+    pub coding_rate: CodingRate,
+    /// Code family.
+    pub fec: FecScheme,
+    /// Eb/N0 needed for [`TARGET_BER`], dB.
+    pub required_eb_n0_db: f64,
+}
+
+impl ModCod {
+    /// Information bits per symbol.
+    pub fn spectral_efficiency(&self) -> f64 {
+        self.modulation.bits_per_symbol() * self.coding_rate.ratio()
+    }
+}
+
+/// MODCOD table of a code family, highest modulation order first and
+/// highest code rate first within each.
+///
+/// - **ID**: FN-ACM-001
+/// - **Requirement**: Required Eb/N0 of every MODCOD from its modulation
+///   threshold and the code family's gain, LDPC gains from their performance
+///   curves.
+pub fn modcod_table(fec: FecScheme) -> Vec<ModCod> {
+    MODULATIONS
+        .iter()
+        .flat_map(|&modulation| {
+            RATES.iter().map(move |&coding_rate| ModCod {
+                modulation,
+                coding_rate,
+                fec,
+                required_eb_n0_db: modulation.min_eb_n0_db() - coding_gain_db(fec, coding_rate),
+            })
+        })
+        .collect()
+}
+
+/// Measure the bit error rate of an LDPC code over AWGN with BPSK.
+///
+/// - **ID**: FN-ACM-002
+/// - **Requirement**: Performance curve points of the decoder as built,
+///   for checking or replacing the tabulated curve.
+/// - **Inputs**:
+///   - `code`: Code to measure, e.g. `LdpcCode::c2()`.
+///   - `eb_n0_db`: Eb/N0 of each point, dB.
+///   - `study`: One trial per codeword at each point; reproducible from its
+///     seed.
+///   - `max_iterations`: Decoder iterations per codeword.
+/// - **Outputs**: One point per Eb/N0, with the information bit error rate.
+#[cfg(feature = "ldpc")]
+pub fn measure_ldpc_curve(
+    code: &space_comms_shared::ldpc::LdpcCode,
+    eb_n0_db: &[f64],
+    study: &crate::monte_carlo::MonteCarlo,
+    max_iterations: usize,
+) -> PerformanceCurve {
+    use rand::Rng;
+
+    let points = eb_n0_db
+        .iter()
+        .map(|&db| {
+            // Unit-energy BPSK symbols; noise variance from Eb/N0 at the
+            // code rate
+            let eb_n0 = 10f64.powf(db / 10.0);
+            let sigma = (1.0 / (2.0 * code.rate() * eb_n0)).sqrt();
+            let errors: usize = study
+                .run(|_, rng| {
+                    let information: Vec<u8> =
+                        (0..code.k()).map(|_| u8::from(rng.gen::<bool>())).collect();
+                    let codeword = code
+                        .encode(&information)
+                        .unwrap_or_else(|e| unreachable!("block sized by the code: {}", e));
+                    let llrs: Vec<f32> = codeword
+                        .iter()
+                        .map(|&bit| {
+                            let symbol = if bit == 0 { 1.0 } else { -1.0 };
+                            let received = symbol + sigma * crate::formation::gaussian(rng);
+                            (2.0 * received / (sigma * sigma)) as f32
+                        })
+                        .collect();
+                    let decoded = code
+                        .decode(&llrs, max_iterations)
+                        .unwrap_or_else(|e| unreachable!("codeword sized by the code: {}", e));
+                    code.information(&decoded.codeword)
+                        .iter()
+                        .zip(&information)
+                        .filter(|(a, b)| a != b)
+                        .count()
+                })
+                .into_iter()
+                .sum();
+            CurvePoint {
+                eb_n0_db: db,
+                bit_error_rate: errors as f64 / (study.trials * code.k()).max(1) as f64,
+            }
+        })
+        .collect();
+    PerformanceCurve { points }
+}
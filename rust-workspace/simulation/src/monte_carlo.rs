@@ -0,0 +1,440 @@
+//! Monte Carlo Module
+//!
+//! `MonteCarlo` runs a trial closure many times, handing each trial its own
+//! random number generator. The generator for trial `i` is seeded from the
+//! study seed and `i` alone, so a trial draws the same numbers whichever
+//! thread runs it and in whatever order: a study is reproducible from its
+//! seed, and any single trial can be re-run on its own with `trial_rng`.
+//!
+//! With the `parallel` feature the trials are distributed over a rayon thread
+//! pool, either the global pool or one sized with `with_threads`. Trials are
+//! independent, so the speedup is close to linear in the number of cores.
+//! Results are identical and in trial order either way.
+//!
+//! `run` keeps every trial result. For long studies, `stream` hands results
+//! to a sink in trial order a batch at a time, so memory stays constant and
+//! `summarize` can feed a `StreamSummary` without storing samples.
+//!
+//! Each of `run`, `stream` and `link_availability` has a `_controlled`
+//! variant taking a `RunControl`, which reports progress after every batch
+//! and stops the study when cancelled; see the `progress` module.
+//!
+//! `link_availability` is the common study built on top: simulate one band
+//! under randomly drawn conditions and count the trials that close the link.
+//! With `with_math(MathPath::Fast)` it simulates each batch of drawn
+//! conditions at once with `FrequencyBand::simulate_batch` and the
+//! `fast_math` approximations, within the accuracy documented there.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (statistical link studies)
+
+use std::fmt;
+use std::ops::Range;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::fast_math::MathPath;
+use crate::locale::{Catalog, Localize};
+use crate::margin::MarginPolicy;
+use crate::progress::{CancelToken, RunControl, RunError};
+use crate::stats::{RunningStats, StreamSummary};
+use crate::validation::ValidationError;
+use crate::{EnvironmentalConditions, FrequencyBand, TransmissionParameters};
+
+/// Trials run between hand-offs to a `stream` sink. Bounds the results held
+/// at once while giving each parallel batch enough work to spread out.
+pub const STREAM_BATCH_TRIALS: usize = 16_384;
+
+/// Seed for one trial of a study.
+///
+/// Mixes the study seed and trial index with the SplitMix64 finalizer so
+/// neighbouring trials get unrelated generator streams.
+pub fn trial_seed(seed: u64, trial: usize) -> u64 {
+    let mut z = seed
+        .wrapping_add(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add((trial as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// A reproducible Monte Carlo study.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonteCarlo {
+    /// Number of trials to run.
+    pub trials: usize,
+    /// Study seed; every trial seed derives from it.
+    pub seed: u64,
+    /// Worker threads with the `parallel` feature; `None` uses the global
+    /// rayon pool. Ignored without the feature.
+    pub threads: Option<usize>,
+    /// Margins a trial's link must keep to count as available.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+    /// Math for the link model in `link_availability`.
+    #[serde(default)]
+    pub math: MathPath,
+}
+
+impl MonteCarlo {
+    /// Study of `trials` trials seeded from `seed`.
+    pub fn new(trials: usize, seed: u64) -> Self {
+        Self {
+            trials,
+            seed,
+            threads: None,
+            margins: MarginPolicy::default(),
+            math: MathPath::Precise,
+        }
+    }
+
+    /// Judge link availability against the mission's `margins`.
+    pub fn with_margins(mut self, margins: MarginPolicy) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Simulate links with `math`; `MathPath::Fast` trades the accuracy
+    /// documented in `fast_math` for speed.
+    pub fn with_math(mut self, math: MathPath) -> Self {
+        self.math = math;
+        self
+    }
+
+    /// Run on a dedicated pool of `threads` workers (`parallel` feature).
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Generator for one trial, as handed to the trial closure by `run`.
+    pub fn trial_rng(&self, trial: usize) -> StdRng {
+        StdRng::seed_from_u64(trial_seed(self.seed, trial))
+    }
+
+    /// Run the study.
+    ///
+    /// - **ID**: FN-MC-001
+    /// - **Requirement**: Each trial draws from its own deterministically
+    ///   seeded generator so results do not depend on scheduling.
+    /// - **Inputs**:
+    ///   - `trial`: Called with the trial index and that trial's generator.
+    /// - **Outputs**: One result per trial, in trial order.
+    /// - **Side Effects**: With the `parallel` feature, uses the global rayon
+    ///   thread pool or builds one of `threads` workers.
+    /// - **Failure Modes**: If a dedicated pool cannot be built, the global
+    ///   pool is used instead.
+    pub fn run<T, F>(&self, trial: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+    {
+        self.run_controlled(trial, &mut RunControl::new())
+            .unwrap_or_else(|error| unreachable!("uncontrolled study failed: {}", error))
+    }
+
+    /// Run the study under `control`.
+    ///
+    /// - **ID**: FN-MC-004
+    /// - **Requirement**: Long studies report their progress and can be
+    ///   aborted cleanly.
+    /// - **Inputs**:
+    ///   - `trial`: As for `run`.
+    ///   - `control`: Progress observer and cancel token.
+    /// - **Outputs**: The same results as `run`.
+    /// - **Side Effects**: As `run`; reports progress to `control` at the
+    ///   start and after every `STREAM_BATCH_TRIALS` trials.
+    /// - **Failure Modes**: `RunError::Cancelled` once the cancel token is
+    ///   cancelled; no trial starts after that.
+    pub fn run_controlled<T, F>(
+        &self,
+        trial: F,
+        control: &mut RunControl<'_>,
+    ) -> Result<Vec<T>, RunError>
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+    {
+        let mut results = Vec::with_capacity(self.trials);
+        self.stream_batches(trial, control, |_, batch| results.extend(batch))?;
+        Ok(results)
+    }
+
+    /// Run the study, handing each result to `sink` in trial order.
+    ///
+    /// - **ID**: FN-MC-003
+    /// - **Requirement**: Studies of any length run in constant memory.
+    /// - **Inputs**:
+    ///   - `trial`: As for `run`.
+    ///   - `sink`: Called with the trial index and result.
+    /// - **Outputs**: None; results go to `sink`.
+    /// - **Side Effects**: As `run`; at most `STREAM_BATCH_TRIALS` results
+    ///   are held at once.
+    pub fn stream<T, F, S>(&self, trial: F, sink: S)
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+        S: FnMut(usize, T),
+    {
+        self.stream_controlled(trial, sink, &mut RunControl::new())
+            .unwrap_or_else(|error| unreachable!("uncontrolled study failed: {}", error))
+    }
+
+    /// Run the study as `stream`, under `control` as `run_controlled`.
+    /// Results of trials before cancellation have already reached `sink`.
+    pub fn stream_controlled<T, F, S>(
+        &self,
+        trial: F,
+        mut sink: S,
+        control: &mut RunControl<'_>,
+    ) -> Result<(), RunError>
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+        S: FnMut(usize, T),
+    {
+        self.stream_batches(trial, control, |start, results| {
+            for (index, result) in (start..).zip(results) {
+                sink(index, result);
+            }
+        })
+    }
+
+    /// Run the study, handing results to `sink` a batch of at most
+    /// `STREAM_BATCH_TRIALS` at a time, with the index of the batch's first
+    /// trial.
+    fn stream_batches<T, F, S>(
+        &self,
+        trial: F,
+        control: &mut RunControl<'_>,
+        mut sink: S,
+    ) -> Result<(), RunError>
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+        S: FnMut(usize, Vec<T>),
+    {
+        let pool = self.worker_pool();
+        let mut tracker = control.start(self.trials);
+        let mut start = 0;
+        while start < self.trials {
+            let end = self.trials.min(start + STREAM_BATCH_TRIALS);
+            match self.run_range(&pool, start..end, &trial, tracker.cancel_token()) {
+                Some(results) => sink(start, results),
+                None => return Err(tracker.cancelled()),
+            }
+            tracker.advance(end - start)?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Summary statistics of a per-trial value without storing the values.
+    ///
+    /// The summary is identical with and without the `parallel` feature,
+    /// since samples reach it in trial order either way.
+    pub fn summarize<F>(&self, quantiles: &[f64], trial: F) -> StreamSummary
+    where
+        F: Fn(usize, &mut StdRng) -> f64 + Sync + Send,
+    {
+        let mut summary = StreamSummary::new(quantiles);
+        self.stream(trial, |_, value| summary.push(value));
+        summary
+    }
+
+    fn worker_pool(&self) -> WorkerPool {
+        WorkerPool {
+            #[cfg(feature = "parallel")]
+            pool: self.threads.and_then(|threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .ok()
+            }),
+        }
+    }
+
+    /// Results of the trials in `range`, or `None` if `cancel` was cancelled
+    /// before they all started.
+    fn run_range<T, F>(
+        &self,
+        pool: &WorkerPool,
+        range: Range<usize>,
+        trial: &F,
+        cancel: &CancelToken,
+    ) -> Option<Vec<T>>
+    where
+        T: Send,
+        F: Fn(usize, &mut StdRng) -> T + Sync + Send,
+    {
+        let simulate = |index: usize| {
+            (!cancel.is_cancelled()).then(|| trial(index, &mut self.trial_rng(index)))
+        };
+        pool.install(|| {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+                range.into_par_iter().map(simulate).collect()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                range.map(simulate).collect()
+            }
+        })
+    }
+
+    /// Estimate how often a link closes under random conditions.
+    ///
+    /// - **ID**: FN-MC-002
+    /// - **Requirement**: Link availability over the distribution of
+    ///   environmental conditions (REQ-FN-008).
+    /// - **Inputs**:
+    ///   - `band`: Band to simulate.
+    ///   - `params`: Transmission parameters, the same for every trial.
+    ///   - `sample`: Draws the conditions for one trial.
+    /// - **Outputs**: Successful trials and mean SNR over all trials. With
+    ///   `MathPath::Fast`, the SNR agrees with the precise study to within
+    ///   the `fast_math` bounds.
+    /// - **Side Effects**: As `stream`.
+    /// - **Failure Modes**: `ValidationError` if `params` are out of range,
+    ///   or from the first trial whose sampled conditions are.
+    pub fn link_availability<S>(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        sample: S,
+    ) -> Result<LinkAvailability, ValidationError>
+    where
+        S: Fn(&mut StdRng) -> EnvironmentalConditions + Sync + Send,
+    {
+        self.link_availability_controlled(band, params, sample, &mut RunControl::new())
+            .map_err(RunError::into_invalid)
+    }
+
+    /// Estimate link availability as `link_availability`, under `control` as
+    /// `run_controlled`.
+    pub fn link_availability_controlled<S>(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+        sample: S,
+        control: &mut RunControl<'_>,
+    ) -> Result<LinkAvailability, RunError>
+    where
+        S: Fn(&mut StdRng) -> EnvironmentalConditions + Sync + Send,
+    {
+        params.validate()?;
+
+        let mut successes = 0;
+        let mut snr_db = RunningStats::new();
+        let mut tally = |success: bool, snr: f64| {
+            successes += usize::from(success);
+            snr_db.push(snr);
+        };
+        let mut invalid = None;
+        let streamed = match self.math {
+            MathPath::Precise => self.stream_controlled(
+                |_, rng| {
+                    let environment = sample(rng);
+                    band.simulate_transmission_with(params, &environment, &self.margins)
+                        .map(|result| (result.success, result.signal_to_noise_ratio_db))
+                },
+                |_, outcome| match outcome {
+                    Ok((success, snr)) => tally(success, snr),
+                    Err(error) => {
+                        invalid.get_or_insert(error);
+                    }
+                },
+                control,
+            ),
+            MathPath::Fast => self.stream_batches(
+                |_, rng| sample(rng),
+                control,
+                |_, environments| {
+                    if invalid.is_some() {
+                        return;
+                    }
+                    match band.simulate_batch(params, &environments, &self.margins, self.math) {
+                        Ok(results) => results
+                            .iter()
+                            .for_each(|r| tally(r.success, r.signal_to_noise_ratio_db)),
+                        Err(error) => invalid = Some(error),
+                    }
+                },
+            ),
+        };
+        streamed?;
+        if let Some(error) = invalid {
+            return Err(error.into());
+        }
+
+        Ok(LinkAvailability {
+            trials: self.trials,
+            successes,
+            mean_snr_db: snr_db.mean().unwrap_or(0.0),
+        })
+    }
+}
+
+/// Dedicated rayon pool for a study with `threads` set.
+struct WorkerPool {
+    #[cfg(feature = "parallel")]
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl WorkerPool {
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        #[cfg(feature = "parallel")]
+        if let Some(pool) = &self.pool {
+            return pool.install(op);
+        }
+        op()
+    }
+}
+
+/// Result of a link availability study.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinkAvailability {
+    /// Trials run.
+    pub trials: usize,
+    /// Trials in which the link closed.
+    pub successes: usize,
+    /// Mean signal-to-noise ratio over all trials, dB.
+    pub mean_snr_db: f64,
+}
+
+impl LinkAvailability {
+    /// Fraction of trials in which the link closed, 0-1.
+    pub fn availability(&self) -> f64 {
+        if self.trials == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.trials as f64
+        }
+    }
+}
+
+impl fmt::Display for LinkAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for LinkAvailability {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(&catalog.format(
+            "monte_carlo.availability",
+            &[
+                ("successes", &self.successes),
+                ("trials", &self.trials),
+                (
+                    "availability",
+                    &format!("{:.1}", self.availability() * 100.0),
+                ),
+                ("snr", &format!("{:.1}", self.mean_snr_db)),
+            ],
+        ))
+    }
+}
@@ -0,0 +1,204 @@
+//! Earth Blockage (Radio Occultation) Module
+//!
+//! Line-of-sight geometry for inter-satellite links (ISLs) and relay links in
+//! constellation simulations. A link is blocked when the straight path
+//! between the two nodes passes closer to the Earth's centre than the Earth
+//! radius plus an atmosphere margin, which keeps links from grazing through
+//! the lower atmosphere where refraction and absorption make them unusable.
+//!
+//! `LinkMonitor` steps a set of links through time and emits blockage and
+//! restoration events onto the simulation timeline.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (ISL and relay link availability)
+//! - REQ-FN-008: Frequency Band Simulation (propagation geometry)
+
+use serde::{Deserialize, Serialize};
+
+/// Mean Earth radius, km.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Earth gravitational parameter, km³/s².
+const EARTH_MU_KM3_S2: f64 = 398_600.441_8;
+
+/// Default grazing altitude below which a link is considered blocked, km.
+pub const DEFAULT_ATMOSPHERE_MARGIN_KM: f64 = 100.0;
+
+/// Earth-centred inertial position, km.
+pub type Position = [f64; 3];
+
+/// Circular orbit used to place constellation nodes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CircularOrbit {
+    /// Altitude above the mean Earth radius, km.
+    pub altitude_km: f64,
+    /// Inclination, degrees.
+    pub inclination_deg: f64,
+    /// Right ascension of the ascending node, degrees.
+    pub raan_deg: f64,
+    /// Argument of latitude at t = 0, degrees.
+    pub phase_deg: f64,
+}
+
+impl CircularOrbit {
+    /// Orbital period, seconds.
+    pub fn period_s(&self) -> f64 {
+        let radius = EARTH_RADIUS_KM + self.altitude_km;
+        2.0 * std::f64::consts::PI * (radius.powi(3) / EARTH_MU_KM3_S2).sqrt()
+    }
+
+    /// Position at time `t_s`, km.
+    pub fn position(&self, t_s: f64) -> Position {
+        let radius = EARTH_RADIUS_KM + self.altitude_km;
+        let u = self.phase_deg.to_radians() + 2.0 * std::f64::consts::PI * t_s / self.period_s();
+        let (inc, raan) = (
+            self.inclination_deg.to_radians(),
+            self.raan_deg.to_radians(),
+        );
+
+        // Position in the orbit plane rotated by inclination, then RAAN
+        let (x, y) = (radius * u.cos(), radius * u.sin());
+        let (y, z) = (y * inc.cos(), y * inc.sin());
+        [
+            x * raan.cos() - y * raan.sin(),
+            x * raan.sin() + y * raan.cos(),
+            z,
+        ]
+    }
+}
+
+/// Closest distance from the Earth's centre to the segment `a`–`b`, km.
+pub fn closest_approach_km(a: Position, b: Position) -> f64 {
+    let d = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let len_sq = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+    // Parameter of the point on the segment nearest the origin
+    let t = if len_sq > 0.0 {
+        (-(a[0] * d[0] + a[1] * d[1] + a[2] * d[2]) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let p = [a[0] + t * d[0], a[1] + t * d[1], a[2] + t * d[2]];
+    (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt()
+}
+
+/// Whether the line of sight between `a` and `b` clears the Earth.
+///
+/// - **ID**: FN-OCC-001
+/// - **Requirement**: Links break when the path is occluded by the Earth plus
+///   an atmosphere margin (REQ-FN-007).
+/// - **Inputs**: Node positions (km, Earth-centred) and the grazing margin
+///   above the mean Earth radius, km.
+/// - **Outputs**: `true` when the whole segment stays above
+///   `EARTH_RADIUS_KM + atmosphere_margin_km`.
+/// - **Side Effects**: None.
+pub fn line_of_sight_clear(a: Position, b: Position, atmosphere_margin_km: f64) -> bool {
+    closest_approach_km(a, b) > EARTH_RADIUS_KM + atmosphere_margin_km
+}
+
+/// Link availability change emitted onto the simulation timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkEventKind {
+    /// Line of sight lost behind the Earth.
+    Occulted,
+    /// Line of sight restored.
+    Restored,
+}
+
+/// Timeline event for one link.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinkEvent {
+    /// Simulation time, seconds.
+    pub time_s: f64,
+    /// Index of the link in the monitor.
+    pub link: usize,
+    /// What changed.
+    pub kind: LinkEventKind,
+    /// Grazing altitude of the path above the mean Earth radius at the event, km.
+    pub grazing_altitude_km: f64,
+}
+
+/// Inter-satellite or relay link between two constellation nodes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ConstellationLink {
+    /// Transmitting node.
+    pub from: CircularOrbit,
+    /// Receiving node.
+    pub to: CircularOrbit,
+}
+
+/// Tracks line-of-sight state for a set of links over simulation time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkMonitor {
+    links: Vec<ConstellationLink>,
+    visible: Vec<Option<bool>>,
+    atmosphere_margin_km: f64,
+}
+
+impl LinkMonitor {
+    /// Create a monitor using the given grazing margin above the Earth, km.
+    pub fn new(links: Vec<ConstellationLink>, atmosphere_margin_km: f64) -> Self {
+        let visible = vec![None; links.len()];
+        Self {
+            links,
+            visible,
+            atmosphere_margin_km,
+        }
+    }
+
+    /// Whether link `index` was clear at the last `update`.
+    pub fn is_visible(&self, index: usize) -> Option<bool> {
+        self.visible.get(index).copied().flatten()
+    }
+
+    /// Evaluate every link at time `t_s` and return the state changes.
+    ///
+    /// - **ID**: FN-OCC-002
+    /// - **Requirement**: Emit events to the simulation timeline when links
+    ///   are occluded or restored (REQ-FN-007).
+    /// - **Outputs**: One event per link whose visibility changed since the
+    ///   previous update. The first update only records initial state, except
+    ///   that links already blocked emit `Occulted`.
+    /// - **Side Effects**: Updates the stored visibility.
+    pub fn update(&mut self, t_s: f64) -> Vec<LinkEvent> {
+        let mut events = Vec::new();
+
+        for (index, (link, state)) in self.links.iter().zip(self.visible.iter_mut()).enumerate() {
+            let grazing_altitude_km =
+                closest_approach_km(link.from.position(t_s), link.to.position(t_s))
+                    - EARTH_RADIUS_KM;
+            let clear = grazing_altitude_km > self.atmosphere_margin_km;
+
+            let kind = match (*state, clear) {
+                (Some(true), false) | (None, false) => Some(LinkEventKind::Occulted),
+                (Some(false), true) => Some(LinkEventKind::Restored),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                events.push(LinkEvent {
+                    time_s: t_s,
+                    link: index,
+                    kind,
+                    grazing_altitude_km,
+                });
+            }
+            *state = Some(clear);
+        }
+
+        events
+    }
+
+    /// Step from `start_s` to `end_s` and collect every event, in time order.
+    pub fn run(&mut self, start_s: f64, end_s: f64, step_s: f64) -> Vec<LinkEvent> {
+        let mut events = Vec::new();
+        if step_s <= 0.0 {
+            return events;
+        }
+
+        let mut t = start_s;
+        while t <= end_s {
+            events.extend(self.update(t));
+            t += step_s;
+        }
+        events
+    }
+}
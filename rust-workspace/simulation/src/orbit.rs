@@ -0,0 +1,887 @@
+//! Orbit Propagation Module
+//!
+//! Drives the link model with real orbit geometry instead of a fixed
+//! distance and elevation. A [`Tle`] (NORAD two-line element set) is
+//! propagated with SGP4 ([`Sgp4`]) to the satellite position in the TEME
+//! frame; an [`Observer`] at a ground station location turns that position
+//! into slant range, azimuth and elevation. [`Sgp4::passes`] steps through a
+//! time window and returns each contact above the elevation mask as
+//! `tracking::PassSample`s, which `tracking`, the forecasts and
+//! [`FrequencyBand::simulate_pass`] take as they are.
+//!
+//! SGP4 is implemented for near-Earth orbits (period under 225 minutes)
+//! with the WGS-72 constants the element sets are fitted with, following
+//! Vallado et al., "Revisiting Spacetrack Report #3" (AIAA 2006-6753).
+//! Deep-space orbits (SDP4: lunar and solar perturbations, resonances) are
+//! rejected. TEME is rotated to the Earth-fixed frame by Greenwich mean
+//! sidereal time alone; polar motion and UT1−UTC are ignored, which moves
+//! the ground station by tens of metres at most.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (time-varying range and elevation
+//!   from orbit propagation)
+
+use std::f64::consts::TAU;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::margin::MarginPolicy;
+use crate::tracking::{PassSample, Pointing};
+use crate::validation::ValidationError;
+use crate::{EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult};
+
+/// WGS-72 Earth equatorial radius, km.
+const WGS72_RADIUS_KM: f64 = 6378.135;
+
+/// WGS-72 Earth gravitational parameter, km³/s².
+const WGS72_MU_KM3_S2: f64 = 398_600.8;
+
+/// WGS-72 second zonal harmonic.
+const J2: f64 = 0.001_082_616;
+
+/// WGS-72 third zonal harmonic.
+const J3: f64 = -0.000_002_538_81;
+
+/// WGS-72 fourth zonal harmonic.
+const J4: f64 = -0.000_001_655_97;
+
+/// WGS-84 Earth equatorial radius, km.
+const WGS84_RADIUS_KM: f64 = 6378.137;
+
+/// WGS-84 flattening.
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// Orbit period from which SDP4 deep-space perturbations are needed, minutes.
+pub const DEEP_SPACE_PERIOD_MIN: f64 = 225.0;
+
+/// Minutes in a day.
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Julian date of the J2000 epoch.
+const J2000_JD: f64 = 2_451_545.0;
+
+/// Error reading element sets or propagating them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrbitError {
+    /// A TLE line is malformed.
+    Format { line: u8, reason: &'static str },
+    /// A TLE line's checksum digit does not match its contents.
+    Checksum { line: u8 },
+    /// The orbit needs the SDP4 deep-space model.
+    DeepSpace { period_min: f64 },
+    /// The elements no longer describe an orbit at this time, e.g. after
+    /// decay.
+    Propagation {
+        minutes_since_epoch: f64,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrbitError::Format { line, reason } => write!(f, "TLE line {}: {}", line, reason),
+            OrbitError::Checksum { line } => write!(f, "TLE line {}: checksum mismatch", line),
+            OrbitError::DeepSpace { period_min } => write!(
+                f,
+                "orbit period {:.1} min needs the deep-space model (SDP4)",
+                period_min
+            ),
+            OrbitError::Propagation {
+                minutes_since_epoch,
+                reason,
+            } => write!(
+                f,
+                "propagation failed {:.1} min from epoch: {}",
+                minutes_since_epoch, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrbitError {}
+
+/// NORAD two-line element set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tle {
+    /// Satellite name from the title line, if given.
+    pub name: Option<String>,
+    /// NORAD catalog number.
+    pub catalog_number: u32,
+    /// Epoch year, four digits.
+    pub epoch_year: u16,
+    /// Epoch day of the year, 1.0 at midnight starting 1 January.
+    pub epoch_day: f64,
+    /// SGP4 drag term, 1/Earth radii.
+    pub bstar: f64,
+    /// Inclination, degrees.
+    pub inclination_deg: f64,
+    /// Right ascension of the ascending node, degrees.
+    pub raan_deg: f64,
+    /// Eccentricity.
+    pub eccentricity: f64,
+    /// Argument of perigee, degrees.
+    pub arg_perigee_deg: f64,
+    /// Mean anomaly, degrees.
+    pub mean_anomaly_deg: f64,
+    /// Mean motion, revolutions per day.
+    pub mean_motion_rev_day: f64,
+}
+
+impl Tle {
+    /// Parse the two element lines.
+    ///
+    /// - **ID**: FN-ORB-001
+    /// - **Requirement**: Accept satellite orbits as published element sets
+    ///   (REQ-FN-008).
+    /// - **Inputs**: Lines 1 and 2 in the fixed-column NORAD format; trailing
+    ///   whitespace is ignored.
+    /// - **Outputs**: The element set, without a name.
+    /// - **Failure Modes**: `OrbitError::Format` for a short line, wrong line
+    ///   number, mismatched catalog numbers or an unreadable field;
+    ///   `OrbitError::Checksum` for a wrong checksum digit.
+    pub fn parse(line1: &str, line2: &str) -> Result<Self, OrbitError> {
+        let line1 = checked_line(line1, 1)?;
+        let line2 = checked_line(line2, 2)?;
+
+        let catalog_number = field(line1, 1, 2..7)?;
+        if field::<u32>(line2, 2, 2..7)? != catalog_number {
+            return Err(OrbitError::Format {
+                line: 2,
+                reason: "catalog number differs from line 1",
+            });
+        }
+        let two_digit_year: u16 = field(line1, 1, 18..20)?;
+        Ok(Self {
+            name: None,
+            catalog_number,
+            // Element sets cover 1957 to 2056
+            epoch_year: if two_digit_year < 57 {
+                2000 + two_digit_year
+            } else {
+                1900 + two_digit_year
+            },
+            epoch_day: field(line1, 1, 20..32)?,
+            bstar: implied_exponent(&line1[53..61]).ok_or(OrbitError::Format {
+                line: 1,
+                reason: "unreadable BSTAR drag term",
+            })?,
+            inclination_deg: field(line2, 2, 8..16)?,
+            raan_deg: field(line2, 2, 17..25)?,
+            eccentricity: format!("0.{}", line2[26..33].trim()).parse().map_err(|_| {
+                OrbitError::Format {
+                    line: 2,
+                    reason: "unreadable eccentricity",
+                }
+            })?,
+            arg_perigee_deg: field(line2, 2, 34..42)?,
+            mean_anomaly_deg: field(line2, 2, 43..51)?,
+            mean_motion_rev_day: field(line2, 2, 52..63)?,
+        })
+    }
+
+    /// Orbit period, minutes.
+    pub fn period_min(&self) -> f64 {
+        MINUTES_PER_DAY / self.mean_motion_rev_day
+    }
+
+    /// Julian date of the epoch, UTC.
+    pub fn epoch_jd(&self) -> f64 {
+        // Julian date of 0 January, valid 1901–2099
+        let year = f64::from(self.epoch_year);
+        let jan0 = 367.0 * year - (7.0 * year / 4.0).floor() + 30.0 + 1_721_013.5;
+        jan0 + self.epoch_day
+    }
+}
+
+impl FromStr for Tle {
+    type Err = OrbitError;
+
+    /// Parse a two-line element set, optionally preceded by a title line
+    /// with the satellite name; blank lines are skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().filter(|l| !l.trim().is_empty()).collect();
+        match lines.as_slice() {
+            [line1, line2] => Tle::parse(line1, line2),
+            [title, line1, line2] => {
+                let name = title.trim().trim_start_matches("0 ").trim();
+                Ok(Tle {
+                    name: Some(name.to_string()),
+                    ..Tle::parse(line1, line2)?
+                })
+            }
+            _ => Err(OrbitError::Format {
+                line: 1,
+                reason: "expected two element lines and an optional title",
+            }),
+        }
+    }
+}
+
+/// Line `number` of an element set with its checksum verified.
+fn checked_line(line: &str, number: u8) -> Result<&str, OrbitError> {
+    let line = line.trim_end();
+    let format = |reason| OrbitError::Format {
+        line: number,
+        reason,
+    };
+    if !line.is_ascii() || line.len() < 69 {
+        return Err(format("shorter than 69 columns"));
+    }
+    if line.as_bytes()[0] != b'0' + number {
+        return Err(format("wrong line number"));
+    }
+    let sum: u32 = line.as_bytes()[..68]
+        .iter()
+        .map(|&b| match b {
+            b'0'..=b'9' => u32::from(b - b'0'),
+            b'-' => 1,
+            _ => 0,
+        })
+        .sum();
+    if u32::from(line.as_bytes()[68]) != u32::from(b'0') + sum % 10 {
+        return Err(OrbitError::Checksum { line: number });
+    }
+    Ok(line)
+}
+
+/// Numeric field in `columns` (zero-based) of line `number`.
+fn field<T: FromStr>(
+    line: &str,
+    number: u8,
+    columns: std::ops::Range<usize>,
+) -> Result<T, OrbitError> {
+    line[columns]
+        .trim()
+        .parse()
+        .map_err(|_| OrbitError::Format {
+            line: number,
+            reason: "unreadable numeric field",
+        })
+}
+
+/// Value of a field with an implied leading decimal point and exponent,
+/// e.g. ` 28098-4` for 0.28098e-4.
+fn implied_exponent(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (sign, text) = match text.as_bytes().first()? {
+        b'-' => (-1.0, &text[1..]),
+        b'+' => (1.0, &text[1..]),
+        _ => (1.0, text),
+    };
+    let split = text.rfind(['-', '+'])?;
+    let mantissa: f64 = format!("0.{}", &text[..split]).parse().ok()?;
+    let exponent: i32 = text[split..].parse().ok()?;
+    Some(sign * mantissa * 10f64.powi(exponent))
+}
+
+/// Satellite position and velocity in the TEME frame.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateVector {
+    /// Time from the element set epoch, minutes.
+    pub minutes_since_epoch: f64,
+    /// Position, km.
+    pub position_km: [f64; 3],
+    /// Velocity, km/s.
+    pub velocity_km_s: [f64; 3],
+}
+
+/// SGP4 propagator of one element set, near-Earth orbits only.
+///
+/// - **ID**: MOD-ORB-001
+/// - **Requirement**: Predict satellite positions from published element
+///   sets with the model they were fitted with (REQ-FN-008).
+/// - **Constraints**: Period under [`DEEP_SPACE_PERIOD_MIN`]; WGS-72
+///   constants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sgp4 {
+    epoch_jd: f64,
+    bstar: f64,
+    ecco: f64,
+    inclo: f64,
+    nodeo: f64,
+    argpo: f64,
+    mo: f64,
+    no: f64,
+    // Simplified drag terms for perigees under 220 km
+    isimp: bool,
+    eta: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    mdot: f64,
+    argpdot: f64,
+    nodedot: f64,
+    nodecf: f64,
+    omgcof: f64,
+    xmcof: f64,
+    delmo: f64,
+    sinmao: f64,
+    xlcof: f64,
+    aycof: f64,
+    con41: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+}
+
+impl Sgp4 {
+    /// Initialise SGP4 for an element set.
+    ///
+    /// # Errors
+    /// `OrbitError::DeepSpace` for periods of [`DEEP_SPACE_PERIOD_MIN`] or
+    /// more; `OrbitError::Propagation` for elements that describe no orbit.
+    pub fn new(tle: &Tle) -> Result<Self, OrbitError> {
+        let invalid = |reason| OrbitError::Propagation {
+            minutes_since_epoch: 0.0,
+            reason,
+        };
+        if tle.mean_motion_rev_day.is_nan()
+            || tle.mean_motion_rev_day <= 0.0
+            || !(0.0..1.0).contains(&tle.eccentricity)
+        {
+            return Err(invalid("mean motion or eccentricity out of range"));
+        }
+        if tle.period_min() >= DEEP_SPACE_PERIOD_MIN {
+            return Err(OrbitError::DeepSpace {
+                period_min: tle.period_min(),
+            });
+        }
+
+        let xke = 60.0 / (WGS72_RADIUS_KM.powi(3) / WGS72_MU_KM3_S2).sqrt();
+        let j3oj2 = J3 / J2;
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination_deg.to_radians();
+        let argpo = tle.arg_perigee_deg.to_radians();
+        let mo = tle.mean_anomaly_deg.to_radians();
+        let no_kozai = tle.mean_motion_rev_day * TAU / MINUTES_PER_DAY;
+
+        // Recover the original mean motion and semi-major axis from the
+        // Kozai mean motion of the element set
+        let eccsq = ecco * ecco;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (xke / no_kozai).powf(2.0 / 3.0);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1.0 + del);
+        let ao = (xke / no).powf(2.0 / 3.0);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - 2.0 * cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - ecco);
+
+        // Atmospheric density parameters, lowered for perigees under 156 km
+        let isimp = rp < 220.0 / WGS72_RADIUS_KM + 1.0;
+        let mut sfour = 78.0 / WGS72_RADIUS_KM + 1.0;
+        let mut qzms24 = ((120.0 - 78.0) / WGS72_RADIUS_KM).powi(4);
+        let perigee_km = (rp - 1.0) * WGS72_RADIUS_KM;
+        if perigee_km < 156.0 {
+            let s = if perigee_km < 98.0 {
+                20.0
+            } else {
+                perigee_km - 78.0
+            };
+            qzms24 = ((120.0 - s) / WGS72_RADIUS_KM).powi(4);
+            sfour = s / WGS72_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = tle.bstar * cc2;
+        let cc3 = if ecco > 1e-4 {
+            -2.0 * coef * tsi * j3oj2 * no * sinio / ecco
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75
+                            * x1mth2
+                            * (2.0 * etasq - eeta * (1.0 + etasq))
+                            * (2.0 * argpo).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates of the mean anomaly, perigee and node
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * no;
+        let mdot = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1
+            + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        // Long-period periodic coefficients, guarded against retrograde
+        // equatorial orbits
+        let xlcof_divisor = if (cosio + 1.0).abs() > 1.5e-12 {
+            1.0 + cosio
+        } else {
+            1.5e-12
+        };
+
+        let mut sgp4 = Self {
+            epoch_jd: tle.epoch_jd(),
+            bstar: tle.bstar,
+            ecco,
+            inclo,
+            nodeo: tle.raan_deg.to_radians(),
+            argpo,
+            mo,
+            no,
+            isimp,
+            eta,
+            cc1,
+            cc4,
+            cc5,
+            d2: 0.0,
+            d3: 0.0,
+            d4: 0.0,
+            t2cof: 1.5 * cc1,
+            t3cof: 0.0,
+            t4cof: 0.0,
+            t5cof: 0.0,
+            mdot,
+            argpdot,
+            nodedot,
+            nodecf: 3.5 * omeosq * xhdot1 * cc1,
+            omgcof: tle.bstar * cc3 * argpo.cos(),
+            xmcof: if ecco > 1e-4 {
+                -2.0 / 3.0 * coef * tle.bstar / eeta
+            } else {
+                0.0
+            },
+            delmo: (1.0 + eta * mo.cos()).powi(3),
+            sinmao: mo.sin(),
+            xlcof: -0.25 * j3oj2 * sinio * (3.0 + 5.0 * cosio) / xlcof_divisor,
+            aycof: -0.5 * j3oj2 * sinio,
+            con41,
+            x1mth2,
+            x7thm1: 7.0 * cosio2 - 1.0,
+        };
+
+        // Higher-order drag terms, dropped for low perigees
+        if !isimp {
+            let cc1sq = cc1 * cc1;
+            let d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            let d3 = (17.0 * ao + sfour) * temp;
+            let d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            sgp4.d2 = d2;
+            sgp4.d3 = d3;
+            sgp4.d4 = d4;
+            sgp4.t3cof = d2 + 2.0 * cc1sq;
+            sgp4.t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            sgp4.t5cof = 0.2
+                * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+        }
+        Ok(sgp4)
+    }
+
+    /// Julian date of the element set epoch, UTC.
+    pub fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+
+    /// Propagate to `minutes_since_epoch`.
+    ///
+    /// - **ID**: FN-ORB-002
+    /// - **Requirement**: Satellite position at any time of a pass
+    ///   (REQ-FN-008).
+    /// - **Inputs**: Time from the element set epoch, minutes; negative
+    ///   before it.
+    /// - **Outputs**: TEME position and velocity.
+    /// - **Failure Modes**: `OrbitError::Propagation` once drag has taken the
+    ///   elements past a valid orbit or the satellite below the surface.
+    pub fn propagate(&self, minutes_since_epoch: f64) -> Result<StateVector, OrbitError> {
+        let t = minutes_since_epoch;
+        let fail = |reason| OrbitError::Propagation {
+            minutes_since_epoch: t,
+            reason,
+        };
+        let xke = 60.0 / (WGS72_RADIUS_KM.powi(3) / WGS72_MU_KM3_S2).sqrt();
+
+        // Secular gravity and drag
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let t2 = t * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+        if !self.isimp {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            mm = xmdf + delomg + delm;
+            argpm = argpdf - delomg - delm;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let am = (xke / self.no).powf(2.0 / 3.0) * tempa * tempa;
+        let nm = xke / am.powf(1.5);
+        let em = self.ecco - tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(fail("eccentricity out of range"));
+        }
+        let em = em.max(1e-6);
+        mm += self.no * templ;
+        let xlm = mm + argpm + nodem;
+        let nodem = nodem.rem_euclid(TAU);
+        let argpm = argpm.rem_euclid(TAU);
+        let xlm = xlm.rem_euclid(TAU);
+        let mm = (xlm - argpm - nodem).rem_euclid(TAU);
+
+        // Long-period periodics
+        let sinip = self.inclo.sin();
+        let cosip = self.inclo.cos();
+        let axnl = em * argpm.cos();
+        let temp = 1.0 / (am * (1.0 - em * em));
+        let aynl = em * argpm.sin() + temp * self.aycof;
+        let xl = mm + argpm + nodem + temp * self.xlcof * axnl;
+
+        // Kepler's equation
+        let u = (xl - nodem).rem_euclid(TAU);
+        let mut eo1 = u;
+        for _ in 0..10 {
+            let (sineo1, coseo1) = eo1.sin_cos();
+            let step =
+                (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+            eo1 += step.clamp(-0.95, 0.95);
+            if step.abs() < 1e-12 {
+                break;
+            }
+        }
+        let (sineo1, coseo1) = eo1.sin_cos();
+
+        // Short-period preliminary quantities
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(fail("semi-latus rectum negative"));
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = 2.0 * cosu * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        // Short-period periodics
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        if mrt < 1.0 {
+            return Err(fail("satellite has decayed"));
+        }
+        let su = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode = nodem + 1.5 * temp2 * cosip * sin2u;
+        let xinc = self.inclo + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * self.x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / xke;
+
+        // Orientation vectors
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let ux = [
+            xmx * sinsu + cnod * cossu,
+            xmy * sinsu + snod * cossu,
+            sini * sinsu,
+        ];
+        let vx = [
+            xmx * cossu - cnod * sinsu,
+            xmy * cossu - snod * sinsu,
+            sini * cossu,
+        ];
+        let vkmpersec = WGS72_RADIUS_KM * xke / 60.0;
+        Ok(StateVector {
+            minutes_since_epoch: t,
+            position_km: ux.map(|u| mrt * u * WGS72_RADIUS_KM),
+            velocity_km_s: [0, 1, 2].map(|i| (mvt * ux[i] + rvdot * vx[i]) * vkmpersec),
+        })
+    }
+
+    /// Passes over `observer` between `start_min` and `end_min` from epoch.
+    ///
+    /// - **ID**: FN-ORB-004
+    /// - **Requirement**: Contacts of a real orbit, in the pass sample form
+    ///   the tracking and link models take (REQ-FN-008).
+    /// - **Inputs**: Observer, time window in minutes from epoch, sample
+    ///   spacing in seconds and elevation mask in degrees.
+    /// - **Outputs**: Passes in time order, each sampled from the first
+    ///   sample above the mask to the last; a pass already in progress at
+    ///   `start_min` or still in progress at `end_min` is cut there.
+    /// - **Failure Modes**: `OrbitError::Propagation` if the orbit cannot be
+    ///   propagated within the window.
+    pub fn passes(
+        &self,
+        observer: &Observer,
+        start_min: f64,
+        end_min: f64,
+        step_s: f64,
+        elevation_mask_deg: f64,
+    ) -> Result<Vec<OrbitPass>, OrbitError> {
+        let mut passes = Vec::new();
+        let mut current: Option<OrbitPass> = None;
+        let step_min = step_s.max(f64::EPSILON) / 60.0;
+        let steps = ((end_min - start_min) / step_min).floor().max(0.0) as u64;
+        for i in 0..=steps {
+            let t = start_min + i as f64 * step_min;
+            let look = observer.look_at(self, t)?;
+            if look.pointing.elevation_deg < elevation_mask_deg {
+                passes.extend(current.take());
+                continue;
+            }
+            let pass = current.get_or_insert_with(|| OrbitPass {
+                aos_minutes_since_epoch: t,
+                samples: Vec::new(),
+            });
+            pass.samples.push(PassSample {
+                time_s: (t - pass.aos_minutes_since_epoch) * 60.0,
+                pointing: look.pointing,
+                range_km: look.range_km,
+            });
+        }
+        passes.extend(current);
+        Ok(passes)
+    }
+}
+
+/// Greenwich mean sidereal time at a Julian date, radians (IAU 1982).
+pub fn gmst_rad(jd: f64) -> f64 {
+    let t = (jd - J2000_JD) / 36_525.0;
+    let seconds = -6.2e-6 * t.powi(3)
+        + 0.093_104 * t * t
+        + (876_600.0 * 3600.0 + 8_640_184.812_866) * t
+        + 67_310.548_41;
+    // 240 seconds of time per degree
+    (seconds / 240.0).to_radians().rem_euclid(TAU)
+}
+
+/// Ground station location on the WGS-84 ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Observer {
+    /// Geodetic latitude, degrees, north positive.
+    pub latitude_deg: f64,
+    /// Longitude, degrees, east positive.
+    pub longitude_deg: f64,
+    /// Height above the ellipsoid, km.
+    pub altitude_km: f64,
+}
+
+/// Direction and distance from an observer to the satellite.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LookAngles {
+    /// Time from the element set epoch, minutes.
+    pub minutes_since_epoch: f64,
+    /// Direction to the satellite.
+    pub pointing: Pointing,
+    /// Slant range to the satellite, km.
+    pub range_km: f64,
+}
+
+impl Observer {
+    /// Observer at a ground station; takes `GroundStationConfig::location`
+    /// as (latitude, longitude, altitude in meters).
+    pub fn at_station(location: (f64, f64, f64)) -> Self {
+        Self {
+            latitude_deg: location.0,
+            longitude_deg: location.1,
+            altitude_km: location.2 / 1000.0,
+        }
+    }
+
+    /// Earth-fixed position, km.
+    fn ecef_km(&self) -> [f64; 3] {
+        let (sin_lat, cos_lat) = self.latitude_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude_deg.to_radians().sin_cos();
+        let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+        let n = WGS84_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        [
+            (n + self.altitude_km) * cos_lat * cos_lon,
+            (n + self.altitude_km) * cos_lat * sin_lon,
+            (n * (1.0 - e2) + self.altitude_km) * sin_lat,
+        ]
+    }
+
+    /// Look angles to a TEME position at Julian date `jd`.
+    pub fn look_angles(&self, position_km: [f64; 3], jd: f64) -> (Pointing, f64) {
+        // TEME to Earth-fixed, by sidereal time only
+        let (sin_g, cos_g) = gmst_rad(jd).sin_cos();
+        let sat = [
+            cos_g * position_km[0] + sin_g * position_km[1],
+            -sin_g * position_km[0] + cos_g * position_km[1],
+            position_km[2],
+        ];
+        let station = self.ecef_km();
+        let d = [0, 1, 2].map(|i| sat[i] - station[i]);
+
+        // Topocentric south, east and zenith components
+        let (sin_lat, cos_lat) = self.latitude_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude_deg.to_radians().sin_cos();
+        let south = sin_lat * cos_lon * d[0] + sin_lat * sin_lon * d[1] - cos_lat * d[2];
+        let east = -sin_lon * d[0] + cos_lon * d[1];
+        let zenith = cos_lat * cos_lon * d[0] + cos_lat * sin_lon * d[1] + sin_lat * d[2];
+        let range = (south * south + east * east + zenith * zenith).sqrt();
+        (
+            Pointing {
+                azimuth_deg: east.atan2(-south).to_degrees().rem_euclid(360.0),
+                elevation_deg: (zenith / range).asin().to_degrees(),
+            },
+            range,
+        )
+    }
+
+    /// Look angles to the satellite `minutes_since_epoch` from its epoch.
+    ///
+    /// - **ID**: FN-ORB-003
+    /// - **Requirement**: Slant range and elevation of the satellite from the
+    ///   ground station at any time (REQ-FN-008).
+    /// - **Failure Modes**: `OrbitError::Propagation` as for
+    ///   [`Sgp4::propagate`].
+    pub fn look_at(&self, sgp4: &Sgp4, minutes_since_epoch: f64) -> Result<LookAngles, OrbitError> {
+        let state = sgp4.propagate(minutes_since_epoch)?;
+        let jd = sgp4.epoch_jd() + minutes_since_epoch / MINUTES_PER_DAY;
+        let (pointing, range_km) = self.look_angles(state.position_km, jd);
+        Ok(LookAngles {
+            minutes_since_epoch,
+            pointing,
+            range_km,
+        })
+    }
+}
+
+/// One contact of a propagated orbit above the elevation mask.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrbitPass {
+    /// Acquisition of signal, minutes from the element set epoch.
+    pub aos_minutes_since_epoch: f64,
+    /// Samples from AOS, times in seconds since AOS.
+    pub samples: Vec<PassSample>,
+}
+
+impl OrbitPass {
+    /// Time from the first sample to the last, seconds.
+    pub fn duration_s(&self) -> f64 {
+        self.samples.last().map_or(0.0, |s| s.time_s)
+    }
+
+    /// Highest elevation of the pass, degrees.
+    pub fn max_elevation_deg(&self) -> f64 {
+        self.samples
+            .iter()
+            .map(|s| s.pointing.elevation_deg)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl FrequencyBand {
+    /// Simulate transmission at every sample of a pass.
+    ///
+    /// - **ID**: FN-ORB-005
+    /// - **Requirement**: Drive the link model over a pass instead of a
+    ///   single range and elevation (REQ-FN-008).
+    /// - **Inputs**: Pass samples (from [`Sgp4::passes`] or
+    ///   `tracking::generate_pass`), parameters whose distance, elevation,
+    ///   range rate and range acceleration each sample replaces, conditions
+    ///   and margins. The range rate and acceleration come from differencing
+    ///   the sampled ranges, for the Doppler the receiver tracks.
+    /// - **Outputs**: One result per sample, in order.
+    /// - **Failure Modes**: `ValidationError` from the first sample whose
+    ///   inputs are out of range, e.g. below the horizon.
+    pub fn simulate_pass(
+        &self,
+        pass: &[PassSample],
+        params: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+        margins: &MarginPolicy,
+    ) -> Result<Vec<TransmissionResult>, ValidationError> {
+        let times: Vec<f64> = pass.iter().map(|s| s.time_s).collect();
+        let ranges_m: Vec<f64> = pass.iter().map(|s| s.range_km * 1000.0).collect();
+        let range_rates = derivative(&times, &ranges_m);
+        let range_accelerations = derivative(&times, &range_rates);
+
+        pass.iter()
+            .zip(range_rates.iter().zip(&range_accelerations))
+            .map(|(sample, (&range_rate_m_s, &range_acceleration_m_s2))| {
+                let params = TransmissionParameters {
+                    distance_km: sample.range_km,
+                    elevation_angle_degrees: sample.pointing.elevation_deg,
+                    range_rate_m_s,
+                    range_acceleration_m_s2,
+                    ..params.clone()
+                };
+                self.simulate_transmission_with(&params, environment, margins)
+            })
+            .collect()
+    }
+}
+
+/// Derivative of `values` sampled at `times`: central differences inside,
+/// one-sided at the ends, zero for a single sample or repeated times.
+fn derivative(times: &[f64], values: &[f64]) -> Vec<f64> {
+    let last = values.len().saturating_sub(1);
+    (0..values.len())
+        .map(|i| {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(last));
+            let dt = times[after] - times[before];
+            if dt > 0.0 {
+                (values[after] - values[before]) / dt
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
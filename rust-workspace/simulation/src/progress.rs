@@ -0,0 +1,292 @@
+//! Progress and Cancellation Module
+//!
+//! Monte Carlo studies and parameter sweeps can run for minutes, and a front
+//! end showing one needs to draw a progress bar and let the user abort it.
+//! The `*_controlled` entry points of `MonteCarlo` and `ParameterSweep` take
+//! a `RunControl`, which:
+//! - reports `Progress` (runs completed, percent complete and estimated time
+//!   remaining) to a callback or an `mpsc` channel, and
+//! - stops the run once its `CancelToken` is cancelled, from any thread.
+//!
+//! Work is handed out in batches — `STREAM_BATCH_TRIALS` Monte Carlo trials
+//! or `SWEEP_BATCH_RUNS` sweep runs — and progress is reported from the
+//! calling thread at the start and after every batch, so the observer needs
+//! to be neither `Sync` nor fast. Cancellation is checked before every trial
+//! or run, so workers stop within one trial each. A cancelled run returns
+//! `RunError::Cancelled` with the progress reached and discards its partial
+//! results; results of runs that finish are identical to uncontrolled runs.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (observable, abortable studies)
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{Catalog, Localize};
+use crate::validation::ValidationError;
+
+/// Shared flag that stops a controlled run. Clones share the flag, so one
+/// clone can be handed to the run and another kept to cancel it.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Token not yet cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop every run holding a clone of this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel` has been called on any clone.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far a run has got.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    /// Trials or runs finished.
+    pub completed: usize,
+    /// Trials or runs in the whole run.
+    pub total: usize,
+    /// Time since the run started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Fraction of the run finished, 0-1; an empty run is complete.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+
+    /// Percent of the run finished, 0-100.
+    pub fn percent(&self) -> f64 {
+        self.fraction() * 100.0
+    }
+
+    /// Whether every trial or run has finished.
+    pub fn is_complete(&self) -> bool {
+        self.completed >= self.total
+    }
+
+    /// Estimated time remaining at the rate so far; `None` until something
+    /// has finished.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.completed == 0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.completed);
+        Some(
+            self.elapsed
+                .mul_f64(remaining as f64 / self.completed as f64),
+        )
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for Progress {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        let percent = format!("{:.1}", self.percent());
+        let text = match self.eta().filter(|_| !self.is_complete()) {
+            Some(eta) => catalog.format(
+                "progress.eta",
+                &[
+                    ("completed", &self.completed),
+                    ("total", &self.total),
+                    ("percent", &percent),
+                    ("seconds", &eta.as_secs()),
+                ],
+            ),
+            None => catalog.format(
+                "progress.status",
+                &[
+                    ("completed", &self.completed),
+                    ("total", &self.total),
+                    ("percent", &percent),
+                ],
+            ),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Why a controlled run stopped without a result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunError {
+    /// Inputs out of range.
+    Invalid(ValidationError),
+    /// The run's `CancelToken` was cancelled.
+    Cancelled(Progress),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Invalid(error) => write!(f, "{}", error),
+            RunError::Cancelled(progress) => write!(
+                f,
+                "run cancelled after {} of {}",
+                progress.completed, progress.total
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunError::Invalid(error) => Some(error),
+            RunError::Cancelled(_) => None,
+        }
+    }
+}
+
+impl From<ValidationError> for RunError {
+    fn from(error: ValidationError) -> Self {
+        RunError::Invalid(error)
+    }
+}
+
+impl RunError {
+    /// The validation error of a run that had no way to be cancelled.
+    pub(crate) fn into_invalid(self) -> ValidationError {
+        match self {
+            RunError::Invalid(error) => error,
+            RunError::Cancelled(_) => unreachable!("run without a cancel token was cancelled"),
+        }
+    }
+}
+
+type Observer<'a> = Box<dyn FnMut(&Progress) + Send + 'a>;
+
+/// Progress reporting and cancellation of one run.
+#[derive(Default)]
+pub struct RunControl<'a> {
+    cancel: CancelToken,
+    observer: Option<Observer<'a>>,
+}
+
+impl fmt::Debug for RunControl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunControl")
+            .field("cancel", &self.cancel)
+            .field("observed", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl<'a> RunControl<'a> {
+    /// Control that neither reports nor can be cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the run once `cancel` is cancelled.
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Call `observer` with the progress at the start and after every
+    /// batch.
+    pub fn on_progress(mut self, observer: impl FnMut(&Progress) + Send + 'a) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Send the progress at the start and after every batch to `sender`;
+    /// the run carries on if the receiver is dropped.
+    pub fn with_channel(self, sender: Sender<Progress>) -> Self {
+        self.on_progress(move |progress| {
+            let _ = sender.send(*progress);
+        })
+    }
+
+    /// Token stopping this run.
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    /// Start tracking a run of `total` trials or runs, reporting it started.
+    pub(crate) fn start(&mut self, total: usize) -> Tracker<'_, 'a> {
+        let mut tracker = Tracker {
+            control: self,
+            total,
+            completed: 0,
+            started: Instant::now(),
+        };
+        tracker.report();
+        tracker
+    }
+}
+
+/// Progress of one run under a `RunControl`.
+pub(crate) struct Tracker<'c, 'a> {
+    control: &'c mut RunControl<'a>,
+    total: usize,
+    completed: usize,
+    started: Instant,
+}
+
+impl Tracker<'_, '_> {
+    /// Token workers check before every trial or run.
+    pub(crate) fn cancel_token(&self) -> &CancelToken {
+        &self.control.cancel
+    }
+
+    /// Count a finished batch and report it, or fail if the run was
+    /// cancelled.
+    pub(crate) fn advance(&mut self, finished: usize) -> Result<(), RunError> {
+        self.completed += finished;
+        self.report();
+        self.check()
+    }
+
+    /// Fail with the progress reached if the run was cancelled.
+    pub(crate) fn check(&self) -> Result<(), RunError> {
+        if self.control.cancel.is_cancelled() {
+            Err(self.cancelled())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Error of the run cancelled at the progress reached.
+    pub(crate) fn cancelled(&self) -> RunError {
+        RunError::Cancelled(self.progress())
+    }
+
+    fn progress(&self) -> Progress {
+        Progress {
+            completed: self.completed,
+            total: self.total,
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    fn report(&mut self) {
+        let progress = self.progress();
+        if let Some(observer) = &mut self.control.observer {
+            observer(&progress);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! ITU Rain Zone Module
+//!
+//! ITU-R P.837-1 divides the world into rain climatic zones A to Q, each with
+//! the rain rate exceeded for 0.01% of an average year (R0.01) that drives
+//! rain fade margins. This module embeds a lookup from a ground station's
+//! latitude and longitude to its zone, so scenarios take rain statistics from
+//! where the station is (`GroundStationConfig::location`) instead of a rain
+//! rate picked by hand.
+//!
+//! The embedded table is a regional digitization of the P.837-1 zone maps:
+//! rectangular regions of a few to tens of degrees, checked in order, with a
+//! latitude-band default for open ocean and anything not covered. It gives
+//! the zone of a station's climate, not of its exact site; a study that
+//! needs local statistics should set the zone, or replay local data.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (rain statistics by station location)
+
+use serde::{Deserialize, Serialize};
+
+/// ITU-R P.837 rain climatic zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItuRainZone {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    J,
+    K,
+    L,
+    M,
+    N,
+    P,
+    Q,
+}
+
+impl ItuRainZone {
+    /// Every zone, driest first by letter.
+    pub const ALL: [ItuRainZone; 15] = [
+        ItuRainZone::A,
+        ItuRainZone::B,
+        ItuRainZone::C,
+        ItuRainZone::D,
+        ItuRainZone::E,
+        ItuRainZone::F,
+        ItuRainZone::G,
+        ItuRainZone::H,
+        ItuRainZone::J,
+        ItuRainZone::K,
+        ItuRainZone::L,
+        ItuRainZone::M,
+        ItuRainZone::N,
+        ItuRainZone::P,
+        ItuRainZone::Q,
+    ];
+
+    /// Rain rate exceeded for 0.01% of an average year, mm/h (ITU-R P.837-1).
+    pub fn rain_rate_0_01_mm_hour(self) -> f64 {
+        match self {
+            ItuRainZone::A => 8.0,
+            ItuRainZone::B => 12.0,
+            ItuRainZone::C => 15.0,
+            ItuRainZone::D => 19.0,
+            ItuRainZone::E => 22.0,
+            ItuRainZone::F => 28.0,
+            ItuRainZone::G => 30.0,
+            ItuRainZone::H => 32.0,
+            ItuRainZone::J => 35.0,
+            ItuRainZone::K => 42.0,
+            ItuRainZone::L => 60.0,
+            ItuRainZone::M => 63.0,
+            ItuRainZone::N => 95.0,
+            ItuRainZone::P => 145.0,
+            ItuRainZone::Q => 115.0,
+        }
+    }
+
+    /// Rain zone at a location.
+    ///
+    /// - **ID**: FN-RZ-001
+    /// - **Requirement**: Rain statistics follow from the ground station
+    ///   location.
+    /// - **Inputs**: Latitude (degrees, north positive) and longitude
+    ///   (degrees, east positive; any range, wrapped to ±180).
+    /// - **Outputs**: The zone of the first region in the embedded table
+    ///   containing the point, or the latitude-band default.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: Latitudes outside ±90 are clamped.
+    pub fn at(latitude_deg: f64, longitude_deg: f64) -> ItuRainZone {
+        let lat = latitude_deg.clamp(-90.0, 90.0);
+        let lon = (longitude_deg + 180.0).rem_euclid(360.0) - 180.0;
+        REGIONS
+            .iter()
+            .find(|r| r.contains(lat, lon))
+            .map(|r| r.zone)
+            .unwrap_or_else(|| latitude_default(lat))
+    }
+
+    /// Rain zone of a ground station; takes `GroundStationConfig::location`
+    /// as (latitude, longitude, altitude in meters).
+    pub fn at_station(location: (f64, f64, f64)) -> ItuRainZone {
+        ItuRainZone::at(location.0, location.1)
+    }
+}
+
+/// R0.01 rain rate at a location, mm/h; see [`ItuRainZone::at`].
+pub fn rain_rate_0_01_at(latitude_deg: f64, longitude_deg: f64) -> f64 {
+    ItuRainZone::at(latitude_deg, longitude_deg).rain_rate_0_01_mm_hour()
+}
+
+/// One region of the embedded zone table.
+struct Region {
+    lat: (f64, f64),
+    lon: (f64, f64),
+    zone: ItuRainZone,
+}
+
+impl Region {
+    fn contains(&self, lat: f64, lon: f64) -> bool {
+        (self.lat.0..self.lat.1).contains(&lat) && (self.lon.0..self.lon.1).contains(&lon)
+    }
+}
+
+const fn region(lat: (f64, f64), lon: (f64, f64), zone: ItuRainZone) -> Region {
+    Region { lat, lon, zone }
+}
+
+/// Zone regions; the first containing a point wins, so smaller regions come
+/// before the larger regions around them.
+const REGIONS: &[Region] = {
+    use ItuRainZone::*;
+    &[
+        // North America
+        region((65.0, 90.0), (-170.0, -10.0), A),
+        region((55.0, 65.0), (-170.0, -50.0), C),
+        region((24.0, 31.0), (-88.0, -79.0), N),
+        region((25.0, 35.0), (-100.0, -75.0), M),
+        region((30.0, 42.0), (-125.0, -110.0), E),
+        region((40.0, 55.0), (-135.0, -115.0), E),
+        region((30.0, 42.0), (-115.0, -103.0), E),
+        region((42.0, 55.0), (-115.0, -90.0), D),
+        region((30.0, 42.0), (-103.0, -90.0), K),
+        region((35.0, 47.0), (-90.0, -65.0), K),
+        region((47.0, 55.0), (-90.0, -50.0), D),
+        // Central America and the Caribbean
+        region((5.0, 25.0), (-120.0, -60.0), N),
+        // South America
+        region((-30.0, -18.0), (-76.0, -68.0), A),
+        region((-56.0, -30.0), (-76.0, -68.0), D),
+        region((-15.0, 12.0), (-80.0, -35.0), P),
+        region((-35.0, -15.0), (-68.0, -35.0), N),
+        region((-56.0, -35.0), (-68.0, -50.0), D),
+        // Europe
+        region((55.0, 72.0), (-10.0, 40.0), E),
+        region((43.0, 55.0), (-10.0, 30.0), H),
+        region((35.0, 43.0), (-10.0, 36.0), K),
+        region((45.0, 70.0), (30.0, 60.0), E),
+        // Africa and the Middle East
+        region((15.0, 35.0), (-20.0, 35.0), A),
+        region((12.0, 40.0), (35.0, 65.0), E),
+        region((-5.0, 15.0), (35.0, 52.0), K),
+        region((-10.0, 5.0), (10.0, 35.0), P),
+        region((0.0, 15.0), (-20.0, 35.0), N),
+        region((-26.0, -12.0), (43.0, 51.0), N),
+        region((-35.0, -10.0), (10.0, 42.0), K),
+        // Asia
+        region((28.0, 40.0), (75.0, 105.0), E),
+        region((5.0, 30.0), (65.0, 92.0), N),
+        region((0.0, 25.0), (92.0, 125.0), P),
+        region((30.0, 46.0), (125.0, 146.0), M),
+        region((20.0, 30.0), (105.0, 125.0), N),
+        region((30.0, 45.0), (105.0, 125.0), K),
+        region((40.0, 65.0), (60.0, 180.0), E),
+        region((65.0, 90.0), (-10.0, 180.0), A),
+        // Maritime continent and Oceania
+        region((-10.0, 8.0), (95.0, 155.0), P),
+        region((-20.0, -10.0), (110.0, 155.0), N),
+        region((-40.0, -28.0), (140.0, 155.0), K),
+        region((-36.0, -28.0), (113.0, 125.0), F),
+        region((-32.0, -20.0), (113.0, 150.0), E),
+        region((-48.0, -34.0), (165.0, 180.0), F),
+    ]
+};
+
+/// Zone of open ocean and regions not in the table, by latitude.
+fn latitude_default(lat: f64) -> ItuRainZone {
+    match lat.abs() {
+        l if l < 15.0 => ItuRainZone::N,
+        l if l < 30.0 => ItuRainZone::K,
+        l if l < 45.0 => ItuRainZone::F,
+        l if l < 60.0 => ItuRainZone::D,
+        l if l < 70.0 => ItuRainZone::C,
+        _ => ItuRainZone::A,
+    }
+}
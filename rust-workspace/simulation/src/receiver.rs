@@ -0,0 +1,463 @@
+//! Receiver Front-End Module
+//!
+//! The link model treats interference as one more noise term: an interferer
+//! at J dBm after the channel filter lowers the SNR exactly as J dBm of
+//! noise would. A real receiver is worse off. Everything inside the
+//! preselector passband reaches the LNA and the AGC detector before the
+//! channel filter removes it, so a strong interferer well outside the
+//! channel still hurts the wanted signal:
+//!
+//! - **AGC gain reduction**: the AGC sets its gain to keep the strongest
+//!   signal in the passband below ADC full scale. A near-far interferer
+//!   pulls the gain down, and the ADC noise floor, fixed at the AGC output,
+//!   rises relative to the weak wanted signal.
+//! - **Compression**: near the LNA 1 dB compression point a strong
+//!   interferer shrinks the gain seen by every other signal (blocking), by
+//!   the third-order model g = 1 − 2·(1 − 10^(−1/20))·P/P1dB.
+//! - **Saturation**: a signal that arrives faster than the AGC can attack
+//!   overdrives the ADC; the clipped power becomes distortion.
+//! - **AGC dynamics**: the gain follows the input with a fast attack and a
+//!   slow decay, so after a pulsed jammer switches off the gain stays low,
+//!   and the wanted signal stays desensitized, for several decay time
+//!   constants.
+//!
+//! [`ReceiverFrontEnd::settled`] evaluates one input with the AGC settled,
+//! and [`ReceiverFrontEnd::run`] steps the AGC loop through a time series.
+//! Each [`FrontEndOutput`] reports the SNR after the front end next to the
+//! SNR the additive interference term alone would give; the difference is
+//! the desensitization.
+//!
+//! # Requirements Traceability
+//! - REQ-SE-002: Interference Mitigation (near-far and jamming effects on
+//!   receiver sensitivity)
+//! - REQ-FN-010: Anti-Jam / LPI/LPD Communication (pulsed jammer against AGC
+//!   dynamics)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Thermal noise density at 290 K, dBm/Hz.
+pub const THERMAL_NOISE_DBM_HZ: f64 = -174.0;
+
+/// Compression beyond which the wanted signal is taken as fully blocked, dB.
+pub const MAX_COMPRESSION_DB: f64 = 60.0;
+
+/// Desensitization that counts toward time desensitized in a
+/// [`FrontEndSummary`], dB.
+pub const DESENSITIZATION_THRESHOLD_DB: f64 = 1.0;
+
+/// Error configuring the front end or its input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiverError {
+    /// Front-end parameters are not usable.
+    InvalidFrontEnd(&'static str),
+    /// Input sample times go backwards.
+    TimeReversed {
+        /// Index of the sample earlier than the one before it.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ReceiverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReceiverError::InvalidFrontEnd(reason) => write!(f, "invalid front end: {}", reason),
+            ReceiverError::TimeReversed { index } => {
+                write!(f, "sample {} is earlier than the one before it", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReceiverError {}
+
+/// dB to linear power ratio, or dBm to mW
+fn linear(db: f64) -> f64 {
+    10f64.powf(db / 10.0)
+}
+
+/// Linear power ratio to dB, or mW to dBm
+fn decibels(linear: f64) -> f64 {
+    10.0 * linear.log10()
+}
+
+/// Automatic gain control loop settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AgcConfig {
+    /// Lowest gain the AGC can set, dB.
+    pub min_gain_db: f64,
+    /// Highest gain, used with no signal present, dB.
+    pub max_gain_db: f64,
+    /// Headroom kept between the AGC output and ADC full scale for signal
+    /// peaks, dB.
+    pub target_backoff_db: f64,
+    /// Time constant of gain reduction when the input rises, s.
+    pub attack_time_s: f64,
+    /// Time constant of gain recovery when the input falls, s.
+    pub decay_time_s: f64,
+}
+
+impl Default for AgcConfig {
+    /// 80 dB of gain range, 12 dB of peak headroom, 1 ms attack and 100 ms
+    /// decay.
+    fn default() -> Self {
+        Self {
+            min_gain_db: 0.0,
+            max_gain_db: 80.0,
+            target_backoff_db: 12.0,
+            attack_time_s: 0.001,
+            decay_time_s: 0.1,
+        }
+    }
+}
+
+/// Receiver front end from the LNA to the ADC.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReceiverFrontEnd {
+    /// System noise figure referred to the LNA input, dB.
+    pub noise_figure_db: f64,
+    /// Channel noise bandwidth, Hz.
+    pub noise_bandwidth_hz: f64,
+    /// LNA input 1 dB compression point, dBm.
+    pub input_p1db_dbm: f64,
+    /// ADC full scale referred to the AGC output, dBm.
+    pub adc_full_scale_dbm: f64,
+    /// ADC full scale over its noise floor in the channel bandwidth, dB.
+    pub adc_dynamic_range_db: f64,
+    /// Gain control loop.
+    pub agc: AgcConfig,
+}
+
+impl Default for ReceiverFrontEnd {
+    /// 2 dB noise figure in a 1 MHz channel, −20 dBm input P1dB and an ADC
+    /// with 75 dB of in-channel dynamic range.
+    fn default() -> Self {
+        Self {
+            noise_figure_db: 2.0,
+            noise_bandwidth_hz: 1e6,
+            input_p1db_dbm: -20.0,
+            adc_full_scale_dbm: 0.0,
+            adc_dynamic_range_db: 75.0,
+            agc: AgcConfig::default(),
+        }
+    }
+}
+
+/// Power reaching the antenna port at one instant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReceivedPower {
+    /// Wanted signal, dBm.
+    pub signal_dbm: f64,
+    /// Interferer inside the preselector passband, dBm; `f64::NEG_INFINITY`
+    /// for none.
+    pub interferer_dbm: f64,
+    /// Channel filter rejection of the interferer, dB; 0 for co-channel.
+    pub interferer_rejection_db: f64,
+}
+
+impl ReceivedPower {
+    /// Wanted signal with no interferer.
+    pub fn clear(signal_dbm: f64) -> Self {
+        Self {
+            signal_dbm,
+            interferer_dbm: f64::NEG_INFINITY,
+            interferer_rejection_db: 0.0,
+        }
+    }
+}
+
+/// Front-end state and SNR for one input.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrontEndOutput {
+    /// Sample time, s; 0 for a settled evaluation.
+    pub time_s: f64,
+    /// AGC gain applied, dB.
+    pub gain_db: f64,
+    /// Gain compression of the LNA, dB.
+    pub compression_db: f64,
+    /// Whether the AGC output exceeded ADC full scale.
+    pub clipped: bool,
+    /// SNR at the ADC output in the channel bandwidth, dB.
+    pub snr_db: f64,
+    /// SNR with the interferer counted only as additive noise, dB.
+    pub additive_snr_db: f64,
+    /// SNR lost to the front end beyond the additive term, dB.
+    pub desensitization_db: f64,
+}
+
+impl ReceiverFrontEnd {
+    /// Check the front end parameters.
+    pub fn validate(&self) -> Result<(), ReceiverError> {
+        let finite = [
+            self.noise_figure_db,
+            self.input_p1db_dbm,
+            self.adc_full_scale_dbm,
+            self.agc.min_gain_db,
+            self.agc.max_gain_db,
+            self.agc.target_backoff_db,
+        ];
+        if finite.iter().any(|v| !v.is_finite()) {
+            return Err(ReceiverError::InvalidFrontEnd("levels must be finite"));
+        }
+        if self.noise_figure_db < 0.0 {
+            return Err(ReceiverError::InvalidFrontEnd("noise figure below 0 dB"));
+        }
+        if !(self.noise_bandwidth_hz.is_finite() && self.noise_bandwidth_hz > 0.0) {
+            return Err(ReceiverError::InvalidFrontEnd(
+                "noise bandwidth must be positive",
+            ));
+        }
+        if !(self.adc_dynamic_range_db.is_finite() && self.adc_dynamic_range_db > 0.0) {
+            return Err(ReceiverError::InvalidFrontEnd(
+                "ADC dynamic range must be positive",
+            ));
+        }
+        if self.agc.min_gain_db > self.agc.max_gain_db {
+            return Err(ReceiverError::InvalidFrontEnd(
+                "AGC minimum gain above maximum",
+            ));
+        }
+        if self.agc.target_backoff_db < 0.0 {
+            return Err(ReceiverError::InvalidFrontEnd("AGC backoff below 0 dB"));
+        }
+        let positive = |t: f64| t.is_finite() && t > 0.0;
+        if !positive(self.agc.attack_time_s) || !positive(self.agc.decay_time_s) {
+            return Err(ReceiverError::InvalidFrontEnd(
+                "AGC time constants must be positive",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Thermal noise in the channel referred to the LNA input, dBm.
+    pub fn thermal_noise_dbm(&self) -> f64 {
+        THERMAL_NOISE_DBM_HZ + self.noise_figure_db + decibels(self.noise_bandwidth_hz)
+    }
+
+    /// Total power the LNA and AGC detector see, dBm.
+    fn passband_power_dbm(&self, input: &ReceivedPower) -> f64 {
+        decibels(
+            linear(input.signal_dbm)
+                + linear(input.interferer_dbm)
+                + linear(self.thermal_noise_dbm()),
+        )
+    }
+
+    /// LNA gain compression at a passband power, dB.
+    ///
+    /// Third-order model: the small-signal gain in the presence of a blocker
+    /// of power P falls by 2·(1 − 10^(−1/20))·P/P1dB in amplitude, capped at
+    /// [`MAX_COMPRESSION_DB`] where the model no longer holds.
+    pub fn compression_db(&self, passband_dbm: f64) -> f64 {
+        let coefficient = 2.0 * (1.0 - 10f64.powf(-1.0 / 20.0));
+        let amplitude = 1.0 - coefficient * linear(passband_dbm - self.input_p1db_dbm);
+        if amplitude > 10f64.powf(-MAX_COMPRESSION_DB / 20.0) {
+            -20.0 * amplitude.log10()
+        } else {
+            MAX_COMPRESSION_DB
+        }
+    }
+
+    /// Gain the AGC converges to for an input, dB.
+    pub fn settled_gain_db(&self, input: &ReceivedPower) -> f64 {
+        let passband = self.passband_power_dbm(input);
+        let detected = passband - self.compression_db(passband);
+        (self.adc_full_scale_dbm - self.agc.target_backoff_db - detected)
+            .clamp(self.agc.min_gain_db, self.agc.max_gain_db)
+    }
+
+    /// Front-end output for an input at a given AGC gain.
+    ///
+    /// Every term is taken to the AGC output: the wanted signal, thermal
+    /// noise and filtered interferer residue pass through the compressed LNA
+    /// and the AGC gain; the ADC noise floor and any clipped power are added
+    /// there.
+    fn evaluate(&self, time_s: f64, input: &ReceivedPower, gain_db: f64) -> FrontEndOutput {
+        let passband = self.passband_power_dbm(input);
+        let compression_db = self.compression_db(passband);
+        let through = gain_db - compression_db;
+
+        let output_dbm = passband + through;
+        let clipped = output_dbm > self.adc_full_scale_dbm;
+        let clipped_w = (linear(output_dbm) - linear(self.adc_full_scale_dbm)).max(0.0);
+
+        let thermal = self.thermal_noise_dbm();
+        let residue = input.interferer_dbm - input.interferer_rejection_db;
+        let noise_out = linear(thermal + through)
+            + linear(residue + through)
+            + linear(self.adc_full_scale_dbm - self.adc_dynamic_range_db)
+            + clipped_w;
+        let snr_db = input.signal_dbm + through - decibels(noise_out);
+
+        let additive_snr_db = input.signal_dbm - decibels(linear(thermal) + linear(residue));
+        FrontEndOutput {
+            time_s,
+            gain_db,
+            compression_db,
+            clipped,
+            snr_db,
+            additive_snr_db,
+            desensitization_db: additive_snr_db - snr_db,
+        }
+    }
+
+    /// Front-end output with the AGC settled on the input.
+    ///
+    /// - **ID**: FN-RX-001
+    /// - **Requirement**: SNR after AGC gain reduction, compression and
+    ///   clipping, for a steady near-far or jamming geometry.
+    /// - **Failure Modes**: `InvalidFrontEnd` if the parameters fail
+    ///   [`ReceiverFrontEnd::validate`].
+    pub fn settled(&self, input: &ReceivedPower) -> Result<FrontEndOutput, ReceiverError> {
+        self.validate()?;
+        Ok(self.evaluate(0.0, input, self.settled_gain_db(input)))
+    }
+
+    /// Step the AGC loop through a time series of inputs.
+    ///
+    /// - **ID**: FN-RX-002
+    /// - **Requirement**: Gain following the input with the configured
+    ///   attack and decay, so that transients clip and a departed
+    ///   interferer leaves the receiver desensitized until the gain
+    ///   recovers.
+    /// - **Inputs**: `(time_s, input)` pairs in time order. The receiver
+    ///   starts idle at maximum gain.
+    /// - **Outputs**: One output per sample, evaluated at the gain reached
+    ///   before that sample's input acts on the loop.
+    /// - **Failure Modes**: `InvalidFrontEnd` for unusable parameters;
+    ///   `TimeReversed` if a sample is earlier than the one before it.
+    pub fn run(
+        &self,
+        samples: &[(f64, ReceivedPower)],
+    ) -> Result<Vec<FrontEndOutput>, ReceiverError> {
+        self.validate()?;
+        if let Some(index) = (1..samples.len()).find(|&i| samples[i].0 < samples[i - 1].0) {
+            return Err(ReceiverError::TimeReversed { index });
+        }
+
+        let mut gain_db = self.agc.max_gain_db;
+        let mut outputs = Vec::with_capacity(samples.len());
+        for (i, (time_s, input)) in samples.iter().enumerate() {
+            outputs.push(self.evaluate(*time_s, input, gain_db));
+
+            let dt = samples.get(i + 1).map_or(0.0, |next| next.0 - time_s);
+            let target = self.settled_gain_db(input);
+            let time_constant = if target < gain_db {
+                self.agc.attack_time_s
+            } else {
+                self.agc.decay_time_s
+            };
+            gain_db += (target - gain_db) * (1.0 - (-dt / time_constant).exp());
+        }
+        Ok(outputs)
+    }
+
+    /// Settled outputs for a wanted signal against a range of interferer
+    /// levels, as the interferer moves from far to near.
+    pub fn near_far_sweep(
+        &self,
+        signal_dbm: f64,
+        interferer_rejection_db: f64,
+        interferer_dbm: &[f64],
+    ) -> Result<Vec<FrontEndOutput>, ReceiverError> {
+        interferer_dbm
+            .iter()
+            .map(|&level| {
+                self.settled(&ReceivedPower {
+                    signal_dbm,
+                    interferer_dbm: level,
+                    interferer_rejection_db,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Pulsed jammer against a steady wanted signal.
+///
+/// The jammer is on for the first `on_s` of every `period_s`; samples every
+/// `step_s` from 0 up to `duration_s`.
+pub fn pulsed_jammer(
+    signal_dbm: f64,
+    jammer: ReceivedPower,
+    on_s: f64,
+    period_s: f64,
+    duration_s: f64,
+    step_s: f64,
+) -> Vec<(f64, ReceivedPower)> {
+    let steps = (duration_s / step_s).floor().max(0.0) as usize;
+    (0..=steps)
+        .map(|i| {
+            let time_s = i as f64 * step_s;
+            let input = if time_s % period_s < on_s {
+                ReceivedPower {
+                    signal_dbm,
+                    ..jammer
+                }
+            } else {
+                ReceivedPower::clear(signal_dbm)
+            };
+            (time_s, input)
+        })
+        .collect()
+}
+
+/// Summary of a front-end run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrontEndSummary {
+    /// Samples summarized.
+    pub samples: usize,
+    /// Lowest SNR, dB.
+    pub min_snr_db: f64,
+    /// Mean SNR, dB.
+    pub mean_snr_db: f64,
+    /// Largest desensitization, dB.
+    pub max_desensitization_db: f64,
+    /// Samples that clipped the ADC.
+    pub clipped_samples: usize,
+    /// Time desensitized by at least [`DESENSITIZATION_THRESHOLD_DB`], s.
+    pub desensitized_s: f64,
+}
+
+impl FrontEndSummary {
+    /// Summarize outputs in time order; each sample lasts until the next.
+    pub fn from_outputs(outputs: &[FrontEndOutput]) -> Self {
+        let count = outputs.len().max(1) as f64;
+        let desensitized_s = outputs
+            .windows(2)
+            .filter(|pair| pair[0].desensitization_db >= DESENSITIZATION_THRESHOLD_DB)
+            .map(|pair| pair[1].time_s - pair[0].time_s)
+            .sum();
+        Self {
+            samples: outputs.len(),
+            min_snr_db: outputs
+                .iter()
+                .map(|o| o.snr_db)
+                .fold(f64::INFINITY, f64::min),
+            mean_snr_db: outputs.iter().map(|o| o.snr_db).sum::<f64>() / count,
+            max_desensitization_db: outputs
+                .iter()
+                .map(|o| o.desensitization_db)
+                .fold(0.0, f64::max),
+            clipped_samples: outputs.iter().filter(|o| o.clipped).count(),
+            desensitized_s,
+        }
+    }
+}
+
+impl fmt::Display for FrontEndSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} samples: SNR min {:.1} dB mean {:.1} dB, desensitization up to {:.1} dB, \
+             {:.0} ms desensitized, {} clipped",
+            self.samples,
+            self.min_snr_db,
+            self.mean_snr_db,
+            self.max_desensitization_db,
+            self.desensitized_s * 1000.0,
+            self.clipped_samples
+        )
+    }
+}
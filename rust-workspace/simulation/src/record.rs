@@ -0,0 +1,260 @@
+//! Simulation Record Module
+//!
+//! `SimulationRecord` is the canonical serialized form of a link simulation:
+//! the `TransmissionResult` together with the band, transmission parameters
+//! and environment that produced it, a schema version, a timestamp and a
+//! scenario hash. Every exporter writes records, so external tools can check
+//! the format version and group or de-duplicate runs of the same scenario.
+//!
+//! Exporters:
+//! - JSON Lines, one record per line (`to_json_lines` / `from_json_lines`)
+//! - CSV with a fixed header (`to_csv`)
+//!
+//! Bump `SCHEMA_VERSION` whenever a serialized field is added, removed or
+//! changes meaning.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (reproducible simulation output)
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::validation::ValidationError;
+use crate::{
+    BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult,
+};
+
+/// Current record schema version.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// CSV header written by `to_csv`, one column per record field.
+pub const CSV_HEADER: &str = "schema_version,timestamp_unix_ms,band,scenario_hash,\
+distance_km,data_size_mb,required_data_rate_mbps,elevation_angle_degrees,\
+transmit_power_watts,antenna_diameter_meters,range_rate_m_s,range_acceleration_m_s2,\
+rain_rate_mm_hour,cloud_cover_percent,atmospheric_pressure_mb,temperature_celsius,\
+humidity_percent,ionospheric_activity,solar_activity,\
+success,actual_data_rate_mbps,total_latency_ms,power_consumption_watts,\
+transmission_efficiency,weather_impact_factor,signal_to_noise_ratio_db,path_loss_db,\
+rain_attenuation_db,doppler_shift_hz,doppler_rate_hz_s,carrier_tracking_loss_db,carrier_locked";
+
+/// One simulated transmission with the scenario that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SimulationRecord {
+    /// Record schema version, `SCHEMA_VERSION` when written.
+    pub schema_version: u32,
+    /// Time the simulation ran, milliseconds since the Unix epoch.
+    pub timestamp_unix_ms: u64,
+    /// Band simulated.
+    pub band: BandType,
+    /// `scenario_hash` of band, parameters and environment.
+    pub scenario_hash: String,
+    /// Transmission parameters.
+    pub parameters: TransmissionParameters,
+    /// Environmental conditions.
+    pub environment: EnvironmentalConditions,
+    /// Simulation result.
+    pub result: TransmissionResult,
+}
+
+/// Error reading serialized records.
+#[derive(Debug)]
+pub enum RecordError {
+    /// The input is not a valid record.
+    Json(serde_json::Error),
+    /// The record was written with a schema this build does not read.
+    UnsupportedSchema(u32),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Json(e) => write!(f, "invalid simulation record: {}", e),
+            RecordError::UnsupportedSchema(v) => write!(
+                f,
+                "unsupported simulation record schema {} (expected {})",
+                v, SCHEMA_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl From<serde_json::Error> for RecordError {
+    fn from(e: serde_json::Error) -> Self {
+        RecordError::Json(e)
+    }
+}
+
+impl SimulationRecord {
+    /// Wrap a result with its scenario.
+    ///
+    /// - **ID**: FN-REC-001
+    /// - **Requirement**: Every exported result carries the schema version,
+    ///   band, inputs and a scenario hash.
+    /// - **Inputs**: Band, parameters, environment, result, timestamp (ms).
+    /// - **Outputs**: A record at the current `SCHEMA_VERSION`.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: None.
+    pub fn new(
+        band: BandType,
+        parameters: TransmissionParameters,
+        environment: EnvironmentalConditions,
+        result: TransmissionResult,
+        timestamp_unix_ms: u64,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            timestamp_unix_ms,
+            band,
+            scenario_hash: scenario_hash(band, &parameters, &environment),
+            parameters,
+            environment,
+            result,
+        }
+    }
+
+    /// Run `band.simulate_transmission` and record it with the current time.
+    ///
+    /// Fails, recording nothing, if the inputs are out of range.
+    pub fn simulate(
+        band: &FrequencyBand,
+        parameters: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+    ) -> Result<Self, ValidationError> {
+        let result = band.simulate_transmission(parameters, environment)?;
+        let timestamp_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        Ok(Self::new(
+            band.name,
+            parameters.clone(),
+            environment.clone(),
+            result,
+            timestamp_unix_ms,
+        ))
+    }
+
+    /// Parse one JSON record, rejecting other schema versions.
+    pub fn from_json(json: &str) -> Result<Self, RecordError> {
+        let record: Self = serde_json::from_str(json)?;
+        if record.schema_version != SCHEMA_VERSION {
+            return Err(RecordError::UnsupportedSchema(record.schema_version));
+        }
+        Ok(record)
+    }
+
+    /// One CSV row in `CSV_HEADER` column order.
+    pub fn to_csv_row(&self) -> String {
+        let (p, e, r) = (&self.parameters, &self.environment, &self.result);
+        let columns = [
+            self.schema_version.to_string(),
+            self.timestamp_unix_ms.to_string(),
+            self.band.to_string(),
+            self.scenario_hash.clone(),
+            p.distance_km.to_string(),
+            p.data_size_mb.to_string(),
+            p.required_data_rate_mbps.to_string(),
+            p.elevation_angle_degrees.to_string(),
+            p.transmit_power_watts.to_string(),
+            p.antenna_diameter_meters.to_string(),
+            p.range_rate_m_s.to_string(),
+            p.range_acceleration_m_s2.to_string(),
+            e.rain_rate_mm_hour.to_string(),
+            e.cloud_cover_percent.to_string(),
+            e.atmospheric_pressure_mb.to_string(),
+            e.temperature_celsius.to_string(),
+            e.humidity_percent.to_string(),
+            e.ionospheric_activity.to_string(),
+            e.solar_activity.to_string(),
+            r.success.to_string(),
+            r.actual_data_rate_mbps.to_string(),
+            r.total_latency_ms.to_string(),
+            r.power_consumption_watts.to_string(),
+            r.transmission_efficiency.to_string(),
+            r.weather_impact_factor.to_string(),
+            r.signal_to_noise_ratio_db.to_string(),
+            r.path_loss_db.to_string(),
+            r.rain_attenuation_db.to_string(),
+            r.doppler_shift_hz.to_string(),
+            r.doppler_rate_hz_s.to_string(),
+            r.carrier_tracking_loss_db.to_string(),
+            r.carrier_locked.to_string(),
+        ];
+        columns.join(",")
+    }
+}
+
+/// Stable hash of a scenario, 16 lowercase hex digits.
+///
+/// FNV-1a over the band name and the bit patterns of every parameter and
+/// environment field in declaration order, so identical inputs hash the same
+/// across runs, platforms and builds.
+pub fn scenario_hash(
+    band: BandType,
+    parameters: &TransmissionParameters,
+    environment: &EnvironmentalConditions,
+) -> String {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let values = [
+        parameters.distance_km,
+        parameters.data_size_mb,
+        parameters.required_data_rate_mbps,
+        parameters.elevation_angle_degrees,
+        parameters.transmit_power_watts,
+        parameters.antenna_diameter_meters,
+        parameters.range_rate_m_s,
+        parameters.range_acceleration_m_s2,
+        environment.rain_rate_mm_hour,
+        environment.cloud_cover_percent,
+        environment.atmospheric_pressure_mb,
+        environment.temperature_celsius,
+        environment.humidity_percent,
+        environment.ionospheric_activity,
+        environment.solar_activity,
+    ];
+
+    let bytes = band
+        .to_string()
+        .into_bytes()
+        .into_iter()
+        .chain(values.iter().flat_map(|v| v.to_bits().to_le_bytes()));
+    let hash = bytes.fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Export records as JSON Lines, one record per line.
+pub fn to_json_lines(records: &[SimulationRecord]) -> serde_json::Result<String> {
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Read records exported by `to_json_lines`, skipping blank lines.
+pub fn from_json_lines(text: &str) -> Result<Vec<SimulationRecord>, RecordError> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(SimulationRecord::from_json)
+        .collect()
+}
+
+/// Export records as CSV with `CSV_HEADER`.
+pub fn to_csv(records: &[SimulationRecord]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for record in records {
+        out.push_str(&record.to_csv_row());
+        out.push('\n');
+    }
+    out
+}
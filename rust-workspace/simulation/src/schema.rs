@@ -0,0 +1,48 @@
+//! JSON Schema Module
+//!
+//! Schemas of the simulation's public inputs and outputs, generated from the
+//! Rust definitions so that external tooling and REST front ends validate
+//! payloads against the same format the simulation reads and writes.
+//! `SimulationRecord` is the export format; its schema changes together with
+//! `record::SCHEMA_VERSION`.
+//!
+//! Only built with the `schema` feature.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (authoritative input and output formats)
+
+use std::path::{Path, PathBuf};
+
+use schemars::{schema::RootSchema, schema_for};
+
+use crate::record::SimulationRecord;
+use crate::{EnvironmentalConditions, TransmissionParameters, TransmissionResult};
+
+/// Schema of every public payload type, by type name.
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        (
+            "TransmissionParameters",
+            schema_for!(TransmissionParameters),
+        ),
+        (
+            "EnvironmentalConditions",
+            schema_for!(EnvironmentalConditions),
+        ),
+        ("TransmissionResult", schema_for!(TransmissionResult)),
+        ("SimulationRecord", schema_for!(SimulationRecord)),
+    ]
+}
+
+/// Write every schema to `dir` as pretty-printed `<type>.schema.json`,
+/// creating the directory if needed, and return the paths written.
+pub fn write_schemas(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for (name, schema) in schemas() {
+        let path = dir.join(format!("{}.schema.json", name));
+        std::fs::write(&path, serde_json::to_string_pretty(&schema)?)?;
+        written.push(path);
+    }
+    Ok(written)
+}
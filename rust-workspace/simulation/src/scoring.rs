@@ -0,0 +1,410 @@
+//! Band Recommendation Scoring Module
+//!
+//! Criterion-of-merit ranking of frequency bands for a mission. Each band is
+//! simulated under a set of environments and scored from 0.0 (worst) to 1.0
+//! (best) on five criteria:
+//!
+//! - **Availability**: fraction of environments in which the link succeeds
+//! - **Throughput**: mean achieved rate, relative to the best band
+//! - **Power**: mean power consumption, relative to the most frugal band
+//! - **Latency**: mean end-to-end latency, relative to the fastest band
+//! - **Weather robustness**: `1 / (1 + fade / 3 dB)`, where fade is the SNR
+//!   drop from the best to the worst environment
+//!
+//! The overall score is the weighted mean of the criteria. Planners pick the
+//! weights for their mission; the ranking logic lives here so every front end
+//! recommends the same bands for the same inputs.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (band selection)
+//! - REQ-PF-002: Data Transfer Rates (throughput and latency criteria)
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{self, SimulationCache};
+use crate::locale::{Catalog, Localize};
+use crate::margin::MarginPolicy;
+use crate::validation::ValidationError;
+use crate::{BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters};
+
+/// A scoring criterion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Criterion {
+    Availability,
+    Throughput,
+    Power,
+    Latency,
+    WeatherRobustness,
+}
+
+impl Criterion {
+    /// Every criterion in display order.
+    pub const ALL: [Criterion; 5] = [
+        Criterion::Availability,
+        Criterion::Throughput,
+        Criterion::Power,
+        Criterion::Latency,
+        Criterion::WeatherRobustness,
+    ];
+}
+
+impl fmt::Display for Criterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for Criterion {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        let key = match self {
+            Criterion::Availability => "criterion.availability",
+            Criterion::Throughput => "criterion.throughput",
+            Criterion::Power => "criterion.power",
+            Criterion::Latency => "criterion.latency",
+            Criterion::WeatherRobustness => "criterion.weather_robustness",
+        };
+        f.write_str(catalog.text(key))
+    }
+}
+
+/// Score for each criterion, each in [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CriterionScores {
+    pub availability: f64,
+    pub throughput: f64,
+    pub power: f64,
+    pub latency: f64,
+    pub weather_robustness: f64,
+}
+
+impl CriterionScores {
+    /// Score for one criterion.
+    pub fn get(&self, criterion: Criterion) -> f64 {
+        match criterion {
+            Criterion::Availability => self.availability,
+            Criterion::Throughput => self.throughput,
+            Criterion::Power => self.power,
+            Criterion::Latency => self.latency,
+            Criterion::WeatherRobustness => self.weather_robustness,
+        }
+    }
+}
+
+/// Relative importance of each criterion; only ratios matter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CriteriaWeights {
+    pub availability: f64,
+    pub throughput: f64,
+    pub power: f64,
+    pub latency: f64,
+    pub weather_robustness: f64,
+}
+
+impl CriteriaWeights {
+    /// Same weight for every criterion.
+    pub const fn uniform() -> Self {
+        Self {
+            availability: 1.0,
+            throughput: 1.0,
+            power: 1.0,
+            latency: 1.0,
+            weather_robustness: 1.0,
+        }
+    }
+
+    /// Weight of one criterion.
+    pub fn get(&self, criterion: Criterion) -> f64 {
+        match criterion {
+            Criterion::Availability => self.availability,
+            Criterion::Throughput => self.throughput,
+            Criterion::Power => self.power,
+            Criterion::Latency => self.latency,
+            Criterion::WeatherRobustness => self.weather_robustness,
+        }
+    }
+}
+
+impl Default for CriteriaWeights {
+    /// Link availability first, then throughput and weather robustness.
+    fn default() -> Self {
+        Self {
+            availability: 0.3,
+            throughput: 0.25,
+            power: 0.15,
+            latency: 0.1,
+            weather_robustness: 0.2,
+        }
+    }
+}
+
+/// Coarse rating of an overall score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Rating {
+    Poor,
+    Fair,
+    Good,
+    Excellent,
+}
+
+impl Rating {
+    /// Rating for an overall score in [0, 1].
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.8 {
+            Rating::Excellent
+        } else if score >= 0.6 {
+            Rating::Good
+        } else if score >= 0.4 {
+            Rating::Fair
+        } else {
+            Rating::Poor
+        }
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_localized(Catalog::english(), f)
+    }
+}
+
+impl Localize for Rating {
+    fn write_localized(&self, catalog: &Catalog, f: &mut dyn fmt::Write) -> fmt::Result {
+        let key = match self {
+            Rating::Excellent => "rating.excellent",
+            Rating::Good => "rating.good",
+            Rating::Fair => "rating.fair",
+            Rating::Poor => "rating.poor",
+        };
+        f.write_str(catalog.text(key))
+    }
+}
+
+/// Ranked recommendation for one band.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandRecommendation {
+    /// Band scored.
+    pub band: BandType,
+    /// Per-criterion scores, each in [0, 1].
+    pub scores: CriterionScores,
+    /// Weighted mean of `scores`, in [0, 1].
+    pub overall: f64,
+    /// Rating of `overall`.
+    pub rating: Rating,
+}
+
+/// SNR fade between best and worst environment that halves weather robustness, dB.
+const FADE_HALVING_DB: f64 = 3.0;
+
+/// Raw per-band figures before normalisation across bands.
+struct RawMetrics {
+    band: BandType,
+    availability: f64,
+    mean_rate_mbps: f64,
+    mean_power_watts: f64,
+    mean_latency_ms: f64,
+    weather_robustness: f64,
+}
+
+/// Optimal, typical and adverse conditions used by `BandScorer::default`.
+pub fn reference_environments() -> Vec<EnvironmentalConditions> {
+    vec![
+        EnvironmentalConditions {
+            rain_rate_mm_hour: 0.0,
+            cloud_cover_percent: 0.0,
+            atmospheric_pressure_mb: 1013.25,
+            temperature_celsius: 20.0,
+            humidity_percent: 30.0,
+            ionospheric_activity: 0.05,
+            solar_activity: 0.05,
+        },
+        EnvironmentalConditions {
+            rain_rate_mm_hour: 2.0,
+            cloud_cover_percent: 40.0,
+            atmospheric_pressure_mb: 1010.0,
+            temperature_celsius: 15.0,
+            humidity_percent: 70.0,
+            ionospheric_activity: 0.2,
+            solar_activity: 0.2,
+        },
+        EnvironmentalConditions {
+            rain_rate_mm_hour: 15.0,
+            cloud_cover_percent: 100.0,
+            atmospheric_pressure_mb: 1000.0,
+            temperature_celsius: 10.0,
+            humidity_percent: 95.0,
+            ionospheric_activity: 0.6,
+            solar_activity: 0.5,
+        },
+    ]
+}
+
+/// Weighted criterion-of-merit band scorer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandScorer {
+    /// Criterion weights.
+    pub weights: CriteriaWeights,
+    /// Environments each band is simulated under.
+    pub environments: Vec<EnvironmentalConditions>,
+    /// Margins a band must keep to count as available.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+    /// Results shared across recommendations; `None` simulates every time.
+    #[serde(skip)]
+    pub cache: Option<Arc<SimulationCache>>,
+}
+
+impl Default for BandScorer {
+    fn default() -> Self {
+        Self::new(CriteriaWeights::default())
+    }
+}
+
+impl BandScorer {
+    /// Scorer with `weights` over the reference environments.
+    pub fn new(weights: CriteriaWeights) -> Self {
+        Self {
+            weights,
+            environments: reference_environments(),
+            margins: MarginPolicy::default(),
+            cache: None,
+        }
+    }
+
+    /// Judge availability against the mission's `margins`.
+    pub fn with_margins(mut self, margins: MarginPolicy) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Reuse results of configurations already in `cache`, and add new ones
+    /// to it.
+    pub fn with_cache(mut self, cache: Arc<SimulationCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Replace the environments each band is simulated under.
+    pub fn with_environments(mut self, environments: Vec<EnvironmentalConditions>) -> Self {
+        self.environments = environments;
+        self
+    }
+
+    /// Rank `bands` for a mission.
+    ///
+    /// - **ID**: FN-SIM-002
+    /// - **Requirement**: Recommend bands from weighted availability,
+    ///   throughput, power, latency and weather robustness criteria, with the
+    ///   per-criterion scores that produced each ranking.
+    /// - **Inputs**: Candidate bands and the mission's transmission parameters.
+    /// - **Outputs**: One recommendation per band, best `overall` first; ties
+    ///   keep the input order.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: With no environments, or weights summing to zero,
+    ///   every band scores 0.0 and rates `Poor`. Negative weights count as zero.
+    ///   `ValidationError` if `params` or an environment are out of range.
+    pub fn recommend(
+        &self,
+        bands: &[FrequencyBand],
+        params: &TransmissionParameters,
+    ) -> Result<Vec<BandRecommendation>, ValidationError> {
+        params.validate()?;
+        let raw = bands
+            .iter()
+            .map(|b| self.measure(b, params))
+            .collect::<Result<Vec<RawMetrics>, _>>()?;
+
+        let best_rate = raw.iter().map(|m| m.mean_rate_mbps).fold(0.0, f64::max);
+        let least_power = raw
+            .iter()
+            .map(|m| m.mean_power_watts)
+            .fold(f64::INFINITY, f64::min);
+        let least_latency = raw
+            .iter()
+            .map(|m| m.mean_latency_ms)
+            .fold(f64::INFINITY, f64::min);
+
+        let mut recommendations: Vec<BandRecommendation> = raw
+            .iter()
+            .map(|m| {
+                let scores = CriterionScores {
+                    availability: m.availability,
+                    throughput: ratio(m.mean_rate_mbps, best_rate),
+                    power: ratio(least_power, m.mean_power_watts),
+                    latency: ratio(least_latency, m.mean_latency_ms),
+                    weather_robustness: m.weather_robustness,
+                };
+                let overall = self.overall(&scores);
+                BandRecommendation {
+                    band: m.band,
+                    scores,
+                    overall,
+                    rating: Rating::from_score(overall),
+                }
+            })
+            .collect();
+
+        recommendations.sort_by(|a, b| b.overall.total_cmp(&a.overall));
+        Ok(recommendations)
+    }
+
+    /// Weighted mean of criterion scores.
+    pub fn overall(&self, scores: &CriterionScores) -> f64 {
+        let (weighted, total) = Criterion::ALL.iter().fold((0.0, 0.0), |(sum, total), c| {
+            let w = self.weights.get(*c).max(0.0);
+            (sum + w * scores.get(*c), total + w)
+        });
+        if total > 0.0 {
+            weighted / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Simulate one band under every environment.
+    fn measure(
+        &self,
+        band: &FrequencyBand,
+        params: &TransmissionParameters,
+    ) -> Result<RawMetrics, ValidationError> {
+        let results = self
+            .environments
+            .iter()
+            .map(|env| cache::simulate(self.cache.as_deref(), band, params, env, &self.margins))
+            .collect::<Result<Vec<_>, _>>()?;
+        let n = results.len().max(1) as f64;
+        let mean =
+            |f: &dyn Fn(&crate::TransmissionResult) -> f64| results.iter().map(f).sum::<f64>() / n;
+
+        let worst_snr = results
+            .iter()
+            .map(|r| r.signal_to_noise_ratio_db)
+            .fold(f64::INFINITY, f64::min);
+        let best_snr = results
+            .iter()
+            .map(|r| r.signal_to_noise_ratio_db)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let fade_db = best_snr - worst_snr;
+
+        Ok(RawMetrics {
+            band: band.name,
+            availability: results.iter().filter(|r| r.success).count() as f64 / n,
+            mean_rate_mbps: mean(&|r| r.actual_data_rate_mbps),
+            mean_power_watts: mean(&|r| r.power_consumption_watts),
+            mean_latency_ms: mean(&|r| r.total_latency_ms),
+            weather_robustness: ratio(1.0, 1.0 + fade_db / FADE_HALVING_DB),
+        })
+    }
+}
+
+/// `numerator / denominator` clamped to [0, 1]; 0 when undefined.
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    let r = numerator / denominator;
+    if r.is_finite() {
+        r.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,410 @@
+//! Shadow-Mode Policy Evaluation Module
+//!
+//! A new band-selection or adaptive coding and modulation (ACM) policy is
+//! evaluated in shadow mode before it is enabled. The active policy makes
+//! the decision that is applied; the shadow policy sees the same band scores
+//! at the same moment and makes the decision it would have made, which is
+//! logged but never applied. Every decision pair is recorded with the
+//! throughput and outage each decision would give, so the two policies can
+//! be compared over live or simulated traffic before the switch.
+//!
+//! Policies implement [`SelectionPolicy`]. Provided are the planner's
+//! current rule ([`BestScore`]), a rule that holds its band until another
+//! is clearly better ([`Hysteresis`]), a fixed band ([`FixedBand`]), and
+//! [`WithAcm`], which adds a modulation and coding choice to any band
+//! policy with `advanced_rf::select_amc`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (band-selection policy evaluation)
+//! - REQ-PF-003: Link Capacity Optimisation (ACM policy evaluation)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::advanced_rf::{select_amc, AmcConditions, CodingRate, ModulationScheme};
+use crate::modcod::FecScheme;
+use crate::margin::MarginPolicy;
+use crate::validation::ValidationError;
+use crate::weather::WeatherSample;
+use crate::{score_bands_with_margins, BandScore, BandType, FrequencyBand, TransmissionParameters};
+
+/// A band-selection policy.
+///
+/// `scores` holds every candidate band, best composite score first, as
+/// returned by `score_bands_with_margins`; it is never empty.
+pub trait SelectionPolicy {
+    /// Name the policy is reported under.
+    fn name(&self) -> &str;
+
+    /// Decide the band, and optionally the modulation and coding, for the
+    /// conditions `scores` were taken in.
+    fn decide(&mut self, scores: &[BandScore]) -> PolicyDecision;
+}
+
+/// Modulation and coding chosen by an ACM policy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AcmChoice {
+    /// Modulation scheme.
+    pub modulation: ModulationScheme,
+    /// FEC coding rate.
+    pub coding_rate: CodingRate,
+    /// Information throughput of the choice in the policy's bandwidth, Mbps.
+    pub throughput_mbps: f64,
+}
+
+impl AcmChoice {
+    /// SNR the choice needs to close, dB.
+    pub fn required_snr_db(&self) -> f64 {
+        self.modulation.min_eb_n0_db() - self.coding_rate.coding_gain_db()
+    }
+}
+
+/// A policy's decision.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    /// Band to transmit on.
+    pub band: BandType,
+    /// Modulation and coding; `None` for a band-only policy.
+    pub acm: Option<AcmChoice>,
+}
+
+impl PolicyDecision {
+    /// Decision on `band` without an ACM choice.
+    pub fn band(band: BandType) -> Self {
+        Self { band, acm: None }
+    }
+
+    /// What the decision would give in the conditions `scores` were taken in.
+    ///
+    /// A band short of the mission's margins is an outage; so is an ACM
+    /// choice needing more SNR than the band has. ACM throughput is capped
+    /// at the band's achievable rate.
+    pub fn outcome(&self, scores: &[BandScore]) -> DecisionOutcome {
+        let Some(score) = scores.iter().find(|score| score.band == self.band) else {
+            return DecisionOutcome::OUTAGE;
+        };
+        let acm_closes = self
+            .acm
+            .is_none_or(|acm| score.snr_db >= acm.required_snr_db());
+        if !score.meets_requirement || !acm_closes {
+            return DecisionOutcome::OUTAGE;
+        }
+        DecisionOutcome {
+            throughput_mbps: match self.acm {
+                Some(acm) => acm.throughput_mbps.min(score.achievable_rate_mbps),
+                None => score.achievable_rate_mbps,
+            },
+            outage: false,
+        }
+    }
+}
+
+/// Throughput a decision gives, or would have given.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecisionOutcome {
+    /// Throughput, Mbps; zero in an outage.
+    pub throughput_mbps: f64,
+    /// Whether the link fails to close.
+    pub outage: bool,
+}
+
+impl DecisionOutcome {
+    const OUTAGE: Self = Self {
+        throughput_mbps: 0.0,
+        outage: true,
+    };
+}
+
+// ─── Policies ─────────────────────────────────────────────────────────────────
+
+/// Highest composite score, re-chosen at every decision.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BestScore;
+
+impl SelectionPolicy for BestScore {
+    fn name(&self) -> &str {
+        "best-score"
+    }
+
+    fn decide(&mut self, scores: &[BandScore]) -> PolicyDecision {
+        PolicyDecision::band(scores[0].band)
+    }
+}
+
+/// Highest composite score, but the current band is held until it no
+/// longer meets the margins or another band beats it by `switch_margin`.
+#[derive(Debug, Clone, Copy)]
+pub struct Hysteresis {
+    /// Composite score another band must exceed the current one by.
+    pub switch_margin: f64,
+    current: Option<BandType>,
+}
+
+impl Hysteresis {
+    /// Hold the current band unless beaten by `switch_margin`.
+    pub fn new(switch_margin: f64) -> Self {
+        Self {
+            switch_margin,
+            current: None,
+        }
+    }
+}
+
+impl SelectionPolicy for Hysteresis {
+    fn name(&self) -> &str {
+        "hysteresis"
+    }
+
+    fn decide(&mut self, scores: &[BandScore]) -> PolicyDecision {
+        let best = &scores[0];
+        let held = self
+            .current
+            .and_then(|band| scores.iter().find(|score| score.band == band))
+            .filter(|score| {
+                score.meets_requirement
+                    && best.composite_score - score.composite_score < self.switch_margin
+            });
+        let band = held.map_or(best.band, |score| score.band);
+        self.current = Some(band);
+        PolicyDecision::band(band)
+    }
+}
+
+/// Always the same band.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedBand(pub BandType);
+
+impl SelectionPolicy for FixedBand {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn decide(&mut self, _scores: &[BandScore]) -> PolicyDecision {
+        PolicyDecision::band(self.0)
+    }
+}
+
+/// A band policy with modulation and coding chosen by `select_amc` at the
+/// chosen band's SNR.
+#[derive(Debug, Clone)]
+pub struct WithAcm<P> {
+    /// Policy choosing the band.
+    pub bands: P,
+    /// Channel bandwidth, MHz.
+    pub bandwidth_mhz: f64,
+    /// Keep to robust modulations for latency-sensitive traffic.
+    pub latency_sensitive: bool,
+    name: String,
+}
+
+impl<P: SelectionPolicy> WithAcm<P> {
+    /// Add ACM in `bandwidth_mhz` to the band policy `bands`.
+    pub fn new(bands: P, bandwidth_mhz: f64, latency_sensitive: bool) -> Self {
+        let name = format!("{}+acm", bands.name());
+        Self {
+            bands,
+            bandwidth_mhz,
+            latency_sensitive,
+            name,
+        }
+    }
+}
+
+impl<P: SelectionPolicy> SelectionPolicy for WithAcm<P> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn decide(&mut self, scores: &[BandScore]) -> PolicyDecision {
+        let band = self.bands.decide(scores).band;
+        let snr_db = scores
+            .iter()
+            .find(|score| score.band == band)
+            .map_or(f64::NEG_INFINITY, |score| score.snr_db);
+        let amc = select_amc(&AmcConditions {
+            link_snr_db: snr_db,
+            bandwidth_mhz: self.bandwidth_mhz,
+            latency_sensitive: self.latency_sensitive,
+            fec: FecScheme::Legacy,
+        });
+        PolicyDecision {
+            band,
+            acm: Some(AcmChoice {
+                modulation: amc.modulation,
+                coding_rate: amc.coding_rate,
+                throughput_mbps: amc.throughput_mbps,
+            }),
+        }
+    }
+}
+
+// ─── Shadow Evaluation ────────────────────────────────────────────────────────
+
+/// One decision of the active policy and the shadow policy's counterpart.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShadowRecord {
+    /// Time of the decision, scenario seconds.
+    pub time_s: f64,
+    /// Decision applied.
+    pub active: PolicyDecision,
+    /// Decision logged only.
+    pub shadow: PolicyDecision,
+    /// What the applied decision gave.
+    pub active_outcome: DecisionOutcome,
+    /// What the shadow decision would have given.
+    pub shadow_outcome: DecisionOutcome,
+}
+
+impl ShadowRecord {
+    /// Whether both policies made the same decision.
+    pub fn agrees(&self) -> bool {
+        self.active == self.shadow
+    }
+}
+
+/// Performance of one policy over a shadow evaluation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyStats {
+    /// Policy name.
+    pub name: String,
+    /// Mean throughput over the decisions, Mbps.
+    pub mean_throughput_mbps: f64,
+    /// Decisions that left the link in outage.
+    pub outages: usize,
+    /// Decisions that changed band from the previous one.
+    pub band_switches: usize,
+}
+
+/// Comparison of the active and shadow policies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowSummary {
+    /// Decisions made.
+    pub decisions: usize,
+    /// Decisions on which the policies agreed.
+    pub agreements: usize,
+    /// Active policy.
+    pub active: PolicyStats,
+    /// Shadow policy.
+    pub shadow: PolicyStats,
+}
+
+impl fmt::Display for ShadowSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} decisions, {} agreed ({:.1}%)",
+            self.decisions,
+            self.agreements,
+            self.agreements as f64 * 100.0 / self.decisions.max(1) as f64
+        )?;
+        for (role, stats) in [("active", &self.active), ("shadow", &self.shadow)] {
+            writeln!(
+                f,
+                "  {:<6} {:<16} {:>10.1} Mbps mean, {} outages, {} band switches",
+                role, stats.name, stats.mean_throughput_mbps, stats.outages, stats.band_switches
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// An active policy with a shadow policy evaluated alongside it.
+pub struct ShadowEvaluation {
+    active: Box<dyn SelectionPolicy>,
+    shadow: Box<dyn SelectionPolicy>,
+    records: Vec<ShadowRecord>,
+}
+
+impl ShadowEvaluation {
+    /// Apply `active`, evaluate `shadow` alongside it.
+    pub fn new(active: Box<dyn SelectionPolicy>, shadow: Box<dyn SelectionPolicy>) -> Self {
+        Self {
+            active,
+            shadow,
+            records: Vec::new(),
+        }
+    }
+
+    /// Make one decision.
+    ///
+    /// - **ID**: FN-SHD-001
+    /// - **Requirement**: Run a candidate policy on the same inputs as the
+    ///   active one without applying its decisions (REQ-FN-007, REQ-PF-003).
+    /// - **Inputs**: Decision time and the band scores, best first, as from
+    ///   `score_bands_with_margins`; must not be empty.
+    /// - **Outputs**: The active policy's decision, to apply.
+    /// - **Side Effects**: Both decisions and their outcomes are logged.
+    pub fn decide(&mut self, time_s: f64, scores: &[BandScore]) -> PolicyDecision {
+        let active = self.active.decide(scores);
+        let shadow = self.shadow.decide(scores);
+        self.records.push(ShadowRecord {
+            time_s,
+            active,
+            shadow,
+            active_outcome: active.outcome(scores),
+            shadow_outcome: shadow.outcome(scores),
+        });
+        active
+    }
+
+    /// Decide at every sample of a simulated weather series.
+    ///
+    /// - **ID**: FN-SHD-002
+    /// - **Requirement**: Evaluate a candidate policy on simulated traffic
+    ///   before it is enabled (REQ-FN-007).
+    /// - **Inputs**: Candidate bands, link parameters, the weather series and
+    ///   the mission's margins.
+    /// - **Outputs**: Summary of the evaluation so far.
+    /// - **Failure Modes**: `ValidationError` from the first sample whose
+    ///   inputs are out of range; earlier samples stay logged.
+    pub fn run(
+        &mut self,
+        bands: &[FrequencyBand],
+        params: &TransmissionParameters,
+        samples: &[WeatherSample],
+        margins: &MarginPolicy,
+    ) -> Result<ShadowSummary, ValidationError> {
+        for sample in samples {
+            let scores = score_bands_with_margins(bands, params, &sample.conditions, margins)?;
+            self.decide(sample.time_s, &scores);
+        }
+        Ok(self.summary())
+    }
+
+    /// Logged decisions in order.
+    pub fn records(&self) -> &[ShadowRecord] {
+        &self.records
+    }
+
+    /// Compare the two policies over the logged decisions.
+    pub fn summary(&self) -> ShadowSummary {
+        let stats =
+            |name: &str, decision: fn(&ShadowRecord) -> (PolicyDecision, DecisionOutcome)| {
+                let mut stats = PolicyStats {
+                    name: name.to_string(),
+                    mean_throughput_mbps: 0.0,
+                    outages: 0,
+                    band_switches: 0,
+                };
+                let mut previous: Option<BandType> = None;
+                for record in &self.records {
+                    let (decision, outcome) = decision(record);
+                    stats.mean_throughput_mbps += outcome.throughput_mbps;
+                    stats.outages += usize::from(outcome.outage);
+                    stats.band_switches +=
+                        usize::from(previous.is_some_and(|band| band != decision.band));
+                    previous = Some(decision.band);
+                }
+                stats.mean_throughput_mbps /= self.records.len().max(1) as f64;
+                stats
+            };
+
+        ShadowSummary {
+            decisions: self.records.len(),
+            agreements: self.records.iter().filter(|r| r.agrees()).count(),
+            active: stats(self.active.name(), |r| (r.active, r.active_outcome)),
+            shadow: stats(self.shadow.name(), |r| (r.shadow, r.shadow_outcome)),
+        }
+    }
+}
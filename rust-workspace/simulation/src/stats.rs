@@ -0,0 +1,292 @@
+//! Streaming Statistics Module
+//!
+//! Summary statistics computed one sample at a time in constant memory, so
+//! long simulation runs need not keep every result:
+//!
+//! - `RunningStats` — count, mean, variance (Welford), min and max; two
+//!   accumulators over disjoint samples merge exactly
+//! - `P2Quantile`   — one quantile estimated with the P² algorithm (Jain and
+//!   Chlamtac), five markers regardless of sample count
+//! - `StreamSummary` — running statistics plus a set of quantile estimates
+//!
+//! P² estimates depend on sample order and cannot be merged, so feed a
+//! `StreamSummary` from a single ordered stream such as `MonteCarlo::stream`.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (summary statistics for long runs)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Quantiles tracked by `StreamSummary::default()`: median, 95th and 99th
+/// percentile.
+pub const DEFAULT_QUANTILES: [f64; 3] = [0.5, 0.95, 0.99];
+
+/// Count, mean, variance, min and max of a stream of samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    /// Sum of squared deviations from the mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunningStats {
+    /// Empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one sample. NaN samples are ignored.
+    pub fn push(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Fold in an accumulator over other samples (Chan et al. pairwise
+    /// update), as if its samples had been pushed here.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count = count;
+    }
+
+    /// Samples seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Mean, or `None` before the first sample.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Sample variance (n - 1 denominator), or `None` below two samples.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Sample standard deviation, or `None` below two samples.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Smallest sample, or `None` before the first sample.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Largest sample, or `None` before the first sample.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+}
+
+/// Streaming estimate of one quantile using the P² algorithm.
+///
+/// Keeps five markers: the minimum, the maximum, the target quantile and the
+/// quantiles halfway to either side. Marker heights are adjusted with
+/// piecewise-parabolic interpolation as samples arrive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct P2Quantile {
+    p: f64,
+    count: u64,
+    /// Marker heights; the first five samples until they are all seen.
+    heights: [f64; 5],
+    /// Actual marker positions (zero-based sample ranks).
+    positions: [f64; 5],
+    /// Desired marker positions.
+    desired: [f64; 5],
+    /// Desired position increment per sample.
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Estimator for quantile `p`, clamped to 0-1.
+    pub fn new(p: f64) -> Self {
+        let p = if p.is_nan() { 0.5 } else { p.clamp(0.0, 1.0) };
+        Self {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired: [0.0, 2.0 * p, 4.0 * p, 2.0 + 2.0 * p, 4.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    /// Quantile being estimated.
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Samples seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Add one sample. NaN samples are ignored.
+    pub fn push(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // Cell the sample falls in, stretching the extremes if needed
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (0..4).find(|&i| value < self.heights[i + 1]).unwrap_or(3)
+        };
+
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_above = self.positions[i + 1] - self.positions[i];
+            let room_below = self.positions[i - 1] - self.positions[i];
+            if (offset >= 1.0 && room_above > 1.0) || (offset <= -1.0 && room_below < -1.0) {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] =
+                    if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                        parabolic
+                    } else {
+                        self.linear(i, step)
+                    };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Current estimate, or `None` before the first sample. Exact (nearest
+    /// rank) until five samples have been seen.
+    pub fn estimate(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..=4 => {
+                let mut seen = self.heights;
+                let seen = &mut seen[..self.count as usize];
+                seen.sort_by(f64::total_cmp);
+                let rank = (self.p * (seen.len() - 1) as f64).round() as usize;
+                Some(seen[rank])
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i]
+            + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+/// Running statistics and quantile estimates over one stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamSummary {
+    pub stats: RunningStats,
+    pub quantiles: Vec<P2Quantile>,
+}
+
+impl StreamSummary {
+    /// Summary tracking the given quantiles.
+    pub fn new(quantiles: &[f64]) -> Self {
+        Self {
+            stats: RunningStats::new(),
+            quantiles: quantiles.iter().map(|&p| P2Quantile::new(p)).collect(),
+        }
+    }
+
+    /// Add one sample. NaN samples are ignored.
+    pub fn push(&mut self, value: f64) {
+        self.stats.push(value);
+        for quantile in &mut self.quantiles {
+            quantile.push(value);
+        }
+    }
+
+    /// Estimate of quantile `p`, if it is tracked and samples have been seen.
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        self.quantiles
+            .iter()
+            .find(|quantile| (quantile.p() - p).abs() < 1e-9)
+            .and_then(P2Quantile::estimate)
+    }
+}
+
+impl Default for StreamSummary {
+    fn default() -> Self {
+        Self::new(&DEFAULT_QUANTILES)
+    }
+}
+
+impl fmt::Display for StreamSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.stats;
+        write!(f, "n={}", stats.count())?;
+        if let (Some(mean), Some(min), Some(max)) = (stats.mean(), stats.min(), stats.max()) {
+            write!(f, " mean={:.3} min={:.3} max={:.3}", mean, min, max)?;
+        }
+        if let Some(std_dev) = stats.std_dev() {
+            write!(f, " sd={:.3}", std_dev)?;
+        }
+        for quantile in &self.quantiles {
+            if let Some(estimate) = quantile.estimate() {
+                write!(f, " p{}={:.3}", quantile.p() * 100.0, estimate)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,528 @@
+//! Parameter Sweep Module
+//!
+//! `ParameterSweep` runs `simulate_transmission` over the full cross product
+//! of swept input values and bands. Any field of `TransmissionParameters` or
+//! `EnvironmentalConditions` can be swept, either over an inclusive
+//! start/stop/step range or over explicit values; unswept fields keep their
+//! base value.
+//!
+//! Results come back as a `SweepTable` that exports in tidy long format: one
+//! row per run and output metric, with a column for each swept field, so the
+//! table loads directly into dataframe and plotting tools. Each run can also
+//! be converted to a `SimulationRecord` for the record exporters.
+//!
+//! With the `parallel` feature the runs are distributed over a rayon thread
+//! pool. Results are identical and in the same order either way.
+//! `run_controlled` reports progress every `SWEEP_BATCH_RUNS` runs and stops
+//! when cancelled; see the `progress` module.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (parametric link studies)
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{self, SimulationCache};
+use crate::margin::MarginPolicy;
+use crate::progress::{RunControl, RunError};
+use crate::record::SimulationRecord;
+use crate::validation::ValidationError;
+use crate::{
+    BandType, EnvironmentalConditions, FrequencyBand, TransmissionParameters, TransmissionResult,
+};
+
+/// Runs simulated between progress reports of `run_controlled`.
+pub const SWEEP_BATCH_RUNS: usize = 4096;
+
+/// An input field that can be swept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SweepField {
+    DistanceKm,
+    DataSizeMb,
+    RequiredDataRateMbps,
+    ElevationAngleDegrees,
+    TransmitPowerWatts,
+    AntennaDiameterMeters,
+    RangeRateMS,
+    RangeAccelerationMS2,
+    RainRateMmHour,
+    CloudCoverPercent,
+    AtmosphericPressureMb,
+    TemperatureCelsius,
+    HumidityPercent,
+    IonosphericActivity,
+    SolarActivity,
+}
+
+impl SweepField {
+    /// Every field, transmission parameters first, in declaration order.
+    pub const ALL: [SweepField; 15] = [
+        SweepField::DistanceKm,
+        SweepField::DataSizeMb,
+        SweepField::RequiredDataRateMbps,
+        SweepField::ElevationAngleDegrees,
+        SweepField::TransmitPowerWatts,
+        SweepField::AntennaDiameterMeters,
+        SweepField::RangeRateMS,
+        SweepField::RangeAccelerationMS2,
+        SweepField::RainRateMmHour,
+        SweepField::CloudCoverPercent,
+        SweepField::AtmosphericPressureMb,
+        SweepField::TemperatureCelsius,
+        SweepField::HumidityPercent,
+        SweepField::IonosphericActivity,
+        SweepField::SolarActivity,
+    ];
+
+    /// Column name, matching the struct field and the record CSV column.
+    pub fn name(self) -> &'static str {
+        match self {
+            SweepField::DistanceKm => "distance_km",
+            SweepField::DataSizeMb => "data_size_mb",
+            SweepField::RequiredDataRateMbps => "required_data_rate_mbps",
+            SweepField::ElevationAngleDegrees => "elevation_angle_degrees",
+            SweepField::TransmitPowerWatts => "transmit_power_watts",
+            SweepField::AntennaDiameterMeters => "antenna_diameter_meters",
+            SweepField::RangeRateMS => "range_rate_m_s",
+            SweepField::RangeAccelerationMS2 => "range_acceleration_m_s2",
+            SweepField::RainRateMmHour => "rain_rate_mm_hour",
+            SweepField::CloudCoverPercent => "cloud_cover_percent",
+            SweepField::AtmosphericPressureMb => "atmospheric_pressure_mb",
+            SweepField::TemperatureCelsius => "temperature_celsius",
+            SweepField::HumidityPercent => "humidity_percent",
+            SweepField::IonosphericActivity => "ionospheric_activity",
+            SweepField::SolarActivity => "solar_activity",
+        }
+    }
+
+    /// Value of this field in a scenario.
+    pub fn get(
+        self,
+        parameters: &TransmissionParameters,
+        environment: &EnvironmentalConditions,
+    ) -> f64 {
+        match self {
+            SweepField::DistanceKm => parameters.distance_km,
+            SweepField::DataSizeMb => parameters.data_size_mb,
+            SweepField::RequiredDataRateMbps => parameters.required_data_rate_mbps,
+            SweepField::ElevationAngleDegrees => parameters.elevation_angle_degrees,
+            SweepField::TransmitPowerWatts => parameters.transmit_power_watts,
+            SweepField::AntennaDiameterMeters => parameters.antenna_diameter_meters,
+            SweepField::RangeRateMS => parameters.range_rate_m_s,
+            SweepField::RangeAccelerationMS2 => parameters.range_acceleration_m_s2,
+            SweepField::RainRateMmHour => environment.rain_rate_mm_hour,
+            SweepField::CloudCoverPercent => environment.cloud_cover_percent,
+            SweepField::AtmosphericPressureMb => environment.atmospheric_pressure_mb,
+            SweepField::TemperatureCelsius => environment.temperature_celsius,
+            SweepField::HumidityPercent => environment.humidity_percent,
+            SweepField::IonosphericActivity => environment.ionospheric_activity,
+            SweepField::SolarActivity => environment.solar_activity,
+        }
+    }
+
+    /// Set this field in a scenario.
+    pub fn set(
+        self,
+        parameters: &mut TransmissionParameters,
+        environment: &mut EnvironmentalConditions,
+        value: f64,
+    ) {
+        let slot = match self {
+            SweepField::DistanceKm => &mut parameters.distance_km,
+            SweepField::DataSizeMb => &mut parameters.data_size_mb,
+            SweepField::RequiredDataRateMbps => &mut parameters.required_data_rate_mbps,
+            SweepField::ElevationAngleDegrees => &mut parameters.elevation_angle_degrees,
+            SweepField::TransmitPowerWatts => &mut parameters.transmit_power_watts,
+            SweepField::AntennaDiameterMeters => &mut parameters.antenna_diameter_meters,
+            SweepField::RangeRateMS => &mut parameters.range_rate_m_s,
+            SweepField::RangeAccelerationMS2 => &mut parameters.range_acceleration_m_s2,
+            SweepField::RainRateMmHour => &mut environment.rain_rate_mm_hour,
+            SweepField::CloudCoverPercent => &mut environment.cloud_cover_percent,
+            SweepField::AtmosphericPressureMb => &mut environment.atmospheric_pressure_mb,
+            SweepField::TemperatureCelsius => &mut environment.temperature_celsius,
+            SweepField::HumidityPercent => &mut environment.humidity_percent,
+            SweepField::IonosphericActivity => &mut environment.ionospheric_activity,
+            SweepField::SolarActivity => &mut environment.solar_activity,
+        };
+        *slot = value;
+    }
+}
+
+impl fmt::Display for SweepField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An output metric of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SweepMetric {
+    Success,
+    ActualDataRateMbps,
+    TotalLatencyMs,
+    PowerConsumptionWatts,
+    TransmissionEfficiency,
+    WeatherImpactFactor,
+    SignalToNoiseRatioDb,
+    PathLossDb,
+    RainAttenuationDb,
+    DopplerShiftHz,
+    DopplerRateHzS,
+    CarrierTrackingLossDb,
+    CarrierLocked,
+}
+
+impl SweepMetric {
+    /// Every metric in `TransmissionResult` declaration order.
+    pub const ALL: [SweepMetric; 13] = [
+        SweepMetric::Success,
+        SweepMetric::ActualDataRateMbps,
+        SweepMetric::TotalLatencyMs,
+        SweepMetric::PowerConsumptionWatts,
+        SweepMetric::TransmissionEfficiency,
+        SweepMetric::WeatherImpactFactor,
+        SweepMetric::SignalToNoiseRatioDb,
+        SweepMetric::PathLossDb,
+        SweepMetric::RainAttenuationDb,
+        SweepMetric::DopplerShiftHz,
+        SweepMetric::DopplerRateHzS,
+        SweepMetric::CarrierTrackingLossDb,
+        SweepMetric::CarrierLocked,
+    ];
+
+    /// Metric name, matching the `TransmissionResult` field.
+    pub fn name(self) -> &'static str {
+        match self {
+            SweepMetric::Success => "success",
+            SweepMetric::ActualDataRateMbps => "actual_data_rate_mbps",
+            SweepMetric::TotalLatencyMs => "total_latency_ms",
+            SweepMetric::PowerConsumptionWatts => "power_consumption_watts",
+            SweepMetric::TransmissionEfficiency => "transmission_efficiency",
+            SweepMetric::WeatherImpactFactor => "weather_impact_factor",
+            SweepMetric::SignalToNoiseRatioDb => "signal_to_noise_ratio_db",
+            SweepMetric::PathLossDb => "path_loss_db",
+            SweepMetric::RainAttenuationDb => "rain_attenuation_db",
+            SweepMetric::DopplerShiftHz => "doppler_shift_hz",
+            SweepMetric::DopplerRateHzS => "doppler_rate_hz_s",
+            SweepMetric::CarrierTrackingLossDb => "carrier_tracking_loss_db",
+            SweepMetric::CarrierLocked => "carrier_locked",
+        }
+    }
+
+    /// Value of this metric in a result; `success` and `carrier_locked` are
+    /// 1.0 or 0.0.
+    pub fn get(self, result: &TransmissionResult) -> f64 {
+        match self {
+            SweepMetric::Success => f64::from(u8::from(result.success)),
+            SweepMetric::ActualDataRateMbps => result.actual_data_rate_mbps,
+            SweepMetric::TotalLatencyMs => result.total_latency_ms,
+            SweepMetric::PowerConsumptionWatts => result.power_consumption_watts,
+            SweepMetric::TransmissionEfficiency => result.transmission_efficiency,
+            SweepMetric::WeatherImpactFactor => result.weather_impact_factor,
+            SweepMetric::SignalToNoiseRatioDb => result.signal_to_noise_ratio_db,
+            SweepMetric::PathLossDb => result.path_loss_db,
+            SweepMetric::RainAttenuationDb => result.rain_attenuation_db,
+            SweepMetric::DopplerShiftHz => result.doppler_shift_hz,
+            SweepMetric::DopplerRateHzS => result.doppler_rate_hz_s,
+            SweepMetric::CarrierTrackingLossDb => result.carrier_tracking_loss_db,
+            SweepMetric::CarrierLocked => f64::from(u8::from(result.carrier_locked)),
+        }
+    }
+}
+
+impl fmt::Display for SweepMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Values taken by one swept field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepAxis {
+    /// Field swept.
+    pub field: SweepField,
+    /// Values in sweep order.
+    pub values: Vec<f64>,
+}
+
+/// Values from `start` to `stop` inclusive in increments of `step`.
+///
+/// A step that is not positive and finite, or a `stop` that is not finite or
+/// not above `start`, gives just `start`. `stop` is included when it lies
+/// within a millionth of a step of the last increment, so decimal steps do
+/// not lose their end point.
+pub fn range_values(start: f64, stop: f64, step: f64) -> Vec<f64> {
+    if !(step.is_finite() && step > 0.0 && stop.is_finite() && stop > start) {
+        return vec![start];
+    }
+    let steps = ((stop - start) / step + 1e-6).floor() as usize;
+    (0..=steps).map(|i| start + i as f64 * step).collect()
+}
+
+/// Cross-product sweep over inputs and bands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterSweep {
+    /// Base transmission parameters; swept fields are overridden per run.
+    pub parameters: TransmissionParameters,
+    /// Base environment; swept fields are overridden per run.
+    pub environment: EnvironmentalConditions,
+    /// Bands simulated at every point.
+    pub bands: Vec<FrequencyBand>,
+    /// Swept fields, outermost first.
+    pub axes: Vec<SweepAxis>,
+    /// Margins a run must keep to count as a success.
+    #[serde(default)]
+    pub margins: MarginPolicy,
+    /// Results shared with other sweeps; `None` simulates every run.
+    #[serde(skip)]
+    pub cache: Option<Arc<SimulationCache>>,
+}
+
+impl ParameterSweep {
+    /// Sweep of the standard bands around a base scenario, with no axes yet.
+    pub fn new(parameters: TransmissionParameters, environment: EnvironmentalConditions) -> Self {
+        Self {
+            parameters,
+            environment,
+            bands: FrequencyBand::get_standard_bands(),
+            axes: Vec::new(),
+            margins: MarginPolicy::default(),
+            cache: None,
+        }
+    }
+
+    /// Judge success against the mission's `margins`.
+    pub fn with_margins(mut self, margins: MarginPolicy) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Reuse results of configurations already in `cache`, and add this
+    /// sweep's to it.
+    pub fn with_cache(mut self, cache: Arc<SimulationCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Replace the bands simulated at every point.
+    pub fn with_bands(mut self, bands: Vec<FrequencyBand>) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// Sweep `field` from `start` to `stop` inclusive; see `range_values`.
+    pub fn with_range(self, field: SweepField, start: f64, stop: f64, step: f64) -> Self {
+        self.with_values(field, range_values(start, stop, step))
+    }
+
+    /// Sweep `field` over explicit values, replacing any earlier axis for it.
+    pub fn with_values(mut self, field: SweepField, values: Vec<f64>) -> Self {
+        match self.axes.iter_mut().find(|axis| axis.field == field) {
+            Some(axis) => axis.values = values,
+            None => self.axes.push(SweepAxis { field, values }),
+        }
+        self
+    }
+
+    /// Input points in the cross product; the last axis varies fastest.
+    pub fn point_count(&self) -> usize {
+        self.axes.iter().map(|axis| axis.values.len()).product()
+    }
+
+    /// Runs in the sweep: every point for every band.
+    pub fn run_count(&self) -> usize {
+        self.point_count() * self.bands.len()
+    }
+
+    /// Scenario at `point`, counting from 0 in cross-product order.
+    pub fn scenario(&self, point: usize) -> (TransmissionParameters, EnvironmentalConditions) {
+        let mut parameters = self.parameters.clone();
+        let mut environment = self.environment.clone();
+        let mut rest = point;
+        for axis in self.axes.iter().rev() {
+            let len = axis.values.len();
+            axis.field
+                .set(&mut parameters, &mut environment, axis.values[rest % len]);
+            rest /= len;
+        }
+        (parameters, environment)
+    }
+
+    /// Run the sweep.
+    ///
+    /// - **ID**: FN-SWP-001
+    /// - **Requirement**: Simulate every band at every point of the cross
+    ///   product of swept values.
+    /// - **Inputs**: The sweep definition.
+    /// - **Outputs**: `run_count()` runs ordered by point, then band order.
+    /// - **Side Effects**: With the `parallel` feature, uses the global rayon
+    ///   thread pool.
+    /// - **Failure Modes**: An axis with no values, or no bands, yields no
+    ///   runs. `ValidationError` from the first point, in run order, whose
+    ///   inputs are out of range.
+    pub fn run(&self) -> Result<SweepTable, ValidationError> {
+        self.run_controlled(&mut RunControl::new())
+            .map_err(RunError::into_invalid)
+    }
+
+    /// Run the sweep under `control`.
+    ///
+    /// - **ID**: FN-SWP-002
+    /// - **Requirement**: Long sweeps report their progress and can be
+    ///   aborted cleanly.
+    /// - **Inputs**: The sweep definition; progress observer and cancel
+    ///   token.
+    /// - **Outputs**: The same runs as `run`.
+    /// - **Side Effects**: As `run`; reports progress to `control` at the
+    ///   start and after every `SWEEP_BATCH_RUNS` runs.
+    /// - **Failure Modes**: As `run`, as `RunError::Invalid`.
+    ///   `RunError::Cancelled` once the cancel token is cancelled; no run
+    ///   starts after that.
+    pub fn run_controlled(&self, control: &mut RunControl<'_>) -> Result<SweepTable, RunError> {
+        let bands = self.bands.len();
+        let total = self.run_count();
+        let mut tracker = control.start(total);
+        let mut runs = Vec::with_capacity(total);
+        let mut start = 0;
+        while start < total {
+            let end = total.min(start + SWEEP_BATCH_RUNS);
+            let cancel = tracker.cancel_token();
+            let simulate = |index: usize| {
+                if cancel.is_cancelled() {
+                    return Ok(None);
+                }
+                let point = index / bands;
+                let band = &self.bands[index % bands];
+                let (parameters, environment) = self.scenario(point);
+                let result = cache::simulate(
+                    self.cache.as_deref(),
+                    band,
+                    &parameters,
+                    &environment,
+                    &self.margins,
+                )?;
+                Ok(Some(SweepRun {
+                    point,
+                    band: band.name,
+                    parameters,
+                    environment,
+                    result,
+                }))
+            };
+
+            #[cfg(feature = "parallel")]
+            let batch: Result<Vec<Option<SweepRun>>, ValidationError> = {
+                use rayon::prelude::*;
+                (start..end).into_par_iter().map(simulate).collect()
+            };
+            #[cfg(not(feature = "parallel"))]
+            let batch: Result<Vec<Option<SweepRun>>, ValidationError> =
+                (start..end).map(simulate).collect();
+
+            match batch?.into_iter().collect::<Option<Vec<_>>>() {
+                Some(batch) => runs.extend(batch),
+                None => return Err(tracker.cancelled()),
+            }
+            tracker.advance(end - start)?;
+            start = end;
+        }
+
+        Ok(SweepTable {
+            fields: self.axes.iter().map(|axis| axis.field).collect(),
+            runs,
+        })
+    }
+}
+
+/// One simulated band at one sweep point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepRun {
+    /// Point index in cross-product order.
+    pub point: usize,
+    /// Band simulated.
+    pub band: BandType,
+    /// Transmission parameters at the point.
+    pub parameters: TransmissionParameters,
+    /// Environment at the point.
+    pub environment: EnvironmentalConditions,
+    /// Simulation result.
+    pub result: TransmissionResult,
+}
+
+/// One row of the long-format table.
+#[derive(Debug, Clone, Copy)]
+pub struct SweepRow<'a> {
+    /// The run the row belongs to.
+    pub run: &'a SweepRun,
+    /// Metric reported.
+    pub metric: SweepMetric,
+    /// Metric value.
+    pub value: f64,
+}
+
+/// Results of a sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepTable {
+    /// Swept fields, one input column each in the long format.
+    pub fields: Vec<SweepField>,
+    /// Runs ordered by point, then band.
+    pub runs: Vec<SweepRun>,
+}
+
+impl SweepTable {
+    /// Long-format rows: every metric of every run.
+    pub fn rows(&self) -> impl Iterator<Item = SweepRow<'_>> {
+        self.runs.iter().flat_map(|run| {
+            SweepMetric::ALL.into_iter().map(move |metric| SweepRow {
+                run,
+                metric,
+                value: metric.get(&run.result),
+            })
+        })
+    }
+
+    /// CSV header of `to_csv`: point, band, each swept field, metric, value.
+    pub fn csv_header(&self) -> String {
+        let mut columns = vec!["point", "band"];
+        columns.extend(self.fields.iter().map(|field| field.name()));
+        columns.extend(["metric", "value"]);
+        columns.join(",")
+    }
+
+    /// Export the long-format table as CSV.
+    pub fn to_csv(&self) -> String {
+        let mut out = self.csv_header();
+        out.push('\n');
+        for row in self.rows() {
+            let mut columns = vec![row.run.point.to_string(), row.run.band.to_string()];
+            columns.extend(self.fields.iter().map(|field| {
+                field
+                    .get(&row.run.parameters, &row.run.environment)
+                    .to_string()
+            }));
+            columns.push(row.metric.to_string());
+            columns.push(row.value.to_string());
+            out.push_str(&columns.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Every run as a `SimulationRecord` stamped with `timestamp_unix_ms`.
+    pub fn to_records(&self, timestamp_unix_ms: u64) -> Vec<SimulationRecord> {
+        self.runs
+            .iter()
+            .map(|run| {
+                SimulationRecord::new(
+                    run.band,
+                    run.parameters.clone(),
+                    run.environment.clone(),
+                    run.result.clone(),
+                    timestamp_unix_ms,
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,469 @@
+//! Constellation Time Transfer Module
+//!
+//! Keeps the clocks of a constellation on a common timescale by two-way time
+//! transfer over inter-satellite links (ISLs). Each exchange carries four
+//! timestamps: the request leaves node A at `t1` by A's clock, arrives at B
+//! at `t2` by B's clock, the reply leaves at `t3` and arrives back at `t4`.
+//! Half the difference of the two legs gives B's offset from A with the link
+//! delay cancelled; half their sum gives the one-way delay. Propagation is
+//! computed from the node positions at emission and reception, so the motion
+//! of the nodes during the exchange makes the two legs differ slightly and
+//! biases the measured offset by half the difference. With
+//! `compensate_motion` set, each node removes that bias using the leg
+//! delays predicted from both orbits, as a real ISL terminal would from the
+//! onboard ephemeris.
+//!
+//! One node holds the reference timescale. At every exchange epoch the nodes
+//! with line of sight to it synchronise to it, their neighbours to them, and
+//! so on outward, so a node's timescale error grows with its hop count. A node
+//! with no path to the reference keeps free-running on its own oscillator.
+//! Each node steers its clock in offset and, from successive exchanges, in
+//! rate, so drift between exchanges shrinks as the loop settles.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-007: Multi-Band Communication (ISL time transfer)
+//! - REQ-FN-008: Frequency Band Simulation (propagation delay compensation)
+
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::occultation::{
+    line_of_sight_clear, CircularOrbit, Position, DEFAULT_ATMOSPHERE_MARGIN_KM,
+};
+use crate::stats::RunningStats;
+
+/// Speed of light in vacuum, km/s.
+pub const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+
+/// Free-running onboard oscillator.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClockModel {
+    /// Clock offset from the true time at t = 0, seconds.
+    pub initial_offset_s: f64,
+    /// Fractional frequency error, parts per million.
+    pub drift_ppm: f64,
+    /// Peak error of each timestamp taken on the link, seconds.
+    pub timestamp_jitter_s: f64,
+}
+
+impl ClockModel {
+    /// Offset from the true time at `t_s` with no steering, seconds.
+    pub fn free_running_offset_s(&self, t_s: f64) -> f64 {
+        self.initial_offset_s + self.drift_ppm * 1e-6 * t_s
+    }
+}
+
+/// Satellite taking part in time transfer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstellationNode {
+    /// Name used in reports.
+    pub name: String,
+    /// Orbit placing the node.
+    pub orbit: CircularOrbit,
+    /// Onboard oscillator.
+    pub clock: ClockModel,
+}
+
+/// Time transfer protocol settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeTransferConfig {
+    /// Time between exchange epochs, seconds.
+    pub exchange_interval_s: f64,
+    /// Time the responder holds a request before replying, seconds.
+    pub turnaround_s: f64,
+    /// Fraction of each measured offset removed at once, (0, 1].
+    pub offset_gain: f64,
+    /// Fraction of each measured rate error removed at once, [0, 1].
+    pub rate_gain: f64,
+    /// Grazing altitude below which an ISL is blocked, km.
+    pub atmosphere_margin_km: f64,
+    /// Remove the offset bias from node motion during each exchange.
+    pub compensate_motion: bool,
+    /// Seed of the timestamp jitter.
+    pub seed: u64,
+}
+
+impl Default for TimeTransferConfig {
+    fn default() -> Self {
+        Self {
+            exchange_interval_s: 60.0,
+            turnaround_s: 0.010,
+            offset_gain: 1.0,
+            rate_gain: 0.5,
+            atmosphere_margin_km: DEFAULT_ATMOSPHERE_MARGIN_KM,
+            compensate_motion: true,
+            seed: 0,
+        }
+    }
+}
+
+/// Timestamps of one two-way exchange, each read on its node's clock.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TwoWayExchange {
+    /// Request sent, initiator clock, seconds.
+    pub t1: f64,
+    /// Request received, responder clock, seconds.
+    pub t2: f64,
+    /// Reply sent, responder clock, seconds.
+    pub t3: f64,
+    /// Reply received, initiator clock, seconds.
+    pub t4: f64,
+}
+
+impl TwoWayExchange {
+    /// Responder clock minus initiator clock, seconds.
+    ///
+    /// - **ID**: FN-TTR-001
+    /// - **Requirement**: Measure clock offset across an ISL with the link
+    ///   delay cancelled (REQ-FN-007).
+    /// - **Outputs**: Exact when the two legs take the same time; a leg
+    ///   asymmetry `d` biases the result by `d / 2`.
+    pub fn offset_s(&self) -> f64 {
+        ((self.t2 - self.t1) - (self.t4 - self.t3)) / 2.0
+    }
+
+    /// Mean one-way link delay, seconds.
+    pub fn delay_s(&self) -> f64 {
+        ((self.t2 - self.t1) + (self.t4 - self.t3)) / 2.0
+    }
+}
+
+/// Offset of one node at one exchange epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClockOffsetSample {
+    /// Simulation time, seconds.
+    pub time_s: f64,
+    /// Index of the node.
+    pub node: usize,
+    /// Offset from the reference timescale as the epoch began, before its
+    /// exchanges: the error built up since the node was last steered, seconds.
+    pub offset_s: f64,
+    /// ISL hops to the reference node; `None` while free-running.
+    pub hops: Option<usize>,
+    /// One-way delay measured to the node's parent, seconds.
+    pub measured_delay_s: Option<f64>,
+}
+
+/// Offset statistics of one node over a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeOffsetSummary {
+    /// Node name.
+    pub name: String,
+    /// Offset from the reference timescale at the last epoch, seconds.
+    pub final_offset_s: f64,
+    /// Largest offset magnitude after the first epoch, seconds.
+    pub max_abs_offset_s: f64,
+    /// RMS offset after the first epoch, seconds.
+    pub rms_offset_s: f64,
+    /// Epochs with no ISL path to the reference.
+    pub free_running_epochs: usize,
+}
+
+/// Per-epoch clock offsets of every node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeTransferReport {
+    /// Node names, by index.
+    pub nodes: Vec<String>,
+    /// Index of the reference node.
+    pub reference: usize,
+    /// One sample per node per epoch, in time order.
+    pub samples: Vec<ClockOffsetSample>,
+}
+
+impl TimeTransferReport {
+    /// Offset statistics per node, by index.
+    ///
+    /// The first epoch is left out of the maximum and RMS: it shows the
+    /// clocks as launched, before any exchange could correct them.
+    pub fn summary(&self) -> Vec<NodeOffsetSummary> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(node, name)| {
+                let samples: Vec<_> = self.samples.iter().filter(|s| s.node == node).collect();
+                let first_time = samples.first().map(|s| s.time_s);
+                let mut stats = RunningStats::new();
+                let mut max_abs_offset_s: f64 = 0.0;
+                for sample in samples.iter().filter(|s| Some(s.time_s) != first_time) {
+                    stats.push(sample.offset_s * sample.offset_s);
+                    max_abs_offset_s = max_abs_offset_s.max(sample.offset_s.abs());
+                }
+                NodeOffsetSummary {
+                    name: name.clone(),
+                    final_offset_s: samples.last().map_or(0.0, |s| s.offset_s),
+                    max_abs_offset_s,
+                    rms_offset_s: stats.mean().map_or(0.0, f64::sqrt),
+                    free_running_epochs: samples.iter().filter(|s| s.hops.is_none()).count(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Steering applied on top of a node's free-running oscillator.
+#[derive(Debug, Clone, Copy, Default)]
+struct Steering {
+    offset_s: f64,
+    rate: f64,
+    epoch_s: f64,
+    /// Time and offset left right after the previous correction.
+    last_residual: Option<(f64, f64)>,
+}
+
+impl Steering {
+    fn at(&self, t_s: f64) -> f64 {
+        self.offset_s + self.rate * (t_s - self.epoch_s)
+    }
+}
+
+/// Two-way time transfer across a constellation.
+#[derive(Debug, Clone)]
+pub struct TimeTransferSimulation {
+    nodes: Vec<ConstellationNode>,
+    links: Vec<(usize, usize)>,
+    reference: usize,
+    config: TimeTransferConfig,
+}
+
+impl TimeTransferSimulation {
+    /// Create a simulation over bidirectional ISLs given as node index pairs,
+    /// with `reference` holding the constellation timescale.
+    pub fn new(
+        nodes: Vec<ConstellationNode>,
+        links: Vec<(usize, usize)>,
+        reference: usize,
+        config: TimeTransferConfig,
+    ) -> Self {
+        Self {
+            nodes,
+            links,
+            reference,
+            config,
+        }
+    }
+
+    /// Run exchange epochs from `start_s` to `end_s` inclusive.
+    ///
+    /// - **ID**: FN-TTR-002
+    /// - **Requirement**: Maintain a common constellation timescale over ISLs
+    ///   and report each satellite's clock offset (REQ-FN-007).
+    /// - **Inputs**: Simulation start and end, seconds.
+    /// - **Outputs**: Report with one sample per node per epoch. The
+    ///   reference node's offset is always zero.
+    /// - **Side Effects**: None; repeated runs give the same report.
+    pub fn run(&self, start_s: f64, end_s: f64) -> TimeTransferReport {
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut steering = vec![Steering::default(); self.nodes.len()];
+        let mut samples = Vec::new();
+
+        if self.config.exchange_interval_s > 0.0 {
+            let mut t_s = start_s;
+            while t_s <= end_s {
+                self.epoch(t_s, &mut steering, &mut rng, &mut samples);
+                t_s += self.config.exchange_interval_s;
+            }
+        }
+
+        TimeTransferReport {
+            nodes: self.nodes.iter().map(|n| n.name.clone()).collect(),
+            reference: self.reference,
+            samples,
+        }
+    }
+
+    /// Synchronise outward from the reference over the ISLs clear at `t_s`.
+    fn epoch(
+        &self,
+        t_s: f64,
+        steering: &mut [Steering],
+        rng: &mut StdRng,
+        samples: &mut Vec<ClockOffsetSample>,
+    ) {
+        let reference_error = self.clock_error(self.reference, t_s, steering);
+        let offsets: Vec<_> = (0..self.nodes.len())
+            .map(|node| self.clock_error(node, t_s, steering) - reference_error)
+            .collect();
+        let mut hops = vec![None; self.nodes.len()];
+        let mut delays = vec![None; self.nodes.len()];
+        hops[self.reference] = Some(0);
+
+        let mut queue = VecDeque::from([self.reference]);
+        while let Some(parent) = queue.pop_front() {
+            for child in self.neighbours(parent) {
+                if hops[child].is_some() || !self.link_clear(parent, child, t_s) {
+                    continue;
+                }
+                let exchange = self.exchange(parent, child, t_s, steering, rng);
+                let mut offset_s = exchange.offset_s();
+                if self.config.compensate_motion {
+                    offset_s -= self.leg_asymmetry_s(parent, child, t_s);
+                }
+                self.steer(&mut steering[child], offset_s, t_s);
+                hops[child] = hops[parent].map(|h| h + 1);
+                delays[child] = Some(exchange.delay_s());
+                queue.push_back(child);
+            }
+        }
+
+        for (node, offset_s) in offsets.into_iter().enumerate() {
+            samples.push(ClockOffsetSample {
+                time_s: t_s,
+                node,
+                offset_s,
+                hops: hops[node],
+                measured_delay_s: delays[node],
+            });
+        }
+    }
+
+    fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.links.iter().filter_map(move |&(a, b)| {
+            if a == node {
+                Some(b)
+            } else if b == node {
+                Some(a)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn link_clear(&self, a: usize, b: usize, t_s: f64) -> bool {
+        line_of_sight_clear(
+            self.position(a, t_s),
+            self.position(b, t_s),
+            self.config.atmosphere_margin_km,
+        )
+    }
+
+    fn position(&self, node: usize, t_s: f64) -> Position {
+        self.nodes[node].orbit.position(t_s)
+    }
+
+    /// Clock reading minus true time, seconds.
+    fn clock_error(&self, node: usize, t_s: f64, steering: &[Steering]) -> f64 {
+        self.nodes[node].clock.free_running_offset_s(t_s) + steering[node].at(t_s)
+    }
+
+    /// Time a signal sent from `from` at `t_s` takes to reach `to`, seconds.
+    fn light_time_s(&self, from: usize, to: usize, t_s: f64) -> f64 {
+        let sent = self.position(from, t_s);
+        let mut delay_s = 0.0;
+        // Two iterations converge well below a nanosecond at orbital speeds
+        for _ in 0..2 {
+            let received = self.position(to, t_s + delay_s);
+            let d = [
+                received[0] - sent[0],
+                received[1] - sent[1],
+                received[2] - sent[2],
+            ];
+            delay_s = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() / SPEED_OF_LIGHT_KM_S;
+        }
+        delay_s
+    }
+
+    /// Half the outbound leg delay less the return leg, predicted from the
+    /// orbits: the bias motion adds to an exchange started at `t_s`.
+    fn leg_asymmetry_s(&self, initiator: usize, responder: usize, t_s: f64) -> f64 {
+        let outbound_s = self.light_time_s(initiator, responder, t_s);
+        let replied_s = t_s + outbound_s + self.config.turnaround_s;
+        (outbound_s - self.light_time_s(responder, initiator, replied_s)) / 2.0
+    }
+
+    fn exchange(
+        &self,
+        initiator: usize,
+        responder: usize,
+        t_s: f64,
+        steering: &[Steering],
+        rng: &mut StdRng,
+    ) -> TwoWayExchange {
+        let mut read = |node: usize, at_s: f64| {
+            let jitter_s = self.nodes[node].clock.timestamp_jitter_s;
+            let noise_s = if jitter_s > 0.0 {
+                rng.gen_range(-jitter_s..=jitter_s)
+            } else {
+                0.0
+            };
+            at_s + self.clock_error(node, at_s, steering) + noise_s
+        };
+
+        let received_s = t_s + self.light_time_s(initiator, responder, t_s);
+        let replied_s = received_s + self.config.turnaround_s;
+        let returned_s = replied_s + self.light_time_s(responder, initiator, replied_s);
+        TwoWayExchange {
+            t1: read(initiator, t_s),
+            t2: read(responder, received_s),
+            t3: read(responder, replied_s),
+            t4: read(initiator, returned_s),
+        }
+    }
+
+    /// Remove the measured offset, and the rate seen since the last exchange.
+    fn steer(&self, steering: &mut Steering, measured_offset_s: f64, t_s: f64) {
+        let current = steering.at(t_s);
+        if let Some((last_s, residual_s)) = steering.last_residual {
+            if t_s > last_s {
+                let rate = (measured_offset_s - residual_s) / (t_s - last_s);
+                steering.rate -= self.config.rate_gain * rate;
+            }
+        }
+        let correction_s = self.config.offset_gain * measured_offset_s;
+        steering.offset_s = current - correction_s;
+        steering.epoch_s = t_s;
+        steering.last_residual = Some((t_s, measured_offset_s - correction_s));
+    }
+}
+
+/// Run time transfer over a representative constellation for three hours.
+///
+/// Six satellites share a 1200 km, 53° plane, 60° apart, each linked to its
+/// neighbours in the ring; a seventh in a plane 90° away is linked to the
+/// first and loses its path to the reference while the Earth blocks that
+/// link. Clocks start up to 2 ms off with drifts up to 0.5 ppm and 20 ns
+/// timestamp jitter; satellite 1 holds the reference timescale.
+pub fn run_time_transfer_demo() -> TimeTransferReport {
+    let orbit = |raan_deg: f64, phase_deg: f64| CircularOrbit {
+        altitude_km: 1200.0,
+        inclination_deg: 53.0,
+        raan_deg,
+        phase_deg,
+    };
+    let clocks = [
+        (0.0, 0.0),
+        (1.2e-3, 0.35),
+        (-0.8e-3, -0.20),
+        (2.0e-3, 0.50),
+        (-1.5e-3, 0.10),
+        (0.4e-3, -0.45),
+        (-2.0e-3, 0.30),
+    ];
+    let nodes = clocks
+        .iter()
+        .enumerate()
+        .map(|(i, &(initial_offset_s, drift_ppm))| ConstellationNode {
+            name: format!("SAT-{}", i + 1),
+            orbit: if i < 6 {
+                orbit(0.0, 60.0 * i as f64)
+            } else {
+                orbit(90.0, 0.0)
+            },
+            clock: ClockModel {
+                initial_offset_s,
+                drift_ppm,
+                timestamp_jitter_s: 20e-9,
+            },
+        })
+        .collect();
+    let mut links: Vec<_> = (0..6).map(|i| (i, (i + 1) % 6)).collect();
+    links.push((0, 6));
+
+    let config = TimeTransferConfig {
+        seed: 42,
+        ..TimeTransferConfig::default()
+    };
+    TimeTransferSimulation::new(nodes, links, 0, config).run(0.0, 3.0 * 3600.0)
+}
@@ -0,0 +1,365 @@
+//! Ground Antenna Tracking Dynamics Module
+//!
+//! Models an azimuth-over-elevation ground antenna following a LEO pass:
+//!
+//! - Pass geometry for a circular orbit over a spherical Earth
+//! - Per-axis servo with slew-rate and acceleration limits
+//! - Pointing error against the commanded direction and tracking loss when it
+//!   exceeds the allowed error (normally half the 3 dB beamwidth)
+//!
+//! Near zenith the azimuth rate needed to follow the satellite grows as
+//! 1/cos(elevation), so high-elevation passes run into the azimuth limits —
+//! the "keyhole" — and lose track for part of the pass. The resulting loss
+//! windows reduce the achievable contact time used for pass scheduling.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (contact geometry)
+//! - REQ-PF-002: Data Transfer Rates (achievable contact time per pass)
+
+use serde::{Deserialize, Serialize};
+
+/// Mean Earth radius, km.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Earth gravitational parameter, km³/s².
+const EARTH_MU_KM3_S2: f64 = 398_600.441_8;
+
+/// Servo integration sub-steps per pass sample.
+const SERVO_SUBSTEPS: u32 = 10;
+
+/// Position loop time constant on top of rate feed-forward, seconds.
+const SERVO_TIME_CONSTANT_S: f64 = 0.5;
+
+/// Antenna pointing direction.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pointing {
+    /// Azimuth clockwise from north, degrees (0–360).
+    pub azimuth_deg: f64,
+    /// Elevation above the horizon, degrees.
+    pub elevation_deg: f64,
+}
+
+impl Pointing {
+    /// Angular separation between two pointing directions, degrees.
+    pub fn separation_deg(&self, other: &Pointing) -> f64 {
+        let (az1, el1) = (
+            self.azimuth_deg.to_radians(),
+            self.elevation_deg.to_radians(),
+        );
+        let (az2, el2) = (
+            other.azimuth_deg.to_radians(),
+            other.elevation_deg.to_radians(),
+        );
+        let cos_sep = el1.sin() * el2.sin() + el1.cos() * el2.cos() * (az1 - az2).cos();
+        cos_sep.clamp(-1.0, 1.0).acos().to_degrees()
+    }
+}
+
+/// One sample of a pass as seen from the ground station.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PassSample {
+    /// Time since acquisition of signal, seconds.
+    pub time_s: f64,
+    /// Direction to the satellite.
+    pub pointing: Pointing,
+    /// Slant range to the satellite, km.
+    pub range_km: f64,
+}
+
+/// Generate a LEO pass above the elevation mask.
+///
+/// - **ID**: FN-TRK-001
+/// - **Requirement**: Provide pass geometry for tracking and contact time
+///   analysis (REQ-FN-008).
+/// - **Inputs**:
+///   - `altitude_km`: Circular orbit altitude, km.
+///   - `max_elevation_deg`: Culmination elevation of the pass, degrees (≤ 90).
+///   - `elevation_mask_deg`: Minimum usable elevation, degrees.
+///   - `step_s`: Sample spacing, seconds.
+/// - **Outputs**: Samples from AOS to LOS; empty if the pass never rises above
+///   the mask.
+/// - **Constraints**: Spherical Earth; Earth rotation ignored. The ground
+///   track runs south to north, passing east of the station.
+pub fn generate_pass(
+    altitude_km: f64,
+    max_elevation_deg: f64,
+    elevation_mask_deg: f64,
+    step_s: f64,
+) -> Vec<PassSample> {
+    let orbit_radius = EARTH_RADIUS_KM + altitude_km;
+    let mean_motion = (EARTH_MU_KM3_S2 / orbit_radius.powi(3)).sqrt();
+
+    // Earth central angle between the station and the sub-satellite point at culmination
+    let el_max = max_elevation_deg.clamp(0.0, 90.0).to_radians();
+    let closest_angle = (EARTH_RADIUS_KM * el_max.cos() / orbit_radius).acos() - el_max;
+
+    let look = |theta: f64| {
+        // Station at (R, 0, 0) with up = x, east = y, north = z; orbit plane
+        // tilted east of the station by the closest-approach angle
+        let sat = [
+            orbit_radius * closest_angle.cos() * theta.cos(),
+            orbit_radius * theta.sin(),
+            orbit_radius * closest_angle.sin() * theta.cos(),
+        ];
+        let d = [sat[0] - EARTH_RADIUS_KM, sat[1], sat[2]];
+        let range = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        let elevation = (d[0] / range).asin().to_degrees();
+        let azimuth = d[2].atan2(d[1]).to_degrees();
+        // atan2(north, east) measures from east; convert to compass azimuth
+        let azimuth = (90.0 - azimuth).rem_euclid(360.0);
+        (
+            Pointing {
+                azimuth_deg: azimuth,
+                elevation_deg: elevation,
+            },
+            range,
+        )
+    };
+
+    // Half-width of the visible arc, found by stepping out from culmination
+    let step_angle = mean_motion * step_s;
+    if step_angle <= 0.0 || look(0.0).0.elevation_deg < elevation_mask_deg {
+        return Vec::new();
+    }
+    let mut half_steps = 0u32;
+    while look(step_angle * f64::from(half_steps + 1)).0.elevation_deg >= elevation_mask_deg {
+        half_steps += 1;
+    }
+
+    (0..=2 * half_steps)
+        .map(|i| {
+            let theta = step_angle * (f64::from(i) - f64::from(half_steps));
+            let (pointing, range_km) = look(theta);
+            PassSample {
+                time_s: f64::from(i) * step_s,
+                pointing,
+                range_km,
+            }
+        })
+        .collect()
+}
+
+/// Dynamic limits of an azimuth-over-elevation antenna mount.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServoLimits {
+    /// Maximum azimuth slew rate, degrees/second.
+    pub max_azimuth_rate_deg_s: f64,
+    /// Maximum azimuth acceleration, degrees/second².
+    pub max_azimuth_accel_deg_s2: f64,
+    /// Maximum elevation slew rate, degrees/second.
+    pub max_elevation_rate_deg_s: f64,
+    /// Maximum elevation acceleration, degrees/second².
+    pub max_elevation_accel_deg_s2: f64,
+    /// Mechanical elevation limit, degrees.
+    pub max_elevation_deg: f64,
+    /// Pointing error beyond which the link is lost, degrees.
+    pub max_pointing_error_deg: f64,
+}
+
+impl Default for ServoLimits {
+    /// A typical 3.7 m S/X-band LEO tracking antenna.
+    fn default() -> Self {
+        Self {
+            max_azimuth_rate_deg_s: 5.0,
+            max_azimuth_accel_deg_s2: 2.0,
+            max_elevation_rate_deg_s: 3.0,
+            max_elevation_accel_deg_s2: 2.0,
+            max_elevation_deg: 90.0,
+            max_pointing_error_deg: 0.6,
+        }
+    }
+}
+
+/// Tracking state at one pass sample.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TrackingSample {
+    /// Time since AOS, seconds.
+    pub time_s: f64,
+    /// Direction to the satellite.
+    pub commanded: Pointing,
+    /// Direction the antenna actually points.
+    pub actual: Pointing,
+    /// Angle between commanded and actual, degrees.
+    pub pointing_error_deg: f64,
+    /// Whether the pointing error is within the allowed limit.
+    pub in_track: bool,
+}
+
+/// Interval during which the antenna could not hold track.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrackingLossWindow {
+    /// Time track was lost, seconds since AOS.
+    pub start_s: f64,
+    /// Time track was regained (or LOS), seconds since AOS.
+    pub end_s: f64,
+}
+
+impl TrackingLossWindow {
+    /// Duration of the window, seconds.
+    pub fn duration_s(&self) -> f64 {
+        self.end_s - self.start_s
+    }
+}
+
+/// Result of tracking a pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackingReport {
+    /// Tracking state at every pass sample.
+    pub samples: Vec<TrackingSample>,
+    /// Intervals where track was lost.
+    pub loss_windows: Vec<TrackingLossWindow>,
+    /// Total pass duration above the elevation mask, seconds.
+    pub pass_duration_s: f64,
+    /// Pass duration minus tracking loss, seconds.
+    pub achievable_contact_s: f64,
+    /// Largest pointing error over the pass, degrees.
+    pub max_pointing_error_deg: f64,
+}
+
+/// Wrap an angle difference into -180..180 degrees.
+fn wrap_deg(angle: f64) -> f64 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Single-axis servo state.
+#[derive(Debug, Clone, Copy)]
+struct Axis {
+    position: f64,
+    rate: f64,
+}
+
+impl Axis {
+    /// Advance one step toward a target moving at `target_rate`, `error`
+    /// (target minus position) away, under rate and acceleration limits.
+    fn step(&mut self, error: f64, target_rate: f64, max_rate: f64, max_accel: f64, dt: f64) {
+        let desired = (target_rate + error / SERVO_TIME_CONSTANT_S).clamp(-max_rate, max_rate);
+        let max_delta = max_accel * dt;
+        self.rate += (desired - self.rate).clamp(-max_delta, max_delta);
+        self.position += self.rate * dt;
+    }
+}
+
+impl ServoLimits {
+    /// Track a pass and report pointing error and loss windows.
+    ///
+    /// - **ID**: FN-TRK-002
+    /// - **Requirement**: Show tracking loss windows on high-elevation passes
+    ///   and the resulting achievable contact time (REQ-PF-002).
+    /// - **Inputs**: `pass` — samples from `generate_pass` or equivalent, in
+    ///   time order with uniform spacing.
+    /// - **Outputs**: `TrackingReport`; the antenna starts pre-positioned on
+    ///   the AOS direction.
+    /// - **Side Effects**: None.
+    /// - **Constraints**: O(samples × SERVO_SUBSTEPS).
+    pub fn track_pass(&self, pass: &[PassSample]) -> TrackingReport {
+        let mut samples = Vec::with_capacity(pass.len());
+        let mut loss_windows = Vec::new();
+        let mut loss_start: Option<f64> = None;
+        let mut max_error: f64 = 0.0;
+
+        let Some(first) = pass.first() else {
+            return TrackingReport {
+                samples,
+                loss_windows,
+                pass_duration_s: 0.0,
+                achievable_contact_s: 0.0,
+                max_pointing_error_deg: 0.0,
+            };
+        };
+
+        let mut azimuth = Axis {
+            position: first.pointing.azimuth_deg,
+            rate: 0.0,
+        };
+        let mut elevation = Axis {
+            position: first.pointing.elevation_deg.min(self.max_elevation_deg),
+            rate: 0.0,
+        };
+        let mut previous = *first;
+
+        for sample in pass {
+            let sample_dt = sample.time_s - previous.time_s;
+            let dt = sample_dt / f64::from(SERVO_SUBSTEPS);
+
+            if dt > 0.0 {
+                // Commanded motion over the sample, shortest way round in azimuth
+                let az_delta =
+                    wrap_deg(sample.pointing.azimuth_deg - previous.pointing.azimuth_deg);
+                let el_from = previous.pointing.elevation_deg.min(self.max_elevation_deg);
+                let el_delta = sample.pointing.elevation_deg.min(self.max_elevation_deg) - el_from;
+
+                for k in 1..=SERVO_SUBSTEPS {
+                    let fraction = f64::from(k) / f64::from(SERVO_SUBSTEPS);
+                    let az_target = previous.pointing.azimuth_deg + az_delta * fraction;
+                    let el_target = el_from + el_delta * fraction;
+
+                    azimuth.step(
+                        wrap_deg(az_target - azimuth.position),
+                        az_delta / sample_dt,
+                        self.max_azimuth_rate_deg_s,
+                        self.max_azimuth_accel_deg_s2,
+                        dt,
+                    );
+                    elevation.step(
+                        el_target - elevation.position,
+                        el_delta / sample_dt,
+                        self.max_elevation_rate_deg_s,
+                        self.max_elevation_accel_deg_s2,
+                        dt,
+                    );
+                }
+            }
+            previous = *sample;
+
+            let actual = Pointing {
+                azimuth_deg: azimuth.position.rem_euclid(360.0),
+                elevation_deg: elevation.position,
+            };
+            let error = sample.pointing.separation_deg(&actual);
+            let in_track = error <= self.max_pointing_error_deg;
+            max_error = max_error.max(error);
+
+            match (in_track, loss_start) {
+                (false, None) => loss_start = Some(sample.time_s),
+                (true, Some(start_s)) => {
+                    loss_windows.push(TrackingLossWindow {
+                        start_s,
+                        end_s: sample.time_s,
+                    });
+                    loss_start = None;
+                }
+                _ => {}
+            }
+
+            samples.push(TrackingSample {
+                time_s: sample.time_s,
+                commanded: sample.pointing,
+                actual,
+                pointing_error_deg: error,
+                in_track,
+            });
+        }
+
+        let last_time = pass.last().map_or(first.time_s, |s| s.time_s);
+        if let Some(start_s) = loss_start {
+            loss_windows.push(TrackingLossWindow {
+                start_s,
+                end_s: last_time,
+            });
+        }
+
+        let pass_duration_s = last_time - first.time_s;
+        let lost: f64 = loss_windows
+            .iter()
+            .map(TrackingLossWindow::duration_s)
+            .sum();
+
+        TrackingReport {
+            samples,
+            loss_windows,
+            pass_duration_s,
+            achievable_contact_s: (pass_duration_s - lost).max(0.0),
+            max_pointing_error_deg: max_error,
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Mission Traffic Generation Module
+//!
+//! Produces representative onboard data arrival patterns for capacity
+//! planning, in place of a single fixed file size:
+//!
+//! - **Periodic**      — housekeeping telemetry at a fixed cadence
+//! - **Campaign**      — bursty imaging campaigns: clusters of large products
+//! - **Event-driven**  — alerts arriving as a Poisson process
+//!
+//! A `MissionProfile` combines named sources; generating it yields a
+//! time-ordered list of `DataArrival`s to feed the recorder and downlink
+//! scheduler models.
+//!
+//! # Requirements Traceability
+//! - REQ-PF-002: Data Transfer Rates (representative offered load)
+//! - REQ-FN-001: Priority Classification (per-source data priority)
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Downlink priority class of generated data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DataClass {
+    /// Bulk science/imaging products.
+    Science,
+    /// Routine housekeeping telemetry.
+    Housekeeping,
+    /// Alerts and anomaly reports.
+    Alert,
+}
+
+/// Arrival pattern of a traffic source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TrafficPattern {
+    /// Fixed-size products at a fixed cadence.
+    Periodic {
+        /// Interval between products, seconds.
+        period_s: f64,
+        /// Product size, MB.
+        size_mb: f64,
+        /// Uniform timing jitter applied to each product, ± seconds.
+        jitter_s: f64,
+    },
+    /// Imaging campaigns: bursts of products separated by idle gaps.
+    Campaign {
+        /// Mean time between campaign starts, seconds (exponentially distributed).
+        mean_interval_s: f64,
+        /// Products per campaign.
+        products_per_campaign: u32,
+        /// Spacing between products within a campaign, seconds.
+        product_spacing_s: f64,
+        /// Smallest product size, MB.
+        min_size_mb: f64,
+        /// Largest product size, MB.
+        max_size_mb: f64,
+    },
+    /// Independent events arriving as a Poisson process.
+    EventDriven {
+        /// Mean arrivals per hour.
+        rate_per_hour: f64,
+        /// Product size, MB.
+        size_mb: f64,
+    },
+}
+
+/// A named traffic source within a mission profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficSource {
+    /// Source name used in reports.
+    pub name: String,
+    /// Priority class of the data produced.
+    pub class: DataClass,
+    /// Arrival pattern.
+    pub pattern: TrafficPattern,
+}
+
+/// One data product arriving at the onboard recorder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataArrival {
+    /// Arrival time, seconds from the start of the run.
+    pub time_s: f64,
+    /// Index of the producing source in the profile.
+    pub source: usize,
+    /// Priority class.
+    pub class: DataClass,
+    /// Product size, MB.
+    pub size_mb: f64,
+}
+
+/// Set of traffic sources describing a mission's data production.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionProfile {
+    /// Profile name used in reports.
+    pub name: String,
+    /// Traffic sources.
+    pub sources: Vec<TrafficSource>,
+}
+
+/// Sample an exponentially distributed interval with the given mean.
+fn exponential<R: Rng + ?Sized>(rng: &mut R, mean: f64) -> f64 {
+    // 1 - U lies in (0, 1], keeping ln finite
+    -mean * (1.0 - rng.gen::<f64>()).ln()
+}
+
+impl TrafficSource {
+    /// Long-run average data rate of this source, MB/s.
+    pub fn mean_rate_mb_s(&self) -> f64 {
+        match &self.pattern {
+            TrafficPattern::Periodic {
+                period_s, size_mb, ..
+            } => {
+                if *period_s > 0.0 {
+                    size_mb / period_s
+                } else {
+                    0.0
+                }
+            }
+            TrafficPattern::Campaign {
+                mean_interval_s,
+                products_per_campaign,
+                min_size_mb,
+                max_size_mb,
+                ..
+            } => {
+                if *mean_interval_s > 0.0 {
+                    f64::from(*products_per_campaign) * (min_size_mb + max_size_mb)
+                        / 2.0
+                        / mean_interval_s
+                } else {
+                    0.0
+                }
+            }
+            TrafficPattern::EventDriven {
+                rate_per_hour,
+                size_mb,
+            } => rate_per_hour * size_mb / 3600.0,
+        }
+    }
+
+    /// Generate this source's arrivals over `[0, duration_s)`.
+    fn generate<R: Rng + ?Sized>(
+        &self,
+        index: usize,
+        duration_s: f64,
+        rng: &mut R,
+        out: &mut Vec<DataArrival>,
+    ) {
+        let mut push = |time_s: f64, size_mb: f64| {
+            if (0.0..duration_s).contains(&time_s) {
+                out.push(DataArrival {
+                    time_s,
+                    source: index,
+                    class: self.class,
+                    size_mb,
+                });
+            }
+        };
+
+        match &self.pattern {
+            TrafficPattern::Periodic {
+                period_s,
+                size_mb,
+                jitter_s,
+            } => {
+                if *period_s <= 0.0 {
+                    return;
+                }
+                let mut t = 0.0;
+                while t < duration_s {
+                    let jitter = if *jitter_s > 0.0 {
+                        rng.gen_range(-jitter_s..=*jitter_s)
+                    } else {
+                        0.0
+                    };
+                    push((t + jitter).max(0.0), *size_mb);
+                    t += period_s;
+                }
+            }
+            TrafficPattern::Campaign {
+                mean_interval_s,
+                products_per_campaign,
+                product_spacing_s,
+                min_size_mb,
+                max_size_mb,
+            } => {
+                if *mean_interval_s <= 0.0 {
+                    return;
+                }
+                let mut t = exponential(rng, *mean_interval_s);
+                while t < duration_s {
+                    for product in 0..*products_per_campaign {
+                        let size_mb = if max_size_mb > min_size_mb {
+                            rng.gen_range(*min_size_mb..=*max_size_mb)
+                        } else {
+                            *min_size_mb
+                        };
+                        push(t + f64::from(product) * product_spacing_s, size_mb);
+                    }
+                    t += exponential(rng, *mean_interval_s);
+                }
+            }
+            TrafficPattern::EventDriven {
+                rate_per_hour,
+                size_mb,
+            } => {
+                if *rate_per_hour <= 0.0 {
+                    return;
+                }
+                let mean_interval_s = 3600.0 / rate_per_hour;
+                let mut t = exponential(rng, mean_interval_s);
+                while t < duration_s {
+                    push(t, *size_mb);
+                    t += exponential(rng, mean_interval_s);
+                }
+            }
+        }
+    }
+}
+
+impl MissionProfile {
+    /// Earth-observation mission: per-minute housekeeping, imaging
+    /// campaigns roughly every 90 minutes, and occasional alerts.
+    pub fn earth_observation() -> Self {
+        Self {
+            name: "Earth observation".to_string(),
+            sources: vec![
+                TrafficSource {
+                    name: "Housekeeping".to_string(),
+                    class: DataClass::Housekeeping,
+                    pattern: TrafficPattern::Periodic {
+                        period_s: 60.0,
+                        size_mb: 0.05,
+                        jitter_s: 0.5,
+                    },
+                },
+                TrafficSource {
+                    name: "Imaging campaign".to_string(),
+                    class: DataClass::Science,
+                    pattern: TrafficPattern::Campaign {
+                        mean_interval_s: 5400.0,
+                        products_per_campaign: 12,
+                        product_spacing_s: 15.0,
+                        min_size_mb: 80.0,
+                        max_size_mb: 250.0,
+                    },
+                },
+                TrafficSource {
+                    name: "Anomaly alerts".to_string(),
+                    class: DataClass::Alert,
+                    pattern: TrafficPattern::EventDriven {
+                        rate_per_hour: 0.5,
+                        size_mb: 0.01,
+                    },
+                },
+            ],
+        }
+    }
+
+    /// Housekeeping-only profile, e.g. for safe mode or LEOP studies.
+    pub fn housekeeping_only() -> Self {
+        Self {
+            name: "Housekeeping only".to_string(),
+            sources: vec![TrafficSource {
+                name: "Housekeeping".to_string(),
+                class: DataClass::Housekeeping,
+                pattern: TrafficPattern::Periodic {
+                    period_s: 60.0,
+                    size_mb: 0.05,
+                    jitter_s: 0.0,
+                },
+            }],
+        }
+    }
+
+    /// Long-run average offered load of all sources, MB/s.
+    pub fn mean_rate_mb_s(&self) -> f64 {
+        self.sources.iter().map(TrafficSource::mean_rate_mb_s).sum()
+    }
+
+    /// Generate data arrivals over `[0, duration_s)`.
+    ///
+    /// - **ID**: FN-TRF-001
+    /// - **Requirement**: Provide representative data arrival patterns for
+    ///   capacity planning (REQ-PF-002).
+    /// - **Inputs**:
+    ///   - `duration_s`: Length of the run, seconds.
+    ///   - `rng`: Random source; seed it for repeatable studies.
+    /// - **Outputs**: Arrivals from every source, sorted by time.
+    /// - **Side Effects**: None.
+    pub fn generate<R: Rng + ?Sized>(&self, duration_s: f64, rng: &mut R) -> Vec<DataArrival> {
+        let mut arrivals = Vec::new();
+        for (index, source) in self.sources.iter().enumerate() {
+            source.generate(index, duration_s, rng, &mut arrivals);
+        }
+        arrivals.sort_by(|a, b| {
+            a.time_s
+                .partial_cmp(&b.time_s)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        arrivals
+    }
+}
+
+/// Total volume of `arrivals`, MB.
+pub fn total_volume_mb(arrivals: &[DataArrival]) -> f64 {
+    arrivals.iter().map(|a| a.size_mb).sum()
+}
+
+/// Volume arriving in consecutive buckets of `bucket_s` seconds, MB.
+///
+/// Bucket `i` covers `[i * bucket_s, (i + 1) * bucket_s)`; the result has
+/// one entry per bucket up to the last arrival.
+pub fn volume_per_interval(arrivals: &[DataArrival], bucket_s: f64) -> Vec<f64> {
+    let mut buckets = Vec::new();
+    if bucket_s <= 0.0 {
+        return buckets;
+    }
+    for arrival in arrivals {
+        let index = (arrival.time_s / bucket_s).floor().max(0.0) as usize;
+        if buckets.len() <= index {
+            buckets.resize(index + 1, 0.0);
+        }
+        buckets[index] += arrival.size_mb;
+    }
+    buckets
+}
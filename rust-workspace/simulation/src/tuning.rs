@@ -0,0 +1,312 @@
+//! Live Parameter Tuning Module
+//!
+//! A time-stepped run, such as the real-time weather simulation of the
+//! interactive demo, otherwise fixes its transmission parameters up front and
+//! takes its rain from the weather model for the whole run. [`LiveTuning`]
+//! holds the parameters an operator may change while the run goes on:
+//!
+//! - **Rain rate**: held at a given value in place of the weather model's
+//!   until returned to the model.
+//! - **Transmit power** and **distance**: set to a value or stepped by one.
+//!
+//! Each [`Adjustment`] is validated against the same ranges as any other
+//! simulation input before it takes effect, and an accepted one is recorded
+//! as an [`Annotation`] at the run time it was applied, giving the timeline
+//! of what changed when. Adjustments reach the run however the front end
+//! likes; `Adjustment` parses the short commands typed at a terminal, e.g.
+//! `rain 12`, `power -20`, `distance 2500` or `rain auto`, and is `Send`, so
+//! another thread can feed them over an `mpsc` channel.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (runtime adjustment of rain rate,
+//!   transmit power and distance)
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::validation::ValidationError;
+use crate::{EnvironmentalConditions, TransmissionParameters};
+
+/// Parameter that can be changed while a run goes on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TunableParameter {
+    /// Rain rate, mm/h.
+    RainRate,
+    /// Transmitter power, W.
+    TransmitPower,
+    /// Slant range to the satellite, km.
+    Distance,
+}
+
+impl TunableParameter {
+    /// Every tunable parameter, in command order.
+    pub const ALL: [TunableParameter; 3] = [
+        TunableParameter::RainRate,
+        TunableParameter::TransmitPower,
+        TunableParameter::Distance,
+    ];
+
+    /// Command word naming the parameter.
+    pub fn command(&self) -> &'static str {
+        match self {
+            TunableParameter::RainRate => "rain",
+            TunableParameter::TransmitPower => "power",
+            TunableParameter::Distance => "distance",
+        }
+    }
+
+    /// Unit of the parameter's values.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            TunableParameter::RainRate => "mm/h",
+            TunableParameter::TransmitPower => "W",
+            TunableParameter::Distance => "km",
+        }
+    }
+}
+
+impl fmt::Display for TunableParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TunableParameter::RainRate => "rain rate",
+            TunableParameter::TransmitPower => "transmit power",
+            TunableParameter::Distance => "distance",
+        })
+    }
+}
+
+/// New value of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Change {
+    /// Set to this value.
+    To(f64),
+    /// Step by this amount from the current value.
+    By(f64),
+    /// Return to the weather model; rain rate only.
+    Model,
+}
+
+/// One change of one parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    /// Parameter changed.
+    pub parameter: TunableParameter,
+    /// How it changes.
+    pub change: Change,
+}
+
+impl FromStr for Adjustment {
+    type Err = TuningError;
+
+    /// Parse `<parameter> <value>`: the parameter by its command word or
+    /// first letter, the value absolute, signed for a step, or `auto` to
+    /// return the rain rate to the weather model.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unrecognized = || TuningError::Unrecognized(s.trim().to_string());
+        let mut words = s.split_whitespace();
+        let (word, value) = match (words.next(), words.next(), words.next()) {
+            (Some(word), Some(value), None) => (word.to_ascii_lowercase(), value),
+            _ => return Err(unrecognized()),
+        };
+        let parameter = TunableParameter::ALL
+            .into_iter()
+            .find(|p| word == p.command() || word == p.command()[..1])
+            .ok_or_else(unrecognized)?;
+        let change = if value.eq_ignore_ascii_case("auto") {
+            Change::Model
+        } else {
+            let number: f64 = value.parse().map_err(|_| unrecognized())?;
+            if value.starts_with(['+', '-']) {
+                Change::By(number)
+            } else {
+                Change::To(number)
+            }
+        };
+        Ok(Adjustment { parameter, change })
+    }
+}
+
+/// Why an adjustment was not applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuningError {
+    /// Text that is not an adjustment.
+    Unrecognized(String),
+    /// `Change::Model` of a parameter that has no model.
+    NotModelled(TunableParameter),
+    /// The new value is out of range.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningError::Unrecognized(text) => write!(f, "unrecognized adjustment '{}'", text),
+            TuningError::NotModelled(parameter) => {
+                write!(f, "{} has no model to return to", parameter)
+            }
+            TuningError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for TuningError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TuningError::Invalid(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationError> for TuningError {
+    fn from(error: ValidationError) -> Self {
+        TuningError::Invalid(error)
+    }
+}
+
+/// Adjustment applied during a run, for the output timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Run time the adjustment took effect, hours.
+    pub time_hours: f64,
+    /// Parameter changed.
+    pub parameter: TunableParameter,
+    /// Value in effect before.
+    pub from: f64,
+    /// Value in effect after; `None` for rain returned to the weather model.
+    pub to: Option<f64>,
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.1}h {} {:.1} → ",
+            self.time_hours, self.parameter, self.from
+        )?;
+        match self.to {
+            Some(to) => write!(f, "{:.1} {}", to, self.parameter.unit()),
+            None => write!(f, "weather model"),
+        }
+    }
+}
+
+/// Parameters of a run that change while it goes on.
+///
+/// - **ID**: MOD-TUNE-001
+/// - **Requirement**: Rain rate, transmit power and distance adjustable
+///   during a run, each change validated and annotated with its run time.
+#[derive(Debug, Clone)]
+pub struct LiveTuning {
+    params: TransmissionParameters,
+    rain_override: Option<f64>,
+    annotations: Vec<Annotation>,
+}
+
+impl LiveTuning {
+    /// Tuning starting from `params`, with rain from the weather model.
+    pub fn new(params: TransmissionParameters) -> Self {
+        Self {
+            params,
+            rain_override: None,
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Transmission parameters in effect.
+    pub fn parameters(&self) -> &TransmissionParameters {
+        &self.params
+    }
+
+    /// Rain rate held in place of the weather model's, mm/h.
+    pub fn rain_override(&self) -> Option<f64> {
+        self.rain_override
+    }
+
+    /// Conditions in effect: the weather model's, with the rain rate
+    /// overridden if one is held.
+    pub fn environment(&self, modelled: &EnvironmentalConditions) -> EnvironmentalConditions {
+        EnvironmentalConditions {
+            rain_rate_mm_hour: self.rain_override.unwrap_or(modelled.rain_rate_mm_hour),
+            ..modelled.clone()
+        }
+    }
+
+    /// Apply an adjustment at run time `time_hours`.
+    ///
+    /// - **ID**: FN-TUNE-001
+    /// - **Requirement**: Change a parameter without restarting the run,
+    ///   validated as any other input (REQ-FN-008).
+    /// - **Inputs**: Run time, hours; the adjustment; the weather model's
+    ///   conditions at that time, which a rain step starts from unless a
+    ///   rain rate is already held.
+    /// - **Outputs**: The annotation recorded for the change.
+    /// - **Side Effects**: Updates the parameters in effect and appends the
+    ///   annotation.
+    /// - **Failure Modes**: `TuningError::NotModelled` for a return to the
+    ///   model of power or distance, `TuningError::Invalid` for a value out
+    ///   of range; the parameters are left unchanged.
+    pub fn apply(
+        &mut self,
+        time_hours: f64,
+        adjustment: Adjustment,
+        modelled: &EnvironmentalConditions,
+    ) -> Result<&Annotation, TuningError> {
+        let from = match adjustment.parameter {
+            TunableParameter::RainRate => self.environment(modelled).rain_rate_mm_hour,
+            TunableParameter::TransmitPower => self.params.transmit_power_watts,
+            TunableParameter::Distance => self.params.distance_km,
+        };
+        let to = match adjustment.change {
+            Change::To(value) => Some(value),
+            Change::By(step) => Some(from + step),
+            Change::Model if adjustment.parameter == TunableParameter::RainRate => None,
+            Change::Model => return Err(TuningError::NotModelled(adjustment.parameter)),
+        };
+
+        match (adjustment.parameter, to) {
+            (TunableParameter::RainRate, _) => {
+                if let Some(rain) = to {
+                    EnvironmentalConditions {
+                        rain_rate_mm_hour: rain,
+                        ..modelled.clone()
+                    }
+                    .validate()?;
+                }
+                self.rain_override = to;
+            }
+            (TunableParameter::TransmitPower, Some(power)) => {
+                let params = TransmissionParameters {
+                    transmit_power_watts: power,
+                    ..self.params.clone()
+                };
+                params.validate()?;
+                self.params = params;
+            }
+            (TunableParameter::Distance, Some(distance)) => {
+                let params = TransmissionParameters {
+                    distance_km: distance,
+                    ..self.params.clone()
+                };
+                params.validate()?;
+                self.params = params;
+            }
+            (_, None) => unreachable!("only the rain rate returns to a model"),
+        }
+
+        self.annotations.push(Annotation {
+            time_hours,
+            parameter: adjustment.parameter,
+            from,
+            to,
+        });
+        Ok(self.annotations.last().expect("annotation just recorded"))
+    }
+
+    /// Every adjustment applied so far, in the order applied.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+}
@@ -0,0 +1,363 @@
+//! Input Validation Module
+//!
+//! Range checks of `TransmissionParameters` and `EnvironmentalConditions`
+//! before they reach the link model. Out-of-range inputs otherwise run
+//! straight through it: a negative distance takes the logarithm of a
+//! negative path length and returns NaN, zero transmit power gives an SNR of
+//! minus infinity, and an elevation above 90° is silently accepted. Every
+//! public entry point that simulates a transmission validates its inputs
+//! first and returns a `ValidationError` naming every field out of range,
+//! not only the first one found.
+//!
+//! `TransmissionParameters::builder()` sets parameters field by field and
+//! validates them on `build()`, reporting fields never set along with
+//! invalid ones.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (inputs checked against the
+//!   model's valid ranges)
+
+use std::fmt;
+
+use crate::atmospheric::itu_p618::RainPath;
+use crate::{EnvironmentalConditions, TransmissionParameters};
+
+/// Range check of one field: whether a value is valid, and the valid values
+/// in words.
+type Rule = (fn(f64) -> bool, &'static str);
+
+fn positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn non_negative(value: f64) -> bool {
+    value.is_finite() && value >= 0.0
+}
+
+fn elevation(value: f64) -> bool {
+    (0.0..=90.0).contains(&value)
+}
+
+fn latitude(value: f64) -> bool {
+    (-90.0..=90.0).contains(&value)
+}
+
+fn finite(value: f64) -> bool {
+    value.is_finite()
+}
+
+fn percent(value: f64) -> bool {
+    (0.0..=100.0).contains(&value)
+}
+
+fn fraction(value: f64) -> bool {
+    (0.0..=1.0).contains(&value)
+}
+
+fn above_absolute_zero(value: f64) -> bool {
+    value.is_finite() && value > -273.15
+}
+
+/// Rules of the `TransmissionParameters` fields, in declaration order.
+const PARAMETER_RULES: [(&str, Rule); 8] = [
+    ("distance_km", (positive, "a distance greater than 0 km")),
+    ("data_size_mb", (non_negative, "a size of 0 MB or more")),
+    (
+        "required_data_rate_mbps",
+        (non_negative, "a data rate of 0 Mbps or more"),
+    ),
+    (
+        "elevation_angle_degrees",
+        (elevation, "an elevation from 0° to 90°"),
+    ),
+    (
+        "transmit_power_watts",
+        (positive, "a power greater than 0 W"),
+    ),
+    (
+        "antenna_diameter_meters",
+        (non_negative, "a diameter of 0 m or more"),
+    ),
+    ("range_rate_m_s", (finite, "a finite range rate")),
+    (
+        "range_acceleration_m_s2",
+        (finite, "a finite range acceleration"),
+    ),
+];
+
+/// Rules of the `EnvironmentalConditions` fields, in declaration order.
+const ENVIRONMENT_RULES: [(&str, Rule); 7] = [
+    (
+        "rain_rate_mm_hour",
+        (non_negative, "a rain rate of 0 mm/h or more"),
+    ),
+    ("cloud_cover_percent", (percent, "a cover from 0% to 100%")),
+    (
+        "atmospheric_pressure_mb",
+        (positive, "a pressure greater than 0 mb"),
+    ),
+    (
+        "temperature_celsius",
+        (above_absolute_zero, "a temperature above -273.15 °C"),
+    ),
+    ("humidity_percent", (percent, "a humidity from 0% to 100%")),
+    ("ionospheric_activity", (fraction, "an index from 0 to 1")),
+    ("solar_activity", (fraction, "an index from 0 to 1")),
+];
+
+/// Rules of the `RainPath` fields, in declaration order.
+const RAIN_PATH_RULES: [(&str, Rule); 3] = [
+    ("latitude_deg", (latitude, "a latitude from -90° to 90°")),
+    ("station_altitude_km", (finite, "a finite altitude")),
+    ("polarization_tilt_deg", (finite, "a finite tilt angle")),
+];
+
+/// One input field outside its valid range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// Field name, as serialized.
+    pub field: &'static str,
+    /// Value given; `None` if the field was never set.
+    pub value: Option<f64>,
+    /// Values the field accepts.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{} = {} (expected {})", self.field, value, self.expected),
+            None => write!(f, "{} not set (expected {})", self.field, self.expected),
+        }
+    }
+}
+
+/// Simulation inputs rejected before they reach the link model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// Every invalid field, parameters before environment, each in
+    /// declaration order.
+    pub fields: Vec<FieldError>,
+}
+
+impl ValidationError {
+    /// Error of field `name`, if it is invalid.
+    pub fn field(&self, name: &str) -> Option<&FieldError> {
+        self.fields.iter().find(|e| e.field == name)
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid simulation inputs: ")?;
+        for (i, error) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Errors of the fields that are unset or break their rule.
+fn check<const N: usize>(
+    rules: &[(&'static str, Rule); N],
+    values: [Option<f64>; N],
+) -> Vec<FieldError> {
+    rules
+        .iter()
+        .zip(values)
+        .filter(|((_, (valid, _)), value)| !value.is_some_and(*valid))
+        .map(|((field, (_, expected)), value)| FieldError {
+            field,
+            value,
+            expected,
+        })
+        .collect()
+}
+
+fn result(fields: Vec<FieldError>) -> Result<(), ValidationError> {
+    if fields.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { fields })
+    }
+}
+
+/// Validate the inputs of one simulated transmission.
+///
+/// - **ID**: FN-VAL-001
+/// - **Requirement**: Reject inputs outside the link model's valid ranges
+///   before simulating (REQ-FN-008).
+/// - **Outputs**: `Ok` if every field is valid, else every invalid field of
+///   both inputs.
+/// - **Side Effects**: None.
+pub fn validate_inputs(
+    params: &TransmissionParameters,
+    environment: &EnvironmentalConditions,
+) -> Result<(), ValidationError> {
+    let mut fields = params.field_errors();
+    fields.extend(environment.field_errors());
+    result(fields)
+}
+
+impl TransmissionParameters {
+    /// Check every field against its valid range.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        result(self.field_errors())
+    }
+
+    /// Builder setting the parameters field by field.
+    pub fn builder() -> TransmissionParametersBuilder {
+        TransmissionParametersBuilder::default()
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        check(
+            &PARAMETER_RULES,
+            [
+                Some(self.distance_km),
+                Some(self.data_size_mb),
+                Some(self.required_data_rate_mbps),
+                Some(self.elevation_angle_degrees),
+                Some(self.transmit_power_watts),
+                Some(self.antenna_diameter_meters),
+                Some(self.range_rate_m_s),
+                Some(self.range_acceleration_m_s2),
+            ],
+        )
+    }
+}
+
+impl EnvironmentalConditions {
+    /// Check every field against its valid range.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        result(self.field_errors())
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        check(
+            &ENVIRONMENT_RULES,
+            [
+                Some(self.rain_rate_mm_hour),
+                Some(self.cloud_cover_percent),
+                Some(self.atmospheric_pressure_mb),
+                Some(self.temperature_celsius),
+                Some(self.humidity_percent),
+                Some(self.ionospheric_activity),
+                Some(self.solar_activity),
+            ],
+        )
+    }
+}
+
+impl RainPath {
+    /// Check every field against its valid range.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        result(check(
+            &RAIN_PATH_RULES,
+            [
+                Some(self.latitude_deg),
+                Some(self.station_altitude_km),
+                Some(self.polarization.tilt_deg()),
+            ],
+        ))
+    }
+}
+
+/// Validating builder of `TransmissionParameters`.
+///
+/// Every field but the relative motion must be set; `build()` reports unset
+/// and invalid fields together. The range rate and acceleration default to
+/// zero, a stationary satellite.
+#[derive(Debug, Clone, Default)]
+pub struct TransmissionParametersBuilder {
+    distance_km: Option<f64>,
+    data_size_mb: Option<f64>,
+    required_data_rate_mbps: Option<f64>,
+    elevation_angle_degrees: Option<f64>,
+    transmit_power_watts: Option<f64>,
+    antenna_diameter_meters: Option<f64>,
+    range_rate_m_s: f64,
+    range_acceleration_m_s2: f64,
+}
+
+impl TransmissionParametersBuilder {
+    /// Slant range to the satellite, km.
+    pub fn distance_km(mut self, value: f64) -> Self {
+        self.distance_km = Some(value);
+        self
+    }
+
+    /// Data to transfer, MB.
+    pub fn data_size_mb(mut self, value: f64) -> Self {
+        self.data_size_mb = Some(value);
+        self
+    }
+
+    /// Data rate the link must sustain, Mbps.
+    pub fn required_data_rate_mbps(mut self, value: f64) -> Self {
+        self.required_data_rate_mbps = Some(value);
+        self
+    }
+
+    /// Elevation of the satellite above the horizon, degrees.
+    pub fn elevation_angle_degrees(mut self, value: f64) -> Self {
+        self.elevation_angle_degrees = Some(value);
+        self
+    }
+
+    /// Transmitter power, W.
+    pub fn transmit_power_watts(mut self, value: f64) -> Self {
+        self.transmit_power_watts = Some(value);
+        self
+    }
+
+    /// Ground antenna diameter, m.
+    pub fn antenna_diameter_meters(mut self, value: f64) -> Self {
+        self.antenna_diameter_meters = Some(value);
+        self
+    }
+
+    /// Rate of change of the slant range, m/s, positive receding.
+    pub fn range_rate_m_s(mut self, value: f64) -> Self {
+        self.range_rate_m_s = value;
+        self
+    }
+
+    /// Rate of change of the range rate, m/s².
+    pub fn range_acceleration_m_s2(mut self, value: f64) -> Self {
+        self.range_acceleration_m_s2 = value;
+        self
+    }
+
+    /// Validated parameters, or every field that is unset or invalid.
+    pub fn build(self) -> Result<TransmissionParameters, ValidationError> {
+        result(check(
+            &PARAMETER_RULES,
+            [
+                self.distance_km,
+                self.data_size_mb,
+                self.required_data_rate_mbps,
+                self.elevation_angle_degrees,
+                self.transmit_power_watts,
+                self.antenna_diameter_meters,
+                Some(self.range_rate_m_s),
+                Some(self.range_acceleration_m_s2),
+            ],
+        ))?;
+        Ok(TransmissionParameters {
+            distance_km: self.distance_km.unwrap_or_default(),
+            data_size_mb: self.data_size_mb.unwrap_or_default(),
+            required_data_rate_mbps: self.required_data_rate_mbps.unwrap_or_default(),
+            elevation_angle_degrees: self.elevation_angle_degrees.unwrap_or_default(),
+            transmit_power_watts: self.transmit_power_watts.unwrap_or_default(),
+            antenna_diameter_meters: self.antenna_diameter_meters.unwrap_or_default(),
+            range_rate_m_s: self.range_rate_m_s,
+            range_acceleration_m_s2: self.range_acceleration_m_s2,
+        })
+    }
+}
@@ -0,0 +1,660 @@
+//! Weather Generator Module
+//!
+//! Time-varying weather for scenarios that run longer than one fixed set of
+//! [`EnvironmentalConditions`]. A [`WeatherGenerator`] produces the
+//! conditions at each scenario time; three models are provided:
+//!
+//! - [`MarkovRainModel`] — rain states (dry, stratiform, convective) with
+//!   per-step transition probabilities, giving rain events that persist and
+//!   decay realistically.
+//! - [`SeasonalClimatology`] — rain statistics of an ITU-R P.837 rain zone,
+//!   given or looked up from the ground station location, with a seasonal
+//!   wet-season cycle; samples are independent draws.
+//! - [`HistoricalReplay`] — a recorded time series, e.g. from a weather
+//!   station log, replayed by holding each sample until the next.
+//!
+//! Scenario files select a model with [`WeatherModel`], a JSON object tagged
+//! by `"model"`: `"markov"`, `"climatology"` or `"replay"`. Conditions a model
+//! does not cover (pressure, ionospheric and solar activity, and temperature
+//! for the rain models) are taken from the scenario's base environment.
+//!
+//! # Requirements Traceability
+//! - REQ-FN-008: Frequency Band Simulation (time-varying weather models)
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub use crate::rain_zone::ItuRainZone;
+use crate::EnvironmentalConditions;
+
+/// Fraction of an average year the ITU-R P.837 rain rate is exceeded.
+const EXCEEDANCE_0_01_PERCENT: f64 = 1e-4;
+
+/// Days in a year for the seasonal cycle.
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Error building or loading a weather model.
+#[derive(Debug)]
+pub enum WeatherError {
+    /// The time series file could not be read.
+    Io(std::io::Error),
+    /// A time series line could not be parsed.
+    Csv { line: usize, reason: String },
+    /// The model parameters are not usable.
+    InvalidModel(&'static str),
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeatherError::Io(e) => write!(f, "cannot read weather time series: {}", e),
+            WeatherError::Csv { line, reason } => {
+                write!(f, "weather time series line {}: {}", line, reason)
+            }
+            WeatherError::InvalidModel(reason) => write!(f, "invalid weather model: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for WeatherError {}
+
+impl From<std::io::Error> for WeatherError {
+    fn from(e: std::io::Error) -> Self {
+        WeatherError::Io(e)
+    }
+}
+
+/// Source of weather over scenario time.
+pub trait WeatherGenerator {
+    /// Conditions at `time_s` seconds into the scenario.
+    ///
+    /// Calls are made in non-decreasing time order. Conditions the model
+    /// does not cover are copied from `base`.
+    fn conditions_at(
+        &mut self,
+        time_s: f64,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> EnvironmentalConditions;
+
+    /// Conditions at `count` times `step_s` apart, starting at `start_s`.
+    fn series(
+        &mut self,
+        start_s: f64,
+        step_s: f64,
+        count: usize,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> Vec<WeatherSample> {
+        (0..count)
+            .map(|i| {
+                let time_s = start_s + step_s * i as f64;
+                WeatherSample {
+                    time_s,
+                    conditions: self.conditions_at(time_s, base, rng),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Conditions at one time of a weather series.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherSample {
+    /// Seconds into the scenario.
+    pub time_s: f64,
+    /// Conditions from this time until the next sample.
+    pub conditions: EnvironmentalConditions,
+}
+
+/// `base` with rain and the cloud and humidity that come with it.
+fn rainy(
+    base: &EnvironmentalConditions,
+    rain_rate_mm_hour: f64,
+    cloud_cover_percent: f64,
+    humidity_percent: f64,
+) -> EnvironmentalConditions {
+    EnvironmentalConditions {
+        rain_rate_mm_hour,
+        cloud_cover_percent,
+        humidity_percent,
+        ..base.clone()
+    }
+}
+
+// ─── Markov Chain Rain Model ──────────────────────────────────────────────────
+
+/// One state of a [`MarkovRainModel`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RainState {
+    /// State name, e.g. `convective`.
+    pub name: String,
+    /// Lowest rain rate in this state, mm/h.
+    pub min_rain_mm_hour: f64,
+    /// Highest rain rate in this state, mm/h.
+    pub max_rain_mm_hour: f64,
+    /// Cloud cover in this state, %.
+    pub cloud_cover_percent: f64,
+    /// Relative humidity in this state, %.
+    pub humidity_percent: f64,
+}
+
+impl RainState {
+    fn new(name: &str, rain_mm_hour: (f64, f64), cloud: f64, humidity: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            min_rain_mm_hour: rain_mm_hour.0,
+            max_rain_mm_hour: rain_mm_hour.1,
+            cloud_cover_percent: cloud,
+            humidity_percent: humidity,
+        }
+    }
+}
+
+/// Markov chain over rain states.
+///
+/// - **ID**: MOD-WX-001
+/// - **Requirement**: Rain that persists over consecutive contacts, with
+///   realistic event durations, for availability over a pass sequence.
+/// - **Constraints**: The chain advances in whole `step_s` steps; rows of
+///   `transitions` are normalized when drawn, so only their ratios matter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkovRainModel {
+    /// Rain states; each draws its rain rate uniformly from its range.
+    pub states: Vec<RainState>,
+    /// `transitions[i][j]`: probability of moving from state `i` to `j`
+    /// in one step.
+    pub transitions: Vec<Vec<f64>>,
+    /// Time step of the chain, seconds.
+    pub step_s: f64,
+    /// State at the start of the scenario.
+    #[serde(default)]
+    pub initial_state: usize,
+    /// Current state and the step it was entered at.
+    #[serde(skip)]
+    current: Option<(usize, i64)>,
+}
+
+impl Default for MarkovRainModel {
+    /// Dry, stratiform and convective states with 10-minute steps.
+    ///
+    /// Dry spells last about 8 hours, stratiform rain about 1.5 hours and
+    /// convective cells about half an hour.
+    fn default() -> Self {
+        Self {
+            states: vec![
+                RainState::new("dry", (0.0, 0.0), 20.0, 50.0),
+                RainState::new("stratiform", (0.5, 5.0), 90.0, 85.0),
+                RainState::new("convective", (10.0, 60.0), 100.0, 95.0),
+            ],
+            transitions: vec![
+                vec![0.98, 0.018, 0.002],
+                vec![0.10, 0.88, 0.02],
+                vec![0.05, 0.25, 0.70],
+            ],
+            step_s: 600.0,
+            initial_state: 0,
+            current: None,
+        }
+    }
+}
+
+impl MarkovRainModel {
+    /// Check that the chain can be run.
+    ///
+    /// - **Failure Modes**: No states, a non-positive step, a transition
+    ///   matrix that is not square over the states, negative or all-zero
+    ///   rows, or an initial state out of range → `InvalidModel`.
+    pub fn validate(&self) -> Result<(), WeatherError> {
+        if self.states.is_empty() {
+            return Err(WeatherError::InvalidModel("Markov model has no states"));
+        }
+        if !(self.step_s.is_finite() && self.step_s > 0.0) {
+            return Err(WeatherError::InvalidModel("Markov step must be positive"));
+        }
+        if self.initial_state >= self.states.len() {
+            return Err(WeatherError::InvalidModel("initial state out of range"));
+        }
+        if self.transitions.len() != self.states.len()
+            || self
+                .transitions
+                .iter()
+                .any(|row| row.len() != self.states.len())
+        {
+            return Err(WeatherError::InvalidModel(
+                "transition matrix must have one row and column per state",
+            ));
+        }
+        if self.transitions.iter().any(|row| {
+            row.iter().any(|&p| !p.is_finite() || p < 0.0) || row.iter().sum::<f64>() <= 0.0
+        }) {
+            return Err(WeatherError::InvalidModel(
+                "transition probabilities must be non-negative with a positive row sum",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Index and name of the current state, once the chain has started.
+    pub fn state(&self) -> Option<(usize, &str)> {
+        self.current.map(|(i, _)| (i, self.states[i].name.as_str()))
+    }
+
+    fn next_state(&self, from: usize, rng: &mut StdRng) -> usize {
+        let row = &self.transitions[from];
+        let mut draw = rng.gen::<f64>() * row.iter().sum::<f64>();
+        for (to, &p) in row.iter().enumerate() {
+            if draw < p {
+                return to;
+            }
+            draw -= p;
+        }
+        from
+    }
+}
+
+impl WeatherGenerator for MarkovRainModel {
+    fn conditions_at(
+        &mut self,
+        time_s: f64,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> EnvironmentalConditions {
+        if self.states.is_empty() || !(self.step_s.is_finite() && self.step_s > 0.0) {
+            return base.clone();
+        }
+        let step = (time_s / self.step_s).floor() as i64;
+        let (mut state, mut at) = self
+            .current
+            .unwrap_or((self.initial_state.min(self.states.len() - 1), step));
+        while at < step {
+            state = self.next_state(state, rng);
+            at += 1;
+        }
+        self.current = Some((state, at));
+
+        let s = &self.states[state];
+        let rain = if s.max_rain_mm_hour > s.min_rain_mm_hour {
+            rng.gen_range(s.min_rain_mm_hour..s.max_rain_mm_hour)
+        } else {
+            s.min_rain_mm_hour
+        };
+        rainy(base, rain, s.cloud_cover_percent, s.humidity_percent)
+    }
+}
+
+// ─── Seasonal Climatology ─────────────────────────────────────────────────────
+
+/// Rain statistics of an ITU rain zone with a seasonal cycle.
+///
+/// - **ID**: MOD-WX-002
+/// - **Requirement**: Weather drawn from the long-term rain statistics of a
+///   ground station's climate, for annual availability studies.
+/// - **Constraints**: Samples are independent, so rain does not persist
+///   between calls; use [`MarkovRainModel`] where event duration matters.
+///   The rain rate is exponential given rain, with its scale set so the
+///   zone's 0.01% rate is exceeded for about 0.01% of the year.
+///
+/// Scenario files give either the `zone` or the ground station `location`
+/// as `[latitude, longitude, altitude]`, from which the zone and the
+/// hemisphere's wet season are looked up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ClimatologySpec")]
+pub struct SeasonalClimatology {
+    /// Rain zone of the ground station.
+    pub zone: ItuRainZone,
+    /// Mean fraction of the year with rain, 0-1.
+    pub rain_probability: f64,
+    /// Relative swing of the rain probability over the year, 0-1.
+    pub seasonal_amplitude: f64,
+    /// Day of year (0 = 1 January) the rain probability peaks.
+    pub wet_season_peak_day: f64,
+    /// Day of year at scenario time zero.
+    pub start_day_of_year: f64,
+}
+
+/// Scenario file form of a [`SeasonalClimatology`].
+#[derive(Deserialize)]
+struct ClimatologySpec {
+    zone: Option<ItuRainZone>,
+    location: Option<(f64, f64, f64)>,
+    #[serde(default = "default_rain_probability")]
+    rain_probability: f64,
+    #[serde(default = "default_seasonal_amplitude")]
+    seasonal_amplitude: f64,
+    wet_season_peak_day: Option<f64>,
+    #[serde(default)]
+    start_day_of_year: f64,
+}
+
+impl TryFrom<ClimatologySpec> for SeasonalClimatology {
+    type Error = WeatherError;
+
+    fn try_from(spec: ClimatologySpec) -> Result<Self, Self::Error> {
+        let mut climatology = match (spec.zone, spec.location) {
+            (Some(zone), _) => SeasonalClimatology::new(zone),
+            (None, Some(location)) => SeasonalClimatology::for_station(location),
+            (None, None) => {
+                return Err(WeatherError::InvalidModel(
+                    "climatology needs a zone or a location",
+                ))
+            }
+        };
+        climatology.rain_probability = spec.rain_probability;
+        climatology.seasonal_amplitude = spec.seasonal_amplitude;
+        climatology.start_day_of_year = spec.start_day_of_year;
+        if let Some(day) = spec.wet_season_peak_day {
+            climatology.wet_season_peak_day = day;
+        }
+        Ok(climatology)
+    }
+}
+
+fn default_rain_probability() -> f64 {
+    0.05
+}
+
+fn default_seasonal_amplitude() -> f64 {
+    0.5
+}
+
+fn default_wet_season_peak_day() -> f64 {
+    196.0
+}
+
+impl SeasonalClimatology {
+    /// Climatology of `zone` with the default rain probability and a
+    /// northern-hemisphere summer wet season.
+    pub fn new(zone: ItuRainZone) -> Self {
+        Self {
+            zone,
+            rain_probability: default_rain_probability(),
+            seasonal_amplitude: default_seasonal_amplitude(),
+            wet_season_peak_day: default_wet_season_peak_day(),
+            start_day_of_year: 0.0,
+        }
+    }
+
+    /// Climatology at a ground station location, given as
+    /// `GroundStationConfig::location` (latitude, longitude, altitude in
+    /// meters).
+    ///
+    /// - **ID**: FN-WX-003
+    /// - **Requirement**: Scenarios derive rain statistics from where the
+    ///   ground station is.
+    /// - **Outputs**: The climatology of the station's ITU rain zone, with
+    ///   the wet season in the summer of its hemisphere.
+    /// - **Side Effects**: None.
+    pub fn for_station(location: (f64, f64, f64)) -> Self {
+        let mut climatology = Self::new(ItuRainZone::at_station(location));
+        if location.0 < 0.0 {
+            climatology.wet_season_peak_day =
+                (default_wet_season_peak_day() + DAYS_PER_YEAR / 2.0) % DAYS_PER_YEAR;
+        }
+        climatology
+    }
+
+    /// Check that the climatology can be sampled.
+    ///
+    /// - **Failure Modes**: A rain probability outside (0, 1] or a seasonal
+    ///   amplitude outside 0-1 → `InvalidModel`.
+    pub fn validate(&self) -> Result<(), WeatherError> {
+        if !(self.rain_probability > 0.0 && self.rain_probability <= 1.0) {
+            return Err(WeatherError::InvalidModel(
+                "rain probability must be above 0 and at most 1",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.seasonal_amplitude) {
+            return Err(WeatherError::InvalidModel(
+                "seasonal amplitude must be between 0 and 1",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Probability of rain at `time_s` seconds into the scenario.
+    pub fn rain_probability_at(&self, time_s: f64) -> f64 {
+        let day = self.start_day_of_year + time_s / 86_400.0;
+        let phase = 2.0 * std::f64::consts::PI * (day - self.wet_season_peak_day) / DAYS_PER_YEAR;
+        (self.rain_probability * (1.0 + self.seasonal_amplitude * phase.cos())).clamp(0.0, 1.0)
+    }
+
+    /// Mean rain rate while raining, mm/h.
+    pub fn mean_rain_rate_mm_hour(&self) -> f64 {
+        let ratio = self.rain_probability / EXCEEDANCE_0_01_PERCENT;
+        if ratio > std::f64::consts::E {
+            self.zone.rain_rate_0_01_mm_hour() / ratio.ln()
+        } else {
+            self.zone.rain_rate_0_01_mm_hour()
+        }
+    }
+}
+
+impl WeatherGenerator for SeasonalClimatology {
+    fn conditions_at(
+        &mut self,
+        time_s: f64,
+        base: &EnvironmentalConditions,
+        rng: &mut StdRng,
+    ) -> EnvironmentalConditions {
+        if rng.gen::<f64>() >= self.rain_probability_at(time_s) {
+            return rainy(base, 0.0, base.cloud_cover_percent, base.humidity_percent);
+        }
+        // Exponential rain rate given rain; 1 - u keeps the draw above zero
+        let rain = -self.mean_rain_rate_mm_hour() * (1.0 - rng.gen::<f64>()).ln();
+        rainy(base, rain, 90.0, 90.0)
+    }
+}
+
+// ─── Historical Replay ────────────────────────────────────────────────────────
+
+/// Replay of a recorded weather time series.
+///
+/// - **ID**: MOD-WX-003
+/// - **Requirement**: Re-run a scenario through weather that actually
+///   occurred, e.g. the day of a link outage.
+/// - **Constraints**: Each sample holds until the next; times before the
+///   first sample take the first. After the last sample the series either
+///   holds or, with `repeat`, starts again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalReplay {
+    /// Samples in time order.
+    pub samples: Vec<WeatherSample>,
+    /// Start the series again after its last sample.
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl HistoricalReplay {
+    /// Parse a CSV time series.
+    ///
+    /// - **ID**: FN-WX-001
+    /// - **Requirement**: Weather station logs can be replayed without
+    ///   conversion by hand.
+    /// - **Inputs**: CSV with a header row naming a `time_s` column and any
+    ///   of the `EnvironmentalConditions` fields; conditions for columns
+    ///   left out are taken from `base`.
+    /// - **Outputs**: The replay, without repeat.
+    /// - **Side Effects**: None.
+    /// - **Failure Modes**: Missing `time_s` column, unknown column, wrong
+    ///   field count, unparsable number, or times out of order → `Csv`
+    ///   naming the line (1 = header).
+    pub fn from_csv(csv: &str, base: &EnvironmentalConditions) -> Result<Self, WeatherError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let (_, header) = lines.next().ok_or(WeatherError::Csv {
+            line: 1,
+            reason: "missing header".to_string(),
+        })?;
+        let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+        if !columns.contains(&"time_s") {
+            return Err(WeatherError::Csv {
+                line: 1,
+                reason: "no time_s column".to_string(),
+            });
+        }
+        if let Some(unknown) = columns
+            .iter()
+            .find(|c| **c != "time_s" && field(&mut base.clone(), c).is_none())
+        {
+            return Err(WeatherError::Csv {
+                line: 1,
+                reason: format!("unknown column {}", unknown),
+            });
+        }
+
+        let mut samples: Vec<WeatherSample> = Vec::new();
+        for (line, text) in lines {
+            let values: Vec<&str> = text.split(',').map(str::trim).collect();
+            if values.len() != columns.len() {
+                return Err(WeatherError::Csv {
+                    line,
+                    reason: format!("expected {} fields, found {}", columns.len(), values.len()),
+                });
+            }
+            let mut sample = WeatherSample {
+                time_s: 0.0,
+                conditions: base.clone(),
+            };
+            for (column, value) in columns.iter().zip(values) {
+                let value: f64 = value.parse().map_err(|_| WeatherError::Csv {
+                    line,
+                    reason: format!("{} is not a number: {}", column, value),
+                })?;
+                match field(&mut sample.conditions, column) {
+                    Some(slot) => *slot = value,
+                    None => sample.time_s = value,
+                }
+            }
+            if samples
+                .last()
+                .is_some_and(|last| sample.time_s < last.time_s)
+            {
+                return Err(WeatherError::Csv {
+                    line,
+                    reason: "time_s goes backwards".to_string(),
+                });
+            }
+            samples.push(sample);
+        }
+        Ok(Self {
+            samples,
+            repeat: false,
+        })
+    }
+
+    /// Read a CSV time series from a file; see [`HistoricalReplay::from_csv`].
+    pub fn load_csv(
+        path: impl AsRef<Path>,
+        base: &EnvironmentalConditions,
+    ) -> Result<Self, WeatherError> {
+        Self::from_csv(&fs::read_to_string(path)?, base)
+    }
+
+    /// Check that the series can be replayed.
+    ///
+    /// - **Failure Modes**: No samples, or times out of order →
+    ///   `InvalidModel`.
+    pub fn validate(&self) -> Result<(), WeatherError> {
+        if self.samples.is_empty() {
+            return Err(WeatherError::InvalidModel("replay has no samples"));
+        }
+        if self.samples.windows(2).any(|w| w[1].time_s < w[0].time_s) {
+            return Err(WeatherError::InvalidModel(
+                "replay samples must be in time order",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Field of `conditions` named by a CSV column.
+fn field<'a>(conditions: &'a mut EnvironmentalConditions, name: &str) -> Option<&'a mut f64> {
+    Some(match name {
+        "rain_rate_mm_hour" => &mut conditions.rain_rate_mm_hour,
+        "cloud_cover_percent" => &mut conditions.cloud_cover_percent,
+        "atmospheric_pressure_mb" => &mut conditions.atmospheric_pressure_mb,
+        "temperature_celsius" => &mut conditions.temperature_celsius,
+        "humidity_percent" => &mut conditions.humidity_percent,
+        "ionospheric_activity" => &mut conditions.ionospheric_activity,
+        "solar_activity" => &mut conditions.solar_activity,
+        _ => return None,
+    })
+}
+
+impl WeatherGenerator for HistoricalReplay {
+    fn conditions_at(
+        &mut self,
+        time_s: f64,
+        base: &EnvironmentalConditions,
+        _rng: &mut StdRng,
+    ) -> EnvironmentalConditions {
+        let (first, last) = match (self.samples.first(), self.samples.last()) {
+            (Some(first), Some(last)) => (first.time_s, last.time_s),
+            _ => return base.clone(),
+        };
+        let span = last - first;
+        let time_s = if self.repeat && span > 0.0 && time_s > last {
+            first + (time_s - first).rem_euclid(span)
+        } else {
+            time_s
+        };
+        let held = self.samples.partition_point(|s| s.time_s <= time_s);
+        self.samples[held.saturating_sub(1)].conditions.clone()
+    }
+}
+
+// ─── Scenario Selection ───────────────────────────────────────────────────────
+
+/// Weather model selected by a scenario file.
+///
+/// Serialized as the model's fields plus a `"model"` tag, e.g.
+/// `{"model": "climatology", "zone": "K"}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum WeatherModel {
+    /// Markov chain rain states.
+    Markov(MarkovRainModel),
+    /// ITU rain zone climatology.
+    Climatology(SeasonalClimatology),
+    /// Recorded time series.
+    Replay(HistoricalReplay),
+}
+
+impl WeatherModel {
+    /// Check the selected model; see each model's `validate`.
+    pub fn validate(&self) -> Result<(), WeatherError> {
+        match self {
+            WeatherModel::Markov(model) => model.validate(),
+            WeatherModel::Climatology(model) => model.validate(),
+            WeatherModel::Replay(model) => model.validate(),
+        }
+    }
+
+    /// Fresh generator for one run of the scenario.
+    ///
+    /// - **ID**: FN-WX-002
+    /// - **Requirement**: Scenarios choose their weather model without code
+    ///   changes; each run starts the model from its initial state.
+    /// - **Outputs**: A generator with no state carried over from earlier
+    ///   runs.
+    /// - **Side Effects**: None.
+    pub fn generator(&self) -> Box<dyn WeatherGenerator + Send> {
+        match self {
+            WeatherModel::Markov(model) => Box::new(MarkovRainModel {
+                current: None,
+                ..model.clone()
+            }),
+            WeatherModel::Climatology(model) => Box::new(model.clone()),
+            WeatherModel::Replay(model) => Box::new(model.clone()),
+        }
+    }
+}
@@ -2,15 +2,133 @@
 //!
 //! Tests cover the core public API of `frequency_band_simulation`:
 //! - `FrequencyBand::get_standard_bands()` coverage and field correctness
+//! - `BandLimitations` and `FrequencyRange::center_ghz()`
 //! - `FrequencyBand::simulate_transmission()` — physics properties
 //! - `score_bands_for_conditions()` — ranking logic in clear sky vs storm
 //! - `TransmissionResult` — invariants on computed output fields
 //! - Determinism — identical inputs yield identical outputs
+//! - `deployment::AntennaDeployment` — low-gain limits and deployment outcomes
+//! - `leop::LeopScenario` — canned LEOP timeline and its link conditions
+//! - `tracking` — ground antenna servo limits and keyhole tracking loss
+//! - `occultation` — Earth blockage of inter-satellite links
+//! - `time_transfer` — two-way ISL time transfer and constellation clock offsets
+//! - `formation` — crosslink range and range rate between formation partners
+//! - `traffic` — mission data arrival profiles
+//! - `capacity` — recorder queueing and downlink sizing report
+//! - `record` — versioned simulation records and their exporters
+//! - `scoring` — weighted criterion-of-merit band recommendations
+//! - `sweep` — cross-product parameter sweeps and long-format export
+//! - `monte_carlo` — reproducible per-trial seeding and link availability
+//! - `stats` — streaming running statistics and P² quantile estimates
+//! - `link_budget` — asymmetric uplink/downlink budgets over one pass and
+//!   itemized single-band link budgets
+//! - `locale` — report message catalogs and English fallback
+//! - `margin` — mission link and power margin policy
+//! - `weather` — Markov, climatology and replay weather generators
+//! - `rain_zone` — ITU rain zone lookup by ground station location
+//! - `forecast` — per-contact link capacity forecasts for uplink
+//! - `schema` — JSON Schemas of the public types (`schema` feature)
+//! - `validation` — input range checks and the parameter builder
+//! - `cache` — bounded, least-recently-used simulation result cache
+//! - `fast_math` — fast log/pow approximations and the batched link model
+//! - `progress` — progress reporting and cancellation of long runs
+//! - `deep_space` — light time, timer-based expectations and DSN link budgets
+//! - `shadow` — band-selection and ACM policies evaluated in shadow mode
+//! - `interleaving` — burst error channel, interleaving, randomization and
+//!   residual frame error rates
+//! - `modcod` — MODCOD tables per FEC family and LDPC performance curves
+//! - `receiver` — AGC dynamics, compression and desensitization of the front end
+//! - `antenna_noise` — ground antenna noise temperature against elevation and
+//!   its effect on SNR and contact capacity
+//! - `atmospheric::itu_p618` — ITU-R P.618 rain attenuation in the link model
+//! - `orbit` — TLE parsing, SGP4 propagation and passes over a ground station
+//! - `harq` — NAK bitmaps, combining receiver and hybrid against plain ARQ
+//! - `tuning` — live adjustment of a running simulation and its annotations
 
+use frequency_band_simulation::advanced_rf::{select_amc, AmcConditions, CodingRate, ModulationScheme};
+use frequency_band_simulation::antenna_noise::{
+    AntennaNoiseError, AntennaTemperatureCurve, AntennaTemperaturePoint, GroundAntenna,
+};
+use frequency_band_simulation::atmospheric::itu_p618::{Polarization, RainPath};
+use frequency_band_simulation::cache::SimulationCache;
+use frequency_band_simulation::capacity::{regular_contacts, CapacityStudy, ContactWindow};
+use frequency_band_simulation::deep_space::{
+    light_time_s, round_trip_light_time_s, DeepSpaceLink, DeepSpaceTarget, DsnAntenna,
+    ExpectationStatus, ProtocolMode, ResponseExpectation, MIN_RESPONSE_WINDOW_S,
+};
+use frequency_band_simulation::deployment::{AntennaDeployment, DeploymentConfig, DeploymentState};
+use frequency_band_simulation::doppler::{CarrierLoop, SPEED_OF_LIGHT_M_S};
+use frequency_band_simulation::fast_math::{self, MathPath};
+use frequency_band_simulation::formation::{
+    run_formation_ranging_demo, summarize_ranging, CrosslinkTerminal, FormationRanging,
+};
+use frequency_band_simulation::forecast::{
+    ContactOpportunity, ForecastStudy, LinkForecast, DEFAULT_DEGRADED_PERCENT,
+    MAX_FORECAST_CONTACTS,
+};
+use frequency_band_simulation::harq::{
+    measure_arq, run_harq_demo, ArqConfig, ArqError, ArqScheme, HarqReceiver, NakBitmap,
+};
+use frequency_band_simulation::interleaving::{
+    measure_residual_errors, pseudo_random_sequence, randomize, run_burst_error_demo,
+    BlockInterleaver, CodingChain, CodingError, GilbertElliott, InterleaverConfig, RS_255_223,
+};
+use frequency_band_simulation::leop::{ExpectedCommand, LeopPhase, LeopScenario, LeopStep};
+use frequency_band_simulation::link_budget::{
+    AsymmetricLink, LinkDirection, LINK_CLOSURE_SNR_DB,
+};
+use frequency_band_simulation::locale::{Catalog, Localize, BUILTIN_LANGUAGES};
+use frequency_band_simulation::margin::MarginPolicy;
+use frequency_band_simulation::modcod::{
+    coding_gain_db, ldpc_curve, modcod_table, FecScheme, PerformanceCurve, TARGET_BER,
+};
+use frequency_band_simulation::monte_carlo::{trial_seed, MonteCarlo, STREAM_BATCH_TRIALS};
+use frequency_band_simulation::occultation::{
+    line_of_sight_clear, CircularOrbit, ConstellationLink, LinkEventKind, LinkMonitor,
+    DEFAULT_ATMOSPHERE_MARGIN_KM,
+};
+use frequency_band_simulation::orbit::{Observer, OrbitError, Sgp4, Tle};
+use frequency_band_simulation::progress::{CancelToken, Progress, RunControl, RunError};
+use frequency_band_simulation::rain_zone::rain_rate_0_01_at;
+use frequency_band_simulation::receiver::{
+    pulsed_jammer, FrontEndSummary, ReceivedPower, ReceiverError, ReceiverFrontEnd,
+};
+use frequency_band_simulation::record::{
+    from_json_lines, scenario_hash, to_csv, to_json_lines, RecordError, SimulationRecord,
+    CSV_HEADER, SCHEMA_VERSION,
+};
+use frequency_band_simulation::scoring::{BandScorer, CriteriaWeights, Criterion, Rating};
+use frequency_band_simulation::stats::{P2Quantile, RunningStats, StreamSummary};
+use frequency_band_simulation::shadow::{
+    BestScore, FixedBand, Hysteresis, SelectionPolicy, ShadowEvaluation, WithAcm,
+};
+use frequency_band_simulation::sweep::{
+    range_values, ParameterSweep, SweepField, SweepMetric, SWEEP_BATCH_RUNS,
+};
+use frequency_band_simulation::time_transfer::{
+    run_time_transfer_demo, ClockModel, ConstellationNode, TimeTransferConfig,
+    TimeTransferSimulation, TwoWayExchange,
+};
+use frequency_band_simulation::tracking::{generate_pass, ServoLimits};
+use frequency_band_simulation::traffic::{
+    total_volume_mb, volume_per_interval, DataClass, MissionProfile,
+};
+use frequency_band_simulation::tuning::{
+    Adjustment, Change, LiveTuning, TunableParameter, TuningError,
+};
+use frequency_band_simulation::weather::{
+    HistoricalReplay, ItuRainZone, MarkovRainModel, SeasonalClimatology, WeatherError,
+    WeatherGenerator, WeatherModel,
+};
 use frequency_band_simulation::{
-    score_bands_for_conditions, BandType, EnvironmentalConditions, FrequencyBand,
+    score_bands_for_conditions, BandType, EnvironmentalConditions, FrequencyBand, RainModel,
     TransmissionParameters,
 };
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
@@ -46,6 +164,8 @@ fn leo_params() -> TransmissionParameters {
         elevation_angle_degrees: 45.0,
         transmit_power_watts: 50.0,
         antenna_diameter_meters: 2.0,
+        range_rate_m_s: 0.0,
+        range_acceleration_m_s2: 0.0,
     }
 }
 
@@ -76,12 +196,34 @@ fn test_band_centre_within_range() {
     for band in FrequencyBand::get_standard_bands() {
         let lo = band.frequency_range.min_ghz;
         let hi = band.frequency_range.max_ghz;
-        let center = (lo + hi) / 2.0;
+        let center = band.frequency_range.center_ghz();
+        assert!((center - (lo + hi) / 2.0).abs() < 1e-12);
         assert!(center >= lo && center <= hi,
             "{:?}: centre {:.2} not in [{:.2}, {:.2}]", band.name, center, lo, hi);
     }
 }
 
+/// Higher bands are more rain- and weather-sensitive and need tighter pointing.
+#[test]
+fn test_band_limitations_scale_with_frequency() {
+    let mut bands = FrequencyBand::get_standard_bands();
+    bands.sort_by(|a, b| a.frequency_range.center_ghz().total_cmp(&b.frequency_range.center_ghz()));
+
+    for band in &bands {
+        let l = &band.limitations;
+        assert!((0.0..=1.0).contains(&l.rain_fade_susceptibility), "{:?}", band.name);
+        assert!((0.0..=1.0).contains(&l.weather_dependence), "{:?}", band.name);
+        assert!(l.pointing_accuracy_required > 0.0, "{:?}", band.name);
+    }
+    for pair in bands.windows(2) {
+        let (lower, higher) = (&pair[0].limitations, &pair[1].limitations);
+        assert!(higher.rain_fade_susceptibility > lower.rain_fade_susceptibility,
+            "{:?} must fade more than {:?}", pair[1].name, pair[0].name);
+        assert!(higher.pointing_accuracy_required < lower.pointing_accuracy_required,
+            "{:?} must need tighter pointing than {:?}", pair[1].name, pair[0].name);
+    }
+}
+
 /// Ka-Band must support the highest max data rate of all standard bands.
 #[test]
 fn test_ka_band_has_highest_max_data_rate() {
@@ -141,7 +283,7 @@ fn test_actual_data_rate_does_not_exceed_max() {
     let params = leo_params();
     let env = clear_sky();
     for band in FrequencyBand::get_standard_bands() {
-        let result = band.simulate_transmission(&params, &env);
+        let result = band.simulate_transmission(&params, &env).unwrap();
         assert!(
             result.actual_data_rate_mbps <= band.characteristics.max_data_rate_mbps + 1e-9,
             "{:?}: actual {:.2} Mbps > max {:.2} Mbps",
@@ -157,7 +299,7 @@ fn test_actual_data_rate_does_not_exceed_max() {
 fn test_actual_data_rate_non_negative() {
     for (env_name, env) in [("clear", clear_sky()), ("storm", tropical_storm())] {
         for band in FrequencyBand::get_standard_bands() {
-            let result = band.simulate_transmission(&leo_params(), &env);
+            let result = band.simulate_transmission(&leo_params(), &env).unwrap();
             assert!(
                 result.actual_data_rate_mbps >= 0.0,
                 "{:?} in {}: negative data rate {:.2}",
@@ -172,12 +314,12 @@ fn test_actual_data_rate_non_negative() {
 fn test_higher_distance_increases_latency() {
     let env = clear_sky();
     for band in FrequencyBand::get_standard_bands() {
-        let res_leo = band.simulate_transmission(&leo_params(), &env);
+        let res_leo = band.simulate_transmission(&leo_params(), &env).unwrap();
         let geo_params = TransmissionParameters {
             distance_km: 36_000.0,
             ..leo_params()
         };
-        let res_geo = band.simulate_transmission(&geo_params, &env);
+        let res_geo = band.simulate_transmission(&geo_params, &env).unwrap();
         assert!(
             res_geo.total_latency_ms > res_leo.total_latency_ms,
             "{:?}: GEO latency {:.2} must exceed LEO latency {:.2}",
@@ -193,8 +335,8 @@ fn test_higher_tx_power_improves_or_maintains_snr() {
     for band in FrequencyBand::get_standard_bands() {
         let params_lo = TransmissionParameters { transmit_power_watts: 1.0, ..leo_params() };
         let params_hi = TransmissionParameters { transmit_power_watts: 10_000.0, ..leo_params() };
-        let res_lo = band.simulate_transmission(&params_lo, &env);
-        let res_hi = band.simulate_transmission(&params_hi, &env);
+        let res_lo = band.simulate_transmission(&params_lo, &env).unwrap();
+        let res_hi = band.simulate_transmission(&params_hi, &env).unwrap();
         assert!(
             res_hi.signal_to_noise_ratio_db >= res_lo.signal_to_noise_ratio_db,
             "{:?}: higher power must improve SNR: {:.2} >= {:.2}",
@@ -208,7 +350,7 @@ fn test_higher_tx_power_improves_or_maintains_snr() {
 fn test_path_loss_is_positive() {
     let env = clear_sky();
     for band in FrequencyBand::get_standard_bands() {
-        let result = band.simulate_transmission(&leo_params(), &env);
+        let result = band.simulate_transmission(&leo_params(), &env).unwrap();
         assert!(result.path_loss_db > 0.0, "{:?}: path loss must be > 0", band.name);
     }
 }
@@ -222,7 +364,7 @@ fn test_path_loss_is_positive() {
 #[test]
 fn test_clear_sky_ka_band_scores_highest() {
     let bands = FrequencyBand::get_standard_bands();
-    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
+    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
     assert!(!scores.is_empty());
     assert_eq!(
         scores[0].band, BandType::KaBand,
@@ -238,7 +380,7 @@ fn test_clear_sky_ka_band_scores_highest() {
 #[test]
 fn test_storm_uhf_band_scores_highest() {
     let bands = FrequencyBand::get_standard_bands();
-    let scores = score_bands_for_conditions(&bands, &leo_params(), &tropical_storm());
+    let scores = score_bands_for_conditions(&bands, &leo_params(), &tropical_storm()).unwrap();
     assert!(!scores.is_empty());
     assert_eq!(
         scores[0].band, BandType::UHFBand,
@@ -253,7 +395,7 @@ fn test_storm_uhf_band_scores_highest() {
 #[test]
 fn test_score_count_equals_input_count() {
     let bands = FrequencyBand::get_standard_bands();
-    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
+    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
     assert_eq!(scores.len(), bands.len(),
         "score_bands_for_conditions must return one score per input band");
 }
@@ -262,7 +404,7 @@ fn test_score_count_equals_input_count() {
 #[test]
 fn test_scores_are_sorted_descending() {
     let bands = FrequencyBand::get_standard_bands();
-    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
+    let scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
     for win in scores.windows(2) {
         assert!(win[0].composite_score >= win[1].composite_score,
             "Scores not sorted descending: {:.4} < {:.4}", win[0].composite_score, win[1].composite_score);
@@ -274,7 +416,7 @@ fn test_scores_are_sorted_descending() {
 fn test_composite_scores_in_unit_range() {
     let bands = FrequencyBand::get_standard_bands();
     for env in [clear_sky(), tropical_storm()] {
-        let scores = score_bands_for_conditions(&bands, &leo_params(), &env);
+        let scores = score_bands_for_conditions(&bands, &leo_params(), &env).unwrap();
         for s in &scores {
             assert!(s.composite_score >= 0.0 && s.composite_score <= 1.0,
                 "{:?}: composite score {:.4} not in [0, 1]", s.band, s.composite_score);
@@ -286,8 +428,8 @@ fn test_composite_scores_in_unit_range() {
 #[test]
 fn test_scoring_is_deterministic() {
     let bands = FrequencyBand::get_standard_bands();
-    let first = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
-    let second = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
+    let first = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
+    let second = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
     for (a, b) in first.iter().zip(second.iter()) {
         assert_eq!(a.band, b.band);
         assert_eq!(a.composite_score, b.composite_score,
@@ -300,7 +442,7 @@ fn test_scoring_is_deterministic() {
 fn test_achievable_rates_non_negative_all_envs() {
     let bands = FrequencyBand::get_standard_bands();
     for env in [clear_sky(), tropical_storm()] {
-        for score in score_bands_for_conditions(&bands, &leo_params(), &env) {
+        for score in score_bands_for_conditions(&bands, &leo_params(), &env).unwrap() {
             assert!(score.achievable_rate_mbps >= 0.0,
                 "{:?}: achievable rate must be >= 0", score.band);
         }
@@ -315,7 +457,7 @@ fn test_all_bands_meet_lenient_requirement_in_clear_sky() {
         required_data_rate_mbps: 0.001, // near-zero requirement
         ..leo_params()
     };
-    let scores = score_bands_for_conditions(&bands, &lenient, &clear_sky());
+    let scores = score_bands_for_conditions(&bands, &lenient, &clear_sky()).unwrap();
     for s in &scores {
         assert!(s.meets_requirement,
             "{:?} must meet a 0.001 Mbps requirement in clear sky", s.band);
@@ -327,11 +469,2644 @@ fn test_all_bands_meet_lenient_requirement_in_clear_sky() {
 fn test_geo_scores_lower_than_leo() {
     let bands = FrequencyBand::get_standard_bands();
     let geo_params = TransmissionParameters { distance_km: 36_000.0, ..leo_params() };
-    let leo_scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky());
-    let geo_scores = score_bands_for_conditions(&bands, &geo_params, &clear_sky());
+    let leo_scores = score_bands_for_conditions(&bands, &leo_params(), &clear_sky()).unwrap();
+    let geo_scores = score_bands_for_conditions(&bands, &geo_params, &clear_sky()).unwrap();
     assert!(
         leo_scores[0].composite_score > geo_scores[0].composite_score,
         "LEO best score ({:.4}) must exceed GEO best ({:.4})",
         leo_scores[0].composite_score, geo_scores[0].composite_score
     );
 }
+
+// ─── Antenna Deployment Tests ─────────────────────────────────────────────────
+
+fn x_band() -> FrequencyBand {
+    FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|b| b.name == BandType::XBand)
+        .unwrap()
+}
+
+/// A stowed antenna must be limited to the low-gain path and degrade SNR.
+#[test]
+fn test_stowed_antenna_limited_to_low_gain() {
+    let band = x_band();
+    let antenna = AntennaDeployment::new(DeploymentConfig::default());
+    assert_eq!(
+        antenna.effective_gain_dbi(&band),
+        DeploymentConfig::default().low_gain_antenna_dbi
+    );
+
+    let nominal = band.simulate_transmission(&leo_params(), &clear_sky()).unwrap();
+    let stowed = antenna.simulate_transmission(&band, &leo_params(), &clear_sky()).unwrap();
+    assert!(stowed.signal_to_noise_ratio_db < nominal.signal_to_noise_ratio_db);
+}
+
+/// Deployment must take the commanded time before gain improves.
+#[test]
+fn test_deployment_takes_commanded_time() {
+    let band = x_band();
+    let config = DeploymentConfig {
+        failure_probability: 0.0,
+        partial_failure_probability: 0.0,
+        ..DeploymentConfig::default()
+    };
+    let mut antenna = AntennaDeployment::new(config);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // 90° at 3°/s → 30 s
+    assert!(antenna.command_deploy(100.0, 90.0, 3.0));
+    assert!(!antenna.command_deploy(101.0, 90.0, 3.0), "second command must be refused");
+    assert!(matches!(antenna.advance(129.9, &mut rng), DeploymentState::Deploying { .. }));
+    assert_eq!(antenna.gain_fraction(), 0.0);
+
+    assert_eq!(antenna.advance(130.0, &mut rng), DeploymentState::Deployed);
+    assert_eq!(antenna.effective_gain_dbi(&band), band.characteristics.antenna_gain_dbi);
+}
+
+/// Certain failure leaves the low-gain path; certain stall yields partial gain.
+#[test]
+fn test_deployment_failure_outcomes() {
+    let band = x_band();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut failing = AntennaDeployment::new(DeploymentConfig {
+        failure_probability: 1.0,
+        ..DeploymentConfig::default()
+    });
+    failing.command_deploy(0.0, 90.0, 9.0);
+    assert_eq!(failing.advance(10.0, &mut rng), DeploymentState::Failed);
+    assert_eq!(failing.gain_fraction(), 0.0);
+
+    let mut stalling = AntennaDeployment::new(DeploymentConfig {
+        failure_probability: 0.0,
+        partial_failure_probability: 1.0,
+        ..DeploymentConfig::default()
+    });
+    stalling.command_deploy(0.0, 90.0, 9.0);
+    assert!(matches!(stalling.advance(10.0, &mut rng), DeploymentState::PartiallyDeployed { .. }));
+    let gain = stalling.effective_gain_dbi(&band);
+    assert!(gain > DeploymentConfig::default().low_gain_antenna_dbi);
+    assert!(gain <= band.characteristics.antenna_gain_dbi);
+}
+
+// ─── LEOP Scenario Tests ──────────────────────────────────────────────────────
+
+/// The canned timeline must be time-ordered and cover every LEOP phase.
+#[test]
+fn test_leop_timeline_covers_all_phases() {
+    let scenario = LeopScenario::standard();
+    assert!(scenario.events.windows(2).all(|w| w[0].time_s <= w[1].time_s));
+
+    for phase in [
+        LeopPhase::Separation,
+        LeopPhase::Detumble,
+        LeopPhase::FirstAcquisition,
+        LeopPhase::Deployment,
+        LeopPhase::Commissioning,
+    ] {
+        assert!(scenario.events.iter().any(|e| e.phase == phase), "{:?} missing", phase);
+    }
+
+    let first_contact = scenario.events.iter().find(|e| e.contact.is_some()).unwrap();
+    assert_eq!(first_contact.phase, LeopPhase::FirstAcquisition);
+    assert_eq!(first_contact.contact.as_ref().unwrap().band, BandType::UHFBand);
+}
+
+/// With a reliable mechanism, contacts before deployment use the low-gain
+/// path and commissioning passes see the deployed antenna.
+#[test]
+fn test_leop_run_follows_deployment_state() {
+    let mut scenario = LeopScenario::standard();
+    scenario.deployment.failure_probability = 0.0;
+    scenario.deployment.partial_failure_probability = 0.0;
+    // The stowed low-gain path closes, but short of the default 3 dB margin
+    scenario.margins = MarginPolicy::closure_only();
+
+    let steps = scenario.run(&mut StdRng::seed_from_u64(1)).unwrap();
+    assert_eq!(steps.len(), scenario.events.len());
+
+    for step in &steps {
+        assert_eq!(step.link.is_some(), step.event.contact.is_some());
+        match step.event.phase {
+            LeopPhase::FirstAcquisition => {
+                assert_eq!(step.deployment_state, DeploymentState::Stowed);
+                assert!(step.link.as_ref().unwrap().success, "first UHF acquisition must close");
+            }
+            LeopPhase::Commissioning => {
+                assert_eq!(step.deployment_state, DeploymentState::Deployed);
+                assert!(step
+                    .event
+                    .expected_commands
+                    .iter()
+                    .any(|c| matches!(c, ExpectedCommand::ReconfigureComm { .. })));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A failed deployment must leave commissioning passes on the low-gain path.
+#[test]
+fn test_leop_run_with_failed_deployment() {
+    let mut scenario = LeopScenario::standard();
+    scenario.deployment.failure_probability = 1.0;
+
+    let steps = scenario.run(&mut StdRng::seed_from_u64(1)).unwrap();
+    let last = steps.last().unwrap();
+    assert_eq!(last.event.phase, LeopPhase::Commissioning);
+    assert_eq!(last.deployment_state, DeploymentState::Failed);
+    assert_eq!(ExpectedCommand::DeploySolarArray.command_name(), "Deploy");
+}
+
+// ─── Ground Antenna Tracking Tests ────────────────────────────────────────────
+
+/// A pass culminating below the mask must produce no samples.
+#[test]
+fn test_pass_below_mask_is_empty() {
+    assert!(generate_pass(550.0, 4.0, 5.0, 1.0).is_empty());
+    let pass = generate_pass(550.0, 45.0, 5.0, 1.0);
+    assert!(!pass.is_empty());
+    let peak = pass.iter().map(|s| s.pointing.elevation_deg).fold(0.0, f64::max);
+    assert!((peak - 45.0).abs() < 0.5, "culmination {:.2}° must match request", peak);
+}
+
+/// A moderate-elevation pass must be tracked end to end.
+#[test]
+fn test_moderate_pass_fully_tracked() {
+    let pass = generate_pass(550.0, 40.0, 5.0, 1.0);
+    let report = ServoLimits::default().track_pass(&pass);
+    assert!(report.loss_windows.is_empty(), "max error {:.3}°", report.max_pointing_error_deg);
+    assert_eq!(report.achievable_contact_s, report.pass_duration_s);
+}
+
+/// A near-zenith pass must hit the azimuth keyhole and lose contact time.
+#[test]
+fn test_zenith_pass_keyhole_loss() {
+    let pass = generate_pass(550.0, 89.5, 5.0, 1.0);
+    let report = ServoLimits::default().track_pass(&pass);
+    assert!(!report.loss_windows.is_empty());
+    assert!(report.achievable_contact_s < report.pass_duration_s);
+
+    // Loss must occur around culmination, not at the horizons
+    let mid = report.pass_duration_s / 2.0;
+    for window in &report.loss_windows {
+        assert!((window.start_s - mid).abs() < 60.0, "{:?} far from culmination", window);
+    }
+}
+
+// ─── Earth Blockage Tests ─────────────────────────────────────────────────────
+
+fn leo_node(phase_deg: f64) -> CircularOrbit {
+    CircularOrbit { altitude_km: 550.0, inclination_deg: 53.0, raan_deg: 0.0, phase_deg }
+}
+
+/// Nodes on opposite sides of the Earth are blocked; neighbours are not.
+#[test]
+fn test_line_of_sight_geometry() {
+    let a = leo_node(0.0).position(0.0);
+    assert!(line_of_sight_clear(a, leo_node(20.0).position(0.0), DEFAULT_ATMOSPHERE_MARGIN_KM));
+    assert!(!line_of_sight_clear(a, leo_node(180.0).position(0.0), DEFAULT_ATMOSPHERE_MARGIN_KM));
+    // A larger margin blocks links that graze the upper atmosphere
+    let grazing = leo_node(40.0).position(0.0);
+    assert!(line_of_sight_clear(a, grazing, 0.0));
+    assert!(!line_of_sight_clear(a, grazing, 600.0));
+}
+
+/// Co-planar nodes at fixed separation never change visibility.
+#[test]
+fn test_same_plane_link_stays_up() {
+    let link = ConstellationLink { from: leo_node(0.0), to: leo_node(30.0) };
+    let mut monitor = LinkMonitor::new(vec![link], DEFAULT_ATMOSPHERE_MARGIN_KM);
+    let period = leo_node(0.0).period_s();
+    assert!(monitor.run(0.0, period, 30.0).is_empty());
+    assert_eq!(monitor.is_visible(0), Some(true));
+}
+
+/// A relay link to a higher, slower node must alternate occultation and
+/// restoration events as the phase between the nodes drifts.
+#[test]
+fn test_drifting_relay_link_emits_events() {
+    let relay = CircularOrbit { altitude_km: 1200.0, ..leo_node(0.0) };
+    let link = ConstellationLink { from: leo_node(0.0), to: relay };
+    let mut monitor = LinkMonitor::new(vec![link], DEFAULT_ATMOSPHERE_MARGIN_KM);
+    let events = monitor.run(0.0, 20.0 * leo_node(0.0).period_s(), 10.0);
+
+    assert!(events.iter().any(|e| e.kind == LinkEventKind::Occulted));
+    assert!(events.iter().any(|e| e.kind == LinkEventKind::Restored));
+    assert!(events.windows(2).all(|w| w[0].kind != w[1].kind && w[0].time_s < w[1].time_s));
+}
+
+// ─── Constellation Time Transfer Tests ───────────────────────────────────────
+
+fn clocked_node(name: &str, phase_deg: f64, initial_offset_s: f64, drift_ppm: f64) -> ConstellationNode {
+    ConstellationNode {
+        name: name.to_string(),
+        orbit: CircularOrbit { altitude_km: 1200.0, ..leo_node(phase_deg) },
+        clock: ClockModel { initial_offset_s, drift_ppm, timestamp_jitter_s: 10e-9 },
+    }
+}
+
+/// Symmetric legs cancel the delay; the offset is half the leg difference.
+#[test]
+fn test_two_way_exchange_offset_and_delay() {
+    // Responder 1 ms ahead, 25 ms each way, 10 ms turnaround
+    let exchange = TwoWayExchange { t1: 100.0, t2: 100.026, t3: 100.036, t4: 100.060 };
+    assert!((exchange.offset_s() - 0.001).abs() < 1e-9);
+    assert!((exchange.delay_s() - 0.025).abs() < 1e-9);
+}
+
+/// A chain of drifting clocks must settle on the reference timescale, with
+/// the reference itself at zero offset, and repeat exactly for a seed.
+#[test]
+fn test_time_transfer_chain_converges() {
+    let nodes = vec![
+        clocked_node("A", 0.0, 0.0, 0.0),
+        clocked_node("B", 30.0, 1.5e-3, 0.4),
+        clocked_node("C", 60.0, -0.7e-3, -0.3),
+    ];
+    let sim = TimeTransferSimulation::new(
+        nodes,
+        vec![(0, 1), (1, 2)],
+        0,
+        TimeTransferConfig::default(),
+    );
+    let report = sim.run(0.0, 3600.0);
+    assert_eq!(report.samples.len(), 61 * 3);
+
+    let settled: Vec<_> = report.samples.iter().filter(|s| s.time_s >= 1800.0).collect();
+    assert!(settled.iter().filter(|s| s.node == 0).all(|s| s.offset_s == 0.0));
+    assert!(settled.iter().all(|s| s.offset_s.abs() < 200e-9));
+    assert!(settled.iter().filter(|s| s.node == 2).all(|s| s.hops == Some(2)));
+    let delay = settled[1].measured_delay_s.unwrap();
+    assert!(delay > 0.01 && delay < 0.02, "30° at 1200 km is ~13 ms one way: {delay}");
+
+    let summary = report.summary();
+    assert_eq!(summary[2].name, "C");
+    assert_eq!(summary[2].free_running_epochs, 0);
+    assert_eq!(sim.run(0.0, 3600.0).samples, report.samples);
+}
+
+/// A node whose only link is blocked by the Earth keeps free-running.
+#[test]
+fn test_time_transfer_blocked_node_free_runs() {
+    let nodes = vec![clocked_node("A", 0.0, 0.0, 0.0), clocked_node("B", 180.0, 1e-3, 0.5)];
+    let report = TimeTransferSimulation::new(
+        nodes,
+        vec![(0, 1)],
+        0,
+        TimeTransferConfig::default(),
+    )
+    .run(0.0, 600.0);
+
+    let summary = report.summary();
+    assert_eq!(summary[1].free_running_epochs, 11);
+    assert!((summary[1].final_offset_s - (1e-3 + 0.5e-6 * 600.0)).abs() < 1e-12);
+
+    // The demo constellation holds every ring satellite within 1 µs at the end
+    let demo = run_time_transfer_demo().summary();
+    assert!(demo[..6].iter().all(|n| n.final_offset_s.abs() < 1e-6));
+    assert!(demo[6].free_running_epochs > 0);
+}
+
+// ─── Formation Ranging Tests ─────────────────────────────────────────────────
+
+/// C/N0 falls 6 dB per range doubling, and the sigmas grow with it.
+#[test]
+fn test_crosslink_ranging_noise_scales_with_range() {
+    let terminal = CrosslinkTerminal::default();
+    let (near, far) = (terminal.cn0_dbhz(1000.0), terminal.cn0_dbhz(2000.0));
+    assert!((near - far - 20.0 * 2f64.log10()).abs() < 1e-9);
+
+    let (range_near, rate_near) = terminal.sigmas(near);
+    let (range_far, rate_far) = terminal.sigmas(far);
+    assert!((range_far / range_near - 2.0).abs() < 1e-9);
+    assert!((rate_far / rate_near - 2.0).abs() < 1e-9);
+}
+
+/// Measured errors match the predicted sigmas and repeat for a seed.
+#[test]
+fn test_formation_ranging_errors_match_sigmas() {
+    let (samples, summary) = run_formation_ranging_demo();
+    assert_eq!(summary.locked_epochs, summary.epochs);
+    assert!(summary.range_span_m.0 > 500.0 && summary.range_span_m.1 < 2000.0);
+    let range_ratio = summary.range_rms_error_m / summary.mean_range_sigma_m;
+    let rate_ratio = summary.range_rate_rms_error_m_s / summary.mean_range_rate_sigma_m_s;
+    assert!((range_ratio - 1.0).abs() < 0.15, "range RMS/sigma {range_ratio}");
+    assert!((rate_ratio - 1.0).abs() < 0.15, "range rate RMS/sigma {rate_ratio}");
+    assert_eq!(run_formation_ranging_demo().0, samples);
+}
+
+/// Partners beyond the lock range, or behind the Earth, give no measurement.
+#[test]
+fn test_formation_ranging_loses_lock() {
+    let ranging = FormationRanging {
+        chief: leo_node(0.0),
+        deputy: leo_node(20.0),
+        terminal: CrosslinkTerminal::default(),
+        seed: 1,
+    };
+    let samples = ranging.run(0.0, 60.0, 10.0);
+    assert!(samples.iter().all(|s| s.measured.is_none()));
+    assert!(samples.iter().all(|s| s.cn0_dbhz < ranging.terminal.lock_threshold_dbhz));
+
+    let blocked = FormationRanging { deputy: leo_node(180.0), ..ranging };
+    let terminal = CrosslinkTerminal { lock_threshold_dbhz: f64::NEG_INFINITY, ..ranging.terminal };
+    let blocked = FormationRanging { terminal, ..blocked };
+    assert_eq!(summarize_ranging(&blocked.run(0.0, 60.0, 10.0)).locked_epochs, 0);
+}
+
+// ─── Traffic Generation Tests ─────────────────────────────────────────────────
+
+/// Arrivals must be time-ordered, in range, and repeatable for a given seed.
+#[test]
+fn test_traffic_generation_ordered_and_deterministic() {
+    let profile = MissionProfile::earth_observation();
+    let day = 86_400.0;
+    let a = profile.generate(day, &mut StdRng::seed_from_u64(3));
+    let b = profile.generate(day, &mut StdRng::seed_from_u64(3));
+
+    assert_eq!(a.len(), b.len());
+    assert!(a.windows(2).all(|w| w[0].time_s <= w[1].time_s));
+    assert!(a.iter().all(|x| (0.0..day).contains(&x.time_s)));
+    for class in [DataClass::Housekeeping, DataClass::Science] {
+        assert!(a.iter().any(|x| x.class == class), "{:?} missing", class);
+    }
+}
+
+/// Periodic housekeeping must produce exactly one product per period.
+#[test]
+fn test_periodic_housekeeping_volume() {
+    let profile = MissionProfile::housekeeping_only();
+    let arrivals = profile.generate(3600.0, &mut StdRng::seed_from_u64(0));
+    assert_eq!(arrivals.len(), 60);
+    assert!((total_volume_mb(&arrivals) - 3.0).abs() < 1e-9);
+
+    let buckets = volume_per_interval(&arrivals, 600.0);
+    assert_eq!(buckets.len(), 6);
+    assert!(buckets.iter().all(|v| (v - 0.5).abs() < 1e-9));
+}
+
+/// Over a long run the generated volume must approach the analytic mean rate.
+#[test]
+fn test_traffic_volume_matches_mean_rate() {
+    let profile = MissionProfile::earth_observation();
+    let duration = 30.0 * 86_400.0;
+    let arrivals = profile.generate(duration, &mut StdRng::seed_from_u64(11));
+    let expected = profile.mean_rate_mb_s() * duration;
+    let ratio = total_volume_mb(&arrivals) / expected;
+    assert!((0.85..1.15).contains(&ratio), "volume ratio {:.3}", ratio);
+}
+
+// ─── Capacity Sizing Tests ────────────────────────────────────────────────────
+
+/// Ample X-band contacts must deliver everything generated before the last pass.
+#[test]
+fn test_capacity_sustainable_schedule() {
+    let day = 86_400.0;
+    let arrivals = MissionProfile::earth_observation().generate(3.0 * day, &mut StdRng::seed_from_u64(5));
+    let contacts = regular_contacts(3.0 * day, 6, 600.0, BandType::XBand);
+    let report = CapacityStudy::default().analyse(&arrivals, &contacts, 3.0 * day);
+
+    assert!(report.is_sustainable(), "{}", report);
+    assert!(report.delivered_mb <= report.offered_mb + 1e-6);
+    assert!(report.delay.p50_s <= report.delay.p90_s && report.delay.p90_s <= report.delay.max_s);
+    assert_eq!(report.bands.len(), 1);
+    assert!(report.bands[0].required_rate_alone_mbps < report.bands[0].rate_mbps);
+}
+
+/// A UHF-only schedule cannot keep up with imaging and must show backlog growth.
+#[test]
+fn test_capacity_undersized_schedule_backlog_grows() {
+    let day = 86_400.0;
+    let arrivals = MissionProfile::earth_observation().generate(3.0 * day, &mut StdRng::seed_from_u64(5));
+    let contacts = regular_contacts(3.0 * day, 4, 480.0, BandType::UHFBand);
+    let report = CapacityStudy::default().analyse(&arrivals, &contacts, 3.0 * day);
+
+    assert!(!report.is_sustainable());
+    assert!(report.utilisation > 1.0);
+    assert!(report.backlog_growth_mb_per_day > 0.0);
+    assert!(report.undelivered_products > 0);
+}
+
+/// Alerts must be served ahead of queued science data.
+#[test]
+fn test_capacity_priority_order() {
+    use frequency_band_simulation::traffic::{DataArrival, DataClass};
+    let arrivals = vec![
+        DataArrival { time_s: 0.0, source: 0, class: DataClass::Science, size_mb: 100.0 },
+        DataArrival { time_s: 1.0, source: 1, class: DataClass::Alert, size_mb: 1.0 },
+    ];
+    // 8 Mbps = 1 MB/s, contact starts after both arrivals
+    let mut study = CapacityStudy::default();
+    study.rates_mbps.insert(BandType::SBand, 8.0);
+    let contacts = [ContactWindow { start_s: 10.0, end_s: 200.0, band: BandType::SBand }];
+    let report = study.analyse(&arrivals, &contacts, 200.0);
+
+    let alert = report.delay_by_class.iter().find(|(c, _)| *c == DataClass::Alert).unwrap();
+    assert!((alert.1.max_s - 10.0).abs() < 1e-9, "alert delay {}", alert.1.max_s);
+    assert_eq!(report.undelivered_products, 0);
+    assert!(report.to_string().contains("Downlink Capacity Report"));
+}
+
+// ─── Simulation Record Tests ──────────────────────────────────────────────────
+
+fn x_band_record() -> SimulationRecord {
+    let band = x_band();
+    let result = band.simulate_transmission(&leo_params(), &clear_sky()).unwrap();
+    SimulationRecord::new(band.name, leo_params(), clear_sky(), result, 1_700_000_000_000)
+}
+
+/// Records survive a JSON Lines round trip with their metadata intact.
+#[test]
+fn test_record_json_lines_round_trip() {
+    let storm = SimulationRecord::simulate(&x_band(), &leo_params(), &tropical_storm()).unwrap();
+    let records = vec![x_band_record(), storm];
+    let text = to_json_lines(&records).unwrap();
+    assert_eq!(text.lines().count(), 2);
+
+    let decoded = from_json_lines(&text).unwrap();
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(decoded[0].schema_version, SCHEMA_VERSION);
+    assert_eq!(decoded[0].timestamp_unix_ms, 1_700_000_000_000);
+    assert_eq!(decoded[0].band, BandType::XBand);
+    assert_eq!(decoded[0].scenario_hash, records[0].scenario_hash);
+    assert_eq!(decoded[0].result.path_loss_db, records[0].result.path_loss_db);
+    assert!(decoded[1].timestamp_unix_ms > 0);
+}
+
+/// Readers refuse records written with a different schema version.
+#[test]
+fn test_record_rejects_unknown_schema() {
+    let mut record = x_band_record();
+    record.schema_version = SCHEMA_VERSION + 1;
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(matches!(
+        SimulationRecord::from_json(&json),
+        Err(RecordError::UnsupportedSchema(v)) if v == SCHEMA_VERSION + 1
+    ));
+    assert!(matches!(SimulationRecord::from_json("{}"), Err(RecordError::Json(_))));
+}
+
+/// The scenario hash is stable and changes with any input.
+#[test]
+fn test_scenario_hash_identifies_inputs() {
+    let hash = scenario_hash(BandType::XBand, &leo_params(), &clear_sky());
+    assert_eq!(hash.len(), 16);
+    assert_eq!(hash, scenario_hash(BandType::XBand, &leo_params(), &clear_sky()));
+    assert_ne!(hash, scenario_hash(BandType::SBand, &leo_params(), &clear_sky()));
+    assert_ne!(hash, scenario_hash(BandType::XBand, &leo_params(), &tropical_storm()));
+
+    let mut params = leo_params();
+    params.distance_km += 1.0;
+    assert_ne!(hash, scenario_hash(BandType::XBand, &params, &clear_sky()));
+}
+
+/// CSV rows line up with the header.
+#[test]
+fn test_record_csv_matches_header() {
+    let record = x_band_record();
+    let csv = to_csv(std::slice::from_ref(&record));
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(CSV_HEADER));
+
+    let columns = CSV_HEADER.split(',').count();
+    let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(row.len(), columns);
+    assert_eq!(row[0], SCHEMA_VERSION.to_string());
+    assert_eq!(row[2], "X-Band");
+    assert_eq!(row[3], record.scenario_hash);
+    assert!(lines.next().is_none());
+}
+
+// ─── Band Recommendation Scoring Tests ────────────────────────────────────────
+
+/// Every band is ranked once, best first, with scores in [0, 1].
+#[test]
+fn test_band_scorer_ranks_all_bands() {
+    let bands = FrequencyBand::get_standard_bands();
+    let recs = BandScorer::default().recommend(&bands, &leo_params()).unwrap();
+
+    assert_eq!(recs.len(), bands.len());
+    for pair in recs.windows(2) {
+        assert!(pair[0].overall >= pair[1].overall);
+    }
+    for rec in &recs {
+        for criterion in Criterion::ALL {
+            let score = rec.scores.get(criterion);
+            assert!((0.0..=1.0).contains(&score), "{:?} {:?} = {}", rec.band, criterion, score);
+        }
+        assert_eq!(rec.rating, Rating::from_score(rec.overall));
+    }
+    // Relative criteria give the best band in each a full score
+    assert!(recs.iter().any(|r| (r.scores.throughput - 1.0).abs() < 1e-12));
+    assert!(recs.iter().any(|r| (r.scores.power - 1.0).abs() < 1e-12));
+}
+
+/// Weighting a single criterion ranks bands by that criterion alone.
+#[test]
+fn test_band_scorer_single_criterion_weights() {
+    let bands = FrequencyBand::get_standard_bands();
+    let none = CriteriaWeights {
+        availability: 0.0,
+        throughput: 0.0,
+        power: 0.0,
+        latency: 0.0,
+        weather_robustness: 0.0,
+    };
+
+    let by_throughput = BandScorer::new(CriteriaWeights { throughput: 1.0, ..none })
+        .recommend(&bands, &leo_params()).unwrap();
+    assert_eq!(by_throughput[0].band, BandType::KaBand);
+    assert!((by_throughput[0].overall - by_throughput[0].scores.throughput).abs() < 1e-12);
+
+    // Rain fades high bands most: UHF is the most robust, Ka the least
+    let by_weather = BandScorer::new(CriteriaWeights { weather_robustness: 1.0, ..none })
+        .with_environments(vec![clear_sky(), tropical_storm()])
+        .recommend(&bands, &leo_params()).unwrap();
+    assert_eq!(by_weather[0].band, BandType::UHFBand);
+    assert_eq!(by_weather.last().unwrap().band, BandType::KaBand);
+}
+
+/// Degenerate weights and empty environment sets score zero rather than NaN.
+#[test]
+fn test_band_scorer_degenerate_inputs() {
+    let bands = FrequencyBand::get_standard_bands();
+    let zero = CriteriaWeights {
+        availability: 0.0,
+        throughput: 0.0,
+        power: 0.0,
+        latency: 0.0,
+        weather_robustness: 0.0,
+    };
+    for rec in BandScorer::new(zero).recommend(&bands, &leo_params()).unwrap() {
+        assert_eq!(rec.overall, 0.0);
+        assert_eq!(rec.rating, Rating::Poor);
+    }
+    for rec in BandScorer::default().with_environments(vec![]).recommend(&bands, &leo_params()).unwrap() {
+        assert!(!rec.overall.is_nan());
+        assert_eq!(rec.scores.availability, 0.0);
+    }
+}
+
+// ─── Parameter Sweep Tests ────────────────────────────────────────────────────
+
+/// Inclusive ranges keep decimal end points; invalid steps give the start only.
+#[test]
+fn test_sweep_range_values() {
+    assert_eq!(range_values(0.0, 1.0, 0.25), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(range_values(0.1, 0.3, 0.1).len(), 3);
+    assert_eq!(range_values(0.0, 10.0, 4.0), vec![0.0, 4.0, 8.0]);
+    assert_eq!(range_values(5.0, 1.0, 1.0), vec![5.0]);
+    assert_eq!(range_values(0.0, 1.0, 0.0), vec![0.0]);
+    assert_eq!(range_values(0.0, f64::INFINITY, 1.0), vec![0.0]);
+}
+
+/// Every band runs at every point of the cross product, last axis fastest.
+#[test]
+fn test_sweep_cross_product() {
+    let bands = FrequencyBand::get_standard_bands();
+    let sweep = ParameterSweep::new(leo_params(), clear_sky())
+        .with_bands(vec![bands[2].clone(), bands[3].clone()])
+        .with_range(SweepField::RainRateMmHour, 0.0, 20.0, 10.0)
+        .with_values(SweepField::DistanceKm, vec![500.0, 2000.0])
+        .with_values(SweepField::DistanceKm, vec![500.0, 1000.0]);
+    assert_eq!(sweep.point_count(), 6);
+    assert_eq!(sweep.run_count(), 12);
+
+    let table = sweep.run().unwrap();
+    assert_eq!(table.fields, vec![SweepField::RainRateMmHour, SweepField::DistanceKm]);
+    assert_eq!(table.runs.len(), 12);
+
+    let run = &table.runs[7];
+    assert_eq!((run.point, run.band), (3, BandType::XBand));
+    assert_eq!(run.environment.rain_rate_mm_hour, 10.0);
+    assert_eq!(run.parameters.distance_km, 1000.0);
+    // Unswept fields keep their base value
+    assert_eq!(run.parameters.transmit_power_watts, leo_params().transmit_power_watts);
+
+    let (parameters, environment) = sweep.scenario(run.point);
+    let expected = bands[3].simulate_transmission(&parameters, &environment).unwrap();
+    assert_eq!(run.result.signal_to_noise_ratio_db, expected.signal_to_noise_ratio_db);
+
+    // Rain degrades the link at fixed distance
+    let snr = |point: usize| table.runs[point * 2 + 1].result.signal_to_noise_ratio_db;
+    assert!(snr(0) > snr(2) && snr(2) > snr(4));
+}
+
+/// The long format has one row per run and metric and one column per swept field.
+#[test]
+fn test_sweep_long_format_export() {
+    let table = ParameterSweep::new(leo_params(), clear_sky())
+        .with_range(SweepField::TransmitPowerWatts, 10.0, 30.0, 10.0)
+        .run().unwrap();
+    assert_eq!(table.rows().count(), 3 * 5 * SweepMetric::ALL.len());
+
+    let csv = table.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("point,band,transmit_power_watts,metric,value"));
+    let first: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(first[..4], ["0", "K-Band", "10", "success"]);
+    assert_eq!(lines.count(), table.rows().count() - 1);
+
+    let records = table.to_records(1_000);
+    assert_eq!(records.len(), table.runs.len());
+    assert_eq!(records[0].parameters.transmit_power_watts, 10.0);
+
+    // No axes is the base scenario alone
+    assert_eq!(ParameterSweep::new(leo_params(), clear_sky()).run().unwrap().runs.len(), 5);
+}
+
+// ─── Monte Carlo Tests ────────────────────────────────────────────────────────
+
+/// Trial draws depend only on the study seed and trial index.
+#[test]
+fn test_monte_carlo_reproducible() {
+    let study = MonteCarlo::new(200, 42);
+    let draw = |trial: usize, rng: &mut StdRng| (trial, rng.gen::<u64>());
+
+    let a = study.run(draw);
+    assert_eq!(a.len(), 200);
+    assert!(a.iter().enumerate().all(|(i, (trial, _))| *trial == i));
+    assert_eq!(a, study.run(draw));
+    // Thread count does not change the results
+    assert_eq!(a, study.with_threads(3).run(draw));
+
+    // Any single trial can be replayed on its own
+    assert_eq!(a[57].1, study.trial_rng(57).gen::<u64>());
+
+    // Trials and studies get distinct streams
+    assert_ne!(a[0].1, a[1].1);
+    assert_ne!(a, MonteCarlo::new(200, 43).run(draw));
+    assert_ne!(trial_seed(0, 1), trial_seed(1, 0));
+}
+
+/// Availability falls as the rain distribution gets heavier.
+#[test]
+fn test_monte_carlo_link_availability() {
+    let bands = FrequencyBand::get_standard_bands();
+    let ka = &bands[1];
+    assert_eq!(ka.name, BandType::KaBand);
+    let study = MonteCarlo::new(300, 7);
+    let rainy = |max_rain: f64| {
+        move |rng: &mut StdRng| EnvironmentalConditions {
+            rain_rate_mm_hour: rng.gen_range(0.0..=max_rain),
+            ..clear_sky()
+        }
+    };
+
+    let dry = study.link_availability(ka, &leo_params(), rainy(0.0)).unwrap();
+    assert_eq!(dry.trials, 300);
+    assert_eq!(dry.availability(), 1.0);
+
+    let wet = study.link_availability(ka, &leo_params(), rainy(100.0)).unwrap();
+    assert!(wet.availability() < dry.availability());
+    assert!(wet.mean_snr_db < dry.mean_snr_db);
+    assert_eq!(wet, study.link_availability(ka, &leo_params(), rainy(100.0)).unwrap());
+
+    let empty = MonteCarlo::new(0, 7).link_availability(ka, &leo_params(), rainy(0.0)).unwrap();
+    assert_eq!((empty.availability(), empty.mean_snr_db), (0.0, 0.0));
+}
+
+// ─── Streaming Statistics Tests ───────────────────────────────────────────────
+
+/// Running statistics match the two-pass values and merge exactly.
+#[test]
+fn test_running_stats_welford_and_merge() {
+    let samples: Vec<f64> = (0..1000).map(|i| 1e9 + (i % 17) as f64 * 0.5).collect();
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    let mut all = RunningStats::new();
+    samples.iter().for_each(|&x| all.push(x));
+    assert_eq!(all.count(), 1000);
+    assert!((all.mean().unwrap() - mean).abs() < 1e-6);
+    // Large offset: Welford keeps the variance accurate
+    assert!((all.variance().unwrap() - variance).abs() < 1e-6);
+    assert_eq!((all.min(), all.max()), (Some(1e9), Some(1e9 + 8.0)));
+
+    let (mut head, mut tail) = (RunningStats::new(), RunningStats::new());
+    samples[..300].iter().for_each(|&x| head.push(x));
+    samples[300..].iter().for_each(|&x| tail.push(x));
+    head.merge(&tail);
+    assert_eq!(head.count(), all.count());
+    assert!((head.mean().unwrap() - mean).abs() < 1e-6);
+    assert!((head.variance().unwrap() - variance).abs() < 1e-6);
+
+    let mut empty = RunningStats::new();
+    assert_eq!((empty.mean(), empty.variance(), empty.min()), (None, None, None));
+    empty.push(f64::NAN);
+    empty.push(3.0);
+    assert_eq!((empty.count(), empty.mean(), empty.variance()), (1, Some(3.0), None));
+}
+
+/// P² estimates converge on the true quantiles of a large stream.
+#[test]
+fn test_p2_quantile_estimates() {
+    let mut rng = StdRng::seed_from_u64(9);
+    let mut summary = StreamSummary::default();
+    for _ in 0..100_000 {
+        summary.push(rng.gen::<f64>() * 10.0);
+    }
+    assert!((summary.quantile(0.5).unwrap() - 5.0).abs() < 0.1);
+    assert!((summary.quantile(0.95).unwrap() - 9.5).abs() < 0.1);
+    assert!((summary.quantile(0.99).unwrap() - 9.9).abs() < 0.1);
+    assert_eq!(summary.quantile(0.25), None);
+    assert!(summary.to_string().starts_with("n=100000 mean=5.0"));
+
+    // Exact nearest rank before five samples
+    let mut few = P2Quantile::new(0.5);
+    assert_eq!(few.estimate(), None);
+    [3.0, 1.0, 2.0].iter().for_each(|&x| few.push(x));
+    assert_eq!(few.estimate(), Some(2.0));
+}
+
+/// Streaming a study delivers every trial in order, across batches.
+#[test]
+fn test_monte_carlo_stream_summary() {
+    let study = MonteCarlo::new(STREAM_BATCH_TRIALS + 100, 5);
+    let mut next = 0;
+    study.stream(
+        |trial, _| trial,
+        |index, trial| {
+            assert_eq!((index, trial), (next, next));
+            next += 1;
+        },
+    );
+    assert_eq!(next, study.trials);
+
+    let draw = |_: usize, rng: &mut StdRng| rng.gen_range(0.0..1.0);
+    let summary = study.summarize(&[0.5], draw);
+    let values = study.run(draw);
+    assert_eq!(summary.stats.count(), values.len() as u64);
+    assert_eq!(summary.stats.max(), values.iter().copied().reduce(f64::max));
+    assert_eq!(summary, study.with_threads(2).summarize(&[0.5], draw));
+}
+
+// ─── Asymmetric Link Budget Tests ─────────────────────────────────────────────
+
+/// S-band up and X-band down are budgeted separately and both close in clear sky.
+#[test]
+fn test_asymmetric_budget_clear_sky() {
+    let link = AsymmetricLink::default();
+    assert!(link.is_asymmetric());
+
+    let bands = FrequencyBand::get_standard_bands();
+    let budget = link.evaluate(&bands, 1000.0, 45.0, &clear_sky()).unwrap().unwrap();
+    assert!(budget.closes());
+    assert_eq!(budget.direction(LinkDirection::Uplink).band, BandType::SBand);
+    assert_eq!(budget.direction(LinkDirection::Downlink).band, BandType::XBand);
+
+    // Each direction carries its own power into the budget
+    let up = &budget.uplink;
+    let snr_db = up.result.signal_to_noise_ratio_db;
+    assert!((up.margin_db - (snr_db - LINK_CLOSURE_SNR_DB)).abs() < 1e-9);
+    assert_ne!(
+        up.result.power_consumption_watts,
+        budget.downlink.result.power_consumption_watts
+    );
+    assert!(budget.to_string().contains("limiting direction"));
+
+    // Bands missing from the definitions cannot be budgeted
+    assert!(link.evaluate(&bands[..2], 1000.0, 45.0, &clear_sky()).unwrap().is_none());
+}
+
+/// In heavy rain a Ka-band downlink fails while the S-band uplink still closes.
+#[test]
+fn test_asymmetric_budget_rain_limits_downlink() {
+    let mut link = AsymmetricLink::default();
+    link.downlink.band = BandType::KaBand;
+    link.downlink.data_rate_mbps = 500.0;
+
+    let bands = FrequencyBand::get_standard_bands();
+    let budget = link.evaluate(&bands, 600.0, 60.0, &tropical_storm()).unwrap().unwrap();
+    assert!(budget.uplink.result.success);
+    assert!(!budget.downlink.result.success);
+    assert!(!budget.closes());
+    assert_eq!(budget.limiting_direction(), LinkDirection::Downlink);
+}
+
+/// The itemized budget reconciles with the simulated SNR and its own identities.
+#[test]
+fn test_link_budget_itemized() {
+    let bands = FrequencyBand::get_standard_bands();
+    let x_band = bands.iter().find(|b| b.name == BandType::XBand).unwrap();
+    let params = leo_params();
+    let margins = MarginPolicy::default();
+    let environment = tropical_storm();
+
+    let budget = x_band.compute_link_budget(&params, &environment, &margins).unwrap();
+    let result = x_band.simulate_transmission_with(&params, &environment, &margins).unwrap();
+    assert!((budget.snr_db + budget.pointing_loss_db - result.signal_to_noise_ratio_db).abs() < 1e-9);
+    assert!((budget.path_loss_db - result.path_loss_db).abs() < 1e-9);
+    assert!((budget.rain_loss_db - result.rain_attenuation_db).abs() < 1e-9);
+    assert!(budget.pointing_loss_db > 0.0);
+
+    // C/N0 = EIRP - losses + G/T + 228.6; Eb/N0 = C/N0 - 10 log R
+    let c_n0 = budget.eirp_dbw - budget.total_loss_db() + budget.g_over_t_db_k + 228.6;
+    assert!((budget.c_n0_dbhz - c_n0).abs() < 0.01);
+    let eb_n0 = budget.c_n0_dbhz - 10.0 * (params.required_data_rate_mbps * 1e6).log10();
+    assert!((budget.eb_n0_db - eb_n0).abs() < 1e-9);
+    assert!((budget.margin_db - (budget.snr_db - budget.required_snr_db)).abs() < 1e-9);
+
+    // Without an antenna diameter there is no beamwidth to mispoint from
+    let isotropic = TransmissionParameters { antenna_diameter_meters: 0.0, ..leo_params() };
+    let budget = x_band.compute_link_budget(&isotropic, &environment, &margins).unwrap();
+    assert_eq!(budget.pointing_loss_db, 0.0);
+    let table = budget.to_string();
+    assert!(table.contains("EIRP") && table.contains("Eb/N0") && table.contains("Margin"));
+
+    let invalid = TransmissionParameters { distance_km: -1.0, ..leo_params() };
+    assert!(x_band.compute_link_budget(&invalid, &environment, &margins).is_err());
+}
+
+// ─── Margin Policy Tests ──────────────────────────────────────────────────────
+
+/// Success requires the policy's link margin and, with a power limit, its power margin.
+#[test]
+fn test_margin_policy_decides_success() {
+    let bands = FrequencyBand::get_standard_bands();
+    let s_band = bands.iter().find(|b| b.name == BandType::SBand).unwrap();
+    let params = leo_params();
+    let base = s_band.simulate_transmission_with(&params, &clear_sky(), &MarginPolicy::closure_only()).unwrap();
+    assert!(base.success);
+    let margin_db = base.signal_to_noise_ratio_db - 10.0;
+
+    let judge = |policy: MarginPolicy| s_band.simulate_transmission_with(&params, &clear_sky(), &policy).unwrap().success;
+    let link = |link_margin_db| MarginPolicy { link_margin_db, ..MarginPolicy::default() };
+    assert!(judge(link(margin_db - 0.5)));
+    assert!(!judge(link(margin_db + 0.5)));
+
+    // 15% of the available power left against the required 20%
+    let consumption = base.power_consumption_watts;
+    let power = |available| MarginPolicy { power_margin: 0.2, ..MarginPolicy::closure_only().with_available_power(available) };
+    assert!(!judge(power(consumption / 0.85)));
+    assert!(judge(power(consumption / 0.75)));
+
+    // Policy files share their field names with the ground station's policy
+    let parsed: MarginPolicy = serde_json::from_str(r#"{"link_margin_db": 6.0, "power_margin": 0.1}"#).unwrap();
+    assert_eq!(parsed.required_snr_db(), 16.0);
+    assert_eq!(parsed.available_power_watts, None);
+}
+
+/// The planner and the link budget judge bands by the same policy.
+#[test]
+fn test_margin_policy_applied_by_planner_and_budget() {
+    let bands = FrequencyBand::get_standard_bands();
+    let unreachable = MarginPolicy { link_margin_db: 200.0, ..MarginPolicy::default() };
+    let recommendations = BandScorer::default().with_margins(unreachable).recommend(&bands, &leo_params()).unwrap();
+    assert!(recommendations.iter().all(|r| r.scores.availability == 0.0));
+
+    // A power limit constrains the spacecraft downlink only
+    let link = AsymmetricLink {
+        margins: MarginPolicy::closure_only().with_available_power(20.0),
+        ..AsymmetricLink::default()
+    };
+    let budget = link.evaluate(&bands, 1000.0, 45.0, &clear_sky()).unwrap().unwrap();
+    assert!(budget.uplink.result.success);
+    assert!(!budget.downlink.result.success);
+    assert!((budget.uplink.margin_db - (budget.uplink.result.signal_to_noise_ratio_db - LINK_CLOSURE_SNR_DB)).abs() < 1e-9);
+}
+
+// ─── Report Localization Tests ────────────────────────────────────────────────
+
+/// Every built-in catalog must parse and translate every English key.
+#[test]
+fn test_builtin_catalogs_complete() {
+    for language in BUILTIN_LANGUAGES {
+        let catalog = Catalog::builtin(language).expect("built-in catalog parses");
+        assert_eq!(catalog.language, language);
+        assert!(catalog.missing_keys().is_empty(), "{}: {:?}", language, catalog.missing_keys());
+    }
+    assert_eq!(Catalog::builtin("es_MX.UTF-8").unwrap().language, "es");
+    assert!(Catalog::builtin("xx").is_none());
+}
+
+/// The English rendering is the Display output; other catalogs translate the same report.
+#[test]
+fn test_capacity_report_localized() {
+    let day = 86_400.0;
+    let arrivals = MissionProfile::earth_observation().generate(day, &mut StdRng::seed_from_u64(5));
+    let contacts = regular_contacts(day, 4, 600.0, BandType::XBand);
+    let report = CapacityStudy::default().analyse(&arrivals, &contacts, day);
+
+    assert_eq!(report.localized(Catalog::english()).to_string(), report.to_string());
+    let spanish = report.localized(&Catalog::builtin("es").unwrap()).to_string();
+    assert!(spanish.contains("Informe de capacidad"), "{}", spanish);
+    assert!(!spanish.contains("Downlink Capacity Report"));
+    assert!(spanish.contains(&format!("{:.1} MB", report.offered_mb)));
+
+    assert_eq!(Rating::Good.localized(&Catalog::builtin("es").unwrap()).to_string(), "BUENA");
+    assert_eq!(format!("{:>6}", Rating::Poor.localized(Catalog::english())), "  POOR");
+}
+
+/// Keys a partial catalog lacks fall back to English; placeholders are filled by name.
+#[test]
+fn test_partial_catalog_falls_back_to_english() {
+    let catalog = Catalog::from_json(
+        r#"{"language": "fr", "messages": {
+            "link.limiting_direction": "sens limitant : {direction}",
+            "link.downlink": "descendant"
+        }}"#,
+    )
+    .unwrap();
+    assert!(catalog.missing_keys().contains(&"link.uplink"));
+    assert_eq!(catalog.text("link.uplink"), "uplink");
+    assert_eq!(catalog.text("no.such.key"), "no.such.key");
+    assert_eq!(
+        catalog.format("link.limiting_direction", &[("direction", &LinkDirection::Downlink.localized(&catalog))]),
+        "sens limitant : descendant"
+    );
+
+    let mut link = AsymmetricLink::default();
+    link.downlink.band = BandType::KaBand;
+    link.downlink.data_rate_mbps = 500.0;
+    let budget = link
+        .evaluate(&FrequencyBand::get_standard_bands(), 600.0, 60.0, &tropical_storm()).unwrap()
+        .unwrap();
+    let text = budget.localized(&catalog).to_string();
+    assert!(text.ends_with("sens limitant : descendant"), "{}", text);
+    assert!(text.contains("fails"));
+
+    assert!(Catalog::from_json("{}").is_err());
+}
+
+// ─── Weather Generator Tests ──────────────────────────────────────────────────
+
+/// The Markov chain holds its state within a step, visits every rain state
+/// and repeats exactly under the same seed.
+#[test]
+fn test_markov_rain_model_persists_and_repeats() {
+    let model = MarkovRainModel::default();
+    assert!(model.validate().is_ok());
+
+    let series = |seed| {
+        let mut generator = WeatherModel::Markov(model.clone()).generator();
+        generator.series(0.0, 300.0, 2000, &clear_sky(), &mut StdRng::seed_from_u64(seed))
+    };
+    let samples = series(5);
+    let rain: Vec<f64> = samples.iter().map(|s| s.conditions.rain_rate_mm_hour).collect();
+    assert_eq!(rain, series(5).iter().map(|s| s.conditions.rain_rate_mm_hour).collect::<Vec<_>>());
+
+    // Dry most of the time, with both stratiform and convective spells
+    let dry = rain.iter().filter(|&&r| r == 0.0).count();
+    assert!(dry > rain.len() / 2, "{} dry of {}", dry, rain.len());
+    assert!(rain.iter().any(|&r| (0.5..5.0).contains(&r)));
+    assert!(rain.iter().any(|&r| r >= 10.0));
+    // Fields the chain does not model come from the base conditions
+    assert!(samples.iter().all(|s| s.conditions.atmospheric_pressure_mb == 1013.0));
+
+    // Two samples within one 600 s step share the state
+    let mut generator = model.clone();
+    let mut rng = StdRng::seed_from_u64(9);
+    for step in 0..200 {
+        let t = step as f64 * 600.0;
+        let a = generator.conditions_at(t, &clear_sky(), &mut rng).rain_rate_mm_hour;
+        let state = generator.state().unwrap().0;
+        let b = generator.conditions_at(t + 300.0, &clear_sky(), &mut rng).rain_rate_mm_hour;
+        assert_eq!(generator.state().unwrap().0, state);
+        assert_eq!(a == 0.0, b == 0.0);
+    }
+
+    let mut bad = model;
+    bad.transitions.pop();
+    assert!(matches!(bad.validate(), Err(WeatherError::InvalidModel(_))));
+}
+
+/// Climatology rain follows the wet season and the zone's rain intensity.
+#[test]
+fn test_seasonal_climatology_follows_zone_and_season() {
+    let mut wet = SeasonalClimatology::new(ItuRainZone::K);
+    assert!(wet.validate().is_ok());
+    let peak_s = wet.wet_season_peak_day * 86_400.0;
+    let trough_s = peak_s + 182.0 * 86_400.0;
+    assert!((wet.rain_probability_at(peak_s) - 0.075).abs() < 1e-9);
+    assert!(wet.rain_probability_at(trough_s) < 0.03);
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut rain_at = |generator: &mut SeasonalClimatology, time_s: f64| -> Vec<f64> {
+        (0..20_000)
+            .map(|_| generator.conditions_at(time_s, &clear_sky(), &mut rng).rain_rate_mm_hour)
+            .filter(|&r| r > 0.0)
+            .collect()
+    };
+    let peak = rain_at(&mut wet, peak_s);
+    let trough = rain_at(&mut wet, trough_s);
+    assert!(peak.len() > 2 * trough.len(), "{} vs {}", peak.len(), trough.len());
+
+    // Rain is heavier in a wetter zone
+    let mean = |rain: &[f64]| rain.iter().sum::<f64>() / rain.len() as f64;
+    let mut dry = SeasonalClimatology::new(ItuRainZone::A);
+    let dry_rain = rain_at(&mut dry, peak_s);
+    assert!((mean(&peak) - wet.mean_rain_rate_mm_hour()).abs() < 0.1 * wet.mean_rain_rate_mm_hour());
+    assert!(mean(&dry_rain) < mean(&peak) / 3.0);
+    assert!(ItuRainZone::ALL.iter().all(|z| z.rain_rate_0_01_mm_hour() > 0.0));
+
+    wet.seasonal_amplitude = 1.5;
+    assert!(wet.validate().is_err());
+}
+
+/// A replayed CSV series holds each sample until the next, and can repeat.
+#[test]
+fn test_historical_replay_from_csv() {
+    let csv = "time_s,rain_rate_mm_hour,cloud_cover_percent\n\
+               0,0.0,10\n\
+               600,12.5,95\n\
+               1200,2.0,80\n";
+    let mut replay = HistoricalReplay::from_csv(csv, &clear_sky()).unwrap();
+    assert_eq!(replay.samples.len(), 3);
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut rain_at = |replay: &mut HistoricalReplay, t| replay.conditions_at(t, &clear_sky(), &mut rng);
+
+    assert_eq!(rain_at(&mut replay, -10.0).rain_rate_mm_hour, 0.0);
+    let held = rain_at(&mut replay, 900.0);
+    assert_eq!(held.rain_rate_mm_hour, 12.5);
+    assert_eq!(held.cloud_cover_percent, 95.0);
+    // Columns left out of the file come from the base conditions
+    assert_eq!(held.humidity_percent, clear_sky().humidity_percent);
+    assert_eq!(rain_at(&mut replay, 5000.0).rain_rate_mm_hour, 2.0);
+
+    replay.repeat = true;
+    assert_eq!(rain_at(&mut replay, 1200.0 + 700.0).rain_rate_mm_hour, 12.5);
+
+    let error = |csv: &str| match HistoricalReplay::from_csv(csv, &clear_sky()) {
+        Err(WeatherError::Csv { line, .. }) => line,
+        other => panic!("expected a CSV error, got {:?}", other.map(|r| r.samples.len())),
+    };
+    assert_eq!(error("rain_rate_mm_hour\n1.0\n"), 1);
+    assert_eq!(error("time_s,snow_mm_hour\n0,1\n"), 1);
+    assert_eq!(error("time_s,rain_rate_mm_hour\n0,1\n60,heavy\n"), 3);
+    assert_eq!(error("time_s,rain_rate_mm_hour\n60,1\n0,1\n"), 3);
+}
+
+/// A scenario file selects its weather model, and each LEOP event sees the
+/// model's conditions at its time.
+#[test]
+fn test_leop_scenario_selects_weather_model() {
+    let mut scenario = LeopScenario::standard();
+    scenario.deployment.failure_probability = 0.0;
+    scenario.deployment.partial_failure_probability = 0.0;
+    let first_contact_s = scenario.events.iter().find(|e| e.contact.is_some()).unwrap().time_s;
+
+    let mut json = serde_json::to_value(&scenario).unwrap();
+    json["weather"] = serde_json::json!({
+        "model": "replay",
+        "samples": [
+            { "time_s": 0.0, "conditions": clear_sky() },
+            { "time_s": first_contact_s, "conditions": tropical_storm() },
+        ],
+    });
+    let stormy: LeopScenario = serde_json::from_value(json).unwrap();
+    assert!(stormy.weather.as_ref().unwrap().validate().is_ok());
+
+    let steps = stormy.run(&mut StdRng::seed_from_u64(1)).unwrap();
+    for step in &steps {
+        let expected = if step.event.time_s < first_contact_s { clear_sky() } else { tropical_storm() };
+        assert_eq!(step.environment.rain_rate_mm_hour, expected.rain_rate_mm_hour);
+    }
+
+    // Without a model the scenario keeps its fixed environment
+    let fixed = scenario.run(&mut StdRng::seed_from_u64(1)).unwrap();
+    assert!(fixed.iter().all(|s| s.environment.rain_rate_mm_hour == scenario.environment.rain_rate_mm_hour));
+
+    // Random models repeat under the same seed
+    let markov: WeatherModel = serde_json::from_str(
+        &serde_json::to_string(&WeatherModel::Markov(MarkovRainModel::default())).unwrap(),
+    )
+    .unwrap();
+    scenario.weather = Some(markov);
+    let rain = |steps: Vec<LeopStep>| -> Vec<f64> { steps.iter().map(|s| s.environment.rain_rate_mm_hour).collect() };
+    assert_eq!(rain(scenario.run(&mut StdRng::seed_from_u64(2)).unwrap()), rain(scenario.run(&mut StdRng::seed_from_u64(2)).unwrap()));
+
+    let climatology: WeatherModel = serde_json::from_str(r#"{"model": "climatology", "zone": "P"}"#).unwrap();
+    assert!(climatology.validate().is_ok());
+}
+
+// ─── ITU Rain Zone Tests ──────────────────────────────────────────────────────
+
+/// Station coordinates map to the rain zone of their climate.
+#[test]
+fn test_rain_zone_by_station_location() {
+    // The default ground station location (Los Angeles)
+    assert_eq!(ItuRainZone::at_station((34.0522, -118.2437, 75.0)), ItuRainZone::E);
+    assert_eq!(ItuRainZone::at(25.8, -80.2), ItuRainZone::N); // Miami
+    assert_eq!(ItuRainZone::at(51.5, -0.1), ItuRainZone::H); // London
+    assert_eq!(ItuRainZone::at(1.35, 103.8), ItuRainZone::P); // Singapore
+    assert_eq!(ItuRainZone::at(5.2, -52.8), ItuRainZone::P); // Kourou
+    assert_eq!(ItuRainZone::at(78.2, 15.6), ItuRainZone::A); // Svalbard
+    assert_eq!(ItuRainZone::at(-31.95, 115.86), ItuRainZone::F); // Perth
+
+    // Longitudes wrap, and open ocean falls back to its latitude band
+    assert_eq!(ItuRainZone::at(51.5, 359.9), ItuRainZone::at(51.5, -0.1));
+    assert_eq!(ItuRainZone::at(0.0, -140.0), ItuRainZone::N);
+    assert_eq!(ItuRainZone::at(-50.0, -140.0), ItuRainZone::D);
+    assert_eq!(rain_rate_0_01_at(1.35, 103.8), 145.0);
+}
+
+/// A climatology scenario can name the station location instead of a zone,
+/// and southern stations get their wet season six months later.
+#[test]
+fn test_climatology_from_station_location() {
+    let model: WeatherModel =
+        serde_json::from_str(r#"{"model": "climatology", "location": [1.35, 103.8, 15.0]}"#).unwrap();
+    let climatology = match &model {
+        WeatherModel::Climatology(c) => c,
+        other => panic!("expected a climatology, got {:?}", other),
+    };
+    assert_eq!(climatology.zone, ItuRainZone::P);
+    assert_eq!(climatology.rain_probability, SeasonalClimatology::new(ItuRainZone::P).rain_probability);
+
+    let north = SeasonalClimatology::for_station((51.5, -0.1, 0.0));
+    let south = SeasonalClimatology::for_station((-35.3, 149.1, 0.0));
+    assert_eq!(south.zone, ItuRainZone::K);
+    let offset = (south.wet_season_peak_day - north.wet_season_peak_day).rem_euclid(365.25);
+    assert!((offset - 182.625).abs() < 1e-9, "{}", offset);
+
+    // An explicit zone wins over the location; one of the two is required
+    let zoned: SeasonalClimatology =
+        serde_json::from_str(r#"{"zone": "A", "location": [1.35, 103.8, 15.0]}"#).unwrap();
+    assert_eq!(zoned.zone, ItuRainZone::A);
+    assert!(serde_json::from_str::<SeasonalClimatology>(r#"{"rain_probability": 0.1}"#).is_err());
+
+    // Serialized climatologies read back unchanged
+    let json = serde_json::to_string(&south).unwrap();
+    assert_eq!(serde_json::from_str::<SeasonalClimatology>(&json).unwrap(), south);
+}
+
+// ─── Link Forecast Tests ──────────────────────────────────────────────────────
+
+/// Weather that turns to a tropical storm from `storm_from_s` on.
+struct StormAfter {
+    storm_from_s: f64,
+}
+
+impl WeatherGenerator for StormAfter {
+    fn conditions_at(
+        &mut self,
+        time_s: f64,
+        base: &EnvironmentalConditions,
+        _rng: &mut StdRng,
+    ) -> EnvironmentalConditions {
+        if time_s >= self.storm_from_s {
+            tropical_storm()
+        } else {
+            base.clone()
+        }
+    }
+}
+
+/// A contact in a storm is forecast degraded against its clear-sky capacity,
+/// and the forecast serializes to the ground station's file format.
+#[test]
+fn test_link_forecast_flags_contact_in_storm() {
+    let ka = FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|b| b.name == BandType::KaBand)
+        .unwrap();
+    let study = ForecastStudy::default();
+    let contacts = [
+        ContactOpportunity { contact_id: 2, start_s: 20_000, max_elevation_deg: 60.0 },
+        ContactOpportunity { contact_id: 1, start_s: 5_000, max_elevation_deg: 60.0 },
+    ];
+    let mut weather = StormAfter { storm_from_s: 10_000.0 };
+    let mut rng = StdRng::seed_from_u64(1);
+    let forecast = LinkForecast {
+        forecast_id: 7,
+        issued_s: 1_000,
+        contacts: study.forecast(&ka, &contacts, &mut weather, &clear_sky(), &mut rng).unwrap(),
+    };
+
+    // Contacts come out in start order with the pass duration
+    assert_eq!(forecast.contacts.iter().map(|c| c.contact_id).collect::<Vec<_>>(), vec![1, 2]);
+    let (clear, storm) = (forecast.contacts[0], forecast.contacts[1]);
+    assert!(clear.duration_s > 0);
+    assert_eq!(clear.duration_s, storm.duration_s);
+    assert!(clear.nominal_capacity_bytes > 0);
+    assert_eq!(clear.nominal_capacity_bytes, storm.nominal_capacity_bytes);
+
+    // Clear weather keeps the nominal capacity; the storm cuts it
+    assert_eq!(clear.capacity_percent(), 100);
+    assert!(storm.capacity_bytes < clear.capacity_bytes);
+    assert!(storm.is_degraded(DEFAULT_DEGRADED_PERCENT), "{}%", storm.capacity_percent());
+    assert_eq!(forecast.degraded(DEFAULT_DEGRADED_PERCENT).count(), 1);
+
+    let json = forecast.to_json().unwrap();
+    for field in ["forecast_id", "issued_s", "contact_id", "capacity_bytes", "nominal_capacity_bytes"] {
+        assert!(json.contains(field), "missing {}", field);
+    }
+    let parsed: LinkForecast = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, forecast);
+}
+
+/// A forecast holds at most the onboard contact limit.
+#[test]
+fn test_link_forecast_limits_contacts() {
+    let s_band = FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|b| b.name == BandType::SBand)
+        .unwrap();
+    let contacts: Vec<ContactOpportunity> = (0..20)
+        .map(|i| ContactOpportunity {
+            contact_id: i,
+            start_s: u64::from(i) * 5_400,
+            max_elevation_deg: 30.0,
+        })
+        .collect();
+    let mut weather = StormAfter { storm_from_s: f64::INFINITY };
+    let forecast = ForecastStudy::default().forecast(
+        &s_band,
+        &contacts,
+        &mut weather,
+        &clear_sky(),
+        &mut StdRng::seed_from_u64(2),
+    ).unwrap();
+    assert_eq!(forecast.len(), MAX_FORECAST_CONTACTS);
+    assert_eq!(forecast.last().unwrap().contact_id, MAX_FORECAST_CONTACTS as u32 - 1);
+}
+
+// ─── JSON Schema Tests ────────────────────────────────────────────────────────
+
+/// Every field of the simulation inputs and outputs is required in its schema.
+#[cfg(feature = "schema")]
+#[test]
+fn test_schemas_require_every_field() {
+    use frequency_band_simulation::schema::schemas;
+
+    let all = schemas();
+    assert_eq!(all.len(), 4);
+    for (name, expected) in [
+        ("TransmissionParameters", 8),
+        ("EnvironmentalConditions", 7),
+        ("TransmissionResult", 13),
+    ] {
+        let (_, schema) = all.iter().find(|(n, _)| *n == name).unwrap();
+        let json = serde_json::to_value(schema).unwrap();
+        assert_eq!(json["title"], name);
+        assert_eq!(json["required"].as_array().unwrap().len(), expected, "{}", name);
+        assert_eq!(json["properties"]["success"].is_null(), name != "TransmissionResult");
+    }
+
+    // Records refer to the input and output schemas and carry the band names
+    let (_, record) = all.iter().find(|(n, _)| *n == "SimulationRecord").unwrap();
+    let json = serde_json::to_value(record).unwrap();
+    for definition in ["TransmissionParameters", "EnvironmentalConditions", "TransmissionResult"] {
+        assert!(json["definitions"][definition].is_object(), "{}", definition);
+    }
+    let bands = json["definitions"]["BandType"]["enum"].as_array().unwrap();
+    assert!(bands.contains(&serde_json::Value::from("KaBand")));
+}
+
+/// Schemas are written one file per type.
+#[cfg(feature = "schema")]
+#[test]
+fn test_write_schemas() {
+    use frequency_band_simulation::schema::write_schemas;
+
+    let dir = std::env::temp_dir().join(format!("sim-schemas-{}", std::process::id()));
+    let written = write_schemas(&dir).unwrap();
+    assert_eq!(written.len(), 4);
+    let text = std::fs::read_to_string(dir.join("SimulationRecord.schema.json")).unwrap();
+    let schema: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(schema["title"], "SimulationRecord");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ─── Input Validation Tests ───────────────────────────────────────────────────
+
+/// Every invalid field is reported, in declaration order, not just the first.
+#[test]
+fn test_validation_reports_every_invalid_field() {
+    assert!(leo_params().validate().is_ok());
+    assert!(clear_sky().validate().is_ok());
+    assert!(tropical_storm().validate().is_ok());
+
+    let params = TransmissionParameters {
+        distance_km: -5.0,
+        transmit_power_watts: 0.0,
+        elevation_angle_degrees: 95.0,
+        ..leo_params()
+    };
+    let error = params.validate().unwrap_err();
+    let fields: Vec<&str> = error.fields.iter().map(|e| e.field).collect();
+    assert_eq!(
+        fields,
+        ["distance_km", "elevation_angle_degrees", "transmit_power_watts"]
+    );
+    assert_eq!(error.field("distance_km").unwrap().value, Some(-5.0));
+    let message = error.to_string();
+    for field in fields {
+        assert!(message.contains(field), "{}", message);
+    }
+    assert!(message.contains("from 0° to 90°"), "{}", message);
+
+    let environment = EnvironmentalConditions {
+        humidity_percent: 120.0,
+        solar_activity: f64::NAN,
+        temperature_celsius: -300.0,
+        ..clear_sky()
+    };
+    let error = environment.validate().unwrap_err();
+    assert_eq!(error.fields.len(), 3);
+    assert!(error.field("rain_rate_mm_hour").is_none());
+    assert!(error.field("solar_activity").unwrap().value.unwrap().is_nan());
+}
+
+/// The builder reports unset fields alongside invalid ones.
+#[test]
+fn test_parameter_builder() {
+    let built = TransmissionParameters::builder()
+        .distance_km(550.0)
+        .data_size_mb(100.0)
+        .required_data_rate_mbps(10.0)
+        .elevation_angle_degrees(45.0)
+        .transmit_power_watts(50.0)
+        .antenna_diameter_meters(2.0)
+        .build()
+        .unwrap();
+    assert_eq!(format!("{:?}", built), format!("{:?}", leo_params()));
+
+    let error = TransmissionParameters::builder()
+        .distance_km(550.0)
+        .elevation_angle_degrees(-10.0)
+        .build()
+        .unwrap_err();
+    assert_eq!(error.fields.len(), 5);
+    assert_eq!(error.field("data_size_mb").unwrap().value, None);
+    assert_eq!(error.field("elevation_angle_degrees").unwrap().value, Some(-10.0));
+    assert!(error.to_string().contains("transmit_power_watts not set"));
+}
+
+/// Public entry points reject invalid inputs instead of returning NaN.
+#[test]
+fn test_entry_points_reject_invalid_inputs() {
+    let bands = FrequencyBand::get_standard_bands();
+    let nan_distance = TransmissionParameters {
+        distance_km: f64::NAN,
+        ..leo_params()
+    };
+    let error = bands[2].simulate_transmission(&nan_distance, &clear_sky()).unwrap_err();
+    assert_eq!(error.fields.len(), 1);
+    assert_eq!(error.fields[0].field, "distance_km");
+
+    let soaked = EnvironmentalConditions {
+        cloud_cover_percent: 150.0,
+        ..clear_sky()
+    };
+    let error = score_bands_for_conditions(&bands, &nan_distance, &soaked).unwrap_err();
+    assert_eq!(error.fields.len(), 2);
+    assert!(SimulationRecord::simulate(&bands[0], &leo_params(), &soaked).is_err());
+    assert!(BandScorer::default().recommend(&bands, &nan_distance).is_err());
+
+    let sweep = ParameterSweep::new(leo_params(), clear_sky())
+        .with_values(SweepField::DistanceKm, vec![500.0, -1.0]);
+    assert_eq!(
+        sweep.run().unwrap_err().field("distance_km").unwrap().value,
+        Some(-1.0)
+    );
+
+    let error = MonteCarlo::new(20, 7)
+        .link_availability(&bands[1], &leo_params(), |_: &mut StdRng| soaked.clone())
+        .unwrap_err();
+    assert_eq!(error.fields[0].field, "cloud_cover_percent");
+}
+
+// ─── Result Cache Tests ───────────────────────────────────────────────────────
+
+/// Repeated configurations are answered from the cache with the same result.
+#[test]
+fn test_cache_reuses_identical_configurations() {
+    let bands = FrequencyBand::get_standard_bands();
+    let margins = MarginPolicy::default();
+    let cache = SimulationCache::new(8);
+
+    let first = cache.simulate(&bands[1], &leo_params(), &clear_sky(), &margins).unwrap();
+    let again = cache.simulate(&bands[1], &leo_params(), &clear_sky(), &margins).unwrap();
+    let direct = bands[1].simulate_transmission_with(&leo_params(), &clear_sky(), &margins).unwrap();
+    assert_eq!(format!("{:?}", first), format!("{:?}", direct));
+    assert_eq!(format!("{:?}", again), format!("{:?}", direct));
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    assert_eq!(stats.hit_rate(), 0.5);
+
+    // Any change to the band, margins or inputs is a different configuration
+    let mut modified = bands[1].clone();
+    modified.characteristics.antenna_gain_dbi -= 10.0;
+    let weaker = cache.simulate(&modified, &leo_params(), &clear_sky(), &margins).unwrap();
+    assert!(weaker.signal_to_noise_ratio_db < first.signal_to_noise_ratio_db);
+    cache.simulate(&bands[1], &leo_params(), &clear_sky(), &MarginPolicy::closure_only()).unwrap();
+    cache.simulate(&bands[1], &leo_params(), &tropical_storm(), &margins).unwrap();
+    assert_eq!(cache.stats().misses, 4);
+
+    // Invalid inputs are rejected and never cached
+    let invalid = TransmissionParameters { distance_km: -1.0, ..leo_params() };
+    assert!(cache.simulate(&bands[1], &invalid, &clear_sky(), &margins).is_err());
+    assert_eq!(cache.stats().entries, 4);
+
+    cache.clear();
+    assert_eq!(cache.stats(), Default::default());
+}
+
+/// A full cache evicts the least recently used result.
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let band = &FrequencyBand::get_standard_bands()[2];
+    let margins = MarginPolicy::default();
+    let at = |km: f64| TransmissionParameters { distance_km: km, ..leo_params() };
+    let cache = SimulationCache::new(2);
+
+    cache.simulate(band, &at(500.0), &clear_sky(), &margins).unwrap();
+    cache.simulate(band, &at(1000.0), &clear_sky(), &margins).unwrap();
+    cache.simulate(band, &at(500.0), &clear_sky(), &margins).unwrap();
+    cache.simulate(band, &at(2000.0), &clear_sky(), &margins).unwrap();
+    assert_eq!(cache.stats().evictions, 1);
+    assert_eq!(cache.stats().entries, 2);
+
+    // 500 km was used more recently than 1000 km, so it survived
+    cache.simulate(band, &at(500.0), &clear_sky(), &margins).unwrap();
+    assert_eq!(cache.stats().hits, 2);
+    cache.simulate(band, &at(1000.0), &clear_sky(), &margins).unwrap();
+    assert_eq!(cache.stats().misses, 4);
+
+    let disabled = SimulationCache::new(0);
+    disabled.simulate(band, &at(500.0), &clear_sky(), &margins).unwrap();
+    disabled.simulate(band, &at(500.0), &clear_sky(), &margins).unwrap();
+    assert_eq!((disabled.stats().hits, disabled.stats().entries), (0, 0));
+}
+
+/// Sweeps and recommendations sharing a cache skip configurations already run.
+#[test]
+fn test_cache_shared_by_sweeps_and_scorer() {
+    let cache = Arc::new(SimulationCache::default());
+    let sweep = ParameterSweep::new(leo_params(), clear_sky())
+        .with_range(SweepField::RainRateMmHour, 0.0, 20.0, 10.0)
+        .with_cache(Arc::clone(&cache));
+    let cold = sweep.run().unwrap();
+    assert_eq!(cache.stats().misses, sweep.run_count() as u64);
+    let warm = sweep.run().unwrap();
+    assert_eq!(cache.stats().hits, sweep.run_count() as u64);
+    for (a, b) in cold.runs.iter().zip(&warm.runs) {
+        assert_eq!(a.result.signal_to_noise_ratio_db, b.result.signal_to_noise_ratio_db);
+    }
+
+    let bands = FrequencyBand::get_standard_bands();
+    let scorer = BandScorer::default().with_cache(Arc::clone(&cache));
+    let first = scorer.recommend(&bands, &leo_params()).unwrap();
+    let hits = cache.stats().hits;
+    let second = scorer.recommend(&bands, &leo_params()).unwrap();
+    assert_eq!(cache.stats().hits - hits, (bands.len() * scorer.environments.len()) as u64);
+    assert_eq!(format!("{:?}", first), format!("{:?}", second));
+    assert_eq!(
+        format!("{:?}", first),
+        format!("{:?}", BandScorer::default().recommend(&bands, &leo_params()).unwrap())
+    );
+}
+
+// ─── Fast Math Tests ──────────────────────────────────────────────────────────
+
+/// Fast logarithms and powers stay within the documented bounds.
+#[test]
+fn test_fast_math_accuracy() {
+    let mut x = 1e-300_f64;
+    while x < 1e300 {
+        assert!((fast_math::log2(x) - x.log2()).abs() < 1e-10, "log2({})", x);
+        assert!((fast_math::log10(x) - x.log10()).abs() < 1e-10, "log10({})", x);
+        x *= 1.37;
+    }
+    let mut y = -300.0_f64;
+    while y < 300.0 {
+        let exact = 10.0_f64.powf(y);
+        assert!(((fast_math::pow10(y) - exact) / exact).abs() < 1e-9, "pow10({})", y);
+        assert!(((fast_math::exp2(y) - y.exp2()) / y.exp2()).abs() < 1e-9, "exp2({})", y);
+        y += 0.173;
+    }
+
+    // Special values behave as the standard library
+    assert_eq!(fast_math::log2(0.0), f64::NEG_INFINITY);
+    assert!(fast_math::log10(-1.0).is_nan());
+    assert!(fast_math::log2(f64::NAN).is_nan());
+    assert_eq!(fast_math::log2(f64::INFINITY), f64::INFINITY);
+    assert_eq!(fast_math::log2(f64::MIN_POSITIVE / 4.0), -1024.0);
+    assert_eq!(fast_math::pow10(400.0), f64::INFINITY);
+    assert_eq!(fast_math::pow10(-400.0), 0.0);
+    assert!(fast_math::pow10(f64::NAN).is_nan());
+}
+
+/// Slice forms give exactly the scalar results, special values included.
+#[test]
+fn test_fast_math_slices_match_scalar() {
+    let inputs: Vec<f64> = (0..23)
+        .map(|i| (i as f64 - 7.0) * 13.7)
+        .chain([0.0, f64::NAN, f64::INFINITY, 5e-320])
+        .collect();
+
+    let mut logs = inputs.clone();
+    fast_math::log2_slice(&mut logs);
+    let mut decimal_logs = inputs.clone();
+    fast_math::log10_slice(&mut decimal_logs);
+    let mut powers = inputs.clone();
+    fast_math::pow10_slice(&mut powers);
+    for (i, x) in inputs.iter().enumerate() {
+        assert_eq!(logs[i].to_bits(), fast_math::log2(*x).to_bits(), "log2({})", x);
+        assert_eq!(decimal_logs[i].to_bits(), fast_math::log10(*x).to_bits(), "log10({})", x);
+        assert_eq!(powers[i].to_bits(), fast_math::pow10(*x).to_bits(), "pow10({})", x);
+    }
+
+    let mut precise = inputs.clone();
+    MathPath::Precise.pow10_slice(&mut precise);
+    for (p, x) in precise.iter().zip(&inputs) {
+        assert_eq!(p.to_bits(), 10.0_f64.powf(*x).to_bits());
+    }
+}
+
+/// Batched simulation matches per-environment simulation: exactly on the
+/// precise path, within the documented bounds on the fast one.
+#[test]
+fn test_simulate_batch_matches_single_runs() {
+    let margins = MarginPolicy::default();
+    let environments: Vec<EnvironmentalConditions> = (0..11)
+        .map(|i| EnvironmentalConditions {
+            rain_rate_mm_hour: i as f64 * 5.0,
+            cloud_cover_percent: i as f64 * 9.0,
+            ..clear_sky()
+        })
+        .collect();
+    for band in FrequencyBand::get_standard_bands() {
+        let precise = band
+            .simulate_batch(&leo_params(), &environments, &margins, MathPath::Precise)
+            .unwrap();
+        let fast = band
+            .simulate_batch(&leo_params(), &environments, &margins, MathPath::Fast)
+            .unwrap();
+        for (i, environment) in environments.iter().enumerate() {
+            let single = band
+                .simulate_transmission_with(&leo_params(), environment, &margins)
+                .unwrap();
+            assert_eq!(format!("{:?}", precise[i]), format!("{:?}", single));
+            let snr_error = fast[i].signal_to_noise_ratio_db - single.signal_to_noise_ratio_db;
+            assert!(snr_error.abs() < 1e-8, "{:?} SNR off by {}", band.name, snr_error);
+            let rate_error = fast[i].actual_data_rate_mbps / single.actual_data_rate_mbps - 1.0;
+            assert!(rate_error.abs() < 1e-8, "{:?} rate off by {}", band.name, rate_error);
+            assert_eq!(fast[i].success, single.success);
+        }
+    }
+
+    let invalid = EnvironmentalConditions { humidity_percent: 140.0, ..clear_sky() };
+    let band = &FrequencyBand::get_standard_bands()[0];
+    let error = band
+        .simulate_batch(&leo_params(), &[clear_sky(), invalid], &margins, MathPath::Fast)
+        .unwrap_err();
+    assert!(error.field("humidity_percent").is_some());
+    assert!(band.simulate_batch(&leo_params(), &[], &margins, MathPath::Fast).unwrap().is_empty());
+}
+
+/// Monte Carlo availability on the fast path agrees with the precise path.
+#[test]
+fn test_monte_carlo_fast_math_agrees() {
+    let ka = &FrequencyBand::get_standard_bands()[1];
+    let rainy = |rng: &mut StdRng| EnvironmentalConditions {
+        rain_rate_mm_hour: rng.gen_range(0.0..=60.0),
+        ..clear_sky()
+    };
+    let trials = STREAM_BATCH_TRIALS + 37;
+    let precise = MonteCarlo::new(trials, 11).link_availability(ka, &leo_params(), rainy).unwrap();
+    let fast = MonteCarlo::new(trials, 11)
+        .with_math(MathPath::Fast)
+        .link_availability(ka, &leo_params(), rainy)
+        .unwrap();
+    assert_eq!((fast.trials, fast.successes), (precise.trials, precise.successes));
+    assert!((fast.mean_snr_db - precise.mean_snr_db).abs() < 1e-8);
+    assert!(precise.successes > 0 && precise.successes < trials);
+}
+
+// ─── Progress and Cancellation Tests ──────────────────────────────────────────
+
+/// Progress fraction, estimated time remaining and status text.
+#[test]
+fn test_progress_estimates_time_remaining() {
+    let progress = Progress { completed: 250, total: 1000, elapsed: Duration::from_secs(10) };
+    assert_eq!(progress.percent(), 25.0);
+    assert_eq!(progress.eta(), Some(Duration::from_secs(30)));
+    assert_eq!(progress.to_string(), "250/1000 runs (25.0% complete, about 30 s remaining)");
+    let spanish = Catalog::builtin("es").unwrap();
+    assert!(progress.localized(&spanish).to_string().starts_with("250/1000 ejecuciones"));
+
+    let started = Progress { completed: 0, ..progress };
+    assert_eq!(started.eta(), None);
+    assert_eq!(started.to_string(), "0/1000 runs (0.0% complete)");
+    let empty = Progress { completed: 0, total: 0, elapsed: Duration::ZERO };
+    assert!(empty.is_complete());
+    assert_eq!(empty.fraction(), 1.0);
+}
+
+/// Controlled Monte Carlo studies report every batch and return the same
+/// results as uncontrolled ones.
+#[test]
+fn test_monte_carlo_reports_progress() {
+    let study = MonteCarlo::new(2 * STREAM_BATCH_TRIALS + 5, 3);
+    let draw = |i: usize, rng: &mut StdRng| i as f64 + rng.gen::<f64>();
+    let (sender, receiver) = mpsc::channel();
+    let results = study
+        .run_controlled(draw, &mut RunControl::new().with_channel(sender))
+        .unwrap();
+    assert_eq!(results, study.run(draw));
+
+    let reported: Vec<usize> = receiver.try_iter().map(|p| p.completed).collect();
+    assert_eq!(reported, [0, STREAM_BATCH_TRIALS, 2 * STREAM_BATCH_TRIALS, study.trials]);
+
+    let ka = &FrequencyBand::get_standard_bands()[1];
+    let mut reports = 0;
+    let availability = study
+        .link_availability_controlled(
+            ka,
+            &leo_params(),
+            |_| clear_sky(),
+            &mut RunControl::new().on_progress(|_| reports += 1),
+        )
+        .unwrap();
+    assert_eq!(reports, 4);
+    assert_eq!(
+        availability,
+        study.link_availability(ka, &leo_params(), |_| clear_sky()).unwrap()
+    );
+}
+
+/// A cancelled study stops at the next batch with the progress reached.
+#[test]
+fn test_cancel_stops_monte_carlo_study() {
+    let study = MonteCarlo::new(3 * STREAM_BATCH_TRIALS, 3);
+    let token = CancelToken::new();
+    let canceller = token.clone();
+    let mut control = RunControl::new().with_cancel(token).on_progress(move |progress| {
+        if progress.completed > 0 {
+            canceller.cancel();
+        }
+    });
+    match study.run_controlled(|i, _| i, &mut control) {
+        Err(RunError::Cancelled(progress)) => {
+            assert_eq!((progress.completed, progress.total), (STREAM_BATCH_TRIALS, study.trials));
+        }
+        other => panic!("expected cancellation, got {:?}", other.map(|r| r.len())),
+    }
+    assert!(control.cancel_token().is_cancelled());
+
+    // Cancelled before starting, no trial runs
+    let ka = &FrequencyBand::get_standard_bands()[1];
+    let error = study
+        .with_math(MathPath::Fast)
+        .link_availability_controlled(ka, &leo_params(), |_| clear_sky(), &mut control)
+        .unwrap_err();
+    assert!(matches!(error, RunError::Cancelled(Progress { completed: 0, .. })));
+    assert!(error.to_string().contains("cancelled after 0 of"));
+
+    let invalid = TransmissionParameters { transmit_power_watts: 0.0, ..leo_params() };
+    let error = study
+        .link_availability_controlled(ka, &invalid, |_| clear_sky(), &mut RunControl::new())
+        .unwrap_err();
+    assert!(matches!(error, RunError::Invalid(ref e) if e.field("transmit_power_watts").is_some()));
+}
+
+/// Controlled sweeps report every batch and can be cancelled.
+#[test]
+fn test_sweep_progress_and_cancel() {
+    let sweep = ParameterSweep::new(leo_params(), clear_sky())
+        .with_range(SweepField::DistanceKm, 500.0, 3000.0, 1.0);
+    assert!(sweep.run_count() > 2 * SWEEP_BATCH_RUNS);
+
+    let mut reported = Vec::new();
+    let table = sweep
+        .run_controlled(&mut RunControl::new().on_progress(|p| reported.push(p.completed)))
+        .unwrap();
+    assert_eq!(table.runs.len(), sweep.run_count());
+    assert_eq!(reported.first(), Some(&0));
+    assert_eq!(reported.last(), Some(&sweep.run_count()));
+    assert_eq!(reported.len(), sweep.run_count().div_ceil(SWEEP_BATCH_RUNS) + 1);
+    let plain = sweep.run().unwrap();
+    assert_eq!(table.to_csv(), plain.to_csv());
+
+    let token = CancelToken::new();
+    let canceller = token.clone();
+    let mut control = RunControl::new().with_cancel(token).on_progress(move |progress| {
+        if progress.completed >= SWEEP_BATCH_RUNS {
+            canceller.cancel();
+        }
+    });
+    match sweep.run_controlled(&mut control) {
+        Err(RunError::Cancelled(progress)) => assert_eq!(progress.completed, SWEEP_BATCH_RUNS),
+        other => panic!("expected cancellation, got {:?}", other.map(|t| t.runs.len())),
+    }
+}
+
+// ─── Deep-Space Link Tests ────────────────────────────────────────────────────
+
+/// The Moon is close enough for interactive protocols; Mars is not.
+#[test]
+fn test_light_time_selects_protocol_mode() {
+    let moon = DeepSpaceTarget::Moon.distance_km();
+    assert!((light_time_s(moon) - 1.282).abs() < 1e-3);
+    assert_eq!(
+        ProtocolMode::for_round_trip(round_trip_light_time_s(moon)),
+        ProtocolMode::Interactive
+    );
+
+    let mars_rtlt = round_trip_light_time_s(DeepSpaceTarget::Mars.distance_km());
+    assert!(mars_rtlt > 1000.0, "Mars round trip is tens of minutes");
+    assert_eq!(ProtocolMode::for_round_trip(mars_rtlt), ProtocolMode::TimerBased);
+
+    // Ten commands: interactive operation pays the round trip ten times
+    let interactive = ProtocolMode::Interactive.session_time_s(10, 1.0, mars_rtlt);
+    let timer_based = ProtocolMode::TimerBased.session_time_s(10, 1.0, mars_rtlt);
+    assert!((interactive - timer_based - 9.0 * mars_rtlt).abs() < 1e-6);
+}
+
+/// Replies are expected one round trip plus turnaround after sending.
+#[test]
+fn test_response_expectation_window() {
+    let expectation = ResponseExpectation::new(100.0, 1500.0, 2.0, 0.01);
+    assert_eq!(expectation.due_s, 1602.0);
+    assert_eq!(expectation.deadline_s, 1617.0);
+    assert_eq!(expectation.status(1000.0), ExpectationStatus::InFlight);
+    assert_eq!(expectation.status(1610.0), ExpectationStatus::Due);
+    assert_eq!(expectation.status(1620.0), ExpectationStatus::Overdue);
+
+    // Short round trips still get the minimum window
+    let lunar = ResponseExpectation::new(0.0, 2.56, 0.0, 0.01);
+    assert!((lunar.deadline_s - lunar.due_s - MIN_RESPONSE_WINDOW_S).abs() < 1e-9);
+}
+
+/// DSN rates at Mars are kbps to Mbps, better on 70 m, falling with range².
+#[test]
+fn test_deep_space_downlink_rates() {
+    let mars = DeepSpaceTarget::Mars.distance_km();
+    let link = DeepSpaceLink::default();
+    let budget_34 = link.evaluate(mars).unwrap();
+    let budget_70 = DeepSpaceLink { ground: DsnAntenna::Dsn70m, ..link }.evaluate(mars).unwrap();
+
+    assert!(budget_34.max_data_rate_bps > 1e4 && budget_34.max_data_rate_bps < 1e7);
+    let gain_db = 10.0 * (budget_70.max_data_rate_bps / budget_34.max_data_rate_bps).log10();
+    assert!((gain_db - 8.3).abs() < 0.5, "70 m advantage {:.1} dB", gain_db);
+    assert_eq!(budget_34.protocol, ProtocolMode::TimerBased);
+
+    let jupiter = link.evaluate(DeepSpaceTarget::Jupiter.distance_km()).unwrap();
+    let ratio = budget_34.max_data_rate_bps / jupiter.max_data_rate_bps;
+    assert!((ratio / (7.8e8f64 / 2.25e8).powi(2) - 1.0).abs() < 1e-9);
+
+    // The 70 m antenna has no Ka-band receiver; UHF has no allocation
+    let ka_70 = DeepSpaceLink { band: BandType::KaBand, ground: DsnAntenna::Dsn70m, ..link };
+    assert!(ka_70.evaluate(mars).is_none());
+    assert!(DeepSpaceLink { band: BandType::UHFBand, ..link }.evaluate(mars).is_none());
+}
+
+// ─── Shadow Policy Tests ──────────────────────────────────────────────────────
+
+/// The active decision is applied; the shadow decision is only logged, with
+/// the outcome it would have had.
+#[test]
+fn test_shadow_decisions_are_logged_not_applied() {
+    let bands = FrequencyBand::get_standard_bands();
+    let mut evaluation =
+        ShadowEvaluation::new(Box::new(BestScore), Box::new(FixedBand(BandType::UHFBand)));
+
+    for (time_s, environment) in [(0.0, clear_sky()), (60.0, tropical_storm())] {
+        let scores = score_bands_for_conditions(&bands, &leo_params(), &environment).unwrap();
+        let applied = evaluation.decide(time_s, &scores);
+        assert_eq!(applied.band, scores[0].band);
+        assert_eq!(applied.acm, None);
+    }
+
+    let records = evaluation.records();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.shadow.band == BandType::UHFBand));
+    assert!(!records[0].agrees(), "clear sky favours a high band");
+    assert!(records[0].active_outcome.throughput_mbps > records[0].shadow_outcome.throughput_mbps);
+
+    let summary = evaluation.summary();
+    assert_eq!(summary.decisions, 2);
+    assert_eq!(summary.active.name, "best-score");
+    assert_eq!(summary.shadow.name, "fixed");
+    assert_eq!(summary.shadow.band_switches, 0);
+    assert!(summary.to_string().contains("2 decisions"));
+}
+
+/// Over changing weather a hysteresis policy switches band less often than
+/// re-choosing the best band, and ACM never chooses a scheme that cannot
+/// close.
+#[test]
+fn test_shadow_run_over_simulated_weather() {
+    let bands = FrequencyBand::get_standard_bands();
+    let samples = WeatherModel::Markov(MarkovRainModel::default())
+        .generator()
+        .series(0.0, 600.0, 500, &clear_sky(), &mut StdRng::seed_from_u64(11));
+    let margins = MarginPolicy::default();
+
+    let mut evaluation =
+        ShadowEvaluation::new(Box::new(BestScore), Box::new(Hysteresis::new(0.2)));
+    let summary = evaluation.run(&bands, &leo_params(), &samples, &margins).unwrap();
+    assert_eq!(summary.decisions, samples.len());
+    assert!(summary.agreements > 0);
+    assert!(
+        summary.shadow.band_switches <= summary.active.band_switches,
+        "{}",
+        summary
+    );
+
+    let acm = WithAcm::new(BestScore, 50.0, false);
+    assert_eq!(acm.name(), "best-score+acm");
+    let mut evaluation = ShadowEvaluation::new(Box::new(BestScore), Box::new(acm));
+    let summary = evaluation.run(&bands, &leo_params(), &samples, &margins).unwrap();
+    assert_eq!(summary.agreements, 0, "ACM decisions carry a scheme");
+    for record in evaluation.records() {
+        assert_eq!(record.shadow.band, record.active.band);
+        let choice = record.shadow.acm.unwrap();
+        assert!(
+            record.shadow_outcome.outage
+                || record.shadow_outcome.throughput_mbps <= choice.throughput_mbps
+        );
+    }
+}
+
+// ─── Interleaving and Randomization Tests ─────────────────────────────────────
+
+/// The randomizer reproduces the CCSDS sequence and undoes itself; the
+/// interleaver round-trips and spreads a burst over every codeword.
+#[test]
+fn test_randomizer_and_interleaver() {
+    let sequence = pseudo_random_sequence();
+    assert_eq!(sequence[..8], [0xFF, 0x48, 0x0E, 0xC0, 0x9A, 0x0D, 0x70, 0xBC]);
+
+    let original: Vec<u8> = (0..600).map(|i| (i % 7) as u8).collect();
+    let mut frame = original.clone();
+    randomize(&mut frame);
+    assert_ne!(frame, original);
+    assert_eq!(frame[255] ^ original[255], sequence[0], "sequence repeats");
+    randomize(&mut frame);
+    assert_eq!(frame, original);
+
+    let interleaver = BlockInterleaver::new(4).unwrap();
+    let codewords: Vec<u8> = (0..4 * 255).map(|i| (i / 255) as u8).collect();
+    let mut interleaved = interleaver.interleave(&codewords).unwrap();
+    assert_eq!(interleaved[..4], [0, 1, 2, 3]);
+    for byte in &mut interleaved[100..140] {
+        *byte = 0xFF;
+    }
+    let received = interleaver.deinterleave(&interleaved).unwrap();
+    for (i, codeword) in received.chunks(255).enumerate() {
+        let errors = codeword.iter().filter(|&&b| b != i as u8).count();
+        assert_eq!(errors, 10, "a 40-byte burst costs each codeword 10 bytes");
+    }
+
+    assert_eq!(BlockInterleaver::new(6), Err(CodingError::InvalidDepth(6)));
+    assert!(interleaver.interleave(&[0; 10]).is_err());
+    assert!(InterleaverConfig::default().with_depth(BandType::KaBand, 7).is_err());
+    assert_eq!(
+        InterleaverConfig::default()
+            .with_depth(BandType::SBand, 3)
+            .unwrap()
+            .depth(BandType::SBand),
+        3
+    );
+}
+
+/// Interleaving turns Ka-band bursts RS(255,223) cannot correct into
+/// correctable scattered errors, cutting the residual error rate.
+#[test]
+fn test_interleaving_lowers_residual_error_rate() {
+    let channel = GilbertElliott::for_band(BandType::KaBand);
+    assert!(channel.validate().is_ok());
+    assert!((channel.mean_bit_error_rate() - 0.3 * channel.bad_state_fraction()).abs() < 1e-5);
+
+    let study = MonteCarlo::new(200, 42);
+    let config = InterleaverConfig::default();
+    let plain = CodingChain::for_band(&InterleaverConfig::uniform(1).unwrap(), BandType::KaBand)
+        .unwrap();
+    let interleaved = CodingChain::for_band(&config, BandType::KaBand).unwrap();
+    assert_eq!(interleaved.frame_len(), 8 * RS_255_223.n);
+
+    let before = measure_residual_errors(BandType::KaBand, &plain, &channel, &study).unwrap();
+    let after = measure_residual_errors(BandType::KaBand, &interleaved, &channel, &study).unwrap();
+    assert_eq!(after.codewords, 1600);
+    assert!(before.channel_bit_errors > 0 && after.channel_bit_errors > 0);
+    assert!(
+        after.codeword_error_rate() < before.codeword_error_rate() / 2.0,
+        "{} vs {}",
+        after,
+        before
+    );
+    assert_eq!(
+        measure_residual_errors(BandType::KaBand, &interleaved, &channel, &study).unwrap(),
+        after,
+        "reproducible from the study seed"
+    );
+
+    let bad_channel = GilbertElliott {
+        p_bad_to_good: 0.0,
+        ..channel
+    };
+    assert!(measure_residual_errors(BandType::KaBand, &plain, &bad_channel, &study).is_err());
+
+    let reports = run_burst_error_demo(&config, &MonteCarlo::new(20, 1)).unwrap();
+    assert_eq!(reports.len(), 5);
+    assert!(reports.iter().all(|(plain, _)| plain.depth == 1));
+    assert_eq!(reports[4].1.depth, 8);
+}
+
+/// The NAK bitmap names only missing frames and survives the return link.
+#[test]
+fn test_nak_bitmap_round_trip() {
+    let mut nak = NakBitmap::new(u32::MAX - 1);
+    nak.mark_missing(u32::MAX - 1).unwrap();
+    nak.mark_missing(2).unwrap();
+    assert_eq!(nak.missing().collect::<Vec<_>>(), vec![u32::MAX - 1, 2]);
+    assert!(nak.is_missing(2) && !nak.is_missing(0));
+    assert_eq!(nak.count(), 2);
+    assert!(matches!(
+        nak.mark_missing(100),
+        Err(ArqError::OutsideWindow { frame: 100, .. })
+    ));
+
+    let bytes = nak.to_bytes();
+    assert_eq!(NakBitmap::from_bytes(&bytes).unwrap(), nak);
+    assert_eq!(
+        NakBitmap::from_bytes(&bytes[..5]),
+        Err(ArqError::NakLength(5))
+    );
+}
+
+/// Copies that each fail alone decode from their majority, and a cleaner
+/// retransmission decodes on its own.
+#[test]
+fn test_harq_receiver_combines_failed_copies() {
+    let sent: Vec<u8> = (0..RS_255_223.n).map(|i| i as u8).collect();
+    let corrupt = |positions: std::ops::Range<usize>| {
+        let mut copy = sent.clone();
+        positions.for_each(|i| copy[i] ^= 0xFF);
+        copy
+    };
+
+    let mut receiver = HarqReceiver::new(RS_255_223);
+    assert!(!receiver.receive(7, &corrupt(0..40), &sent).unwrap());
+    assert!(!receiver.receive(7, &corrupt(100..140), &sent).unwrap());
+    assert_eq!(receiver.stored_copies(7), 2);
+    assert!(receiver.nak(0, 10).unwrap().is_missing(7));
+    assert!(
+        receiver.receive(7, &corrupt(200..240), &sent).unwrap(),
+        "majority of three"
+    );
+    assert!(receiver.is_delivered(7));
+    assert_eq!(receiver.stored_copies(7), 0);
+    assert!(!receiver.nak(0, 10).unwrap().is_missing(7));
+
+    assert!(!receiver.receive(8, &corrupt(0..40), &sent).unwrap());
+    assert!(receiver.receive(8, &corrupt(0..10), &sent).unwrap());
+    assert!(receiver.receive(9, &sent[..10], &sent).is_err());
+    assert!(matches!(
+        receiver.nak(0, 65),
+        Err(ArqError::InvalidConfig(_))
+    ));
+}
+
+/// Plain ARQ keeps the code rate on a clean link but collapses on a
+/// marginal one, where hybrid ARQ still delivers every frame.
+#[test]
+fn test_hybrid_arq_degrades_gracefully() {
+    let config = ArqConfig::default();
+    let study = MonteCarlo::new(10, 3);
+    let chain = CodingChain::for_band(&InterleaverConfig::default(), BandType::XBand).unwrap();
+    let clean = GilbertElliott::with_bursts(64.0, 1e9, 1e-9, 0.3);
+    let marginal = GilbertElliott {
+        ber_good: 1e-3,
+        ..GilbertElliott::for_band(BandType::XBand)
+    };
+
+    let run = |scheme, channel: &GilbertElliott| {
+        measure_arq(BandType::XBand, scheme, &chain, channel, &config, &study).unwrap()
+    };
+    let plain_clean = run(ArqScheme::Plain, &clean);
+    let hybrid_clean = run(ArqScheme::Hybrid, &clean);
+    assert_eq!(plain_clean.frames, 320);
+    assert!(plain_clean.throughput_efficiency() > hybrid_clean.throughput_efficiency());
+    assert!((hybrid_clean.throughput_efficiency() - RS_255_223.rate()).abs() < 0.01);
+
+    let plain = run(ArqScheme::Plain, &marginal);
+    let hybrid = run(ArqScheme::Hybrid, &marginal);
+    assert!(plain.frame_loss_rate() > 0.9, "{}", plain);
+    assert_eq!(hybrid.delivered, hybrid.frames, "{}", hybrid);
+    assert!(hybrid.throughput_efficiency() > 0.5, "{}", hybrid);
+    assert!(hybrid.transmissions_per_frame() < 1.5);
+    assert_eq!(
+        run(ArqScheme::Hybrid, &marginal),
+        hybrid,
+        "reproducible from the study seed"
+    );
+
+    let bad_config = ArqConfig {
+        window: 65,
+        ..config
+    };
+    assert!(matches!(
+        measure_arq(
+            BandType::XBand,
+            ArqScheme::Hybrid,
+            &chain,
+            &marginal,
+            &bad_config,
+            &study
+        ),
+        Err(ArqError::InvalidConfig(_))
+    ));
+
+    // The study reads as link statistics: at 1 Mbit/s every channel bit is 1 µs
+    let stats = hybrid.link_stats(1_000_000);
+    assert_eq!(stats.frames_sent, hybrid.transmissions as u64);
+    assert_eq!(stats.frames_received, hybrid.delivered as u64);
+    assert_eq!(
+        stats.retransmissions,
+        stats.frames_sent - hybrid.frames as u64
+    );
+    assert_eq!(stats.frame_errors, stats.retransmissions);
+    assert_eq!(stats.window_ms, hybrid.channel_bits as u64 / 1_000);
+    assert!((stats.rx_throughput_bps() / 1e6 - hybrid.throughput_efficiency()).abs() < 0.01);
+    assert_eq!(stats.snr_db, None);
+
+    let rows = run_harq_demo(
+        &InterleaverConfig::default(),
+        &config,
+        &MonteCarlo::new(2, 1),
+    )
+    .unwrap();
+    assert_eq!(rows.len(), 4);
+    assert!(rows
+        .iter()
+        .all(|(_, plain, hybrid)| plain.scheme == ArqScheme::Plain
+            && hybrid.scheme == ArqScheme::Hybrid));
+}
+
+// ─── MODCOD Tests ─────────────────────────────────────────────────────────────
+
+/// LDPC needs less Eb/N0 than legacy coding at every MODCOD, and its gain is
+/// read off the tabulated curve at the target error rate.
+#[test]
+fn test_ldpc_modcod_table_needs_less_eb_n0() {
+    let legacy = modcod_table(FecScheme::Legacy);
+    let ldpc = modcod_table(FecScheme::Ldpc);
+    assert_eq!(legacy.len(), 25);
+    assert_eq!(ldpc.len(), legacy.len());
+    assert_eq!(ldpc[0].modulation, ModulationScheme::Qam256);
+    assert_eq!(ldpc[0].coding_rate, CodingRate::Rate7_8);
+    for (l, c) in legacy.iter().zip(&ldpc) {
+        assert_eq!((l.modulation, l.coding_rate), (c.modulation, c.coding_rate));
+        assert!(c.required_eb_n0_db < l.required_eb_n0_db, "{:?}", c);
+        assert_eq!(c.fec, FecScheme::Ldpc);
+    }
+
+    let c2 = ldpc_curve(CodingRate::Rate7_8);
+    let required = c2.required_eb_n0_db(TARGET_BER).unwrap();
+    assert!((required - 4.0).abs() < 1e-9);
+    let gain = ModulationScheme::Bpsk.min_eb_n0_db() - required;
+    assert!((coding_gain_db(FecScheme::Ldpc, CodingRate::Rate7_8) - gain).abs() < 1e-9);
+
+    // Halfway between decades in log error rate
+    let curve = PerformanceCurve::from_points(&[(1.0, 1e-2), (2.0, 1e-4), (3.0, 0.0)]);
+    assert!((curve.required_eb_n0_db(1e-3).unwrap() - 1.5).abs() < 1e-9);
+    assert_eq!(curve.required_eb_n0_db(1e-6), None);
+}
+
+/// At the same SNR, ACM over the LDPC table picks a MODCOD with more bits per
+/// symbol and still keeps its margin.
+#[test]
+fn test_select_amc_with_ldpc_gains_throughput() {
+    let conditions = |fec| AmcConditions {
+        link_snr_db: 14.0,
+        bandwidth_mhz: 500.0,
+        latency_sensitive: false,
+        fec,
+    };
+    let legacy = select_amc(&conditions(FecScheme::Legacy));
+    let ldpc = select_amc(&conditions(FecScheme::Ldpc));
+    assert_eq!(legacy.fec, FecScheme::Legacy);
+    assert_eq!(ldpc.fec, FecScheme::Ldpc);
+    assert!(
+        ldpc.throughput_mbps > legacy.throughput_mbps,
+        "{:?} vs {:?}",
+        ldpc,
+        legacy
+    );
+    assert!(ldpc.link_margin_db >= 2.0);
+
+    // Tables without the field deserialize as legacy
+    let parsed: AmcConditions = serde_json::from_str(
+        r#"{"link_snr_db": 14.0, "bandwidth_mhz": 500.0, "latency_sensitive": false}"#,
+    )
+    .unwrap();
+    assert_eq!(parsed.fec, FecScheme::Legacy);
+}
+
+/// The C2 decoder as built meets the tabulated curve: error free well above
+/// the waterfall, failing well below it.
+#[cfg(feature = "ldpc")]
+#[test]
+fn test_measured_c2_curve() {
+    use frequency_band_simulation::modcod::measure_ldpc_curve;
+    use space_comms_shared::ldpc::LdpcCode;
+
+    let code = LdpcCode::c2().unwrap();
+    let curve = measure_ldpc_curve(&code, &[2.0, 5.0], &MonteCarlo::new(3, 7), 30);
+    assert!(curve.points[0].bit_error_rate > 1e-3, "{:?}", curve);
+    assert_eq!(curve.points[1].bit_error_rate, 0.0, "{:?}", curve);
+}
+
+// ─── Receiver Front-End Tests ─────────────────────────────────────────────────
+
+/// A near-far interferer the channel filter removes still desensitizes the
+/// receiver through AGC gain reduction, and blocks it near compression;
+/// the additive interference term alone misses both.
+#[test]
+fn test_near_far_interferer_desensitizes_receiver() {
+    let front_end = ReceiverFrontEnd::default();
+    let clear = front_end.settled(&ReceivedPower::clear(-100.0)).unwrap();
+    assert_eq!(clear.gain_db, front_end.agc.max_gain_db);
+    assert!(clear.desensitization_db.abs() < 0.1, "{:?}", clear);
+    assert!((clear.snr_db - (-100.0 - front_end.thermal_noise_dbm())).abs() < 0.1);
+
+    let sweep = front_end
+        .near_far_sweep(-100.0, 80.0, &[-90.0, -50.0, -30.0, -15.0])
+        .unwrap();
+    for pair in sweep.windows(2) {
+        assert!(pair[1].gain_db < pair[0].gain_db);
+        assert!(pair[1].desensitization_db > pair[0].desensitization_db);
+    }
+    assert!(sweep[0].desensitization_db < 0.5);
+    assert!(sweep[2].desensitization_db > 10.0, "{:?}", sweep[2]);
+    assert!(sweep[2].additive_snr_db > 5.0, "the additive model barely notices");
+    assert!(sweep[3].compression_db > 1.0);
+    assert!(sweep[3].snr_db < 0.0);
+
+    let mut broken = front_end;
+    broken.agc.min_gain_db = 90.0;
+    assert!(matches!(
+        broken.settled(&ReceivedPower::clear(-100.0)),
+        Err(ReceiverError::InvalidFrontEnd(_))
+    ));
+}
+
+/// A jammer pulse clips the ADC until the AGC attacks, and the slow decay
+/// keeps the receiver desensitized well after the jammer stops.
+#[test]
+fn test_agc_attack_and_decay_under_pulsed_jammer() {
+    let front_end = ReceiverFrontEnd::default();
+    let jammer = ReceivedPower {
+        signal_dbm: -100.0,
+        interferer_dbm: -30.0,
+        interferer_rejection_db: 80.0,
+    };
+    let samples = pulsed_jammer(-100.0, jammer, 0.02, 0.5, 0.45, 0.0005);
+    let outputs = front_end.run(&samples).unwrap();
+    assert_eq!(outputs.len(), samples.len());
+
+    // Jammer arrives at full gain and clips until the attack pulls it down
+    assert!(outputs[0].clipped);
+    let settled = front_end.settled_gain_db(&jammer);
+    let at_10_ms = outputs.iter().find(|o| o.time_s >= 0.01).unwrap();
+    assert!((at_10_ms.gain_db - settled).abs() < 0.1);
+    assert!(!at_10_ms.clipped);
+
+    // 30 ms after the jammer stops the gain is still recovering
+    let after = outputs.iter().find(|o| o.time_s >= 0.05).unwrap();
+    assert!(after.gain_db < front_end.agc.max_gain_db - 20.0);
+    assert!(after.desensitization_db > 3.0, "{:?}", after);
+    assert!(outputs.last().unwrap().desensitization_db < 0.5);
+
+    let summary = FrontEndSummary::from_outputs(&outputs);
+    assert!(summary.clipped_samples > 0);
+    assert!(summary.desensitized_s > 0.05, "{}", summary);
+    assert!(summary.to_string().contains("clipped"));
+
+    let mut reversed = samples[..3].to_vec();
+    reversed.swap(1, 2);
+    assert_eq!(
+        front_end.run(&reversed),
+        Err(ReceiverError::TimeReversed { index: 2 })
+    );
+}
+
+// ─── Ground Antenna Noise Tests ───────────────────────────────────────────────
+
+/// The antenna temperature table interpolates between its points, holds its
+/// end values outside them, and is checked when read from configuration.
+#[test]
+fn test_antenna_temperature_curve_interpolates_and_validates() {
+    let curve = AntennaTemperatureCurve::typical(BandType::SBand);
+    assert_eq!(curve.temperature_k(90.0), 12.0);
+    assert!((curve.temperature_k(7.5) - 47.5).abs() < 1e-9);
+    assert_eq!(curve.temperature_k(-1.0), 120.0);
+    assert_eq!(curve.excess_over_zenith_k(95.0), 0.0);
+
+    let antenna = GroundAntenna::typical("Station 1 X", BandType::XBand, 50.0);
+    assert_eq!(antenna.system_noise_k(90.0), 67.0);
+    assert!((antenna.g_over_t_db_k(40.0, 90.0) - (40.0 - 10.0 * 67f64.log10())).abs() < 1e-9);
+    assert!(antenna.degradation_db(5.0) > 2.0, "{}", antenna.degradation_db(5.0));
+    assert!(antenna.degradation_db(45.0) < 0.2);
+    assert!(antenna.validate().is_ok());
+
+    let json = r#"[{"elevation_deg":0,"temperature_k":100},{"elevation_deg":90,"temperature_k":10}]"#;
+    let parsed: AntennaTemperatureCurve = serde_json::from_str(json).unwrap();
+    assert_eq!(parsed.temperature_k(45.0), 55.0);
+    let written = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(serde_json::from_str::<AntennaTemperatureCurve>(&written).unwrap(), parsed);
+
+    let reversed = r#"[{"elevation_deg":90,"temperature_k":10},{"elevation_deg":0,"temperature_k":100}]"#;
+    assert!(serde_json::from_str::<AntennaTemperatureCurve>(reversed).is_err());
+    assert_eq!(
+        AntennaTemperatureCurve::new(Vec::new()),
+        Err(AntennaNoiseError::InvalidCurve("the table has no points"))
+    );
+    let negative = AntennaTemperaturePoint { elevation_deg: 10.0, temperature_k: -1.0 };
+    assert!(matches!(
+        AntennaTemperatureCurve::new(vec![negative]),
+        Err(AntennaNoiseError::InvalidCurve(_))
+    ));
+    let mut broken = antenna;
+    broken.receiver_noise_k = f64::NAN;
+    assert!(matches!(broken.validate(), Err(AntennaNoiseError::InvalidReceiver(_))));
+}
+
+/// Low elevations lose SNR to the antenna's noise in the simulation, and a
+/// low pass carries less in pass planning than the fixed noise temperature
+/// predicts; at zenith nothing changes.
+#[test]
+fn test_ground_antenna_degrades_low_elevation_snr_and_capacity() {
+    let x_band = FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|b| b.name == BandType::XBand)
+        .unwrap();
+    let antenna = GroundAntenna::typical("Station 1 X", BandType::XBand, 50.0);
+    let margins = MarginPolicy::default();
+    let snr_db = |elevation: f64, with_antenna: bool| {
+        let params = TransmissionParameters {
+            elevation_angle_degrees: elevation,
+            ..leo_params()
+        };
+        let result = if with_antenna {
+            x_band.simulate_transmission_at(&antenna, &params, &clear_sky(), &margins)
+        } else {
+            x_band.simulate_transmission_with(&params, &clear_sky(), &margins)
+        };
+        result.unwrap().signal_to_noise_ratio_db
+    };
+
+    assert!((snr_db(90.0, true) - snr_db(90.0, false)).abs() < 1e-9);
+    let zenith_k = x_band.characteristics.noise_temperature_k;
+    let expected_db = 10.0 * ((zenith_k + antenna.curve.excess_over_zenith_k(5.0)) / zenith_k).log10();
+    let lost_db = snr_db(5.0, false) - snr_db(5.0, true);
+    assert!((lost_db - expected_db).abs() < 1e-9, "{} dB", lost_db);
+    assert!(lost_db > snr_db(30.0, false) - snr_db(30.0, true));
+
+    let study = ForecastStudy {
+        transmit_power_watts: 20.0,
+        elevation_mask_deg: 5.0,
+        ..ForecastStudy::default()
+    };
+    let contact = ContactOpportunity { contact_id: 1, start_s: 0, max_elevation_deg: 30.0 };
+    let forecast = |study: &ForecastStudy| {
+        let mut weather = StormAfter { storm_from_s: f64::INFINITY };
+        let mut rng = StdRng::seed_from_u64(3);
+        study.forecast_contact(&x_band, &contact, &mut weather, &clear_sky(), &mut rng).unwrap()
+    };
+    let fixed = forecast(&study);
+    let with_antenna = forecast(&study.clone().with_ground_antenna(antenna.clone()));
+    assert_eq!(fixed.duration_s, with_antenna.duration_s);
+    assert!(with_antenna.capacity_bytes < fixed.capacity_bytes, "{:?}", with_antenna);
+}
+
+/// The simple rain model reproduces the default simulation; the ITU model
+/// attenuates Ka more at low elevation and at the equator, and reports its
+/// rain attenuation apart from the rest of the weather impact.
+#[test]
+fn test_itu_rain_model_in_simulation() {
+    let ka_band = FrequencyBand::get_standard_bands()
+        .into_iter()
+        .find(|b| b.name == BandType::KaBand)
+        .unwrap();
+    let margins = MarginPolicy::default();
+    let rain = EnvironmentalConditions { rain_rate_mm_hour: 20.0, ..clear_sky() };
+    let simulate = |model: &RainModel, elevation: f64| {
+        let params = TransmissionParameters {
+            elevation_angle_degrees: elevation,
+            ..leo_params()
+        };
+        ka_band
+            .simulate_transmission_with_rain_model(&params, &rain, &margins, model)
+            .unwrap()
+    };
+
+    let simple = simulate(&RainModel::Simple, 45.0);
+    let default = ka_band.simulate_transmission(&leo_params(), &rain).unwrap();
+    assert_eq!(simple.signal_to_noise_ratio_db, default.signal_to_noise_ratio_db);
+    assert_eq!(simple.rain_attenuation_db, 20.0 * 2.5);
+
+    let itu = RainModel::ItuP618(RainPath::default());
+    let high = simulate(&itu, 60.0);
+    let low = simulate(&itu, 10.0);
+    assert!(high.rain_attenuation_db > 0.0);
+    assert!(low.rain_attenuation_db > high.rain_attenuation_db);
+    assert!(low.signal_to_noise_ratio_db < high.signal_to_noise_ratio_db);
+    assert!(high.weather_impact_factor * 100.0 > high.rain_attenuation_db);
+
+    let equator = RainModel::ItuP618(RainPath { latitude_deg: 0.0, ..RainPath::default() });
+    assert!(simulate(&equator, 60.0).rain_attenuation_db > high.rain_attenuation_db);
+
+    let clear = ka_band
+        .simulate_transmission_with_rain_model(&leo_params(), &clear_sky(), &margins, &itu)
+        .unwrap();
+    assert_eq!(clear.rain_attenuation_db, 0.0);
+
+    let tilted = RainModel::ItuP618(RainPath {
+        latitude_deg: 95.0,
+        polarization: Polarization::Linear { tilt_deg: f64::NAN },
+        ..RainPath::default()
+    });
+    let error = ka_band
+        .simulate_transmission_with_rain_model(&leo_params(), &rain, &margins, &tilted)
+        .unwrap_err();
+    assert!(error.field("latitude_deg").is_some());
+    assert!(error.field("polarization_tilt_deg").is_some());
+}
+
+/// SGP4 reproduces the published verification states, and passes of a
+/// propagated orbit drive the link model sample by sample.
+#[test]
+fn test_sgp4_orbit_passes() {
+    // Spacetrack Report #3 verification case, satellite 00005
+    let tle = Tle::parse(
+        "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+        "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+    )
+    .unwrap();
+    assert_eq!((tle.catalog_number, tle.epoch_year), (5, 2000));
+    assert!((tle.bstar - 0.28098e-4).abs() < 1e-12);
+    assert!((tle.eccentricity - 0.1859667).abs() < 1e-12);
+
+    let sgp4 = Sgp4::new(&tle).unwrap();
+    for (t, position, velocity) in [
+        (
+            0.0,
+            [7022.46529266, -1400.08296755, 0.03995155],
+            [1.893841015, 6.405893759, 4.534807250],
+        ),
+        (
+            360.0,
+            [-7154.03120202, -3783.17682504, -3536.19412294],
+            [4.741887409, -4.151817765, -2.093935425],
+        ),
+    ] {
+        let state = sgp4.propagate(t).unwrap();
+        for i in 0..3 {
+            assert!((state.position_km[i] - position[i]).abs() < 1e-3);
+            assert!((state.velocity_km_s[i] - velocity[i]).abs() < 1e-6);
+        }
+    }
+
+    // A corrupted digit fails the checksum; deep-space orbits need SDP4
+    let corrupted = Tle::parse(
+        "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4754",
+        "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+    );
+    assert_eq!(corrupted, Err(OrbitError::Checksum { line: 1 }));
+    let geostationary = Tle {
+        mean_motion_rev_day: 1.0027,
+        ..tle
+    };
+    assert!(matches!(
+        Sgp4::new(&geostationary),
+        Err(OrbitError::DeepSpace { .. })
+    ));
+
+    // A day of ISS passes over a mid-latitude station
+    let iss: Tle = "ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537"
+        .parse()
+        .unwrap();
+    assert_eq!(iss.name.as_deref(), Some("ISS (ZARYA)"));
+    let sgp4 = Sgp4::new(&iss).unwrap();
+    let observer = Observer::at_station((40.0, -105.0, 1600.0));
+    let passes = sgp4.passes(&observer, 0.0, 1440.0, 10.0, 10.0).unwrap();
+    assert!(!passes.is_empty());
+    for pass in &passes {
+        assert!(pass.duration_s() < 900.0, "{}", pass.duration_s());
+        assert!(pass.max_elevation_deg() <= 90.0);
+        for sample in &pass.samples {
+            assert!(sample.pointing.elevation_deg >= 10.0);
+            // Between the orbit altitude and the range at the 10° mask
+            assert!((340.0..2_000.0).contains(&sample.range_km), "{:?}", sample);
+        }
+    }
+
+    // The link follows the geometry: best at culmination, worst at the mask
+    let pass = passes
+        .iter()
+        .max_by(|a, b| a.max_elevation_deg().total_cmp(&b.max_elevation_deg()))
+        .unwrap();
+    let results = x_band()
+        .simulate_pass(
+            &pass.samples,
+            &leo_params(),
+            &clear_sky(),
+            &MarginPolicy::default(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), pass.samples.len());
+    let closest = pass
+        .samples
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.range_km.total_cmp(&b.1.range_km))
+        .unwrap()
+        .0;
+    let snr = |i: usize| results[i].signal_to_noise_ratio_db;
+    assert!(snr(closest) > snr(0));
+    assert!(snr(closest) > snr(results.len() - 1));
+}
+
+/// Relative motion shifts the carrier; its rate costs SNR and too much of
+/// either loses lock.
+#[test]
+fn test_doppler_shift_and_carrier_tracking() {
+    let band = x_band();
+    let margins = MarginPolicy::default();
+    let carrier_hz = band.frequency_range.center_ghz() * 1e9;
+
+    let stationary = band.simulate_transmission_with(&leo_params(), &clear_sky(), &margins).unwrap();
+    assert_eq!(stationary.doppler_shift_hz, 0.0);
+    assert_eq!(stationary.carrier_tracking_loss_db, 0.0);
+    assert!(stationary.carrier_locked);
+
+    // A LEO satellite closing at 7 km/s raises an X-band carrier by ~233 kHz;
+    // the loop follows a constant shift at no cost
+    let closing = TransmissionParameters { range_rate_m_s: -7_000.0, ..leo_params() };
+    let result = band.simulate_transmission_with(&closing, &clear_sky(), &margins).unwrap();
+    assert!((result.doppler_shift_hz - carrier_hz * 7_000.0 / SPEED_OF_LIGHT_M_S).abs() < 1e-6);
+    assert!((result.doppler_shift_hz - 233.5e3).abs() < 1e3, "{}", result.doppler_shift_hz);
+    assert!(result.carrier_locked && result.success);
+    assert_eq!(result.signal_to_noise_ratio_db, stationary.signal_to_noise_ratio_db);
+
+    // Culmination: the shift sweeps fastest and the phase error costs SNR
+    let culmination = TransmissionParameters { range_acceleration_m_s2: 100.0, ..leo_params() };
+    let result = band.simulate_transmission_with(&culmination, &clear_sky(), &margins).unwrap();
+    assert!(result.doppler_rate_hz_s < 0.0);
+    assert!(result.carrier_locked);
+    assert!(result.carrier_tracking_loss_db > 0.0);
+    let lost = stationary.signal_to_noise_ratio_db - result.signal_to_noise_ratio_db;
+    assert!((lost - result.carrier_tracking_loss_db).abs() < 1e-9);
+    let budget = band.compute_link_budget(&culmination, &clear_sky(), &margins).unwrap();
+    assert_eq!(budget.carrier_tracking_loss_db, result.carrier_tracking_loss_db);
+
+    // Outside the search range, or past the phase error limit, nothing gets through
+    for params in [
+        TransmissionParameters { range_rate_m_s: 20_000.0, ..leo_params() },
+        TransmissionParameters { range_acceleration_m_s2: 5_000.0, ..leo_params() },
+    ] {
+        let result = band.simulate_transmission_with(&params, &clear_sky(), &margins).unwrap();
+        assert!(!result.carrier_locked);
+        assert!(!result.success);
+        assert_eq!(result.actual_data_rate_mbps, 0.0);
+        assert_eq!(result.carrier_tracking_loss_db, 0.0);
+    }
+
+    // Wider loops at the higher bands
+    let s_loop = CarrierLoop::typical(BandType::SBand);
+    let ka_loop = CarrierLoop::typical(BandType::KaBand);
+    assert!(ka_loop.loop_bandwidth_hz > s_loop.loop_bandwidth_hz);
+    assert!(ka_loop.acquisition_range_hz > s_loop.acquisition_range_hz);
+
+    let invalid = TransmissionParameters { range_rate_m_s: f64::NAN, ..leo_params() };
+    assert!(band.simulate_transmission_with(&invalid, &clear_sky(), &margins).is_err());
+    let built = TransmissionParameters::builder()
+        .distance_km(1_000.0)
+        .data_size_mb(1.0)
+        .required_data_rate_mbps(1.0)
+        .elevation_angle_degrees(30.0)
+        .transmit_power_watts(10.0)
+        .antenna_diameter_meters(3.0)
+        .build()
+        .unwrap();
+    assert_eq!((built.range_rate_m_s, built.range_acceleration_m_s2), (0.0, 0.0));
+}
+
+/// Over a pass the Doppler runs from up at the rise to down at the set, with
+/// the range rate taken from the pass geometry.
+#[test]
+fn test_pass_doppler_follows_geometry() {
+    let pass = generate_pass(550.0, 80.0, 5.0, 1.0);
+    let results = x_band()
+        .simulate_pass(&pass, &leo_params(), &clear_sky(), &MarginPolicy::default())
+        .unwrap();
+    let (first, last) = (&results[0], &results[results.len() - 1]);
+    assert!(first.doppler_shift_hz > 100e3, "{}", first.doppler_shift_hz);
+    assert!(last.doppler_shift_hz < -100e3, "{}", last.doppler_shift_hz);
+    assert!(results.iter().all(|r| r.carrier_locked));
+
+    // Fastest sweep at the closest approach
+    let closest = pass
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.range_km.total_cmp(&b.1.range_km))
+        .unwrap()
+        .0;
+    let rate = |i: usize| results[i].doppler_rate_hz_s.abs();
+    assert!(rate(closest) > rate(0));
+    assert!(rate(closest) > rate(results.len() - 1));
+}
+
+// ─── Live Tuning Tests ────────────────────────────────────────────────────────
+
+/// Typed commands parse to absolute values, signed steps and a return to
+/// the weather model.
+#[test]
+fn test_tuning_commands_parse() {
+    let parse = |text: &str| text.parse::<Adjustment>();
+    assert_eq!(
+        parse("rain 12").unwrap(),
+        Adjustment { parameter: TunableParameter::RainRate, change: Change::To(12.0) }
+    );
+    assert_eq!(
+        parse("  P -20\n").unwrap(),
+        Adjustment { parameter: TunableParameter::TransmitPower, change: Change::By(-20.0) }
+    );
+    assert_eq!(
+        parse("distance +500").unwrap(),
+        Adjustment { parameter: TunableParameter::Distance, change: Change::By(500.0) }
+    );
+    assert_eq!(parse("r auto").unwrap().change, Change::Model);
+    for text in ["", "rain", "rain 1 2", "wind 5", "power lots"] {
+        assert!(matches!(parse(text), Err(TuningError::Unrecognized(_))), "{}", text);
+    }
+}
+
+/// Adjustments change the link mid-run, are validated, and are annotated
+/// with the run time they took effect.
+#[test]
+fn test_live_tuning_adjusts_running_simulation() {
+    let band = x_band();
+    let mut tuning = LiveTuning::new(leo_params());
+    let modelled = clear_sky();
+    let before = band.simulate_transmission(tuning.parameters(), &tuning.environment(&modelled)).unwrap();
+
+    // Rain held over the weather model's, then stepped
+    tuning.apply(1.0, "rain 20".parse().unwrap(), &modelled).unwrap();
+    assert_eq!(tuning.environment(&modelled).rain_rate_mm_hour, 20.0);
+    let annotation = tuning.apply(1.5, "rain +5".parse().unwrap(), &modelled).unwrap();
+    assert_eq!((annotation.from, annotation.to), (20.0, Some(25.0)));
+    let rainy = band.simulate_transmission(tuning.parameters(), &tuning.environment(&modelled)).unwrap();
+    assert!(rainy.signal_to_noise_ratio_db < before.signal_to_noise_ratio_db);
+
+    // More power buys the SNR back
+    tuning.apply(2.0, "power 200".parse().unwrap(), &modelled).unwrap();
+    let powered = band.simulate_transmission(tuning.parameters(), &tuning.environment(&modelled)).unwrap();
+    assert!(powered.signal_to_noise_ratio_db > rainy.signal_to_noise_ratio_db);
+
+    // Returning to the model follows the model's rain again
+    let stormy = tropical_storm();
+    let annotation = tuning.apply(3.0, "rain auto".parse().unwrap(), &stormy).unwrap();
+    assert_eq!(annotation.to, None);
+    assert!(annotation.to_string().contains("weather model"));
+    assert_eq!(tuning.rain_override(), None);
+    assert_eq!(tuning.environment(&stormy).rain_rate_mm_hour, stormy.rain_rate_mm_hour);
+
+    // Invalid values are rejected and change nothing
+    let recorded = tuning.annotations().len();
+    for text in ["distance -600", "power -500", "rain -1"] {
+        let error = tuning.apply(4.0, text.parse().unwrap(), &modelled).unwrap_err();
+        assert!(matches!(error, TuningError::Invalid(_)), "{}", text);
+    }
+    assert!(matches!(
+        tuning.apply(4.0, "power auto".parse().unwrap(), &modelled),
+        Err(TuningError::NotModelled(TunableParameter::TransmitPower))
+    ));
+    assert_eq!(tuning.annotations().len(), recorded);
+    assert_eq!(tuning.parameters().transmit_power_watts, 200.0);
+
+    let times: Vec<f64> = tuning.annotations().iter().map(|a| a.time_hours).collect();
+    assert_eq!(times, [1.0, 1.5, 2.0, 3.0]);
+    assert_eq!(tuning.annotations()[2].to_string(), "2.0h transmit power 50.0 → 200.0 W");
+}
+
+/// Adjustments sent from another thread over a channel reach the run.
+#[test]
+fn test_tuning_over_control_channel() {
+    let (sender, receiver) = mpsc::channel::<Adjustment>();
+    std::thread::spawn(move || {
+        for text in ["distance 2000", "power -10"] {
+            sender.send(text.parse().unwrap()).unwrap();
+        }
+    })
+    .join()
+    .unwrap();
+
+    let mut tuning = LiveTuning::new(leo_params());
+    for adjustment in receiver.try_iter() {
+        tuning.apply(0.5, adjustment, &clear_sky()).unwrap();
+    }
+    assert_eq!(tuning.parameters().distance_km, 2000.0);
+    assert_eq!(tuning.parameters().transmit_power_watts, 40.0);
+}
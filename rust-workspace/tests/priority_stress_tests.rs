@@ -10,6 +10,7 @@ use std::collections::VecDeque;
 
 use space_comms_shared::{
     commands::*,
+    link_config::LinkDirection,
     messaging::{Message, MessagePriority, PriorityQueue},
     types::{ComponentId, BandType},
     Result,
@@ -139,9 +140,11 @@ impl PriorityStressTester {
                 true_anomaly: 0.0,
             },
             SpaceCommand::ReconfigureComm {
+                direction: LinkDirection::Downlink,
                 band: BandType::XBand,
                 frequency_hz: 8_450_000_000, // 8.45 GHz
                 power_level: 75,
+                data_rate_bps: 100_000_000,
                 modulation: ModulationType::QPSK,
                 error_correction: true,
             },
@@ -201,6 +204,7 @@ impl PriorityStressTester {
                 storage_location: StorageLocation::NonVolatileMemory,
                 compression_level: 5,
                 encryption: true,
+                science_value: 0,
             },
 
             // Low Priority Commands (4 commands)